@@ -108,14 +108,14 @@ EOF
     PGPASSWORD=$POSTGRES_PASSWORD psql -h "$POSTGRES_HOST" -p "$POSTGRES_PORT" -U "$POSTGRES_USER" -d "$POSTGRES_DB" <<EOF
 BEGIN;
 
-\i $MIGRATION_DIR/V1__initial_schema.sql
-\i $MIGRATION_DIR/V2__market_data_tables.sql
-\i $MIGRATION_DIR/V3__strategy_tables.sql
+\i $MIGRATION_DIR/1_initial_schema.up.sql
+\i $MIGRATION_DIR/2_market_data_tables.up.sql
+\i $MIGRATION_DIR/3_strategy_tables.up.sql
 
 INSERT INTO schema_migrations (version) VALUES 
-    ('V1__initial_schema'),
-    ('V2__market_data_tables'),
-    ('V3__strategy_tables');
+    ('1_initial_schema'),
+    ('2_market_data_tables'),
+    ('3_strategy_tables');
 
 COMMIT;
 EOF
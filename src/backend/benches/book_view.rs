@@ -0,0 +1,81 @@
+//! Compares strategy reads of a 100-level book through the clone path (lock the map entry,
+//! clone the book, derive mid and spread) with loads of the precomputed `BookView`, and
+//! prints reads per second for each.
+//!
+//! Run with `cargo bench --bench book_view`.
+
+use std::time::Instant;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+
+use solana_trading_bot::execution_engine::book_view::BookViews;
+use solana_trading_bot::models::market::{OrderBook, OrderBookLevel};
+
+const PAIR: &str = "SOL/USDC";
+const BOOK_DEPTH: i64 = 100;
+const READS: usize = 100_000;
+
+fn book() -> OrderBook {
+    let level = |price: i64| OrderBookLevel::new(Decimal::new(price, 2), Decimal::new(1_500, 3));
+    OrderBook::new(
+        PAIR.to_string(),
+        "jupiter".to_string(),
+        (1..=BOOK_DEPTH).map(|i| level(10_000 - i)).collect(),
+        (1..=BOOK_DEPTH).map(|i| level(10_000 + i)).collect(),
+    )
+    .unwrap()
+}
+
+/// What strategies did before: clone the book out of the map and derive prices per read
+fn read_cloned(books: &DashMap<String, OrderBook>) -> Option<Decimal> {
+    let book = books.get(PAIR)?.clone();
+    let (bids, asks) = book.levels(1);
+    let spread = book.get_spread().ok()??;
+    Some((bids.first()?.price() + asks.first()?.price()) / Decimal::TWO + spread)
+}
+
+fn reads_per_second(read: impl Fn() -> Option<Decimal>) -> f64 {
+    let start = Instant::now();
+    for _ in 0..READS {
+        black_box(read());
+    }
+    READS as f64 / start.elapsed().as_secs_f64()
+}
+
+fn bench_book_view(c: &mut Criterion) {
+    let books = DashMap::new();
+    books.insert(PAIR.to_string(), book());
+    let views = BookViews::default();
+    views.publish(&book(), false);
+    let handle = views.handle(PAIR).unwrap();
+
+    let read_view = || {
+        let view = handle.load();
+        Some(view.mid? + view.spread?)
+    };
+    assert_eq!(read_cloned(&books), read_view());
+
+    let cloned = reads_per_second(|| read_cloned(&books));
+    let viewed = reads_per_second(read_view);
+    println!(
+        "reads/sec over {} reads: clone {:.0}, view {:.0} ({:.1}x)",
+        READS,
+        cloned,
+        viewed,
+        viewed / cloned
+    );
+
+    let mut group = c.benchmark_group("book_view");
+    group.bench_function("clone_read", |b| b.iter(|| read_cloned(&books)));
+    group.bench_function("view_load", |b| b.iter(read_view));
+    group.bench_function("view_publish", |b| {
+        let book = book();
+        b.iter(|| views.publish(black_box(&book), false))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_book_view);
+criterion_main!(benches);
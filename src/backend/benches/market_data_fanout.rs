@@ -0,0 +1,115 @@
+//! Compares the market data hot path before and after the serialization changes on a
+//! synthetic 20k msg/sec price stream: re-serializing each message as its cache key and
+//! cloning every update into each strategy lane, against hashing the message fields and
+//! sharing one `Arc` across lanes. Prints the CPU time one second of stream costs each way.
+//!
+//! Run with `cargo bench --bench market_data_fanout`.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+use solana_trading_bot::data_collector::jupiter::market_data_key;
+use solana_trading_bot::models::market::MarketData;
+
+const MESSAGES_PER_SECOND: usize = 20_000;
+const PAIRS: [&str; 4] = ["SOL/USDC", "BONK/USDC", "JUP/USDC", "RAY/USDC"];
+const LANES_PER_PAIR: usize = 4;
+
+fn stream() -> Vec<Value> {
+    (0..MESSAGES_PER_SECOND)
+        .map(|i| {
+            serde_json::json!({
+                "channel": "quotes",
+                "trading_pair": PAIRS[i % PAIRS.len()],
+                "price": format!("23.{:04}", i % 10_000),
+                "volume": format!("{}.5", 100 + i % 50),
+                "timestamp": 1_700_000_000_000i64 + i as i64,
+            })
+        })
+        .collect()
+}
+
+fn parse(raw: &Value) -> MarketData {
+    MarketData::new(
+        raw["trading_pair"].as_str().unwrap().to_string(),
+        "jupiter".to_string(),
+        Decimal::from_str(raw["price"].as_str().unwrap()).unwrap(),
+        Decimal::from_str(raw["volume"].as_str().unwrap()).unwrap(),
+    )
+    .unwrap()
+}
+
+/// What the collector and driver did before: the whole message as the cache key and a
+/// full copy of the update per lane
+fn run_second_cloned(stream: &[Value]) -> usize {
+    let mut cache: HashMap<String, MarketData> = HashMap::new();
+    let mut lanes: Vec<MarketData> = Vec::with_capacity(LANES_PER_PAIR);
+    let mut delivered = 0;
+    for raw in stream {
+        let key = raw.to_string();
+        let data = parse(raw);
+        cache.insert(key, data.clone());
+        lanes.extend((0..LANES_PER_PAIR).map(|_| data.clone()));
+        delivered += lanes.len();
+        black_box(lanes.drain(..).count());
+    }
+    delivered
+}
+
+/// Field hash as the cache key and one shared update per lane
+fn run_second_shared(stream: &[Value]) -> usize {
+    let mut cache: HashMap<u64, MarketData> = HashMap::new();
+    let mut lanes: Vec<Arc<MarketData>> = Vec::with_capacity(LANES_PER_PAIR);
+    let mut delivered = 0;
+    for raw in stream {
+        let key = market_data_key(
+            raw["trading_pair"].as_str().unwrap(),
+            raw["price"].as_str().unwrap(),
+            raw["volume"].as_str().unwrap(),
+            raw["timestamp"].as_i64(),
+        );
+        let data = parse(raw);
+        cache.insert(key, data.clone());
+        let data = Arc::new(data);
+        lanes.extend((0..LANES_PER_PAIR).map(|_| data.clone()));
+        delivered += lanes.len();
+        black_box(lanes.drain(..).count());
+    }
+    delivered
+}
+
+fn cpu_time(run: impl Fn() -> usize) -> Duration {
+    let start = Instant::now();
+    black_box(run());
+    start.elapsed()
+}
+
+fn bench_market_data_fanout(c: &mut Criterion) {
+    let stream = stream();
+    assert_eq!(run_second_cloned(&stream), run_second_shared(&stream));
+
+    let cloned = cpu_time(|| run_second_cloned(&stream));
+    let shared = cpu_time(|| run_second_shared(&stream));
+    println!(
+        "CPU per second of {} msg/sec: cloned {:.1}ms ({:.1}% of a core), shared {:.1}ms ({:.1}% of a core)",
+        MESSAGES_PER_SECOND,
+        cloned.as_secs_f64() * 1000.0,
+        cloned.as_secs_f64() * 100.0,
+        shared.as_secs_f64() * 1000.0,
+        shared.as_secs_f64() * 100.0
+    );
+
+    let mut group = c.benchmark_group("market_data_fanout");
+    group.bench_function("cloned_20k_messages", |b| b.iter(|| run_second_cloned(&stream)));
+    group.bench_function("shared_20k_messages", |b| b.iter(|| run_second_shared(&stream)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_market_data_fanout);
+criterion_main!(benches);
@@ -0,0 +1,115 @@
+//! Compares per-call metric macros with pre-registered handles and aggregated counters on
+//! a synthetic 50k updates/sec position update workload, counting heap allocations per
+//! second of updates and checking both paths export the same metric names.
+//!
+//! Run with `cargo bench --bench metrics_emission`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use lazy_static::lazy_static;
+use metrics::{counter, gauge, histogram, register_gauge, register_histogram, Gauge, Histogram};
+use metrics_util::debugging::{DebuggingRecorder, Snapshotter};
+
+use solana_trading_bot::utils::metric_handles::{flush_aggregated, AggregatedCounter};
+
+const UPDATES_PER_SECOND: usize = 50_000;
+const POSITION_PREFIX: &str = "trading_bot.position";
+const PORTFOLIO_PREFIX: &str = "trading_bot.portfolio";
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+lazy_static! {
+    static ref UNREALIZED_PNL: Gauge = register_gauge!(format!("{}.unrealized_pnl", POSITION_PREFIX));
+    static ref MAX_DRAWDOWN: Gauge = register_gauge!(format!("{}.max_drawdown", POSITION_PREFIX));
+    static ref UPDATE_DURATION_MS: Histogram = register_histogram!(format!("{}.update_duration_ms", POSITION_PREFIX));
+    static ref POSITIONS_UPDATED: &'static AggregatedCounter =
+        AggregatedCounter::register(format!("{}.positions_updated", PORTFOLIO_PREFIX));
+}
+
+/// The per-call macros the hot paths used before
+fn emit_with_macros(i: usize) {
+    let value = i as f64;
+    gauge!(format!("{}.unrealized_pnl", POSITION_PREFIX), value);
+    gauge!(format!("{}.max_drawdown", POSITION_PREFIX), value);
+    histogram!(format!("{}.update_duration_ms", POSITION_PREFIX), value);
+    counter!(format!("{}.positions_updated", PORTFOLIO_PREFIX), 1);
+}
+
+/// Pre-registered handles, with the update counter aggregated
+fn emit_with_handles(i: usize) {
+    let value = i as f64;
+    UNREALIZED_PNL.set(value);
+    MAX_DRAWDOWN.set(value);
+    UPDATE_DURATION_MS.record(value);
+    POSITIONS_UPDATED.increment(1);
+}
+
+/// Runs one second of updates, flushing aggregated counters every 250ms of updates as the
+/// flusher would, and returns the allocations made
+fn run_second(emit: fn(usize)) -> u64 {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for i in 0..UPDATES_PER_SECOND {
+        emit(black_box(i));
+        if (i + 1) % (UPDATES_PER_SECOND / 4) == 0 {
+            flush_aggregated();
+        }
+    }
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn metric_names(snapshotter: &Snapshotter) -> BTreeSet<String> {
+    snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .map(|(key, _, _, _)| key.key().name().to_string())
+        .collect()
+}
+
+fn bench_metrics_emission(c: &mut Criterion) {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::set_boxed_recorder(Box::new(recorder)).expect("recorder already installed");
+
+    // Handles are registered on their first use inside the measured run, so any name they
+    // introduced would show up as a difference between the two snapshots
+    emit_with_macros(0);
+
+    let macro_allocations = run_second(emit_with_macros);
+    let macro_names = metric_names(&snapshotter);
+    let handle_allocations = run_second(emit_with_handles);
+    let handle_names = metric_names(&snapshotter);
+    println!(
+        "allocations per {} updates: macros {}, handles {}",
+        UPDATES_PER_SECOND, macro_allocations, handle_allocations
+    );
+    assert!(handle_allocations < macro_allocations / 100);
+    assert_eq!(macro_names, handle_names, "handles must export the same metric names");
+
+    let mut group = c.benchmark_group("metrics_emission");
+    group.bench_function("macros_50k_updates", |b| b.iter(|| run_second(emit_with_macros)));
+    group.bench_function("handles_50k_updates", |b| b.iter(|| run_second(emit_with_handles)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_metrics_emission);
+criterion_main!(benches);
@@ -0,0 +1,64 @@
+//! Compiles the gRPC service definitions and bakes the git commit and build time into the
+//! binary for the system info endpoint.
+//!
+//! Version dependencies:
+//! - tonic-build = "0.9"
+
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=proto/trading.proto");
+    tonic_build::configure()
+        .build_client(true)
+        .build_server(true)
+        .compile(&["proto/trading.proto"], &["proto"])?;
+
+    emit_build_info();
+    Ok(())
+}
+
+/// Exposes `FIREBOT_GIT_COMMIT` and `FIREBOT_BUILD_TIMESTAMP` (unix seconds) to the crate.
+/// Builds outside a checkout take the commit from `GIT_COMMIT`; `SOURCE_DATE_EPOCH` pins the
+/// timestamp for reproducible builds.
+fn emit_build_info() {
+    println!("cargo:rerun-if-env-changed=GIT_COMMIT");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    if let Some(git_dir) = git(&["rev-parse", "--git-dir"]) {
+        println!("cargo:rerun-if-changed={}/HEAD", git_dir);
+        if let Some(head_ref) = git(&["symbolic-ref", "-q", "HEAD"]) {
+            println!("cargo:rerun-if-changed={}/{}", git_dir, head_ref);
+        }
+    }
+
+    let commit = std::env::var("GIT_COMMIT")
+        .ok()
+        .filter(|commit| !commit.is_empty())
+        .or_else(|| git(&["rev-parse", "HEAD"]))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let timestamp = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default()
+        });
+
+    println!("cargo:rustc-env=FIREBOT_GIT_COMMIT={}", commit);
+    println!("cargo:rustc-env=FIREBOT_BUILD_TIMESTAMP={}", timestamp);
+}
+
+/// Runs a git command, returning its trimmed output when it succeeds
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let trimmed = stdout.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
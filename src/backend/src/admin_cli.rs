@@ -0,0 +1,546 @@
+//! Operator command line for a running bot, shipped as the `firebot-admin` binary. Commands
+//! are parsed and rendered here and sent to the bot's REST API with an admin bearer token;
+//! only `config check` runs locally, through the same checks as `--check-config`. Commands
+//! that stop trading or discard state refuse to run without `--yes`, and the exit code tells
+//! scripts whether the command succeeded.
+//!
+//! Version dependencies:
+//! - reqwest = "0.11"
+//! - serde_json = "1.0"
+//! - thiserror = "1.0"
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use reqwest::Method;
+use serde_json::{json, Value};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::models::pair::TradingPair;
+use crate::quarantine::QuarantineMode;
+
+// Admin CLI constants
+pub const API_URL_ENV: &str = "FIREBOT_ADMIN_URL";
+pub const TOKEN_ENV: &str = "FIREBOT_ADMIN_TOKEN";
+pub const TOKEN_FILE_ENV: &str = "FIREBOT_ADMIN_TOKEN_FILE";
+const DEFAULT_API_URL: &str = "http://127.0.0.1:8080";
+const DEFAULT_TOKEN_FILE: &str = ".config/firebot/admin-token";
+const BASE_PATH: &str = "/api/v1";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const POSITION_CLOSE_REASON: &str = "closed via firebot-admin";
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_FAILURE: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+
+pub const USAGE: &str = "\
+usage: firebot-admin [--url URL] [--json] [--yes] <command>
+
+commands:
+  status                                  system info and component health
+  strategies list                         strategies with state and returns
+  strategies pause <id>                   pause a strategy until resumed (--yes)
+  strategies resume <id>                  resume a paused strategy
+  positions list                          open positions
+  positions close <pair> [--reason R]     flatten a pair and block it from trading (--yes)
+  orders cancel <order-id>                cancel one pending order (--yes)
+  orders cancel [--pair P] [--strategy S] cancel every pending order matching (--yes)
+  quarantine add <pair> [--mode M] [--reason R]
+                                          stop new entries, or flatten-and-block (--yes)
+  quarantine remove <pair> --reason R     lift a quarantine
+  config check                            validate local configuration
+  snapshot create                         take a state snapshot
+  snapshot restore <id>                   restore a snapshot; trading stays halted (--yes)
+
+The API is read from --url or FIREBOT_ADMIN_URL. The admin token is read from
+FIREBOT_ADMIN_TOKEN, or from the file named by FIREBOT_ADMIN_TOKEN_FILE
+(default ~/.config/firebot/admin-token).";
+
+/// Admin CLI errors, each mapped to an exit code
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("{0}")]
+    Usage(String),
+    #[error("{0} stops trading or discards state; rerun with --yes to confirm")]
+    Unconfirmed(String),
+    #[error("no admin token: set {} or write one to {}", TOKEN_ENV, DEFAULT_TOKEN_FILE)]
+    MissingToken,
+    #[error("request failed: {0}")]
+    Request(String),
+    #[error("API returned {status}: {message}")]
+    Api { status: u16, message: String },
+    #[error("{0}")]
+    ConfigInvalid(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Usage(_) | Self::Unconfirmed(_) => EXIT_USAGE,
+            Self::ConfigInvalid(_) => crate::config::CONFIG_EXIT_CODE,
+            Self::MissingToken | Self::Request(_) | Self::Api { .. } => EXIT_FAILURE,
+        }
+    }
+}
+
+/// One admin operation
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Help,
+    Status,
+    StrategiesList,
+    StrategyPause { id: String },
+    StrategyResume { id: String },
+    PositionsList,
+    /// Flattens the pair through a flatten-and-block quarantine
+    PositionClose { trading_pair: String, reason: Option<String> },
+    OrderCancel { id: Uuid },
+    OrdersCancel { trading_pair: Option<String>, strategy_id: Option<String> },
+    QuarantineAdd { trading_pair: String, mode: QuarantineMode, reason: Option<String> },
+    QuarantineRemove { trading_pair: String, reason: String },
+    ConfigCheck,
+    SnapshotCreate,
+    SnapshotRestore { id: Uuid },
+}
+
+impl Command {
+    /// Whether the command stops trading or discards state and so needs `--yes`
+    pub fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            Self::StrategyPause { .. }
+                | Self::PositionClose { .. }
+                | Self::OrderCancel { .. }
+                | Self::OrdersCancel { .. }
+                | Self::QuarantineAdd { .. }
+                | Self::SnapshotRestore { .. }
+        )
+    }
+
+    /// Method, API path and body of the request carrying out the command
+    fn request(&self) -> Option<(Method, String, Option<Value>)> {
+        let request = match self {
+            Self::Help | Self::Status | Self::ConfigCheck => return None,
+            Self::StrategiesList => (Method::GET, "/strategies/performance".to_string(), None),
+            Self::StrategyPause { id } => (Method::POST, format!("/admin/strategies/{}/pause", id), None),
+            Self::StrategyResume { id } => (Method::POST, format!("/strategies/{}/resume", id), None),
+            Self::PositionsList => (Method::GET, "/admin/positions".to_string(), None),
+            Self::PositionClose { trading_pair, reason } => (
+                Method::POST,
+                "/risk/quarantine".to_string(),
+                Some(json!({
+                    "trading_pair": trading_pair,
+                    "mode": QuarantineMode::FlattenAndBlock,
+                    "reason": reason.as_deref().unwrap_or(POSITION_CLOSE_REASON),
+                })),
+            ),
+            Self::OrderCancel { id } => (Method::DELETE, format!("/orders/{}", id), None),
+            Self::OrdersCancel { trading_pair, strategy_id } => (
+                Method::POST,
+                "/orders/cancel".to_string(),
+                Some(json!({ "trading_pair": trading_pair, "strategy_id": strategy_id })),
+            ),
+            Self::QuarantineAdd { trading_pair, mode, reason } => (
+                Method::POST,
+                "/risk/quarantine".to_string(),
+                Some(json!({ "trading_pair": trading_pair, "mode": mode, "reason": reason })),
+            ),
+            Self::QuarantineRemove { trading_pair, reason } => (
+                Method::DELETE,
+                format!("/admin/risk/quarantine/{}", trading_pair.replace('/', "-")),
+                Some(json!({ "reason": reason })),
+            ),
+            Self::SnapshotCreate => (Method::POST, "/admin/snapshots".to_string(), None),
+            Self::SnapshotRestore { id } => (Method::POST, format!("/admin/snapshots/{}/restore", id), None),
+        };
+        Some(request)
+    }
+
+    /// Columns shown when a list response is rendered as a table
+    fn columns(&self) -> &'static [&'static str] {
+        match self {
+            Self::StrategiesList => &["strategy_id", "strategy_type", "state", "roi", "realized_pnl", "total_trades"],
+            Self::PositionsList => &["position_id", "trading_pair", "size", "entry_price", "current_price", "status"],
+            _ => &[],
+        }
+    }
+}
+
+/// Parsed command line
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invocation {
+    pub command: Command,
+    pub json: bool,
+    pub url: Option<String>,
+}
+
+/// Options taking a value, by the flag that introduces them
+const VALUE_OPTIONS: &[&str] = &["--url", "--reason", "--mode", "--pair", "--strategy"];
+
+/// Parses the arguments following the program name
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Invocation, CliError> {
+    let mut positional = Vec::new();
+    let mut options: HashMap<&'static str, String> = HashMap::new();
+    let (mut json, mut yes, mut help) = (false, false, false);
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--yes" | "-y" => yes = true,
+            "--help" | "-h" => help = true,
+            flag if flag.starts_with('-') => {
+                let name = VALUE_OPTIONS
+                    .iter()
+                    .find(|option| **option == flag)
+                    .ok_or_else(|| CliError::Usage(format!("unknown option {}", flag)))?;
+                let value = args
+                    .next()
+                    .ok_or_else(|| CliError::Usage(format!("{} requires a value", name)))?;
+                options.insert(name, value);
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let url = options.remove("--url");
+    if help || positional.is_empty() {
+        return Ok(Invocation { command: Command::Help, json, url });
+    }
+
+    let words: Vec<&str> = positional.iter().map(String::as_str).collect();
+    let command = match words.as_slice() {
+        ["help"] => Command::Help,
+        ["status"] => Command::Status,
+        ["strategies", "list"] => Command::StrategiesList,
+        ["strategies", "pause", id] => Command::StrategyPause { id: id.to_string() },
+        ["strategies", "resume", id] => Command::StrategyResume { id: id.to_string() },
+        ["positions", "list"] => Command::PositionsList,
+        ["positions", "close", pair] => Command::PositionClose {
+            trading_pair: trading_pair(pair)?,
+            reason: options.remove("--reason"),
+        },
+        ["orders", "cancel", id] => Command::OrderCancel {
+            id: Uuid::parse_str(id).map_err(|_| CliError::Usage(format!("invalid order id: {}", id)))?,
+        },
+        ["orders", "cancel"] => {
+            let trading_pair = options.remove("--pair").map(|pair| trading_pair(&pair)).transpose()?;
+            let strategy_id = options.remove("--strategy");
+            if trading_pair.is_none() && strategy_id.is_none() {
+                return Err(CliError::Usage(
+                    "orders cancel needs an order id, --pair or --strategy".to_string(),
+                ));
+            }
+            Command::OrdersCancel { trading_pair, strategy_id }
+        }
+        ["quarantine", "add", pair] => Command::QuarantineAdd {
+            trading_pair: trading_pair(pair)?,
+            mode: options
+                .remove("--mode")
+                .map(|mode| QuarantineMode::from_str(&mode).map_err(|e| CliError::Usage(e.to_string())))
+                .transpose()?
+                .unwrap_or(QuarantineMode::NoNewEntries),
+            reason: options.remove("--reason"),
+        },
+        ["quarantine", "remove", pair] => Command::QuarantineRemove {
+            trading_pair: trading_pair(pair)?,
+            reason: options
+                .remove("--reason")
+                .ok_or_else(|| CliError::Usage("quarantine remove requires --reason".to_string()))?,
+        },
+        ["config", "check"] => Command::ConfigCheck,
+        ["snapshot", "create"] => Command::SnapshotCreate,
+        ["snapshot", "restore", id] => Command::SnapshotRestore {
+            id: Uuid::parse_str(id).map_err(|_| CliError::Usage(format!("invalid snapshot id: {}", id)))?,
+        },
+        _ => return Err(CliError::Usage(format!("unknown command: {}", positional.join(" ")))),
+    };
+
+    if let Some(option) = options.keys().next() {
+        return Err(CliError::Usage(format!(
+            "{} does not apply to {}",
+            option,
+            positional.join(" ")
+        )));
+    }
+    if command.is_destructive() && !yes {
+        return Err(CliError::Unconfirmed(positional.join(" ")));
+    }
+    Ok(Invocation { command, json, url })
+}
+
+/// Normalizes a pair the same way the API does, so typos fail before any request
+fn trading_pair(raw: &str) -> Result<String, CliError> {
+    TradingPair::parse(raw)
+        .map(|pair| pair.as_str().to_string())
+        .map_err(|e| CliError::Usage(e.to_string()))
+}
+
+/// Reads the admin token from the environment, falling back to the token file
+pub fn load_token() -> Result<String, CliError> {
+    if let Some(token) = std::env::var(TOKEN_ENV).ok().filter(|token| !token.trim().is_empty()) {
+        return Ok(token.trim().to_string());
+    }
+    let path = match std::env::var_os(TOKEN_FILE_ENV) {
+        Some(path) => PathBuf::from(path),
+        None => std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(DEFAULT_TOKEN_FILE))
+            .ok_or(CliError::MissingToken)?,
+    };
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+        .ok_or(CliError::MissingToken)
+}
+
+/// REST client for the bot's API, authenticated with an admin token
+#[derive(Debug, Clone)]
+pub struct AdminClient {
+    http: reqwest::Client,
+    base_url: String,
+    token: String,
+}
+
+impl AdminClient {
+    pub fn new(base_url: &str, token: &str) -> Result<Self, CliError> {
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| CliError::Request(e.to_string()))?;
+        Ok(Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+        })
+    }
+
+    /// Sends the command's request and returns the response body
+    pub async fn execute(&self, command: &Command) -> Result<Value, CliError> {
+        if *command == Command::Status {
+            let info = self.send(Method::GET, &format!("{}/system/info", BASE_PATH), None).await?;
+            let health = self.send(Method::GET, "/health", None).await?;
+            return Ok(json!({
+                "status": health["status"],
+                "version": info["build"]["version"],
+                "git_commit": info["build"]["git_commit"],
+                "environment": info["environment"],
+                "started_at": info["started_at"],
+                "uptime_secs": info["uptime_secs"],
+                "persistence": health["persistence"],
+                "execution": health["execution"],
+                "circuit_breakers": health["circuit_breakers"],
+            }));
+        }
+        match command.request() {
+            Some((method, path, body)) => self.send(method, &format!("{}{}", BASE_PATH, path), body).await,
+            None => Err(CliError::Usage("command does not call the API".to_string())),
+        }
+    }
+
+    async fn send(&self, method: Method, path: &str, body: Option<Value>) -> Result<Value, CliError> {
+        let mut request = self
+            .http
+            .request(method, format!("{}{}", self.base_url, path))
+            .bearer_auth(&self.token);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request.send().await.map_err(|e| CliError::Request(e.to_string()))?;
+
+        let status = response.status();
+        let text = response.text().await.map_err(|e| CliError::Request(e.to_string()))?;
+        let value = if text.trim().is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(&text).unwrap_or(Value::String(text))
+        };
+        if !status.is_success() {
+            let message = value["error"]
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| value.to_string());
+            return Err(CliError::Api { status: status.as_u16(), message });
+        }
+        Ok(value)
+    }
+}
+
+/// Runs a parsed command and returns what to print on success
+pub async fn run(invocation: &Invocation) -> Result<String, CliError> {
+    match invocation.command {
+        Command::Help => return Ok(USAGE.to_string()),
+        Command::ConfigCheck => {
+            let report = crate::config::check_config().await;
+            let rendered = if invocation.json {
+                serde_json::to_string_pretty(&report).unwrap_or_else(|_| report.render())
+            } else {
+                report.render().trim_end().to_string()
+            };
+            return if report.is_ok() { Ok(rendered) } else { Err(CliError::ConfigInvalid(rendered)) };
+        }
+        _ => {}
+    }
+
+    let base_url = invocation
+        .url
+        .clone()
+        .or_else(|| std::env::var(API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_API_URL.to_string());
+    let client = AdminClient::new(&base_url, &load_token()?)?;
+    let value = client.execute(&invocation.command).await?;
+    Ok(render(&invocation.command, &value, invocation.json))
+}
+
+/// Formats a response as pretty JSON, a table for lists or key/value lines otherwise
+pub fn render(command: &Command, value: &Value, json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string());
+    }
+    match value {
+        Value::Array(rows) => render_table(command.columns(), rows),
+        Value::Object(fields) => {
+            let width = fields.keys().map(String::len).max().unwrap_or(0);
+            fields
+                .iter()
+                .map(|(key, value)| format!("{:<width$}  {}", key, cell(value), width = width))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        Value::Null => "ok".to_string(),
+        other => cell(other),
+    }
+}
+
+fn render_table(columns: &[&str], rows: &[Value]) -> String {
+    if rows.is_empty() {
+        return "(none)".to_string();
+    }
+    let columns: Vec<String> = if columns.is_empty() {
+        rows[0]
+            .as_object()
+            .map(|fields| fields.keys().cloned().collect())
+            .unwrap_or_default()
+    } else {
+        columns.iter().map(|column| column.to_string()).collect()
+    };
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| columns.iter().map(|column| cell(&row[column.as_str()])).collect())
+        .collect();
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| cells.iter().map(|row| row[i].len()).chain([column.len()]).max().unwrap_or(0))
+        .collect();
+
+    let line = |values: &[String]| {
+        values
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| format!("{:<width$}", value, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    let header: Vec<String> = columns.iter().map(|column| column.to_uppercase()).collect();
+    let mut lines = vec![line(&header)];
+    lines.extend(cells.iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Result<Invocation, CliError> {
+        parse_args(line.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn test_parses_commands_and_global_flags() {
+        let invocation = parse("--json strategies resume grid-1 --url http://bot:8080").unwrap();
+        assert!(invocation.json);
+        assert_eq!(invocation.url.as_deref(), Some("http://bot:8080"));
+        assert_eq!(invocation.command, Command::StrategyResume { id: "grid-1".to_string() });
+
+        assert_eq!(parse("").unwrap().command, Command::Help);
+        assert_eq!(parse("status --help").unwrap().command, Command::Help);
+        assert_eq!(parse("config check").unwrap().command, Command::ConfigCheck);
+        assert_eq!(
+            parse("quarantine add sol/usdc --mode flatten-and-block --yes").unwrap().command,
+            Command::QuarantineAdd {
+                trading_pair: "SOL/USDC".to_string(),
+                mode: QuarantineMode::FlattenAndBlock,
+                reason: None,
+            }
+        );
+        assert_eq!(
+            parse("orders cancel --strategy grid-1 -y").unwrap().command,
+            Command::OrdersCancel { trading_pair: None, strategy_id: Some("grid-1".to_string()) }
+        );
+    }
+
+    #[test]
+    fn test_destructive_commands_need_confirmation() {
+        for line in [
+            "strategies pause grid-1",
+            "positions close SOL/USDC",
+            "orders cancel --pair SOL/USDC",
+            "quarantine add SOL/USDC",
+            "snapshot restore 9b2f6c1e-8f1a-4c7e-9a51-0d3f2b7c6a10",
+        ] {
+            let error = parse(line).unwrap_err();
+            assert!(matches!(error, CliError::Unconfirmed(_)), "{}", line);
+            assert_eq!(error.exit_code(), EXIT_USAGE);
+            assert!(parse(&format!("{} --yes", line)).is_ok(), "{}", line);
+        }
+        assert!(parse("strategies resume grid-1").is_ok());
+        assert!(parse("quarantine remove SOL/USDC --reason fixed").is_ok());
+    }
+
+    #[test]
+    fn test_rejects_malformed_arguments() {
+        for line in [
+            "strategies",
+            "strategies pause",
+            "orders cancel not-a-uuid --yes",
+            "orders cancel --yes",
+            "quarantine remove SOL/USDC",
+            "quarantine add SOL/USDC --mode sideways --yes",
+            "positions close SOLUSDC --yes",
+            "status --reason why",
+            "status --verbose",
+            "snapshot create --url",
+        ] {
+            assert!(matches!(parse(line), Err(CliError::Usage(_))), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_renders_lists_as_tables() {
+        let rows = json!([
+            {"position_id": "p-1", "trading_pair": "SOL/USDC", "size": "2.5", "entry_price": "23.45", "current_price": "24", "status": "OPEN"},
+            {"position_id": "p-2", "trading_pair": "BONK/USDC", "size": "-100", "entry_price": "0.00002", "current_price": null, "status": "OPEN"},
+        ]);
+        assert_eq!(
+            render(&Command::PositionsList, &rows, false),
+            "POSITION_ID  TRADING_PAIR  SIZE  ENTRY_PRICE  CURRENT_PRICE  STATUS\n\
+             p-1          SOL/USDC      2.5   23.45        24             OPEN\n\
+             p-2          BONK/USDC     -100  0.00002      -              OPEN"
+        );
+        assert_eq!(render(&Command::PositionsList, &json!([]), false), "(none)");
+        assert_eq!(render(&Command::SnapshotCreate, &json!({"id": "s-1", "trigger": "manual"}), false), "id       s-1\ntrigger  manual");
+        assert_eq!(render(&Command::PositionsList, &rows, true), serde_json::to_string_pretty(&rows).unwrap());
+    }
+}
@@ -0,0 +1,313 @@
+//! Bot-level admission control bounding the number of concurrent strategy executions.
+//! Capacity is sized from configuration and can be resized at runtime through the
+//! config reload path without cancelling in-flight trades.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - metrics = "0.20"
+//! - parking_lot = "0.12"
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use metrics::{counter, gauge, histogram};
+use parking_lot::Mutex;
+use tokio::sync::{watch, OwnedSemaphorePermit, Semaphore};
+use tracing::{error, info, warn};
+
+use crate::config::environment::EnvironmentConfig;
+use crate::Error;
+
+// Admission control constants
+pub const AT_CAPACITY: &str = "at capacity";
+const METRICS_PREFIX: &str = "trading_bot.admission";
+
+/// Admission limits derived from configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdmissionConfig {
+    pub max_concurrent_trades: usize,
+    pub wait_timeout: Duration,
+}
+
+impl AdmissionConfig {
+    pub fn from_environment(config: &EnvironmentConfig) -> Self {
+        Self {
+            max_concurrent_trades: config.max_concurrent_trades,
+            wait_timeout: Duration::from_millis(config.admission_timeout_ms),
+        }
+    }
+}
+
+/// Configured capacity and the permits still to retire after a shrink
+#[derive(Debug)]
+struct Limits {
+    capacity: usize,
+    pending_shrink: usize,
+}
+
+/// Semaphore-backed admission controller for strategy executions
+#[derive(Debug)]
+pub struct AdmissionController {
+    semaphore: Arc<Semaphore>,
+    limits: Mutex<Limits>,
+    wait_timeout_ms: AtomicU64,
+    in_flight: AtomicUsize,
+    queued: AtomicUsize,
+}
+
+/// Slot held by an admitted execution; released when dropped
+#[derive(Debug)]
+pub struct AdmissionPermit {
+    permit: Option<OwnedSemaphorePermit>,
+    controller: Arc<AdmissionController>,
+}
+
+impl Drop for AdmissionPermit {
+    fn drop(&mut self) {
+        let mut limits = self.controller.limits.lock();
+        if let Some(permit) = self.permit.take() {
+            // Retire the slot instead of returning it while a shrink is outstanding
+            if limits.pending_shrink > 0 {
+                limits.pending_shrink -= 1;
+                permit.forget();
+            }
+        }
+        drop(limits);
+
+        let in_flight = self.controller.in_flight.fetch_sub(1, Ordering::SeqCst) - 1;
+        gauge!(format!("{}.in_flight", METRICS_PREFIX), in_flight as f64);
+    }
+}
+
+impl AdmissionController {
+    pub fn new(config: AdmissionConfig) -> Self {
+        gauge!(format!("{}.capacity", METRICS_PREFIX), config.max_concurrent_trades as f64);
+
+        Self {
+            semaphore: Arc::new(Semaphore::new(config.max_concurrent_trades)),
+            limits: Mutex::new(Limits {
+                capacity: config.max_concurrent_trades,
+                pending_shrink: 0,
+            }),
+            wait_timeout_ms: AtomicU64::new(config.wait_timeout.as_millis() as u64),
+            in_flight: AtomicUsize::new(0),
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.limits.lock().capacity
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::SeqCst)
+    }
+
+    /// Waits for a free slot, rejecting with `at capacity` once the deadline passes
+    pub async fn admit(self: &Arc<Self>) -> Result<AdmissionPermit, Error> {
+        let start = Instant::now();
+
+        let permit = match self.semaphore.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                let timeout = Duration::from_millis(self.wait_timeout_ms.load(Ordering::SeqCst));
+                let queued = self.queued.fetch_add(1, Ordering::SeqCst) + 1;
+                gauge!(format!("{}.queued", METRICS_PREFIX), queued as f64);
+
+                let result = tokio::time::timeout(timeout, self.semaphore.clone().acquire_owned()).await;
+
+                let queued = self.queued.fetch_sub(1, Ordering::SeqCst) - 1;
+                gauge!(format!("{}.queued", METRICS_PREFIX), queued as f64);
+
+                match result {
+                    Ok(Ok(permit)) => permit,
+                    Ok(Err(_)) => return Err(Error::System("admission closed".to_string())),
+                    Err(_) => {
+                        counter!(format!("{}.rejected", METRICS_PREFIX), 1);
+                        warn!(timeout_ms = timeout.as_millis() as u64, "Trade admission rejected");
+                        return Err(Error::System(AT_CAPACITY.to_string()));
+                    }
+                }
+            }
+        };
+
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        gauge!(format!("{}.in_flight", METRICS_PREFIX), in_flight as f64);
+        histogram!(
+            format!("{}.wait_ms", METRICS_PREFIX),
+            start.elapsed().as_millis() as f64
+        );
+        counter!(format!("{}.admitted", METRICS_PREFIX), 1);
+
+        Ok(AdmissionPermit {
+            permit: Some(permit),
+            controller: self.clone(),
+        })
+    }
+
+    /// Runs an execution inside an admission slot held until it reaches a terminal state
+    pub async fn run<F, T>(self: &Arc<Self>, execution: F) -> Result<T, Error>
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
+        let _permit = self.admit().await?;
+        execution.await
+    }
+
+    /// Resizes capacity; shrinking only stops admissions and never cancels in-flight trades
+    pub fn resize(&self, capacity: usize) {
+        let mut limits = self.limits.lock();
+        if capacity == limits.capacity {
+            return;
+        }
+
+        if capacity > limits.capacity {
+            // Cancel outstanding retirements before adding new slots
+            let grow = capacity - limits.capacity;
+            let repaid = grow.min(limits.pending_shrink);
+            limits.pending_shrink -= repaid;
+            self.semaphore.add_permits(grow - repaid);
+        } else {
+            // Retire idle slots now and the rest as in-flight trades finish
+            let mut shrink = limits.capacity - capacity;
+            while shrink > 0 {
+                match self.semaphore.try_acquire() {
+                    Ok(permit) => {
+                        permit.forget();
+                        shrink -= 1;
+                    }
+                    Err(_) => break,
+                }
+            }
+            limits.pending_shrink += shrink;
+        }
+
+        info!(from = limits.capacity, to = capacity, "Resized trade admission capacity");
+        limits.capacity = capacity;
+        gauge!(format!("{}.capacity", METRICS_PREFIX), capacity as f64);
+    }
+
+    /// Applies new admission limits
+    pub fn apply_config(&self, config: AdmissionConfig) {
+        self.wait_timeout_ms
+            .store(config.wait_timeout.as_millis() as u64, Ordering::SeqCst);
+        self.resize(config.max_concurrent_trades);
+    }
+
+    /// Applies environment config updates from the config reload path
+    pub fn spawn_reload_listener(
+        self: Arc<Self>,
+        mut updates: watch::Receiver<Option<EnvironmentConfig>>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            while updates.changed().await.is_ok() {
+                let config = updates.borrow().clone();
+                if let Some(config) = config {
+                    self.apply_config(AdmissionConfig::from_environment(&config));
+                }
+            }
+            error!("Environment config update channel closed");
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::sleep;
+
+    const SLOW_EXECUTION: Duration = Duration::from_millis(300);
+
+    fn controller(max_concurrent_trades: usize, wait_timeout: Duration) -> Arc<AdmissionController> {
+        Arc::new(AdmissionController::new(AdmissionConfig {
+            max_concurrent_trades,
+            wait_timeout,
+        }))
+    }
+
+    fn saturate(controller: &Arc<AdmissionController>) -> Vec<tokio::task::JoinHandle<Result<(), Error>>> {
+        (0..controller.capacity())
+            .map(|_| {
+                let controller = controller.clone();
+                tokio::spawn(async move {
+                    controller
+                        .run(async {
+                            sleep(SLOW_EXECUTION).await;
+                            Ok(())
+                        })
+                        .await
+                })
+            })
+            .collect()
+    }
+
+    async fn wait_for_in_flight(controller: &AdmissionController, expected: usize) {
+        while controller.in_flight() < expected {
+            sleep(Duration::from_millis(1)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejects_when_saturated_past_deadline() {
+        let controller = controller(100, Duration::from_millis(20));
+        let handles = saturate(&controller);
+        wait_for_in_flight(&controller, 100).await;
+
+        let result = controller.run(async { Ok(()) }).await;
+        assert!(matches!(result, Err(Error::System(ref msg)) if msg == AT_CAPACITY));
+
+        for handle in handles {
+            assert!(handle.await.unwrap().is_ok());
+        }
+        assert_eq!(controller.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_queues_when_deadline_allows() {
+        let controller = controller(100, Duration::from_secs(5));
+        let handles = saturate(&controller);
+        wait_for_in_flight(&controller, 100).await;
+
+        let start = Instant::now();
+        assert!(controller.run(async { Ok(()) }).await.is_ok());
+        assert!(start.elapsed() >= SLOW_EXECUTION / 2);
+
+        for handle in handles {
+            assert!(handle.await.unwrap().is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shrink_keeps_in_flight_trades() {
+        let controller = controller(2, Duration::from_millis(20));
+        let first = controller.admit().await.unwrap();
+        let second = controller.admit().await.unwrap();
+
+        controller.resize(1);
+        assert_eq!(controller.capacity(), 1);
+        assert_eq!(controller.in_flight(), 2);
+
+        // First release is retired, so the bot is still full at the new limit
+        drop(first);
+        assert!(controller.admit().await.is_err());
+
+        drop(second);
+        let third = controller.admit().await.unwrap();
+        assert!(controller.admit().await.is_err());
+        drop(third);
+
+        controller.resize(3);
+        let _held: Vec<_> = vec![
+            controller.admit().await.unwrap(),
+            controller.admit().await.unwrap(),
+            controller.admit().await.unwrap(),
+        ];
+        assert!(controller.admit().await.is_err());
+    }
+}
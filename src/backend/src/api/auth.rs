@@ -7,12 +7,12 @@ use axum::{
     Json,
     http::{HeaderMap, StatusCode},
 }; // v0.6.18
-use jsonwebtoken::{encode, decode, Header, Validation, EncodingKey, DecodingKey}; // v8.1.1
 use redis::{Client as RedisClient, AsyncCommands}; // v0.23.0
 use serde::{Deserialize, Serialize}; // v1.0.164
 use tracing::{error, info, instrument, warn}; // v0.1.37
 use validator::Validate; // v0.16.0
 
+use crate::api::jwks::{JwtKeyStore, KeyStoreError};
 use crate::utils::crypto::{verify_wallet_signature, generate_nonce};
 use crate::config::security::SecurityConfig;
 
@@ -62,21 +62,22 @@ pub struct RefreshResponse {
 }
 
 /// JWT claims structure
-#[derive(Debug, Serialize, Deserialize)]
-struct Claims {
-    sub: String, // wallet address
-    exp: i64,    // expiration time
-    iat: i64,    // issued at
-    device_id: Option<String>,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String, // wallet address
+    pub exp: i64,    // expiration time
+    pub iat: i64,    // issued at
+    pub device_id: Option<String>,
 }
 
 /// Authenticates a Solana wallet with rate limiting and audit logging
-#[instrument(skip(auth_request, redis_client, security_config))]
+#[instrument(skip(auth_request, redis_client, security_config, key_store))]
 pub async fn authenticate_wallet(
     client_ip: ClientIp,
     Json(auth_request): Json<AuthRequest>,
     redis_client: Arc<RedisClient>,
     security_config: Arc<SecurityConfig>,
+    key_store: Arc<JwtKeyStore>,
 ) -> Result<(HeaderMap, Json<AuthResponse>), (StatusCode, String)> {
     // Validate request format
     if let Err(e) = auth_request.validate() {
@@ -135,20 +136,13 @@ pub async fn authenticate_wallet(
         device_id: auth_request.device_id,
     };
 
-    let access_token = encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(security_config.jwt.secret_key.as_bytes()),
-    ).map_err(|e| {
+    // Sign with the newest key; older keys stay valid for outstanding tokens
+    let access_token = key_store.sign(&claims).map_err(|e| {
         error!("Token generation failed: {}", e);
         (StatusCode::INTERNAL_SERVER_ERROR, "Authentication failed".to_string())
     })?;
 
-    let refresh_token = encode(
-        &Header::default(),
-        &refresh_claims,
-        &EncodingKey::from_secret(security_config.jwt.secret_key.as_bytes()),
-    ).map_err(|e| {
+    let refresh_token = key_store.sign(&refresh_claims).map_err(|e| {
         error!("Refresh token generation failed: {}", e);
         (StatusCode::INTERNAL_SERVER_ERROR, "Authentication failed".to_string())
     })?;
@@ -179,19 +173,15 @@ pub async fn authenticate_wallet(
 }
 
 /// Refreshes access token using a valid refresh token
-#[instrument(skip(refresh_request, redis_client, security_config))]
+#[instrument(skip(refresh_request, redis_client, security_config, key_store))]
 pub async fn refresh_token(
     Json(refresh_request): Json<RefreshRequest>,
     redis_client: Arc<RedisClient>,
     security_config: Arc<SecurityConfig>,
+    key_store: Arc<JwtKeyStore>,
 ) -> Result<Json<RefreshResponse>, (StatusCode, String)> {
     // Validate refresh token
-    let validation = Validation::default();
-    let token_data = decode::<Claims>(
-        &refresh_request.refresh_token,
-        &DecodingKey::from_secret(security_config.jwt.secret_key.as_bytes()),
-        &validation,
-    ).map_err(|e| {
+    let token_data = key_store.decode::<Claims>(&refresh_request.refresh_token).await.map_err(|e| {
         error!("Invalid refresh token: {}", e);
         (StatusCode::UNAUTHORIZED, "Invalid refresh token".to_string())
     })?;
@@ -218,11 +208,7 @@ pub async fn refresh_token(
         device_id: token_data.claims.device_id,
     };
 
-    let access_token = encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(security_config.jwt.secret_key.as_bytes()),
-    ).map_err(|e| {
+    let access_token = key_store.sign(&claims).map_err(|e| {
         error!("Token generation failed: {}", e);
         (StatusCode::INTERNAL_SERVER_ERROR, "Token refresh failed".to_string())
     })?;
@@ -232,4 +218,11 @@ pub async fn refresh_token(
         access_token,
         expires_in: security_config.jwt.token_expiry,
     }))
-}
\ No newline at end of file
+}
+
+/// Validates an access token against the active key set
+#[instrument(skip(token, key_store))]
+pub async fn validate_token(token: &str, key_store: &JwtKeyStore) -> Result<Claims, KeyStoreError> {
+    let token_data = key_store.decode::<Claims>(token).await?;
+    Ok(token_data.claims)
+}
@@ -0,0 +1,349 @@
+//! Redis pub/sub bridge for multi-instance deployments. The trading instance publishes
+//! market data aggregates, executed trades and risk snapshots to Redis channels; API
+//! replicas subscribe and feed them into their local WebSocket broadcast paths. Every
+//! message carries the publishing instance's id so an instance never re-delivers its own
+//! events, and subscribers resubscribe with backoff after Redis restarts.
+//!
+//! The bridge is optional: single-process deployments wire the WebSocket forwarders
+//! directly to the in-process channels and never construct it.
+//!
+//! Version dependencies:
+//! - redis = "0.23"
+//! - tokio = "1.28"
+//! - serde = "1.0"
+//! - metrics = "0.21"
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use metrics::{counter, gauge};
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::{broadcast, Mutex};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::api::websocket::WebSocketServer;
+use crate::execution_engine::TradeEvent;
+use crate::models::market::MarketData;
+use crate::risk_manager::factors::RiskFactorSnapshot;
+
+// Bridge constants
+const METRICS_PREFIX: &str = "trading_bot.bridge";
+pub const DEFAULT_CHANNEL_PREFIX: &str = "firebot:bridge";
+const RESUBSCRIBE_DELAY_MS: u64 = 500;
+const MAX_RESUBSCRIBE_DELAY_MS: u64 = 30_000;
+
+/// Bridge error types
+#[derive(Error, Debug)]
+pub enum BridgeError {
+    #[error("redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+    #[error("serialization error: {0}")]
+    Serialization(String),
+}
+
+/// Event stream carried across instances
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BridgeTopic {
+    MarketData,
+    Trades,
+    Risk,
+}
+
+impl BridgeTopic {
+    pub const ALL: [BridgeTopic; 3] = [Self::MarketData, Self::Trades, Self::Risk];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MarketData => "market_data",
+            Self::Trades => "trades",
+            Self::Risk => "risk",
+        }
+    }
+}
+
+/// Event published to or received from the bridge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum BridgeEvent {
+    MarketData(Vec<MarketData>),
+    Trade(TradeEvent),
+    Risk(RiskFactorSnapshot),
+}
+
+impl BridgeEvent {
+    pub fn topic(&self) -> BridgeTopic {
+        match self {
+            Self::MarketData(_) => BridgeTopic::MarketData,
+            Self::Trade(_) => BridgeTopic::Trades,
+            Self::Risk(_) => BridgeTopic::Risk,
+        }
+    }
+}
+
+/// Wire format of a bridged event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BridgeMessage {
+    /// Instance that published the event
+    pub instance_id: String,
+    pub published_at: DateTime<Utc>,
+    pub event: BridgeEvent,
+}
+
+/// Bridge settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct BridgeConfig {
+    /// Identity stamped on published messages; unique per process
+    pub instance_id: String,
+    /// Prefix of the Redis channel names, shared by every instance of a deployment
+    pub channel_prefix: String,
+    /// First delay before resubscribing after the connection drops, doubled per failure
+    pub resubscribe_delay: Duration,
+    pub max_resubscribe_delay: Duration,
+}
+
+impl Default for BridgeConfig {
+    fn default() -> Self {
+        Self {
+            instance_id: Uuid::new_v4().to_string(),
+            channel_prefix: DEFAULT_CHANNEL_PREFIX.to_string(),
+            resubscribe_delay: Duration::from_millis(RESUBSCRIBE_DELAY_MS),
+            max_resubscribe_delay: Duration::from_millis(MAX_RESUBSCRIBE_DELAY_MS),
+        }
+    }
+}
+
+impl BridgeConfig {
+    /// Redis channel carrying a topic
+    pub fn channel(&self, topic: BridgeTopic) -> String {
+        format!("{}:{}", self.channel_prefix, topic.as_str())
+    }
+}
+
+/// Local delivery path for events received from other instances
+#[async_trait]
+pub trait BridgeSink: Send + Sync {
+    async fn deliver(&self, event: BridgeEvent);
+}
+
+#[async_trait]
+impl BridgeSink for WebSocketServer {
+    async fn deliver(&self, event: BridgeEvent) {
+        let result = match &event {
+            BridgeEvent::MarketData(batch) => self.broadcast_market_data(batch.clone()).await.map(|_| ()),
+            BridgeEvent::Trade(trade) => self.broadcast_trade(trade).map(|_| ()),
+            BridgeEvent::Risk(snapshot) => self.broadcast_risk_snapshot(snapshot).map(|_| ()),
+        };
+        if let Err(e) = result {
+            warn!(topic = event.topic().as_str(), "Bridged event broadcast failed: {}", e);
+        }
+    }
+}
+
+/// Publishes local events to Redis and delivers other instances' events locally
+pub struct EventBridge {
+    config: BridgeConfig,
+    client: Arc<redis::Client>,
+    publisher: Mutex<Option<ConnectionManager>>,
+}
+
+impl std::fmt::Debug for EventBridge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBridge")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl EventBridge {
+    pub fn new(config: BridgeConfig, client: Arc<redis::Client>) -> Self {
+        Self {
+            config,
+            client,
+            publisher: Mutex::new(None),
+        }
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.config.instance_id
+    }
+
+    /// Publishes one event, connecting on first use and reconnecting after failures
+    pub async fn publish(&self, event: BridgeEvent) -> Result<(), BridgeError> {
+        let topic = event.topic();
+        let payload = serde_json::to_string(&BridgeMessage {
+            instance_id: self.config.instance_id.clone(),
+            published_at: Utc::now(),
+            event,
+        })
+        .map_err(|e| BridgeError::Serialization(e.to_string()))?;
+
+        let mut publisher = self.publisher.lock().await;
+        let mut connection = match publisher.take() {
+            Some(connection) => connection,
+            None => ConnectionManager::new((*self.client).clone()).await?,
+        };
+        let result: redis::RedisResult<()> = connection.publish(self.config.channel(topic), payload).await;
+        if let Err(e) = result {
+            // The connection is dropped so the next publish starts from a fresh one
+            counter!(format!("{}.publish_failures", METRICS_PREFIX), 1, "topic" => topic.as_str());
+            return Err(e.into());
+        }
+        *publisher = Some(connection);
+        counter!(format!("{}.published", METRICS_PREFIX), 1, "topic" => topic.as_str());
+        Ok(())
+    }
+
+    /// Publishes every event received on an in-process channel
+    pub fn spawn_publisher<T, F>(
+        self: Arc<Self>,
+        mut events: broadcast::Receiver<T>,
+        into_event: F,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        T: Clone + Send + 'static,
+        F: Fn(T) -> BridgeEvent + Send + 'static,
+    {
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        if let Err(e) = self.publish(into_event(event)).await {
+                            warn!("Bridge publish failed: {}", e);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        counter!(format!("{}.lagged", METRICS_PREFIX), skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Decodes a received payload, dropping this instance's own messages
+    pub fn accept(&self, payload: &str, now: DateTime<Utc>) -> Option<BridgeEvent> {
+        let message: BridgeMessage = match serde_json::from_str(payload) {
+            Ok(message) => message,
+            Err(e) => {
+                counter!(format!("{}.decode_failures", METRICS_PREFIX), 1);
+                warn!("Dropping undecodable bridge message: {}", e);
+                return None;
+            }
+        };
+        if message.instance_id == self.config.instance_id {
+            counter!(format!("{}.own_messages_skipped", METRICS_PREFIX), 1);
+            return None;
+        }
+
+        let lag_ms = (now - message.published_at).num_milliseconds().max(0);
+        gauge!(
+            format!("{}.lag_ms", METRICS_PREFIX),
+            lag_ms as f64,
+            "topic" => message.event.topic().as_str()
+        );
+        Some(message.event)
+    }
+
+    /// Subscribes to every topic and delivers received events to the sink, resubscribing
+    /// with backoff whenever the subscription drops
+    pub fn spawn_subscriber(self: Arc<Self>, sink: Arc<dyn BridgeSink>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut delay = self.config.resubscribe_delay;
+            loop {
+                match self.run_subscription(sink.as_ref()).await {
+                    Ok(delivered) if delivered > 0 => {
+                        delay = self.config.resubscribe_delay;
+                        warn!("Bridge subscription ended after {} events, resubscribing", delivered);
+                    }
+                    Ok(_) => warn!("Bridge subscription ended, resubscribing"),
+                    Err(e) => warn!("Bridge subscription failed: {}", e),
+                }
+                counter!(format!("{}.resubscribes", METRICS_PREFIX), 1);
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(self.config.max_resubscribe_delay);
+            }
+        })
+    }
+
+    /// Runs one subscription until the connection closes, returning the events delivered
+    async fn run_subscription(&self, sink: &dyn BridgeSink) -> Result<u64, BridgeError> {
+        let mut pubsub = self.client.get_async_connection().await?.into_pubsub();
+        for topic in BridgeTopic::ALL {
+            pubsub.subscribe(self.config.channel(topic)).await?;
+        }
+        info!(instance_id = %self.config.instance_id, "Subscribed to bridge channels");
+
+        let mut delivered = 0;
+        let mut messages = pubsub.on_message();
+        while let Some(message) = messages.next().await {
+            let payload: String = match message.get_payload() {
+                Ok(payload) => payload,
+                Err(e) => {
+                    debug!("Skipping non-text bridge payload: {}", e);
+                    continue;
+                }
+            };
+            if let Some(event) = self.accept(&payload, Utc::now()) {
+                sink.deliver(event).await;
+                delivered += 1;
+            }
+        }
+        Ok(delivered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn bridge(instance_id: &str) -> EventBridge {
+        EventBridge::new(
+            BridgeConfig {
+                instance_id: instance_id.to_string(),
+                ..BridgeConfig::default()
+            },
+            Arc::new(redis::Client::open("redis://127.0.0.1").unwrap()),
+        )
+    }
+
+    fn message(instance_id: &str, published_at: DateTime<Utc>) -> String {
+        serde_json::to_string(&BridgeMessage {
+            instance_id: instance_id.to_string(),
+            published_at,
+            event: BridgeEvent::MarketData(vec![MarketData::new(
+                "SOL/USDC".to_string(),
+                "jupiter".to_string(),
+                dec!(23.45),
+                dec!(1000),
+            )
+            .unwrap()]),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_own_messages_are_not_redelivered() {
+        let bridge = bridge("api-replica");
+        let now = Utc::now();
+
+        assert!(bridge.accept(&message("api-replica", now), now).is_none());
+        let event = bridge.accept(&message("trading", now - chrono::Duration::milliseconds(40)), now);
+        assert_eq!(event.unwrap().topic(), BridgeTopic::MarketData);
+        assert!(bridge.accept("not json", now).is_none());
+    }
+
+    #[test]
+    fn test_channels_share_deployment_prefix() {
+        let config = BridgeConfig::default();
+        assert_eq!(config.channel(BridgeTopic::Trades), "firebot:bridge:trades");
+        assert_ne!(config.instance_id, BridgeConfig::default().instance_id);
+    }
+}
@@ -3,9 +3,9 @@
 //! Implements secure, rate-limited endpoints with comprehensive monitoring and caching
 
 use axum::{
-    extract::{Extension, Query},
+    extract::{BodyStream, Extension, Path, Query},
     Json,
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::{IntoResponse, Response},
 }; // v0.6.18
 use tower::{limit::RateLimitLayer, ServiceBuilder}; // v0.4.13
@@ -13,9 +13,64 @@ use metrics::{counter, histogram}; // v0.20.1
 use cached::{Cached, TimedCache}; // v0.42.0
 use serde::{Deserialize, Serialize};
 use tracing::{error, info, instrument, warn};
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
-use crate::api::auth::{authenticate_wallet, validate_token};
+use crate::api::auth::{authenticate_wallet, validate_token, Claims};
+use crate::api::idempotency::{self, CachedResponse, IdempotencyError};
+use crate::api::order_signing::{OrderAuthorization, OrderSignatureError, SignedOrder};
+use crate::api::scoped_tokens::{MintTokenRequest, MintedToken, ScopedAccess, ScopedToken, ScopedTokenError, TokenScope};
+use crate::api::websocket::OrderPlacement;
+use crate::api::AppState;
+use crate::attribution::{AttributionDimension, AttributionError, AttributionReport, DEFAULT_GROUP_BY};
+use crate::api::webhooks::WebhookDispatcher;
+use crate::data_collector::gaps::{DataGap, GapError};
+use crate::data_collector::ingest::{IngestError, IngestFormat, IngestSummary, MarketDataIngestor};
+use crate::data_collector::lifecycle::{CollectorRestart, LifecycleError, RestartTrigger};
+use crate::data_collector::ohlcv::{fill_gaps, Candle, CandleAggregator, CandleInterval};
+use crate::data_collector::quality::SourceScore;
+use crate::db::repositories::{CandleRepository, TransferRepository};
+use crate::events::{EconomicEvent, EventCalendar, EventError, EventRequest, UPCOMING_HORIZON};
+use crate::execution_engine::open_orders::{CancelError, CancelFilter, CancelOutcome, CancelStatus, OpenOrderRegistry};
+use crate::execution_engine::order_book::{OrderBookSnapshot, MAX_SNAPSHOT_DEPTH};
+use crate::execution_engine::position_events::{LivePositions, PositionEventError, PositionEventRecord, PositionState};
+use crate::execution_engine::preview::{PreviewError, PreviewRequest, StrategyPreview};
+use crate::execution_engine::cost_model::{CostModel, CostModelCalibration};
+use crate::execution_engine::simulation::{SimulationRequest, TradeSimulation};
+use crate::execution_engine::stats::{ExecutionStats, ExecutionStatsService, StatsError, StatsWindow};
+#[cfg(feature = "fault-injection")]
+use crate::fault_injection::{self, ActiveFault, FaultError, FaultSpec};
+use crate::jobs::{JobError, JobFilter, JobQueue, QueuedJob};
+use crate::key_rotation::{KeyRotationError, KeyRotationService, RotationRun};
+use crate::maintenance::{MaintenanceError, MaintenanceRequest, MaintenanceScheduler, MaintenanceStatus, MaintenanceWindow, ScheduledWindow};
+use crate::models::pair::{PairError, TradingPair};
+use crate::models::portfolio::Portfolio;
+use crate::models::strategy::{Strategy, StrategyParams, StrategySnapshot, StrategyType};
+use crate::models::transfer::Transfer;
+use crate::models::webhook::{Webhook, WebhookAuditEntry, WebhookError, WebhookEventType};
+use crate::optimizer::{
+    JobProgress, OptimizationRequest, OptimizationRun, OptimizerError, OptimizerService,
+};
+use crate::performance::{
+    EquityPoint, LeaderboardColumn, LeaderboardEntry, PerformanceError, SortOrder, StrategyTrade,
+};
+use crate::quarantine::{QuarantineEntry, QuarantineError, QuarantineList, QuarantineOutcome, QuarantineRelease, QuarantineRequest, ReleaseRequest};
+use crate::reports::{DailyReport, ReportError};
+use crate::retention::{EffectiveRetention, RetentionError, RetentionManager, RetentionOverride, RetentionOverrideRequest};
+use crate::risk_manager::degradation::{DegradationError, DegradationSizer, SizingOptOut};
+use crate::risk_manager::exposure::TradeSide;
+use crate::risk_manager::stress::{StressError, StressRequest, StressRun};
+use crate::state_snapshot::{SnapshotError, SnapshotService, SnapshotSummary, SnapshotTrigger};
+use crate::strategy_archive::{ArchiveError, DeletionOutcome, PositionPolicy, StrategyArchive};
+use crate::strategy_versions::{
+    is_variant, AbTest, AbTestConfig, StrategyVersion, StrategyVersionService, VersionError, VersionHistory,
+    SYSTEM_AUTHOR,
+};
+use crate::supervision::{StrategyHealthSnapshot, SupervisionError};
+use crate::system_info::SystemInfo;
+use crate::utils::crypto::generate_nonce;
+use crate::utils::logger::{self, LoggerError};
+use crate::utils::token_accounts::{AccountClosure, TokenAccountError, TokenAccountManager};
 use std::time::Duration;
 use std::sync::Arc;
 
@@ -24,6 +79,11 @@ pub const API_VERSION: &str = "v1";
 pub const MAX_PAGE_SIZE: u32 = 100;
 pub const MARKET_DATA_CACHE_TTL: Duration = Duration::from_secs(5);
 pub const ORDER_RATE_LIMIT: u32 = 100;
+pub const MAX_CANDLES_PER_REQUEST: i64 = 1000;
+pub const DEFAULT_CANDLE_COUNT: i64 = 100;
+pub const DEFAULT_TRANSFER_LIMIT: i64 = 50;
+pub const DEFAULT_ORDER_BOOK_DEPTH: usize = 20;
+pub const DEFAULT_TRADE_LIMIT: usize = 100;
 
 /// Market data request parameters with validation
 #[derive(Debug, Deserialize, Validate)]
@@ -70,11 +130,242 @@ pub struct PageInfo {
     pub items_per_page: u32,
 }
 
-/// Order creation request with validation
+/// Candle query parameters; `from` and `to` are unix seconds
+#[derive(Debug, Deserialize, Validate)]
+pub struct CandleRequest {
+    #[validate(length(min = 2, max = 3))]
+    pub interval: String,
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+}
+
+/// Execution statistics query; `window` is one of 1d, 7d or 30d
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExecutionStatsRequest {
+    pub pair: String,
+    pub window: Option<String>,
+}
+
+/// Attribution report query; `from` and `to` are inclusive UTC dates and `group_by` is a
+/// comma-separated subset of strategy, version, venue, pair, wallet and day
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AttributionRequest {
+    pub from: chrono::NaiveDate,
+    pub to: chrono::NaiveDate,
+    pub group_by: Option<String>,
+}
+
+/// Event schedule query; events scheduled in `[from, to)` are returned
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EventRangeRequest {
+    pub from: chrono::DateTime<chrono::Utc>,
+    pub to: chrono::DateTime<chrono::Utc>,
+}
+
+/// Bulk market data ingestion query; rows are stored under `vendor:<vendor>`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IngestRequest {
+    pub vendor: String,
+}
+
+/// Per-venue execution statistics for a trading pair
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct ExecutionStatsResponse {
+    pub trading_pair: String,
+    pub window: StatsWindow,
+    pub venues: Vec<ExecutionStats>,
+}
+
+/// Leaderboard query; `sort_by` is any entry column and `order` is asc or desc
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LeaderboardRequest {
+    pub sort_by: Option<String>,
+    pub order: Option<String>,
+    /// Lists deleted strategies alongside live ones
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+/// Equity curve query; `granularity` is one of 1m, 5m or 1h
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EquityRequest {
+    pub granularity: Option<String>,
+}
+
+/// Cumulative realized P&L series for a strategy
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct EquityResponse {
+    pub strategy_id: String,
+    pub granularity: CandleInterval,
+    pub points: Vec<EquityPoint>,
+}
+
+/// Strategy registration request; the strategy starts inactive
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateStrategyRequest {
+    /// Key the strategy is registered and addressed under
+    #[validate(length(min = 1, max = 64))]
+    pub strategy_id: String,
+    pub strategy_type: StrategyType,
+    pub parameters: StrategyParams,
+    #[validate(length(min = 1, max = 20))]
+    pub trading_pairs: Vec<String>,
+}
+
+/// New parameters for a strategy, recorded as its next version
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateParametersRequest {
+    pub parameters: StrategyParams,
+}
+
+/// Earlier version to make live again
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RollbackRequest {
+    pub version: u32,
+}
+
+/// Challenger parameters and how to trial them against the active version
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StartAbTestRequest {
+    pub parameters: StrategyParams,
+    pub config: AbTestConfig,
+}
+
+/// Strategy trade list query
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TradeListRequest {
+    /// Trades to return, newest first; at most 500
+    #[validate(range(min = 1, max = 500))]
+    pub limit: Option<usize>,
+}
+
+/// Strategy deletion query
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteStrategyRequest {
+    /// Flatten open positions (default) or transfer them to the manual strategy
+    #[serde(default)]
+    pub policy: PositionPolicy,
+}
+
+/// A strategy's most recent realized trades
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct TradeListResponse {
+    pub strategy_id: String,
+    pub trades: Vec<StrategyTrade>,
+    pub timestamp: i64,
+}
+
+/// Candle series response
+#[derive(Debug, Serialize, Clone)]
+pub struct CandleResponse {
+    pub trading_pair: String,
+    pub interval: CandleInterval,
+    pub candles: Vec<Candle>,
+    pub gap_filled_count: usize,
+    pub timestamp: i64,
+}
+
+/// Order book query parameters; depth is capped at `MAX_SNAPSHOT_DEPTH`
+#[derive(Debug, Deserialize, Validate)]
+pub struct OrderBookRequest {
+    #[validate(range(min = 1))]
+    pub depth: Option<usize>,
+    #[validate(length(min = 1, max = 32))]
+    pub exchange: Option<String>,
+}
+
+/// Transfer history query parameters
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TransferListRequest {
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<i64>,
+}
+
+/// Wallet deposits and withdrawals
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct TransferListResponse {
+    pub wallet_address: String,
+    pub transfers: Vec<Transfer>,
+    pub timestamp: i64,
+}
+
+/// Portfolio performance with raw and flow-adjusted returns
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct PortfolioPerformanceResponse {
+    pub wallet_address: String,
+    pub raw_return_pct: rust_decimal::Decimal,
+    pub flow_adjusted_return_pct: rust_decimal::Decimal,
+    pub drawdown_pct: rust_decimal::Decimal,
+    pub max_drawdown_pct: rust_decimal::Decimal,
+    pub net_flows: rust_decimal::Decimal,
+    pub high_water_mark: rust_decimal::Decimal,
+    pub timestamp: i64,
+}
+
+/// Webhook registration request; a signing secret is generated when omitted
 #[derive(Debug, Deserialize, Validate)]
+pub struct CreateWebhookRequest {
+    #[validate(url)]
+    pub url: String,
+    #[validate(length(min = 16, max = 256))]
+    pub secret: Option<String>,
+    pub event_types: Vec<WebhookEventType>,
+}
+
+/// Webhook update request
+#[derive(Debug, Deserialize)]
+pub struct UpdateWebhookRequest {
+    pub active: Option<bool>,
+    pub event_types: Option<Vec<WebhookEventType>>,
+}
+
+/// Newly registered webhook; the secret is only returned here
+#[derive(Debug, Serialize, Clone)]
+pub struct CreateWebhookResponse {
+    pub webhook: Webhook,
+    pub secret: String,
+    pub timestamp: i64,
+}
+
+/// Registered webhooks
+#[derive(Debug, Serialize, Clone)]
+pub struct WebhookListResponse {
+    pub webhooks: Vec<Webhook>,
+    pub timestamp: i64,
+}
+
+/// Webhook with its status change history
+#[derive(Debug, Serialize, Clone)]
+pub struct WebhookDetailResponse {
+    pub webhook: Webhook,
+    pub audit_log: Vec<WebhookAuditEntry>,
+    pub timestamp: i64,
+}
+
+/// Optimization results ranked by the job's objective
+#[derive(Debug, Serialize, Clone)]
+pub struct OptimizationResultsResponse {
+    pub job: JobProgress,
+    pub runs: Vec<OptimizationRun>,
+    pub timestamp: i64,
+}
+
+/// Order creation request with validation
+#[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct OrderRequest {
     #[validate(length(min = 1, max = 20))]
     pub trading_pair: String,
+
+    pub side: TradeSide,
     
     #[validate(range(min = 0.0))]
     pub amount: f64,
@@ -87,6 +378,10 @@ pub struct OrderRequest {
     
     #[validate(range(min = 0.0, max = 100.0))]
     pub slippage_tolerance: Option<f64>,
+
+    /// Wallet signature, required above the configured notional
+    #[serde(default)]
+    pub authorization: Option<OrderAuthorization>,
 }
 
 /// Order response with execution details
@@ -141,25 +436,362 @@ pub enum ApiError {
     
     #[error("Authentication error: {0}")]
     AuthError(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
     
+    #[error("Not found: {0}")]
+    NotFound(String),
+
     #[error("Internal error: {0}")]
     InternalError(String),
+
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
+    #[error("Request timeout: {0}")]
+    RequestTimeout(String),
+
+    #[error("{0}")]
+    OrderSignature(#[from] OrderSignatureError),
+
+    #[error("{0}")]
+    InvalidTradingPair(#[from] PairError),
+
+    #[error("{0}")]
+    Idempotency(#[from] IdempotencyError),
+}
+
+impl From<WebhookError> for ApiError {
+    fn from(error: WebhookError) -> Self {
+        match error {
+            WebhookError::ValidationError(_) | WebhookError::UnknownEventType(_) => {
+                Self::ValidationError(error.to_string())
+            }
+            WebhookError::NotFound(_) => Self::NotFound(error.to_string()),
+            _ => Self::InternalError(error.to_string()),
+        }
+    }
+}
+
+impl From<OptimizerError> for ApiError {
+    fn from(error: OptimizerError) -> Self {
+        match error {
+            OptimizerError::ValidationError(_) | OptimizerError::TooManyCombinations(..) => {
+                Self::ValidationError(error.to_string())
+            }
+            OptimizerError::NotFound(_) => Self::NotFound(error.to_string()),
+            _ => Self::InternalError(error.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "fault-injection")]
+impl From<FaultError> for ApiError {
+    fn from(error: FaultError) -> Self {
+        match error {
+            FaultError::Invalid(_) => Self::ValidationError(error.to_string()),
+            FaultError::Disabled => Self::AuthError(error.to_string()),
+        }
+    }
+}
+
+impl From<SupervisionError> for ApiError {
+    fn from(error: SupervisionError) -> Self {
+        match error {
+            SupervisionError::NotFound(_) => Self::NotFound(error.to_string()),
+            SupervisionError::NotPaused(_)
+            | SupervisionError::AlreadyPaused(_)
+            | SupervisionError::AlreadyRegistered(_)
+            | SupervisionError::Deleted(_) => {
+                Self::ValidationError(error.to_string())
+            }
+        }
+    }
+}
+
+impl From<MaintenanceError> for ApiError {
+    fn from(error: MaintenanceError) -> Self {
+        match error {
+            MaintenanceError::InvalidWindow(_) | MaintenanceError::Overlap(_) => {
+                Self::ValidationError(error.to_string())
+            }
+            MaintenanceError::NotFound(_) => Self::NotFound(error.to_string()),
+            MaintenanceError::Store(_) => Self::InternalError(error.to_string()),
+        }
+    }
+}
+
+impl From<JobError> for ApiError {
+    fn from(error: JobError) -> Self {
+        match error {
+            JobError::NotFound(_) => Self::NotFound(error.to_string()),
+            JobError::InvalidState { .. } | JobError::UnknownKind(_) | JobError::Payload(_) => {
+                Self::ValidationError(error.to_string())
+            }
+            JobError::LeaseLost(_) | JobError::Store(_) => Self::InternalError(error.to_string()),
+        }
+    }
+}
+
+impl From<AttributionError> for ApiError {
+    fn from(error: AttributionError) -> Self {
+        match error {
+            AttributionError::Store(_) => Self::InternalError(error.to_string()),
+            _ => Self::ValidationError(error.to_string()),
+        }
+    }
+}
+
+impl From<ReportError> for ApiError {
+    fn from(error: ReportError) -> Self {
+        match error {
+            ReportError::NotFound(_) => Self::NotFound(error.to_string()),
+            _ => Self::InternalError(error.to_string()),
+        }
+    }
+}
+
+impl From<ArchiveError> for ApiError {
+    fn from(error: ArchiveError) -> Self {
+        match error {
+            ArchiveError::NotFound(_) => Self::NotFound(error.to_string()),
+            ArchiveError::Flatten { .. } => Self::InternalError(error.to_string()),
+            _ => Self::ValidationError(error.to_string()),
+        }
+    }
+}
+
+impl From<QuarantineError> for ApiError {
+    fn from(error: QuarantineError) -> Self {
+        match error {
+            QuarantineError::NotQuarantined(_) => Self::NotFound(error.to_string()),
+            QuarantineError::Flatten { .. } | QuarantineError::Store(_) => Self::InternalError(error.to_string()),
+            _ => Self::ValidationError(error.to_string()),
+        }
+    }
+}
+
+impl From<EventError> for ApiError {
+    fn from(error: EventError) -> Self {
+        match error {
+            EventError::NotFound(_) => Self::NotFound(error.to_string()),
+            EventError::Store(_) => Self::InternalError(error.to_string()),
+            _ => Self::ValidationError(error.to_string()),
+        }
+    }
+}
+
+impl From<RetentionError> for ApiError {
+    fn from(error: RetentionError) -> Self {
+        match error {
+            RetentionError::NotFound(_) => Self::NotFound(error.to_string()),
+            RetentionError::Store(_) => Self::InternalError(error.to_string()),
+            _ => Self::ValidationError(error.to_string()),
+        }
+    }
+}
+
+impl From<IngestError> for ApiError {
+    fn from(error: IngestError) -> Self {
+        match error {
+            IngestError::Busy => Self::RateLimitExceeded,
+            IngestError::TooLarge { .. } | IngestError::TooManyRows { .. } => Self::PayloadTooLarge(error.to_string()),
+            IngestError::Store(_) => Self::InternalError(error.to_string()),
+            _ => Self::ValidationError(error.to_string()),
+        }
+    }
+}
+
+impl From<TokenAccountError> for ApiError {
+    fn from(error: TokenAccountError) -> Self {
+        match error {
+            TokenAccountError::UnknownPair(_) => Self::NotFound(error.to_string()),
+            _ => Self::InternalError(error.to_string()),
+        }
+    }
+}
+
+impl From<DegradationError> for ApiError {
+    fn from(error: DegradationError) -> Self {
+        Self::ValidationError(error.to_string())
+    }
+}
+
+impl From<StressError> for ApiError {
+    fn from(error: StressError) -> Self {
+        match error {
+            StressError::UnknownScenario(_) | StressError::InvalidScenario(_) => Self::ValidationError(error.to_string()),
+            StressError::Valuation(_) | StressError::Store(_) => Self::InternalError(error.to_string()),
+        }
+    }
+}
+
+impl From<ScopedTokenError> for ApiError {
+    fn from(error: ScopedTokenError) -> Self {
+        match error {
+            ScopedTokenError::InvalidScope(_) => Self::ValidationError(error.to_string()),
+            ScopedTokenError::NotFound(_) => Self::NotFound(error.to_string()),
+            ScopedTokenError::OutOfScope(_) => Self::Forbidden(error.to_string()),
+            ScopedTokenError::RateLimited(_) => Self::RateLimitExceeded,
+            ScopedTokenError::Unknown | ScopedTokenError::Revoked(_) | ScopedTokenError::Expired(_) => {
+                Self::AuthError(error.to_string())
+            }
+            ScopedTokenError::Store(_) => Self::InternalError(error.to_string()),
+        }
+    }
+}
+
+impl From<CancelError> for ApiError {
+    fn from(error: CancelError) -> Self {
+        match error {
+            CancelError::NotFound(_) => Self::NotFound(error.to_string()),
+            CancelError::NotOwner(_) => Self::AuthError(error.to_string()),
+        }
+    }
+}
+
+impl From<KeyRotationError> for ApiError {
+    fn from(error: KeyRotationError) -> Self {
+        match error {
+            KeyRotationError::InProgress => Self::ValidationError(error.to_string()),
+            _ => Self::InternalError(error.to_string()),
+        }
+    }
+}
+
+impl From<StatsError> for ApiError {
+    fn from(error: StatsError) -> Self {
+        match error {
+            StatsError::UnknownWindow(_) => Self::ValidationError(error.to_string()),
+            StatsError::Store(_) => Self::InternalError(error.to_string()),
+        }
+    }
+}
+
+impl From<PerformanceError> for ApiError {
+    fn from(error: PerformanceError) -> Self {
+        match error {
+            PerformanceError::UnknownStrategy(_) => Self::NotFound(error.to_string()),
+            PerformanceError::Store(_) => Self::InternalError(error.to_string()),
+            _ => Self::ValidationError(error.to_string()),
+        }
+    }
+}
+
+impl From<VersionError> for ApiError {
+    fn from(error: VersionError) -> Self {
+        match error {
+            VersionError::UnknownStrategy(_) | VersionError::UnknownVersion { .. } => {
+                Self::NotFound(error.to_string())
+            }
+            VersionError::Store(_) => Self::InternalError(error.to_string()),
+            _ => Self::ValidationError(error.to_string()),
+        }
+    }
+}
+
+impl From<LoggerError> for ApiError {
+    fn from(error: LoggerError) -> Self {
+        match error {
+            LoggerError::InvalidDirective(..) => Self::ValidationError(error.to_string()),
+            _ => Self::InternalError(error.to_string()),
+        }
+    }
+}
+
+impl From<LifecycleError> for ApiError {
+    fn from(error: LifecycleError) -> Self {
+        match error {
+            LifecycleError::NotRunning(_) => Self::NotFound(error.to_string()),
+            _ => Self::InternalError(error.to_string()),
+        }
+    }
+}
+
+impl From<PositionEventError> for ApiError {
+    fn from(error: PositionEventError) -> Self {
+        match error {
+            PositionEventError::NotFound(_) => Self::NotFound(error.to_string()),
+            _ => Self::InternalError(error.to_string()),
+        }
+    }
+}
+
+impl From<PreviewError> for ApiError {
+    fn from(error: PreviewError) -> Self {
+        match error {
+            PreviewError::InvalidDefinition(_) => Self::ValidationError(error.to_string()),
+        }
+    }
+}
+
+impl From<GapError> for ApiError {
+    fn from(error: GapError) -> Self {
+        Self::InternalError(error.to_string())
+    }
+}
+
+impl From<SnapshotError> for ApiError {
+    fn from(error: SnapshotError) -> Self {
+        match error {
+            SnapshotError::NotFound(_) => Self::NotFound(error.to_string()),
+            SnapshotError::ChecksumMismatch(_) | SnapshotError::UnsupportedVersion(_) => {
+                Self::ValidationError(error.to_string())
+            }
+            _ => Self::InternalError(error.to_string()),
+        }
+    }
+}
+
+/// Body of every error response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: String,
+    pub status: u16,
+    pub timestamp: i64,
+    /// Machine-readable reason, set on order signature and trading pair failures
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
+        // Signature failures carry a machine-readable code alongside the message
+        let code = match &self {
+            Self::OrderSignature(e) => Some(e.code()),
+            Self::Idempotency(e) => Some(e.code()),
+            Self::InvalidTradingPair(_) => Some("invalid_trading_pair"),
+            _ => None,
+        };
         let (status, error_message) = match self {
             Self::RateLimitExceeded => (StatusCode::TOO_MANY_REQUESTS, self.to_string()),
             Self::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg),
             Self::AuthError(msg) => (StatusCode::UNAUTHORIZED, msg),
+            Self::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
+            Self::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
             Self::InternalError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+            Self::PayloadTooLarge(msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg),
+            Self::RequestTimeout(msg) => (StatusCode::REQUEST_TIMEOUT, msg),
+            Self::OrderSignature(e) => {
+                let status = match e {
+                    OrderSignatureError::Replayed(_) => StatusCode::CONFLICT,
+                    OrderSignatureError::Store(_) => StatusCode::SERVICE_UNAVAILABLE,
+                    _ => StatusCode::UNAUTHORIZED,
+                };
+                (status, e.to_string())
+            }
+            Self::InvalidTradingPair(e) => (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()),
+            Self::Idempotency(e) => (e.status(), e.to_string()),
         };
 
-        Json(serde_json::json!({
-            "error": error_message,
-            "status": status.as_u16(),
-            "timestamp": chrono::Utc::now().timestamp()
-        })).into_response()
+        let body = ErrorResponse {
+            error: error_message,
+            status: status.as_u16(),
+            timestamp: chrono::Utc::now().timestamp(),
+            code: code.map(str::to_string),
+        };
+        (status, Json(body)).into_response()
     }
 }
 
@@ -220,24 +852,51 @@ pub async fn get_market_data(
     Ok(Json(response))
 }
 
-/// Creates a new trading order with slippage protection
+/// Creates a new trading order with slippage protection; high-value orders also need a wallet
+/// signature. With an `Idempotency-Key` header the order is placed at most once per key.
 #[axum::debug_handler]
-#[tracing::instrument(skip(request, claims))]
+#[tracing::instrument(skip(request, claims, state, headers))]
 pub async fn create_order(
-    Json(request): Json<OrderRequest>,
     Extension(claims): Extension<Claims>,
-) -> Result<Json<OrderResponse>, ApiError> {
+    Extension(state): Extension<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<OrderRequest>,
+) -> Result<Response, ApiError> {
+    let Some(key) = idempotency::idempotency_key(&headers)? else {
+        return place_order(&state, &claims.sub, &request).await.map(IntoResponse::into_response);
+    };
+
+    let fingerprint = idempotency::fingerprint(&request);
+    let outcome = state
+        .idempotency
+        .run(&claims.sub, &key, &fingerprint, || async {
+            CachedResponse::from_response(place_order(&state, &claims.sub, &request).await.into_response()).await
+        })
+        .await?;
+    Ok(outcome.into_response())
+}
+
+/// Validates, authorizes and executes an order for a wallet
+pub async fn place_order(state: &AppState, wallet: &str, request: &OrderRequest) -> Result<Json<OrderResponse>, ApiError> {
     // Validate request parameters
     if let Err(e) = request.validate() {
         counter!("api.orders.validation_errors").increment(1);
         return Err(ApiError::ValidationError(e.to_string()));
     }
 
+    // Second factor for orders above the signature threshold
+    let signed = SignedOrder::from_request(&request.trading_pair, request.side, request.amount, request.price)
+        .ok_or_else(|| ApiError::ValidationError("amount and price must be finite".to_string()))?;
+    state
+        .order_signatures
+        .authorize(wallet, &signed, request.authorization.as_ref(), chrono::Utc::now().timestamp())
+        .await?;
+
     // Verify portfolio balance
-    verify_portfolio_balance(&claims.sub, &request).await?;
+    verify_portfolio_balance(wallet, request).await?;
 
     // Calculate slippage impact
-    let slippage = calculate_slippage(&request).await?;
+    let slippage = calculate_slippage(request).await?;
     if slippage > request.slippage_tolerance.unwrap_or(1.0) {
         counter!("api.orders.slippage_exceeded").increment(1);
         return Err(ApiError::ValidationError("Slippage exceeds tolerance".to_string()));
@@ -247,12 +906,1791 @@ pub async fn create_order(
     let timer = histogram!("api.orders.execution_duration");
     let _timer_guard = timer.start_timer();
 
-    let order_result = execute_order(&request, &claims.sub).await?;
+    let order_result = execute_order(request, wallet).await?;
     
     counter!("api.orders.successful").increment(1);
     Ok(Json(order_result))
 }
 
+#[async_trait::async_trait]
+impl OrderPlacement for AppState {
+    async fn place_order(&self, wallet: &str, order: &OrderRequest) -> CachedResponse {
+        CachedResponse::from_response(place_order(self, wallet, order).await.into_response()).await
+    }
+}
+
+/// Cancels one pending order owned by the caller's wallet
+#[utoipa::path(
+    delete,
+    path = "/api/v1/orders/{id}",
+    tag = "orders",
+    params(("id" = uuid::Uuid, Path, description = "Order ID")),
+    responses(
+        (status = 200, description = "Cancel outcome", body = CancelOutcome),
+        (status = 404, description = "No pending order with this ID for the caller", body = ErrorResponse),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(claims, state))]
+pub async fn cancel_order(
+    Path(id): Path<uuid::Uuid>,
+    Extension(claims): Extension<Claims>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<CancelOutcome>, ApiError> {
+    let outcome = open_orders(&state)?.cancel(id, &claims.sub).await?;
+    counter!("api.orders.cancel_requests").increment(1);
+    Ok(Json(outcome))
+}
+
+/// Outcomes of a bulk cancel
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkCancelResponse {
+    pub cancelled: usize,
+    pub already_filled: usize,
+    pub failed: usize,
+    pub outcomes: Vec<CancelOutcome>,
+}
+
+/// Cancels every pending order of the caller's wallet matching the filter
+#[utoipa::path(
+    post,
+    path = "/api/v1/orders/cancel",
+    tag = "orders",
+    request_body = CancelFilter,
+    responses(
+        (status = 200, description = "Outcome per matched order", body = BulkCancelResponse),
+        (status = 400, description = "Empty filter", body = ErrorResponse),
+        (status = 422, description = "Unrecognized or unsupported trading pair", body = ErrorResponse),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(claims, state))]
+pub async fn cancel_orders(
+    Extension(claims): Extension<Claims>,
+    Extension(state): Extension<Arc<AppState>>,
+    Json(mut filter): Json<CancelFilter>,
+) -> Result<Json<BulkCancelResponse>, ApiError> {
+    if filter.is_empty() {
+        return Err(ApiError::ValidationError(
+            "at least one of trading_pair, exchange, strategy_id or side is required".to_string(),
+        ));
+    }
+    filter.trading_pair = filter
+        .trading_pair
+        .map(|pair| state.pairs.parse(&pair).map(TradingPair::into_string))
+        .transpose()?;
+
+    let outcomes = open_orders(&state)?.cancel_matching(&claims.sub, &filter).await;
+    let count = |status: fn(&CancelStatus) -> bool| outcomes.iter().filter(|o| status(&o.status)).count();
+    let response = BulkCancelResponse {
+        cancelled: count(|s| matches!(s, CancelStatus::Cancelled)),
+        already_filled: count(|s| matches!(s, CancelStatus::AlreadyFilled)),
+        failed: count(|s| matches!(s, CancelStatus::Failed { .. })),
+        outcomes,
+    };
+    counter!("api.orders.bulk_cancel_requests").increment(1);
+    Ok(Json(response))
+}
+
+fn open_orders(state: &AppState) -> Result<&Arc<OpenOrderRegistry>, ApiError> {
+    state.open_orders.as_ref().ok_or_else(|| {
+        ApiError::InternalError("order cancellation unavailable".to_string())
+    })
+}
+
+/// Runs an order through validation, risk, routing and fee estimation without executing it
+#[axum::debug_handler]
+#[tracing::instrument(skip(request, claims, state))]
+pub async fn simulate_trade(
+    Extension(claims): Extension<Claims>,
+    Extension(state): Extension<Arc<AppState>>,
+    Json(mut request): Json<SimulationRequest>,
+) -> Result<Json<TradeSimulation>, ApiError> {
+    if request.size <= rust_decimal::Decimal::ZERO || request.price < rust_decimal::Decimal::ZERO {
+        counter!("api.trades.simulate.validation_errors").increment(1);
+        return Err(ApiError::ValidationError(
+            "size must be positive and price non-negative".to_string(),
+        ));
+    }
+    request.trading_pair = state.pairs.parse(&request.trading_pair)?.into_string();
+
+    let simulator = state.simulator.as_ref().ok_or_else(|| {
+        ApiError::InternalError("trade simulation unavailable".to_string())
+    })?;
+    let simulation = simulator.simulate(&request, &claims.sub).await;
+    if simulation.would_execute {
+        counter!("api.trades.simulate.accepted").increment(1);
+    } else {
+        counter!("api.trades.simulate.rejected").increment(1);
+    }
+    Ok(Json(simulation))
+}
+
+/// Previews the orders a strategy definition would place against live data, without
+/// registering or activating it
+#[axum::debug_handler]
+#[tracing::instrument(skip(request, claims, state))]
+pub async fn preview_strategy(
+    Extension(claims): Extension<Claims>,
+    Extension(state): Extension<Arc<AppState>>,
+    Json(mut request): Json<PreviewRequest>,
+) -> Result<Json<StrategyPreview>, ApiError> {
+    if request.trading_pairs.is_empty() {
+        counter!("api.strategies.preview.validation_errors").increment(1);
+        return Err(ApiError::ValidationError(
+            "at least one trading pair is required".to_string(),
+        ));
+    }
+    for pair in request.trading_pairs.iter_mut() {
+        *pair = state.pairs.parse(pair)?.into_string();
+    }
+
+    let previewer = state.strategy_preview.as_ref().ok_or_else(|| {
+        ApiError::InternalError("strategy preview unavailable".to_string())
+    })?;
+    let preview = previewer
+        .preview(&request, &claims.sub, chrono::Utc::now())
+        .await?;
+    counter!("api.strategies.preview.requests").increment(1);
+    Ok(Json(preview))
+}
+
+/// Retrieves OHLCV candles for a trading pair with carry-forward gap filling
+#[axum::debug_handler]
+#[tracing::instrument(skip(request, aggregator, repository))]
+pub async fn get_candles(
+    Path(pair): Path<String>,
+    Query(request): Query<CandleRequest>,
+    Extension(aggregator): Extension<Arc<CandleAggregator>>,
+    Extension(repository): Extension<Arc<CandleRepository>>,
+) -> Result<Json<CandleResponse>, ApiError> {
+    // Validate request parameters
+    if let Err(e) = request.validate() {
+        counter!("api.candles.validation_errors").increment(1);
+        return Err(ApiError::ValidationError(e.to_string()));
+    }
+
+    // History outlives the registry, so delisted pairs are only normalized
+    let trading_pair = TradingPair::parse(&pair)?.into_string();
+
+    let interval = request
+        .interval
+        .parse::<CandleInterval>()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    // Resolve time range
+    let to = request.to.unwrap_or_else(|| chrono::Utc::now().timestamp());
+    let from = request
+        .from
+        .unwrap_or(to - DEFAULT_CANDLE_COUNT * interval.as_secs());
+    if from >= to {
+        return Err(ApiError::ValidationError("from must be before to".to_string()));
+    }
+    if (to - from) / interval.as_secs() > MAX_CANDLES_PER_REQUEST {
+        return Err(ApiError::ValidationError(format!(
+            "range exceeds {} candles",
+            MAX_CANDLES_PER_REQUEST
+        )));
+    }
+
+    let from = chrono::DateTime::<chrono::Utc>::from_timestamp(from, 0)
+        .ok_or_else(|| ApiError::ValidationError("invalid from timestamp".to_string()))?;
+    let to = chrono::DateTime::<chrono::Utc>::from_timestamp(to, 0)
+        .ok_or_else(|| ApiError::ValidationError("invalid to timestamp".to_string()))?;
+
+    let timer = histogram!("api.candles.fetch_duration");
+    let _timer_guard = timer.start_timer();
+
+    // Stored candles, overlaid with in-memory candles which may carry corrections
+    let mut candles = repository
+        .get_candles(&trading_pair, interval, from, to)
+        .await
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    let in_memory = aggregator
+        .recent_candles(&trading_pair, interval)
+        .into_iter()
+        .chain(aggregator.current_candle(&trading_pair, interval));
+    for candle in in_memory {
+        if candle.open_time < from || candle.open_time >= to {
+            continue;
+        }
+        match candles.iter_mut().find(|c| c.open_time == candle.open_time) {
+            Some(existing) => *existing = candle,
+            None => candles.push(candle),
+        }
+    }
+    candles.sort_by(|a, b| a.open_time.cmp(&b.open_time));
+
+    let candles = fill_gaps(candles, interval, from, to);
+    let gap_filled_count = candles.iter().filter(|c| c.gap_filled).count();
+
+    counter!("api.candles.requests").increment(1);
+    Ok(Json(CandleResponse {
+        trading_pair,
+        interval,
+        candles,
+        gap_filled_count,
+        timestamp: chrono::Utc::now().timestamp(),
+    }))
+}
+
+/// Returns live order book depth for a trading pair with a staleness flag
+#[axum::debug_handler]
+#[tracing::instrument(skip(request, state))]
+pub async fn get_order_book(
+    Path(pair): Path<String>,
+    Query(request): Query<OrderBookRequest>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<OrderBookSnapshot>, ApiError> {
+    if let Err(e) = request.validate() {
+        counter!("api.orderbook.validation_errors").increment(1);
+        return Err(ApiError::ValidationError(e.to_string()));
+    }
+
+    let trading_pair = state.pairs.parse(&pair)?.into_string();
+    let depth = request
+        .depth
+        .unwrap_or(DEFAULT_ORDER_BOOK_DEPTH)
+        .min(MAX_SNAPSHOT_DEPTH);
+
+    let snapshot = state
+        .order_books
+        .as_ref()
+        .and_then(|books| books.snapshot(&trading_pair, request.exchange.as_deref(), depth))
+        .ok_or_else(|| {
+            ApiError::NotFound(format!("no live order book for {}", trading_pair))
+        })?;
+
+    if snapshot.is_stale {
+        counter!("api.orderbook.stale_responses").increment(1);
+    }
+    counter!("api.orderbook.requests").increment(1);
+    Ok(Json(snapshot))
+}
+
+/// Lists recorded deposits and withdrawals for the portfolio wallet
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolio/transfers",
+    tag = "portfolio",
+    params(TransferListRequest),
+    responses(
+        (status = 200, description = "Most recent transfers", body = TransferListResponse),
+        (status = 400, description = "Invalid limit", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(request, portfolio, repository))]
+pub async fn get_transfers(
+    Query(request): Query<TransferListRequest>,
+    Extension(portfolio): Extension<Arc<Portfolio>>,
+    Extension(repository): Extension<Arc<TransferRepository>>,
+) -> Result<Json<TransferListResponse>, ApiError> {
+    if let Err(e) = request.validate() {
+        counter!("api.transfers.validation_errors").increment(1);
+        return Err(ApiError::ValidationError(e.to_string()));
+    }
+
+    let wallet_address = portfolio.wallet_address().to_string();
+    let transfers = repository
+        .get_transfers(&wallet_address, request.limit.unwrap_or(DEFAULT_TRANSFER_LIMIT))
+        .await
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    counter!("api.transfers.requests").increment(1);
+    Ok(Json(TransferListResponse {
+        wallet_address,
+        transfers,
+        timestamp: chrono::Utc::now().timestamp(),
+    }))
+}
+
+/// Lists a position's lifecycle events in order
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn get_position_events(
+    Path(id): Path<uuid::Uuid>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<Vec<PositionEventRecord>>, ApiError> {
+    let history = state.position_history.as_ref().ok_or_else(|| {
+        ApiError::InternalError("position history unavailable".to_string())
+    })?;
+
+    counter!("api.positions.events_requests").increment(1);
+    Ok(Json(history.events(id).await?))
+}
+
+/// Returns raw and flow-adjusted portfolio returns
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolio/performance",
+    tag = "portfolio",
+    responses(
+        (status = 200, description = "Portfolio returns", body = PortfolioPerformanceResponse),
+        (status = 403, description = "Scoped token does not cover the portfolio summary", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(access, portfolio))]
+pub async fn get_portfolio_performance(
+    access: Option<Extension<ScopedAccess>>,
+    Extension(portfolio): Extension<Arc<Portfolio>>,
+) -> Result<Json<PortfolioPerformanceResponse>, ApiError> {
+    require_scope(&access, TokenScope::PortfolioSummary)?;
+    let returns = portfolio.flow_adjusted_returns().await;
+
+    counter!("api.portfolio.performance_requests").increment(1);
+    Ok(Json(PortfolioPerformanceResponse {
+        wallet_address: portfolio.wallet_address().to_string(),
+        raw_return_pct: returns.raw_return_pct,
+        flow_adjusted_return_pct: returns.time_weighted_return_pct,
+        drawdown_pct: returns.drawdown_pct,
+        max_drawdown_pct: returns.max_drawdown_pct,
+        net_flows: returns.net_flows,
+        high_water_mark: portfolio.get_high_water_mark().await,
+        timestamp: chrono::Utc::now().timestamp(),
+    }))
+}
+
+/// Registers an outbound webhook
+#[axum::debug_handler]
+#[tracing::instrument(skip(request, dispatcher))]
+pub async fn create_webhook(
+    Extension(dispatcher): Extension<Arc<WebhookDispatcher>>,
+    Json(request): Json<CreateWebhookRequest>,
+) -> Result<(StatusCode, Json<CreateWebhookResponse>), ApiError> {
+    if let Err(e) = request.validate() {
+        counter!("api.webhooks.validation_errors").increment(1);
+        return Err(ApiError::ValidationError(e.to_string()));
+    }
+
+    let secret = request.secret.unwrap_or_else(generate_nonce);
+    let webhook = dispatcher
+        .register(request.url, secret.clone(), request.event_types)
+        .await?;
+
+    counter!("api.webhooks.created").increment(1);
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateWebhookResponse {
+            webhook,
+            secret,
+            timestamp: chrono::Utc::now().timestamp(),
+        }),
+    ))
+}
+
+/// Lists registered webhooks
+#[axum::debug_handler]
+#[tracing::instrument(skip(dispatcher))]
+pub async fn list_webhooks(
+    Extension(dispatcher): Extension<Arc<WebhookDispatcher>>,
+) -> Json<WebhookListResponse> {
+    Json(WebhookListResponse {
+        webhooks: dispatcher.list(),
+        timestamp: chrono::Utc::now().timestamp(),
+    })
+}
+
+/// Returns a webhook with its audit history
+#[axum::debug_handler]
+#[tracing::instrument(skip(dispatcher))]
+pub async fn get_webhook(
+    Path(id): Path<uuid::Uuid>,
+    Extension(dispatcher): Extension<Arc<WebhookDispatcher>>,
+) -> Result<Json<WebhookDetailResponse>, ApiError> {
+    let webhook = dispatcher
+        .get(id)
+        .ok_or_else(|| ApiError::from(WebhookError::NotFound(id)))?;
+
+    Ok(Json(WebhookDetailResponse {
+        webhook,
+        audit_log: dispatcher.audit_log(id),
+        timestamp: chrono::Utc::now().timestamp(),
+    }))
+}
+
+/// Enables, disables or changes the subscriptions of a webhook
+#[axum::debug_handler]
+#[tracing::instrument(skip(request, dispatcher))]
+pub async fn update_webhook(
+    Path(id): Path<uuid::Uuid>,
+    Extension(dispatcher): Extension<Arc<WebhookDispatcher>>,
+    Json(request): Json<UpdateWebhookRequest>,
+) -> Result<Json<Webhook>, ApiError> {
+    let webhook = dispatcher
+        .update(id, request.active, request.event_types)
+        .await?;
+    Ok(Json(webhook))
+}
+
+/// Removes a webhook
+#[axum::debug_handler]
+#[tracing::instrument(skip(dispatcher))]
+pub async fn delete_webhook(
+    Path(id): Path<uuid::Uuid>,
+    Extension(dispatcher): Extension<Arc<WebhookDispatcher>>,
+) -> Result<StatusCode, ApiError> {
+    dispatcher.remove(id).await?;
+    counter!("api.webhooks.deleted").increment(1);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Submits a strategy parameter optimization job
+#[axum::debug_handler]
+#[tracing::instrument(skip(request, optimizer))]
+pub async fn submit_optimization(
+    Extension(optimizer): Extension<Arc<OptimizerService>>,
+    Json(request): Json<OptimizationRequest>,
+) -> Result<(StatusCode, Json<JobProgress>), ApiError> {
+    let progress = optimizer.submit(request).map_err(|e| {
+        counter!("api.optimizations.rejected").increment(1);
+        ApiError::from(e)
+    })?;
+
+    counter!("api.optimizations.submitted").increment(1);
+    Ok((StatusCode::ACCEPTED, Json(progress)))
+}
+
+/// Returns optimization job status and progress
+#[axum::debug_handler]
+#[tracing::instrument(skip(optimizer))]
+pub async fn get_optimization(
+    Path(id): Path<uuid::Uuid>,
+    Extension(optimizer): Extension<Arc<OptimizerService>>,
+) -> Result<Json<JobProgress>, ApiError> {
+    Ok(Json(optimizer.progress(id)?))
+}
+
+/// Returns ranked optimization runs; empty until the job finishes
+#[axum::debug_handler]
+#[tracing::instrument(skip(optimizer))]
+pub async fn get_optimization_results(
+    Path(id): Path<uuid::Uuid>,
+    Extension(optimizer): Extension<Arc<OptimizerService>>,
+) -> Result<Json<OptimizationResultsResponse>, ApiError> {
+    let (job, runs) = optimizer.results(id)?;
+    Ok(Json(OptimizationResultsResponse {
+        job,
+        runs,
+        timestamp: chrono::Utc::now().timestamp(),
+    }))
+}
+
+/// Cancels an optimization job, keeping the runs already completed
+#[axum::debug_handler]
+#[tracing::instrument(skip(optimizer))]
+pub async fn cancel_optimization(
+    Path(id): Path<uuid::Uuid>,
+    Extension(optimizer): Extension<Arc<OptimizerService>>,
+) -> Result<Json<JobProgress>, ApiError> {
+    let progress = optimizer.cancel(id)?;
+    counter!("api.optimizations.cancelled").increment(1);
+    Ok(Json(progress))
+}
+
+/// Resumes a strategy paused by supervision; paused strategies never resume on their own
+#[utoipa::path(
+    post,
+    path = "/api/v1/strategies/{id}/resume",
+    tag = "strategies",
+    params(("id" = String, Path, description = "Strategy ID")),
+    responses(
+        (status = 200, description = "Strategy health after resuming", body = StrategyHealthSnapshot),
+        (status = 400, description = "Strategy is not paused", body = ErrorResponse),
+        (status = 404, description = "Unknown strategy", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn resume_strategy(
+    Path(id): Path<String>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<StrategyHealthSnapshot>, ApiError> {
+    let supervisor = state.supervisor.as_ref().ok_or_else(|| {
+        ApiError::InternalError("strategy supervision unavailable".to_string())
+    })?;
+
+    let health = supervisor.resume(&id).await?;
+    counter!("api.strategies.resumed").increment(1);
+    Ok(Json(health))
+}
+
+fn strategy_versions(state: &AppState) -> Result<&Arc<StrategyVersionService>, ApiError> {
+    state.versions.as_ref().ok_or_else(|| {
+        ApiError::InternalError("strategy versioning unavailable".to_string())
+    })
+}
+
+/// Lists a strategy's parameter versions with the metrics each produced
+#[utoipa::path(
+    get,
+    path = "/api/v1/strategies/{id}/versions",
+    tag = "strategies",
+    params(("id" = String, Path, description = "Strategy ID")),
+    responses(
+        (status = 200, description = "Versions, per-version metrics and latest A/B test", body = VersionHistory),
+        (status = 404, description = "Unknown strategy", body = ErrorResponse),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn get_strategy_versions(
+    Path(id): Path<String>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<VersionHistory>, ApiError> {
+    let history = strategy_versions(&state)?.history(&id).await?;
+    counter!("api.strategies.versions_requests").increment(1);
+    Ok(Json(history))
+}
+
+/// Records new parameters as the strategy's next version and makes it live
+#[utoipa::path(
+    put,
+    path = "/api/v1/strategies/{id}/parameters",
+    tag = "strategies",
+    params(("id" = String, Path, description = "Strategy ID")),
+    request_body = UpdateParametersRequest,
+    responses(
+        (status = 200, description = "Active version; unchanged parameters return the current one", body = StrategyVersion),
+        (status = 400, description = "Invalid parameters or an A/B test is running", body = ErrorResponse),
+        (status = 404, description = "Unknown strategy", body = ErrorResponse),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(request, claims, state))]
+pub async fn update_strategy_parameters(
+    Path(id): Path<String>,
+    Extension(claims): Extension<Claims>,
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<UpdateParametersRequest>,
+) -> Result<Json<StrategyVersion>, ApiError> {
+    let version = strategy_versions(&state)?
+        .update_parameters(&id, request.parameters, &claims.sub)
+        .await?;
+    counter!("api.strategies.parameters_updated").increment(1);
+    Ok(Json(version))
+}
+
+/// Makes an earlier parameter version live again, cancelling any running A/B test
+#[utoipa::path(
+    post,
+    path = "/api/v1/strategies/{id}/rollback",
+    tag = "strategies",
+    params(("id" = String, Path, description = "Strategy ID")),
+    request_body = RollbackRequest,
+    responses(
+        (status = 200, description = "Re-activated version", body = StrategyVersion),
+        (status = 404, description = "Unknown strategy or version", body = ErrorResponse),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(claims, state))]
+pub async fn rollback_strategy(
+    Path(id): Path<String>,
+    Extension(claims): Extension<Claims>,
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<RollbackRequest>,
+) -> Result<Json<StrategyVersion>, ApiError> {
+    let version = strategy_versions(&state)?.rollback(&id, request.version).await?;
+    info!(strategy_id = %id, version = request.version, actor = %claims.sub, "Strategy rollback requested");
+    counter!("api.strategies.rollbacks").increment(1);
+    Ok(Json(version))
+}
+
+/// Trials challenger parameters against the active version on split position size
+#[utoipa::path(
+    post,
+    path = "/api/v1/strategies/{id}/ab-tests",
+    tag = "strategies",
+    params(("id" = String, Path, description = "Strategy ID")),
+    request_body = StartAbTestRequest,
+    responses(
+        (status = 201, description = "Started A/B test", body = AbTest),
+        (status = 400, description = "Invalid parameters or test, or a test is already running", body = ErrorResponse),
+        (status = 404, description = "Unknown strategy", body = ErrorResponse),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(request, claims, state))]
+pub async fn start_ab_test(
+    Path(id): Path<String>,
+    Extension(claims): Extension<Claims>,
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<StartAbTestRequest>,
+) -> Result<(StatusCode, Json<AbTest>), ApiError> {
+    let test = strategy_versions(&state)?
+        .start_ab_test(&id, request.parameters, &claims.sub, request.config)
+        .await?;
+    counter!("api.strategies.ab_tests_started").increment(1);
+    Ok((StatusCode::CREATED, Json(test)))
+}
+
+/// Ranks every strategy by its current performance metrics
+#[utoipa::path(
+    get,
+    path = "/api/v1/strategies/performance",
+    tag = "strategies",
+    params(LeaderboardRequest),
+    responses(
+        (status = 200, description = "Leaderboard entries in the requested order; scoped tokens see only their strategies", body = [LeaderboardEntry]),
+        (status = 400, description = "Unknown column or sort order", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(access, state))]
+pub async fn get_strategy_performance(
+    access: Option<Extension<ScopedAccess>>,
+    Query(request): Query<LeaderboardRequest>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<Vec<LeaderboardEntry>>, ApiError> {
+    let column = request
+        .sort_by
+        .as_deref()
+        .map(str::parse::<LeaderboardColumn>)
+        .transpose()?
+        .unwrap_or(LeaderboardColumn::PerformanceScore);
+    let order = request
+        .order
+        .as_deref()
+        .map(str::parse::<SortOrder>)
+        .transpose()?
+        .unwrap_or(SortOrder::Desc);
+
+    let performance = state.performance.as_ref().ok_or_else(|| {
+        ApiError::InternalError("strategy performance unavailable".to_string())
+    })?;
+    let mut entries = performance.leaderboard(column, order, request.include_deleted).await?;
+    if let Some(Extension(access)) = &access {
+        entries.retain(|entry| access.allows(&TokenScope::strategy(&entry.strategy_id)));
+    }
+
+    counter!("api.strategies.performance").increment(1);
+    Ok(Json(entries))
+}
+
+/// Returns a strategy's cumulative P&L series from its materialized equity buckets
+#[utoipa::path(
+    get,
+    path = "/api/v1/strategies/{id}/equity",
+    tag = "strategies",
+    params(("id" = String, Path, description = "Strategy ID"), EquityRequest),
+    responses(
+        (status = 200, description = "Equity buckets in ascending time order", body = EquityResponse),
+        (status = 400, description = "Unsupported granularity", body = ErrorResponse),
+        (status = 403, description = "Scoped token does not cover this strategy", body = ErrorResponse),
+        (status = 404, description = "Unknown strategy", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(access, state))]
+pub async fn get_strategy_equity(
+    access: Option<Extension<ScopedAccess>>,
+    Path(id): Path<String>,
+    Query(request): Query<EquityRequest>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<EquityResponse>, ApiError> {
+    require_scope(&access, TokenScope::strategy(&id))?;
+    let granularity = request
+        .granularity
+        .as_deref()
+        .unwrap_or("1h")
+        .parse::<CandleInterval>()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let performance = state.performance.as_ref().ok_or_else(|| {
+        ApiError::InternalError("strategy performance unavailable".to_string())
+    })?;
+    let points = performance.equity(&id, granularity).await?;
+
+    counter!("api.strategies.equity").increment(1);
+    Ok(Json(EquityResponse {
+        strategy_id: id,
+        granularity,
+        points,
+    }))
+}
+
+/// Registers a new strategy in the inactive state and starts supervising it
+#[utoipa::path(
+    post,
+    path = "/api/v1/strategies",
+    tag = "strategies",
+    request_body = CreateStrategyRequest,
+    responses(
+        (status = 201, description = "Registered strategy", body = StrategySnapshot),
+        (status = 400, description = "Invalid parameters or strategy ID already registered", body = ErrorResponse),
+        (status = 422, description = "Unrecognized or unsupported trading pair", body = ErrorResponse),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(request, state))]
+pub async fn create_strategy(
+    claims: Option<Extension<Claims>>,
+    Extension(state): Extension<Arc<AppState>>,
+    Json(mut request): Json<CreateStrategyRequest>,
+) -> Result<(StatusCode, Json<StrategySnapshot>), ApiError> {
+    if let Err(e) = request.validate() {
+        counter!("api.strategies.validation_errors").increment(1);
+        return Err(ApiError::ValidationError(e.to_string()));
+    }
+    if is_variant(&request.strategy_id) {
+        return Err(ApiError::ValidationError(
+            "strategy ID must not end in a version suffix".to_string(),
+        ));
+    }
+    for pair in request.trading_pairs.iter_mut() {
+        *pair = state.pairs.parse(pair)?.into_string();
+    }
+
+    let strategy = Strategy::new(request.strategy_type, request.parameters, request.trading_pairs)
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    let supervisor = state.supervisor.as_ref().ok_or_else(|| {
+        ApiError::InternalError("strategy registration unavailable".to_string())
+    })?;
+    let snapshot = supervisor.add_strategy(&request.strategy_id, strategy).await?;
+    if let Some(versions) = &state.versions {
+        let created_by = claims.map_or_else(|| SYSTEM_AUTHOR.to_string(), |Extension(claims)| claims.sub);
+        versions.record_initial(&request.strategy_id, &created_by).await?;
+    }
+
+    counter!("api.strategies.created").increment(1);
+    Ok((StatusCode::CREATED, Json(snapshot)))
+}
+
+fn archive(state: &AppState) -> Result<&Arc<StrategyArchive>, ApiError> {
+    state
+        .archive
+        .as_ref()
+        .ok_or_else(|| ApiError::InternalError("strategy archive unavailable".to_string()))
+}
+
+/// Soft-deletes a strategy, flattening or transferring its open positions and keeping its history
+#[utoipa::path(
+    delete,
+    path = "/api/v1/strategies/{id}",
+    tag = "strategies",
+    params(("id" = String, Path, description = "Strategy ID"), DeleteStrategyRequest),
+    responses(
+        (status = 200, description = "Deleted strategy and the positions it held", body = DeletionOutcome),
+        (status = 400, description = "Strategy already deleted or invalid position policy", body = ErrorResponse),
+        (status = 404, description = "Unknown strategy", body = ErrorResponse),
+        (status = 500, description = "Position could not be flattened or backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn delete_strategy(
+    Path(id): Path<String>,
+    Query(request): Query<DeleteStrategyRequest>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<DeletionOutcome>, ApiError> {
+    let outcome = archive(&state)?
+        .delete(&id, request.policy, chrono::Utc::now())
+        .await?;
+    counter!("api.strategies.deleted").increment(1);
+    Ok(Json(outcome))
+}
+
+/// Lists a strategy's most recent realized trades, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/strategies/{id}/trades",
+    tag = "trades",
+    params(("id" = String, Path, description = "Strategy ID"), TradeListRequest),
+    responses(
+        (status = 200, description = "Most recent trades", body = TradeListResponse),
+        (status = 400, description = "Invalid limit", body = ErrorResponse),
+        (status = 403, description = "Scoped token does not cover this strategy", body = ErrorResponse),
+        (status = 404, description = "Unknown strategy", body = ErrorResponse),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(access, request, state))]
+pub async fn list_strategy_trades(
+    access: Option<Extension<ScopedAccess>>,
+    Path(id): Path<String>,
+    Query(request): Query<TradeListRequest>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<TradeListResponse>, ApiError> {
+    require_scope(&access, TokenScope::strategy(&id))?;
+    if let Err(e) = request.validate() {
+        counter!("api.trades.validation_errors").increment(1);
+        return Err(ApiError::ValidationError(e.to_string()));
+    }
+
+    let performance = state.performance.as_ref().ok_or_else(|| {
+        ApiError::InternalError("strategy performance unavailable".to_string())
+    })?;
+    let trades = performance
+        .trades(&id, request.limit.unwrap_or(DEFAULT_TRADE_LIMIT))
+        .await?;
+
+    counter!("api.trades.list_requests").increment(1);
+    Ok(Json(TradeListResponse {
+        strategy_id: id,
+        trades,
+        timestamp: chrono::Utc::now().timestamp(),
+    }))
+}
+
+/// Lists current quality scores and promotion status for every market data source
+#[utoipa::path(
+    get,
+    path = "/api/v1/monitoring/data-quality",
+    tag = "monitoring",
+    responses(
+        (status = 200, description = "Score per source", body = [SourceScore]),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn get_data_quality(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<Vec<SourceScore>>, ApiError> {
+    let monitor = state.data_quality.as_ref().ok_or_else(|| {
+        ApiError::InternalError("data quality monitoring unavailable".to_string())
+    })?;
+
+    counter!("api.monitoring.data_quality").increment(1);
+    Ok(Json(monitor.scores()))
+}
+
+/// Lists market data gaps detected recently and how far their backfill has progressed
+#[utoipa::path(
+    get,
+    path = "/api/v1/monitoring/data-gaps",
+    tag = "monitoring",
+    responses(
+        (status = 200, description = "Recent gaps", body = [DataGap]),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn get_data_gaps(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<Vec<DataGap>>, ApiError> {
+    let monitor = state.data_gaps.as_ref().ok_or_else(|| {
+        ApiError::InternalError("data gap monitoring unavailable".to_string())
+    })?;
+
+    counter!("api.monitoring.data_gaps").increment(1);
+    Ok(Json(monitor.status(chrono::Utc::now()).await?))
+}
+
+fn retention(state: &AppState) -> Result<&Arc<RetentionManager>, ApiError> {
+    state
+        .retention
+        .as_ref()
+        .ok_or_else(|| ApiError::InternalError("retention management unavailable".to_string()))
+}
+
+/// Lists the market data retention in force per pair and where it comes from
+#[utoipa::path(
+    get,
+    path = "/api/v1/monitoring/retention",
+    tag = "monitoring",
+    responses(
+        (status = 200, description = "Effective retention per pair", body = [EffectiveRetention]),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn get_retention(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<Vec<EffectiveRetention>>, ApiError> {
+    let effective = retention(&state)?.effective(&state.pairs.pairs());
+    counter!("api.monitoring.retention").increment(1);
+    Ok(Json(effective))
+}
+
+fn ingestor(state: &AppState) -> Result<&Arc<MarketDataIngestor>, ApiError> {
+    state
+        .ingest
+        .as_ref()
+        .ok_or_else(|| ApiError::InternalError("market data ingestion unavailable".to_string()))
+}
+
+/// Streams vendor market data in as NDJSON or CSV, validating each row, and summarizes what
+/// was stored, rejected by line number, and skipped as already ingested
+#[utoipa::path(
+    post,
+    path = "/api/v1/ingest/market-data",
+    tag = "ingest",
+    params(IngestRequest),
+    request_body(content = String, content_type = "application/x-ndjson", description = "One row per line with trading_pair, exchange, price, volume and an RFC 3339 timestamp; text/csv with a header line is also accepted"),
+    responses(
+        (status = 200, description = "Ingestion summary", body = IngestSummary),
+        (status = 400, description = "Invalid vendor, content type or CSV header", body = ErrorResponse),
+        (status = 413, description = "Body over the size or row limit", body = ErrorResponse),
+        (status = 429, description = "Too many ingestions in progress", body = ErrorResponse),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state, headers, body))]
+pub async fn ingest_market_data(
+    Query(request): Query<IngestRequest>,
+    Extension(state): Extension<Arc<AppState>>,
+    headers: HeaderMap,
+    body: BodyStream,
+) -> Result<Json<IngestSummary>, ApiError> {
+    let ingestor = ingestor(&state)?;
+    let content_type = headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let format = IngestFormat::from_content_type(content_type)?;
+
+    // A declared length over the limit is refused before reading anything
+    let limit = ingestor.config().max_bytes;
+    let declared_length = headers
+        .get(axum::http::header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    if declared_length.map_or(false, |length| length > limit as u64) {
+        return Err(IngestError::TooLarge { limit }.into());
+    }
+
+    let summary = ingestor.ingest(&request.vendor, format, body).await?;
+    counter!("api.ingest.market_data").increment(1);
+    Ok(Json(summary))
+}
+
+fn quarantine(state: &AppState) -> Result<&Arc<QuarantineList>, ApiError> {
+    state
+        .quarantine
+        .as_ref()
+        .ok_or_else(|| ApiError::InternalError("pair quarantine unavailable".to_string()))
+}
+
+/// Lists quarantined pairs
+#[utoipa::path(
+    get,
+    path = "/api/v1/risk/quarantine",
+    tag = "risk",
+    responses(
+        (status = 200, description = "Pairs currently quarantined", body = [QuarantineEntry]),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn list_quarantined_pairs(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<Vec<QuarantineEntry>>, ApiError> {
+    Ok(Json(quarantine(&state)?.entries()))
+}
+
+/// Quarantines a pair, flattening its open position in flatten-and-block mode
+#[utoipa::path(
+    post,
+    path = "/api/v1/risk/quarantine",
+    tag = "risk",
+    request_body = QuarantineRequest,
+    responses(
+        (status = 201, description = "Quarantined pair and the position closed", body = QuarantineOutcome),
+        (status = 400, description = "Invalid pair or pair already quarantined", body = ErrorResponse),
+        (status = 500, description = "Position could not be flattened or backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn quarantine_pair(
+    claims: Option<Extension<Claims>>,
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<QuarantineRequest>,
+) -> Result<(StatusCode, Json<QuarantineOutcome>), ApiError> {
+    let quarantined_by = claims.map_or_else(|| SYSTEM_AUTHOR.to_string(), |Extension(claims)| claims.sub);
+    let outcome = quarantine(&state)?
+        .quarantine(request, &quarantined_by, chrono::Utc::now())
+        .await?;
+    counter!("api.risk.pairs_quarantined").increment(1);
+    Ok((StatusCode::CREATED, Json(outcome)))
+}
+
+/// Revalues the portfolio under built-in or custom stress scenarios; nothing is traded
+#[utoipa::path(
+    post,
+    path = "/api/v1/risk/stress",
+    tag = "risk",
+    request_body = StressRequest,
+    responses(
+        (status = 200, description = "Stressed value, drawdown, margin calls and limit breaches per scenario", body = StressRun),
+        (status = 400, description = "Unknown or invalid scenario", body = ErrorResponse),
+        (status = 500, description = "Valuation or audit persistence failed, or backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn run_stress_test(
+    claims: Option<Extension<Claims>>,
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<StressRequest>,
+) -> Result<Json<StressRun>, ApiError> {
+    let stress = state
+        .stress
+        .as_ref()
+        .ok_or_else(|| ApiError::InternalError("stress testing unavailable".to_string()))?;
+    let scenarios = request.resolve()?;
+    let requested_by = claims.map_or_else(|| SYSTEM_AUTHOR.to_string(), |Extension(claims)| claims.sub);
+    let run = stress.run(&scenarios, &requested_by, chrono::Utc::now()).await?;
+
+    counter!("api.risk.stress_runs").increment(1);
+    Ok(Json(run))
+}
+
+/// Reports the build, uptime, feature flags and live activity of this instance
+#[utoipa::path(
+    get,
+    path = "/api/v1/system/info",
+    tag = "system",
+    responses(
+        (status = 200, description = "Build and runtime identity", body = SystemInfo),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn get_system_info(Extension(state): Extension<Arc<AppState>>) -> Json<SystemInfo> {
+    counter!("api.system.info").increment(1);
+    let now = chrono::Utc::now();
+    let info = SystemInfo::collect(&state.config.environment, &state.activity, now).await;
+    let upcoming = state
+        .events
+        .as_ref()
+        .map(|events| events.upcoming(now, UPCOMING_HORIZON))
+        .unwrap_or_default();
+    Json(info.with_upcoming_events(upcoming))
+}
+
+fn events(state: &AppState) -> Result<&Arc<EventCalendar>, ApiError> {
+    state
+        .events
+        .as_ref()
+        .ok_or_else(|| ApiError::InternalError("event calendar unavailable".to_string()))
+}
+
+/// Lists scheduled economic events in a time range
+#[utoipa::path(
+    get,
+    path = "/api/v1/events",
+    tag = "events",
+    params(EventRangeRequest),
+    responses(
+        (status = 200, description = "Events in time order", body = [EconomicEvent]),
+        (status = 400, description = "Range ends before it starts", body = ErrorResponse),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn get_events(
+    Query(request): Query<EventRangeRequest>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<Vec<EconomicEvent>>, ApiError> {
+    Ok(Json(events(&state)?.between(request.from, request.to).await?))
+}
+
+/// Compares realized execution quality across venues for a trading pair
+#[utoipa::path(
+    get,
+    path = "/api/v1/analytics/execution",
+    tag = "analytics",
+    params(ExecutionStatsRequest),
+    responses(
+        (status = 200, description = "Statistics per venue", body = ExecutionStatsResponse),
+        (status = 400, description = "Invalid window", body = ErrorResponse),
+        (status = 422, description = "Unrecognized or unsupported trading pair", body = ErrorResponse),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn get_execution_stats(
+    Query(request): Query<ExecutionStatsRequest>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<ExecutionStatsResponse>, ApiError> {
+    let trading_pair = state.pairs.parse(&request.pair)?.into_string();
+    let window = request
+        .window
+        .as_deref()
+        .map(str::parse::<StatsWindow>)
+        .transpose()?
+        .unwrap_or(StatsWindow::Week);
+
+    let execution_stats = state.execution_stats.as_ref().ok_or_else(|| {
+        ApiError::InternalError("execution statistics unavailable".to_string())
+    })?;
+    let venues = execution_stats.stats(&trading_pair, window).await?;
+
+    counter!("api.analytics.execution").increment(1);
+    Ok(Json(ExecutionStatsResponse {
+        trading_pair,
+        window,
+        venues,
+    }))
+}
+
+/// Serves daily P&L rollups grouped by attribution tags, with a balance reconciliation per wallet
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/attribution",
+    tag = "reports",
+    params(AttributionRequest),
+    responses(
+        (status = 200, description = "Attribution rows and reconciliation", body = AttributionReport),
+        (status = 400, description = "Invalid range or grouping", body = ErrorResponse),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn get_attribution_report(
+    Query(request): Query<AttributionRequest>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<AttributionReport>, ApiError> {
+    let group_by = match request.group_by.as_deref() {
+        Some(raw) => AttributionDimension::parse_list(raw)?,
+        None => DEFAULT_GROUP_BY.to_vec(),
+    };
+
+    let attribution = state.attribution.as_ref().ok_or_else(|| {
+        ApiError::InternalError("attribution reporting unavailable".to_string())
+    })?;
+    let report = attribution.report(request.from, request.to, &group_by).await?;
+
+    counter!("api.reports.attribution").increment(1);
+    Ok(Json(report))
+}
+
+/// Serves the stored end-of-day performance report of one day
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/daily/{date}",
+    tag = "reports",
+    params(("date" = String, Path, description = "Report day (YYYY-MM-DD), starting at the daily loss reset time")),
+    responses(
+        (status = 200, description = "Daily performance report", body = DailyReport),
+        (status = 404, description = "No report generated for the day", body = ErrorResponse),
+        (status = 500, description = "Backing service unavailable", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn get_daily_report(
+    Path(date): Path<chrono::NaiveDate>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<DailyReport>, ApiError> {
+    let reports = state.reports.as_ref().ok_or_else(|| {
+        ApiError::InternalError("daily reports unavailable".to_string())
+    })?;
+    let report = reports.report(date).await?;
+
+    counter!("api.reports.daily").increment(1);
+    Ok(Json(report))
+}
+
+/// Number of faults removed by a clear request
+#[cfg(feature = "fault-injection")]
+#[derive(Debug, Serialize)]
+pub struct ClearedFaults {
+    pub cleared: usize,
+}
+
+/// Lists injected faults still in effect
+#[cfg(feature = "fault-injection")]
+#[axum::debug_handler]
+#[tracing::instrument]
+pub async fn list_faults() -> Result<Json<Vec<ActiveFault>>, ApiError> {
+    if !fault_injection::runtime_control_allowed() {
+        return Err(FaultError::Disabled.into());
+    }
+    Ok(Json(fault_injection::registry().active(chrono::Utc::now())))
+}
+
+/// Injects a fault for resilience testing; refused in production
+#[cfg(feature = "fault-injection")]
+#[axum::debug_handler]
+#[tracing::instrument]
+pub async fn inject_fault(Json(spec): Json<FaultSpec>) -> Result<(StatusCode, Json<ActiveFault>), ApiError> {
+    let fault = fault_injection::inject(spec)?;
+    counter!("api.faults.injected").increment(1);
+    Ok((StatusCode::CREATED, Json(fault)))
+}
+
+/// Clears every injected fault
+#[cfg(feature = "fault-injection")]
+#[axum::debug_handler]
+#[tracing::instrument]
+pub async fn clear_faults() -> Result<Json<ClearedFaults>, ApiError> {
+    if !fault_injection::runtime_control_allowed() {
+        return Err(FaultError::Disabled.into());
+    }
+    let cleared = fault_injection::registry().clear();
+    Ok(Json(ClearedFaults { cleared }))
+}
+
+/// Latest data key rotation, if any has run since startup
+#[derive(Debug, Serialize)]
+pub struct KeyRotationProgress {
+    pub running: bool,
+    pub run: Option<RotationRun>,
+}
+
+/// Reports progress of the current or last data key rotation
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn get_key_rotation(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<KeyRotationProgress>, ApiError> {
+    let rotation = key_rotation(&state)?;
+    Ok(Json(KeyRotationProgress {
+        running: rotation.is_running(),
+        run: rotation.progress(),
+    }))
+}
+
+/// Starts a data key rotation in the background, resuming an interrupted one
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn rotate_keys(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<(StatusCode, Json<KeyRotationProgress>), ApiError> {
+    let rotation = key_rotation(&state)?.clone();
+    if rotation.is_running() {
+        return Err(KeyRotationError::InProgress.into());
+    }
+
+    let task = rotation.clone();
+    tokio::spawn(async move {
+        if let Err(e) = task.rotate().await {
+            warn!(error = %e, "Key rotation failed");
+        }
+    });
+    counter!("api.admin.key_rotations").increment(1);
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(KeyRotationProgress {
+            running: true,
+            run: rotation.progress(),
+        }),
+    ))
+}
+
+fn key_rotation(state: &AppState) -> Result<&Arc<KeyRotationService>, ApiError> {
+    state.key_rotation.as_ref().ok_or_else(|| {
+        ApiError::InternalError("key rotation unavailable".to_string())
+    })
+}
+
+/// Log filter directives, e.g. `solana_trading_bot::data_collector=debug`
+#[derive(Debug, Deserialize)]
+pub struct LogLevelRequest {
+    pub directives: String,
+}
+
+/// Log filter in effect after an update
+#[derive(Debug, Serialize)]
+pub struct LogLevelResponse {
+    pub filter: String,
+}
+
+/// Changes log levels at runtime without restarting the bot
+#[axum::debug_handler]
+#[tracing::instrument]
+pub async fn set_log_level(
+    Json(request): Json<LogLevelRequest>,
+) -> Result<Json<LogLevelResponse>, ApiError> {
+    let handle = logger::log_handle().ok_or(LoggerError::NotInitialized)?;
+    let filter = handle.set_directives(&request.directives)?;
+    counter!("api.admin.log_level").increment(1);
+    Ok(Json(LogLevelResponse { filter }))
+}
+
+/// Restarts one market data collector, leaving the other collectors and execution running
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn restart_collector(
+    Path(dex): Path<String>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<CollectorRestart>, ApiError> {
+    let collectors = state.collectors.as_ref().ok_or_else(|| {
+        ApiError::InternalError("collector lifecycle unavailable".to_string())
+    })?;
+    let dex = dex
+        .parse()
+        .map_err(|e: crate::data_collector::CollectorError| ApiError::ValidationError(e.to_string()))?;
+
+    let restart = collectors.restart(&dex, RestartTrigger::Manual).await?;
+    counter!("api.admin.collector_restarts").increment(1);
+    Ok(Json(restart))
+}
+
+fn maintenance(state: &AppState) -> Result<&Arc<MaintenanceScheduler>, ApiError> {
+    state
+        .maintenance
+        .as_ref()
+        .ok_or_else(|| ApiError::InternalError("maintenance scheduling unavailable".to_string()))
+}
+
+/// Schedules a maintenance window, reporting daily loss or circuit breaker conflicts
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn schedule_maintenance(
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<MaintenanceRequest>,
+) -> Result<(StatusCode, Json<ScheduledWindow>), ApiError> {
+    let scheduled = maintenance(&state)?.schedule(request, chrono::Utc::now()).await?;
+    counter!("api.admin.maintenance_scheduled").increment(1);
+    Ok((StatusCode::CREATED, Json(scheduled)))
+}
+
+/// Lists scheduled windows and the progress of the current wind-down
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn get_maintenance(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<MaintenanceStatus>, ApiError> {
+    Ok(Json(maintenance(&state)?.status(chrono::Utc::now()).await))
+}
+
+/// Restores a deleted strategy within the retention window; it comes back inactive
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn restore_strategy(
+    Path(id): Path<String>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<StrategySnapshot>, ApiError> {
+    let strategy = archive(&state)?.restore(&id, chrono::Utc::now()).await?;
+    counter!("api.admin.strategies_restored").increment(1);
+    Ok(Json(strategy))
+}
+
+/// Pauses a strategy until it is resumed through the resume endpoint
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn pause_strategy(
+    Path(id): Path<String>,
+    claims: Option<Extension<Claims>>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<StrategyHealthSnapshot>, ApiError> {
+    let supervisor = state.supervisor.as_ref().ok_or_else(|| {
+        ApiError::InternalError("strategy supervision unavailable".to_string())
+    })?;
+
+    let paused_by = claims.map_or_else(|| SYSTEM_AUTHOR.to_string(), |Extension(claims)| claims.sub);
+    let health = supervisor.pause_manually(&id, &paused_by).await?;
+    counter!("api.admin.strategies_paused").increment(1);
+    Ok(Json(health))
+}
+
+fn positions(state: &AppState) -> Result<&Arc<dyn LivePositions>, ApiError> {
+    state
+        .positions
+        .as_ref()
+        .ok_or_else(|| ApiError::InternalError("live positions unavailable".to_string()))
+}
+
+/// Lists open positions as execution currently holds them
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn list_positions(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<Vec<PositionState>>, ApiError> {
+    Ok(Json(positions(&state)?.position_states().await))
+}
+
+/// Lifts a pair quarantine; the reason is kept with the release history
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn release_quarantine(
+    Path(pair): Path<String>,
+    claims: Option<Extension<Claims>>,
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<ReleaseRequest>,
+) -> Result<Json<QuarantineRelease>, ApiError> {
+    let released_by = claims.map_or_else(|| SYSTEM_AUTHOR.to_string(), |Extension(claims)| claims.sub);
+    let release = quarantine(&state)?
+        .release(&pair, &released_by, &request.reason, chrono::Utc::now())
+        .await?;
+    counter!("api.admin.quarantines_released").increment(1);
+    Ok(Json(release))
+}
+
+/// Exchange an override applies to; unset for a pair-wide override
+#[derive(Debug, Deserialize)]
+pub struct RetentionOverrideQuery {
+    pub exchange: Option<String>,
+}
+
+/// Lists retention overrides
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn list_retention_overrides(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<Vec<RetentionOverride>>, ApiError> {
+    Ok(Json(retention(&state)?.list()))
+}
+
+/// Sets a pair's retention; the next retention run applies it
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn set_retention_override(
+    claims: Option<Extension<Claims>>,
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<RetentionOverrideRequest>,
+) -> Result<Json<RetentionOverride>, ApiError> {
+    let updated_by = claims.map_or_else(|| SYSTEM_AUTHOR.to_string(), |Extension(claims)| claims.sub);
+    let entry = retention(&state)?.set(request, &updated_by, chrono::Utc::now()).await?;
+    counter!("api.admin.retention_overrides_set").increment(1);
+    Ok(Json(entry))
+}
+
+/// Removes a pair's retention override, falling back to the next most specific rule
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn remove_retention_override(
+    Path(pair): Path<String>,
+    Query(query): Query<RetentionOverrideQuery>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<RetentionOverride>, ApiError> {
+    let entry = retention(&state)?.remove(&pair, query.exchange.as_deref()).await?;
+    counter!("api.admin.retention_overrides_removed").increment(1);
+    Ok(Json(entry))
+}
+
+fn token_accounts(state: &AppState) -> Result<&Arc<TokenAccountManager>, ApiError> {
+    state
+        .token_accounts
+        .as_ref()
+        .ok_or_else(|| ApiError::InternalError("token account management unavailable".to_string()))
+}
+
+/// Closes the empty token accounts of a quarantined or abandoned pair to reclaim their rent
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn close_token_accounts(
+    Path(pair): Path<String>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<AccountClosure>, ApiError> {
+    let trading_pair = TradingPair::parse(&pair)?;
+    let quarantined = state
+        .quarantine
+        .as_ref()
+        .map_or(false, |quarantine| quarantine.mode(trading_pair.as_str()).is_some());
+    if state.pairs.contains(&trading_pair) && !quarantined {
+        return Err(ApiError::ValidationError(format!(
+            "{} is still trading; quarantine it before closing its token accounts",
+            trading_pair.as_str()
+        )));
+    }
+
+    let closure = token_accounts(&state)?.close_pair(trading_pair.as_str()).await?;
+    counter!("api.admin.token_accounts_closed").increment(1);
+    Ok(Json(closure))
+}
+
+fn degradation(state: &AppState) -> Result<&Arc<DegradationSizer>, ApiError> {
+    state
+        .degradation
+        .as_ref()
+        .ok_or_else(|| ApiError::InternalError("degraded-mode sizing unavailable".to_string()))
+}
+
+/// Whether a strategy trades at full size while the system is degraded
+#[derive(Debug, Deserialize)]
+pub struct SizingOptOutRequest {
+    pub opt_out: bool,
+}
+
+/// Lists strategies exempted from degraded-mode sizing
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn list_sizing_opt_outs(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<Vec<SizingOptOut>>, ApiError> {
+    Ok(Json(degradation(&state)?.opt_outs()))
+}
+
+/// Exempts a strategy from degraded-mode sizing or returns it to the curve
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn set_sizing_opt_out(
+    Path(id): Path<String>,
+    claims: Option<Extension<Claims>>,
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<SizingOptOutRequest>,
+) -> Result<Json<Option<SizingOptOut>>, ApiError> {
+    let set_by = claims.map_or_else(|| SYSTEM_AUTHOR.to_string(), |Extension(claims)| claims.sub);
+    let opt_out = degradation(&state)?.set_opt_out(&id, request.opt_out, &set_by, chrono::Utc::now())?;
+    counter!("api.admin.sizing_opt_outs_set").increment(1);
+    Ok(Json(opt_out))
+}
+
+/// Cancels a window; trading resumes if it was already winding down
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn cancel_maintenance(
+    Path(id): Path<uuid::Uuid>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<MaintenanceWindow>, ApiError> {
+    let window = maintenance(&state)?.cancel(id, chrono::Utc::now()).await?;
+    counter!("api.admin.maintenance_cancelled").increment(1);
+    Ok(Json(window))
+}
+
+/// Adds an economic event to the calendar
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn add_event(
+    claims: Option<Extension<Claims>>,
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<EventRequest>,
+) -> Result<(StatusCode, Json<EconomicEvent>), ApiError> {
+    let created_by = claims.map_or_else(|| SYSTEM_AUTHOR.to_string(), |Extension(claims)| claims.sub);
+    let event = events(&state)?.add(request, &created_by, chrono::Utc::now()).await?;
+    counter!("api.admin.events_added").increment(1);
+    Ok((StatusCode::CREATED, Json(event)))
+}
+
+/// Imports events from a `timestamp,name,impact` CSV body; a bad row rejects the whole file
+#[axum::debug_handler]
+#[tracing::instrument(skip(state, body))]
+pub async fn import_events(
+    claims: Option<Extension<Claims>>,
+    Extension(state): Extension<Arc<AppState>>,
+    body: String,
+) -> Result<(StatusCode, Json<Vec<EconomicEvent>>), ApiError> {
+    let created_by = claims.map_or_else(|| SYSTEM_AUTHOR.to_string(), |Extension(claims)| claims.sub);
+    let imported = events(&state)?.import_csv(&body, &created_by, chrono::Utc::now()).await?;
+    counter!("api.admin.events_imported").increment(1);
+    Ok((StatusCode::CREATED, Json(imported)))
+}
+
+/// Removes an event from the calendar
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn remove_event(
+    Path(id): Path<uuid::Uuid>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<EconomicEvent>, ApiError> {
+    let event = events(&state)?.remove(id).await?;
+    counter!("api.admin.events_removed").increment(1);
+    Ok(Json(event))
+}
+
+fn jobs(state: &AppState) -> Result<&Arc<JobQueue>, ApiError> {
+    state
+        .jobs
+        .as_ref()
+        .ok_or_else(|| ApiError::InternalError("job queue unavailable".to_string()))
+}
+
+/// Lists background jobs, most recently updated first
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn list_jobs(
+    Query(filter): Query<JobFilter>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<Vec<QueuedJob>>, ApiError> {
+    Ok(Json(jobs(&state)?.list(&filter).await?))
+}
+
+/// Requeues a dead-lettered or cancelled job from its first attempt
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn retry_job(
+    Path(id): Path<uuid::Uuid>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<QueuedJob>, ApiError> {
+    let job = jobs(&state)?.retry(id).await?;
+    counter!("api.admin.jobs_retried").increment(1);
+    Ok(Json(job))
+}
+
+/// Cancels a job that has not started
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn cancel_job(
+    Path(id): Path<uuid::Uuid>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<QueuedJob>, ApiError> {
+    let job = jobs(&state)?.cancel(id).await?;
+    counter!("api.admin.jobs_cancelled").increment(1);
+    Ok(Json(job))
+}
+
+/// Number of cost models returned by the listing endpoint unless a limit is given
+const COST_MODEL_LIST_LIMIT: usize = 50;
+
+/// Cost model listing filter
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CostModelFilter {
+    #[serde(default)]
+    pub exchange: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Lists calibrated execution cost models newest first, including expired ones for comparison
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn list_cost_models(
+    Query(filter): Query<CostModelFilter>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<Vec<CostModel>>, ApiError> {
+    let cost_models = state.cost_models.as_ref().ok_or_else(|| {
+        ApiError::InternalError("cost models unavailable".to_string())
+    })?;
+    let models = cost_models
+        .history(filter.exchange.as_deref(), filter.limit.unwrap_or(COST_MODEL_LIST_LIMIT))
+        .await
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(models))
+}
+
+/// Enqueues a recalibration of every venue's cost model
+#[axum::debug_handler]
+#[tracing::instrument(skip(claims, state))]
+pub async fn calibrate_cost_models(
+    Extension(claims): Extension<Claims>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<(StatusCode, Json<QueuedJob>), ApiError> {
+    let queue = jobs(&state)?;
+    let id = queue
+        .enqueue(CostModelCalibration {
+            requested_by: Some(claims.sub.clone()),
+        })
+        .await?;
+    counter!("api.admin.cost_model_calibrations").increment(1);
+    Ok((StatusCode::ACCEPTED, Json(queue.get(id).await?)))
+}
+
+/// Rejects scoped tokens that do not cover `scope`; session tokens are unrestricted
+fn require_scope(access: &Option<Extension<ScopedAccess>>, scope: TokenScope) -> Result<(), ApiError> {
+    match access {
+        Some(Extension(access)) if !access.allows(&scope) => {
+            counter!("api.scoped_tokens.forbidden").increment(1);
+            Err(ScopedTokenError::OutOfScope(scope.to_string()).into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Mints a read-only token scoped to the requested resources; the secret is returned once
+#[axum::debug_handler]
+#[tracing::instrument(skip(request, state))]
+pub async fn mint_scoped_token(
+    claims: Option<Extension<Claims>>,
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<MintTokenRequest>,
+) -> Result<(StatusCode, Json<MintedToken>), ApiError> {
+    let created_by = claims.map_or_else(|| SYSTEM_AUTHOR.to_string(), |Extension(claims)| claims.sub);
+    let minted = state.scoped_tokens.mint(request, &created_by, chrono::Utc::now()).await?;
+    counter!("api.admin.scoped_tokens_minted").increment(1);
+    Ok((StatusCode::CREATED, Json(minted)))
+}
+
+/// Lists scoped tokens newest first, revoked ones included; secrets are never listed
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn list_scoped_tokens(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Json<Vec<ScopedToken>> {
+    Json(state.scoped_tokens.list())
+}
+
+/// Revokes a scoped token; requests carrying it are rejected from then on
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn revoke_scoped_token(
+    Path(id): Path<uuid::Uuid>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<ScopedToken>, ApiError> {
+    let token = state.scoped_tokens.revoke(id, chrono::Utc::now()).await?;
+    counter!("api.admin.scoped_tokens_revoked").increment(1);
+    Ok(Json(token))
+}
+
+/// Number of snapshots returned by the listing endpoint
+const SNAPSHOT_LIST_LIMIT: i64 = 50;
+
+/// Takes a state snapshot on demand
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn take_snapshot(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<(StatusCode, Json<SnapshotSummary>), ApiError> {
+    let summary = snapshots(&state)?.take(SnapshotTrigger::Manual).await?;
+    counter!("api.admin.snapshots").increment(1);
+    Ok((StatusCode::CREATED, Json(summary)))
+}
+
+/// Lists recent state snapshots, newest first
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn list_snapshots(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<Vec<SnapshotSummary>>, ApiError> {
+    Ok(Json(snapshots(&state)?.list(SNAPSHOT_LIST_LIMIT).await?))
+}
+
+/// State rehydrated from a stored snapshot
+#[derive(Debug, Serialize)]
+pub struct SnapshotRestore {
+    pub snapshot_id: uuid::Uuid,
+    pub captured_at: chrono::DateTime<chrono::Utc>,
+    pub strategies: usize,
+    pub pending_orders: usize,
+}
+
+/// Restores a stored snapshot into the running bot; trading stays halted until resumed
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn restore_snapshot(
+    Path(id): Path<uuid::Uuid>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<SnapshotRestore>, ApiError> {
+    let restored = snapshots(&state)?.restore(id).await?;
+    counter!("api.admin.snapshots_restored").increment(1);
+    Ok(Json(SnapshotRestore {
+        snapshot_id: id,
+        captured_at: restored.captured_at,
+        strategies: restored.strategies.len(),
+        pending_orders: restored.pending_orders.len(),
+    }))
+}
+
+/// Lifts the trading halt left by a restore once the operator has reviewed state
+#[axum::debug_handler]
+#[tracing::instrument(skip(state))]
+pub async fn resume_trading(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<StatusCode, ApiError> {
+    snapshots(&state)?.resume().await;
+    counter!("api.admin.trading_resumed").increment(1);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn snapshots(state: &AppState) -> Result<&Arc<SnapshotService>, ApiError> {
+    state.snapshots.as_ref().ok_or_else(|| {
+        ApiError::InternalError("state snapshots unavailable".to_string())
+    })
+}
+
 // Helper functions
 async fn fetch_market_data(trading_pairs: &[String]) -> Result<Vec<PairData>, ApiError> {
     // Implementation for fetching market data from DEXs
@@ -0,0 +1,513 @@
+//! gRPC interface for co-located clients, serving streaming market data, order entry and
+//! portfolio state as a thin adapter over the same paths used by REST and WebSocket.
+//!
+//! Version dependencies:
+//! - tonic = "0.9"
+//! - prost = "0.11"
+//! - tokio-stream = "0.1"
+//! - tokio = "1.28"
+
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use rust_decimal::Decimal;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tokio_stream::Stream;
+use tonic::metadata::MetadataMap;
+use tonic::transport::Server;
+use tonic::{Request, Response, Status};
+use tracing::{debug, info, instrument, warn};
+
+use crate::api::auth::{validate_token, Claims};
+use crate::api::jwks::JwtKeyStore;
+use crate::execution_engine::adapters::DEFAULT_MAX_SLIPPAGE;
+use crate::execution_engine::order_book::OrderBookSnapshot;
+use crate::execution_engine::queue::PriorityClass;
+use crate::execution_engine::passive::ExecutionStyle;
+use crate::execution_engine::{ExecutionResult, StrategyParams};
+use crate::models::market::{MarketData, MarketDataBatch};
+use crate::models::order::OrderType;
+use crate::models::portfolio::{Portfolio, Position};
+use crate::risk_manager::exposure::TradeSide;
+use crate::risk_manager::RiskError;
+use crate::{Error, ExecutionError};
+
+/// Generated protobuf types, servers and clients for `proto/trading.proto`
+pub mod proto {
+    tonic::include_proto!("firebot.v1");
+}
+
+use proto::market_data_event::Event;
+use proto::market_data_stream_server::{MarketDataStream, MarketDataStreamServer};
+use proto::order_service_server::{OrderService, OrderServiceServer};
+use proto::portfolio_service_server::{PortfolioService, PortfolioServiceServer};
+
+// gRPC constants
+const AUTHORIZATION_METADATA: &str = "authorization";
+const BEARER_PREFIX: &str = "Bearer ";
+const STREAM_BUFFER: usize = 256;
+const PRICE_CHANNEL_CAPACITY: usize = 10_000;
+const POSITION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Server-streaming response type shared by every streaming RPC
+pub type EventStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send + 'static>>;
+
+/// Order entry path shared with REST, so gRPC orders pass the same admission, supervision,
+/// risk and execution checks
+#[tonic::async_trait]
+pub trait OrderGateway: Send + Sync + 'static {
+    /// Submits an order and waits for it to execute or be rejected
+    async fn submit_order(&self, params: StrategyParams) -> Result<ExecutionResult, Error>;
+
+    /// Cancels a strategy's orders still waiting for execution, returning how many were removed
+    fn cancel_orders(&self, strategy_id: &str) -> usize;
+}
+
+/// Validates the bearer token carried in request metadata
+async fn authenticate(key_store: &JwtKeyStore, metadata: &MetadataMap) -> Result<Claims, Status> {
+    let token = metadata
+        .get(AUTHORIZATION_METADATA)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix(BEARER_PREFIX))
+        .ok_or_else(|| Status::unauthenticated("missing bearer token"))?;
+
+    validate_token(token, key_store).await.map_err(|e| {
+        counter!("api.grpc.auth_failures").increment(1);
+        Status::unauthenticated(e.to_string())
+    })
+}
+
+fn parse_decimal(field: &str, value: &str) -> Result<Decimal, Status> {
+    Decimal::from_str(value)
+        .map_err(|_| Status::invalid_argument(format!("{} '{}' is not a valid decimal", field, value)))
+}
+
+fn timestamp_ms(timestamp: DateTime<Utc>) -> i64 {
+    timestamp.timestamp_millis()
+}
+
+/// Maps order path errors onto gRPC status codes
+fn order_status(error: Error) -> Status {
+    match &error {
+        Error::Execution(ExecutionError::ValidationError(_))
+        | Error::Execution(ExecutionError::ComputeBudgetExceeded { .. })
+        | Error::Execution(ExecutionError::LiveTradingDisabled(_))
+        | Error::Risk(RiskError::PairQuarantined(_)) => {
+            Status::failed_precondition(error.to_string())
+        }
+        Error::Execution(ExecutionError::ExpiredError(_))
+        | Error::Execution(ExecutionError::TimeoutError(..)) => {
+            Status::deadline_exceeded(error.to_string())
+        }
+        Error::Execution(ExecutionError::RateLimitError(..))
+        | Error::Execution(ExecutionError::Throttled { .. }) => {
+            Status::resource_exhausted(error.to_string())
+        }
+        Error::System(_) | Error::Execution(ExecutionError::WarmingUp(_)) => {
+            Status::unavailable(error.to_string())
+        }
+        _ => Status::internal(error.to_string()),
+    }
+}
+
+fn price_event(data: &MarketData) -> proto::MarketDataEvent {
+    proto::MarketDataEvent {
+        event: Some(Event::Price(proto::PriceUpdate {
+            trading_pair: data.trading_pair().to_string(),
+            exchange: data.exchange().to_string(),
+            price: data.price().to_string(),
+            volume: data.volume().to_string(),
+            timestamp_ms: timestamp_ms(data.timestamp()),
+        })),
+    }
+}
+
+fn order_book_event(snapshot: &OrderBookSnapshot) -> proto::MarketDataEvent {
+    let levels = |levels: &[crate::models::market::OrderBookLevel]| {
+        levels
+            .iter()
+            .map(|level| proto::BookLevel {
+                price: level.price().to_string(),
+                volume: level.volume().to_string(),
+            })
+            .collect()
+    };
+
+    proto::MarketDataEvent {
+        event: Some(Event::OrderBook(proto::OrderBookUpdate {
+            trading_pair: snapshot.trading_pair.clone(),
+            exchange: snapshot.exchange.clone(),
+            bids: levels(&snapshot.bids),
+            asks: levels(&snapshot.asks),
+            timestamp_ms: timestamp_ms(snapshot.timestamp),
+            is_stale: snapshot.is_stale,
+        })),
+    }
+}
+
+fn position_view(position: &Position) -> proto::PositionView {
+    proto::PositionView {
+        trading_pair: position.trading_pair.to_string(),
+        size: position.size.to_string(),
+        entry_price: position.entry_price.to_string(),
+        realized_pnl: position.realized_pnl.to_string(),
+        last_updated_ms: timestamp_ms(position.last_updated),
+    }
+}
+
+/// Streams aggregated prices and order book updates
+#[derive(Debug, Clone)]
+pub struct MarketDataGrpc {
+    key_store: Arc<JwtKeyStore>,
+    prices: broadcast::Sender<MarketDataBatch>,
+    order_books: broadcast::Sender<OrderBookSnapshot>,
+}
+
+#[tonic::async_trait]
+impl MarketDataStream for MarketDataGrpc {
+    type SubscribeStream = EventStream<proto::MarketDataEvent>;
+
+    #[instrument(skip(self, request))]
+    async fn subscribe(
+        &self,
+        request: Request<proto::MarketDataRequest>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let claims = authenticate(&self.key_store, request.metadata()).await?;
+        let pairs: HashSet<String> = request.into_inner().trading_pairs.into_iter().collect();
+        let wanted = move |pair: &str| pairs.is_empty() || pairs.contains(pair);
+
+        let mut prices = self.prices.subscribe();
+        let mut order_books = self.order_books.subscribe();
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+
+        counter!("api.grpc.market_data.subscriptions").increment(1);
+        debug!(wallet = %claims.sub, "gRPC market data stream opened");
+
+        tokio::spawn(async move {
+            loop {
+                let events: Vec<proto::MarketDataEvent> = tokio::select! {
+                    batch = prices.recv() => match batch {
+                        Ok(batch) => batch
+                            .iter()
+                            .filter(|data| wanted(data.trading_pair()))
+                            .map(price_event)
+                            .collect(),
+                        Err(RecvError::Lagged(skipped)) => {
+                            warn!(skipped, "gRPC market data subscriber lagged on prices");
+                            continue;
+                        }
+                        Err(RecvError::Closed) => break,
+                    },
+                    snapshot = order_books.recv() => match snapshot {
+                        Ok(snapshot) if wanted(&snapshot.trading_pair) => vec![order_book_event(&snapshot)],
+                        Ok(_) => continue,
+                        Err(RecvError::Lagged(skipped)) => {
+                            warn!(skipped, "gRPC market data subscriber lagged on order books");
+                            continue;
+                        }
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+
+                for event in events {
+                    if tx.send(Ok(event)).await.is_err() {
+                        debug!("gRPC market data client disconnected");
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
+
+/// Submits and cancels orders through the order gateway
+#[derive(Clone)]
+pub struct OrderGrpc {
+    key_store: Arc<JwtKeyStore>,
+    gateway: Arc<dyn OrderGateway>,
+}
+
+impl OrderGrpc {
+    fn strategy_params(request: proto::SubmitOrderRequest) -> Result<StrategyParams, Status> {
+        let side = match request.side() {
+            proto::Side::Buy => TradeSide::Buy,
+            proto::Side::Sell => TradeSide::Sell,
+            proto::Side::Unspecified => return Err(Status::invalid_argument("side is required")),
+        };
+        let order_type = match request.order_type() {
+            proto::OrderKind::Market => OrderType::Market,
+            proto::OrderKind::Limit => OrderType::Limit,
+            proto::OrderKind::StopLoss => OrderType::StopLoss,
+            proto::OrderKind::TakeProfit => OrderType::TakeProfit,
+            proto::OrderKind::Unspecified => {
+                return Err(Status::invalid_argument("order_type is required"))
+            }
+        };
+        if request.strategy_id.is_empty() {
+            return Err(Status::invalid_argument("strategy_id is required"));
+        }
+
+        Ok(StrategyParams {
+            size: parse_decimal("size", &request.size)?,
+            price: parse_decimal("price", &request.price)?,
+            strategy_id: request.strategy_id,
+            priority: PriorityClass::Normal,
+            trading_pair: request.trading_pair,
+            exchange: request.exchange,
+            side,
+            order_type,
+            execution_style: ExecutionStyle::Aggressive,
+            max_slippage: DEFAULT_MAX_SLIPPAGE,
+            tick: None,
+            size_multiplier: None,
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl OrderService for OrderGrpc {
+    #[instrument(skip(self, request))]
+    async fn submit_order(
+        &self,
+        request: Request<proto::SubmitOrderRequest>,
+    ) -> Result<Response<proto::SubmitOrderResponse>, Status> {
+        let claims = authenticate(&self.key_store, request.metadata()).await?;
+        let params = Self::strategy_params(request.into_inner())?;
+        debug!(wallet = %claims.sub, strategy_id = %params.strategy_id, "gRPC order submitted");
+
+        counter!("api.grpc.orders.submitted").increment(1);
+        let result = self.gateway.submit_order(params).await.map_err(order_status)?;
+
+        Ok(Response::new(proto::SubmitOrderResponse {
+            trade_id: result.trade_id,
+            executed_price: result.price.to_string(),
+            execution_time_ms: result.execution_time.as_millis() as u64,
+        }))
+    }
+
+    #[instrument(skip(self, request))]
+    async fn cancel_orders(
+        &self,
+        request: Request<proto::CancelOrdersRequest>,
+    ) -> Result<Response<proto::CancelOrdersResponse>, Status> {
+        let claims = authenticate(&self.key_store, request.metadata()).await?;
+        let strategy_id = request.into_inner().strategy_id;
+        if strategy_id.is_empty() {
+            return Err(Status::invalid_argument("strategy_id is required"));
+        }
+
+        let cancelled = self.gateway.cancel_orders(&strategy_id);
+        counter!("api.grpc.orders.cancelled").increment(cancelled as u64);
+        debug!(wallet = %claims.sub, strategy_id = %strategy_id, cancelled, "gRPC orders cancelled");
+
+        Ok(Response::new(proto::CancelOrdersResponse {
+            cancelled: cancelled as u32,
+        }))
+    }
+}
+
+/// Serves portfolio snapshots and position changes
+#[derive(Debug, Clone)]
+pub struct PortfolioGrpc {
+    key_store: Arc<JwtKeyStore>,
+    portfolio: Portfolio,
+}
+
+#[tonic::async_trait]
+impl PortfolioService for PortfolioGrpc {
+    type StreamPositionsStream = EventStream<proto::PositionUpdate>;
+
+    #[instrument(skip(self, request))]
+    async fn get_snapshot(
+        &self,
+        request: Request<proto::PortfolioRequest>,
+    ) -> Result<Response<proto::PortfolioSnapshot>, Status> {
+        authenticate(&self.key_store, request.metadata()).await?;
+
+        let mut positions = self.portfolio.get_positions().await;
+        positions.sort_by(|a, b| a.trading_pair.cmp(&b.trading_pair));
+
+        counter!("api.grpc.portfolio.snapshots").increment(1);
+        Ok(Response::new(proto::PortfolioSnapshot {
+            wallet_address: self.portfolio.wallet_address().to_string(),
+            realized_pnl: self.portfolio.get_realized_pnl().await.to_string(),
+            positions: positions.iter().map(position_view).collect(),
+        }))
+    }
+
+    #[instrument(skip(self, request))]
+    async fn stream_positions(
+        &self,
+        request: Request<proto::PortfolioRequest>,
+    ) -> Result<Response<Self::StreamPositionsStream>, Status> {
+        authenticate(&self.key_store, request.metadata()).await?;
+
+        let portfolio = self.portfolio.clone();
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+
+        // Positions change under a lock rather than a channel, so poll and emit differences
+        tokio::spawn(async move {
+            let mut known: HashMap<String, proto::PositionView> = HashMap::new();
+            let mut interval = tokio::time::interval(POSITION_POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+
+                let current: HashMap<String, proto::PositionView> = portfolio
+                    .get_positions()
+                    .await
+                    .iter()
+                    .map(|position| (position.trading_pair.to_string(), position_view(position)))
+                    .collect();
+
+                let mut updates: Vec<proto::PositionUpdate> = current
+                    .iter()
+                    .filter(|(pair, view)| known.get(*pair) != Some(*view))
+                    .map(|(_, view)| proto::PositionUpdate {
+                        position: Some(view.clone()),
+                        closed: false,
+                    })
+                    .collect();
+                updates.extend(
+                    known
+                        .iter()
+                        .filter(|(pair, _)| !current.contains_key(*pair))
+                        .map(|(_, view)| proto::PositionUpdate {
+                            position: Some(view.clone()),
+                            closed: true,
+                        }),
+                );
+                known = current;
+
+                for update in updates {
+                    if tx.send(Ok(update)).await.is_err() {
+                        debug!("gRPC position stream client disconnected");
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
+
+/// gRPC server bundling the market data, order and portfolio services
+pub struct GrpcServer {
+    market_data: MarketDataGrpc,
+    orders: OrderGrpc,
+    portfolio: PortfolioGrpc,
+}
+
+impl GrpcServer {
+    pub fn new(
+        key_store: Arc<JwtKeyStore>,
+        gateway: Arc<dyn OrderGateway>,
+        portfolio: Portfolio,
+        order_books: broadcast::Sender<OrderBookSnapshot>,
+    ) -> Self {
+        let (prices, _) = broadcast::channel(PRICE_CHANNEL_CAPACITY);
+
+        Self {
+            market_data: MarketDataGrpc {
+                key_store: key_store.clone(),
+                prices,
+                order_books,
+            },
+            orders: OrderGrpc {
+                key_store: key_store.clone(),
+                gateway,
+            },
+            portfolio: PortfolioGrpc {
+                key_store,
+                portfolio,
+            },
+        }
+    }
+
+    /// Shares an existing price feed, such as the WebSocket server's, instead of a dedicated one
+    pub fn with_price_feed(mut self, prices: broadcast::Sender<MarketDataBatch>) -> Self {
+        self.market_data.prices = prices;
+        self
+    }
+
+    /// Sender publishing aggregated prices to market data subscribers
+    pub fn price_sender(&self) -> broadcast::Sender<MarketDataBatch> {
+        self.market_data.prices.clone()
+    }
+
+    /// Binds the address and serves until the server fails
+    pub async fn serve(self, addr: SocketAddr) -> Result<(), Error> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| Error::System(format!("failed to bind gRPC port {}: {}", addr, e)))?;
+        self.serve_with_listener(listener).await
+    }
+
+    /// Serves on an already bound listener
+    pub async fn serve_with_listener(self, listener: TcpListener) -> Result<(), Error> {
+        info!(addr = ?listener.local_addr().ok(), "Starting gRPC server");
+        Server::builder()
+            .add_service(MarketDataStreamServer::new(self.market_data))
+            .add_service(OrderServiceServer::new(self.orders))
+            .add_service(PortfolioServiceServer::new(self.portfolio))
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await
+            .map_err(|e| Error::System(format!("gRPC server failed: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_submit_request_maps_to_strategy_params() {
+        let request = proto::SubmitOrderRequest {
+            strategy_id: "grid".to_string(),
+            trading_pair: "SOL/USDC".to_string(),
+            exchange: "jupiter".to_string(),
+            side: proto::Side::Sell as i32,
+            order_type: proto::OrderKind::Limit as i32,
+            size: "1.5".to_string(),
+            price: "23.45".to_string(),
+        };
+
+        let params = OrderGrpc::strategy_params(request.clone()).unwrap();
+        assert_eq!(params.side, TradeSide::Sell);
+        assert_eq!(params.order_type, OrderType::Limit);
+        assert_eq!(params.size, dec!(1.5));
+        assert_eq!(params.price, dec!(23.45));
+
+        let unspecified = proto::SubmitOrderRequest { side: 0, ..request.clone() };
+        assert_eq!(
+            OrderGrpc::strategy_params(unspecified).unwrap_err().code(),
+            tonic::Code::InvalidArgument
+        );
+
+        let bad_size = proto::SubmitOrderRequest { size: "1.5e".to_string(), ..request };
+        assert_eq!(
+            OrderGrpc::strategy_params(bad_size).unwrap_err().code(),
+            tonic::Code::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_order_errors_map_to_status_codes() {
+        let rejected = Error::Execution(ExecutionError::ValidationError("risk".to_string()));
+        assert_eq!(order_status(rejected).code(), tonic::Code::FailedPrecondition);
+        let paused = Error::System("strategy grid is paused".to_string());
+        assert_eq!(order_status(paused).code(), tonic::Code::Unavailable);
+    }
+}
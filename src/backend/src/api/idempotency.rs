@@ -0,0 +1,420 @@
+//! Idempotency keys for order placement. A request carrying an `Idempotency-Key` claims its
+//! (wallet, key) pair before the order is submitted and records the response once it is
+//! known, so a retried request replays the recorded response instead of placing the order
+//! again. Because the claim is written first, a crash between submission and recording
+//! leaves the key in flight rather than free: retries are refused until the key expires but
+//! can never place the order twice. Reusing a key with a different body is a conflict.
+//!
+//! Version dependencies:
+//! - redis = "0.23"
+//! - sha2 = "0.10"
+//! - serde_json = "1.0"
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use metrics::counter; // v0.20.1
+use redis::Client as RedisClient; // v0.23.0
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tracing::{error, warn}; // v0.1.37
+
+// Idempotency constants
+pub const IDEMPOTENCY_HEADER: &str = "idempotency-key";
+pub const REPLAYED_HEADER: &str = "idempotent-replayed";
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const IDEMPOTENCY_KEY_PREFIX: &str = "idempotency:";
+const MAX_KEY_LENGTH: usize = 255;
+
+// Stores the record unless the key exists, returning the existing record otherwise
+const CLAIM_SCRIPT: &str = r#"
+if redis.call('SET', KEYS[1], ARGV[1], 'NX', 'EX', ARGV[2]) then
+    return false
+end
+return redis.call('GET', KEYS[1])
+"#;
+
+/// Idempotency failures, each with a stable error code for clients
+#[derive(Debug, Error)]
+pub enum IdempotencyError {
+    #[error("invalid idempotency key: {0}")]
+    InvalidKey(String),
+    #[error("idempotency key {0} was used with a different request body")]
+    Conflict(String),
+    #[error("request with idempotency key {0} is still in flight")]
+    InFlight(String),
+    #[error("idempotency store unavailable: {0}")]
+    Store(String),
+}
+
+impl IdempotencyError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidKey(_) => "idempotency_key_invalid",
+            Self::Conflict(_) => "idempotency_key_conflict",
+            Self::InFlight(_) => "idempotency_key_in_flight",
+            Self::Store(_) => "idempotency_unavailable",
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::InvalidKey(_) => StatusCode::BAD_REQUEST,
+            Self::Conflict(_) | Self::InFlight(_) => StatusCode::CONFLICT,
+            Self::Store(_) => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
+
+/// Status and JSON body of a response as recorded for replay
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl CachedResponse {
+    /// Buffers a handler response; a body that cannot be read is recorded as empty
+    pub async fn from_response(response: Response) -> Self {
+        let status = response.status().as_u16();
+        let body = match hyper::body::to_bytes(response.into_body()).await {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(e) => {
+                warn!("Failed to buffer response for idempotent replay: {}", e);
+                String::new()
+            }
+        };
+        Self { status, body }
+    }
+}
+
+/// What is stored per key: the request fingerprint, and the response once there is one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdempotencyRecord {
+    pub fingerprint: String,
+    #[serde(default)]
+    pub response: Option<CachedResponse>,
+}
+
+/// Response for an idempotent request, either fresh or replayed from an earlier attempt
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotentResponse {
+    pub response: CachedResponse,
+    pub replayed: bool,
+}
+
+impl IntoResponse for IdempotentResponse {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        if self.replayed {
+            headers.insert(REPLAYED_HEADER, HeaderValue::from_static("true"));
+        }
+        (status, headers, self.response.body).into_response()
+    }
+}
+
+/// Fingerprint of a request body; equal bodies always hash the same
+pub fn fingerprint<T: Serialize>(body: &T) -> String {
+    // Round-tripping through a Value sorts object keys
+    let canonical = serde_json::to_value(body)
+        .and_then(|value| serde_json::to_vec(&value))
+        .unwrap_or_default();
+    hex::encode(Sha256::digest(&canonical))
+}
+
+/// Idempotency key from request headers, if the client sent one
+pub fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, IdempotencyError> {
+    let Some(value) = headers.get(IDEMPOTENCY_HEADER) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .map(|key| Some(key.trim().to_string()))
+        .map_err(|_| IdempotencyError::InvalidKey("key must be visible ASCII".to_string()))
+}
+
+/// Records idempotency keys until they expire
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// Atomically stores the record if the key is free; otherwise returns the stored record
+    async fn claim(
+        &self,
+        wallet: &str,
+        key: &str,
+        record: &IdempotencyRecord,
+        ttl: Duration,
+    ) -> Result<Option<IdempotencyRecord>, String>;
+
+    /// Overwrites the record of a claimed key
+    async fn complete(&self, wallet: &str, key: &str, record: &IdempotencyRecord, ttl: Duration) -> Result<(), String>;
+}
+
+/// Idempotency store backed by Redis, claiming keys with `SET NX EX`
+pub struct RedisIdempotencyStore {
+    client: Arc<RedisClient>,
+}
+
+impl RedisIdempotencyStore {
+    pub fn new(client: Arc<RedisClient>) -> Self {
+        Self { client }
+    }
+
+    fn redis_key(wallet: &str, key: &str) -> String {
+        format!("{}{}:{}", IDEMPOTENCY_KEY_PREFIX, wallet, key)
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for RedisIdempotencyStore {
+    async fn claim(
+        &self,
+        wallet: &str,
+        key: &str,
+        record: &IdempotencyRecord,
+        ttl: Duration,
+    ) -> Result<Option<IdempotencyRecord>, String> {
+        let value = serde_json::to_string(record).map_err(|e| e.to_string())?;
+        let mut conn = self.client.get_async_connection().await.map_err(|e| e.to_string())?;
+        let existing: Option<String> = redis::Script::new(CLAIM_SCRIPT)
+            .key(Self::redis_key(wallet, key))
+            .arg(value)
+            .arg(ttl.as_secs().max(1))
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| e.to_string())?;
+        existing
+            .map(|existing| serde_json::from_str(&existing).map_err(|e| e.to_string()))
+            .transpose()
+    }
+
+    async fn complete(&self, wallet: &str, key: &str, record: &IdempotencyRecord, ttl: Duration) -> Result<(), String> {
+        let value = serde_json::to_string(record).map_err(|e| e.to_string())?;
+        let mut conn = self.client.get_async_connection().await.map_err(|e| e.to_string())?;
+        redis::cmd("SET")
+            .arg(Self::redis_key(wallet, key))
+            .arg(value)
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .query_async::<_, ()>(&mut conn)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Runs order placements at most once per (wallet, key)
+pub struct IdempotencyCache {
+    store: Arc<dyn IdempotencyStore>,
+    ttl: Duration,
+}
+
+impl std::fmt::Debug for IdempotencyCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IdempotencyCache")
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl IdempotencyCache {
+    pub fn new(store: Arc<dyn IdempotencyStore>) -> Self {
+        Self {
+            store,
+            ttl: DEFAULT_IDEMPOTENCY_TTL,
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Runs `place` unless the key was already used: a completed request with the same
+    /// fingerprint is replayed, a different fingerprint is a conflict and a request still in
+    /// flight is refused. The response is recorded whatever its status.
+    pub async fn run<F, Fut>(
+        &self,
+        wallet: &str,
+        key: &str,
+        fingerprint: &str,
+        place: F,
+    ) -> Result<IdempotentResponse, IdempotencyError>
+    where
+        F: FnOnce() -> Fut + Send,
+        Fut: Future<Output = CachedResponse> + Send,
+    {
+        if key.is_empty() || key.len() > MAX_KEY_LENGTH {
+            return Err(IdempotencyError::InvalidKey(format!(
+                "key must be 1 to {} characters",
+                MAX_KEY_LENGTH
+            )));
+        }
+
+        let mut record = IdempotencyRecord {
+            fingerprint: fingerprint.to_string(),
+            response: None,
+        };
+        let existing = self
+            .store
+            .claim(wallet, key, &record, self.ttl)
+            .await
+            .map_err(IdempotencyError::Store)?;
+        if let Some(existing) = existing {
+            if existing.fingerprint != fingerprint {
+                counter!("api.idempotency.conflicts").increment(1);
+                return Err(IdempotencyError::Conflict(key.to_string()));
+            }
+            let Some(response) = existing.response else {
+                counter!("api.idempotency.in_flight").increment(1);
+                return Err(IdempotencyError::InFlight(key.to_string()));
+            };
+            counter!("api.idempotency.replays").increment(1);
+            return Ok(IdempotentResponse {
+                response,
+                replayed: true,
+            });
+        }
+
+        let response = place().await;
+        record.response = Some(response.clone());
+        // The claim already blocks a second placement; losing the response only costs replays
+        if let Err(e) = self.store.complete(wallet, key, &record, self.ttl).await {
+            counter!("api.idempotency.record_failures").increment(1);
+            error!(wallet, key, "Failed to record idempotent response: {}", e);
+        }
+        Ok(IdempotentResponse {
+            response,
+            replayed: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct MemoryStore(Mutex<HashMap<String, IdempotencyRecord>>);
+
+    #[async_trait]
+    impl IdempotencyStore for MemoryStore {
+        async fn claim(
+            &self,
+            wallet: &str,
+            key: &str,
+            record: &IdempotencyRecord,
+            _ttl: Duration,
+        ) -> Result<Option<IdempotencyRecord>, String> {
+            let mut records = self.0.lock();
+            let entry = format!("{}:{}", wallet, key);
+            if let Some(existing) = records.get(&entry) {
+                return Ok(Some(existing.clone()));
+            }
+            records.insert(entry, record.clone());
+            Ok(None)
+        }
+
+        async fn complete(&self, wallet: &str, key: &str, record: &IdempotencyRecord, _ttl: Duration) -> Result<(), String> {
+            self.0.lock().insert(format!("{}:{}", wallet, key), record.clone());
+            Ok(())
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Order {
+        pair: &'static str,
+        amount: f64,
+    }
+
+    fn cache() -> IdempotencyCache {
+        IdempotencyCache::new(Arc::new(MemoryStore::default()))
+    }
+
+    /// Places an order, counting placements; each response carries the placement number
+    async fn place(placed: &AtomicUsize) -> CachedResponse {
+        let n = placed.fetch_add(1, Ordering::SeqCst) + 1;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        CachedResponse {
+            status: 200,
+            body: format!(r#"{{"order_id":"order-{}"}}"#, n),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_returns_same_body() {
+        let cache = cache();
+        let placed = AtomicUsize::new(0);
+        let print = fingerprint(&Order { pair: "SOL/USDC", amount: 1.5 });
+
+        let first = cache.run("wallet", "key-1", &print, || place(&placed)).await.unwrap();
+        let retry = cache.run("wallet", "key-1", &print, || place(&placed)).await.unwrap();
+
+        assert!(!first.replayed);
+        assert!(retry.replayed);
+        assert_eq!(retry.response, first.response);
+        assert_eq!(placed.load(Ordering::SeqCst), 1);
+
+        // Keys are scoped to the wallet
+        let other = cache.run("other", "key-1", &print, || place(&placed)).await.unwrap();
+        assert!(!other.replayed);
+        assert_eq!(placed.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_key_reused_with_different_body_conflicts() {
+        let cache = cache();
+        let placed = AtomicUsize::new(0);
+        let original = fingerprint(&Order { pair: "SOL/USDC", amount: 1.5 });
+        let changed = fingerprint(&Order { pair: "SOL/USDC", amount: 15.0 });
+
+        cache.run("wallet", "key-1", &original, || place(&placed)).await.unwrap();
+        let err = cache.run("wallet", "key-1", &changed, || place(&placed)).await.unwrap_err();
+
+        assert_eq!(err.code(), "idempotency_key_conflict");
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+        assert_eq!(placed.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_double_submit_places_one_order() {
+        let cache = cache();
+        let placed = AtomicUsize::new(0);
+        let print = fingerprint(&Order { pair: "SOL/USDC", amount: 1.5 });
+
+        let (a, b) = tokio::join!(
+            cache.run("wallet", "key-1", &print, || place(&placed)),
+            cache.run("wallet", "key-1", &print, || place(&placed)),
+        );
+
+        assert_eq!(placed.load(Ordering::SeqCst), 1);
+        let (ok, err) = match (a, b) {
+            (Ok(ok), Err(err)) | (Err(err), Ok(ok)) => (ok, err),
+            other => panic!("expected one placement and one refusal, got {:?}", other),
+        };
+        assert_eq!(ok.response.body, r#"{"order_id":"order-1"}"#);
+        assert_eq!(err.code(), "idempotency_key_in_flight");
+
+        // Once the first completes, the retry replays it
+        let retry = cache.run("wallet", "key-1", &print, || place(&placed)).await.unwrap();
+        assert!(retry.replayed);
+        assert_eq!(retry.response, ok.response);
+        assert_eq!(placed.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_key_rejected() {
+        let placed = AtomicUsize::new(0);
+        let err = cache().run("wallet", "", "print", || place(&placed)).await.unwrap_err();
+        assert_eq!(err.code(), "idempotency_key_invalid");
+        assert_eq!(placed.load(Ordering::SeqCst), 0);
+    }
+}
@@ -8,26 +8,34 @@ use axum::{
     response::Response,
 }; // v0.6.18
 use tower::{Service, ServiceBuilder}; // v0.4.13
-use tracing::{error, info, instrument, warn}; // v0.1.37
+use tracing::{error, info, info_span, instrument, warn, Instrument}; // v0.1.37
+use tracing_opentelemetry::OpenTelemetrySpanExt; // v0.19.0
 use redis::{
     cluster::ClusterClient as RedisClusterClient,
     AsyncCommands,
 }; // v0.23.0
-use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig}; // v0.1.0
 use uuid::Uuid;
 
 use crate::api::auth::validate_token;
+use crate::api::jwks::JwtKeyStore;
+use crate::api::scoped_tokens::{is_scoped_route, is_scoped_token, redact_response, ScopedTokenRegistry};
+use crate::utils::circuit_breaker::{BreakerConfig, CircuitBreaker};
 use crate::utils::logger::log_error;
 use crate::utils::metrics::MetricsCollector;
+use crate::utils::telemetry::extract_context;
 
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 // Global constants
 const RATE_LIMIT_PREFIX: &str = "rate:limit:";
 const REQUEST_TIMEOUT_MS: u64 = 30000;
 const MAX_RETRIES: u32 = 3;
 const CIRCUIT_BREAKER_THRESHOLD: f64 = 0.5;
+const CIRCUIT_BREAKER_WINDOW: Duration = Duration::from_secs(60);
+const CIRCUIT_BREAKER_MIN_CALLS: u32 = 10;
+const CIRCUIT_BREAKER_COOLDOWN: Duration = Duration::from_secs(60);
+const CORRELATION_ID_HEADER: &str = "x-correlation-id";
 
 /// Rate limiter implementation with Redis cluster support and circuit breaker
 #[derive(Debug, Clone)]
@@ -47,13 +55,16 @@ impl RateLimiter {
         window_seconds: u32,
         metrics: Arc<MetricsCollector>,
     ) -> Self {
-        let circuit_breaker_config = CircuitBreakerConfig::new()
-            .failure_threshold(CIRCUIT_BREAKER_THRESHOLD)
-            .retry_timeout(std::time::Duration::from_secs(60));
+        let circuit_breaker_config = BreakerConfig::error_rate(
+            CIRCUIT_BREAKER_WINDOW,
+            CIRCUIT_BREAKER_THRESHOLD,
+            CIRCUIT_BREAKER_MIN_CALLS,
+        )
+        .with_cooldown(CIRCUIT_BREAKER_COOLDOWN);
 
         Self {
             redis_client: Arc::new(redis_client),
-            circuit_breaker: Arc::new(CircuitBreaker::new(circuit_breaker_config)),
+            circuit_breaker: CircuitBreaker::new("rate_limiter", circuit_breaker_config).registered(),
             max_requests,
             window_seconds,
             metrics,
@@ -63,12 +74,17 @@ impl RateLimiter {
     /// Checks if request is within rate limits using sliding window
     #[instrument(skip(self))]
     async fn check_rate_limit(&self, key: String) -> Result<bool, String> {
-        if !self.circuit_breaker.can_execute() {
+        if !self.circuit_breaker.allow() {
             return Err("Circuit breaker is open".to_string());
         }
 
-        let mut redis_conn = self.redis_client.get_async_connection().await
-            .map_err(|e| format!("Redis connection failed: {}", e))?;
+        let mut redis_conn = match self.redis_client.get_async_connection().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                self.circuit_breaker.record_failure();
+                return Err(format!("Redis connection failed: {}", e));
+            }
+        };
 
         let current_time = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -89,6 +105,7 @@ impl RateLimiter {
 
         match pipeline_result {
             Ok((count, _)) => {
+                self.circuit_breaker.record_success();
                 let within_limit = count <= self.max_requests;
                 self.metrics.record_rate_limit_hit(!within_limit).await.ok();
                 Ok(within_limit)
@@ -101,15 +118,50 @@ impl RateLimiter {
     }
 }
 
+/// Opens the request span, continuing the caller's trace from a W3C `traceparent` header, and
+/// attaches a correlation ID (the caller's `x-correlation-id` when present) to the request
+/// and response
+pub async fn correlation_middleware(mut request: Request, next: Next) -> Response {
+    let correlation_id = request
+        .headers()
+        .get(CORRELATION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    request.extensions_mut().insert(correlation_id.clone());
+
+    let span = info_span!(
+        "http_request",
+        method = %request.method(),
+        path = %request.uri().path(),
+        correlation_id = %correlation_id,
+        status = tracing::field::Empty,
+    );
+    span.set_parent(extract_context(request.headers()));
+
+    let mut response = next.run(request).instrument(span.clone()).await;
+    span.record("status", response.status().as_u16());
+    if let Ok(value) = correlation_id.parse() {
+        response.headers_mut().insert(CORRELATION_ID_HEADER, value);
+    }
+    response
+}
+
 /// Authentication middleware with enhanced security features
 #[instrument(skip(request, next), fields(correlation_id))]
 pub async fn auth_middleware(
     mut request: Request,
     next: Next,
 ) -> Result<Response, (StatusCode, String)> {
-    // Generate and attach correlation ID
-    let correlation_id = Uuid::new_v4().to_string();
+    // Reuse the request's correlation ID, generating one when none was attached
+    let correlation_id = request
+        .extensions()
+        .get::<String>()
+        .cloned()
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
     request.extensions_mut().insert(correlation_id.clone());
+    tracing::Span::current().record("correlation_id", correlation_id.as_str());
 
     // Extract and validate JWT token
     let token = match request.headers().get("Authorization") {
@@ -122,8 +174,43 @@ pub async fn auth_middleware(
         None => return Err((StatusCode::UNAUTHORIZED, "Missing authorization header".to_string())),
     };
 
-    // Validate token with comprehensive security checks
-    let wallet_data = validate_token(&token).map_err(|e| {
+    // Scoped read-only tokens reach only the dashboard routes and get redacted responses
+    if is_scoped_token(&token) {
+        let registry = request
+            .extensions()
+            .get::<Arc<ScopedTokenRegistry>>()
+            .cloned()
+            .ok_or_else(|| {
+                error!("Scoped token registry not configured");
+                (StatusCode::INTERNAL_SERVER_ERROR, "Authentication unavailable".to_string())
+            })?;
+        let now = chrono::Utc::now();
+        let access = registry.authenticate(&token, now).map_err(|e| {
+            warn!("Scoped token rejected: {}", e);
+            (StatusCode::UNAUTHORIZED, "Invalid token".to_string())
+        })?;
+        if !is_scoped_route(request.method(), request.uri().path()) {
+            return Err((StatusCode::FORBIDDEN, "Token scope does not cover this resource".to_string()));
+        }
+        registry.admit(&access, now).map_err(|e| {
+            warn!("{}", e);
+            (StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded".to_string())
+        })?;
+        request.extensions_mut().insert(access);
+        return Ok(redact_response(next.run(request).await).await);
+    }
+
+    // Validate token against the active key set
+    let key_store = request
+        .extensions()
+        .get::<Arc<JwtKeyStore>>()
+        .cloned()
+        .ok_or_else(|| {
+            error!("JWT key store not configured");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Authentication unavailable".to_string())
+        })?;
+
+    let wallet_data = validate_token(&token, &key_store).await.map_err(|e| {
         log_error(Box::new(e), "Token validation failed".into(), Some(correlation_id.clone()));
         (StatusCode::UNAUTHORIZED, "Invalid token".to_string())
     })?;
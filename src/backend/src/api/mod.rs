@@ -3,6 +3,7 @@
 //! Version: 1.0.0
 
 use axum::{
+    Extension,
     Router,
     middleware::{self, from_fn},
     routing::IntoMakeService,
@@ -19,8 +20,61 @@ use std::sync::Arc;
 use std::time::Duration;
 use uuid::Uuid;
 
+use crate::attribution::AttributionEngine;
+use crate::data_collector::lifecycle::CollectorManager;
+use crate::data_collector::ohlcv::CandleAggregator;
+use crate::data_collector::gaps::GapMonitor;
+use crate::data_collector::ingest::MarketDataIngestor;
+use crate::data_collector::quality::DataQualityMonitor;
+use crate::data_collector::replay::ReplaySource;
+use crate::events::EventCalendar;
+use crate::execution_engine::cost_model::CostModelCalibrator;
+use crate::execution_engine::open_orders::OpenOrderRegistry;
+use crate::execution_engine::stats::ExecutionStatsService;
+use crate::execution_engine::order_book::LiveOrderBook;
+use crate::execution_engine::simulation::TradeSimulator;
+use crate::execution_engine::position_events::{LivePositions, PositionHistory};
+use crate::execution_engine::preview::StrategyPreviewer;
+use crate::models::pair::PairRegistry;
+use crate::execution_engine::readiness::ReadinessGate;
+use crate::key_rotation::KeyRotationService;
+use crate::jobs::JobQueue;
+use crate::persistence::PersistenceQueue;
+use crate::maintenance::MaintenanceScheduler;
+use crate::optimizer::{OptimizerConfig, OptimizerService, DEFAULT_CAPTURE_PATH};
+use crate::performance::PerformanceService;
+use crate::quarantine::QuarantineList;
+use crate::reports::DailyReporter;
+use crate::retention::RetentionManager;
+use crate::risk_manager::degradation::DegradationSizer;
+use crate::risk_manager::stress::StressTester;
+use crate::strategy_archive::StrategyArchive;
+use crate::strategy_versions::StrategyVersionService;
+use crate::state_snapshot::SnapshotService;
+use crate::supervision::StrategySupervisor;
+use crate::system_info::ActivitySource;
+use crate::utils::token_accounts::TokenAccountManager;
+
 // Re-export API components
 pub use self::auth::{authenticate_wallet, validate_token, Claims};
+pub use self::grpc::{GrpcServer, OrderGateway};
+pub use self::idempotency::{
+    CachedResponse, IdempotencyCache, IdempotencyError, IdempotencyStore, IdempotentResponse, RedisIdempotencyStore,
+};
+pub use self::jwks::{JwtKeyStore, KeyStoreError};
+pub use self::order_signing::{
+    canonical_order_message, NonceStore, OrderAuthorization, OrderSignatureError, OrderSignatureVerifier,
+    RedisNonceStore, SignedOrder,
+};
+pub use self::webhooks::{WebhookConfig, WebhookDisabled, WebhookDispatcher};
+pub use self::request_limits::{body_limit_middleware, RouteClass};
+pub use self::scoped_tokens::{
+    redact_response, MintTokenRequest, MintedToken, ScopedAccess, ScopedToken, ScopedTokenConfig, ScopedTokenError,
+    ScopedTokenRegistry, ScopedTokenStore, TokenScope,
+};
+pub use self::response_cache::{
+    response_cache_middleware, CacheTier, CachedEntry, CachedRoute, RedisCacheTier, ResponseCache, ResponseCacheConfig,
+};
 pub use self::routes::{create_router, ApiRouter, health_check};
 pub use self::middleware::{
     auth_middleware,
@@ -30,10 +84,26 @@ pub use self::middleware::{
     correlation_middleware,
 };
 
+// gRPC services and generated clients
+pub mod grpc;
+
+// Cross-instance event distribution
+pub mod bridge;
+
+// OpenAPI document for client SDK generation
+pub mod openapi;
+
 // Internal modules
 mod auth;
+mod idempotency;
+mod jwks;
+mod order_signing;
+mod request_limits;
+mod response_cache;
 mod routes;
+mod scoped_tokens;
 mod middleware;
+mod webhooks;
 
 // Global constants
 const API_VERSION: &str = "v1";
@@ -51,14 +121,42 @@ pub async fn init_api(app_state: Arc<AppState>) -> Router {
     counter!("api.initialization").increment(1);
     let initialization_timer = histogram!("api.initialization.duration").start_timer();
 
+    // Keep JWT keys in sync with config reloads
+    app_state
+        .key_store
+        .clone()
+        .spawn_reload_listener(crate::config::subscribe_security_updates());
+
+    // Start outbound webhook delivery
+    app_state.webhooks.start();
+
+    // Load data keys and schedule their rotation
+    if let Some(key_rotation) = &app_state.key_rotation {
+        key_rotation.clone().spawn();
+    }
+
     // Create base router
     let router = create_router(app_state.clone());
+    let request_limits = app_state.config.security.request_limits.clone();
+    let scoped_tokens = app_state.scoped_tokens.clone();
+    let response_cache = app_state.response_cache.clone();
+
+    // Drop cached responses as their producers publish new data
+    if let Some(candles) = &app_state.candles {
+        response_cache.clone().spawn_candle_listener(candles.subscribe());
+    }
+    if let Some(performance) = &app_state.performance {
+        response_cache.clone().spawn_performance_listener(performance.subscribe());
+    }
 
     // Configure comprehensive middleware stack
     let router = router
-        // Add correlation IDs for request tracking
-        .layer(from_fn(correlation_middleware))
-        
+        // Cached dashboard reads, inside authentication so private entries are keyed to
+        // the caller
+        .layer(from_fn(move |req, next| {
+            response_cache_middleware(req, next, response_cache.clone())
+        }))
+
         // Circuit breaker for system stability
         .layer(from_fn(move |req, next| {
             circuit_breaker_middleware(
@@ -70,6 +168,11 @@ pub async fn init_api(app_state: Arc<AppState>) -> Router {
         
         // Request timeout enforcement
         .layer(TimeoutLayer::new(MAX_REQUEST_TIMEOUT))
+
+        // Body size limits and read timeout, applied outside the handler timeout
+        .layer(from_fn(move |req, next| {
+            body_limit_middleware(req, next, request_limits.clone())
+        }))
         
         // Rate limiting with Redis backing
         .layer(from_fn(move |req, next| {
@@ -82,6 +185,9 @@ pub async fn init_api(app_state: Arc<AppState>) -> Router {
         
         // Authentication and authorization
         .layer(from_fn(auth_middleware))
+
+        // Scoped read-only tokens, resolved by the auth middleware
+        .layer(Extension(scoped_tokens))
         
         // Performance monitoring and logging
         .layer(from_fn(logging_middleware))
@@ -94,7 +200,11 @@ pub async fn init_api(app_state: Arc<AppState>) -> Router {
                 timer.stop_and_record();
                 response
             }
-        }));
+        }))
+
+        // Request span continuing the caller's trace, with correlation IDs; outermost so
+        // every other layer runs inside it
+        .layer(from_fn(correlation_middleware));
 
     // Configure graceful shutdown
     let router = router.layer(
@@ -116,6 +226,83 @@ pub struct AppState {
     pub config: Arc<crate::config::Config>,
     pub redis_client: Arc<redis::Client>,
     pub metrics: Arc<crate::utils::metrics::MetricsCollector>,
+    pub key_store: Arc<JwtKeyStore>,
+    pub webhooks: Arc<WebhookDispatcher>,
+    pub optimizer: Arc<OptimizerService>,
+    /// Wallet signature checks for high-value orders
+    pub order_signatures: Arc<OrderSignatureVerifier>,
+    /// Idempotency keys for order placement
+    pub idempotency: Arc<IdempotencyCache>,
+    /// Cached responses of read-heavy dashboard routes
+    pub response_cache: Arc<ResponseCache>,
+    /// Read-only tokens for shared dashboards
+    pub scoped_tokens: Arc<ScopedTokenRegistry>,
+    /// Live order books served to dashboard clients, when execution is running
+    pub order_books: Option<Arc<LiveOrderBook>>,
+    /// What-if execution against the live engine and risk manager, when execution is running
+    pub simulator: Option<Arc<TradeSimulator>>,
+    /// Dry-run strategy previews against live data, when execution is running
+    pub strategy_preview: Option<Arc<StrategyPreviewer>>,
+    /// Strategy supervision backing the resume endpoint, when the bot is running
+    pub supervisor: Option<Arc<StrategySupervisor>>,
+    /// Per-source market data quality scores, when the collector is running
+    pub data_quality: Option<Arc<DataQualityMonitor>>,
+    /// Market data gap detection and backfill progress, when the collector is running
+    pub data_gaps: Option<Arc<GapMonitor>>,
+    /// Open orders backing the cancellation endpoints, when execution is running
+    pub open_orders: Option<Arc<OpenOrderRegistry>>,
+    /// Data key rotation backing the admin endpoint, when KMS is configured
+    pub key_rotation: Option<Arc<KeyRotationService>>,
+    /// State snapshots backing the disaster recovery endpoints, when the bot is running
+    pub snapshots: Option<Arc<SnapshotService>>,
+    /// Materialized per-venue execution statistics, when execution is running
+    pub execution_stats: Option<Arc<ExecutionStatsService>>,
+    /// Strategy leaderboard and equity curves, when the bot is running
+    pub performance: Option<Arc<PerformanceService>>,
+    /// Candle aggregation whose closes invalidate cached candle responses, when collection is running
+    pub candles: Option<Arc<CandleAggregator>>,
+    /// Strategy parameter versions and A/B tests, when the bot is running
+    pub versions: Option<Arc<StrategyVersionService>>,
+    /// Strategy soft deletion backing the delete and restore endpoints, when the bot is running
+    pub archive: Option<Arc<StrategyArchive>>,
+    /// Collector tasks backing the collector restart endpoint, when collection is running
+    pub collectors: Option<Arc<CollectorManager>>,
+    /// Position lifecycle events backing the position history endpoint
+    pub position_history: Option<Arc<PositionHistory>>,
+    /// Open positions backing the positions admin endpoint, when execution is running
+    pub positions: Option<Arc<dyn LivePositions>>,
+    /// Maintenance window scheduling backing the maintenance admin endpoints
+    pub maintenance: Option<Arc<MaintenanceScheduler>>,
+    /// Per-pair kill switches backing the quarantine endpoints, when the bot is running
+    pub quarantine: Option<Arc<QuarantineList>>,
+    /// Economic event calendar backing the events endpoints, when the bot is running
+    pub events: Option<Arc<EventCalendar>>,
+    /// Degraded-mode sizing backing the sizing opt-out admin endpoints, when risk checks run
+    pub degradation: Option<Arc<DegradationSizer>>,
+    /// Execution warm-up state reported on the health endpoint, when execution is running
+    pub readiness: Option<Arc<ReadinessGate>>,
+    /// Background job queue backing the job admin endpoints
+    pub jobs: Option<Arc<JobQueue>>,
+    /// Write-behind persistence backlog reported on the health endpoint
+    pub persistence: Option<Arc<PersistenceQueue>>,
+    /// P&L attribution backing the reports endpoint, when the bot is running
+    pub attribution: Option<Arc<AttributionEngine>>,
+    /// End-of-day performance reports backing the daily report endpoint
+    pub reports: Option<Arc<DailyReporter>>,
+    /// Calibrated execution cost models backing the cost model admin endpoints
+    pub cost_models: Option<Arc<CostModelCalibrator>>,
+    /// Portfolio stress testing backing the stress endpoint, when the bot is running
+    pub stress: Option<Arc<StressTester>>,
+    /// Per-pair market data retention backing the retention endpoints
+    pub retention: Option<Arc<RetentionManager>>,
+    /// Bulk vendor market data ingestion, when the database is available
+    pub ingest: Option<Arc<MarketDataIngestor>>,
+    /// Trading wallet token accounts backing the account closing endpoint, when execution is running
+    pub token_accounts: Option<Arc<TokenAccountManager>>,
+    /// Components reporting live activity counts on the system info endpoint
+    pub activity: Vec<Arc<dyn ActivitySource>>,
+    /// Pairs accepted at the API boundary
+    pub pairs: Arc<PairRegistry>,
 }
 
 impl AppState {
@@ -125,12 +312,284 @@ impl AppState {
         redis_client: redis::Client,
         metrics: crate::utils::metrics::MetricsCollector,
     ) -> Self {
+        let key_store = Arc::new(JwtKeyStore::new(&config.security.jwt));
+        let redis_client = Arc::new(redis_client);
+        let order_signatures = Arc::new(OrderSignatureVerifier::new(
+            config.security.order_signing.clone(),
+            Arc::new(RedisNonceStore::new(redis_client.clone())),
+        ));
+        let idempotency = Arc::new(IdempotencyCache::new(Arc::new(RedisIdempotencyStore::new(
+            redis_client.clone(),
+        ))));
+        let response_cache_config = ResponseCacheConfig::default();
+        let response_cache = if response_cache_config.redis_tier {
+            ResponseCache::new(response_cache_config).with_tier(Arc::new(RedisCacheTier::new(redis_client.clone())))
+        } else {
+            ResponseCache::new(response_cache_config)
+        };
+        let webhooks = Arc::new(WebhookDispatcher::new(WebhookConfig::default(), None));
+        let optimizer = Arc::new(OptimizerService::new(
+            OptimizerConfig::default(),
+            ReplaySource::File(DEFAULT_CAPTURE_PATH.into()),
+            None,
+        ));
+
         Self {
             config: Arc::new(config),
-            redis_client: Arc::new(redis_client),
+            redis_client,
             metrics: Arc::new(metrics),
+            key_store,
+            webhooks,
+            optimizer,
+            order_signatures,
+            idempotency,
+            response_cache: Arc::new(response_cache),
+            scoped_tokens: Arc::new(ScopedTokenRegistry::default()),
+            order_books: None,
+            simulator: None,
+            strategy_preview: None,
+            supervisor: None,
+            data_quality: None,
+            data_gaps: None,
+            open_orders: None,
+            key_rotation: None,
+            snapshots: None,
+            execution_stats: None,
+            performance: None,
+            candles: None,
+            versions: None,
+            archive: None,
+            collectors: None,
+            position_history: None,
+            positions: None,
+            maintenance: None,
+            quarantine: None,
+            events: None,
+            degradation: None,
+            readiness: None,
+            jobs: None,
+            persistence: None,
+            attribution: None,
+            reports: None,
+            cost_models: None,
+            stress: None,
+            retention: None,
+            ingest: None,
+            token_accounts: None,
+            activity: Vec::new(),
+            pairs: Arc::new(PairRegistry::default()),
         }
     }
+
+    /// Replaces the scoped token registry, typically with one backed by a store
+    pub fn with_scoped_tokens(mut self, scoped_tokens: Arc<ScopedTokenRegistry>) -> Self {
+        self.scoped_tokens = scoped_tokens;
+        self
+    }
+
+    /// Attaches the execution engine's live order books
+    pub fn with_order_books(mut self, order_books: Arc<LiveOrderBook>) -> Self {
+        self.order_books = Some(order_books);
+        self
+    }
+
+    /// Attaches the simulator backing the trade simulation endpoint
+    pub fn with_trade_simulator(mut self, simulator: Arc<TradeSimulator>) -> Self {
+        self.simulator = Some(simulator);
+        self
+    }
+
+    /// Attaches the previewer backing the strategy preview endpoint
+    pub fn with_strategy_preview(mut self, strategy_preview: Arc<StrategyPreviewer>) -> Self {
+        self.strategy_preview = Some(strategy_preview);
+        self
+    }
+
+    /// Attaches the bot's strategy supervisor
+    pub fn with_strategy_supervisor(mut self, supervisor: Arc<StrategySupervisor>) -> Self {
+        self.supervisor = Some(supervisor);
+        self
+    }
+
+    /// Attaches the collector's data quality monitor
+    pub fn with_data_quality(mut self, data_quality: Arc<DataQualityMonitor>) -> Self {
+        self.data_quality = Some(data_quality);
+        self
+    }
+
+    /// Attaches the collector's market data gap monitor
+    pub fn with_data_gaps(mut self, data_gaps: Arc<GapMonitor>) -> Self {
+        self.data_gaps = Some(data_gaps);
+        self
+    }
+
+    /// Attaches the position event history
+    pub fn with_position_history(mut self, position_history: Arc<PositionHistory>) -> Self {
+        self.position_history = Some(position_history);
+        self
+    }
+
+    /// Attaches the bot's open order registry
+    pub fn with_open_orders(mut self, open_orders: Arc<OpenOrderRegistry>) -> Self {
+        self.open_orders = Some(open_orders);
+        self
+    }
+
+    /// Attaches the data key rotation service
+    pub fn with_key_rotation(mut self, key_rotation: Arc<KeyRotationService>) -> Self {
+        self.key_rotation = Some(key_rotation);
+        self
+    }
+
+    /// Attaches the bot's state snapshot service
+    pub fn with_snapshots(mut self, snapshots: Arc<SnapshotService>) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
+
+    /// Attaches the execution statistics backing the analytics endpoint
+    pub fn with_execution_stats(mut self, execution_stats: Arc<ExecutionStatsService>) -> Self {
+        self.execution_stats = Some(execution_stats);
+        self
+    }
+
+    /// Attaches the strategy performance service backing the leaderboard and equity endpoints
+    pub fn with_performance(mut self, performance: Arc<PerformanceService>) -> Self {
+        self.performance = Some(performance);
+        self
+    }
+
+    /// Replaces the response cache, typically to change route TTLs or add a shared tier
+    pub fn with_response_cache(mut self, response_cache: Arc<ResponseCache>) -> Self {
+        self.response_cache = response_cache;
+        self
+    }
+
+    /// Attaches the candle aggregator whose closes invalidate cached candle responses
+    pub fn with_candles(mut self, candles: Arc<CandleAggregator>) -> Self {
+        self.candles = Some(candles);
+        self
+    }
+
+    /// Attaches the strategy version service backing the versioning and A/B test endpoints
+    pub fn with_strategy_versions(mut self, versions: Arc<StrategyVersionService>) -> Self {
+        self.versions = Some(versions);
+        self
+    }
+
+    /// Attaches the strategy archive backing the delete and restore endpoints
+    pub fn with_strategy_archive(mut self, archive: Arc<StrategyArchive>) -> Self {
+        self.archive = Some(archive);
+        self
+    }
+
+    /// Attaches the collector lifecycle backing the collector restart endpoint
+    pub fn with_collectors(mut self, collectors: Arc<CollectorManager>) -> Self {
+        self.collectors = Some(collectors);
+        self
+    }
+
+    /// Attaches execution's open positions
+    pub fn with_positions(mut self, positions: Arc<dyn LivePositions>) -> Self {
+        self.positions = Some(positions);
+        self
+    }
+
+    /// Attaches the bot's maintenance scheduler
+    pub fn with_maintenance(mut self, maintenance: Arc<MaintenanceScheduler>) -> Self {
+        self.maintenance = Some(maintenance);
+        self
+    }
+
+    /// Attaches the pair quarantine list backing the quarantine endpoints
+    pub fn with_quarantine(mut self, quarantine: Arc<QuarantineList>) -> Self {
+        self.quarantine = Some(quarantine);
+        self
+    }
+
+    /// Attaches the economic event calendar backing the events endpoints
+    pub fn with_events(mut self, events: Arc<EventCalendar>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Attaches the risk manager's degraded-mode sizing
+    pub fn with_degradation(mut self, degradation: Arc<DegradationSizer>) -> Self {
+        self.degradation = Some(degradation);
+        self
+    }
+
+    /// Attaches the execution engine's warm-up gate
+    pub fn with_readiness(mut self, readiness: Arc<ReadinessGate>) -> Self {
+        self.readiness = Some(readiness);
+        self
+    }
+
+    /// Attaches the write-behind persistence queue
+    pub fn with_persistence(mut self, persistence: Arc<PersistenceQueue>) -> Self {
+        self.persistence = Some(persistence);
+        self
+    }
+
+    /// Attaches the background job queue
+    pub fn with_jobs(mut self, jobs: Arc<JobQueue>) -> Self {
+        self.jobs = Some(jobs);
+        self
+    }
+
+    /// Attaches the bot's P&L attribution engine
+    pub fn with_attribution(mut self, attribution: Arc<AttributionEngine>) -> Self {
+        self.attribution = Some(attribution);
+        self
+    }
+
+    /// Attaches the daily performance reporter
+    pub fn with_reports(mut self, reports: Arc<DailyReporter>) -> Self {
+        self.reports = Some(reports);
+        self
+    }
+
+    /// Attaches the calibrated execution cost models
+    pub fn with_cost_models(mut self, cost_models: Arc<CostModelCalibrator>) -> Self {
+        self.cost_models = Some(cost_models);
+        self
+    }
+
+    /// Attaches portfolio stress testing
+    pub fn with_stress_tester(mut self, stress: Arc<StressTester>) -> Self {
+        self.stress = Some(stress);
+        self
+    }
+
+    /// Attaches per-pair retention overrides
+    pub fn with_retention(mut self, retention: Arc<RetentionManager>) -> Self {
+        self.retention = Some(retention);
+        self
+    }
+
+    /// Attaches bulk market data ingestion
+    pub fn with_ingest(mut self, ingest: Arc<MarketDataIngestor>) -> Self {
+        self.ingest = Some(ingest);
+        self
+    }
+
+    /// Attaches the trading wallet's token account management
+    pub fn with_token_accounts(mut self, token_accounts: Arc<TokenAccountManager>) -> Self {
+        self.token_accounts = Some(token_accounts);
+        self
+    }
+
+    /// Adds a component to the activity counts reported on the system info endpoint
+    pub fn with_activity_source(mut self, source: Arc<dyn ActivitySource>) -> Self {
+        self.activity.push(source);
+        self
+    }
+
+    /// Replaces the registry of pairs accepted at the API boundary
+    pub fn with_pair_registry(mut self, pairs: PairRegistry) -> Self {
+        self.pairs = Arc::new(pairs);
+        self
+    }
 }
 
 #[cfg(test)]
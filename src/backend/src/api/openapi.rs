@@ -0,0 +1,469 @@
+//! OpenAPI document for the REST API, generated from the path and schema annotations on the
+//! handlers and their request and response types. Clients generate typed SDKs from the spec
+//! served at `/api/v1/openapi.json`; a Swagger UI is mounted at `/api/v1/docs` outside
+//! production. Decimals are documented as strings, matching how they serialize.
+//!
+//! `tests/fixtures/openapi.json` holds the committed spec; rerun the snapshot test with
+//! `UPDATE_OPENAPI_SNAPSHOT=1` after an intended API change.
+//!
+//! Version dependencies:
+//! - utoipa = "3.5"
+//! - utoipa-swagger-ui = "3.1"
+
+use axum::Json;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::api::endpoints::{
+    self, BulkCancelResponse, CreateStrategyRequest, EquityResponse, ErrorResponse, ExecutionStatsResponse,
+    PortfolioPerformanceResponse, RollbackRequest, StartAbTestRequest, TradeListResponse, TransferListResponse,
+    UpdateParametersRequest,
+};
+use crate::attribution::{AttributionDimension, AttributionReport, AttributionRow, ReconciliationLine};
+use crate::data_collector::gaps::{DataGap, GapStatus};
+use crate::data_collector::ingest::{IngestSummary, RowRejection};
+use crate::data_collector::ohlcv::CandleInterval;
+use crate::data_collector::quality::{SourceScore, SourceScoreBreakdown, SourceStatus};
+use crate::events::{EconomicEvent, ImpactLevel};
+use crate::execution_engine::open_orders::{CancelFilter, CancelOutcome, CancelStatus};
+use crate::execution_engine::stats::{ExecutionStats, StatsWindow};
+use crate::models::strategy::{PerformanceMetrics, StrategyParams, StrategySnapshot, StrategyState, StrategyType};
+use crate::regime::MarketRegime;
+use crate::models::transfer::{Transfer, TransferDirection};
+use crate::performance::{EquityPoint, LeaderboardEntry, StrategyTrade};
+use crate::quarantine::{QuarantineEntry, QuarantineMode, QuarantineOutcome, QuarantineRequest};
+use crate::reports::{DailyReport, Incident, IncidentKind, LimitUtilization, PnlLine};
+use crate::retention::{EffectiveRetention, RetentionSource};
+use crate::risk_manager::exposure::TradeSide;
+use crate::risk_manager::margin::LiquidationLevel;
+use crate::risk_manager::stress::{LimitBreach, MarginCall, StressRequest, StressResult, StressRun, StressScenario};
+use crate::strategies::MarketMakingParams;
+use crate::strategy_archive::{DeletionOutcome, PositionDisposition, PositionPolicy};
+use crate::strategy_versions::{
+    AbObjective, AbTest, AbTestConfig, AbTestStatus, StrategyVersion, VersionHistory, VersionMetrics,
+};
+use crate::supervision::{PauseTrigger, StrategyHealthSnapshot};
+use crate::system_info::{ActivityCounts, BuildInfo, FeatureFlags, SystemInfo};
+use crate::utils::percent::{Bps, Percent};
+
+// Spec constants
+pub const OPENAPI_PATH: &str = "/api/v1/openapi.json";
+pub const DOCS_PATH: &str = "/api/v1/docs";
+const BEARER_SCHEME: &str = "bearer_auth";
+
+/// OpenAPI document covering the annotated endpoints
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Solana Trading Bot API",
+        description = "REST API of the Solana trading bot. Decimal amounts, prices and ratios are strings."
+    ),
+    paths(
+        endpoints::create_strategy,
+        endpoints::delete_strategy,
+        endpoints::get_strategy_performance,
+        endpoints::get_strategy_equity,
+        endpoints::resume_strategy,
+        endpoints::list_strategy_trades,
+        endpoints::get_strategy_versions,
+        endpoints::update_strategy_parameters,
+        endpoints::rollback_strategy,
+        endpoints::start_ab_test,
+        endpoints::cancel_order,
+        endpoints::cancel_orders,
+        endpoints::get_portfolio_performance,
+        endpoints::get_transfers,
+        endpoints::get_execution_stats,
+        endpoints::list_quarantined_pairs,
+        endpoints::quarantine_pair,
+        endpoints::run_stress_test,
+        endpoints::get_attribution_report,
+        endpoints::get_daily_report,
+        endpoints::get_data_quality,
+        endpoints::get_data_gaps,
+        endpoints::get_retention,
+        endpoints::ingest_market_data,
+        endpoints::get_system_info,
+        endpoints::get_events,
+    ),
+    components(schemas(
+        ErrorResponse,
+        CreateStrategyRequest,
+        StrategySnapshot,
+        DeletionOutcome,
+        PositionDisposition,
+        PositionPolicy,
+        StrategyType,
+        StrategyState,
+        StrategyParams,
+        MarketMakingParams,
+        MarketRegime,
+        PerformanceMetrics,
+        Percent,
+        Bps,
+        LeaderboardEntry,
+        EquityResponse,
+        EquityPoint,
+        CandleInterval,
+        StrategyHealthSnapshot,
+        PauseTrigger,
+        TradeListResponse,
+        StrategyTrade,
+        UpdateParametersRequest,
+        RollbackRequest,
+        StartAbTestRequest,
+        StrategyVersion,
+        VersionMetrics,
+        VersionHistory,
+        AbTest,
+        AbTestConfig,
+        AbObjective,
+        AbTestStatus,
+        CancelFilter,
+        CancelOutcome,
+        CancelStatus,
+        TradeSide,
+        BulkCancelResponse,
+        PortfolioPerformanceResponse,
+        TransferListResponse,
+        Transfer,
+        TransferDirection,
+        ExecutionStatsResponse,
+        ExecutionStats,
+        StatsWindow,
+        QuarantineRequest,
+        QuarantineEntry,
+        QuarantineOutcome,
+        QuarantineMode,
+        StressRequest,
+        StressScenario,
+        StressRun,
+        StressResult,
+        MarginCall,
+        LimitBreach,
+        LiquidationLevel,
+        AttributionReport,
+        AttributionRow,
+        AttributionDimension,
+        ReconciliationLine,
+        DailyReport,
+        PnlLine,
+        LimitUtilization,
+        Incident,
+        IncidentKind,
+        SourceScore,
+        SourceScoreBreakdown,
+        SourceStatus,
+        DataGap,
+        GapStatus,
+        EffectiveRetention,
+        RetentionSource,
+        IngestSummary,
+        RowRejection,
+        SystemInfo,
+        BuildInfo,
+        FeatureFlags,
+        ActivityCounts,
+        EconomicEvent,
+        ImpactLevel,
+    )),
+    modifiers(&BearerAuth),
+    security(("bearer_auth" = [])),
+    tags(
+        (name = "strategies", description = "Strategy registration, supervision and performance"),
+        (name = "trades", description = "Realized strategy trades"),
+        (name = "orders", description = "Open order cancellation"),
+        (name = "portfolio", description = "Portfolio returns and wallet transfers"),
+        (name = "analytics", description = "Execution quality per venue"),
+        (name = "risk", description = "Per-pair kill switches and portfolio stress tests"),
+        (name = "reports", description = "P&L attribution, balance reconciliation and daily performance reports"),
+        (name = "monitoring", description = "Market data quality, gaps and retention"),
+        (name = "ingest", description = "Bulk market data from external vendors"),
+        (name = "system", description = "Build, uptime and activity of the running instance"),
+        (name = "events", description = "Economic event calendar tightening risk limits"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Registers the JWT bearer scheme every endpoint requires
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                BEARER_SCHEME,
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .bearer_format("JWT")
+                        .build(),
+                ),
+            );
+        }
+    }
+}
+
+/// Serves the OpenAPI document
+#[tracing::instrument]
+pub async fn get_openapi() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use axum::routing::{get, post};
+    use axum::{Extension, Router};
+    use parking_lot::Mutex;
+    use rust_decimal_macros::dec;
+    use serde_json::{json, Value};
+    use tokio::sync::RwLock;
+
+    use crate::api::AppState;
+    use crate::performance::{PerformanceConfig, PerformanceError, PerformanceService, PerformanceStore};
+    use crate::supervision::{StrategySupervisor, SupervisionConfig};
+
+    const SNAPSHOT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/openapi.json");
+
+    #[derive(Default)]
+    struct MemoryStore {
+        trades: Mutex<Vec<StrategyTrade>>,
+    }
+
+    #[async_trait]
+    impl PerformanceStore for MemoryStore {
+        async fn record_trade(&self, trade: &StrategyTrade) -> Result<(), PerformanceError> {
+            self.trades.lock().push(trade.clone());
+            Ok(())
+        }
+
+        async fn trades(&self, strategy_id: &str, limit: usize) -> Result<Vec<StrategyTrade>, PerformanceError> {
+            let trades = self.trades.lock();
+            Ok(trades.iter().rev().filter(|t| t.strategy_id == strategy_id).take(limit).cloned().collect())
+        }
+
+        async fn upsert_equity(&self, _points: &[EquityPoint]) -> Result<(), PerformanceError> {
+            Ok(())
+        }
+
+        async fn latest_equity(&self, _strategy_id: &str) -> Result<Vec<EquityPoint>, PerformanceError> {
+            Ok(Vec::new())
+        }
+
+        async fn equity(
+            &self,
+            _strategy_id: &str,
+            _granularity: CandleInterval,
+            _limit: usize,
+        ) -> Result<Vec<EquityPoint>, PerformanceError> {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Minimal client driven entirely by the served spec, as a generated SDK would be:
+    /// operations are looked up by ID and responses checked against their declared schema
+    struct SpecClient {
+        base_url: String,
+        spec: Value,
+        http: reqwest::Client,
+    }
+
+    impl SpecClient {
+        async fn connect(base_url: String) -> Self {
+            let http = reqwest::Client::new();
+            let spec = http
+                .get(format!("{}{}", base_url, OPENAPI_PATH))
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            Self { base_url, spec, http }
+        }
+
+        fn operation(&self, operation_id: &str) -> (String, String, &Value) {
+            for (path, item) in self.spec["paths"].as_object().unwrap() {
+                for (method, operation) in item.as_object().unwrap() {
+                    if operation["operationId"] == operation_id {
+                        return (method.clone(), path.clone(), operation);
+                    }
+                }
+            }
+            panic!("operation {} missing from spec", operation_id);
+        }
+
+        async fn call(
+            &self,
+            operation_id: &str,
+            path_params: &[(&str, &str)],
+            query: &[(&str, &str)],
+            body: Option<Value>,
+        ) -> (u16, Value) {
+            let (method, mut path, operation) = self.operation(operation_id);
+            for (name, value) in path_params {
+                path = path.replace(&format!("{{{}}}", name), value);
+            }
+            let method = reqwest::Method::from_bytes(method.to_uppercase().as_bytes()).unwrap();
+            let mut request = self.http.request(method, format!("{}{}", self.base_url, path)).query(query);
+            if let Some(body) = body {
+                request = request.json(&body);
+            }
+            let response = request.send().await.unwrap();
+            let status = response.status().as_u16();
+            let body: Value = response.json().await.unwrap();
+
+            let schema = &operation["responses"][status.to_string()]["content"]["application/json"]["schema"];
+            assert!(!schema.is_null(), "{} returned undeclared status {}", operation_id, status);
+            self.check(&body, schema, operation_id);
+            (status, body)
+        }
+
+        fn resolve<'a>(&'a self, schema: &'a Value) -> &'a Value {
+            match schema["$ref"].as_str() {
+                Some(reference) => {
+                    let name = reference.trim_start_matches("#/components/schemas/");
+                    self.resolve(&self.spec["components"]["schemas"][name])
+                }
+                None => schema,
+            }
+        }
+
+        fn check(&self, value: &Value, schema: &Value, at: &str) {
+            let schema = self.resolve(schema);
+            if value.is_null() && schema["nullable"] == true {
+                return;
+            }
+            if let Some(all_of) = schema["allOf"].as_array() {
+                all_of.iter().for_each(|part| self.check(value, part, at));
+                return;
+            }
+            match schema["type"].as_str() {
+                Some("object") => {
+                    let object = value.as_object().unwrap_or_else(|| panic!("{} is not an object", at));
+                    for required in schema["required"].as_array().into_iter().flatten() {
+                        let name = required.as_str().unwrap();
+                        assert!(object.contains_key(name), "{} is missing required {}", at, name);
+                    }
+                    for (name, property) in schema["properties"].as_object().into_iter().flatten() {
+                        if let Some(field) = object.get(name) {
+                            self.check(field, property, &format!("{}.{}", at, name));
+                        }
+                    }
+                }
+                Some("array") => {
+                    let items = value.as_array().unwrap_or_else(|| panic!("{} is not an array", at));
+                    items.iter().for_each(|item| self.check(item, &schema["items"], at));
+                }
+                Some("string") => assert!(value.is_string(), "{} is not a string: {}", at, value),
+                Some("integer") => assert!(value.is_i64() || value.is_u64(), "{} is not an integer", at),
+                Some("number") => assert!(value.is_number(), "{} is not a number", at),
+                Some("boolean") => assert!(value.is_boolean(), "{} is not a boolean", at),
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn test_spec_matches_snapshot() {
+        let spec = ApiDoc::openapi().to_pretty_json().unwrap();
+        if std::env::var_os("UPDATE_OPENAPI_SNAPSHOT").is_some() {
+            std::fs::write(SNAPSHOT_PATH, format!("{}\n", spec)).unwrap();
+            return;
+        }
+
+        let snapshot = std::fs::read_to_string(SNAPSHOT_PATH).expect("committed OpenAPI snapshot");
+        let expected: Value = serde_json::from_str(&snapshot).unwrap();
+        let actual: Value = serde_json::from_str(&spec).unwrap();
+        assert!(
+            expected == actual,
+            "OpenAPI spec no longer matches {}; review the change and rerun with UPDATE_OPENAPI_SNAPSHOT=1",
+            SNAPSHOT_PATH
+        );
+    }
+
+    #[test]
+    fn test_decimals_documented_as_strings() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let schemas = &spec["components"]["schemas"];
+        assert_eq!(schemas["StrategyTrade"]["properties"]["realized_pnl"]["type"], "string");
+        assert_eq!(schemas["Bps"]["type"], "string");
+        assert_eq!(spec["components"]["securitySchemes"][BEARER_SCHEME]["scheme"], "bearer");
+    }
+
+    #[tokio::test]
+    async fn test_spec_client_round_trips_strategy_create_and_trade_list() {
+        let strategies = Arc::new(RwLock::new(HashMap::new()));
+        let supervisor = Arc::new(StrategySupervisor::new(SupervisionConfig::default(), strategies.clone()));
+        let performance = Arc::new(PerformanceService::new(PerformanceConfig::default(), strategies));
+        performance.set_store(Arc::new(MemoryStore::default()));
+        let state = AppState::new(
+            crate::config::Config::default(),
+            redis::Client::open("redis://localhost").unwrap(),
+            crate::utils::metrics::MetricsCollector::new().unwrap(),
+        )
+        .with_strategy_supervisor(supervisor)
+        .with_performance(performance.clone());
+
+        let app = Router::new()
+            .route(OPENAPI_PATH, get(get_openapi))
+            .route("/api/v1/strategies", post(endpoints::create_strategy))
+            .route("/api/v1/strategies/:id/trades", get(endpoints::list_strategy_trades))
+            .layer(Extension(Arc::new(state)));
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(axum::Server::from_tcp(listener).unwrap().serve(app.into_make_service()));
+
+        let client = SpecClient::connect(base_url).await;
+        let request = json!({
+            "strategy_id": "grid-sol",
+            "strategy_type": "GRID",
+            "parameters": {
+                "position_size_bps": "1000",
+                "grid_levels": 10,
+                "stop_loss_pct": "-5",
+                "take_profit_pct": "1",
+                "max_slippage_bps": "100",
+                "exchanges": ["jupiter"],
+                "risk_factor": "0.5"
+            },
+            "trading_pairs": ["sol-usdc"]
+        });
+        let (status, created) = client.call("create_strategy", &[], &[], Some(request.clone())).await;
+        assert_eq!(status, 201);
+        let snapshot: StrategySnapshot = serde_json::from_value(created).unwrap();
+        assert_eq!(snapshot.strategy_id, "grid-sol");
+        assert_eq!(snapshot.state, StrategyState::Inactive);
+        assert_eq!(snapshot.trading_pairs, vec!["SOL/USDC".to_string()]);
+
+        let (status, error) = client.call("create_strategy", &[], &[], Some(request)).await;
+        assert_eq!(status, 400);
+        assert!(error["error"].as_str().unwrap().contains("already registered"));
+
+        let trade = StrategyTrade {
+            id: uuid::Uuid::new_v4(),
+            strategy_id: "grid-sol".to_string(),
+            trading_pair: "SOL/USDC".to_string(),
+            realized_pnl: dec!(12.5),
+            closed_at: chrono::Utc::now(),
+            version: None,
+        };
+        performance.record_trade(&trade).await.unwrap();
+
+        let (status, listed) = client
+            .call("list_strategy_trades", &[("id", "grid-sol")], &[("limit", "10")], None)
+            .await;
+        assert_eq!(status, 200);
+        assert_eq!(listed["trades"][0]["realized_pnl"], "12.5");
+        let trades: Vec<StrategyTrade> = serde_json::from_value(listed["trades"].clone()).unwrap();
+        assert_eq!(trades, vec![trade]);
+
+        let (status, _) = client.call("list_strategy_trades", &[("id", "missing")], &[], None).await;
+        assert_eq!(status, 404);
+    }
+}
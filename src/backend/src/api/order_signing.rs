@@ -0,0 +1,381 @@
+//! Wallet signature authorization for high-value orders. Above the configured notional an
+//! order needs, besides a valid JWT, a fresh Ed25519 signature from the token's wallet over
+//! the order's canonical form. Each signature carries a single-use nonce and an expiry.
+//!
+//! Version dependencies:
+//! - redis = "0.23"
+//! - rust_decimal = "1.30"
+//! - ed25519-dalek = "1.0"
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use metrics::counter; // v0.20.1
+use redis::Client as RedisClient; // v0.23.0
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal; // v1.30.0
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn; // v0.1.37
+
+use crate::config::security::OrderSigningConfig;
+use crate::risk_manager::exposure::TradeSide;
+use crate::utils::crypto::verify_wallet_signature;
+
+// Canonical message constants
+const CANONICAL_PREFIX: &str = "firebot-order:v1";
+const NONCE_KEY_PREFIX: &str = "order_nonce:";
+const MAX_NONCE_LENGTH: usize = 128;
+
+/// Signature block attached to an order request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderAuthorization {
+    /// Client-chosen single-use value
+    pub nonce: String,
+    /// Unix seconds after which the signature is no longer accepted
+    pub expires_at: i64,
+    /// Base58 Ed25519 signature over the canonical order message
+    pub signature: String,
+}
+
+/// Order fields covered by the signature
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedOrder {
+    pub trading_pair: String,
+    pub side: TradeSide,
+    pub size: Decimal,
+    pub price: Decimal,
+}
+
+impl SignedOrder {
+    /// Builds the signed fields from request values; floats are rounded to their shortest decimal form
+    pub fn from_request(trading_pair: &str, side: TradeSide, size: f64, price: f64) -> Option<Self> {
+        Some(Self {
+            trading_pair: trading_pair.to_string(),
+            side,
+            size: Decimal::from_f64(size)?,
+            price: Decimal::from_f64(price)?,
+        })
+    }
+
+    pub fn notional(&self) -> Decimal {
+        self.size * self.price
+    }
+}
+
+/// Canonical message a wallet signs for an order: fixed field order, one `key=value` per line,
+/// pair upper-cased with `/`, side upper-cased and decimals without trailing zeros
+pub fn canonical_order_message(order: &SignedOrder, nonce: &str, expires_at: i64) -> String {
+    let side = match order.side {
+        TradeSide::Buy => "BUY",
+        TradeSide::Sell => "SELL",
+    };
+    format!(
+        "{}\npair={}\nside={}\nsize={}\nprice={}\nnonce={}\nexpiry={}",
+        CANONICAL_PREFIX,
+        order.trading_pair.replace('-', "/").to_uppercase(),
+        side,
+        normalize_decimal(order.size),
+        normalize_decimal(order.price),
+        nonce,
+        expires_at,
+    )
+}
+
+/// Drops trailing zeros and the sign of zero so equal amounts always print the same
+fn normalize_decimal(value: Decimal) -> String {
+    let normalized = value.normalize();
+    if normalized.is_zero() {
+        "0".to_string()
+    } else {
+        normalized.to_string()
+    }
+}
+
+/// Order signature failures, each with a stable error code for clients
+#[derive(Debug, Error)]
+pub enum OrderSignatureError {
+    #[error("order notional {0} requires a wallet signature")]
+    Missing(Decimal),
+    #[error("invalid order signature: {0}")]
+    Invalid(String),
+    #[error("order signature expired: {0}")]
+    Expired(String),
+    #[error("order nonce already used: {0}")]
+    Replayed(String),
+    #[error("nonce store unavailable: {0}")]
+    Store(String),
+}
+
+impl OrderSignatureError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Missing(_) => "order_signature_required",
+            Self::Invalid(_) => "order_signature_invalid",
+            Self::Expired(_) => "order_signature_expired",
+            Self::Replayed(_) => "order_nonce_replayed",
+            Self::Store(_) => "order_signature_unavailable",
+        }
+    }
+}
+
+/// Records used order nonces until their signatures expire
+#[async_trait]
+pub trait NonceStore: Send + Sync {
+    /// Marks the nonce used; false if it already was
+    async fn claim(&self, wallet: &str, nonce: &str, ttl: Duration) -> Result<bool, String>;
+}
+
+/// Nonce store backed by Redis `SET NX EX`
+pub struct RedisNonceStore {
+    client: Arc<RedisClient>,
+}
+
+impl RedisNonceStore {
+    pub fn new(client: Arc<RedisClient>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl NonceStore for RedisNonceStore {
+    async fn claim(&self, wallet: &str, nonce: &str, ttl: Duration) -> Result<bool, String> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| e.to_string())?;
+        let set: Option<String> = redis::cmd("SET")
+            .arg(format!("{}{}:{}", NONCE_KEY_PREFIX, wallet, nonce))
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(set.is_some())
+    }
+}
+
+/// Enforces wallet signatures on orders above the configured notional
+pub struct OrderSignatureVerifier {
+    config: OrderSigningConfig,
+    nonces: Arc<dyn NonceStore>,
+}
+
+impl std::fmt::Debug for OrderSignatureVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OrderSignatureVerifier")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OrderSignatureVerifier {
+    pub fn new(config: OrderSigningConfig, nonces: Arc<dyn NonceStore>) -> Self {
+        Self { config, nonces }
+    }
+
+    /// True when the order must carry a signature; unpriced orders always do
+    pub fn requires_signature(&self, order: &SignedOrder) -> bool {
+        order.price.is_zero() || order.notional() > self.config.signature_threshold
+    }
+
+    /// Checks the order's signature when its notional calls for one, consuming the nonce on success
+    pub async fn authorize(
+        &self,
+        wallet: &str,
+        order: &SignedOrder,
+        authorization: Option<&OrderAuthorization>,
+        now: i64,
+    ) -> Result<(), OrderSignatureError> {
+        if !self.requires_signature(order) {
+            return Ok(());
+        }
+
+        let result = self.verify(wallet, order, authorization, now).await;
+        if let Err(e) = &result {
+            counter!("api.orders.signature_rejections", "code" => e.code()).increment(1);
+            warn!(wallet, code = e.code(), "High-value order signature rejected: {}", e);
+        }
+        result
+    }
+
+    async fn verify(
+        &self,
+        wallet: &str,
+        order: &SignedOrder,
+        authorization: Option<&OrderAuthorization>,
+        now: i64,
+    ) -> Result<(), OrderSignatureError> {
+        let auth = authorization.ok_or_else(|| OrderSignatureError::Missing(order.notional()))?;
+        if auth.nonce.is_empty() || auth.nonce.len() > MAX_NONCE_LENGTH {
+            return Err(OrderSignatureError::Invalid(format!(
+                "nonce must be 1 to {} characters",
+                MAX_NONCE_LENGTH
+            )));
+        }
+
+        if auth.expires_at <= now {
+            return Err(OrderSignatureError::Expired(format!("expired at {}", auth.expires_at)));
+        }
+        let max_ttl = self.config.max_signature_ttl.as_secs() as i64;
+        if auth.expires_at - now > max_ttl {
+            return Err(OrderSignatureError::Expired(format!(
+                "expiry more than {}s ahead",
+                max_ttl
+            )));
+        }
+
+        // Verify before touching the nonce so forged requests cannot burn it
+        let message = canonical_order_message(order, &auth.nonce, auth.expires_at);
+        match verify_wallet_signature(message, auth.signature.clone(), wallet.to_string()) {
+            Ok(true) => {}
+            Ok(false) => {
+                return Err(OrderSignatureError::Invalid(
+                    "signature does not match order and wallet".to_string(),
+                ))
+            }
+            Err(e) => return Err(OrderSignatureError::Invalid(e)),
+        }
+
+        // The nonce only needs remembering while the signature could still be accepted
+        let ttl = Duration::from_secs((auth.expires_at - now) as u64);
+        if !self
+            .nonces
+            .claim(wallet, &auth.nonce, ttl)
+            .await
+            .map_err(OrderSignatureError::Store)?
+        {
+            return Err(OrderSignatureError::Replayed(auth.nonce.clone()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base58::ToBase58;
+    use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
+    use parking_lot::Mutex;
+    use rust_decimal_macros::dec;
+    use std::collections::HashSet;
+
+    const NOW: i64 = 1_700_000_000;
+
+    #[derive(Default)]
+    struct MemoryNonces(Mutex<HashSet<String>>);
+
+    #[async_trait]
+    impl NonceStore for MemoryNonces {
+        async fn claim(&self, wallet: &str, nonce: &str, _ttl: Duration) -> Result<bool, String> {
+            Ok(self.0.lock().insert(format!("{}:{}", wallet, nonce)))
+        }
+    }
+
+    fn keypair(seed: u8) -> Keypair {
+        let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+        let public = PublicKey::from(&secret);
+        Keypair { secret, public }
+    }
+
+    fn wallet(keypair: &Keypair) -> String {
+        keypair.public.as_bytes().to_base58()
+    }
+
+    fn order() -> SignedOrder {
+        SignedOrder {
+            trading_pair: "SOL/USDC".to_string(),
+            side: TradeSide::Buy,
+            size: dec!(500),
+            price: dec!(25.10),
+        }
+    }
+
+    fn sign(keypair: &Keypair, order: &SignedOrder, nonce: &str, expires_at: i64) -> OrderAuthorization {
+        let message = canonical_order_message(order, nonce, expires_at);
+        OrderAuthorization {
+            nonce: nonce.to_string(),
+            expires_at,
+            signature: keypair.sign(message.as_bytes()).to_bytes().to_base58(),
+        }
+    }
+
+    fn verifier() -> OrderSignatureVerifier {
+        OrderSignatureVerifier::new(OrderSigningConfig::default(), Arc::new(MemoryNonces::default()))
+    }
+
+    #[test]
+    fn test_canonical_message_is_stable() {
+        let mut respelled = order();
+        respelled.trading_pair = "sol-usdc".to_string();
+        respelled.size = dec!(500.000);
+        respelled.price = dec!(25.1);
+
+        let message = canonical_order_message(&order(), "n1", NOW);
+        assert_eq!(message, canonical_order_message(&respelled, "n1", NOW));
+        assert_eq!(
+            message,
+            "firebot-order:v1\npair=SOL/USDC\nside=BUY\nsize=500\nprice=25.1\nnonce=n1\nexpiry=1700000000"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_signed_high_value_order_accepted() {
+        let signer = keypair(7);
+        let auth = sign(&signer, &order(), "n1", NOW + 60);
+
+        verifier().authorize(&wallet(&signer), &order(), Some(&auth), NOW).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_low_value_order_needs_no_signature() {
+        let small = SignedOrder { size: dec!(1), ..order() };
+        verifier().authorize("any", &small, None, NOW).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_missing_signature_rejected() {
+        let err = verifier().authorize("any", &order(), None, NOW).await.unwrap_err();
+        assert_eq!(err.code(), "order_signature_required");
+    }
+
+    #[tokio::test]
+    async fn test_bad_signature_rejected() {
+        let signer = keypair(7);
+        let auth = sign(&signer, &order(), "n1", NOW + 60);
+
+        // Signature over a different size
+        let tampered = SignedOrder { size: dec!(5000), ..order() };
+        let err = verifier().authorize(&wallet(&signer), &tampered, Some(&auth), NOW).await.unwrap_err();
+        assert_eq!(err.code(), "order_signature_invalid");
+
+        // Signature from another wallet
+        let err = verifier().authorize(&wallet(&keypair(9)), &order(), Some(&auth), NOW).await.unwrap_err();
+        assert_eq!(err.code(), "order_signature_invalid");
+    }
+
+    #[tokio::test]
+    async fn test_expired_signature_rejected() {
+        let signer = keypair(7);
+        let verifier = verifier();
+
+        let stale = sign(&signer, &order(), "n1", NOW - 1);
+        let err = verifier.authorize(&wallet(&signer), &order(), Some(&stale), NOW).await.unwrap_err();
+        assert_eq!(err.code(), "order_signature_expired");
+
+        let far = sign(&signer, &order(), "n2", NOW + 3600);
+        let err = verifier.authorize(&wallet(&signer), &order(), Some(&far), NOW).await.unwrap_err();
+        assert_eq!(err.code(), "order_signature_expired");
+    }
+
+    #[tokio::test]
+    async fn test_replayed_nonce_rejected() {
+        let signer = keypair(7);
+        let verifier = verifier();
+        let auth = sign(&signer, &order(), "n1", NOW + 60);
+
+        verifier.authorize(&wallet(&signer), &order(), Some(&auth), NOW).await.unwrap();
+        let err = verifier.authorize(&wallet(&signer), &order(), Some(&auth), NOW + 1).await.unwrap_err();
+        assert_eq!(err.code(), "order_nonce_replayed");
+    }
+}
@@ -0,0 +1,245 @@
+//! Request body size limits per route class and body read timeouts. Bodies are buffered
+//! before the handler runs, so oversized uploads and slowly dribbled bodies are rejected
+//! without ever reaching it. Streamed uploads such as bulk ingestion are passed through
+//! unbuffered and enforce their own limits as they read.
+//!
+//! Version dependencies:
+//! - axum = "0.6"
+//! - hyper = "0.14"
+//! - tokio = "1.28"
+
+use axum::{
+    body::{Body, Bytes},
+    http::{header::CONTENT_LENGTH, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+}; // v0.6.18
+use hyper::body::HttpBody; // v0.14.27
+use metrics::counter; // v0.20.1
+use tracing::warn; // v0.1.37
+
+use crate::api::endpoints::ApiError;
+use crate::config::security::RequestLimitsConfig;
+
+// Route classification constants
+const ADMIN_PATH_PREFIXES: &[&str] = &[
+    "/api/v1/admin",
+    "/api/v1/strategies",
+    "/api/v1/optimizations",
+    "/api/v1/webhooks",
+];
+const STREAMED_PATH_PREFIXES: &[&str] = &["/api/v1/ingest"];
+
+/// Route classes with separate body size limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteClass {
+    Trading,
+    Admin,
+}
+
+impl RouteClass {
+    /// Classifies a request path; anything outside admin and configuration routes is trading
+    pub fn for_path(path: &str) -> Self {
+        if ADMIN_PATH_PREFIXES.iter().any(|prefix| path.starts_with(prefix)) {
+            Self::Admin
+        } else {
+            Self::Trading
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Trading => "trading",
+            Self::Admin => "admin",
+        }
+    }
+
+    /// Largest body accepted for this class
+    pub fn body_limit(&self, limits: &RequestLimitsConfig) -> usize {
+        match self {
+            Self::Trading => limits.trading_body_limit,
+            Self::Admin => limits.admin_body_limit,
+        }
+    }
+}
+
+/// Reasons a body was not buffered
+enum BodyRejection {
+    TooLarge,
+    Failed(String),
+}
+
+/// Buffers the request body within its route class's size limit and the read timeout,
+/// answering 413 or 408 instead of running the handler
+pub async fn body_limit_middleware(
+    request: Request<Body>,
+    next: Next<Body>,
+    limits: RequestLimitsConfig,
+) -> Response {
+    let path = request.uri().path();
+    if STREAMED_PATH_PREFIXES.iter().any(|prefix| path.starts_with(prefix)) {
+        return next.run(request).await;
+    }
+    let class = RouteClass::for_path(request.uri().path());
+    let limit = class.body_limit(&limits);
+
+    // A declared length over the limit is rejected before reading anything
+    let declared_length = request
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    if declared_length.map_or(false, |length| length > limit as u64) {
+        return reject_oversized(class, limit);
+    }
+
+    let (parts, body) = request.into_parts();
+    let bytes = match tokio::time::timeout(limits.body_read_timeout, read_body(body, limit)).await {
+        Ok(Ok(bytes)) => bytes,
+        Ok(Err(BodyRejection::TooLarge)) => return reject_oversized(class, limit),
+        Ok(Err(BodyRejection::Failed(e))) => {
+            return ApiError::ValidationError(format!("failed to read request body: {}", e)).into_response();
+        }
+        Err(_) => {
+            counter!("api.request.body_timeouts", "route_class" => class.as_str()).increment(1);
+            warn!(route_class = class.as_str(), "Request body read timed out");
+            return ApiError::RequestTimeout(format!(
+                "request body not received within {}ms",
+                limits.body_read_timeout.as_millis()
+            ))
+            .into_response();
+        }
+    };
+
+    next.run(Request::from_parts(parts, Body::from(bytes))).await
+}
+
+/// Reads the body chunk by chunk, stopping as soon as it passes the limit
+async fn read_body(mut body: Body, limit: usize) -> Result<Bytes, BodyRejection> {
+    let mut buffered = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|e| BodyRejection::Failed(e.to_string()))?;
+        if buffered.len() + chunk.len() > limit {
+            return Err(BodyRejection::TooLarge);
+        }
+        buffered.extend_from_slice(&chunk);
+    }
+    Ok(Bytes::from(buffered))
+}
+
+fn reject_oversized(class: RouteClass, limit: usize) -> Response {
+    counter!("api.request.body_too_large", "route_class" => class.as_str()).increment(1);
+    warn!(route_class = class.as_str(), limit, "Request body too large");
+    ApiError::PayloadTooLarge(format!("request body exceeds {} bytes", limit)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware::from_fn, routing::post, Router};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    fn limited_router(limits: RequestLimitsConfig, handled: Arc<AtomicBool>) -> Router {
+        let handler = move || {
+            let handled = handled.clone();
+            async move {
+                handled.store(true, Ordering::SeqCst);
+                "ok"
+            }
+        };
+        Router::new()
+            .route("/api/v1/order", post(handler.clone()))
+            .route("/api/v1/admin/snapshots", post(handler.clone()))
+            .route("/api/v1/ingest/market-data", post(handler))
+            .layer(from_fn(move |req, next| body_limit_middleware(req, next, limits.clone())))
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_rejected_per_route_class() {
+        let handled = Arc::new(AtomicBool::new(false));
+        let router = limited_router(RequestLimitsConfig::default(), handled.clone());
+        let body = vec![b'x'; 64 * 1024 + 1];
+
+        let response = router
+            .clone()
+            .oneshot(Request::post("/api/v1/order").body(Body::from(body.clone())).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 413);
+        assert!(!handled.load(Ordering::SeqCst));
+
+        // The same body fits the admin limit
+        let response = router
+            .oneshot(Request::post("/api/v1/admin/snapshots").body(Body::from(body)).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(handled.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_streamed_ingest_body_not_buffered() {
+        let handled = Arc::new(AtomicBool::new(false));
+        let router = limited_router(RequestLimitsConfig::default(), handled.clone());
+
+        // Well over the admin limit; the ingest handler applies its own
+        let response = router
+            .oneshot(
+                Request::post("/api/v1/ingest/market-data")
+                    .body(Body::from(vec![b'x'; 2 * 1024 * 1024]))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(handled.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_declared_length_rejected_before_read() {
+        let handled = Arc::new(AtomicBool::new(false));
+        let router = limited_router(RequestLimitsConfig::default(), handled.clone());
+
+        let response = router
+            .oneshot(
+                Request::post("/api/v1/order")
+                    .header(CONTENT_LENGTH, 50 * 1024 * 1024)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 413);
+        assert!(!handled.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_slow_body_times_out() {
+        let handled = Arc::new(AtomicBool::new(false));
+        let limits = RequestLimitsConfig {
+            body_read_timeout: Duration::from_millis(50),
+            ..RequestLimitsConfig::default()
+        };
+        let router = limited_router(limits, handled.clone());
+
+        // Sends the first chunk, then stalls well past the read timeout
+        let chunks = futures::stream::unfold(false, |sent| async move {
+            if sent {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                None
+            } else {
+                Some((Ok::<_, std::io::Error>(Bytes::from_static(b"{\"size\":")), true))
+            }
+        });
+
+        let response = router
+            .oneshot(Request::post("/api/v1/order").body(Body::wrap_stream(chunks)).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 408);
+        assert!(!handled.load(Ordering::SeqCst));
+    }
+}
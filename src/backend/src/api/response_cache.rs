@@ -0,0 +1,752 @@
+//! Response cache for read-heavy dashboard routes. GET responses on an allowlist of routes
+//! are cached in memory, optionally backed by Redis so instances share entries, under a key
+//! of route, path parameter, normalized query and caller: routes serving private data key
+//! every entry to the authenticated wallet or scoped token, so no entry is shared between
+//! callers. Entries expire on a per-route TTL and are dropped early when their producer
+//! publishes new data: a candle close clears that pair's candle responses and a leaderboard
+//! recompute clears the strategy performance routes. Responses carry an ETag and a
+//! Cache-Control header, and a matching `If-None-Match` is answered with 304.
+//!
+//! Version dependencies:
+//! - axum = "0.6"
+//! - hyper = "0.14"
+//! - redis = "0.23"
+//! - lru = "0.7"
+//! - sha2 = "0.10"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use axum::{
+    body::{boxed, Body, Full},
+    http::{header, HeaderMap, HeaderValue, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+}; // v0.6.18
+use lru::LruCache;
+use metrics::counter; // v0.20.1
+use parking_lot::Mutex;
+use redis::{AsyncCommands, Client as RedisClient}; // v0.23.0
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::broadcast;
+use tracing::warn; // v0.1.37
+
+use crate::api::auth::Claims;
+use crate::api::scoped_tokens::ScopedAccess;
+use crate::data_collector::ohlcv::{CandleEvent, CandleInterval};
+use crate::models::pair::TradingPair;
+use crate::performance::LeaderboardEntry;
+
+// Response cache constants
+pub const CACHE_STATUS_HEADER: &str = "x-cache";
+const REDIS_KEY_PREFIX: &str = "response_cache:";
+const DEFAULT_MAX_ENTRIES: usize = 10_000;
+const DEFAULT_MIN_CANDLE_INTERVAL: CandleInterval = CandleInterval::FiveMinutes;
+const ETAG_BYTES: usize = 16;
+/// Stands in for the path parameter of routes without one
+const NO_PARAM: &str = "-";
+
+/// Routes whose GET responses may be cached; every other route, and every other method,
+/// always reaches its handler
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CachedRoute {
+    Candles,
+    StrategyPerformance,
+    StrategyEquity,
+    StrategyTrades,
+    PortfolioPerformance,
+    SystemInfo,
+}
+
+impl CachedRoute {
+    pub const ALL: [CachedRoute; 6] = [
+        Self::Candles,
+        Self::StrategyPerformance,
+        Self::StrategyEquity,
+        Self::StrategyTrades,
+        Self::PortfolioPerformance,
+        Self::SystemInfo,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Candles => "candles",
+            Self::StrategyPerformance => "strategy_performance",
+            Self::StrategyEquity => "strategy_equity",
+            Self::StrategyTrades => "strategy_trades",
+            Self::PortfolioPerformance => "portfolio_performance",
+            Self::SystemInfo => "system_info",
+        }
+    }
+
+    /// Path pattern, where a `:name` segment matches any one non-empty segment
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            Self::Candles => "/api/v1/markets/:pair/candles",
+            Self::StrategyPerformance => "/api/v1/strategies/performance",
+            Self::StrategyEquity => "/api/v1/strategies/:id/equity",
+            Self::StrategyTrades => "/api/v1/strategies/:id/trades",
+            Self::PortfolioPerformance => "/api/v1/portfolio/performance",
+            Self::SystemInfo => "/api/v1/system/info",
+        }
+    }
+
+    /// Whether responses depend on the caller; such entries are keyed per wallet or token
+    pub fn is_private(&self) -> bool {
+        !matches!(self, Self::Candles | Self::SystemInfo)
+    }
+
+    fn default_ttl(&self) -> Duration {
+        match self {
+            Self::Candles | Self::StrategyEquity => Duration::from_secs(30),
+            Self::StrategyPerformance | Self::StrategyTrades => Duration::from_secs(15),
+            Self::PortfolioPerformance => Duration::from_secs(10),
+            Self::SystemInfo => Duration::from_secs(5),
+        }
+    }
+
+    /// Matches a request path, yielding the path parameter ("-" when the pattern has none)
+    fn matches(&self, path: &str) -> Option<String> {
+        let segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+        let pattern: Vec<&str> = self.pattern().split('/').collect();
+        if pattern.len() != segments.len() {
+            return None;
+        }
+
+        let mut param = NO_PARAM.to_string();
+        for (expected, actual) in pattern.iter().zip(&segments) {
+            if expected.starts_with(':') {
+                if actual.is_empty() {
+                    return None;
+                }
+                param = actual.to_string();
+            } else if expected != actual {
+                return None;
+            }
+        }
+        // Pair spellings share entries; unparseable pairs are left to the handler to reject
+        match self {
+            Self::Candles => TradingPair::parse(&param).ok().map(TradingPair::into_string),
+            _ => Some(param),
+        }
+    }
+}
+
+/// Per-route TTLs and cache limits
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseCacheConfig {
+    pub enabled: bool,
+    /// Per-route TTL overrides; a zero TTL turns caching off for that route
+    pub ttls: HashMap<CachedRoute, Duration>,
+    /// In-memory entries held before the least recently used is evicted
+    pub max_entries: usize,
+    /// Candle responses finer than this change too often to cache
+    pub min_candle_interval: CandleInterval,
+    /// Shares entries across instances through Redis behind the in-memory tier
+    pub redis_tier: bool,
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttls: HashMap::new(),
+            max_entries: DEFAULT_MAX_ENTRIES,
+            min_candle_interval: DEFAULT_MIN_CANDLE_INTERVAL,
+            redis_tier: false,
+        }
+    }
+}
+
+impl ResponseCacheConfig {
+    pub fn ttl(&self, route: CachedRoute) -> Duration {
+        self.ttls.get(&route).copied().unwrap_or_else(|| route.default_ttl())
+    }
+}
+
+/// A cached 200 response body with its validator
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedEntry {
+    pub body: String,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    /// Strong validator derived from the body
+    pub etag: String,
+}
+
+impl CachedEntry {
+    pub fn new(body: String, content_type: Option<String>) -> Self {
+        let digest = Sha256::digest(body.as_bytes());
+        let etag = format!("\"{}\"", hex::encode(&digest[..ETAG_BYTES]));
+        Self {
+            body,
+            content_type,
+            etag,
+        }
+    }
+}
+
+/// Shared cache tier behind the in-memory one
+#[async_trait]
+pub trait CacheTier: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<CachedEntry>, String>;
+    async fn put(&self, key: &str, entry: &CachedEntry, ttl: Duration) -> Result<(), String>;
+    /// Removes every entry whose key starts with the prefix
+    async fn invalidate(&self, prefix: &str) -> Result<(), String>;
+}
+
+/// Cache tier in Redis, expiring entries with `SET EX`
+pub struct RedisCacheTier {
+    client: Arc<RedisClient>,
+}
+
+impl RedisCacheTier {
+    pub fn new(client: Arc<RedisClient>) -> Self {
+        Self { client }
+    }
+
+    fn redis_key(key: &str) -> String {
+        format!("{}{}", REDIS_KEY_PREFIX, key)
+    }
+}
+
+#[async_trait]
+impl CacheTier for RedisCacheTier {
+    async fn get(&self, key: &str) -> Result<Option<CachedEntry>, String> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| e.to_string())?;
+        let value: Option<String> = conn.get(Self::redis_key(key)).await.map_err(|e| e.to_string())?;
+        value
+            .map(|value| serde_json::from_str(&value).map_err(|e| e.to_string()))
+            .transpose()
+    }
+
+    async fn put(&self, key: &str, entry: &CachedEntry, ttl: Duration) -> Result<(), String> {
+        let value = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+        let mut conn = self.client.get_async_connection().await.map_err(|e| e.to_string())?;
+        redis::cmd("SET")
+            .arg(Self::redis_key(key))
+            .arg(value)
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .query_async::<_, ()>(&mut conn)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn invalidate(&self, prefix: &str) -> Result<(), String> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| e.to_string())?;
+        let pattern = format!("{}*", Self::redis_key(prefix));
+        let keys: Vec<String> = {
+            let mut iter = conn.scan_match::<_, String>(pattern).await.map_err(|e| e.to_string())?;
+            let mut keys = Vec::new();
+            while let Some(key) = iter.next_item().await {
+                keys.push(key);
+            }
+            keys
+        };
+        if keys.is_empty() {
+            return Ok(());
+        }
+        conn.del::<_, ()>(keys).await.map_err(|e| e.to_string())
+    }
+}
+
+/// Two-tier response cache with per-route TTLs and producer-driven invalidation
+pub struct ResponseCache {
+    config: ResponseCacheConfig,
+    memory: Mutex<LruCache<String, (CachedEntry, Instant)>>,
+    tier: Option<Arc<dyn CacheTier>>,
+}
+
+impl std::fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("config", &self.config)
+            .field("tiered", &self.tier.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new(ResponseCacheConfig::default())
+    }
+}
+
+impl ResponseCache {
+    pub fn new(config: ResponseCacheConfig) -> Self {
+        let memory = Mutex::new(LruCache::new(config.max_entries));
+        Self {
+            config,
+            memory,
+            tier: None,
+        }
+    }
+
+    /// Backs the in-memory tier with a shared one
+    pub fn with_tier(mut self, tier: Arc<dyn CacheTier>) -> Self {
+        self.tier = Some(tier);
+        self
+    }
+
+    pub fn config(&self) -> &ResponseCacheConfig {
+        &self.config
+    }
+
+    /// Route and cache key for a request, or None when the request must reach its handler:
+    /// non-GET methods, routes off the allowlist, zero TTLs, fine-grained candles and
+    /// private routes without an authenticated caller
+    pub fn key(&self, request: &Request<Body>) -> Option<(CachedRoute, String)> {
+        if !self.config.enabled || request.method() != Method::GET {
+            return None;
+        }
+        let path = request.uri().path();
+        let (route, param) = CachedRoute::ALL
+            .iter()
+            .find_map(|route| route.matches(path).map(|param| (*route, param)))?;
+        if self.config.ttl(route).is_zero() {
+            return None;
+        }
+
+        let query = normalized_query(request.uri().query());
+        if route == CachedRoute::Candles {
+            let interval = query
+                .iter()
+                .find(|(name, _)| name == "interval")
+                .and_then(|(_, value)| value.parse::<CandleInterval>().ok())?;
+            if interval < self.config.min_candle_interval {
+                return None;
+            }
+        }
+
+        let scope = if route.is_private() {
+            caller_scope(request)?
+        } else {
+            "public".to_string()
+        };
+        let query = query
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("&");
+        let query_hash = hex::encode(&Sha256::digest(query.as_bytes())[..ETAG_BYTES]);
+
+        Some((route, format!("{}{}:{}", route_prefix(route, Some(&param)), scope, query_hash)))
+    }
+
+    /// Looks an entry up in memory, then in the shared tier
+    pub async fn get(&self, route: CachedRoute, key: &str) -> Option<CachedEntry> {
+        {
+            let mut memory = self.memory.lock();
+            match memory.get(key) {
+                Some((entry, expires_at)) if *expires_at > Instant::now() => return Some(entry.clone()),
+                Some(_) => {
+                    memory.pop(key);
+                }
+                None => {}
+            }
+        }
+
+        let tier = self.tier.as_ref()?;
+        match tier.get(key).await {
+            Ok(Some(entry)) => {
+                // The shared tier's remaining TTL is unknown; a full local TTL is bounded by it
+                let expires_at = Instant::now() + self.config.ttl(route);
+                self.memory.lock().put(key.to_string(), (entry.clone(), expires_at));
+                Some(entry)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                counter!("api.cache.tier_errors").increment(1);
+                warn!("Response cache tier lookup failed: {}", e);
+                None
+            }
+        }
+    }
+
+    pub async fn put(&self, route: CachedRoute, key: &str, entry: &CachedEntry) {
+        let ttl = self.config.ttl(route);
+        self.memory
+            .lock()
+            .put(key.to_string(), (entry.clone(), Instant::now() + ttl));
+        if let Some(tier) = &self.tier {
+            if let Err(e) = tier.put(key, entry, ttl).await {
+                counter!("api.cache.tier_errors").increment(1);
+                warn!("Response cache tier write failed: {}", e);
+            }
+        }
+    }
+
+    /// Drops every cached response of a route, or only those for one path parameter
+    pub async fn invalidate(&self, route: CachedRoute, param: Option<&str>) {
+        let prefix = route_prefix(route, param);
+        let removed = {
+            let mut memory = self.memory.lock();
+            let keys: Vec<String> = memory
+                .iter()
+                .filter(|(key, _)| key.starts_with(&prefix))
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in &keys {
+                memory.pop(key);
+            }
+            keys.len()
+        };
+        counter!("api.cache.invalidations", "route" => route.as_str()).increment(1);
+        counter!("api.cache.evicted", "route" => route.as_str()).increment(removed as u64);
+
+        if let Some(tier) = &self.tier {
+            if let Err(e) = tier.invalidate(&prefix).await {
+                counter!("api.cache.tier_errors").increment(1);
+                warn!("Response cache tier invalidation failed: {}", e);
+            }
+        }
+    }
+
+    /// Clears a pair's candle responses whenever one of its candles closes or is corrected
+    pub fn spawn_candle_listener(
+        self: Arc<Self>,
+        mut events: broadcast::Receiver<CandleEvent>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(CandleEvent::Closed { candle } | CandleEvent::Correction { candle }) => {
+                        self.invalidate(CachedRoute::Candles, Some(&candle.trading_pair)).await;
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        // Missed closes could be for any pair
+                        warn!("Response cache skipped {} candle events", skipped);
+                        self.invalidate(CachedRoute::Candles, None).await;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Clears the leaderboard, and a strategy's equity and trades, whenever its metrics are
+    /// recomputed
+    pub fn spawn_performance_listener(
+        self: Arc<Self>,
+        mut updates: broadcast::Receiver<LeaderboardEntry>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match updates.recv().await {
+                    Ok(entry) => {
+                        self.invalidate(CachedRoute::StrategyPerformance, None).await;
+                        self.invalidate(CachedRoute::StrategyEquity, Some(&entry.strategy_id)).await;
+                        self.invalidate(CachedRoute::StrategyTrades, Some(&entry.strategy_id)).await;
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Response cache skipped {} leaderboard updates", skipped);
+                        for route in [
+                            CachedRoute::StrategyPerformance,
+                            CachedRoute::StrategyEquity,
+                            CachedRoute::StrategyTrades,
+                        ] {
+                            self.invalidate(route, None).await;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+/// Key prefix shared by a route's entries, narrowed to one path parameter when given
+fn route_prefix(route: CachedRoute, param: Option<&str>) -> String {
+    match param {
+        Some(param) => format!("{}:{}:", route.as_str(), param),
+        None => format!("{}:", route.as_str()),
+    }
+}
+
+/// Query parameters sorted by name, so parameter order does not split entries
+fn normalized_query(query: Option<&str>) -> Vec<(String, String)> {
+    let mut pairs: Vec<(String, String)> = query
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (name.to_string(), value.to_string())
+        })
+        .collect();
+    pairs.sort();
+    pairs
+}
+
+/// Identity private entries are keyed to: the wallet of a JWT, or the id of a scoped token
+fn caller_scope(request: &Request<Body>) -> Option<String> {
+    if let Some(access) = request.extensions().get::<ScopedAccess>() {
+        return Some(format!("token:{}", access.token_id));
+    }
+    request
+        .extensions()
+        .get::<Claims>()
+        .map(|claims| format!("wallet:{}", claims.sub))
+}
+
+/// Whether the client asked to skip cached copies
+fn bypasses_cache(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|value| value.contains("no-cache") || value.contains("no-store"))
+}
+
+fn not_modified(if_none_match: Option<&HeaderValue>, etag: &str) -> bool {
+    if_none_match
+        .and_then(|value| value.to_str().ok())
+        .map_or(false, |value| {
+            value.split(',').any(|candidate| {
+                let candidate = candidate.trim();
+                candidate == "*" || candidate.trim_start_matches("W/") == etag
+            })
+        })
+}
+
+/// Builds the response for an entry, or a bare 304 when the client already holds it
+fn respond(
+    entry: CachedEntry,
+    route: CachedRoute,
+    ttl: Duration,
+    if_none_match: Option<&HeaderValue>,
+    status: &'static str,
+) -> Response {
+    let mut headers = HeaderMap::new();
+    let visibility = if route.is_private() { "private" } else { "public" };
+    if let Ok(value) = HeaderValue::from_str(&format!("{}, max-age={}", visibility, ttl.as_secs())) {
+        headers.insert(header::CACHE_CONTROL, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&entry.etag) {
+        headers.insert(header::ETAG, value);
+    }
+    headers.insert(CACHE_STATUS_HEADER, HeaderValue::from_static(status));
+    // Private responses vary by the bearer token behind them
+    if route.is_private() {
+        headers.insert(header::VARY, HeaderValue::from_static("authorization"));
+    }
+
+    if not_modified(if_none_match, &entry.etag) {
+        counter!("api.cache.not_modified", "route" => route.as_str()).increment(1);
+        return (StatusCode::NOT_MODIFIED, headers).into_response();
+    }
+    let content_type = entry
+        .content_type
+        .as_deref()
+        .and_then(|value| HeaderValue::from_str(value).ok())
+        .unwrap_or_else(|| HeaderValue::from_static("application/json"));
+    headers.insert(header::CONTENT_TYPE, content_type);
+    (StatusCode::OK, headers, entry.body).into_response()
+}
+
+/// Serves cacheable GET requests from the cache, storing successful responses on a miss.
+/// Runs inside authentication so private entries can be keyed to the caller.
+pub async fn response_cache_middleware(
+    request: Request<Body>,
+    next: Next<Body>,
+    cache: Arc<ResponseCache>,
+) -> Response {
+    let Some((route, key)) = cache.key(&request) else {
+        return next.run(request).await;
+    };
+    let ttl = cache.config().ttl(route);
+    let if_none_match = request.headers().get(header::IF_NONE_MATCH).cloned();
+
+    if !bypasses_cache(request.headers()) {
+        if let Some(entry) = cache.get(route, &key).await {
+            counter!("api.cache.hits", "route" => route.as_str()).increment(1);
+            return respond(entry, route, ttl, if_none_match.as_ref(), "HIT");
+        }
+    }
+    counter!("api.cache.misses", "route" => route.as_str()).increment(1);
+
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+    let (parts, body) = response.into_parts();
+    let bytes = match hyper::body::to_bytes(body).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("Failed to buffer response for caching: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let Ok(body) = String::from_utf8(bytes.to_vec()) else {
+        return Response::from_parts(parts, boxed(Full::from(bytes)));
+    };
+
+    let content_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let entry = CachedEntry::new(body, content_type);
+    cache.put(route, &key, &entry).await;
+    respond(entry, route, ttl, if_none_match.as_ref(), "MISS")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        middleware::from_fn,
+        routing::{get, post},
+        Router,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    const CANDLES: &str = "/api/v1/markets/SOL-USDC/candles?interval=1h";
+    const PERFORMANCE: &str = "/api/v1/portfolio/performance";
+
+    /// Routes standing in for the real handlers, counting every call that reaches them; the
+    /// test auth layer turns an `x-wallet` header into the caller's claims
+    fn cached_router(cache: Arc<ResponseCache>, calls: Arc<AtomicUsize>) -> Router {
+        let counted = |calls: Arc<AtomicUsize>| {
+            move |request: Request<Body>| {
+                let calls = calls.clone();
+                async move {
+                    let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                    let wallet = request
+                        .extensions()
+                        .get::<Claims>()
+                        .map(|claims| claims.sub.clone())
+                        .unwrap_or_default();
+                    axum::Json(serde_json::json!({ "call": n, "wallet": wallet }))
+                }
+            }
+        };
+        Router::new()
+            .route("/api/v1/markets/:pair/candles", get(counted(calls.clone())))
+            .route(PERFORMANCE, get(counted(calls.clone())))
+            .route("/api/v1/order", post(counted(calls)))
+            .layer(from_fn(move |req, next| response_cache_middleware(req, next, cache.clone())))
+            .layer(from_fn(|mut req: Request<Body>, next: Next<Body>| async move {
+                if let Some(wallet) = req.headers().get("x-wallet").and_then(|value| value.to_str().ok()) {
+                    let claims = Claims {
+                        sub: wallet.to_string(),
+                        exp: i64::MAX,
+                        iat: 0,
+                        device_id: None,
+                    };
+                    req.extensions_mut().insert(claims);
+                }
+                next.run(req).await
+            }))
+    }
+
+    async fn call(router: &Router, method: Method, uri: &str, wallet: Option<&str>) -> (Response, serde_json::Value) {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(wallet) = wallet {
+            request = request.header("x-wallet", wallet);
+        }
+        let response = router.clone().oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+        let (parts, body) = response.into_parts();
+        let bytes = hyper::body::to_bytes(body).await.unwrap();
+        let json = serde_json::from_slice(&bytes).unwrap_or_default();
+        (Response::from_parts(parts, boxed(Full::from(bytes))), json)
+    }
+
+    #[tokio::test]
+    async fn test_hit_served_without_reaching_handler() {
+        let cache = Arc::new(ResponseCache::default());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = cached_router(cache, calls.clone());
+
+        let (first, body) = call(&router, Method::GET, CANDLES, None).await;
+        assert_eq!(first.headers()[CACHE_STATUS_HEADER], "MISS");
+        assert_eq!(first.headers()[header::CACHE_CONTROL], "public, max-age=30");
+        let (second, cached) = call(&router, Method::GET, "/api/v1/markets/sol_usdc/candles?interval=1h", None).await;
+        assert_eq!(second.headers()[CACHE_STATUS_HEADER], "HIT");
+        assert_eq!(cached, body);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // A client holding the entry gets a bare 304
+        let etag = second.headers()[header::ETAG].clone();
+        let response = router
+            .clone()
+            .oneshot(
+                Request::get(CANDLES)
+                    .header(header::IF_NONE_MATCH, etag)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Fine-grained candles and mutating routes always reach their handler
+        call(&router, Method::GET, "/api/v1/markets/SOL-USDC/candles?interval=1m", None).await;
+        call(&router, Method::GET, "/api/v1/markets/SOL-USDC/candles?interval=1m", None).await;
+        call(&router, Method::POST, "/api/v1/order", Some("wallet-a")).await;
+        call(&router, Method::POST, "/api/v1/order", Some("wallet-a")).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_candle_close_invalidates_only_that_pair() {
+        let cache = Arc::new(ResponseCache::default());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = cached_router(cache.clone(), calls.clone());
+        let eth = "/api/v1/markets/ETH-USDC/candles?interval=1h";
+
+        call(&router, Method::GET, CANDLES, None).await;
+        call(&router, Method::GET, eth, None).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let (events, receiver) = broadcast::channel(8);
+        let listener = cache.clone().spawn_candle_listener(receiver);
+        let candle = crate::data_collector::ohlcv::Candle::carry_forward(
+            "SOL/USDC",
+            CandleInterval::OneHour,
+            chrono::Utc::now(),
+            rust_decimal::Decimal::ONE,
+        );
+        events.send(CandleEvent::Closed { candle }).unwrap();
+        drop(events);
+        listener.await.unwrap();
+
+        let (response, body) = call(&router, Method::GET, CANDLES, None).await;
+        assert_eq!(response.headers()[CACHE_STATUS_HEADER], "MISS");
+        assert_eq!(body["call"], 3);
+        let (response, _) = call(&router, Method::GET, eth, None).await;
+        assert_eq!(response.headers()[CACHE_STATUS_HEADER], "HIT");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_private_entries_isolated_per_wallet() {
+        let cache = Arc::new(ResponseCache::default());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = cached_router(cache, calls.clone());
+
+        let (response, a) = call(&router, Method::GET, PERFORMANCE, Some("wallet-a")).await;
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "private, max-age=10");
+        let (_, b) = call(&router, Method::GET, PERFORMANCE, Some("wallet-b")).await;
+        assert_eq!(a["wallet"], "wallet-a");
+        assert_eq!(b["wallet"], "wallet-b");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Each wallet hits only its own entry
+        let (response, again) = call(&router, Method::GET, PERFORMANCE, Some("wallet-a")).await;
+        assert_eq!(response.headers()[CACHE_STATUS_HEADER], "HIT");
+        assert_eq!(again, a);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Without a caller to key to, private routes are never cached
+        call(&router, Method::GET, PERFORMANCE, None).await;
+        call(&router, Method::GET, PERFORMANCE, None).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+}
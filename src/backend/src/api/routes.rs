@@ -2,26 +2,108 @@
 //! Version: 1.0.0
 
 use axum::{
-    routing::{get, post},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get, post, put},
+    Json,
     Router,
     Extension,
     middleware::{self, from_fn},
 }; // v0.6.18
 use tower::{ServiceBuilder, limit::RateLimitLayer}; // v0.4.13
+use utoipa::OpenApi; // v3.5
+use utoipa_swagger_ui::SwaggerUi; // v3.1
 use metrics::{counter, histogram}; // v0.20.1
+use serde_json::json;
 
 use std::sync::Arc;
 use std::time::Duration;
 
 use crate::api::endpoints::{
+    add_event,
+    calibrate_cost_models,
+    cancel_job,
+    cancel_maintenance,
+    cancel_optimization,
+    cancel_order,
+    cancel_orders,
+    close_token_accounts,
+    create_strategy,
+    create_webhook,
+    delete_strategy,
+    delete_webhook,
+    get_candles,
+    get_data_gaps,
+    get_data_quality,
+    get_attribution_report,
+    get_daily_report,
+    get_events,
+    get_execution_stats,
+    get_key_rotation,
+    get_maintenance,
+    get_optimization,
+    get_optimization_results,
+    get_order_book,
+    get_portfolio_performance,
+    get_retention,
+    get_position_events,
+    get_strategy_equity,
+    get_strategy_performance,
+    get_strategy_versions,
+    get_system_info,
+    get_transfers,
+    get_webhook,
     handle_auth_challenge,
     handle_create_order,
+    import_events,
+    ingest_market_data,
+    list_cost_models,
+    list_jobs,
+    list_positions,
+    list_quarantined_pairs,
+    list_retention_overrides,
+    list_scoped_tokens,
+    list_sizing_opt_outs,
+    list_snapshots,
+    list_strategy_trades,
+    list_webhooks,
+    mint_scoped_token,
+    pause_strategy,
+    preview_strategy,
+    quarantine_pair,
+    release_quarantine,
+    remove_event,
+    remove_retention_override,
+    resume_strategy,
+    restart_collector,
+    restore_snapshot,
+    restore_strategy,
+    resume_trading,
+    retry_job,
+    revoke_scoped_token,
+    rollback_strategy,
+    rotate_keys,
+    run_stress_test,
+    schedule_maintenance,
+    set_retention_override,
+    set_sizing_opt_out,
+    set_log_level,
+    simulate_trade,
+    start_ab_test,
+    submit_optimization,
+    take_snapshot,
+    update_strategy_parameters,
+    update_webhook,
 };
+#[cfg(feature = "fault-injection")]
+use crate::api::endpoints::{clear_faults, inject_fault, list_faults};
+use crate::api::openapi::{get_openapi, ApiDoc, DOCS_PATH, OPENAPI_PATH};
 use crate::api::middleware::{
     auth_middleware,
     rate_limit_middleware,
-    CircuitBreaker,
 };
+use crate::events::UPCOMING_HORIZON;
+use crate::utils::circuit_breaker::{self, BreakerConfig, CircuitBreaker};
 use crate::utils::logger::log_error;
 use crate::utils::metrics::MetricsCollector;
 use crate::config::security::SecurityConfig;
@@ -32,12 +114,18 @@ const BASE_PATH: &str = "/api/v1";
 const REQUEST_TIMEOUT_MS: u64 = 500; // 500ms max latency requirement
 const MAX_REQUESTS_PER_MINUTE: u32 = 1000;
 
+// Circuit breaker constants
+const CIRCUIT_BREAKER_THRESHOLD: f64 = 0.5;
+const CIRCUIT_BREAKER_WINDOW: Duration = Duration::from_secs(60);
+const CIRCUIT_BREAKER_MIN_CALLS: u32 = 20;
+const CIRCUIT_BREAKER_COOLDOWN: Duration = Duration::from_secs(30);
+
 /// Enhanced API router with comprehensive monitoring and security features
 #[derive(Debug)]
 pub struct ApiRouter {
     router: Router,
     state: Arc<AppState>,
-    circuit_breaker: CircuitBreaker,
+    circuit_breaker: Arc<CircuitBreaker>,
     metrics: Arc<MetricsCollector>,
 }
 
@@ -50,7 +138,16 @@ impl ApiRouter {
         Self {
             router: Router::new(),
             state,
-            circuit_breaker: CircuitBreaker::new(),
+            circuit_breaker: CircuitBreaker::new(
+                "api",
+                BreakerConfig::error_rate(
+                    CIRCUIT_BREAKER_WINDOW,
+                    CIRCUIT_BREAKER_THRESHOLD,
+                    CIRCUIT_BREAKER_MIN_CALLS,
+                )
+                .with_cooldown(CIRCUIT_BREAKER_COOLDOWN),
+            )
+            .registered(),
             metrics,
         }
     }
@@ -63,8 +160,16 @@ impl ApiRouter {
             .layer(from_fn(move |req, next| {
                 let cb = self.circuit_breaker.clone();
                 async move {
-                    cb.check_health().await?;
-                    next.run(req).await
+                    if !cb.allow() {
+                        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+                    }
+                    let response = next.run(req).await;
+                    if response.status().is_server_error() {
+                        cb.record_failure();
+                    } else {
+                        cb.record_success();
+                    }
+                    response
                 }
             }))
             // Request timeout enforcement
@@ -106,6 +211,357 @@ impl ApiRouter {
                             response
                         }
                     }))
+            )
+            .route(
+                &format!("{}/orders/:id", BASE_PATH),
+                delete(cancel_order)
+            )
+            .route(
+                &format!("{}/orders/cancel", BASE_PATH),
+                post(cancel_orders)
+            )
+            .route(
+                &format!("{}/trades/simulate", BASE_PATH),
+                post(simulate_trade)
+            );
+        self
+    }
+
+    /// Configures market data routes
+    #[tracing::instrument(skip(self))]
+    fn configure_market_routes(&mut self) -> &mut Self {
+        self.router = self.router
+            .route(
+                &format!("{}/markets/:pair/candles", BASE_PATH),
+                get(get_candles)
+            )
+            .route(
+                &format!("{}/markets/:pair/orderbook", BASE_PATH),
+                get(get_order_book)
+            );
+        self
+    }
+
+    /// Configures portfolio performance, transfer and position history routes
+    #[tracing::instrument(skip(self))]
+    fn configure_portfolio_routes(&mut self) -> &mut Self {
+        self.router = self.router
+            .route(
+                &format!("{}/portfolio/performance", BASE_PATH),
+                get(get_portfolio_performance)
+            )
+            .route(
+                &format!("{}/portfolio/transfers", BASE_PATH),
+                get(get_transfers)
+            )
+            .route(
+                &format!("{}/positions/:id/events", BASE_PATH),
+                get(get_position_events)
+            );
+        self
+    }
+
+    /// Configures outbound webhook management routes
+    #[tracing::instrument(skip(self))]
+    fn configure_webhook_routes(&mut self) -> &mut Self {
+        self.router = self.router
+            .route(
+                &format!("{}/webhooks", BASE_PATH),
+                get(list_webhooks).post(create_webhook)
+            )
+            .route(
+                &format!("{}/webhooks/:id", BASE_PATH),
+                get(get_webhook).patch(update_webhook).delete(delete_webhook)
+            );
+        self
+    }
+
+    /// Configures strategy optimization job routes
+    #[tracing::instrument(skip(self))]
+    fn configure_optimizer_routes(&mut self) -> &mut Self {
+        self.router = self.router
+            .route(
+                &format!("{}/optimizations", BASE_PATH),
+                post(submit_optimization)
+            )
+            .route(
+                &format!("{}/optimizations/:id", BASE_PATH),
+                get(get_optimization).delete(cancel_optimization)
+            )
+            .route(
+                &format!("{}/optimizations/:id/results", BASE_PATH),
+                get(get_optimization_results)
+            );
+        self
+    }
+
+    /// Configures strategy supervision, performance and versioning routes
+    #[tracing::instrument(skip(self))]
+    fn configure_strategy_routes(&mut self) -> &mut Self {
+        self.router = self.router
+            .route(
+                &format!("{}/strategies", BASE_PATH),
+                post(create_strategy)
+            )
+            .route(
+                &format!("{}/strategies/performance", BASE_PATH),
+                get(get_strategy_performance)
+            )
+            .route(
+                &format!("{}/strategies/preview", BASE_PATH),
+                post(preview_strategy)
+            )
+            .route(
+                &format!("{}/strategies/:id", BASE_PATH),
+                delete(delete_strategy)
+            )
+            .route(
+                &format!("{}/strategies/:id/equity", BASE_PATH),
+                get(get_strategy_equity)
+            )
+            .route(
+                &format!("{}/strategies/:id/resume", BASE_PATH),
+                post(resume_strategy)
+            )
+            .route(
+                &format!("{}/strategies/:id/trades", BASE_PATH),
+                get(list_strategy_trades)
+            )
+            .route(
+                &format!("{}/strategies/:id/versions", BASE_PATH),
+                get(get_strategy_versions)
+            )
+            .route(
+                &format!("{}/strategies/:id/parameters", BASE_PATH),
+                put(update_strategy_parameters)
+            )
+            .route(
+                &format!("{}/strategies/:id/rollback", BASE_PATH),
+                post(rollback_strategy)
+            )
+            .route(
+                &format!("{}/strategies/:id/ab-tests", BASE_PATH),
+                post(start_ab_test)
+            );
+        self
+    }
+
+    /// Configures monitoring routes for collector data quality, gaps and retention
+    #[tracing::instrument(skip(self))]
+    fn configure_monitoring_routes(&mut self) -> &mut Self {
+        self.router = self.router
+            .route(
+                &format!("{}/monitoring/data-quality", BASE_PATH),
+                get(get_data_quality)
+            )
+            .route(
+                &format!("{}/monitoring/data-gaps", BASE_PATH),
+                get(get_data_gaps)
+            )
+            .route(
+                &format!("{}/monitoring/retention", BASE_PATH),
+                get(get_retention)
+            );
+        self
+    }
+
+    /// Configures bulk market data ingestion routes
+    #[tracing::instrument(skip(self))]
+    fn configure_ingest_routes(&mut self) -> &mut Self {
+        self.router = self.router
+            .route(
+                &format!("{}/ingest/market-data", BASE_PATH),
+                post(ingest_market_data)
+            );
+        self
+    }
+
+    /// Configures execution analytics routes
+    #[tracing::instrument(skip(self))]
+    fn configure_analytics_routes(&mut self) -> &mut Self {
+        self.router = self.router
+            .route(
+                &format!("{}/analytics/execution", BASE_PATH),
+                get(get_execution_stats)
+            );
+        self
+    }
+
+    /// Configures per-pair risk controls and stress testing
+    #[tracing::instrument(skip(self))]
+    fn configure_risk_routes(&mut self) -> &mut Self {
+        self.router = self.router
+            .route(
+                &format!("{}/risk/quarantine", BASE_PATH),
+                get(list_quarantined_pairs).post(quarantine_pair)
+            )
+            .route(
+                &format!("{}/risk/stress", BASE_PATH),
+                post(run_stress_test)
+            );
+        self
+    }
+
+    /// Configures finance reporting routes
+    #[tracing::instrument(skip(self))]
+    fn configure_report_routes(&mut self) -> &mut Self {
+        self.router = self.router
+            .route(
+                &format!("{}/reports/attribution", BASE_PATH),
+                get(get_attribution_report)
+            )
+            .route(
+                &format!("{}/reports/daily/:date", BASE_PATH),
+                get(get_daily_report)
+            );
+        self
+    }
+
+    /// Configures instance identity routes
+    #[tracing::instrument(skip(self))]
+    fn configure_system_routes(&mut self) -> &mut Self {
+        self.router = self.router
+            .route(
+                &format!("{}/system/info", BASE_PATH),
+                get(get_system_info)
+            );
+        self
+    }
+
+    /// Configures the economic event calendar routes
+    #[tracing::instrument(skip(self))]
+    fn configure_event_routes(&mut self) -> &mut Self {
+        self.router = self.router
+            .route(
+                &format!("{}/events", BASE_PATH),
+                get(get_events)
+            );
+        self
+    }
+
+    /// Configures administrative routes
+    #[tracing::instrument(skip(self))]
+    fn configure_admin_routes(&mut self) -> &mut Self {
+        self.router = self.router
+            .route(
+                &format!("{}/admin/rotate-keys", BASE_PATH),
+                get(get_key_rotation).post(rotate_keys)
+            )
+            .route(
+                &format!("{}/admin/snapshots", BASE_PATH),
+                get(list_snapshots).post(take_snapshot)
+            )
+            .route(
+                &format!("{}/admin/snapshots/:id/restore", BASE_PATH),
+                post(restore_snapshot)
+            )
+            .route(
+                &format!("{}/admin/resume-trading", BASE_PATH),
+                post(resume_trading)
+            )
+            .route(
+                &format!("{}/admin/log-level", BASE_PATH),
+                put(set_log_level)
+            )
+            .route(
+                &format!("{}/admin/collectors/:dex/restart", BASE_PATH),
+                post(restart_collector)
+            )
+            .route(
+                &format!("{}/admin/maintenance", BASE_PATH),
+                get(get_maintenance).post(schedule_maintenance)
+            )
+            .route(
+                &format!("{}/admin/maintenance/:id", BASE_PATH),
+                delete(cancel_maintenance)
+            )
+            .route(
+                &format!("{}/admin/strategies/:id/restore", BASE_PATH),
+                post(restore_strategy)
+            )
+            .route(
+                &format!("{}/admin/strategies/:id/pause", BASE_PATH),
+                post(pause_strategy)
+            )
+            .route(
+                &format!("{}/admin/positions", BASE_PATH),
+                get(list_positions)
+            )
+            .route(
+                &format!("{}/admin/strategies/:id/sizing-opt-out", BASE_PATH),
+                put(set_sizing_opt_out)
+            )
+            .route(
+                &format!("{}/admin/risk/quarantine/:pair", BASE_PATH),
+                delete(release_quarantine)
+            )
+            .route(
+                &format!("{}/admin/risk/sizing-opt-outs", BASE_PATH),
+                get(list_sizing_opt_outs)
+            )
+            .route(
+                &format!("{}/admin/events", BASE_PATH),
+                post(add_event)
+            )
+            .route(
+                &format!("{}/admin/events/import", BASE_PATH),
+                post(import_events)
+            )
+            .route(
+                &format!("{}/admin/events/:id", BASE_PATH),
+                delete(remove_event)
+            )
+            .route(
+                &format!("{}/admin/retention", BASE_PATH),
+                get(list_retention_overrides).put(set_retention_override)
+            )
+            .route(
+                &format!("{}/admin/retention/:pair", BASE_PATH),
+                delete(remove_retention_override)
+            )
+            .route(
+                &format!("{}/admin/token-accounts/:pair", BASE_PATH),
+                delete(close_token_accounts)
+            )
+            .route(
+                &format!("{}/admin/jobs", BASE_PATH),
+                get(list_jobs)
+            )
+            .route(
+                &format!("{}/admin/jobs/:id/retry", BASE_PATH),
+                post(retry_job)
+            )
+            .route(
+                &format!("{}/admin/jobs/:id/cancel", BASE_PATH),
+                post(cancel_job)
+            )
+            .route(
+                &format!("{}/admin/cost-models", BASE_PATH),
+                get(list_cost_models)
+            )
+            .route(
+                &format!("{}/admin/cost-models/calibrate", BASE_PATH),
+                post(calibrate_cost_models)
+            )
+            .route(
+                &format!("{}/admin/tokens", BASE_PATH),
+                get(list_scoped_tokens).post(mint_scoped_token)
+            )
+            .route(
+                &format!("{}/admin/tokens/:id", BASE_PATH),
+                delete(revoke_scoped_token)
+            );
+        self
+    }
+
+    /// Configures fault injection admin routes for resilience testing
+    #[cfg(feature = "fault-injection")]
+    #[tracing::instrument(skip(self))]
+    fn configure_fault_routes(&mut self) -> &mut Self {
+        self.router = self.router
+            .route(
+                &format!("{}/admin/faults", BASE_PATH),
+                get(list_faults).post(inject_fault).delete(clear_faults)
             );
         self
     }
@@ -121,11 +577,24 @@ impl ApiRouter {
         self
     }
 
+    /// Configures the OpenAPI document, with a Swagger UI outside production
+    #[tracing::instrument(skip(self))]
+    fn configure_docs_routes(&mut self) -> &mut Self {
+        self.router = if self.state.config.is_production() {
+            self.router.route(OPENAPI_PATH, get(get_openapi))
+        } else {
+            // The UI serves the document at OPENAPI_PATH itself
+            self.router
+                .merge(SwaggerUi::new(DOCS_PATH).url(OPENAPI_PATH, ApiDoc::openapi()))
+        };
+        self
+    }
+
     /// Configures health check endpoint
     #[tracing::instrument(skip(self))]
     fn configure_health_routes(&mut self) -> &mut Self {
         self.router = self.router
-            .route("/health", get(|| async { "OK" }));
+            .route("/health", get(health_check));
         self
     }
 
@@ -134,15 +603,56 @@ impl ApiRouter {
     pub fn build(mut self) -> Router {
         self.configure_middleware()
             .configure_trading_routes()
+            .configure_market_routes()
+            .configure_portfolio_routes()
+            .configure_webhook_routes()
+            .configure_optimizer_routes()
+            .configure_strategy_routes()
+            .configure_monitoring_routes()
+            .configure_ingest_routes()
+            .configure_analytics_routes()
+            .configure_risk_routes()
+            .configure_report_routes()
+            .configure_system_routes()
+            .configure_event_routes()
+            .configure_admin_routes()
             .configure_auth_routes()
+            .configure_docs_routes()
             .configure_health_routes();
 
+        #[cfg(feature = "fault-injection")]
+        self.configure_fault_routes();
+
         self.router
             .layer(Extension(self.state.clone()))
+            .layer(Extension(self.state.key_store.clone()))
+            .layer(Extension(self.state.scoped_tokens.clone()))
+            .layer(Extension(self.state.webhooks.clone()))
+            .layer(Extension(self.state.optimizer.clone()))
             .layer(Extension(self.metrics.clone()))
     }
 }
 
+/// Reports service health along with the state of every registered circuit breaker, the
+/// write-behind persistence backlog, high-impact economic events due within 24 hours and,
+/// when execution is running, its warm-up state and per-pair market data readiness
+pub async fn health_check(Extension(state): Extension<Arc<AppState>>) -> Json<serde_json::Value> {
+    let persistence = state.persistence.as_ref().map(|persistence| persistence.status());
+    let degraded = persistence.as_ref().map_or(false, |status| status.degraded);
+    let upcoming_events = state
+        .events
+        .as_ref()
+        .map(|events| events.upcoming(chrono::Utc::now(), UPCOMING_HORIZON))
+        .unwrap_or_default();
+    Json(json!({
+        "status": if degraded { "degraded" } else { "ok" },
+        "circuit_breakers": circuit_breaker::registry().statuses(),
+        "persistence": persistence,
+        "execution": state.readiness.as_ref().map(|readiness| readiness.report()),
+        "upcoming_events": upcoming_events,
+    }))
+}
+
 /// Creates and configures the main API router
 #[tracing::instrument(skip(app_state))]
 pub fn create_router(app_state: Arc<AppState>) -> Router {
@@ -168,6 +678,208 @@ mod tests {
         assert_eq!(response.status(), 200);
     }
 
+    #[tokio::test]
+    async fn test_health_lists_circuit_breakers() {
+        let breaker = CircuitBreaker::new("health_test", BreakerConfig::manual()).registered();
+        breaker.trip("test");
+
+        let Json(body) = health_check(Extension(Arc::new(AppState::default()))).await;
+        let breakers = body["circuit_breakers"].as_array().unwrap();
+        let entry = breakers
+            .iter()
+            .find(|b| b["name"] == "health_test")
+            .expect("registered breaker listed");
+        assert_eq!(entry["state"], "open");
+        assert_eq!(entry["reason"], "test");
+        assert!(body["execution"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_persistence_backlog() {
+        use crate::persistence::{PersistenceConfig, PersistenceQueue};
+
+        let persistence = Arc::new(PersistenceQueue::new(PersistenceConfig {
+            degraded_backlog: 1,
+            ..PersistenceConfig::default()
+        }));
+        persistence.enqueue("test", || async { Ok(()) });
+
+        let state = Arc::new(AppState::default().with_persistence(persistence.clone()));
+        let Json(body) = health_check(Extension(state.clone())).await;
+        assert_eq!(body["status"], "degraded");
+        assert_eq!(body["persistence"]["backlog"], 1);
+        assert!(body["persistence"]["oldest_age_ms"].is_u64());
+        assert_eq!(body["persistence"]["trading_paused"], false);
+
+        persistence.flush().await.unwrap();
+        let Json(body) = health_check(Extension(state)).await;
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["persistence"]["backlog"], 0);
+        assert!(body["persistence"]["oldest_age_ms"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_execution_warm_up() {
+        use crate::execution_engine::readiness::{BookFeed, ReadinessConfig, ReadinessGate};
+
+        struct NoBooks;
+        impl BookFeed for NoBooks {
+            fn fresh_book_at(&self, _: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+                None
+            }
+        }
+
+        let readiness = Arc::new(ReadinessGate::new(ReadinessConfig::default(), Arc::new(NoBooks)));
+        let state = Arc::new(AppState::default().with_readiness(readiness));
+        let Json(body) = health_check(Extension(state)).await;
+        assert_eq!(body["execution"]["state"], "warming_up");
+        assert!(body["execution"]["warm_up_ms"].is_null());
+        assert_eq!(body["execution"]["pairs"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_system_info_shape() {
+        let Json(info) = get_system_info(Extension(Arc::new(AppState::default()))).await;
+        let body = serde_json::to_value(&info).unwrap();
+
+        assert_eq!(body["build"]["version"], crate::VERSION);
+        assert!(!body["build"]["git_commit"].as_str().unwrap().is_empty());
+        assert!(!body["build"]["build_timestamp"].as_str().unwrap().is_empty());
+        assert!(body["started_at"].is_string());
+        assert!(body["uptime_secs"].as_i64().unwrap() >= 0);
+        assert!(body["environment"].is_string());
+        for flag in ["paper_trading", "mev_optimization", "grpc"] {
+            assert!(body["features"][flag].is_boolean(), "missing feature flag {}", flag);
+        }
+        assert_eq!(body["module_versions"]["metrics"], "1.0.0");
+        assert_eq!(body["activity"]["active_strategies"], 0);
+        assert_eq!(body["activity"]["open_positions"], 0);
+        assert_eq!(body["activity"]["websocket_connections"], 0);
+        assert_eq!(body["upcoming_events"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_health_lists_upcoming_high_impact_events() {
+        use crate::events::{EventCalendar, EventGuardConfig, EventRequest, ImpactLevel};
+
+        let now = chrono::Utc::now();
+        let events = Arc::new(EventCalendar::new(EventGuardConfig::default()));
+        for (hours, name, impact) in [
+            (2, "FOMC", ImpactLevel::High),
+            (3, "PMI", ImpactLevel::Low),
+            (30, "NFP", ImpactLevel::High),
+        ] {
+            let request = EventRequest {
+                timestamp: now + chrono::Duration::hours(hours),
+                name: name.to_string(),
+                impact,
+            };
+            events.add(request, "ops", now).await.unwrap();
+        }
+
+        let state = Arc::new(AppState::default().with_events(events));
+        let Json(body) = health_check(Extension(state.clone())).await;
+        let upcoming = body["upcoming_events"].as_array().unwrap();
+        assert_eq!(upcoming.len(), 1);
+        assert_eq!(upcoming[0]["name"], "FOMC");
+        assert_eq!(upcoming[0]["impact"], "high");
+
+        let Json(info) = get_system_info(Extension(state)).await;
+        assert_eq!(info.upcoming_events.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_scoped_token_reads_only_its_strategy() {
+        use crate::api::{MintTokenRequest, TokenScope};
+        use crate::models::strategy::{Strategy, StrategyParams, StrategyType};
+        use crate::performance::{PerformanceConfig, PerformanceService};
+        use crate::utils::percent::{Bps, Percent};
+        use axum::body::Body;
+        use axum::http::Method;
+        use rust_decimal_macros::dec;
+        use std::collections::HashMap;
+
+        let strategy = || {
+            Strategy::new(
+                StrategyType::Grid,
+                StrategyParams {
+                    position_size_bps: Bps::new(1000),
+                    grid_levels: Some(10),
+                    stop_loss_pct: Percent::from_percent(dec!(-5)),
+                    take_profit_pct: Percent::from_percent(dec!(1)),
+                    max_slippage_bps: Bps::new(100),
+                    exchanges: vec!["jupiter".to_string()],
+                    risk_factor: dec!(0.5),
+                    allowed_regimes: Vec::new(),
+                    market_making: None,
+                },
+                vec!["SOL/USDC".to_string()],
+            )
+            .unwrap()
+        };
+        let strategies = HashMap::from([("grid-1".to_string(), strategy()), ("ml-1".to_string(), strategy())]);
+        let performance = PerformanceService::new(PerformanceConfig::default(), Arc::new(tokio::sync::RwLock::new(strategies)));
+        let state = Arc::new(AppState::default().with_performance(Arc::new(performance)));
+        let minted = state
+            .scoped_tokens
+            .mint(
+                MintTokenRequest {
+                    label: "public dashboard".to_string(),
+                    scopes: vec![TokenScope::strategy("grid-1")],
+                    expires_in_days: None,
+                },
+                "admin",
+                chrono::Utc::now(),
+            )
+            .await
+            .unwrap();
+        let router = create_router(state.clone())
+            .layer(from_fn(auth_middleware))
+            .layer(Extension(state.scoped_tokens.clone()));
+        let call = |method: Method, uri: &str| {
+            router.clone().oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("Authorization", format!("Bearer {}", minted.secret))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        // The leaderboard lists only the token's strategy, without absolute amounts
+        let response = call(Method::GET, "/api/v1/strategies/performance").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value =
+            serde_json::from_slice(&hyper::body::to_bytes(response.into_body()).await.unwrap()).unwrap();
+        let entries = body.as_array().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["strategy_id"], "grid-1");
+        assert!(entries[0].get("roi").is_some());
+        assert!(entries[0].get("win_rate").is_some());
+        assert!(entries[0].get("realized_pnl").is_none());
+        assert!(entries[0].get("allocation_used").is_none());
+
+        // Other strategies, writes, trading and admin routes are all forbidden
+        for (method, uri) in [
+            (Method::GET, "/api/v1/strategies/ml-1/equity"),
+            (Method::GET, "/api/v1/strategies/ml-1/trades"),
+            (Method::PUT, "/api/v1/strategies/grid-1/parameters"),
+            (Method::GET, "/api/v1/strategies/grid-1/versions"),
+            (Method::POST, "/api/v1/orders/cancel"),
+            (Method::GET, "/api/v1/portfolio/transfers"),
+            (Method::GET, "/api/v1/admin/tokens"),
+        ] {
+            let response = call(method.clone(), uri).await.unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN, "{} {}", method, uri);
+        }
+
+        // Revoked tokens are rejected outright
+        state.scoped_tokens.revoke(minted.token.id, chrono::Utc::now()).await.unwrap();
+        let response = call(Method::GET, "/api/v1/strategies/performance").await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
     #[tokio::test]
     async fn test_rate_limiting() {
         let state = Arc::new(AppState::default());
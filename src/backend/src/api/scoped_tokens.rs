@@ -0,0 +1,554 @@
+//! Read-only API tokens scoped to individual resources, for sharing a live strategy
+//! dashboard publicly. An admin mints a token covering one strategy's metrics or the
+//! portfolio summary; the auth middleware accepts it only for reads on the dashboard routes
+//! and meters it per token well below a session, handlers check the specific resource, and
+//! every response it receives is passed through `redact_response` so absolute sizes and
+//! wallet addresses never leave the API. The secret is shown once at minting and only its
+//! hash is kept, so a revoked token is rejected from the next request on.
+//!
+//! Version dependencies:
+//! - axum = "0.6"
+//! - hyper = "0.14"
+//! - sha2 = "0.10"
+//! - rand = "0.8"
+//! - serde_json = "1.0"
+//! - parking_lot = "0.12"
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::body::{boxed, Full};
+use axum::http::{header, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use metrics::counter; // v0.20.1
+use parking_lot::{Mutex, RwLock};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tracing::{info, warn}; // v0.1.37
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::api::websocket::strategy_performance_channel;
+
+// Scoped token constants
+pub const SCOPED_TOKEN_PREFIX: &str = "rot_";
+const SECRET_BYTES: usize = 32;
+const DEFAULT_REQUESTS_PER_WINDOW: u32 = 30;
+const DEFAULT_RATE_WINDOW: Duration = Duration::from_secs(60);
+/// Routes a scoped token may read; `:id` matches any single path segment
+const SCOPED_ROUTES: &[&str] = &[
+    "/api/v1/strategies/performance",
+    "/api/v1/strategies/:id/equity",
+    "/api/v1/strategies/:id/trades",
+    "/api/v1/portfolio/performance",
+];
+/// Response fields withheld from scoped tokens: wallets and absolute sizes and amounts
+const REDACTED_FIELDS: &[&str] = &[
+    "wallet",
+    "wallet_address",
+    "size",
+    "amount",
+    "quantity",
+    "allocation_used",
+    "realized_pnl",
+    "pnl",
+    "cumulative_pnl",
+    "net_flows",
+    "high_water_mark",
+];
+
+/// Resource a read-only token may read
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TokenScope {
+    /// One strategy's leaderboard entry, equity curve and trades
+    Strategy { strategy_id: String },
+    /// Portfolio returns and drawdown, without wallet or absolute values
+    PortfolioSummary,
+}
+
+impl TokenScope {
+    pub fn strategy(strategy_id: &str) -> Self {
+        Self::Strategy {
+            strategy_id: strategy_id.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for TokenScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Strategy { strategy_id } => write!(f, "strategy:{}", strategy_id),
+            Self::PortfolioSummary => write!(f, "portfolio_summary"),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ScopedTokenError {
+    #[error("invalid token scope: {0}")]
+    InvalidScope(String),
+    #[error("unknown token")]
+    Unknown,
+    #[error("token {0} not found")]
+    NotFound(Uuid),
+    #[error("token {0} has been revoked")]
+    Revoked(Uuid),
+    #[error("token {0} has expired")]
+    Expired(Uuid),
+    #[error("token scope does not cover {0}")]
+    OutOfScope(String),
+    #[error("rate limit exceeded for token {0}")]
+    RateLimited(Uuid),
+    #[error("token store error: {0}")]
+    Store(String),
+}
+
+/// A minted read-only token; the secret itself is never stored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScopedToken {
+    pub id: Uuid,
+    pub label: String,
+    pub scopes: Vec<TokenScope>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ScopedToken {
+    fn check_active(&self, now: DateTime<Utc>) -> Result<(), ScopedTokenError> {
+        if self.revoked_at.is_some() {
+            return Err(ScopedTokenError::Revoked(self.id));
+        }
+        if self.expires_at.map_or(false, |expires_at| now >= expires_at) {
+            return Err(ScopedTokenError::Expired(self.id));
+        }
+        Ok(())
+    }
+}
+
+/// Token minting request
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct MintTokenRequest {
+    /// Where the token is shared, for the admin listing
+    #[validate(length(min = 1, max = 64))]
+    pub label: String,
+    #[validate(length(min = 1, max = 16))]
+    pub scopes: Vec<TokenScope>,
+    /// Days until the token lapses; it never does when omitted
+    #[serde(default)]
+    #[validate(range(min = 1, max = 365))]
+    pub expires_in_days: Option<u32>,
+}
+
+/// A freshly minted token with its secret, which is returned only this once
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MintedToken {
+    pub secret: String,
+    pub token: ScopedToken,
+}
+
+/// What a validated scoped token grants, attached to its requests in place of `Claims`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopedAccess {
+    pub token_id: Uuid,
+    pub scopes: Vec<TokenScope>,
+}
+
+impl ScopedAccess {
+    pub fn allows(&self, scope: &TokenScope) -> bool {
+        self.scopes.contains(scope)
+    }
+
+    /// Whether the token may subscribe to a WebSocket channel; only its strategies'
+    /// redacted performance channels qualify
+    pub fn allows_channel(&self, channel: &str) -> bool {
+        self.scopes.iter().any(|scope| match scope {
+            TokenScope::Strategy { strategy_id } => channel == strategy_performance_channel(strategy_id),
+            TokenScope::PortfolioSummary => false,
+        })
+    }
+}
+
+/// Whether a bearer token is a scoped token rather than a session JWT
+pub fn is_scoped_token(token: &str) -> bool {
+    token.starts_with(SCOPED_TOKEN_PREFIX)
+}
+
+/// Whether scoped tokens may make a request at all; handlers then check the resource
+pub fn is_scoped_route(method: &Method, path: &str) -> bool {
+    if method != Method::GET {
+        return false;
+    }
+    let segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+    SCOPED_ROUTES.iter().any(|route| {
+        let pattern: Vec<&str> = route.split('/').collect();
+        pattern.len() == segments.len()
+            && pattern
+                .iter()
+                .zip(&segments)
+                .all(|(expected, actual)| *expected == *actual || (expected.starts_with(':') && !actual.is_empty()))
+    })
+}
+
+/// Strips withheld fields from a JSON document, at any depth
+pub fn redact(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            fields.retain(|key, _| !REDACTED_FIELDS.contains(&key.as_str()));
+            fields.values_mut().for_each(redact);
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// Filters a response bound for a scoped token through `redact`. Bodies that are not JSON
+/// pass through unchanged.
+pub async fn redact_response(response: Response) -> Response {
+    let (mut parts, body) = response.into_parts();
+    let bytes = match hyper::body::to_bytes(body).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("Failed to buffer response for redaction: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let Ok(mut document) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, boxed(Full::from(bytes)));
+    };
+    redact(&mut document);
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, boxed(Full::from(document.to_string())))
+}
+
+fn hash_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopedTokenConfig {
+    /// Requests one token may make per rate window, well below a session's allowance
+    pub requests_per_window: u32,
+    pub rate_window: Duration,
+}
+
+impl Default for ScopedTokenConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_window: DEFAULT_REQUESTS_PER_WINDOW,
+            rate_window: DEFAULT_RATE_WINDOW,
+        }
+    }
+}
+
+/// Durable scoped tokens, keyed by the hash of their secret
+#[async_trait]
+pub trait ScopedTokenStore: Send + Sync {
+    async fn save(&self, token: &ScopedToken, secret_hash: &str) -> Result<(), ScopedTokenError>;
+    async fn revoke(&self, id: Uuid, revoked_at: DateTime<Utc>) -> Result<(), ScopedTokenError>;
+    /// Every token with its secret hash, revoked ones included
+    async fn load(&self) -> Result<Vec<(ScopedToken, String)>, ScopedTokenError>;
+}
+
+/// Mints, validates, meters and revokes scoped tokens
+pub struct ScopedTokenRegistry {
+    config: ScopedTokenConfig,
+    /// Tokens keyed by the SHA-256 of their secret
+    tokens: RwLock<HashMap<String, ScopedToken>>,
+    /// Request times per token within the rate window, oldest first
+    requests: Mutex<HashMap<Uuid, VecDeque<DateTime<Utc>>>>,
+    store: Option<Arc<dyn ScopedTokenStore>>,
+}
+
+impl fmt::Debug for ScopedTokenRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopedTokenRegistry")
+            .field("config", &self.config)
+            .field("tokens", &self.tokens.read().len())
+            .finish()
+    }
+}
+
+impl ScopedTokenRegistry {
+    pub fn new(config: ScopedTokenConfig) -> Self {
+        Self {
+            config,
+            tokens: RwLock::new(HashMap::new()),
+            requests: Mutex::new(HashMap::new()),
+            store: None,
+        }
+    }
+
+    pub fn with_store(mut self, store: Arc<dyn ScopedTokenStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Loads persisted tokens, returning how many were loaded
+    pub async fn load(&self) -> Result<usize, ScopedTokenError> {
+        let Some(store) = &self.store else {
+            return Ok(0);
+        };
+        let loaded = store.load().await?;
+        let count = loaded.len();
+        let mut tokens = self.tokens.write();
+        for (token, secret_hash) in loaded {
+            tokens.insert(secret_hash, token);
+        }
+        Ok(count)
+    }
+
+    /// Mints a token, returning it with its secret
+    pub async fn mint(
+        &self,
+        request: MintTokenRequest,
+        created_by: &str,
+        now: DateTime<Utc>,
+    ) -> Result<MintedToken, ScopedTokenError> {
+        request
+            .validate()
+            .map_err(|e| ScopedTokenError::InvalidScope(e.to_string()))?;
+        if let Some(scope) = request.scopes.iter().find(|scope| match scope {
+            TokenScope::Strategy { strategy_id } => strategy_id.trim().is_empty(),
+            TokenScope::PortfolioSummary => false,
+        }) {
+            return Err(ScopedTokenError::InvalidScope(format!("{} names no strategy", scope)));
+        }
+
+        let mut bytes = [0u8; SECRET_BYTES];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let secret = format!("{}{}", SCOPED_TOKEN_PREFIX, hex::encode(bytes));
+        let mut scopes: Vec<TokenScope> = Vec::with_capacity(request.scopes.len());
+        for scope in request.scopes {
+            if !scopes.contains(&scope) {
+                scopes.push(scope);
+            }
+        }
+        let token = ScopedToken {
+            id: Uuid::new_v4(),
+            label: request.label,
+            scopes,
+            created_by: created_by.to_string(),
+            created_at: now,
+            expires_at: request
+                .expires_in_days
+                .map(|days| now + chrono::Duration::days(i64::from(days))),
+            revoked_at: None,
+        };
+
+        let secret_hash = hash_secret(&secret);
+        if let Some(store) = &self.store {
+            store.save(&token, &secret_hash).await?;
+        }
+        self.tokens.write().insert(secret_hash, token.clone());
+
+        counter!("api.scoped_tokens.minted").increment(1);
+        info!(token_id = %token.id, label = %token.label, created_by, "Minted scoped API token");
+        Ok(MintedToken { secret, token })
+    }
+
+    /// Revokes a token; requests carrying it are rejected from then on
+    pub async fn revoke(&self, id: Uuid, now: DateTime<Utc>) -> Result<ScopedToken, ScopedTokenError> {
+        let current = self
+            .tokens
+            .read()
+            .values()
+            .find(|token| token.id == id)
+            .cloned()
+            .ok_or(ScopedTokenError::NotFound(id))?;
+        if current.revoked_at.is_some() {
+            return Err(ScopedTokenError::Revoked(id));
+        }
+        if let Some(store) = &self.store {
+            store.revoke(id, now).await?;
+        }
+
+        let mut tokens = self.tokens.write();
+        let token = tokens
+            .values_mut()
+            .find(|token| token.id == id)
+            .ok_or(ScopedTokenError::NotFound(id))?;
+        token.revoked_at = Some(now);
+        self.requests.lock().remove(&id);
+
+        counter!("api.scoped_tokens.revoked").increment(1);
+        info!(token_id = %id, "Revoked scoped API token");
+        Ok(token.clone())
+    }
+
+    /// Every token, newest first
+    pub fn list(&self) -> Vec<ScopedToken> {
+        let mut tokens: Vec<ScopedToken> = self.tokens.read().values().cloned().collect();
+        tokens.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        tokens
+    }
+
+    /// Resolves a secret to what it grants
+    pub fn authenticate(&self, secret: &str, now: DateTime<Utc>) -> Result<ScopedAccess, ScopedTokenError> {
+        let tokens = self.tokens.read();
+        let token = tokens.get(&hash_secret(secret)).ok_or(ScopedTokenError::Unknown)?;
+        token.check_active(now)?;
+        Ok(ScopedAccess {
+            token_id: token.id,
+            scopes: token.scopes.clone(),
+        })
+    }
+
+    /// Whether the token behind `access` is still live, for long-lived connections
+    pub fn is_active(&self, access: &ScopedAccess, now: DateTime<Utc>) -> bool {
+        self.tokens
+            .read()
+            .values()
+            .any(|token| token.id == access.token_id && token.check_active(now).is_ok())
+    }
+
+    /// Counts a request against the token's rate window, refusing it once the window is full
+    pub fn admit(&self, access: &ScopedAccess, now: DateTime<Utc>) -> Result<(), ScopedTokenError> {
+        let window = chrono::Duration::from_std(self.config.rate_window).unwrap_or_else(|_| chrono::Duration::zero());
+        let mut requests = self.requests.lock();
+        let recent = requests.entry(access.token_id).or_default();
+        while recent.front().map_or(false, |at| now - *at >= window) {
+            recent.pop_front();
+        }
+        if recent.len() >= self.config.requests_per_window as usize {
+            counter!("api.scoped_tokens.rate_limited").increment(1);
+            return Err(ScopedTokenError::RateLimited(access.token_id));
+        }
+        recent.push_back(now);
+        Ok(())
+    }
+}
+
+impl Default for ScopedTokenRegistry {
+    fn default() -> Self {
+        Self::new(ScopedTokenConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(scopes: Vec<TokenScope>) -> MintTokenRequest {
+        MintTokenRequest {
+            label: "public dashboard".to_string(),
+            scopes,
+            expires_in_days: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_minted_token_authenticates_until_revoked() {
+        let registry = ScopedTokenRegistry::default();
+        let now = Utc::now();
+        let minted = registry
+            .mint(request(vec![TokenScope::strategy("grid-1")]), "admin", now)
+            .await
+            .unwrap();
+        assert!(is_scoped_token(&minted.secret));
+
+        let access = registry.authenticate(&minted.secret, now).unwrap();
+        assert!(access.allows(&TokenScope::strategy("grid-1")));
+        assert!(!access.allows(&TokenScope::strategy("ml-1")));
+        assert!(!access.allows(&TokenScope::PortfolioSummary));
+        assert!(access.allows_channel("strategy_performance:grid-1"));
+        assert!(!access.allows_channel("strategy_performance:ml-1"));
+        assert!(!access.allows_channel("signals"));
+
+        registry.revoke(minted.token.id, now).await.unwrap();
+        assert!(matches!(
+            registry.authenticate(&minted.secret, now),
+            Err(ScopedTokenError::Revoked(_))
+        ));
+        assert!(!registry.is_active(&access, now));
+        assert!(matches!(
+            registry.authenticate("rot_unknown", now),
+            Err(ScopedTokenError::Unknown)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_rejected() {
+        let registry = ScopedTokenRegistry::default();
+        let now = Utc::now();
+        let mut mint = request(vec![TokenScope::PortfolioSummary]);
+        mint.expires_in_days = Some(1);
+        let minted = registry.mint(mint, "admin", now).await.unwrap();
+
+        assert!(registry.authenticate(&minted.secret, now).is_ok());
+        assert!(matches!(
+            registry.authenticate(&minted.secret, now + chrono::Duration::days(1)),
+            Err(ScopedTokenError::Expired(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_requests_metered_per_token() {
+        let registry = ScopedTokenRegistry::new(ScopedTokenConfig {
+            requests_per_window: 2,
+            rate_window: Duration::from_secs(60),
+        });
+        let now = Utc::now();
+        let minted = registry
+            .mint(request(vec![TokenScope::PortfolioSummary]), "admin", now)
+            .await
+            .unwrap();
+        let access = registry.authenticate(&minted.secret, now).unwrap();
+
+        assert!(registry.admit(&access, now).is_ok());
+        assert!(registry.admit(&access, now).is_ok());
+        assert!(matches!(registry.admit(&access, now), Err(ScopedTokenError::RateLimited(_))));
+        // The window slides past the earliest requests
+        assert!(registry.admit(&access, now + chrono::Duration::seconds(60)).is_ok());
+    }
+
+    #[test]
+    fn test_scoped_routes_are_reads_on_dashboard_paths() {
+        assert!(is_scoped_route(&Method::GET, "/api/v1/strategies/grid-1/equity"));
+        assert!(is_scoped_route(&Method::GET, "/api/v1/strategies/performance"));
+        assert!(is_scoped_route(&Method::GET, "/api/v1/portfolio/performance"));
+        assert!(!is_scoped_route(&Method::PUT, "/api/v1/strategies/grid-1/parameters"));
+        assert!(!is_scoped_route(&Method::DELETE, "/api/v1/strategies/grid-1/equity"));
+        assert!(!is_scoped_route(&Method::GET, "/api/v1/strategies/grid-1/versions"));
+        assert!(!is_scoped_route(&Method::GET, "/api/v1/portfolio/transfers"));
+        assert!(!is_scoped_route(&Method::GET, "/api/v1/admin/tokens"));
+    }
+
+    #[test]
+    fn test_redact_strips_sizes_and_wallets_at_any_depth() {
+        let mut document = json!({
+            "wallet_address": "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin",
+            "raw_return_pct": "4.2",
+            "trades": [{"trading_pair": "SOL/USDC", "realized_pnl": "12.5", "closed_at": "2024-01-01T00:00:00Z"}],
+        });
+        redact(&mut document);
+        assert_eq!(
+            document,
+            json!({
+                "raw_return_pct": "4.2",
+                "trades": [{"trading_pair": "SOL/USDC", "closed_at": "2024-01-01T00:00:00Z"}],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mint_rejects_empty_scopes() {
+        let registry = ScopedTokenRegistry::default();
+        let result = registry.mint(request(Vec::new()), "admin", Utc::now()).await;
+        assert!(matches!(result, Err(ScopedTokenError::InvalidScope(_))));
+        let result = registry.mint(request(vec![TokenScope::strategy(" ")]), "admin", Utc::now()).await;
+        assert!(matches!(result, Err(ScopedTokenError::InvalidScope(_))));
+    }
+}
@@ -0,0 +1,573 @@
+//! Signed outbound webhook delivery for order, position and daily summary events.
+//! Events are queued without blocking trading, retried with exponential backoff, and
+//! endpoints that keep failing are disabled with an audit entry and a notification.
+//!
+//! Version dependencies:
+//! - reqwest = "0.11"
+//! - hmac = "0.12"
+//! - sha2 = "0.10"
+//! - tokio = "1.28"
+//! - parking_lot = "0.12"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac}; // v0.12.1
+use metrics::counter; // v0.20.1
+use parking_lot::{Mutex, RwLock}; // v0.12.1
+use serde::Serialize;
+use sha2::Sha256; // v0.10.7
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, error, info, instrument, warn}; // v0.1.37
+use uuid::Uuid;
+
+use crate::db::repositories::WebhookRepository;
+use crate::models::webhook::{
+    validate_event_types, Webhook, WebhookAuditEntry, WebhookError, WebhookEvent, WebhookEventType,
+};
+use crate::utils::circuit_breaker::BreakerConfig;
+use crate::utils::http::{HostConfig, HttpClient, RetryPolicy};
+
+// Delivery header names
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+pub const DELIVERY_HEADER: &str = "X-Webhook-Delivery";
+
+// Audit actions
+const AUDIT_AUTO_DISABLED: &str = "auto_disabled";
+const AUDIT_ENABLED: &str = "enabled";
+const AUDIT_DISABLED: &str = "disabled";
+
+const NOTIFICATION_CAPACITY: usize = 64;
+
+/// Delivery retry and auto-disable policy
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    /// Attempts per event before the delivery counts as failed
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub request_timeout: Duration,
+    /// Consecutive failed deliveries before the endpoint is disabled
+    pub disable_after_failures: u32,
+    pub queue_capacity: usize,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            request_timeout: Duration::from_secs(5),
+            disable_after_failures: 3,
+            queue_capacity: 1024,
+        }
+    }
+}
+
+/// Notification published when an endpoint is disabled for persistent failures
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookDisabled {
+    pub webhook_id: Uuid,
+    pub url: String,
+    pub consecutive_failures: u32,
+    pub reason: String,
+    pub disabled_at: DateTime<Utc>,
+}
+
+/// Computes the `sha256=<hex>` signature over `<timestamp>.<body>`
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("hmac accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Registry and asynchronous delivery worker for outbound webhooks
+#[derive(Debug)]
+pub struct WebhookDispatcher {
+    config: WebhookConfig,
+    http: HttpClient,
+    webhooks: RwLock<HashMap<Uuid, Webhook>>,
+    audit_log: RwLock<Vec<WebhookAuditEntry>>,
+    repository: Option<Arc<WebhookRepository>>,
+    queue: mpsc::Sender<WebhookEvent>,
+    receiver: Mutex<Option<mpsc::Receiver<WebhookEvent>>>,
+    notifications: broadcast::Sender<WebhookDisabled>,
+}
+
+impl WebhookDispatcher {
+    pub fn new(config: WebhookConfig, repository: Option<Arc<WebhookRepository>>) -> Self {
+        // Delivery retries and endpoint disabling are handled here, so each host's breaker
+        // only opens once an endpoint has failed for longer than it takes to disable it
+        let http = HttpClient::for_host(
+            "webhooks",
+            HostConfig {
+                read_timeout: config.request_timeout,
+                retry: RetryPolicy::none(),
+                breaker: BreakerConfig::consecutive_failures(config.max_attempts * config.disable_after_failures)
+                    .with_cooldown(config.max_backoff),
+                ..HostConfig::default()
+            },
+        );
+        let (queue, receiver) = mpsc::channel(config.queue_capacity);
+        let (notifications, _) = broadcast::channel(NOTIFICATION_CAPACITY);
+
+        Self {
+            config,
+            http,
+            webhooks: RwLock::new(HashMap::new()),
+            audit_log: RwLock::new(Vec::new()),
+            repository,
+            queue,
+            receiver: Mutex::new(Some(receiver)),
+            notifications,
+        }
+    }
+
+    /// Loads persisted webhooks into the registry
+    pub async fn load(&self) -> Result<usize, WebhookError> {
+        let Some(repository) = &self.repository else {
+            return Ok(0);
+        };
+        let webhooks = repository
+            .get_webhooks()
+            .await
+            .map_err(|e| WebhookError::StorageError(e.to_string()))?;
+
+        let mut registry = self.webhooks.write();
+        for webhook in webhooks {
+            registry.insert(webhook.id, webhook);
+        }
+        Ok(registry.len())
+    }
+
+    /// Starts the delivery worker; each event fans out to subscribers concurrently
+    pub fn start(self: &Arc<Self>) -> Option<tokio::task::JoinHandle<()>> {
+        let mut receiver = self.receiver.lock().take()?;
+        let dispatcher = self.clone();
+
+        Some(tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                for webhook_id in dispatcher.subscribers(event.event_type) {
+                    let dispatcher = dispatcher.clone();
+                    let event = event.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = dispatcher.deliver(webhook_id, &event).await {
+                            debug!(webhook_id = %webhook_id, "Webhook delivery failed: {}", e);
+                        }
+                    });
+                }
+            }
+            info!("Webhook delivery worker stopped");
+        }))
+    }
+
+    /// Queues an event for delivery without waiting; returns false if it was dropped
+    pub fn dispatch(&self, event: WebhookEvent) -> bool {
+        match self.queue.try_send(event) {
+            Ok(()) => {
+                counter!("webhooks.events_queued", 1);
+                true
+            }
+            Err(e) => {
+                counter!("webhooks.events_dropped", 1);
+                warn!("Dropping webhook event: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Receives notifications about automatically disabled endpoints
+    pub fn subscribe_notifications(&self) -> broadcast::Receiver<WebhookDisabled> {
+        self.notifications.subscribe()
+    }
+
+    pub fn list(&self) -> Vec<Webhook> {
+        let mut webhooks: Vec<_> = self.webhooks.read().values().cloned().collect();
+        webhooks.sort_by_key(|webhook| webhook.created_at);
+        webhooks
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<Webhook> {
+        self.webhooks.read().get(&id).cloned()
+    }
+
+    pub fn audit_log(&self, id: Uuid) -> Vec<WebhookAuditEntry> {
+        self.audit_log
+            .read()
+            .iter()
+            .filter(|entry| entry.webhook_id == id)
+            .cloned()
+            .collect()
+    }
+
+    /// Registers a new webhook endpoint
+    #[instrument(skip(self, secret))]
+    pub async fn register(
+        &self,
+        url: String,
+        secret: String,
+        event_types: Vec<WebhookEventType>,
+    ) -> Result<Webhook, WebhookError> {
+        let webhook = Webhook::new(url, secret, event_types)?;
+        self.persist(&webhook).await?;
+        self.webhooks.write().insert(webhook.id, webhook.clone());
+
+        counter!("webhooks.registered", 1);
+        info!(webhook_id = %webhook.id, "Registered webhook");
+        Ok(webhook)
+    }
+
+    /// Updates subscriptions or status; re-enabling clears the failure count
+    #[instrument(skip(self))]
+    pub async fn update(
+        &self,
+        id: Uuid,
+        active: Option<bool>,
+        event_types: Option<Vec<WebhookEventType>>,
+    ) -> Result<Webhook, WebhookError> {
+        if let Some(event_types) = &event_types {
+            validate_event_types(event_types)?;
+        }
+
+        let mut webhook = self.get(id).ok_or(WebhookError::NotFound(id))?;
+        if let Some(event_types) = event_types {
+            webhook.event_types = event_types;
+        }
+        let status_change = match active {
+            Some(active) if active != webhook.active => {
+                webhook.active = active;
+                webhook.consecutive_failures = 0;
+                Some(if active { AUDIT_ENABLED } else { AUDIT_DISABLED })
+            }
+            _ => None,
+        };
+        webhook.updated_at = Utc::now();
+
+        self.persist(&webhook).await?;
+        self.webhooks.write().insert(id, webhook.clone());
+        if let Some(action) = status_change {
+            self.audit(WebhookAuditEntry::new(id, action, "changed via api".to_string()))
+                .await;
+        }
+        Ok(webhook)
+    }
+
+    /// Removes a webhook endpoint
+    #[instrument(skip(self))]
+    pub async fn remove(&self, id: Uuid) -> Result<(), WebhookError> {
+        if let Some(repository) = &self.repository {
+            repository
+                .delete_webhook(id)
+                .await
+                .map_err(|e| WebhookError::StorageError(e.to_string()))?;
+        }
+        self.webhooks
+            .write()
+            .remove(&id)
+            .map(|_| ())
+            .ok_or(WebhookError::NotFound(id))
+    }
+
+    /// Delivers an event to one endpoint, retrying with exponential backoff
+    #[instrument(skip(self, event), fields(event_id = %event.id))]
+    pub async fn deliver(&self, webhook_id: Uuid, event: &WebhookEvent) -> Result<(), WebhookError> {
+        let body = serde_json::to_vec(event)
+            .map_err(|e| WebhookError::SerializationError(e.to_string()))?;
+        let mut backoff = self.config.initial_backoff;
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            // Re-read each attempt so endpoints disabled mid-retry stop receiving events
+            let webhook = self.get(webhook_id).ok_or(WebhookError::NotFound(webhook_id))?;
+            if !webhook.active {
+                return Err(WebhookError::DeliveryError("webhook is disabled".to_string()));
+            }
+
+            match self.send(&webhook, event, &body).await {
+                Ok(()) => {
+                    counter!("webhooks.delivered", 1);
+                    self.record_success(webhook_id).await;
+                    return Ok(());
+                }
+                Err(e) if attempt >= self.config.max_attempts => {
+                    counter!("webhooks.delivery_failures", 1);
+                    warn!(webhook_id = %webhook_id, attempt, "Webhook delivery exhausted retries: {}", e);
+                    self.record_failure(webhook_id, &e).await;
+                    return Err(e);
+                }
+                Err(e) => {
+                    counter!("webhooks.retries", 1);
+                    debug!(webhook_id = %webhook_id, attempt, "Retrying webhook delivery: {}", e);
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(self.config.max_backoff);
+                }
+            }
+        }
+    }
+
+    async fn send(&self, webhook: &Webhook, event: &WebhookEvent, body: &[u8]) -> Result<(), WebhookError> {
+        let timestamp = Utc::now().timestamp();
+        let signature = sign_payload(&webhook.secret, timestamp, body);
+        self.http
+            .post(&webhook.url, None, |request| {
+                request
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .header(SIGNATURE_HEADER, &signature)
+                    .header(TIMESTAMP_HEADER, timestamp.to_string())
+                    .header(EVENT_HEADER, event.event_type.as_str())
+                    .header(DELIVERY_HEADER, event.id.to_string())
+                    .body(body.to_vec())
+            })
+            .await
+            .map_err(|e| match e.status() {
+                Some(status) => WebhookError::DeliveryError(format!("endpoint returned {}", status)),
+                None => WebhookError::DeliveryError(e.to_string()),
+            })?;
+        Ok(())
+    }
+
+    fn subscribers(&self, event_type: WebhookEventType) -> Vec<Uuid> {
+        self.webhooks
+            .read()
+            .values()
+            .filter(|webhook| webhook.subscribes_to(event_type))
+            .map(|webhook| webhook.id)
+            .collect()
+    }
+
+    async fn record_success(&self, webhook_id: Uuid) {
+        let updated = {
+            let mut registry = self.webhooks.write();
+            match registry.get_mut(&webhook_id) {
+                Some(webhook) if webhook.consecutive_failures > 0 => {
+                    webhook.consecutive_failures = 0;
+                    webhook.updated_at = Utc::now();
+                    Some(webhook.clone())
+                }
+                _ => None,
+            }
+        };
+
+        if let Some(webhook) = updated {
+            self.persist_quietly(&webhook).await;
+        }
+    }
+
+    async fn record_failure(&self, webhook_id: Uuid, cause: &WebhookError) {
+        let (webhook, disabled) = {
+            let mut registry = self.webhooks.write();
+            let Some(webhook) = registry.get_mut(&webhook_id) else {
+                return;
+            };
+            webhook.consecutive_failures += 1;
+            webhook.updated_at = Utc::now();
+
+            let disabled = webhook.active
+                && webhook.consecutive_failures >= self.config.disable_after_failures;
+            if disabled {
+                webhook.active = false;
+            }
+            (webhook.clone(), disabled)
+        };
+
+        self.persist_quietly(&webhook).await;
+        if !disabled {
+            return;
+        }
+
+        let reason = format!(
+            "{} consecutive failed deliveries, last error: {}",
+            webhook.consecutive_failures, cause
+        );
+        error!(webhook_id = %webhook.id, url = %webhook.url, "Disabled failing webhook: {}", reason);
+        counter!("webhooks.auto_disabled", 1);
+
+        self.audit(WebhookAuditEntry::new(webhook.id, AUDIT_AUTO_DISABLED, reason.clone()))
+            .await;
+        // No receivers is fine; the error log above is the fallback notification
+        let _ = self.notifications.send(WebhookDisabled {
+            webhook_id: webhook.id,
+            url: webhook.url,
+            consecutive_failures: webhook.consecutive_failures,
+            reason,
+            disabled_at: webhook.updated_at,
+        });
+    }
+
+    async fn audit(&self, entry: WebhookAuditEntry) {
+        if let Some(repository) = &self.repository {
+            if let Err(e) = repository.record_audit(&entry).await {
+                warn!("Failed to persist webhook audit entry: {}", e);
+            }
+        }
+        self.audit_log.write().push(entry);
+    }
+
+    async fn persist(&self, webhook: &Webhook) -> Result<(), WebhookError> {
+        match &self.repository {
+            Some(repository) => repository
+                .save_webhook(webhook)
+                .await
+                .map_err(|e| WebhookError::StorageError(e.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Persists delivery bookkeeping; storage errors must not fail deliveries
+    async fn persist_quietly(&self, webhook: &Webhook) {
+        if let Err(e) = self.persist(webhook).await {
+            warn!(webhook_id = %webhook.id, "Failed to persist webhook state: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const TEST_SECRET: &str = "whsec_test_0123456789abcdef";
+
+    fn test_config() -> WebhookConfig {
+        WebhookConfig {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+            request_timeout: Duration::from_secs(2),
+            disable_after_failures: 2,
+            queue_capacity: 16,
+        }
+    }
+
+    fn order_event() -> WebhookEvent {
+        WebhookEvent::new(
+            WebhookEventType::OrderExecuted,
+            &serde_json::json!({ "trading_pair": "SOL/USDC", "price": "23.45" }),
+        )
+        .unwrap()
+    }
+
+    async fn register(dispatcher: &WebhookDispatcher, server: &MockServer) -> Webhook {
+        dispatcher
+            .register(
+                format!("{}/hook", server.uri()),
+                TEST_SECRET.to_string(),
+                vec![WebhookEventType::OrderExecuted],
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_dispatched_event_is_signed() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let dispatcher = Arc::new(WebhookDispatcher::new(test_config(), None));
+        dispatcher.start();
+        register(&dispatcher, &server).await;
+
+        let event = order_event();
+        assert!(dispatcher.dispatch(event.clone()));
+
+        let request = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(request) = server.received_requests().await.unwrap().pop() {
+                    return request;
+                }
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        let header = |name: &str| request.headers.get(name).unwrap().to_str().unwrap().to_string();
+        let timestamp: i64 = header(TIMESTAMP_HEADER).parse().unwrap();
+        assert_eq!(header(SIGNATURE_HEADER), sign_payload(TEST_SECRET, timestamp, &request.body));
+        assert_ne!(header(SIGNATURE_HEADER), sign_payload("wrong-secret-value", timestamp, &request.body));
+        assert_eq!(header(EVENT_HEADER), "order.executed");
+        assert_eq!(header(DELIVERY_HEADER), event.id.to_string());
+
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body["data"]["price"], "23.45");
+    }
+
+    #[tokio::test]
+    async fn test_delivery_retries_until_success() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let dispatcher = WebhookDispatcher::new(test_config(), None);
+        let webhook = register(&dispatcher, &server).await;
+
+        assert!(dispatcher.deliver(webhook.id, &order_event()).await.is_ok());
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+
+        let webhook = dispatcher.get(webhook.id).unwrap();
+        assert!(webhook.active);
+        assert_eq!(webhook.consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn test_persistent_failures_disable_endpoint() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let dispatcher = WebhookDispatcher::new(test_config(), None);
+        let mut notifications = dispatcher.subscribe_notifications();
+        let webhook = register(&dispatcher, &server).await;
+
+        // First exhausted delivery only counts a failure
+        assert!(dispatcher.deliver(webhook.id, &order_event()).await.is_err());
+        assert!(dispatcher.get(webhook.id).unwrap().active);
+
+        assert!(dispatcher.deliver(webhook.id, &order_event()).await.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 6);
+
+        let disabled = dispatcher.get(webhook.id).unwrap();
+        assert!(!disabled.active);
+        assert_eq!(disabled.consecutive_failures, 2);
+
+        let audit = dispatcher.audit_log(webhook.id);
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].action, AUDIT_AUTO_DISABLED);
+
+        let notice = notifications.try_recv().unwrap();
+        assert_eq!(notice.webhook_id, webhook.id);
+
+        // Disabled endpoints receive nothing further
+        assert!(dispatcher.deliver(webhook.id, &order_event()).await.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 6);
+
+        // Re-enabling clears the failure count
+        let enabled = dispatcher.update(webhook.id, Some(true), None).await.unwrap();
+        assert!(enabled.active);
+        assert_eq!(enabled.consecutive_failures, 0);
+    }
+}
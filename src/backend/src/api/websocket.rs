@@ -9,24 +9,44 @@
 //! - tracing = "0.1"
 //! - lz4 = "1.24"
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use async_trait::async_trait;
+use axum::response::IntoResponse;
 use chrono::{DateTime, Utc};
 use futures::{SinkExt, StreamExt};
-use metrics::{counter, histogram};
-use parking_lot::RwLock;
+use metrics::{counter, gauge, histogram};
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
 use thiserror::Error;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc, watch};
+use tokio::task::JoinHandle;
 use tracing::{debug, error, info, instrument, warn};
 use uuid::Uuid;
 use warp::ws::{Message, WebSocket};
 use warp::Filter;
 
-use crate::models::market::MarketData;
+use crate::api::endpoints::{ApiError, OrderRequest};
+use crate::api::idempotency::{self, CachedResponse, IdempotencyCache, IdempotentResponse};
+use crate::api::scoped_tokens::{is_scoped_token, redact, ScopedAccess, ScopedTokenRegistry};
+use crate::api::{validate_token, JwtKeyStore};
+use crate::data_collector::ohlcv::CandleEvent;
+use crate::data_collector::trades::PublicTrade;
+use crate::execution_engine::order_book::OrderBookSnapshot;
+use crate::execution_engine::TradeEvent;
+use crate::models::market::{MarketData, MarketDataBatch};
 use crate::models::portfolio::Portfolio;
+use crate::performance::LeaderboardEntry;
+use crate::risk_manager::factors::RiskFactorSnapshot;
+use crate::signals::{Signal, SignalConsumer};
+use crate::system_info::{ActivityCounts, ActivitySource};
+use crate::utils::circuit_breaker::{BreakerConfig, CircuitBreaker};
+use crate::utils::health::{ComponentStatus, HealthMonitor, HealthProbe};
 
 // Constants defined in JSON specification
 const PING_INTERVAL_MS: u64 = 30000;
@@ -34,7 +54,49 @@ const MAX_CONNECTIONS_PER_IP: usize = 5;
 const MARKET_DATA_BROADCAST_INTERVAL_MS: u64 = 100;
 const MESSAGE_BATCH_SIZE: usize = 100;
 const CONNECTION_TIMEOUT_MS: u64 = 60000;
+const REAP_INTERVAL_MS: u64 = 5000;
 const RETRY_ATTEMPTS: u8 = 3;
+const CANDLES_CHANNEL: &str = "candles";
+const SIGNALS_CHANNEL: &str = "signals";
+const STRATEGY_PERFORMANCE_CHANNEL: &str = "strategy_performance";
+const RISK_CHANNEL: &str = "risk";
+const TRADES_CHANNEL: &str = "trades";
+const ORDER_BOOK_CHANNEL_PREFIX: &str = "orderbook:";
+const PUBLIC_TRADES_CHANNEL_PREFIX: &str = "trades:";
+const STRATEGY_PERFORMANCE_CHANNEL_PREFIX: &str = "strategy_performance:";
+const ORDER_BOOK_THROTTLE_MS: u64 = 250;
+const ORDER_BOOK_WS_DEPTH: usize = 20;
+const SHUTDOWN_DRAIN_TIMEOUT_MS: u64 = 5000;
+const CLOSE_GOING_AWAY: u16 = 1001;
+const HEALTH_COMPONENT: &str = "websocket";
+const DEFAULT_CANDLE_HISTORY: usize = 300;
+const DEFAULT_TRADE_HISTORY: usize = 100;
+const DEFAULT_SIGNAL_HISTORY: usize = 100;
+const HISTORY_IDLE_TTL_MS: u64 = 600_000;
+const SNAPSHOT_FRAME_TYPE: &str = "snapshot";
+
+/// Channel name for a trading pair's order book updates
+pub fn order_book_channel(trading_pair: &str) -> String {
+    format!("{}{}", ORDER_BOOK_CHANNEL_PREFIX, trading_pair)
+}
+
+/// Channel name for a trading pair's public trade prints
+pub fn public_trades_channel(trading_pair: &str) -> String {
+    format!("{}{}", PUBLIC_TRADES_CHANNEL_PREFIX, trading_pair)
+}
+
+/// Channel name for one strategy's leaderboard entries, redacted for scoped tokens
+pub fn strategy_performance_channel(strategy_id: &str) -> String {
+    format!("{}{}", STRATEGY_PERFORMANCE_CHANNEL_PREFIX, strategy_id)
+}
+
+/// Renders an outbound frame as the JSON text clients receive. Frames go through `Value`
+/// so object keys are sorted, the layout clients have always been sent.
+fn render_frame<T: Serialize>(frame: &T) -> Result<String, WsError> {
+    serde_json::to_value(frame)
+        .map(|value| value.to_string())
+        .map_err(|e| WsError::BroadcastError(e.to_string()))
+}
 
 /// WebSocket-related error types
 #[derive(Error, Debug)]
@@ -57,6 +119,11 @@ struct ClientState {
     last_ping: Instant,
     connected_at: DateTime<Utc>,
     metrics: ClientMetrics,
+    sender: mpsc::UnboundedSender<Message>,
+    /// Wallet the connection's token was issued to, when it could be validated
+    wallet: Option<String>,
+    /// What the connection's token grants when it is a scoped read-only token
+    scope: Option<ScopedAccess>,
 }
 
 /// Performance metrics for client connections
@@ -68,6 +135,157 @@ struct ClientMetrics {
     errors: u64,
 }
 
+/// Outbound frame on a channel; `seq` increases by one with every frame the channel carries
+#[derive(Debug, Serialize)]
+struct Frame<'a, T> {
+    channel: &'a str,
+    seq: u64,
+    data: &'a T,
+}
+
+/// Retained frames replayed to a new subscriber; live frames continue after `seq`
+#[derive(Debug, Serialize)]
+struct SnapshotFrame<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    channel: &'a str,
+    seq: u64,
+    data: Vec<&'a RawValue>,
+}
+
+/// Control message sent by a client
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientRequest {
+    /// Market data subscriptions name the pair as `market_pair`
+    Subscribe {
+        #[serde(alias = "market_pair")]
+        channel: String,
+    },
+    Unsubscribe { channel: String },
+    /// Same order and idempotency semantics as `POST /api/v1/orders`
+    PlaceOrder {
+        #[serde(default)]
+        request_id: Option<String>,
+        #[serde(default)]
+        idempotency_key: Option<String>,
+        order: OrderRequest,
+    },
+}
+
+/// Reply to a client control message
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ControlFrame<'a> {
+    Subscribed { channel: &'a str },
+    Unsubscribed { channel: &'a str },
+    /// Status and body the REST API would have answered the order with
+    OrderResult {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<&'a str>,
+        status: u16,
+        replayed: bool,
+        body: serde_json::Value,
+    },
+    Error { message: String },
+}
+
+/// Places orders submitted over the socket
+#[async_trait]
+pub trait OrderPlacement: Send + Sync {
+    /// Places an order for a wallet, rendering the response the REST API would send
+    async fn place_order(&self, wallet: &str, order: &OrderRequest) -> CachedResponse;
+}
+
+impl std::fmt::Debug for dyn OrderPlacement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("OrderPlacement")
+    }
+}
+
+/// Frames retained per channel type and how long unsubscribed history is kept
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryConfig {
+    /// Candle events per pair on the `candles` channel
+    pub candles: usize,
+    /// Snapshots per `orderbook:{pair}` channel
+    pub order_book: usize,
+    /// Executed trades per pair on the `trades` channel
+    pub trades: usize,
+    /// Prints per `trades:{pair}` channel
+    pub public_trades: usize,
+    /// Signals per pair
+    pub signals: usize,
+    /// Leaderboard entries per strategy, and per `strategy_performance:{id}` channel
+    pub strategy_performance: usize,
+    pub risk: usize,
+    /// History of a channel without subscribers is dropped once idle this long
+    pub idle_ttl: Duration,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            candles: DEFAULT_CANDLE_HISTORY,
+            order_book: 1,
+            trades: DEFAULT_TRADE_HISTORY,
+            public_trades: DEFAULT_TRADE_HISTORY,
+            signals: DEFAULT_SIGNAL_HISTORY,
+            strategy_performance: 1,
+            risk: 1,
+            idle_ttl: Duration::from_millis(HISTORY_IDLE_TTL_MS),
+        }
+    }
+}
+
+impl HistoryConfig {
+    /// Frames retained per pair on a channel; 0 keeps no history
+    fn limit(&self, channel: &str) -> usize {
+        if channel.starts_with(ORDER_BOOK_CHANNEL_PREFIX) {
+            return self.order_book;
+        }
+        if channel.starts_with(PUBLIC_TRADES_CHANNEL_PREFIX) {
+            return self.public_trades;
+        }
+        if channel.starts_with(STRATEGY_PERFORMANCE_CHANNEL_PREFIX) {
+            return self.strategy_performance;
+        }
+        match channel {
+            CANDLES_CHANNEL => self.candles,
+            TRADES_CHANNEL => self.trades,
+            SIGNALS_CHANNEL => self.signals,
+            STRATEGY_PERFORMANCE_CHANNEL => self.strategy_performance,
+            RISK_CHANNEL => self.risk,
+            _ => 0,
+        }
+    }
+}
+
+/// Ring buffers of one channel's recent frames, keyed by pair (or strategy) where the
+/// channel carries several
+#[derive(Debug)]
+struct ChannelHistory {
+    /// Sequence number of the channel's last frame
+    seq: u64,
+    /// Frames as sent, rendered once at publish time
+    frames: HashMap<String, VecDeque<(u64, Box<RawValue>)>>,
+    /// Since when the channel has had no subscribers
+    idle_since: Option<Instant>,
+    /// Set when pruned, so a publisher holding a stale handle looks the channel up again
+    retired: bool,
+}
+
+impl ChannelHistory {
+    fn new() -> Self {
+        Self {
+            seq: 0,
+            frames: HashMap::new(),
+            idle_since: Some(Instant::now()),
+            retired: false,
+        }
+    }
+}
+
 /// Broadcast statistics for monitoring
 #[derive(Debug, Default)]
 pub struct BroadcastStats {
@@ -82,40 +300,116 @@ pub struct BroadcastStats {
 pub struct WebSocketServer {
     clients: Arc<RwLock<HashMap<Uuid, ClientState>>>,
     subscriptions: Arc<RwLock<HashMap<String, HashSet<Uuid>>>>,
-    market_data_tx: broadcast::Sender<Vec<MarketData>>,
+    market_data_tx: broadcast::Sender<MarketDataBatch>,
+    candles_tx: broadcast::Sender<CandleEvent>,
     metrics_collector: Arc<metrics::Metrics>,
-    circuit_breaker: Arc<RwLock<CircuitBreaker>>,
+    circuit_breaker: Arc<CircuitBreaker>,
+    /// Minimum spacing between order book frames per channel
+    order_book_throttle: Duration,
+    /// Per-connection writer tasks, awaited on shutdown so queued frames are flushed
+    writers: Mutex<Vec<JoinHandle<()>>>,
+    health: Option<Arc<HealthMonitor>>,
+    serving: AtomicBool,
+    history_config: HistoryConfig,
+    /// Recent frames per channel, replayed to new subscribers. Each channel has its own lock
+    /// so publishing on one channel never waits on another
+    history: RwLock<HashMap<String, Arc<Mutex<ChannelHistory>>>>,
+    /// Validates connection tokens so clients can act for their wallet
+    key_store: Option<Arc<JwtKeyStore>>,
+    /// Validates scoped read-only tokens, whose connections may subscribe only within scope
+    scoped_tokens: Option<Arc<ScopedTokenRegistry>>,
+    orders: Option<Arc<dyn OrderPlacement>>,
+    idempotency: Option<Arc<IdempotencyCache>>,
 }
 
 impl WebSocketServer {
     /// Creates new WebSocket server instance with optimized configuration
     pub fn new(metrics_collector: Arc<metrics::Metrics>) -> Self {
         let (market_data_tx, _) = broadcast::channel(10000); // Buffer size for market data broadcasts
+        let (candles_tx, _) = broadcast::channel(1000);
 
         Self {
             clients: Arc::new(RwLock::new(HashMap::new())),
             subscriptions: Arc::new(RwLock::new(HashMap::new())),
             market_data_tx,
+            candles_tx,
             metrics_collector,
-            circuit_breaker: Arc::new(RwLock::new(CircuitBreaker::new(
-                5, // Max consecutive failures
-                Duration::from_secs(60), // Reset period
-            ))),
+            circuit_breaker: CircuitBreaker::new(
+                "websocket_broadcast",
+                BreakerConfig::consecutive_failures(5).with_cooldown(Duration::from_secs(60)),
+            )
+            .registered(),
+            order_book_throttle: Duration::from_millis(ORDER_BOOK_THROTTLE_MS),
+            writers: Mutex::new(Vec::new()),
+            health: None,
+            serving: AtomicBool::new(false),
+            history_config: HistoryConfig::default(),
+            history: RwLock::new(HashMap::new()),
+            key_store: None,
+            scoped_tokens: None,
+            orders: None,
+            idempotency: None,
         }
     }
 
-    /// Starts the WebSocket server with monitoring
-    #[instrument(skip(self))]
-    pub fn start(
+    /// Sender carrying aggregated price updates, shared with the gRPC market data stream
+    pub fn market_data_sender(&self) -> broadcast::Sender<MarketDataBatch> {
+        self.market_data_tx.clone()
+    }
+
+    /// Overrides how often order book frames are pushed to each channel
+    pub fn with_order_book_throttle(mut self, throttle: Duration) -> Self {
+        self.order_book_throttle = throttle;
+        self
+    }
+
+    /// Overrides how many frames each channel type retains for new subscribers
+    pub fn with_history(mut self, config: HistoryConfig) -> Self {
+        self.history_config = config;
+        self
+    }
+
+    /// Authenticates connection tokens, which order placement requires
+    pub fn with_key_store(mut self, key_store: Arc<JwtKeyStore>) -> Self {
+        self.key_store = Some(key_store);
+        self
+    }
+
+    /// Accepts scoped read-only tokens, limited to the channels they cover
+    pub fn with_scoped_tokens(mut self, scoped_tokens: Arc<ScopedTokenRegistry>) -> Self {
+        self.scoped_tokens = Some(scoped_tokens);
+        self
+    }
+
+    /// Accepts `place_order` requests, deduplicated by idempotency key like the REST endpoint
+    pub fn with_order_placement(mut self, orders: Arc<dyn OrderPlacement>, idempotency: Arc<IdempotencyCache>) -> Self {
+        self.orders = Some(orders);
+        self.idempotency = Some(idempotency);
+        self
+    }
+
+    /// Registers the server with the health monitor while it is serving
+    pub fn with_health_monitor(mut self, health: Arc<HealthMonitor>) -> Self {
+        self.health = Some(health);
+        self
+    }
+
+    /// Binds `addr` and serves `/ws` until `shutdown` turns true, then closes every client
+    /// connection before the server task completes. Returns the server task and the bound
+    /// address, which differs from `addr` when binding port 0.
+    #[instrument(skip(self, shutdown))]
+    pub async fn start(
         self: Arc<Self>,
-        addr: std::net::SocketAddr,
-    ) -> Result<impl warp::Reply, warp::Rejection> {
+        addr: SocketAddr,
+        mut shutdown: watch::Receiver<bool>,
+    ) -> Result<(JoinHandle<()>, SocketAddr), WsError> {
+        let server = self.clone();
         let ws_route = warp::path("ws")
             .and(warp::ws())
             .and(warp::header::<String>("authorization"))
             .and(warp::addr::remote())
-            .map(move |ws: warp::ws::Ws, auth_token: String, client_ip: Option<std::net::SocketAddr>| {
-                let server = self.clone();
+            .map(move |ws: warp::ws::Ws, auth_token: String, client_ip: Option<SocketAddr>| {
+                let server = server.clone();
                 ws.on_upgrade(move |socket| async move {
                     if let Err(e) = server.handle_ws_connection(socket, auth_token, client_ip).await {
                         error!("WebSocket connection error: {}", e);
@@ -123,8 +417,66 @@ impl WebSocketServer {
                 })
             });
 
-        warp::serve(ws_route).run(addr);
-        Ok(warp::reply())
+        let server = self.clone();
+        let signal = async move {
+            // A dropped sender means the bot is gone, which is a shutdown too
+            while !*shutdown.borrow() {
+                if shutdown.changed().await.is_err() {
+                    break;
+                }
+            }
+            server.close_connections().await;
+        };
+
+        let (bound, serve) = warp::serve(ws_route)
+            .try_bind_with_graceful_shutdown(addr, signal)
+            .map_err(|e| WsError::ConnectionError(format!("failed to bind {}: {}", addr, e)))?;
+
+        self.serving.store(true, Ordering::SeqCst);
+        if let Some(health) = &self.health {
+            health.register(HEALTH_COMPONENT, self.clone());
+        }
+        info!(%bound, "WebSocket server listening");
+
+        let server = self;
+        let handle = tokio::spawn(async move {
+            serve.await;
+            server.serving.store(false, Ordering::SeqCst);
+            if let Some(health) = &server.health {
+                health.deregister(HEALTH_COMPONENT);
+            }
+            info!("WebSocket server stopped");
+        });
+
+        Ok((handle, bound))
+    }
+
+    /// Sends a going-away close frame to every client after its queued frames, then waits
+    /// for the writers to flush. Returns the number of connections closed.
+    pub async fn close_connections(&self) -> usize {
+        self.serving.store(false, Ordering::SeqCst);
+
+        let client_ids: Vec<Uuid> = {
+            let clients = self.clients.read();
+            for client in clients.values() {
+                let _ = client.sender.send(Message::close_with(CLOSE_GOING_AWAY, "server shutting down"));
+            }
+            clients.keys().copied().collect()
+        };
+        let closed = self.remove_clients(&client_ids);
+
+        let writers = std::mem::take(&mut *self.writers.lock());
+        let drain = futures::future::join_all(writers);
+        if tokio::time::timeout(Duration::from_millis(SHUTDOWN_DRAIN_TIMEOUT_MS), drain)
+            .await
+            .is_err()
+        {
+            warn!("Timed out flushing WebSocket clients on shutdown");
+        }
+
+        counter!("ws.connections_closed_on_shutdown", closed as u64);
+        info!(closed, "Closed WebSocket connections for shutdown");
+        closed
     }
 
     /// Handles new WebSocket connection establishment
@@ -133,8 +485,12 @@ impl WebSocketServer {
         &self,
         ws: WebSocket,
         auth_token: String,
-        client_ip: Option<std::net::SocketAddr>,
+        client_ip: Option<SocketAddr>,
     ) -> Result<(), WsError> {
+        if !self.serving.load(Ordering::SeqCst) {
+            return Err(WsError::ConnectionError("server shutting down".to_string()));
+        }
+
         // Validate connection limits
         if let Some(ip) = client_ip {
             let connections = self.clients.read().iter()
@@ -148,33 +504,77 @@ impl WebSocketServer {
             }
         }
 
-        let client_id = Uuid::new_v4();
-        let (mut ws_tx, mut ws_rx) = ws.split();
-
-        // Initialize client state
-        let client_state = ClientState {
-            id: client_id,
-            subscriptions: HashSet::new(),
-            last_ping: Instant::now(),
-            connected_at: Utc::now(),
-            metrics: ClientMetrics::default(),
+        // Scoped tokens are checked before registering; one that fails is refused outright
+        let token = auth_token.trim_start_matches("Bearer ").trim();
+        let scope = match (&self.scoped_tokens, is_scoped_token(token)) {
+            (Some(scoped_tokens), true) => Some(
+                scoped_tokens
+                    .authenticate(token, Utc::now())
+                    .map_err(|e| WsError::ConnectionError(e.to_string()))?,
+            ),
+            _ => None,
         };
 
-        self.clients.write().insert(client_id, client_state);
+        let (mut ws_tx, mut ws_rx) = ws.split();
+        let (client_id, mut outbound_rx) = self.register_client();
+        if scope.is_some() {
+            if let Some(client) = self.clients.write().get_mut(&client_id) {
+                client.scope = scope;
+            }
+        } else if let Some(key_store) = &self.key_store {
+            match validate_token(token, key_store).await {
+                Ok(claims) => {
+                    if let Some(client) = self.clients.write().get_mut(&client_id) {
+                        client.wallet = Some(claims.sub);
+                    }
+                }
+                Err(e) => debug!(%client_id, "Connection token rejected; orders disabled: {}", e),
+            }
+        }
 
-        // Start ping/pong heartbeat
-        let ping_interval = tokio::time::interval(Duration::from_millis(PING_INTERVAL_MS));
-        tokio::spawn(async move {
-            ping_interval.tick().await;
-            if let Err(e) = ws_tx.send(Message::ping(vec![])).await {
-                error!("Failed to send ping: {}", e);
+        // Forward outbound messages until the client is reaped, disconnects or is sent a
+        // close frame; the ping task's sender would otherwise keep this loop alive
+        let writer = tokio::spawn(async move {
+            while let Some(msg) = outbound_rx.recv().await {
+                let closing = msg.is_close();
+                if let Err(e) = ws_tx.send(msg).await {
+                    error!("Failed to send message: {}", e);
+                    break;
+                }
+                if closing {
+                    break;
+                }
             }
+            let _ = ws_tx.close().await;
         });
+        {
+            let mut writers = self.writers.lock();
+            writers.retain(|writer| !writer.is_finished());
+            writers.push(writer);
+        }
+
+        // Start ping/pong heartbeat
+        let ping_sender = self.clients.read().get(&client_id).map(|c| c.sender.clone());
+        if let Some(ping_sender) = ping_sender {
+            tokio::spawn(async move {
+                let mut ping_interval = tokio::time::interval(Duration::from_millis(PING_INTERVAL_MS));
+                loop {
+                    ping_interval.tick().await;
+                    if ping_sender.send(Message::ping(vec![])).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
 
         // Handle incoming messages
         while let Some(result) = ws_rx.next().await {
             match result {
                 Ok(msg) => {
+                    if msg.is_pong() {
+                        self.record_pong(client_id);
+                        continue;
+                    }
                     if let Err(e) = self.handle_ws_message(client_id, msg).await {
                         error!("Message handling error: {}", e);
                         break;
@@ -192,7 +592,8 @@ impl WebSocketServer {
         Ok(())
     }
 
-    /// Broadcasts market data updates with batching and compression
+    /// Broadcasts market data updates with batching and compression. The batch is shared,
+    /// not copied, with every in-process listener.
     #[instrument(skip(self, data_batch))]
     pub async fn broadcast_market_data(
         &self,
@@ -200,41 +601,50 @@ impl WebSocketServer {
     ) -> Result<BroadcastStats, WsError> {
         let mut stats = BroadcastStats::default();
         let start_time = Instant::now();
+        let batch: MarketDataBatch = data_batch.into();
 
         // Compress data batch
         let compressed_data = lz4::block::compress(
-            &bincode::serialize(&data_batch).map_err(|e| WsError::BroadcastError(e.to_string()))?,
+            &bincode::serialize(&*batch).map_err(|e| WsError::BroadcastError(e.to_string()))?,
             None,
         ).map_err(|e| WsError::BroadcastError(e.to_string()))?;
 
         stats.compressed_size_bytes = compressed_data.len();
 
         // Check circuit breaker
-        if self.circuit_breaker.read().should_break() {
+        if !self.circuit_breaker.allow() {
             return Err(WsError::BroadcastError("circuit breaker triggered".to_string()));
         }
 
-        // Broadcast to subscribed clients
-        let clients = self.clients.read();
-        let subscriptions = self.subscriptions.read();
-
-        for data in data_batch.iter() {
-            if let Some(subscribers) = subscriptions.get(&data.trading_pair) {
-                for client_id in subscribers {
-                    if let Some(client) = clients.get(client_id) {
-                        if let Err(e) = self.market_data_tx.send(vec![data.clone()]) {
-                            stats.failed_clients += 1;
-                            error!("Broadcast error for client {}: {}", client_id, e);
-                            continue;
-                        }
-                        stats.successful_clients += 1;
-                    }
+        // Broadcast once on behalf of every subscribed client
+        let subscribed: usize = {
+            let clients = self.clients.read();
+            let subscriptions = self.subscriptions.read();
+            batch
+                .iter()
+                .filter_map(|data| subscriptions.get(&data.trading_pair))
+                .map(|subscribers| subscribers.iter().filter(|client_id| clients.contains_key(client_id)).count())
+                .sum()
+        };
+
+        if subscribed > 0 {
+            match self.market_data_tx.send(batch) {
+                Ok(_) => stats.successful_clients = subscribed,
+                Err(e) => {
+                    stats.failed_clients = subscribed;
+                    error!("Market data broadcast error for {} clients: {}", subscribed, e);
                 }
             }
         }
 
         stats.total_latency_ms = start_time.elapsed().as_millis() as u64;
 
+        if stats.failed_clients > 0 {
+            self.circuit_breaker.record_failure();
+        } else {
+            self.circuit_breaker.record_success();
+        }
+
         // Record metrics
         histogram!(
             "ws.broadcast.latency_ms",
@@ -252,39 +662,654 @@ impl WebSocketServer {
         Ok(stats)
     }
 
+    /// Publishes candle close and correction events to `candles` channel subscribers
+    pub fn broadcast_candle_event(&self, event: CandleEvent) -> Result<usize, WsError> {
+        if matches!(event, CandleEvent::Correction { .. }) {
+            counter!("ws.candles.corrections", 1);
+        }
+
+        let trading_pair = match &event {
+            CandleEvent::Closed { candle } | CandleEvent::Correction { candle } => candle.trading_pair.clone(),
+        };
+        let sent = self.publish(CANDLES_CHANNEL, &trading_pair, &event)?;
+
+        // In-process listeners are optional
+        let _ = self.candles_tx.send(event);
+        counter!("ws.candles.events", 1);
+
+        Ok(sent)
+    }
+
+    /// Forwards aggregator candle events to the `candles` channel
+    pub fn spawn_candle_forwarder(
+        self: Arc<Self>,
+        mut events: broadcast::Receiver<CandleEvent>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        if let Err(e) = self.broadcast_candle_event(event) {
+                            warn!("Candle broadcast failed: {}", e);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        counter!("ws.candles.lagged", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Sends an order book snapshot to `orderbook:{pair}` subscribers
+    pub fn broadcast_order_book(&self, snapshot: OrderBookSnapshot) -> Result<usize, WsError> {
+        let channel = order_book_channel(&snapshot.trading_pair);
+        let sent = self.publish(&channel, "", &snapshot.truncated(ORDER_BOOK_WS_DEPTH))?;
+        counter!("ws.orderbook.frames", sent as u64);
+
+        Ok(sent)
+    }
+
+    /// Sends a strategy signal to `signals` channel subscribers
+    pub fn broadcast_signal(&self, signal: &Signal) -> Result<usize, WsError> {
+        let sent = self.publish(SIGNALS_CHANNEL, &signal.pair, signal)?;
+        counter!("ws.signals.frames", sent as u64);
+
+        Ok(sent)
+    }
+
+    /// Sends a recomputed leaderboard entry to `strategy_performance` channel subscribers, and
+    /// redacted to the strategy's own `strategy_performance:{id}` channel
+    pub fn broadcast_strategy_performance(&self, entry: &LeaderboardEntry) -> Result<usize, WsError> {
+        let mut sent = self.publish(STRATEGY_PERFORMANCE_CHANNEL, &entry.strategy_id, entry)?;
+        let mut redacted = serde_json::to_value(entry).map_err(|e| WsError::BroadcastError(e.to_string()))?;
+        redact(&mut redacted);
+        sent += self.publish(&strategy_performance_channel(&entry.strategy_id), &entry.strategy_id, &redacted)?;
+        counter!("ws.strategy_performance.frames", sent as u64);
+
+        Ok(sent)
+    }
+
+    /// Forwards leaderboard entries to the `strategy_performance` channel as they are recomputed
+    pub fn spawn_strategy_performance_forwarder(
+        self: Arc<Self>,
+        mut updates: broadcast::Receiver<LeaderboardEntry>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match updates.recv().await {
+                    Ok(entry) => {
+                        if let Err(e) = self.broadcast_strategy_performance(&entry) {
+                            warn!("Strategy performance broadcast failed: {}", e);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        counter!("ws.strategy_performance.lagged", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Sends a risk factor snapshot to `risk` channel subscribers
+    pub fn broadcast_risk_snapshot(&self, snapshot: &RiskFactorSnapshot) -> Result<usize, WsError> {
+        let sent = self.publish(RISK_CHANNEL, "", snapshot)?;
+        counter!("ws.risk.frames", sent as u64);
+
+        Ok(sent)
+    }
+
+    /// Forwards risk factor snapshots to the `risk` channel as they are computed
+    pub fn spawn_risk_forwarder(
+        self: Arc<Self>,
+        mut updates: broadcast::Receiver<RiskFactorSnapshot>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match updates.recv().await {
+                    Ok(snapshot) => {
+                        if let Err(e) = self.broadcast_risk_snapshot(&snapshot) {
+                            warn!("Risk snapshot broadcast failed: {}", e);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        counter!("ws.risk.lagged", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Sends an executed trade to `trades` channel subscribers
+    pub fn broadcast_trade(&self, trade: &TradeEvent) -> Result<usize, WsError> {
+        let sent = self.publish(TRADES_CHANNEL, &trade.trading_pair, trade)?;
+        counter!("ws.trades.frames", sent as u64);
+
+        Ok(sent)
+    }
+
+    /// Forwards executed trades to the `trades` channel
+    pub fn spawn_trade_forwarder(
+        self: Arc<Self>,
+        mut trades: broadcast::Receiver<TradeEvent>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match trades.recv().await {
+                    Ok(trade) => {
+                        if let Err(e) = self.broadcast_trade(&trade) {
+                            warn!("Trade broadcast failed: {}", e);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        counter!("ws.trades.lagged", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Sends a venue trade print to `trades:{pair}` subscribers
+    pub fn broadcast_public_trade(&self, trade: &PublicTrade) -> Result<usize, WsError> {
+        let channel = public_trades_channel(&trade.pair);
+        let sent = self.publish(&channel, "", trade)?;
+        counter!("ws.public_trades.frames", sent as u64);
+
+        Ok(sent)
+    }
+
+    /// Returns the live history of a channel, creating it on first use
+    fn channel_history(&self, channel: &str) -> Arc<Mutex<ChannelHistory>> {
+        if let Some(retained) = self.history.read().get(channel) {
+            return retained.clone();
+        }
+        self.history
+            .write()
+            .entry(channel.to_string())
+            .or_insert_with(|| Arc::new(Mutex::new(ChannelHistory::new())))
+            .clone()
+    }
+
+    /// Numbers a frame, retains it in the channel's history under `key` and sends it to the
+    /// channel's subscribers. The payload is serialized before any lock is taken; the channel's
+    /// own lock is then held while numbering and sending so a concurrent subscribe sees each
+    /// frame either in its snapshot or live, never both or neither.
+    fn publish<T: Serialize>(&self, channel: &str, key: &str, data: &T) -> Result<usize, WsError> {
+        let limit = self.history_config.limit(channel);
+        let has_subscribers = self
+            .subscriptions
+            .read()
+            .get(channel)
+            .map_or(false, |clients| !clients.is_empty());
+        if !has_subscribers && limit == 0 {
+            return Ok(0);
+        }
+
+        let payload = RawValue::from_string(render_frame(data)?)
+            .map_err(|e| WsError::BroadcastError(e.to_string()))?;
+
+        loop {
+            let handle = self.channel_history(channel);
+            let mut retained = handle.lock();
+            if retained.retired {
+                continue;
+            }
+
+            retained.seq += 1;
+            let seq = retained.seq;
+            let text = serde_json::to_string(&Frame { channel, seq, data: &payload })
+                .map_err(|e| WsError::BroadcastError(e.to_string()))?;
+
+            if limit > 0 {
+                let frame = RawValue::from_string(text.clone())
+                    .map_err(|e| WsError::BroadcastError(e.to_string()))?;
+                let frames = retained.frames.entry(key.to_string()).or_default();
+                frames.push_back((seq, frame));
+                while frames.len() > limit {
+                    frames.pop_front();
+                }
+            }
+
+            let subscribers: Vec<Uuid> = self
+                .subscriptions
+                .read()
+                .get(channel)
+                .map(|clients| clients.iter().copied().collect())
+                .unwrap_or_default();
+            let clients = self.clients.read();
+            let sent = subscribers
+                .iter()
+                .filter_map(|client_id| clients.get(client_id))
+                .filter(|client| client.sender.send(Message::text(text.clone())).is_ok())
+                .count();
+
+            return Ok(sent);
+        }
+    }
+
+    /// Subscribes a client to a channel, first sending the channel's retained history as one
+    /// `snapshot` frame when there is any. Returns how many frames the snapshot carried.
+    fn subscribe(&self, client_id: Uuid, channel: &str) -> usize {
+        let handle = loop {
+            let handle = self.channel_history(channel);
+            if !handle.lock().retired {
+                break handle;
+            }
+        };
+        let mut retained = handle.lock();
+        self.subscriptions
+            .write()
+            .entry(channel.to_string())
+            .or_default()
+            .insert(client_id);
+        retained.idle_since = None;
+
+        let mut frames: Vec<&(u64, Box<RawValue>)> = retained.frames.values().flatten().collect();
+        if frames.is_empty() {
+            return 0;
+        }
+        frames.sort_by_key(|(seq, _)| *seq);
+
+        let snapshot = SnapshotFrame {
+            kind: SNAPSHOT_FRAME_TYPE,
+            channel,
+            seq: retained.seq,
+            data: frames.iter().map(|(_, frame)| frame.as_ref()).collect(),
+        };
+        let text = match serde_json::to_string(&snapshot) {
+            Ok(text) => text,
+            Err(e) => {
+                warn!(channel, "Failed to serialize channel snapshot: {}", e);
+                return 0;
+            }
+        };
+
+        let clients = self.clients.read();
+        let Some(client) = clients.get(&client_id) else {
+            return 0;
+        };
+        if client.sender.send(Message::text(text)).is_err() {
+            return 0;
+        }
+        counter!("ws.snapshots.sent", 1);
+        histogram!("ws.snapshots.frames", frames.len() as f64);
+        frames.len()
+    }
+
+    /// Drops the history of channels that have had no subscribers for the idle TTL
+    pub fn prune_history(&self) -> usize {
+        let now = Instant::now();
+        let ttl = self.history_config.idle_ttl;
+        let mut history = self.history.write();
+
+        let before = history.len();
+        history.retain(|channel, handle| {
+            let mut retained = handle.lock();
+            let subscribed = self
+                .subscriptions
+                .read()
+                .get(channel)
+                .map_or(false, |clients| !clients.is_empty());
+            if subscribed {
+                retained.idle_since = None;
+                return true;
+            }
+            let keep = now.duration_since(*retained.idle_since.get_or_insert(now)) < ttl;
+            retained.retired = !keep;
+            keep
+        });
+
+        let pruned = before - history.len();
+        if pruned > 0 {
+            counter!("ws.history.pruned", pruned as u64);
+            debug!("Dropped history of {} idle WebSocket channels", pruned);
+        }
+        pruned
+    }
+
+    /// Forwards deduplicated venue prints to their `trades:{pair}` channels
+    pub fn spawn_public_trade_forwarder(
+        self: Arc<Self>,
+        mut trades: broadcast::Receiver<PublicTrade>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match trades.recv().await {
+                    Ok(trade) => {
+                        if let Err(e) = self.broadcast_public_trade(&trade) {
+                            warn!("Public trade broadcast failed: {}", e);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        counter!("ws.public_trades.lagged", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Forwards order book snapshots, coalescing internal updates so each channel
+    /// receives at most its latest snapshot once per throttle interval
+    pub fn spawn_order_book_forwarder(
+        self: Arc<Self>,
+        mut snapshots: broadcast::Receiver<OrderBookSnapshot>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let throttle = self.order_book_throttle;
+            let mut pending: HashMap<String, OrderBookSnapshot> = HashMap::new();
+            let mut interval =
+                tokio::time::interval_at(tokio::time::Instant::now() + throttle, throttle);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    received = snapshots.recv() => match received {
+                        Ok(snapshot) => {
+                            if pending.insert(snapshot.trading_pair.clone(), snapshot).is_some() {
+                                counter!("ws.orderbook.coalesced", 1);
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            counter!("ws.orderbook.lagged", skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = interval.tick() => {
+                        for (_, snapshot) in pending.drain() {
+                            if let Err(e) = self.broadcast_order_book(snapshot) {
+                                warn!("Order book broadcast failed: {}", e);
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Registers a new client and returns its id with the outbound message receiver
+    fn register_client(&self) -> (Uuid, mpsc::UnboundedReceiver<Message>) {
+        let client_id = Uuid::new_v4();
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        // Initialize client state
+        let client_state = ClientState {
+            id: client_id,
+            subscriptions: HashSet::new(),
+            last_ping: Instant::now(),
+            connected_at: Utc::now(),
+            metrics: ClientMetrics::default(),
+            sender,
+            wallet: None,
+            scope: None,
+        };
+
+        let active = {
+            let mut clients = self.clients.write();
+            clients.insert(client_id, client_state);
+            clients.len()
+        };
+        gauge!("ws.connections_active", active as f64);
+
+        (client_id, receiver)
+    }
+
+    /// Registers an in-process client subscribed to one channel, receiving the same frames a
+    /// socket client would; it never answers pings, so the reaper removes it after the timeout
+    pub fn subscribe_local(&self, channel: &str) -> (Uuid, mpsc::UnboundedReceiver<Message>) {
+        let (client_id, receiver) = self.register_client();
+        self.subscribe(client_id, channel);
+        (client_id, receiver)
+    }
+
+    /// Handles a client's subscribe, unsubscribe and order requests
+    async fn handle_ws_message(&self, client_id: Uuid, msg: Message) -> Result<(), WsError> {
+        let Ok(text) = msg.to_str() else {
+            return Ok(());
+        };
+
+        match serde_json::from_str::<ClientRequest>(text) {
+            Ok(ClientRequest::Subscribe { channel }) => {
+                if let Some(message) = self.scope_violation(client_id, &channel) {
+                    return self.send_control(client_id, &ControlFrame::Error { message });
+                }
+                // Acknowledge before the snapshot so the client knows what follows
+                self.send_control(client_id, &ControlFrame::Subscribed { channel: &channel })?;
+                if let Some(client) = self.clients.write().get_mut(&client_id) {
+                    client.subscriptions.insert(channel.clone());
+                }
+                self.subscribe(client_id, &channel);
+                Ok(())
+            }
+            Ok(ClientRequest::Unsubscribe { channel }) => {
+                if let Some(subscribers) = self.subscriptions.write().get_mut(&channel) {
+                    subscribers.remove(&client_id);
+                }
+                if let Some(client) = self.clients.write().get_mut(&client_id) {
+                    client.subscriptions.remove(&channel);
+                }
+                self.send_control(client_id, &ControlFrame::Unsubscribed { channel: &channel })
+            }
+            Ok(ClientRequest::PlaceOrder {
+                request_id,
+                idempotency_key,
+                order,
+            }) => {
+                self.place_order(client_id, request_id.as_deref(), idempotency_key, order)
+                    .await
+            }
+            Err(e) => self.send_control(
+                client_id,
+                &ControlFrame::Error {
+                    message: format!("invalid request: {}", e),
+                },
+            ),
+        }
+    }
+
+    /// Why a scoped connection may not subscribe to a channel, if it may not
+    fn scope_violation(&self, client_id: Uuid, channel: &str) -> Option<String> {
+        let scope = self.clients.read().get(&client_id).and_then(|client| client.scope.clone())?;
+        let active = self
+            .scoped_tokens
+            .as_ref()
+            .map_or(false, |scoped_tokens| scoped_tokens.is_active(&scope, Utc::now()));
+        if !active {
+            return Some("token has been revoked or has expired".to_string());
+        }
+        if !scope.allows_channel(channel) {
+            counter!("ws.scoped_tokens.forbidden", 1);
+            return Some(format!("token scope does not cover channel {}", channel));
+        }
+        None
+    }
+
+    /// Places an order for the connection's wallet and answers with the REST response
+    async fn place_order(
+        &self,
+        client_id: Uuid,
+        request_id: Option<&str>,
+        idempotency_key: Option<String>,
+        order: OrderRequest,
+    ) -> Result<(), WsError> {
+        let (Some(orders), Some(idempotency)) = (&self.orders, &self.idempotency) else {
+            return self.send_control(
+                client_id,
+                &ControlFrame::Error {
+                    message: "order placement is not available".to_string(),
+                },
+            );
+        };
+        let wallet = self.clients.read().get(&client_id).and_then(|client| client.wallet.clone());
+        let Some(wallet) = wallet else {
+            return self.send_control(
+                client_id,
+                &ControlFrame::Error {
+                    message: "order placement requires an authenticated connection".to_string(),
+                },
+            );
+        };
+
+        let outcome = match idempotency_key {
+            Some(key) => {
+                idempotency
+                    .run(&wallet, &key, &idempotency::fingerprint(&order), || {
+                        orders.place_order(&wallet, &order)
+                    })
+                    .await
+            }
+            None => Ok(IdempotentResponse {
+                response: orders.place_order(&wallet, &order).await,
+                replayed: false,
+            }),
+        };
+        let outcome = match outcome {
+            Ok(outcome) => outcome,
+            Err(e) => IdempotentResponse {
+                response: CachedResponse::from_response(ApiError::from(e).into_response()).await,
+                replayed: false,
+            },
+        };
+
+        self.send_control(
+            client_id,
+            &ControlFrame::OrderResult {
+                request_id,
+                status: outcome.response.status,
+                replayed: outcome.replayed,
+                body: serde_json::from_str(&outcome.response.body).unwrap_or(serde_json::Value::Null),
+            },
+        )
+    }
+
+    fn send_control(&self, client_id: Uuid, frame: &ControlFrame<'_>) -> Result<(), WsError> {
+        let text = serde_json::to_string(frame).map_err(|e| WsError::BroadcastError(e.to_string()))?;
+        let clients = self.clients.read();
+        let client = clients
+            .get(&client_id)
+            .ok_or_else(|| WsError::ConnectionError(format!("client {} disconnected", client_id)))?;
+        client
+            .sender
+            .send(Message::text(text))
+            .map_err(|e| WsError::ConnectionError(e.to_string()))
+    }
+
+    /// Refreshes the heartbeat timestamp on pong
+    fn record_pong(&self, client_id: Uuid) {
+        if let Some(client) = self.clients.write().get_mut(&client_id) {
+            client.last_ping = Instant::now();
+        }
+    }
+
+    /// Removes a client from the client map and every subscription set
+    async fn handle_client_disconnect(&self, client_id: Uuid) {
+        if self.remove_clients(&[client_id]) > 0 {
+            debug!("Client {} disconnected", client_id);
+        }
+    }
+
+    /// Removes clients whose last pong is older than the timeout, dropping their senders
+    pub fn reap_stale_clients(&self, timeout: Duration) -> usize {
+        let stale: Vec<Uuid> = self
+            .clients
+            .read()
+            .iter()
+            .filter(|(_, state)| state.last_ping.elapsed() > timeout)
+            .map(|(id, _)| *id)
+            .collect();
+
+        if stale.is_empty() {
+            return 0;
+        }
+
+        let reaped = self.remove_clients(&stale);
+        counter!("ws.connections_reaped", reaped as u64);
+        info!("Reaped {} unresponsive WebSocket clients", reaped);
+        reaped
+    }
+
+    /// Periodically reaps clients that stopped answering pings
+    pub fn spawn_reaper(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(REAP_INTERVAL_MS));
+            loop {
+                interval.tick().await;
+                self.reap_stale_clients(Duration::from_millis(CONNECTION_TIMEOUT_MS));
+                self.prune_history();
+            }
+        })
+    }
+
+    fn remove_clients(&self, client_ids: &[Uuid]) -> usize {
+        let mut clients = self.clients.write();
+        let mut subscriptions = self.subscriptions.write();
+
+        let mut removed = 0;
+        for client_id in client_ids {
+            // Dropping the state drops the sender, which ends the writer task
+            if clients.remove(client_id).is_some() {
+                removed += 1;
+            }
+        }
+
+        for subscribers in subscriptions.values_mut() {
+            for client_id in client_ids {
+                subscribers.remove(client_id);
+            }
+        }
+        subscriptions.retain(|_, subscribers| !subscribers.is_empty());
+
+        gauge!("ws.connections_active", clients.len() as f64);
+        removed
+    }
+
     // Additional helper methods would be implemented here
 }
 
-/// Circuit breaker for broadcast protection
-#[derive(Debug)]
-struct CircuitBreaker {
-    failures: u32,
-    max_failures: u32,
-    last_failure: Instant,
-    reset_period: Duration,
+#[async_trait]
+impl SignalConsumer for WebSocketServer {
+    fn name(&self) -> &'static str {
+        "websocket"
+    }
+
+    async fn handle(&self, signal: &Signal) {
+        if let Err(e) = self.broadcast_signal(signal) {
+            warn!("Signal broadcast failed: {}", e);
+        }
+    }
 }
 
-impl CircuitBreaker {
-    fn new(max_failures: u32, reset_period: Duration) -> Self {
-        Self {
-            failures: 0,
-            max_failures,
-            last_failure: Instant::now(),
-            reset_period,
+#[async_trait]
+impl ActivitySource for WebSocketServer {
+    async fn activity(&self) -> ActivityCounts {
+        ActivityCounts {
+            websocket_connections: self.clients.read().len(),
+            ..Default::default()
         }
     }
+}
 
-    fn should_break(&self) -> bool {
-        if self.last_failure.elapsed() > self.reset_period {
-            return false;
+impl HealthProbe for WebSocketServer {
+    fn status(&self) -> ComponentStatus {
+        if self.serving.load(Ordering::SeqCst) {
+            ComponentStatus::Healthy
+        } else {
+            ComponentStatus::Down("not serving".to_string())
         }
-        self.failures >= self.max_failures
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::idempotency::{IdempotencyRecord, IdempotencyStore};
+    use crate::models::market::OrderBookLevel;
+    use std::sync::atomic::AtomicUsize;
+    use rust_decimal::Decimal;
     use rust_decimal_macros::dec;
 
     #[tokio::test]
@@ -294,6 +1319,119 @@ mod tests {
         assert!(server.clients.read().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_close_connections_after_queued_frames() {
+        let metrics = Arc::new(metrics::Metrics::new());
+        let server = WebSocketServer::new(metrics);
+
+        let (client_id, mut rx) = server.register_client();
+        server.clients.read()[&client_id]
+            .sender
+            .send(Message::text("queued"))
+            .unwrap();
+
+        assert_eq!(server.close_connections().await, 1);
+        assert!(server.clients.read().is_empty());
+
+        // In-flight frames drain ahead of the close frame
+        assert_eq!(rx.recv().await.unwrap().to_str().unwrap(), "queued");
+        assert!(rx.recv().await.unwrap().is_close());
+        assert!(rx.recv().await.is_none());
+        assert_eq!(server.status(), ComponentStatus::Down("not serving".to_string()));
+    }
+
+    #[derive(Default)]
+    struct MemoryIdempotency(Mutex<HashMap<String, IdempotencyRecord>>);
+
+    #[async_trait]
+    impl IdempotencyStore for MemoryIdempotency {
+        async fn claim(
+            &self,
+            wallet: &str,
+            key: &str,
+            record: &IdempotencyRecord,
+            _ttl: Duration,
+        ) -> Result<Option<IdempotencyRecord>, String> {
+            let mut records = self.0.lock();
+            let entry = format!("{}:{}", wallet, key);
+            if let Some(existing) = records.get(&entry) {
+                return Ok(Some(existing.clone()));
+            }
+            records.insert(entry, record.clone());
+            Ok(None)
+        }
+
+        async fn complete(&self, wallet: &str, key: &str, record: &IdempotencyRecord, _ttl: Duration) -> Result<(), String> {
+            self.0.lock().insert(format!("{}:{}", wallet, key), record.clone());
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct CountingPlacement(AtomicUsize);
+
+    #[async_trait]
+    impl OrderPlacement for CountingPlacement {
+        async fn place_order(&self, wallet: &str, _order: &OrderRequest) -> CachedResponse {
+            let n = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            CachedResponse {
+                status: 200,
+                body: format!(r#"{{"order_id":"{}-{}"}}"#, wallet, n),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_place_order_replayed_by_idempotency_key() {
+        let placement = Arc::new(CountingPlacement::default());
+        let server = WebSocketServer::new(Arc::new(metrics::Metrics::new())).with_order_placement(
+            placement.clone(),
+            Arc::new(IdempotencyCache::new(Arc::new(MemoryIdempotency::default()))),
+        );
+        let (client_id, mut rx) = server.register_client();
+        server.clients.write().get_mut(&client_id).unwrap().wallet = Some("wallet".to_string());
+
+        let request = |amount: f64| {
+            Message::text(
+                serde_json::json!({
+                    "type": "place_order",
+                    "request_id": "r1",
+                    "idempotency_key": "key-1",
+                    "order": {
+                        "trading_pair": "SOL/USDC",
+                        "side": "buy",
+                        "amount": amount,
+                        "price": 25.0,
+                        "order_type": "LIMIT",
+                        "time_in_force": "GOOD_TIL_CANCELLED"
+                    }
+                })
+                .to_string(),
+            )
+        };
+        let reply = |rx: &mut mpsc::UnboundedReceiver<Message>| {
+            serde_json::from_str::<serde_json::Value>(rx.try_recv().unwrap().to_str().unwrap()).unwrap()
+        };
+
+        server.handle_ws_message(client_id, request(1.0)).await.unwrap();
+        let first = reply(&mut rx);
+        assert_eq!(first["type"], "order_result");
+        assert_eq!(first["request_id"], "r1");
+        assert_eq!(first["replayed"], false);
+        assert_eq!(first["body"]["order_id"], "wallet-1");
+
+        server.handle_ws_message(client_id, request(1.0)).await.unwrap();
+        let retry = reply(&mut rx);
+        assert_eq!(retry["replayed"], true);
+        assert_eq!(retry["body"], first["body"]);
+
+        server.handle_ws_message(client_id, request(2.0)).await.unwrap();
+        let conflict = reply(&mut rx);
+        assert_eq!(conflict["status"], 409);
+        assert_eq!(conflict["body"]["code"], "idempotency_key_conflict");
+        assert_eq!(placement.0.load(Ordering::SeqCst), 1);
+    }
+
     #[tokio::test]
     async fn test_market_data_broadcast() {
         let metrics = Arc::new(metrics::Metrics::new());
@@ -309,4 +1447,405 @@ mod tests {
         let result = server.broadcast_market_data(market_data).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_order_book_updates_coalesced() {
+        let metrics = Arc::new(metrics::Metrics::new());
+        let server = Arc::new(
+            WebSocketServer::new(metrics).with_order_book_throttle(Duration::from_millis(50)),
+        );
+
+        let (client_id, mut rx) = server.register_client();
+        server
+            .subscriptions
+            .write()
+            .entry(order_book_channel("SOL/USDC"))
+            .or_default()
+            .insert(client_id);
+
+        let (tx, snapshots) = broadcast::channel(64);
+        let forwarder = server.clone().spawn_order_book_forwarder(snapshots);
+
+        // Ten internal updates well inside one throttle window
+        for i in 1..=10 {
+            tx.send(OrderBookSnapshot {
+                trading_pair: "SOL/USDC".to_string(),
+                exchange: "jupiter".to_string(),
+                bids: vec![OrderBookLevel::new(dec!(23.40000000), Decimal::from(i))],
+                asks: vec![],
+                timestamp: Utc::now(),
+                is_stale: false,
+            })
+            .unwrap();
+        }
+
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        let frame = rx.try_recv().unwrap();
+        assert!(rx.try_recv().is_err());
+
+        let frame: serde_json::Value = serde_json::from_str(frame.to_str().unwrap()).unwrap();
+        assert_eq!(frame["channel"], "orderbook:SOL/USDC");
+        assert_eq!(frame["data"]["bids"][0]["volume"], "10");
+
+        // No further frames once the book stops changing
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert!(rx.try_recv().is_err());
+        forwarder.abort();
+    }
+
+    #[tokio::test]
+    async fn test_strategy_performance_forwarded_to_subscribers() {
+        let metrics = Arc::new(metrics::Metrics::new());
+        let server = Arc::new(WebSocketServer::new(metrics));
+
+        let (client_id, mut rx) = server.register_client();
+        let (_other_id, mut other_rx) = server.register_client();
+        server
+            .subscriptions
+            .write()
+            .entry(STRATEGY_PERFORMANCE_CHANNEL.to_string())
+            .or_default()
+            .insert(client_id);
+
+        let (tx, updates) = broadcast::channel(16);
+        let forwarder = server.clone().spawn_strategy_performance_forwarder(updates);
+        tx.send(LeaderboardEntry {
+            strategy_id: "grid".to_string(),
+            strategy_type: crate::models::strategy::StrategyType::Grid,
+            state: crate::models::strategy::StrategyState::Active,
+            sharpe_ratio: None,
+            sample_size: 4,
+            win_rate: dec!(50),
+            roi: dec!(1.5),
+            max_drawdown: dec!(0.4),
+            total_trades: 4,
+            allocation_used: Some(dec!(1000)),
+            performance_score: dec!(14.92),
+            realized_pnl: dec!(15),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        })
+        .unwrap();
+
+        let frame = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await.unwrap().unwrap();
+        let frame: serde_json::Value = serde_json::from_str(frame.to_str().unwrap()).unwrap();
+        assert_eq!(frame["channel"], "strategy_performance");
+        assert!(frame["data"]["sharpe_ratio"].is_null());
+        assert_eq!(frame["data"]["sample_size"], 4);
+        assert!(other_rx.try_recv().is_err());
+        forwarder.abort();
+    }
+
+    #[tokio::test]
+    async fn test_scoped_client_limited_to_redacted_strategy_channel() {
+        let metrics = Arc::new(metrics::Metrics::new());
+        let registry = Arc::new(ScopedTokenRegistry::default());
+        let minted = registry
+            .mint(
+                crate::api::MintTokenRequest {
+                    label: "public dashboard".to_string(),
+                    scopes: vec![crate::api::TokenScope::strategy("grid")],
+                    expires_in_days: None,
+                },
+                "admin",
+                Utc::now(),
+            )
+            .await
+            .unwrap();
+        let server = Arc::new(WebSocketServer::new(metrics).with_scoped_tokens(registry.clone()));
+
+        let (client_id, mut rx) = server.register_client();
+        server.clients.write().get_mut(&client_id).unwrap().scope =
+            Some(registry.authenticate(&minted.secret, Utc::now()).unwrap());
+
+        let subscribe =
+            |channel: &str| Message::text(serde_json::json!({ "type": "subscribe", "channel": channel }).to_string());
+        server.handle_ws_message(client_id, subscribe("signals")).await.unwrap();
+        let frame: serde_json::Value = serde_json::from_str(rx.recv().await.unwrap().to_str().unwrap()).unwrap();
+        assert_eq!(frame["type"], "error");
+        assert!(!server.subscriptions.read().contains_key("signals"));
+
+        server
+            .handle_ws_message(client_id, subscribe("strategy_performance:grid"))
+            .await
+            .unwrap();
+        let frame: serde_json::Value = serde_json::from_str(rx.recv().await.unwrap().to_str().unwrap()).unwrap();
+        assert_eq!(frame["type"], "subscribed");
+
+        server
+            .broadcast_strategy_performance(&LeaderboardEntry {
+                strategy_id: "grid".to_string(),
+                strategy_type: crate::models::strategy::StrategyType::Grid,
+                state: crate::models::strategy::StrategyState::Active,
+                sharpe_ratio: None,
+                sample_size: 4,
+                win_rate: dec!(50),
+                roi: dec!(1.5),
+                max_drawdown: dec!(0.4),
+                total_trades: 4,
+                allocation_used: Some(dec!(1000)),
+                performance_score: dec!(14.92),
+                realized_pnl: dec!(15),
+                updated_at: Utc::now(),
+                deleted_at: None,
+            })
+            .unwrap();
+        let frame: serde_json::Value = serde_json::from_str(rx.recv().await.unwrap().to_str().unwrap()).unwrap();
+        assert_eq!(frame["channel"], "strategy_performance:grid");
+        assert_eq!(frame["data"]["sample_size"], 4);
+        assert!(frame["data"].get("realized_pnl").is_none());
+        assert!(frame["data"].get("allocation_used").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_risk_snapshots_forwarded_to_subscribers() {
+        use crate::risk_manager::factors::RiskFactor;
+
+        let metrics = Arc::new(metrics::Metrics::new());
+        let server = Arc::new(WebSocketServer::new(metrics));
+
+        let (client_id, mut rx) = server.register_client();
+        let (_other_id, mut other_rx) = server.register_client();
+        server
+            .subscriptions
+            .write()
+            .entry(RISK_CHANNEL.to_string())
+            .or_default()
+            .insert(client_id);
+
+        let (tx, updates) = broadcast::channel(16);
+        let forwarder = server.clone().spawn_risk_forwarder(updates);
+        tx.send(RiskFactorSnapshot {
+            id: Uuid::new_v4(),
+            computed_at: Utc::now(),
+            total_value: dec!(1000),
+            exposures: Default::default(),
+            net_exposure: dec!(0),
+            gross_exposure: dec!(0),
+            leverage: dec!(0),
+            drawdown: dec!(0),
+            value_at_risk: RiskFactor::missing("insufficient volatility or correlation history for SOL"),
+            margin_utilization: RiskFactor::known(dec!(0.25)),
+            breakers: Vec::new(),
+            circuit_breaker_tripped: false,
+        })
+        .unwrap();
+
+        let frame = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await.unwrap().unwrap();
+        let frame: serde_json::Value = serde_json::from_str(frame.to_str().unwrap()).unwrap();
+        assert_eq!(frame["channel"], "risk");
+        assert!(frame["data"]["value_at_risk"]["value"].is_null());
+        assert!(frame["data"]["value_at_risk"]["reason"].as_str().unwrap().contains("SOL"));
+        assert!(other_rx.try_recv().is_err());
+        forwarder.abort();
+    }
+
+    #[tokio::test]
+    async fn test_public_trades_forwarded_by_pair() {
+        use crate::risk_manager::exposure::TradeSide;
+
+        let metrics = Arc::new(metrics::Metrics::new());
+        let server = Arc::new(WebSocketServer::new(metrics));
+
+        let (_client_id, mut rx) = server.subscribe_local(&public_trades_channel("SOL/USDC"));
+        let (_other_id, mut other_rx) = server.subscribe_local(&public_trades_channel("BONK/SOL"));
+
+        let (tx, trades) = broadcast::channel(16);
+        let forwarder = server.clone().spawn_public_trade_forwarder(trades);
+        let trade = PublicTrade {
+            pair: "SOL/USDC".to_string(),
+            exchange: "drift".to_string(),
+            price: dec!(23.55),
+            size: dec!(4),
+            side: TradeSide::Sell,
+            timestamp: Utc::now(),
+            trade_id: Some("SOL-PERP-88120".to_string()),
+        };
+        tx.send(trade).unwrap();
+
+        let frame = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await.unwrap().unwrap();
+        let frame: serde_json::Value = serde_json::from_str(frame.to_str().unwrap()).unwrap();
+        assert_eq!(frame["channel"], "trades:SOL/USDC");
+        assert_eq!(frame["data"]["side"], "sell");
+        assert_eq!(frame["data"]["trade_id"], "SOL-PERP-88120");
+        assert!(other_rx.try_recv().is_err());
+        forwarder.abort();
+    }
+
+    fn print(pair: &str, i: u64) -> PublicTrade {
+        PublicTrade {
+            pair: pair.to_string(),
+            exchange: "drift".to_string(),
+            price: dec!(23.55),
+            size: Decimal::from(i),
+            side: crate::risk_manager::exposure::TradeSide::Buy,
+            timestamp: Utc::now(),
+            trade_id: Some(format!("SOL-PERP-{}", i)),
+        }
+    }
+
+    fn next_frame(rx: &mut mpsc::UnboundedReceiver<Message>) -> serde_json::Value {
+        serde_json::from_str(rx.try_recv().unwrap().to_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_history_replayed_on_subscribe() {
+        let metrics = Arc::new(metrics::Metrics::new());
+        let server = WebSocketServer::new(metrics);
+        let channel = public_trades_channel("SOL/USDC");
+
+        for i in 1..=500 {
+            server.broadcast_public_trade(&print("SOL/USDC", i)).unwrap();
+        }
+
+        let (_client_id, mut rx) = server.subscribe_local(&channel);
+        for i in 501..=503 {
+            assert_eq!(server.broadcast_public_trade(&print("SOL/USDC", i)).unwrap(), 1);
+        }
+
+        // Only the retained tail arrives, as one snapshot ahead of the live frames
+        let snapshot = next_frame(&mut rx);
+        assert_eq!(snapshot["type"], "snapshot");
+        assert_eq!(snapshot["channel"], channel.as_str());
+        assert_eq!(snapshot["seq"], 500);
+        let frames = snapshot["data"].as_array().unwrap();
+        assert_eq!(frames.len(), DEFAULT_TRADE_HISTORY);
+        let seqs: Vec<u64> = frames.iter().map(|frame| frame["seq"].as_u64().unwrap()).collect();
+        assert_eq!(seqs, (401..=500).collect::<Vec<_>>());
+
+        for seq in 501..=503 {
+            let frame = next_frame(&mut rx);
+            assert_eq!(frame["seq"], seq);
+            assert_eq!(frame["data"]["trade_id"], format!("SOL-PERP-{}", seq));
+        }
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_frames_byte_identical_to_value_rendering() {
+        use chrono::TimeZone;
+
+        let metrics = Arc::new(metrics::Metrics::new());
+        let server = WebSocketServer::new(metrics);
+        let channel = public_trades_channel("SOL/USDC");
+        let stamped = |i: u64| PublicTrade {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+            ..print("SOL/USDC", i)
+        };
+
+        let (_live_id, mut live_rx) = server.subscribe_local(&channel);
+        server.broadcast_public_trade(&stamped(1)).unwrap();
+        server.broadcast_public_trade(&stamped(2)).unwrap();
+
+        let frame = |seq: u64| {
+            format!(
+                r#"{{"channel":"trades:SOL/USDC","data":{{"exchange":"drift","pair":"SOL/USDC","price":"23.55","side":"buy","size":"{seq}","timestamp":"2024-01-02T03:04:05Z","trade_id":"SOL-PERP-{seq}"}},"seq":{seq}}}"#,
+                seq = seq
+            )
+        };
+        for seq in 1..=2 {
+            assert_eq!(live_rx.try_recv().unwrap().to_str().unwrap(), frame(seq));
+        }
+
+        // Snapshots splice the retained text in unchanged
+        let (_late_id, mut late_rx) = server.subscribe_local(&channel);
+        assert_eq!(
+            late_rx.try_recv().unwrap().to_str().unwrap(),
+            format!(r#"{{"type":"snapshot","channel":"trades:SOL/USDC","seq":2,"data":[{},{}]}}"#, frame(1), frame(2))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_bounded_per_pair() {
+        let metrics = Arc::new(metrics::Metrics::new());
+        let server = WebSocketServer::new(metrics).with_history(HistoryConfig {
+            trades: 2,
+            ..HistoryConfig::default()
+        });
+
+        let mut trade = TradeEvent {
+            trade_id: "fill-1".to_string(),
+            strategy_id: "grid".to_string(),
+            trading_pair: "SOL/USDC".to_string(),
+            exchange: "jupiter".to_string(),
+            side: crate::risk_manager::exposure::TradeSide::Buy,
+            size: dec!(1),
+            price: dec!(23.45),
+            executed_at: Utc::now(),
+            configured_slippage_bps: None,
+            realized_slippage_bps: None,
+            size_multiplier: None,
+        };
+        for _ in 0..5 {
+            server.broadcast_trade(&trade).unwrap();
+        }
+        trade.trading_pair = "ORCA/USDC".to_string();
+        server.broadcast_trade(&trade).unwrap();
+
+        // A busy pair cannot push a quiet one out of the snapshot
+        let (_client_id, mut rx) = server.subscribe_local(TRADES_CHANNEL);
+        let snapshot = next_frame(&mut rx);
+        let seqs: Vec<u64> = snapshot["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|frame| frame["seq"].as_u64().unwrap())
+            .collect();
+        assert_eq!(seqs, vec![4, 5, 6]);
+    }
+
+    #[tokio::test]
+    async fn test_idle_history_pruned() {
+        let metrics = Arc::new(metrics::Metrics::new());
+        let server = WebSocketServer::new(metrics).with_history(HistoryConfig {
+            idle_ttl: Duration::from_millis(20),
+            ..HistoryConfig::default()
+        });
+        let channel = public_trades_channel("SOL/USDC");
+        server.broadcast_public_trade(&print("SOL/USDC", 1)).unwrap();
+        server.broadcast_public_trade(&print("BONK/SOL", 1)).unwrap();
+
+        let (client_id, _rx) = server.subscribe_local(&channel);
+        tokio::time::sleep(Duration::from_millis(40)).await;
+
+        // The subscribed channel keeps its history
+        assert_eq!(server.prune_history(), 1);
+        assert!(server.history.read().contains_key(&channel));
+
+        server.remove_clients(&[client_id]);
+        assert_eq!(server.prune_history(), 0);
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        assert_eq!(server.prune_history(), 1);
+        assert!(server.history.read().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unresponsive_client_reaped() {
+        let metrics = Arc::new(metrics::Metrics::new());
+        let server = WebSocketServer::new(metrics);
+        let timeout = Duration::from_millis(50);
+
+        let (alive_id, _alive_rx) = server.register_client();
+        let (dead_id, mut dead_rx) = server.register_client();
+        for id in [alive_id, dead_id] {
+            server
+                .subscriptions
+                .write()
+                .entry(CANDLES_CHANNEL.to_string())
+                .or_default()
+                .insert(id);
+        }
+
+        // Only the live client answers pings within the timeout
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        server.record_pong(alive_id);
+
+        assert_eq!(server.reap_stale_clients(timeout), 1);
+        assert!(server.clients.read().contains_key(&alive_id));
+        assert!(!server.clients.read().contains_key(&dead_id));
+        assert!(!server.subscriptions.read()[CANDLES_CHANNEL].contains(&dead_id));
+
+        // Sender was dropped with the client state
+        assert!(dead_rx.recv().await.is_none());
+    }
 }
\ No newline at end of file
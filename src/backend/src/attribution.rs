@@ -0,0 +1,1163 @@
+//! P&L attribution and balance reconciliation for finance reporting. Every fill, fee, funding
+//! payment and transfer is recorded as a ledger entry tagged with the wallet it moved and, for
+//! P&L, the strategy, parameter version, venue and pair that produced it. A periodic job rolls
+//! each day's entries up into `attribution_periods`; reports regroup those rows by any subset
+//! of tags and reconcile the explained P&L and flows against each wallet's observed balance
+//! change, alerting when the unexplained residue exceeds a threshold.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+//! - utoipa = "3.5"
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use metrics::counter;
+use parking_lot::{Mutex, RwLock as SyncRwLock};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::api::WebhookDispatcher;
+use crate::jobs::{Job, JobContext, JobHandler, JobQueue};
+use crate::models::order::OrderFill;
+use crate::risk_manager::exposure::TradeSide;
+use crate::models::transfer::Transfer;
+use crate::models::webhook::{WebhookEvent, WebhookEventType};
+use crate::performance::VersionTagger;
+use crate::persistence::PersistenceQueue;
+use crate::strategy_archive::DeletionRegistry;
+
+// Attribution constants
+const METRICS_PREFIX: &str = "trading_bot.attribution";
+const ALERT_CHANNEL_CAPACITY: usize = 64;
+const DEFAULT_RESIDUAL_THRESHOLD: Decimal = Decimal::ONE;
+const DEFAULT_ROLLUP_INTERVAL: Duration = Duration::from_secs(3600);
+/// Tag value for P&L recorded without a strategy, venue or pair
+pub const UNATTRIBUTED: &str = "unattributed";
+pub const MAX_REPORT_DAYS: i64 = 366;
+pub const DEFAULT_GROUP_BY: [AttributionDimension; 3] = [
+    AttributionDimension::Strategy,
+    AttributionDimension::Venue,
+    AttributionDimension::Pair,
+];
+
+/// Attribution error types
+#[derive(Error, Debug)]
+pub enum AttributionError {
+    #[error("unknown attribution dimension: {0}")]
+    UnknownDimension(String),
+    #[error("unknown ledger entry kind: {0}")]
+    UnknownKind(String),
+    #[error("invalid report range: {0}")]
+    InvalidRange(String),
+    #[error("store error: {0}")]
+    Store(String),
+}
+
+/// What moved a wallet's balance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    Trade,
+    Fee,
+    Funding,
+    /// Deposit or withdrawal; moves the balance without being P&L
+    Transfer,
+}
+
+impl EntryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Trade => "trade",
+            Self::Fee => "fee",
+            Self::Funding => "funding",
+            Self::Transfer => "transfer",
+        }
+    }
+}
+
+impl FromStr for EntryKind {
+    type Err = AttributionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "trade" => Ok(Self::Trade),
+            "fee" => Ok(Self::Fee),
+            "funding" => Ok(Self::Funding),
+            "transfer" => Ok(Self::Transfer),
+            other => Err(AttributionError::UnknownKind(other.to_string())),
+        }
+    }
+}
+
+/// Sub-account an entry is attributed to; transfers carry only the wallet
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct AttributionTags {
+    pub wallet: String,
+    pub strategy_id: Option<String>,
+    pub version: Option<u32>,
+    pub venue: Option<String>,
+    pub trading_pair: Option<String>,
+}
+
+impl AttributionTags {
+    pub fn wallet(wallet: &str) -> Self {
+        Self {
+            wallet: wallet.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn trade(wallet: &str, strategy_id: &str, venue: &str, trading_pair: &str) -> Self {
+        Self {
+            wallet: wallet.to_string(),
+            strategy_id: Some(strategy_id.to_string()),
+            version: None,
+            venue: Some(venue.to_string()),
+            trading_pair: Some(trading_pair.to_string()),
+        }
+    }
+}
+
+/// One balance-moving event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub id: Uuid,
+    pub kind: EntryKind,
+    pub tags: AttributionTags,
+    /// P&L contribution for trades, fees and funding; signed flow for transfers
+    pub amount: Decimal,
+    /// Cost basis moved into (positive) or out of open positions; the part of a fill's cash
+    /// movement that is not yet P&L
+    pub inventory: Decimal,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl LedgerEntry {
+    /// Fill of `size` at `price`; `realized_pnl` is set when the fill reduced a position
+    pub fn fill(
+        tags: AttributionTags,
+        side: TradeSide,
+        size: Decimal,
+        price: Decimal,
+        realized_pnl: Option<Decimal>,
+        occurred_at: DateTime<Utc>,
+    ) -> Self {
+        let notional = size * price;
+        let cash = match side {
+            TradeSide::Buy => -notional,
+            TradeSide::Sell => notional,
+        };
+        let realized = realized_pnl.unwrap_or_default();
+        Self::new(EntryKind::Trade, tags, realized, realized - cash, occurred_at)
+    }
+
+    /// Fee paid on a fill net of maker rebates
+    pub fn fee(tags: AttributionTags, fee: Decimal, occurred_at: DateTime<Utc>) -> Self {
+        Self::new(EntryKind::Fee, tags, -fee, Decimal::ZERO, occurred_at)
+    }
+
+    /// Perp funding payment, positive when received
+    pub fn funding(tags: AttributionTags, payment: Decimal, occurred_at: DateTime<Utc>) -> Self {
+        Self::new(EntryKind::Funding, tags, payment, Decimal::ZERO, occurred_at)
+    }
+
+    pub fn transfer(transfer: &Transfer) -> Self {
+        Self::new(
+            EntryKind::Transfer,
+            AttributionTags::wallet(&transfer.wallet_address),
+            transfer.signed_amount(),
+            Decimal::ZERO,
+            transfer.detected_at,
+        )
+    }
+
+    fn new(
+        kind: EntryKind,
+        tags: AttributionTags,
+        amount: Decimal,
+        inventory: Decimal,
+        occurred_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            kind,
+            tags,
+            amount,
+            inventory,
+            occurred_at,
+        }
+    }
+
+    /// Change in the wallet's quote balance the entry accounts for
+    pub fn cash_flow(&self) -> Decimal {
+        self.amount - self.inventory
+    }
+}
+
+/// One day of P&L for one sub-account, as stored in `attribution_periods`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributionPeriod {
+    pub period_start: DateTime<Utc>,
+    pub wallet: String,
+    pub strategy_id: String,
+    pub version: Option<u32>,
+    pub venue: String,
+    pub trading_pair: String,
+    pub realized_pnl: Decimal,
+    /// Fees paid net of rebates, as a negative amount
+    pub fees: Decimal,
+    pub funding: Decimal,
+    pub net_pnl: Decimal,
+    /// Fills, opening and closing
+    pub trade_count: u64,
+}
+
+/// Wallet, strategy, version, venue and pair of a rollup row
+type PeriodKey = (String, Option<String>, Option<u32>, Option<String>, Option<String>);
+
+impl AttributionPeriod {
+    fn empty(period_start: DateTime<Utc>, tags: &AttributionTags) -> Self {
+        let tag = |value: &Option<String>| value.clone().unwrap_or_else(|| UNATTRIBUTED.to_string());
+        Self {
+            period_start,
+            wallet: tags.wallet.clone(),
+            strategy_id: tag(&tags.strategy_id),
+            version: tags.version,
+            venue: tag(&tags.venue),
+            trading_pair: tag(&tags.trading_pair),
+            realized_pnl: Decimal::ZERO,
+            fees: Decimal::ZERO,
+            funding: Decimal::ZERO,
+            net_pnl: Decimal::ZERO,
+            trade_count: 0,
+        }
+    }
+
+    fn apply(&mut self, entry: &LedgerEntry) {
+        match entry.kind {
+            EntryKind::Trade => {
+                self.realized_pnl += entry.amount;
+                self.trade_count += 1;
+            }
+            EntryKind::Fee => self.fees += entry.amount,
+            EntryKind::Funding => self.funding += entry.amount,
+            EntryKind::Transfer => return,
+        }
+        self.net_pnl += entry.amount;
+    }
+}
+
+/// Tag a report groups rows by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributionDimension {
+    Strategy,
+    Version,
+    Venue,
+    Pair,
+    Wallet,
+    Day,
+}
+
+impl AttributionDimension {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Strategy => "strategy",
+            Self::Version => "version",
+            Self::Venue => "venue",
+            Self::Pair => "pair",
+            Self::Wallet => "wallet",
+            Self::Day => "day",
+        }
+    }
+
+    /// Parses a comma-separated `group_by` list, keeping the first occurrence of each tag
+    pub fn parse_list(raw: &str) -> Result<Vec<Self>, AttributionError> {
+        let mut dimensions = Vec::new();
+        for part in raw.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+            let dimension = part.parse()?;
+            if !dimensions.contains(&dimension) {
+                dimensions.push(dimension);
+            }
+        }
+        Ok(dimensions)
+    }
+}
+
+impl FromStr for AttributionDimension {
+    type Err = AttributionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "strategy" => Ok(Self::Strategy),
+            "version" => Ok(Self::Version),
+            "venue" => Ok(Self::Venue),
+            "pair" => Ok(Self::Pair),
+            "wallet" => Ok(Self::Wallet),
+            "day" => Ok(Self::Day),
+            _ => Err(AttributionError::UnknownDimension(s.to_string())),
+        }
+    }
+}
+
+/// P&L of one group of a report; tags the report is not grouped by are omitted
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, ToSchema)]
+pub struct AttributionRow {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub day: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy_id: Option<String>,
+    /// Omitted for trades predating versioning
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub venue: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trading_pair: Option<String>,
+    pub realized_pnl: Decimal,
+    /// Fees paid net of rebates, as a negative amount
+    pub fees: Decimal,
+    pub funding: Decimal,
+    pub net_pnl: Decimal,
+    pub trade_count: u64,
+}
+
+impl AttributionRow {
+    /// Empty row keyed by the grouped tags of a period
+    fn key(period: &AttributionPeriod, group_by: &[AttributionDimension]) -> Self {
+        let mut row = Self::default();
+        for dimension in group_by {
+            match dimension {
+                AttributionDimension::Strategy => row.strategy_id = Some(period.strategy_id.clone()),
+                AttributionDimension::Version => row.version = period.version,
+                AttributionDimension::Venue => row.venue = Some(period.venue.clone()),
+                AttributionDimension::Pair => row.trading_pair = Some(period.trading_pair.clone()),
+                AttributionDimension::Wallet => row.wallet = Some(period.wallet.clone()),
+                AttributionDimension::Day => row.day = Some(period.period_start.date_naive()),
+            }
+        }
+        row
+    }
+
+    fn add(&mut self, period: &AttributionPeriod) {
+        self.realized_pnl += period.realized_pnl;
+        self.fees += period.fees;
+        self.funding += period.funding;
+        self.net_pnl += period.net_pnl;
+        self.trade_count += period.trade_count;
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn sort_key(&self) -> (Option<NaiveDate>, Option<&str>, Option<&str>, Option<u32>, Option<&str>, Option<&str>) {
+        (
+            self.day,
+            self.wallet.as_deref(),
+            self.strategy_id.as_deref(),
+            self.version,
+            self.venue.as_deref(),
+            self.trading_pair.as_deref(),
+        )
+    }
+}
+
+/// Observed balance change of a wallet between two balance observations, split into the part
+/// explained by recorded P&L and flows and the unexplained residue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ReconciliationLine {
+    pub wallet: String,
+    /// Latest balance observed at or before the start of the range; null without one
+    pub opening_balance: Option<Decimal>,
+    pub opening_at: Option<DateTime<Utc>>,
+    /// Latest balance observed at or before the end of the range
+    pub closing_balance: Option<Decimal>,
+    pub closing_at: Option<DateTime<Utc>>,
+    pub balance_change: Option<Decimal>,
+    /// Realized P&L, fees and funding between the two observations
+    pub net_pnl: Decimal,
+    /// Deposits less withdrawals
+    pub transfers: Decimal,
+    /// Cost basis moved into open positions
+    pub inventory_change: Decimal,
+    /// `net_pnl + transfers - inventory_change`
+    pub explained: Decimal,
+    /// `balance_change - explained`; null without both observations
+    pub unexplained: Option<Decimal>,
+}
+
+impl ReconciliationLine {
+    fn new(
+        wallet: &str,
+        opening: Option<(Decimal, DateTime<Utc>)>,
+        closing: Option<(Decimal, DateTime<Utc>)>,
+        entries: &[LedgerEntry],
+    ) -> Self {
+        let mut line = Self {
+            wallet: wallet.to_string(),
+            opening_balance: opening.map(|(balance, _)| balance),
+            opening_at: opening.map(|(_, at)| at),
+            closing_balance: closing.map(|(balance, _)| balance),
+            closing_at: closing.map(|(_, at)| at),
+            balance_change: None,
+            net_pnl: Decimal::ZERO,
+            transfers: Decimal::ZERO,
+            inventory_change: Decimal::ZERO,
+            explained: Decimal::ZERO,
+            unexplained: None,
+        };
+        for entry in entries.iter().filter(|entry| entry.tags.wallet == wallet) {
+            match entry.kind {
+                EntryKind::Transfer => line.transfers += entry.amount,
+                _ => line.net_pnl += entry.amount,
+            }
+            line.inventory_change += entry.inventory;
+        }
+        line.explained = line.net_pnl + line.transfers - line.inventory_change;
+        if let (Some(opening), Some(closing)) = (line.opening_balance, line.closing_balance) {
+            let change = closing - opening;
+            line.balance_change = Some(change);
+            line.unexplained = Some(change - line.explained);
+        }
+        line
+    }
+}
+
+/// P&L attribution over a date range with each wallet's balance reconciliation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AttributionReport {
+    pub from: NaiveDate,
+    /// Inclusive
+    pub to: NaiveDate,
+    pub group_by: Vec<AttributionDimension>,
+    pub rows: Vec<AttributionRow>,
+    pub totals: AttributionRow,
+    pub reconciliation: Vec<ReconciliationLine>,
+}
+
+/// Raised when a wallet's unexplained balance change exceeds the residual threshold
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReconciliationAlert {
+    pub wallet: String,
+    pub period_start: DateTime<Utc>,
+    pub unexplained: Decimal,
+    pub threshold: Decimal,
+    pub line: ReconciliationLine,
+}
+
+/// Persistence for ledger entries, balance observations and daily rollups
+#[async_trait]
+pub trait AttributionStore: Send + Sync {
+    async fn record_entry(&self, entry: &LedgerEntry) -> Result<(), AttributionError>;
+    async fn record_balance(
+        &self,
+        wallet: &str,
+        balance: Decimal,
+        observed_at: DateTime<Utc>,
+    ) -> Result<(), AttributionError>;
+    /// Latest balance observed at or before `at`, with the time it was observed
+    async fn balance_at(
+        &self,
+        wallet: &str,
+        at: DateTime<Utc>,
+    ) -> Result<Option<(Decimal, DateTime<Utc>)>, AttributionError>;
+    /// Wallets with at least one balance observation
+    async fn wallets(&self) -> Result<Vec<String>, AttributionError>;
+    /// Entries with `from <= occurred_at < to`, oldest first
+    async fn entries(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<LedgerEntry>, AttributionError>;
+    /// Replaces every rollup row of the day starting at `period_start`
+    async fn replace_periods(
+        &self,
+        period_start: DateTime<Utc>,
+        periods: &[AttributionPeriod],
+    ) -> Result<(), AttributionError>;
+    /// Rollup rows of days starting in `[from, to)`
+    async fn periods(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<AttributionPeriod>, AttributionError>;
+}
+
+/// Rolls one day of ledger entries up into `attribution_periods` and reconciles it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributionRollup {
+    pub period_start: DateTime<Utc>,
+}
+
+impl Job for AttributionRollup {
+    const KIND: &'static str = "attribution.rollup";
+}
+
+/// Attribution configuration
+#[derive(Debug, Clone)]
+pub struct AttributionConfig {
+    /// Absolute unexplained balance change tolerated per wallet and day
+    pub residual_threshold: Decimal,
+    /// How often today's rollup is refreshed
+    pub rollup_interval: Duration,
+}
+
+impl Default for AttributionConfig {
+    fn default() -> Self {
+        Self {
+            residual_threshold: DEFAULT_RESIDUAL_THRESHOLD,
+            rollup_interval: DEFAULT_ROLLUP_INTERVAL,
+        }
+    }
+}
+
+/// Records tagged ledger entries, rolls them up daily and serves attribution reports
+pub struct AttributionEngine {
+    config: AttributionConfig,
+    store: SyncRwLock<Option<Arc<dyn AttributionStore>>>,
+    versions: SyncRwLock<Option<Arc<dyn VersionTagger>>>,
+    deletions: SyncRwLock<Option<Arc<dyn DeletionRegistry>>>,
+    webhooks: SyncRwLock<Option<Arc<WebhookDispatcher>>>,
+    persistence: SyncRwLock<Option<Arc<PersistenceQueue>>>,
+    alerts: broadcast::Sender<ReconciliationAlert>,
+    /// Day of the last enqueued rollup, so the previous day is closed out once it ends
+    last_rollup_day: Mutex<Option<DateTime<Utc>>>,
+}
+
+impl std::fmt::Debug for AttributionEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttributionEngine")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl AttributionEngine {
+    pub fn new(config: AttributionConfig) -> Self {
+        let (alerts, _) = broadcast::channel(ALERT_CHANNEL_CAPACITY);
+        Self {
+            config,
+            store: SyncRwLock::new(None),
+            versions: SyncRwLock::new(None),
+            deletions: SyncRwLock::new(None),
+            webhooks: SyncRwLock::new(None),
+            persistence: SyncRwLock::new(None),
+            alerts,
+            last_rollup_day: Mutex::new(None),
+        }
+    }
+
+    pub fn set_store(&self, store: Arc<dyn AttributionStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    /// Tags P&L with the strategy and parameter version that traded it
+    pub fn set_versions(&self, versions: Arc<dyn VersionTagger>) {
+        *self.versions.write() = Some(versions);
+    }
+
+    /// Stops attributing new P&L to deleted strategies; their historical rows are kept
+    pub fn set_deletions(&self, deletions: Arc<dyn DeletionRegistry>) {
+        *self.deletions.write() = Some(deletions);
+    }
+
+    /// Sends reconciliation alerts through the webhook dispatcher
+    pub fn set_webhooks(&self, webhooks: Arc<WebhookDispatcher>) {
+        *self.webhooks.write() = Some(webhooks);
+    }
+
+    /// Defers ledger entries to the write-behind queue instead of awaiting the store
+    pub fn set_persistence(&self, persistence: Arc<PersistenceQueue>) {
+        *self.persistence.write() = Some(persistence);
+    }
+
+    /// Receives reconciliation alerts as they are raised
+    pub fn subscribe(&self) -> broadcast::Receiver<ReconciliationAlert> {
+        self.alerts.subscribe()
+    }
+
+    fn store(&self) -> Option<Arc<dyn AttributionStore>> {
+        self.store.read().clone()
+    }
+
+    /// Tags for a fill placed by `strategy_id`, resolving A/B challengers to their strategy
+    /// and version
+    pub fn tags(&self, wallet: &str, strategy_id: &str, venue: &str, trading_pair: &str) -> AttributionTags {
+        let mut tags = AttributionTags::trade(wallet, strategy_id, venue, trading_pair);
+        if let Some(versions) = self.versions.read().clone() {
+            let (strategy_id, version) = versions.tag(strategy_id);
+            tags.strategy_id = Some(strategy_id);
+            tags.version = version;
+        }
+        let deleted = match (self.deletions.read().as_ref(), tags.strategy_id.as_deref()) {
+            (Some(deletions), Some(strategy_id)) => deletions.is_deleted(strategy_id),
+            _ => false,
+        };
+        if deleted {
+            counter!(format!("{}.deleted_strategy_entries", METRICS_PREFIX), 1);
+            tags.strategy_id = None;
+            tags.version = None;
+        }
+        tags
+    }
+
+    /// Persists an entry; failures are logged so attribution never blocks trading
+    pub async fn record(&self, entry: LedgerEntry) {
+        let Some(store) = self.store() else {
+            return;
+        };
+        counter!(format!("{}.entries", METRICS_PREFIX), 1, "kind" => entry.kind.as_str());
+        let persistence = self.persistence.read().clone();
+        if let Some(persistence) = persistence {
+            persistence.enqueue("ledger_entry", move || {
+                let (store, entry) = (store.clone(), entry.clone());
+                async move { store.record_entry(&entry).await.map_err(|e| e.to_string()) }
+            });
+            return;
+        }
+        if let Err(e) = store.record_entry(&entry).await {
+            counter!(format!("{}.store_failures", METRICS_PREFIX), 1);
+            warn!(kind = entry.kind.as_str(), "Failed to record ledger entry: {}", e);
+        }
+    }
+
+    /// Records a fill and the fee paid on it
+    pub async fn record_fill(
+        &self,
+        tags: AttributionTags,
+        side: TradeSide,
+        fill: &OrderFill,
+        realized_pnl: Option<Decimal>,
+        fee: Decimal,
+    ) {
+        if !fee.is_zero() {
+            self.record(LedgerEntry::fee(tags.clone(), fee, fill.timestamp)).await;
+        }
+        self.record(LedgerEntry::fill(tags, side, fill.size, fill.price, realized_pnl, fill.timestamp))
+            .await;
+    }
+
+    /// Records an observed wallet balance for reconciliation
+    pub async fn record_balance(&self, wallet: &str, balance: Decimal, observed_at: DateTime<Utc>) {
+        let Some(store) = self.store() else {
+            return;
+        };
+        if let Err(e) = store.record_balance(wallet, balance, observed_at).await {
+            counter!(format!("{}.store_failures", METRICS_PREFIX), 1);
+            warn!(wallet, "Failed to record wallet balance: {}", e);
+        }
+    }
+
+    /// Rebuilds the rollup of the day starting at `period_start` and reconciles every wallet
+    /// over it, alerting on residue above the threshold
+    pub async fn rollup(&self, period_start: DateTime<Utc>) -> Result<Vec<AttributionPeriod>, AttributionError> {
+        let store = self.store().ok_or_else(|| AttributionError::Store("no store configured".to_string()))?;
+        let period_start = day_start(period_start);
+        let period_end = period_start + chrono::Duration::days(1);
+
+        let entries = store.entries(period_start, period_end).await?;
+        let mut periods: BTreeMap<PeriodKey, AttributionPeriod> = BTreeMap::new();
+        for entry in entries.iter().filter(|entry| entry.kind != EntryKind::Transfer) {
+            let tags = &entry.tags;
+            let key = (
+                tags.wallet.clone(),
+                tags.strategy_id.clone(),
+                tags.version,
+                tags.venue.clone(),
+                tags.trading_pair.clone(),
+            );
+            periods
+                .entry(key)
+                .or_insert_with(|| AttributionPeriod::empty(period_start, tags))
+                .apply(entry);
+        }
+        let periods: Vec<AttributionPeriod> = periods.into_values().collect();
+        store.replace_periods(period_start, &periods).await?;
+        counter!(format!("{}.rollups", METRICS_PREFIX), 1);
+
+        for wallet in store.wallets().await? {
+            let line = self.reconcile(&wallet, period_start, period_end).await?;
+            self.check_residual(period_start, line);
+        }
+        Ok(periods)
+    }
+
+    /// Reconciles a wallet's balance change between the observations in effect at `from` and `to`
+    pub async fn reconcile(
+        &self,
+        wallet: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<ReconciliationLine, AttributionError> {
+        let store = self.store().ok_or_else(|| AttributionError::Store("no store configured".to_string()))?;
+        let opening = store.balance_at(wallet, from).await?;
+        let closing = store.balance_at(wallet, to).await?;
+        let entries = match (opening, closing) {
+            (Some((_, opening_at)), Some((_, closing_at))) => store.entries(opening_at, closing_at).await?,
+            _ => store.entries(from, to).await?,
+        };
+        Ok(ReconciliationLine::new(wallet, opening, closing, &entries))
+    }
+
+    fn check_residual(&self, period_start: DateTime<Utc>, line: ReconciliationLine) {
+        let Some(unexplained) = line.unexplained else {
+            return;
+        };
+        if unexplained.abs() <= self.config.residual_threshold {
+            return;
+        }
+        counter!(format!("{}.unexplained_residue", METRICS_PREFIX), 1, "wallet" => line.wallet.clone());
+        warn!(
+            wallet = %line.wallet,
+            day = %period_start.date_naive(),
+            "Unexplained balance change of {} exceeds threshold {}",
+            unexplained,
+            self.config.residual_threshold
+        );
+        let alert = ReconciliationAlert {
+            wallet: line.wallet.clone(),
+            period_start,
+            unexplained,
+            threshold: self.config.residual_threshold,
+            line,
+        };
+        if let Some(webhooks) = self.webhooks.read().clone() {
+            match WebhookEvent::new(WebhookEventType::ReconciliationUnexplained, &alert) {
+                Ok(event) => webhooks.dispatch(event),
+                Err(e) => warn!("Failed to build webhook event: {}", e),
+            }
+        }
+        let _ = self.alerts.send(alert);
+    }
+
+    /// Rollups for the inclusive day range `[from, to]`, grouped by `group_by`, with each
+    /// wallet's reconciliation over the whole range
+    pub async fn report(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        group_by: &[AttributionDimension],
+    ) -> Result<AttributionReport, AttributionError> {
+        if to < from {
+            return Err(AttributionError::InvalidRange(format!("{} is before {}", to, from)));
+        }
+        if (to - from).num_days() >= MAX_REPORT_DAYS {
+            return Err(AttributionError::InvalidRange(format!(
+                "at most {} days per report",
+                MAX_REPORT_DAYS
+            )));
+        }
+        let store = self.store().ok_or_else(|| AttributionError::Store("no store configured".to_string()))?;
+        let range_start = date_start(from);
+        let range_end = date_start(to) + chrono::Duration::days(1);
+
+        let periods = store.periods(range_start, range_end).await?;
+        let mut rows: Vec<AttributionRow> = Vec::new();
+        let mut totals = AttributionRow::default();
+        for period in &periods {
+            let key = AttributionRow::key(period, group_by);
+            match rows.iter_mut().find(|row| row.sort_key() == key.sort_key()) {
+                Some(row) => row.add(period),
+                None => {
+                    let mut row = key;
+                    row.add(period);
+                    rows.push(row);
+                }
+            }
+            totals.add(period);
+        }
+        rows.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+
+        let wallets: BTreeSet<String> = store
+            .wallets()
+            .await?
+            .into_iter()
+            .chain(periods.iter().map(|period| period.wallet.clone()))
+            .collect();
+        let mut reconciliation = Vec::with_capacity(wallets.len());
+        for wallet in &wallets {
+            reconciliation.push(self.reconcile(wallet, range_start, range_end).await?);
+        }
+
+        Ok(AttributionReport {
+            from,
+            to,
+            group_by: group_by.to_vec(),
+            rows,
+            totals,
+            reconciliation,
+        })
+    }
+
+    /// Enqueues today's rollup, and yesterday's once the day has rolled over
+    pub async fn enqueue_rollups(&self, jobs: &JobQueue, now: DateTime<Utc>) {
+        let today = day_start(now);
+        let previous = self.last_rollup_day.lock().replace(today);
+        let mut days = vec![today];
+        if let Some(previous) = previous.filter(|previous| *previous < today) {
+            days.insert(0, previous);
+        }
+        for period_start in days {
+            if let Err(e) = jobs.enqueue(AttributionRollup { period_start }).await {
+                warn!(day = %period_start.date_naive(), "Failed to enqueue attribution rollup: {}", e);
+            }
+        }
+    }
+
+    /// Registers the rollup job and enqueues it on the configured interval
+    pub fn spawn(self: Arc<Self>, jobs: Arc<JobQueue>) -> tokio::task::JoinHandle<()> {
+        jobs.register::<AttributionRollup, _>(self.clone());
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.rollup_interval);
+            loop {
+                interval.tick().await;
+                self.enqueue_rollups(&jobs, Utc::now()).await;
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl JobHandler<AttributionRollup> for AttributionEngine {
+    async fn handle(&self, job: AttributionRollup, _ctx: JobContext) -> Result<(), String> {
+        let periods = self.rollup(job.period_start).await.map_err(|e| e.to_string())?;
+        info!(
+            day = %job.period_start.date_naive(),
+            rows = periods.len(),
+            "Rolled up attribution periods"
+        );
+        Ok(())
+    }
+}
+
+/// Midnight UTC starting the day containing `at`
+pub fn day_start(at: DateTime<Utc>) -> DateTime<Utc> {
+    date_start(at.date_naive())
+}
+
+fn date_start(date: NaiveDate) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).expect("midnight is a valid time"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::transfer::TransferDirection;
+    use rust_decimal_macros::dec;
+
+    const WALLET: &str = "wallet_a";
+
+    #[derive(Default)]
+    struct MemoryStore {
+        entries: Mutex<Vec<LedgerEntry>>,
+        balances: Mutex<Vec<(String, Decimal, DateTime<Utc>)>>,
+        periods: Mutex<Vec<AttributionPeriod>>,
+    }
+
+    #[async_trait]
+    impl AttributionStore for MemoryStore {
+        async fn record_entry(&self, entry: &LedgerEntry) -> Result<(), AttributionError> {
+            self.entries.lock().push(entry.clone());
+            Ok(())
+        }
+
+        async fn record_balance(
+            &self,
+            wallet: &str,
+            balance: Decimal,
+            observed_at: DateTime<Utc>,
+        ) -> Result<(), AttributionError> {
+            self.balances.lock().push((wallet.to_string(), balance, observed_at));
+            Ok(())
+        }
+
+        async fn balance_at(
+            &self,
+            wallet: &str,
+            at: DateTime<Utc>,
+        ) -> Result<Option<(Decimal, DateTime<Utc>)>, AttributionError> {
+            Ok(self
+                .balances
+                .lock()
+                .iter()
+                .filter(|(w, _, observed_at)| w == wallet && *observed_at <= at)
+                .max_by_key(|(_, _, observed_at)| *observed_at)
+                .map(|(_, balance, observed_at)| (*balance, *observed_at)))
+        }
+
+        async fn wallets(&self) -> Result<Vec<String>, AttributionError> {
+            let wallets: BTreeSet<String> = self.balances.lock().iter().map(|(w, _, _)| w.clone()).collect();
+            Ok(wallets.into_iter().collect())
+        }
+
+        async fn entries(
+            &self,
+            from: DateTime<Utc>,
+            to: DateTime<Utc>,
+        ) -> Result<Vec<LedgerEntry>, AttributionError> {
+            let mut entries: Vec<LedgerEntry> = self
+                .entries
+                .lock()
+                .iter()
+                .filter(|entry| entry.occurred_at >= from && entry.occurred_at < to)
+                .cloned()
+                .collect();
+            entries.sort_by_key(|entry| entry.occurred_at);
+            Ok(entries)
+        }
+
+        async fn replace_periods(
+            &self,
+            period_start: DateTime<Utc>,
+            periods: &[AttributionPeriod],
+        ) -> Result<(), AttributionError> {
+            let mut stored = self.periods.lock();
+            stored.retain(|period| period.period_start != period_start);
+            stored.extend_from_slice(periods);
+            Ok(())
+        }
+
+        async fn periods(
+            &self,
+            from: DateTime<Utc>,
+            to: DateTime<Utc>,
+        ) -> Result<Vec<AttributionPeriod>, AttributionError> {
+            Ok(self
+                .periods
+                .lock()
+                .iter()
+                .filter(|period| period.period_start >= from && period.period_start < to)
+                .cloned()
+                .collect())
+        }
+    }
+
+    /// Reports challenger "momentum-b" as version 2 of "momentum"
+    struct StaticTagger;
+
+    impl VersionTagger for StaticTagger {
+        fn tag(&self, strategy_id: &str) -> (String, Option<u32>) {
+            match strategy_id {
+                "momentum-b" => ("momentum".to_string(), Some(2)),
+                "momentum" => ("momentum".to_string(), Some(1)),
+                other => (other.to_string(), None),
+            }
+        }
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, day, hour, 0, 0).unwrap()
+    }
+
+    fn fill(size: Decimal, price: Decimal, timestamp: DateTime<Utc>) -> OrderFill {
+        OrderFill {
+            fill_id: Uuid::new_v4().to_string(),
+            size,
+            price,
+            timestamp,
+        }
+    }
+
+    fn engine(store: Arc<MemoryStore>) -> AttributionEngine {
+        let engine = AttributionEngine::new(AttributionConfig::default());
+        engine.set_store(store);
+        engine.set_versions(Arc::new(StaticTagger));
+        engine
+    }
+
+    /// Opening balance 1000, a 500 deposit, a momentum round trip on Jupiter, an opening
+    /// grid buy on Raydium left open, a Drift funding payment and a 100 withdrawal
+    async fn record_fixtures(engine: &AttributionEngine) {
+        engine.record_balance(WALLET, dec!(1000), at(1, 0)).await;
+        engine
+            .record(LedgerEntry::transfer(&Transfer {
+                id: Uuid::new_v4(),
+                wallet_address: WALLET.to_string(),
+                direction: TransferDirection::Deposit,
+                amount: dec!(500),
+                signature: None,
+                detected_at: at(1, 1),
+            }))
+            .await;
+
+        let momentum = engine.tags(WALLET, "momentum", "jupiter", "SOL/USDC");
+        engine
+            .record_fill(momentum.clone(), TradeSide::Buy, &fill(dec!(10), dec!(20), at(1, 2)), None, dec!(0.2))
+            .await;
+        engine
+            .record_fill(momentum, TradeSide::Sell, &fill(dec!(10), dec!(25), at(1, 3)), Some(dec!(50)), dec!(0.25))
+            .await;
+
+        let challenger = engine.tags(WALLET, "momentum-b", "jupiter", "SOL/USDC");
+        engine
+            .record_fill(challenger.clone(), TradeSide::Buy, &fill(dec!(2), dec!(21), at(1, 4)), None, dec!(0.05))
+            .await;
+        engine
+            .record_fill(challenger, TradeSide::Sell, &fill(dec!(2), dec!(20), at(1, 5)), Some(dec!(-2)), dec!(0.05))
+            .await;
+
+        let grid = engine.tags(WALLET, "grid", "raydium", "ORCA/USDC");
+        engine
+            .record_fill(grid, TradeSide::Buy, &fill(dec!(100), dec!(1), at(1, 6)), None, dec!(0.1))
+            .await;
+
+        let basis = engine.tags(WALLET, "basis", "drift", "SOL-PERP");
+        engine.record(LedgerEntry::funding(basis, dec!(-1.5), at(1, 7))).await;
+
+        engine
+            .record(LedgerEntry::transfer(&Transfer {
+                id: Uuid::new_v4(),
+                wallet_address: WALLET.to_string(),
+                direction: TransferDirection::Withdrawal,
+                amount: dec!(100),
+                signature: None,
+                detected_at: at(1, 8),
+            }))
+            .await;
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn period(
+        strategy_id: &str,
+        version: Option<u32>,
+        venue: &str,
+        trading_pair: &str,
+        realized_pnl: Decimal,
+        fees: Decimal,
+        funding: Decimal,
+        trade_count: u64,
+    ) -> AttributionPeriod {
+        AttributionPeriod {
+            period_start: at(1, 0),
+            wallet: WALLET.to_string(),
+            strategy_id: strategy_id.to_string(),
+            version,
+            venue: venue.to_string(),
+            trading_pair: trading_pair.to_string(),
+            realized_pnl,
+            fees,
+            funding,
+            net_pnl: realized_pnl + fees + funding,
+            trade_count,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fixture_attribution_and_zero_residual() {
+        let store = Arc::new(MemoryStore::default());
+        let engine = engine(store.clone());
+        let mut alerts = engine.subscribe();
+        record_fixtures(&engine).await;
+        // 1000 + 500 - 100 transferred, -200 + 250 momentum, -42 + 40 challenger, -100 grid,
+        // -0.65 fees, -1.5 funding
+        engine.record_balance(WALLET, dec!(1345.85), at(2, 0)).await;
+
+        let periods = engine.rollup(at(1, 12)).await.unwrap();
+        assert_eq!(
+            periods,
+            vec![
+                period("basis", None, "drift", "SOL-PERP", dec!(0), dec!(0), dec!(-1.5), 0),
+                period("grid", None, "raydium", "ORCA/USDC", dec!(0), dec!(-0.1), dec!(0), 1),
+                period("momentum", Some(1), "jupiter", "SOL/USDC", dec!(50), dec!(-0.45), dec!(0), 2),
+                period("momentum", Some(2), "jupiter", "SOL/USDC", dec!(-2), dec!(-0.1), dec!(0), 2),
+            ]
+        );
+
+        let date = at(1, 0).date_naive();
+        let group_by = AttributionDimension::parse_list("strategy,venue").unwrap();
+        let report = engine.report(date, date, &group_by).await.unwrap();
+        let row = |strategy_id: &str, venue: &str, realized_pnl, fees, funding, trade_count| AttributionRow {
+            strategy_id: Some(strategy_id.to_string()),
+            venue: Some(venue.to_string()),
+            realized_pnl,
+            fees,
+            funding,
+            net_pnl: realized_pnl + fees + funding,
+            trade_count,
+            ..AttributionRow::default()
+        };
+        assert_eq!(
+            report.rows,
+            vec![
+                row("basis", "drift", dec!(0), dec!(0), dec!(-1.5), 0),
+                row("grid", "raydium", dec!(0), dec!(-0.1), dec!(0), 1),
+                row("momentum", "jupiter", dec!(48), dec!(-0.55), dec!(0), 4),
+            ]
+        );
+        assert_eq!(report.totals.net_pnl, dec!(45.85));
+
+        let line = &report.reconciliation[0];
+        assert_eq!(line.balance_change, Some(dec!(345.85)));
+        assert_eq!(line.net_pnl, dec!(45.85));
+        assert_eq!(line.transfers, dec!(400));
+        assert_eq!(line.inventory_change, dec!(100));
+        assert_eq!(line.explained, dec!(345.85));
+        assert_eq!(line.unexplained, Some(Decimal::ZERO));
+        assert!(alerts.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unexplained_residue_alerts() {
+        let store = Arc::new(MemoryStore::default());
+        let engine = engine(store);
+        let mut alerts = engine.subscribe();
+        record_fixtures(&engine).await;
+        // 5 more than the recorded activity accounts for
+        engine.record_balance(WALLET, dec!(1350.85), at(2, 0)).await;
+
+        engine.rollup(at(1, 0)).await.unwrap();
+        let alert = alerts.try_recv().unwrap();
+        assert_eq!(alert.wallet, WALLET);
+        assert_eq!(alert.period_start, at(1, 0));
+        assert_eq!(alert.unexplained, dec!(5));
+
+        // Within the threshold stays quiet
+        engine.record_balance(WALLET, dec!(1346.35), at(2, 0) + chrono::Duration::seconds(1)).await;
+        let line = engine.reconcile(WALLET, at(1, 0), at(2, 1)).await.unwrap();
+        assert_eq!(line.unexplained, Some(dec!(0.5)));
+    }
+
+    #[test]
+    fn test_deleted_strategy_left_unattributed() {
+        struct DeletedGrid;
+        impl DeletionRegistry for DeletedGrid {
+            fn is_deleted(&self, strategy_id: &str) -> bool {
+                strategy_id == "grid"
+            }
+        }
+
+        let engine = engine(Arc::new(MemoryStore::default()));
+        engine.set_deletions(Arc::new(DeletedGrid));
+        let grid = engine.tags(WALLET, "grid", "raydium", "ORCA/USDC");
+        assert_eq!(grid.strategy_id, None);
+        assert_eq!(grid.venue.as_deref(), Some("raydium"));
+        let momentum = engine.tags(WALLET, "momentum", "jupiter", "SOL/USDC");
+        assert_eq!(momentum.strategy_id.as_deref(), Some("momentum"));
+    }
+
+    #[test]
+    fn test_group_by_parsing() {
+        assert_eq!(
+            AttributionDimension::parse_list("strategy, Venue,strategy,pair").unwrap(),
+            vec![
+                AttributionDimension::Strategy,
+                AttributionDimension::Venue,
+                AttributionDimension::Pair
+            ]
+        );
+        assert!(AttributionDimension::parse_list("strategy,desk").is_err());
+    }
+}
@@ -0,0 +1,26 @@
+//! Command line administration for a running trading bot; see `admin_cli` for the commands.
+//! Version: 1.0.0
+
+use solana_trading_bot::admin_cli::{parse_args, run, EXIT_OK, USAGE};
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let invocation = match parse_args(std::env::args().skip(1)) {
+        Ok(invocation) => invocation,
+        Err(e) => {
+            eprintln!("firebot-admin: {}\n\n{}", e, USAGE);
+            std::process::exit(e.exit_code());
+        }
+    };
+
+    match run(&invocation).await {
+        Ok(output) => {
+            println!("{}", output);
+            std::process::exit(EXIT_OK);
+        }
+        Err(e) => {
+            eprintln!("firebot-admin: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
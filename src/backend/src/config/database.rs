@@ -2,14 +2,13 @@
 //! with support for read replicas, connection pooling, and secure credential management.
 //! Version: 1.0.0
 
-use deadpool_postgres::{Config as PoolConfig, Pool, PoolError, Runtime}; // v0.10.5
 use opentelemetry::trace::{Span, Tracer}; // v0.19.0
 use serde::Deserialize; // v1.0.164
-use sqlx::{postgres::{PgConnectOptions, PgPoolOptions}, PgPool}; // v0.7.1
-use tokio_postgres::{NoTls, tls::{MakeTlsConnect, TlsConnect}}; // v0.7.8
+use sqlx::{postgres::{PgConnectOptions, PgPoolOptions, PgSslMode}, PgPool}; // v0.7.1
 use tracing::{error, info, instrument, warn};
 
-use crate::utils::crypto::{encrypt_sensitive_data, decrypt_sensitive_data};
+use crate::config::{ensure_valid, ConfigIssue};
+use crate::utils::crypto::{encrypt_sensitive_data, decrypt_sensitive_data, resolve_secret, Secret};
 use crate::utils::metrics::MetricsCollector;
 
 // Global constants for database configuration
@@ -21,6 +20,7 @@ const MIN_POOL_SIZE: u32 = 5;
 const HEALTH_CHECK_INTERVAL_SECONDS: u64 = 60;
 const MAX_CONNECTION_RETRIES: u32 = 3;
 const REPLICA_LAG_THRESHOLD_SECONDS: i64 = 30;
+const DEFAULT_STATEMENT_CACHE_SIZE: usize = 100;
 
 /// SSL mode configuration for database connections
 #[derive(Debug, Clone, Deserialize)]
@@ -31,6 +31,23 @@ pub enum SSLMode {
     VerifyFull,
 }
 
+impl From<&SSLMode> for PgSslMode {
+    fn from(mode: &SSLMode) -> Self {
+        match mode {
+            SSLMode::Disable => PgSslMode::Disable,
+            SSLMode::Require => PgSslMode::Require,
+            SSLMode::VerifyCA => PgSslMode::VerifyCa,
+            SSLMode::VerifyFull => PgSslMode::VerifyFull,
+        }
+    }
+}
+
+/// Replica lag row returned by the health check
+#[derive(Debug)]
+struct ReplicaLagRow {
+    lag: Option<i32>,
+}
+
 /// Database backup configuration
 #[derive(Debug, Clone, Deserialize)]
 pub struct BackupConfig {
@@ -64,7 +81,7 @@ pub struct DatabaseConfig {
     pub host: String,
     pub port: u16,
     pub username: String,
-    pub password: String,
+    pub password: Secret<String>,
     pub database: String,
     pub pool_size: u32,
     pub timeout_seconds: u32,
@@ -74,7 +91,7 @@ pub struct DatabaseConfig {
     pub replica_host: Option<String>,
     pub replica_port: Option<u16>,
     pub replica_username: Option<String>,
-    pub replica_password: Option<String>,
+    pub replica_password: Option<Secret<String>>,
 
     // Advanced configuration
     pub retention_policy: RetentionPolicy,
@@ -89,7 +106,7 @@ impl DatabaseConfig {
             host: String::new(),
             port: 5432,
             username: String::new(),
-            password: String::new(),
+            password: Secret::default(),
             database: String::new(),
             pool_size: DEFAULT_POOL_SIZE,
             timeout_seconds: DEFAULT_TIMEOUT_SECONDS,
@@ -118,56 +135,129 @@ impl DatabaseConfig {
         }
     }
 
-    /// Validates the database configuration
+    /// Loads connection settings from `DB_*` variables over the defaults, decrypting
+    /// `kms:`-prefixed passwords with the given key
+    pub async fn from_env(kms_key_id: &str) -> Result<Self, String> {
+        let mut config = Self::new();
+
+        config.host = std::env::var("DB_HOST").unwrap_or_default();
+        if let Ok(port) = std::env::var("DB_PORT") {
+            config.port = port.parse().map_err(|_| "Invalid DB_PORT")?;
+        }
+        config.username = std::env::var("DB_USERNAME").unwrap_or_default();
+        config.password = resolve_secret(std::env::var("DB_PASSWORD").unwrap_or_default(), kms_key_id)
+            .await
+            .map_err(|e| format!("Failed to load DB_PASSWORD: {}", e))?;
+        config.database = std::env::var("DB_DATABASE").unwrap_or_default();
+        if let Ok(pool_size) = std::env::var("DB_POOL_SIZE") {
+            config.pool_size = pool_size.parse().map_err(|_| "Invalid DB_POOL_SIZE")?;
+        }
+
+        // Optional read replica
+        config.replica_host = std::env::var("DB_REPLICA_HOST").ok();
+        config.replica_port = match std::env::var("DB_REPLICA_PORT") {
+            Ok(port) => Some(port.parse().map_err(|_| "Invalid DB_REPLICA_PORT")?),
+            Err(_) => None,
+        };
+        config.replica_username = std::env::var("DB_REPLICA_USERNAME").ok();
+        config.replica_password = match std::env::var("DB_REPLICA_PASSWORD") {
+            Ok(raw) => Some(
+                resolve_secret(raw, kms_key_id)
+                    .await
+                    .map_err(|e| format!("Failed to load DB_REPLICA_PASSWORD: {}", e))?,
+            ),
+            Err(_) => None,
+        };
+
+        Ok(config)
+    }
+
+    /// Validates the database configuration, reporting every problem found
     #[instrument(skip(self))]
-    pub fn validate_config(&self) -> Result<(), String> {
+    pub fn validate_config(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+
         // Validate primary connection parameters
-        if self.host.is_empty() || self.username.is_empty() || self.password.is_empty() || self.database.is_empty() {
-            return Err("Missing required primary database configuration".to_string());
+        let required = [
+            (&self.host, "host"),
+            (&self.username, "username"),
+            (self.password.expose(), "password"),
+            (&self.database, "database"),
+        ];
+        for (value, field) in required.iter() {
+            if value.is_empty() {
+                issues.push(ConfigIssue::error(
+                    "database",
+                    field,
+                    "missing required primary database setting",
+                    format!("set DB_{}", field.to_uppercase()),
+                ));
+            }
         }
 
         // Validate pool size limits
         if self.pool_size < MIN_POOL_SIZE || self.pool_size > MAX_POOL_SIZE {
-            return Err(format!("Pool size must be between {} and {}", MIN_POOL_SIZE, MAX_POOL_SIZE));
+            issues.push(ConfigIssue::error(
+                "database",
+                "pool_size",
+                format!("pool size {} is out of range", self.pool_size),
+                format!("use a value between {} and {}", MIN_POOL_SIZE, MAX_POOL_SIZE),
+            ));
         }
 
         // Validate replica configuration if enabled
         if let (Some(host), Some(port)) = (&self.replica_host, &self.replica_port) {
             if host.is_empty() {
-                return Err("Invalid replica host configuration".to_string());
+                issues.push(ConfigIssue::error(
+                    "database",
+                    "replica_host",
+                    "replica host is empty",
+                    "set the replica host or remove the replica configuration",
+                ));
             }
             if *port == 0 {
-                return Err("Invalid replica port configuration".to_string());
+                issues.push(ConfigIssue::error(
+                    "database",
+                    "replica_port",
+                    "replica port is 0",
+                    "set a valid replica port",
+                ));
             }
         }
 
-        Ok(())
+        if matches!(self.ssl_mode, SSLMode::Disable) {
+            issues.push(ConfigIssue::warning(
+                "database",
+                "ssl_mode",
+                "TLS is disabled for database connections",
+                "use Require or VerifyFull outside local development",
+            ));
+        }
+
+        issues
+    }
+
+    /// Builds the sqlx connect options for the primary instance
+    pub fn connect_options(&self) -> PgConnectOptions {
+        PgConnectOptions::new()
+            .host(&self.host)
+            .port(self.port)
+            .username(&self.username)
+            .password(self.password.expose())
+            .database(&self.database)
+            .ssl_mode((&self.ssl_mode).into())
+            .statement_cache_capacity(DEFAULT_STATEMENT_CACHE_SIZE)
     }
 
     /// Builds connection pool with failover support
     #[instrument(skip(self))]
-    pub async fn build_connection_pool(&self) -> Result<Pool, PoolError> {
-        let mut pool_config = PoolConfig::new();
-        
-        // Configure primary connection
-        pool_config.host = Some(self.host.clone());
-        pool_config.port = Some(self.port);
-        pool_config.user = Some(self.username.clone());
-        pool_config.password = Some(self.password.clone());
-        pool_config.dbname = Some(self.database.clone());
-        
-        // Configure pool settings
-        pool_config.pool = Some(deadpool_postgres::PoolConfig {
-            max_size: self.pool_size,
-            timeouts: deadpool_postgres::Timeouts {
-                wait: Some(std::time::Duration::from_secs(self.timeout_seconds as u64)),
-                create: Some(std::time::Duration::from_secs(self.timeout_seconds as u64)),
-                recycle: Some(std::time::Duration::from_secs(DEFAULT_IDLE_TIMEOUT_SECONDS as u64)),
-            },
-        });
-
-        // Create the connection pool
-        let pool = pool_config.create_pool(Some(Runtime::Tokio1), NoTls)?;
+    pub async fn build_connection_pool(&self) -> Result<PgPool, sqlx::Error> {
+        let pool = PgPoolOptions::new()
+            .max_connections(self.pool_size)
+            .acquire_timeout(std::time::Duration::from_secs(self.timeout_seconds as u64))
+            .idle_timeout(std::time::Duration::from_secs(DEFAULT_IDLE_TIMEOUT_SECONDS as u64))
+            .connect_with(self.connect_options())
+            .await?;
 
         // Initialize health check
         tokio::spawn(periodic_health_check(pool.clone()));
@@ -177,10 +267,10 @@ impl DatabaseConfig {
 }
 
 /// Creates and configures a high-availability database connection pool
-#[instrument]
-pub async fn create_pool(config: DatabaseConfig) -> Result<Pool, String> {
+#[instrument(skip(config))]
+pub async fn create_pool(config: DatabaseConfig) -> Result<PgPool, String> {
     // Validate configuration
-    config.validate_config()?;
+    ensure_valid(config.validate_config())?;
 
     // Build primary connection pool
     let pool = config.build_connection_pool()
@@ -201,19 +291,15 @@ pub async fn create_pool(config: DatabaseConfig) -> Result<Pool, String> {
 
 /// Performs comprehensive database health check
 #[instrument(skip(pool))]
-pub async fn health_check(pool: Pool) -> Result<bool, String> {
-    let client = pool.get()
-        .await
-        .map_err(|e| format!("Failed to get connection: {}", e))?;
-
+pub async fn health_check(pool: &PgPool) -> Result<bool, String> {
     // Check primary connectivity
-    let result = client
-        .query_one("SELECT 1", &[])
+    sqlx::query_scalar!(r#"SELECT 1 AS "alive!""#)
+        .fetch_one(pool)
         .await
         .map_err(|e| format!("Health check failed: {}", e))?;
 
     // Check replica lag if configured
-    if let Some(replica_lag) = check_replica_lag(&client).await? {
+    if let Some(replica_lag) = check_replica_lag(pool).await? {
         if replica_lag > REPLICA_LAG_THRESHOLD_SECONDS {
             warn!("Replica lag exceeds threshold: {} seconds", replica_lag);
             return Ok(false);
@@ -224,24 +310,24 @@ pub async fn health_check(pool: Pool) -> Result<bool, String> {
 }
 
 /// Internal function to check replica lag
-async fn check_replica_lag(client: &deadpool_postgres::Client) -> Result<Option<i64>, String> {
-    let lag = client
-        .query_opt(
-            "SELECT EXTRACT(EPOCH FROM (now() - pg_last_xact_replay_timestamp()))::INTEGER as lag",
-            &[],
-        )
-        .await
-        .map_err(|e| format!("Failed to check replica lag: {}", e))?;
-
-    Ok(lag.and_then(|row| row.get(0)))
+async fn check_replica_lag(pool: &PgPool) -> Result<Option<i64>, String> {
+    let row = sqlx::query_as!(
+        ReplicaLagRow,
+        "SELECT EXTRACT(EPOCH FROM (now() - pg_last_xact_replay_timestamp()))::INTEGER as lag",
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| format!("Failed to check replica lag: {}", e))?;
+
+    Ok(row.lag.map(i64::from))
 }
 
 /// Periodic health check task
-async fn periodic_health_check(pool: Pool) {
+async fn periodic_health_check(pool: PgPool) {
     loop {
         tokio::time::sleep(tokio::time::Duration::from_secs(HEALTH_CHECK_INTERVAL_SECONDS)).await;
         
-        match health_check(pool.clone()).await {
+        match health_check(&pool).await {
             Ok(true) => info!("Database health check passed"),
             Ok(false) => warn!("Database health check failed"),
             Err(e) => error!("Database health check error: {}", e),
@@ -256,30 +342,42 @@ mod tests {
     #[tokio::test]
     async fn test_database_config_validation() {
         let config = DatabaseConfig::new();
-        assert!(config.validate_config().is_err());
+        assert!(config.validate_config().iter().any(ConfigIssue::is_error));
 
         let mut valid_config = DatabaseConfig::new();
         valid_config.host = "localhost".to_string();
         valid_config.username = "user".to_string();
-        valid_config.password = "pass".to_string();
+        valid_config.password = "pass".into();
         valid_config.database = "testdb".to_string();
-        assert!(valid_config.validate_config().is_ok());
+        assert!(!valid_config.validate_config().iter().any(ConfigIssue::is_error));
+    }
+
+    #[test]
+    fn test_debug_output_redacts_passwords() {
+        let mut config = DatabaseConfig::new();
+        config.password = "primary-db-password".into();
+        config.replica_password = Some("replica-db-password".into());
+
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("primary-db-password"));
+        assert!(!debug.contains("replica-db-password"));
+        assert!(debug.contains("[REDACTED]"));
     }
 
     #[tokio::test]
     async fn test_pool_size_validation() {
         let mut config = DatabaseConfig::new();
         config.pool_size = MAX_POOL_SIZE + 1;
-        assert!(config.validate_config().is_err());
+        assert!(config.validate_config().iter().any(ConfigIssue::is_error));
 
         config.pool_size = MIN_POOL_SIZE - 1;
-        assert!(config.validate_config().is_err());
+        assert!(config.validate_config().iter().any(ConfigIssue::is_error));
 
         config.pool_size = DEFAULT_POOL_SIZE;
         config.host = "localhost".to_string();
         config.username = "user".to_string();
-        config.password = "pass".to_string();
+        config.password = "pass".into();
         config.database = "testdb".to_string();
-        assert!(config.validate_config().is_ok());
+        assert!(!config.validate_config().iter().any(ConfigIssue::is_error));
     }
 }
\ No newline at end of file
@@ -1,10 +1,18 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use chrono::NaiveTime;
 use dotenv::dotenv;
 use log::{error, info, warn};
+use rust_decimal::Decimal;
 use url::Url;
+use std::collections::HashMap;
 use std::env;
+use std::str::FromStr;
 use std::time::Duration;
 
+use crate::config::ConfigIssue;
+use crate::risk_manager::degradation::{DegradationCurve, SizingBreakpoint};
+use crate::utils::percent::Percent;
+
 // Package versions in use:
 // serde = "1.0.164"
 // dotenv = "0.15.0"
@@ -17,31 +25,240 @@ pub const STAGING_ENV: &str = "staging";
 pub const DEVELOPMENT_ENV: &str = "development";
 pub const DEFAULT_AWS_REGION: &str = "ap-southeast-1";
 pub const DEFAULT_API_PORT: u16 = 8080;
+pub const DEFAULT_GRPC_PORT: u16 = 50051;
+pub const DEFAULT_WS_PORT: u16 = 8081;
+pub const DEFAULT_MAX_CONCURRENT_TRADES: usize = 100;
+pub const DEFAULT_ADMISSION_TIMEOUT_MS: u64 = 5000;
+pub const MAX_VENUE_SUBMISSIONS_PER_SECOND: u32 = 1000;
+
+// Network endpoint defaults; Jupiter and Pump Fun price feeds are read-only and mainnet-only
+const MAINNET_SOLANA_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+const DEVNET_SOLANA_RPC_URL: &str = "https://api.devnet.solana.com";
+const JUPITER_REST_URL: &str = "https://price.jup.ag/v1";
+const JUPITER_WS_URL: &str = "wss://price.jup.ag/v1/stream";
+const PUMP_FUN_REST_URL: &str = "https://api.pump.fun/v1";
+const PUMP_FUN_PROGRAM_ID: &str = "pf1xyPydBXyPxGZwpvpuXNB1K3zMqCVbJKfihvQhKGE";
+const MAINNET_DRIFT_REST_URL: &str = "https://api.drift.trade";
+const MAINNET_DRIFT_WS_URL: &str = "wss://api.drift.trade/ws";
+const DEVNET_DRIFT_REST_URL: &str = "https://master.dlob.drift.trade";
+const DEVNET_DRIFT_WS_URL: &str = "wss://master.dlob.drift.trade/ws";
+const MAINNET_JITO_URL: &str = "https://mainnet.block-engine.jito.wtf";
+const TESTNET_JITO_URL: &str = "https://dallas.testnet.block-engine.jito.wtf";
+/// URL fragments marking an endpoint as something other than mainnet
+const NON_MAINNET_MARKERS: &[&str] = &["devnet", "testnet", "localhost", "127.0.0.1"];
 
 // Required environment variables
 const REQUIRED_ENV_VARS: &[&str] = &[
     "NODE_ENV",
     "AWS_REGION",
-    "JUPITER_API_ENDPOINT",
-    "PUMP_FUN_API_ENDPOINT",
-    "DRIFT_API_ENDPOINT",
-    "JITO_API_ENDPOINT"
 ];
 
+/// Deployment profile selected by NODE_ENV; anything unrecognised is treated as development
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvironmentProfile {
+    Development,
+    Staging,
+    Production,
+}
+
+impl EnvironmentProfile {
+    pub fn from_node_env(node_env: &str) -> Option<Self> {
+        match node_env {
+            DEVELOPMENT_ENV => Some(Self::Development),
+            STAGING_ENV => Some(Self::Staging),
+            PRODUCTION_ENV => Some(Self::Production),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Development => DEVELOPMENT_ENV,
+            Self::Staging => STAGING_ENV,
+            Self::Production => PRODUCTION_ENV,
+        }
+    }
+
+    /// Factor applied to collector polling intervals
+    pub fn interval_multiplier(&self) -> u32 {
+        match self {
+            Self::Development => 10,
+            Self::Staging => 2,
+            Self::Production => 1,
+        }
+    }
+
+    /// Divisor applied to connection pool sizes
+    pub fn pool_size_divisor(&self) -> usize {
+        match self {
+            Self::Development => 5,
+            Self::Staging => 2,
+            Self::Production => 1,
+        }
+    }
+}
+
+/// Venue and chain endpoints used by collectors and execution
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkEndpoints {
+    pub solana_rpc_url: String,
+    pub jupiter_rest_url: String,
+    pub jupiter_ws_url: String,
+    pub pump_fun_rest_url: String,
+    pub pump_fun_program_id: String,
+    pub drift_rest_url: String,
+    pub drift_ws_url: String,
+    pub jito_block_engine_url: String,
+}
+
+impl NetworkEndpoints {
+    /// Defaults for a profile: devnet and the Jito testnet in development, mainnet otherwise
+    pub fn for_profile(profile: EnvironmentProfile) -> Self {
+        let mainnet = profile != EnvironmentProfile::Development;
+        let pick = |mainnet_url: &str, devnet_url: &str| {
+            if mainnet { mainnet_url } else { devnet_url }.to_string()
+        };
+        Self {
+            solana_rpc_url: pick(MAINNET_SOLANA_RPC_URL, DEVNET_SOLANA_RPC_URL),
+            jupiter_rest_url: JUPITER_REST_URL.to_string(),
+            jupiter_ws_url: JUPITER_WS_URL.to_string(),
+            pump_fun_rest_url: PUMP_FUN_REST_URL.to_string(),
+            pump_fun_program_id: PUMP_FUN_PROGRAM_ID.to_string(),
+            drift_rest_url: pick(MAINNET_DRIFT_REST_URL, DEVNET_DRIFT_REST_URL),
+            drift_ws_url: pick(MAINNET_DRIFT_WS_URL, DEVNET_DRIFT_WS_URL),
+            jito_block_engine_url: pick(MAINNET_JITO_URL, TESTNET_JITO_URL),
+        }
+    }
+
+    /// Endpoint URLs keyed by the variable that overrides them
+    pub fn urls(&self) -> [(&'static str, &str); 7] {
+        [
+            ("SOLANA_RPC_URL", &self.solana_rpc_url),
+            ("JUPITER_API_ENDPOINT", &self.jupiter_rest_url),
+            ("JUPITER_WS_ENDPOINT", &self.jupiter_ws_url),
+            ("PUMP_FUN_API_ENDPOINT", &self.pump_fun_rest_url),
+            ("DRIFT_API_ENDPOINT", &self.drift_rest_url),
+            ("DRIFT_WS_ENDPOINT", &self.drift_ws_url),
+            ("JITO_API_ENDPOINT", &self.jito_block_engine_url),
+        ]
+    }
+
+    /// Endpoints pointing at devnet, a testnet or a local validator
+    pub fn non_mainnet(&self) -> Vec<(&'static str, &str)> {
+        self.urls()
+            .into_iter()
+            .filter(|(_, url)| is_non_mainnet(url))
+            .collect()
+    }
+}
+
+fn is_non_mainnet(url: &str) -> bool {
+    NON_MAINNET_MARKERS.iter().any(|marker| url.contains(marker))
+}
+
+/// Order count and notional caps for one strategy or wallet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VelocityCaps {
+    pub max_orders_per_minute: u32,
+    pub max_notional_per_hour: Decimal,
+}
+
+impl FromStr for VelocityCaps {
+    type Err = String;
+
+    /// Parses `orders/notional` such as `60/250000`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (orders, notional) = s
+            .split_once('/')
+            .ok_or_else(|| format!("'{}' is missing the hourly notional", s))?;
+        Ok(Self {
+            max_orders_per_minute: orders
+                .trim()
+                .parse()
+                .map_err(|_| format!("'{}' is not a valid order count", orders))?,
+            max_notional_per_hour: notional
+                .trim()
+                .parse()
+                .map_err(|_| format!("'{}' is not a valid notional", notional))?,
+        })
+    }
+}
+
+/// Outbound order limit on one venue for each signing wallet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VenueThrottle {
+    pub max_per_second: u32,
+    /// Submissions allowed back to back before pacing starts
+    pub burst: u32,
+}
+
+impl FromStr for VenueThrottle {
+    type Err = String;
+
+    /// Parses `rate` or `rate/burst`; the burst defaults to the rate
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rate, burst) = match s.split_once('/') {
+            Some((rate, burst)) => (rate, Some(burst)),
+            None => (s, None),
+        };
+        let max_per_second: u32 = rate.trim().parse().map_err(|_| format!("'{}' is not a valid rate", rate))?;
+        let burst = match burst {
+            Some(burst) => burst.trim().parse().map_err(|_| format!("'{}' is not a valid burst", burst))?,
+            None => max_per_second,
+        };
+        Ok(Self { max_per_second, burst })
+    }
+}
+
+impl Default for NetworkEndpoints {
+    fn default() -> Self {
+        Self::for_profile(EnvironmentProfile::Development)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct EnvironmentConfig {
     pub node_env: String,
     pub aws_region: String,
-    pub jupiter_api_endpoint: String,
-    pub pump_fun_api_endpoint: String,
-    pub drift_api_endpoint: String,
-    pub jito_api_endpoint: String,
+    pub endpoints: NetworkEndpoints,
+    /// Permits real transactions outside the production profile
+    pub allow_live_trading: bool,
     pub api_port: u16,
+    /// Port for the gRPC server; disabled when unset
+    pub grpc_port: Option<u16>,
+    /// Port for the WebSocket streaming server; disabled when unset
+    pub ws_port: Option<u16>,
     pub debug_mode: bool,
     pub log_level: Option<String>,
+    /// Per-module filter directives such as `firebot::data_collector=debug`
+    pub log_directives: Vec<String>,
     pub allowed_origins: Vec<String>,
     pub request_timeout_ms: u32,
     pub max_connections: u32,
+    pub max_concurrent_trades: usize,
+    pub admission_timeout_ms: u64,
+    /// Order submission limits by exchange; unlisted venues are not throttled
+    pub venue_throttles: HashMap<String, VenueThrottle>,
+    /// Velocity caps for every strategy and wallet; the risk defaults apply when unset
+    pub velocity_limits: Option<VelocityCaps>,
+    /// Velocity caps replacing the defaults for individual strategies
+    pub strategy_velocity_overrides: HashMap<String, VelocityCaps>,
+    /// Daily loss thresholds in USDC and as a share of the day's starting equity; unset
+    /// thresholds keep the risk defaults
+    pub daily_max_loss: Option<Decimal>,
+    pub daily_max_loss_pct: Option<Percent>,
+    pub daily_hard_max_loss: Option<Decimal>,
+    pub daily_hard_max_loss_pct: Option<Percent>,
+    /// Flattens open positions once the hard daily loss threshold is breached
+    pub flatten_on_daily_hard_breach: bool,
+    /// UTC time at which the daily loss budget and the report day reset; midnight when unset
+    pub daily_reset_time: Option<NaiveTime>,
+    /// Degraded-mode size multiplier by system quality score; the risk default curve
+    /// applies when empty
+    pub degradation_breakpoints: Vec<(Decimal, Decimal)>,
+    /// Strategies kept at full size while the system is degraded
+    pub degradation_opt_outs: Vec<String>,
 }
 
 impl EnvironmentConfig {
@@ -49,16 +266,30 @@ impl EnvironmentConfig {
         EnvironmentConfig {
             node_env: DEVELOPMENT_ENV.to_string(),
             aws_region: DEFAULT_AWS_REGION.to_string(),
-            jupiter_api_endpoint: String::new(),
-            pump_fun_api_endpoint: String::new(),
-            drift_api_endpoint: String::new(),
-            jito_api_endpoint: String::new(),
+            endpoints: NetworkEndpoints::for_profile(EnvironmentProfile::Development),
+            allow_live_trading: false,
             api_port: DEFAULT_API_PORT,
+            grpc_port: None,
+            ws_port: None,
             debug_mode: true,
             log_level: Some("debug".to_string()),
+            log_directives: vec![],
             allowed_origins: vec![],
             request_timeout_ms: 30000,
             max_connections: 1000,
+            max_concurrent_trades: DEFAULT_MAX_CONCURRENT_TRADES,
+            admission_timeout_ms: DEFAULT_ADMISSION_TIMEOUT_MS,
+            venue_throttles: HashMap::new(),
+            velocity_limits: None,
+            strategy_velocity_overrides: HashMap::new(),
+            daily_max_loss: None,
+            daily_max_loss_pct: None,
+            daily_hard_max_loss: None,
+            daily_hard_max_loss_pct: None,
+            flatten_on_daily_hard_breach: false,
+            daily_reset_time: None,
+            degradation_breakpoints: vec![],
+            degradation_opt_outs: vec![],
         }
     }
 
@@ -86,107 +317,647 @@ impl EnvironmentConfig {
         self.node_env == DEVELOPMENT_ENV
     }
 
-    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+    /// Active profile, falling back to development for an unrecognised NODE_ENV
+    pub fn profile(&self) -> EnvironmentProfile {
+        EnvironmentProfile::from_node_env(&self.node_env).unwrap_or(EnvironmentProfile::Development)
+    }
+
+    /// Whether real transactions may be submitted
+    pub fn live_trading_enabled(&self) -> bool {
+        self.is_production() || self.allow_live_trading
+    }
+
+    /// Settings that put funds or mainnet state at risk, for the startup banner
+    pub fn risky_settings(&self) -> Vec<String> {
+        let mut risky = Vec::new();
+        if self.live_trading_enabled() {
+            risky.push("live trading enabled: real transactions will be submitted".to_string());
+        }
+        if self.allow_live_trading && self.profile() != EnvironmentProfile::Production {
+            risky.push(format!("ALLOW_LIVE_TRADING overrides the {} profile", self.profile().as_str()));
+        }
+        if !is_non_mainnet(&self.endpoints.solana_rpc_url) {
+            risky.push(format!("mainnet RPC: {}", self.endpoints.solana_rpc_url));
+        }
+        if self.debug_mode && self.profile() != EnvironmentProfile::Development {
+            risky.push(format!("debug mode enabled in {}", self.profile().as_str()));
+        }
+        risky
+    }
+
+    /// Logs the active profile, highlighting the riskiest settings
+    pub fn log_startup_banner(&self) {
+        info!(
+            "Environment profile: {} (region {}, RPC {}, Jito {})",
+            self.profile().as_str(),
+            self.aws_region,
+            self.endpoints.solana_rpc_url,
+            self.endpoints.jito_block_engine_url,
+        );
+        for setting in self.risky_settings() {
+            warn!("!! {}", setting);
+        }
+    }
+
+    pub fn from_env() -> Result<Self, Vec<ConfigIssue>> {
         // Load .env file if present
         dotenv().ok();
 
+        let mut issues = Vec::new();
+
         // Validate required environment variables
         for var in REQUIRED_ENV_VARS {
             if env::var(var).is_err() {
-                return Err(format!("Missing required environment variable: {}", var).into());
+                issues.push(ConfigIssue::error(
+                    "environment",
+                    var,
+                    "missing required environment variable",
+                    format!("set {} in the environment or .env file", var),
+                ));
             }
         }
 
-        let node_env = env::var("NODE_ENV")?;
-        if ![PRODUCTION_ENV, STAGING_ENV, DEVELOPMENT_ENV].contains(&node_env.as_str()) {
-            return Err("Invalid NODE_ENV value".into());
+        let node_env = env::var("NODE_ENV").unwrap_or_default();
+        if !node_env.is_empty() && ![PRODUCTION_ENV, STAGING_ENV, DEVELOPMENT_ENV].contains(&node_env.as_str()) {
+            issues.push(ConfigIssue::error(
+                "environment",
+                "NODE_ENV",
+                format!("invalid value '{}'", node_env),
+                "use one of production, staging, development",
+            ));
         }
 
-        let aws_region = env::var("AWS_REGION").unwrap_or_else(|_| DEFAULT_AWS_REGION.to_string());
-        if node_env == PRODUCTION_ENV && aws_region != DEFAULT_AWS_REGION {
-            return Err(format!("Production environment must run in {} region", DEFAULT_AWS_REGION).into());
-        }
+        // Endpoints default per profile; any of them can be overridden individually
+        let defaults = NetworkEndpoints::for_profile(
+            EnvironmentProfile::from_node_env(&node_env).unwrap_or(EnvironmentProfile::Development),
+        );
+        let endpoints = NetworkEndpoints {
+            solana_rpc_url: env::var("SOLANA_RPC_URL").unwrap_or(defaults.solana_rpc_url),
+            jupiter_rest_url: env::var("JUPITER_API_ENDPOINT").unwrap_or(defaults.jupiter_rest_url),
+            jupiter_ws_url: env::var("JUPITER_WS_ENDPOINT").unwrap_or(defaults.jupiter_ws_url),
+            pump_fun_rest_url: env::var("PUMP_FUN_API_ENDPOINT").unwrap_or(defaults.pump_fun_rest_url),
+            pump_fun_program_id: env::var("PUMP_FUN_PROGRAM_ID").unwrap_or(defaults.pump_fun_program_id),
+            drift_rest_url: env::var("DRIFT_API_ENDPOINT").unwrap_or(defaults.drift_rest_url),
+            drift_ws_url: env::var("DRIFT_WS_ENDPOINT").unwrap_or(defaults.drift_ws_url),
+            jito_block_engine_url: env::var("JITO_API_ENDPOINT").unwrap_or(defaults.jito_block_engine_url),
+        };
 
         let config = EnvironmentConfig {
             node_env,
-            aws_region,
-            jupiter_api_endpoint: env::var("JUPITER_API_ENDPOINT")?,
-            pump_fun_api_endpoint: env::var("PUMP_FUN_API_ENDPOINT")?,
-            drift_api_endpoint: env::var("DRIFT_API_ENDPOINT")?,
-            jito_api_endpoint: env::var("JITO_API_ENDPOINT")?,
-            api_port: env::var("API_PORT")
-                .unwrap_or_else(|_| DEFAULT_API_PORT.to_string())
-                .parse()?,
+            aws_region: env::var("AWS_REGION").unwrap_or_else(|_| DEFAULT_AWS_REGION.to_string()),
+            endpoints,
+            allow_live_trading: env::var("ALLOW_LIVE_TRADING")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            api_port: parse_env_var("API_PORT", DEFAULT_API_PORT, &mut issues),
+            grpc_port: env::var("GRPC_PORT")
+                .ok()
+                .map(|_| parse_env_var("GRPC_PORT", DEFAULT_GRPC_PORT, &mut issues)),
+            ws_port: env::var("WS_PORT")
+                .ok()
+                .map(|_| parse_env_var("WS_PORT", DEFAULT_WS_PORT, &mut issues)),
             debug_mode: env::var("DEBUG_MODE")
                 .map(|v| v == "true")
                 .unwrap_or(false),
             log_level: env::var("LOG_LEVEL").ok(),
+            log_directives: env::var("LOG_DIRECTIVES")
+                .map(|v| v.split(',').map(|d| d.trim().to_string()).filter(|d| !d.is_empty()).collect())
+                .unwrap_or_else(|_| vec![]),
             allowed_origins: env::var("ALLOWED_ORIGINS")
                 .map(|v| v.split(',').map(String::from).collect())
                 .unwrap_or_else(|_| vec![]),
-            request_timeout_ms: env::var("REQUEST_TIMEOUT_MS")
-                .unwrap_or_else(|_| "30000".to_string())
-                .parse()?,
-            max_connections: env::var("MAX_CONNECTIONS")
-                .unwrap_or_else(|_| "1000".to_string())
-                .parse()?,
+            request_timeout_ms: parse_env_var("REQUEST_TIMEOUT_MS", 30000, &mut issues),
+            max_connections: parse_env_var("MAX_CONNECTIONS", 1000, &mut issues),
+            max_concurrent_trades: parse_env_var(
+                "MAX_CONCURRENT_TRADES",
+                DEFAULT_MAX_CONCURRENT_TRADES,
+                &mut issues,
+            ),
+            admission_timeout_ms: parse_env_var(
+                "ADMISSION_TIMEOUT_MS",
+                DEFAULT_ADMISSION_TIMEOUT_MS,
+                &mut issues,
+            ),
+            venue_throttles: env::var("VENUE_THROTTLES")
+                .map(|v| parse_venue_throttles(&v, &mut issues))
+                .unwrap_or_default(),
+            velocity_limits: parse_optional_var(
+                "RISK_VELOCITY_LIMITS",
+                "use orders/notional such as 60/250000",
+                &mut issues,
+            ),
+            strategy_velocity_overrides: env::var("RISK_VELOCITY_OVERRIDES")
+                .map(|v| {
+                    parse_keyed(
+                        &v,
+                        "RISK_VELOCITY_OVERRIDES",
+                        "use comma-separated strategy=orders/notional entries",
+                        &mut issues,
+                    )
+                })
+                .unwrap_or_default(),
+            daily_max_loss: parse_optional_var("DAILY_MAX_LOSS", "use a USDC amount", &mut issues),
+            daily_max_loss_pct: parse_optional_var("DAILY_MAX_LOSS_PCT", "use a percentage such as 5", &mut issues)
+                .map(Percent::from_percent),
+            daily_hard_max_loss: parse_optional_var("DAILY_HARD_MAX_LOSS", "use a USDC amount", &mut issues),
+            daily_hard_max_loss_pct: parse_optional_var(
+                "DAILY_HARD_MAX_LOSS_PCT",
+                "use a percentage such as 8",
+                &mut issues,
+            )
+            .map(Percent::from_percent),
+            flatten_on_daily_hard_breach: env::var("DAILY_LOSS_FLATTEN")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            daily_reset_time: parse_optional_var(
+                "DAILY_RESET_TIME",
+                "use a UTC time of day such as 17:00:00",
+                &mut issues,
+            ),
+            degradation_breakpoints: env::var("DEGRADATION_CURVE")
+                .map(|v| parse_breakpoints(&v, &mut issues))
+                .unwrap_or_default(),
+            degradation_opt_outs: env::var("DEGRADATION_OPT_OUTS")
+                .map(|v| v.split(',').map(|id| id.trim().to_string()).filter(|id| !id.is_empty()).collect())
+                .unwrap_or_else(|_| vec![]),
         };
 
-        validate_environment(&config)?;
+        // Missing variables are already reported, so only validate values that were loaded
+        if issues.is_empty() {
+            issues.extend(validate_environment(&config));
+        }
+
+        if issues.iter().any(ConfigIssue::is_error) {
+            return Err(issues);
+        }
+        for issue in &issues {
+            warn!("{}", issue);
+        }
         Ok(config)
     }
 }
 
-pub fn validate_environment(config: &EnvironmentConfig) -> Result<(), Box<dyn std::error::Error>> {
+/// Parses an optional numeric variable, recording an issue on malformed input
+fn parse_env_var<T: FromStr>(name: &str, default: T, issues: &mut Vec<ConfigIssue>) -> T {
+    match env::var(name) {
+        Ok(raw) => raw.parse().unwrap_or_else(|_| {
+            issues.push(ConfigIssue::error(
+                "environment",
+                name,
+                format!("'{}' is not a valid number", raw),
+                format!("set {} to an integer or unset it to use the default", name),
+            ));
+            default
+        }),
+        Err(_) => default,
+    }
+}
+
+/// Parses an optional structured variable, recording an issue on malformed input
+fn parse_optional_var<T>(name: &str, hint: &str, issues: &mut Vec<ConfigIssue>) -> Option<T>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    let raw = env::var(name).ok()?;
+    match raw.trim().parse() {
+        Ok(value) => Some(value),
+        Err(e) => {
+            issues.push(ConfigIssue::error("environment", name, format!("'{}' is invalid: {}", raw, e), hint));
+            None
+        }
+    }
+}
+
+/// Parses `exchange=rate[/burst]` pairs such as `jupiter=10,drift=5/10`
+fn parse_venue_throttles(raw: &str, issues: &mut Vec<ConfigIssue>) -> HashMap<String, VenueThrottle> {
+    parse_keyed::<VenueThrottle>(
+        raw,
+        "VENUE_THROTTLES",
+        "use comma-separated exchange=rate or exchange=rate/burst entries",
+        issues,
+    )
+    .into_iter()
+    .map(|(exchange, throttle)| (exchange.to_lowercase(), throttle))
+    .collect()
+}
+
+/// Parses `score=multiplier` breakpoints such as `0.9=1,0.7=0.5,0.5=0`
+fn parse_breakpoints(raw: &str, issues: &mut Vec<ConfigIssue>) -> Vec<(Decimal, Decimal)> {
+    let mut breakpoints = Vec::new();
+    for entry in raw.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let parsed = entry
+            .split_once('=')
+            .and_then(|(score, multiplier)| Some((score.trim().parse().ok()?, multiplier.trim().parse().ok()?)));
+        match parsed {
+            Some(breakpoint) => breakpoints.push(breakpoint),
+            None => issues.push(ConfigIssue::error(
+                "environment",
+                "DEGRADATION_CURVE",
+                format!("'{}' is not a score=multiplier breakpoint", entry),
+                "use comma-separated score=multiplier entries between 0 and 1",
+            )),
+        }
+    }
+    breakpoints
+}
+
+/// Parses comma-separated `key=value` entries, recording an issue for each malformed one
+fn parse_keyed<T: FromStr<Err = String>>(
+    raw: &str,
+    name: &str,
+    hint: &str,
+    issues: &mut Vec<ConfigIssue>,
+) -> HashMap<String, T> {
+    let mut entries = HashMap::new();
+    for entry in raw.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let parsed = entry
+            .split_once('=')
+            .ok_or_else(|| format!("'{}' is missing a limit", entry))
+            .and_then(|(key, value)| Ok((key.trim().to_string(), value.parse::<T>()?)));
+        match parsed {
+            Ok((key, value)) => {
+                entries.insert(key, value);
+            }
+            Err(problem) => issues.push(ConfigIssue::error("environment", name, problem, hint)),
+        }
+    }
+    entries
+}
+
+pub fn validate_environment(config: &EnvironmentConfig) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+
     // Validate API endpoints
-    let endpoints = [
-        (&config.jupiter_api_endpoint, "Jupiter API"),
-        (&config.pump_fun_api_endpoint, "Pump Fun API"),
-        (&config.drift_api_endpoint, "Drift API"),
-        (&config.jito_api_endpoint, "Jito API"),
-    ];
-
-    for (endpoint, name) in endpoints.iter() {
+    for (field, endpoint) in config.endpoints.urls() {
         if let Err(e) = Url::parse(endpoint) {
-            return Err(format!("Invalid {} endpoint URL: {}", name, e).into());
+            issues.push(ConfigIssue::error(
+                "environment",
+                field,
+                format!("invalid endpoint URL: {}", e),
+                "use an absolute URL such as https://host/path",
+            ));
         }
     }
 
     // Validate port number
-    if config.api_port < 1024 || config.api_port > 65535 {
-        return Err("Invalid API port number".into());
+    if config.api_port < 1024 {
+        issues.push(ConfigIssue::error(
+            "environment",
+            "API_PORT",
+            format!("port {} is in the privileged range", config.api_port),
+            "use a port between 1024 and 65535",
+        ));
+    }
+    if let Some(grpc_port) = config.grpc_port {
+        if grpc_port < 1024 {
+            issues.push(ConfigIssue::error(
+                "environment",
+                "GRPC_PORT",
+                format!("port {} is in the privileged range", grpc_port),
+                "use a port between 1024 and 65535",
+            ));
+        } else if grpc_port == config.api_port {
+            issues.push(ConfigIssue::error(
+                "environment",
+                "GRPC_PORT",
+                format!("port {} is already used by API_PORT", grpc_port),
+                "serve gRPC on a different port from the REST API",
+            ));
+        }
+    }
+    if let Some(ws_port) = config.ws_port {
+        if ws_port < 1024 {
+            issues.push(ConfigIssue::error(
+                "environment",
+                "WS_PORT",
+                format!("port {} is in the privileged range", ws_port),
+                "use a port between 1024 and 65535",
+            ));
+        } else if ws_port == config.api_port || Some(ws_port) == config.grpc_port {
+            issues.push(ConfigIssue::error(
+                "environment",
+                "WS_PORT",
+                format!("port {} is already used by API_PORT or GRPC_PORT", ws_port),
+                "serve WebSocket streams on their own port",
+            ));
+        }
     }
 
     // Environment-specific validations
     match config.node_env.as_str() {
         PRODUCTION_ENV => {
             if config.debug_mode {
-                return Err("Debug mode must be disabled in production".into());
+                issues.push(ConfigIssue::error(
+                    "environment",
+                    "DEBUG_MODE",
+                    "debug mode is enabled in production",
+                    "unset DEBUG_MODE or set it to false",
+                ));
             }
             if config.aws_region != DEFAULT_AWS_REGION {
-                return Err(format!("Production must run in {} region", DEFAULT_AWS_REGION).into());
+                issues.push(ConfigIssue::error(
+                    "environment",
+                    "AWS_REGION",
+                    format!("production is running in {}", config.aws_region),
+                    format!("production must run in {}", DEFAULT_AWS_REGION),
+                ));
             }
             if config.allowed_origins.is_empty() {
-                return Err("Production requires explicit CORS configuration".into());
+                issues.push(ConfigIssue::error(
+                    "environment",
+                    "ALLOWED_ORIGINS",
+                    "no CORS origins configured",
+                    "production requires an explicit comma-separated origin list",
+                ));
+            }
+            for (field, url) in config.endpoints.non_mainnet() {
+                issues.push(ConfigIssue::error(
+                    "environment",
+                    field,
+                    format!("production profile points at non-mainnet endpoint {}", url),
+                    format!("unset {} to use the mainnet default or point it at mainnet", field),
+                ));
             }
         }
-        STAGING_ENV => {
-            info!("Running in staging environment");
-        }
-        DEVELOPMENT_ENV => {
-            info!("Running in development environment");
+        STAGING_ENV | DEVELOPMENT_ENV => {
+            info!("Running in {} environment", config.node_env);
+            if config.allow_live_trading {
+                issues.push(ConfigIssue::warning(
+                    "environment",
+                    "ALLOW_LIVE_TRADING",
+                    format!("live trading is enabled in {}", config.node_env),
+                    "unset ALLOW_LIVE_TRADING unless real transactions are intended",
+                ));
+            }
         }
-        _ => return Err("Invalid environment".into()),
+        other => issues.push(ConfigIssue::error(
+            "environment",
+            "NODE_ENV",
+            format!("invalid environment '{}'", other),
+            "use one of production, staging, development",
+        )),
     }
 
     // Validate timeout and connection limits
     if config.request_timeout_ms < 1000 || config.request_timeout_ms > 60000 {
-        return Err("Invalid request timeout value".into());
+        issues.push(ConfigIssue::error(
+            "environment",
+            "REQUEST_TIMEOUT_MS",
+            format!("{}ms is out of range", config.request_timeout_ms),
+            "use a value between 1000 and 60000",
+        ));
     }
     if config.max_connections < 10 || config.max_connections > 10000 {
-        return Err("Invalid max connections value".into());
+        issues.push(ConfigIssue::error(
+            "environment",
+            "MAX_CONNECTIONS",
+            format!("{} is out of range", config.max_connections),
+            "use a value between 10 and 10000",
+        ));
+    }
+
+    // Validate trade admission limits
+    if config.max_concurrent_trades == 0 || config.max_concurrent_trades > 1000 {
+        issues.push(ConfigIssue::error(
+            "environment",
+            "MAX_CONCURRENT_TRADES",
+            format!("{} is out of range", config.max_concurrent_trades),
+            "use a value between 1 and 1000",
+        ));
+    }
+    if config.admission_timeout_ms == 0 || config.admission_timeout_ms > 60000 {
+        issues.push(ConfigIssue::error(
+            "environment",
+            "ADMISSION_TIMEOUT_MS",
+            format!("{}ms is out of range", config.admission_timeout_ms),
+            "use a value between 1 and 60000",
+        ));
+    }
+
+    // Validate venue submission throttles
+    let mut venues: Vec<_> = config.venue_throttles.iter().collect();
+    venues.sort_by(|a, b| a.0.cmp(b.0));
+    for (exchange, throttle) in venues {
+        if throttle.max_per_second == 0 || throttle.max_per_second > MAX_VENUE_SUBMISSIONS_PER_SECOND {
+            issues.push(ConfigIssue::error(
+                "environment",
+                "VENUE_THROTTLES",
+                format!("{} rate {}/s is out of range", exchange, throttle.max_per_second),
+                format!("use a rate between 1 and {}", MAX_VENUE_SUBMISSIONS_PER_SECOND),
+            ));
+        }
+        if throttle.burst == 0 {
+            issues.push(ConfigIssue::error(
+                "environment",
+                "VENUE_THROTTLES",
+                format!("{} burst must be at least 1", exchange),
+                "omit the burst to default it to the rate",
+            ));
+        }
+    }
+
+    // Validate daily loss thresholds
+    for (field, amount) in [
+        ("DAILY_MAX_LOSS", config.daily_max_loss),
+        ("DAILY_HARD_MAX_LOSS", config.daily_hard_max_loss),
+    ] {
+        if let Some(amount) = amount.filter(|amount| *amount <= Decimal::ZERO) {
+            issues.push(ConfigIssue::error(
+                "environment",
+                field,
+                format!("{} is not a positive loss", amount),
+                "use a positive USDC amount or unset it",
+            ));
+        }
     }
+    for (field, pct) in [
+        ("DAILY_MAX_LOSS_PCT", config.daily_max_loss_pct),
+        ("DAILY_HARD_MAX_LOSS_PCT", config.daily_hard_max_loss_pct),
+    ] {
+        if let Some(pct) = pct.filter(|pct| *pct <= Percent::ZERO || *pct > Percent::ONE_HUNDRED) {
+            issues.push(ConfigIssue::error(
+                "environment",
+                field,
+                format!("{} is out of range", pct),
+                "use a percentage above 0 and at most 100",
+            ));
+        }
+    }
+
+    // Validate the degraded-mode sizing curve
+    if !config.degradation_breakpoints.is_empty() {
+        if let Err(e) = DegradationCurve::new(
+            config
+                .degradation_breakpoints
+                .iter()
+                .map(|(score, multiplier)| SizingBreakpoint::new(*score, *multiplier))
+                .collect(),
+        ) {
+            issues.push(ConfigIssue::error(
+                "environment",
+                "DEGRADATION_CURVE",
+                e.to_string(),
+                "use scores and multipliers between 0 and 1 that never size up as the score drops",
+            ));
+        }
+    }
+
+    // Validate risk velocity caps
+    let mut velocity: Vec<_> = config
+        .velocity_limits
+        .iter()
+        .map(|caps| ("RISK_VELOCITY_LIMITS", "default", caps))
+        .chain(
+            config
+                .strategy_velocity_overrides
+                .iter()
+                .map(|(strategy_id, caps)| ("RISK_VELOCITY_OVERRIDES", strategy_id.as_str(), caps)),
+        )
+        .collect();
+    velocity.sort_by(|a, b| a.1.cmp(b.1));
+    for (field, scope, caps) in velocity {
+        if caps.max_orders_per_minute == 0 || caps.max_notional_per_hour <= Decimal::ZERO {
+            issues.push(ConfigIssue::error(
+                "environment",
+                field,
+                format!("{} caps must allow at least one order and a positive notional", scope),
+                "use positive orders/notional values",
+            ));
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_for(profile: EnvironmentProfile) -> EnvironmentConfig {
+        let mut config = EnvironmentConfig::new();
+        config.node_env = profile.as_str().to_string();
+        config.endpoints = NetworkEndpoints::for_profile(profile);
+        config.debug_mode = profile == EnvironmentProfile::Development;
+        config.allowed_origins = vec!["https://app.example.com".to_string()];
+        config
+    }
+
+    fn errors(config: &EnvironmentConfig) -> Vec<String> {
+        validate_environment(config)
+            .into_iter()
+            .filter(ConfigIssue::is_error)
+            .map(|issue| issue.field)
+            .collect()
+    }
+
+    #[test]
+    fn test_development_profile_defaults() {
+        let config = config_for(EnvironmentProfile::Development);
+        assert_eq!(config.profile(), EnvironmentProfile::Development);
+        assert_eq!(config.endpoints.solana_rpc_url, DEVNET_SOLANA_RPC_URL);
+        assert_eq!(config.endpoints.drift_ws_url, DEVNET_DRIFT_WS_URL);
+        assert_eq!(config.endpoints.jito_block_engine_url, TESTNET_JITO_URL);
+        assert!(!config.live_trading_enabled());
+        assert!(config.risky_settings().is_empty());
+        assert_eq!(config.profile().interval_multiplier(), 10);
+        assert!(errors(&config).is_empty());
+    }
+
+    #[test]
+    fn test_staging_profile_defaults() {
+        let mut config = config_for(EnvironmentProfile::Staging);
+        assert_eq!(config.endpoints.solana_rpc_url, MAINNET_SOLANA_RPC_URL);
+        assert_eq!(config.endpoints.jito_block_engine_url, MAINNET_JITO_URL);
+        assert!(!config.live_trading_enabled());
+        assert_eq!(config.risky_settings().len(), 1);
 
-    info!("Environment configuration validated successfully");
-    Ok(())
-}
\ No newline at end of file
+        // The explicit override enables live trading with a warning rather than an error
+        config.allow_live_trading = true;
+        assert!(config.live_trading_enabled());
+        assert_eq!(config.risky_settings().len(), 3);
+        assert!(errors(&config).is_empty());
+        assert!(validate_environment(&config).iter().any(|issue| issue.field == "ALLOW_LIVE_TRADING"));
+    }
+
+    #[test]
+    fn test_production_profile_defaults() {
+        let config = config_for(EnvironmentProfile::Production);
+        assert_eq!(config.endpoints, NetworkEndpoints::for_profile(EnvironmentProfile::Staging));
+        assert!(config.endpoints.non_mainnet().is_empty());
+        assert!(config.live_trading_enabled());
+        assert_eq!(config.profile().pool_size_divisor(), 1);
+        assert!(errors(&config).is_empty());
+    }
+
+    #[test]
+    fn test_production_rejects_devnet_endpoints() {
+        let mut config = config_for(EnvironmentProfile::Production);
+        config.endpoints.solana_rpc_url = DEVNET_SOLANA_RPC_URL.to_string();
+        config.endpoints.jito_block_engine_url = TESTNET_JITO_URL.to_string();
+        assert_eq!(errors(&config), vec!["SOLANA_RPC_URL", "JITO_API_ENDPOINT"]);
+    }
+
+    #[test]
+    fn test_venue_throttles_parse_and_validate() {
+        let mut issues = Vec::new();
+        let throttles = parse_venue_throttles("jupiter=10, Drift=5/20,raydium", &mut issues);
+        assert_eq!(throttles["jupiter"], VenueThrottle { max_per_second: 10, burst: 10 });
+        assert_eq!(throttles["drift"], VenueThrottle { max_per_second: 5, burst: 20 });
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "VENUE_THROTTLES");
+
+        let mut config = config_for(EnvironmentProfile::Development);
+        config.venue_throttles = throttles;
+        assert!(errors(&config).is_empty());
+        config
+            .venue_throttles
+            .insert("jupiter".to_string(), VenueThrottle { max_per_second: 0, burst: 1 });
+        assert_eq!(errors(&config), vec!["VENUE_THROTTLES"]);
+    }
+
+    #[test]
+    fn test_daily_loss_thresholds_validate() {
+        let mut config = config_for(EnvironmentProfile::Development);
+        config.daily_max_loss = Some(Decimal::new(500, 0));
+        config.daily_hard_max_loss_pct = Some(Percent::from_percent(Decimal::new(8, 0)));
+        config.daily_reset_time = NaiveTime::from_hms_opt(17, 0, 0);
+        assert!(errors(&config).is_empty());
+
+        config.daily_hard_max_loss = Some(Decimal::ZERO);
+        config.daily_max_loss_pct = Some(Percent::from_percent(Decimal::new(150, 0)));
+        assert_eq!(errors(&config), vec!["DAILY_HARD_MAX_LOSS", "DAILY_MAX_LOSS_PCT"]);
+    }
+
+    #[test]
+    fn test_degradation_curve_parse_and_validate() {
+        let mut issues = Vec::new();
+        let breakpoints = parse_breakpoints("0.8=1, 0.6=0,0.4", &mut issues);
+        assert_eq!(breakpoints, vec![(Decimal::new(8, 1), Decimal::ONE), (Decimal::new(6, 1), Decimal::ZERO)]);
+        assert_eq!(issues.len(), 1);
+
+        let mut config = config_for(EnvironmentProfile::Development);
+        config.degradation_breakpoints = breakpoints;
+        assert!(errors(&config).is_empty());
+        config.degradation_breakpoints.push((Decimal::new(2, 1), Decimal::new(5, 1)));
+        assert_eq!(errors(&config), vec!["DEGRADATION_CURVE"]);
+    }
+
+    #[test]
+    fn test_velocity_overrides_parse_and_validate() {
+        let mut issues = Vec::new();
+        let overrides: HashMap<String, VelocityCaps> =
+            parse_keyed("grid-1=30/100000, arb=120/0,mm", "RISK_VELOCITY_OVERRIDES", "", &mut issues);
+        assert_eq!(
+            overrides["grid-1"],
+            VelocityCaps { max_orders_per_minute: 30, max_notional_per_hour: Decimal::new(100_000, 0) }
+        );
+        assert_eq!(issues.len(), 1);
+
+        let mut config = config_for(EnvironmentProfile::Development);
+        config.strategy_velocity_overrides = overrides;
+        assert_eq!(errors(&config), vec!["RISK_VELOCITY_OVERRIDES"]);
+        config.strategy_velocity_overrides.remove("arb");
+        assert!(errors(&config).is_empty());
+    }
+}
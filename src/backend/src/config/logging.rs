@@ -8,6 +8,7 @@ use std::time::Duration;
 use std::collections::HashSet;
 
 use crate::config::environment::EnvironmentConfig;
+use crate::config::{ensure_valid, ConfigIssue};
 
 // Package versions:
 // serde = "1.0.164"
@@ -24,6 +25,11 @@ const LOG_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
 const MAX_LOG_SIZE_MB: u32 = 500;
 const DEFAULT_RETENTION_DAYS: u32 = 30;
 const SENSITIVE_FIELDS: [&str; 3] = ["private_key", "wallet_key", "signature"];
+const DEFAULT_OTLP_ENDPOINT: &str = "http://localhost:4317";
+const DEFAULT_SERVICE_NAME: &str = "firebot";
+const DEFAULT_PROD_SAMPLE_RATIO: f64 = 0.05;
+/// Collector loops poll every 100ms, so their spans stay out of exported traces by default
+const DEFAULT_TRACE_DIRECTIVES: [&str; 2] = ["info", "firebot::data_collector=warn"];
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -36,10 +42,107 @@ pub struct LogConfig {
     pub rotation_size_mb: u32,
     pub retention_days: u32,
     pub enable_async: bool,
+    /// Hot-path debug events are logged 1 in this many
     pub sampling_rate: u32,
+    /// Per-module directives applied on top of `log_level`
+    #[serde(default)]
+    pub directives: Vec<String>,
     pub enable_compression: bool,
     pub sensitive_fields: Vec<String>,
     pub backup_endpoint: Option<String>,
+    #[serde(default)]
+    pub tracing: TracingConfig,
+}
+
+/// OpenTelemetry trace export over OTLP
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TracingConfig {
+    pub enabled: bool,
+    /// OTLP gRPC collector, e.g. a local Jaeger or Tempo on port 4317
+    pub otlp_endpoint: String,
+    pub service_name: String,
+    /// Share of new traces exported; spans continuing a remote trace follow its decision
+    pub sample_ratio: f64,
+    /// Span filter for export, independent of the log filter
+    pub directives: Vec<String>,
+}
+
+impl Default for TracingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            otlp_endpoint: DEFAULT_OTLP_ENDPOINT.to_string(),
+            service_name: DEFAULT_SERVICE_NAME.to_string(),
+            sample_ratio: 1.0,
+            directives: DEFAULT_TRACE_DIRECTIVES.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+}
+
+impl TracingConfig {
+    /// Reads TRACING_ENABLED, OTEL_EXPORTER_OTLP_ENDPOINT, OTEL_SERVICE_NAME,
+    /// OTEL_TRACES_SAMPLER_ARG and TRACING_DIRECTIVES; production samples sparsely by default
+    pub fn new(env_config: &EnvironmentConfig) -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.trim().is_empty());
+
+        TracingConfig {
+            enabled: var("TRACING_ENABLED").map_or(false, |value| value.eq_ignore_ascii_case("true")),
+            otlp_endpoint: var("OTEL_EXPORTER_OTLP_ENDPOINT").unwrap_or(defaults.otlp_endpoint),
+            service_name: var("OTEL_SERVICE_NAME").unwrap_or(defaults.service_name),
+            // An unparsable ratio is kept out of range so validation reports it
+            sample_ratio: match var("OTEL_TRACES_SAMPLER_ARG") {
+                Some(value) => value.trim().parse().unwrap_or(f64::NAN),
+                None if env_config.is_production() => DEFAULT_PROD_SAMPLE_RATIO,
+                None => defaults.sample_ratio,
+            },
+            directives: var("TRACING_DIRECTIVES")
+                .map(|value| value.split(',').map(|d| d.trim().to_string()).filter(|d| !d.is_empty()).collect())
+                .unwrap_or(defaults.directives),
+        }
+    }
+
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        if !(0.0..=1.0).contains(&self.sample_ratio) {
+            issues.push(ConfigIssue::error(
+                "tracing",
+                "OTEL_TRACES_SAMPLER_ARG",
+                format!("sample ratio {} is out of range", self.sample_ratio),
+                "use a ratio between 0.0 and 1.0",
+            ));
+        }
+        for directive in &self.directives {
+            if let Err(e) = directive.parse::<tracing_subscriber::filter::Directive>() {
+                issues.push(ConfigIssue::error(
+                    "tracing",
+                    "TRACING_DIRECTIVES",
+                    format!("invalid directive '{}': {}", directive, e),
+                    "use target=level pairs such as firebot::execution_engine=debug",
+                ));
+            }
+        }
+        if self.enabled {
+            if let Err(e) = url::Url::parse(&self.otlp_endpoint) {
+                issues.push(ConfigIssue::error(
+                    "tracing",
+                    "OTEL_EXPORTER_OTLP_ENDPOINT",
+                    format!("invalid OTLP endpoint URL: {}", e),
+                    "use an absolute URL such as http://localhost:4317",
+                ));
+            }
+            if self.service_name.trim().is_empty() {
+                issues.push(ConfigIssue::error(
+                    "tracing",
+                    "OTEL_SERVICE_NAME",
+                    "service name is empty",
+                    "set the service name traces are reported under",
+                ));
+            }
+        }
+        issues
+    }
 }
 
 impl LogConfig {
@@ -61,6 +164,7 @@ impl LogConfig {
             retention_days: DEFAULT_RETENTION_DAYS,
             enable_async: is_prod,
             sampling_rate: if is_prod { 100 } else { 1 },
+            directives: env_config.log_directives.clone(),
             enable_compression: is_prod,
             sensitive_fields: SENSITIVE_FIELDS.iter()
                 .map(|&s| s.to_string())
@@ -70,58 +174,117 @@ impl LogConfig {
             } else {
                 None
             },
+            tracing: TracingConfig::new(env_config),
         }
     }
 
-    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+
         // Validate log level
         match self.log_level.to_uppercase().as_str() {
             "ERROR" | "WARN" | "INFO" | "DEBUG" | "TRACE" => (),
-            _ => return Err("Invalid log level specified".into()),
+            _ => issues.push(ConfigIssue::error(
+                "logging",
+                "LOG_LEVEL",
+                format!("invalid log level '{}'", self.log_level),
+                "use one of ERROR, WARN, INFO, DEBUG, TRACE",
+            )),
+        }
+
+        // Validate per-module directives
+        for directive in &self.directives {
+            if let Err(e) = directive.parse::<tracing_subscriber::filter::Directive>() {
+                issues.push(ConfigIssue::error(
+                    "logging",
+                    "LOG_DIRECTIVES",
+                    format!("invalid directive '{}': {}", directive, e),
+                    "use target=level pairs such as firebot::data_collector=debug",
+                ));
+            }
         }
 
         // Validate file path
         let path = PathBuf::from(&self.log_file_path);
         if !path.exists() {
-            std::fs::create_dir_all(&path)?;
+            if let Err(e) = std::fs::create_dir_all(&path) {
+                issues.push(ConfigIssue::error(
+                    "logging",
+                    "log_file_path",
+                    format!("cannot create {}: {}", self.log_file_path, e),
+                    "point the log path at a writable directory",
+                ));
+            }
         }
 
         // Validate ELK configuration
         if self.elk_enabled {
             if self.elk_endpoint.is_empty() {
-                return Err("ELK endpoint must be specified when ELK is enabled".into());
+                issues.push(ConfigIssue::error(
+                    "logging",
+                    "elk_endpoint",
+                    "ELK is enabled without an endpoint",
+                    "set the ELK endpoint or disable ELK shipping",
+                ));
+            } else if let Err(e) = url::Url::parse(&self.elk_endpoint) {
+                issues.push(ConfigIssue::error(
+                    "logging",
+                    "elk_endpoint",
+                    format!("invalid ELK endpoint URL: {}", e),
+                    "use an absolute URL such as http://elk:9200",
+                ));
             }
-            url::Url::parse(&self.elk_endpoint)?;
         }
 
         // Validate rotation and retention settings
         if self.rotation_size_mb < 1 || self.rotation_size_mb > 1000 {
-            return Err("Invalid rotation size".into());
+            issues.push(ConfigIssue::error(
+                "logging",
+                "rotation_size_mb",
+                format!("{}MB is out of range", self.rotation_size_mb),
+                "use a value between 1 and 1000",
+            ));
         }
         if self.retention_days < 1 || self.retention_days > 365 {
-            return Err("Invalid retention period".into());
+            issues.push(ConfigIssue::error(
+                "logging",
+                "retention_days",
+                format!("{} days is out of range", self.retention_days),
+                "use a value between 1 and 365",
+            ));
         }
 
         // Validate sampling rate
         if self.sampling_rate < 1 || self.sampling_rate > 1000 {
-            return Err("Invalid sampling rate".into());
+            issues.push(ConfigIssue::error(
+                "logging",
+                "sampling_rate",
+                format!("{} is out of range", self.sampling_rate),
+                "use a value between 1 and 1000",
+            ));
         }
 
         // Validate backup endpoint if configured
         if let Some(endpoint) = &self.backup_endpoint {
             if !endpoint.starts_with("s3://") {
-                return Err("Backup endpoint must be an S3 URL".into());
+                issues.push(ConfigIssue::error(
+                    "logging",
+                    "backup_endpoint",
+                    format!("'{}' is not an S3 URL", endpoint),
+                    "use an s3://bucket/prefix URL",
+                ));
             }
         }
 
-        Ok(())
+        issues.extend(self.tracing.validate());
+        issues
     }
 }
 
 #[tracing::instrument]
 pub async fn build_logger(config: &LogConfig) -> Result<Box<dyn Subscriber + Send + Sync>, Box<dyn std::error::Error>> {
     // Validate configuration
-    config.validate()?;
+    ensure_valid(config.validate())?;
 
     // Set up file appender with rotation
     let file_appender = RollingFileAppender::new(
@@ -3,10 +3,13 @@
 //! versioning, and disaster recovery capabilities.
 //! Version: 1.0.0
 
+use std::fmt;
+
 use chrono::{DateTime, Utc};
 use dotenv::dotenv; // v0.15.0
 use prometheus::{Registry, register_gauge}; // v0.13.3
 use serde::{Deserialize, Serialize}; // v1.0.164
+use tokio::sync::watch; // v1.28.0
 use tracing::{error, info, instrument, warn}; // v0.1.37
 
 use crate::config::database::DatabaseConfig;
@@ -17,6 +20,7 @@ use crate::config::security::SecurityConfig;
 // Global constants
 const CONFIG_ERROR: &str = "Configuration error";
 const CONFIG_VERSION: &str = "1.0.0";
+pub const CONFIG_EXIT_CODE: i32 = 78; // EX_CONFIG
 
 // Initialize global metrics registry
 lazy_static::lazy_static! {
@@ -25,6 +29,151 @@ lazy_static::lazy_static! {
         "config_changes_total",
         "Total number of configuration changes"
     ).unwrap();
+    static ref SECURITY_UPDATES: watch::Sender<Option<SecurityConfig>> = watch::channel(None).0;
+    static ref ENVIRONMENT_UPDATES: watch::Sender<Option<EnvironmentConfig>> = watch::channel(None).0;
+}
+
+/// Subscribes to security configuration published by successful reloads
+pub fn subscribe_security_updates() -> watch::Receiver<Option<SecurityConfig>> {
+    SECURITY_UPDATES.subscribe()
+}
+
+/// Subscribes to environment configuration published by successful reloads
+pub fn subscribe_environment_updates() -> watch::Receiver<Option<EnvironmentConfig>> {
+    ENVIRONMENT_UPDATES.subscribe()
+}
+
+/// Severity of a configuration issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+/// Single configuration problem with a hint for fixing it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigIssue {
+    pub section: String,
+    pub field: String,
+    pub problem: String,
+    pub hint: String,
+    pub severity: IssueSeverity,
+}
+
+impl ConfigIssue {
+    /// Creates a fatal configuration issue
+    pub fn error(
+        section: &str,
+        field: &str,
+        problem: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            section: section.to_string(),
+            field: field.to_string(),
+            problem: problem.into(),
+            hint: hint.into(),
+            severity: IssueSeverity::Error,
+        }
+    }
+
+    /// Creates a non-fatal configuration warning
+    pub fn warning(
+        section: &str,
+        field: &str,
+        problem: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity: IssueSeverity::Warning,
+            ..Self::error(section, field, problem, hint)
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == IssueSeverity::Error
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}: {} (hint: {})", self.section, self.field, self.problem, self.hint)
+    }
+}
+
+/// Consolidated validation result with fatal errors listed apart from warnings
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidationReport {
+    pub errors: Vec<ConfigIssue>,
+    pub warnings: Vec<ConfigIssue>,
+}
+
+impl ValidationReport {
+    /// Splits issues into errors and warnings
+    pub fn from_issues(issues: impl IntoIterator<Item = ConfigIssue>) -> Self {
+        let mut report = Self::default();
+        report.extend(issues);
+        report
+    }
+
+    pub fn extend(&mut self, issues: impl IntoIterator<Item = ConfigIssue>) {
+        for issue in issues {
+            if issue.is_error() {
+                self.errors.push(issue);
+            } else {
+                self.warnings.push(issue);
+            }
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Renders the human-readable report printed at startup and by --check-config
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        if self.errors.is_empty() {
+            out.push_str("Configuration OK\n");
+        } else {
+            out.push_str(&format!("Configuration invalid: {} error(s)\n", self.errors.len()));
+            for issue in &self.errors {
+                out.push_str(&format!("  ERROR   {}\n", issue));
+            }
+        }
+        if !self.warnings.is_empty() {
+            out.push_str(&format!("{} warning(s)\n", self.warnings.len()));
+            for issue in &self.warnings {
+                out.push_str(&format!("  WARNING {}\n", issue));
+            }
+        }
+        out
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+/// Converts section issues into a Result carrying every fatal error
+pub fn ensure_valid(issues: Vec<ConfigIssue>) -> Result<(), String> {
+    let report = ValidationReport::from_issues(issues);
+    for warning in &report.warnings {
+        warn!("{}", warning);
+    }
+    if report.is_ok() {
+        Ok(())
+    } else {
+        Err(report
+            .errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; "))
+    }
 }
 
 /// Main application configuration structure with enhanced capabilities
@@ -58,7 +207,10 @@ impl AppConfig {
         };
 
         // Validate configuration
-        validate_config(&config)?;
+        let report = validate_config(&config);
+        if !report.is_ok() {
+            return Err(format!("{}:\n{}", CONFIG_ERROR, report.render()));
+        }
 
         // Update metrics
         CONFIG_CHANGES.inc();
@@ -79,9 +231,10 @@ impl AppConfig {
 
         // Load new configurations
         let env_config = EnvironmentConfig::from_env()
-            .map_err(|e| format!("Failed to reload environment config: {}", e))?;
-        
-        let db_config = DatabaseConfig::new();
+            .map_err(|issues| format!(
+                "Failed to reload environment config:\n{}",
+                ValidationReport::from_issues(issues).render()
+            ))?;
         
         let log_config = LogConfig::new(&env_config);
         
@@ -89,6 +242,10 @@ impl AppConfig {
             .await
             .map_err(|e| format!("Failed to reload security config: {}", e))?;
 
+        let db_config = DatabaseConfig::from_env(&security_config.kms.key_id)
+            .await
+            .map_err(|e| format!("Failed to reload database config: {}", e))?;
+
         // Validate new configurations
         let new_config = AppConfig::new(
             env_config,
@@ -101,6 +258,10 @@ impl AppConfig {
         *self = new_config;
         self.last_updated = Utc::now();
 
+        // Notify hot-reload listeners such as the JWT key store and trade admission
+        SECURITY_UPDATES.send_replace(Some(self.security.clone()));
+        ENVIRONMENT_UPDATES.send_replace(Some(self.environment.clone()));
+
         // Update metrics
         CONFIG_CHANGES.inc();
 
@@ -109,83 +270,196 @@ impl AppConfig {
     }
 }
 
-/// Initializes all configuration components with comprehensive validation and monitoring
+/// Loads every configuration section, collecting all problems instead of stopping at the first
 #[instrument]
-pub async fn init_config() -> Result<AppConfig, String> {
-    info!("Initializing configuration");
-
+pub async fn load_config() -> Result<(AppConfig, ValidationReport), ValidationReport> {
     // Load environment variables
     dotenv().ok();
 
+    let mut report = ValidationReport::default();
+
     // Initialize environment configuration
-    let env_config = EnvironmentConfig::from_env()
-        .map_err(|e| format!("Environment configuration error: {}", e))?;
+    let env_config = match EnvironmentConfig::from_env() {
+        Ok(config) => Some(config),
+        Err(issues) => {
+            report.extend(issues);
+            None
+        }
+    };
 
-    // Initialize database configuration
-    let db_config = DatabaseConfig::new();
+    // Initialize security configuration
+    let security_config = match SecurityConfig::load_security_config().await {
+        Ok(config) => Some(config),
+        Err(e) => {
+            report.extend([ConfigIssue::error(
+                "security",
+                "load",
+                e,
+                "check JWT, KMS and access control variables",
+            )]);
+            None
+        }
+    };
+
+    // Initialize database configuration, which needs the KMS key from the security section
+    let db_config = match &security_config {
+        Some(security) => match DatabaseConfig::from_env(&security.kms.key_id).await {
+            Ok(config) => Some(config),
+            Err(e) => {
+                report.extend([ConfigIssue::error(
+                    "database",
+                    "load",
+                    e,
+                    "check DB_* variables and that kms: values decrypt with KMS_KEY_ID",
+                )]);
+                None
+            }
+        },
+        None => None,
+    };
+
+    assemble_config(env_config, security_config, db_config, report)
+}
 
-    // Initialize logging configuration
+/// Builds the config from the loaded sections and validates it. Sections that loaded are
+/// still validated when another failed, so one bad section does not hide the others' problems
+fn assemble_config(
+    env_config: Option<EnvironmentConfig>,
+    security_config: Option<SecurityConfig>,
+    db_config: Option<DatabaseConfig>,
+    mut report: ValidationReport,
+) -> Result<(AppConfig, ValidationReport), ValidationReport> {
+    let (env_config, security_config, db_config) = match (env_config, security_config, db_config) {
+        (Some(env), Some(security), Some(db)) => (env, security, db),
+        (env, security, db) => {
+            if let Some(env) = &env {
+                report.extend(crate::config::environment::validate_environment(env));
+                report.extend(LogConfig::new(env).validate());
+            }
+            if let Some(security) = &security {
+                report.extend(security.validate());
+            }
+            if let Some(db) = &db {
+                report.extend(db.validate_config());
+            }
+            return Err(report);
+        }
+    };
     let log_config = LogConfig::new(&env_config);
 
-    // Initialize security configuration
-    let security_config = SecurityConfig::load_security_config()
-        .await
-        .map_err(|e| format!("Security configuration error: {}", e))?;
+    let config = AppConfig {
+        environment: env_config,
+        database: db_config,
+        logging: log_config,
+        security: security_config,
+        version: CONFIG_VERSION.to_string(),
+        last_updated: Utc::now(),
+    };
 
-    // Create and validate complete configuration
-    let config = AppConfig::new(
-        env_config,
-        db_config,
-        log_config,
-        security_config,
-    )?;
+    let section_report = validate_config(&config);
+    report.errors.extend(section_report.errors);
+    report.warnings.extend(section_report.warnings);
 
-    info!("Configuration initialized successfully");
-    Ok(config)
+    if report.is_ok() {
+        CONFIG_CHANGES.inc();
+        Ok((config, report))
+    } else {
+        Err(report)
+    }
 }
 
-/// Performs comprehensive validation of all configuration components
-#[instrument(skip(config))]
-pub fn validate_config(config: &AppConfig) -> Result<(), String> {
-    info!("Validating configuration");
+/// Initializes configuration, logging warnings and returning the consolidated report as the
+/// error so the caller fails startup
+#[instrument]
+pub async fn init_config() -> Result<AppConfig, String> {
+    info!("Initializing configuration");
 
-    // Validate environment configuration
-    if let Err(e) = config.environment.validate_environment(&config.environment) {
-        error!("Environment validation failed: {}", e);
-        return Err(format!("{}: environment validation failed", CONFIG_ERROR));
+    match load_config().await {
+        Ok((config, report)) => {
+            for warning in &report.warnings {
+                warn!("{}", warning);
+            }
+            info!("Configuration initialized successfully");
+            Ok(config)
+        }
+        Err(report) => {
+            error!(error_count = report.errors.len(), "{}", CONFIG_ERROR);
+            Err(format!("{}:\n{}", CONFIG_ERROR, report.render()))
+        }
     }
+}
 
-    // Validate database configuration
-    if let Err(e) = config.database.validate_config() {
-        error!("Database validation failed: {}", e);
-        return Err(format!("{}: database validation failed", CONFIG_ERROR));
+/// Runs full validation including KMS and database connectivity probes without starting the bot
+#[instrument]
+pub async fn check_config() -> ValidationReport {
+    // KMS access is probed while loading the security section
+    let (config, mut report) = match load_config().await {
+        Ok(loaded) => loaded,
+        Err(report) => return report,
+    };
+
+    // Probe database connectivity
+    match crate::config::database::create_pool(config.database.clone()).await {
+        Ok(pool) => {
+            if !crate::config::database::health_check(&pool).await.unwrap_or(false) {
+                report.extend([ConfigIssue::error(
+                    "database",
+                    "connectivity",
+                    "database health check failed",
+                    "verify the host is reachable and the credentials are correct",
+                )]);
+            }
+        }
+        Err(e) => report.extend([ConfigIssue::error(
+            "database",
+            "connectivity",
+            e,
+            "verify the host is reachable and the credentials are correct",
+        )]),
     }
 
-    // Validate logging configuration
-    if let Err(e) = config.logging.validate() {
-        error!("Logging validation failed: {}", e);
-        return Err(format!("{}: logging validation failed", CONFIG_ERROR));
-    }
+    report
+}
 
-    // Validate security configuration
-    if let Err(e) = config.security.validate() {
-        error!("Security validation failed: {}", e);
-        return Err(format!("{}: security validation failed", CONFIG_ERROR));
-    }
+/// Performs comprehensive validation of all configuration components
+#[instrument(skip(config))]
+pub fn validate_config(config: &AppConfig) -> ValidationReport {
+    info!("Validating configuration");
+
+    let mut report = ValidationReport::default();
+
+    // Validate each section independently so every problem is reported
+    report.extend(crate::config::environment::validate_environment(&config.environment));
+    report.extend(config.database.validate_config());
+    report.extend(config.logging.validate());
+    report.extend(config.security.validate());
 
     // Cross-component validation
-    if config.is_production() {
-        // Additional production-specific validations
-        if config.logging.json_format != true {
-            warn!("Production environment should use JSON logging format");
+    if config.environment.node_env == crate::config::environment::PRODUCTION_ENV {
+        if !config.logging.json_format {
+            report.extend([ConfigIssue::warning(
+                "logging",
+                "json_format",
+                "production is not using JSON logging",
+                "enable JSON logging so logs are machine-parseable",
+            )]);
         }
         if !config.security.audit.enabled {
-            return Err(format!("{}: audit logging must be enabled in production", CONFIG_ERROR));
+            report.extend([ConfigIssue::error(
+                "security",
+                "AUDIT_ENABLED",
+                "audit logging must be enabled in production",
+                "set AUDIT_ENABLED=true",
+            )]);
         }
     }
 
-    info!("Configuration validation completed successfully");
-    Ok(())
+    if report.is_ok() {
+        info!("Configuration validation completed successfully");
+    } else {
+        error!(error_count = report.errors.len(), "Configuration validation failed");
+    }
+    report
 }
 
 #[cfg(test)]
@@ -210,6 +484,8 @@ mod tests {
             Default::default(),
             Default::default(),
             Default::default(),
+            Default::default(),
+            Default::default(),
         ).unwrap();
 
         let config = AppConfig::new(
@@ -221,6 +497,169 @@ mod tests {
         assert!(config.is_ok());
     }
 
+    fn valid_security_config() -> SecurityConfig {
+        use crate::config::security::{
+            AccessControlConfig, AuditConfig, JWTConfig, KMSConfig, OrderSigningConfig,
+            RateLimitConfig, RequestLimitsConfig, SignerConfig,
+        };
+
+        SecurityConfig {
+            jwt: JWTConfig {
+                secret_key: "test_secret_key_for_validation".into(),
+                token_expiry: 3600,
+                refresh_expiry: 86400,
+                algorithm: jsonwebtoken::Algorithm::HS256,
+                keys: vec![],
+                jwks: None,
+            },
+            kms: KMSConfig {
+                key_id: "test-key".to_string(),
+                region: "us-east-1".to_string(),
+                auto_rotation: true,
+                rotation_period: 90,
+            },
+            rate_limit: RateLimitConfig {
+                max_requests: 100,
+                window_size: std::time::Duration::from_secs(60),
+                max_failed_attempts: 3,
+            },
+            audit: AuditConfig {
+                enabled: true,
+                log_level: "INFO".to_string(),
+                retention_days: 30,
+            },
+            access_control: AccessControlConfig {
+                allowed_ips: vec![],
+                allowed_origins: vec!["https://app.example.com".to_string()],
+                required_permissions: Default::default(),
+            },
+            request_limits: RequestLimitsConfig::default(),
+            order_signing: OrderSigningConfig::default(),
+            signer: SignerConfig::default(),
+        }
+    }
+
+    #[test]
+    fn test_all_independent_errors_reported() {
+        let mut env_config = EnvironmentConfig::new();
+        env_config.api_port = 80;
+        env_config.grpc_port = Some(443);
+
+        let mut db_config = DatabaseConfig::new();
+        db_config.host = "localhost".to_string();
+        db_config.username = "user".to_string();
+        db_config.password = "pass".into();
+        db_config.database = "trading".to_string();
+        db_config.pool_size = 500;
+
+        let mut log_config = LogConfig::new(&env_config);
+        log_config.log_file_path = std::env::temp_dir().to_string_lossy().to_string();
+        log_config.log_level = "VERBOSE".to_string();
+
+        let config = AppConfig {
+            environment: env_config,
+            database: db_config,
+            logging: log_config,
+            security: valid_security_config(),
+            version: CONFIG_VERSION.to_string(),
+            last_updated: Utc::now(),
+        };
+
+        let report = validate_config(&config);
+        let fields: Vec<&str> = report.errors.iter().map(|issue| issue.field.as_str()).collect();
+        assert!(fields.contains(&"API_PORT"));
+        assert!(fields.contains(&"GRPC_PORT"));
+        assert!(fields.contains(&"pool_size"));
+        assert!(fields.contains(&"LOG_LEVEL"));
+
+        let rendered = report.render();
+        assert!(rendered.contains("API_PORT") && rendered.contains("pool_size") && rendered.contains("LOG_LEVEL"));
+    }
+
+    #[test]
+    fn test_loaded_sections_validated_when_another_fails() {
+        let mut env_config = EnvironmentConfig::new();
+        env_config.api_port = 80;
+        let mut report = ValidationReport::default();
+        report.extend([ConfigIssue::error("security", "load", "missing JWT_SECRET", "set JWT_SECRET")]);
+
+        let report = assemble_config(Some(env_config), None, None, report).unwrap_err();
+        let fields: Vec<&str> = report.errors.iter().map(|issue| issue.field.as_str()).collect();
+        assert!(fields.contains(&"load"));
+        assert!(fields.contains(&"API_PORT"));
+    }
+
+    #[test]
+    fn test_signer_backend_validated() {
+        use crate::config::security::{RemoteSignerConfig, SignerConfig};
+
+        // Without a wallet key the config loads, with a warning
+        let mut security = valid_security_config();
+        let report = ValidationReport::from_issues(security.validate());
+        assert!(report.is_ok());
+        assert!(report.warnings.iter().any(|issue| issue.field == "WALLET_SECRET_KEY"));
+
+        security.signer = SignerConfig::Remote(RemoteSignerConfig {
+            url: "http://signer.internal".to_string(),
+            pubkey: String::new(),
+            client_cert_path: "/etc/signer/client.pem".to_string(),
+            client_key_path: "/etc/signer/client.key".to_string(),
+            ca_cert_path: "/etc/signer/ca.pem".to_string(),
+            timeout: std::time::Duration::ZERO,
+            retry_on_failure: false,
+        });
+        let report = ValidationReport::from_issues(security.validate());
+        let fields: Vec<&str> = report.errors.iter().map(|issue| issue.field.as_str()).collect();
+        assert_eq!(fields, vec!["SIGNER_REMOTE_URL", "SIGNER_REMOTE_CLIENT_CERT", "SIGNER_TIMEOUT_MS"]);
+    }
+
+    #[test]
+    fn test_warnings_listed_separately() {
+        let report = ValidationReport::from_issues(vec![
+            ConfigIssue::error("database", "pool_size", "out of range", "use 5-50"),
+            ConfigIssue::warning("logging", "json_format", "not JSON", "enable JSON"),
+        ]);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(!report.is_ok());
+        assert!(ensure_valid(vec![ConfigIssue::warning("logging", "json_format", "not JSON", "enable JSON")]).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_loaded_config_debug_redacts_secrets() {
+        std::env::set_var("DB_HOST", "localhost");
+        std::env::set_var("DB_PASSWORD", "loaded-db-password");
+        std::env::set_var("DB_REPLICA_PASSWORD", "loaded-replica-password");
+        let db_config = DatabaseConfig::from_env("test-key").await.unwrap();
+        assert_eq!(db_config.password.expose(), "loaded-db-password");
+
+        let mut security_config = valid_security_config();
+        security_config.jwt.keys = vec![crate::config::security::JwtKey {
+            kid: "k1".to_string(),
+            secret: "rotated_jwt_signing_key".into(),
+        }];
+
+        let env_config = EnvironmentConfig::new();
+        let config = AppConfig {
+            logging: LogConfig::new(&env_config),
+            environment: env_config,
+            database: db_config,
+            security: security_config,
+            version: CONFIG_VERSION.to_string(),
+            last_updated: Utc::now(),
+        };
+
+        let debug = format!("{:?}", config);
+        for secret in [
+            "loaded-db-password",
+            "loaded-replica-password",
+            "test_secret_key_for_validation",
+            "rotated_jwt_signing_key",
+        ] {
+            assert!(!debug.contains(secret), "debug output leaked {}", secret);
+        }
+    }
+
     #[tokio::test]
     async fn test_config_reload() {
         let mut config = init_config().await.unwrap();
@@ -2,12 +2,14 @@
 //! Version: 1.0.0
 //! Manages authentication, encryption, and security settings with comprehensive validation
 
+use rust_decimal::Decimal; // v1.30.0
 use serde::{Deserialize, Serialize}; // v1.0.164
 use jsonwebtoken::{DecodingKey, EncodingKey, Algorithm}; // v8.1.1
 use aws_sdk_kms::{Client as KmsClient, Region}; // v0.28.0
 use tracing::{error, info, instrument, warn}; // v0.1.37
 
-use crate::utils::crypto::{encrypt_sensitive_data, decrypt_sensitive_data};
+use crate::config::{ensure_valid, ConfigIssue};
+use crate::utils::crypto::{encrypt_sensitive_data, decrypt_sensitive_data, resolve_secret, Secret};
 
 use std::time::Duration;
 use std::collections::HashMap;
@@ -18,14 +20,60 @@ const DEFAULT_REFRESH_EXPIRY: i64 = 604800; // 7 days
 const MAX_FAILED_ATTEMPTS: u32 = 5;
 const MIN_PASSWORD_LENGTH: usize = 12;
 const KMS_KEY_ROTATION_DAYS: i64 = 90;
+const DEFAULT_JWKS_CACHE_TTL_SECS: u64 = 300;
+const DEFAULT_TRADING_BODY_LIMIT: usize = 64 * 1024; // 64KB
+const DEFAULT_ADMIN_BODY_LIMIT: usize = 1024 * 1024; // 1MB
+const DEFAULT_BODY_READ_TIMEOUT_MS: u64 = 5000;
+const DEFAULT_ORDER_SIGNATURE_THRESHOLD: i64 = 10_000; // USDC notional
+const DEFAULT_ORDER_SIGNATURE_MAX_TTL_SECS: u64 = 120;
+const DEFAULT_REMOTE_SIGNER_TIMEOUT_MS: u64 = 150;
+pub const LEGACY_JWT_KID: &str = "default";
 
 /// JWT configuration settings
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct JWTConfig {
-    pub secret_key: String,
+    pub secret_key: Secret<String>,
     pub token_expiry: i64,
     pub refresh_expiry: i64,
     pub algorithm: Algorithm,
+    /// Active HS256 signing keys, oldest first; the last entry signs new tokens
+    #[serde(default)]
+    pub keys: Vec<JwtKey>,
+    /// Optional external IdP key set for RS256 tokens
+    #[serde(default)]
+    pub jwks: Option<JwksConfig>,
+}
+
+/// HS256 signing key identified by key id
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JwtKey {
+    pub kid: String,
+    pub secret: Secret<String>,
+}
+
+/// JWKS endpoint configuration for RS256 validation
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct JwksConfig {
+    pub url: String,
+    pub cache_ttl_secs: u64,
+    pub issuer: Option<String>,
+    pub audience: Option<String>,
+}
+
+impl JWTConfig {
+    /// Returns active HS256 keys, falling back to the legacy single secret
+    pub fn active_keys(&self) -> Vec<JwtKey> {
+        if !self.keys.is_empty() {
+            return self.keys.clone();
+        }
+        if self.secret_key.is_empty() {
+            return Vec::new();
+        }
+        vec![JwtKey {
+            kid: LEGACY_JWT_KID.to_string(),
+            secret: self.secret_key.clone(),
+        }]
+    }
 }
 
 /// AWS KMS configuration
@@ -61,6 +109,81 @@ pub struct AccessControlConfig {
     pub required_permissions: HashMap<String, Vec<String>>,
 }
 
+/// API request body limits per route class
+#[derive(Debug, Clone, Deserialize)]
+pub struct RequestLimitsConfig {
+    /// Largest body accepted by trading, market data and portfolio endpoints
+    pub trading_body_limit: usize,
+    /// Largest body accepted by admin, strategy and other configuration endpoints
+    pub admin_body_limit: usize,
+    /// Time allowed to receive the whole body, separate from the handler timeout
+    pub body_read_timeout: Duration,
+}
+
+impl Default for RequestLimitsConfig {
+    fn default() -> Self {
+        Self {
+            trading_body_limit: DEFAULT_TRADING_BODY_LIMIT,
+            admin_body_limit: DEFAULT_ADMIN_BODY_LIMIT,
+            body_read_timeout: Duration::from_millis(DEFAULT_BODY_READ_TIMEOUT_MS),
+        }
+    }
+}
+
+/// Wallet signature requirement for high-value orders
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrderSigningConfig {
+    /// Orders with a notional above this need a fresh wallet signature besides the JWT
+    pub signature_threshold: Decimal,
+    /// Longest validity window a signed order may declare
+    pub max_signature_ttl: Duration,
+}
+
+impl Default for OrderSigningConfig {
+    fn default() -> Self {
+        Self {
+            signature_threshold: Decimal::from(DEFAULT_ORDER_SIGNATURE_THRESHOLD),
+            max_signature_ttl: Duration::from_secs(DEFAULT_ORDER_SIGNATURE_MAX_TTL_SECS),
+        }
+    }
+}
+
+/// Backend signing trading transactions
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum SignerConfig {
+    /// Base58 secret key held in process memory
+    Keypair { secret_key: Secret<String> },
+    /// Ed25519 key in AWS KMS, signed with `kms:Sign`
+    Kms { key_id: String, region: String },
+    /// Remote signing service over HTTPS with mutual TLS
+    Remote(RemoteSignerConfig),
+}
+
+impl Default for SignerConfig {
+    fn default() -> Self {
+        Self::Keypair {
+            secret_key: Secret::default(),
+        }
+    }
+}
+
+/// Remote signer endpoint and the client certificate presented to it
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteSignerConfig {
+    pub url: String,
+    /// Wallet public key the signer signs for; every returned signature is checked against it
+    pub pubkey: String,
+    pub client_cert_path: String,
+    pub client_key_path: String,
+    /// CA the signer's certificate must chain to; public roots are not trusted
+    pub ca_cert_path: String,
+    pub timeout: Duration,
+    /// Signing failures are not retried unless enabled
+    #[serde(default)]
+    pub retry_on_failure: bool,
+}
+
 /// Comprehensive security configuration
 #[derive(Debug, Clone, Deserialize)]
 pub struct SecurityConfig {
@@ -69,6 +192,12 @@ pub struct SecurityConfig {
     pub rate_limit: RateLimitConfig,
     pub audit: AuditConfig,
     pub access_control: AccessControlConfig,
+    #[serde(default)]
+    pub request_limits: RequestLimitsConfig,
+    #[serde(default)]
+    pub order_signing: OrderSigningConfig,
+    #[serde(default)]
+    pub signer: SignerConfig,
 }
 
 impl SecurityConfig {
@@ -79,6 +208,9 @@ impl SecurityConfig {
         rate_limit_config: RateLimitConfig,
         audit_config: AuditConfig,
         access_control_config: AccessControlConfig,
+        request_limits_config: RequestLimitsConfig,
+        order_signing_config: OrderSigningConfig,
+        signer_config: SignerConfig,
     ) -> Result<Self, String> {
         let config = Self {
             jwt: jwt_config,
@@ -86,53 +218,209 @@ impl SecurityConfig {
             rate_limit: rate_limit_config,
             audit: audit_config,
             access_control: access_control_config,
+            request_limits: request_limits_config,
+            order_signing: order_signing_config,
+            signer: signer_config,
         };
 
-        config.validate()?;
+        ensure_valid(config.validate())?;
         Ok(config)
     }
 
-    /// Validates the entire security configuration
+    /// Validates the entire security configuration, reporting every problem found
     #[instrument(skip(self))]
-    pub fn validate(&self) -> Result<(), String> {
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+
         // Validate JWT configuration
         if self.jwt.token_expiry <= 0 || self.jwt.refresh_expiry <= 0 {
-            return Err("Invalid JWT expiry settings".to_string());
+            issues.push(ConfigIssue::error(
+                "security",
+                "JWT_TOKEN_EXPIRY",
+                "token expiry settings must be positive",
+                "set JWT_TOKEN_EXPIRY and JWT_REFRESH_EXPIRY in seconds",
+            ));
+        }
+
+        let active_keys = self.jwt.active_keys();
+        if active_keys.is_empty() && self.jwt.jwks.is_none() {
+            issues.push(ConfigIssue::error(
+                "security",
+                "JWT_KEYS",
+                "no JWT signing keys configured",
+                "set JWT_KEYS (kid:secret,...) or JWT_SECRET_KEY",
+            ));
         }
 
-        if self.jwt.secret_key.len() < MIN_PASSWORD_LENGTH {
-            return Err("JWT secret key too short".to_string());
+        if active_keys.iter().any(|key| key.secret.len() < MIN_PASSWORD_LENGTH) {
+            issues.push(ConfigIssue::error(
+                "security",
+                "JWT_KEYS",
+                "JWT secret key too short",
+                format!("use secrets of at least {} characters", MIN_PASSWORD_LENGTH),
+            ));
+        }
+
+        let mut kids: Vec<&str> = active_keys.iter().map(|key| key.kid.as_str()).collect();
+        kids.sort_unstable();
+        kids.dedup();
+        if kids.len() != active_keys.len() {
+            issues.push(ConfigIssue::error(
+                "security",
+                "JWT_KEYS",
+                "duplicate JWT key ids",
+                "give every key a unique kid",
+            ));
+        }
+
+        if let Some(jwks) = &self.jwt.jwks {
+            if !jwks.url.starts_with("https://") {
+                issues.push(ConfigIssue::error(
+                    "security",
+                    "JWT_JWKS_URL",
+                    "JWKS URL must use https",
+                    "point JWT_JWKS_URL at the IdP's https JWKS endpoint",
+                ));
+            }
         }
 
         // Validate KMS configuration
         if self.kms.key_id.is_empty() {
-            return Err("KMS key ID is required".to_string());
+            issues.push(ConfigIssue::error(
+                "security",
+                "KMS_KEY_ID",
+                "KMS key ID is required",
+                "set KMS_KEY_ID to the key ARN or alias",
+            ));
         }
 
         if self.kms.rotation_period < KMS_KEY_ROTATION_DAYS {
-            return Err("KMS rotation period too short".to_string());
+            issues.push(ConfigIssue::error(
+                "security",
+                "KMS_ROTATION_PERIOD",
+                format!("rotation period of {} days is too short", self.kms.rotation_period),
+                format!("use at least {} days", KMS_KEY_ROTATION_DAYS),
+            ));
         }
 
         // Validate rate limiting
         if self.rate_limit.max_requests == 0 || self.rate_limit.window_size.as_secs() == 0 {
-            return Err("Invalid rate limiting configuration".to_string());
+            issues.push(ConfigIssue::error(
+                "security",
+                "RATE_LIMIT_MAX_REQUESTS",
+                "rate limit requests and window must be non-zero",
+                "set RATE_LIMIT_MAX_REQUESTS and RATE_LIMIT_WINDOW_SECS",
+            ));
         }
 
         if self.rate_limit.max_failed_attempts > MAX_FAILED_ATTEMPTS {
-            return Err("Max failed attempts too high".to_string());
+            issues.push(ConfigIssue::error(
+                "security",
+                "RATE_LIMIT_MAX_FAILED",
+                "max failed attempts too high",
+                format!("use at most {}", MAX_FAILED_ATTEMPTS),
+            ));
+        }
+
+        // Validate request body limits
+        if self.request_limits.trading_body_limit == 0
+            || self.request_limits.admin_body_limit == 0
+            || self.request_limits.body_read_timeout.is_zero()
+        {
+            issues.push(ConfigIssue::error(
+                "security",
+                "API_BODY_READ_TIMEOUT_MS",
+                "request body limits and read timeout must be non-zero",
+                "set API_TRADING_BODY_LIMIT, API_ADMIN_BODY_LIMIT and API_BODY_READ_TIMEOUT_MS",
+            ));
+        }
+
+        // Validate order signing
+        if self.order_signing.signature_threshold < Decimal::ZERO
+            || self.order_signing.max_signature_ttl.is_zero()
+        {
+            issues.push(ConfigIssue::error(
+                "security",
+                "ORDER_SIGNATURE_THRESHOLD",
+                "order signature threshold must be non-negative and max ttl non-zero",
+                "set ORDER_SIGNATURE_THRESHOLD and ORDER_SIGNATURE_MAX_TTL_SECS",
+            ));
+        }
+
+        // Validate the transaction signer
+        match &self.signer {
+            SignerConfig::Keypair { secret_key } if secret_key.is_empty() => {
+                issues.push(ConfigIssue::warning(
+                    "security",
+                    "WALLET_SECRET_KEY",
+                    "no wallet key configured; transactions cannot be signed",
+                    "set WALLET_SECRET_KEY, or SIGNER_BACKEND=kms or remote",
+                ));
+            }
+            SignerConfig::Keypair { .. } => {}
+            SignerConfig::Kms { key_id, .. } => {
+                if key_id.is_empty() {
+                    issues.push(ConfigIssue::error(
+                        "security",
+                        "SIGNER_KMS_KEY_ID",
+                        "KMS signer key ID is required",
+                        "set SIGNER_KMS_KEY_ID to an ECC_NIST_EDWARDS25519 key",
+                    ));
+                }
+            }
+            SignerConfig::Remote(remote) => {
+                if !remote.url.starts_with("https://") {
+                    issues.push(ConfigIssue::error(
+                        "security",
+                        "SIGNER_REMOTE_URL",
+                        "remote signer URL must use https",
+                        "point SIGNER_REMOTE_URL at the signer's https endpoint",
+                    ));
+                }
+                if remote.pubkey.is_empty()
+                    || remote.client_cert_path.is_empty()
+                    || remote.client_key_path.is_empty()
+                    || remote.ca_cert_path.is_empty()
+                {
+                    issues.push(ConfigIssue::error(
+                        "security",
+                        "SIGNER_REMOTE_CLIENT_CERT",
+                        "remote signer needs a pubkey, client certificate, client key and CA",
+                        "set SIGNER_PUBKEY, SIGNER_REMOTE_CLIENT_CERT, SIGNER_REMOTE_CLIENT_KEY and SIGNER_REMOTE_CA_CERT",
+                    ));
+                }
+                if remote.timeout.is_zero() {
+                    issues.push(ConfigIssue::error(
+                        "security",
+                        "SIGNER_TIMEOUT_MS",
+                        "remote signer timeout must be non-zero",
+                        "set SIGNER_TIMEOUT_MS",
+                    ));
+                }
+            }
         }
 
         // Validate audit configuration
         if self.audit.enabled && self.audit.retention_days == 0 {
-            return Err("Invalid audit log retention period".to_string());
+            issues.push(ConfigIssue::error(
+                "security",
+                "AUDIT_RETENTION_DAYS",
+                "audit log retention must be non-zero",
+                "set AUDIT_RETENTION_DAYS",
+            ));
         }
 
         // Validate access control
         if self.access_control.allowed_origins.is_empty() {
-            return Err("No allowed origins specified".to_string());
+            issues.push(ConfigIssue::error(
+                "security",
+                "ALLOWED_ORIGINS",
+                "no allowed origins specified",
+                "set ALLOWED_ORIGINS to a comma-separated list",
+            ));
         }
 
-        Ok(())
+        issues
     }
 }
 
@@ -141,22 +429,7 @@ impl SecurityConfig {
 pub async fn load_security_config() -> Result<SecurityConfig, String> {
     info!("Loading security configuration");
 
-    // Load JWT configuration
-    let jwt_config = JWTConfig {
-        secret_key: std::env::var("JWT_SECRET_KEY")
-            .map_err(|_| "JWT secret key not found")?,
-        token_expiry: std::env::var("JWT_TOKEN_EXPIRY")
-            .unwrap_or_else(|_| DEFAULT_JWT_EXPIRY.to_string())
-            .parse()
-            .map_err(|_| "Invalid JWT token expiry")?,
-        refresh_expiry: std::env::var("JWT_REFRESH_EXPIRY")
-            .unwrap_or_else(|_| DEFAULT_REFRESH_EXPIRY.to_string())
-            .parse()
-            .map_err(|_| "Invalid JWT refresh expiry")?,
-        algorithm: Algorithm::HS256,
-    };
-
-    // Load KMS configuration
+    // Load KMS configuration first so encrypted secrets can be decrypted
     let kms_config = KMSConfig {
         key_id: std::env::var("KMS_KEY_ID")
             .map_err(|_| "KMS key ID not found")?,
@@ -172,6 +445,61 @@ pub async fn load_security_config() -> Result<SecurityConfig, String> {
             .map_err(|_| "Invalid KMS rotation period")?,
     };
 
+    // Load JWT signing keys as "kid:secret" pairs, oldest first
+    let mut jwt_keys = Vec::new();
+    for entry in std::env::var("JWT_KEYS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+    {
+        let (kid, secret) = entry
+            .split_once(':')
+            .ok_or("Invalid JWT_KEYS entry, expected kid:secret")?;
+        jwt_keys.push(JwtKey {
+            kid: kid.to_string(),
+            secret: resolve_secret(secret.to_string(), &kms_config.key_id)
+                .await
+                .map_err(|e| format!("Failed to load JWT key {}: {}", kid, e))?,
+        });
+    }
+
+    // Load optional JWKS configuration
+    let jwks_config = match std::env::var("JWT_JWKS_URL") {
+        Ok(url) => Some(JwksConfig {
+            url,
+            cache_ttl_secs: std::env::var("JWT_JWKS_CACHE_TTL_SECS")
+                .unwrap_or_else(|_| DEFAULT_JWKS_CACHE_TTL_SECS.to_string())
+                .parse()
+                .map_err(|_| "Invalid JWKS cache TTL")?,
+            issuer: std::env::var("JWT_ISSUER").ok(),
+            audience: std::env::var("JWT_AUDIENCE").ok(),
+        }),
+        Err(_) => None,
+    };
+
+    // Load JWT configuration
+    let jwt_config = JWTConfig {
+        secret_key: match std::env::var("JWT_SECRET_KEY") {
+            Ok(secret) => resolve_secret(secret, &kms_config.key_id)
+                .await
+                .map_err(|e| format!("Failed to load JWT secret key: {}", e))?,
+            Err(_) if !jwt_keys.is_empty() || jwks_config.is_some() => Secret::default(),
+            Err(_) => return Err("JWT secret key not found".to_string()),
+        },
+        token_expiry: std::env::var("JWT_TOKEN_EXPIRY")
+            .unwrap_or_else(|_| DEFAULT_JWT_EXPIRY.to_string())
+            .parse()
+            .map_err(|_| "Invalid JWT token expiry")?,
+        refresh_expiry: std::env::var("JWT_REFRESH_EXPIRY")
+            .unwrap_or_else(|_| DEFAULT_REFRESH_EXPIRY.to_string())
+            .parse()
+            .map_err(|_| "Invalid JWT refresh expiry")?,
+        algorithm: Algorithm::HS256,
+        keys: jwt_keys,
+        jwks: jwks_config,
+    };
+
     // Load rate limiting configuration
     let rate_limit_config = RateLimitConfig {
         max_requests: std::env::var("RATE_LIMIT_MAX_REQUESTS")
@@ -190,6 +518,74 @@ pub async fn load_security_config() -> Result<SecurityConfig, String> {
             .map_err(|_| "Invalid max failed attempts")?,
     };
 
+    // Load request body limits
+    let request_limits_config = RequestLimitsConfig {
+        trading_body_limit: std::env::var("API_TRADING_BODY_LIMIT")
+            .unwrap_or_else(|_| DEFAULT_TRADING_BODY_LIMIT.to_string())
+            .parse()
+            .map_err(|_| "Invalid trading body limit")?,
+        admin_body_limit: std::env::var("API_ADMIN_BODY_LIMIT")
+            .unwrap_or_else(|_| DEFAULT_ADMIN_BODY_LIMIT.to_string())
+            .parse()
+            .map_err(|_| "Invalid admin body limit")?,
+        body_read_timeout: Duration::from_millis(
+            std::env::var("API_BODY_READ_TIMEOUT_MS")
+                .unwrap_or_else(|_| DEFAULT_BODY_READ_TIMEOUT_MS.to_string())
+                .parse()
+                .map_err(|_| "Invalid body read timeout")?,
+        ),
+    };
+
+    // Load high-value order signing requirements
+    let order_signing_config = OrderSigningConfig {
+        signature_threshold: std::env::var("ORDER_SIGNATURE_THRESHOLD")
+            .unwrap_or_else(|_| DEFAULT_ORDER_SIGNATURE_THRESHOLD.to_string())
+            .parse()
+            .map_err(|_| "Invalid order signature threshold")?,
+        max_signature_ttl: Duration::from_secs(
+            std::env::var("ORDER_SIGNATURE_MAX_TTL_SECS")
+                .unwrap_or_else(|_| DEFAULT_ORDER_SIGNATURE_MAX_TTL_SECS.to_string())
+                .parse()
+                .map_err(|_| "Invalid order signature max ttl")?,
+        ),
+    };
+
+    // Load the transaction signing backend
+    let signer_config = match std::env::var("SIGNER_BACKEND")
+        .unwrap_or_else(|_| "keypair".to_string())
+        .as_str()
+    {
+        "keypair" => SignerConfig::Keypair {
+            secret_key: match std::env::var("WALLET_SECRET_KEY") {
+                Ok(secret) => resolve_secret(secret, &kms_config.key_id)
+                    .await
+                    .map_err(|e| format!("Failed to load wallet secret key: {}", e))?,
+                Err(_) => Secret::default(),
+            },
+        },
+        "kms" => SignerConfig::Kms {
+            key_id: std::env::var("SIGNER_KMS_KEY_ID").unwrap_or_default(),
+            region: std::env::var("SIGNER_KMS_REGION").unwrap_or_else(|_| kms_config.region.clone()),
+        },
+        "remote" => SignerConfig::Remote(RemoteSignerConfig {
+            url: std::env::var("SIGNER_REMOTE_URL").unwrap_or_default(),
+            pubkey: std::env::var("SIGNER_PUBKEY").unwrap_or_default(),
+            client_cert_path: std::env::var("SIGNER_REMOTE_CLIENT_CERT").unwrap_or_default(),
+            client_key_path: std::env::var("SIGNER_REMOTE_CLIENT_KEY").unwrap_or_default(),
+            ca_cert_path: std::env::var("SIGNER_REMOTE_CA_CERT").unwrap_or_default(),
+            timeout: Duration::from_millis(
+                std::env::var("SIGNER_TIMEOUT_MS")
+                    .unwrap_or_else(|_| DEFAULT_REMOTE_SIGNER_TIMEOUT_MS.to_string())
+                    .parse()
+                    .map_err(|_| "Invalid signer timeout")?,
+            ),
+            retry_on_failure: std::env::var("SIGNER_RETRY_ON_FAILURE")
+                .map(|value| value == "true")
+                .unwrap_or(false),
+        }),
+        other => return Err(format!("Unknown signer backend {}, expected keypair, kms or remote", other)),
+    };
+
     // Load audit configuration
     let audit_config = AuditConfig {
         enabled: std::env::var("AUDIT_ENABLED")
@@ -227,6 +623,9 @@ pub async fn load_security_config() -> Result<SecurityConfig, String> {
         rate_limit_config,
         audit_config,
         access_control_config,
+        request_limits_config,
+        order_signing_config,
+        signer_config,
     )?;
 
     validate_security_config(&config).await?;
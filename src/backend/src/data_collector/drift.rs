@@ -7,6 +7,7 @@
 //! - tracing = "0.1"
 
 use std::{
+    str::FromStr,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -22,26 +23,68 @@ use tokio::{
     sync::{mpsc, RwLock},
     time::sleep,
 };
+use rust_decimal::Decimal;
+use serde::Deserialize;
 use tokio_tungstenite::tungstenite::Message;
 use tracing::{debug, error, info, instrument, warn};
 
 use crate::{
     data_collector::{
-        Collector, CollectorConfig, CollectorError, CollectorMetrics, ConnectionPool, HealthStatus,
+        trades::PublicTrade, BookUpdate, Collector, CollectorConfig, CollectorError,
+        CollectorMetrics, ConnectionPool, HealthStatus,
     },
-    models::market::{MarketData, validate_price, validate_volume},
+    execution_engine::book_sync::BookSequence,
+    models::market::{MarketData, OrderBook, OrderBookLevel, validate_price, validate_volume},
+    models::pair::TradingPair,
+    risk_manager::exposure::TradeSide,
+    utils::circuit_breaker::{BreakerConfig, CircuitBreaker},
+    utils::logger::LogSampler,
     utils::solana::SolanaClient,
 };
 
 // Constants from globals
-const DRIFT_WS_URL: &str = "wss://api.drift.trade/ws";
-const DRIFT_RPC_URL: &str = "https://api.drift.trade";
 const RECONNECT_DELAY_MS: u64 = 1000;
 const MAX_RECONNECT_ATTEMPTS: u32 = 5;
 const CONNECTION_POOL_SIZE: usize = 5;
 const MESSAGE_BATCH_SIZE: usize = 100;
 const HEALTH_CHECK_INTERVAL_MS: u64 = 5000;
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 3;
+const TRADES_CHANNEL: &str = "trades";
+const PERP_SUFFIX: &str = "-PERP";
+// Drift perps settle in USDC
+const PERP_QUOTE_ASSET: &str = "USDC";
+
+// Per-message debug events are sampled so enabling debug stays affordable
+static MARKET_UPDATE_LOG: LogSampler = LogSampler::new();
+static ORDER_BOOK_UPDATE_LOG: LogSampler = LogSampler::new();
+
+/// Message from the DLOB trades channel
+#[derive(Debug, Deserialize)]
+struct FillEnvelope {
+    channel: String,
+    data: FillRecord,
+}
+
+/// Taker fill as published on the trades channel
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FillRecord {
+    /// Perp market name, e.g. `SOL-PERP`
+    market: String,
+    fill_record_id: String,
+    base_asset_amount_filled: String,
+    quote_asset_amount_filled: String,
+    /// `long` or `short`
+    taker_order_direction: String,
+    /// Fill time in unix seconds
+    ts: i64,
+}
+
+/// Maps a perp market name to the bot's BASE/QUOTE pair form
+fn perp_pair(market: &str) -> String {
+    let base = market.strip_suffix(PERP_SUFFIX).unwrap_or(market);
+    format!("{}/{}", base, PERP_QUOTE_ASSET)
+}
 
 /// High-performance Drift Protocol data collector
 #[derive(Debug)]
@@ -50,8 +93,14 @@ pub struct DriftCollector {
     solana_client: Arc<SolanaClient>,
     drift_client: Arc<DriftWsClient>,
     metrics: Arc<RwLock<CollectorMetrics>>,
-    circuit_breaker: Arc<RwLock<u32>>,
+    circuit_breaker: Arc<CircuitBreaker>,
     is_running: AtomicBool,
+    /// Receives normalized fills when the trades channel is enabled
+    trades_tx: Option<mpsc::Sender<PublicTrade>>,
+    /// Receives validated market updates when the bot consumes collector data
+    market_data_tx: Option<mpsc::Sender<MarketData>>,
+    /// Receives depth updates sequenced by slot when the bot maintains live books
+    book_updates_tx: Option<mpsc::Sender<BookUpdate>>,
     config: CollectorConfig,
 }
 
@@ -60,7 +109,7 @@ impl DriftCollector {
     pub fn new(solana_client: Arc<SolanaClient>, config: CollectorConfig) -> Result<Self, CollectorError> {
         let ws_pool = Arc::new(ConnectionPool::new(config.connection_pool_size));
         
-        let drift_client = DriftWsClient::new(DRIFT_WS_URL)
+        let drift_client = DriftWsClient::new(&config.endpoints.drift_ws_url)
             .map_err(|e| CollectorError::ConnectionError(format!("Failed to create Drift client: {}", e)))?;
 
         Ok(Self {
@@ -68,12 +117,114 @@ impl DriftCollector {
             solana_client,
             drift_client: Arc::new(drift_client),
             metrics: Arc::new(RwLock::new(CollectorMetrics::default())),
-            circuit_breaker: Arc::new(RwLock::new(0)),
+            circuit_breaker: CircuitBreaker::new(
+                "drift_collector",
+                BreakerConfig::consecutive_failures(CIRCUIT_BREAKER_THRESHOLD),
+            )
+            .registered(),
             is_running: AtomicBool::new(false),
+            trades_tx: config.public_trades_tx.clone(),
+            market_data_tx: config.market_data_tx.clone(),
+            book_updates_tx: config.book_updates_tx.clone(),
             config,
         })
     }
 
+    /// Normalizes a taker fill into a public trade keyed by its fill record id
+    fn handle_fill(&self, fill: FillRecord) -> Result<PublicTrade, CollectorError> {
+        let parse = |amount: &str| {
+            Decimal::from_str(amount).map_err(|e| CollectorError::DataValidationError(e.to_string()))
+        };
+        let base = parse(&fill.base_asset_amount_filled)?;
+        let quote = parse(&fill.quote_asset_amount_filled)?;
+        if base <= Decimal::ZERO {
+            return Err(CollectorError::DataValidationError(
+                "fill has no base amount".to_string(),
+            ));
+        }
+
+        let side = match fill.taker_order_direction.as_str() {
+            "long" => TradeSide::Buy,
+            "short" => TradeSide::Sell,
+            other => {
+                return Err(CollectorError::DataValidationError(format!(
+                    "unknown taker direction: {}",
+                    other
+                )))
+            }
+        };
+        let timestamp = chrono::DateTime::from_timestamp(fill.ts, 0).ok_or_else(|| {
+            CollectorError::DataValidationError(format!("invalid fill time: {}", fill.ts))
+        })?;
+
+        let trade = PublicTrade::new(
+            perp_pair(&fill.market),
+            "drift".to_string(),
+            quote / base,
+            base,
+            side,
+            timestamp,
+        )
+        .map_err(|e| CollectorError::DataValidationError(e.to_string()))?;
+
+        // Fill record ids are sequential per market
+        Ok(trade.with_trade_id(format!("{}-{}", fill.market, fill.fill_record_id)))
+    }
+
+    /// Sends a trades channel fill on to the trade stream, dropping malformed fills
+    async fn forward_fill(&self, fill: FillRecord) {
+        let Some(trades_tx) = &self.trades_tx else {
+            return;
+        };
+        match self.handle_fill(fill) {
+            Ok(trade) => {
+                if trades_tx.send(trade).await.is_err() {
+                    warn!("Public trade channel closed");
+                }
+            }
+            Err(e) => warn!("Failed to process fill: {}", e),
+        }
+    }
+
+    /// Sends a validated market update on to the market data stream
+    async fn forward_market_data(&self, market_data: MarketData) {
+        if let Some(market_data_tx) = &self.market_data_tx {
+            if market_data_tx.send(market_data).await.is_err() {
+                warn!("Market data channel closed");
+            }
+        }
+    }
+
+    /// Sends a depth update on to the book stream, sequenced by the slot it was published at
+    async fn forward_book_update(&self, message: OrderBookUpdateMessage) {
+        let Some(book_updates_tx) = &self.book_updates_tx else {
+            return;
+        };
+        let slot = message.slot;
+        let book = self.handle_orderbook_update(message).await.and_then(|data| {
+            OrderBook::new(
+                data.market_name,
+                "drift".to_string(),
+                data.bids.iter().map(|level| OrderBookLevel::new(level.price, level.size)).collect(),
+                data.asks.iter().map(|level| OrderBookLevel::new(level.price, level.size)).collect(),
+            )
+            .map_err(|e| CollectorError::DataValidationError(e.to_string()))
+        });
+
+        match book {
+            Ok(book) => {
+                let update = BookUpdate {
+                    book,
+                    sequence: BookSequence { sequence: slot, checksum: None },
+                };
+                if book_updates_tx.send(update).await.is_err() {
+                    warn!("Order book update channel closed");
+                }
+            }
+            Err(e) => warn!("Failed to process order book update: {}", e),
+        }
+    }
+
     /// Handles market data updates with performance optimization
     #[instrument(skip(message))]
     async fn handle_market_update(
@@ -115,10 +266,13 @@ impl DriftCollector {
             );
         }
 
-        debug!(
-            "Processed market update in {:?}: {} @ {}",
-            processing_time, volume, price
-        );
+        if let Some(sampled) = MARKET_UPDATE_LOG.sample() {
+            debug!(
+                sampled,
+                "Processed market update in {:?}: {} @ {}",
+                processing_time, volume, price
+            );
+        }
 
         Ok(market_data)
     }
@@ -158,12 +312,15 @@ impl DriftCollector {
             }
         }
 
-        debug!(
-            "Processed order book update in {:?}: {} bids, {} asks",
-            start.elapsed(),
-            order_book.bids.len(),
-            order_book.asks.len()
-        );
+        if let Some(sampled) = ORDER_BOOK_UPDATE_LOG.sample() {
+            debug!(
+                sampled,
+                "Processed order book update in {:?}: {} bids, {} asks",
+                start.elapsed(),
+                order_book.bids.len(),
+                order_book.asks.len()
+            );
+        }
 
         Ok(order_book)
     }
@@ -200,17 +357,32 @@ impl Collector for DriftCollector {
                 };
 
                 match message {
+                    Message::Text(_) if super::drop_injected_message() => {
+                        debug!("Dropped market update via injected fault");
+                    }
                     Message::Text(text) => {
-                        if let Ok(market_update) = serde_json::from_str::<MarketUpdateMessage>(&text) {
+                        if let Some(envelope) = serde_json::from_str::<FillEnvelope>(&text)
+                            .ok()
+                            .filter(|envelope| envelope.channel.starts_with(TRADES_CHANNEL))
+                        {
+                            self.forward_fill(envelope.data).await;
+                        } else if let Ok(book_update) = serde_json::from_str::<OrderBookUpdateMessage>(&text) {
+                            self.forward_book_update(book_update).await;
+                        } else if let Ok(market_update) = serde_json::from_str::<MarketUpdateMessage>(&text) {
                             // Process market update
                             let start = Instant::now();
-                            if let Err(e) = self.handle_market_update(market_update).await {
-                                error!("Failed to process market update: {}", e);
-                                let mut cb = circuit_breaker.write().await;
-                                *cb += 1;
-                                if *cb >= CIRCUIT_BREAKER_THRESHOLD {
-                                    error!("Circuit breaker triggered");
-                                    break;
+                            match self.handle_market_update(market_update).await {
+                                Ok(market_data) => {
+                                    circuit_breaker.record_success();
+                                    metrics.write().await.last_collection_time = Some(chrono::Utc::now());
+                                    self.forward_market_data(market_data).await;
+                                }
+                                Err(e) => {
+                                    error!("Failed to process market update: {}", e);
+                                    if circuit_breaker.record_failure() {
+                                        error!("Circuit breaker triggered");
+                                        break;
+                                    }
                                 }
                             }
                             metrics.write().await.average_latency = start.elapsed();
@@ -269,16 +441,31 @@ impl Collector for DriftCollector {
     /// Performs health check of collector and its connections
     async fn health_check(&self) -> Result<HealthStatus, CollectorError> {
         let metrics = self.metrics.read().await;
-        let circuit_breaker = self.circuit_breaker.read().await;
 
         Ok(HealthStatus {
-            is_healthy: *circuit_breaker < CIRCUIT_BREAKER_THRESHOLD,
+            is_healthy: !self.circuit_breaker.is_open(),
             connection_count: self.config.connection_pool_size,
             last_collection_latency: metrics.average_latency,
+            last_collection_time: metrics.last_collection_time,
             error_count: metrics.connection_errors,
             last_error: None,
         })
     }
+
+    /// Subscribes to the trades channel of each pair's perp market when fills are enabled
+    async fn subscribe(&self, trading_pairs: &[TradingPair]) -> Result<(), CollectorError> {
+        if self.trades_tx.is_none() {
+            return Ok(());
+        }
+        for pair in trading_pairs {
+            let market = format!("{}{}", pair.base(), PERP_SUFFIX);
+            self.drift_client
+                .subscribe_trades(&market)
+                .await
+                .map_err(|e| CollectorError::ConnectionError(format!("Failed to subscribe to {} trades: {}", market, e)))?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -317,4 +504,41 @@ mod tests {
         let result = collector.handle_market_update(message).await;
         assert!(result.is_ok());
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_fill_normalization() {
+        let solana_client = Arc::new(
+            SolanaClient::new("http://localhost:8899".to_string(), None, None)
+                .await
+                .unwrap(),
+        );
+        let collector = DriftCollector::new(solana_client, CollectorConfig::default()).unwrap();
+
+        let message = serde_json::json!({
+            "channel": "trades",
+            "data": {
+                "market": "SOL-PERP",
+                "fillRecordId": "88120",
+                "baseAssetAmountFilled": "4",
+                "quoteAssetAmountFilled": "94.2",
+                "takerOrderDirection": "short",
+                "ts": chrono::Utc::now().timestamp()
+            }
+        });
+        let envelope: FillEnvelope = serde_json::from_value(message).unwrap();
+        let fill = envelope.data.clone();
+        let trade = collector.handle_fill(envelope.data).unwrap();
+
+        assert_eq!(trade.pair, "SOL/USDC");
+        assert_eq!(trade.price, dec!(23.55));
+        assert_eq!(trade.size, dec!(4));
+        assert_eq!(trade.side, TradeSide::Sell);
+        assert_eq!(trade.dedup_key(), "drift:SOL-PERP-88120");
+
+        let empty = FillRecord {
+            base_asset_amount_filled: "0".to_string(),
+            ..fill
+        };
+        assert!(collector.handle_fill(empty).is_err());
+    }
+}
@@ -0,0 +1,761 @@
+//! Market data gap detection and backfill. Recent ticks for every (pair, exchange) source
+//! are scanned for intervals longer than a multiple of the expected collection cadence;
+//! each gap is recorded and filled, where the venue still has the history, from its REST
+//! API. Backfilled rows are flagged so analytics can tell them apart from live data, and
+//! backfill progress is persisted so an interrupted run resumes where it stopped.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - reqwest = "0.11"
+//! - rust_decimal = "1.30"
+//! - utoipa = "3.5"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use metrics::{counter, gauge};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::execution_engine::benchmarks::MarketTick;
+use crate::utils::http::{self, HttpClient};
+
+// Gap detection constants
+const METRICS_PREFIX: &str = "trading_bot.data_gaps";
+const DEFAULT_CADENCE: Duration = Duration::from_secs(1);
+const DEFAULT_GAP_MULTIPLIER: u32 = 10;
+const DEFAULT_SCAN_LOOKBACK: Duration = Duration::from_secs(6 * 3600);
+const DEFAULT_SCAN_INTERVAL: Duration = Duration::from_secs(300);
+const DEFAULT_MIN_REQUEST_INTERVAL: Duration = Duration::from_millis(250);
+const DEFAULT_MAX_PAGES_PER_RUN: usize = 20;
+const DEFAULT_STATUS_WINDOW: Duration = Duration::from_secs(24 * 3600);
+const BACKFILL_FETCH_TIMEOUT_MS: u64 = 5000;
+
+/// Gap detection and backfill error types
+#[derive(Error, Debug)]
+pub enum GapError {
+    #[error("gap store error: {0}")]
+    Store(String),
+    #[error("backfill source error: {0}")]
+    Source(String),
+}
+
+/// Detection thresholds and backfill pacing
+#[derive(Debug, Clone, PartialEq)]
+pub struct GapConfig {
+    /// Expected interval between ticks from one source
+    pub cadence: Duration,
+    /// Intervals longer than this many cadences count as a gap
+    pub gap_multiplier: u32,
+    /// How far back each scan looks
+    pub scan_lookback: Duration,
+    pub scan_interval: Duration,
+    /// Minimum spacing between REST requests to one venue
+    pub min_request_interval: Duration,
+    /// Pages fetched per gap in one run; the rest resumes on the next run
+    pub max_pages_per_run: usize,
+    /// How far back the status endpoint reports gaps
+    pub status_window: Duration,
+}
+
+impl Default for GapConfig {
+    fn default() -> Self {
+        Self {
+            cadence: DEFAULT_CADENCE,
+            gap_multiplier: DEFAULT_GAP_MULTIPLIER,
+            scan_lookback: DEFAULT_SCAN_LOOKBACK,
+            scan_interval: DEFAULT_SCAN_INTERVAL,
+            min_request_interval: DEFAULT_MIN_REQUEST_INTERVAL,
+            max_pages_per_run: DEFAULT_MAX_PAGES_PER_RUN,
+            status_window: DEFAULT_STATUS_WINDOW,
+        }
+    }
+}
+
+impl GapConfig {
+    /// Longest interval between ticks that is not a gap
+    pub fn threshold(&self) -> chrono::Duration {
+        chrono::Duration::from_std(self.cadence * self.gap_multiplier.max(1))
+            .unwrap_or_else(|_| chrono::Duration::max_value())
+    }
+}
+
+/// Where a stored tick came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TickSource {
+    Live,
+    Backfill,
+}
+
+impl TickSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TickSource::Live => "live",
+            TickSource::Backfill => "backfill",
+        }
+    }
+}
+
+/// Backfill progress of a gap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GapStatus {
+    Open,
+    /// Partially backfilled; resumes from the cursor
+    Backfilling,
+    Filled,
+    /// The venue had no history for part of the gap, or no backfill source covers it
+    Unfillable,
+}
+
+impl GapStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GapStatus::Open => "open",
+            GapStatus::Backfilling => "backfilling",
+            GapStatus::Filled => "filled",
+            GapStatus::Unfillable => "unfillable",
+        }
+    }
+
+    pub fn parse(status: &str) -> Result<Self, GapError> {
+        match status {
+            "open" => Ok(GapStatus::Open),
+            "backfilling" => Ok(GapStatus::Backfilling),
+            "filled" => Ok(GapStatus::Filled),
+            "unfillable" => Ok(GapStatus::Unfillable),
+            other => Err(GapError::Store(format!("unknown gap status: {}", other))),
+        }
+    }
+
+    fn is_pending(&self) -> bool {
+        matches!(self, GapStatus::Open | GapStatus::Backfilling)
+    }
+}
+
+/// A hole in one source's stored market data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DataGap {
+    pub id: Uuid,
+    pub trading_pair: String,
+    pub exchange: String,
+    /// Last tick before the gap
+    pub gap_start: DateTime<Utc>,
+    /// First tick after the gap
+    pub gap_end: DateTime<Utc>,
+    pub status: GapStatus,
+    /// Latest backfilled tick; backfill resumes after it
+    pub backfill_cursor: Option<DateTime<Utc>>,
+    pub backfilled_rows: u64,
+    pub last_error: Option<String>,
+    pub detected_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl DataGap {
+    fn new(trading_pair: &str, exchange: &str, gap_start: DateTime<Utc>, gap_end: DateTime<Utc>, now: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            trading_pair: trading_pair.to_string(),
+            exchange: exchange.to_string(),
+            gap_start,
+            gap_end,
+            status: GapStatus::Open,
+            backfill_cursor: None,
+            backfilled_rows: 0,
+            last_error: None,
+            detected_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn overlaps(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.gap_start < end && start < self.gap_end
+    }
+}
+
+/// Intervals between consecutive ticks longer than the threshold; timestamps must be sorted
+pub fn find_gaps(timestamps: &[DateTime<Utc>], threshold: chrono::Duration) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    timestamps
+        .windows(2)
+        .filter(|pair| pair[1] - pair[0] > threshold)
+        .map(|pair| (pair[0], pair[1]))
+        .collect()
+}
+
+/// Persistence for stored tick times, gap records and backfilled rows
+#[async_trait]
+pub trait GapStore: Send + Sync {
+    /// Sources with ticks since the given time
+    async fn sources(&self, since: DateTime<Utc>) -> Result<Vec<(String, String)>, GapError>;
+
+    /// Sorted tick timestamps for a source within a range
+    async fn tick_times(
+        &self,
+        trading_pair: &str,
+        exchange: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<DateTime<Utc>>, GapError>;
+
+    /// Gaps detected since the given time, newest first
+    async fn gaps(&self, since: DateTime<Utc>) -> Result<Vec<DataGap>, GapError>;
+
+    /// Inserts a new gap or updates an existing one by id
+    async fn save_gap(&self, gap: &DataGap) -> Result<(), GapError>;
+
+    /// Stores backfilled ticks flagged as backfill, returning how many were written
+    async fn insert_backfill(&self, trading_pair: &str, exchange: &str, ticks: &[MarketTick]) -> Result<u64, GapError>;
+}
+
+/// Venue REST history used to fill gaps
+#[async_trait]
+pub trait BackfillSource: Send + Sync {
+    fn exchange(&self) -> &str;
+
+    /// Historical ticks after `from` up to `to`, oldest first; may return only the first page
+    async fn fetch(&self, trading_pair: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<MarketTick>, GapError>;
+}
+
+/// Detects gaps in stored market data and backfills them from venue history
+pub struct GapMonitor {
+    config: GapConfig,
+    store: Arc<dyn GapStore>,
+    sources: HashMap<String, Arc<dyn BackfillSource>>,
+    last_request: Mutex<HashMap<String, Instant>>,
+}
+
+impl std::fmt::Debug for GapMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GapMonitor")
+            .field("config", &self.config)
+            .field("sources", &self.sources.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl GapMonitor {
+    pub fn new(config: GapConfig, store: Arc<dyn GapStore>) -> Self {
+        Self {
+            config,
+            store,
+            sources: HashMap::new(),
+            last_request: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Backfills gaps on the source's exchange from its REST history
+    pub fn with_backfill_source(mut self, source: Arc<dyn BackfillSource>) -> Self {
+        self.sources.insert(source.exchange().to_string(), source);
+        self
+    }
+
+    /// Gaps detected within the status window, newest first
+    pub async fn status(&self, now: DateTime<Utc>) -> Result<Vec<DataGap>, GapError> {
+        let since = now - chrono::Duration::from_std(self.config.status_window).unwrap_or_else(|_| chrono::Duration::days(1));
+        self.store.gaps(since).await
+    }
+
+    /// Scans recent data of every source and records gaps not already known
+    pub async fn detect(&self, now: DateTime<Utc>) -> Result<Vec<DataGap>, GapError> {
+        let since = now - chrono::Duration::from_std(self.config.scan_lookback).unwrap_or_else(|_| chrono::Duration::hours(6));
+        let threshold = self.config.threshold();
+        let known = self.store.gaps(since - threshold).await?;
+
+        let mut detected = Vec::new();
+        for (trading_pair, exchange) in self.store.sources(since).await? {
+            let timestamps = self.store.tick_times(&trading_pair, &exchange, since, now).await?;
+            for (start, end) in find_gaps(&timestamps, threshold) {
+                let already_known = known.iter().any(|gap| {
+                    gap.trading_pair == trading_pair && gap.exchange == exchange && gap.overlaps(start, end)
+                });
+                if already_known {
+                    continue;
+                }
+                let gap = DataGap::new(&trading_pair, &exchange, start, end, now);
+                self.store.save_gap(&gap).await?;
+                counter!(format!("{}.detected", METRICS_PREFIX), 1, "exchange" => exchange.clone());
+                info!(
+                    trading_pair = %trading_pair,
+                    exchange = %exchange,
+                    gap_start = %start,
+                    gap_end = %end,
+                    "Market data gap detected"
+                );
+                detected.push(gap);
+            }
+        }
+        Ok(detected)
+    }
+
+    /// Works through pending gaps, resuming partially backfilled ones from their cursor.
+    /// Returns the gaps whose status changed.
+    pub async fn backfill(&self, now: DateTime<Utc>) -> Result<Vec<DataGap>, GapError> {
+        let since = now - chrono::Duration::from_std(self.config.scan_lookback).unwrap_or_else(|_| chrono::Duration::hours(6));
+        let pending: Vec<DataGap> = self
+            .store
+            .gaps(since)
+            .await?
+            .into_iter()
+            .filter(|gap| gap.status.is_pending())
+            .collect();
+        gauge!(format!("{}.pending", METRICS_PREFIX), pending.len() as f64);
+
+        let mut updated = Vec::new();
+        for mut gap in pending {
+            let previous = gap.status;
+            match self.sources.get(&gap.exchange) {
+                Some(source) => self.backfill_gap(source.as_ref(), &mut gap).await?,
+                None => {
+                    gap.status = GapStatus::Unfillable;
+                    gap.last_error = Some(format!("no backfill source for {}", gap.exchange));
+                }
+            }
+            gap.updated_at = Utc::now();
+            self.store.save_gap(&gap).await?;
+            if gap.status != previous {
+                counter!(
+                    format!("{}.{}", METRICS_PREFIX, gap.status.as_str()),
+                    1,
+                    "exchange" => gap.exchange.clone()
+                );
+                updated.push(gap);
+            }
+        }
+        Ok(updated)
+    }
+
+    /// Fetches pages of history into a gap, persisting the cursor after every page
+    async fn backfill_gap(&self, source: &dyn BackfillSource, gap: &mut DataGap) -> Result<(), GapError> {
+        gap.status = GapStatus::Backfilling;
+        for _ in 0..self.config.max_pages_per_run.max(1) {
+            let cursor = gap.backfill_cursor.unwrap_or(gap.gap_start);
+            self.pace(source.exchange()).await;
+            let ticks = match source.fetch(&gap.trading_pair, cursor, gap.gap_end).await {
+                Ok(ticks) => ticks,
+                Err(e) => {
+                    warn!(gap_id = %gap.id, exchange = %gap.exchange, "Backfill fetch failed: {}", e);
+                    gap.last_error = Some(e.to_string());
+                    return Ok(());
+                }
+            };
+            let ticks: Vec<MarketTick> = ticks
+                .into_iter()
+                .filter(|tick| tick.timestamp > cursor && tick.timestamp < gap.gap_end)
+                .collect();
+            let Some(last) = ticks.last().map(|tick| tick.timestamp) else {
+                return self.finish(gap).await;
+            };
+
+            gap.backfilled_rows += self.store.insert_backfill(&gap.trading_pair, &gap.exchange, &ticks).await?;
+            gap.backfill_cursor = Some(last);
+            gap.last_error = None;
+            gap.updated_at = Utc::now();
+            self.store.save_gap(gap).await?;
+            debug!(gap_id = %gap.id, rows = ticks.len(), "Backfilled page");
+        }
+        Ok(())
+    }
+
+    /// Closes a gap the venue has no more history for, checking what the backfill covered
+    async fn finish(&self, gap: &mut DataGap) -> Result<(), GapError> {
+        let mut timestamps = self
+            .store
+            .tick_times(&gap.trading_pair, &gap.exchange, gap.gap_start, gap.gap_end)
+            .await?;
+        timestamps.sort();
+        gap.status = if find_gaps(&timestamps, self.config.threshold()).is_empty() {
+            GapStatus::Filled
+        } else {
+            GapStatus::Unfillable
+        };
+        info!(
+            gap_id = %gap.id,
+            status = gap.status.as_str(),
+            rows = gap.backfilled_rows,
+            "Market data gap backfill finished"
+        );
+        Ok(())
+    }
+
+    /// Spaces REST requests to one venue by the configured interval
+    async fn pace(&self, exchange: &str) {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(at) = last_request.get(exchange) {
+            let elapsed = at.elapsed();
+            if elapsed < self.config.min_request_interval {
+                tokio::time::sleep(self.config.min_request_interval - elapsed).await;
+            }
+        }
+        last_request.insert(exchange.to_string(), Instant::now());
+    }
+
+    /// Periodically detects and backfills gaps
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.scan_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.detect(Utc::now()).await {
+                    warn!("Market data gap detection failed: {}", e);
+                }
+                if let Err(e) = self.backfill(Utc::now()).await {
+                    warn!("Market data backfill failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+fn source_error(e: impl std::fmt::Display) -> GapError {
+    GapError::Source(e.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+struct JupiterHistory {
+    data: JupiterHistoryData,
+}
+
+#[derive(Debug, Deserialize)]
+struct JupiterHistoryData {
+    items: Vec<JupiterPricePoint>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JupiterPricePoint {
+    unix_time: i64,
+    value: Decimal,
+}
+
+/// Jupiter price history; points carry no volume and are stored with zero volume
+#[derive(Debug)]
+pub struct JupiterHistorySource {
+    base_url: String,
+    http: Arc<HttpClient>,
+}
+
+impl JupiterHistorySource {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            http: http::shared(),
+        }
+    }
+
+    pub fn with_http(mut self, http: Arc<HttpClient>) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+#[async_trait]
+impl BackfillSource for JupiterHistorySource {
+    fn exchange(&self) -> &str {
+        "jupiter"
+    }
+
+    async fn fetch(&self, trading_pair: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<MarketTick>, GapError> {
+        let (base, quote) = trading_pair
+            .split_once('/')
+            .ok_or_else(|| GapError::Source(format!("invalid trading pair: {}", trading_pair)))?;
+        let query = [
+            ("id", base.to_string()),
+            ("vsToken", quote.to_string()),
+            ("from", from.timestamp().to_string()),
+            ("to", to.timestamp().to_string()),
+        ];
+        let history: JupiterHistory = self
+            .http
+            .get(
+                &format!("{}/history", self.base_url.trim_end_matches('/')),
+                Some(Duration::from_millis(BACKFILL_FETCH_TIMEOUT_MS)),
+                |request| request.query(&query),
+            )
+            .await
+            .map_err(source_error)?
+            .json()
+            .await
+            .map_err(source_error)?;
+
+        Ok(history
+            .data
+            .items
+            .into_iter()
+            .filter_map(|point| {
+                Some(MarketTick {
+                    price: point.value,
+                    volume: Decimal::ZERO,
+                    timestamp: Utc.timestamp_opt(point.unix_time, 0).single()?,
+                })
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DriftTrades {
+    trades: Vec<DriftTrade>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DriftTrade {
+    ts: i64,
+    price: Decimal,
+    base_asset_amount_filled: Decimal,
+}
+
+/// Drift trade history
+#[derive(Debug)]
+pub struct DriftTradeHistorySource {
+    base_url: String,
+    http: Arc<HttpClient>,
+}
+
+impl DriftTradeHistorySource {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            http: http::shared(),
+        }
+    }
+
+    pub fn with_http(mut self, http: Arc<HttpClient>) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+#[async_trait]
+impl BackfillSource for DriftTradeHistorySource {
+    fn exchange(&self) -> &str {
+        "drift"
+    }
+
+    async fn fetch(&self, trading_pair: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<MarketTick>, GapError> {
+        let query = [
+            ("marketName", trading_pair.to_string()),
+            ("startTs", from.timestamp().to_string()),
+            ("endTs", to.timestamp().to_string()),
+        ];
+        let history: DriftTrades = self
+            .http
+            .get(
+                &format!("{}/trades", self.base_url.trim_end_matches('/')),
+                Some(Duration::from_millis(BACKFILL_FETCH_TIMEOUT_MS)),
+                |request| request.query(&query),
+            )
+            .await
+            .map_err(source_error)?
+            .json()
+            .await
+            .map_err(source_error)?;
+
+        let mut ticks: Vec<MarketTick> = history
+            .trades
+            .into_iter()
+            .filter(|trade| trade.base_asset_amount_filled > Decimal::ZERO)
+            .filter_map(|trade| {
+                Some(MarketTick {
+                    price: trade.price,
+                    volume: trade.base_asset_amount_filled,
+                    timestamp: Utc.timestamp_opt(trade.ts, 0).single()?,
+                })
+            })
+            .collect();
+        ticks.sort_by_key(|tick| tick.timestamp);
+        Ok(ticks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex as SyncMutex;
+    use rust_decimal_macros::dec;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const PAIR: &str = "SOL/USDC";
+
+    #[derive(Default)]
+    struct MemoryStore {
+        ticks: SyncMutex<Vec<(MarketTick, TickSource)>>,
+        gaps: SyncMutex<Vec<DataGap>>,
+    }
+
+    #[async_trait]
+    impl GapStore for MemoryStore {
+        async fn sources(&self, _since: DateTime<Utc>) -> Result<Vec<(String, String)>, GapError> {
+            Ok(vec![(PAIR.to_string(), "drift".to_string())])
+        }
+
+        async fn tick_times(
+            &self,
+            _trading_pair: &str,
+            _exchange: &str,
+            from: DateTime<Utc>,
+            to: DateTime<Utc>,
+        ) -> Result<Vec<DateTime<Utc>>, GapError> {
+            let mut times: Vec<DateTime<Utc>> = self
+                .ticks
+                .lock()
+                .iter()
+                .map(|(tick, _)| tick.timestamp)
+                .filter(|at| *at >= from && *at <= to)
+                .collect();
+            times.sort();
+            Ok(times)
+        }
+
+        async fn gaps(&self, since: DateTime<Utc>) -> Result<Vec<DataGap>, GapError> {
+            Ok(self.gaps.lock().iter().filter(|gap| gap.gap_end >= since).cloned().collect())
+        }
+
+        async fn save_gap(&self, gap: &DataGap) -> Result<(), GapError> {
+            let mut gaps = self.gaps.lock();
+            match gaps.iter_mut().find(|existing| existing.id == gap.id) {
+                Some(existing) => *existing = gap.clone(),
+                None => gaps.push(gap.clone()),
+            }
+            Ok(())
+        }
+
+        async fn insert_backfill(&self, _trading_pair: &str, _exchange: &str, ticks: &[MarketTick]) -> Result<u64, GapError> {
+            let mut stored = self.ticks.lock();
+            stored.extend(ticks.iter().cloned().map(|tick| (tick, TickSource::Backfill)));
+            Ok(ticks.len() as u64)
+        }
+    }
+
+    /// Drift history with one tick per second, served ten at a time; fails once when asked
+    struct MockHistory {
+        start: DateTime<Utc>,
+        fail_on_call: Option<usize>,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl BackfillSource for MockHistory {
+        fn exchange(&self) -> &str {
+            "drift"
+        }
+
+        async fn fetch(&self, _trading_pair: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<MarketTick>, GapError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.fail_on_call == Some(call) {
+                return Err(GapError::Source("429 too many requests".to_string()));
+            }
+            Ok((0..3600)
+                .map(|i| tick(self.start, i))
+                .filter(|tick| tick.timestamp > from && tick.timestamp <= to)
+                .take(10)
+                .collect())
+        }
+    }
+
+    fn tick(start: DateTime<Utc>, second: i64) -> MarketTick {
+        MarketTick {
+            price: dec!(100),
+            volume: dec!(1),
+            timestamp: start + chrono::Duration::seconds(second),
+        }
+    }
+
+    fn config() -> GapConfig {
+        GapConfig {
+            min_request_interval: Duration::from_millis(1),
+            max_pages_per_run: 3,
+            ..GapConfig::default()
+        }
+    }
+
+    /// Ten minutes of live ticks with seconds 120..180 deleted
+    fn store_with_outage(start: DateTime<Utc>) -> Arc<MemoryStore> {
+        let store = Arc::new(MemoryStore::default());
+        store.ticks.lock().extend(
+            (0..600)
+                .filter(|second| !(120..180).contains(second))
+                .map(|second| (tick(start, second), TickSource::Live)),
+        );
+        store
+    }
+
+    #[test]
+    fn test_find_gaps_uses_threshold() {
+        let start = Utc::now();
+        let times: Vec<DateTime<Utc>> = [0, 1, 2, 15, 16, 26]
+            .iter()
+            .map(|second| start + chrono::Duration::seconds(*second))
+            .collect();
+        let gaps = find_gaps(&times, config().threshold());
+        assert_eq!(gaps, vec![(times[2], times[3])]);
+    }
+
+    #[tokio::test]
+    async fn test_deleted_window_is_detected_and_backfilled() {
+        let now = Utc::now();
+        let start = now - chrono::Duration::minutes(10);
+        let store = store_with_outage(start);
+        let history = Arc::new(MockHistory { start, fail_on_call: Some(1), calls: AtomicUsize::new(0) });
+        let monitor = GapMonitor::new(config(), store.clone()).with_backfill_source(history);
+
+        let detected = monitor.detect(now).await.unwrap();
+        assert_eq!(detected.len(), 1);
+        assert_eq!(detected[0].gap_start, start + chrono::Duration::seconds(119));
+        assert_eq!(detected[0].gap_end, start + chrono::Duration::seconds(180));
+        // Detection is idempotent
+        assert!(monitor.detect(now).await.unwrap().is_empty());
+
+        // The second page hits a rate limit; the gap keeps its progress
+        monitor.backfill(now).await.unwrap();
+        let gap = store.gaps.lock()[0].clone();
+        assert_eq!(gap.status, GapStatus::Backfilling);
+        assert_eq!(gap.backfilled_rows, 10);
+        assert_eq!(gap.backfill_cursor, Some(start + chrono::Duration::seconds(129)));
+        assert!(gap.last_error.is_some());
+
+        // Later runs resume from the cursor without refetching what is stored
+        monitor.backfill(now).await.unwrap();
+        monitor.backfill(now).await.unwrap();
+        let gap = store.gaps.lock()[0].clone();
+        assert_eq!(gap.status, GapStatus::Filled);
+        assert_eq!(gap.backfilled_rows, 60);
+        assert_eq!(monitor.status(now).await.unwrap()[0].status, GapStatus::Filled);
+
+        // Every row in the window is a single flagged backfill row
+        let window: Vec<(MarketTick, TickSource)> = store
+            .ticks
+            .lock()
+            .iter()
+            .filter(|(tick, _)| tick.timestamp > gap.gap_start && tick.timestamp < gap.gap_end)
+            .cloned()
+            .collect();
+        assert_eq!(window.len(), 60);
+        assert!(window.iter().all(|(_, source)| *source == TickSource::Backfill));
+        assert!(monitor.detect(now).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_gap_without_source_is_unfillable() {
+        let now = Utc::now();
+        let store = store_with_outage(now - chrono::Duration::minutes(10));
+        let monitor = GapMonitor::new(config(), store.clone());
+
+        monitor.detect(now).await.unwrap();
+        let updated = monitor.backfill(now).await.unwrap();
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].status, GapStatus::Unfillable);
+        assert!(monitor.backfill(now).await.unwrap().is_empty());
+    }
+}
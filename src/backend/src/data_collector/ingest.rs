@@ -0,0 +1,569 @@
+//! Bulk market data ingestion from external sources such as purchased vendor history. An
+//! NDJSON or CSV body is read as a stream, one line at a time; each row is validated with the
+//! same rules as collected data and written in batches tagged `vendor:<name>`, so backtests
+//! see deeper history while analytics can still tell it apart from live collection.
+//!
+//! Ingestion never competes with the collectors for more than it needs: the body is only read
+//! as fast as batches are written, each request holds one connection at a time, and a fixed
+//! number of requests run concurrently; further requests are refused rather than queued.
+//! Vendor rows are unique per pair, exchange, timestamp and source, so re-sending a file
+//! skips what was already loaded.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - futures = "0.3"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - utoipa = "3.5"
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use metrics::counter;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::Semaphore;
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::db::models::MarketDataRecord;
+
+// Ingestion constants
+pub const VENDOR_SOURCE_PREFIX: &str = "vendor:";
+const METRICS_PREFIX: &str = "trading_bot.ingest";
+const DEFAULT_MAX_BYTES: usize = 256 * 1024 * 1024; // 256MB
+const DEFAULT_MAX_ROWS: usize = 5_000_000;
+const DEFAULT_BATCH_SIZE: usize = 1_000;
+const DEFAULT_MAX_CONCURRENT: usize = 2;
+const DEFAULT_MAX_REPORTED_REJECTIONS: usize = 1_000;
+const MAX_VENDOR_LENGTH: usize = 48;
+const CSV_COLUMNS: [&str; 5] = ["trading_pair", "exchange", "price", "volume", "timestamp"];
+
+/// Market data ingestion error types
+#[derive(Error, Debug)]
+pub enum IngestError {
+    #[error("invalid vendor name: {0}")]
+    InvalidVendor(String),
+    #[error("unsupported content type: {0}")]
+    UnsupportedFormat(String),
+    #[error("invalid csv header: {0}")]
+    InvalidHeader(String),
+    #[error("body exceeds {limit} bytes; rows before the limit were kept and are skipped as duplicates on retry")]
+    TooLarge { limit: usize },
+    #[error("body exceeds {limit} rows; rows before the limit were kept and are skipped as duplicates on retry")]
+    TooManyRows { limit: usize },
+    #[error("too many ingestion requests in progress")]
+    Busy,
+    #[error("failed to read request body: {0}")]
+    Read(String),
+    #[error("ingestion store error: {0}")]
+    Store(String),
+}
+
+/// Body encodings accepted for ingestion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IngestFormat {
+    /// One JSON object per line
+    Ndjson,
+    /// Header line naming the columns, then one row per line
+    Csv,
+}
+
+impl IngestFormat {
+    /// Format of a `Content-Type` header value, ignoring parameters such as charset
+    pub fn from_content_type(content_type: &str) -> Result<Self, IngestError> {
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+        match media_type.to_ascii_lowercase().as_str() {
+            "application/x-ndjson" | "application/ndjson" | "application/jsonl" => Ok(Self::Ndjson),
+            "text/csv" => Ok(Self::Csv),
+            _ => Err(IngestError::UnsupportedFormat(content_type.to_string())),
+        }
+    }
+}
+
+/// A row refused during ingestion
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct RowRejection {
+    /// Line number in the body, starting at 1 and counting the CSV header
+    pub line: u64,
+    pub reason: String,
+}
+
+/// Outcome of one ingestion request
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct IngestSummary {
+    /// Source the rows were stored under, `vendor:<name>`
+    pub source: String,
+    /// Rows written
+    pub accepted: u64,
+    pub rejected: u64,
+    /// Valid rows already stored from an earlier ingestion
+    pub duplicates: u64,
+    /// Rejections in line order, up to the configured cap
+    pub rejections: Vec<RowRejection>,
+}
+
+/// Writes ingested rows alongside collected market data
+#[async_trait]
+pub trait MarketDataSink: Send + Sync {
+    /// Inserts rows tagged with `source`, skipping any already stored; returns rows inserted
+    async fn insert_sourced(&self, records: Vec<MarketDataRecord>, source: &str) -> Result<u64, IngestError>;
+}
+
+/// Per-request limits, write batching and the concurrency cap
+#[derive(Debug, Clone, PartialEq)]
+pub struct IngestConfig {
+    pub max_bytes: usize,
+    pub max_rows: usize,
+    /// Rows per insert; the body is not read further until a batch is written
+    pub batch_size: usize,
+    /// Ingestion requests allowed at once
+    pub max_concurrent: usize,
+    pub max_reported_rejections: usize,
+}
+
+impl Default for IngestConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_BYTES,
+            max_rows: DEFAULT_MAX_ROWS,
+            batch_size: DEFAULT_BATCH_SIZE,
+            max_concurrent: DEFAULT_MAX_CONCURRENT,
+            max_reported_rejections: DEFAULT_MAX_REPORTED_REJECTIONS,
+        }
+    }
+}
+
+/// NDJSON row
+#[derive(Debug, Deserialize)]
+struct IngestRow {
+    trading_pair: String,
+    exchange: String,
+    price: Decimal,
+    volume: Decimal,
+    timestamp: DateTime<Utc>,
+}
+
+/// Turns body lines into validated records
+#[derive(Debug)]
+struct LineParser {
+    format: IngestFormat,
+    /// Position of each of `CSV_COLUMNS` in a CSV row, once the header is read
+    columns: Option<[usize; CSV_COLUMNS.len()]>,
+}
+
+impl LineParser {
+    fn new(format: IngestFormat) -> Self {
+        Self { format, columns: None }
+    }
+
+    /// Parses one line; `Ok(None)` for blank lines and the CSV header
+    fn parse(&mut self, line: &[u8]) -> Result<Option<MarketDataRecord>, String> {
+        let line = std::str::from_utf8(line).map_err(|_| "line is not valid UTF-8".to_string())?;
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(None);
+        }
+        let row = match self.format {
+            IngestFormat::Ndjson => serde_json::from_str::<IngestRow>(line).map_err(|e| e.to_string())?,
+            IngestFormat::Csv => match self.columns {
+                Some(columns) => csv_row(line, &columns)?,
+                None => {
+                    self.columns = Some(csv_header(line)?);
+                    return Ok(None);
+                }
+            },
+        };
+        MarketDataRecord::new(row.trading_pair, row.exchange, row.price, row.volume, row.timestamp)
+            .map(Some)
+            .map_err(|e| e.to_string())
+    }
+}
+
+fn csv_header(line: &str) -> Result<[usize; CSV_COLUMNS.len()], String> {
+    let names: Vec<String> = line.split(',').map(|name| name.trim().to_ascii_lowercase()).collect();
+    let mut columns = [0; CSV_COLUMNS.len()];
+    for (position, column) in columns.iter_mut().zip(CSV_COLUMNS) {
+        *position = names
+            .iter()
+            .position(|name| name == column)
+            .ok_or_else(|| format!("missing column {}", column))?;
+    }
+    Ok(columns)
+}
+
+fn csv_row(line: &str, columns: &[usize; CSV_COLUMNS.len()]) -> Result<IngestRow, String> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    let field = |i: usize| {
+        fields
+            .get(columns[i])
+            .copied()
+            .ok_or_else(|| format!("expected at least {} fields, found {}", columns[i] + 1, fields.len()))
+    };
+    let decimal = |i: usize| {
+        let value = field(i)?;
+        Decimal::from_str(value).map_err(|_| format!("invalid {}: {}", CSV_COLUMNS[i], value))
+    };
+    let timestamp = field(4)?;
+    Ok(IngestRow {
+        trading_pair: field(0)?.to_string(),
+        exchange: field(1)?.to_string(),
+        price: decimal(2)?,
+        volume: decimal(3)?,
+        timestamp: DateTime::parse_from_rfc3339(timestamp)
+            .map_err(|_| format!("invalid timestamp: {}", timestamp))?
+            .with_timezone(&Utc),
+    })
+}
+
+/// Source tag for a vendor's rows; names are lowercase letters, digits, `-` and `_`
+pub fn vendor_source(vendor: &str) -> Result<String, IngestError> {
+    let valid = !vendor.is_empty()
+        && vendor.len() <= MAX_VENDOR_LENGTH
+        && vendor
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !valid {
+        return Err(IngestError::InvalidVendor(vendor.to_string()));
+    }
+    Ok(format!("{}{}", VENDOR_SOURCE_PREFIX, vendor))
+}
+
+/// Progress of one request
+struct Ingestion {
+    parser: LineParser,
+    summary: IngestSummary,
+    pending: Vec<MarketDataRecord>,
+    line: u64,
+    rows: usize,
+}
+
+/// Streams external market data into storage under the concurrency cap
+pub struct MarketDataIngestor {
+    config: IngestConfig,
+    sink: Arc<dyn MarketDataSink>,
+    permits: Semaphore,
+}
+
+impl fmt::Debug for MarketDataIngestor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MarketDataIngestor")
+            .field("config", &self.config)
+            .field("available", &self.permits.available_permits())
+            .finish()
+    }
+}
+
+impl MarketDataIngestor {
+    pub fn new(config: IngestConfig, sink: Arc<dyn MarketDataSink>) -> Self {
+        let permits = Semaphore::new(config.max_concurrent.max(1));
+        Self { config, sink, permits }
+    }
+
+    pub fn config(&self) -> &IngestConfig {
+        &self.config
+    }
+
+    /// Reads `body` line by line and stores its valid rows under `vendor:<vendor>`. Fails
+    /// with `Busy` when the concurrency cap is reached, and stops at the size limits.
+    pub async fn ingest<S, B, E>(&self, vendor: &str, format: IngestFormat, mut body: S) -> Result<IngestSummary, IngestError>
+    where
+        S: Stream<Item = Result<B, E>> + Unpin,
+        B: AsRef<[u8]>,
+        E: fmt::Display,
+    {
+        let source = vendor_source(vendor)?;
+        let _permit = self.permits.try_acquire().map_err(|_| {
+            counter!(format!("{}.busy", METRICS_PREFIX), 1);
+            IngestError::Busy
+        })?;
+
+        let mut ingestion = Ingestion {
+            parser: LineParser::new(format),
+            summary: IngestSummary {
+                source: source.clone(),
+                accepted: 0,
+                rejected: 0,
+                duplicates: 0,
+                rejections: Vec::new(),
+            },
+            pending: Vec::with_capacity(self.config.batch_size),
+            line: 0,
+            rows: 0,
+        };
+        let (mut buffer, mut read) = (Vec::new(), 0usize);
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk.map_err(|e| IngestError::Read(e.to_string()))?;
+            let chunk = chunk.as_ref();
+            read += chunk.len();
+            if read > self.config.max_bytes {
+                return Err(IngestError::TooLarge {
+                    limit: self.config.max_bytes,
+                });
+            }
+            buffer.extend_from_slice(chunk);
+
+            let mut start = 0;
+            while let Some(end) = buffer[start..].iter().position(|byte| *byte == b'\n') {
+                self.accept_line(&mut ingestion, &buffer[start..start + end])?;
+                start += end + 1;
+                if ingestion.pending.len() >= self.config.batch_size {
+                    self.write(&mut ingestion).await?;
+                }
+            }
+            buffer.drain(..start);
+        }
+        if !buffer.is_empty() {
+            self.accept_line(&mut ingestion, &buffer)?;
+        }
+        self.write(&mut ingestion).await?;
+
+        let summary = ingestion.summary;
+        counter!(format!("{}.rows_accepted", METRICS_PREFIX), summary.accepted, "source" => source.clone());
+        counter!(format!("{}.rows_rejected", METRICS_PREFIX), summary.rejected, "source" => source.clone());
+        info!(
+            source = %source,
+            accepted = summary.accepted,
+            rejected = summary.rejected,
+            duplicates = summary.duplicates,
+            "Market data ingestion finished"
+        );
+        Ok(summary)
+    }
+
+    fn accept_line(&self, ingestion: &mut Ingestion, line: &[u8]) -> Result<(), IngestError> {
+        ingestion.line += 1;
+        match ingestion.parser.parse(line) {
+            Ok(Some(record)) => {
+                ingestion.rows += 1;
+                if ingestion.rows > self.config.max_rows {
+                    return Err(IngestError::TooManyRows {
+                        limit: self.config.max_rows,
+                    });
+                }
+                ingestion.pending.push(record);
+            }
+            Ok(None) => {}
+            // Without a header no row can be read, so the request fails outright
+            Err(reason) if ingestion.parser.format == IngestFormat::Csv && ingestion.parser.columns.is_none() => {
+                return Err(IngestError::InvalidHeader(reason));
+            }
+            Err(reason) => {
+                ingestion.summary.rejected += 1;
+                if ingestion.summary.rejections.len() < self.config.max_reported_rejections {
+                    ingestion.summary.rejections.push(RowRejection {
+                        line: ingestion.line,
+                        reason,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn write(&self, ingestion: &mut Ingestion) -> Result<(), IngestError> {
+        if ingestion.pending.is_empty() {
+            return Ok(());
+        }
+        let batch = std::mem::replace(&mut ingestion.pending, Vec::with_capacity(self.config.batch_size));
+        let rows = batch.len() as u64;
+        let inserted = self.sink.insert_sourced(batch, &ingestion.summary.source).await?;
+        ingestion.summary.accepted += inserted;
+        ingestion.summary.duplicates += rows.saturating_sub(inserted);
+        // Let collector writes waiting on the same pool in between batches
+        tokio::task::yield_now().await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use std::collections::HashSet;
+    use std::time::{Duration, Instant};
+
+    const ROWS: usize = 50_000;
+
+    /// Stores rows in memory behind one lock, like writers sharing a connection pool
+    #[derive(Default)]
+    struct MemorySink {
+        keys: tokio::sync::Mutex<HashSet<(String, String, DateTime<Utc>, String)>>,
+        batches: Mutex<Vec<usize>>,
+    }
+
+    impl MemorySink {
+        async fn write(&self, records: Vec<MarketDataRecord>, source: &str) -> u64 {
+            let mut keys = self.keys.lock().await;
+            // Holds the lock as long as a database round trip would
+            tokio::time::sleep(Duration::from_micros(200)).await;
+            records
+                .into_iter()
+                .filter(|r| keys.insert((r.trading_pair.clone(), r.exchange.clone(), r.timestamp, source.to_string())))
+                .count() as u64
+        }
+    }
+
+    #[async_trait]
+    impl MarketDataSink for MemorySink {
+        async fn insert_sourced(&self, records: Vec<MarketDataRecord>, source: &str) -> Result<u64, IngestError> {
+            self.batches.lock().push(records.len());
+            Ok(self.write(records, source).await)
+        }
+    }
+
+    fn body(lines: Vec<String>, chunk_size: usize) -> impl Stream<Item = Result<Vec<u8>, std::io::Error>> + Unpin {
+        let bytes = lines.join("\n").into_bytes();
+        let chunks: Vec<Result<Vec<u8>, std::io::Error>> = bytes
+            .chunks(chunk_size)
+            .map(|chunk| Ok(chunk.to_vec()))
+            .collect();
+        futures::stream::iter(chunks)
+    }
+
+    fn timestamp(i: usize) -> String {
+        (DateTime::parse_from_rfc3339("2023-01-01T00:00:00Z").unwrap() + chrono::Duration::seconds(i as i64))
+            .with_timezone(&Utc)
+            .to_rfc3339()
+    }
+
+    fn csv_file() -> Vec<String> {
+        let mut lines = vec!["timestamp,trading_pair,exchange,price,volume".to_string()];
+        for i in 0..ROWS {
+            let line = match i {
+                // Deliberately malformed rows, each on a known line
+                100 => format!("{},SOLUSDC,jupiter,23.5,10", timestamp(i)),
+                2_000 => format!("{},SOL/USDC,jupiter,-1,10", timestamp(i)),
+                30_000 => format!("{},SOL/USDC,jupiter,abc,10", timestamp(i)),
+                49_999 => "not-a-time,SOL/USDC,jupiter,23.5,10".to_string(),
+                _ => format!("{},SOL/USDC,jupiter,{}.25,{}", timestamp(i), 20 + i % 5, 1 + i % 7),
+            };
+            lines.push(line);
+        }
+        lines
+    }
+
+    #[tokio::test]
+    async fn test_streamed_file_summarized_without_blocking_live_writes() {
+        let sink = Arc::new(MemorySink::default());
+        let ingestor = MarketDataIngestor::new(IngestConfig::default(), sink.clone());
+
+        // A live collector writes one tick every millisecond while the file streams in
+        let live = {
+            let sink = sink.clone();
+            tokio::spawn(async move {
+                let mut slowest = Duration::ZERO;
+                for i in 0..200 {
+                    let record = MarketDataRecord::new(
+                        "RAY/USDC".into(),
+                        "raydium".into(),
+                        Decimal::ONE,
+                        Decimal::ONE,
+                        Utc::now() + chrono::Duration::milliseconds(i),
+                    )
+                    .unwrap();
+                    let started = Instant::now();
+                    sink.write(vec![record], "live").await;
+                    slowest = slowest.max(started.elapsed());
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                }
+                slowest
+            })
+        };
+
+        let summary = ingestor
+            .ingest("kaiko", IngestFormat::Csv, body(csv_file(), 64 * 1024))
+            .await
+            .unwrap();
+        assert_eq!(summary.source, "vendor:kaiko");
+        assert_eq!(summary.accepted, (ROWS - 4) as u64);
+        assert_eq!(summary.rejected, 4);
+        assert_eq!(summary.duplicates, 0);
+        // Line numbers count the header
+        let lines: Vec<u64> = summary.rejections.iter().map(|rejection| rejection.line).collect();
+        assert_eq!(lines, vec![102, 2_002, 30_002, 50_001]);
+        assert!(summary.rejections[2].reason.contains("invalid price"));
+
+        // Writes went out in bounded batches, leaving room for the collector between them
+        assert!(sink.batches.lock().iter().all(|rows| *rows <= DEFAULT_BATCH_SIZE));
+        let slowest = live.await.unwrap();
+        assert!(slowest < Duration::from_millis(100), "live write waited {:?}", slowest);
+
+        // Re-sending the file skips every stored row
+        let summary = ingestor
+            .ingest("kaiko", IngestFormat::Csv, body(csv_file(), 64 * 1024))
+            .await
+            .unwrap();
+        assert_eq!((summary.accepted, summary.duplicates, summary.rejected), (0, (ROWS - 4) as u64, 4));
+    }
+
+    #[tokio::test]
+    async fn test_ndjson_rows_and_limits() {
+        let sink = Arc::new(MemorySink::default());
+        let ingestor = MarketDataIngestor::new(
+            IngestConfig {
+                max_rows: 2,
+                max_concurrent: 1,
+                ..IngestConfig::default()
+            },
+            sink.clone(),
+        );
+        let row = |price: &str| {
+            format!(
+                r#"{{"trading_pair":"SOL/USDC","exchange":"drift","price":"{}","volume":"3","timestamp":"{}"}}"#,
+                price,
+                timestamp(0)
+            )
+        };
+
+        let summary = ingestor
+            .ingest("amberdata", IngestFormat::Ndjson, body(vec![row("23.5"), String::new(), "{".into()], 7))
+            .await
+            .unwrap();
+        assert_eq!((summary.accepted, summary.rejected), (1, 1));
+        assert_eq!(summary.rejections[0].line, 3);
+
+        let too_many = ingestor
+            .ingest("amberdata", IngestFormat::Ndjson, body(vec![row("1"), row("2"), row("3")], 16))
+            .await;
+        assert!(matches!(too_many, Err(IngestError::TooManyRows { limit: 2 })));
+
+        assert!(matches!(
+            ingestor.ingest("Bad Vendor", IngestFormat::Ndjson, body(vec![row("1")], 16)).await,
+            Err(IngestError::InvalidVendor(_))
+        ));
+        assert!(matches!(
+            ingestor.ingest("kaiko", IngestFormat::Csv, body(vec!["price,volume".into()], 16)).await,
+            Err(IngestError::InvalidHeader(_))
+        ));
+
+        // The only permit is held by a stalled upload
+        let stalled = futures::stream::pending::<Result<Vec<u8>, std::io::Error>>();
+        let ingestor = Arc::new(ingestor);
+        let holder = {
+            let ingestor = ingestor.clone();
+            tokio::spawn(async move { ingestor.ingest("kaiko", IngestFormat::Ndjson, stalled).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(matches!(
+            ingestor.ingest("kaiko", IngestFormat::Ndjson, body(vec![row("1")], 16)).await,
+            Err(IngestError::Busy)
+        ));
+        holder.abort();
+    }
+
+    #[test]
+    fn test_content_types() {
+        assert_eq!(
+            IngestFormat::from_content_type("application/x-ndjson").unwrap(),
+            IngestFormat::Ndjson
+        );
+        assert_eq!(IngestFormat::from_content_type("text/csv; charset=utf-8").unwrap(), IngestFormat::Csv);
+        assert!(IngestFormat::from_content_type("application/json").is_err());
+    }
+}
@@ -8,7 +8,11 @@
 //! - tungstenite = "0.20"
 //! - metrics = "0.20"
 
+use crate::config::environment::NetworkEndpoints;
+use crate::data_collector::trades::PublicTrade;
 use crate::models::market::{MarketData, OrderBook};
+use crate::models::pair::TradingPair;
+use crate::risk_manager::exposure::TradeSide;
 use crate::utils::metrics::MetricsCollector;
 use crate::utils::solana::SolanaClient;
 use crate::utils::time::{current_timestamp, calculate_duration_ms};
@@ -19,7 +23,9 @@ use parking_lot::RwLock;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::net::TcpStream;
@@ -28,14 +34,14 @@ use tokio_tungstenite::{connect_async, WebSocketStream};
 use tracing::{debug, error, info, instrument, warn};
 
 // Global constants
-const JUPITER_WS_URL: &str = "wss://price.jup.ag/v1/stream";
-const JUPITER_REST_URL: &str = "https://price.jup.ag/v1";
 const RECONNECT_DELAY_MS: u64 = 5000;
 const MAX_BATCH_SIZE: usize = 100;
 const METRICS_PREFIX: &str = "jupiter_collector";
 const CACHE_TTL_MS: u64 = 1000;
 const MAX_RECONNECT_ATTEMPTS: u8 = 5;
 const MEMORY_POOL_SIZE: usize = 1000;
+const QUOTES_CHANNEL: &str = "quotes";
+const TRADES_CHANNEL: &str = "trades";
 
 /// Error types for Jupiter data collection
 #[derive(Error, Debug)]
@@ -54,35 +60,50 @@ pub enum CollectorError {
 #[derive(Debug, Serialize)]
 struct SubscriptionMessage {
     op: String,
-    trading_pairs: Vec<String>,
+    trading_pairs: Vec<TradingPair>,
+    channels: Vec<&'static str>,
 }
 
 /// Memory-efficient market data cache
 #[derive(Debug)]
 struct DataCache {
-    entries: HashMap<String, (MarketData, i64)>,
-    queue: VecDeque<String>,
+    entries: HashMap<u64, (MarketData, i64)>,
+    queue: VecDeque<u64>,
     capacity: usize,
 }
 
+/// Cache key of a price message: a hash of its pair, price, volume and timestamp fields as
+/// received, so repeated messages are recognized without re-serializing them
+pub fn market_data_key(trading_pair: &str, price: &str, volume: &str, timestamp: Option<i64>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    trading_pair.hash(&mut hasher);
+    price.hash(&mut hasher);
+    volume.hash(&mut hasher);
+    timestamp.hash(&mut hasher);
+    hasher.finish()
+}
+
 /// High-performance Jupiter DEX data collector
 #[derive(Debug)]
 pub struct JupiterCollector {
     ws_stream: Option<(SplitSink<WebSocketStream<TcpStream>, tungstenite::Message>, 
                       SplitStream<WebSocketStream<TcpStream>>)>,
     solana_client: Arc<SolanaClient>,
-    trading_pairs: Vec<String>,
+    trading_pairs: Vec<TradingPair>,
     market_data_tx: mpsc::Sender<MarketData>,
+    /// Receives normalized public trades when the trades channel is enabled
+    trades_tx: Option<mpsc::Sender<PublicTrade>>,
     metrics: Arc<MetricsCollector>,
     memory_pool: Arc<RwLock<Vec<MarketData>>>,
     data_cache: Arc<RwLock<DataCache>>,
     reconnect_backoff: ExponentialBackoff,
+    ws_url: String,
 }
 
 impl JupiterCollector {
     /// Creates a new Jupiter data collector instance
     pub fn new(
-        trading_pairs: Vec<String>,
+        trading_pairs: Vec<TradingPair>,
         solana_client: Arc<SolanaClient>,
         market_data_tx: mpsc::Sender<MarketData>,
         metrics_config: MetricsConfig,
@@ -99,13 +120,27 @@ impl JupiterCollector {
             solana_client,
             trading_pairs,
             market_data_tx,
+            trades_tx: None,
             metrics: Arc::new(MetricsCollector::new().unwrap()),
             memory_pool,
             data_cache,
             reconnect_backoff: ExponentialBackoff::new(RECONNECT_DELAY_MS),
+            ws_url: NetworkEndpoints::default().jupiter_ws_url,
         }
     }
 
+    /// Streams from the price feed configured for the active profile
+    pub fn with_endpoints(mut self, endpoints: &NetworkEndpoints) -> Self {
+        self.ws_url = endpoints.jupiter_ws_url.clone();
+        self
+    }
+
+    /// Also subscribes to the trades channel, sending normalized prints to `trades_tx`
+    pub fn with_trade_sender(mut self, trades_tx: mpsc::Sender<PublicTrade>) -> Self {
+        self.trades_tx = Some(trades_tx);
+        self
+    }
+
     /// Starts the market data collection process
     #[instrument(skip(self))]
     pub async fn start_collection(&mut self) -> Result<(), CollectorError> {
@@ -157,7 +192,7 @@ impl JupiterCollector {
     > {
         let start = current_timestamp();
         
-        let (ws_stream, _) = connect_async(JUPITER_WS_URL)
+        let (ws_stream, _) = connect_async(self.ws_url.as_str())
             .await
             .map_err(|e| CollectorError::ConnectionError(e.to_string()))?;
             
@@ -180,9 +215,14 @@ impl JupiterCollector {
     #[instrument(skip(self))]
     async fn subscribe_to_market_data(&mut self) -> Result<(), CollectorError> {
         if let Some((sink, _)) = &mut self.ws_stream {
+            let mut channels = vec![QUOTES_CHANNEL];
+            if self.trades_tx.is_some() {
+                channels.push(TRADES_CHANNEL);
+            }
             let subscription = SubscriptionMessage {
                 op: "subscribe".to_string(),
                 trading_pairs: self.trading_pairs.clone(),
+                channels,
             };
             
             let message = serde_json::to_string(&subscription)
@@ -209,9 +249,19 @@ impl JupiterCollector {
                     .map_err(|e| CollectorError::WebSocketError(e.to_string()))?;
                     
                 if let tungstenite::Message::Text(data) = message {
+                    if super::drop_injected_message() {
+                        debug!("Dropped market data via injected fault");
+                        continue;
+                    }
                     let start = current_timestamp();
+                    let raw_data: Value = serde_json::from_str(&data)?;
+
+                    if raw_data["channel"] == TRADES_CHANNEL {
+                        self.forward_trade(raw_data).await;
+                        continue;
+                    }
                     
-                    match self.parse_market_data(serde_json::from_str(&data)?) {
+                    match self.parse_market_data(raw_data) {
                         Ok(market_data) => {
                             batch.push(market_data);
                             
@@ -246,13 +296,77 @@ impl JupiterCollector {
         Ok(())
     }
 
+    /// Sends a trades channel message on to the trade stream, dropping malformed prints
+    async fn forward_trade(&self, raw_data: Value) {
+        let Some(trades_tx) = &self.trades_tx else {
+            return;
+        };
+        match self.parse_trade(&raw_data) {
+            Ok(trade) => {
+                if trades_tx.send(trade).await.is_err() {
+                    warn!("Public trade channel closed");
+                }
+            }
+            Err(e) => {
+                warn!("Failed to parse trade: {}", e);
+                counter!(format!("{}.trade_parse_errors", METRICS_PREFIX), 1);
+            }
+        }
+    }
+
+    /// Parses a trades channel message into a public trade
+    fn parse_trade(&self, raw_data: &Value) -> Result<PublicTrade, CollectorError> {
+        let field = |name: &str| {
+            raw_data[name]
+                .as_str()
+                .ok_or_else(|| CollectorError::ParseError(format!("missing {}", name)))
+        };
+
+        let side = match field("side")? {
+            "buy" => TradeSide::Buy,
+            "sell" => TradeSide::Sell,
+            other => return Err(CollectorError::ParseError(format!("unknown side: {}", other))),
+        };
+        let timestamp = raw_data["timestamp"]
+            .as_i64()
+            .and_then(chrono::DateTime::from_timestamp_millis)
+            .ok_or_else(|| CollectorError::ParseError("missing timestamp".to_string()))?;
+
+        let trade = PublicTrade::new(
+            field("trading_pair")?.to_string(),
+            "jupiter".to_string(),
+            Decimal::from_str(field("price")?)?,
+            Decimal::from_str(field("size")?)?,
+            side,
+            timestamp,
+        )
+        .map_err(|e| CollectorError::ValidationError(e.to_string()))?;
+
+        Ok(match raw_data["trade_id"].as_str() {
+            Some(trade_id) => trade.with_trade_id(trade_id),
+            None => trade,
+        })
+    }
+
     /// Parses raw market data with validation and caching
     #[instrument(skip(raw_data))]
     fn parse_market_data(&self, raw_data: Value) -> Result<MarketData, CollectorError> {
         let start = current_timestamp();
         
+        let trading_pair = raw_data["trading_pair"]
+            .as_str()
+            .ok_or_else(|| CollectorError::ParseError("missing trading pair".to_string()))?;
+
+        let price = raw_data["price"]
+            .as_str()
+            .ok_or_else(|| CollectorError::ParseError("missing price".to_string()))?;
+
+        let volume = raw_data["volume"]
+            .as_str()
+            .ok_or_else(|| CollectorError::ParseError("missing volume".to_string()))?;
+
         // Check cache for recent identical data
-        let cache_key = raw_data.to_string();
+        let cache_key = market_data_key(trading_pair, price, volume, raw_data["timestamp"].as_i64());
         {
             let cache = self.data_cache.read();
             if let Some((data, timestamp)) = cache.entries.get(&cache_key) {
@@ -261,22 +375,9 @@ impl JupiterCollector {
                 }
             }
         }
-        
-        let trading_pair = raw_data["trading_pair"]
-            .as_str()
-            .ok_or_else(|| CollectorError::ParseError("missing trading pair".to_string()))?
-            .to_string();
-            
-        let price = raw_data["price"]
-            .as_str()
-            .ok_or_else(|| CollectorError::ParseError("missing price".to_string()))?;
-            
-        let volume = raw_data["volume"]
-            .as_str()
-            .ok_or_else(|| CollectorError::ParseError("missing volume".to_string()))?;
-            
+
         let market_data = MarketData::new(
-            trading_pair,
+            trading_pair.to_string(),
             "jupiter".to_string(),
             Decimal::from_str(price)?,
             Decimal::from_str(volume)?,
@@ -320,7 +421,7 @@ mod tests {
     #[tokio::test]
     async fn test_market_data_parsing() {
         let collector = JupiterCollector::new(
-            vec!["SOL/USDC".to_string()],
+            vec!["SOL/USDC".parse().unwrap()],
             Arc::new(SolanaClient::new(
                 "https://api.mainnet-beta.solana.com".to_string(),
                 None,
@@ -339,4 +440,48 @@ mod tests {
         let result = collector.parse_market_data(raw_data);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_market_data_key_covers_every_field() {
+        let key = market_data_key("SOL/USDC", "23.45", "100", Some(1_700_000_000_000));
+        assert_eq!(key, market_data_key("SOL/USDC", "23.45", "100", Some(1_700_000_000_000)));
+        assert_ne!(key, market_data_key("BONK/USDC", "23.45", "100", Some(1_700_000_000_000)));
+        assert_ne!(key, market_data_key("SOL/USDC", "23.46", "100", Some(1_700_000_000_000)));
+        assert_ne!(key, market_data_key("SOL/USDC", "23.45", "101", Some(1_700_000_000_000)));
+        assert_ne!(key, market_data_key("SOL/USDC", "23.45", "100", Some(1_700_000_000_001)));
+        assert_ne!(key, market_data_key("SOL/USDC", "23.45", "100", None));
+    }
+
+    #[tokio::test]
+    async fn test_trade_parsing() {
+        let collector = JupiterCollector::new(
+            vec!["SOL/USDC".parse().unwrap()],
+            Arc::new(SolanaClient::new(
+                "https://api.mainnet-beta.solana.com".to_string(),
+                None,
+                None
+            ).await.unwrap()),
+            mpsc::channel(100).0,
+            MetricsConfig::default(),
+        );
+
+        let raw_data = serde_json::json!({
+            "channel": "trades",
+            "trading_pair": "SOL/USDC",
+            "trade_id": "5hY3x",
+            "price": "23.4567",
+            "size": "12.5",
+            "side": "sell",
+            "timestamp": current_timestamp().timestamp_millis()
+        });
+
+        let trade = collector.parse_trade(&raw_data).unwrap();
+        assert_eq!(trade.price, dec!(23.4567));
+        assert_eq!(trade.side, TradeSide::Sell);
+        assert_eq!(trade.dedup_key(), "jupiter:5hY3x");
+
+        let mut missing_side = raw_data.clone();
+        missing_side["side"] = serde_json::json!("hold");
+        assert!(collector.parse_trade(&missing_side).is_err());
+    }
 }
\ No newline at end of file
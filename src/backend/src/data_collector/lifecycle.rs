@@ -0,0 +1,511 @@
+//! Per-collector lifecycle management. Each DEX collector runs in its own task so one can be
+//! stopped, recreated and re-subscribed without interrupting the others or the execution
+//! engine. A watchdog restarts collectors that stay connected but stop producing data, capped
+//! at a maximum restart rate so a venue outage does not turn into a restart loop.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - metrics = "0.20"
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::Mutex as SyncMutex;
+use serde::Serialize;
+use thiserror::Error;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use super::{create_collector, Collector, CollectorConfig, CollectorError, DexType};
+use crate::models::TradingPair;
+use crate::utils::solana::SolanaClient;
+
+// Lifecycle constants
+const METRICS_PREFIX: &str = "trading_bot.collectors";
+const DEFAULT_WATCHDOG_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_WEDGE_THRESHOLD: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_RESTARTS: usize = 3;
+const DEFAULT_RESTART_WINDOW: Duration = Duration::from_secs(600);
+const DEFAULT_STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Lifecycle error types
+#[derive(Error, Debug)]
+pub enum LifecycleError {
+    #[error("collector not running: {0}")]
+    NotRunning(String),
+    #[error("collector already running: {0}")]
+    AlreadyRunning(String),
+    #[error("restart rate exceeded for {0}")]
+    RestartRateExceeded(String),
+    #[error("{0}")]
+    Collector(#[from] CollectorError),
+}
+
+/// Watchdog thresholds and restart limits
+#[derive(Debug, Clone, PartialEq)]
+pub struct LifecycleConfig {
+    pub watchdog_interval: Duration,
+    /// How long a healthy collector may go without producing data before it counts as wedged
+    pub wedge_threshold: Duration,
+    /// Automatic restarts allowed per collector within the restart window
+    pub max_restarts: usize,
+    pub restart_window: Duration,
+    /// Time allowed for `stop_collection` before the task is aborted anyway
+    pub stop_timeout: Duration,
+}
+
+impl Default for LifecycleConfig {
+    fn default() -> Self {
+        Self {
+            watchdog_interval: DEFAULT_WATCHDOG_INTERVAL,
+            wedge_threshold: DEFAULT_WEDGE_THRESHOLD,
+            max_restarts: DEFAULT_MAX_RESTARTS,
+            restart_window: DEFAULT_RESTART_WINDOW,
+            stop_timeout: DEFAULT_STOP_TIMEOUT,
+        }
+    }
+}
+
+/// Why a collector was restarted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RestartTrigger {
+    /// Requested by an operator through the admin API
+    Manual,
+    /// Connected but silent for longer than the wedge threshold
+    Watchdog { silent_secs: i64 },
+}
+
+impl RestartTrigger {
+    fn label(&self) -> &'static str {
+        match self {
+            RestartTrigger::Manual => "manual",
+            RestartTrigger::Watchdog { .. } => "watchdog",
+        }
+    }
+}
+
+/// Audit record of one restart attempt
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectorRestart {
+    pub dex: String,
+    pub trigger: RestartTrigger,
+    pub succeeded: bool,
+    pub detail: Option<String>,
+    pub restarted_at: DateTime<Utc>,
+}
+
+/// Builds fresh collector instances for restarts
+pub trait CollectorFactory: Send + Sync {
+    fn create(&self, dex: &DexType) -> Result<Box<dyn Collector>, CollectorError>;
+}
+
+/// Creates venue collectors through `create_collector`
+#[derive(Debug)]
+pub struct DexCollectorFactory {
+    solana_client: Arc<SolanaClient>,
+    config: CollectorConfig,
+}
+
+impl DexCollectorFactory {
+    pub fn new(solana_client: Arc<SolanaClient>, config: CollectorConfig) -> Self {
+        Self { solana_client, config }
+    }
+}
+
+impl CollectorFactory for DexCollectorFactory {
+    fn create(&self, dex: &DexType) -> Result<Box<dyn Collector>, CollectorError> {
+        create_collector(dex.clone(), self.solana_client.clone(), self.config.clone())
+    }
+}
+
+/// A running collector and its task
+struct ManagedCollector {
+    collector: Arc<dyn Collector>,
+    task: JoinHandle<()>,
+    trading_pairs: Vec<TradingPair>,
+    started_at: DateTime<Utc>,
+    /// Recent automatic restarts, for the rate limit
+    restarts: VecDeque<Instant>,
+}
+
+/// Owns every collector task and restarts them individually
+pub struct CollectorManager {
+    config: LifecycleConfig,
+    factory: Arc<dyn CollectorFactory>,
+    collectors: Mutex<HashMap<DexType, ManagedCollector>>,
+    /// Pairs of collectors stopped by `suspend_all`, restarted by `resume_suspended`
+    suspended: Mutex<HashMap<DexType, Vec<TradingPair>>>,
+    audit_log: SyncMutex<Vec<CollectorRestart>>,
+}
+
+impl std::fmt::Debug for CollectorManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CollectorManager")
+            .field("config", &self.config)
+            .field("restarts", &self.audit_log.lock().len())
+            .finish()
+    }
+}
+
+impl CollectorManager {
+    pub fn new(config: LifecycleConfig, factory: Arc<dyn CollectorFactory>) -> Self {
+        Self {
+            config,
+            factory,
+            collectors: Mutex::new(HashMap::new()),
+            suspended: Mutex::new(HashMap::new()),
+            audit_log: SyncMutex::new(Vec::new()),
+        }
+    }
+
+    /// Creates a collector for a DEX, subscribes it to its pairs and starts its task
+    pub async fn start(&self, dex: DexType, trading_pairs: Vec<TradingPair>) -> Result<(), LifecycleError> {
+        let mut collectors = self.collectors.lock().await;
+        if collectors.contains_key(&dex) {
+            return Err(LifecycleError::AlreadyRunning(dex.to_string()));
+        }
+        let (collector, task) = self.launch(&dex, &trading_pairs).await?;
+        collectors.insert(
+            dex,
+            ManagedCollector {
+                collector,
+                task,
+                trading_pairs,
+                started_at: Utc::now(),
+                restarts: VecDeque::new(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Stops every collector
+    pub async fn stop_all(&self) {
+        let mut collectors = self.collectors.lock().await;
+        for (dex, managed) in collectors.drain() {
+            self.stop(&dex, &managed).await;
+        }
+    }
+
+    /// Stops every collector, remembering its pairs so `resume_suspended` can restart it
+    pub async fn suspend_all(&self) {
+        let mut collectors = self.collectors.lock().await;
+        let mut suspended = self.suspended.lock().await;
+        for (dex, managed) in collectors.drain() {
+            self.stop(&dex, &managed).await;
+            info!(dex = %dex, "Collector suspended");
+            suspended.insert(dex, managed.trading_pairs);
+        }
+    }
+
+    /// Restarts the collectors stopped by `suspend_all`
+    pub async fn resume_suspended(&self) {
+        let suspended: Vec<_> = self.suspended.lock().await.drain().collect();
+        for (dex, trading_pairs) in suspended {
+            match self.start(dex.clone(), trading_pairs).await {
+                Ok(()) => info!(dex = %dex, "Collector resumed"),
+                Err(e) => error!(dex = %dex, "Failed to resume collector: {}", e),
+            }
+        }
+    }
+
+    /// DEXs with a running collector
+    pub async fn running(&self) -> Vec<DexType> {
+        self.collectors.lock().await.keys().cloned().collect()
+    }
+
+    /// Recorded restart attempts, oldest first
+    pub fn audit_log(&self) -> Vec<CollectorRestart> {
+        self.audit_log.lock().clone()
+    }
+
+    /// Stops one collector, recreates it and re-subscribes its pairs; the others keep running.
+    /// Automatic restarts are refused once the collector hits the restart rate limit.
+    pub async fn restart(&self, dex: &DexType, trigger: RestartTrigger) -> Result<CollectorRestart, LifecycleError> {
+        let mut collectors = self.collectors.lock().await;
+        let managed = collectors
+            .get_mut(dex)
+            .ok_or_else(|| LifecycleError::NotRunning(dex.to_string()))?;
+
+        let now = Instant::now();
+        while managed
+            .restarts
+            .front()
+            .map_or(false, |at| now.duration_since(*at) > self.config.restart_window)
+        {
+            managed.restarts.pop_front();
+        }
+        if trigger != RestartTrigger::Manual && managed.restarts.len() >= self.config.max_restarts {
+            counter!(format!("{}.restarts_suppressed", METRICS_PREFIX), 1, "dex" => dex.to_string());
+            return Err(LifecycleError::RestartRateExceeded(dex.to_string()));
+        }
+
+        warn!(dex = %dex, trigger = trigger.label(), "Restarting collector");
+        self.stop(dex, managed).await;
+        if trigger != RestartTrigger::Manual {
+            managed.restarts.push_back(now);
+        }
+
+        let result = self.launch(dex, &managed.trading_pairs).await;
+        let restart = CollectorRestart {
+            dex: dex.to_string(),
+            trigger: trigger.clone(),
+            succeeded: result.is_ok(),
+            detail: result.as_ref().err().map(|e| e.to_string()),
+            restarted_at: Utc::now(),
+        };
+        counter!(
+            format!("{}.restarts", METRICS_PREFIX),
+            1,
+            "dex" => dex.to_string(),
+            "trigger" => trigger.label(),
+            "succeeded" => restart.succeeded.to_string()
+        );
+        self.audit_log.lock().push(restart.clone());
+
+        match result {
+            Ok((collector, task)) => {
+                managed.collector = collector;
+                managed.task = task;
+                managed.started_at = restart.restarted_at;
+                info!(dex = %dex, "Collector restarted");
+                Ok(restart)
+            }
+            Err(e) => {
+                error!(dex = %dex, "Collector restart failed: {}", e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Collectors reporting a healthy connection without producing data within the threshold
+    pub async fn wedged(&self, now: DateTime<Utc>) -> Vec<(DexType, i64)> {
+        let threshold = chrono::Duration::from_std(self.config.wedge_threshold)
+            .unwrap_or_else(|_| chrono::Duration::max_value());
+        let collectors = self.collectors.lock().await;
+        let mut wedged = Vec::new();
+        for (dex, managed) in collectors.iter() {
+            let health = match managed.collector.health_check().await {
+                Ok(health) => health,
+                Err(e) => {
+                    warn!(dex = %dex, "Collector health check failed: {}", e);
+                    continue;
+                }
+            };
+            // Unhealthy connections are left to the collector's own reconnect logic
+            if !health.is_healthy {
+                continue;
+            }
+            let last = health
+                .last_collection_time
+                .map_or(managed.started_at, |at| at.max(managed.started_at));
+            if now - last > threshold {
+                wedged.push((dex.clone(), (now - last).num_seconds()));
+            }
+        }
+        wedged
+    }
+
+    /// Periodically restarts wedged collectors
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.watchdog_interval);
+            loop {
+                interval.tick().await;
+                for (dex, silent_secs) in self.wedged(Utc::now()).await {
+                    counter!(format!("{}.wedged", METRICS_PREFIX), 1, "dex" => dex.to_string());
+                    if let Err(e) = self.restart(&dex, RestartTrigger::Watchdog { silent_secs }).await {
+                        warn!(dex = %dex, "Watchdog restart skipped: {}", e);
+                    }
+                }
+            }
+        })
+    }
+
+    async fn launch(
+        &self,
+        dex: &DexType,
+        trading_pairs: &[TradingPair],
+    ) -> Result<(Arc<dyn Collector>, JoinHandle<()>), LifecycleError> {
+        let collector: Arc<dyn Collector> = Arc::from(self.factory.create(dex)?);
+        collector.subscribe(trading_pairs).await?;
+
+        let task = tokio::spawn({
+            let collector = collector.clone();
+            let dex = dex.clone();
+            async move {
+                if let Err(e) = collector.start_collection().await {
+                    error!(dex = %dex, "Collector exited: {}", e);
+                }
+            }
+        });
+        Ok((collector, task))
+    }
+
+    async fn stop(&self, dex: &DexType, managed: &ManagedCollector) {
+        match tokio::time::timeout(self.config.stop_timeout, managed.collector.stop_collection()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!(dex = %dex, "Collector failed to stop cleanly: {}", e),
+            Err(_) => warn!(dex = %dex, "Collector stop timed out"),
+        }
+        managed.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_collector::HealthStatus;
+    use async_trait::async_trait;
+
+    type Subscriptions = Arc<SyncMutex<Vec<(DexType, Vec<TradingPair>)>>>;
+
+    /// Connected collector that either keeps producing data or went silent five minutes ago
+    struct MockCollector {
+        dex: DexType,
+        wedged: bool,
+        subscriptions: Subscriptions,
+    }
+
+    #[async_trait]
+    impl Collector for MockCollector {
+        async fn start_collection(&self) -> Result<(), CollectorError> {
+            std::future::pending().await
+        }
+
+        async fn stop_collection(&self) -> Result<(), CollectorError> {
+            Ok(())
+        }
+
+        async fn health_check(&self) -> Result<HealthStatus, CollectorError> {
+            let last_collection_time = if self.wedged {
+                Utc::now() - chrono::Duration::minutes(5)
+            } else {
+                Utc::now()
+            };
+            Ok(HealthStatus {
+                is_healthy: true,
+                connection_count: 1,
+                last_collection_latency: Duration::ZERO,
+                last_collection_time: Some(last_collection_time),
+                error_count: 0,
+                last_error: None,
+            })
+        }
+
+        async fn subscribe(&self, trading_pairs: &[TradingPair]) -> Result<(), CollectorError> {
+            self.subscriptions.lock().push((self.dex.clone(), trading_pairs.to_vec()));
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockFactory {
+        wedged: Option<DexType>,
+        created: SyncMutex<HashMap<DexType, usize>>,
+        subscriptions: Subscriptions,
+    }
+
+    impl MockFactory {
+        fn created(&self, dex: &DexType) -> usize {
+            self.created.lock().get(dex).copied().unwrap_or(0)
+        }
+    }
+
+    impl CollectorFactory for MockFactory {
+        fn create(&self, dex: &DexType) -> Result<Box<dyn Collector>, CollectorError> {
+            *self.created.lock().entry(dex.clone()).or_insert(0) += 1;
+            Ok(Box::new(MockCollector {
+                dex: dex.clone(),
+                wedged: self.wedged.as_ref() == Some(dex),
+                subscriptions: self.subscriptions.clone(),
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_restarts_only_wedged_collector() {
+        let factory = Arc::new(MockFactory {
+            wedged: Some(DexType::Jupiter),
+            ..MockFactory::default()
+        });
+        let config = LifecycleConfig {
+            watchdog_interval: Duration::from_millis(10),
+            wedge_threshold: Duration::from_millis(50),
+            max_restarts: 2,
+            ..LifecycleConfig::default()
+        };
+        let manager = Arc::new(CollectorManager::new(config, factory.clone()));
+        let pairs: Vec<TradingPair> = vec!["SOL/USDC".parse().unwrap(), "BONK/USDC".parse().unwrap()];
+        for dex in DexType::ALL {
+            manager.start(dex, pairs.clone()).await.unwrap();
+        }
+
+        // A freshly started collector gets the full threshold before it counts as wedged
+        assert!(manager.wedged(Utc::now()).await.is_empty());
+
+        let watchdog = manager.clone().spawn();
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        watchdog.abort();
+
+        // Jupiter was recreated until the rate limit kicked in; the others were never touched
+        assert_eq!(factory.created(&DexType::Jupiter), 3);
+        assert_eq!(factory.created(&DexType::PumpFun), 1);
+        assert_eq!(factory.created(&DexType::Drift), 1);
+        assert_eq!(manager.running().await.len(), 3);
+
+        let audit = manager.audit_log();
+        assert_eq!(audit.len(), 2);
+        assert!(audit.iter().all(|restart| restart.dex == "jupiter" && restart.succeeded));
+        assert!(matches!(audit[0].trigger, RestartTrigger::Watchdog { .. }));
+
+        // Every replacement re-subscribed the collector's pairs
+        let jupiter_subscriptions: Vec<_> = factory
+            .subscriptions
+            .lock()
+            .iter()
+            .filter(|(dex, _)| *dex == DexType::Jupiter)
+            .map(|(_, subscribed)| subscribed.clone())
+            .collect();
+        assert_eq!(jupiter_subscriptions, vec![pairs.clone(); 3]);
+
+        // Automatic restarts are capped, operator restarts are not
+        assert!(matches!(
+            manager.restart(&DexType::Jupiter, RestartTrigger::Watchdog { silent_secs: 300 }).await,
+            Err(LifecycleError::RestartRateExceeded(_))
+        ));
+        manager.restart(&DexType::Jupiter, RestartTrigger::Manual).await.unwrap();
+        assert_eq!(factory.created(&DexType::Jupiter), 4);
+        assert!(matches!(
+            manager.restart(&DexType::Drift, RestartTrigger::Manual).await,
+            Ok(CollectorRestart { succeeded: true, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_suspended_collectors_resume_with_their_pairs() {
+        let factory = Arc::new(MockFactory::default());
+        let manager = CollectorManager::new(LifecycleConfig::default(), factory.clone());
+        manager.start(DexType::Drift, vec!["SOL-PERP".parse().unwrap()]).await.unwrap();
+
+        manager.suspend_all().await;
+        assert!(manager.running().await.is_empty());
+
+        manager.resume_suspended().await;
+        assert_eq!(manager.running().await, vec![DexType::Drift]);
+        assert_eq!(factory.created(&DexType::Drift), 2);
+        assert_eq!(
+            factory.subscriptions.lock().last().cloned(),
+            Some((DexType::Drift, vec!["SOL-PERP".parse().unwrap()]))
+        );
+
+        // Nothing left to resume
+        manager.resume_suspended().await;
+        assert_eq!(factory.created(&DexType::Drift), 2);
+    }
+}
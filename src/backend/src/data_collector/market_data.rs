@@ -17,6 +17,8 @@ use thiserror::Error;
 use tokio::{sync::RwLock, task::JoinHandle, time};
 
 use crate::models::market::{MarketData, MarketError};
+use crate::models::pair::{PairRegistry, TradingPair};
+use crate::utils::circuit_breaker::{BreakerConfig, CircuitBreaker};
 use crate::utils::metrics::MetricsCollector;
 use crate::utils::time::{current_timestamp, is_valid_market_timestamp};
 
@@ -26,10 +28,7 @@ const MAX_BATCH_SIZE: usize = 1000;
 const MAX_RETRIES: u32 = 3;
 const RETRY_DELAY_MS: u64 = 50;
 const ERROR_THRESHOLD: f64 = 0.1;
-const CIRCUIT_BREAKER_THRESHOLD: f64 = 0.2;
-
-// Supported trading pairs
-const SUPPORTED_PAIRS: [&str; 3] = ["SOL/USDC", "ORCA/USDC", "RAY/USDC"];
+const CIRCUIT_BREAKER_THRESHOLD: u32 = 200; // consecutive failed collection rounds
 
 /// Error types for market data collection operations
 #[derive(Error, Debug)]
@@ -53,46 +52,14 @@ struct ExchangeConfig {
     rate_limit: u32,
 }
 
-/// Circuit breaker for preventing cascading failures
-#[derive(Debug)]
-struct CircuitBreaker {
-    error_count: Arc<RwLock<u32>>,
-    last_reset: Arc<RwLock<chrono::DateTime<chrono::Utc>>>,
-    threshold: f64,
-}
-
-impl CircuitBreaker {
-    fn new(threshold: f64) -> Self {
-        Self {
-            error_count: Arc::new(RwLock::new(0)),
-            last_reset: Arc::new(RwLock::new(current_timestamp())),
-            threshold,
-        }
-    }
-
-    async fn record_error(&self) -> bool {
-        let mut count = self.error_count.write().await;
-        *count += 1;
-        let error_rate = *count as f64 / MAX_BATCH_SIZE as f64;
-        error_rate > self.threshold
-    }
-
-    async fn reset(&self) {
-        let mut count = self.error_count.write().await;
-        *count = 0;
-        let mut last_reset = self.last_reset.write().await;
-        *last_reset = current_timestamp();
-    }
-}
-
 /// High-performance market data collector with error handling and metrics
 #[derive(Debug)]
 pub struct MarketDataCollector {
     metrics: MetricsCollector,
-    trading_pairs: Vec<String>,
+    trading_pairs: Vec<TradingPair>,
     collection_interval: Duration,
     dex_configs: HashMap<String, ExchangeConfig>,
-    circuit_breaker: CircuitBreaker,
+    circuit_breaker: Arc<CircuitBreaker>,
     retry_policy: RetryPolicy,
 }
 
@@ -109,17 +76,22 @@ impl MarketDataCollector {
         metrics: MetricsCollector,
         dex_configs: HashMap<String, ExchangeConfig>,
     ) -> Result<Self, CollectionError> {
-        // Validate trading pairs
-        for pair in &trading_pairs {
-            validate_trading_pair(pair)?;
-        }
+        // Validate and normalize trading pairs
+        let trading_pairs = trading_pairs
+            .iter()
+            .map(|pair| validate_trading_pair(pair))
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self {
             metrics,
             trading_pairs,
             collection_interval: Duration::from_millis(COLLECTION_INTERVAL_MS),
             dex_configs,
-            circuit_breaker: CircuitBreaker::new(CIRCUIT_BREAKER_THRESHOLD),
+            circuit_breaker: CircuitBreaker::new(
+                "market_data_collector",
+                BreakerConfig::consecutive_failures(CIRCUIT_BREAKER_THRESHOLD),
+            )
+            .registered(),
             retry_policy: RetryPolicy {
                 max_retries: MAX_RETRIES,
                 delay: Duration::from_millis(RETRY_DELAY_MS),
@@ -144,11 +116,11 @@ impl MarketDataCollector {
                                 collector.metrics.record_collection_error("processing_error");
                             }
                         }
-                        collector.circuit_breaker.reset().await;
+                        collector.circuit_breaker.record_success();
                     }
                     Err(e) => {
                         collector.metrics.record_collection_error("collection_error");
-                        if collector.circuit_breaker.record_error().await {
+                        if collector.circuit_breaker.record_failure() {
                             break;
                         }
                     }
@@ -207,29 +179,17 @@ impl MarketDataCollector {
     }
 }
 
-/// Validates if a trading pair is supported and properly formatted
-fn validate_trading_pair(trading_pair: &str) -> Result<(), CollectionError> {
-    if !SUPPORTED_PAIRS.contains(&trading_pair) {
-        return Err(CollectionError::ValidationError(format!(
-            "unsupported trading pair: {}",
-            trading_pair
-        )));
-    }
-
-    let parts: Vec<&str> = trading_pair.split('/').collect();
-    if parts.len() != 2 {
-        return Err(CollectionError::ValidationError(
-            "invalid trading pair format".to_string(),
-        ));
-    }
-
-    Ok(())
+/// Parses a trading pair and checks it against the default pair registry
+pub fn validate_trading_pair(trading_pair: &str) -> Result<TradingPair, CollectionError> {
+    PairRegistry::default()
+        .parse(trading_pair)
+        .map_err(|e| CollectionError::ValidationError(e.to_string()))
 }
 
 /// Processes and validates collected market data with error handling
 fn process_market_data(data: MarketData) -> Result<MarketData, CollectionError> {
     // Validate trading pair
-    validate_trading_pair(&data.trading_pair)?;
+    validate_trading_pair(data.trading_pair())?;
 
     // Validate data freshness
     if !is_valid_market_timestamp(data.timestamp) {
@@ -264,6 +224,8 @@ mod tests {
     #[test]
     fn test_trading_pair_validation() {
         assert!(validate_trading_pair("SOL/USDC").is_ok());
+        assert_eq!(validate_trading_pair("ray-usdc").unwrap(), "RAY/USDC");
         assert!(validate_trading_pair("INVALID").is_err());
+        assert!(validate_trading_pair("BTC/USDC").is_err());
     }
 }
\ No newline at end of file
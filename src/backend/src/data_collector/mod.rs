@@ -15,7 +15,11 @@ use tracing::{debug, error, info, instrument, warn};
 use async_trait::async_trait;
 
 use crate::{
-    models::market::{MarketData, validate_price, validate_volume},
+    config::environment::{EnvironmentConfig, EnvironmentProfile, NetworkEndpoints},
+    data_collector::trades::PublicTrade,
+    execution_engine::book_sync::BookSequence,
+    models::market::{MarketData, OrderBook, validate_price, validate_volume},
+    models::pair::TradingPair,
     utils::solana::SolanaClient,
 };
 
@@ -27,13 +31,42 @@ pub const CONNECTION_POOL_SIZE: usize = 10;
 pub const VALIDATION_TIMEOUT_MS: u64 = 50;
 
 /// Supported DEX types for data collection
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum DexType {
     Jupiter,
     PumpFun,
     Drift,
 }
 
+impl DexType {
+    pub const ALL: [DexType; 3] = [DexType::Jupiter, DexType::PumpFun, DexType::Drift];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DexType::Jupiter => "jupiter",
+            DexType::PumpFun => "pump_fun",
+            DexType::Drift => "drift",
+        }
+    }
+}
+
+impl std::fmt::Display for DexType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for DexType {
+    type Err = CollectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|dex| dex.as_str() == s)
+            .ok_or_else(|| CollectorError::CollectionError(format!("unknown dex: {}", s)))
+    }
+}
+
 /// Configuration for data collectors
 #[derive(Debug, Clone)]
 pub struct CollectorConfig {
@@ -41,17 +74,56 @@ pub struct CollectorConfig {
     pub connection_pool_size: usize,
     pub max_reconnect_attempts: u8,
     pub validation_timeout: Duration,
+    /// Capture file for recording collected data, set by `--record`
+    pub record_path: Option<std::path::PathBuf>,
+    /// Venue endpoints and program ids for the active profile
+    pub endpoints: NetworkEndpoints,
+    /// When set, collectors also follow venue trade feeds and send normalized prints here
+    pub public_trades_tx: Option<mpsc::Sender<PublicTrade>>,
+    /// When set, collectors send every validated market data sample here
+    pub market_data_tx: Option<mpsc::Sender<MarketData>>,
+    /// When set, collectors streaming venue depth send sequenced book updates here
+    pub book_updates_tx: Option<mpsc::Sender<BookUpdate>>,
 }
 
-impl Default for CollectorConfig {
-    fn default() -> Self {
+impl CollectorConfig {
+    /// Scales intervals and pool sizes for a profile; production runs at full rate
+    pub fn for_profile(profile: EnvironmentProfile, endpoints: NetworkEndpoints) -> Self {
         Self {
-            collection_interval: Duration::from_millis(COLLECTION_INTERVAL_MS),
-            connection_pool_size: CONNECTION_POOL_SIZE,
+            collection_interval: Duration::from_millis(
+                COLLECTION_INTERVAL_MS * profile.interval_multiplier() as u64,
+            ),
+            connection_pool_size: (CONNECTION_POOL_SIZE / profile.pool_size_divisor()).max(1),
             max_reconnect_attempts: MAX_RECONNECT_ATTEMPTS,
             validation_timeout: Duration::from_millis(VALIDATION_TIMEOUT_MS),
+            record_path: replay::record_path_from_args(std::env::args()),
+            endpoints,
+            public_trades_tx: None,
+            market_data_tx: None,
+            book_updates_tx: None,
         }
     }
+
+    /// Collector settings for the configured environment
+    pub fn for_environment(environment: &EnvironmentConfig) -> Self {
+        Self::for_profile(environment.profile(), environment.endpoints.clone())
+    }
+}
+
+impl Default for CollectorConfig {
+    fn default() -> Self {
+        Self::for_profile(
+            EnvironmentProfile::Development,
+            NetworkEndpoints::for_profile(EnvironmentProfile::Development),
+        )
+    }
+}
+
+/// Venue depth update with the sequence metadata needed to apply it in order
+#[derive(Debug, Clone)]
+pub struct BookUpdate {
+    pub book: OrderBook,
+    pub sequence: BookSequence,
 }
 
 /// Comprehensive error types for data collection operations
@@ -77,6 +149,8 @@ pub struct HealthStatus {
     pub is_healthy: bool,
     pub connection_count: usize,
     pub last_collection_latency: Duration,
+    /// When the collector last produced market data, if it has since starting
+    pub last_collection_time: Option<chrono::DateTime<chrono::Utc>>,
     pub error_count: u64,
     pub last_error: Option<String>,
 }
@@ -105,6 +179,11 @@ pub trait Collector: Send + Sync {
     /// Performs health check of collector and its connections
     #[instrument(skip(self))]
     async fn health_check(&self) -> Result<HealthStatus, CollectorError>;
+
+    /// Subscribes to market data for the given pairs; collectors covering every market ignore it
+    async fn subscribe(&self, _trading_pairs: &[TradingPair]) -> Result<(), CollectorError> {
+        Ok(())
+    }
 }
 
 /// Creates appropriate DEX collector with connection pooling
@@ -130,6 +209,29 @@ pub fn create_collector(
     }
 }
 
+/// Creates a collector that replays recorded market data instead of connecting to a DEX
+#[instrument(skip(market_data_tx))]
+pub fn create_replay_collector(
+    source: replay::ReplaySource,
+    speed: replay::ReplaySpeed,
+    market_data_tx: mpsc::Sender<MarketData>,
+) -> Box<dyn Collector> {
+    info!("Creating replay collector");
+    Box::new(replay::ReplayCollector::new(source, speed, market_data_tx))
+}
+
+/// True when an injected fault drops an inbound websocket message; a no-op without fault injection
+pub(crate) fn drop_injected_message() -> bool {
+    #[cfg(feature = "fault-injection")]
+    {
+        crate::fault_injection::should_drop(crate::fault_injection::FaultComponent::CollectorWs)
+    }
+    #[cfg(not(feature = "fault-injection"))]
+    {
+        false
+    }
+}
+
 /// Connection pool for managing DEX websocket connections
 #[derive(Debug)]
 struct ConnectionPool {
@@ -197,6 +299,14 @@ impl WebSocketConnection {
 pub mod jupiter;
 pub mod pump_fun;
 pub mod drift;
+pub mod gaps;
+pub mod ingest;
+pub mod lifecycle;
+pub mod ohlcv;
+pub mod quality;
+pub mod replay;
+pub mod sanity;
+pub mod trades;
 
 #[cfg(test)]
 mod tests {
@@ -220,6 +330,22 @@ mod tests {
         assert!(collector.is_ok());
     }
 
+    #[test]
+    fn test_collector_config_scales_with_profile() {
+        let dev = CollectorConfig::default();
+        assert_eq!(dev.collection_interval, Duration::from_millis(1000));
+        assert_eq!(dev.connection_pool_size, 2);
+        assert!(dev.endpoints.drift_ws_url.contains("dlob"));
+
+        let prod = CollectorConfig::for_profile(
+            EnvironmentProfile::Production,
+            NetworkEndpoints::for_profile(EnvironmentProfile::Production),
+        );
+        assert_eq!(prod.collection_interval, Duration::from_millis(COLLECTION_INTERVAL_MS));
+        assert_eq!(prod.connection_pool_size, CONNECTION_POOL_SIZE);
+        assert!(prod.endpoints.non_mainnet().is_empty());
+    }
+
     #[tokio::test]
     async fn test_connection_pool() {
         let pool = ConnectionPool::new(2);
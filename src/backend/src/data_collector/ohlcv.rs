@@ -0,0 +1,694 @@
+//! OHLCV candlestick aggregation over the aggregated price stream with rolling
+//! in-memory candles per trading pair and interval, late-tick corrections and
+//! periodic persistence to the `candles` table.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - rust_decimal = "1.30"
+//! - chrono = "0.4"
+//! - metrics = "0.20"
+//! - tracing = "0.1"
+//! - utoipa = "3.5"
+
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration as ChronoDuration, TimeZone, Utc};
+use metrics::{counter, gauge, histogram};
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::broadcast;
+use tokio::time::{interval, Duration};
+use tracing::{debug, error, info, instrument, warn};
+use utoipa::ToSchema;
+
+use crate::db::repositories::CandleRepository;
+use crate::models::market::MarketData;
+use crate::utils::time::current_timestamp;
+
+// Aggregation constants
+pub const LATE_TICK_GRACE_MS: i64 = 5000;
+pub const CANDLE_FLUSH_INTERVAL_MS: u64 = 1000;
+pub const MAX_CLOSED_CANDLES_RETAINED: usize = 16;
+const CANDLE_EVENT_BUFFER: usize = 10000;
+const METRICS_PREFIX: &str = "trading_bot.ohlcv";
+
+/// Candle aggregation error types
+#[derive(Error, Debug)]
+pub enum CandleError {
+    #[error("invalid interval: {0}")]
+    InvalidInterval(String),
+    #[error("invalid tick: {0}")]
+    InvalidTick(String),
+    #[error("persistence error: {0}")]
+    PersistenceError(String),
+}
+
+/// Supported candle intervals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+pub enum CandleInterval {
+    #[serde(rename = "1s")]
+    OneSecond,
+    #[serde(rename = "1m")]
+    OneMinute,
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+}
+
+impl CandleInterval {
+    /// All intervals maintained by default
+    pub const ALL: [CandleInterval; 4] = [
+        CandleInterval::OneSecond,
+        CandleInterval::OneMinute,
+        CandleInterval::FiveMinutes,
+        CandleInterval::OneHour,
+    ];
+
+    /// Interval length in seconds
+    pub fn as_secs(&self) -> i64 {
+        match self {
+            CandleInterval::OneSecond => 1,
+            CandleInterval::OneMinute => 60,
+            CandleInterval::FiveMinutes => 300,
+            CandleInterval::OneHour => 3600,
+        }
+    }
+
+    /// Interval label as used in the API and the `candles` table
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CandleInterval::OneSecond => "1s",
+            CandleInterval::OneMinute => "1m",
+            CandleInterval::FiveMinutes => "5m",
+            CandleInterval::OneHour => "1h",
+        }
+    }
+
+    /// Returns the open time of the bucket containing the timestamp
+    pub fn bucket_start(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        let secs = timestamp.timestamp();
+        let bucket = secs - secs.rem_euclid(self.as_secs());
+        Utc.timestamp_opt(bucket, 0).single().unwrap_or(timestamp)
+    }
+}
+
+impl FromStr for CandleInterval {
+    type Err = CandleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1s" => Ok(CandleInterval::OneSecond),
+            "1m" => Ok(CandleInterval::OneMinute),
+            "5m" => Ok(CandleInterval::FiveMinutes),
+            "1h" => Ok(CandleInterval::OneHour),
+            other => Err(CandleError::InvalidInterval(format!(
+                "unsupported interval: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Single price tick from the aggregated price stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceTick {
+    pub trading_pair: String,
+    pub price: Decimal,
+    pub volume: Decimal,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl From<&MarketData> for PriceTick {
+    fn from(market_data: &MarketData) -> Self {
+        Self {
+            trading_pair: market_data.trading_pair().to_string(),
+            price: market_data.price(),
+            volume: market_data.volume(),
+            timestamp: market_data.timestamp(),
+        }
+    }
+}
+
+/// OHLCV candle for a trading pair and interval
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    pub trading_pair: String,
+    pub interval: CandleInterval,
+    pub open_time: DateTime<Utc>,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub volume: Decimal,
+    pub trade_count: u64,
+    /// True when the candle was synthesized by gap filling (carry-forward close)
+    pub gap_filled: bool,
+    #[serde(skip)]
+    first_tick_at: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    last_tick_at: Option<DateTime<Utc>>,
+}
+
+impl Candle {
+    /// Creates a candle opened by a tick
+    fn from_tick(tick: &PriceTick, interval: CandleInterval) -> Self {
+        Self {
+            trading_pair: tick.trading_pair.clone(),
+            interval,
+            open_time: interval.bucket_start(tick.timestamp),
+            open: tick.price,
+            high: tick.price,
+            low: tick.price,
+            close: tick.price,
+            volume: tick.volume,
+            trade_count: 1,
+            gap_filled: false,
+            first_tick_at: Some(tick.timestamp),
+            last_tick_at: Some(tick.timestamp),
+        }
+    }
+
+    /// Creates a flat carry-forward candle with zero volume
+    pub fn carry_forward(
+        trading_pair: &str,
+        interval: CandleInterval,
+        open_time: DateTime<Utc>,
+        close: Decimal,
+    ) -> Self {
+        Self {
+            trading_pair: trading_pair.to_string(),
+            interval,
+            open_time,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: Decimal::ZERO,
+            trade_count: 0,
+            gap_filled: true,
+            first_tick_at: None,
+            last_tick_at: None,
+        }
+    }
+
+    /// Rebuilds a persisted candle
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_parts(
+        trading_pair: String,
+        interval: CandleInterval,
+        open_time: DateTime<Utc>,
+        open: Decimal,
+        high: Decimal,
+        low: Decimal,
+        close: Decimal,
+        volume: Decimal,
+        trade_count: u64,
+    ) -> Self {
+        Self {
+            trading_pair,
+            interval,
+            open_time,
+            open,
+            high,
+            low,
+            close,
+            volume,
+            trade_count,
+            gap_filled: false,
+            first_tick_at: None,
+            last_tick_at: None,
+        }
+    }
+
+    /// Close time of the candle (exclusive)
+    pub fn close_time(&self) -> DateTime<Utc> {
+        self.open_time + ChronoDuration::seconds(self.interval.as_secs())
+    }
+
+    /// Applies a tick, respecting tick time ordering for open and close
+    fn apply(&mut self, tick: &PriceTick) {
+        self.high = self.high.max(tick.price);
+        self.low = self.low.min(tick.price);
+        self.volume += tick.volume;
+        self.trade_count += 1;
+        self.gap_filled = false;
+
+        if self.first_tick_at.map_or(true, |t| tick.timestamp < t) {
+            self.open = tick.price;
+            self.first_tick_at = Some(tick.timestamp);
+        }
+        if self.last_tick_at.map_or(true, |t| tick.timestamp >= t) {
+            self.close = tick.price;
+            self.last_tick_at = Some(tick.timestamp);
+        }
+    }
+}
+
+/// Candle lifecycle events published on the WS `candles` channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CandleEvent {
+    Closed { candle: Candle },
+    Correction { candle: Candle },
+}
+
+/// Tracks the rolling state of one pair/interval series
+#[derive(Debug, Default)]
+struct CandleSeries {
+    open: Option<Candle>,
+    recently_closed: VecDeque<(Candle, DateTime<Utc>)>,
+}
+
+/// In-memory OHLCV aggregator with late-tick corrections
+#[derive(Debug)]
+pub struct CandleAggregator {
+    intervals: Vec<CandleInterval>,
+    grace_period: ChronoDuration,
+    series: RwLock<HashMap<(String, CandleInterval), CandleSeries>>,
+    pending_flush: RwLock<HashMap<(String, CandleInterval, DateTime<Utc>), Candle>>,
+    event_tx: broadcast::Sender<CandleEvent>,
+}
+
+impl CandleAggregator {
+    /// Creates new aggregator for the given intervals
+    pub fn new(intervals: Vec<CandleInterval>, grace_period: ChronoDuration) -> Self {
+        let (event_tx, _) = broadcast::channel(CANDLE_EVENT_BUFFER);
+
+        Self {
+            intervals,
+            grace_period,
+            series: RwLock::new(HashMap::new()),
+            pending_flush: RwLock::new(HashMap::new()),
+            event_tx,
+        }
+    }
+
+    /// Subscribes to candle close and correction events
+    pub fn subscribe(&self) -> broadcast::Receiver<CandleEvent> {
+        self.event_tx.subscribe()
+    }
+
+    /// Ingests a tick received now
+    pub fn ingest(&self, tick: &PriceTick) -> Result<Vec<CandleEvent>, CandleError> {
+        self.ingest_at(tick, current_timestamp())
+    }
+
+    /// Ingests a tick with an explicit arrival time, returning emitted events
+    pub fn ingest_at(
+        &self,
+        tick: &PriceTick,
+        received_at: DateTime<Utc>,
+    ) -> Result<Vec<CandleEvent>, CandleError> {
+        // Validate tick
+        if tick.price <= Decimal::ZERO {
+            return Err(CandleError::InvalidTick("price must be positive".to_string()));
+        }
+        if tick.volume < Decimal::ZERO {
+            return Err(CandleError::InvalidTick("volume must not be negative".to_string()));
+        }
+
+        let mut events = Vec::new();
+        let mut series_map = self.series.write();
+
+        for interval in &self.intervals {
+            let key = (tick.trading_pair.clone(), *interval);
+            let series = series_map.entry(key).or_default();
+            let bucket = interval.bucket_start(tick.timestamp);
+
+            match series.open.as_mut() {
+                None => {
+                    series.open = Some(Candle::from_tick(tick, *interval));
+                }
+                Some(open) if open.open_time == bucket => {
+                    open.apply(tick);
+                }
+                Some(open) if open.open_time < bucket => {
+                    // Roll the open candle over
+                    let closed = std::mem::replace(open, Candle::from_tick(tick, *interval));
+                    series.recently_closed.push_back((closed.clone(), received_at));
+                    while series.recently_closed.len() > MAX_CLOSED_CANDLES_RETAINED {
+                        series.recently_closed.pop_front();
+                    }
+                    self.queue_flush(&closed);
+                    events.push(CandleEvent::Closed { candle: closed });
+                }
+                Some(_) => {
+                    // Late tick for an already closed candle
+                    let grace_period = self.grace_period;
+                    let target = series.recently_closed.iter_mut().find(|(candle, _)| {
+                        candle.open_time == bucket
+                    });
+
+                    match target {
+                        Some((candle, closed_at))
+                            if received_at - *closed_at <= grace_period =>
+                        {
+                            candle.apply(tick);
+                            let corrected = candle.clone();
+                            self.queue_flush(&corrected);
+                            counter!(format!("{}.corrections", METRICS_PREFIX), 1);
+                            events.push(CandleEvent::Correction { candle: corrected });
+                        }
+                        _ => {
+                            counter!(format!("{}.late_ticks_dropped", METRICS_PREFIX), 1);
+                            debug!(
+                                trading_pair = %tick.trading_pair,
+                                interval = interval.as_str(),
+                                "Dropping tick outside correction grace window"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        drop(series_map);
+
+        // Publish events to WS subscribers
+        for event in &events {
+            let _ = self.event_tx.send(event.clone());
+        }
+
+        counter!(format!("{}.ticks", METRICS_PREFIX), 1);
+        Ok(events)
+    }
+
+    /// Returns the currently open candle for a pair and interval
+    pub fn current_candle(&self, trading_pair: &str, interval: CandleInterval) -> Option<Candle> {
+        self.series
+            .read()
+            .get(&(trading_pair.to_string(), interval))
+            .and_then(|series| series.open.clone())
+    }
+
+    /// Returns recently closed candles still held in memory
+    pub fn recent_candles(&self, trading_pair: &str, interval: CandleInterval) -> Vec<Candle> {
+        self.series
+            .read()
+            .get(&(trading_pair.to_string(), interval))
+            .map(|series| {
+                series
+                    .recently_closed
+                    .iter()
+                    .map(|(candle, _)| candle.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Drains candles waiting to be persisted
+    pub fn take_pending(&self) -> Vec<Candle> {
+        let mut pending = self.pending_flush.write();
+        let mut candles: Vec<Candle> = pending.drain().map(|(_, candle)| candle).collect();
+        candles.sort_by(|a, b| a.open_time.cmp(&b.open_time));
+        candles
+    }
+
+    fn queue_flush(&self, candle: &Candle) {
+        self.pending_flush.write().insert(
+            (candle.trading_pair.clone(), candle.interval, candle.open_time),
+            candle.clone(),
+        );
+    }
+
+    /// Consumes the price stream until the sender is dropped
+    pub fn spawn_ingest_task(
+        self: Arc<Self>,
+        mut ticks: broadcast::Receiver<PriceTick>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match ticks.recv().await {
+                    Ok(tick) => {
+                        if let Err(e) = self.ingest(&tick) {
+                            warn!(trading_pair = %tick.trading_pair, "Rejected tick: {}", e);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        counter!(format!("{}.ticks_lagged", METRICS_PREFIX), skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+            info!("Price stream closed, stopping candle aggregation");
+        })
+    }
+
+    /// Periodically flushes closed and corrected candles to the database
+    pub fn spawn_flush_task(
+        self: Arc<Self>,
+        repository: Arc<CandleRepository>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = interval(Duration::from_millis(CANDLE_FLUSH_INTERVAL_MS));
+            loop {
+                ticker.tick().await;
+                let pending = self.take_pending();
+                if pending.is_empty() {
+                    continue;
+                }
+
+                let count = pending.len();
+                match repository.upsert_candles(&pending).await {
+                    Ok(_) => {
+                        histogram!(format!("{}.flush_batch_size", METRICS_PREFIX), count as f64);
+                    }
+                    Err(e) => {
+                        error!("Failed to flush {} candles: {}", count, e);
+                        // Requeue for the next flush
+                        for candle in &pending {
+                            self.queue_flush(candle);
+                        }
+                    }
+                }
+                gauge!(
+                    format!("{}.pending_flush", METRICS_PREFIX),
+                    self.pending_flush.read().len() as f64
+                );
+            }
+        })
+    }
+}
+
+impl Default for CandleAggregator {
+    fn default() -> Self {
+        Self::new(
+            CandleInterval::ALL.to_vec(),
+            ChronoDuration::milliseconds(LATE_TICK_GRACE_MS),
+        )
+    }
+}
+
+/// Fills gaps in a sorted candle series by carrying the previous close forward
+#[instrument(skip(candles))]
+pub fn fill_gaps(
+    candles: Vec<Candle>,
+    interval: CandleInterval,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<Candle> {
+    let Some(first) = candles.first() else {
+        return candles;
+    };
+    let trading_pair = first.trading_pair.clone();
+    let step = ChronoDuration::seconds(interval.as_secs());
+
+    let mut by_time: HashMap<DateTime<Utc>, Candle> = candles
+        .into_iter()
+        .map(|candle| (candle.open_time, candle))
+        .collect();
+
+    let mut filled = Vec::with_capacity(by_time.len());
+    let mut last_close: Option<Decimal> = None;
+    let mut cursor = interval.bucket_start(from);
+
+    while cursor < to {
+        match by_time.remove(&cursor) {
+            Some(candle) => {
+                last_close = Some(candle.close);
+                filled.push(candle);
+            }
+            None => {
+                // Only fill once a real close is known
+                if let Some(close) = last_close {
+                    filled.push(Candle::carry_forward(&trading_pair, interval, cursor, close));
+                }
+            }
+        }
+        cursor = cursor + step;
+    }
+
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn ts(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_100 + secs, 0).unwrap()
+    }
+
+    fn tick(secs: i64, price: Decimal, volume: Decimal) -> PriceTick {
+        PriceTick {
+            trading_pair: "SOL/USDC".to_string(),
+            price,
+            volume,
+            timestamp: ts(secs),
+        }
+    }
+
+    #[test]
+    fn test_deterministic_ohlcv() {
+        // ts(0) is aligned to a 5m boundary
+        let aggregator = CandleAggregator::new(
+            vec![CandleInterval::OneMinute, CandleInterval::FiveMinutes],
+            ChronoDuration::milliseconds(LATE_TICK_GRACE_MS),
+        );
+
+        let ticks = vec![
+            tick(0, dec!(100), dec!(1)),
+            tick(10, dec!(105), dec!(2)),
+            tick(30, dec!(95), dec!(1)),
+            tick(59, dec!(101), dec!(3)),
+            tick(61, dec!(102), dec!(1)),
+            tick(119, dec!(99), dec!(2)),
+            tick(120, dec!(103), dec!(1)),
+        ];
+        let mut events = Vec::new();
+        for t in &ticks {
+            events.extend(aggregator.ingest_at(t, t.timestamp).unwrap());
+        }
+
+        let closed: Vec<Candle> = events
+            .into_iter()
+            .filter_map(|e| match e {
+                CandleEvent::Closed { candle } => Some(candle),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(closed.len(), 2);
+
+        let first = &closed[0];
+        assert_eq!(first.interval, CandleInterval::OneMinute);
+        assert_eq!(first.open_time, ts(0));
+        assert_eq!(
+            (first.open, first.high, first.low, first.close, first.volume),
+            (dec!(100), dec!(105), dec!(95), dec!(101), dec!(7))
+        );
+        assert_eq!(first.trade_count, 4);
+
+        let second = &closed[1];
+        assert_eq!(second.open_time, ts(60));
+        assert_eq!(
+            (second.open, second.high, second.low, second.close, second.volume),
+            (dec!(102), dec!(102), dec!(99), dec!(99), dec!(3))
+        );
+
+        // 5m candle is still open and holds every tick
+        let five = aggregator
+            .current_candle("SOL/USDC", CandleInterval::FiveMinutes)
+            .unwrap();
+        assert_eq!(
+            (five.open, five.high, five.low, five.close, five.volume),
+            (dec!(100), dec!(105), dec!(95), dec!(103), dec!(11))
+        );
+        assert_eq!(five.trade_count, 7);
+    }
+
+    #[test]
+    fn test_late_tick_correction() {
+        let aggregator = CandleAggregator::new(
+            vec![CandleInterval::OneMinute],
+            ChronoDuration::milliseconds(LATE_TICK_GRACE_MS),
+        );
+        let mut rx = aggregator.subscribe();
+
+        aggregator.ingest_at(&tick(0, dec!(100), dec!(1)), ts(0)).unwrap();
+        aggregator.ingest_at(&tick(50, dec!(101), dec!(1)), ts(50)).unwrap();
+        aggregator.ingest_at(&tick(60, dec!(102), dec!(1)), ts(60)).unwrap();
+
+        // Late tick for the first minute arrives 2s after it closed
+        let events = aggregator
+            .ingest_at(&tick(55, dec!(110), dec!(4)), ts(62))
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            CandleEvent::Correction { candle } => {
+                assert_eq!(candle.open_time, ts(0));
+                assert_eq!(
+                    (candle.open, candle.high, candle.low, candle.close, candle.volume),
+                    (dec!(100), dec!(110), dec!(100), dec!(110), dec!(6))
+                );
+            }
+            other => panic!("expected correction, got {:?}", other),
+        }
+
+        // Closed event followed by the correction on the WS channel
+        assert!(matches!(rx.try_recv().unwrap(), CandleEvent::Closed { .. }));
+        assert!(matches!(rx.try_recv().unwrap(), CandleEvent::Correction { .. }));
+
+        // The corrected candle replaces the pending write
+        let pending = aggregator.take_pending();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].high, dec!(110));
+
+        // Outside the grace window the tick is dropped
+        let events = aggregator
+            .ingest_at(&tick(58, dec!(120), dec!(1)), ts(70))
+            .unwrap();
+        assert!(events.is_empty());
+        let recent = aggregator.recent_candles("SOL/USDC", CandleInterval::OneMinute);
+        assert_eq!(recent[0].high, dec!(110));
+    }
+
+    #[test]
+    fn test_gap_filling_carries_close_forward() {
+        let candles = vec![
+            Candle::from_parts(
+                "SOL/USDC".to_string(),
+                CandleInterval::OneMinute,
+                ts(0),
+                dec!(100),
+                dec!(105),
+                dec!(95),
+                dec!(101),
+                dec!(7),
+                4,
+            ),
+            Candle::from_parts(
+                "SOL/USDC".to_string(),
+                CandleInterval::OneMinute,
+                ts(180),
+                dec!(102),
+                dec!(103),
+                dec!(100),
+                dec!(100),
+                dec!(2),
+                2,
+            ),
+        ];
+
+        let filled = fill_gaps(candles, CandleInterval::OneMinute, ts(0), ts(240));
+        assert_eq!(filled.len(), 4);
+        assert!(!filled[0].gap_filled);
+        assert!(filled[1].gap_filled && filled[2].gap_filled);
+        assert_eq!(filled[1].open, dec!(101));
+        assert_eq!(filled[2].close, dec!(101));
+        assert_eq!(filled[2].volume, Decimal::ZERO);
+        assert!(!filled[3].gap_filled);
+    }
+
+    #[test]
+    fn test_interval_parsing() {
+        assert_eq!("5m".parse::<CandleInterval>().unwrap(), CandleInterval::FiveMinutes);
+        assert!("2m".parse::<CandleInterval>().is_err());
+        assert_eq!(CandleInterval::OneMinute.bucket_start(ts(125)), ts(120));
+        assert_eq!(CandleInterval::OneHour.bucket_start(ts(125)).timestamp() % 3600, 0);
+    }
+}
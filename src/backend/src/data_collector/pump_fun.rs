@@ -6,6 +6,7 @@
 //! - tracing = "0.1"
 //! - metrics = "0.20"
 //! - r2d2 = "0.8"
+//! - base64 = "0.21"
 
 use std::{
     collections::HashMap,
@@ -17,29 +18,56 @@ use std::{
 };
 
 use async_trait::async_trait;
-use metrics::{counter, gauge, histogram};
+use base64::Engine;
+use lazy_static::lazy_static;
+use metrics::{counter, gauge, register_counter, register_histogram, Counter, Histogram};
 use r2d2::Pool;
+use rust_decimal::Decimal;
+use sha2::{Digest, Sha256};
 use tokio::{
-    sync::RwLock,
+    sync::{mpsc, RwLock},
     time::{sleep, timeout},
 };
 use tracing::{debug, error, info, instrument, warn};
 
 use crate::{
-    data_collector::{Collector, CollectorError, HealthStatus},
+    data_collector::{trades::PublicTrade, Collector, CollectorConfig, CollectorError, HealthStatus},
     models::market::{MarketData, validate_price, validate_volume},
+    risk_manager::exposure::TradeSide,
     utils::{
-        solana::SolanaClient,
+        metric_handles::AggregatedCounter,
+        solana::{SolanaClient, TransactionLogs},
         time::{current_timestamp, is_valid_market_timestamp},
     },
 };
 
 // Program constants
-const PUMP_FUN_PROGRAM_ID: &str = "pf1xyPydBXyPxGZwpvpuXNB1K3zMqCVbJKfihvQhKGE";
-const MARKET_REFRESH_INTERVAL_MS: u64 = 100;
 const MAX_RETRIES: u8 = 3;
 const BACKOFF_BASE_MS: u64 = 50;
-const CONNECTION_POOL_SIZE: u32 = 10;
+const TRADE_POLL_LIMIT: usize = 100;
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+// Bonding curve trades settle in SOL against 6-decimal tokens
+const LAMPORTS_PER_SOL: i64 = 1_000_000_000;
+const TOKEN_UNITS: i64 = 1_000_000;
+const QUOTE_ASSET: &str = "SOL";
+
+// Metric handles for the per-market collection path, registered once on first use
+lazy_static! {
+    static ref COLLECTION_TIME: Histogram = register_histogram!("pump_fun_collector.collection_time");
+    static ref PARSING_TIME: Histogram = register_histogram!("pump_fun_collector.parsing_time");
+    static ref SUCCESSFUL_COLLECTIONS: &'static AggregatedCounter =
+        AggregatedCounter::register("pump_fun_collector.successful_collections".to_string());
+    static ref PRICE_ANOMALIES: Counter = register_counter!("pump_fun_collector.price_anomalies");
+    static ref CONNECTION_ERRORS: Counter = register_counter!("pump_fun_collector.connection_errors");
+    static ref TRADES_POLLED: Counter = register_counter!("pump_fun_collector.trades_polled");
+    /// Anchor event discriminator of the program's trade event
+    static ref TRADE_EVENT_DISCRIMINATOR: [u8; 8] = {
+        let digest = Sha256::digest(b"event:TradeEvent");
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&digest[..8]);
+        discriminator
+    };
+}
 
 /// Enhanced error types for Pump Fun data collection
 #[derive(Debug, thiserror::Error)]
@@ -79,13 +107,26 @@ pub struct PumpFunCollector {
     market_states: RwLock<HashMap<String, MarketState>>,
     is_running: AtomicBool,
     health_monitor: RwLock<HealthMonitor>,
+    /// Program owning market accounts on the configured cluster
+    program_id: solana_sdk::pubkey::Pubkey,
+    refresh_interval: Duration,
+    /// Receives trades decoded from program transactions when trade polling is enabled
+    trades_tx: Option<mpsc::Sender<PublicTrade>>,
+    /// Newest program transaction already polled for trades
+    last_trade_signature: RwLock<Option<String>>,
+    /// Receives validated market data when the bot consumes collector data
+    market_data_tx: Option<mpsc::Sender<MarketData>>,
 }
 
 impl PumpFunCollector {
     /// Creates a new PumpFunCollector instance with connection pooling
-    pub fn new(solana_client: Arc<SolanaClient>) -> Result<Self, CollectorError> {
+    pub fn new(solana_client: Arc<SolanaClient>, config: CollectorConfig) -> Result<Self, CollectorError> {
+        let program_id = config.endpoints.pump_fun_program_id.parse().map_err(|e| {
+            CollectorError::ConnectionError(format!("invalid Pump Fun program id: {}", e))
+        })?;
+        let pool_size = config.connection_pool_size as u32;
         let pool_config = r2d2::Pool::builder()
-            .max_size(CONNECTION_POOL_SIZE)
+            .max_size(pool_size)
             .connection_timeout(Duration::from_secs(5))
             .build_unchecked(solana_client);
 
@@ -99,15 +140,66 @@ impl PumpFunCollector {
                 validation_errors: 0,
                 average_latency: Duration::from_millis(0),
             }),
+            program_id,
+            refresh_interval: config.collection_interval,
+            trades_tx: config.public_trades_tx.clone(),
+            last_trade_signature: RwLock::new(None),
+            market_data_tx: config.market_data_tx.clone(),
         };
 
         // Initialize metrics
-        gauge!("pump_fun_collector.pool_size", CONNECTION_POOL_SIZE as f64);
+        gauge!("pump_fun_collector.pool_size", pool_size as f64);
         counter!("pump_fun_collector.init", 1);
 
         Ok(collector)
     }
 
+    /// Decodes trades from program transactions since the last poll and forwards them
+    #[instrument(skip(self))]
+    async fn poll_trades(&self) -> Result<usize, PumpFunError> {
+        let Some(trades_tx) = &self.trades_tx else {
+            return Ok(0);
+        };
+
+        let conn = timeout(
+            Duration::from_secs(5),
+            self.connection_pool.get(),
+        )
+        .await
+        .map_err(|e| PumpFunError::ConnectionError(e.to_string()))?
+        .map_err(|e| PumpFunError::ConnectionError(e.to_string()))?;
+
+        let until = self.last_trade_signature.read().await.clone();
+        let transactions = conn
+            .get_recent_transaction_logs(&self.program_id, until.as_deref(), TRADE_POLL_LIMIT)
+            .await
+            .map_err(|e| {
+                CONNECTION_ERRORS.increment(1);
+                PumpFunError::ConnectionError(e.to_string())
+            })?;
+
+        let Some(newest) = transactions.first() else {
+            return Ok(0);
+        };
+        *self.last_trade_signature.write().await = Some(newest.signature.clone());
+
+        // Signatures come newest first; forward in execution order
+        let mut forwarded = 0;
+        for transaction in transactions.iter().rev() {
+            for trade in parse_trade_logs(transaction) {
+                if trades_tx.send(trade).await.is_err() {
+                    return Err(PumpFunError::PerformanceError(
+                        "public trade channel closed".to_string(),
+                    ));
+                }
+                forwarded += 1;
+            }
+        }
+        TRADES_POLLED.increment(forwarded as u64);
+
+        Ok(forwarded)
+    }
+
     /// Collects market data with caching and parallel processing
     #[instrument(skip(self, market_account))]
     async fn collect_market_data(
@@ -129,6 +221,12 @@ impl PumpFunCollector {
         let mut retries = 0;
         let account_data = loop {
             match conn.get_account(&market_account).await {
+                Ok(Some(account)) if account.owner != self.program_id => {
+                    return Err(PumpFunError::MarketDataError(format!(
+                        "market account not owned by program {}",
+                        self.program_id
+                    )))
+                }
                 Ok(Some(account)) => break account,
                 Ok(None) => {
                     return Err(PumpFunError::MarketDataError(
@@ -141,7 +239,7 @@ impl PumpFunCollector {
                     sleep(Duration::from_millis(BACKOFF_BASE_MS * 2u64.pow(retries as u32))).await;
                 }
                 Err(e) => {
-                    counter!("pump_fun_collector.connection_errors", 1);
+                    CONNECTION_ERRORS.increment(1);
                     return Err(PumpFunError::ConnectionError(e.to_string()));
                 }
             }
@@ -169,8 +267,14 @@ impl PumpFunCollector {
 
         // Record metrics
         let elapsed = start_time.elapsed();
-        histogram!("pump_fun_collector.collection_time", elapsed);
-        counter!("pump_fun_collector.successful_collections", 1);
+        COLLECTION_TIME.record(elapsed);
+        SUCCESSFUL_COLLECTIONS.increment(1);
+
+        if let Some(market_data_tx) = &self.market_data_tx {
+            if market_data_tx.send(market_data.clone()).await.is_err() {
+                warn!("Market data channel closed");
+            }
+        }
 
         Ok(market_data)
     }
@@ -214,13 +318,13 @@ impl PumpFunCollector {
                     "Large price change detected: {}% for pair {}",
                     price_change_pct, market_info.trading_pair
                 );
-                counter!("pump_fun_collector.price_anomalies", 1);
+                PRICE_ANOMALIES.increment(1);
             }
         }
 
         // Record parsing metrics
         let elapsed = start_time.elapsed();
-        histogram!("pump_fun_collector.parsing_time", elapsed);
+        PARSING_TIME.record(elapsed);
 
         Ok(market_data)
     }
@@ -244,7 +348,11 @@ impl Collector for PumpFunCollector {
                     error!("Market collection error: {}", e);
                     counter!("pump_fun_collector.collection_errors", 1);
                 }
-                sleep(Duration::from_millis(MARKET_REFRESH_INTERVAL_MS)).await;
+                if let Err(e) = collector.poll_trades().await {
+                    error!("Trade polling error: {}", e);
+                    counter!("pump_fun_collector.trade_poll_errors", 1);
+                }
+                sleep(collector.refresh_interval).await;
             }
         });
 
@@ -266,6 +374,13 @@ impl Collector for PumpFunCollector {
             is_healthy: monitor.connection_errors < 100 && monitor.validation_errors < 50,
             connection_count: self.connection_pool.state().connections as usize,
             last_collection_latency: monitor.average_latency,
+            last_collection_time: self
+                .market_states
+                .read()
+                .await
+                .values()
+                .map(|state| state.last_update)
+                .max(),
             error_count: monitor.connection_errors + monitor.validation_errors,
             last_error: None,
         })
@@ -279,4 +394,105 @@ struct PumpFunMarketInfo {
     price: rust_decimal::Decimal,
     volume: rust_decimal::Decimal,
     timestamp: i64,
-}
\ No newline at end of file
+}
+
+/// Trade event emitted by the bonding curve program on every buy and sell
+#[derive(Debug, borsh::BorshDeserialize)]
+struct PumpFunTradeEvent {
+    mint: [u8; 32],
+    sol_amount: u64,
+    token_amount: u64,
+    is_buy: bool,
+    user: [u8; 32],
+    timestamp: i64,
+}
+
+/// Decodes the trade events in a transaction's logs; the trade id is the signature
+/// with the event's position, so a re-polled transaction yields the same ids
+fn parse_trade_logs(transaction: &TransactionLogs) -> Vec<PublicTrade> {
+    transaction
+        .logs
+        .iter()
+        .filter_map(|log| log.strip_prefix(PROGRAM_DATA_PREFIX))
+        .filter_map(|data| base64::engine::general_purpose::STANDARD.decode(data).ok())
+        .filter(|bytes| bytes.len() > 8 && bytes[..8] == TRADE_EVENT_DISCRIMINATOR[..])
+        .enumerate()
+        .filter_map(|(index, bytes)| {
+            let event: PumpFunTradeEvent =
+                match borsh::BorshDeserialize::deserialize(&mut &bytes[8..]) {
+                    Ok(event) => event,
+                    Err(e) => {
+                        debug!("Skipping undecodable trade event: {}", e);
+                        return None;
+                    }
+                };
+            if event.token_amount == 0 {
+                return None;
+            }
+
+            let sol = Decimal::new(event.sol_amount as i64, 0) / Decimal::new(LAMPORTS_PER_SOL, 0);
+            let tokens = Decimal::new(event.token_amount as i64, 0) / Decimal::new(TOKEN_UNITS, 0);
+            let timestamp = chrono::DateTime::from_timestamp(event.timestamp, 0)
+                .or(transaction.block_time)?;
+            let side = if event.is_buy { TradeSide::Buy } else { TradeSide::Sell };
+
+            PublicTrade::new(
+                format!("{}/{}", solana_sdk::pubkey::Pubkey::new_from_array(event.mint), QUOTE_ASSET),
+                "pump_fun".to_string(),
+                sol / tokens,
+                tokens,
+                side,
+                timestamp,
+            )
+            .map_err(|e| warn!("Rejected Pump Fun trade in {}: {}", transaction.signature, e))
+            .ok()
+            .map(|trade| trade.with_trade_id(format!("{}:{}", transaction.signature, index)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn trade_event_log(sol_amount: u64, token_amount: u64, is_buy: bool, timestamp: i64) -> String {
+        let mut bytes = TRADE_EVENT_DISCRIMINATOR.to_vec();
+        bytes.extend_from_slice(&[7u8; 32]);
+        bytes.extend_from_slice(&sol_amount.to_le_bytes());
+        bytes.extend_from_slice(&token_amount.to_le_bytes());
+        bytes.push(is_buy as u8);
+        bytes.extend_from_slice(&[9u8; 32]);
+        bytes.extend_from_slice(&timestamp.to_le_bytes());
+        // Reserve fields trailing the decoded prefix
+        bytes.extend_from_slice(&[0u8; 16]);
+        format!("{}{}", PROGRAM_DATA_PREFIX, base64::engine::general_purpose::STANDARD.encode(bytes))
+    }
+
+    #[test]
+    fn test_trade_events_decoded_from_logs() {
+        let now = current_timestamp().timestamp();
+        let transaction = TransactionLogs {
+            signature: "3vZk".to_string(),
+            block_time: None,
+            logs: vec![
+                "Program log: Instruction: Buy".to_string(),
+                trade_event_log(500_000_000, 2_000_000_000, true, now),
+                format!("{}AAAA", PROGRAM_DATA_PREFIX),
+                trade_event_log(250_000_000, 1_000_000_000, false, now),
+            ],
+        };
+
+        let trades = parse_trade_logs(&transaction);
+        assert_eq!(trades.len(), 2);
+        assert!(trades[0].pair.ends_with("/SOL"));
+        assert_eq!(trades[0].price, dec!(0.00025));
+        assert_eq!(trades[0].size, dec!(2000));
+        assert_eq!(trades[0].side, TradeSide::Buy);
+        assert_eq!(trades[1].side, TradeSide::Sell);
+
+        // Re-polling the same transaction yields the same keys
+        let keys: Vec<String> = parse_trade_logs(&transaction).iter().map(|t| t.dedup_key()).collect();
+        assert_eq!(keys, vec!["pump_fun:3vZk:0", "pump_fun:3vZk:1"]);
+    }
+}
@@ -0,0 +1,690 @@
+//! Per-source market data quality scoring. Each (exchange, pair) feed is scored on staleness,
+//! gap frequency, distance from the aggregate mid and anomalous jumps; sources scoring below
+//! the demotion threshold are left out of the aggregate mid until they recover for a
+//! sustained period.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+//! - utoipa = "3.5"
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use parking_lot::{Mutex, RwLock as SyncRwLock};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tokio::sync::{broadcast, RwLock};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::api::WebhookDispatcher;
+use crate::db::repositories::DataQualityRepository;
+use crate::execution_engine::order_book::OrderBookSnapshot;
+use crate::execution_engine::readiness::PriceActivity;
+use crate::models::strategy::Strategy;
+use crate::models::webhook::{DataSourceStatusEvent, WebhookEvent, WebhookEventType};
+use crate::utils::time::to_chrono;
+
+// Data quality constants
+const METRICS_PREFIX: &str = "trading_bot.data_quality";
+const TRANSITION_CHANNEL_CAPACITY: usize = 64;
+const MAX_TICKS_PER_SOURCE: usize = 10_000;
+const STALENESS_WEIGHT: f64 = 0.3;
+const GAP_WEIGHT: f64 = 0.2;
+const SPREAD_WEIGHT: f64 = 0.3;
+const ANOMALY_WEIGHT: f64 = 0.2;
+const DEFAULT_EVALUATION_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_PERSIST_INTERVAL: Duration = Duration::from_secs(3600);
+const DEFAULT_SCORE_WINDOW: Duration = Duration::from_secs(900);
+const DEFAULT_MAX_TICK_GAP: Duration = Duration::from_secs(5);
+const DEFAULT_STALE_AFTER: Duration = Duration::from_secs(60);
+const DEFAULT_MAX_DEVIATION_BPS: f64 = 250.0;
+const DEFAULT_ANOMALY_JUMP_PCT: f64 = 10.0; // matches the Pump Fun collector's anomaly check
+const DEFAULT_DEMOTION_THRESHOLD: f64 = 0.6;
+const DEFAULT_RECOVERY_PERIOD: Duration = Duration::from_secs(600);
+
+/// Scoring windows and demotion thresholds
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityConfig {
+    pub evaluation_interval: Duration,
+    pub persist_interval: Duration,
+    /// Ticks older than this no longer count towards gap, spread and anomaly rates
+    pub score_window: Duration,
+    /// Longer than this between ticks counts as a gap
+    pub max_tick_gap: Duration,
+    /// Staleness score reaches zero once the last tick is this old
+    pub stale_after: Duration,
+    /// Distance from the aggregate mid beyond which a tick is unreasonable
+    pub max_deviation_bps: f64,
+    pub anomaly_jump_pct: f64,
+    pub demotion_threshold: f64,
+    /// Time a demoted source must stay above the threshold before re-promotion
+    pub recovery_period: Duration,
+}
+
+impl Default for QualityConfig {
+    fn default() -> Self {
+        Self {
+            evaluation_interval: DEFAULT_EVALUATION_INTERVAL,
+            persist_interval: DEFAULT_PERSIST_INTERVAL,
+            score_window: DEFAULT_SCORE_WINDOW,
+            max_tick_gap: DEFAULT_MAX_TICK_GAP,
+            stale_after: DEFAULT_STALE_AFTER,
+            max_deviation_bps: DEFAULT_MAX_DEVIATION_BPS,
+            anomaly_jump_pct: DEFAULT_ANOMALY_JUMP_PCT,
+            demotion_threshold: DEFAULT_DEMOTION_THRESHOLD,
+            recovery_period: DEFAULT_RECOVERY_PERIOD,
+        }
+    }
+}
+
+/// Whether a source contributes to the aggregate mid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceStatus {
+    Promoted,
+    Demoted,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Tick {
+    at: DateTime<Utc>,
+    gap: bool,
+    deviates: bool,
+    anomaly: bool,
+}
+
+#[derive(Debug)]
+struct SourceQuality {
+    last_mid: Decimal,
+    last_tick_at: DateTime<Utc>,
+    ticks: VecDeque<Tick>,
+    status: SourceStatus,
+    demoted_since: Option<DateTime<Utc>>,
+    /// Start of the current run above the threshold while demoted
+    recovering_since: Option<DateTime<Utc>>,
+    score: SourceScoreBreakdown,
+}
+
+/// Component scores between 0 (unusable) and 1 (healthy)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, ToSchema)]
+pub struct SourceScoreBreakdown {
+    pub score: f64,
+    pub staleness: f64,
+    pub gap_frequency: f64,
+    pub spread: f64,
+    pub anomaly_rate: f64,
+}
+
+impl SourceScoreBreakdown {
+    const HEALTHY: Self = Self {
+        score: 1.0,
+        staleness: 1.0,
+        gap_frequency: 1.0,
+        spread: 1.0,
+        anomaly_rate: 1.0,
+    };
+}
+
+/// Current quality of one (exchange, pair) feed
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct SourceScore {
+    pub exchange: String,
+    pub trading_pair: String,
+    #[serde(flatten)]
+    pub breakdown: SourceScoreBreakdown,
+    pub status: SourceStatus,
+    pub last_tick_at: DateTime<Utc>,
+    pub demoted_since: Option<DateTime<Utc>>,
+}
+
+/// Demotion or re-promotion of a source
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityTransition {
+    pub exchange: String,
+    pub trading_pair: String,
+    pub status: SourceStatus,
+    pub score: f64,
+    pub affected_strategies: Vec<String>,
+    pub at: DateTime<Utc>,
+}
+
+/// Scores every market data source and demotes those that fall below the threshold
+#[derive(Debug)]
+pub struct DataQualityMonitor {
+    config: QualityConfig,
+    strategies: Arc<RwLock<HashMap<String, Strategy>>>,
+    sources: Mutex<HashMap<(String, String), SourceQuality>>,
+    transitions: broadcast::Sender<QualityTransition>,
+    repository: SyncRwLock<Option<Arc<DataQualityRepository>>>,
+    webhooks: SyncRwLock<Option<Arc<WebhookDispatcher>>>,
+}
+
+impl DataQualityMonitor {
+    pub fn new(config: QualityConfig, strategies: Arc<RwLock<HashMap<String, Strategy>>>) -> Self {
+        let (transitions, _) = broadcast::channel(TRANSITION_CHANNEL_CAPACITY);
+        Self {
+            config,
+            strategies,
+            sources: Mutex::new(HashMap::new()),
+            transitions,
+            repository: SyncRwLock::new(None),
+            webhooks: SyncRwLock::new(None),
+        }
+    }
+
+    /// Persists hourly scores
+    pub fn set_repository(&self, repository: Arc<DataQualityRepository>) {
+        *self.repository.write() = Some(repository);
+    }
+
+    /// Sends demotion and re-promotion notifications through the webhook dispatcher
+    pub fn set_webhooks(&self, webhooks: Arc<WebhookDispatcher>) {
+        *self.webhooks.write() = Some(webhooks);
+    }
+
+    /// Receives demotions and re-promotions as they happen
+    pub fn subscribe(&self) -> broadcast::Receiver<QualityTransition> {
+        self.transitions.subscribe()
+    }
+
+    /// Records a mid price observed from a source
+    pub fn record_price(&self, exchange: &str, trading_pair: &str, mid: Decimal, at: DateTime<Utc>) {
+        let mut sources = self.sources.lock();
+        let key = (exchange.to_string(), trading_pair.to_string());
+
+        let previous = sources.get(&key).map(|source| (source.last_mid, source.last_tick_at));
+        let source = sources.entry(key).or_insert_with(|| SourceQuality {
+            last_mid: mid,
+            last_tick_at: at,
+            ticks: VecDeque::new(),
+            status: SourceStatus::Promoted,
+            demoted_since: None,
+            recovering_since: None,
+            score: SourceScoreBreakdown::HEALTHY,
+        });
+        source.last_mid = mid;
+        source.last_tick_at = source.last_tick_at.max(at);
+
+        let (gap, anomaly) = match previous {
+            Some((last_mid, last_at)) => (
+                at - last_at > to_chrono(self.config.max_tick_gap),
+                pct_change(last_mid, mid) > self.config.anomaly_jump_pct,
+            ),
+            None => (false, false),
+        };
+
+        let deviates = aggregate_mid_of(&sources, trading_pair, at, &self.config)
+            .map_or(false, |aggregate| deviation_bps(mid, aggregate) > self.config.max_deviation_bps);
+
+        let source = sources
+            .get_mut(&(exchange.to_string(), trading_pair.to_string()))
+            .expect("source recorded above");
+        source.ticks.push_back(Tick { at, gap, deviates, anomaly });
+        while source.ticks.len() > MAX_TICKS_PER_SOURCE {
+            source.ticks.pop_front();
+        }
+        if anomaly {
+            counter!(format!("{}.anomalies", METRICS_PREFIX), 1, "exchange" => exchange.to_string());
+        }
+    }
+
+    /// Records the mid of a fresh order book snapshot
+    pub fn record_snapshot(&self, snapshot: &OrderBookSnapshot) {
+        if snapshot.is_stale {
+            return;
+        }
+        let (Some(bid), Some(ask)) = (snapshot.bids.first(), snapshot.asks.first()) else {
+            return;
+        };
+        let mid = (bid.price + ask.price) / Decimal::TWO;
+        self.record_price(&snapshot.exchange, &snapshot.trading_pair, mid, snapshot.timestamp);
+    }
+
+    /// Median mid across promoted sources with fresh data for a pair
+    pub fn aggregate_mid(&self, trading_pair: &str, now: DateTime<Utc>) -> Option<Decimal> {
+        aggregate_mid_of(&self.sources.lock(), trading_pair, now, &self.config)
+    }
+
+    /// Ticks received for a pair since `since` across promoted sources, with the latest
+    /// tick among them
+    pub fn price_activity(&self, trading_pair: &str, since: DateTime<Utc>) -> PriceActivity {
+        let sources = self.sources.lock();
+        let promoted = sources.iter().filter(|((_, pair), source)| {
+            pair == trading_pair && source.status == SourceStatus::Promoted
+        });
+        let mut activity = PriceActivity::default();
+        for (_, source) in promoted {
+            activity.ticks += source.ticks.iter().filter(|tick| tick.at >= since).count();
+            activity.last_tick_at = activity.last_tick_at.max(Some(source.last_tick_at));
+        }
+        activity
+    }
+
+    pub fn is_demoted(&self, exchange: &str, trading_pair: &str) -> bool {
+        self.sources
+            .lock()
+            .get(&(exchange.to_string(), trading_pair.to_string()))
+            .map_or(false, |source| source.status == SourceStatus::Demoted)
+    }
+
+    /// Latest scores of every known source, as of the last evaluation
+    pub fn scores(&self) -> Vec<SourceScore> {
+        let mut scores: Vec<SourceScore> = self
+            .sources
+            .lock()
+            .iter()
+            .map(|((exchange, trading_pair), source)| SourceScore {
+                exchange: exchange.clone(),
+                trading_pair: trading_pair.clone(),
+                breakdown: source.score,
+                status: source.status,
+                last_tick_at: source.last_tick_at,
+                demoted_since: source.demoted_since,
+            })
+            .collect();
+        scores.sort_by(|a, b| (&a.trading_pair, &a.exchange).cmp(&(&b.trading_pair, &b.exchange)));
+        scores
+    }
+
+    /// Rescores every source, demoting those below the threshold and re-promoting those that
+    /// have stayed above it for the recovery period
+    pub async fn evaluate(&self, now: DateTime<Utc>) -> Vec<QualityTransition> {
+        let changed = {
+            let mut sources = self.sources.lock();
+            let mut changed = Vec::new();
+            for ((exchange, trading_pair), source) in sources.iter_mut() {
+                let window_start = now - to_chrono(self.config.score_window);
+                while source.ticks.front().map_or(false, |tick| tick.at < window_start) {
+                    source.ticks.pop_front();
+                }
+                source.score = self.score(source, now);
+                let score = source.score.score;
+
+                gauge!(
+                    format!("{}.score", METRICS_PREFIX), score,
+                    "exchange" => exchange.clone(), "trading_pair" => trading_pair.clone()
+                );
+
+                match source.status {
+                    SourceStatus::Promoted if score < self.config.demotion_threshold => {
+                        source.status = SourceStatus::Demoted;
+                        source.demoted_since = Some(now);
+                        source.recovering_since = None;
+                        changed.push((exchange.clone(), trading_pair.clone(), SourceStatus::Demoted, score));
+                    }
+                    SourceStatus::Demoted if score >= self.config.demotion_threshold => {
+                        let since = *source.recovering_since.get_or_insert(now);
+                        if now - since >= to_chrono(self.config.recovery_period) {
+                            source.status = SourceStatus::Promoted;
+                            source.demoted_since = None;
+                            source.recovering_since = None;
+                            changed.push((exchange.clone(), trading_pair.clone(), SourceStatus::Promoted, score));
+                        }
+                    }
+                    SourceStatus::Demoted => source.recovering_since = None,
+                    SourceStatus::Promoted => {}
+                }
+            }
+            changed
+        };
+
+        let mut transitions = Vec::with_capacity(changed.len());
+        for (exchange, trading_pair, status, score) in changed {
+            let affected_strategies = self.strategies_on(&exchange, &trading_pair).await;
+            let transition = QualityTransition {
+                exchange,
+                trading_pair,
+                status,
+                score,
+                affected_strategies,
+                at: now,
+            };
+            self.announce(&transition);
+            transitions.push(transition);
+        }
+
+        let demoted = self
+            .sources
+            .lock()
+            .values()
+            .filter(|source| source.status == SourceStatus::Demoted)
+            .count();
+        gauge!(format!("{}.demoted_sources", METRICS_PREFIX), demoted as f64);
+
+        transitions
+    }
+
+    /// Writes the current score of every source to the hourly history
+    pub async fn persist_scores(&self, now: DateTime<Utc>) {
+        let Some(repository) = self.repository.read().clone() else {
+            return;
+        };
+        if let Err(e) = repository.record_scores(&self.scores(), now).await {
+            warn!("Failed to persist data quality scores: {}", e);
+        }
+    }
+
+    /// Evaluates on the configured interval and persists scores every persist interval
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut evaluation = tokio::time::interval(self.config.evaluation_interval);
+            let mut persistence = tokio::time::interval(self.config.persist_interval);
+            // The first persistence tick fires immediately; skip it so the first row has data
+            persistence.tick().await;
+            loop {
+                tokio::select! {
+                    _ = evaluation.tick() => {
+                        self.evaluate(Utc::now()).await;
+                    }
+                    _ = persistence.tick() => {
+                        self.persist_scores(Utc::now()).await;
+                    }
+                }
+            }
+        })
+    }
+
+    /// Scores every fresh order book snapshot
+    pub fn spawn_market_data_listener(
+        self: Arc<Self>,
+        mut snapshots: broadcast::Receiver<OrderBookSnapshot>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match snapshots.recv().await {
+                    Ok(snapshot) => self.record_snapshot(&snapshot),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Data quality listener skipped {} snapshots", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    fn score(&self, source: &SourceQuality, now: DateTime<Utc>) -> SourceScoreBreakdown {
+        let age = (now - source.last_tick_at).to_std().unwrap_or_default();
+        let staleness = if age <= self.config.max_tick_gap {
+            1.0
+        } else {
+            let decay = self.config.stale_after.saturating_sub(self.config.max_tick_gap).as_secs_f64();
+            let overdue = (age - self.config.max_tick_gap).as_secs_f64();
+            if decay <= 0.0 { 0.0 } else { (1.0 - overdue / decay).max(0.0) }
+        };
+
+        let ticks = source.ticks.len().max(1) as f64;
+        let rate = |count: usize| 1.0 - count as f64 / ticks;
+        let gap_frequency = rate(source.ticks.iter().filter(|tick| tick.gap).count());
+        let spread = rate(source.ticks.iter().filter(|tick| tick.deviates).count());
+        let anomaly_rate = rate(source.ticks.iter().filter(|tick| tick.anomaly).count());
+
+        SourceScoreBreakdown {
+            score: STALENESS_WEIGHT * staleness
+                + GAP_WEIGHT * gap_frequency
+                + SPREAD_WEIGHT * spread
+                + ANOMALY_WEIGHT * anomaly_rate,
+            staleness,
+            gap_frequency,
+            spread,
+            anomaly_rate,
+        }
+    }
+
+    async fn strategies_on(&self, exchange: &str, trading_pair: &str) -> Vec<String> {
+        let mut affected: Vec<String> = self
+            .strategies
+            .read()
+            .await
+            .iter()
+            .filter(|(_, strategy)| {
+                strategy.parameters.exchanges.iter().any(|venue| venue == exchange)
+                    && strategy.trading_pairs.iter().any(|pair| pair == trading_pair)
+            })
+            .map(|(strategy_id, _)| strategy_id.clone())
+            .collect();
+        affected.sort();
+        affected
+    }
+
+    fn announce(&self, transition: &QualityTransition) {
+        let event_type = match transition.status {
+            SourceStatus::Demoted => {
+                warn!(
+                    exchange = %transition.exchange,
+                    trading_pair = %transition.trading_pair,
+                    score = transition.score,
+                    "Data source demoted from aggregate mid"
+                );
+                for strategy_id in &transition.affected_strategies {
+                    warn!(
+                        strategy_id = %strategy_id,
+                        "Strategy requires demoted venue {} for {}",
+                        transition.exchange, transition.trading_pair
+                    );
+                }
+                counter!(format!("{}.demotions", METRICS_PREFIX), 1);
+                WebhookEventType::DataSourceDemoted
+            }
+            SourceStatus::Promoted => {
+                info!(
+                    exchange = %transition.exchange,
+                    trading_pair = %transition.trading_pair,
+                    score = transition.score,
+                    "Data source re-promoted to aggregate mid"
+                );
+                counter!(format!("{}.promotions", METRICS_PREFIX), 1);
+                WebhookEventType::DataSourcePromoted
+            }
+        };
+
+        let _ = self.transitions.send(transition.clone());
+
+        let Some(webhooks) = self.webhooks.read().clone() else {
+            return;
+        };
+        let payload = DataSourceStatusEvent {
+            exchange: transition.exchange.clone(),
+            trading_pair: transition.trading_pair.clone(),
+            score: transition.score,
+            affected_strategies: transition.affected_strategies.clone(),
+            changed_at: transition.at,
+        };
+        match WebhookEvent::new(event_type, &payload) {
+            Ok(event) => webhooks.dispatch(event),
+            Err(e) => warn!("Failed to build webhook event: {}", e),
+        }
+    }
+}
+
+fn aggregate_mid_of(
+    sources: &HashMap<(String, String), SourceQuality>,
+    trading_pair: &str,
+    now: DateTime<Utc>,
+    config: &QualityConfig,
+) -> Option<Decimal> {
+    let fresh_after = now - to_chrono(config.stale_after);
+    let mut mids: Vec<Decimal> = sources
+        .iter()
+        .filter(|((_, pair), source)| {
+            pair == trading_pair
+                && source.status == SourceStatus::Promoted
+                && source.last_tick_at >= fresh_after
+        })
+        .map(|(_, source)| source.last_mid)
+        .collect();
+    if mids.is_empty() {
+        return None;
+    }
+    mids.sort();
+    let middle = mids.len() / 2;
+    Some(if mids.len() % 2 == 0 {
+        (mids[middle - 1] + mids[middle]) / Decimal::TWO
+    } else {
+        mids[middle]
+    })
+}
+
+fn pct_change(from: Decimal, to: Decimal) -> f64 {
+    if from.is_zero() {
+        return 0.0;
+    }
+    ((to - from) / from * Decimal::ONE_HUNDRED).abs().to_f64().unwrap_or(0.0)
+}
+
+fn deviation_bps(price: Decimal, aggregate: Decimal) -> f64 {
+    pct_change(aggregate, price) * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::strategy::{StrategyParams, StrategyType};
+    use crate::utils::percent::{Bps, Percent};
+    use rust_decimal_macros::dec;
+
+    const PAIR: &str = "SOL/USDC";
+
+    fn monitor() -> DataQualityMonitor {
+        let mut strategy = Strategy::new(
+            StrategyType::Grid,
+            StrategyParams {
+                position_size_bps: Bps::new(1000),
+                grid_levels: Some(10),
+                stop_loss_pct: Percent::from_percent(dec!(-5)),
+                take_profit_pct: Percent::from_percent(dec!(1)),
+                max_slippage_bps: Bps::new(100),
+                exchanges: vec!["pump_fun".to_string()],
+                risk_factor: dec!(0.5),
+                allowed_regimes: Vec::new(),
+                market_making: None,
+            },
+            vec![PAIR.to_string()],
+        )
+        .unwrap();
+        strategy.state = crate::models::strategy::StrategyState::Active;
+
+        let strategies = Arc::new(RwLock::new(HashMap::from([("grid-1".to_string(), strategy)])));
+        DataQualityMonitor::new(
+            QualityConfig {
+                score_window: Duration::from_secs(60),
+                recovery_period: Duration::from_secs(120),
+                ..QualityConfig::default()
+            },
+            strategies,
+        )
+    }
+
+    /// Healthy reference venues tick every second around 100-102
+    fn reference_ticks(monitor: &DataQualityMonitor, at: DateTime<Utc>) {
+        monitor.record_price("jupiter", PAIR, dec!(100), at);
+        monitor.record_price("drift", PAIR, dec!(102), at);
+    }
+
+    fn score_of(monitor: &DataQualityMonitor, exchange: &str) -> f64 {
+        monitor
+            .scores()
+            .into_iter()
+            .find(|score| score.exchange == exchange)
+            .unwrap()
+            .breakdown
+            .score
+    }
+
+    #[tokio::test]
+    async fn test_degraded_source_demoted_and_repromoted() {
+        let monitor = monitor();
+        let mut notifications = monitor.subscribe();
+        let start = Utc::now();
+        let at = |secs: i64| start + chrono::Duration::seconds(secs);
+
+        // Healthy phase: all three venues agree
+        for s in 0..=60 {
+            reference_ticks(&monitor, at(s));
+            monitor.record_price("pump_fun", PAIR, dec!(101), at(s));
+        }
+        assert!(monitor.evaluate(at(60)).await.is_empty());
+        assert!((score_of(&monitor, "pump_fun") - 1.0).abs() < 1e-9);
+        assert_eq!(monitor.aggregate_mid(PAIR, at(60)), Some(dec!(101)));
+
+        // Degraded phase: pump_fun ticks every 6s and jumps between 100 and 120
+        let mut scores = Vec::new();
+        let mut demotion = None;
+        for s in 61..=180 {
+            reference_ticks(&monitor, at(s));
+            if (s - 60) % 6 == 0 {
+                let price = if (s - 60) % 12 == 6 { dec!(120) } else { dec!(100) };
+                monitor.record_price("pump_fun", PAIR, price, at(s));
+                let transitions = monitor.evaluate(at(s)).await;
+                scores.push(score_of(&monitor, "pump_fun"));
+                if let Some(transition) = transitions.into_iter().next() {
+                    assert!(demotion.is_none(), "demoted twice");
+                    demotion = Some(transition);
+                }
+            }
+        }
+
+        // The score decays as bad ticks displace good ones, then demotes the source
+        assert!(scores[0] >= 0.6);
+        assert!(scores.windows(2).take(10).all(|pair| pair[1] <= pair[0]));
+        assert!(*scores.last().unwrap() < 0.6);
+
+        let demotion = demotion.expect("source was not demoted");
+        assert_eq!(demotion.exchange, "pump_fun");
+        assert_eq!(demotion.status, SourceStatus::Demoted);
+        assert_eq!(demotion.affected_strategies, vec!["grid-1".to_string()]);
+        assert_eq!(notifications.try_recv().unwrap(), demotion);
+        assert!(monitor.is_demoted("pump_fun", PAIR));
+        assert!(!monitor.is_demoted("jupiter", PAIR));
+
+        // Demoted sources no longer move the aggregate mid
+        monitor.record_price("pump_fun", PAIR, dec!(120), at(180));
+        assert_eq!(monitor.aggregate_mid(PAIR, at(180)), Some(dec!(101)));
+
+        // Recovery: clean data must hold above the threshold for the recovery period
+        let mut promotion = None;
+        for s in 181..=480 {
+            reference_ticks(&monitor, at(s));
+            monitor.record_price("pump_fun", PAIR, dec!(101), at(s));
+            if s % 10 == 0 {
+                if let Some(transition) = monitor.evaluate(at(s)).await.into_iter().next() {
+                    promotion = Some(transition);
+                    break;
+                }
+            }
+        }
+
+        let promotion = promotion.expect("source was not re-promoted");
+        assert_eq!(promotion.status, SourceStatus::Promoted);
+        assert!(promotion.at - demotion.at >= chrono::Duration::seconds(120));
+        assert_eq!(notifications.try_recv().unwrap(), promotion);
+        assert!(!monitor.is_demoted("pump_fun", PAIR));
+    }
+
+    #[tokio::test]
+    async fn test_silent_source_decays_on_staleness() {
+        let monitor = monitor();
+        let start = Utc::now();
+        monitor.record_price("jupiter", PAIR, dec!(100), start);
+
+        monitor.evaluate(start + chrono::Duration::seconds(5)).await;
+        assert!((score_of(&monitor, "jupiter") - 1.0).abs() < 1e-9);
+
+        monitor.evaluate(start + chrono::Duration::seconds(30)).await;
+        let partial = score_of(&monitor, "jupiter");
+        assert!(partial < 1.0 && partial > 0.6);
+
+        let transitions = monitor.evaluate(start + chrono::Duration::seconds(60)).await;
+        // Fully stale but otherwise clean sources keep the remaining components
+        assert!((score_of(&monitor, "jupiter") - 0.7).abs() < 1e-9);
+        assert!(transitions.is_empty());
+        assert_eq!(monitor.aggregate_mid(PAIR, start + chrono::Duration::seconds(61)), None);
+    }
+}
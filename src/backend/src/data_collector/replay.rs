@@ -0,0 +1,494 @@
+//! Market data capture and replay for deterministic strategy and execution testing
+//! without live DEX connections.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - serde_json = "1.0"
+//! - bincode = "1.3"
+//! - tracing = "0.1"
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio::time::{sleep, Duration};
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::data_collector::{Collector, CollectorError, HealthStatus};
+use crate::db::repositories::MarketDataRepository;
+use crate::models::market::MarketData;
+
+// Replay configuration constants
+pub const RECORD_FLAG: &str = "--record";
+pub const RECORD_PATH_ENV: &str = "COLLECTOR_RECORD_PATH";
+pub const REPLAY_FLAG: &str = "--replay";
+pub const REPLAY_SPEED_FLAG: &str = "--replay-speed";
+const RECORD_CHANNEL_SIZE: usize = 10000;
+const DEFAULT_DB_REPLAY_LIMIT: i64 = 10000;
+
+/// Serialized market data observation in a capture file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedTick {
+    pub trading_pair: String,
+    pub exchange: String,
+    pub price: Decimal,
+    pub volume: Decimal,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl RecordedTick {
+    pub fn from_market_data(data: &MarketData) -> Self {
+        Self {
+            trading_pair: data.trading_pair().to_string(),
+            exchange: data.exchange().to_string(),
+            price: data.price(),
+            volume: data.volume(),
+            timestamp: data.timestamp(),
+        }
+    }
+
+    /// Rebuilds validated market data with the original observation time
+    pub fn into_market_data(self) -> Result<MarketData, CollectorError> {
+        MarketData::new(self.trading_pair, self.exchange, self.price, self.volume)
+            .map(|data| data.with_timestamp(self.timestamp))
+            .map_err(|e| CollectorError::DataValidationError(e.to_string()))
+    }
+}
+
+/// On-disk capture encoding, chosen by file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureFormat {
+    Ndjson,
+    Bincode,
+}
+
+impl CaptureFormat {
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("bin") | Some("bincode") => Self::Bincode,
+            _ => Self::Ndjson,
+        }
+    }
+}
+
+/// Appends market data observations to a capture file
+#[derive(Debug)]
+pub struct MarketDataRecorder {
+    writer: BufWriter<File>,
+    format: CaptureFormat,
+    recorded: u64,
+}
+
+impl MarketDataRecorder {
+    /// Creates or truncates a capture file
+    pub fn create(path: &Path) -> Result<Self, CollectorError> {
+        let file = File::create(path)
+            .map_err(|e| CollectorError::CollectionError(format!("failed to create capture: {}", e)))?;
+
+        Ok(Self {
+            writer: BufWriter::new(file),
+            format: CaptureFormat::from_path(path),
+            recorded: 0,
+        })
+    }
+
+    pub fn record(&mut self, data: &MarketData) -> Result<(), CollectorError> {
+        let tick = RecordedTick::from_market_data(data);
+        match self.format {
+            CaptureFormat::Ndjson => {
+                serde_json::to_writer(&mut self.writer, &tick)
+                    .map_err(|e| CollectorError::CollectionError(e.to_string()))?;
+                self.writer
+                    .write_all(b"\n")
+                    .map_err(|e| CollectorError::CollectionError(e.to_string()))?;
+            }
+            CaptureFormat::Bincode => {
+                bincode::serialize_into(&mut self.writer, &tick)
+                    .map_err(|e| CollectorError::CollectionError(e.to_string()))?;
+            }
+        }
+        self.recorded += 1;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), CollectorError> {
+        self.writer
+            .flush()
+            .map_err(|e| CollectorError::CollectionError(e.to_string()))
+    }
+
+    pub fn recorded(&self) -> u64 {
+        self.recorded
+    }
+}
+
+/// Reads every tick from a capture file in file order
+pub fn read_capture(path: &Path) -> Result<Vec<RecordedTick>, CollectorError> {
+    let file = File::open(path)
+        .map_err(|e| CollectorError::CollectionError(format!("failed to open capture: {}", e)))?;
+    let mut reader = BufReader::new(file);
+    let mut ticks = Vec::new();
+
+    match CaptureFormat::from_path(path) {
+        CaptureFormat::Ndjson => {
+            for (line_no, line) in reader.lines().enumerate() {
+                let line = line.map_err(|e| CollectorError::CollectionError(e.to_string()))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let tick = serde_json::from_str(&line).map_err(|e| {
+                    CollectorError::DataValidationError(format!("line {}: {}", line_no + 1, e))
+                })?;
+                ticks.push(tick);
+            }
+        }
+        CaptureFormat::Bincode => loop {
+            match bincode::deserialize_from::<_, RecordedTick>(&mut reader) {
+                Ok(tick) => ticks.push(tick),
+                Err(e) => match *e {
+                    bincode::ErrorKind::Io(ref io) if io.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                    _ => return Err(CollectorError::DataValidationError(e.to_string())),
+                },
+            }
+        },
+    }
+
+    Ok(ticks)
+}
+
+/// Returns the capture path requested via `--record <path>` or `COLLECTOR_RECORD_PATH`
+pub fn record_path_from_args(args: impl IntoIterator<Item = String>) -> Option<PathBuf> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == RECORD_FLAG {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.strip_prefix("--record=") {
+            return Some(PathBuf::from(path));
+        }
+    }
+    std::env::var(RECORD_PATH_ENV).ok().map(PathBuf::from)
+}
+
+/// Returns the capture file requested via `--replay <path>` with the pacing given by
+/// `--replay-speed <factor|max>`, realtime when unset
+pub fn replay_from_args(args: impl IntoIterator<Item = String>) -> Option<(PathBuf, ReplaySpeed)> {
+    let args: Vec<String> = args.into_iter().collect();
+    let value = |flag: &str| {
+        args.iter()
+            .position(|arg| arg == flag)
+            .and_then(|index| args.get(index + 1))
+    };
+
+    let path = PathBuf::from(value(REPLAY_FLAG)?);
+    let speed = match value(REPLAY_SPEED_FLAG).map(String::as_str) {
+        None => ReplaySpeed::Realtime,
+        Some("max") => ReplaySpeed::Unthrottled,
+        Some(factor) => match factor.parse::<f64>() {
+            Ok(factor) if factor > 0.0 => ReplaySpeed::Scaled(factor),
+            _ => {
+                warn!("Ignoring invalid replay speed {:?}, replaying in realtime", factor);
+                ReplaySpeed::Realtime
+            }
+        },
+    };
+    Some((path, speed))
+}
+
+/// Tees a collector output channel into a capture file while forwarding every observation
+pub fn spawn_recording_tap(
+    mut source: mpsc::Receiver<MarketData>,
+    path: &Path,
+) -> Result<(mpsc::Receiver<MarketData>, tokio::task::JoinHandle<()>), CollectorError> {
+    let mut recorder = MarketDataRecorder::create(path)?;
+    let (tx, rx) = mpsc::channel(RECORD_CHANNEL_SIZE);
+    let path = path.to_path_buf();
+
+    let handle = tokio::spawn(async move {
+        while let Some(data) = source.recv().await {
+            if let Err(e) = recorder.record(&data) {
+                error!("Failed to record market data: {}", e);
+            }
+            if tx.send(data).await.is_err() {
+                break;
+            }
+        }
+        if let Err(e) = recorder.flush() {
+            error!("Failed to flush capture file: {}", e);
+        }
+        info!("Recorded {} observations to {}", recorder.recorded(), path.display());
+    });
+
+    Ok((rx, handle))
+}
+
+/// Replay pacing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    /// Original inter-arrival times on the wall clock
+    Realtime,
+    /// Original inter-arrival times divided by the factor
+    Scaled(f64),
+    /// No delay between observations
+    Unthrottled,
+}
+
+impl ReplaySpeed {
+    /// Delay to wait before emitting `next` after `previous`
+    pub fn delay_between(&self, previous: DateTime<Utc>, next: DateTime<Utc>) -> Option<Duration> {
+        let gap = (next - previous).to_std().ok()?;
+        match self {
+            Self::Realtime => Some(gap),
+            Self::Scaled(factor) if *factor > 0.0 => Some(gap.div_f64(*factor)),
+            Self::Scaled(_) | Self::Unthrottled => None,
+        }
+    }
+}
+
+/// Origin of recorded market data
+#[derive(Debug, Clone)]
+pub enum ReplaySource {
+    File(PathBuf),
+    Database {
+        repository: Arc<MarketDataRepository>,
+        trading_pairs: Vec<String>,
+        limit: Option<i64>,
+    },
+}
+
+impl ReplaySource {
+    /// Loads recorded ticks ordered by original timestamp
+    pub async fn load_ticks(&self) -> Result<Vec<RecordedTick>, CollectorError> {
+        let mut ticks = match self {
+            ReplaySource::File(path) => read_capture(path)?,
+            ReplaySource::Database { repository, trading_pairs, limit } => {
+                let mut ticks = Vec::new();
+                for pair in trading_pairs {
+                    let records = repository
+                        .get_market_data(pair, limit.unwrap_or(DEFAULT_DB_REPLAY_LIMIT))
+                        .await
+                        .map_err(|e| CollectorError::CollectionError(e.to_string()))?;
+                    ticks.extend(records.into_iter().map(|record| RecordedTick {
+                        trading_pair: record.trading_pair,
+                        exchange: record.exchange,
+                        price: record.price,
+                        volume: record.volume,
+                        timestamp: record.timestamp,
+                    }));
+                }
+                ticks
+            }
+        };
+
+        // Stable sort keeps capture order for identical timestamps
+        ticks.sort_by_key(|tick| tick.timestamp);
+        Ok(ticks)
+    }
+}
+
+/// Collector that re-emits recorded market data through the regular collector channel
+#[derive(Debug)]
+pub struct ReplayCollector {
+    source: ReplaySource,
+    speed: ReplaySpeed,
+    market_data_tx: mpsc::Sender<MarketData>,
+    running: AtomicBool,
+    replayed: AtomicU64,
+    errors: AtomicU64,
+    last_error: RwLock<Option<String>>,
+}
+
+impl ReplayCollector {
+    pub fn new(
+        source: ReplaySource,
+        speed: ReplaySpeed,
+        market_data_tx: mpsc::Sender<MarketData>,
+    ) -> Self {
+        Self {
+            source,
+            speed,
+            market_data_tx,
+            running: AtomicBool::new(false),
+            replayed: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            last_error: RwLock::new(None),
+        }
+    }
+
+    /// Replays all recorded data, returning the number of observations emitted
+    #[instrument(skip(self))]
+    pub async fn replay(&self) -> Result<u64, CollectorError> {
+        let ticks = self.source.load_ticks().await?;
+        info!("Replaying {} recorded observations at {:?}", ticks.len(), self.speed);
+
+        self.running.store(true, Ordering::SeqCst);
+        let mut previous: Option<DateTime<Utc>> = None;
+        let mut emitted = 0;
+
+        for tick in ticks {
+            if !self.running.load(Ordering::SeqCst) {
+                debug!("Replay stopped after {} observations", emitted);
+                break;
+            }
+
+            if let Some(delay) = previous.and_then(|prev| self.speed.delay_between(prev, tick.timestamp)) {
+                sleep(delay).await;
+            }
+            previous = Some(tick.timestamp);
+
+            let data = match tick.into_market_data() {
+                Ok(data) => data,
+                Err(e) => {
+                    warn!("Skipping invalid recorded observation: {}", e);
+                    self.errors.fetch_add(1, Ordering::Relaxed);
+                    *self.last_error.write() = Some(e.to_string());
+                    continue;
+                }
+            };
+
+            self.market_data_tx
+                .send(data)
+                .await
+                .map_err(|e| CollectorError::CollectionError(e.to_string()))?;
+            emitted += 1;
+            self.replayed.fetch_add(1, Ordering::Relaxed);
+            counter!("replay_collector.observations", 1);
+        }
+
+        self.running.store(false, Ordering::SeqCst);
+        Ok(emitted)
+    }
+}
+
+#[async_trait]
+impl Collector for ReplayCollector {
+    async fn start_collection(&self) -> Result<(), CollectorError> {
+        self.replay().await.map(|_| ())
+    }
+
+    async fn stop_collection(&self) -> Result<(), CollectorError> {
+        self.running.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, CollectorError> {
+        Ok(HealthStatus {
+            is_healthy: !self.market_data_tx.is_closed(),
+            connection_count: 0,
+            last_collection_latency: Duration::ZERO,
+            last_collection_time: None,
+            error_count: self.errors.load(Ordering::Relaxed),
+            last_error: self.last_error.read().clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn sample(price: Decimal, offset_ms: i64) -> MarketData {
+        let base = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        MarketData::new(
+            "SOL/USDC".to_string(),
+            "jupiter".to_string(),
+            price,
+            dec!(10.000000),
+        )
+        .unwrap()
+        .with_timestamp(base + chrono::Duration::milliseconds(offset_ms))
+    }
+
+    fn round_trip(file_name: &str) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(file_name);
+        let recorded = vec![sample(dec!(100.00000000), 0), sample(dec!(101.50000000), 250)];
+
+        let mut recorder = MarketDataRecorder::create(&path).unwrap();
+        for data in &recorded {
+            recorder.record(data).unwrap();
+        }
+        recorder.flush().unwrap();
+
+        let ticks = read_capture(&path).unwrap();
+        let expected: Vec<RecordedTick> = recorded.iter().map(RecordedTick::from_market_data).collect();
+        assert_eq!(ticks, expected);
+    }
+
+    #[test]
+    fn test_ndjson_round_trip() {
+        round_trip("capture.ndjson");
+    }
+
+    #[test]
+    fn test_bincode_round_trip() {
+        round_trip("capture.bin");
+    }
+
+    #[tokio::test]
+    async fn test_replay_preserves_original_timestamps() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.ndjson");
+        let mut recorder = MarketDataRecorder::create(&path).unwrap();
+        // Recorded out of order; replay follows original time
+        recorder.record(&sample(dec!(101.00000000), 500)).unwrap();
+        recorder.record(&sample(dec!(100.00000000), 0)).unwrap();
+        recorder.flush().unwrap();
+
+        let (tx, mut rx) = mpsc::channel(10);
+        let collector = ReplayCollector::new(ReplaySource::File(path), ReplaySpeed::Unthrottled, tx);
+        assert_eq!(collector.replay().await.unwrap(), 2);
+
+        let first = rx.recv().await.unwrap();
+        let second = rx.recv().await.unwrap();
+        assert_eq!(first.price(), dec!(100.00000000));
+        assert_eq!(second.timestamp() - first.timestamp(), chrono::Duration::milliseconds(500));
+    }
+
+    #[test]
+    fn test_replay_speed_delays() {
+        let start = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        let next = start + chrono::Duration::seconds(2);
+
+        assert_eq!(ReplaySpeed::Realtime.delay_between(start, next), Some(Duration::from_secs(2)));
+        assert_eq!(ReplaySpeed::Scaled(4.0).delay_between(start, next), Some(Duration::from_millis(500)));
+        assert_eq!(ReplaySpeed::Unthrottled.delay_between(start, next), None);
+    }
+
+    #[test]
+    fn test_record_path_from_args() {
+        let args = vec!["bot".to_string(), RECORD_FLAG.to_string(), "/tmp/capture.bin".to_string()];
+        assert_eq!(record_path_from_args(args), Some(PathBuf::from("/tmp/capture.bin")));
+    }
+
+    #[test]
+    fn test_replay_from_args() {
+        let args = |extra: &[&str]| {
+            let mut args = vec!["bot".to_string(), REPLAY_FLAG.to_string(), "/tmp/capture.bin".to_string()];
+            args.extend(extra.iter().map(|arg| arg.to_string()));
+            args
+        };
+        let path = PathBuf::from("/tmp/capture.bin");
+
+        assert_eq!(replay_from_args(args(&[])), Some((path.clone(), ReplaySpeed::Realtime)));
+        assert_eq!(
+            replay_from_args(args(&[REPLAY_SPEED_FLAG, "10"])),
+            Some((path.clone(), ReplaySpeed::Scaled(10.0)))
+        );
+        assert_eq!(
+            replay_from_args(args(&[REPLAY_SPEED_FLAG, "max"])),
+            Some((path, ReplaySpeed::Unthrottled))
+        );
+        assert_eq!(replay_from_args(vec!["bot".to_string()]), None);
+    }
+}
@@ -0,0 +1,492 @@
+//! Sanity checks on collected market data beyond per-field validation. `validate_price` and
+//! `validate_volume` only check each value on its own; this layer checks samples against the
+//! pair's configured price bounds and against the recent history of the same (exchange, pair)
+//! feed. A price outside the pair's bounds is rejected. A price exactly a power of ten off the
+//! rolling median, the signature of a venue mis-scaling decimals, or a volume far above the
+//! recent average is quarantined instead: the sample is stored for review but never reaches
+//! aggregation, and it does not enter the history later samples are judged against.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use metrics::counter;
+use parking_lot::{Mutex, RwLock as SyncRwLock};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::models::market::MarketData;
+use crate::models::pair::{PairRegistry, TradingPair};
+
+// Sanity check constants
+const METRICS_PREFIX: &str = "trading_bot.market_data.sanity";
+const DEFAULT_HISTORY_WINDOW: usize = 50;
+const DEFAULT_MIN_HISTORY: usize = 20;
+const DEFAULT_MAX_VOLUME_RATIO: Decimal = Decimal::from_parts(25, 0, 0, false, 0);
+/// Bonding-curve launches trade in bursts; only far larger jumps are implausible
+const PUMP_FUN_MAX_VOLUME_RATIO: Decimal = Decimal::from_parts(100, 0, 0, false, 0);
+/// Relative distance from an exact power of ten still treated as a scaling error
+const DEFAULT_SCALE_TOLERANCE: f64 = 0.02;
+const SCREEN_CHANNEL_SIZE: usize = 10000;
+
+/// Sanity check errors
+#[derive(Error, Debug)]
+pub enum SanityError {
+    #[error("store error: {0}")]
+    Store(String),
+}
+
+/// Check a sample failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SanityRule {
+    /// Price outside the pair's registered bounds
+    PriceBounds,
+    /// Volume far above the feed's recent average
+    VolumeSpike,
+    /// Price exactly a power of ten away from the feed's rolling median
+    DecimalScale,
+}
+
+impl SanityRule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PriceBounds => "price_bounds",
+            Self::VolumeSpike => "volume_spike",
+            Self::DecimalScale => "decimal_scale",
+        }
+    }
+}
+
+/// Failed check and why
+#[derive(Debug, Clone, PartialEq)]
+pub struct SanityViolation {
+    pub rule: SanityRule,
+    pub detail: String,
+}
+
+/// Classification of a sample
+#[derive(Debug, Clone, PartialEq)]
+pub enum SanityVerdict {
+    Accept,
+    /// Stored for review, excluded from aggregation
+    Quarantine(SanityViolation),
+    /// Dropped and logged
+    Reject(SanityViolation),
+}
+
+/// Checks applied to one exchange's feeds
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeRules {
+    /// Most a single sample's volume may exceed the recent average by, as a multiple
+    pub max_volume_ratio: Option<Decimal>,
+    /// Relative tolerance around an exact power of ten for a scaling error; None disables it
+    pub scale_tolerance: Option<f64>,
+    /// Samples of history needed before the volume and scale checks apply
+    pub min_history: usize,
+}
+
+impl Default for ExchangeRules {
+    fn default() -> Self {
+        Self {
+            max_volume_ratio: Some(DEFAULT_MAX_VOLUME_RATIO),
+            scale_tolerance: Some(DEFAULT_SCALE_TOLERANCE),
+            min_history: DEFAULT_MIN_HISTORY,
+        }
+    }
+}
+
+/// Per-exchange rule sets
+#[derive(Debug, Clone, PartialEq)]
+pub struct SanityConfig {
+    pub exchanges: HashMap<String, ExchangeRules>,
+    /// Rules for exchanges without their own
+    pub default_rules: ExchangeRules,
+    /// Accepted samples kept per (exchange, pair) for the median and average
+    pub history_window: usize,
+}
+
+impl Default for SanityConfig {
+    fn default() -> Self {
+        let pump_fun = ExchangeRules {
+            max_volume_ratio: Some(PUMP_FUN_MAX_VOLUME_RATIO),
+            ..ExchangeRules::default()
+        };
+        Self {
+            exchanges: HashMap::from([
+                ("jupiter".to_string(), ExchangeRules::default()),
+                ("pump_fun".to_string(), pump_fun),
+                ("drift".to_string(), ExchangeRules::default()),
+            ]),
+            default_rules: ExchangeRules::default(),
+            history_window: DEFAULT_HISTORY_WINDOW,
+        }
+    }
+}
+
+impl SanityConfig {
+    fn rules(&self, exchange: &str) -> &ExchangeRules {
+        self.exchanges.get(&exchange.to_lowercase()).unwrap_or(&self.default_rules)
+    }
+}
+
+/// Persists quarantined samples for review
+#[async_trait]
+pub trait SuspectSampleStore: Send + Sync {
+    async fn save_suspect(&self, market_data: &MarketData, rule: SanityRule) -> Result<(), SanityError>;
+}
+
+/// Recent accepted samples of one feed
+#[derive(Debug, Default)]
+struct FeedHistory {
+    prices: VecDeque<Decimal>,
+    volumes: VecDeque<Decimal>,
+}
+
+impl FeedHistory {
+    fn push(&mut self, price: Decimal, volume: Decimal, window: usize) {
+        self.prices.push_back(price);
+        self.volumes.push_back(volume);
+        while self.prices.len() > window.max(1) {
+            self.prices.pop_front();
+            self.volumes.pop_front();
+        }
+    }
+
+    fn median_price(&self) -> Option<Decimal> {
+        let mut prices: Vec<Decimal> = self.prices.iter().copied().collect();
+        prices.sort();
+        let mid = prices.len() / 2;
+        match prices.len() {
+            0 => None,
+            n if n % 2 == 0 => Some((prices[mid - 1] + prices[mid]) / Decimal::TWO),
+            _ => Some(prices[mid]),
+        }
+    }
+
+    fn average_volume(&self) -> Option<Decimal> {
+        if self.volumes.is_empty() {
+            return None;
+        }
+        Some(self.volumes.iter().copied().sum::<Decimal>() / Decimal::from(self.volumes.len()))
+    }
+}
+
+/// Classifies collected samples and keeps each feed's recent history
+pub struct MarketDataValidator {
+    config: SanityConfig,
+    registry: SyncRwLock<Arc<PairRegistry>>,
+    history: Mutex<HashMap<(String, TradingPair), FeedHistory>>,
+    store: SyncRwLock<Option<Arc<dyn SuspectSampleStore>>>,
+}
+
+impl std::fmt::Debug for MarketDataValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MarketDataValidator")
+            .field("config", &self.config)
+            .field("feeds", &self.history.lock().len())
+            .finish()
+    }
+}
+
+impl MarketDataValidator {
+    pub fn new(config: SanityConfig, registry: Arc<PairRegistry>) -> Self {
+        Self {
+            config,
+            registry: SyncRwLock::new(registry),
+            history: Mutex::new(HashMap::new()),
+            store: SyncRwLock::new(None),
+        }
+    }
+
+    /// Takes price bounds from `registry` from now on
+    pub fn set_registry(&self, registry: Arc<PairRegistry>) {
+        *self.registry.write() = registry;
+    }
+
+    pub fn set_store(&self, store: Arc<dyn SuspectSampleStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    /// Classifies a sample; accepted samples join their feed's history
+    pub fn check(&self, market_data: &MarketData) -> SanityVerdict {
+        let exchange = market_data.exchange().to_lowercase();
+        let (price, volume) = (market_data.price(), market_data.volume());
+
+        if let Some(bounds) = self.registry.read().price_bounds(market_data.pair()) {
+            if !bounds.contains(price) {
+                return self.classify(
+                    &exchange,
+                    SanityVerdict::Reject(SanityViolation {
+                        rule: SanityRule::PriceBounds,
+                        detail: format!("price {} outside [{}, {}]", price, bounds.min, bounds.max),
+                    }),
+                );
+            }
+        }
+
+        let rules = self.config.rules(&exchange);
+        let mut history = self.history.lock();
+        let feed = history
+            .entry((exchange.clone(), market_data.pair().clone()))
+            .or_default();
+        if feed.prices.len() >= rules.min_history {
+            if let Some(violation) = scale_violation(feed, price, rules) {
+                return self.classify(&exchange, SanityVerdict::Quarantine(violation));
+            }
+            if let Some(violation) = volume_violation(feed, volume, rules) {
+                return self.classify(&exchange, SanityVerdict::Quarantine(violation));
+            }
+        }
+        feed.push(price, volume, self.config.history_window);
+        SanityVerdict::Accept
+    }
+
+    fn classify(&self, exchange: &str, verdict: SanityVerdict) -> SanityVerdict {
+        let (outcome, violation) = match &verdict {
+            SanityVerdict::Accept => return verdict,
+            SanityVerdict::Quarantine(violation) => ("quarantine", violation),
+            SanityVerdict::Reject(violation) => ("reject", violation),
+        };
+        counter!(
+            format!("{}.violations", METRICS_PREFIX),
+            1,
+            "rule" => violation.rule.as_str(),
+            "exchange" => exchange.to_string(),
+            "outcome" => outcome
+        );
+        verdict
+    }
+
+    /// Passes accepted samples through; quarantined samples are stored and withheld,
+    /// rejected ones logged and dropped
+    pub async fn screen(&self, market_data: MarketData) -> Option<MarketData> {
+        match self.check(&market_data) {
+            SanityVerdict::Accept => Some(market_data),
+            SanityVerdict::Quarantine(violation) => {
+                let store = self.store.read().clone();
+                if let Some(store) = store {
+                    if let Err(e) = store.save_suspect(&market_data, violation.rule).await {
+                        counter!(format!("{}.store_failures", METRICS_PREFIX), 1);
+                        warn!(rule = violation.rule.as_str(), "Failed to store quarantined sample: {}", e);
+                    }
+                }
+                None
+            }
+            SanityVerdict::Reject(violation) => {
+                warn!(
+                    exchange = market_data.exchange(),
+                    trading_pair = market_data.trading_pair(),
+                    rule = violation.rule.as_str(),
+                    "Rejected market data: {}",
+                    violation.detail
+                );
+                None
+            }
+        }
+    }
+
+    /// Screens a collector output channel, forwarding only the samples `screen` passes
+    pub fn spawn_screen(
+        self: Arc<Self>,
+        mut source: mpsc::Receiver<MarketData>,
+    ) -> (mpsc::Receiver<MarketData>, tokio::task::JoinHandle<()>) {
+        let (tx, rx) = mpsc::channel(SCREEN_CHANNEL_SIZE);
+        let handle = tokio::spawn(async move {
+            while let Some(market_data) = source.recv().await {
+                if let Some(market_data) = self.screen(market_data).await {
+                    if tx.send(market_data).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        (rx, handle)
+    }
+}
+
+/// Power of ten the price is off the feed's median by, when it is off by one within tolerance
+fn scale_violation(feed: &FeedHistory, price: Decimal, rules: &ExchangeRules) -> Option<SanityViolation> {
+    let tolerance = rules.scale_tolerance?;
+    let median = feed.median_price().filter(|median| *median > Decimal::ZERO)?;
+    let ratio = (price / median).to_f64().filter(|ratio| *ratio > 0.0)?;
+    let exponent = ratio.log10().round() as i32;
+    if exponent == 0 || (ratio / 10f64.powi(exponent) - 1.0).abs() > tolerance {
+        return None;
+    }
+    Some(SanityViolation {
+        rule: SanityRule::DecimalScale,
+        detail: format!("price {} is 10^{} times the rolling median {}", price, exponent, median),
+    })
+}
+
+fn volume_violation(feed: &FeedHistory, volume: Decimal, rules: &ExchangeRules) -> Option<SanityViolation> {
+    let max_ratio = rules.max_volume_ratio?;
+    let average = feed.average_volume().filter(|average| *average > Decimal::ZERO)?;
+    if volume <= average * max_ratio {
+        return None;
+    }
+    Some(SanityViolation {
+        rule: SanityRule::VolumeSpike,
+        detail: format!("volume {} exceeds {}x the recent average {}", volume, max_ratio, average.round_dp(6)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::pair::PriceBounds;
+    use rust_decimal_macros::dec;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        samples: Mutex<Vec<(Decimal, SanityRule)>>,
+    }
+
+    #[async_trait]
+    impl SuspectSampleStore for MemoryStore {
+        async fn save_suspect(&self, market_data: &MarketData, rule: SanityRule) -> Result<(), SanityError> {
+            self.samples.lock().push((market_data.price(), rule));
+            Ok(())
+        }
+    }
+
+    fn sample(exchange: &str, pair: &str, price: Decimal, volume: Decimal) -> MarketData {
+        MarketData::new(pair.to_string(), exchange.to_string(), price, volume).unwrap()
+    }
+
+    /// Validator with `min_history` steady samples around 100 with volume 10 on each feed
+    fn warmed(exchange: &str, pair: &str, registry: PairRegistry) -> MarketDataValidator {
+        let validator = MarketDataValidator::new(SanityConfig::default(), Arc::new(registry));
+        for i in 0..DEFAULT_MIN_HISTORY {
+            let price = dec!(100.00000000) + Decimal::new(i as i64 % 3, 1);
+            assert_eq!(validator.check(&sample(exchange, pair, price, dec!(10.000000))), SanityVerdict::Accept);
+        }
+        validator
+    }
+
+    fn bounded_registry() -> PairRegistry {
+        let sol = TradingPair::parse("SOL/USDC").unwrap();
+        PairRegistry::default().with_price_bounds(sol, PriceBounds::new(dec!(1), dec!(10000)))
+    }
+
+    fn rule(verdict: &SanityVerdict) -> Option<SanityRule> {
+        match verdict {
+            SanityVerdict::Accept => None,
+            SanityVerdict::Quarantine(violation) | SanityVerdict::Reject(violation) => Some(violation.rule),
+        }
+    }
+
+    #[test]
+    fn test_price_outside_registry_bounds_is_rejected() {
+        let validator = MarketDataValidator::new(SanityConfig::default(), Arc::new(bounded_registry()));
+
+        let verdict = validator.check(&sample("jupiter", "SOL/USDC", dec!(25000.00000000), dec!(5.000000)));
+        assert!(matches!(verdict, SanityVerdict::Reject(_)));
+        assert_eq!(rule(&verdict), Some(SanityRule::PriceBounds));
+        let verdict = validator.check(&sample("pump_fun", "SOL/USDC", dec!(0.50000000), dec!(5.000000)));
+        assert_eq!(rule(&verdict), Some(SanityRule::PriceBounds));
+        assert_eq!(
+            validator.check(&sample("jupiter", "SOL/USDC", dec!(150.00000000), dec!(5.000000))),
+            SanityVerdict::Accept
+        );
+        // Pairs without bounds are not bounded
+        assert_eq!(
+            validator.check(&sample("jupiter", "RAY/USDC", dec!(25000.00000000), dec!(5.000000))),
+            SanityVerdict::Accept
+        );
+    }
+
+    #[test]
+    fn test_power_of_ten_price_is_quarantined_as_scaling_error() {
+        let validator = warmed("pump_fun", "RAY/USDC", PairRegistry::default());
+
+        // Six decimals dropped by the venue, with the volume scaled up to match
+        let verdict = validator.check(&sample("pump_fun", "RAY/USDC", dec!(0.00010000), dec!(10.000000)));
+        assert!(matches!(verdict, SanityVerdict::Quarantine(_)));
+        assert_eq!(rule(&verdict), Some(SanityRule::DecimalScale));
+        let verdict = validator.check(&sample("pump_fun", "RAY/USDC", dec!(1000.00000000), dec!(10.000000)));
+        assert_eq!(rule(&verdict), Some(SanityRule::DecimalScale));
+
+        // A large move that is not a power of ten is a real price
+        assert_eq!(
+            validator.check(&sample("pump_fun", "RAY/USDC", dec!(140.00000000), dec!(10.000000))),
+            SanityVerdict::Accept
+        );
+    }
+
+    #[test]
+    fn test_volume_spike_is_quarantined_per_exchange_ratio() {
+        let validator = warmed("jupiter", "SOL/USDC", PairRegistry::default());
+        let verdict = validator.check(&sample("jupiter", "SOL/USDC", dec!(100.10000000), dec!(50000000.000000)));
+        assert!(matches!(verdict, SanityVerdict::Quarantine(_)));
+        assert_eq!(rule(&verdict), Some(SanityRule::VolumeSpike));
+        assert_eq!(
+            validator.check(&sample("jupiter", "SOL/USDC", dec!(100.10000000), dec!(200.000000))),
+            SanityVerdict::Accept
+        );
+
+        // Pump Fun tolerates bigger bursts before calling a volume implausible
+        let validator = warmed("pump_fun", "SOL/USDC", PairRegistry::default());
+        assert_eq!(
+            validator.check(&sample("pump_fun", "SOL/USDC", dec!(100.10000000), dec!(500.000000))),
+            SanityVerdict::Accept
+        );
+        let verdict = validator.check(&sample("pump_fun", "SOL/USDC", dec!(100.10000000), dec!(5000.000000)));
+        assert_eq!(rule(&verdict), Some(SanityRule::VolumeSpike));
+    }
+
+    #[test]
+    fn test_checks_wait_for_history() {
+        let validator = MarketDataValidator::new(SanityConfig::default(), Arc::new(PairRegistry::default()));
+        validator.check(&sample("jupiter", "SOL/USDC", dec!(100.00000000), dec!(10.000000)));
+        assert_eq!(
+            validator.check(&sample("jupiter", "SOL/USDC", dec!(0.10000000), dec!(100000.000000))),
+            SanityVerdict::Accept
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quarantined_sample_is_stored_and_kept_out_of_history() {
+        let validator = warmed("jupiter", "SOL/USDC", PairRegistry::default());
+        let store = Arc::new(MemoryStore::default());
+        validator.set_store(store.clone());
+
+        let scaled = sample("jupiter", "SOL/USDC", dec!(0.10000000), dec!(10.000000));
+        for _ in 0..DEFAULT_HISTORY_WINDOW {
+            assert!(validator.screen(scaled.clone()).await.is_none());
+        }
+        assert_eq!(store.samples.lock().len(), DEFAULT_HISTORY_WINDOW);
+        assert_eq!(store.samples.lock()[0], (dec!(0.10000000), SanityRule::DecimalScale));
+
+        // The median never moved towards the bad prices
+        let good = sample("jupiter", "SOL/USDC", dec!(100.20000000), dec!(10.000000));
+        assert!(validator.screen(good).await.is_some());
+
+        // Rejected samples are dropped without being stored
+        validator.set_registry(Arc::new(bounded_registry()));
+        assert!(validator.screen(sample("jupiter", "SOL/USDC", dec!(25000.00000000), dec!(10.000000))).await.is_none());
+        assert_eq!(store.samples.lock().len(), DEFAULT_HISTORY_WINDOW);
+    }
+
+    #[tokio::test]
+    async fn test_screen_forwards_only_accepted_samples() {
+        let validator = Arc::new(MarketDataValidator::new(SanityConfig::default(), Arc::new(bounded_registry())));
+        let (tx, source) = mpsc::channel(4);
+        let (mut screened, _) = validator.spawn_screen(source);
+
+        tx.send(sample("jupiter", "SOL/USDC", dec!(25000.00000000), dec!(10.000000))).await.unwrap();
+        tx.send(sample("jupiter", "SOL/USDC", dec!(100.00000000), dec!(10.000000))).await.unwrap();
+        drop(tx);
+
+        assert_eq!(screened.recv().await.map(|data| data.price()), Some(dec!(100.00000000)));
+        assert!(screened.recv().await.is_none());
+    }
+}
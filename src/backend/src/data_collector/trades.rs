@@ -0,0 +1,584 @@
+//! Public trade (fills) stream alongside the quote feed. Collectors normalize venue prints
+//! into `PublicTrade`s and send them on a dedicated channel; the stream drops prints it has
+//! already seen, since venues resend recent trades after a reconnect, holds the rest for a
+//! short reorder window so each pair is released in timestamp order, then publishes them
+//! to subscribers and persists them to the `public_trades` hypertable.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - sha2 = "0.10"
+//! - metrics = "0.21"
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge, histogram};
+use parking_lot::Mutex;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, error, info, warn};
+
+use crate::data_collector::DexType;
+use crate::risk_manager::exposure::TradeSide;
+use crate::utils::time::current_timestamp;
+
+// Trade stream constants
+/// Buffer of the channel collectors send normalized prints on
+pub const PUBLIC_TRADE_CHANNEL_BUFFER: usize = 10000;
+const PUBLIC_TRADE_EVENT_BUFFER: usize = 10000;
+const DEFAULT_REORDER_WINDOW: Duration = Duration::from_millis(2000);
+const DEFAULT_DEDUPE_TTL: Duration = Duration::from_secs(15 * 60);
+const DEFAULT_MAX_TRACKED_KEYS: usize = 200_000;
+const DEFAULT_RELEASE_INTERVAL: Duration = Duration::from_millis(100);
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_millis(1000);
+const MAX_CLOCK_SKEW_MS: i64 = 5000;
+const COMPOSITE_KEY_HEX_LEN: usize = 32;
+const METRICS_PREFIX: &str = "trading_bot.public_trades";
+
+/// Public trade stream error types
+#[derive(Error, Debug)]
+pub enum TradeStreamError {
+    #[error("invalid trade: {0}")]
+    InvalidTrade(String),
+    #[error("trade store error: {0}")]
+    Store(String),
+}
+
+/// A print from a venue's public trade feed, normalized across venues
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublicTrade {
+    pub pair: String,
+    pub exchange: String,
+    pub price: Decimal,
+    pub size: Decimal,
+    /// Taker side of the print
+    pub side: TradeSide,
+    pub timestamp: DateTime<Utc>,
+    /// Venue-assigned trade id, when the venue publishes one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trade_id: Option<String>,
+}
+
+impl PublicTrade {
+    /// Creates a validated print without a venue trade id
+    pub fn new(
+        pair: String,
+        exchange: String,
+        price: Decimal,
+        size: Decimal,
+        side: TradeSide,
+        timestamp: DateTime<Utc>,
+    ) -> Result<Self, TradeStreamError> {
+        let trade = Self {
+            pair,
+            exchange,
+            price,
+            size,
+            side,
+            timestamp,
+            trade_id: None,
+        };
+        trade.validate_at(current_timestamp())?;
+        Ok(trade)
+    }
+
+    /// Attaches the venue's trade id, which then keys deduplication
+    pub fn with_trade_id(mut self, trade_id: impl Into<String>) -> Self {
+        let trade_id = trade_id.into();
+        self.trade_id = (!trade_id.is_empty()).then_some(trade_id);
+        self
+    }
+
+    /// Checks the print is well formed and not dated in the future relative to `now`
+    pub fn validate_at(&self, now: DateTime<Utc>) -> Result<(), TradeStreamError> {
+        let mut assets = self.pair.split('/');
+        let well_formed = matches!(
+            (assets.next(), assets.next(), assets.next()),
+            (Some(base), Some(quote), None) if !base.is_empty() && !quote.is_empty()
+        );
+        if !well_formed {
+            return Err(TradeStreamError::InvalidTrade(format!(
+                "trading pair must be in format BASE/QUOTE: {}",
+                self.pair
+            )));
+        }
+        if self.exchange.parse::<DexType>().is_err() {
+            return Err(TradeStreamError::InvalidTrade(format!(
+                "unknown exchange: {}",
+                self.exchange
+            )));
+        }
+        // Venue precision rules apply to quotes; prints are stored at the venue's own scale
+        if self.price <= Decimal::ZERO {
+            return Err(TradeStreamError::InvalidTrade("price must be positive".to_string()));
+        }
+        if self.size <= Decimal::ZERO {
+            return Err(TradeStreamError::InvalidTrade("size must be positive".to_string()));
+        }
+        if self.timestamp - now > chrono::Duration::milliseconds(MAX_CLOCK_SKEW_MS) {
+            return Err(TradeStreamError::InvalidTrade(format!(
+                "timestamp {} is in the future",
+                self.timestamp
+            )));
+        }
+        Ok(())
+    }
+
+    /// Key identifying the print across reconnects: the venue trade id where available,
+    /// otherwise a hash of the print's contents with decimals normalized
+    pub fn dedup_key(&self) -> String {
+        if let Some(trade_id) = &self.trade_id {
+            return format!("{}:{}", self.exchange, trade_id);
+        }
+
+        let side = match self.side {
+            TradeSide::Buy => "buy",
+            TradeSide::Sell => "sell",
+        };
+        let digest = Sha256::digest(format!(
+            "{}|{}|{}|{}|{}",
+            self.pair,
+            self.price.normalize(),
+            self.size.normalize(),
+            side,
+            self.timestamp.timestamp_nanos_opt().unwrap_or_default(),
+        ));
+        let mut hash = hex::encode(digest);
+        hash.truncate(COMPOSITE_KEY_HEX_LEN);
+        format!("{}:h:{}", self.exchange, hash)
+    }
+}
+
+/// Reordering and deduplication settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeStreamConfig {
+    /// How long a print is held so earlier prints arriving late can be released before it
+    pub reorder_window: Duration,
+    /// How long a print's key is remembered to drop resends
+    pub dedupe_ttl: Duration,
+    /// Upper bound on remembered keys; the oldest are forgotten first
+    pub max_tracked_keys: usize,
+    pub release_interval: Duration,
+    pub flush_interval: Duration,
+}
+
+impl Default for TradeStreamConfig {
+    fn default() -> Self {
+        Self {
+            reorder_window: DEFAULT_REORDER_WINDOW,
+            dedupe_ttl: DEFAULT_DEDUPE_TTL,
+            max_tracked_keys: DEFAULT_MAX_TRACKED_KEYS,
+            release_interval: DEFAULT_RELEASE_INTERVAL,
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+        }
+    }
+}
+
+/// What happened to a print offered to the sequencer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// Held until its reorder window passes
+    Accepted,
+    /// Already seen, typically resent after a reconnect
+    Duplicate,
+    /// Older than prints already released for its pair
+    Late,
+}
+
+/// Drops repeated prints and releases each pair's prints in timestamp order
+#[derive(Debug)]
+pub struct TradeSequencer {
+    config: TradeStreamConfig,
+    seen: HashSet<String>,
+    /// Keys in arrival order for TTL and capacity eviction
+    seen_order: VecDeque<(DateTime<Utc>, String)>,
+    /// Held prints per pair, keyed by timestamp then dedup key
+    pending: HashMap<String, BTreeMap<(DateTime<Utc>, String), PublicTrade>>,
+    /// Timestamp of the last released print per pair
+    released_until: HashMap<String, DateTime<Utc>>,
+}
+
+impl TradeSequencer {
+    pub fn new(config: TradeStreamConfig) -> Self {
+        Self {
+            config,
+            seen: HashSet::new(),
+            seen_order: VecDeque::new(),
+            pending: HashMap::new(),
+            released_until: HashMap::new(),
+        }
+    }
+
+    /// Offers a print received at `now`
+    pub fn push(&mut self, trade: PublicTrade, now: DateTime<Utc>) -> Admission {
+        self.evict_keys(now);
+
+        let key = trade.dedup_key();
+        if self.seen.contains(&key) {
+            return Admission::Duplicate;
+        }
+        if matches!(self.released_until.get(&trade.pair), Some(until) if trade.timestamp < *until) {
+            return Admission::Late;
+        }
+
+        self.seen.insert(key.clone());
+        self.seen_order.push_back((now, key.clone()));
+        self.pending
+            .entry(trade.pair.clone())
+            .or_default()
+            .insert((trade.timestamp, key), trade);
+        Admission::Accepted
+    }
+
+    /// Prints whose reorder window has passed by `now`, oldest first
+    pub fn release(&mut self, now: DateTime<Utc>) -> Vec<PublicTrade> {
+        let window = chrono::Duration::from_std(self.config.reorder_window)
+            .unwrap_or_else(|_| chrono::Duration::zero());
+        self.release_through(now - window)
+    }
+
+    /// Releases every held print regardless of its reorder window
+    pub fn drain(&mut self) -> Vec<PublicTrade> {
+        self.release_through(DateTime::<Utc>::MAX_UTC)
+    }
+
+    /// Prints currently held for reordering
+    pub fn pending_len(&self) -> usize {
+        self.pending.values().map(BTreeMap::len).sum()
+    }
+
+    fn release_through(&mut self, cutoff: DateTime<Utc>) -> Vec<PublicTrade> {
+        let mut released = Vec::new();
+        for (pair, held) in self.pending.iter_mut() {
+            while let Some(entry) = held.first_entry() {
+                if entry.key().0 > cutoff {
+                    break;
+                }
+                let trade = entry.remove();
+                self.released_until.insert(pair.clone(), trade.timestamp);
+                released.push(trade);
+            }
+        }
+        self.pending.retain(|_, held| !held.is_empty());
+        released.sort_by_key(|trade| trade.timestamp);
+        released
+    }
+
+    fn evict_keys(&mut self, now: DateTime<Utc>) {
+        let ttl = chrono::Duration::from_std(self.config.dedupe_ttl)
+            .unwrap_or_else(|_| chrono::Duration::max_value());
+        while let Some((seen_at, _)) = self.seen_order.front() {
+            if now - *seen_at <= ttl && self.seen_order.len() <= self.config.max_tracked_keys {
+                break;
+            }
+            if let Some((_, key)) = self.seen_order.pop_front() {
+                self.seen.remove(&key);
+            }
+        }
+    }
+}
+
+/// Persistence for released prints
+#[async_trait]
+pub trait PublicTradeStore: Send + Sync {
+    /// Stores prints, skipping any already stored, returning how many were written
+    async fn insert_trades(&self, trades: &[PublicTrade]) -> Result<u64, TradeStreamError>;
+}
+
+/// Deduplicated, ordered public trade stream shared by collectors, subscribers and storage
+pub struct PublicTradeStream {
+    config: TradeStreamConfig,
+    sequencer: Mutex<TradeSequencer>,
+    pending_flush: Mutex<Vec<PublicTrade>>,
+    store: Arc<dyn PublicTradeStore>,
+    event_tx: broadcast::Sender<PublicTrade>,
+}
+
+impl std::fmt::Debug for PublicTradeStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PublicTradeStream")
+            .field("config", &self.config)
+            .field("pending", &self.sequencer.lock().pending_len())
+            .finish()
+    }
+}
+
+impl PublicTradeStream {
+    pub fn new(config: TradeStreamConfig, store: Arc<dyn PublicTradeStore>) -> Self {
+        let (event_tx, _) = broadcast::channel(PUBLIC_TRADE_EVENT_BUFFER);
+
+        Self {
+            sequencer: Mutex::new(TradeSequencer::new(config.clone())),
+            config,
+            pending_flush: Mutex::new(Vec::new()),
+            store,
+            event_tx,
+        }
+    }
+
+    /// Subscribes to released prints, in timestamp order per pair
+    pub fn subscribe(&self) -> broadcast::Receiver<PublicTrade> {
+        self.event_tx.subscribe()
+    }
+
+    /// Offers a print received at `now`
+    pub fn ingest_at(&self, trade: PublicTrade, now: DateTime<Utc>) -> Admission {
+        let exchange = trade.exchange.clone();
+        let admission = self.sequencer.lock().push(trade, now);
+        match admission {
+            Admission::Accepted => counter!(format!("{}.accepted", METRICS_PREFIX), 1),
+            Admission::Duplicate => {
+                counter!(format!("{}.duplicates", METRICS_PREFIX), 1, "exchange" => exchange)
+            }
+            Admission::Late => {
+                counter!(format!("{}.late_dropped", METRICS_PREFIX), 1, "exchange" => exchange)
+            }
+        }
+        admission
+    }
+
+    /// Publishes prints whose reorder window has passed by `now` and queues them for storage
+    pub fn release_at(&self, now: DateTime<Utc>) -> Vec<PublicTrade> {
+        let released = self.sequencer.lock().release(now);
+        self.publish(&released);
+        released
+    }
+
+    /// Writes queued prints to the store; on failure they stay queued for the next flush
+    pub async fn flush(&self) -> Result<u64, TradeStreamError> {
+        let batch = std::mem::take(&mut *self.pending_flush.lock());
+        if batch.is_empty() {
+            return Ok(0);
+        }
+
+        match self.store.insert_trades(&batch).await {
+            Ok(written) => {
+                histogram!(format!("{}.flush_batch_size", METRICS_PREFIX), batch.len() as f64);
+                Ok(written)
+            }
+            Err(e) => {
+                let mut pending = self.pending_flush.lock();
+                let newer = std::mem::replace(&mut *pending, batch);
+                pending.extend(newer);
+                Err(e)
+            }
+        }
+    }
+
+    fn publish(&self, released: &[PublicTrade]) {
+        if released.is_empty() {
+            return;
+        }
+        for trade in released {
+            let _ = self.event_tx.send(trade.clone());
+        }
+        self.pending_flush.lock().extend_from_slice(released);
+        counter!(format!("{}.released", METRICS_PREFIX), released.len() as u64);
+    }
+
+    /// Consumes prints from the collectors, releasing and persisting them until the
+    /// channel closes, then drains whatever is still held
+    pub fn spawn(self: Arc<Self>, mut trades: mpsc::Receiver<PublicTrade>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut release_ticker = tokio::time::interval(self.config.release_interval);
+            let mut flush_ticker = tokio::time::interval(self.config.flush_interval);
+
+            loop {
+                tokio::select! {
+                    received = trades.recv() => match received {
+                        Some(trade) => {
+                            if self.ingest_at(trade, current_timestamp()) != Admission::Accepted {
+                                debug!("Skipped repeated or late public trade");
+                            }
+                        }
+                        None => break,
+                    },
+                    _ = release_ticker.tick() => {
+                        self.release_at(current_timestamp());
+                        gauge!(
+                            format!("{}.pending", METRICS_PREFIX),
+                            self.sequencer.lock().pending_len() as f64
+                        );
+                    }
+                    _ = flush_ticker.tick() => {
+                        if let Err(e) = self.flush().await {
+                            error!("Failed to persist public trades: {}", e);
+                        }
+                    }
+                }
+            }
+
+            let remaining = self.sequencer.lock().drain();
+            self.publish(&remaining);
+            if let Err(e) = self.flush().await {
+                warn!("Failed to persist public trades on shutdown: {}", e);
+            }
+            info!("Public trade channel closed, stopping trade stream");
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        trades: Mutex<Vec<PublicTrade>>,
+        fail: std::sync::atomic::AtomicBool,
+    }
+
+    #[async_trait]
+    impl PublicTradeStore for MemoryStore {
+        async fn insert_trades(&self, trades: &[PublicTrade]) -> Result<u64, TradeStreamError> {
+            if self.fail.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(TradeStreamError::Store("database unavailable".to_string()));
+            }
+            self.trades.lock().extend_from_slice(trades);
+            Ok(trades.len() as u64)
+        }
+    }
+
+    fn at(ms: i64) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(1_700_000_000_000 + ms).unwrap()
+    }
+
+    fn print(pair: &str, price: Decimal, ms: i64) -> PublicTrade {
+        PublicTrade {
+            pair: pair.to_string(),
+            exchange: "drift".to_string(),
+            price,
+            size: dec!(1.5),
+            side: TradeSide::Buy,
+            timestamp: at(ms),
+            trade_id: None,
+        }
+    }
+
+    #[test]
+    fn test_trade_validation() {
+        let now = at(0);
+        assert!(print("SOL/USDC", dec!(100), 0).validate_at(now).is_ok());
+        assert!(print("SOLUSDC", dec!(100), 0).validate_at(now).is_err());
+        assert!(print("SOL/USDC", dec!(0), 0).validate_at(now).is_err());
+        assert!(print("SOL/USDC", dec!(100), 60_000).validate_at(now).is_err());
+
+        let mut trade = print("SOL/USDC", dec!(100), 0);
+        trade.exchange = "binance".to_string();
+        assert!(trade.validate_at(now).is_err());
+        trade.exchange = "drift".to_string();
+        trade.size = dec!(-1);
+        assert!(trade.validate_at(now).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_prints_dropped() {
+        let mut sequencer = TradeSequencer::new(TradeStreamConfig::default());
+
+        // Venue ids key dedupe even when a resend differs in other fields
+        let with_id = print("SOL/USDC", dec!(100), 0).with_trade_id("fill-1");
+        let mut resent = with_id.clone();
+        resent.timestamp = at(5);
+        assert_eq!(sequencer.push(with_id, at(10)), Admission::Accepted);
+        assert_eq!(sequencer.push(resent, at(20)), Admission::Duplicate);
+
+        // Without ids the composite hash ignores decimal scale
+        assert_eq!(sequencer.push(print("SOL/USDC", dec!(101.5), 1), at(10)), Admission::Accepted);
+        assert_eq!(sequencer.push(print("SOL/USDC", dec!(101.50), 1), at(30)), Admission::Duplicate);
+
+        // A different print at the same instant is kept
+        assert_eq!(sequencer.push(print("SOL/USDC", dec!(101.6), 1), at(30)), Admission::Accepted);
+
+        let released = sequencer.drain();
+        assert_eq!(released.len(), 3);
+
+        // Replayed after a reconnect, once released
+        assert_eq!(sequencer.push(print("SOL/USDC", dec!(101.5), 1), at(40)), Admission::Duplicate);
+    }
+
+    #[test]
+    fn test_dedupe_keys_expire() {
+        let config = TradeStreamConfig {
+            dedupe_ttl: Duration::from_secs(60),
+            reorder_window: Duration::ZERO,
+            ..TradeStreamConfig::default()
+        };
+        let mut sequencer = TradeSequencer::new(config);
+
+        assert_eq!(sequencer.push(print("SOL/USDC", dec!(100), 0), at(0)), Admission::Accepted);
+        sequencer.release(at(0));
+        assert_eq!(sequencer.push(print("SOL/USDC", dec!(100), 0), at(30_000)), Admission::Duplicate);
+        // Forgotten once the TTL has passed
+        assert_eq!(sequencer.push(print("SOL/USDC", dec!(100), 0), at(61_000)), Admission::Accepted);
+    }
+
+    #[test]
+    fn test_out_of_order_prints_released_in_order() {
+        let mut sequencer = TradeSequencer::new(TradeStreamConfig::default());
+
+        for (price, ms) in [(dec!(103), 300), (dec!(101), 100), (dec!(104), 400), (dec!(102), 200)] {
+            assert_eq!(sequencer.push(print("SOL/USDC", price, ms), at(450)), Admission::Accepted);
+        }
+        sequencer.push(print("BONK/SOL", dec!(0.5), 250), at(450));
+
+        // Nothing is released inside the reorder window
+        assert!(sequencer.release(at(2000)).is_empty());
+
+        let released = sequencer.release(at(2300));
+        let times: Vec<i64> = released.iter().map(|t| t.timestamp.timestamp_millis() % 100_000).collect();
+        assert_eq!(times, vec![100, 200, 250, 300]);
+        assert_eq!(sequencer.pending_len(), 1);
+
+        // Older than what the pair has already released
+        assert_eq!(sequencer.push(print("SOL/USDC", dec!(99), 150), at(2300)), Admission::Late);
+        // Other pairs keep their own ordering
+        assert_eq!(sequencer.push(print("BONK/SOL", dec!(0.6), 260), at(2300)), Admission::Accepted);
+
+        let released = sequencer.release(at(2400));
+        let prices: Vec<Decimal> = released.iter().map(|t| t.price).collect();
+        assert_eq!(prices, vec![dec!(0.6), dec!(104)]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_publishes_and_persists_in_order() {
+        let store = Arc::new(MemoryStore::default());
+        let stream = PublicTradeStream::new(TradeStreamConfig::default(), store.clone());
+        let mut subscriber = stream.subscribe();
+
+        let prints = [
+            print("SOL/USDC", dec!(102), 200).with_trade_id("2"),
+            print("SOL/USDC", dec!(101), 100).with_trade_id("1"),
+            print("SOL/USDC", dec!(102), 200).with_trade_id("2"),
+            print("SOL/USDC", dec!(103), 300).with_trade_id("3"),
+            print("SOL/USDC", dec!(101), 100).with_trade_id("1"),
+        ];
+        let admissions: Vec<Admission> = prints.into_iter().map(|t| stream.ingest_at(t, at(300))).collect();
+        assert_eq!(admissions.iter().filter(|a| **a == Admission::Duplicate).count(), 2);
+
+        let released = stream.release_at(at(3000));
+        assert_eq!(released.len(), 3);
+        for expected in ["1", "2", "3"] {
+            assert_eq!(subscriber.try_recv().unwrap().trade_id.as_deref(), Some(expected));
+        }
+
+        // Failed writes stay queued
+        store.fail.store(true, std::sync::atomic::Ordering::SeqCst);
+        assert!(stream.flush().await.is_err());
+        store.fail.store(false, std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(stream.flush().await.unwrap(), 3);
+        assert_eq!(stream.flush().await.unwrap(), 0);
+
+        let stored: Vec<Decimal> = store.trades.lock().iter().map(|t| t.price).collect();
+        assert_eq!(stored, vec![dec!(101), dec!(102), dec!(103)]);
+    }
+}
@@ -0,0 +1,4 @@
+-- Down migration for 10_strategy_audit.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS strategy_audit_log;
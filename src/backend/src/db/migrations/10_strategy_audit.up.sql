@@ -0,0 +1,16 @@
+-- Strategy supervision migration for AI-powered Solana trading bot
+-- Version: 10.0
+-- Dependencies: 1_initial_schema.up.sql
+-- Purpose: Stores automatic pauses and manual resumes of strategies with their triggers
+
+CREATE TABLE IF NOT EXISTS strategy_audit_log (
+    id UUID PRIMARY KEY,
+    strategy_id TEXT NOT NULL,
+    action VARCHAR(32) NOT NULL,
+    detail TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Audit history by strategy
+CREATE INDEX IF NOT EXISTS idx_strategy_audit_strategy_time
+    ON strategy_audit_log (strategy_id, created_at DESC);
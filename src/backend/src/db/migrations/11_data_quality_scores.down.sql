@@ -0,0 +1,4 @@
+-- Down migration for 11_data_quality_scores.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS data_quality_scores;
@@ -0,0 +1,21 @@
+-- Market data quality migration for AI-powered Solana trading bot
+-- Version: 11.0
+-- Dependencies: 2_market_data_tables.up.sql
+-- Purpose: Stores hourly quality scores per exchange and trading pair feed
+
+CREATE TABLE IF NOT EXISTS data_quality_scores (
+    id UUID PRIMARY KEY,
+    exchange VARCHAR(32) NOT NULL,
+    trading_pair VARCHAR(32) NOT NULL,
+    score DOUBLE PRECISION NOT NULL CHECK (score BETWEEN 0 AND 1),
+    staleness DOUBLE PRECISION NOT NULL,
+    gap_frequency DOUBLE PRECISION NOT NULL,
+    spread DOUBLE PRECISION NOT NULL,
+    anomaly_rate DOUBLE PRECISION NOT NULL,
+    demoted BOOLEAN NOT NULL DEFAULT FALSE,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Score history by feed
+CREATE INDEX IF NOT EXISTS idx_data_quality_source_time
+    ON data_quality_scores (exchange, trading_pair, recorded_at DESC);
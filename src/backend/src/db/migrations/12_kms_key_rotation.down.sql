@@ -0,0 +1,6 @@
+-- Down migration for 12_kms_key_rotation.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS key_rotation_runs;
+DROP TABLE IF EXISTS encrypted_secrets;
+DROP TABLE IF EXISTS data_keys;
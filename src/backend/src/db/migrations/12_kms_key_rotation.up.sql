@@ -0,0 +1,50 @@
+-- KMS data key rotation migration for AI-powered Solana trading bot
+-- Version: 12.0
+-- Dependencies: 1_initial_schema.up.sql
+-- Purpose: Stores versioned KMS-wrapped data keys, secrets sealed with them, and resumable
+--          rotation progress
+
+CREATE TABLE IF NOT EXISTS data_keys (
+    version INTEGER PRIMARY KEY CHECK (version > 0),
+    wrapped_key BYTEA NOT NULL,
+    status VARCHAR(16) NOT NULL CHECK (status IN ('active', 'retiring', 'retired')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    retired_at TIMESTAMPTZ
+);
+
+-- Only one data key seals new data at a time
+CREATE UNIQUE INDEX IF NOT EXISTS idx_data_keys_single_active
+    ON data_keys (status) WHERE status = 'active';
+
+CREATE TABLE IF NOT EXISTS encrypted_secrets (
+    id UUID PRIMARY KEY,
+    scope VARCHAR(32) NOT NULL,
+    name VARCHAR(128) NOT NULL,
+    key_version INTEGER NOT NULL,
+    nonce BYTEA NOT NULL,
+    ciphertext BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (scope, name)
+);
+
+-- Secrets still sealed with a given key version
+CREATE INDEX IF NOT EXISTS idx_encrypted_secrets_key_version
+    ON encrypted_secrets (key_version);
+
+CREATE TABLE IF NOT EXISTS key_rotation_runs (
+    id UUID PRIMARY KEY,
+    target_version INTEGER NOT NULL REFERENCES data_keys(version),
+    status VARCHAR(16) NOT NULL CHECK (status IN ('running', 'interrupted', 'completed')),
+    total_secrets BIGINT NOT NULL DEFAULT 0,
+    processed_secrets BIGINT NOT NULL DEFAULT 0,
+    last_secret_id UUID,
+    error TEXT,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+-- Latest unfinished run is resumed first
+CREATE INDEX IF NOT EXISTS idx_key_rotation_runs_status
+    ON key_rotation_runs (status, started_at DESC);
@@ -0,0 +1,4 @@
+-- Down migration for 13_state_snapshots.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS state_snapshots;
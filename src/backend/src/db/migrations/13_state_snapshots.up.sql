@@ -0,0 +1,18 @@
+-- State snapshot migration for AI-powered Solana trading bot
+-- Version: 13.0
+-- Dependencies: 1_initial_schema.up.sql
+-- Purpose: Stores versioned, checksummed snapshots of runtime bot state for disaster recovery
+
+CREATE TABLE IF NOT EXISTS state_snapshots (
+    id UUID PRIMARY KEY,
+    format_version INTEGER NOT NULL CHECK (format_version > 0),
+    checksum CHAR(64) NOT NULL,
+    payload BYTEA NOT NULL,
+    trigger VARCHAR(16) NOT NULL CHECK (trigger IN ('manual', 'scheduled')),
+    s3_key TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Newest snapshots first for listing and retention pruning
+CREATE INDEX IF NOT EXISTS idx_state_snapshots_created
+    ON state_snapshots (created_at DESC);
@@ -0,0 +1,5 @@
+-- Down migration for 14_execution_stats.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS execution_stats;
+DROP TABLE IF EXISTS trade_executions;
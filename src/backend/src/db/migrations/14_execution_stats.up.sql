@@ -0,0 +1,39 @@
+-- Execution statistics migration for AI-powered Solana trading bot
+-- Version: 14.0
+-- Dependencies: 1_initial_schema.up.sql
+-- Purpose: Records every execution attempt and materializes per (pair, exchange) execution
+--          quality over rolling windows for the analytics API and routing priors
+
+CREATE TABLE IF NOT EXISTS trade_executions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    trading_pair VARCHAR(20) NOT NULL,
+    exchange VARCHAR(20) NOT NULL,
+    side VARCHAR(4) NOT NULL CHECK (side IN ('buy', 'sell')),
+    requested_size NUMERIC(18,8) NOT NULL CHECK (requested_size > 0),
+    filled_size NUMERIC(18,8) NOT NULL CHECK (filled_size >= 0),
+    expected_price NUMERIC(18,8) NOT NULL,
+    executed_price NUMERIC(18,8),
+    fee NUMERIC(18,8) NOT NULL DEFAULT 0 CHECK (fee >= 0),
+    latency_ms BIGINT NOT NULL CHECK (latency_ms >= 0),
+    mev_value NUMERIC(18,8) NOT NULL DEFAULT 0,
+    executed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Materialization scans the longest window
+CREATE INDEX IF NOT EXISTS idx_trade_executions_executed
+    ON trade_executions (executed_at DESC);
+
+CREATE TABLE IF NOT EXISTS execution_stats (
+    trading_pair VARCHAR(20) NOT NULL,
+    exchange VARCHAR(20) NOT NULL,
+    stats_window VARCHAR(4) NOT NULL CHECK (stats_window IN ('1d', '7d', '30d')),
+    sample_count BIGINT NOT NULL,
+    fill_rate NUMERIC(6,4) NOT NULL,
+    avg_slippage_bps NUMERIC(12,2) NOT NULL,
+    p95_slippage_bps NUMERIC(12,2) NOT NULL,
+    avg_latency_ms NUMERIC(12,2) NOT NULL,
+    mev_capture_bps NUMERIC(12,2) NOT NULL,
+    fee_bps NUMERIC(12,2) NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (trading_pair, stats_window, exchange)
+);
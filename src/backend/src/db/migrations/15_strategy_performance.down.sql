@@ -0,0 +1,5 @@
+-- Down migration for 15_strategy_performance.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS strategy_equity;
+DROP TABLE IF EXISTS strategy_trades;
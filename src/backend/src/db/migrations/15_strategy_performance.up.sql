@@ -0,0 +1,27 @@
+-- Strategy performance migration for AI-powered Solana trading bot
+-- Version: 15.0
+-- Dependencies: 1_initial_schema.up.sql
+-- Purpose: Stores realized P&L per strategy trade and the cumulative P&L buckets
+--          materialized from them for equity curves
+
+CREATE TABLE IF NOT EXISTS strategy_trades (
+    id UUID PRIMARY KEY,
+    strategy_id TEXT NOT NULL,
+    trading_pair VARCHAR(20) NOT NULL,
+    realized_pnl NUMERIC(24,8) NOT NULL,
+    closed_at TIMESTAMPTZ NOT NULL
+);
+
+-- Trade history by strategy
+CREATE INDEX IF NOT EXISTS idx_strategy_trades_strategy_time
+    ON strategy_trades (strategy_id, closed_at DESC);
+
+CREATE TABLE IF NOT EXISTS strategy_equity (
+    strategy_id TEXT NOT NULL,
+    granularity VARCHAR(4) NOT NULL CHECK (granularity IN ('1m', '5m', '1h')),
+    bucket_start TIMESTAMPTZ NOT NULL,
+    pnl NUMERIC(24,8) NOT NULL,
+    cumulative_pnl NUMERIC(24,8) NOT NULL,
+    trade_count BIGINT NOT NULL CHECK (trade_count > 0),
+    PRIMARY KEY (strategy_id, granularity, bucket_start)
+);
@@ -0,0 +1,12 @@
+-- Down migration for 16_schema_consolidation.up.sql
+-- Reversible: yes
+
+DROP INDEX IF EXISTS idx_strategies_name;
+ALTER TABLE strategies ALTER COLUMN performance_score TYPE DECIMAL(10,4);
+ALTER TABLE strategies DROP COLUMN IF EXISTS win_rate;
+ALTER TABLE strategies DROP COLUMN IF EXISTS name;
+ALTER TABLE positions DROP CONSTRAINT IF EXISTS positions_portfolio_id_fkey;
+ALTER TABLE positions ADD CONSTRAINT positions_portfolio_id_fkey
+    FOREIGN KEY (portfolio_id) REFERENCES portfolios(id);
+ALTER TABLE positions DROP COLUMN IF EXISTS pnl;
+ALTER TABLE portfolios DROP COLUMN IF EXISTS balance;
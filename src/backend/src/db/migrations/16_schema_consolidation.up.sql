@@ -0,0 +1,28 @@
+-- Schema consolidation migration for AI-powered Solana trading bot
+-- Version: 16.0
+-- Dependencies: 1_initial_schema.up.sql, 3_strategy_tables.up.sql
+-- Purpose: Folds in the columns and constraints previously created only by the inline schema
+--          bootstrap so PortfolioRecord, PositionRecord and StrategyRecord resolve against
+--          migrated tables. The market_data hypertable, its pair/time index and 90-day
+--          retention policy from that path are already covered by V2.
+
+-- Portfolio cash balance read by PortfolioRecord
+ALTER TABLE portfolios ADD COLUMN IF NOT EXISTS balance NUMERIC(20,6) NOT NULL DEFAULT 0;
+
+-- Position P&L read by PositionRecord
+ALTER TABLE positions ADD COLUMN IF NOT EXISTS pnl NUMERIC(20,6) NOT NULL DEFAULT 0;
+
+-- Positions are removed with their portfolio
+ALTER TABLE positions DROP CONSTRAINT IF EXISTS positions_portfolio_id_fkey;
+ALTER TABLE positions ADD CONSTRAINT positions_portfolio_id_fkey
+    FOREIGN KEY (portfolio_id) REFERENCES portfolios(id) ON DELETE CASCADE;
+
+-- Strategy name and win rate read by StrategyRecord; existing rows are named by id
+ALTER TABLE strategies ADD COLUMN IF NOT EXISTS name VARCHAR(100);
+UPDATE strategies SET name = id::TEXT WHERE name IS NULL;
+ALTER TABLE strategies ALTER COLUMN name SET NOT NULL;
+ALTER TABLE strategies ADD COLUMN IF NOT EXISTS win_rate DOUBLE PRECISION NOT NULL DEFAULT 0;
+ALTER TABLE strategies ALTER COLUMN performance_score TYPE DOUBLE PRECISION;
+
+-- Strategy names are unique
+CREATE UNIQUE INDEX IF NOT EXISTS idx_strategies_name ON strategies (name);
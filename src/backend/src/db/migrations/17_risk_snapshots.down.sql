@@ -0,0 +1,4 @@
+-- Down migration for 17_risk_snapshots.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS risk_snapshots;
@@ -0,0 +1,34 @@
+-- Risk snapshot migration for AI-powered Solana trading bot
+-- Version: 17.0
+-- Dependencies: TimescaleDB 2.11, 1_initial_schema.up.sql
+-- Purpose: Stores the periodic portfolio risk factor snapshots streamed to the dashboard.
+--          Headline factors are columns for charting; the full snapshot, including
+--          per-asset exposure and the reason behind each null factor, is kept as JSONB.
+
+CREATE TABLE IF NOT EXISTS risk_snapshots (
+    id UUID NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL,
+    total_value NUMERIC(24,8) NOT NULL,
+    net_exposure NUMERIC(24,8) NOT NULL,
+    gross_exposure NUMERIC(24,8) NOT NULL,
+    drawdown NUMERIC(12,8) NOT NULL,
+    value_at_risk NUMERIC(24,8),
+    margin_utilization NUMERIC(12,8),
+    circuit_breaker_tripped BOOLEAN NOT NULL,
+    payload JSONB NOT NULL,
+    PRIMARY KEY (id, computed_at)
+);
+
+-- Convert risk_snapshots to hypertable with 1-day chunks
+SELECT create_hypertable(
+    'risk_snapshots',
+    'computed_at',
+    chunk_time_interval => INTERVAL '1 day',
+    if_not_exists => TRUE
+);
+
+-- Recent snapshots first for the dashboard history
+CREATE INDEX IF NOT EXISTS idx_risk_snapshots_time ON risk_snapshots (computed_at DESC);
+
+-- Keep 30 days of snapshots
+SELECT add_retention_policy('risk_snapshots', INTERVAL '30 days', if_not_exists => TRUE);
@@ -0,0 +1,19 @@
+-- Down migration for 18_execution_benchmarks.up.sql
+-- Reversible: yes
+
+ALTER TABLE execution_stats
+    DROP COLUMN IF EXISTS avg_twap_deviation_bps,
+    DROP COLUMN IF EXISTS avg_vwap_deviation_bps,
+    DROP COLUMN IF EXISTS benchmarked_count;
+
+ALTER TABLE trade_executions
+    DROP COLUMN IF EXISTS benchmark_computed_at,
+    DROP COLUMN IF EXISTS benchmark_low_coverage,
+    DROP COLUMN IF EXISTS benchmark_coverage,
+    DROP COLUMN IF EXISTS benchmark_tick_count,
+    DROP COLUMN IF EXISTS twap_deviation_bps,
+    DROP COLUMN IF EXISTS vwap_deviation_bps,
+    DROP COLUMN IF EXISTS twap_benchmark,
+    DROP COLUMN IF EXISTS vwap_benchmark,
+    DROP COLUMN IF EXISTS benchmark_window_end,
+    DROP COLUMN IF EXISTS benchmark_window_start;
@@ -0,0 +1,24 @@
+-- Execution benchmark migration for AI-powered Solana trading bot
+-- Version: 18.0
+-- Dependencies: 14_execution_stats.up.sql
+-- Purpose: Stores the post-trade market VWAP/TWAP benchmarks computed over each execution
+--          window, with the fill's deviation from them and the window's tick coverage, and
+--          adds the average deviations to the materialized execution statistics
+
+ALTER TABLE trade_executions
+    ADD COLUMN IF NOT EXISTS benchmark_window_start TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS benchmark_window_end TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS vwap_benchmark NUMERIC(18,8),
+    ADD COLUMN IF NOT EXISTS twap_benchmark NUMERIC(18,8),
+    ADD COLUMN IF NOT EXISTS vwap_deviation_bps NUMERIC(12,2),
+    ADD COLUMN IF NOT EXISTS twap_deviation_bps NUMERIC(12,2),
+    ADD COLUMN IF NOT EXISTS benchmark_tick_count BIGINT CHECK (benchmark_tick_count >= 0),
+    ADD COLUMN IF NOT EXISTS benchmark_coverage NUMERIC(6,4)
+        CHECK (benchmark_coverage BETWEEN 0 AND 1),
+    ADD COLUMN IF NOT EXISTS benchmark_low_coverage BOOLEAN,
+    ADD COLUMN IF NOT EXISTS benchmark_computed_at TIMESTAMPTZ;
+
+ALTER TABLE execution_stats
+    ADD COLUMN IF NOT EXISTS benchmarked_count BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS avg_vwap_deviation_bps NUMERIC(12,2),
+    ADD COLUMN IF NOT EXISTS avg_twap_deviation_bps NUMERIC(12,2);
@@ -0,0 +1,9 @@
+-- Down migration for 19_data_gaps.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS data_gaps;
+
+DELETE FROM market_data WHERE source = 'backfill';
+ALTER TABLE market_data DROP CONSTRAINT IF EXISTS market_data_volume_check;
+ALTER TABLE market_data ADD CONSTRAINT market_data_volume_check CHECK (volume > 0);
+ALTER TABLE market_data DROP COLUMN IF EXISTS source;
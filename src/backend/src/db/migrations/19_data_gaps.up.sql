@@ -0,0 +1,36 @@
+-- Market data gap migration for AI-powered Solana trading bot
+-- Version: 19.0
+-- Dependencies: 2_market_data_tables.up.sql
+-- Purpose: Flags market data rows backfilled from venue REST history, and tracks detected
+--          gaps in each feed with a resumable backfill cursor
+
+-- Backfilled rows are kept apart from live data; price-only history carries zero volume
+ALTER TABLE market_data
+    ADD COLUMN IF NOT EXISTS source VARCHAR(16) NOT NULL DEFAULT 'live'
+        CHECK (source IN ('live', 'backfill'));
+
+ALTER TABLE market_data DROP CONSTRAINT IF EXISTS market_data_volume_check;
+ALTER TABLE market_data ADD CONSTRAINT market_data_volume_check
+    CHECK (volume > 0 OR (source = 'backfill' AND volume = 0));
+
+CREATE TABLE IF NOT EXISTS data_gaps (
+    id UUID PRIMARY KEY,
+    trading_pair VARCHAR(32) NOT NULL,
+    exchange VARCHAR(32) NOT NULL,
+    gap_start TIMESTAMPTZ NOT NULL,
+    gap_end TIMESTAMPTZ NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'open'
+        CHECK (status IN ('open', 'backfilling', 'filled', 'unfillable')),
+    backfill_cursor TIMESTAMPTZ,
+    backfilled_rows BIGINT NOT NULL DEFAULT 0 CHECK (backfilled_rows >= 0),
+    last_error TEXT,
+    detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (gap_end > gap_start),
+    UNIQUE (trading_pair, exchange, gap_start)
+);
+
+-- Recent gaps and pending backfills
+CREATE INDEX IF NOT EXISTS idx_data_gaps_end ON data_gaps (gap_end DESC);
+CREATE INDEX IF NOT EXISTS idx_data_gaps_pending
+    ON data_gaps (status) WHERE status IN ('open', 'backfilling');
@@ -0,0 +1,13 @@
+-- Down migration for 1_initial_schema.up.sql
+-- Reversible: yes
+
+DROP POLICY IF EXISTS trade_access_policy ON trades;
+DROP POLICY IF EXISTS position_access_policy ON positions;
+DROP POLICY IF EXISTS portfolio_access_policy ON portfolios;
+DROP TABLE IF EXISTS trades;
+DROP TABLE IF EXISTS strategies;
+DROP TABLE IF EXISTS positions;
+DROP TABLE IF EXISTS portfolios;
+DROP MATERIALIZED VIEW IF EXISTS market_data_1m;
+DROP TABLE IF EXISTS market_data;
+DROP FUNCTION IF EXISTS update_updated_at_column();
@@ -8,13 +8,15 @@ CREATE EXTENSION IF NOT EXISTS "timescaledb";
 
 -- Market data time-series table with hypertable configuration
 CREATE TABLE market_data (
-    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    id UUID NOT NULL DEFAULT uuid_generate_v4(),
     trading_pair VARCHAR(20) NOT NULL,
     exchange VARCHAR(20) NOT NULL,
     price NUMERIC(18,8) NOT NULL CHECK (price > 0),
     volume NUMERIC(18,6) NOT NULL CHECK (volume > 0),
     timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Unique keys on a hypertable must include its partitioning column
+    PRIMARY KEY (id, timestamp)
 );
 
 -- Convert to hypertable with 1-day chunks
@@ -28,6 +30,7 @@ CREATE INDEX idx_market_data_pair_time ON market_data (trading_pair, timestamp D
 CREATE INDEX idx_market_data_exchange_time ON market_data (exchange, timestamp DESC);
 
 -- Set up compression policy for market data
+ALTER TABLE market_data SET (timescaledb.compress, timescaledb.compress_segmentby = 'trading_pair, exchange');
 SELECT add_compression_policy('market_data', INTERVAL '7 days');
 
 -- Create continuous aggregates for different time intervals
@@ -43,7 +46,8 @@ SELECT
     LAST(price, timestamp) AS close_price,
     SUM(volume) AS total_volume
 FROM market_data
-GROUP BY bucket, trading_pair, exchange;
+GROUP BY bucket, trading_pair, exchange
+WITH NO DATA;
 
 -- Portfolio management table
 CREATE TABLE portfolios (
@@ -154,6 +158,15 @@ ALTER TABLE portfolios ENABLE ROW LEVEL SECURITY;
 ALTER TABLE positions ENABLE ROW LEVEL SECURITY;
 ALTER TABLE trades ENABLE ROW LEVEL SECURITY;
 
+-- Role the access policies apply to
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_roles WHERE rolname = 'authenticated_users') THEN
+        CREATE ROLE authenticated_users NOLOGIN;
+    END IF;
+END
+$$;
+
 -- Create policies for portfolio access
 CREATE POLICY portfolio_access_policy ON portfolios
     FOR ALL
@@ -0,0 +1,6 @@
+-- Down migration for 20_position_events.up.sql
+-- Reversible: yes
+
+DROP TRIGGER IF EXISTS position_events_append_only ON position_events;
+DROP FUNCTION IF EXISTS reject_position_event_mutation();
+DROP TABLE IF EXISTS position_events;
@@ -0,0 +1,36 @@
+-- Position event log migration for AI-powered Solana trading bot
+-- Version: 20.0
+-- Dependencies: 1_initial_schema.up.sql
+-- Purpose: Append-only log of typed position lifecycle events (open, size change, price
+--          mark, bracket attach, emergency trigger, close) so closed positions can be
+--          replayed for post-mortems
+
+CREATE TABLE IF NOT EXISTS position_events (
+    id UUID PRIMARY KEY,
+    position_id UUID NOT NULL,
+    sequence BIGINT NOT NULL CHECK (sequence >= 0),
+    event_type VARCHAR(32) NOT NULL CHECK (event_type IN (
+        'opened', 'size_changed', 'price_marked', 'bracket_attached',
+        'emergency_triggered', 'closed'
+    )),
+    payload JSONB NOT NULL,
+    cause_id UUID,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (position_id, sequence)
+);
+
+-- Events of one order or trade
+CREATE INDEX IF NOT EXISTS idx_position_events_cause
+    ON position_events (cause_id) WHERE cause_id IS NOT NULL;
+
+-- Reject updates and deletes so the history stays append-only
+CREATE OR REPLACE FUNCTION reject_position_event_mutation() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'position_events is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER position_events_append_only
+    BEFORE UPDATE OR DELETE ON position_events
+    FOR EACH ROW EXECUTE FUNCTION reject_position_event_mutation();
@@ -0,0 +1,4 @@
+-- Down migration for 21_maintenance_windows.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS maintenance_windows;
@@ -0,0 +1,17 @@
+-- Maintenance window migration for AI-powered Solana trading bot
+-- Version: 21.0
+-- Dependencies: 1_initial_schema.up.sql
+-- Purpose: Persists scheduled maintenance windows so a restart before or during a window
+--          resumes its wind-down instead of dropping it
+
+CREATE TABLE IF NOT EXISTS maintenance_windows (
+    id UUID PRIMARY KEY,
+    starts_at TIMESTAMPTZ NOT NULL,
+    duration_secs BIGINT NOT NULL CHECK (duration_secs > 0),
+    mode VARCHAR(16) NOT NULL CHECK (mode IN ('pause_only', 'flatten')),
+    reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Windows that have not ended yet
+CREATE INDEX IF NOT EXISTS idx_maintenance_windows_start ON maintenance_windows (starts_at);
@@ -0,0 +1,5 @@
+-- Down migration for 22_public_trades.up.sql
+-- Reversible: yes
+
+SELECT remove_retention_policy('public_trades', if_exists => TRUE);
+DROP TABLE IF EXISTS public_trades;
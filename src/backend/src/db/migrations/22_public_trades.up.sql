@@ -0,0 +1,35 @@
+-- Public trade (fills) storage migration for AI-powered Solana trading bot
+-- Version: 22.0
+-- Dependencies: TimescaleDB 2.11, 2_market_data_tables.up.sql
+-- Purpose: Persists venue trade prints collected alongside quotes, deduplicated by venue
+--          trade id or a composite hash of the print
+
+CREATE TABLE IF NOT EXISTS public_trades (
+    trade_key VARCHAR(128) NOT NULL,
+    venue_trade_id VARCHAR(128),
+    trading_pair VARCHAR(64) NOT NULL,
+    exchange VARCHAR(20) NOT NULL CHECK (exchange IN ('jupiter', 'pump_fun', 'drift')),
+    price NUMERIC(28,12) NOT NULL CHECK (price > 0),
+    size NUMERIC(28,9) NOT NULL CHECK (size > 0),
+    side VARCHAR(4) NOT NULL CHECK (side IN ('buy', 'sell')),
+    timestamp TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Unique keys on a hypertable must include its partitioning column
+    PRIMARY KEY (trade_key, timestamp)
+);
+
+-- Convert public_trades to hypertable with 1-hour chunks
+SELECT create_hypertable(
+    'public_trades',
+    'timestamp',
+    chunk_time_interval => INTERVAL '1 hour',
+    if_not_exists => TRUE,
+    migrate_data => TRUE
+);
+
+-- Recent prints by pair
+CREATE INDEX IF NOT EXISTS idx_public_trades_pair_time
+    ON public_trades (trading_pair, timestamp DESC);
+
+-- Prints are high volume; keep two weeks
+SELECT add_retention_policy('public_trades', INTERVAL '14 days', if_not_exists => TRUE);
@@ -0,0 +1,6 @@
+-- Down migration for 23_strategy_versions.up.sql
+-- Reversible: yes
+
+DROP INDEX IF EXISTS idx_strategy_trades_version_time;
+ALTER TABLE strategy_trades DROP COLUMN IF EXISTS version;
+DROP TABLE IF EXISTS strategy_versions;
@@ -0,0 +1,23 @@
+-- Strategy versioning migration for AI-powered Solana trading bot
+-- Version: 23.0
+-- Dependencies: 15_strategy_performance.up.sql
+-- Purpose: Records every parameter set a strategy has run with and tags realized trades
+--          with the version that produced them
+
+CREATE TABLE IF NOT EXISTS strategy_versions (
+    strategy_id TEXT NOT NULL,
+    version INTEGER NOT NULL CHECK (version > 0),
+    param_hash CHAR(64) NOT NULL,
+    parameters JSONB NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    activated_at TIMESTAMPTZ,
+    PRIMARY KEY (strategy_id, version)
+);
+
+-- Trades closed before versioning keep a null version
+ALTER TABLE strategy_trades ADD COLUMN IF NOT EXISTS version INTEGER;
+
+-- Per-version trade history
+CREATE INDEX IF NOT EXISTS idx_strategy_trades_version_time
+    ON strategy_trades (strategy_id, version, closed_at);
@@ -0,0 +1,7 @@
+-- Down migration for 24_jobs.up.sql
+-- Reversible: yes
+
+DROP INDEX IF EXISTS idx_jobs_status_updated;
+DROP INDEX IF EXISTS idx_jobs_running_lease;
+DROP INDEX IF EXISTS idx_jobs_pending;
+DROP TABLE IF EXISTS jobs;
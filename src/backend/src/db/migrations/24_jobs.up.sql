@@ -0,0 +1,34 @@
+-- Background job queue migration for AI-powered Solana trading bot
+-- Version: 24.0
+-- Dependencies: 1_initial_schema.up.sql
+-- Purpose: Persists background jobs (exports, backfills, optimizations, reports) so they
+--          survive a restart; workers lease due jobs with FOR UPDATE SKIP LOCKED
+
+CREATE TABLE IF NOT EXISTS jobs (
+    id UUID PRIMARY KEY,
+    kind VARCHAR(64) NOT NULL,
+    payload JSONB NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'running', 'succeeded', 'dead_letter', 'cancelled')),
+    priority INTEGER NOT NULL DEFAULT 0,
+    attempts INTEGER NOT NULL DEFAULT 0 CHECK (attempts >= 0),
+    max_attempts INTEGER NOT NULL CHECK (max_attempts > 0),
+    last_error TEXT,
+    locked_by TEXT,
+    locked_until TIMESTAMPTZ,
+    run_after TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT running_jobs_locked CHECK (status <> 'running' OR locked_until IS NOT NULL)
+);
+
+-- Due jobs in claim order
+CREATE INDEX IF NOT EXISTS idx_jobs_pending
+    ON jobs (kind, priority DESC, run_after) WHERE status = 'pending';
+
+-- Leases that may have lapsed
+CREATE INDEX IF NOT EXISTS idx_jobs_running_lease
+    ON jobs (locked_until) WHERE status = 'running';
+
+-- Admin listing
+CREATE INDEX IF NOT EXISTS idx_jobs_status_updated ON jobs (status, updated_at DESC);
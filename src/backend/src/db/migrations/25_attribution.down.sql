@@ -0,0 +1,7 @@
+-- Down migration for 25_attribution.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS attribution_periods;
+DROP TABLE IF EXISTS attribution_balances;
+DROP INDEX IF EXISTS idx_attribution_entries_occurred;
+DROP TABLE IF EXISTS attribution_entries;
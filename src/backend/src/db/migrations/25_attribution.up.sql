@@ -0,0 +1,45 @@
+-- P&L attribution migration for AI-powered Solana trading bot
+-- Version: 25.0
+-- Dependencies: 24_jobs.up.sql
+-- Purpose: Tagged ledger of fills, fees, funding and transfers, observed wallet balances for
+--          reconciliation, and the daily attribution rollups served by the reports API
+
+CREATE TABLE IF NOT EXISTS attribution_entries (
+    id UUID PRIMARY KEY,
+    kind VARCHAR(16) NOT NULL CHECK (kind IN ('trade', 'fee', 'funding', 'transfer')),
+    wallet VARCHAR(64) NOT NULL,
+    strategy_id VARCHAR(100),
+    version INTEGER CHECK (version > 0),
+    venue VARCHAR(50),
+    trading_pair VARCHAR(64),
+    amount DECIMAL(30, 10) NOT NULL,
+    inventory DECIMAL(30, 10) NOT NULL DEFAULT 0,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    CONSTRAINT transfers_untagged CHECK (kind <> 'transfer' OR strategy_id IS NULL)
+);
+
+CREATE INDEX IF NOT EXISTS idx_attribution_entries_occurred ON attribution_entries (occurred_at);
+
+CREATE TABLE IF NOT EXISTS attribution_balances (
+    wallet VARCHAR(64) NOT NULL,
+    observed_at TIMESTAMPTZ NOT NULL,
+    balance DECIMAL(30, 10) NOT NULL,
+    PRIMARY KEY (wallet, observed_at)
+);
+
+-- One row per sub-account and day; untagged P&L is rolled up under 'unattributed'
+CREATE TABLE IF NOT EXISTS attribution_periods (
+    period_start TIMESTAMPTZ NOT NULL,
+    wallet VARCHAR(64) NOT NULL,
+    strategy_id VARCHAR(100) NOT NULL,
+    -- 0 for P&L predating versioning, since key columns cannot be null
+    version INTEGER NOT NULL DEFAULT 0,
+    venue VARCHAR(50) NOT NULL,
+    trading_pair VARCHAR(64) NOT NULL,
+    realized_pnl DECIMAL(30, 10) NOT NULL,
+    fees DECIMAL(30, 10) NOT NULL,
+    funding DECIMAL(30, 10) NOT NULL,
+    net_pnl DECIMAL(30, 10) NOT NULL,
+    trade_count BIGINT NOT NULL CHECK (trade_count >= 0),
+    PRIMARY KEY (period_start, wallet, strategy_id, version, venue, trading_pair)
+);
@@ -0,0 +1,5 @@
+-- Down migration for 26_market_regimes.up.sql
+-- Reversible: yes
+
+DROP INDEX IF EXISTS idx_market_regimes_pair_changed;
+DROP TABLE IF EXISTS market_regimes;
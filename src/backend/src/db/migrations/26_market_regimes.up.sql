@@ -0,0 +1,19 @@
+-- Market regime migration for AI-powered Solana trading bot
+-- Version: 26.0
+-- Dependencies: 25_attribution.up.sql
+-- Purpose: History of per-pair market regime changes with the features each classification
+--          was made from; the latest row per pair seeds the detector on startup
+
+CREATE TABLE IF NOT EXISTS market_regimes (
+    id UUID PRIMARY KEY,
+    trading_pair VARCHAR(64) NOT NULL,
+    previous_regime VARCHAR(16) CHECK (previous_regime IN ('quiet', 'trending', 'volatile', 'dislocated')),
+    regime VARCHAR(16) NOT NULL CHECK (regime IN ('quiet', 'trending', 'volatile', 'dislocated')),
+    volatility DOUBLE PRECISION NOT NULL CHECK (volatility >= 0),
+    r_squared DOUBLE PRECISION NOT NULL CHECK (r_squared BETWEEN 0 AND 1),
+    slope DOUBLE PRECISION NOT NULL,
+    volume_ratio DOUBLE PRECISION NOT NULL CHECK (volume_ratio >= 0),
+    changed_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_market_regimes_pair_changed ON market_regimes (trading_pair, changed_at DESC);
@@ -0,0 +1,9 @@
+-- Down migration for 27_cost_models.up.sql
+-- Reversible: yes
+
+DROP INDEX IF EXISTS idx_cost_models_exchange_calibrated;
+DROP TABLE IF EXISTS cost_models;
+
+ALTER TABLE trade_executions
+    DROP COLUMN IF EXISTS volatility_bps,
+    DROP COLUMN IF EXISTS book_depth;
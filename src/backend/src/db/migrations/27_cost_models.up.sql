@@ -0,0 +1,26 @@
+-- Cost model migration for AI-powered Solana trading bot
+-- Version: 27.0
+-- Dependencies: 26_market_regimes.up.sql
+-- Purpose: Records the visible book depth and pair volatility each execution was planned
+--          against, and stores the per-venue slippage models calibrated from them; earlier
+--          models are kept for comparison after their validity window ends
+
+ALTER TABLE trade_executions
+    ADD COLUMN IF NOT EXISTS book_depth NUMERIC(24,8) CHECK (book_depth >= 0),
+    ADD COLUMN IF NOT EXISTS volatility_bps NUMERIC(12,4) CHECK (volatility_bps >= 0);
+
+CREATE TABLE IF NOT EXISTS cost_models (
+    id UUID PRIMARY KEY,
+    exchange VARCHAR(32) NOT NULL,
+    intercept_bps DOUBLE PRECISION NOT NULL,
+    depth_coefficient DOUBLE PRECISION NOT NULL,
+    volatility_coefficient DOUBLE PRECISION NOT NULL,
+    sample_count BIGINT NOT NULL CHECK (sample_count > 0),
+    r_squared DOUBLE PRECISION NOT NULL CHECK (r_squared BETWEEN 0 AND 1),
+    valid_from TIMESTAMPTZ NOT NULL,
+    valid_until TIMESTAMPTZ NOT NULL,
+    calibrated_at TIMESTAMPTZ NOT NULL,
+    CHECK (valid_until > valid_from)
+);
+
+CREATE INDEX IF NOT EXISTS idx_cost_models_exchange_calibrated ON cost_models (exchange, calibrated_at DESC);
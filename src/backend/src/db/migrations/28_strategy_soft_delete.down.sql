@@ -0,0 +1,7 @@
+-- Down migration for 28_strategy_soft_delete.up.sql
+-- Reversible: yes
+
+DROP INDEX IF EXISTS idx_strategies_live;
+
+ALTER TABLE strategies
+    DROP COLUMN IF EXISTS deleted_at;
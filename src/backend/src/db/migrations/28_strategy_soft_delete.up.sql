@@ -0,0 +1,11 @@
+-- Strategy soft delete migration for AI-powered Solana trading bot
+-- Version: 28.0
+-- Dependencies: 27_cost_models.up.sql
+-- Purpose: Marks strategies as deleted instead of removing them, so their trades, equity
+--          and attribution history stay queryable; the unique name index still covers
+--          deleted rows so a deleted strategy's name cannot be reused
+
+ALTER TABLE strategies
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_strategies_live ON strategies (name) WHERE deleted_at IS NULL;
@@ -0,0 +1,6 @@
+-- Down migration for 29_compute_units.up.sql
+-- Reversible: yes
+
+ALTER TABLE trade_executions
+    DROP COLUMN IF EXISTS compute_units_consumed,
+    DROP COLUMN IF EXISTS compute_unit_limit;
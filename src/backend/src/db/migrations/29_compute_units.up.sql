@@ -0,0 +1,9 @@
+-- Compute unit migration for AI-powered Solana trading bot
+-- Version: 29.0
+-- Dependencies: 28_strategy_soft_delete.up.sql
+-- Purpose: Records the compute unit limit each budgeted transaction carried and the units it
+--          consumed on-chain, so per-venue safety margins can be reviewed against history
+
+ALTER TABLE trade_executions
+    ADD COLUMN IF NOT EXISTS compute_unit_limit INTEGER CHECK (compute_unit_limit > 0),
+    ADD COLUMN IF NOT EXISTS compute_units_consumed BIGINT CHECK (compute_units_consumed >= 0);
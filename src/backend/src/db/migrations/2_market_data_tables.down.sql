@@ -0,0 +1,2 @@
+-- Down migration for 2_market_data_tables.up.sql
+-- Reversible: no, drops and recreates market_data, so rows written under the V1 layout cannot be restored
@@ -1,6 +1,6 @@
 -- Market data hypertables migration for AI-powered Solana trading bot
 -- Version: 2.0
--- Dependencies: TimescaleDB 2.11, V1__initial_schema.sql
+-- Dependencies: TimescaleDB 2.11, 1_initial_schema.up.sql
 -- Purpose: Creates and configures optimized time-series storage for market data and order books
 
 -- Drop existing market data tables if they exist to ensure clean migration
@@ -9,13 +9,14 @@ DROP TABLE IF EXISTS order_book_snapshots CASCADE;
 
 -- Create market data table with optimized column types
 CREATE TABLE market_data (
-    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    id UUID NOT NULL DEFAULT uuid_generate_v4(),
     trading_pair VARCHAR(20) NOT NULL,
     exchange VARCHAR(20) NOT NULL CHECK (exchange IN ('jupiter', 'pump_fun', 'drift')),
     price NUMERIC(18,8) NOT NULL CHECK (price > 0),
     volume NUMERIC(18,6) NOT NULL CHECK (volume > 0),
     timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (id, timestamp)
 );
 
 -- Convert market_data to hypertable with 1-day chunks
@@ -29,13 +30,14 @@ SELECT create_hypertable(
 
 -- Create order book snapshots table with JSONB for bid/ask storage
 CREATE TABLE order_book_snapshots (
-    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    id UUID NOT NULL DEFAULT uuid_generate_v4(),
     trading_pair VARCHAR(20) NOT NULL,
     exchange VARCHAR(20) NOT NULL CHECK (exchange IN ('jupiter', 'pump_fun', 'drift')),
     bids JSONB NOT NULL CHECK (jsonb_array_length(bids) > 0),
     asks JSONB NOT NULL CHECK (jsonb_array_length(asks) > 0),
     timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (id, timestamp)
 );
 
 -- Convert order_book_snapshots to hypertable with 1-hour chunks
@@ -90,6 +92,8 @@ SELECT add_retention_policy('market_data', INTERVAL '90 days');
 SELECT add_retention_policy('order_book_snapshots', INTERVAL '30 days');
 
 -- Set up compression policies
+ALTER TABLE market_data SET (timescaledb.compress, timescaledb.compress_segmentby = 'trading_pair, exchange');
+ALTER TABLE order_book_snapshots SET (timescaledb.compress, timescaledb.compress_segmentby = 'trading_pair, exchange');
 SELECT add_compression_policy('market_data', INTERVAL '7 days');
 SELECT add_compression_policy('order_book_snapshots', INTERVAL '24 hours');
 
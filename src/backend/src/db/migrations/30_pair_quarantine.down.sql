@@ -0,0 +1,6 @@
+-- Down migration for 30_pair_quarantine.up.sql
+-- Reversible: yes
+
+DROP INDEX IF EXISTS idx_pair_quarantine_releases_pair;
+DROP TABLE IF EXISTS pair_quarantine_releases;
+DROP TABLE IF EXISTS pair_quarantines;
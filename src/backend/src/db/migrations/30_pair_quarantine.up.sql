@@ -0,0 +1,29 @@
+-- Pair quarantine migration for AI-powered Solana trading bot
+-- Version: 30.0
+-- Dependencies: 29_compute_units.up.sql
+-- Purpose: Persists per-pair kill switches so a quarantine survives a restart, and keeps
+--          every lifted quarantine with the admin who lifted it and why
+
+CREATE TABLE IF NOT EXISTS pair_quarantines (
+    trading_pair VARCHAR(64) PRIMARY KEY,
+    mode VARCHAR(32) NOT NULL CHECK (mode IN ('no-new-entries', 'flatten-and-block')),
+    reason TEXT,
+    quarantined_by VARCHAR(128) NOT NULL,
+    quarantined_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS pair_quarantine_releases (
+    id UUID PRIMARY KEY,
+    trading_pair VARCHAR(64) NOT NULL,
+    mode VARCHAR(32) NOT NULL CHECK (mode IN ('no-new-entries', 'flatten-and-block')),
+    quarantine_reason TEXT,
+    quarantined_by VARCHAR(128) NOT NULL,
+    quarantined_at TIMESTAMPTZ NOT NULL,
+    released_by VARCHAR(128) NOT NULL,
+    release_reason TEXT NOT NULL CHECK (length(trim(release_reason)) > 0),
+    released_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Release history per pair, newest first
+CREATE INDEX IF NOT EXISTS idx_pair_quarantine_releases_pair
+    ON pair_quarantine_releases (trading_pair, released_at DESC);
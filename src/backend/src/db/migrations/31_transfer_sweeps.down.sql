@@ -0,0 +1,5 @@
+-- Down migration for 31_transfer_sweeps.up.sql
+-- Reversible: yes
+
+DROP INDEX IF EXISTS idx_transfers_swept;
+ALTER TABLE transfers DROP COLUMN IF EXISTS destination;
@@ -0,0 +1,11 @@
+-- Transfer sweeps migration for AI-powered Solana trading bot
+-- Version: 31.0
+-- Dependencies: 30_pair_quarantine.up.sql
+-- Purpose: Records the cold wallet each profit sweep was sent to, so swept withdrawals can
+--          be told apart from other withdrawals and summed against the daily sweep cap
+
+ALTER TABLE transfers ADD COLUMN IF NOT EXISTS destination VARCHAR(44);
+
+CREATE INDEX IF NOT EXISTS idx_transfers_swept
+    ON transfers (wallet_address, detected_at)
+    WHERE destination IS NOT NULL;
@@ -0,0 +1,4 @@
+-- Down migration for 32_stress_runs.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS stress_runs;
@@ -0,0 +1,18 @@
+-- Stress runs migration for AI-powered Solana trading bot
+-- Version: 32.0
+-- Dependencies: 31_transfer_sweeps.up.sql
+-- Purpose: Audit trail of portfolio stress tests. Who ran it and the worst loss are columns
+--          for review; every scenario and its stressed result is kept as JSONB.
+
+CREATE TABLE IF NOT EXISTS stress_runs (
+    id UUID PRIMARY KEY,
+    requested_by VARCHAR(128) NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL,
+    scenarios TEXT[] NOT NULL,
+    base_value NUMERIC(24,8) NOT NULL,
+    max_loss NUMERIC(24,8) NOT NULL,
+    payload JSONB NOT NULL
+);
+
+-- Recent runs first for the audit history
+CREATE INDEX IF NOT EXISTS idx_stress_runs_time ON stress_runs (computed_at DESC);
@@ -0,0 +1,4 @@
+-- Down migration for 33_scoped_tokens.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS scoped_tokens;
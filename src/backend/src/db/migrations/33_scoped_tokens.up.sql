@@ -0,0 +1,16 @@
+-- Scoped tokens migration for AI-powered Solana trading bot
+-- Version: 33.0
+-- Dependencies: 32_stress_runs.up.sql
+-- Purpose: Read-only API tokens for sharing a strategy dashboard. Only the SHA-256 of the
+--          secret is stored; revocation is kept rather than deleting the row.
+
+CREATE TABLE IF NOT EXISTS scoped_tokens (
+    id UUID PRIMARY KEY,
+    secret_hash CHAR(64) NOT NULL UNIQUE,
+    label VARCHAR(64) NOT NULL,
+    scopes JSONB NOT NULL,
+    created_by VARCHAR(128) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
@@ -0,0 +1,4 @@
+-- Down migration for 34_trade_groups.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS trade_groups;
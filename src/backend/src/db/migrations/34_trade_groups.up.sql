@@ -0,0 +1,20 @@
+-- Trade groups migration for AI-powered Solana trading bot
+-- Version: 34.0
+-- Dependencies: 33_scoped_tokens.up.sql
+-- Purpose: Audit trail of multi-leg trade groups. Status and whether hedges were placed are
+--          columns so partial outcomes can be found; legs and hedges are kept as JSONB.
+
+CREATE TABLE IF NOT EXISTS trade_groups (
+    correlation_id UUID PRIMARY KEY,
+    strategy_id VARCHAR(64) NOT NULL,
+    policy VARCHAR(32) NOT NULL,
+    status VARCHAR(16) NOT NULL,
+    bundled BOOLEAN NOT NULL,
+    hedged BOOLEAN NOT NULL,
+    completed_at TIMESTAMPTZ NOT NULL,
+    payload JSONB NOT NULL
+);
+
+-- Partial outcomes awaiting review
+CREATE INDEX IF NOT EXISTS idx_trade_groups_partial ON trade_groups (completed_at DESC)
+    WHERE status IN ('hedged', 'unhedged');
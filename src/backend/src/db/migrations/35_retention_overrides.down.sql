@@ -0,0 +1,4 @@
+-- Down migration for 35_retention_overrides.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS retention_overrides;
@@ -0,0 +1,14 @@
+-- Retention overrides migration for AI-powered Solana trading bot
+-- Version: 35.0
+-- Dependencies: 34_trade_groups.up.sql
+-- Purpose: Per-pair market data retention set by admins. An empty exchange applies the
+--          override to the pair on every exchange; the global default covers the rest.
+
+CREATE TABLE IF NOT EXISTS retention_overrides (
+    trading_pair VARCHAR(64) NOT NULL,
+    exchange VARCHAR(32) NOT NULL DEFAULT '',
+    days INTEGER NOT NULL CHECK (days > 0),
+    updated_by VARCHAR(128) NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (trading_pair, exchange)
+);
@@ -0,0 +1,4 @@
+-- Down migration for 36_microstructure_aggregates.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS microstructure_aggregates;
@@ -0,0 +1,23 @@
+-- Microstructure aggregates migration for AI-powered Solana trading bot
+-- Version: 36.0
+-- Dependencies: 35_retention_overrides.up.sql
+-- Purpose: Per-minute means of order book imbalance, depth within 10/25/50 bps of the mid,
+--          quoted and effective spread and quote update rate, kept for research. Means are
+--          over the updates where a feature was defined; flagged counts degenerate books.
+
+CREATE TABLE IF NOT EXISTS microstructure_aggregates (
+    trading_pair VARCHAR(64) NOT NULL,
+    bucket_start TIMESTAMPTZ NOT NULL,
+    exchange VARCHAR(32) NOT NULL,
+    updates INTEGER NOT NULL CHECK (updates > 0),
+    flagged INTEGER NOT NULL CHECK (flagged >= 0 AND flagged <= updates),
+    mean_imbalance NUMERIC(12,8) CHECK (mean_imbalance BETWEEN -1 AND 1),
+    mean_depth JSONB,
+    mean_spread_bps NUMERIC(18,8),
+    mean_effective_spread_bps NUMERIC(18,8),
+    mean_quote_rate DOUBLE PRECISION NOT NULL CHECK (mean_quote_rate >= 0),
+    PRIMARY KEY (trading_pair, bucket_start)
+);
+
+-- Research queries scan a time range across pairs
+CREATE INDEX IF NOT EXISTS idx_microstructure_aggregates_time ON microstructure_aggregates (bucket_start);
@@ -0,0 +1,13 @@
+-- Down migration for 37_vendor_market_data.up.sql
+-- Reversible: yes
+
+DROP INDEX IF EXISTS idx_market_data_vendor_rows;
+
+-- Vendor rows cannot satisfy the narrower constraint
+DELETE FROM market_data WHERE source LIKE 'vendor:%';
+
+ALTER TABLE market_data DROP CONSTRAINT IF EXISTS market_data_source_check;
+ALTER TABLE market_data ADD CONSTRAINT market_data_source_check
+    CHECK (source IN ('live', 'backfill'));
+
+ALTER TABLE market_data ALTER COLUMN source TYPE VARCHAR(16);
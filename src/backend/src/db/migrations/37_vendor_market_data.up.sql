@@ -0,0 +1,16 @@
+-- Vendor market data migration for AI-powered Solana trading bot
+-- Version: 37.0
+-- Dependencies: 36_microstructure_aggregates.up.sql
+-- Purpose: Allows market data bulk-ingested from external vendors, tagged 'vendor:<name>',
+--          and makes vendor rows unique per pair, exchange and timestamp so re-ingesting a
+--          file skips rows already stored
+
+ALTER TABLE market_data ALTER COLUMN source TYPE VARCHAR(64);
+
+ALTER TABLE market_data DROP CONSTRAINT IF EXISTS market_data_source_check;
+ALTER TABLE market_data ADD CONSTRAINT market_data_source_check
+    CHECK (source IN ('live', 'backfill') OR source ~ '^vendor:[a-z0-9_-]{1,48}$');
+
+-- Idempotency target for ingestion; includes the hypertable time column
+CREATE UNIQUE INDEX IF NOT EXISTS idx_market_data_vendor_rows
+    ON market_data (trading_pair, exchange, timestamp, source) WHERE source LIKE 'vendor:%';
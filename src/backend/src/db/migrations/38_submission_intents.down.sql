@@ -0,0 +1,4 @@
+-- Down migration for 38_submission_intents.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS submission_intents;
@@ -0,0 +1,32 @@
+-- Submission intents migration for AI-powered Solana trading bot
+-- Version: 38.0
+-- Dependencies: 37_vendor_market_data.up.sql
+-- Purpose: Orders persisted before submission with the signature of every signed attempt,
+--          so fills that land after a crash are found in wallet history on restart and
+--          applied to positions. Pending intents are resolved when the result is recorded,
+--          recovered when found on-chain, or failed once past the expiry cutoff.
+
+CREATE TABLE IF NOT EXISTS submission_intents (
+    id UUID PRIMARY KEY,
+    strategy_id VARCHAR(64) NOT NULL,
+    trading_pair VARCHAR(64) NOT NULL,
+    exchange VARCHAR(32) NOT NULL,
+    side VARCHAR(4) NOT NULL CHECK (side IN ('buy', 'sell')),
+    size NUMERIC(36,18) NOT NULL CHECK (size > 0),
+    price NUMERIC(36,18) NOT NULL CHECK (price > 0),
+    signatures TEXT[] NOT NULL DEFAULT '{}',
+    bundle_id VARCHAR(128),
+    status VARCHAR(16) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'resolved', 'recovered', 'failed')),
+    fill_signature VARCHAR(128),
+    fill_size NUMERIC(36,18),
+    fill_price NUMERIC(36,18),
+    detail TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    closed_at TIMESTAMPTZ,
+    CHECK ((status = 'pending') = (closed_at IS NULL))
+);
+
+-- Startup recovery reads only the intents still pending
+CREATE INDEX IF NOT EXISTS idx_submission_intents_pending
+    ON submission_intents (created_at) WHERE status = 'pending';
@@ -0,0 +1,9 @@
+-- Down migration for 39_economic_events.up.sql
+-- Reversible: yes
+
+DROP INDEX IF EXISTS idx_trade_executions_event;
+
+ALTER TABLE trade_executions
+    DROP COLUMN IF EXISTS event_id;
+
+DROP TABLE IF EXISTS economic_events;
@@ -0,0 +1,27 @@
+-- Economic events migration for AI-powered Solana trading bot
+-- Version: 39.0
+-- Dependencies: 38_submission_intents.up.sql
+-- Purpose: Calendar of scheduled economic events, added by admins or imported from CSV.
+--          Risk limits tighten for a window around high-impact events, and executions
+--          inside a window record the event that opened it.
+
+CREATE TABLE IF NOT EXISTS economic_events (
+    id UUID PRIMARY KEY,
+    timestamp TIMESTAMPTZ NOT NULL,
+    name VARCHAR(200) NOT NULL CHECK (length(trim(name)) > 0),
+    impact VARCHAR(8) NOT NULL CHECK (impact IN ('low', 'medium', 'high')),
+    created_by VARCHAR(128) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (timestamp, name)
+);
+
+-- Schedule lookups by time range
+CREATE INDEX IF NOT EXISTS idx_economic_events_timestamp
+    ON economic_events (timestamp);
+
+ALTER TABLE trade_executions
+    ADD COLUMN IF NOT EXISTS event_id UUID REFERENCES economic_events (id) ON DELETE SET NULL;
+
+-- Executions inside each event's window
+CREATE INDEX IF NOT EXISTS idx_trade_executions_event
+    ON trade_executions (event_id) WHERE event_id IS NOT NULL;
@@ -0,0 +1,2 @@
+-- Down migration for 3_strategy_tables.up.sql
+-- Reversible: no, replaces the V1 strategies table with an incompatible enum-typed layout, discarding its rows
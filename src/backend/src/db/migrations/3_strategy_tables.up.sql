@@ -1,11 +1,15 @@
 -- Strategy tables migration for AI-powered Solana trading bot
 -- Version: 3.0
--- Dependencies: V1__initial_schema.sql, TimescaleDB extension
+-- Dependencies: 1_initial_schema.up.sql, TimescaleDB extension
 
 -- Create enum types for strategy configuration
 CREATE TYPE strategy_type AS ENUM ('grid', 'arbitrage', 'ml_based', 'hybrid');
 CREATE TYPE strategy_state AS ENUM ('active', 'inactive', 'backtest', 'error', 'optimizing');
 
+-- Replace the placeholder strategies table from V1; CASCADE also drops the trades foreign key,
+-- which is restored below
+DROP TABLE IF EXISTS strategies CASCADE;
+
 -- Create strategies table with comprehensive configuration
 CREATE TABLE strategies (
     id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
@@ -41,7 +45,7 @@ CREATE TABLE strategies (
 
 -- Create hypertable for strategy performance tracking
 CREATE TABLE strategy_performance (
-    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    id UUID NOT NULL DEFAULT uuid_generate_v4(),
     strategy_id UUID NOT NULL REFERENCES strategies(id) ON DELETE CASCADE,
     pnl DECIMAL(20,8) NOT NULL,
     trades_count INTEGER NOT NULL DEFAULT 0 CHECK (trades_count >= 0),
@@ -52,7 +56,8 @@ CREATE TABLE strategy_performance (
     sharpe_ratio DECIMAL(10,4),
     max_drawdown DECIMAL(5,2) CHECK (max_drawdown >= 0),
     timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (id, timestamp)
 );
 
 -- Convert to hypertable with 1-day chunks
@@ -76,6 +81,10 @@ CREATE TABLE strategy_portfolio_mappings (
     UNIQUE (strategy_id, portfolio_id)
 );
 
+-- Restore the trades foreign key dropped with the V1 table
+ALTER TABLE trades ADD CONSTRAINT trades_strategy_id_fkey
+    FOREIGN KEY (strategy_id) REFERENCES strategies(id);
+
 -- Create indices for high-performance queries
 CREATE INDEX idx_strategies_type_state ON strategies (strategy_type, state);
 CREATE INDEX idx_strategies_performance ON strategies (performance_score DESC) WHERE state = 'active';
@@ -86,6 +95,7 @@ CREATE INDEX idx_strategy_mappings_portfolio ON strategy_portfolio_mappings (por
 CREATE INDEX idx_strategy_performance_time_brin ON strategy_performance USING BRIN (timestamp) WITH (pages_per_range = 32);
 
 -- Set up compression policy for performance data
+ALTER TABLE strategy_performance SET (timescaledb.compress, timescaledb.compress_segmentby = 'strategy_id');
 SELECT add_compression_policy('strategy_performance', INTERVAL '7 days');
 
 -- Add retention policy for performance data (90 days)
@@ -0,0 +1,7 @@
+-- Down migration for 40_perp_reconciliations.up.sql
+-- Reversible: yes
+
+DROP INDEX IF EXISTS idx_perp_reconciliations_halted;
+DROP INDEX IF EXISTS idx_perp_reconciliations_market_time;
+
+DROP TABLE IF EXISTS perp_reconciliations;
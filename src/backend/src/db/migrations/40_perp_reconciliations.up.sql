@@ -0,0 +1,30 @@
+-- Perp reconciliations migration for AI-powered Solana trading bot
+-- Version: 40.0
+-- Dependencies: 39_economic_events.up.sql
+-- Purpose: Audit trail of perp position reconciliations against the Drift user account.
+--          Each run stores one row per market with both sides' size, quote entry and
+--          unrealized P&L and the action taken.
+
+CREATE TABLE IF NOT EXISTS perp_reconciliations (
+    run_id UUID NOT NULL,
+    market VARCHAR(32) NOT NULL,
+    reconciled_at TIMESTAMPTZ NOT NULL,
+    internal_size NUMERIC(28,12) NOT NULL,
+    venue_size NUMERIC(28,12) NOT NULL,
+    size_diff NUMERIC(28,12) NOT NULL,
+    internal_quote_entry NUMERIC(28,12) NOT NULL,
+    venue_quote_entry NUMERIC(28,12) NOT NULL,
+    mark_price NUMERIC(28,12),
+    internal_unrealized_pnl NUMERIC(28,12),
+    venue_unrealized_pnl NUMERIC(28,12),
+    action VARCHAR(16) NOT NULL CHECK (action IN ('matched', 'corrected', 'halted')),
+    PRIMARY KEY (run_id, market)
+);
+
+-- Audit queries by market over time
+CREATE INDEX IF NOT EXISTS idx_perp_reconciliations_market_time
+    ON perp_reconciliations (market, reconciled_at DESC);
+
+-- Mismatches only
+CREATE INDEX IF NOT EXISTS idx_perp_reconciliations_halted
+    ON perp_reconciliations (reconciled_at DESC) WHERE action = 'halted';
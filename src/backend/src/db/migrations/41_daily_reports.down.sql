@@ -0,0 +1,6 @@
+-- Down migration for 41_daily_reports.up.sql
+-- Reversible: yes
+
+DROP INDEX IF EXISTS idx_reports_undelivered;
+
+DROP TABLE IF EXISTS reports;
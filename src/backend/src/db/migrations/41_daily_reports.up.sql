@@ -0,0 +1,22 @@
+-- Daily reports migration for AI-powered Solana trading bot
+-- Version: 41.0
+-- Dependencies: 40_perp_reconciliations.up.sql
+-- Purpose: Stores each generated end-of-day performance report as served by the API, with
+--          the time its summary was pushed so a retried job never delivers it twice
+
+CREATE TABLE IF NOT EXISTS reports (
+    kind VARCHAR(16) NOT NULL CHECK (kind IN ('daily')),
+    report_date DATE NOT NULL,
+    period_start TIMESTAMPTZ NOT NULL,
+    period_end TIMESTAMPTZ NOT NULL,
+    net_pnl NUMERIC(28,12) NOT NULL,
+    payload JSONB NOT NULL,
+    generated_at TIMESTAMPTZ NOT NULL,
+    delivered_at TIMESTAMPTZ,
+    PRIMARY KEY (kind, report_date),
+    CHECK (period_end > period_start)
+);
+
+-- Reports not yet pushed through the notification channels
+CREATE INDEX IF NOT EXISTS idx_reports_undelivered
+    ON reports (report_date) WHERE delivered_at IS NULL;
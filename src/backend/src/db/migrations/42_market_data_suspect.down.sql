@@ -0,0 +1,6 @@
+-- Down migration for 42_market_data_suspect.up.sql
+-- Reversible: yes
+
+DROP INDEX IF EXISTS idx_market_data_suspect;
+
+ALTER TABLE market_data DROP COLUMN IF EXISTS suspect_rule;
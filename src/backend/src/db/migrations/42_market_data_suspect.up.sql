@@ -0,0 +1,13 @@
+-- Suspect market data migration for AI-powered Solana trading bot
+-- Version: 42.0
+-- Dependencies: 41_daily_reports.up.sql
+-- Purpose: Keeps samples quarantined by the market data sanity checks alongside accepted
+--          data, tagged with the rule they failed, so they can be reviewed while every
+--          read that feeds aggregation skips them
+
+ALTER TABLE market_data ADD COLUMN IF NOT EXISTS suspect_rule VARCHAR(32)
+    CHECK (suspect_rule IN ('volume_spike', 'decimal_scale'));
+
+-- Review queue; only the few suspect rows are indexed
+CREATE INDEX IF NOT EXISTS idx_market_data_suspect
+    ON market_data (trading_pair, exchange, timestamp) WHERE suspect_rule IS NOT NULL;
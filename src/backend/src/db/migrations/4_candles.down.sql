@@ -0,0 +1,4 @@
+-- Down migration for 4_candles.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS candles;
@@ -0,0 +1,33 @@
+-- Candlestick (OHLCV) storage migration for AI-powered Solana trading bot
+-- Version: 4.0
+-- Dependencies: TimescaleDB 2.11, 2_market_data_tables.up.sql
+-- Purpose: Persists completed and corrected candles produced by the OHLCV aggregator
+
+CREATE TABLE IF NOT EXISTS candles (
+    trading_pair VARCHAR(20) NOT NULL,
+    interval VARCHAR(4) NOT NULL CHECK (interval IN ('1s', '1m', '5m', '1h')),
+    open_time TIMESTAMPTZ NOT NULL,
+    open NUMERIC(18,8) NOT NULL CHECK (open > 0),
+    high NUMERIC(18,8) NOT NULL CHECK (high > 0),
+    low NUMERIC(18,8) NOT NULL CHECK (low > 0),
+    close NUMERIC(18,8) NOT NULL CHECK (close > 0),
+    volume NUMERIC(24,6) NOT NULL DEFAULT 0 CHECK (volume >= 0),
+    trade_count BIGINT NOT NULL DEFAULT 0 CHECK (trade_count >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (trading_pair, interval, open_time),
+    CHECK (low <= high)
+);
+
+-- Convert candles to hypertable with 1-day chunks
+SELECT create_hypertable(
+    'candles',
+    'open_time',
+    chunk_time_interval => INTERVAL '1 day',
+    if_not_exists => TRUE,
+    migrate_data => TRUE
+);
+
+-- Range queries by pair and interval
+CREATE INDEX IF NOT EXISTS idx_candles_pair_interval_time
+    ON candles (trading_pair, interval, open_time DESC);
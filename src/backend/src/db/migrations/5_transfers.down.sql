@@ -0,0 +1,4 @@
+-- Down migration for 5_transfers.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS transfers;
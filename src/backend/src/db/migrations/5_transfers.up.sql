@@ -0,0 +1,18 @@
+-- Wallet transfer tracking migration for AI-powered Solana trading bot
+-- Version: 5.0
+-- Dependencies: 1_initial_schema.up.sql
+-- Purpose: Records deposits and withdrawals so performance can be flow-adjusted
+
+CREATE TABLE IF NOT EXISTS transfers (
+    id UUID PRIMARY KEY,
+    wallet_address VARCHAR(44) NOT NULL,
+    direction VARCHAR(10) NOT NULL CHECK (direction IN ('DEPOSIT', 'WITHDRAWAL')),
+    amount NUMERIC(24,6) NOT NULL CHECK (amount > 0),
+    signature VARCHAR(88) UNIQUE,
+    detected_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Transfer history by wallet
+CREATE INDEX IF NOT EXISTS idx_transfers_wallet_time
+    ON transfers (wallet_address, detected_at DESC);
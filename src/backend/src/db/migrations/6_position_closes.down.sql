@@ -0,0 +1,4 @@
+-- Down migration for 6_position_closes.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS position_closes;
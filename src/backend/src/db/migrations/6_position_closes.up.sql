@@ -0,0 +1,20 @@
+-- Position close tracking migration for AI-powered Solana trading bot
+-- Version: 6.0
+-- Dependencies: 1_initial_schema.up.sql
+-- Purpose: Records partial and full position closes with realized P&L
+
+CREATE TABLE IF NOT EXISTS position_closes (
+    id UUID PRIMARY KEY,
+    trading_pair VARCHAR(20) NOT NULL,
+    closed_size NUMERIC(24,8) NOT NULL CHECK (closed_size > 0),
+    entry_price NUMERIC(24,8) NOT NULL CHECK (entry_price > 0),
+    exit_price NUMERIC(24,8) NOT NULL CHECK (exit_price > 0),
+    realized_pnl NUMERIC(24,8) NOT NULL,
+    remaining_size NUMERIC(24,8) NOT NULL,
+    closed_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Close history by pair
+CREATE INDEX IF NOT EXISTS idx_position_closes_pair_time
+    ON position_closes (trading_pair, closed_at DESC);
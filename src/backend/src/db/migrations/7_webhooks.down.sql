@@ -0,0 +1,5 @@
+-- Down migration for 7_webhooks.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS webhook_audit_log;
+DROP TABLE IF EXISTS webhooks;
@@ -0,0 +1,27 @@
+-- Outbound webhook migration for AI-powered Solana trading bot
+-- Version: 7.0
+-- Dependencies: 1_initial_schema.up.sql
+-- Purpose: Stores webhook endpoints and an audit trail of delivery-driven status changes
+
+CREATE TABLE IF NOT EXISTS webhooks (
+    id UUID PRIMARY KEY,
+    url TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    event_types TEXT[] NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    consecutive_failures INTEGER NOT NULL DEFAULT 0 CHECK (consecutive_failures >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS webhook_audit_log (
+    id UUID PRIMARY KEY,
+    webhook_id UUID NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+    action VARCHAR(32) NOT NULL,
+    detail TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Audit history by webhook
+CREATE INDEX IF NOT EXISTS idx_webhook_audit_webhook_time
+    ON webhook_audit_log (webhook_id, created_at DESC);
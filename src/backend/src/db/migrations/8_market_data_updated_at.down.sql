@@ -0,0 +1,4 @@
+-- Down migration for 8_market_data_updated_at.up.sql
+-- Reversible: yes
+
+ALTER TABLE market_data DROP COLUMN IF EXISTS updated_at;
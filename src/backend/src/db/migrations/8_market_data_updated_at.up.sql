@@ -0,0 +1,6 @@
+-- Market data alignment migration for AI-powered Solana trading bot
+-- Version: 8.0
+-- Dependencies: 1_initial_schema.up.sql
+-- Purpose: Adds the updated_at column read by MarketDataRecord so checked queries resolve
+
+ALTER TABLE market_data ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ;
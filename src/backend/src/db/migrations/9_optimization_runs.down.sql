@@ -0,0 +1,4 @@
+-- Down migration for 9_optimization_runs.up.sql
+-- Reversible: yes
+
+DROP TABLE IF EXISTS optimization_runs;
@@ -0,0 +1,28 @@
+-- Strategy optimization migration for AI-powered Solana trading bot
+-- Version: 9.0
+-- Dependencies: 1_initial_schema.up.sql
+-- Purpose: Stores every backtest run of a parameter optimization job with its ranking
+
+CREATE TABLE IF NOT EXISTS optimization_runs (
+    id UUID PRIMARY KEY,
+    job_id UUID NOT NULL,
+    combination INTEGER NOT NULL CHECK (combination >= 0),
+    strategy_type VARCHAR(20) NOT NULL,
+    trading_pair VARCHAR(20) NOT NULL,
+    objective VARCHAR(10) NOT NULL,
+    seed BIGINT NOT NULL,
+    parameters JSONB NOT NULL,
+    total_trades INTEGER,
+    realized_pnl NUMERIC(24,8),
+    roi_pct NUMERIC(18,8),
+    sharpe_ratio DOUBLE PRECISION,
+    max_drawdown_pct NUMERIC(18,8),
+    feasible BOOLEAN NOT NULL,
+    rank INTEGER,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (job_id, combination)
+);
+
+CREATE INDEX IF NOT EXISTS idx_optimization_runs_job_rank
+    ON optimization_runs (job_id, rank);
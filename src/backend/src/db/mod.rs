@@ -2,13 +2,12 @@
 //! for the AI-powered Solana trading bot with support for PostgreSQL and TimescaleDB.
 //! Version: 1.0.0
 
-use deadpool_postgres::{Config as PoolConfig, Pool, Runtime}; // v0.10.0
 use opentelemetry::trace::{Span, Tracer}; // v0.19.0
-use sqlx::{postgres::{PgConnectOptions, PgPoolOptions}, PgPool}; // v0.7.1
+use sqlx::{migrate::Migrator, postgres::{PgConnectOptions, PgPoolOptions}, PgPool}; // v0.7.1
 use tokio::time::{Duration, sleep}; // v1.28.0
 use tracing::{error, info, instrument, warn}; // v0.1.37
 
-use crate::config::database::{DatabaseConfig, SSLMode};
+use crate::config::database::DatabaseConfig;
 
 // Re-export submodules
 pub mod models;
@@ -93,68 +92,43 @@ impl DatabaseError {
 }
 
 /// Creates and initializes a PostgreSQL connection pool with monitoring
-#[instrument(level = "info")]
-pub async fn create_pool(config: DatabaseConfig) -> Result<Pool, DatabaseError> {
+#[instrument(level = "info", skip(config))]
+pub async fn create_pool(config: DatabaseConfig) -> Result<PgPool, DatabaseError> {
     info!("Initializing database connection pool");
 
     // Validate configuration
-    config.validate_config()
+    crate::config::ensure_valid(config.validate_config())
         .map_err(|e| DatabaseError::new_connection_error(
             format!("Invalid database configuration: {}", e),
             None,
             false
         ))?;
 
-    let mut pool_config = PoolConfig::new();
-    
-    // Configure primary connection
-    pool_config.host = Some(config.host.clone());
-    pool_config.port = Some(config.port);
-    pool_config.user = Some(config.username.clone());
-    pool_config.password = Some(config.password.clone());
-    pool_config.dbname = Some(config.database.clone());
-
-    // Configure SSL mode
-    match config.ssl_mode {
-        SSLMode::Require => pool_config.ssl_mode = Some("require".to_string()),
-        SSLMode::VerifyCA => pool_config.ssl_mode = Some("verify-ca".to_string()),
-        SSLMode::VerifyFull => pool_config.ssl_mode = Some("verify-full".to_string()),
-        SSLMode::Disable => pool_config.ssl_mode = Some("disable".to_string()),
-    }
+    let connect_options: PgConnectOptions = config
+        .connect_options()
+        .statement_cache_capacity(DB_STATEMENT_CACHE_SIZE as usize);
 
-    // Configure pool settings
-    pool_config.pool = Some(deadpool_postgres::PoolConfig {
-        max_size: DB_POOL_MAX_CONNECTIONS,
-        timeouts: deadpool_postgres::Timeouts {
-            wait: Some(Duration::from_secs(DB_CONNECTION_TIMEOUT_SECONDS)),
-            create: Some(Duration::from_secs(DB_CONNECTION_TIMEOUT_SECONDS)),
-            recycle: Some(Duration::from_secs(600)), // 10 minutes
-        },
-    });
+    let pool_options = PgPoolOptions::new()
+        .max_connections(DB_POOL_MAX_CONNECTIONS)
+        .acquire_timeout(Duration::from_secs(DB_CONNECTION_TIMEOUT_SECONDS))
+        .idle_timeout(Duration::from_secs(600)) // 10 minutes
+        .test_before_acquire(true);
 
     // Create pool with retry mechanism
     let mut last_error = None;
     for attempt in 1..=DB_RETRY_ATTEMPTS {
-        match pool_config.create_pool(Some(Runtime::Tokio1), tokio_postgres::NoTls) {
+        match pool_options.clone().connect_with(connect_options.clone()).await {
             Ok(pool) => {
-                // Verify connectivity
-                if let Err(e) = pool.get().await {
-                    last_error = Some(e);
-                    warn!("Failed to verify pool connectivity on attempt {}", attempt);
-                    sleep(Duration::from_secs(1)).await;
-                    continue;
-                }
-
                 info!("Database connection pool initialized successfully");
-                
+
                 // Initialize health check task
                 tokio::spawn(periodic_health_check(pool.clone()));
-                
+
                 return Ok(pool);
             }
             Err(e) => {
+                warn!(error = %e, "Failed to create connection pool on attempt {}", attempt);
                 last_error = Some(e);
-                warn!("Failed to create connection pool on attempt {}", attempt);
                 sleep(Duration::from_secs(1)).await;
             }
         }
@@ -162,57 +136,88 @@ pub async fn create_pool(config: DatabaseConfig) -> Result<Pool, DatabaseError>
 
     Err(DatabaseError::new_connection_error(
         "Failed to create database connection pool after multiple attempts".to_string(),
-        None,
+        last_error,
         true
     ))
 }
 
-/// Initializes database schema and runs migrations
-#[instrument(skip(pool), level = "info")]
-pub async fn initialize_database(pool: Pool) -> Result<(), DatabaseError> {
-    info!("Initializing database schema and running migrations");
+/// Embedded schema migrations; the only source of DDL for the bot's tables.
+/// Each `{version}_{name}.up.sql` has a matching `.down.sql` that reverts it or marks it irreversible.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./src/db/migrations");
 
-    let client = pool.get().await.map_err(|e| 
-        DatabaseError::new_connection_error(
-            "Failed to get connection for initialization".to_string(),
-            Some(sqlx::Error::PoolTimedOut),
-            true
-        ))?;
-
-    // Initialize TimescaleDB extension
-    client.execute("CREATE EXTENSION IF NOT EXISTS timescaledb CASCADE", &[])
-        .await
-        .map_err(|e| DatabaseError::MigrationError {
-            message: "Failed to initialize TimescaleDB extension".to_string(),
-            source: Some(sqlx::Error::Database(Box::new(e))),
-        })?;
+/// Brings the schema up to date by running every pending migration
+#[instrument(skip(pool), level = "info")]
+pub async fn initialize_database(pool: &PgPool) -> Result<(), DatabaseError> {
+    info!("Running database migrations");
 
-    // Run migrations using sqlx
-    sqlx::migrate!("./migrations")
-        .run(&pool)
+    MIGRATOR
+        .run(pool)
         .await
         .map_err(|e| DatabaseError::MigrationError {
             message: "Failed to run database migrations".to_string(),
-            source: Some(e),
+            source: Some(e.into()),
         })?;
 
     info!("Database initialization completed successfully");
     Ok(())
 }
 
+/// Versions of embedded migrations not yet applied to the database
+pub async fn pending_migrations(pool: &PgPool) -> Result<Vec<i64>, DatabaseError> {
+    // An empty database has no migrations table yet, so everything is pending
+    let tracked = sqlx::query_scalar!("SELECT to_regclass('_sqlx_migrations')::TEXT")
+        .fetch_one(pool)
+        .await
+        .map_err(|e| DatabaseError::from_sqlx_error(e, "failed to look up migrations table"))?;
+
+    let applied = if tracked.is_some() {
+        sqlx::query_scalar!("SELECT version FROM _sqlx_migrations WHERE success")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| DatabaseError::from_sqlx_error(e, "failed to read applied migrations"))?
+    } else {
+        Vec::new()
+    };
+
+    Ok(MIGRATOR
+        .iter()
+        .map(|migration| migration.version)
+        .filter(|version| !applied.contains(version))
+        .collect())
+}
+
+/// Refuses to start against an out-of-date schema unless auto-migration was requested
+#[instrument(skip(pool), level = "info")]
+pub async fn ensure_schema_current(pool: &PgPool, auto_migrate: bool) -> Result<(), DatabaseError> {
+    let pending = pending_migrations(pool).await?;
+    if pending.is_empty() {
+        info!("Database schema is up to date");
+        return Ok(());
+    }
+
+    if auto_migrate {
+        warn!(?pending, "Applying pending migrations");
+        return initialize_database(pool).await;
+    }
+
+    Err(DatabaseError::MigrationError {
+        message: format!(
+            "{} pending migration(s) {:?}; run with --auto-migrate or apply them before starting",
+            pending.len(),
+            pending
+        ),
+        source: None,
+    })
+}
+
 /// Performs periodic health checks on the database connection
-async fn periodic_health_check(pool: Pool) {
+async fn periodic_health_check(pool: PgPool) {
     loop {
         sleep(Duration::from_secs(DB_HEALTH_CHECK_INTERVAL_SECONDS)).await;
 
-        match pool.get().await {
-            Ok(client) => {
-                match client.execute("SELECT 1", &[]).await {
-                    Ok(_) => info!("Database health check passed"),
-                    Err(e) => error!("Database health check failed: {}", e),
-                }
-            }
-            Err(e) => error!("Failed to get connection for health check: {}", e),
+        match sqlx::query_scalar!(r#"SELECT 1 AS "alive!""#).fetch_one(&pool).await {
+            Ok(_) => info!("Database health check passed"),
+            Err(e) => error!("{}", DatabaseError::from_sqlx_error(e, "database health check failed")),
         }
     }
 }
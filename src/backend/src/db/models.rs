@@ -9,12 +9,11 @@ use sqlx::{postgres::PgPool, FromRow, Pool, Postgres};
 use thiserror::Error;
 use uuid::Uuid;
 use serde::{Serialize, Deserialize};
-use tracing::{error, info, instrument};
+use tracing::{error, instrument};
 
 use crate::config::database::DatabaseConfig;
 
 // Global constants for data retention and batch operations
-const MARKET_DATA_RETENTION_DAYS: i32 = 90;
 const TRADE_HISTORY_RETENTION_YEARS: i32 = 7;
 const MAX_CONNECTION_RETRIES: i32 = 3;
 const BATCH_INSERT_SIZE: usize = 1000;
@@ -28,8 +27,6 @@ pub enum DatabaseError {
     QueryError(String),
     #[error("Data validation failed: {0}")]
     ValidationError(String),
-    #[error("Schema initialization failed: {0}")]
-    SchemaError(String),
 }
 
 /// Market data record with TimescaleDB optimization
@@ -89,29 +86,77 @@ impl MarketDataRecord {
         let mut inserted_ids = Vec::with_capacity(records.len());
         
         for chunk in records.chunks(BATCH_INSERT_SIZE) {
-            let mut query_builder = sqlx::QueryBuilder::new(
-                "INSERT INTO market_data (id, trading_pair, exchange, price, volume, timestamp, created_at) ",
-            );
-
-            query_builder.push_values(chunk, |mut b, record| {
-                b.push_bind(record.id)
-                    .push_bind(&record.trading_pair)
-                    .push_bind(&record.exchange)
-                    .push_bind(record.price)
-                    .push_bind(record.volume)
-                    .push_bind(record.timestamp)
-                    .push_bind(record.created_at);
-            });
-
-            query_builder
-                .push(" RETURNING id")
-                .build()
-                .fetch_all(pool)
-                .await
-                .map_err(|e| DatabaseError::QueryError(e.to_string()))?
-                .into_iter()
-                .map(|row: sqlx::postgres::PgRow| row.get("id"))
-                .for_each(|id| inserted_ids.push(id));
+            let ids: Vec<Uuid> = chunk.iter().map(|r| r.id).collect();
+            let pairs: Vec<String> = chunk.iter().map(|r| r.trading_pair.clone()).collect();
+            let exchanges: Vec<String> = chunk.iter().map(|r| r.exchange.clone()).collect();
+            let prices: Vec<Decimal> = chunk.iter().map(|r| r.price).collect();
+            let volumes: Vec<Decimal> = chunk.iter().map(|r| r.volume).collect();
+            let timestamps: Vec<DateTime<Utc>> = chunk.iter().map(|r| r.timestamp).collect();
+            let created: Vec<DateTime<Utc>> = chunk.iter().map(|r| r.created_at).collect();
+
+            let ids = sqlx::query_scalar!(
+                r#"
+                INSERT INTO market_data (id, trading_pair, exchange, price, volume, timestamp, created_at)
+                SELECT * FROM UNNEST($1::uuid[], $2::text[], $3::text[], $4::numeric[], $5::numeric[], $6::timestamptz[], $7::timestamptz[])
+                RETURNING id
+                "#,
+                &ids,
+                &pairs,
+                &exchanges,
+                &prices,
+                &volumes,
+                &timestamps,
+                &created,
+            )
+            .fetch_all(pool)
+            .await
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+            inserted_ids.extend(ids);
+        }
+
+        Ok(inserted_ids)
+    }
+
+    /// Inserts records tagged with an external source, skipping rows that source already stored
+    #[instrument(skip(pool, records))]
+    pub async fn batch_insert_sourced(
+        pool: &Pool<Postgres>,
+        records: Vec<MarketDataRecord>,
+        source: &str,
+    ) -> Result<Vec<Uuid>, DatabaseError> {
+        let mut inserted_ids = Vec::with_capacity(records.len());
+
+        for chunk in records.chunks(BATCH_INSERT_SIZE) {
+            let ids: Vec<Uuid> = chunk.iter().map(|r| r.id).collect();
+            let pairs: Vec<String> = chunk.iter().map(|r| r.trading_pair.clone()).collect();
+            let exchanges: Vec<String> = chunk.iter().map(|r| r.exchange.clone()).collect();
+            let prices: Vec<Decimal> = chunk.iter().map(|r| r.price).collect();
+            let volumes: Vec<Decimal> = chunk.iter().map(|r| r.volume).collect();
+            let timestamps: Vec<DateTime<Utc>> = chunk.iter().map(|r| r.timestamp).collect();
+            let created: Vec<DateTime<Utc>> = chunk.iter().map(|r| r.created_at).collect();
+
+            let ids = sqlx::query_scalar!(
+                r#"
+                INSERT INTO market_data (id, trading_pair, exchange, price, volume, timestamp, created_at, source)
+                SELECT *, $8 FROM UNNEST($1::uuid[], $2::text[], $3::text[], $4::numeric[], $5::numeric[], $6::timestamptz[], $7::timestamptz[])
+                ON CONFLICT (trading_pair, exchange, timestamp, source) WHERE source LIKE 'vendor:%' DO NOTHING
+                RETURNING id
+                "#,
+                &ids,
+                &pairs,
+                &exchanges,
+                &prices,
+                &volumes,
+                &timestamps,
+                &created,
+                source,
+            )
+            .fetch_all(pool)
+            .await
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+            inserted_ids.extend(ids);
         }
 
         Ok(inserted_ids)
@@ -157,116 +202,424 @@ pub struct StrategyRecord {
     pub updated_at: Option<DateTime<Utc>>,
 }
 
-/// Initializes database schema with optimized indexes and partitioning
-#[instrument(skip(pool))]
-pub async fn initialize_database_schema(pool: &Pool<Postgres>) -> Result<(), DatabaseError> {
-    // Enable TimescaleDB extension
-    sqlx::query("CREATE EXTENSION IF NOT EXISTS timescaledb")
+/// Persisted OHLCV candle
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CandleRecord {
+    pub trading_pair: String,
+    pub interval: String,
+    pub open_time: DateTime<Utc>,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub volume: Decimal,
+    pub trade_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CandleRecord {
+    /// Upserts candles so late-tick corrections overwrite the stored values
+    #[instrument(skip(pool, records))]
+    pub async fn batch_upsert(
+        pool: &Pool<Postgres>,
+        records: &[CandleRecord],
+    ) -> Result<u64, DatabaseError> {
+        let mut affected = 0;
+
+        for chunk in records.chunks(BATCH_INSERT_SIZE) {
+            let pairs: Vec<String> = chunk.iter().map(|r| r.trading_pair.clone()).collect();
+            let intervals: Vec<String> = chunk.iter().map(|r| r.interval.clone()).collect();
+            let open_times: Vec<DateTime<Utc>> = chunk.iter().map(|r| r.open_time).collect();
+            let opens: Vec<Decimal> = chunk.iter().map(|r| r.open).collect();
+            let highs: Vec<Decimal> = chunk.iter().map(|r| r.high).collect();
+            let lows: Vec<Decimal> = chunk.iter().map(|r| r.low).collect();
+            let closes: Vec<Decimal> = chunk.iter().map(|r| r.close).collect();
+            let volumes: Vec<Decimal> = chunk.iter().map(|r| r.volume).collect();
+            let trade_counts: Vec<i64> = chunk.iter().map(|r| r.trade_count).collect();
+
+            affected += sqlx::query!(
+                r#"
+                INSERT INTO candles (trading_pair, interval, open_time, open, high, low, close, volume, trade_count)
+                SELECT * FROM UNNEST($1::text[], $2::text[], $3::timestamptz[], $4::numeric[], $5::numeric[],
+                                     $6::numeric[], $7::numeric[], $8::numeric[], $9::bigint[])
+                ON CONFLICT (trading_pair, interval, open_time) DO UPDATE SET
+                    open = EXCLUDED.open, high = EXCLUDED.high, low = EXCLUDED.low,
+                    close = EXCLUDED.close, volume = EXCLUDED.volume,
+                    trade_count = EXCLUDED.trade_count, updated_at = NOW()
+                "#,
+                &pairs,
+                &intervals,
+                &open_times,
+                &opens,
+                &highs,
+                &lows,
+                &closes,
+                &volumes,
+                &trade_counts,
+            )
+            .execute(pool)
+            .await
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .rows_affected();
+        }
+
+        Ok(affected)
+    }
+}
+
+/// Persisted wallet deposit or withdrawal
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TransferRecord {
+    pub id: Uuid,
+    pub wallet_address: String,
+    pub direction: String,
+    pub amount: Decimal,
+    pub signature: Option<String>,
+    pub detected_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TransferRecord {
+    /// Inserts a transfer, ignoring duplicates of an already recorded signature
+    #[instrument(skip(pool))]
+    pub async fn insert(&self, pool: &Pool<Postgres>) -> Result<bool, DatabaseError> {
+        let result = sqlx::query!(
+            "INSERT INTO transfers (id, wallet_address, direction, amount, signature, detected_at) \
+             VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (signature) DO NOTHING",
+            self.id,
+            &self.wallet_address,
+            &self.direction,
+            self.amount,
+            self.signature.as_deref(),
+            self.detected_at,
+        )
         .execute(pool)
         .await
-        .map_err(|e| DatabaseError::SchemaError(e.to_string()))?;
-
-    // Create market data hypertable
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS market_data (
-            id UUID PRIMARY KEY,
-            trading_pair TEXT NOT NULL,
-            exchange TEXT NOT NULL,
-            price DECIMAL NOT NULL,
-            volume DECIMAL NOT NULL,
-            timestamp TIMESTAMPTZ NOT NULL,
-            created_at TIMESTAMPTZ NOT NULL,
-            updated_at TIMESTAMPTZ
-        );
-        
-        SELECT create_hypertable('market_data', 'timestamp', 
-            chunk_time_interval => INTERVAL '1 day',
-            if_not_exists => TRUE
-        );
-        
-        CREATE INDEX IF NOT EXISTS idx_market_data_pair_time 
-        ON market_data (trading_pair, timestamp DESC);
-        "#,
-    )
-    .execute(pool)
-    .await
-    .map_err(|e| DatabaseError::SchemaError(e.to_string()))?;
-
-    // Create portfolio table
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS portfolios (
-            id UUID PRIMARY KEY,
-            wallet_address TEXT NOT NULL,
-            balance DECIMAL NOT NULL,
-            risk_params JSONB NOT NULL,
-            created_at TIMESTAMPTZ NOT NULL,
-            updated_at TIMESTAMPTZ,
-            CONSTRAINT unique_wallet UNIQUE (wallet_address)
-        );
-        "#,
-    )
-    .execute(pool)
-    .await
-    .map_err(|e| DatabaseError::SchemaError(e.to_string()))?;
-
-    // Create positions table
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS positions (
-            id UUID PRIMARY KEY,
-            portfolio_id UUID NOT NULL,
-            trading_pair TEXT NOT NULL,
-            size DECIMAL NOT NULL,
-            entry_price DECIMAL NOT NULL,
-            current_price DECIMAL NOT NULL,
-            pnl DECIMAL NOT NULL,
-            status TEXT NOT NULL,
-            created_at TIMESTAMPTZ NOT NULL,
-            updated_at TIMESTAMPTZ,
-            closed_at TIMESTAMPTZ,
-            CONSTRAINT fk_portfolio
-                FOREIGN KEY (portfolio_id)
-                REFERENCES portfolios (id)
-                ON DELETE CASCADE
-        );
-        "#,
-    )
-    .execute(pool)
-    .await
-    .map_err(|e| DatabaseError::SchemaError(e.to_string()))?;
-
-    // Create strategies table
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS strategies (
-            id UUID PRIMARY KEY,
-            name TEXT NOT NULL,
-            parameters JSONB NOT NULL,
-            performance_score DOUBLE PRECISION NOT NULL,
-            win_rate DOUBLE PRECISION NOT NULL,
-            created_at TIMESTAMPTZ NOT NULL,
-            updated_at TIMESTAMPTZ,
-            CONSTRAINT unique_strategy_name UNIQUE (name)
-        );
-        "#,
-    )
-    .execute(pool)
-    .await
-    .map_err(|e| DatabaseError::SchemaError(e.to_string()))?;
-
-    // Set up retention policies
-    sqlx::query(&format!(
-        "SELECT add_retention_policy('market_data', INTERVAL '{} days')",
-        MARKET_DATA_RETENTION_DAYS
-    ))
-    .execute(pool)
-    .await
-    .map_err(|e| DatabaseError::SchemaError(e.to_string()))?;
-
-    info!("Database schema initialized successfully");
-    Ok(())
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Persisted partial or full position close
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct PositionCloseRecord {
+    pub id: Uuid,
+    pub trading_pair: String,
+    pub closed_size: Decimal,
+    pub entry_price: Decimal,
+    pub exit_price: Decimal,
+    pub realized_pnl: Decimal,
+    pub remaining_size: Decimal,
+    pub closed_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PositionCloseRecord {
+    /// Inserts a position close record
+    #[instrument(skip(pool))]
+    pub async fn insert(&self, pool: &Pool<Postgres>) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            "INSERT INTO position_closes \
+             (id, trading_pair, closed_size, entry_price, exit_price, realized_pnl, remaining_size, closed_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            self.id,
+            &self.trading_pair,
+            self.closed_size,
+            self.entry_price,
+            self.exit_price,
+            self.realized_pnl,
+            self.remaining_size,
+            self.closed_at,
+        )
+        .execute(pool)
+        .await
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// Persisted outbound webhook endpoint
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct WebhookRecord {
+    pub id: Uuid,
+    pub url: String,
+    pub secret: String,
+    pub event_types: Vec<String>,
+    pub active: bool,
+    pub consecutive_failures: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookRecord {
+    /// Inserts or updates a webhook endpoint
+    #[instrument(skip(self, pool), fields(id = %self.id))]
+    pub async fn upsert(&self, pool: &Pool<Postgres>) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            "INSERT INTO webhooks \
+             (id, url, secret, event_types, active, consecutive_failures, created_at, updated_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+             ON CONFLICT (id) DO UPDATE SET url = EXCLUDED.url, event_types = EXCLUDED.event_types, \
+             active = EXCLUDED.active, consecutive_failures = EXCLUDED.consecutive_failures, \
+             updated_at = EXCLUDED.updated_at",
+            self.id,
+            &self.url,
+            &self.secret,
+            &self.event_types[..],
+            self.active,
+            self.consecutive_failures,
+            self.created_at,
+            self.updated_at,
+        )
+        .execute(pool)
+        .await
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// Persisted webhook status change
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct WebhookAuditRecord {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub action: String,
+    pub detail: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookAuditRecord {
+    /// Inserts a webhook audit entry
+    #[instrument(skip(pool))]
+    pub async fn insert(&self, pool: &Pool<Postgres>) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            "INSERT INTO webhook_audit_log (id, webhook_id, action, detail, created_at) \
+             VALUES ($1, $2, $3, $4, $5)",
+            self.id,
+            self.webhook_id,
+            &self.action,
+            &self.detail,
+            self.created_at,
+        )
+        .execute(pool)
+        .await
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// Persisted strategy pause or resume
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct StrategyAuditRecord {
+    pub id: Uuid,
+    pub strategy_id: String,
+    pub action: String,
+    pub detail: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StrategyAuditRecord {
+    /// Inserts a strategy audit entry
+    #[instrument(skip(pool))]
+    pub async fn insert(&self, pool: &Pool<Postgres>) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            "INSERT INTO strategy_audit_log (id, strategy_id, action, detail, created_at) \
+             VALUES ($1, $2, $3, $4, $5)",
+            self.id,
+            &self.strategy_id,
+            &self.action,
+            &self.detail,
+            self.created_at,
+        )
+        .execute(pool)
+        .await
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// Persisted hourly quality score of a market data source
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DataQualityScoreRecord {
+    pub id: Uuid,
+    pub exchange: String,
+    pub trading_pair: String,
+    pub score: f64,
+    pub staleness: f64,
+    pub gap_frequency: f64,
+    pub spread: f64,
+    pub anomaly_rate: f64,
+    pub demoted: bool,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl DataQualityScoreRecord {
+    /// Inserts a data quality score
+    #[instrument(skip(pool))]
+    pub async fn insert(&self, pool: &Pool<Postgres>) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            "INSERT INTO data_quality_scores \
+             (id, exchange, trading_pair, score, staleness, gap_frequency, spread, anomaly_rate, demoted, recorded_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+            self.id,
+            &self.exchange,
+            &self.trading_pair,
+            self.score,
+            self.staleness,
+            self.gap_frequency,
+            self.spread,
+            self.anomaly_rate,
+            self.demoted,
+            self.recorded_at,
+        )
+        .execute(pool)
+        .await
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// Persisted backtest run of an optimization job
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct OptimizationRunRecord {
+    pub id: Uuid,
+    pub job_id: Uuid,
+    pub combination: i32,
+    pub strategy_type: String,
+    pub trading_pair: String,
+    pub objective: String,
+    pub seed: i64,
+    pub parameters: serde_json::Value,
+    pub total_trades: Option<i32>,
+    pub realized_pnl: Option<Decimal>,
+    pub roi_pct: Option<Decimal>,
+    pub sharpe_ratio: Option<f64>,
+    pub max_drawdown_pct: Option<Decimal>,
+    pub feasible: bool,
+    pub rank: Option<i32>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OptimizationRunRecord {
+    /// Inserts an optimization run, replacing an earlier write of the same combination
+    #[instrument(skip(self, pool), fields(job_id = %self.job_id))]
+    pub async fn upsert(&self, pool: &Pool<Postgres>) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            "INSERT INTO optimization_runs \
+             (id, job_id, combination, strategy_type, trading_pair, objective, seed, parameters, \
+             total_trades, realized_pnl, roi_pct, sharpe_ratio, max_drawdown_pct, feasible, rank, error, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17) \
+             ON CONFLICT (job_id, combination) DO UPDATE SET feasible = EXCLUDED.feasible, rank = EXCLUDED.rank",
+            self.id,
+            self.job_id,
+            self.combination,
+            &self.strategy_type,
+            &self.trading_pair,
+            &self.objective,
+            self.seed,
+            &self.parameters,
+            self.total_trades,
+            self.realized_pnl,
+            self.roi_pct,
+            self.sharpe_ratio,
+            self.max_drawdown_pct,
+            self.feasible,
+            self.rank,
+            self.error.as_deref(),
+            self.created_at,
+        )
+        .execute(pool)
+        .await
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// Persisted background job
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub id: Uuid,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub priority: i32,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub last_error: Option<String>,
+    pub locked_by: Option<String>,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub run_after: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Persisted attribution ledger entry
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct AttributionEntryRecord {
+    pub id: Uuid,
+    pub kind: String,
+    pub wallet: String,
+    pub strategy_id: Option<String>,
+    pub version: Option<i32>,
+    pub venue: Option<String>,
+    pub trading_pair: Option<String>,
+    pub amount: Decimal,
+    pub inventory: Decimal,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Persisted daily attribution rollup row; version 0 stands for unversioned P&L
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct AttributionPeriodRecord {
+    pub period_start: DateTime<Utc>,
+    pub wallet: String,
+    pub strategy_id: String,
+    pub version: i32,
+    pub venue: String,
+    pub trading_pair: String,
+    pub realized_pnl: Decimal,
+    pub fees: Decimal,
+    pub funding: Decimal,
+    pub net_pnl: Decimal,
+    pub trade_count: i64,
+}
+
+/// Persisted market regime change
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RegimeChangeRecord {
+    pub id: Uuid,
+    pub trading_pair: String,
+    pub previous_regime: Option<String>,
+    pub regime: String,
+    pub volatility: f64,
+    pub r_squared: f64,
+    pub slope: f64,
+    pub volume_ratio: f64,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Persisted per-venue execution cost model
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CostModelRecord {
+    pub id: Uuid,
+    pub exchange: String,
+    pub intercept_bps: f64,
+    pub depth_coefficient: f64,
+    pub volatility_coefficient: f64,
+    pub sample_count: i64,
+    pub r_squared: f64,
+    pub valid_from: DateTime<Utc>,
+    pub valid_until: DateTime<Utc>,
+    pub calibrated_at: DateTime<Utc>,
 }
 
 #[cfg(test)]
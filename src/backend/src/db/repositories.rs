@@ -2,15 +2,70 @@
 //! caching, monitoring, and data retention management.
 //! Version: 1.0.0
 
+use async_trait::async_trait;
 use cached::{Cached, TimedCache}; // v0.42.0
+use chrono::{DateTime, NaiveDate, Utc};
 use metrics::{counter, gauge, histogram}; // v0.20.1
+use rust_decimal::Decimal;
 use sqlx::{Pool, Postgres, Transaction}; // v0.7.1
 use thiserror::Error;
 use tokio::time::{sleep, Duration}; // v1.28.0
 use tracing::{error, info, instrument, warn}; // v0.1.37
 use uuid::Uuid;
 
-use crate::db::models::MarketDataRecord;
+use std::sync::Arc;
+
+use crate::api::{ScopedToken, ScopedTokenError, ScopedTokenStore};
+use crate::attribution::{AttributionError, AttributionPeriod, AttributionStore, AttributionTags, LedgerEntry};
+use crate::data_collector::gaps::{DataGap, GapError, GapStatus, GapStore, TickSource};
+use crate::data_collector::ingest::{IngestError, MarketDataSink};
+use crate::data_collector::ohlcv::{Candle, CandleInterval};
+use crate::data_collector::quality::{SourceScore, SourceStatus};
+use crate::data_collector::sanity::{SanityError, SanityRule, SuspectSampleStore};
+use crate::data_collector::trades::{PublicTrade, PublicTradeStore, TradeStreamError};
+use crate::events::{EconomicEvent, EventError, EventStore};
+use crate::jobs::{JobError, JobFilter, JobStore, QueuedJob};
+use crate::key_rotation::{KeyRotationError, KeyRotationStore, RotationRun, StoredDataKey, StoredSecret};
+use crate::maintenance::{MaintenanceError, MaintenanceStore, MaintenanceWindow};
+use crate::microstructure::{MicrostructureAggregate, MicrostructureError, MicrostructureStore};
+use crate::execution_engine::benchmarks::{ExecutionBenchmark, MarketTick, MarketTickSource};
+use crate::execution_engine::compute_budget::ComputeUsage;
+use crate::execution_engine::cost_model::{CostModel, CostModelError, CostModelStore};
+use crate::execution_engine::error::ExecutionError;
+use crate::execution_engine::fills::PositionCloseStore;
+use crate::execution_engine::multi_leg::{TradeGroupResult, TradeGroupStore};
+use crate::execution_engine::position_events::{PositionEventError, PositionEventRecord, PositionEventStore};
+use crate::execution_engine::recovery::{IntentOutcome, IntentStore, RecoveryError, SubmissionIntent};
+use crate::execution_engine::stats::{
+    ExecutionRecord, ExecutionStats, ExecutionStatsStore, StatsError, StatsWindow,
+};
+use crate::db::models::{
+    AttributionEntryRecord, AttributionPeriodRecord, CandleRecord, CostModelRecord, DataQualityScoreRecord, JobRecord, MarketDataRecord, OptimizationRunRecord,
+    PositionCloseRecord, RegimeChangeRecord, StrategyAuditRecord, TransferRecord, WebhookAuditRecord, WebhookRecord,
+};
+use crate::models::market::MarketData;
+use crate::models::portfolio::PositionClose;
+use crate::models::strategy::StrategyAuditEntry;
+use crate::models::transfer::Transfer;
+use crate::models::webhook::{Webhook, WebhookAuditEntry, WebhookEventType};
+use crate::optimizer::{OptimizationRequest, OptimizationRun};
+use crate::performance::{EquityPoint, PerformanceError, PerformanceStore, StrategyTrade};
+use crate::quarantine::{QuarantineEntry, QuarantineError, QuarantineRelease, QuarantineStore};
+use crate::regime::{RegimeChange, RegimeError, RegimeFeatures, RegimeStore};
+use crate::reports::{DailyReport, Incident, IncidentKind, ReportError, ReportSource, ReportStore};
+use crate::retention::{
+    MarketDataPruner, RetentionError, RetentionOverride, RetentionOverrideStore, RetentionSchedule,
+};
+use crate::risk_manager::exposure::TradeSide;
+use crate::risk_manager::factors::{RiskFactorSnapshot, RiskSnapshotStore};
+use crate::risk_manager::perp_reconciliation::{ReconcileError, ReconciliationRun, ReconciliationStore};
+use crate::risk_manager::stress::{StressError, StressRun, StressRunStore};
+use crate::risk_manager::RiskError;
+use crate::state_snapshot::{SnapshotArtifact, SnapshotError, SnapshotStore, SnapshotSummary};
+use crate::strategy_versions::{StrategyVersion, VersionError, VersionStore};
+use crate::sweep::{SweepError, SweepStore};
+use crate::utils::circuit_breaker::{BreakerConfig, CircuitBreaker};
+use crate::utils::crypto::{decrypt_sensitive_data, encrypt_sensitive_data, EncryptedData};
 use crate::utils::metrics::MetricsCollector;
 use crate::utils::time::{calculate_duration_ms, current_timestamp};
 
@@ -18,7 +73,10 @@ use crate::utils::time::{calculate_duration_ms, current_timestamp};
 const BATCH_SIZE: usize = 1000;
 const MAX_RETRIES: u32 = 3;
 const CACHE_TTL_SECONDS: u64 = 300;
-const MARKET_DATA_RETENTION_DAYS: i32 = 90;
+const CIRCUIT_BREAKER_FAILURES: u32 = 5;
+const CIRCUIT_BREAKER_COOLDOWN: Duration = Duration::from_secs(60);
+/// `encrypted_secrets` scope of webhook signing secrets, keyed by webhook id
+pub const WEBHOOK_SECRET_SCOPE: &str = "webhook";
 
 /// Repository-specific error types
 #[derive(Error, Debug)]
@@ -59,7 +117,7 @@ pub struct MarketDataRepository {
     pool: Pool<Postgres>,
     cache: TimedCache<String, Vec<MarketDataRecord>>,
     metrics: MetricsCollector,
-    circuit_breaker: CircuitBreaker,
+    circuit_breaker: Arc<CircuitBreaker>,
 }
 
 impl MarketDataRepository {
@@ -69,7 +127,7 @@ impl MarketDataRepository {
             pool,
             cache: TimedCache::with_lifespan(CACHE_TTL_SECONDS),
             metrics,
-            circuit_breaker: CircuitBreaker::new(),
+            circuit_breaker: db_breaker("market_data_repository"),
         }
     }
 
@@ -139,14 +197,16 @@ impl MarketDataRepository {
         let result = execute_with_retry(
             &self.pool,
             |tx| async move {
-                sqlx::query_as::<_, MarketDataRecord>(
-                    "SELECT * FROM market_data 
-                     WHERE trading_pair = $1 
+                sqlx::query_as!(
+                    MarketDataRecord,
+                    "SELECT id, trading_pair, exchange, price, volume, timestamp, created_at, updated_at
+                     FROM market_data 
+                     WHERE trading_pair = $1 AND suspect_rule IS NULL
                      ORDER BY timestamp DESC 
                      LIMIT $2",
+                    trading_pair,
+                    limit,
                 )
-                .bind(trading_pair)
-                .bind(limit)
                 .fetch_all(tx)
                 .await
             },
@@ -165,33 +225,64 @@ impl MarketDataRepository {
         Ok(result)
     }
 
-    /// Applies data retention policy
-    #[instrument(skip(self))]
-    pub async fn apply_retention_policy(&self) -> Result<u64, RepositoryError> {
+    /// Deletes market data past the retention its pair and exchange are given: a pair and
+    /// exchange override, else a pair override, else the schedule's default
+    #[instrument(skip(self, schedule))]
+    pub async fn apply_retention_policy(
+        &self,
+        schedule: &RetentionSchedule,
+        now: DateTime<Utc>,
+    ) -> Result<u64, RepositoryError> {
         let start_time = current_timestamp();
 
+        // Pair-wide overrides go in with an empty exchange
+        let pairs: Vec<String> = schedule.overrides.iter().map(|o| o.trading_pair.clone()).collect();
+        let exchanges: Vec<String> = schedule
+            .overrides
+            .iter()
+            .map(|o| o.exchange.clone().unwrap_or_default())
+            .collect();
+        let days: Vec<i32> = schedule.overrides.iter().map(|o| o.days as i32).collect();
+        let default_days = schedule.default_days as i32;
+
         let result = execute_with_retry(
             &self.pool,
-            |tx| async move {
-                sqlx::query(
-                    "DELETE FROM market_data 
-                     WHERE timestamp < NOW() - INTERVAL '$1 days'",
-                )
-                .bind(MARKET_DATA_RETENTION_DAYS)
-                .execute(tx)
-                .await
+            |tx| {
+                let (pairs, exchanges, days) = (pairs.clone(), exchanges.clone(), days.clone());
+                async move {
+                    sqlx::query!(
+                        "WITH overrides AS (
+                             SELECT * FROM UNNEST($1::text[], $2::text[], $3::int[]) AS o(trading_pair, exchange, days)
+                         )
+                         DELETE FROM market_data m
+                         WHERE m.timestamp < $4 - make_interval(days => COALESCE(
+                             (SELECT o.days FROM overrides o
+                              WHERE o.trading_pair = m.trading_pair AND o.exchange = LOWER(m.exchange)),
+                             (SELECT o.days FROM overrides o
+                              WHERE o.trading_pair = m.trading_pair AND o.exchange = ''),
+                             $5))",
+                        &pairs[..],
+                        &exchanges[..],
+                        &days[..],
+                        now,
+                        default_days,
+                    )
+                    .execute(tx)
+                    .await
+                }
             },
             RetryPolicy::default(),
         )
         .await?;
 
         let rows_deleted = result.rows_affected();
-        
+
         // Record metrics
         let duration = calculate_duration_ms(start_time, current_timestamp())
             .map_err(|e| RepositoryError::TimeoutError(e.to_string()))?;
         histogram!("market_data_retention_duration_ms", duration as f64);
         counter!("market_data_records_deleted", rows_deleted);
+        self.invalidate_cache().await?;
 
         info!("Deleted {} old market data records", rows_deleted);
         Ok(rows_deleted)
@@ -205,69 +296,3337 @@ impl MarketDataRepository {
     }
 }
 
-/// Circuit breaker for database operations
+#[async_trait]
+impl MarketDataPruner for MarketDataRepository {
+    async fn prune(&self, schedule: &RetentionSchedule, now: DateTime<Utc>) -> Result<u64, RetentionError> {
+        self.apply_retention_policy(schedule, now)
+            .await
+            .map_err(|e| RetentionError::Store(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl MarketDataSink for MarketDataRepository {
+    #[instrument(skip(self, records), fields(rows = records.len()))]
+    async fn insert_sourced(&self, records: Vec<MarketDataRecord>, source: &str) -> Result<u64, IngestError> {
+        let inserted = execute_with_retry(
+            &self.pool,
+            |tx| async move { MarketDataRecord::batch_insert_sourced(tx, records, source).await },
+            RetryPolicy::default(),
+        )
+        .await
+        .map_err(|e| IngestError::Store(e.to_string()))?;
+
+        counter!("market_data_records_ingested", inserted.len() as u64);
+        self.invalidate_cache()
+            .await
+            .map_err(|e| IngestError::Store(e.to_string()))?;
+        Ok(inserted.len() as u64)
+    }
+}
+
+#[async_trait]
+impl MarketTickSource for MarketDataRepository {
+    async fn ticks(
+        &self,
+        trading_pair: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<MarketTick>, StatsError> {
+        let rows = sqlx::query!(
+            "SELECT price, volume, timestamp FROM market_data
+             WHERE trading_pair = $1 AND timestamp >= $2 AND timestamp <= $3 AND suspect_rule IS NULL
+             ORDER BY timestamp",
+            trading_pair,
+            from,
+            to,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(stats_store_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| MarketTick {
+                price: row.price,
+                volume: row.volume,
+                timestamp: row.timestamp,
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl SuspectSampleStore for MarketDataRepository {
+    async fn save_suspect(&self, market_data: &MarketData, rule: SanityRule) -> Result<(), SanityError> {
+        sqlx::query!(
+            "INSERT INTO market_data (id, trading_pair, exchange, price, volume, timestamp, created_at, source, suspect_rule)
+             VALUES ($1, $2, $3, $4, $5, $6, NOW(), 'live', $7)",
+            market_data.id(),
+            market_data.trading_pair(),
+            market_data.exchange(),
+            market_data.price(),
+            market_data.volume(),
+            market_data.timestamp(),
+            rule.as_str(),
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| SanityError::Store(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Candle repository for OHLCV persistence and range queries
 #[derive(Debug)]
-struct CircuitBreaker {
-    failures: std::sync::atomic::AtomicU32,
-    last_failure: std::sync::atomic::AtomicI64,
-    threshold: u32,
-    reset_timeout: Duration,
+pub struct CandleRepository {
+    pool: Pool<Postgres>,
+    circuit_breaker: Arc<CircuitBreaker>,
 }
 
-impl CircuitBreaker {
-    fn new() -> Self {
+impl CandleRepository {
+    /// Creates a new candle repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
         Self {
-            failures: std::sync::atomic::AtomicU32::new(0),
-            last_failure: std::sync::atomic::AtomicI64::new(0),
-            threshold: 5,
-            reset_timeout: Duration::from_secs(60),
+            pool,
+            circuit_breaker: db_breaker("candle_repository"),
         }
     }
 
-    fn record_failure(&self) {
-        self.failures.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-        self.last_failure.store(
-            current_timestamp().timestamp(),
-            std::sync::atomic::Ordering::SeqCst,
-        );
-    }
+    /// Inserts or corrects candles in a single batch
+    #[instrument(skip(self, candles))]
+    pub async fn upsert_candles(&self, candles: &[Candle]) -> Result<u64, RepositoryError> {
+        if !self.circuit_breaker.allow() {
+            return Err(RepositoryError::CircuitBreakerError(
+                "candle writes suspended".to_string(),
+            ));
+        }
 
-    fn is_open(&self) -> bool {
-        let failures = self.failures.load(std::sync::atomic::Ordering::SeqCst);
-        if failures >= self.threshold {
-            let last_failure = self.last_failure.load(std::sync::atomic::Ordering::SeqCst);
-            let elapsed = current_timestamp().timestamp() - last_failure;
-            if elapsed < self.reset_timeout.as_secs() as i64 {
-                return true;
+        let now = current_timestamp();
+        let records: Vec<CandleRecord> = candles
+            .iter()
+            .filter(|candle| !candle.gap_filled)
+            .map(|candle| CandleRecord {
+                trading_pair: candle.trading_pair.clone(),
+                interval: candle.interval.as_str().to_string(),
+                open_time: candle.open_time,
+                open: candle.open,
+                high: candle.high,
+                low: candle.low,
+                close: candle.close,
+                volume: candle.volume,
+                trade_count: candle.trade_count as i64,
+                created_at: now,
+                updated_at: now,
+            })
+            .collect();
+
+        match CandleRecord::batch_upsert(&self.pool, &records).await {
+            Ok(affected) => {
+                self.circuit_breaker.record_success();
+                counter!("candles_upserted", affected);
+                Ok(affected)
+            }
+            Err(e) => {
+                self.circuit_breaker.record_failure();
+                Err(RepositoryError::DatabaseError(e.to_string()))
             }
-            // Reset after timeout
-            self.failures.store(0, std::sync::atomic::Ordering::SeqCst);
         }
-        false
+    }
+
+    /// Retrieves stored candles for a pair and interval within [from, to)
+    #[instrument(skip(self))]
+    pub async fn get_candles(
+        &self,
+        trading_pair: &str,
+        interval: CandleInterval,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Candle>, RepositoryError> {
+        let start_time = current_timestamp();
+
+        let records = sqlx::query_as!(
+            CandleRecord,
+            "SELECT trading_pair, interval, open_time, open, high, low, close, volume, trade_count,
+                    created_at, updated_at
+             FROM candles 
+             WHERE trading_pair = $1 AND interval = $2 
+             AND open_time >= $3 AND open_time < $4 
+             ORDER BY open_time ASC",
+            trading_pair,
+            interval.as_str(),
+            from,
+            to,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        let duration = calculate_duration_ms(start_time, current_timestamp())
+            .map_err(|e| RepositoryError::TimeoutError(e.to_string()))?;
+        histogram!("candles_query_duration_ms", duration as f64);
+
+        Ok(records
+            .into_iter()
+            .map(|record| {
+                Candle::from_parts(
+                    record.trading_pair,
+                    interval,
+                    record.open_time,
+                    record.open,
+                    record.high,
+                    record.low,
+                    record.close,
+                    record.volume,
+                    record.trade_count.max(0) as u64,
+                )
+            })
+            .collect())
     }
 }
 
-/// Executes database operations with retry logic and circuit breaker
-#[instrument(skip(pool, operation))]
-async fn execute_with_retry<F, T, E>(
-    pool: &Pool<Postgres>,
-    operation: F,
-    policy: RetryPolicy,
-) -> Result<T, RepositoryError>
-where
-    F: Fn(Transaction<'_, Postgres>) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<T, E>> + Send>>,
-    E: std::error::Error,
-{
-    let mut attempt = 0;
-    let mut last_error = None;
+/// Repository for wallet deposits and withdrawals
+#[derive(Debug)]
+pub struct TransferRepository {
+    pool: Pool<Postgres>,
+}
 
-    while attempt < policy.max_attempts {
-        if attempt > 0 {
-            let delay = std::cmp::min(
-                policy.base_delay_ms * 2u64.pow(attempt),
-                policy.max_delay_ms,
-            );
-            sleep(Duration::from_millis(delay)).await;
+impl TransferRepository {
+    /// Creates a new transfer repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    /// Persists a detected transfer; returns false if it was already recorded
+    #[instrument(skip(self, transfer))]
+    pub async fn record_transfer(&self, transfer: &Transfer) -> Result<bool, RepositoryError> {
+        let record = TransferRecord {
+            id: transfer.id,
+            wallet_address: transfer.wallet_address.clone(),
+            direction: transfer.direction.as_str().to_string(),
+            amount: transfer.amount,
+            signature: transfer.signature.clone(),
+            detected_at: transfer.detected_at,
+            created_at: current_timestamp(),
+        };
+
+        let inserted = record
+            .insert(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        if inserted {
+            counter!("transfers_recorded", 1);
+        }
+        Ok(inserted)
+    }
+
+    /// Retrieves the most recent transfers for a wallet, newest first
+    #[instrument(skip(self))]
+    pub async fn get_transfers(
+        &self,
+        wallet_address: &str,
+        limit: i64,
+    ) -> Result<Vec<Transfer>, RepositoryError> {
+        let records = sqlx::query_as!(
+            TransferRecord,
+            "SELECT id, wallet_address, direction, amount, signature, detected_at, created_at
+             FROM transfers 
+             WHERE wallet_address = $1 
+             ORDER BY detected_at DESC 
+             LIMIT $2",
+            wallet_address,
+            limit,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        records
+            .into_iter()
+            .map(|record| {
+                Ok(Transfer {
+                    id: record.id,
+                    wallet_address: record.wallet_address,
+                    direction: record
+                        .direction
+                        .parse()
+                        .map_err(|e: crate::models::transfer::TransferError| {
+                            RepositoryError::ValidationError(e.to_string())
+                        })?,
+                    amount: record.amount,
+                    signature: record.signature,
+                    detected_at: record.detected_at,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Repository for partial and full position closes
+#[derive(Debug)]
+pub struct PositionCloseRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PositionCloseRepository {
+    /// Creates a new position close repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    /// Persists a position close with its realized P&L
+    #[instrument(skip(self, close))]
+    pub async fn record_close(&self, close: &PositionClose) -> Result<(), RepositoryError> {
+        let record = PositionCloseRecord {
+            id: close.id,
+            trading_pair: close.trading_pair.clone(),
+            closed_size: close.closed_size,
+            entry_price: close.entry_price,
+            exit_price: close.exit_price,
+            realized_pnl: close.realized_pnl,
+            remaining_size: close.remaining_size,
+            closed_at: close.closed_at,
+            created_at: current_timestamp(),
+        };
+
+        record
+            .insert(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        counter!("position_closes_recorded", 1);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PositionCloseStore for PositionCloseRepository {
+    async fn record_close(&self, close: &PositionClose) -> Result<(), ExecutionError> {
+        PositionCloseRepository::record_close(self, close)
+            .await
+            .map_err(|e| ExecutionError::InternalError(format!("position close store: {}", e)))
+    }
+}
+
+/// Repository for outbound webhook endpoints and their audit trail. Signing secrets are
+/// stored encrypted and only decrypted when loaded into the dispatcher.
+#[derive(Debug)]
+pub struct WebhookRepository {
+    pool: Pool<Postgres>,
+    kms_key_id: String,
+    /// Signing secrets are sealed in `encrypted_secrets` so key rotation re-encrypts them
+    secrets: KeyRotationRepository,
+}
+
+impl WebhookRepository {
+    /// Creates a new webhook repository instance
+    pub fn new(pool: Pool<Postgres>, kms_key_id: String) -> Self {
+        let secrets = KeyRotationRepository::new(pool.clone());
+        Self { pool, kms_key_id, secrets }
+    }
+
+    /// Inserts or updates a webhook
+    #[instrument(skip(self, webhook), fields(id = %webhook.id))]
+    pub async fn save_webhook(&self, webhook: &Webhook) -> Result<(), RepositoryError> {
+        let secret = encrypt_sensitive_data(webhook.secret.clone(), self.kms_key_id.clone())
+            .await
+            .map_err(RepositoryError::ValidationError)?;
+        self.secrets
+            .save_secret(WEBHOOK_SECRET_SCOPE, &webhook.id.to_string(), &secret)
+            .await?;
+        let record = WebhookRecord {
+            id: webhook.id,
+            url: webhook.url.clone(),
+            // The column only holds secrets written before they moved to `encrypted_secrets`
+            secret: String::new(),
+            event_types: webhook
+                .event_types
+                .iter()
+                .map(|event_type| event_type.as_str().to_string())
+                .collect(),
+            active: webhook.active,
+            consecutive_failures: webhook.consecutive_failures as i32,
+            created_at: webhook.created_at,
+            updated_at: webhook.updated_at,
+        };
+
+        record
+            .upsert(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))
+    }
+
+    /// Loads every registered webhook
+    #[instrument(skip(self))]
+    pub async fn get_webhooks(&self) -> Result<Vec<Webhook>, RepositoryError> {
+        let records = sqlx::query_as!(
+            WebhookRecord,
+            "SELECT id, url, secret, event_types, active, consecutive_failures, created_at, updated_at
+             FROM webhooks ORDER BY created_at",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        let mut webhooks = Vec::with_capacity(records.len());
+        for record in records {
+            let event_types = record
+                .event_types
+                .iter()
+                .map(|event_type| event_type.parse::<WebhookEventType>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| RepositoryError::ValidationError(e.to_string()))?;
+            let encrypted = match self.secrets.load_secret(WEBHOOK_SECRET_SCOPE, &record.id.to_string()).await? {
+                Some(encrypted) => encrypted,
+                None => self.migrate_legacy_secret(record.id, &record.secret).await?,
+            };
+            let secret = decrypt_sensitive_data(encrypted, self.kms_key_id.clone())
+                .await
+                .map_err(RepositoryError::ValidationError)?;
+
+            webhooks.push(Webhook {
+                id: record.id,
+                url: record.url,
+                secret,
+                event_types,
+                active: record.active,
+                consecutive_failures: record.consecutive_failures.max(0) as u32,
+                created_at: record.created_at,
+                updated_at: record.updated_at,
+            });
+        }
+        Ok(webhooks)
+    }
+
+    /// Deletes a webhook and its audit trail
+    #[instrument(skip(self))]
+    pub async fn delete_webhook(&self, id: Uuid) -> Result<bool, RepositoryError> {
+        let result = sqlx::query!("DELETE FROM webhooks WHERE id = $1", id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        self.secrets.delete_secret(WEBHOOK_SECRET_SCOPE, &id.to_string()).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Moves a secret still stored on the webhook row into `encrypted_secrets`
+    async fn migrate_legacy_secret(&self, id: Uuid, encoded: &str) -> Result<EncryptedData, RepositoryError> {
+        let encrypted = EncryptedData::decode(encoded).map_err(RepositoryError::ValidationError)?;
+        self.secrets
+            .save_secret(WEBHOOK_SECRET_SCOPE, &id.to_string(), &encrypted)
+            .await?;
+        sqlx::query!("UPDATE webhooks SET secret = '' WHERE id = $1", id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        info!(webhook_id = %id, "Moved webhook secret to encrypted secrets");
+        Ok(encrypted)
+    }
+
+    /// Persists a webhook audit entry
+    #[instrument(skip(self, entry))]
+    pub async fn record_audit(&self, entry: &WebhookAuditEntry) -> Result<(), RepositoryError> {
+        let record = WebhookAuditRecord {
+            id: entry.id,
+            webhook_id: entry.webhook_id,
+            action: entry.action.clone(),
+            detail: entry.detail.clone(),
+            created_at: entry.created_at,
+        };
+
+        record
+            .insert(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))
+    }
+}
+
+/// Repository for the strategy pause and resume audit trail
+#[derive(Debug)]
+pub struct StrategyAuditRepository {
+    pool: Pool<Postgres>,
+}
+
+impl StrategyAuditRepository {
+    /// Creates a new strategy audit repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    /// Persists a strategy audit entry
+    #[instrument(skip(self, entry))]
+    pub async fn record_audit(&self, entry: &StrategyAuditEntry) -> Result<(), RepositoryError> {
+        let record = StrategyAuditRecord {
+            id: entry.id,
+            strategy_id: entry.strategy_id.clone(),
+            action: entry.action.clone(),
+            detail: entry.detail.clone(),
+            created_at: entry.created_at,
+        };
+
+        record
+            .insert(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))
+    }
+}
+
+/// Repository for hourly market data quality scores
+#[derive(Debug)]
+pub struct DataQualityRepository {
+    pool: Pool<Postgres>,
+}
+
+impl DataQualityRepository {
+    /// Creates a new data quality repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    /// Persists the current score of every source
+    #[instrument(skip(self, scores))]
+    pub async fn record_scores(
+        &self,
+        scores: &[SourceScore],
+        recorded_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), RepositoryError> {
+        for score in scores {
+            let record = DataQualityScoreRecord {
+                id: Uuid::new_v4(),
+                exchange: score.exchange.clone(),
+                trading_pair: score.trading_pair.clone(),
+                score: score.breakdown.score,
+                staleness: score.breakdown.staleness,
+                gap_frequency: score.breakdown.gap_frequency,
+                spread: score.breakdown.spread,
+                anomaly_rate: score.breakdown.anomaly_rate,
+                demoted: score.status == SourceStatus::Demoted,
+                recorded_at,
+            };
+            record
+                .insert(&self.pool)
+                .await
+                .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Repository for detected market data gaps and their backfilled rows
+#[derive(Debug)]
+pub struct DataGapRepository {
+    pool: Pool<Postgres>,
+}
+
+impl DataGapRepository {
+    /// Creates a new data gap repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn gap_store_error(e: sqlx::Error) -> GapError {
+    GapError::Store(e.to_string())
+}
+
+#[async_trait]
+impl GapStore for DataGapRepository {
+    async fn sources(&self, since: DateTime<Utc>) -> Result<Vec<(String, String)>, GapError> {
+        let rows = sqlx::query!(
+            "SELECT DISTINCT trading_pair, exchange FROM market_data WHERE timestamp >= $1",
+            since,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(gap_store_error)?;
+
+        Ok(rows.into_iter().map(|row| (row.trading_pair, row.exchange)).collect())
+    }
+
+    async fn tick_times(
+        &self,
+        trading_pair: &str,
+        exchange: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<DateTime<Utc>>, GapError> {
+        let rows = sqlx::query!(
+            "SELECT timestamp FROM market_data
+             WHERE trading_pair = $1 AND exchange = $2 AND timestamp >= $3 AND timestamp <= $4
+             ORDER BY timestamp",
+            trading_pair,
+            exchange,
+            from,
+            to,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(gap_store_error)?;
+
+        Ok(rows.into_iter().map(|row| row.timestamp).collect())
+    }
+
+    async fn gaps(&self, since: DateTime<Utc>) -> Result<Vec<DataGap>, GapError> {
+        let rows = sqlx::query!(
+            "SELECT id, trading_pair, exchange, gap_start, gap_end, status, backfill_cursor,
+                    backfilled_rows, last_error, detected_at, updated_at
+             FROM data_gaps WHERE gap_end >= $1
+             ORDER BY gap_start DESC",
+            since,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(gap_store_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(DataGap {
+                    id: row.id,
+                    trading_pair: row.trading_pair,
+                    exchange: row.exchange,
+                    gap_start: row.gap_start,
+                    gap_end: row.gap_end,
+                    status: GapStatus::parse(&row.status)?,
+                    backfill_cursor: row.backfill_cursor,
+                    backfilled_rows: row.backfilled_rows.max(0) as u64,
+                    last_error: row.last_error,
+                    detected_at: row.detected_at,
+                    updated_at: row.updated_at,
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(skip(self, gap), fields(gap_id = %gap.id, status = gap.status.as_str()))]
+    async fn save_gap(&self, gap: &DataGap) -> Result<(), GapError> {
+        sqlx::query!(
+            "INSERT INTO data_gaps
+                (id, trading_pair, exchange, gap_start, gap_end, status, backfill_cursor,
+                 backfilled_rows, last_error, detected_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             ON CONFLICT (id) DO UPDATE SET
+                status = EXCLUDED.status,
+                backfill_cursor = EXCLUDED.backfill_cursor,
+                backfilled_rows = EXCLUDED.backfilled_rows,
+                last_error = EXCLUDED.last_error,
+                updated_at = EXCLUDED.updated_at",
+            gap.id,
+            gap.trading_pair,
+            gap.exchange,
+            gap.gap_start,
+            gap.gap_end,
+            gap.status.as_str(),
+            gap.backfill_cursor,
+            gap.backfilled_rows as i64,
+            gap.last_error,
+            gap.detected_at,
+            gap.updated_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(gap_store_error)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self, ticks), fields(rows = ticks.len()))]
+    async fn insert_backfill(&self, trading_pair: &str, exchange: &str, ticks: &[MarketTick]) -> Result<u64, GapError> {
+        let mut tx = self.pool.begin().await.map_err(gap_store_error)?;
+        for tick in ticks {
+            sqlx::query!(
+                "INSERT INTO market_data (trading_pair, exchange, price, volume, timestamp, source)
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+                trading_pair,
+                exchange,
+                tick.price,
+                tick.volume,
+                tick.timestamp,
+                TickSource::Backfill.as_str(),
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(gap_store_error)?;
+        }
+        tx.commit().await.map_err(gap_store_error)?;
+        Ok(ticks.len() as u64)
+    }
+}
+
+/// Repository for strategy optimization runs
+#[derive(Debug)]
+pub struct OptimizationRunRepository {
+    pool: Pool<Postgres>,
+}
+
+impl OptimizationRunRepository {
+    /// Creates a new optimization run repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    /// Persists every run of a finished optimization job
+    #[instrument(skip(self, request, runs))]
+    pub async fn record_runs(
+        &self,
+        job_id: Uuid,
+        request: &OptimizationRequest,
+        runs: &[OptimizationRun],
+    ) -> Result<(), RepositoryError> {
+        let strategy_type = serde_json::to_value(&request.strategy_type)
+            .map_err(|e| RepositoryError::ValidationError(e.to_string()))?;
+        let objective = serde_json::to_value(request.objective)
+            .map_err(|e| RepositoryError::ValidationError(e.to_string()))?;
+
+        for run in runs {
+            let metrics = run.metrics.as_ref();
+            let record = OptimizationRunRecord {
+                id: Uuid::new_v4(),
+                job_id,
+                combination: run.combination as i32,
+                strategy_type: strategy_type.as_str().unwrap_or_default().to_string(),
+                trading_pair: request.trading_pair.clone(),
+                objective: objective.as_str().unwrap_or_default().to_string(),
+                seed: request.seed as i64,
+                parameters: serde_json::to_value(&run.parameters)
+                    .map_err(|e| RepositoryError::ValidationError(e.to_string()))?,
+                total_trades: metrics.map(|m| m.total_trades as i32),
+                realized_pnl: metrics.map(|m| m.realized_pnl),
+                roi_pct: metrics.map(|m| m.roi_pct),
+                sharpe_ratio: metrics.map(|m| m.sharpe_ratio),
+                max_drawdown_pct: metrics.map(|m| m.max_drawdown_pct),
+                feasible: run.feasible,
+                rank: run.rank.map(|rank| rank as i32),
+                error: run.error.clone(),
+                created_at: current_timestamp(),
+            };
+
+            record
+                .upsert(&self.pool)
+                .await
+                .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        }
+
+        counter!("optimization_runs_recorded", runs.len() as u64);
+        Ok(())
+    }
+}
+
+/// Repository for versioned data keys, sealed secrets and key rotation runs
+#[derive(Debug)]
+pub struct KeyRotationRepository {
+    pool: Pool<Postgres>,
+}
+
+impl KeyRotationRepository {
+    /// Creates a new key rotation repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    /// Inserts or replaces a sealed secret
+    #[instrument(skip(self, data))]
+    pub async fn save_secret(&self, scope: &str, name: &str, data: &EncryptedData) -> Result<Uuid, RepositoryError> {
+        let now = current_timestamp();
+        let id = sqlx::query_scalar!(
+            "INSERT INTO encrypted_secrets (id, scope, name, key_version, nonce, ciphertext, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $7)
+             ON CONFLICT (scope, name) DO UPDATE
+             SET key_version = EXCLUDED.key_version, nonce = EXCLUDED.nonce,
+                 ciphertext = EXCLUDED.ciphertext, updated_at = EXCLUDED.updated_at
+             RETURNING id",
+            Uuid::new_v4(),
+            scope,
+            name,
+            data.key_version as i32,
+            data.nonce,
+            data.ciphertext,
+            now,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(id)
+    }
+
+    /// Loads a sealed secret by scope and name
+    #[instrument(skip(self))]
+    pub async fn load_secret(&self, scope: &str, name: &str) -> Result<Option<EncryptedData>, RepositoryError> {
+        let row = sqlx::query!(
+            "SELECT key_version, nonce, ciphertext FROM encrypted_secrets WHERE scope = $1 AND name = $2",
+            scope,
+            name,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.map(|row| {
+            EncryptedData::new(row.ciphertext, row.nonce)
+                .map(|data| data.with_key_version(row.key_version.max(0) as u32))
+                .map_err(RepositoryError::ValidationError)
+        })
+        .transpose()
+    }
+
+    /// Removes a sealed secret; missing secrets are not an error
+    #[instrument(skip(self))]
+    pub async fn delete_secret(&self, scope: &str, name: &str) -> Result<bool, RepositoryError> {
+        let result = sqlx::query!("DELETE FROM encrypted_secrets WHERE scope = $1 AND name = $2", scope, name)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn rotation_store_error(e: sqlx::Error) -> KeyRotationError {
+    KeyRotationError::Store(e.to_string())
+}
+
+#[async_trait]
+impl KeyRotationStore for KeyRotationRepository {
+    async fn data_keys(&self) -> Result<Vec<StoredDataKey>, KeyRotationError> {
+        let rows = sqlx::query!(
+            "SELECT version, wrapped_key, status, created_at, retired_at FROM data_keys ORDER BY version",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(rotation_store_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(StoredDataKey {
+                    version: row.version.max(0) as u32,
+                    wrapped_key: row.wrapped_key,
+                    status: row.status.parse()?,
+                    created_at: row.created_at,
+                    retired_at: row.retired_at,
+                })
+            })
+            .collect()
+    }
+
+    async fn insert_active_key(&self, key: &StoredDataKey) -> Result<(), KeyRotationError> {
+        let mut tx = self.pool.begin().await.map_err(rotation_store_error)?;
+        sqlx::query!("UPDATE data_keys SET status = 'retiring' WHERE status = 'active'")
+            .execute(&mut *tx)
+            .await
+            .map_err(rotation_store_error)?;
+        sqlx::query!(
+            "INSERT INTO data_keys (version, wrapped_key, status, created_at) VALUES ($1, $2, $3, $4)",
+            key.version as i32,
+            key.wrapped_key,
+            key.status.as_str(),
+            key.created_at,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(rotation_store_error)?;
+        tx.commit().await.map_err(rotation_store_error)
+    }
+
+    async fn retire_key(&self, version: u32, at: DateTime<Utc>) -> Result<(), KeyRotationError> {
+        // Drop the wrapped material too; nothing sealed with it remains
+        sqlx::query!(
+            "UPDATE data_keys SET status = 'retired', retired_at = $2, wrapped_key = ''::BYTEA WHERE version = $1",
+            version as i32,
+            at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(rotation_store_error)?;
+        Ok(())
+    }
+
+    async fn count_secrets(&self) -> Result<u64, KeyRotationError> {
+        let count = sqlx::query_scalar!("SELECT COUNT(*) FROM encrypted_secrets")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(rotation_store_error)?;
+        Ok(count.unwrap_or(0).max(0) as u64)
+    }
+
+    async fn secrets_after(&self, after: Option<Uuid>, limit: usize) -> Result<Vec<StoredSecret>, KeyRotationError> {
+        let rows = sqlx::query!(
+            "SELECT id, scope, name, key_version, nonce, ciphertext FROM encrypted_secrets
+             WHERE $1::UUID IS NULL OR id > $1
+             ORDER BY id LIMIT $2",
+            after,
+            limit as i64,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(rotation_store_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                let data = EncryptedData::new(row.ciphertext, row.nonce)
+                    .map_err(KeyRotationError::Crypto)?
+                    .with_key_version(row.key_version.max(0) as u32);
+                Ok(StoredSecret { id: row.id, scope: row.scope, name: row.name, data })
+            })
+            .collect()
+    }
+
+    async fn update_secret(&self, id: Uuid, data: &EncryptedData) -> Result<(), KeyRotationError> {
+        sqlx::query!(
+            "UPDATE encrypted_secrets SET key_version = $2, nonce = $3, ciphertext = $4, updated_at = $5 WHERE id = $1",
+            id,
+            data.key_version as i32,
+            data.nonce,
+            data.ciphertext,
+            current_timestamp(),
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(rotation_store_error)?;
+        Ok(())
+    }
+
+    async fn save_run(&self, run: &RotationRun) -> Result<(), KeyRotationError> {
+        sqlx::query!(
+            "INSERT INTO key_rotation_runs
+                (id, target_version, status, total_secrets, processed_secrets, last_secret_id, error,
+                 started_at, updated_at, completed_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             ON CONFLICT (id) DO UPDATE
+             SET status = EXCLUDED.status, total_secrets = EXCLUDED.total_secrets,
+                 processed_secrets = EXCLUDED.processed_secrets, last_secret_id = EXCLUDED.last_secret_id,
+                 error = EXCLUDED.error, updated_at = EXCLUDED.updated_at, completed_at = EXCLUDED.completed_at",
+            run.id,
+            run.target_version as i32,
+            run.status.as_str(),
+            run.total_secrets as i64,
+            run.processed_secrets as i64,
+            run.last_secret_id,
+            run.error,
+            run.started_at,
+            run.updated_at,
+            run.completed_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(rotation_store_error)?;
+        Ok(())
+    }
+
+    async fn unfinished_run(&self) -> Result<Option<RotationRun>, KeyRotationError> {
+        let row = sqlx::query!(
+            "SELECT id, target_version, status, total_secrets, processed_secrets, last_secret_id, error,
+                    started_at, updated_at, completed_at
+             FROM key_rotation_runs WHERE status <> 'completed'
+             ORDER BY started_at DESC LIMIT 1",
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(rotation_store_error)?;
+
+        row.map(|row| {
+            Ok(RotationRun {
+                id: row.id,
+                target_version: row.target_version.max(0) as u32,
+                status: row.status.parse()?,
+                total_secrets: row.total_secrets.max(0) as u64,
+                processed_secrets: row.processed_secrets.max(0) as u64,
+                last_secret_id: row.last_secret_id,
+                error: row.error,
+                started_at: row.started_at,
+                updated_at: row.updated_at,
+                completed_at: row.completed_at,
+            })
+        })
+        .transpose()
+    }
+}
+
+/// Repository for execution outcomes and their materialized statistics
+#[derive(Debug)]
+pub struct ExecutionStatsRepository {
+    pool: Pool<Postgres>,
+}
+
+impl ExecutionStatsRepository {
+    /// Creates a new execution statistics repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn stats_store_error(e: sqlx::Error) -> StatsError {
+    StatsError::Store(e.to_string())
+}
+
+fn trade_side(side: &str) -> Result<TradeSide, StatsError> {
+    match side {
+        "buy" => Ok(TradeSide::Buy),
+        "sell" => Ok(TradeSide::Sell),
+        other => Err(StatsError::Store(format!("unknown trade side: {}", other))),
+    }
+}
+
+#[async_trait]
+impl ExecutionStatsStore for ExecutionStatsRepository {
+    #[instrument(
+        skip(self, record),
+        fields(execution_id = %record.id, trading_pair = %record.trading_pair, exchange = %record.exchange)
+    )]
+    async fn record(&self, record: &ExecutionRecord) -> Result<(), StatsError> {
+        let side = match record.side {
+            TradeSide::Buy => "buy",
+            TradeSide::Sell => "sell",
+        };
+        sqlx::query!(
+            "INSERT INTO trade_executions
+                (id, trading_pair, exchange, side, requested_size, filled_size, expected_price,
+                 executed_price, fee, latency_ms, mev_value, executed_at, book_depth, volatility_bps,
+                 compute_unit_limit, compute_units_consumed, event_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)",
+            record.id,
+            record.trading_pair,
+            record.exchange,
+            side,
+            record.requested_size,
+            record.filled_size,
+            record.expected_price,
+            record.executed_price,
+            record.fee,
+            record.latency_ms as i64,
+            record.mev_value,
+            record.executed_at,
+            record.book_depth,
+            record.volatility_bps,
+            record.compute.map(|compute| compute.unit_limit as i32),
+            record.compute.and_then(|compute| compute.units_consumed).map(|units| units as i64),
+            record.event_id,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(stats_store_error)?;
+        Ok(())
+    }
+
+    async fn records_since(&self, cutoff: DateTime<Utc>) -> Result<Vec<ExecutionRecord>, StatsError> {
+        let rows = sqlx::query!(
+            "SELECT id, trading_pair, exchange, side, requested_size, filled_size, expected_price,
+                    executed_price, fee, latency_ms, mev_value, executed_at,
+                    benchmark_window_start, benchmark_window_end, vwap_benchmark, twap_benchmark,
+                    vwap_deviation_bps, twap_deviation_bps, benchmark_tick_count, benchmark_coverage,
+                    benchmark_low_coverage, benchmark_computed_at, book_depth, volatility_bps,
+                    compute_unit_limit, compute_units_consumed, event_id
+             FROM trade_executions WHERE executed_at >= $1",
+            cutoff,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(stats_store_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                let benchmark = match (
+                    row.benchmark_window_start,
+                    row.benchmark_window_end,
+                    row.benchmark_coverage,
+                    row.benchmark_computed_at,
+                ) {
+                    (Some(window_start), Some(window_end), Some(coverage), Some(computed_at)) => {
+                        Some(ExecutionBenchmark {
+                            window_start,
+                            window_end,
+                            vwap: row.vwap_benchmark,
+                            twap: row.twap_benchmark,
+                            vwap_deviation_bps: row.vwap_deviation_bps,
+                            twap_deviation_bps: row.twap_deviation_bps,
+                            tick_count: row.benchmark_tick_count.unwrap_or_default().max(0) as u64,
+                            coverage,
+                            low_coverage: row.benchmark_low_coverage.unwrap_or(true),
+                            computed_at,
+                        })
+                    }
+                    _ => None,
+                };
+                Ok(ExecutionRecord {
+                    id: row.id,
+                    trading_pair: row.trading_pair,
+                    exchange: row.exchange,
+                    side: trade_side(&row.side)?,
+                    requested_size: row.requested_size,
+                    filled_size: row.filled_size,
+                    expected_price: row.expected_price,
+                    executed_price: row.executed_price,
+                    fee: row.fee,
+                    latency_ms: row.latency_ms.max(0) as u64,
+                    mev_value: row.mev_value,
+                    executed_at: row.executed_at,
+                    benchmark,
+                    book_depth: row.book_depth,
+                    volatility_bps: row.volatility_bps,
+                    compute: row.compute_unit_limit.map(|unit_limit| ComputeUsage {
+                        unit_limit: unit_limit.max(0) as u32,
+                        units_consumed: row.compute_units_consumed.map(|units| units.max(0) as u64),
+                    }),
+                    event_id: row.event_id,
+                })
+            })
+            .collect()
+    }
+
+    async fn replace_stats(&self, stats: &[ExecutionStats]) -> Result<(), StatsError> {
+        let mut tx = self.pool.begin().await.map_err(stats_store_error)?;
+        sqlx::query!("DELETE FROM execution_stats")
+            .execute(&mut *tx)
+            .await
+            .map_err(stats_store_error)?;
+        for stats in stats {
+            sqlx::query!(
+                "INSERT INTO execution_stats
+                    (trading_pair, exchange, stats_window, sample_count, fill_rate, avg_slippage_bps,
+                     p95_slippage_bps, avg_latency_ms, mev_capture_bps, fee_bps, benchmarked_count,
+                     avg_vwap_deviation_bps, avg_twap_deviation_bps, computed_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)",
+                stats.trading_pair,
+                stats.exchange,
+                stats.window.as_str(),
+                stats.sample_count as i64,
+                stats.fill_rate,
+                stats.avg_slippage_bps,
+                stats.p95_slippage_bps,
+                stats.avg_latency_ms,
+                stats.mev_capture_bps,
+                stats.fee_bps,
+                stats.benchmarked_count as i64,
+                stats.avg_vwap_deviation_bps,
+                stats.avg_twap_deviation_bps,
+                stats.computed_at,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(stats_store_error)?;
+        }
+        tx.commit().await.map_err(stats_store_error)?;
+        Ok(())
+    }
+
+    async fn load_stats(&self, trading_pair: &str, window: StatsWindow) -> Result<Vec<ExecutionStats>, StatsError> {
+        let rows = sqlx::query!(
+            "SELECT trading_pair, exchange, sample_count, fill_rate, avg_slippage_bps, p95_slippage_bps,
+                    avg_latency_ms, mev_capture_bps, fee_bps, benchmarked_count, avg_vwap_deviation_bps,
+                    avg_twap_deviation_bps, computed_at
+             FROM execution_stats WHERE trading_pair = $1 AND stats_window = $2
+             ORDER BY exchange",
+            trading_pair,
+            window.as_str(),
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(stats_store_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ExecutionStats {
+                trading_pair: row.trading_pair,
+                exchange: row.exchange,
+                window,
+                sample_count: row.sample_count.max(0) as u64,
+                fill_rate: row.fill_rate,
+                avg_slippage_bps: row.avg_slippage_bps,
+                p95_slippage_bps: row.p95_slippage_bps,
+                avg_latency_ms: row.avg_latency_ms,
+                mev_capture_bps: row.mev_capture_bps,
+                fee_bps: row.fee_bps,
+                benchmarked_count: row.benchmarked_count.max(0) as u64,
+                avg_vwap_deviation_bps: row.avg_vwap_deviation_bps,
+                avg_twap_deviation_bps: row.avg_twap_deviation_bps,
+                computed_at: row.computed_at,
+            })
+            .collect())
+    }
+
+    async fn record_benchmark(&self, execution_id: Uuid, benchmark: &ExecutionBenchmark) -> Result<(), StatsError> {
+        sqlx::query!(
+            "UPDATE trade_executions SET
+                benchmark_window_start = $2, benchmark_window_end = $3, vwap_benchmark = $4,
+                twap_benchmark = $5, vwap_deviation_bps = $6, twap_deviation_bps = $7,
+                benchmark_tick_count = $8, benchmark_coverage = $9, benchmark_low_coverage = $10,
+                benchmark_computed_at = $11
+             WHERE id = $1",
+            execution_id,
+            benchmark.window_start,
+            benchmark.window_end,
+            benchmark.vwap,
+            benchmark.twap,
+            benchmark.vwap_deviation_bps,
+            benchmark.twap_deviation_bps,
+            benchmark.tick_count as i64,
+            benchmark.coverage,
+            benchmark.low_coverage,
+            benchmark.computed_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(stats_store_error)?;
+        Ok(())
+    }
+}
+
+/// Repository for disaster recovery state snapshots
+#[derive(Debug)]
+pub struct SnapshotRepository {
+    pool: Pool<Postgres>,
+}
+
+impl SnapshotRepository {
+    /// Creates a new snapshot repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn snapshot_store_error(e: sqlx::Error) -> SnapshotError {
+    SnapshotError::Store(e.to_string())
+}
+
+#[async_trait]
+impl SnapshotStore for SnapshotRepository {
+    async fn save(&self, artifact: &SnapshotArtifact) -> Result<(), SnapshotError> {
+        sqlx::query!(
+            "INSERT INTO state_snapshots (id, format_version, checksum, payload, trigger, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            artifact.id,
+            artifact.format_version as i32,
+            artifact.checksum,
+            artifact.payload,
+            artifact.trigger.as_str(),
+            artifact.created_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(snapshot_store_error)?;
+        Ok(())
+    }
+
+    async fn load(&self, id: Uuid) -> Result<Option<SnapshotArtifact>, SnapshotError> {
+        let row = sqlx::query!(
+            "SELECT id, format_version, checksum, payload, trigger, created_at
+             FROM state_snapshots WHERE id = $1",
+            id,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(snapshot_store_error)?;
+
+        row.map(|row| {
+            Ok(SnapshotArtifact {
+                id: row.id,
+                format_version: row.format_version.max(0) as u32,
+                checksum: row.checksum,
+                payload: row.payload,
+                trigger: row.trigger.parse()?,
+                created_at: row.created_at,
+            })
+        })
+        .transpose()
+    }
+
+    async fn list(&self, limit: i64) -> Result<Vec<SnapshotSummary>, SnapshotError> {
+        let rows = sqlx::query!(
+            "SELECT id, format_version, checksum, LENGTH(payload) AS size_bytes, trigger, s3_key, created_at
+             FROM state_snapshots ORDER BY created_at DESC LIMIT $1",
+            limit,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(snapshot_store_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(SnapshotSummary {
+                    id: row.id,
+                    format_version: row.format_version.max(0) as u32,
+                    checksum: row.checksum,
+                    size_bytes: row.size_bytes.unwrap_or(0).max(0) as usize,
+                    trigger: row.trigger.parse()?,
+                    created_at: row.created_at,
+                    s3_key: row.s3_key,
+                })
+            })
+            .collect()
+    }
+
+    async fn mark_uploaded(&self, id: Uuid, s3_key: &str) -> Result<(), SnapshotError> {
+        sqlx::query!("UPDATE state_snapshots SET s3_key = $2 WHERE id = $1", id, s3_key)
+            .execute(&self.pool)
+            .await
+            .map_err(snapshot_store_error)?;
+        Ok(())
+    }
+
+    async fn delete_before(&self, cutoff: DateTime<Utc>) -> Result<u64, SnapshotError> {
+        let result = sqlx::query!("DELETE FROM state_snapshots WHERE created_at < $1", cutoff)
+            .execute(&self.pool)
+            .await
+            .map_err(snapshot_store_error)?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// Repository for per-strategy realized trades and their materialized equity curves
+#[derive(Debug)]
+pub struct PerformanceRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PerformanceRepository {
+    /// Creates a new performance repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn performance_store_error(e: sqlx::Error) -> PerformanceError {
+    PerformanceError::Store(e.to_string())
+}
+
+fn equity_granularity(granularity: &str) -> Result<CandleInterval, PerformanceError> {
+    granularity
+        .parse()
+        .map_err(|_| PerformanceError::Store(format!("unknown equity granularity: {}", granularity)))
+}
+
+#[async_trait]
+impl PerformanceStore for PerformanceRepository {
+    async fn record_trade(&self, trade: &StrategyTrade) -> Result<(), PerformanceError> {
+        sqlx::query!(
+            "INSERT INTO strategy_trades (id, strategy_id, trading_pair, realized_pnl, closed_at, version)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            trade.id,
+            trade.strategy_id,
+            trade.trading_pair,
+            trade.realized_pnl,
+            trade.closed_at,
+            trade.version.map(|version| version as i32),
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(performance_store_error)?;
+        Ok(())
+    }
+
+    async fn trades(&self, strategy_id: &str, limit: usize) -> Result<Vec<StrategyTrade>, PerformanceError> {
+        let rows = sqlx::query!(
+            "SELECT id, strategy_id, trading_pair, realized_pnl, closed_at, version
+             FROM strategy_trades WHERE strategy_id = $1
+             ORDER BY closed_at DESC LIMIT $2",
+            strategy_id,
+            limit as i64,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(performance_store_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| StrategyTrade {
+                id: row.id,
+                strategy_id: row.strategy_id,
+                trading_pair: row.trading_pair,
+                realized_pnl: row.realized_pnl,
+                closed_at: row.closed_at,
+                version: row.version.map(|version| version.max(0) as u32),
+            })
+            .collect())
+    }
+
+    async fn version_trades(
+        &self,
+        strategy_id: &str,
+        version: u32,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<StrategyTrade>, PerformanceError> {
+        let rows = sqlx::query!(
+            "SELECT id, strategy_id, trading_pair, realized_pnl, closed_at, version
+             FROM strategy_trades WHERE strategy_id = $1 AND version = $2 AND closed_at >= $3
+             ORDER BY closed_at ASC",
+            strategy_id,
+            version as i32,
+            since,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(performance_store_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| StrategyTrade {
+                id: row.id,
+                strategy_id: row.strategy_id,
+                trading_pair: row.trading_pair,
+                realized_pnl: row.realized_pnl,
+                closed_at: row.closed_at,
+                version: row.version.map(|version| version.max(0) as u32),
+            })
+            .collect())
+    }
+
+    async fn upsert_equity(&self, points: &[EquityPoint]) -> Result<(), PerformanceError> {
+        let mut tx = self.pool.begin().await.map_err(performance_store_error)?;
+        for point in points {
+            sqlx::query!(
+                "INSERT INTO strategy_equity
+                    (strategy_id, granularity, bucket_start, pnl, cumulative_pnl, trade_count)
+                 VALUES ($1, $2, $3, $4, $5, $6)
+                 ON CONFLICT (strategy_id, granularity, bucket_start) DO UPDATE SET
+                    pnl = EXCLUDED.pnl,
+                    cumulative_pnl = EXCLUDED.cumulative_pnl,
+                    trade_count = EXCLUDED.trade_count",
+                point.strategy_id,
+                point.granularity.as_str(),
+                point.bucket_start,
+                point.pnl,
+                point.cumulative_pnl,
+                point.trade_count as i64,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(performance_store_error)?;
+        }
+        tx.commit().await.map_err(performance_store_error)?;
+        Ok(())
+    }
+
+    async fn latest_equity(&self, strategy_id: &str) -> Result<Vec<EquityPoint>, PerformanceError> {
+        let rows = sqlx::query!(
+            "SELECT DISTINCT ON (granularity)
+                    strategy_id, granularity, bucket_start, pnl, cumulative_pnl, trade_count
+             FROM strategy_equity WHERE strategy_id = $1
+             ORDER BY granularity, bucket_start DESC",
+            strategy_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(performance_store_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(EquityPoint {
+                    strategy_id: row.strategy_id,
+                    granularity: equity_granularity(&row.granularity)?,
+                    bucket_start: row.bucket_start,
+                    pnl: row.pnl,
+                    cumulative_pnl: row.cumulative_pnl,
+                    trade_count: row.trade_count.max(0) as u64,
+                })
+            })
+            .collect()
+    }
+
+    async fn equity(
+        &self,
+        strategy_id: &str,
+        granularity: CandleInterval,
+        limit: usize,
+    ) -> Result<Vec<EquityPoint>, PerformanceError> {
+        let rows = sqlx::query!(
+            "SELECT bucket_start, pnl, cumulative_pnl, trade_count FROM (
+                SELECT bucket_start, pnl, cumulative_pnl, trade_count
+                FROM strategy_equity WHERE strategy_id = $1 AND granularity = $2
+                ORDER BY bucket_start DESC LIMIT $3
+             ) recent ORDER BY bucket_start ASC",
+            strategy_id,
+            granularity.as_str(),
+            limit as i64,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(performance_store_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| EquityPoint {
+                strategy_id: strategy_id.to_string(),
+                granularity,
+                bucket_start: row.bucket_start,
+                pnl: row.pnl,
+                cumulative_pnl: row.cumulative_pnl,
+                trade_count: row.trade_count.max(0) as u64,
+            })
+            .collect())
+    }
+}
+
+/// Repository for strategy parameter versions
+#[derive(Debug)]
+pub struct StrategyVersionRepository {
+    pool: Pool<Postgres>,
+}
+
+impl StrategyVersionRepository {
+    /// Creates a new strategy version repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn version_store_error(e: sqlx::Error) -> VersionError {
+    VersionError::Store(e.to_string())
+}
+
+#[async_trait]
+impl VersionStore for StrategyVersionRepository {
+    async fn insert_version(&self, version: &StrategyVersion) -> Result<(), VersionError> {
+        let parameters =
+            serde_json::to_value(&version.parameters).map_err(|e| VersionError::Store(e.to_string()))?;
+        sqlx::query!(
+            "INSERT INTO strategy_versions
+                (strategy_id, version, param_hash, parameters, created_by, created_at, activated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            version.strategy_id,
+            version.version as i32,
+            version.param_hash,
+            parameters,
+            version.created_by,
+            version.created_at,
+            version.activated_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(version_store_error)?;
+        Ok(())
+    }
+
+    async fn set_activated(&self, strategy_id: &str, version: u32, at: DateTime<Utc>) -> Result<(), VersionError> {
+        sqlx::query!(
+            "UPDATE strategy_versions SET activated_at = $3 WHERE strategy_id = $1 AND version = $2",
+            strategy_id,
+            version as i32,
+            at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(version_store_error)?;
+        Ok(())
+    }
+
+    async fn versions(&self, strategy_id: &str) -> Result<Vec<StrategyVersion>, VersionError> {
+        let rows = sqlx::query!(
+            "SELECT strategy_id, version, param_hash, parameters, created_by, created_at, activated_at
+             FROM strategy_versions WHERE strategy_id = $1
+             ORDER BY version",
+            strategy_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(version_store_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(StrategyVersion {
+                    strategy_id: row.strategy_id,
+                    version: row.version.max(0) as u32,
+                    param_hash: row.param_hash,
+                    parameters: serde_json::from_value(row.parameters)
+                        .map_err(|e| VersionError::Store(e.to_string()))?,
+                    created_by: row.created_by,
+                    created_at: row.created_at,
+                    activated_at: row.activated_at,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Repository for periodic portfolio risk factor snapshots
+#[derive(Debug)]
+pub struct RiskSnapshotRepository {
+    pool: Pool<Postgres>,
+}
+
+impl RiskSnapshotRepository {
+    /// Creates a new risk snapshot repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn risk_store_error(e: impl std::fmt::Display) -> RiskError {
+    RiskError::MonitoringError(e.to_string())
+}
+
+#[async_trait]
+impl RiskSnapshotStore for RiskSnapshotRepository {
+    async fn record(&self, snapshot: &RiskFactorSnapshot) -> Result<(), RiskError> {
+        let payload = serde_json::to_value(snapshot).map_err(risk_store_error)?;
+        sqlx::query!(
+            "INSERT INTO risk_snapshots
+                (id, computed_at, total_value, net_exposure, gross_exposure, drawdown,
+                 value_at_risk, margin_utilization, circuit_breaker_tripped, payload)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+            snapshot.id,
+            snapshot.computed_at,
+            snapshot.total_value,
+            snapshot.net_exposure,
+            snapshot.gross_exposure,
+            snapshot.drawdown,
+            snapshot.value_at_risk.value,
+            snapshot.margin_utilization.value,
+            snapshot.circuit_breaker_tripped,
+            payload,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(risk_store_error)?;
+        Ok(())
+    }
+
+    async fn recent(&self, limit: i64) -> Result<Vec<RiskFactorSnapshot>, RiskError> {
+        let rows = sqlx::query!(
+            "SELECT payload FROM risk_snapshots ORDER BY computed_at DESC LIMIT $1",
+            limit,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(risk_store_error)?;
+
+        rows.into_iter()
+            .map(|row| serde_json::from_value(row.payload).map_err(risk_store_error))
+            .collect()
+    }
+}
+
+/// Repository for the stress test audit trail
+#[derive(Debug)]
+pub struct StressRunRepository {
+    pool: Pool<Postgres>,
+}
+
+impl StressRunRepository {
+    /// Creates a new stress run repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn stress_store_error(e: impl std::fmt::Display) -> StressError {
+    StressError::Store(e.to_string())
+}
+
+#[async_trait]
+impl StressRunStore for StressRunRepository {
+    async fn record(&self, run: &StressRun) -> Result<(), StressError> {
+        let payload = serde_json::to_value(run).map_err(stress_store_error)?;
+        let scenarios: Vec<String> = run.results.iter().map(|result| result.scenario.name.clone()).collect();
+        sqlx::query!(
+            "INSERT INTO stress_runs (id, requested_by, computed_at, scenarios, base_value, max_loss, payload)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            run.id,
+            run.requested_by,
+            run.computed_at,
+            &scenarios,
+            run.base_value,
+            run.max_loss(),
+            payload,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(stress_store_error)?;
+        Ok(())
+    }
+
+    async fn recent(&self, limit: i64) -> Result<Vec<StressRun>, StressError> {
+        let rows = sqlx::query!(
+            "SELECT payload FROM stress_runs ORDER BY computed_at DESC LIMIT $1",
+            limit,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(stress_store_error)?;
+
+        rows.into_iter()
+            .map(|row| serde_json::from_value(row.payload).map_err(stress_store_error))
+            .collect()
+    }
+}
+
+/// Repository for scoped read-only API tokens
+#[derive(Debug)]
+pub struct ScopedTokenRepository {
+    pool: Pool<Postgres>,
+}
+
+impl ScopedTokenRepository {
+    /// Creates a new scoped token repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn scoped_token_store_error(e: impl std::fmt::Display) -> ScopedTokenError {
+    ScopedTokenError::Store(e.to_string())
+}
+
+#[async_trait]
+impl ScopedTokenStore for ScopedTokenRepository {
+    async fn save(&self, token: &ScopedToken, secret_hash: &str) -> Result<(), ScopedTokenError> {
+        let scopes = serde_json::to_value(&token.scopes).map_err(scoped_token_store_error)?;
+        sqlx::query!(
+            "INSERT INTO scoped_tokens (id, secret_hash, label, scopes, created_by, created_at, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            token.id,
+            secret_hash,
+            token.label,
+            scopes,
+            token.created_by,
+            token.created_at,
+            token.expires_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(scoped_token_store_error)?;
+        Ok(())
+    }
+
+    async fn revoke(&self, id: Uuid, revoked_at: DateTime<Utc>) -> Result<(), ScopedTokenError> {
+        sqlx::query!(
+            "UPDATE scoped_tokens SET revoked_at = $2 WHERE id = $1 AND revoked_at IS NULL",
+            id,
+            revoked_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(scoped_token_store_error)?;
+        Ok(())
+    }
+
+    async fn load(&self) -> Result<Vec<(ScopedToken, String)>, ScopedTokenError> {
+        let rows = sqlx::query!(
+            "SELECT id, secret_hash, label, scopes, created_by, created_at, expires_at, revoked_at
+             FROM scoped_tokens ORDER BY created_at",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(scoped_token_store_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                let token = ScopedToken {
+                    id: row.id,
+                    label: row.label,
+                    scopes: serde_json::from_value(row.scopes).map_err(scoped_token_store_error)?,
+                    created_by: row.created_by,
+                    created_at: row.created_at,
+                    expires_at: row.expires_at,
+                    revoked_at: row.revoked_at,
+                };
+                Ok((token, row.secret_hash))
+            })
+            .collect()
+    }
+}
+
+/// Repository for the multi-leg trade group audit trail
+#[derive(Debug)]
+pub struct TradeGroupRepository {
+    pool: Pool<Postgres>,
+}
+
+impl TradeGroupRepository {
+    /// Creates a new trade group repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn trade_group_store_error(e: impl std::fmt::Display) -> ExecutionError {
+    ExecutionError::InternalError(format!("trade group store: {}", e))
+}
+
+#[async_trait]
+impl TradeGroupStore for TradeGroupRepository {
+    async fn record(&self, result: &TradeGroupResult) -> Result<(), ExecutionError> {
+        let payload = serde_json::to_value(result).map_err(trade_group_store_error)?;
+        let status = serde_json::to_value(result.status).map_err(trade_group_store_error)?;
+        let policy = serde_json::to_value(result.policy).map_err(trade_group_store_error)?;
+        sqlx::query!(
+            "INSERT INTO trade_groups
+                (correlation_id, strategy_id, policy, status, bundled, hedged, completed_at, payload)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            result.correlation_id,
+            result.strategy_id,
+            policy.as_str().unwrap_or_default(),
+            status.as_str().unwrap_or_default(),
+            result.bundled,
+            !result.hedges.is_empty(),
+            result.completed_at,
+            payload,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(trade_group_store_error)?;
+        Ok(())
+    }
+}
+
+/// Repository for per-pair market data retention overrides
+#[derive(Debug)]
+pub struct RetentionOverrideRepository {
+    pool: Pool<Postgres>,
+}
+
+impl RetentionOverrideRepository {
+    /// Creates a new retention override repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn retention_store_error(e: sqlx::Error) -> RetentionError {
+    RetentionError::Store(e.to_string())
+}
+
+#[async_trait]
+impl RetentionOverrideStore for RetentionOverrideRepository {
+    async fn save(&self, entry: &RetentionOverride) -> Result<(), RetentionError> {
+        sqlx::query!(
+            "INSERT INTO retention_overrides (trading_pair, exchange, days, updated_by, updated_at)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (trading_pair, exchange)
+             DO UPDATE SET days = EXCLUDED.days, updated_by = EXCLUDED.updated_by, updated_at = EXCLUDED.updated_at",
+            entry.trading_pair,
+            entry.exchange.as_deref().unwrap_or_default(),
+            entry.days as i32,
+            entry.updated_by,
+            entry.updated_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(retention_store_error)?;
+        Ok(())
+    }
+
+    async fn remove(&self, trading_pair: &str, exchange: Option<&str>) -> Result<(), RetentionError> {
+        sqlx::query!(
+            "DELETE FROM retention_overrides WHERE trading_pair = $1 AND exchange = $2",
+            trading_pair,
+            exchange.unwrap_or_default(),
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(retention_store_error)?;
+        Ok(())
+    }
+
+    async fn load(&self) -> Result<Vec<RetentionOverride>, RetentionError> {
+        let rows = sqlx::query!(
+            "SELECT trading_pair, exchange, days, updated_by, updated_at
+             FROM retention_overrides
+             ORDER BY trading_pair, exchange",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(retention_store_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| RetentionOverride {
+                trading_pair: row.trading_pair,
+                exchange: Some(row.exchange).filter(|exchange| !exchange.is_empty()),
+                days: row.days as u32,
+                updated_by: row.updated_by,
+                updated_at: row.updated_at,
+            })
+            .collect())
+    }
+}
+
+/// Repository for the append-only position event log
+#[derive(Debug)]
+pub struct PositionEventRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PositionEventRepository {
+    /// Creates a new position event repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn position_event_store_error(e: impl std::fmt::Display) -> PositionEventError {
+    PositionEventError::Store(e.to_string())
+}
+
+#[async_trait]
+impl PositionEventStore for PositionEventRepository {
+    #[instrument(skip(self, record), fields(position_id = %record.position_id, sequence = record.sequence))]
+    async fn append(&self, record: &PositionEventRecord) -> Result<(), PositionEventError> {
+        let payload = serde_json::to_value(&record.event).map_err(position_event_store_error)?;
+        sqlx::query!(
+            "INSERT INTO position_events
+                (id, position_id, sequence, event_type, payload, cause_id, occurred_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            record.id,
+            record.position_id,
+            record.sequence as i64,
+            record.event.event_type(),
+            payload,
+            record.cause_id,
+            record.occurred_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(position_event_store_error)?;
+        Ok(())
+    }
+
+    async fn events(&self, position_id: Uuid) -> Result<Vec<PositionEventRecord>, PositionEventError> {
+        let rows = sqlx::query!(
+            "SELECT id, position_id, sequence, payload, cause_id, occurred_at
+             FROM position_events WHERE position_id = $1
+             ORDER BY sequence",
+            position_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(position_event_store_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(PositionEventRecord {
+                    id: row.id,
+                    position_id: row.position_id,
+                    sequence: row.sequence.max(0) as u64,
+                    event: serde_json::from_value(row.payload).map_err(position_event_store_error)?,
+                    cause_id: row.cause_id,
+                    occurred_at: row.occurred_at,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Repository for scheduled maintenance windows
+#[derive(Debug)]
+pub struct MaintenanceRepository {
+    pool: Pool<Postgres>,
+}
+
+impl MaintenanceRepository {
+    /// Creates a new maintenance window repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn maintenance_store_error(e: sqlx::Error) -> MaintenanceError {
+    MaintenanceError::Store(e.to_string())
+}
+
+#[async_trait]
+impl MaintenanceStore for MaintenanceRepository {
+    async fn save(&self, window: &MaintenanceWindow) -> Result<(), MaintenanceError> {
+        sqlx::query!(
+            "INSERT INTO maintenance_windows (id, starts_at, duration_secs, mode, reason, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            window.id,
+            window.start,
+            window.duration_secs as i64,
+            window.mode.as_str(),
+            window.reason,
+            window.created_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(maintenance_store_error)?;
+        Ok(())
+    }
+
+    async fn remove(&self, id: Uuid) -> Result<(), MaintenanceError> {
+        sqlx::query!("DELETE FROM maintenance_windows WHERE id = $1", id)
+            .execute(&self.pool)
+            .await
+            .map_err(maintenance_store_error)?;
+        Ok(())
+    }
+
+    async fn pending(&self, now: DateTime<Utc>) -> Result<Vec<MaintenanceWindow>, MaintenanceError> {
+        let rows = sqlx::query!(
+            "SELECT id, starts_at, duration_secs, mode, reason, created_at
+             FROM maintenance_windows
+             WHERE starts_at + make_interval(secs => duration_secs) > $1
+             ORDER BY starts_at",
+            now,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(maintenance_store_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(MaintenanceWindow {
+                    id: row.id,
+                    start: row.starts_at,
+                    duration_secs: row.duration_secs.max(0) as u64,
+                    mode: row.mode.parse()?,
+                    reason: row.reason,
+                    created_at: row.created_at,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Repository for pair quarantines and their release history
+#[derive(Debug)]
+pub struct QuarantineRepository {
+    pool: Pool<Postgres>,
+}
+
+impl QuarantineRepository {
+    /// Creates a new pair quarantine repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn quarantine_store_error(e: sqlx::Error) -> QuarantineError {
+    QuarantineError::Store(e.to_string())
+}
+
+#[async_trait]
+impl QuarantineStore for QuarantineRepository {
+    async fn save(&self, entry: &QuarantineEntry) -> Result<(), QuarantineError> {
+        sqlx::query!(
+            "INSERT INTO pair_quarantines (trading_pair, mode, reason, quarantined_by, quarantined_at)
+             VALUES ($1, $2, $3, $4, $5)",
+            entry.trading_pair,
+            entry.mode.as_str(),
+            entry.reason,
+            entry.quarantined_by,
+            entry.quarantined_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(quarantine_store_error)?;
+        Ok(())
+    }
+
+    async fn release(&self, release: &QuarantineRelease) -> Result<(), QuarantineError> {
+        let mut tx = self.pool.begin().await.map_err(quarantine_store_error)?;
+        sqlx::query!(
+            "DELETE FROM pair_quarantines WHERE trading_pair = $1",
+            release.entry.trading_pair,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(quarantine_store_error)?;
+        sqlx::query!(
+            "INSERT INTO pair_quarantine_releases
+                (id, trading_pair, mode, quarantine_reason, quarantined_by, quarantined_at,
+                 released_by, release_reason, released_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+            Uuid::new_v4(),
+            release.entry.trading_pair,
+            release.entry.mode.as_str(),
+            release.entry.reason,
+            release.entry.quarantined_by,
+            release.entry.quarantined_at,
+            release.released_by,
+            release.reason,
+            release.released_at,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(quarantine_store_error)?;
+        tx.commit().await.map_err(quarantine_store_error)?;
+        Ok(())
+    }
+
+    async fn active(&self) -> Result<Vec<QuarantineEntry>, QuarantineError> {
+        let rows = sqlx::query!(
+            "SELECT trading_pair, mode, reason, quarantined_by, quarantined_at
+             FROM pair_quarantines
+             ORDER BY trading_pair",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(quarantine_store_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(QuarantineEntry {
+                    trading_pair: row.trading_pair,
+                    mode: row.mode.parse()?,
+                    reason: row.reason,
+                    quarantined_by: row.quarantined_by,
+                    quarantined_at: row.quarantined_at,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Repository for the economic event calendar
+#[derive(Debug)]
+pub struct EconomicEventRepository {
+    pool: Pool<Postgres>,
+}
+
+impl EconomicEventRepository {
+    /// Creates a new economic event repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    async fn fetch(
+        &self,
+        from: DateTime<Utc>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<EconomicEvent>, EventError> {
+        let rows = sqlx::query!(
+            "SELECT id, timestamp, name, impact, created_by, created_at
+             FROM economic_events
+             WHERE timestamp >= $1 AND ($2::timestamptz IS NULL OR timestamp < $2)
+             ORDER BY timestamp, name",
+            from,
+            to,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(event_store_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(EconomicEvent {
+                    id: row.id,
+                    timestamp: row.timestamp,
+                    name: row.name,
+                    impact: row.impact.parse()?,
+                    created_by: row.created_by,
+                    created_at: row.created_at,
+                })
+            })
+            .collect()
+    }
+}
+
+fn event_store_error(e: sqlx::Error) -> EventError {
+    EventError::Store(e.to_string())
+}
+
+#[async_trait]
+impl EventStore for EconomicEventRepository {
+    #[instrument(skip(self, events), fields(events = events.len()))]
+    async fn save(&self, events: &[EconomicEvent]) -> Result<(), EventError> {
+        let mut tx = self.pool.begin().await.map_err(event_store_error)?;
+        for event in events {
+            sqlx::query!(
+                "INSERT INTO economic_events (id, timestamp, name, impact, created_by, created_at)
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+                event.id,
+                event.timestamp,
+                event.name,
+                event.impact.as_str(),
+                event.created_by,
+                event.created_at,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(event_store_error)?;
+        }
+        tx.commit().await.map_err(event_store_error)?;
+        Ok(())
+    }
+
+    async fn remove(&self, id: Uuid) -> Result<(), EventError> {
+        sqlx::query!("DELETE FROM economic_events WHERE id = $1", id)
+            .execute(&self.pool)
+            .await
+            .map_err(event_store_error)?;
+        Ok(())
+    }
+
+    async fn between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<EconomicEvent>, EventError> {
+        self.fetch(from, Some(to)).await
+    }
+
+    async fn upcoming(&self, since: DateTime<Utc>) -> Result<Vec<EconomicEvent>, EventError> {
+        self.fetch(since, None).await
+    }
+}
+
+/// Repository for perp reconciliation audit rows
+#[derive(Debug)]
+pub struct PerpReconciliationRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PerpReconciliationRepository {
+    /// Creates a new perp reconciliation repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn reconciliation_store_error(e: sqlx::Error) -> ReconcileError {
+    ReconcileError::Store(e.to_string())
+}
+
+#[async_trait]
+impl ReconciliationStore for PerpReconciliationRepository {
+    #[instrument(skip(self, run), fields(run_id = %run.id, markets = run.markets.len()))]
+    async fn save(&self, run: &ReconciliationRun) -> Result<(), ReconcileError> {
+        let mut tx = self.pool.begin().await.map_err(reconciliation_store_error)?;
+        for result in &run.markets {
+            sqlx::query!(
+                "INSERT INTO perp_reconciliations (
+                    run_id, market, reconciled_at, internal_size, venue_size, size_diff,
+                    internal_quote_entry, venue_quote_entry, mark_price,
+                    internal_unrealized_pnl, venue_unrealized_pnl, action
+                 ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)",
+                run.id,
+                result.market,
+                run.reconciled_at,
+                result.internal_size,
+                result.venue_size,
+                result.size_diff,
+                result.internal_quote_entry,
+                result.venue_quote_entry,
+                result.mark_price,
+                result.internal_unrealized_pnl,
+                result.venue_unrealized_pnl,
+                result.action.as_str(),
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(reconciliation_store_error)?;
+        }
+        tx.commit().await.map_err(reconciliation_store_error)?;
+        Ok(())
+    }
+}
+
+/// Repository for the background job queue
+#[derive(Debug)]
+pub struct JobRepository {
+    pool: Pool<Postgres>,
+}
+
+impl JobRepository {
+    /// Creates a new job queue repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn job_store_error(e: sqlx::Error) -> JobError {
+    JobError::Store(e.to_string())
+}
+
+fn queued_job(record: JobRecord) -> Result<QueuedJob, JobError> {
+    Ok(QueuedJob {
+        id: record.id,
+        kind: record.kind,
+        payload: record.payload,
+        status: record.status.parse()?,
+        priority: record.priority,
+        attempts: record.attempts.max(0) as u32,
+        max_attempts: record.max_attempts.max(0) as u32,
+        last_error: record.last_error,
+        locked_by: record.locked_by,
+        locked_until: record.locked_until,
+        run_after: record.run_after,
+        created_at: record.created_at,
+        updated_at: record.updated_at,
+    })
+}
+
+#[async_trait]
+impl JobStore for JobRepository {
+    async fn insert(&self, job: &QueuedJob) -> Result<(), JobError> {
+        sqlx::query!(
+            "INSERT INTO jobs
+                (id, kind, payload, status, priority, attempts, max_attempts, run_after, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+            job.id,
+            job.kind,
+            job.payload,
+            job.status.as_str(),
+            job.priority,
+            job.attempts as i32,
+            job.max_attempts as i32,
+            job.run_after,
+            job.created_at,
+            job.updated_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(job_store_error)?;
+        Ok(())
+    }
+
+    async fn claim(
+        &self,
+        worker: &str,
+        kinds: &[String],
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Option<QueuedJob>, JobError> {
+        // SKIP LOCKED lets concurrent workers each take a different row instead of queueing
+        // on the same one
+        let record = sqlx::query_as!(
+            JobRecord,
+            "UPDATE jobs
+             SET status = 'running', attempts = attempts + 1, locked_by = $1, locked_until = $4, updated_at = $3
+             WHERE id = (
+                 SELECT id FROM jobs
+                 WHERE kind = ANY($2)
+                   AND ((status = 'pending' AND run_after <= $3)
+                        OR (status = 'running' AND locked_until < $3))
+                 ORDER BY priority DESC, run_after
+                 LIMIT 1
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING id, kind, payload, status, priority, attempts, max_attempts, last_error,
+                       locked_by, locked_until, run_after, created_at, updated_at",
+            worker,
+            kinds,
+            now,
+            lease_until,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(job_store_error)?;
+        record.map(queued_job).transpose()
+    }
+
+    async fn renew(&self, id: Uuid, worker: &str, lease_until: DateTime<Utc>) -> Result<bool, JobError> {
+        let result = sqlx::query!(
+            "UPDATE jobs SET locked_until = $3
+             WHERE id = $1 AND status = 'running' AND locked_by = $2",
+            id,
+            worker,
+            lease_until,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(job_store_error)?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn release(&self, job: &QueuedJob, worker: &str) -> Result<bool, JobError> {
+        let result = sqlx::query!(
+            "UPDATE jobs
+             SET status = $3, last_error = $4, run_after = $5, updated_at = $6,
+                 locked_by = NULL, locked_until = NULL
+             WHERE id = $1 AND status = 'running' AND locked_by = $2",
+            job.id,
+            worker,
+            job.status.as_str(),
+            job.last_error,
+            job.run_after,
+            job.updated_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(job_store_error)?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn cancel(&self, id: Uuid, now: DateTime<Utc>) -> Result<Option<QueuedJob>, JobError> {
+        let record = sqlx::query_as!(
+            JobRecord,
+            "UPDATE jobs SET status = 'cancelled', updated_at = $2
+             WHERE id = $1 AND status = 'pending'
+             RETURNING id, kind, payload, status, priority, attempts, max_attempts, last_error,
+                       locked_by, locked_until, run_after, created_at, updated_at",
+            id,
+            now,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(job_store_error)?;
+        record.map(queued_job).transpose()
+    }
+
+    async fn requeue(&self, id: Uuid, now: DateTime<Utc>) -> Result<Option<QueuedJob>, JobError> {
+        let record = sqlx::query_as!(
+            JobRecord,
+            "UPDATE jobs SET status = 'pending', attempts = 0, run_after = $2, updated_at = $2
+             WHERE id = $1 AND status IN ('dead_letter', 'cancelled')
+             RETURNING id, kind, payload, status, priority, attempts, max_attempts, last_error,
+                       locked_by, locked_until, run_after, created_at, updated_at",
+            id,
+            now,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(job_store_error)?;
+        record.map(queued_job).transpose()
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<QueuedJob>, JobError> {
+        let record = sqlx::query_as!(
+            JobRecord,
+            "SELECT id, kind, payload, status, priority, attempts, max_attempts, last_error,
+                    locked_by, locked_until, run_after, created_at, updated_at
+             FROM jobs WHERE id = $1",
+            id,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(job_store_error)?;
+        record.map(queued_job).transpose()
+    }
+
+    async fn list(&self, filter: &JobFilter, limit: usize) -> Result<Vec<QueuedJob>, JobError> {
+        let records = sqlx::query_as!(
+            JobRecord,
+            "SELECT id, kind, payload, status, priority, attempts, max_attempts, last_error,
+                    locked_by, locked_until, run_after, created_at, updated_at
+             FROM jobs
+             WHERE ($1::TEXT IS NULL OR status = $1) AND ($2::TEXT IS NULL OR kind = $2)
+             ORDER BY updated_at DESC
+             LIMIT $3",
+            filter.status.map(|status| status.as_str()),
+            filter.kind,
+            limit as i64,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(job_store_error)?;
+        records.into_iter().map(queued_job).collect()
+    }
+}
+
+/// Repository for the P&L attribution ledger, wallet balances and daily rollups
+#[derive(Debug)]
+pub struct AttributionRepository {
+    pool: Pool<Postgres>,
+}
+
+impl AttributionRepository {
+    /// Creates a new attribution repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn attribution_store_error(e: sqlx::Error) -> AttributionError {
+    AttributionError::Store(e.to_string())
+}
+
+fn ledger_entry(record: AttributionEntryRecord) -> Result<LedgerEntry, AttributionError> {
+    Ok(LedgerEntry {
+        id: record.id,
+        kind: record.kind.parse()?,
+        tags: AttributionTags {
+            wallet: record.wallet,
+            strategy_id: record.strategy_id,
+            version: record.version.map(|version| version.max(0) as u32),
+            venue: record.venue,
+            trading_pair: record.trading_pair,
+        },
+        amount: record.amount,
+        inventory: record.inventory,
+        occurred_at: record.occurred_at,
+    })
+}
+
+fn attribution_period(record: AttributionPeriodRecord) -> AttributionPeriod {
+    AttributionPeriod {
+        period_start: record.period_start,
+        wallet: record.wallet,
+        strategy_id: record.strategy_id,
+        version: (record.version > 0).then_some(record.version as u32),
+        venue: record.venue,
+        trading_pair: record.trading_pair,
+        realized_pnl: record.realized_pnl,
+        fees: record.fees,
+        funding: record.funding,
+        net_pnl: record.net_pnl,
+        trade_count: record.trade_count.max(0) as u64,
+    }
+}
+
+#[async_trait]
+impl AttributionStore for AttributionRepository {
+    async fn record_entry(&self, entry: &LedgerEntry) -> Result<(), AttributionError> {
+        sqlx::query!(
+            "INSERT INTO attribution_entries
+                (id, kind, wallet, strategy_id, version, venue, trading_pair, amount, inventory, occurred_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             ON CONFLICT (id) DO NOTHING",
+            entry.id,
+            entry.kind.as_str(),
+            entry.tags.wallet,
+            entry.tags.strategy_id,
+            entry.tags.version.map(|version| version as i32),
+            entry.tags.venue,
+            entry.tags.trading_pair,
+            entry.amount,
+            entry.inventory,
+            entry.occurred_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(attribution_store_error)?;
+        Ok(())
+    }
+
+    async fn record_balance(
+        &self,
+        wallet: &str,
+        balance: Decimal,
+        observed_at: DateTime<Utc>,
+    ) -> Result<(), AttributionError> {
+        sqlx::query!(
+            "INSERT INTO attribution_balances (wallet, observed_at, balance)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (wallet, observed_at) DO UPDATE SET balance = EXCLUDED.balance",
+            wallet,
+            observed_at,
+            balance,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(attribution_store_error)?;
+        Ok(())
+    }
+
+    async fn balance_at(
+        &self,
+        wallet: &str,
+        at: DateTime<Utc>,
+    ) -> Result<Option<(Decimal, DateTime<Utc>)>, AttributionError> {
+        let row = sqlx::query!(
+            "SELECT balance, observed_at FROM attribution_balances
+             WHERE wallet = $1 AND observed_at <= $2
+             ORDER BY observed_at DESC
+             LIMIT 1",
+            wallet,
+            at,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(attribution_store_error)?;
+        Ok(row.map(|row| (row.balance, row.observed_at)))
+    }
+
+    async fn wallets(&self) -> Result<Vec<String>, AttributionError> {
+        let rows = sqlx::query!("SELECT DISTINCT wallet FROM attribution_balances ORDER BY wallet")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(attribution_store_error)?;
+        Ok(rows.into_iter().map(|row| row.wallet).collect())
+    }
+
+    async fn entries(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<LedgerEntry>, AttributionError> {
+        let records = sqlx::query_as!(
+            AttributionEntryRecord,
+            "SELECT id, kind, wallet, strategy_id, version, venue, trading_pair, amount, inventory, occurred_at
+             FROM attribution_entries
+             WHERE occurred_at >= $1 AND occurred_at < $2
+             ORDER BY occurred_at",
+            from,
+            to,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(attribution_store_error)?;
+        records.into_iter().map(ledger_entry).collect()
+    }
+
+    async fn replace_periods(
+        &self,
+        period_start: DateTime<Utc>,
+        periods: &[AttributionPeriod],
+    ) -> Result<(), AttributionError> {
+        let mut tx = self.pool.begin().await.map_err(attribution_store_error)?;
+        sqlx::query!("DELETE FROM attribution_periods WHERE period_start = $1", period_start)
+            .execute(&mut *tx)
+            .await
+            .map_err(attribution_store_error)?;
+        for period in periods {
+            sqlx::query!(
+                "INSERT INTO attribution_periods
+                    (period_start, wallet, strategy_id, version, venue, trading_pair,
+                     realized_pnl, fees, funding, net_pnl, trade_count)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+                period_start,
+                period.wallet,
+                period.strategy_id,
+                period.version.map_or(0, |version| version as i32),
+                period.venue,
+                period.trading_pair,
+                period.realized_pnl,
+                period.fees,
+                period.funding,
+                period.net_pnl,
+                period.trade_count as i64,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(attribution_store_error)?;
+        }
+        tx.commit().await.map_err(attribution_store_error)
+    }
+
+    async fn periods(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<AttributionPeriod>, AttributionError> {
+        let records = sqlx::query_as!(
+            AttributionPeriodRecord,
+            "SELECT period_start, wallet, strategy_id, version, venue, trading_pair,
+                    realized_pnl, fees, funding, net_pnl, trade_count
+             FROM attribution_periods
+             WHERE period_start >= $1 AND period_start < $2
+             ORDER BY period_start, wallet, strategy_id, version, venue, trading_pair",
+            from,
+            to,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(attribution_store_error)?;
+        Ok(records.into_iter().map(attribution_period).collect())
+    }
+}
+
+/// Repository for daily reports and the analytics they are assembled from
+#[derive(Debug)]
+pub struct DailyReportRepository {
+    pool: Pool<Postgres>,
+}
+
+impl DailyReportRepository {
+    /// Creates a new daily report repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn report_store_error(e: impl std::fmt::Display) -> ReportError {
+    ReportError::Store(e.to_string())
+}
+
+#[async_trait]
+impl ReportSource for DailyReportRepository {
+    async fn ledger(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<LedgerEntry>, ReportError> {
+        let records = sqlx::query_as!(
+            AttributionEntryRecord,
+            "SELECT id, kind, wallet, strategy_id, version, venue, trading_pair, amount, inventory, occurred_at
+             FROM attribution_entries
+             WHERE occurred_at >= $1 AND occurred_at < $2
+             ORDER BY occurred_at",
+            from,
+            to,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(report_store_error)?;
+        records
+            .into_iter()
+            .map(|record| ledger_entry(record).map_err(report_store_error))
+            .collect()
+    }
+
+    async fn closed_trades(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<StrategyTrade>, ReportError> {
+        let rows = sqlx::query!(
+            "SELECT id, strategy_id, trading_pair, realized_pnl, closed_at, version
+             FROM strategy_trades
+             WHERE closed_at >= $1 AND closed_at < $2
+             ORDER BY closed_at",
+            from,
+            to,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(report_store_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| StrategyTrade {
+                id: row.id,
+                strategy_id: row.strategy_id,
+                trading_pair: row.trading_pair,
+                realized_pnl: row.realized_pnl,
+                closed_at: row.closed_at,
+                version: row.version.map(|version| version.max(0) as u32),
+            })
+            .collect())
+    }
+
+    async fn risk_snapshots(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<RiskFactorSnapshot>, ReportError> {
+        let rows = sqlx::query!(
+            "SELECT payload FROM risk_snapshots
+             WHERE computed_at >= $1 AND computed_at < $2
+             ORDER BY computed_at",
+            from,
+            to,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(report_store_error)?;
+
+        rows.into_iter()
+            .map(|row| serde_json::from_value(row.payload).map_err(report_store_error))
+            .collect()
+    }
+
+    async fn incidents(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<Incident>, ReportError> {
+        // Quarantines raised during the day, whether still active or since released
+        let active = sqlx::query!(
+            "SELECT trading_pair, mode, reason, quarantined_at
+             FROM pair_quarantines
+             WHERE quarantined_at >= $1 AND quarantined_at < $2",
+            from,
+            to,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(report_store_error)?;
+        let released = sqlx::query!(
+            "SELECT trading_pair, mode, quarantine_reason, quarantined_at
+             FROM pair_quarantine_releases
+             WHERE quarantined_at >= $1 AND quarantined_at < $2",
+            from,
+            to,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(report_store_error)?;
+        let reconciliations = sqlx::query!(
+            "SELECT market, reconciled_at, size_diff, action
+             FROM perp_reconciliations
+             WHERE reconciled_at >= $1 AND reconciled_at < $2 AND action <> 'matched'",
+            from,
+            to,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(report_store_error)?;
+
+        let quarantine = |trading_pair: String, mode: String, reason: Option<String>, at: DateTime<Utc>| Incident {
+            kind: IncidentKind::Quarantine,
+            subject: trading_pair,
+            detail: match reason {
+                Some(reason) => format!("{}: {}", mode, reason),
+                None => mode,
+            },
+            occurred_at: at,
+        };
+        let mut incidents: Vec<Incident> = active
+            .into_iter()
+            .map(|row| quarantine(row.trading_pair, row.mode, row.reason, row.quarantined_at))
+            .chain(
+                released
+                    .into_iter()
+                    .map(|row| quarantine(row.trading_pair, row.mode, row.quarantine_reason, row.quarantined_at)),
+            )
+            .collect();
+        incidents.extend(reconciliations.into_iter().map(|row| Incident {
+            kind: IncidentKind::Reconciliation,
+            subject: row.market,
+            detail: format!("{}: size differs by {}", row.action, row.size_diff),
+            occurred_at: row.reconciled_at,
+        }));
+        incidents.sort_by_key(|incident| incident.occurred_at);
+        Ok(incidents)
+    }
+}
+
+#[async_trait]
+impl ReportStore for DailyReportRepository {
+    async fn save(&self, report: &DailyReport) -> Result<(), ReportError> {
+        let payload = serde_json::to_value(report).map_err(report_store_error)?;
+        sqlx::query!(
+            "INSERT INTO reports
+                (kind, report_date, period_start, period_end, net_pnl, payload, generated_at)
+             VALUES ('daily', $1, $2, $3, $4, $5, $6)
+             ON CONFLICT (kind, report_date) DO UPDATE SET
+                period_start = EXCLUDED.period_start,
+                period_end = EXCLUDED.period_end,
+                net_pnl = EXCLUDED.net_pnl,
+                payload = EXCLUDED.payload,
+                generated_at = EXCLUDED.generated_at",
+            report.date,
+            report.period_start,
+            report.period_end,
+            report.total.net_pnl,
+            payload,
+            report.generated_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(report_store_error)?;
+        Ok(())
+    }
+
+    async fn get(&self, date: NaiveDate) -> Result<Option<DailyReport>, ReportError> {
+        let row = sqlx::query!(
+            "SELECT payload, delivered_at FROM reports WHERE kind = 'daily' AND report_date = $1",
+            date,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(report_store_error)?;
+
+        row.map(|row| {
+            let mut report: DailyReport = serde_json::from_value(row.payload).map_err(report_store_error)?;
+            report.delivered_at = row.delivered_at;
+            Ok(report)
+        })
+        .transpose()
+    }
+
+    async fn mark_delivered(&self, date: NaiveDate, at: DateTime<Utc>) -> Result<bool, ReportError> {
+        let result = sqlx::query!(
+            "UPDATE reports SET delivered_at = $2
+             WHERE kind = 'daily' AND report_date = $1 AND delivered_at IS NULL",
+            date,
+            at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(report_store_error)?;
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+/// Repository for market regime changes
+#[derive(Debug)]
+pub struct RegimeRepository {
+    pool: Pool<Postgres>,
+}
+
+impl RegimeRepository {
+    /// Creates a new regime repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn regime_store_error(e: sqlx::Error) -> RegimeError {
+    RegimeError::Store(e.to_string())
+}
+
+fn regime_change(record: RegimeChangeRecord) -> Result<RegimeChange, RegimeError> {
+    Ok(RegimeChange {
+        id: record.id,
+        trading_pair: record.trading_pair,
+        previous: record.previous_regime.map(|regime| regime.parse()).transpose()?,
+        regime: record.regime.parse()?,
+        features: RegimeFeatures {
+            volatility: record.volatility,
+            r_squared: record.r_squared,
+            slope: record.slope,
+            volume_ratio: record.volume_ratio,
+        },
+        changed_at: record.changed_at,
+    })
+}
+
+#[async_trait]
+impl RegimeStore for RegimeRepository {
+    async fn record_change(&self, change: &RegimeChange) -> Result<(), RegimeError> {
+        sqlx::query!(
+            "INSERT INTO market_regimes
+                (id, trading_pair, previous_regime, regime, volatility, r_squared, slope, volume_ratio, changed_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             ON CONFLICT (id) DO NOTHING",
+            change.id,
+            change.trading_pair,
+            change.previous.map(|regime| regime.as_str()),
+            change.regime.as_str(),
+            change.features.volatility,
+            change.features.r_squared,
+            change.features.slope,
+            change.features.volume_ratio,
+            change.changed_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(regime_store_error)?;
+        Ok(())
+    }
+
+    async fn latest(&self) -> Result<Vec<RegimeChange>, RegimeError> {
+        let records = sqlx::query_as!(
+            RegimeChangeRecord,
+            "SELECT DISTINCT ON (trading_pair)
+                    id, trading_pair, previous_regime, regime, volatility, r_squared, slope,
+                    volume_ratio, changed_at
+             FROM market_regimes
+             ORDER BY trading_pair, changed_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(regime_store_error)?;
+        records.into_iter().map(regime_change).collect()
+    }
+}
+
+/// Repository for per-minute order book microstructure aggregates
+#[derive(Debug)]
+pub struct MicrostructureRepository {
+    pool: Pool<Postgres>,
+}
+
+impl MicrostructureRepository {
+    /// Creates a new microstructure repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn microstructure_store_error(e: impl std::fmt::Display) -> MicrostructureError {
+    MicrostructureError::Store(e.to_string())
+}
+
+#[async_trait]
+impl MicrostructureStore for MicrostructureRepository {
+    #[instrument(skip(self, aggregates), fields(rows = aggregates.len()))]
+    async fn record_aggregates(&self, aggregates: &[MicrostructureAggregate]) -> Result<(), MicrostructureError> {
+        let mut tx = self.pool.begin().await.map_err(microstructure_store_error)?;
+        for aggregate in aggregates {
+            let depth = aggregate
+                .mean_depth
+                .as_ref()
+                .map(serde_json::to_value)
+                .transpose()
+                .map_err(microstructure_store_error)?;
+            sqlx::query!(
+                "INSERT INTO microstructure_aggregates
+                    (trading_pair, bucket_start, exchange, updates, flagged, mean_imbalance, mean_depth,
+                     mean_spread_bps, mean_effective_spread_bps, mean_quote_rate)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                 ON CONFLICT (trading_pair, bucket_start) DO UPDATE SET
+                    exchange = EXCLUDED.exchange,
+                    updates = EXCLUDED.updates,
+                    flagged = EXCLUDED.flagged,
+                    mean_imbalance = EXCLUDED.mean_imbalance,
+                    mean_depth = EXCLUDED.mean_depth,
+                    mean_spread_bps = EXCLUDED.mean_spread_bps,
+                    mean_effective_spread_bps = EXCLUDED.mean_effective_spread_bps,
+                    mean_quote_rate = EXCLUDED.mean_quote_rate",
+                aggregate.trading_pair,
+                aggregate.bucket_start,
+                aggregate.exchange,
+                aggregate.updates as i32,
+                aggregate.flagged as i32,
+                aggregate.mean_imbalance,
+                depth,
+                aggregate.mean_spread_bps,
+                aggregate.mean_effective_spread_bps,
+                aggregate.mean_quote_rate,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(microstructure_store_error)?;
+        }
+        tx.commit().await.map_err(microstructure_store_error)?;
+        Ok(())
+    }
+}
+
+/// Repository for calibrated execution cost models
+#[derive(Debug)]
+pub struct CostModelRepository {
+    pool: Pool<Postgres>,
+}
+
+impl CostModelRepository {
+    /// Creates a new cost model repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn cost_model_store_error(e: sqlx::Error) -> CostModelError {
+    CostModelError::Store(e.to_string())
+}
+
+fn cost_model(record: CostModelRecord) -> CostModel {
+    CostModel {
+        id: record.id,
+        exchange: record.exchange,
+        intercept_bps: record.intercept_bps,
+        depth_coefficient: record.depth_coefficient,
+        volatility_coefficient: record.volatility_coefficient,
+        sample_count: record.sample_count.max(0) as u64,
+        r_squared: record.r_squared,
+        valid_from: record.valid_from,
+        valid_until: record.valid_until,
+        calibrated_at: record.calibrated_at,
+    }
+}
+
+#[async_trait]
+impl CostModelStore for CostModelRepository {
+    #[instrument(skip(self, model), fields(model_id = %model.id, exchange = %model.exchange))]
+    async fn save(&self, model: &CostModel) -> Result<(), CostModelError> {
+        sqlx::query!(
+            "INSERT INTO cost_models
+                (id, exchange, intercept_bps, depth_coefficient, volatility_coefficient, sample_count,
+                 r_squared, valid_from, valid_until, calibrated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+            model.id,
+            model.exchange,
+            model.intercept_bps,
+            model.depth_coefficient,
+            model.volatility_coefficient,
+            model.sample_count as i64,
+            model.r_squared,
+            model.valid_from,
+            model.valid_until,
+            model.calibrated_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(cost_model_store_error)?;
+        Ok(())
+    }
+
+    async fn current(&self, at: DateTime<Utc>) -> Result<Vec<CostModel>, CostModelError> {
+        let records = sqlx::query_as!(
+            CostModelRecord,
+            "SELECT DISTINCT ON (exchange)
+                    id, exchange, intercept_bps, depth_coefficient, volatility_coefficient, sample_count,
+                    r_squared, valid_from, valid_until, calibrated_at
+             FROM cost_models
+             WHERE valid_from <= $1 AND valid_until > $1
+             ORDER BY exchange, calibrated_at DESC",
+            at,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(cost_model_store_error)?;
+        Ok(records.into_iter().map(cost_model).collect())
+    }
+
+    async fn history(&self, exchange: Option<&str>, limit: usize) -> Result<Vec<CostModel>, CostModelError> {
+        let records = sqlx::query_as!(
+            CostModelRecord,
+            "SELECT id, exchange, intercept_bps, depth_coefficient, volatility_coefficient, sample_count,
+                    r_squared, valid_from, valid_until, calibrated_at
+             FROM cost_models
+             WHERE $1::TEXT IS NULL OR exchange = $1
+             ORDER BY calibrated_at DESC
+             LIMIT $2",
+            exchange,
+            limit as i64,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(cost_model_store_error)?;
+        Ok(records.into_iter().map(cost_model).collect())
+    }
+}
+
+/// Repository for public trade prints collected from venue trade feeds
+#[derive(Debug)]
+pub struct PublicTradeRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PublicTradeRepository {
+    /// Creates a new public trade repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn public_trade_store_error(e: sqlx::Error) -> TradeStreamError {
+    TradeStreamError::Store(e.to_string())
+}
+
+#[async_trait]
+impl PublicTradeStore for PublicTradeRepository {
+    #[instrument(skip(self, trades), fields(rows = trades.len()))]
+    async fn insert_trades(&self, trades: &[PublicTrade]) -> Result<u64, TradeStreamError> {
+        let mut tx = self.pool.begin().await.map_err(public_trade_store_error)?;
+        let mut written = 0;
+        for trade in trades {
+            let side = match trade.side {
+                TradeSide::Buy => "buy",
+                TradeSide::Sell => "sell",
+            };
+            // Prints stored before a restart arrive again when venues resend recent trades
+            written += sqlx::query!(
+                "INSERT INTO public_trades
+                    (trade_key, venue_trade_id, trading_pair, exchange, price, size, side, timestamp)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                 ON CONFLICT (trade_key, timestamp) DO NOTHING",
+                trade.dedup_key(),
+                trade.trade_id,
+                trade.pair,
+                trade.exchange,
+                trade.price,
+                trade.size,
+                side,
+                trade.timestamp,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(public_trade_store_error)?
+            .rows_affected();
+        }
+        tx.commit().await.map_err(public_trade_store_error)?;
+        Ok(written)
+    }
+}
+
+fn sweep_store_error(e: sqlx::Error) -> SweepError {
+    SweepError::Store(e.to_string())
+}
+
+#[async_trait]
+impl SweepStore for TransferRepository {
+    #[instrument(skip(self, transfer))]
+    async fn record_sweep(&self, transfer: &Transfer, destination: &str) -> Result<(), SweepError> {
+        sqlx::query!(
+            "INSERT INTO transfers
+                (id, wallet_address, direction, amount, signature, detected_at, created_at, destination)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             ON CONFLICT (signature) DO UPDATE SET destination = EXCLUDED.destination",
+            transfer.id,
+            transfer.wallet_address,
+            transfer.direction.as_str(),
+            transfer.amount,
+            transfer.signature,
+            transfer.detected_at,
+            current_timestamp(),
+            destination,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(sweep_store_error)?;
+        counter!("transfers_recorded", 1);
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn swept_since(&self, wallet_address: &str, since: DateTime<Utc>) -> Result<Decimal, SweepError> {
+        let swept = sqlx::query_scalar!(
+            "SELECT COALESCE(SUM(amount), 0) AS \"swept!\"
+             FROM transfers
+             WHERE wallet_address = $1 AND destination IS NOT NULL AND detected_at >= $2",
+            wallet_address,
+            since,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(sweep_store_error)?;
+        Ok(swept)
+    }
+}
+
+/// Repository for submission intents persisted ahead of order submission
+#[derive(Debug)]
+pub struct SubmissionIntentRepository {
+    pool: Pool<Postgres>,
+}
+
+impl SubmissionIntentRepository {
+    /// Creates a new submission intent repository instance
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn intent_store_error(e: sqlx::Error) -> RecoveryError {
+    RecoveryError::Store(e.to_string())
+}
+
+#[async_trait]
+impl IntentStore for SubmissionIntentRepository {
+    #[instrument(skip(self, intent), fields(intent_id = %intent.id))]
+    async fn open(&self, intent: &SubmissionIntent) -> Result<(), RecoveryError> {
+        let side = match intent.side {
+            TradeSide::Buy => "buy",
+            TradeSide::Sell => "sell",
+        };
+        sqlx::query!(
+            "INSERT INTO submission_intents
+                (id, strategy_id, trading_pair, exchange, side, size, price, signatures, status, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+            intent.id,
+            intent.strategy_id,
+            intent.trading_pair,
+            intent.exchange,
+            side,
+            intent.size,
+            intent.price,
+            &intent.signatures,
+            intent.status.as_str(),
+            intent.created_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(intent_store_error)?;
+        Ok(())
+    }
+
+    async fn attach_signature(&self, id: Uuid, signature: &str) -> Result<(), RecoveryError> {
+        sqlx::query!(
+            "UPDATE submission_intents SET signatures = array_append(signatures, $2) WHERE id = $1",
+            id,
+            signature,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(intent_store_error)?;
+        Ok(())
+    }
+
+    async fn attach_bundle(&self, id: Uuid, bundle_id: &str) -> Result<(), RecoveryError> {
+        sqlx::query!(
+            "UPDATE submission_intents SET bundle_id = $2 WHERE id = $1",
+            id,
+            bundle_id,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(intent_store_error)?;
+        Ok(())
+    }
+
+    async fn resolve_signature(&self, signature: &str, at: DateTime<Utc>) -> Result<bool, RecoveryError> {
+        let resolved = sqlx::query!(
+            "UPDATE submission_intents SET status = 'resolved', closed_at = $2
+             WHERE status = 'pending' AND $1 = ANY(signatures)",
+            signature,
+            at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(intent_store_error)?
+        .rows_affected();
+        Ok(resolved > 0)
+    }
+
+    #[instrument(skip(self, outcome))]
+    async fn close(&self, id: Uuid, outcome: &IntentOutcome, at: DateTime<Utc>) -> Result<(), RecoveryError> {
+        let (fill_signature, fill_size, fill_price, detail) = match outcome {
+            IntentOutcome::Recovered { signature, size, price } => {
+                (Some(signature.as_str()), Some(*size), Some(*price), None)
+            }
+            IntentOutcome::Failed { reason } => (None, None, None, Some(reason.as_str())),
+        };
+        sqlx::query!(
+            "UPDATE submission_intents
+             SET status = $2, fill_signature = $3, fill_size = $4, fill_price = $5, detail = $6, closed_at = $7
+             WHERE id = $1 AND status = 'pending'",
+            id,
+            outcome.status().as_str(),
+            fill_signature,
+            fill_size,
+            fill_price,
+            detail,
+            at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(intent_store_error)?;
+        Ok(())
+    }
+
+    async fn pending(&self) -> Result<Vec<SubmissionIntent>, RecoveryError> {
+        let rows = sqlx::query!(
+            "SELECT id, strategy_id, trading_pair, exchange, side, size, price, signatures, bundle_id,
+                    status, created_at, closed_at
+             FROM submission_intents
+             WHERE status = 'pending'
+             ORDER BY created_at",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(intent_store_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                let side = match row.side.as_str() {
+                    "buy" => TradeSide::Buy,
+                    "sell" => TradeSide::Sell,
+                    other => return Err(RecoveryError::Store(format!("unknown trade side: {}", other))),
+                };
+                Ok(SubmissionIntent {
+                    id: row.id,
+                    strategy_id: row.strategy_id,
+                    trading_pair: row.trading_pair,
+                    exchange: row.exchange,
+                    side,
+                    size: row.size,
+                    price: row.price,
+                    signatures: row.signatures,
+                    bundle_id: row.bundle_id,
+                    status: row.status.parse()?,
+                    created_at: row.created_at,
+                    closed_at: row.closed_at,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Breaker suspending writes after repeated database failures
+fn db_breaker(name: &str) -> Arc<CircuitBreaker> {
+    CircuitBreaker::new(
+        name,
+        BreakerConfig::consecutive_failures(CIRCUIT_BREAKER_FAILURES).with_cooldown(CIRCUIT_BREAKER_COOLDOWN),
+    )
+    .registered()
+}
+
+/// Applies injected query latency or failures; a no-op without fault injection
+async fn db_fault() -> Result<(), String> {
+    #[cfg(feature = "fault-injection")]
+    {
+        use crate::fault_injection::{self, FaultComponent};
+        fault_injection::on_call(FaultComponent::Database)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Executes database operations with retry logic and circuit breaker
+#[instrument(skip(pool, operation))]
+async fn execute_with_retry<F, T, E>(
+    pool: &Pool<Postgres>,
+    operation: F,
+    policy: RetryPolicy,
+) -> Result<T, RepositoryError>
+where
+    F: Fn(Transaction<'_, Postgres>) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<T, E>> + Send>>,
+    E: std::error::Error,
+{
+    let mut attempt = 0;
+    let mut last_error = None;
+
+    while attempt < policy.max_attempts {
+        if attempt > 0 {
+            let delay = std::cmp::min(
+                policy.base_delay_ms * 2u64.pow(attempt),
+                policy.max_delay_ms,
+            );
+            sleep(Duration::from_millis(delay)).await;
+        }
+
+        if let Err(e) = db_fault().await {
+            error!("Injected database fault: {}", e);
+            attempt += 1;
+            last_error = Some(RepositoryError::DatabaseError(e));
+            continue;
         }
 
         let tx = match pool.begin().await {
@@ -342,12 +3701,13 @@ mod tests {
 
     #[test]
     fn test_circuit_breaker() {
-        let breaker = CircuitBreaker::new();
+        let breaker = db_breaker("test_repository");
         assert!(!breaker.is_open());
 
-        for _ in 0..breaker.threshold {
+        for _ in 0..CIRCUIT_BREAKER_FAILURES {
             breaker.record_failure();
         }
         assert!(breaker.is_open());
+        assert!(!breaker.allow());
     }
 }
\ No newline at end of file
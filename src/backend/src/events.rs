@@ -0,0 +1,600 @@
+//! Economic event calendar and the event-risk guard. Scheduled releases (rate decisions,
+//! CPI prints, token unlocks) are loaded by admins or imported from CSV, and for a window
+//! around each event at or above the configured impact the risk manager tightens limits:
+//! order notional is capped lower, arbitrage needs a wider edge and selected strategies are
+//! paused. Executions inside a window are annotated with the event that opened it.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - metrics = "0.20"
+//! - parking_lot = "0.12"
+//! - utoipa = "3.5"
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::RwLock as SyncRwLock;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::utils::percent::Bps;
+use crate::utils::time::to_chrono;
+
+// Event constants
+const METRICS_PREFIX: &str = "trading_bot.events";
+const DEFAULT_WINDOW_BEFORE: Duration = Duration::from_secs(30 * 60);
+const DEFAULT_WINDOW_AFTER: Duration = Duration::from_secs(60 * 60);
+const DEFAULT_MAX_ORDER_NOTIONAL: Decimal = Decimal::from_parts(2_500, 0, 0, false, 0);
+const DEFAULT_ARBITRAGE_EDGE: Bps = Bps::new(50);
+pub const UPCOMING_HORIZON: Duration = Duration::from_secs(24 * 3600);
+const CSV_HEADER: &str = "timestamp,name,impact";
+const MAX_NAME_LEN: usize = 200;
+
+/// Event calendar errors
+#[derive(Error, Debug)]
+pub enum EventError {
+    #[error("invalid event: {0}")]
+    InvalidEvent(String),
+    #[error("event already scheduled: {name} at {timestamp}")]
+    Duplicate { name: String, timestamp: DateTime<Utc> },
+    #[error("line {line}: {reason}")]
+    Import { line: usize, reason: String },
+    #[error("event not found: {0}")]
+    NotFound(Uuid),
+    #[error("{name} event window: {reason}")]
+    Restricted { name: String, reason: String },
+    #[error("store error: {0}")]
+    Store(String),
+}
+
+/// Expected market impact of an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImpactLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl ImpactLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+impl std::fmt::Display for ImpactLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ImpactLevel {
+    type Err = EventError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "low" => Ok(Self::Low),
+            "medium" => Ok(Self::Medium),
+            "high" => Ok(Self::High),
+            other => Err(EventError::InvalidEvent(format!("unknown impact level: {}", other))),
+        }
+    }
+}
+
+/// Admin request adding an event to the calendar
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct EventRequest {
+    pub timestamp: DateTime<Utc>,
+    pub name: String,
+    pub impact: ImpactLevel,
+}
+
+/// Scheduled economic event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct EconomicEvent {
+    pub id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub name: String,
+    pub impact: ImpactLevel,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Period around an event during which tightened limits apply
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EventWindow {
+    pub event: EconomicEvent,
+    /// First instant limits are tightened
+    pub starts_at: DateTime<Utc>,
+    /// First instant normal limits apply again
+    pub ends_at: DateTime<Utc>,
+}
+
+impl EventWindow {
+    pub fn contains(&self, now: DateTime<Utc>) -> bool {
+        self.starts_at <= now && now < self.ends_at
+    }
+}
+
+/// Window size and the limits tightened inside it
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventGuardConfig {
+    pub window_before: Duration,
+    pub window_after: Duration,
+    /// Events below this impact don't open a window
+    pub min_impact: ImpactLevel,
+    /// Largest order notional accepted inside a window, in USDC
+    pub max_order_notional: Decimal,
+    /// Smallest cross-venue edge arbitrage orders need inside a window
+    pub arbitrage_edge: Bps,
+    /// Strategies refused for the duration of a window
+    pub paused_strategies: Vec<String>,
+}
+
+impl Default for EventGuardConfig {
+    fn default() -> Self {
+        Self {
+            window_before: DEFAULT_WINDOW_BEFORE,
+            window_after: DEFAULT_WINDOW_AFTER,
+            min_impact: ImpactLevel::High,
+            max_order_notional: DEFAULT_MAX_ORDER_NOTIONAL,
+            arbitrage_edge: DEFAULT_ARBITRAGE_EDGE,
+            paused_strategies: Vec::new(),
+        }
+    }
+}
+
+/// Persistence for the event calendar
+#[async_trait]
+pub trait EventStore: Send + Sync {
+    /// Saves every event or none of them
+    async fn save(&self, events: &[EconomicEvent]) -> Result<(), EventError>;
+    async fn remove(&self, id: Uuid) -> Result<(), EventError>;
+    /// Events scheduled in `[from, to)`, in time order
+    async fn between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<EconomicEvent>, EventError>;
+    /// Events scheduled at or after `since`, in time order
+    async fn upcoming(&self, since: DateTime<Utc>) -> Result<Vec<EconomicEvent>, EventError>;
+}
+
+/// Scheduled events and the guard windows around them
+pub struct EventCalendar {
+    config: EventGuardConfig,
+    /// Events not yet past their window, in time order
+    events: SyncRwLock<Vec<EconomicEvent>>,
+    store: SyncRwLock<Option<Arc<dyn EventStore>>>,
+}
+
+impl std::fmt::Debug for EventCalendar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventCalendar")
+            .field("config", &self.config)
+            .field("events", &self.events.read().len())
+            .finish()
+    }
+}
+
+impl EventCalendar {
+    pub fn new(config: EventGuardConfig) -> Self {
+        Self {
+            config,
+            events: SyncRwLock::new(Vec::new()),
+            store: SyncRwLock::new(None),
+        }
+    }
+
+    pub fn config(&self) -> &EventGuardConfig {
+        &self.config
+    }
+
+    /// Persists the calendar so it survives a restart
+    pub fn set_store(&self, store: Arc<dyn EventStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    /// Restores events whose window hasn't ended, returning how many were loaded
+    pub async fn load(&self, now: DateTime<Utc>) -> Result<usize, EventError> {
+        let store = self.store.read().clone();
+        let Some(store) = store else {
+            return Ok(0);
+        };
+        let upcoming = store.upcoming(now - to_chrono(self.config.window_after)).await?;
+        let mut events = self.events.write();
+        *events = upcoming;
+        events.sort_by_key(|event| event.timestamp);
+        Ok(events.len())
+    }
+
+    /// Adds one event to the calendar
+    pub async fn add(
+        &self,
+        request: EventRequest,
+        created_by: &str,
+        now: DateTime<Utc>,
+    ) -> Result<EconomicEvent, EventError> {
+        let event = self.build(request, created_by, now)?;
+        self.insert(vec![event.clone()]).await?;
+        info!(
+            event_id = %event.id,
+            name = %event.name,
+            timestamp = %event.timestamp,
+            impact = event.impact.as_str(),
+            created_by,
+            "Economic event added"
+        );
+        Ok(event)
+    }
+
+    /// Imports `timestamp,name,impact` rows with RFC 3339 timestamps under a header line.
+    /// The import is all or nothing: any bad or duplicate row rejects the whole file
+    pub async fn import_csv(
+        &self,
+        text: &str,
+        created_by: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<EconomicEvent>, EventError> {
+        let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+        match lines.next() {
+            Some((_, header)) if header.trim().eq_ignore_ascii_case(CSV_HEADER) => {}
+            Some((index, _)) => {
+                return Err(EventError::Import {
+                    line: index + 1,
+                    reason: format!("expected header \"{}\"", CSV_HEADER),
+                })
+            }
+            None => return Err(EventError::InvalidEvent("empty import".to_string())),
+        }
+
+        let mut imported: Vec<EconomicEvent> = Vec::new();
+        for (index, line) in lines {
+            let import_error = |reason: String| EventError::Import { line: index + 1, reason };
+            let request = parse_row(line).map_err(import_error)?;
+            let event = self
+                .build(request, created_by, now)
+                .map_err(|e| import_error(e.to_string()))?;
+            if imported.iter().any(|other| same_event(other, &event)) {
+                return Err(import_error(format!("duplicate of an earlier row: {}", event.name)));
+            }
+            imported.push(event);
+        }
+        if imported.is_empty() {
+            return Err(EventError::InvalidEvent("import has no rows".to_string()));
+        }
+
+        self.insert(imported.clone()).await?;
+        counter!(format!("{}.imported", METRICS_PREFIX), imported.len() as u64);
+        info!(events = imported.len(), created_by, "Economic events imported");
+        Ok(imported)
+    }
+
+    /// Removes an event from the calendar
+    pub async fn remove(&self, id: Uuid) -> Result<EconomicEvent, EventError> {
+        let event = self
+            .events
+            .read()
+            .iter()
+            .find(|event| event.id == id)
+            .cloned()
+            .ok_or(EventError::NotFound(id))?;
+        let store = self.store.read().clone();
+        if let Some(store) = store {
+            store.remove(id).await?;
+        }
+        self.events.write().retain(|event| event.id != id);
+        info!(event_id = %id, name = %event.name, "Economic event removed");
+        Ok(event)
+    }
+
+    /// Events scheduled in `[from, to)`; past events come from the store when one is set
+    pub async fn between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<EconomicEvent>, EventError> {
+        if from >= to {
+            return Err(EventError::InvalidEvent("from must be before to".to_string()));
+        }
+        let store = self.store.read().clone();
+        if let Some(store) = store {
+            return store.between(from, to).await;
+        }
+        Ok(self
+            .events
+            .read()
+            .iter()
+            .filter(|event| from <= event.timestamp && event.timestamp < to)
+            .cloned()
+            .collect())
+    }
+
+    /// Events at or above the guarded impact scheduled within `horizon` of `now`
+    pub fn upcoming(&self, now: DateTime<Utc>, horizon: Duration) -> Vec<EconomicEvent> {
+        let until = now + to_chrono(horizon);
+        self.events
+            .read()
+            .iter()
+            .filter(|event| event.impact >= self.config.min_impact)
+            .filter(|event| now <= event.timestamp && event.timestamp < until)
+            .cloned()
+            .collect()
+    }
+
+    /// Guard window in force at `now`; with overlapping windows the one opened first applies
+    pub fn active_window(&self, now: DateTime<Utc>) -> Option<EventWindow> {
+        self.events
+            .read()
+            .iter()
+            .filter(|event| event.impact >= self.config.min_impact)
+            .map(|event| self.window(event))
+            .filter(|window| window.contains(now))
+            .min_by_key(|window| window.starts_at)
+    }
+
+    /// Window in force at `now` that pauses the strategy, if any
+    pub fn pauses(&self, strategy_id: &str, now: DateTime<Utc>) -> Option<EventWindow> {
+        if !self.config.paused_strategies.iter().any(|paused| paused == strategy_id) {
+            return None;
+        }
+        self.active_window(now)
+    }
+
+    /// Applies the tightened limits to an order; `arbitrage_edge` is the order's cross-venue
+    /// edge when it is an arbitrage leg. Returns the window the order was checked against
+    pub fn check(
+        &self,
+        strategy_id: &str,
+        notional: Decimal,
+        arbitrage_edge: Option<Bps>,
+        now: DateTime<Utc>,
+    ) -> Result<Option<EventWindow>, EventError> {
+        let Some(window) = self.active_window(now) else {
+            return Ok(None);
+        };
+        let restricted = |reason: String| {
+            counter!(format!("{}.orders_restricted", METRICS_PREFIX), 1);
+            warn!(event_id = %window.event.id, strategy_id, "Order restricted by event window: {}", reason);
+            EventError::Restricted {
+                name: window.event.name.clone(),
+                reason,
+            }
+        };
+
+        if self.config.paused_strategies.iter().any(|paused| paused == strategy_id) {
+            return Err(restricted(format!("strategy {} is paused", strategy_id)));
+        }
+        if notional > self.config.max_order_notional {
+            return Err(restricted(format!(
+                "order notional {} exceeds {}",
+                notional.round_dp(2),
+                self.config.max_order_notional
+            )));
+        }
+        if let Some(edge) = arbitrage_edge {
+            if edge < self.config.arbitrage_edge {
+                return Err(restricted(format!(
+                    "arbitrage edge {} below required {}",
+                    Bps::from_bps(edge.as_bps().round_dp(2)),
+                    self.config.arbitrage_edge
+                )));
+            }
+        }
+        Ok(Some(window))
+    }
+
+    fn window(&self, event: &EconomicEvent) -> EventWindow {
+        EventWindow {
+            event: event.clone(),
+            starts_at: event.timestamp - to_chrono(self.config.window_before),
+            ends_at: event.timestamp + to_chrono(self.config.window_after),
+        }
+    }
+
+    fn build(&self, request: EventRequest, created_by: &str, now: DateTime<Utc>) -> Result<EconomicEvent, EventError> {
+        let name = request.name.trim();
+        if name.is_empty() {
+            return Err(EventError::InvalidEvent("name is required".to_string()));
+        }
+        if name.len() > MAX_NAME_LEN {
+            return Err(EventError::InvalidEvent(format!(
+                "name is longer than {} characters",
+                MAX_NAME_LEN
+            )));
+        }
+        Ok(EconomicEvent {
+            id: Uuid::new_v4(),
+            timestamp: request.timestamp,
+            name: name.to_string(),
+            impact: request.impact,
+            created_by: created_by.to_string(),
+            created_at: now,
+        })
+    }
+
+    /// Saves new events, refusing any that repeat a scheduled one
+    async fn insert(&self, new: Vec<EconomicEvent>) -> Result<(), EventError> {
+        if let Some(event) = new
+            .iter()
+            .find(|event| self.events.read().iter().any(|other| same_event(other, event)))
+        {
+            return Err(EventError::Duplicate {
+                name: event.name.clone(),
+                timestamp: event.timestamp,
+            });
+        }
+        let store = self.store.read().clone();
+        if let Some(store) = store {
+            store.save(&new).await?;
+        }
+        counter!(format!("{}.added", METRICS_PREFIX), new.len() as u64);
+        let mut events = self.events.write();
+        events.extend(new);
+        events.sort_by_key(|event| event.timestamp);
+        Ok(())
+    }
+}
+
+fn parse_row(line: &str) -> Result<EventRequest, String> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    let [timestamp, name, impact] = fields.as_slice() else {
+        return Err(format!("expected 3 fields, found {}", fields.len()));
+    };
+    let timestamp = DateTime::parse_from_rfc3339(timestamp)
+        .map_err(|e| format!("invalid timestamp {}: {}", timestamp, e))?
+        .with_timezone(&Utc);
+    let impact = impact.parse::<ImpactLevel>().map_err(|e| e.to_string())?;
+    Ok(EventRequest {
+        timestamp,
+        name: name.to_string(),
+        impact,
+    })
+}
+
+fn same_event(a: &EconomicEvent, b: &EconomicEvent) -> bool {
+    a.timestamp == b.timestamp && a.name.eq_ignore_ascii_case(&b.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use parking_lot::Mutex as SyncMutex;
+    use rust_decimal_macros::dec;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        events: SyncMutex<Vec<EconomicEvent>>,
+    }
+
+    #[async_trait]
+    impl EventStore for MemoryStore {
+        async fn save(&self, events: &[EconomicEvent]) -> Result<(), EventError> {
+            self.events.lock().extend(events.iter().cloned());
+            Ok(())
+        }
+
+        async fn remove(&self, id: Uuid) -> Result<(), EventError> {
+            self.events.lock().retain(|event| event.id != id);
+            Ok(())
+        }
+
+        async fn between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<EconomicEvent>, EventError> {
+            let mut events: Vec<_> = self
+                .events
+                .lock()
+                .iter()
+                .filter(|event| from <= event.timestamp && event.timestamp < to)
+                .cloned()
+                .collect();
+            events.sort_by_key(|event| event.timestamp);
+            Ok(events)
+        }
+
+        async fn upcoming(&self, since: DateTime<Utc>) -> Result<Vec<EconomicEvent>, EventError> {
+            self.between(since, DateTime::<Utc>::MAX_UTC).await
+        }
+    }
+
+    fn at(minute: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000, 0).unwrap() + chrono::Duration::minutes(minute)
+    }
+
+    fn request(minute: i64, name: &str, impact: ImpactLevel) -> EventRequest {
+        EventRequest {
+            timestamp: at(minute),
+            name: name.to_string(),
+            impact,
+        }
+    }
+
+    fn calendar() -> EventCalendar {
+        EventCalendar::new(EventGuardConfig {
+            paused_strategies: vec!["momentum-1".to_string()],
+            ..EventGuardConfig::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_limits_tighten_exactly_within_window() {
+        let calendar = calendar();
+        calendar.add(request(120, "FOMC", ImpactLevel::High), "ops", at(0)).await.unwrap();
+
+        // Default window opens 30 minutes before and closes 60 minutes after the event
+        for (minute, guarded) in [(89, false), (90, true), (120, true), (179, true), (180, false)] {
+            assert_eq!(calendar.active_window(at(minute)).is_some(), guarded, "minute {}", minute);
+            assert_eq!(
+                calendar.check("grid-1", dec!(5000), None, at(minute)).is_err(),
+                guarded,
+                "minute {}",
+                minute
+            );
+            assert_eq!(calendar.pauses("momentum-1", at(minute)).is_some(), guarded);
+        }
+
+        let window = calendar.check("grid-1", dec!(2500), None, at(90)).unwrap().unwrap();
+        assert_eq!(window.event.name, "FOMC");
+        assert!(calendar.check("grid-1", dec!(2500), Some(Bps::new(20)), at(90)).is_err());
+        assert!(calendar.check("grid-1", dec!(2500), Some(Bps::new(50)), at(90)).is_ok());
+        assert!(calendar.pauses("grid-1", at(90)).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_low_impact_events_open_no_window() {
+        let calendar = calendar();
+        calendar.add(request(60, "PMI", ImpactLevel::Medium), "ops", at(0)).await.unwrap();
+
+        assert!(calendar.active_window(at(60)).is_none());
+        assert!(calendar.check("momentum-1", dec!(1000000), None, at(60)).unwrap().is_none());
+        assert!(calendar.upcoming(at(0), UPCOMING_HORIZON).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_csv_import_is_all_or_nothing() {
+        let calendar = calendar();
+        let bad = "timestamp,name,impact\n\
+                   2023-11-15T13:30:00Z,US CPI,high\n\
+                   2023-11-15T19:00:00Z,FOMC,severe\n";
+        match calendar.import_csv(bad, "ops", at(0)).await {
+            Err(EventError::Import { line, .. }) => assert_eq!(line, 3),
+            other => panic!("expected import error, got {:?}", other),
+        }
+        assert!(calendar.upcoming(at(0), UPCOMING_HORIZON).is_empty());
+
+        let good = "timestamp,name,impact\n\
+                    2023-11-15T13:30:00Z,US CPI,high\n\
+                    2023-11-15T19:00:00Z,FOMC,High\n";
+        let imported = calendar.import_csv(good, "ops", at(0)).await.unwrap();
+        assert_eq!(imported.len(), 2);
+        assert_eq!(calendar.upcoming(at(0), UPCOMING_HORIZON).len(), 2);
+        assert!(matches!(
+            calendar.import_csv(good, "ops", at(0)).await,
+            Err(EventError::Duplicate { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_calendar_restored_from_store() {
+        let store = Arc::new(MemoryStore::default());
+        let calendar = calendar();
+        calendar.set_store(store.clone());
+        let past = calendar.add(request(-600, "GDP", ImpactLevel::High), "ops", at(-700)).await.unwrap();
+        let next = calendar.add(request(60, "NFP", ImpactLevel::High), "ops", at(0)).await.unwrap();
+
+        let restored = calendar();
+        restored.set_store(store);
+        assert_eq!(restored.load(at(0)).await.unwrap(), 1);
+        assert_eq!(restored.upcoming(at(0), UPCOMING_HORIZON), vec![next.clone()]);
+        assert_eq!(restored.between(at(-720), at(120)).await.unwrap(), vec![past, next.clone()]);
+
+        restored.remove(next.id).await.unwrap();
+        assert!(restored.active_window(at(60)).is_none());
+        assert!(matches!(restored.remove(next.id).await, Err(EventError::NotFound(_))));
+    }
+}
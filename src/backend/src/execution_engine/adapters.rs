@@ -0,0 +1,942 @@
+//! Exchange adapters that turn an order into the venue's own swap or order parameters with
+//! the slippage limit encoded on-chain. Jupiter swaps carry `slippageBps` and a minimum-out
+//! (`otherAmountThreshold`) derived from the quote fetched at build time; Drift orders are
+//! immediate-or-cancel limits priced off the live mid. Either way the program rejects a fill
+//! past the configured slippage instead of the client only checking before submission.
+//! Jupiter swaps built with a compute budgeter carry their own simulated compute unit limit.
+//! With a token account manager, swaps into a mint the wallet has never held create its
+//! associated token account first, in the swap transaction when the adapter builds it.
+//!
+//! Version dependencies:
+//! - base64 = "0.21"
+//! - reqwest = "0.11"
+//! - rust_decimal = "1.30"
+//! - serde = "1.0"
+//! - solana-sdk = "1.16"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::Engine;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use tracing::debug;
+
+use crate::execution_engine::compute_budget::{ComputeBudget, ComputeBudgeter};
+use crate::execution_engine::error::ExecutionError;
+use crate::execution_engine::preview::LiveMarketData;
+use crate::risk_manager::exposure::TradeSide;
+use crate::utils::http::{self, HttpClient};
+use crate::utils::percent::Bps;
+use crate::utils::solana::FeeUrgency;
+use crate::utils::token_accounts::{TokenAccountError, TokenAccountManager, TokenAccountSetup};
+
+// Adapter constants
+pub const DEFAULT_MAX_SLIPPAGE: Bps = Bps::new(50);
+pub const DEFAULT_JUPITER_QUOTE_URL: &str = "https://quote-api.jup.ag/v6";
+const QUOTE_TIMEOUT_MS: u64 = 300;
+const MAX_SLIPPAGE_BPS: u16 = 10_000;
+const DRIFT_BASE_DECIMALS: u32 = 9;
+const DRIFT_PRICE_DECIMALS: u32 = 6;
+
+/// Order an adapter prices and guards
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderRequest {
+    pub trading_pair: String,
+    pub side: TradeSide,
+    /// Base asset size
+    pub size: Decimal,
+    /// Price the order was decided at; sizes the quote amount spent on buys
+    pub price: Decimal,
+    pub max_slippage: Bps,
+}
+
+/// Slippage limit as encoded on the venue, with the quote it was derived from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlippageGuard {
+    pub max_slippage: Bps,
+    /// Quote per base price quoted at build time
+    pub quoted_price: Decimal,
+    /// Worst quote per base price the venue will fill at
+    pub limit_price: Decimal,
+    /// Minimum output in base units, for swaps that enforce one
+    pub min_out_amount: Option<u64>,
+}
+
+impl SlippageGuard {
+    /// Adverse move from the quoted price; negative when the fill improved on the quote
+    pub fn realized_slippage(&self, side: TradeSide, executed_price: Decimal) -> Bps {
+        if self.quoted_price <= Decimal::ZERO {
+            return Bps::ZERO;
+        }
+        let adverse = match side {
+            TradeSide::Buy => executed_price - self.quoted_price,
+            TradeSide::Sell => self.quoted_price - executed_price,
+        };
+        Bps::from_fraction((adverse / self.quoted_price).round_dp(6))
+    }
+}
+
+/// Configured and realized slippage of an executed trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlippageReport {
+    pub configured: Bps,
+    pub realized: Bps,
+}
+
+/// Venue parameters carrying the slippage limit
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "venue", rename_all = "snake_case")]
+pub enum VenueOrder {
+    /// Body of a Jupiter `/swap` request
+    Jupiter(JupiterSwapRequest),
+    Drift(DriftOrderParams),
+}
+
+/// Venue order together with the guard it encodes
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuardedOrder {
+    pub guard: SlippageGuard,
+    pub order: VenueOrder,
+    /// Compute budget sized from simulating the order's instructions, when budgeted
+    pub compute_budget: Option<ComputeBudget>,
+    /// Instructions executing the order with the compute budget prepended; empty when the
+    /// venue builds the transaction itself
+    pub instructions: Vec<Instruction>,
+    /// Rent locked in token accounts created for the order
+    pub account_rent_lamports: u64,
+}
+
+impl GuardedOrder {
+    /// Lamports the order commits beyond the trade itself: priority fee and account rent
+    pub fn network_cost_lamports(&self) -> u64 {
+        self.compute_budget.map_or(0, |budget| budget.priority_fee_lamports) + self.account_rent_lamports
+    }
+}
+
+/// Builds venue parameters with the order's slippage limit enforced on-chain
+#[async_trait]
+pub trait ExchangeAdapter: std::fmt::Debug + Send + Sync {
+    fn exchange(&self) -> &str;
+
+    async fn prepare(&self, request: &OrderRequest) -> Result<GuardedOrder, ExecutionError>;
+}
+
+/// Whole basis points as venues accept them, rounding fractional settings down so the
+/// encoded limit is never looser than configured
+pub fn slippage_bps(max_slippage: Bps) -> Result<u16, ExecutionError> {
+    let bps = max_slippage.as_bps().trunc();
+    if bps < Decimal::ZERO || bps > Decimal::from(MAX_SLIPPAGE_BPS) {
+        return Err(ExecutionError::ValidationError(format!(
+            "max slippage {} out of range",
+            max_slippage
+        )));
+    }
+    bps.to_u16()
+        .ok_or_else(|| ExecutionError::ValidationError(format!("max slippage {} out of range", max_slippage)))
+}
+
+/// Minimum output the swap may return, rounded down as Jupiter computes it
+pub fn min_out_amount(quoted_out: u64, max_slippage: Bps) -> Result<u64, ExecutionError> {
+    let bps = slippage_bps(max_slippage)? as u128;
+    let max_bps = MAX_SLIPPAGE_BPS as u128;
+    Ok((quoted_out as u128 * (max_bps - bps) / max_bps) as u64)
+}
+
+/// Worst acceptable price: above the reference for buys, below it for sells
+pub fn limit_price(reference: Decimal, side: TradeSide, max_slippage: Bps) -> Decimal {
+    match side {
+        TradeSide::Buy => reference + max_slippage.of(reference),
+        TradeSide::Sell => reference - max_slippage.of(reference),
+    }
+}
+
+/// Converts a token amount to base units, rejecting amounts that do not fit
+pub fn to_base_units(amount: Decimal, decimals: u32) -> Result<u64, ExecutionError> {
+    if amount <= Decimal::ZERO {
+        return Err(ExecutionError::ValidationError("amount must be positive".to_string()));
+    }
+    (amount * Decimal::from(10u64.pow(decimals)))
+        .trunc()
+        .to_u64()
+        .ok_or_else(|| ExecutionError::ValidationError(format!("amount {} out of range", amount)))
+}
+
+fn from_base_units(amount: u64, decimals: u32) -> Decimal {
+    Decimal::from_i128_with_scale(amount as i128, decimals)
+}
+
+/// Mints and decimals of a pair's base and quote tokens
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PairMints {
+    pub base_mint: Pubkey,
+    pub base_decimals: u32,
+    pub quote_mint: Pubkey,
+    pub quote_decimals: u32,
+}
+
+/// Query parameters of a Jupiter quote
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterQuoteParams {
+    pub input_mint: String,
+    pub output_mint: String,
+    pub amount: u64,
+    pub slippage_bps: u16,
+    pub swap_mode: String,
+}
+
+/// Jupiter quote response; fields not modelled here are passed back to `/swap` untouched
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterQuote {
+    pub input_mint: String,
+    pub in_amount: String,
+    pub output_mint: String,
+    pub out_amount: String,
+    /// Minimum output the Jupiter program enforces for ExactIn swaps
+    pub other_amount_threshold: String,
+    pub swap_mode: String,
+    pub slippage_bps: u16,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Body of a Jupiter `/swap` request
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterSwapRequest {
+    pub quote_response: JupiterQuote,
+    pub user_public_key: String,
+    /// wSOL is wrapped and unwrapped around the swap by `swap::build_swap_transaction`
+    pub wrap_and_unwrap_sol: bool,
+}
+
+/// Instruction as returned by Jupiter's `/swap-instructions`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterInstruction {
+    pub program_id: String,
+    pub accounts: Vec<JupiterAccountMeta>,
+    /// Base64 encoded instruction data
+    pub data: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterAccountMeta {
+    pub pubkey: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl JupiterInstruction {
+    fn decode(&self) -> Result<Instruction, ExecutionError> {
+        let pubkey = |value: &str| {
+            value
+                .parse::<Pubkey>()
+                .map_err(|_| ExecutionError::ValidationError(format!("jupiter instruction has invalid key: {}", value)))
+        };
+        let accounts = self
+            .accounts
+            .iter()
+            .map(|account| {
+                Ok(AccountMeta {
+                    pubkey: pubkey(&account.pubkey)?,
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+            })
+            .collect::<Result<Vec<_>, ExecutionError>>()?;
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(&self.data)
+            .map_err(|e| ExecutionError::ValidationError(format!("jupiter instruction has invalid data: {}", e)))?;
+        Ok(Instruction {
+            program_id: pubkey(&self.program_id)?,
+            accounts,
+            data,
+        })
+    }
+}
+
+/// Jupiter `/swap-instructions` response; its own compute budget instructions are replaced
+/// by the simulated budget
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterSwapInstructions {
+    #[serde(default)]
+    pub compute_budget_instructions: Vec<JupiterInstruction>,
+    #[serde(default)]
+    pub setup_instructions: Vec<JupiterInstruction>,
+    pub swap_instruction: JupiterInstruction,
+    pub cleanup_instruction: Option<JupiterInstruction>,
+}
+
+impl JupiterSwapInstructions {
+    /// Setup, swap and cleanup instructions in execution order
+    pub fn route(&self) -> Result<Vec<Instruction>, ExecutionError> {
+        self.setup_instructions
+            .iter()
+            .chain(std::iter::once(&self.swap_instruction))
+            .chain(self.cleanup_instruction.as_ref())
+            .map(JupiterInstruction::decode)
+            .collect()
+    }
+}
+
+/// Source of Jupiter quotes and swap instructions
+#[async_trait]
+pub trait JupiterQuoteApi: Send + Sync {
+    async fn quote(&self, params: &JupiterQuoteParams) -> Result<JupiterQuote, ExecutionError>;
+
+    async fn swap_instructions(&self, request: &JupiterSwapRequest) -> Result<JupiterSwapInstructions, ExecutionError>;
+}
+
+/// Fetches quotes from the Jupiter quote API
+#[derive(Debug)]
+pub struct JupiterHttpApi {
+    base_url: String,
+    http: Arc<HttpClient>,
+}
+
+impl JupiterHttpApi {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            http: http::shared(),
+        }
+    }
+
+    pub fn with_http(mut self, http: Arc<HttpClient>) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+#[async_trait]
+impl JupiterQuoteApi for JupiterHttpApi {
+    async fn quote(&self, params: &JupiterQuoteParams) -> Result<JupiterQuote, ExecutionError> {
+        let response = self
+            .http
+            .get(
+                &format!("{}/quote", self.base_url.trim_end_matches('/')),
+                Some(Duration::from_millis(QUOTE_TIMEOUT_MS)),
+                |request| request.query(params),
+            )
+            .await
+            .map_err(|e| {
+                let status = e.status().unwrap_or(0);
+                ExecutionError::NetworkError(format!("jupiter quote failed: {}", e), status)
+            })?;
+        response
+            .json()
+            .await
+            .map_err(|e| ExecutionError::NetworkError(format!("invalid jupiter quote: {}", e), 502))
+    }
+
+    async fn swap_instructions(&self, request: &JupiterSwapRequest) -> Result<JupiterSwapInstructions, ExecutionError> {
+        let response = self
+            .http
+            .post(
+                &format!("{}/swap-instructions", self.base_url.trim_end_matches('/')),
+                Some(Duration::from_millis(QUOTE_TIMEOUT_MS)),
+                |builder| builder.json(request),
+            )
+            .await
+            .map_err(|e| {
+                let status = e.status().unwrap_or(0);
+                ExecutionError::NetworkError(format!("jupiter swap instructions failed: {}", e), status)
+            })?;
+        response.json().await.map_err(|e| {
+            ExecutionError::NetworkError(format!("invalid jupiter swap instructions: {}", e), 502)
+        })
+    }
+}
+
+/// Jupiter swaps with the minimum output set from the quote at build time
+pub struct JupiterAdapter {
+    api: Arc<dyn JupiterQuoteApi>,
+    user_public_key: Pubkey,
+    pairs: HashMap<String, PairMints>,
+    compute_budget: Option<Arc<ComputeBudgeter>>,
+    token_accounts: Option<Arc<TokenAccountManager>>,
+}
+
+impl std::fmt::Debug for JupiterAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JupiterAdapter")
+            .field("user_public_key", &self.user_public_key)
+            .field("pairs", &self.pairs.keys().collect::<Vec<_>>())
+            .field("compute_budget", &self.compute_budget.is_some())
+            .field("token_accounts", &self.token_accounts.is_some())
+            .finish()
+    }
+}
+
+impl JupiterAdapter {
+    pub fn new(api: Arc<dyn JupiterQuoteApi>, user_public_key: Pubkey) -> Self {
+        Self {
+            api,
+            user_public_key,
+            pairs: HashMap::new(),
+            compute_budget: None,
+            token_accounts: None,
+        }
+    }
+
+    pub fn with_pair(mut self, trading_pair: impl Into<String>, mints: PairMints) -> Self {
+        let trading_pair = trading_pair.into();
+        if let Some(accounts) = &self.token_accounts {
+            accounts.register_pair(trading_pair.clone(), mints.base_mint, mints.quote_mint);
+        }
+        self.pairs.insert(trading_pair, mints);
+        self
+    }
+
+    /// Creates missing associated token accounts ahead of each swap and reports their rent
+    pub fn with_token_accounts(mut self, accounts: Arc<TokenAccountManager>) -> Self {
+        for (trading_pair, mints) in &self.pairs {
+            accounts.register_pair(trading_pair.clone(), mints.base_mint, mints.quote_mint);
+        }
+        self.token_accounts = Some(accounts);
+        self
+    }
+
+    /// Builds swaps from their instructions with a simulated compute unit limit, rejecting
+    /// routes over the compute ceiling before submission
+    pub fn with_compute_budget(mut self, budgeter: Arc<ComputeBudgeter>) -> Self {
+        self.compute_budget = Some(budgeter);
+        self
+    }
+}
+
+#[async_trait]
+impl ExchangeAdapter for JupiterAdapter {
+    fn exchange(&self) -> &str {
+        "jupiter"
+    }
+
+    async fn prepare(&self, request: &OrderRequest) -> Result<GuardedOrder, ExecutionError> {
+        let mints = self.pairs.get(&request.trading_pair).ok_or_else(|| {
+            ExecutionError::ValidationError(format!("no jupiter mints for {}", request.trading_pair))
+        })?;
+        let bps = slippage_bps(request.max_slippage)?;
+
+        // ExactIn both ways: buys spend quote at the decision price, sells spend the base size
+        let (input, output, amount) = match request.side {
+            TradeSide::Buy => (
+                (mints.quote_mint, mints.quote_decimals),
+                (mints.base_mint, mints.base_decimals),
+                to_base_units(request.size * request.price, mints.quote_decimals)?,
+            ),
+            TradeSide::Sell => (
+                (mints.base_mint, mints.base_decimals),
+                (mints.quote_mint, mints.quote_decimals),
+                to_base_units(request.size, mints.base_decimals)?,
+            ),
+        };
+
+        let mut quote = self
+            .api
+            .quote(&JupiterQuoteParams {
+                input_mint: input.0.to_string(),
+                output_mint: output.0.to_string(),
+                amount,
+                slippage_bps: bps,
+                swap_mode: "ExactIn".to_string(),
+            })
+            .await?;
+
+        let parse = |field: &str, value: &str| {
+            value.parse::<u64>().map_err(|_| {
+                ExecutionError::ValidationError(format!("jupiter quote has invalid {}: {}", field, value))
+            })
+        };
+        let in_amount = parse("inAmount", &quote.in_amount)?;
+        let out_amount = parse("outAmount", &quote.out_amount)?;
+        if in_amount == 0 || out_amount == 0 {
+            return Err(ExecutionError::LiquidityError(format!("empty jupiter quote for {}", request.trading_pair)));
+        }
+
+        // The threshold is derived here rather than trusted from the quote
+        let min_out = min_out_amount(out_amount, request.max_slippage)?;
+        quote.slippage_bps = bps;
+        quote.other_amount_threshold = min_out.to_string();
+
+        let (input_amount, output_amount, min_output) = (
+            from_base_units(in_amount, input.1),
+            from_base_units(out_amount, output.1),
+            from_base_units(min_out, output.1),
+        );
+        let (quoted_price, limit_price) = match request.side {
+            // A zero minimum out accepts any price; the quote's input bounds the spend
+            TradeSide::Buy if min_output.is_zero() => (input_amount / output_amount, Decimal::MAX),
+            TradeSide::Buy => (input_amount / output_amount, input_amount / min_output),
+            TradeSide::Sell => (output_amount / input_amount, min_output / input_amount),
+        };
+
+        debug!(
+            trading_pair = %request.trading_pair,
+            slippage_bps = bps,
+            out_amount,
+            min_out,
+            "Prepared guarded jupiter swap"
+        );
+
+        let swap = JupiterSwapRequest {
+            quote_response: quote,
+            user_public_key: self.user_public_key.to_string(),
+            wrap_and_unwrap_sol: false,
+        };
+        let setup = match &self.token_accounts {
+            Some(accounts) => accounts.setup(&[input.0, output.0]).await.map_err(token_account_error)?,
+            None => TokenAccountSetup::default(),
+        };
+        let (compute_budget, instructions) = match &self.compute_budget {
+            Some(budgeter) => {
+                // Missing accounts are created in the swap transaction itself
+                let mut route = setup.instructions.clone();
+                route.extend(self.api.swap_instructions(&swap).await?.route()?);
+                let budget = budgeter.plan(self.exchange(), &route, FeeUrgency::Normal).await?;
+                (Some(budget), budget.apply(route))
+            }
+            None => {
+                // Jupiter builds this transaction, so missing accounts are created ahead of it
+                if let Some(accounts) = &self.token_accounts {
+                    accounts.create(&setup).await.map_err(token_account_error)?;
+                }
+                (None, Vec::new())
+            }
+        };
+
+        Ok(GuardedOrder {
+            guard: SlippageGuard {
+                max_slippage: request.max_slippage,
+                quoted_price,
+                limit_price,
+                min_out_amount: Some(min_out),
+            },
+            order: VenueOrder::Jupiter(swap),
+            compute_budget,
+            instructions,
+            account_rent_lamports: setup.rent_lamports,
+        })
+    }
+}
+
+/// Direction of a Drift perp order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DriftDirection {
+    Long,
+    Short,
+}
+
+/// Drift `OrderParams` for an immediate-or-cancel limit order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftOrderParams {
+    pub market_index: u16,
+    pub direction: DriftDirection,
+    /// Size in BASE_PRECISION (1e9)
+    pub base_asset_amount: u64,
+    /// Limit price in PRICE_PRECISION (1e6)
+    pub price: u64,
+    pub immediate_or_cancel: bool,
+    pub reduce_only: bool,
+}
+
+/// Drift perp orders limited to the configured slippage from the live mid
+pub struct DriftAdapter {
+    market_data: Arc<dyn LiveMarketData>,
+    markets: HashMap<String, u16>,
+}
+
+impl std::fmt::Debug for DriftAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DriftAdapter")
+            .field("markets", &self.markets)
+            .finish()
+    }
+}
+
+impl DriftAdapter {
+    pub fn new(market_data: Arc<dyn LiveMarketData>) -> Self {
+        Self {
+            market_data,
+            markets: HashMap::new(),
+        }
+    }
+
+    pub fn with_market(mut self, trading_pair: impl Into<String>, market_index: u16) -> Self {
+        self.markets.insert(trading_pair.into(), market_index);
+        self
+    }
+}
+
+#[async_trait]
+impl ExchangeAdapter for DriftAdapter {
+    fn exchange(&self) -> &str {
+        "drift"
+    }
+
+    async fn prepare(&self, request: &OrderRequest) -> Result<GuardedOrder, ExecutionError> {
+        let market_index = *self.markets.get(&request.trading_pair).ok_or_else(|| {
+            ExecutionError::ValidationError(format!("no drift market for {}", request.trading_pair))
+        })?;
+        // Without a fresh reference there is nothing to bound the fill against
+        let quoted_price = self
+            .market_data
+            .latest(&request.trading_pair)
+            .map(|market_data| market_data.price())
+            .ok_or_else(|| ExecutionError::OrderBookError(format!("no fresh drift price for {}", request.trading_pair)))?;
+        slippage_bps(request.max_slippage)?;
+
+        // Round towards the reference so the encoded limit is never looser than configured
+        let limit = limit_price(quoted_price, request.side, request.max_slippage);
+        let scaled = limit * Decimal::from(10u64.pow(DRIFT_PRICE_DECIMALS));
+        let (direction, price) = match request.side {
+            TradeSide::Buy => (DriftDirection::Long, scaled.floor()),
+            TradeSide::Sell => (DriftDirection::Short, scaled.ceil()),
+        };
+        let price = price
+            .to_u64()
+            .filter(|price| *price > 0)
+            .ok_or_else(|| ExecutionError::ValidationError(format!("limit price {} out of range", limit)))?;
+
+        Ok(GuardedOrder {
+            guard: SlippageGuard {
+                max_slippage: request.max_slippage,
+                quoted_price,
+                limit_price: from_base_units(price, DRIFT_PRICE_DECIMALS),
+                min_out_amount: None,
+            },
+            order: VenueOrder::Drift(DriftOrderParams {
+                market_index,
+                direction,
+                base_asset_amount: to_base_units(request.size, DRIFT_BASE_DECIMALS)?,
+                price,
+                immediate_or_cancel: true,
+                reduce_only: false,
+            }),
+            compute_budget: None,
+            instructions: Vec::new(),
+            account_rent_lamports: 0,
+        })
+    }
+}
+
+fn token_account_error(error: TokenAccountError) -> ExecutionError {
+    ExecutionError::InternalError(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_engine::compute_budget::{ComputeBudgetConfig, ComputeMeter};
+    use crate::models::market::MarketData;
+    use crate::utils::solana::SolanaError;
+    use crate::utils::token_accounts::TokenAccountRpc;
+    use parking_lot::Mutex;
+    use rust_decimal_macros::dec;
+    use solana_sdk::compute_budget::ComputeBudgetInstruction;
+    use spl_associated_token_account::get_associated_token_address;
+    use std::collections::HashSet;
+
+    const JUPITER_PROGRAM: Pubkey = solana_sdk::pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
+    /// Quotes a fixed output and records the parameters it was asked for
+    struct MockQuoteApi {
+        in_amount: u64,
+        out_amount: u64,
+        requests: Mutex<Vec<JupiterQuoteParams>>,
+    }
+
+    #[async_trait]
+    impl JupiterQuoteApi for MockQuoteApi {
+        async fn quote(&self, params: &JupiterQuoteParams) -> Result<JupiterQuote, ExecutionError> {
+            self.requests.lock().push(params.clone());
+            let extra = serde_json::json!({ "routePlan": [], "priceImpactPct": "0.0001" });
+            Ok(JupiterQuote {
+                input_mint: params.input_mint.clone(),
+                in_amount: self.in_amount.to_string(),
+                output_mint: params.output_mint.clone(),
+                out_amount: self.out_amount.to_string(),
+                // Jupiter's own threshold, which the adapter must not rely on
+                other_amount_threshold: "0".to_string(),
+                swap_mode: params.swap_mode.clone(),
+                slippage_bps: params.slippage_bps,
+                extra: extra.as_object().unwrap().clone(),
+            })
+        }
+
+        async fn swap_instructions(&self, request: &JupiterSwapRequest) -> Result<JupiterSwapInstructions, ExecutionError> {
+            let instruction = |program_id: Pubkey, data: &[u8]| JupiterInstruction {
+                program_id: program_id.to_string(),
+                accounts: vec![JupiterAccountMeta {
+                    pubkey: request.user_public_key.clone(),
+                    is_signer: true,
+                    is_writable: true,
+                }],
+                data: base64::engine::general_purpose::STANDARD.encode(data),
+            };
+            Ok(JupiterSwapInstructions {
+                compute_budget_instructions: vec![instruction(solana_sdk::compute_budget::id(), &[2, 0, 0, 0, 0])],
+                setup_instructions: vec![instruction(spl_associated_token_account::id(), &[1])],
+                swap_instruction: instruction(JUPITER_PROGRAM, &[0xe5, 0x17]),
+                cleanup_instruction: None,
+            })
+        }
+    }
+
+    /// Simulates every route at a fixed number of compute units
+    struct FixedUnits(u64);
+
+    #[async_trait]
+    impl ComputeMeter for FixedUnits {
+        async fn simulate_units(&self, _: &solana_sdk::transaction::Transaction) -> Result<u64, ExecutionError> {
+            Ok(self.0)
+        }
+
+        async fn consumed_units(&self, _: &str) -> Result<Option<u64>, ExecutionError> {
+            Ok(None)
+        }
+    }
+
+    const ACCOUNT_RENT: u64 = 2_039_280;
+
+    /// Chain where only the listed token accounts exist, counting lookups and sends
+    #[derive(Default)]
+    struct MockAccounts {
+        existing: Mutex<HashSet<Pubkey>>,
+        lookups: Mutex<usize>,
+        sent: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl TokenAccountRpc for MockAccounts {
+        async fn token_amount(&self, account: &Pubkey) -> Result<Option<u64>, SolanaError> {
+            *self.lookups.lock() += 1;
+            Ok(self.existing.lock().contains(account).then_some(0))
+        }
+
+        async fn rent_exempt_minimum(&self, _: usize) -> Result<u64, SolanaError> {
+            Ok(ACCOUNT_RENT)
+        }
+
+        async fn send(&self, _: Vec<Instruction>) -> Result<String, SolanaError> {
+            *self.sent.lock() += 1;
+            Ok("setup_sig".to_string())
+        }
+    }
+
+    struct FixedPrice(Decimal);
+
+    impl LiveMarketData for FixedPrice {
+        fn latest(&self, trading_pair: &str) -> Option<MarketData> {
+            MarketData::new(trading_pair.to_string(), "drift".to_string(), self.0, dec!(1000)).ok()
+        }
+    }
+
+    fn sol_usdc() -> PairMints {
+        PairMints {
+            base_mint: Pubkey::new_unique(),
+            base_decimals: 9,
+            quote_mint: Pubkey::new_unique(),
+            quote_decimals: 6,
+        }
+    }
+
+    fn request(side: TradeSide, max_slippage: Bps) -> OrderRequest {
+        OrderRequest {
+            trading_pair: "SOL/USDC".to_string(),
+            side,
+            size: dec!(2),
+            price: dec!(23.45),
+            max_slippage,
+        }
+    }
+
+    #[test]
+    fn test_min_out_for_bps_settings() {
+        assert_eq!(min_out_amount(46_900_000, Bps::new(50)).unwrap(), 46_665_500);
+        assert_eq!(min_out_amount(46_900_000, Bps::ZERO).unwrap(), 46_900_000);
+        assert_eq!(min_out_amount(999, Bps::new(1)).unwrap(), 998);
+        assert_eq!(min_out_amount(u64::MAX, Bps::new(100)).unwrap(), 18_262_276_632_972_456_098);
+        assert_eq!(min_out_amount(1_000, Bps::new(10_000)).unwrap(), 0);
+        assert!(min_out_amount(1_000, Bps::new(10_001)).is_err());
+        // Fractional settings round to the tighter whole bps
+        assert_eq!(slippage_bps(Bps::from_bps(dec!(12.7))).unwrap(), 12);
+    }
+
+    #[tokio::test]
+    async fn test_jupiter_sell_encodes_min_out() {
+        let api = Arc::new(MockQuoteApi {
+            in_amount: 2_000_000_000,
+            out_amount: 46_900_000,
+            requests: Mutex::new(Vec::new()),
+        });
+        let mints = sol_usdc();
+        let adapter = JupiterAdapter::new(api.clone(), Pubkey::new_unique()).with_pair("SOL/USDC", mints);
+
+        let guarded = adapter.prepare(&request(TradeSide::Sell, Bps::new(50))).await.unwrap();
+
+        let requests = api.requests.lock();
+        assert_eq!(requests[0].input_mint, mints.base_mint.to_string());
+        assert_eq!(requests[0].amount, 2_000_000_000);
+        assert_eq!(requests[0].slippage_bps, 50);
+
+        let VenueOrder::Jupiter(swap) = &guarded.order else { panic!("expected a jupiter swap") };
+        assert_eq!(swap.quote_response.slippage_bps, 50);
+        assert_eq!(swap.quote_response.other_amount_threshold, "46665500");
+        assert_eq!(guarded.guard.min_out_amount, Some(46_665_500));
+        assert_eq!(guarded.guard.quoted_price, dec!(23.45));
+        assert_eq!(guarded.guard.limit_price, dec!(23.33275));
+
+        // Unmodelled quote fields are echoed back to /swap
+        let body = serde_json::to_value(swap).unwrap();
+        assert_eq!(body["quoteResponse"]["otherAmountThreshold"], "46665500");
+        assert_eq!(body["quoteResponse"]["priceImpactPct"], "0.0001");
+    }
+
+    #[tokio::test]
+    async fn test_jupiter_buy_spends_quote_and_guards_base() {
+        let api = Arc::new(MockQuoteApi {
+            in_amount: 46_900_000,
+            out_amount: 2_000_000_000,
+            requests: Mutex::new(Vec::new()),
+        });
+        let mints = sol_usdc();
+        let adapter = JupiterAdapter::new(api.clone(), Pubkey::new_unique()).with_pair("SOL/USDC", mints);
+
+        let guarded = adapter.prepare(&request(TradeSide::Buy, Bps::new(100))).await.unwrap();
+
+        assert_eq!(api.requests.lock()[0].input_mint, mints.quote_mint.to_string());
+        assert_eq!(api.requests.lock()[0].amount, 46_900_000);
+        assert_eq!(guarded.guard.min_out_amount, Some(1_980_000_000));
+
+        // A fill 0.5% above the quote realizes 50 bps against a 100 bps limit
+        let realized = guarded.guard.realized_slippage(TradeSide::Buy, dec!(23.56725));
+        assert_eq!(realized, Bps::new(50));
+    }
+
+    #[tokio::test]
+    async fn test_jupiter_swap_carries_simulated_compute_limit() {
+        let api = Arc::new(MockQuoteApi {
+            in_amount: 2_000_000_000,
+            out_amount: 46_900_000,
+            requests: Mutex::new(Vec::new()),
+        });
+        let user = Pubkey::new_unique();
+        let budgeter = ComputeBudgeter::new(
+            ComputeBudgetConfig::default().with_venue_ceiling("jupiter", 600_000),
+            Arc::new(FixedUnits(250_000)),
+            user,
+        );
+        let adapter = JupiterAdapter::new(api.clone(), user)
+            .with_pair("SOL/USDC", sol_usdc())
+            .with_compute_budget(Arc::new(budgeter));
+
+        let guarded = adapter.prepare(&request(TradeSide::Sell, Bps::new(50))).await.unwrap();
+        assert_eq!(guarded.compute_budget.map(|budget| budget.unit_limit), Some(300_000));
+        // Jupiter's own budget instructions give way to the simulated limit
+        let programs: Vec<Pubkey> = guarded.instructions.iter().map(|ix| ix.program_id).collect();
+        assert_eq!(
+            programs,
+            vec![
+                solana_sdk::compute_budget::id(),
+                solana_sdk::compute_budget::id(),
+                spl_associated_token_account::id(),
+                JUPITER_PROGRAM,
+            ]
+        );
+        assert_eq!(guarded.instructions[0], ComputeBudgetInstruction::set_compute_unit_limit(300_000));
+        assert_eq!(guarded.instructions[3].data, vec![0xe5, 0x17]);
+
+        let budgeter = ComputeBudgeter::new(
+            ComputeBudgetConfig::default().with_venue_ceiling("jupiter", 600_000),
+            Arc::new(FixedUnits(700_000)),
+            user,
+        );
+        let adapter = JupiterAdapter::new(api, user)
+            .with_pair("SOL/USDC", sol_usdc())
+            .with_compute_budget(Arc::new(budgeter));
+        assert!(matches!(
+            adapter.prepare(&request(TradeSide::Sell, Bps::new(50))).await,
+            Err(ExecutionError::ComputeBudgetExceeded { estimated: 700_000, ceiling: 600_000, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_jupiter_swap_creates_missing_token_account() {
+        let api = Arc::new(MockQuoteApi {
+            in_amount: 46_900_000,
+            out_amount: 2_000_000_000,
+            requests: Mutex::new(Vec::new()),
+        });
+        let user = Pubkey::new_unique();
+        let mints = sol_usdc();
+        let (base_account, quote_account) = (
+            get_associated_token_address(&user, &mints.base_mint),
+            get_associated_token_address(&user, &mints.quote_mint),
+        );
+        let rpc = Arc::new(MockAccounts::default());
+        rpc.existing.lock().insert(quote_account);
+        let budgeter = ComputeBudgeter::new(ComputeBudgetConfig::default(), Arc::new(FixedUnits(250_000)), user);
+        let adapter = JupiterAdapter::new(api.clone(), user)
+            .with_pair("SOL/USDC", mints)
+            .with_compute_budget(Arc::new(budgeter))
+            .with_token_accounts(Arc::new(TokenAccountManager::new(user, rpc.clone())));
+
+        // First buy of the base token: its account is created inside the swap transaction
+        let guarded = adapter.prepare(&request(TradeSide::Buy, Bps::new(50))).await.unwrap();
+        assert_eq!(guarded.instructions.len(), 5);
+        assert_eq!(guarded.instructions[2].program_id, spl_associated_token_account::id());
+        assert_eq!(guarded.instructions[2].accounts[1].pubkey, base_account);
+        assert_eq!(guarded.account_rent_lamports, ACCOUNT_RENT);
+        let priority_fee = guarded.compute_budget.unwrap().priority_fee_lamports;
+        assert_eq!(guarded.network_cost_lamports(), priority_fee + ACCOUNT_RENT);
+        assert_eq!((*rpc.lookups.lock(), *rpc.sent.lock()), (2, 0));
+
+        // Once the account exists it is cached along with the quote account
+        rpc.existing.lock().insert(base_account);
+        let guarded = adapter.prepare(&request(TradeSide::Buy, Bps::new(50))).await.unwrap();
+        assert_eq!(guarded.instructions.len(), 4);
+        assert_eq!(guarded.account_rent_lamports, 0);
+        adapter.prepare(&request(TradeSide::Sell, Bps::new(50))).await.unwrap();
+        assert_eq!(*rpc.lookups.lock(), 3);
+
+        // Without a compute budget Jupiter builds the swap, so the account is created first
+        let rpc = Arc::new(MockAccounts::default());
+        let adapter = JupiterAdapter::new(api, user)
+            .with_token_accounts(Arc::new(TokenAccountManager::new(user, rpc.clone())))
+            .with_pair("SOL/USDC", mints);
+        let guarded = adapter.prepare(&request(TradeSide::Buy, Bps::new(50))).await.unwrap();
+        assert!(guarded.instructions.is_empty());
+        assert_eq!(guarded.account_rent_lamports, 2 * ACCOUNT_RENT);
+        assert_eq!(*rpc.sent.lock(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_drift_limit_price_from_live_mid() {
+        let adapter = DriftAdapter::new(Arc::new(FixedPrice(dec!(100)))).with_market("SOL/USDC", 0);
+
+        let buy = adapter.prepare(&request(TradeSide::Buy, Bps::new(25))).await.unwrap();
+        let VenueOrder::Drift(order) = &buy.order else { panic!("expected a drift order") };
+        assert_eq!(order.direction, DriftDirection::Long);
+        assert_eq!(order.price, 100_250_000);
+        assert_eq!(order.base_asset_amount, 2_000_000_000);
+        assert!(order.immediate_or_cancel);
+
+        let sell = adapter.prepare(&request(TradeSide::Sell, Bps::new(25))).await.unwrap();
+        let VenueOrder::Drift(order) = &sell.order else { panic!("expected a drift order") };
+        assert_eq!(order.price, 99_750_000);
+        assert_eq!(sell.guard.limit_price, dec!(99.75));
+        assert_eq!(sell.guard.realized_slippage(TradeSide::Sell, dec!(100.1)), Bps::from_bps(dec!(-10)));
+    }
+}
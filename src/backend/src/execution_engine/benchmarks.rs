@@ -0,0 +1,294 @@
+//! Post-trade execution benchmarks. Once late ticks have had time to persist, the market's
+//! interval VWAP and time-sliced TWAP over each execution window are computed from stored
+//! tick data and our fill's deviation from them is written back onto the execution record.
+//! Windows the collector only partially covered are flagged as low coverage.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::execution_engine::stats::{ExecutionRecord, ExecutionStatsStore, StatsError};
+use crate::risk_manager::exposure::TradeSide;
+
+// Benchmark constants
+const METRICS_PREFIX: &str = "trading_bot.execution_benchmarks";
+const BPS_PER_UNIT: Decimal = Decimal::new(10_000, 0);
+const DEFAULT_DELAY: Duration = Duration::from_secs(10);
+const DEFAULT_MIN_WINDOW: Duration = Duration::from_secs(60);
+const DEFAULT_SLICE: Duration = Duration::from_secs(5);
+const DEFAULT_MIN_COVERAGE: Decimal = Decimal::new(8, 1);
+
+/// One persisted market tick
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketTick {
+    pub price: Decimal,
+    pub volume: Decimal,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Market benchmark prices over an execution window and our fill's deviation from them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionBenchmark {
+    pub window_start: DateTime<Utc>,
+    pub window_end: DateTime<Utc>,
+    pub vwap: Option<Decimal>,
+    pub twap: Option<Decimal>,
+    /// Adverse distance of the fill from the VWAP, in basis points
+    pub vwap_deviation_bps: Option<Decimal>,
+    /// Adverse distance of the fill from the TWAP, in basis points
+    pub twap_deviation_bps: Option<Decimal>,
+    pub tick_count: u64,
+    /// Share of time slices in the window holding at least one tick
+    pub coverage: Decimal,
+    pub low_coverage: bool,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Benchmark timing and coverage thresholds
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkConfig {
+    /// Wait after an execution before benchmarking so late ticks can persist
+    pub delay: Duration,
+    /// Shortest window benchmarked; faster executions are measured against this lookback
+    pub min_window: Duration,
+    /// Width of the time slices behind the TWAP and coverage
+    pub slice: Duration,
+    /// Coverage below this flags the benchmark as low coverage
+    pub min_coverage: Decimal,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            delay: DEFAULT_DELAY,
+            min_window: DEFAULT_MIN_WINDOW,
+            slice: DEFAULT_SLICE,
+            min_coverage: DEFAULT_MIN_COVERAGE,
+        }
+    }
+}
+
+/// Source of persisted ticks for a trading pair across all venues
+#[async_trait]
+pub trait MarketTickSource: Send + Sync {
+    async fn ticks(
+        &self,
+        trading_pair: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<MarketTick>, StatsError>;
+}
+
+/// Execution window of a record: its latency, widened to the configured minimum
+pub fn execution_window(record: &ExecutionRecord, config: &BenchmarkConfig) -> (DateTime<Utc>, DateTime<Utc>) {
+    let window_ms = record.latency_ms.max(config.min_window.as_millis() as u64);
+    (record.executed_at - chrono::Duration::milliseconds(window_ms as i64), record.executed_at)
+}
+
+/// Computes VWAP and time-sliced TWAP benchmarks for a filled record from window ticks
+pub fn compute_benchmark(
+    record: &ExecutionRecord,
+    ticks: &[MarketTick],
+    config: &BenchmarkConfig,
+    now: DateTime<Utc>,
+) -> ExecutionBenchmark {
+    let (window_start, window_end) = execution_window(record, config);
+    let window_ms = (window_end - window_start).num_milliseconds().max(1);
+    let slice_ms = (config.slice.as_millis() as i64).max(1);
+    let slice_count = ((window_ms + slice_ms - 1) / slice_ms) as usize;
+
+    let mut notional = Decimal::ZERO;
+    let mut volume = Decimal::ZERO;
+    let mut slices: Vec<(Decimal, u64)> = vec![(Decimal::ZERO, 0); slice_count];
+    let mut tick_count = 0u64;
+    for tick in ticks.iter().filter(|tick| tick.timestamp >= window_start && tick.timestamp <= window_end) {
+        notional += tick.price * tick.volume;
+        volume += tick.volume;
+        let index = ((tick.timestamp - window_start).num_milliseconds() / slice_ms) as usize;
+        let slice = &mut slices[index.min(slice_count - 1)];
+        slice.0 += tick.price;
+        slice.1 += 1;
+        tick_count += 1;
+    }
+
+    let vwap = (!volume.is_zero()).then(|| notional / volume);
+    let slice_prices: Vec<Decimal> = slices
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(sum, count)| sum / Decimal::from(*count))
+        .collect();
+    let twap = (!slice_prices.is_empty())
+        .then(|| slice_prices.iter().sum::<Decimal>() / Decimal::from(slice_prices.len() as u64));
+    let coverage = (Decimal::from(slice_prices.len() as u64) / Decimal::from(slice_count as u64)).round_dp(4);
+
+    ExecutionBenchmark {
+        window_start,
+        window_end,
+        vwap_deviation_bps: deviation_bps(record, vwap),
+        twap_deviation_bps: deviation_bps(record, twap),
+        vwap: vwap.map(|price| price.round_dp(8)),
+        twap: twap.map(|price| price.round_dp(8)),
+        tick_count,
+        coverage,
+        low_coverage: coverage < config.min_coverage,
+        computed_at: now,
+    }
+}
+
+/// Adverse distance of the executed price from a benchmark, in basis points
+fn deviation_bps(record: &ExecutionRecord, benchmark: Option<Decimal>) -> Option<Decimal> {
+    let executed = record.executed_price?;
+    let benchmark = benchmark.filter(|price| !price.is_zero())?;
+    let adverse = match record.side {
+        TradeSide::Buy => executed - benchmark,
+        TradeSide::Sell => benchmark - executed,
+    };
+    Some((adverse / benchmark * BPS_PER_UNIT).round_dp(2))
+}
+
+/// Benchmarks filled executions after a short delay and stores the result on the record
+pub struct BenchmarkService {
+    config: BenchmarkConfig,
+    ticks: Arc<dyn MarketTickSource>,
+    store: Arc<dyn ExecutionStatsStore>,
+}
+
+impl std::fmt::Debug for BenchmarkService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BenchmarkService")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl BenchmarkService {
+    pub fn new(
+        config: BenchmarkConfig,
+        ticks: Arc<dyn MarketTickSource>,
+        store: Arc<dyn ExecutionStatsStore>,
+    ) -> Self {
+        Self { config, ticks, store }
+    }
+
+    /// Loads window ticks, computes the benchmark and stores it on the execution record
+    pub async fn benchmark(&self, record: &ExecutionRecord, now: DateTime<Utc>) -> Result<ExecutionBenchmark, StatsError> {
+        let (from, to) = execution_window(record, &self.config);
+        let ticks = self.ticks.ticks(&record.trading_pair, from, to).await?;
+        let benchmark = compute_benchmark(record, &ticks, &self.config, now);
+        self.store.record_benchmark(record.id, &benchmark).await?;
+
+        counter!(format!("{}.computed", METRICS_PREFIX), 1, "low_coverage" => benchmark.low_coverage.to_string());
+        Ok(benchmark)
+    }
+
+    /// Benchmarks a filled record once the configured delay has passed
+    pub fn schedule(self: Arc<Self>, record: ExecutionRecord) -> Option<tokio::task::JoinHandle<()>> {
+        if !record.filled() {
+            return None;
+        }
+        Some(tokio::spawn(async move {
+            tokio::time::sleep(self.config.delay).await;
+            match self.benchmark(&record, Utc::now()).await {
+                Ok(benchmark) => debug!(
+                    execution_id = %record.id,
+                    coverage = %benchmark.coverage,
+                    "Execution benchmarked"
+                ),
+                Err(e) => warn!(execution_id = %record.id, "Failed to benchmark execution: {}", e),
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    fn fill(price: Decimal, executed_at: DateTime<Utc>) -> ExecutionRecord {
+        ExecutionRecord {
+            id: Uuid::new_v4(),
+            trading_pair: "SOL/USDC".to_string(),
+            exchange: "jupiter".to_string(),
+            side: TradeSide::Buy,
+            requested_size: dec!(10),
+            filled_size: dec!(10),
+            expected_price: dec!(101.5),
+            executed_price: Some(price),
+            fee: Decimal::ZERO,
+            latency_ms: 400,
+            mev_value: Decimal::ZERO,
+            executed_at,
+            benchmark: None,
+            book_depth: None,
+            volatility_bps: None,
+            compute: None,
+            event_id: None,
+        }
+    }
+
+    /// One tick mid-way through each of the first `slices` 5s slices of the 60s window:
+    /// 100 with volume 1 in the first half, 102 with volume 3 in the second
+    fn tick_series(window_start: DateTime<Utc>, slices: i64) -> Vec<MarketTick> {
+        (0..slices)
+            .map(|i| MarketTick {
+                price: if i < 6 { dec!(100) } else { dec!(102) },
+                volume: if i < 6 { dec!(1) } else { dec!(3) },
+                timestamp: window_start + chrono::Duration::milliseconds(2_500 + 5_000 * i),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_vwap_deviation_from_known_ticks() {
+        let now = Utc::now();
+        let config = BenchmarkConfig::default();
+        // VWAP = (6 * 100 + 18 * 102) / 24 = 101.5; a buy at 101.6015 is 10 bps worse
+        let record = fill(dec!(101.6015), now);
+        let (window_start, _) = execution_window(&record, &config);
+
+        let benchmark = compute_benchmark(&record, &tick_series(window_start, 12), &config, now);
+        assert_eq!(benchmark.window_start, now - chrono::Duration::seconds(60));
+        assert_eq!(benchmark.tick_count, 12);
+        assert_eq!(benchmark.vwap, Some(dec!(101.5)));
+        assert_eq!(benchmark.vwap_deviation_bps, Some(dec!(10)));
+        // Equal time in each price, so TWAP is the midpoint
+        assert_eq!(benchmark.twap, Some(dec!(101)));
+        assert_eq!(benchmark.twap_deviation_bps, Some(dec!(59.55)));
+        assert_eq!(benchmark.coverage, dec!(1));
+        assert!(!benchmark.low_coverage);
+    }
+
+    #[test]
+    fn test_collector_outage_flags_low_coverage() {
+        let now = Utc::now();
+        let config = BenchmarkConfig::default();
+        let record = fill(dec!(100), now);
+        let (window_start, _) = execution_window(&record, &config);
+
+        // Ticks stop half-way through the window
+        let benchmark = compute_benchmark(&record, &tick_series(window_start, 6), &config, now);
+        assert_eq!(benchmark.vwap, Some(dec!(100)));
+        assert_eq!(benchmark.vwap_deviation_bps, Some(dec!(0)));
+        assert_eq!(benchmark.coverage, dec!(0.5));
+        assert!(benchmark.low_coverage);
+
+        let benchmark = compute_benchmark(&record, &[], &config, now);
+        assert_eq!(benchmark.vwap, None);
+        assert_eq!(benchmark.twap_deviation_bps, None);
+        assert_eq!(benchmark.coverage, dec!(0));
+        assert!(benchmark.low_coverage);
+    }
+}
@@ -0,0 +1,331 @@
+//! Order book integrity tracking against venue-provided sequence numbers and checksums.
+//! A gap in the sequence or repeated checksum mismatches mark a book degraded until it is
+//! resynchronised from a venue REST snapshot.
+//!
+//! Version dependencies:
+//! - reqwest = "0.11"
+//! - rust_decimal = "1.30"
+//! - serde = "1.0"
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::execution_engine::order_book::OrderBookError;
+use crate::models::market::{OrderBook, OrderBookLevel};
+use crate::utils::http::{self, HttpClient};
+
+// Book sync constants
+const DEFAULT_CHECKSUM_ESCALATION: u32 = 3;
+const DEFAULT_RESYNC_BACKOFF: Duration = Duration::from_secs(1);
+const SNAPSHOT_FETCH_TIMEOUT_MS: u64 = 2000;
+/// Levels per side covered by the book checksum
+pub const CHECKSUM_DEPTH: usize = 25;
+
+/// Integrity of a live order book
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookHealth {
+    Healthy,
+    /// A sequence gap or repeated checksum failures left the book untrusted until resync
+    Degraded,
+}
+
+/// Sequence metadata carried by a venue depth update
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BookSequence {
+    pub sequence: u64,
+    /// CRC32 of the top levels as computed by the venue, when it publishes one
+    pub checksum: Option<u32>,
+}
+
+/// Thresholds for checksum escalation and resync retries
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookSyncConfig {
+    /// Consecutive checksum mismatches before the book is degraded and resynced
+    pub checksum_escalation: u32,
+    /// Minimum wait between snapshot refreshes for one pair
+    pub resync_backoff: Duration,
+    /// Impact added to routes priced on a degraded book; `None` refuses to route on it
+    pub degraded_impact_penalty_bps: Option<Decimal>,
+}
+
+impl Default for BookSyncConfig {
+    fn default() -> Self {
+        Self {
+            checksum_escalation: DEFAULT_CHECKSUM_ESCALATION,
+            resync_backoff: DEFAULT_RESYNC_BACKOFF,
+            degraded_impact_penalty_bps: None,
+        }
+    }
+}
+
+/// Outcome of checking an update's sequence against the last one applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceCheck {
+    InOrder,
+    /// Already applied or superseded by a snapshot
+    Stale,
+    Gap { expected: u64, received: u64 },
+}
+
+/// Outcome of validating an update's checksum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumCheck {
+    Valid,
+    Mismatch { consecutive: u32 },
+    /// Mismatches reached the escalation threshold
+    Escalated { consecutive: u32 },
+}
+
+/// Per-pair sequence, checksum and health state
+#[derive(Debug, Clone)]
+pub struct BookSync {
+    last_sequence: Option<u64>,
+    health: BookHealth,
+    checksum_failures: u32,
+    last_resync: Option<Instant>,
+}
+
+impl Default for BookSync {
+    fn default() -> Self {
+        Self {
+            last_sequence: None,
+            health: BookHealth::Healthy,
+            checksum_failures: 0,
+            last_resync: None,
+        }
+    }
+}
+
+impl BookSync {
+    pub fn health(&self) -> BookHealth {
+        self.health
+    }
+
+    pub fn last_sequence(&self) -> Option<u64> {
+        self.last_sequence
+    }
+
+    /// Checks an incoming sequence; gaps degrade the book and in-order updates advance it
+    pub fn observe(&mut self, sequence: u64) -> SequenceCheck {
+        let check = match self.last_sequence {
+            None => SequenceCheck::InOrder,
+            Some(last) if sequence <= last => SequenceCheck::Stale,
+            Some(last) if sequence == last + 1 => SequenceCheck::InOrder,
+            Some(last) => SequenceCheck::Gap { expected: last + 1, received: sequence },
+        };
+        match check {
+            SequenceCheck::InOrder => self.last_sequence = Some(sequence),
+            SequenceCheck::Gap { .. } => self.health = BookHealth::Degraded,
+            SequenceCheck::Stale => {}
+        }
+        check
+    }
+
+    /// Records a checksum comparison, escalating after the configured consecutive failures
+    pub fn record_checksum(&mut self, valid: bool, config: &BookSyncConfig) -> ChecksumCheck {
+        if valid {
+            self.checksum_failures = 0;
+            return ChecksumCheck::Valid;
+        }
+        self.checksum_failures += 1;
+        if self.checksum_failures >= config.checksum_escalation {
+            self.health = BookHealth::Degraded;
+            ChecksumCheck::Escalated { consecutive: self.checksum_failures }
+        } else {
+            ChecksumCheck::Mismatch { consecutive: self.checksum_failures }
+        }
+    }
+
+    /// Whether a snapshot refresh may be attempted now
+    pub fn resync_due(&self, config: &BookSyncConfig) -> bool {
+        self.health == BookHealth::Degraded
+            && self.last_resync.map_or(true, |at| at.elapsed() >= config.resync_backoff)
+    }
+
+    pub fn resync_started(&mut self) {
+        self.last_resync = Some(Instant::now());
+    }
+
+    /// Re-anchors the sequence on a venue snapshot and marks the book healthy again
+    pub fn resynced(&mut self, sequence: u64) {
+        self.last_sequence = Some(sequence);
+        self.health = BookHealth::Healthy;
+        self.checksum_failures = 0;
+    }
+}
+
+/// CRC32 over the top `CHECKSUM_DEPTH` levels, interleaved bid then ask as `price:volume`
+pub fn book_checksum(book: &OrderBook) -> u32 {
+    let (bids, asks) = book.levels(CHECKSUM_DEPTH);
+    let mut fields = Vec::with_capacity((bids.len() + asks.len()) * 2);
+    for i in 0..bids.len().max(asks.len()) {
+        for level in [bids.get(i), asks.get(i)].into_iter().flatten() {
+            fields.push(level.price().normalize().to_string());
+            fields.push(level.volume().normalize().to_string());
+        }
+    }
+    crc32(fields.join(":").as_bytes())
+}
+
+/// CRC-32 (IEEE) as used by venue book checksums
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Full-depth venue snapshot used to resync a degraded book
+#[derive(Debug, Clone)]
+pub struct BookSnapshot {
+    pub book: OrderBook,
+    pub sequence: u64,
+}
+
+/// Venue REST source of full order book snapshots
+#[async_trait]
+pub trait BookSnapshotSource: Send + Sync + std::fmt::Debug {
+    async fn fetch_snapshot(&self, trading_pair: &str) -> Result<BookSnapshot, OrderBookError>;
+}
+
+#[derive(Debug, Deserialize)]
+struct RestLevel {
+    price: Decimal,
+    size: Decimal,
+}
+
+#[derive(Debug, Deserialize)]
+struct RestSnapshot {
+    bids: Vec<RestLevel>,
+    asks: Vec<RestLevel>,
+    /// Sequence the snapshot is consistent with; Drift publishes the slot
+    slot: u64,
+}
+
+/// Fetches L2 snapshots from a venue REST endpoint
+#[derive(Debug)]
+pub struct RestSnapshotSource {
+    exchange: String,
+    base_url: String,
+    http: Arc<HttpClient>,
+}
+
+impl RestSnapshotSource {
+    pub fn new(exchange: impl Into<String>, base_url: impl Into<String>) -> Self {
+        Self {
+            exchange: exchange.into(),
+            base_url: base_url.into(),
+            http: http::shared(),
+        }
+    }
+
+    pub fn with_http(mut self, http: Arc<HttpClient>) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+#[async_trait]
+impl BookSnapshotSource for RestSnapshotSource {
+    async fn fetch_snapshot(&self, trading_pair: &str) -> Result<BookSnapshot, OrderBookError> {
+        let snapshot: RestSnapshot = self
+            .http
+            .get(
+                &format!("{}/l2", self.base_url.trim_end_matches('/')),
+                Some(Duration::from_millis(SNAPSHOT_FETCH_TIMEOUT_MS)),
+                |request| request.query(&[("marketName", trading_pair)]),
+            )
+            .await
+            .map_err(|e| OrderBookError::UpdateError(format!("snapshot fetch failed: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| OrderBookError::UpdateError(format!("invalid snapshot: {}", e)))?;
+
+        let levels = |levels: Vec<RestLevel>| {
+            levels
+                .into_iter()
+                .map(|level| OrderBookLevel::new(level.price, level.size))
+                .collect()
+        };
+        let book = OrderBook::new(
+            trading_pair.to_string(),
+            self.exchange.clone(),
+            levels(snapshot.bids),
+            levels(snapshot.asks),
+        )?;
+        Ok(BookSnapshot { book, sequence: snapshot.slot })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_dropped_sequence_degrades_until_resync() {
+        let config = BookSyncConfig::default();
+        let mut sync = BookSync::default();
+
+        assert_eq!(sync.observe(1), SequenceCheck::InOrder);
+        assert_eq!(sync.observe(2), SequenceCheck::InOrder);
+        assert_eq!(sync.observe(2), SequenceCheck::Stale);
+
+        // Sequence 3 is dropped on the wire
+        assert_eq!(sync.observe(4), SequenceCheck::Gap { expected: 3, received: 4 });
+        assert_eq!(sync.health(), BookHealth::Degraded);
+        assert!(sync.resync_due(&config));
+
+        // The gap is never filled, so later updates cannot heal the book on their own
+        assert!(matches!(sync.observe(5), SequenceCheck::Gap { .. }));
+        assert_eq!(sync.health(), BookHealth::Degraded);
+
+        // A snapshot at 5 re-anchors the stream
+        sync.resync_started();
+        sync.resynced(5);
+        assert_eq!(sync.health(), BookHealth::Healthy);
+        assert_eq!(sync.observe(5), SequenceCheck::Stale);
+        assert_eq!(sync.observe(6), SequenceCheck::InOrder);
+        assert_eq!(sync.last_sequence(), Some(6));
+        assert!(!sync.resync_due(&config));
+    }
+
+    #[test]
+    fn test_checksum_failures_escalate() {
+        let config = BookSyncConfig { checksum_escalation: 2, ..BookSyncConfig::default() };
+        let mut sync = BookSync::default();
+
+        assert_eq!(sync.record_checksum(false, &config), ChecksumCheck::Mismatch { consecutive: 1 });
+        assert_eq!(sync.record_checksum(true, &config), ChecksumCheck::Valid);
+        assert_eq!(sync.record_checksum(false, &config), ChecksumCheck::Mismatch { consecutive: 1 });
+        assert_eq!(sync.health(), BookHealth::Healthy);
+        assert_eq!(sync.record_checksum(false, &config), ChecksumCheck::Escalated { consecutive: 2 });
+        assert_eq!(sync.health(), BookHealth::Degraded);
+    }
+
+    #[test]
+    fn test_book_checksum_covers_levels() {
+        let book = |ask_volume| {
+            OrderBook::new(
+                "SOL/USDC".to_string(),
+                "drift".to_string(),
+                vec![OrderBookLevel::new(dec!(99.50), dec!(10))],
+                vec![OrderBookLevel::new(dec!(100.5), ask_volume)],
+            )
+            .unwrap()
+        };
+        assert_eq!(crc32(b"99.5:10:100.5:4"), book_checksum(&book(dec!(4))));
+        assert_ne!(book_checksum(&book(dec!(4))), book_checksum(&book(dec!(5))));
+        // Standard CRC-32 check value
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+}
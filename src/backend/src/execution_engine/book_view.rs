@@ -0,0 +1,272 @@
+//! Read-optimized per-pair order book views for strategies. Every accepted update builds
+//! an immutable `BookView` with the top levels and derived prices precomputed and swaps it
+//! into the pair's slot, so a strategy holding the slot reads the latest book with a single
+//! atomic pointer load instead of locking and cloning the full-depth book.
+//!
+//! Version dependencies:
+//! - arc-swap = "1.6"
+//! - dashmap = "5.5"
+//! - rust_decimal = "1.30"
+
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::models::market::{OrderBook, OrderBookLevel};
+use crate::utils::time::is_valid_market_timestamp;
+
+// View constants
+/// Levels kept per side in each view
+pub const BOOK_VIEW_DEPTH: usize = 20;
+const BPS_PER_UNIT: Decimal = Decimal::from_parts(10_000, 0, 0, false, 0);
+const TWO: Decimal = Decimal::TWO;
+
+/// Immutable top-of-book view with derived prices computed once per update
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BookView {
+    pub trading_pair: String,
+    pub exchange: String,
+    /// Best bid first
+    pub bids: Vec<OrderBookLevel>,
+    /// Best ask first
+    pub asks: Vec<OrderBookLevel>,
+    pub mid: Option<Decimal>,
+    /// Top-of-book price weighted toward the side with less resting volume
+    pub microprice: Option<Decimal>,
+    pub spread: Option<Decimal>,
+    pub spread_bps: Option<Decimal>,
+    /// Volume resting across the view's bid levels
+    pub bid_depth: Decimal,
+    /// Volume resting across the view's ask levels
+    pub ask_depth: Decimal,
+    pub bid_notional: Decimal,
+    pub ask_notional: Decimal,
+    /// Timestamp of the source book update
+    pub timestamp: DateTime<Utc>,
+    /// Set while the source book awaits a resync after a sequence gap or checksum failures
+    pub degraded: bool,
+}
+
+impl BookView {
+    /// Builds a view from the top `depth` levels of a book, in O(depth)
+    pub fn from_book(book: &OrderBook, depth: usize, degraded: bool) -> Self {
+        let (bids, asks) = book.levels(depth);
+        let (bid_depth, bid_notional) = aggregate(&bids);
+        let (ask_depth, ask_notional) = aggregate(&asks);
+
+        let (mut mid, mut microprice, mut spread, mut spread_bps) = (None, None, None, None);
+        if let (Some(bid), Some(ask)) = (bids.first(), asks.first()) {
+            let mid_price = (bid.price() + ask.price()) / TWO;
+            let top_volume = bid.volume() + ask.volume();
+            mid = Some(mid_price);
+            spread = Some(ask.price() - bid.price());
+            if mid_price > Decimal::ZERO {
+                spread_bps = Some((ask.price() - bid.price()) / mid_price * BPS_PER_UNIT);
+            }
+            microprice = Some(if top_volume > Decimal::ZERO {
+                (bid.price() * ask.volume() + ask.price() * bid.volume()) / top_volume
+            } else {
+                mid_price
+            });
+        }
+
+        Self {
+            trading_pair: book.trading_pair().to_string(),
+            exchange: book.exchange().to_string(),
+            bids,
+            asks,
+            mid,
+            microprice,
+            spread,
+            spread_bps,
+            bid_depth,
+            ask_depth,
+            bid_notional,
+            ask_notional,
+            timestamp: book.timestamp(),
+            degraded,
+        }
+    }
+
+    pub fn best_bid(&self) -> Option<&OrderBookLevel> {
+        self.bids.first()
+    }
+
+    pub fn best_ask(&self) -> Option<&OrderBookLevel> {
+        self.asks.first()
+    }
+
+    /// Whether the source update is too old to trade on
+    pub fn is_stale(&self) -> bool {
+        !is_valid_market_timestamp(self.timestamp)
+    }
+}
+
+// Total volume and notional across levels
+fn aggregate(levels: &[OrderBookLevel]) -> (Decimal, Decimal) {
+    levels.iter().fold((Decimal::ZERO, Decimal::ZERO), |(volume, notional), level| {
+        (volume + level.volume(), notional + level.price() * level.volume())
+    })
+}
+
+/// Shared slot holding a pair's latest view; `load` is a single atomic pointer read
+pub type BookViewHandle = Arc<ArcSwap<BookView>>;
+
+/// Latest view per trading pair, replaced wholesale on every book change
+#[derive(Debug)]
+pub struct BookViews {
+    views: DashMap<String, BookViewHandle>,
+    depth: usize,
+}
+
+impl Default for BookViews {
+    fn default() -> Self {
+        Self::new(BOOK_VIEW_DEPTH)
+    }
+}
+
+impl BookViews {
+    pub fn new(depth: usize) -> Self {
+        Self {
+            views: DashMap::new(),
+            depth,
+        }
+    }
+
+    /// Builds and stores a new view of `book`, creating the pair's slot on first use
+    pub fn publish(&self, book: &OrderBook, degraded: bool) -> Arc<BookView> {
+        let view = Arc::new(BookView::from_book(book, self.depth, degraded));
+        match self.views.get(book.trading_pair()) {
+            Some(handle) => handle.store(view.clone()),
+            None => {
+                self.views
+                    .entry(book.trading_pair().to_string())
+                    .and_modify(|handle| handle.store(view.clone()))
+                    .or_insert_with(|| Arc::new(ArcSwap::new(view.clone())));
+            }
+        }
+        view
+    }
+
+    /// Flags the pair's current view as degraded without waiting for the next update
+    pub fn mark_degraded(&self, trading_pair: &str) {
+        if let Some(handle) = self.views.get(trading_pair) {
+            handle.rcu(|view| {
+                if view.degraded {
+                    view.clone()
+                } else {
+                    Arc::new(BookView {
+                        degraded: true,
+                        ..BookView::clone(view)
+                    })
+                }
+            });
+        }
+    }
+
+    /// Slot a strategy can hold to read the pair's latest view without further lookups
+    pub fn handle(&self, trading_pair: &str) -> Option<BookViewHandle> {
+        self.views.get(trading_pair).map(|handle| handle.value().clone())
+    }
+
+    /// Latest view for a pair
+    pub fn load(&self, trading_pair: &str) -> Option<Arc<BookView>> {
+        self.views.get(trading_pair).map(|handle| handle.load_full())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use rust_decimal_macros::dec;
+
+    fn random_book(rng: &mut StdRng) -> OrderBook {
+        let mid: i64 = rng.gen_range(5_000..15_000);
+        let level = |rng: &mut StdRng, price: i64| {
+            OrderBookLevel::new(Decimal::new(price, 2), Decimal::new(rng.gen_range(1..10_000), 3))
+        };
+        let bids = (1..=rng.gen_range(0..60)).map(|i| level(rng, mid - i)).collect();
+        let asks = (1..=rng.gen_range(0..60)).map(|i| level(rng, mid + i)).collect();
+        OrderBook::new("SOL/USDC".to_string(), "jupiter".to_string(), bids, asks).unwrap()
+    }
+
+    #[test]
+    fn test_view_derives_top_of_book() {
+        let book = OrderBook::new(
+            "SOL/USDC".to_string(),
+            "jupiter".to_string(),
+            vec![OrderBookLevel::new(dec!(99), dec!(30)), OrderBookLevel::new(dec!(98), dec!(10))],
+            vec![OrderBookLevel::new(dec!(101), dec!(10)), OrderBookLevel::new(dec!(103), dec!(10))],
+        )
+        .unwrap();
+        let view = BookView::from_book(&book, 1, false);
+
+        assert_eq!(view.bids.len(), 1);
+        assert_eq!(view.mid, Some(dec!(100)));
+        assert_eq!(view.spread, Some(dec!(2)));
+        assert_eq!(view.spread_bps, Some(dec!(200)));
+        // Heavier bid volume pulls the microprice toward the ask
+        assert_eq!(view.microprice, Some(dec!(100.5)));
+        assert_eq!(view.bid_depth, dec!(30));
+        assert_eq!(view.ask_notional, dec!(1010));
+    }
+
+    #[test]
+    fn test_views_match_source_after_random_updates() {
+        let mut rng = StdRng::seed_from_u64(1888);
+        let views = BookViews::default();
+        let mut handle = None;
+
+        for _ in 0..500 {
+            let book = random_book(&mut rng);
+            let degraded = rng.gen_bool(0.1);
+            views.publish(&book, degraded);
+            let handle = handle.get_or_insert_with(|| views.handle("SOL/USDC").unwrap());
+            let view = handle.load();
+
+            let (bids, asks) = book.levels(BOOK_VIEW_DEPTH);
+            assert_eq!(view.bids, bids);
+            assert_eq!(view.asks, asks);
+            assert_eq!(view.degraded, degraded);
+            assert_eq!(view.timestamp, book.timestamp());
+            assert_eq!(view.spread, book.get_spread().unwrap());
+            assert_eq!(view.bid_depth, bids.iter().map(|l| l.volume()).sum::<Decimal>());
+            assert_eq!(view.ask_depth, asks.iter().map(|l| l.volume()).sum::<Decimal>());
+            match (bids.first(), asks.first()) {
+                (Some(bid), Some(ask)) => {
+                    assert_eq!(view.mid, Some((bid.price() + ask.price()) / dec!(2)));
+                    let micro = view.microprice.unwrap();
+                    assert!(micro >= bid.price() && micro <= ask.price());
+                }
+                _ => {
+                    assert_eq!(view.mid, None);
+                    assert_eq!(view.microprice, None);
+                }
+            }
+            assert_eq!(**view, *views.load("SOL/USDC").unwrap());
+        }
+    }
+
+    #[test]
+    fn test_mark_degraded_keeps_levels() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let views = BookViews::default();
+        let book = random_book(&mut rng);
+        let before = views.publish(&book, false);
+
+        views.mark_degraded("SOL/USDC");
+        let after = views.load("SOL/USDC").unwrap();
+        assert!(after.degraded);
+        assert_eq!(after.bids, before.bids);
+
+        views.publish(&book, false);
+        assert!(!views.load("SOL/USDC").unwrap().degraded);
+        assert!(views.load("BTC/USDC").is_none());
+    }
+}
@@ -0,0 +1,412 @@
+//! Compute unit budgeting for transactions the adapters build. A route is simulated to
+//! estimate its compute units, given an explicit compute unit limit with a safety margin and
+//! priced with the priority fee estimator, and rejected before submission when the estimate
+//! exceeds the venue or transaction ceiling. Units consumed by landed transactions tighten
+//! each venue's margin over time.
+//!
+//! Version dependencies:
+//! - solana-sdk = "1.16"
+//! - solana-client = "1.17"
+//! - parking_lot = "0.12"
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use metrics::{counter, histogram};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+use tracing::{debug, warn};
+
+use crate::execution_engine::error::{map_solana_error, slippage_rejection, ExecutionError};
+use crate::utils::solana::{FeeEstimator, FeeUrgency, SolanaClient, SolanaError};
+
+// Compute budget constants
+const METRICS_PREFIX: &str = "trading_bot.compute_budget";
+/// Most compute units a single transaction may request
+pub const MAX_TRANSACTION_COMPUTE_UNITS: u32 = 1_400_000;
+const BPS_PER_UNIT: u64 = 10_000;
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+const DEFAULT_MARGIN_BPS: u64 = 2_000;
+const MIN_MARGIN_BPS: u64 = 500;
+const MAX_MARGIN_BPS: u64 = 5_000;
+const REALIZED_WINDOW: usize = 50;
+const MIN_REALIZED_SAMPLES: usize = 10;
+const DEFAULT_UNIT_PRICE_MICRO_LAMPORTS: u64 = 10_000;
+
+/// Estimates and measures the compute units transactions consume
+#[async_trait]
+pub trait ComputeMeter: Send + Sync {
+    /// Units the transaction consumes in simulation
+    async fn simulate_units(&self, transaction: &Transaction) -> Result<u64, ExecutionError>;
+    /// Units a landed transaction consumed, from its status meta
+    async fn consumed_units(&self, signature: &str) -> Result<Option<u64>, ExecutionError>;
+}
+
+#[async_trait]
+impl ComputeMeter for SolanaClient {
+    async fn simulate_units(&self, transaction: &Transaction) -> Result<u64, ExecutionError> {
+        let simulation = self.simulate_transaction(transaction).await.map_err(meter_error)?;
+        let logs = simulation.logs.unwrap_or_default();
+        if let Some(err) = simulation.err {
+            return Err(slippage_rejection(&logs)
+                .unwrap_or_else(|| ExecutionError::ValidationError(format!("route simulation failed: {}", err))));
+        }
+        simulation
+            .units_consumed
+            .ok_or_else(|| ExecutionError::InternalError("simulation reported no compute units".to_string()))
+    }
+
+    async fn consumed_units(&self, signature: &str) -> Result<Option<u64>, ExecutionError> {
+        self.get_compute_units_consumed(signature).await.map_err(meter_error)
+    }
+}
+
+fn meter_error(error: SolanaError) -> ExecutionError {
+    match error {
+        SolanaError::ClientError(e) => map_solana_error(e),
+        other => ExecutionError::InternalError(other.to_string()),
+    }
+}
+
+/// Compute unit ceilings and safety margins
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComputeBudgetConfig {
+    /// Margin over the simulated units before any realized consumption is known
+    pub default_margin_bps: u64,
+    /// Bounds on the margin once realized consumption tunes it
+    pub min_margin_bps: u64,
+    pub max_margin_bps: u64,
+    /// Ceiling for a transaction on any venue
+    pub transaction_ceiling: u32,
+    /// Tighter per-venue ceilings, e.g. to leave room for bundled tip transactions
+    pub venue_ceilings: HashMap<String, u32>,
+    /// Compute unit price bid when no fee estimator is attached
+    pub default_unit_price: u64,
+}
+
+impl Default for ComputeBudgetConfig {
+    fn default() -> Self {
+        Self {
+            default_margin_bps: DEFAULT_MARGIN_BPS,
+            min_margin_bps: MIN_MARGIN_BPS,
+            max_margin_bps: MAX_MARGIN_BPS,
+            transaction_ceiling: MAX_TRANSACTION_COMPUTE_UNITS,
+            venue_ceilings: HashMap::new(),
+            default_unit_price: DEFAULT_UNIT_PRICE_MICRO_LAMPORTS,
+        }
+    }
+}
+
+impl ComputeBudgetConfig {
+    pub fn with_venue_ceiling(mut self, exchange: impl Into<String>, ceiling: u32) -> Self {
+        self.venue_ceilings.insert(exchange.into(), ceiling);
+        self
+    }
+
+    fn ceiling(&self, exchange: &str) -> u32 {
+        self.venue_ceilings
+            .get(exchange)
+            .map_or(self.transaction_ceiling, |ceiling| (*ceiling).min(self.transaction_ceiling))
+    }
+}
+
+/// Compute unit limit and price set on a transaction, with the lamports they commit to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComputeBudget {
+    /// Units the route consumed in simulation
+    pub estimated_units: u64,
+    pub unit_limit: u32,
+    pub unit_price_micro_lamports: u64,
+    /// Priority fee paid if the whole limit is charged
+    pub priority_fee_lamports: u64,
+}
+
+impl ComputeBudget {
+    /// Limit and price instructions for the budget
+    pub fn instructions(&self) -> [Instruction; 2] {
+        [
+            ComputeBudgetInstruction::set_compute_unit_limit(self.unit_limit),
+            ComputeBudgetInstruction::set_compute_unit_price(self.unit_price_micro_lamports),
+        ]
+    }
+
+    /// Prepends the budget instructions to a transaction's instructions
+    pub fn apply(&self, instructions: Vec<Instruction>) -> Vec<Instruction> {
+        self.instructions().into_iter().chain(instructions).collect()
+    }
+}
+
+/// Compute unit limit a transaction carried and what it consumed on-chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComputeUsage {
+    pub unit_limit: u32,
+    pub units_consumed: Option<u64>,
+}
+
+/// Sizes compute budgets from simulation and tunes each venue's margin from realized usage
+pub struct ComputeBudgeter {
+    config: ComputeBudgetConfig,
+    meter: Arc<dyn ComputeMeter>,
+    payer: Pubkey,
+    fee_estimator: Option<Arc<FeeEstimator>>,
+    /// Recent overshoot of consumed over simulated units per venue, in bps
+    realized: Mutex<HashMap<String, VecDeque<u64>>>,
+}
+
+impl std::fmt::Debug for ComputeBudgeter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComputeBudgeter")
+            .field("config", &self.config)
+            .field("payer", &self.payer)
+            .finish()
+    }
+}
+
+impl ComputeBudgeter {
+    pub fn new(config: ComputeBudgetConfig, meter: Arc<dyn ComputeMeter>, payer: Pubkey) -> Self {
+        Self {
+            config,
+            meter,
+            payer,
+            fee_estimator: None,
+            realized: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Prices the compute unit limit at the sampled market rate instead of the default
+    pub fn with_fee_estimator(mut self, fee_estimator: Arc<FeeEstimator>) -> Self {
+        self.fee_estimator = Some(fee_estimator);
+        self
+    }
+
+    /// Margin currently added to simulated units on a venue
+    pub fn margin_bps(&self, exchange: &str) -> u64 {
+        let realized = self.realized.lock();
+        match realized.get(exchange) {
+            Some(samples) if samples.len() >= MIN_REALIZED_SAMPLES => samples
+                .iter()
+                .copied()
+                .max()
+                .unwrap_or_default()
+                .clamp(self.config.min_margin_bps, self.config.max_margin_bps),
+            _ => self.config.default_margin_bps,
+        }
+    }
+
+    /// Simulates a route and sizes its compute budget, rejecting it when the simulated units
+    /// exceed the ceiling
+    pub async fn plan(
+        &self,
+        exchange: &str,
+        instructions: &[Instruction],
+        urgency: FeeUrgency,
+    ) -> Result<ComputeBudget, ExecutionError> {
+        let ceiling = self.config.ceiling(exchange);
+
+        // Simulate at the ceiling so the default per-instruction limit does not cap the estimate
+        let mut simulated = vec![ComputeBudgetInstruction::set_compute_unit_limit(ceiling)];
+        simulated.extend_from_slice(instructions);
+        let transaction = Transaction::new_with_payer(&simulated, Some(&self.payer));
+        let estimated_units = self.meter.simulate_units(&transaction).await?;
+        histogram!(format!("{}.{}.estimated_units", METRICS_PREFIX, exchange), estimated_units as f64);
+
+        if estimated_units > u64::from(ceiling) {
+            counter!(format!("{}.{}.rejected", METRICS_PREFIX, exchange), 1);
+            warn!(exchange, estimated_units, ceiling, "Route exceeds the compute unit ceiling");
+            return Err(ExecutionError::ComputeBudgetExceeded {
+                exchange: exchange.to_string(),
+                estimated: estimated_units,
+                ceiling,
+            });
+        }
+
+        let margin = estimated_units * self.margin_bps(exchange) / BPS_PER_UNIT;
+        let unit_limit = (estimated_units + margin).min(u64::from(ceiling)) as u32;
+        let unit_price_micro_lamports = match &self.fee_estimator {
+            Some(fee_estimator) => fee_estimator.estimate_priority_fee(exchange, urgency),
+            None => self.config.default_unit_price,
+        };
+        let priority_fee_lamports = priority_fee_lamports(unit_limit, unit_price_micro_lamports);
+
+        debug!(
+            exchange,
+            estimated_units,
+            unit_limit,
+            unit_price_micro_lamports,
+            priority_fee_lamports,
+            "Planned compute budget"
+        );
+        Ok(ComputeBudget {
+            estimated_units,
+            unit_limit,
+            unit_price_micro_lamports,
+            priority_fee_lamports,
+        })
+    }
+
+    /// Records the units a transaction consumed against its simulated estimate
+    pub fn record_realized(&self, exchange: &str, budget: &ComputeBudget, units_consumed: u64) {
+        histogram!(format!("{}.{}.consumed_units", METRICS_PREFIX, exchange), units_consumed as f64);
+        if units_consumed >= u64::from(budget.unit_limit) {
+            counter!(format!("{}.{}.exhausted", METRICS_PREFIX, exchange), 1);
+            warn!(exchange, units_consumed, unit_limit = budget.unit_limit, "Transaction used its whole compute budget");
+        }
+        if budget.estimated_units == 0 {
+            return;
+        }
+
+        let overshoot_bps = units_consumed.saturating_sub(budget.estimated_units) * BPS_PER_UNIT / budget.estimated_units;
+        let mut realized = self.realized.lock();
+        let samples = realized.entry(exchange.to_string()).or_default();
+        if samples.len() == REALIZED_WINDOW {
+            samples.pop_front();
+        }
+        samples.push_back(overshoot_bps);
+    }
+
+    /// Looks up the units a landed transaction consumed and records them against its budget
+    pub async fn record_landed(&self, exchange: &str, budget: &ComputeBudget, signature: &str) -> ComputeUsage {
+        let units_consumed = match self.meter.consumed_units(signature).await {
+            Ok(units) => units,
+            Err(e) => {
+                warn!(exchange, signature, "Failed to read consumed compute units: {}", e);
+                None
+            }
+        };
+        if let Some(units) = units_consumed {
+            self.record_realized(exchange, budget, units);
+        }
+        ComputeUsage {
+            unit_limit: budget.unit_limit,
+            units_consumed,
+        }
+    }
+}
+
+/// Lamports a compute unit limit costs at a price in micro-lamports per unit, rounded up
+fn priority_fee_lamports(unit_limit: u32, unit_price_micro_lamports: u64) -> u64 {
+    let micro_lamports = u128::from(unit_limit) * u128::from(unit_price_micro_lamports);
+    ((micro_lamports + MICRO_LAMPORTS_PER_LAMPORT - 1) / MICRO_LAMPORTS_PER_LAMPORT) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::system_instruction;
+
+    /// Reports fixed simulated units and records the transactions it simulated
+    struct MockMeter {
+        units: u64,
+        consumed: Option<u64>,
+        simulated: Mutex<Vec<Transaction>>,
+    }
+
+    impl MockMeter {
+        fn new(units: u64) -> Arc<Self> {
+            Arc::new(Self {
+                units,
+                consumed: None,
+                simulated: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl ComputeMeter for MockMeter {
+        async fn simulate_units(&self, transaction: &Transaction) -> Result<u64, ExecutionError> {
+            self.simulated.lock().push(transaction.clone());
+            Ok(self.units)
+        }
+
+        async fn consumed_units(&self, _: &str) -> Result<Option<u64>, ExecutionError> {
+            Ok(self.consumed)
+        }
+    }
+
+    fn route(payer: &Pubkey) -> Vec<Instruction> {
+        vec![system_instruction::transfer(payer, &Pubkey::new_unique(), 1)]
+    }
+
+    #[tokio::test]
+    async fn test_limit_instruction_from_simulated_units() {
+        let payer = Pubkey::new_unique();
+        let meter = MockMeter::new(180_000);
+        let budgeter = ComputeBudgeter::new(ComputeBudgetConfig::default(), meter.clone(), payer);
+
+        let budget = budgeter.plan("jupiter", &route(&payer), FeeUrgency::Normal).await.unwrap();
+        // 20% default margin over the simulated units
+        assert_eq!(budget.estimated_units, 180_000);
+        assert_eq!(budget.unit_limit, 216_000);
+        assert_eq!(budget.priority_fee_lamports, 2_160);
+
+        let instructions = budget.apply(route(&payer));
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(instructions[0], ComputeBudgetInstruction::set_compute_unit_limit(216_000));
+        assert_eq!(instructions[1], ComputeBudgetInstruction::set_compute_unit_price(10_000));
+        assert_eq!(instructions[0].program_id, solana_sdk::compute_budget::id());
+
+        // The route was simulated with the limit raised to the ceiling
+        let simulated = &meter.simulated.lock()[0];
+        let limit = &simulated.message.instructions[0];
+        assert_eq!(
+            simulated.message.account_keys[limit.program_id_index as usize],
+            solana_sdk::compute_budget::id()
+        );
+        assert_eq!(
+            limit.data,
+            ComputeBudgetInstruction::set_compute_unit_limit(MAX_TRANSACTION_COMPUTE_UNITS).data
+        );
+    }
+
+    #[tokio::test]
+    async fn test_route_over_ceiling_rejected() {
+        let payer = Pubkey::new_unique();
+        let config = ComputeBudgetConfig::default().with_venue_ceiling("jupiter", 1_000_000);
+        let budgeter = ComputeBudgeter::new(config.clone(), MockMeter::new(1_200_000), payer);
+
+        let rejected = budgeter.plan("jupiter", &route(&payer), FeeUrgency::Normal).await;
+        assert!(matches!(
+            rejected,
+            Err(ExecutionError::ComputeBudgetExceeded { estimated: 1_200_000, ceiling: 1_000_000, .. })
+        ));
+
+        // Within the transaction ceiling elsewhere, with the margin capped at the ceiling
+        let budgeter = ComputeBudgeter::new(config, MockMeter::new(1_300_000), payer);
+        let budget = budgeter.plan("drift", &route(&payer), FeeUrgency::Normal).await.unwrap();
+        assert_eq!(budget.unit_limit, MAX_TRANSACTION_COMPUTE_UNITS);
+    }
+
+    #[tokio::test]
+    async fn test_realized_usage_tightens_margin() {
+        let payer = Pubkey::new_unique();
+        let meter = Arc::new(MockMeter {
+            units: 200_000,
+            consumed: Some(206_000),
+            simulated: Mutex::new(Vec::new()),
+        });
+        let budgeter = ComputeBudgeter::new(ComputeBudgetConfig::default(), meter, payer);
+        let budget = budgeter.plan("jupiter", &route(&payer), FeeUrgency::Normal).await.unwrap();
+
+        for _ in 0..MIN_REALIZED_SAMPLES - 1 {
+            budgeter.record_landed("jupiter", &budget, "sig").await;
+        }
+        assert_eq!(budgeter.margin_bps("jupiter"), DEFAULT_MARGIN_BPS);
+
+        // Landed transactions used at most 3% over simulation; the margin floor applies
+        let usage = budgeter.record_landed("jupiter", &budget, "sig").await;
+        assert_eq!(usage.units_consumed, Some(206_000));
+        assert_eq!(budgeter.margin_bps("jupiter"), MIN_MARGIN_BPS);
+
+        // One route running 8% over widens the margin to match
+        budgeter.record_realized("jupiter", &budget, 216_000);
+        assert_eq!(budgeter.margin_bps("jupiter"), 800);
+        let tightened = budgeter.plan("jupiter", &route(&payer), FeeUrgency::Normal).await.unwrap();
+        assert_eq!(tightened.unit_limit, 216_000);
+        assert_eq!(budgeter.margin_bps("drift"), DEFAULT_MARGIN_BPS);
+    }
+}
@@ -0,0 +1,614 @@
+//! Execution cost models calibrated from realized slippage. A weekly job regresses the slippage
+//! of filled executions against order size over the visible book depth they were planned on
+//! and the pair's volatility at the time, fitting one linear model per venue. Calibrated models
+//! carry a validity window and earlier ones are kept for comparison; pre-trade impact
+//! estimation uses the venue's latest valid model and falls back to walking the book.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::RwLock as SyncRwLock;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::execution_engine::stats::{ExecutionRecord, ExecutionStatsStore};
+use crate::jobs::{Job, JobContext, JobHandler, JobQueue};
+use crate::regime::RegimeDetector;
+
+// Cost model constants
+const METRICS_PREFIX: &str = "trading_bot.cost_models";
+const BPS_PER_UNIT: f64 = 10_000.0;
+const DEFAULT_LOOKBACK_DAYS: i64 = 30;
+const DEFAULT_MIN_SAMPLES: usize = 50;
+const DEFAULT_VALIDITY_DAYS: i64 = 14;
+const DEFAULT_RECALIBRATION_INTERVAL: Duration = Duration::from_secs(7 * 24 * 3600);
+/// Pivots below this mean the venue's features are collinear
+const MIN_PIVOT: f64 = 1e-9;
+
+/// Cost model error types
+#[derive(Error, Debug)]
+pub enum CostModelError {
+    #[error("{exchange} has {samples} calibration samples, {required} required")]
+    InsufficientSamples {
+        exchange: String,
+        samples: usize,
+        required: usize,
+    },
+    #[error("calibration samples for {0} do not determine a model")]
+    Degenerate(String),
+    #[error("store error: {0}")]
+    Store(String),
+}
+
+/// Linear slippage model of one venue:
+/// `intercept_bps + depth_coefficient * size / depth + volatility_coefficient * volatility_bps`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostModel {
+    pub id: Uuid,
+    pub exchange: String,
+    pub intercept_bps: f64,
+    /// Slippage in bps per unit of order size over visible depth
+    pub depth_coefficient: f64,
+    /// Slippage in bps per bps of per-candle volatility
+    pub volatility_coefficient: f64,
+    pub sample_count: u64,
+    pub r_squared: f64,
+    pub valid_from: DateTime<Utc>,
+    pub valid_until: DateTime<Utc>,
+    pub calibrated_at: DateTime<Utc>,
+}
+
+impl CostModel {
+    pub fn is_valid_at(&self, at: DateTime<Utc>) -> bool {
+        self.valid_from <= at && at < self.valid_until
+    }
+
+    /// Expected slippage in bps; price improvement is never planned on
+    pub fn estimate_bps(&self, depth_ratio: f64, volatility_bps: f64) -> f64 {
+        (self.intercept_bps
+            + self.depth_coefficient * depth_ratio
+            + self.volatility_coefficient * volatility_bps)
+            .max(0.0)
+    }
+}
+
+/// Which model produced an impact estimate
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ImpactModel {
+    /// Walking the visible book
+    #[default]
+    Static,
+    Calibrated {
+        model_id: Uuid,
+        calibrated_at: DateTime<Utc>,
+    },
+}
+
+/// Slippage of one filled execution with the conditions it was planned in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostSample {
+    /// Requested size over the visible depth on the side it executed against
+    pub depth_ratio: f64,
+    pub volatility_bps: f64,
+    pub slippage_bps: f64,
+}
+
+impl CostSample {
+    /// None for unfilled executions and ones recorded without depth or volatility
+    pub fn from_record(record: &ExecutionRecord) -> Option<Self> {
+        if !record.filled() {
+            return None;
+        }
+        let depth = record.book_depth.filter(|depth| *depth > Decimal::ZERO)?;
+        Some(Self {
+            depth_ratio: (record.requested_size / depth).to_f64()?,
+            volatility_bps: record.volatility_bps?.to_f64()?,
+            slippage_bps: record.slippage_bps()?.to_f64()?,
+        })
+    }
+}
+
+/// Ordinary least squares fit of slippage on depth ratio and volatility, returning the
+/// intercept, the two coefficients and the fit's coefficient of determination
+pub fn fit(samples: &[CostSample]) -> Option<([f64; 3], f64)> {
+    if samples.len() < 3 {
+        return None;
+    }
+
+    // Normal equations (XᵀX) β = Xᵀy over rows [1, depth_ratio, volatility_bps]
+    let mut xtx = [[0.0; 3]; 3];
+    let mut xty = [0.0; 3];
+    for sample in samples {
+        let row = [1.0, sample.depth_ratio, sample.volatility_bps];
+        for (i, xi) in row.iter().enumerate() {
+            for (j, xj) in row.iter().enumerate() {
+                xtx[i][j] += xi * xj;
+            }
+            xty[i] += xi * sample.slippage_bps;
+        }
+    }
+    let beta = solve(xtx, xty)?;
+
+    let mean = samples.iter().map(|sample| sample.slippage_bps).sum::<f64>() / samples.len() as f64;
+    let (mut residual, mut total) = (0.0, 0.0);
+    for sample in samples {
+        let predicted = beta[0] + beta[1] * sample.depth_ratio + beta[2] * sample.volatility_bps;
+        residual += (sample.slippage_bps - predicted).powi(2);
+        total += (sample.slippage_bps - mean).powi(2);
+    }
+    let r_squared = if total > 0.0 { (1.0 - residual / total).clamp(0.0, 1.0) } else { 0.0 };
+
+    Some((beta, r_squared))
+}
+
+/// Gaussian elimination with partial pivoting; None when the system is singular
+fn solve(mut a: [[f64; 3]; 3], mut b: [f64; 3]) -> Option<[f64; 3]> {
+    for col in 0..3 {
+        let pivot = (col..3).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < MIN_PIVOT {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..3 {
+            let factor = a[row][col] / a[col][col];
+            for k in col..3 {
+                a[row][k] -= factor * a[col][k];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    let mut x = [0.0; 3];
+    for row in (0..3).rev() {
+        let known: f64 = (row + 1..3).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - known) / a[row][row];
+    }
+    x.iter().all(|value| value.is_finite()).then_some(x)
+}
+
+/// Persistence for calibrated cost models
+#[async_trait]
+pub trait CostModelStore: Send + Sync {
+    async fn save(&self, model: &CostModel) -> Result<(), CostModelError>;
+    /// Most recently calibrated model of every venue valid at `at`
+    async fn current(&self, at: DateTime<Utc>) -> Result<Vec<CostModel>, CostModelError>;
+    /// Models of one venue, or of every venue, newest first
+    async fn history(&self, exchange: Option<&str>, limit: usize) -> Result<Vec<CostModel>, CostModelError>;
+}
+
+/// Recalibrates the cost models of every venue
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostModelCalibration {
+    /// Operator who requested an on-demand run; None for the weekly schedule
+    #[serde(default)]
+    pub requested_by: Option<String>,
+}
+
+impl Job for CostModelCalibration {
+    const KIND: &'static str = "execution.cost_model_calibration";
+}
+
+/// Calibration window and schedule
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostModelConfig {
+    /// How far back realized executions are drawn from
+    pub lookback: chrono::Duration,
+    /// Venues with fewer usable executions keep their previous model
+    pub min_samples: usize,
+    /// How long a calibrated model is used for
+    pub validity: chrono::Duration,
+    pub recalibration_interval: Duration,
+}
+
+impl Default for CostModelConfig {
+    fn default() -> Self {
+        Self {
+            lookback: chrono::Duration::days(DEFAULT_LOOKBACK_DAYS),
+            min_samples: DEFAULT_MIN_SAMPLES,
+            validity: chrono::Duration::days(DEFAULT_VALIDITY_DAYS),
+            recalibration_interval: DEFAULT_RECALIBRATION_INTERVAL,
+        }
+    }
+}
+
+/// Calibrates per-venue cost models from realized executions and serves impact estimates
+pub struct CostModelCalibrator {
+    config: CostModelConfig,
+    records: Arc<dyn ExecutionStatsStore>,
+    store: SyncRwLock<Option<Arc<dyn CostModelStore>>>,
+    /// Source of the pair volatility estimates are conditioned on
+    regimes: SyncRwLock<Option<Arc<RegimeDetector>>>,
+    /// Latest calibrated model per venue
+    models: SyncRwLock<HashMap<String, CostModel>>,
+}
+
+impl fmt::Debug for CostModelCalibrator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CostModelCalibrator")
+            .field("config", &self.config)
+            .field("models", &self.models.read().len())
+            .finish()
+    }
+}
+
+impl CostModelCalibrator {
+    pub fn new(config: CostModelConfig, records: Arc<dyn ExecutionStatsStore>) -> Self {
+        Self {
+            config,
+            records,
+            store: SyncRwLock::new(None),
+            regimes: SyncRwLock::new(None),
+            models: SyncRwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn set_store(&self, store: Arc<dyn CostModelStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    pub fn set_regimes(&self, regimes: Arc<RegimeDetector>) {
+        *self.regimes.write() = Some(regimes);
+    }
+
+    /// Per-candle volatility of a pair in bps, when the regime detector has seen enough candles
+    pub fn volatility_bps(&self, trading_pair: &str) -> Option<f64> {
+        let regimes = self.regimes.read().clone()?;
+        regimes.volatility(trading_pair).map(|volatility| volatility * BPS_PER_UNIT)
+    }
+
+    /// Fits a model per venue from executions within the lookback of `now`. Venues without
+    /// enough usable samples, or whose samples do not determine a fit, are skipped.
+    pub async fn calibrate(&self, now: DateTime<Utc>) -> Result<Vec<CostModel>, CostModelError> {
+        let records = self
+            .records
+            .records_since(now - self.config.lookback)
+            .await
+            .map_err(|e| CostModelError::Store(e.to_string()))?;
+
+        let mut samples: BTreeMap<&str, Vec<CostSample>> = BTreeMap::new();
+        for record in &records {
+            if let Some(sample) = CostSample::from_record(record) {
+                samples.entry(record.exchange.as_str()).or_default().push(sample);
+            }
+        }
+
+        let store = self.store.read().clone();
+        let mut calibrated = Vec::new();
+        for (exchange, samples) in samples {
+            let model = match self.fit_venue(exchange, &samples, now) {
+                Ok(model) => model,
+                Err(e) => {
+                    info!(exchange, "Keeping previous cost model: {}", e);
+                    counter!(format!("{}.skipped", METRICS_PREFIX), 1, "exchange" => exchange.to_string());
+                    continue;
+                }
+            };
+            if let Some(store) = &store {
+                store.save(&model).await?;
+            }
+            info!(
+                exchange,
+                samples = model.sample_count,
+                intercept_bps = model.intercept_bps,
+                depth_coefficient = model.depth_coefficient,
+                volatility_coefficient = model.volatility_coefficient,
+                r_squared = model.r_squared,
+                "Calibrated execution cost model"
+            );
+            counter!(format!("{}.calibrated", METRICS_PREFIX), 1, "exchange" => exchange.to_string());
+            self.models.write().insert(model.exchange.clone(), model.clone());
+            calibrated.push(model);
+        }
+        Ok(calibrated)
+    }
+
+    fn fit_venue(&self, exchange: &str, samples: &[CostSample], now: DateTime<Utc>) -> Result<CostModel, CostModelError> {
+        if samples.len() < self.config.min_samples {
+            return Err(CostModelError::InsufficientSamples {
+                exchange: exchange.to_string(),
+                samples: samples.len(),
+                required: self.config.min_samples,
+            });
+        }
+        let ([intercept_bps, depth_coefficient, volatility_coefficient], r_squared) =
+            fit(samples).ok_or_else(|| CostModelError::Degenerate(exchange.to_string()))?;
+
+        Ok(CostModel {
+            id: Uuid::new_v4(),
+            exchange: exchange.to_string(),
+            intercept_bps,
+            depth_coefficient,
+            volatility_coefficient,
+            sample_count: samples.len() as u64,
+            r_squared,
+            valid_from: now,
+            valid_until: now + self.config.validity,
+            calibrated_at: now,
+        })
+    }
+
+    /// Loads the models still valid at `now` so estimates survive a restart
+    pub async fn load(&self, now: DateTime<Utc>) -> Result<usize, CostModelError> {
+        let Some(store) = self.store.read().clone() else {
+            return Ok(0);
+        };
+        let models = store.current(now).await?;
+        let mut cached = self.models.write();
+        for model in models {
+            cached.insert(model.exchange.clone(), model);
+        }
+        Ok(cached.len())
+    }
+
+    /// Latest calibrated model of a venue, if still valid at `now`
+    pub fn model(&self, exchange: &str, now: DateTime<Utc>) -> Option<CostModel> {
+        self.models.read().get(exchange).filter(|model| model.is_valid_at(now)).cloned()
+    }
+
+    /// Calibrated impact of an order in bps, or None when the venue has no valid model or the
+    /// depth or volatility it is conditioned on is unknown
+    pub fn estimate(
+        &self,
+        trading_pair: &str,
+        exchange: &str,
+        size: Decimal,
+        depth: Decimal,
+        now: DateTime<Utc>,
+    ) -> Option<(Decimal, ImpactModel)> {
+        let model = self.model(exchange, now)?;
+        if depth <= Decimal::ZERO {
+            return None;
+        }
+        let depth_ratio = (size / depth).to_f64()?;
+        let volatility_bps = self.volatility_bps(trading_pair)?;
+        let impact_bps = Decimal::from_f64_retain(model.estimate_bps(depth_ratio, volatility_bps))?.round_dp(2);
+
+        counter!(format!("{}.estimates", METRICS_PREFIX), 1, "exchange" => exchange.to_string());
+        Some((
+            impact_bps,
+            ImpactModel::Calibrated {
+                model_id: model.id,
+                calibrated_at: model.calibrated_at,
+            },
+        ))
+    }
+
+    /// Calibrated models newest first, including expired ones for comparison
+    pub async fn history(&self, exchange: Option<&str>, limit: usize) -> Result<Vec<CostModel>, CostModelError> {
+        if let Some(store) = self.store.read().clone() {
+            return store.history(exchange, limit).await;
+        }
+        let mut models: Vec<CostModel> = self
+            .models
+            .read()
+            .values()
+            .filter(|model| exchange.map_or(true, |exchange| model.exchange == exchange))
+            .cloned()
+            .collect();
+        models.sort_by(|a, b| b.calibrated_at.cmp(&a.calibrated_at));
+        models.truncate(limit);
+        Ok(models)
+    }
+
+    /// Registers the calibration job and enqueues it on the recalibration interval
+    pub fn spawn(self: Arc<Self>, jobs: Arc<JobQueue>) -> tokio::task::JoinHandle<()> {
+        jobs.register::<CostModelCalibration, _>(self.clone());
+        tokio::spawn(async move {
+            if let Err(e) = self.load(Utc::now()).await {
+                warn!("Failed to load cost models: {}", e);
+            }
+            let mut interval = tokio::time::interval(self.config.recalibration_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = jobs.enqueue(CostModelCalibration::default()).await {
+                    warn!("Failed to enqueue cost model calibration: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl JobHandler<CostModelCalibration> for CostModelCalibrator {
+    async fn handle(&self, job: CostModelCalibration, _ctx: JobContext) -> Result<(), String> {
+        let models = self.calibrate(Utc::now()).await.map_err(|e| e.to_string())?;
+        info!(
+            requested_by = job.requested_by.as_deref().unwrap_or("schedule"),
+            venues = models.len(),
+            "Cost model calibration finished"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_engine::benchmarks::ExecutionBenchmark;
+    use crate::execution_engine::stats::{ExecutionStats, StatsError, StatsWindow};
+    use crate::risk_manager::exposure::TradeSide;
+    use parking_lot::Mutex;
+    use rust_decimal_macros::dec;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        records: Mutex<Vec<ExecutionRecord>>,
+        models: Mutex<Vec<CostModel>>,
+    }
+
+    #[async_trait]
+    impl ExecutionStatsStore for MemoryStore {
+        async fn record(&self, record: &ExecutionRecord) -> Result<(), StatsError> {
+            self.records.lock().push(record.clone());
+            Ok(())
+        }
+
+        async fn records_since(&self, cutoff: DateTime<Utc>) -> Result<Vec<ExecutionRecord>, StatsError> {
+            Ok(self.records.lock().iter().filter(|record| record.executed_at >= cutoff).cloned().collect())
+        }
+
+        async fn replace_stats(&self, _stats: &[ExecutionStats]) -> Result<(), StatsError> {
+            Ok(())
+        }
+
+        async fn load_stats(&self, _trading_pair: &str, _window: StatsWindow) -> Result<Vec<ExecutionStats>, StatsError> {
+            Ok(Vec::new())
+        }
+
+        async fn record_benchmark(&self, _execution_id: Uuid, _benchmark: &ExecutionBenchmark) -> Result<(), StatsError> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl CostModelStore for MemoryStore {
+        async fn save(&self, model: &CostModel) -> Result<(), CostModelError> {
+            self.models.lock().push(model.clone());
+            Ok(())
+        }
+
+        async fn current(&self, at: DateTime<Utc>) -> Result<Vec<CostModel>, CostModelError> {
+            let mut latest: HashMap<String, CostModel> = HashMap::new();
+            for model in self.models.lock().iter().filter(|model| model.is_valid_at(at)) {
+                if latest.get(&model.exchange).map_or(true, |current| current.calibrated_at < model.calibrated_at) {
+                    latest.insert(model.exchange.clone(), model.clone());
+                }
+            }
+            Ok(latest.into_values().collect())
+        }
+
+        async fn history(&self, exchange: Option<&str>, limit: usize) -> Result<Vec<CostModel>, CostModelError> {
+            let mut models: Vec<CostModel> = self
+                .models
+                .lock()
+                .iter()
+                .filter(|model| exchange.map_or(true, |exchange| model.exchange == exchange))
+                .cloned()
+                .collect();
+            models.sort_by(|a, b| b.calibrated_at.cmp(&a.calibrated_at));
+            models.truncate(limit);
+            Ok(models)
+        }
+    }
+
+    /// Buy of `size` against `depth` that slipped `slippage_bps` from the expected price
+    fn execution(exchange: &str, size: Decimal, depth: Decimal, volatility_bps: Decimal, slippage_bps: Decimal, now: DateTime<Utc>) -> ExecutionRecord {
+        ExecutionRecord {
+            id: Uuid::new_v4(),
+            trading_pair: "SOL/USDC".to_string(),
+            exchange: exchange.to_string(),
+            side: TradeSide::Buy,
+            requested_size: size,
+            filled_size: size,
+            expected_price: dec!(100),
+            executed_price: Some(dec!(100) * (Decimal::ONE + slippage_bps / dec!(10000))),
+            fee: Decimal::ZERO,
+            latency_ms: 300,
+            mev_value: Decimal::ZERO,
+            executed_at: now - chrono::Duration::hours(1),
+            benchmark: None,
+            book_depth: Some(depth),
+            volatility_bps: Some(volatility_bps),
+            compute: None,
+            event_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_calibration_recovers_known_coefficients() {
+        let now = Utc::now();
+        let store = Arc::new(MemoryStore::default());
+        let calibrator = CostModelCalibrator::new(CostModelConfig::default(), store.clone());
+        calibrator.set_store(store.clone());
+
+        // Realized slippage = 1.5 + 40 * size/depth + 0.25 * volatility, with ±0.3 bps of noise
+        // alternating in blocks that each cover every order size
+        for i in 0..200u32 {
+            let size = Decimal::from(1 + i % 20);
+            let volatility_bps = Decimal::from(5 + (i * 7) % 40);
+            let noise = if (i / 20) % 2 == 0 { dec!(0.3) } else { dec!(-0.3) };
+            let slippage_bps = dec!(1.5) + dec!(40) * size / dec!(100) + dec!(0.25) * volatility_bps + noise;
+            store
+                .record(&execution("jupiter", size, dec!(100), volatility_bps, slippage_bps, now))
+                .await
+                .unwrap();
+        }
+        // Too few samples to calibrate; keeps the static estimate
+        for _ in 0..10 {
+            store.record(&execution("drift", dec!(5), dec!(100), dec!(10), dec!(12), now)).await.unwrap();
+        }
+
+        let models = calibrator.calibrate(now).await.unwrap();
+        assert_eq!(models.len(), 1);
+        let model = &models[0];
+        assert_eq!(model.exchange, "jupiter");
+        assert_eq!(model.sample_count, 200);
+        assert!((model.intercept_bps - 1.5).abs() < 0.2, "{}", model.intercept_bps);
+        assert!((model.depth_coefficient - 40.0).abs() < 1.0, "{}", model.depth_coefficient);
+        assert!((model.volatility_coefficient - 0.25).abs() < 0.01, "{}", model.volatility_coefficient);
+        assert!(model.r_squared > 0.99);
+        assert_eq!(model.valid_until, now + CostModelConfig::default().validity);
+        assert!(calibrator.model("drift", now).is_none());
+
+        // Recalibrating keeps the earlier model for comparison
+        calibrator.calibrate(now + chrono::Duration::days(7)).await.unwrap();
+        let history = calibrator.history(Some("jupiter"), 10).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(history[0].calibrated_at > history[1].calibrated_at);
+    }
+
+    #[test]
+    fn test_estimate_falls_back_without_volatility() {
+        let now = Utc::now();
+        let calibrator = CostModelCalibrator::new(CostModelConfig::default(), Arc::new(MemoryStore::default()));
+        calibrator.models.write().insert(
+            "jupiter".to_string(),
+            CostModel {
+                id: Uuid::new_v4(),
+                exchange: "jupiter".to_string(),
+                intercept_bps: 1.5,
+                depth_coefficient: 40.0,
+                volatility_coefficient: 0.25,
+                sample_count: 200,
+                r_squared: 0.99,
+                valid_from: now - chrono::Duration::days(1),
+                valid_until: now + chrono::Duration::days(13),
+                calibrated_at: now - chrono::Duration::days(1),
+            },
+        );
+
+        // No regime detector, so no volatility to condition on
+        assert!(calibrator.estimate("SOL/USDC", "jupiter", dec!(10), dec!(500), now).is_none());
+        // Expired models are not used
+        assert!(calibrator.model("jupiter", now + chrono::Duration::days(14)).is_none());
+    }
+
+    #[test]
+    fn test_collinear_samples_rejected() {
+        let samples: Vec<CostSample> = (0..10)
+            .map(|i| CostSample {
+                depth_ratio: i as f64 / 100.0,
+                volatility_bps: 20.0,
+                slippage_bps: 2.0 + i as f64,
+            })
+            .collect();
+        assert!(fit(&samples).is_none());
+    }
+}
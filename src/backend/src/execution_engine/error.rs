@@ -7,6 +7,7 @@
 
 use crate::models::order::OrderStatus;
 use crate::utils::metrics;
+use crate::utils::signer::{Signer, SignerError};
 use crate::utils::solana::ClientError;
 use chrono::{DateTime, Utc};
 use thiserror::Error;
@@ -43,6 +44,27 @@ pub enum ExecutionError {
     #[error("insufficient liquidity: {0}")]
     LiquidityError(String),
 
+    #[error("order expired before execution: {0}")]
+    ExpiredError(String),
+
+    #[error("live trading disabled: {0}")]
+    LiveTradingDisabled(String),
+
+    #[error("transaction signing failed: {message}")]
+    SigningFailed { message: String, retryable: bool },
+
+    #[error("engine warming up: {0}")]
+    WarmingUp(String),
+
+    #[error("slippage exceeded on-chain: {0}")]
+    SlippageExceeded(String),
+
+    #[error("compute budget exceeded on {exchange}: route needs {estimated} units, ceiling is {ceiling}")]
+    ComputeBudgetExceeded { exchange: String, estimated: u64, ceiling: u32 },
+
+    #[error("order submission throttled on {exchange}: next slot in {retry_after_ms}ms")]
+    Throttled { exchange: String, retry_after_ms: u64 },
+
     #[error("Solana client error: {0}")]
     SolanaError(#[from] ClientError),
 
@@ -50,6 +72,16 @@ pub enum ExecutionError {
     InternalError(String),
 }
 
+// Slippage rejection markers in program logs
+const SLIPPAGE_LOG_MARKERS: [&str; 4] = [
+    // Jupiter aggregator v6, custom error 6001 (0x1771)
+    "SlippageToleranceExceeded",
+    "custom program error: 0x1771",
+    // Drift limit price crossed before the order could fill
+    "SlippageOutsideLimit",
+    "OrderBreachesOraclePriceLimits",
+];
+
 /// Detailed context information for trade execution errors
 #[derive(Debug, Clone)]
 pub struct TradeContext {
@@ -127,9 +159,28 @@ impl TradeContext {
     }
 }
 
+/// Finds a venue slippage rejection in simulation or transaction logs
+pub fn slippage_rejection<S: AsRef<str>>(logs: &[S]) -> Option<ExecutionError> {
+    logs.iter()
+        .map(AsRef::as_ref)
+        .find(|line| SLIPPAGE_LOG_MARKERS.iter().any(|marker| line.contains(marker)))
+        .map(|line| ExecutionError::SlippageExceeded(line.trim().to_string()))
+}
+
+/// Maps a signer failure; it is final unless the signer opts into retries
+pub fn map_signer_error(signer: &dyn Signer, error: SignerError) -> ExecutionError {
+    ExecutionError::SigningFailed {
+        message: error.to_string(),
+        retryable: signer.retryable(),
+    }
+}
+
 /// Maps Solana client errors to execution engine errors
 pub fn map_solana_error(error: ClientError) -> ExecutionError {
     match error {
+        ClientError::TransactionError(msg) if slippage_rejection(&[&msg]).is_some() => {
+            ExecutionError::SlippageExceeded(msg.to_string())
+        }
         ClientError::TransactionError(msg) => ExecutionError::TradeExecutionFailed(
             TradeContext::new(
                 "UNKNOWN".to_string(),
@@ -201,4 +252,23 @@ mod tests {
             _ => panic!("Expected TradeExecutionFailed variant"),
         }
     }
+
+    #[test]
+    fn test_slippage_rejection_from_logs() {
+        let logs = vec![
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]".to_string(),
+            "Program log: AnchorError occurred. Error Code: SlippageToleranceExceeded. Error Number: 6001.".to_string(),
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 failed: custom program error: 0x1771".to_string(),
+        ];
+        match slippage_rejection(&logs) {
+            Some(ExecutionError::SlippageExceeded(line)) => assert!(line.contains("Error Number: 6001")),
+            other => panic!("Expected SlippageExceeded, got {:?}", other),
+        }
+        assert!(slippage_rejection(&["Program log: Error: insufficient funds"]).is_none());
+
+        let rejected = ClientError::TransactionError(
+            "Transaction simulation failed: Error processing Instruction 3: custom program error: 0x1771".to_string(),
+        );
+        assert!(matches!(map_solana_error(rejected), ExecutionError::SlippageExceeded(_)));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,445 @@
+//! Per-fill order tracking that applies incremental fills from bundle status and order status
+//! polling to positions as they arrive, and notifies strategies of each partial state.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - rust_decimal = "1.30"
+//! - uuid = "1.4"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use metrics::counter;
+use parking_lot::RwLock as SyncRwLock;
+use rust_decimal::Decimal;
+use tokio::sync::{broadcast, Mutex};
+use tracing::{debug, error, instrument, warn};
+use uuid::Uuid;
+
+use crate::api::webhooks::WebhookDispatcher;
+use crate::execution_engine::error::ExecutionError;
+use crate::models::order::{Amendment, Order, OrderFill, OrderStatus};
+use crate::models::portfolio::{Portfolio, PositionClose};
+use crate::models::webhook::WebhookEvent;
+use crate::risk_manager::exposure::TradeSide;
+
+// Fill tracking constants
+const FILL_CHANNEL_CAPACITY: usize = 1024;
+const METRICS_PREFIX: &str = "trading_bot.fills";
+
+/// Order state published to strategies after every fill
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillUpdate {
+    pub order_id: Uuid,
+    pub client_order_id: Uuid,
+    pub strategy_id: String,
+    pub trading_pair: String,
+    pub fill: OrderFill,
+    pub filled_size: Decimal,
+    pub remaining_size: Decimal,
+    pub average_price: Option<Decimal>,
+    pub status: OrderStatus,
+    /// P&L realized by the fill when it reduced or closed the position
+    pub realized_pnl: Option<Decimal>,
+}
+
+impl FillUpdate {
+    /// True while part of the order is still working on the venue
+    pub fn is_partial(&self) -> bool {
+        self.status == OrderStatus::PartiallyFilled
+    }
+}
+
+/// Durable record of the partial and full closes fills realize
+#[async_trait]
+pub trait PositionCloseStore: std::fmt::Debug + Send + Sync {
+    async fn record_close(&self, close: &PositionClose) -> Result<(), ExecutionError>;
+}
+
+#[derive(Debug)]
+struct TrackedOrder {
+    order: Order,
+    strategy_id: String,
+    side: TradeSide,
+}
+
+/// Working orders keyed by order id, with fills netted into the portfolio one at a time
+#[derive(Debug)]
+pub struct FillTracker {
+    portfolio: Portfolio,
+    orders: Mutex<HashMap<Uuid, TrackedOrder>>,
+    /// Signed net size per strategy and pair, from the fills each strategy's orders received
+    strategy_positions: Mutex<HashMap<String, HashMap<String, Decimal>>>,
+    updates_tx: broadcast::Sender<FillUpdate>,
+    closes: SyncRwLock<Option<Arc<dyn PositionCloseStore>>>,
+    webhooks: SyncRwLock<Option<Arc<WebhookDispatcher>>>,
+}
+
+impl FillTracker {
+    pub fn new(portfolio: Portfolio) -> Self {
+        let (updates_tx, _) = broadcast::channel(FILL_CHANNEL_CAPACITY);
+        Self {
+            portfolio,
+            orders: Mutex::new(HashMap::new()),
+            strategy_positions: Mutex::new(HashMap::new()),
+            updates_tx,
+            closes: SyncRwLock::new(None),
+            webhooks: SyncRwLock::new(None),
+        }
+    }
+
+    /// Persists every close a fill realizes through the store
+    pub fn set_close_store(&self, store: Arc<dyn PositionCloseStore>) {
+        *self.closes.write() = Some(store);
+    }
+
+    /// Publishes position opened and closed webhook events for every fill
+    pub fn set_webhooks(&self, webhooks: Arc<WebhookDispatcher>) {
+        *self.webhooks.write() = Some(webhooks);
+    }
+
+    /// Subscribes to fill updates so strategies can amend or cancel partially filled orders
+    pub fn subscribe(&self) -> broadcast::Receiver<FillUpdate> {
+        self.updates_tx.subscribe()
+    }
+
+    /// Sender behind `subscribe`, for consumers that subscribe per execution
+    pub fn update_sender(&self) -> broadcast::Sender<FillUpdate> {
+        self.updates_tx.clone()
+    }
+
+    /// Starts tracking a submitted order
+    pub async fn track(&self, order: Order, strategy_id: &str, side: TradeSide) {
+        self.orders.lock().await.insert(
+            order.id,
+            TrackedOrder {
+                order,
+                strategy_id: strategy_id.to_string(),
+                side,
+            },
+        );
+    }
+
+    /// Current state of a tracked order
+    pub async fn get(&self, order_id: Uuid) -> Option<Order> {
+        self.orders.lock().await.get(&order_id).map(|tracked| tracked.order.clone())
+    }
+
+    /// Applies one fill to its order and position. Repeated fills are ignored, and the order
+    /// is only updated once the position has accepted the fill.
+    #[instrument(skip(self, fill), fields(fill_id = %fill.fill_id))]
+    pub async fn apply_fill(
+        &self,
+        order_id: Uuid,
+        fill: OrderFill,
+    ) -> Result<Option<FillUpdate>, ExecutionError> {
+        let mut orders = self.orders.lock().await;
+        let tracked = orders.get_mut(&order_id).ok_or_else(|| {
+            ExecutionError::ValidationError(format!("order {} is not tracked", order_id))
+        })?;
+
+        let mut order = tracked.order.clone();
+        let applied = order
+            .apply_fill(&fill)
+            .map_err(|e| ExecutionError::ValidationError(e.to_string()))?;
+        if !applied {
+            debug!(order_id = %order_id, "Ignoring duplicate fill");
+            return Ok(None);
+        }
+
+        let outcome = self
+            .portfolio
+            .apply_fill(
+                order.trading_pair.clone(),
+                tracked.side.signed(fill.size),
+                fill.price,
+            )
+            .await
+            .map_err(|e| ExecutionError::PositionError(e.to_string()))?;
+
+        *self
+            .strategy_positions
+            .lock()
+            .await
+            .entry(tracked.strategy_id.clone())
+            .or_default()
+            .entry(order.trading_pair.to_string())
+            .or_default() += tracked.side.signed(fill.size);
+
+        let update = FillUpdate {
+            order_id,
+            client_order_id: order.client_order_id,
+            strategy_id: tracked.strategy_id.clone(),
+            trading_pair: order.trading_pair.to_string(),
+            fill,
+            filled_size: order.filled_size,
+            remaining_size: order.remaining_size(),
+            average_price: order.average_fill_price(),
+            status: order.status.clone(),
+            realized_pnl: outcome.close.as_ref().map(|close| close.realized_pnl),
+        };
+
+        // Fully filled orders have nothing left to amend or cancel
+        if order.status == OrderStatus::Executed {
+            orders.remove(&order_id);
+        } else {
+            tracked.order = order;
+        }
+        drop(orders);
+
+        // The close is already applied to the portfolio; a failed write is counted, not retried
+        let store = self.closes.read().clone();
+        if let (Some(store), Some(close)) = (store, &outcome.close) {
+            if let Err(e) = store.record_close(close).await {
+                counter!(format!("{}.close_persist_failures", METRICS_PREFIX), 1);
+                error!(order_id = %order_id, close_id = %close.id, "Failed to persist position close: {}", e);
+            }
+        }
+
+        let webhooks = self.webhooks.read().clone();
+        if let Some(webhooks) = webhooks {
+            let events = outcome
+                .close
+                .iter()
+                .map(WebhookEvent::position_closed)
+                .chain(outcome.opened.iter().map(WebhookEvent::position_opened));
+            for event in events {
+                match event {
+                    Ok(event) => {
+                        webhooks.dispatch(event);
+                    }
+                    Err(e) => warn!(order_id = %order_id, "Failed to build position webhook event: {}", e),
+                }
+            }
+        }
+
+        let _ = self.updates_tx.send(update.clone());
+        Ok(Some(update))
+    }
+
+    /// Amends a working order, tracking the replacement in its place when the venue
+    /// cancels and replaces; returns the order now working
+    #[instrument(skip(self, new_price, new_size))]
+    pub async fn amend(
+        &self,
+        order_id: Uuid,
+        new_price: Decimal,
+        new_size: Decimal,
+    ) -> Result<Order, ExecutionError> {
+        let mut orders = self.orders.lock().await;
+        let tracked = orders.get_mut(&order_id).ok_or_else(|| {
+            ExecutionError::ValidationError(format!("order {} is not tracked", order_id))
+        })?;
+
+        let amendment = tracked
+            .order
+            .amend(new_price, new_size)
+            .map_err(|e| ExecutionError::ValidationError(e.to_string()))?;
+
+        match amendment {
+            Amendment::InPlace => Ok(tracked.order.clone()),
+            Amendment::Replaced(replacement) => {
+                let cancelled = orders.remove(&order_id).expect("amended order is tracked");
+                orders.insert(
+                    replacement.id,
+                    TrackedOrder {
+                        order: replacement.clone(),
+                        strategy_id: cancelled.strategy_id,
+                        side: cancelled.side,
+                    },
+                );
+                Ok(replacement)
+            }
+        }
+    }
+
+    /// Open net size per pair held by a strategy, short positions negative
+    pub async fn strategy_positions(&self, strategy_id: &str) -> Vec<(String, Decimal)> {
+        let positions = self.strategy_positions.lock().await;
+        let mut open: Vec<(String, Decimal)> = positions
+            .get(strategy_id)
+            .map(|pairs| {
+                pairs
+                    .iter()
+                    .filter(|(_, size)| !size.is_zero())
+                    .map(|(pair, size)| (pair.clone(), *size))
+                    .collect()
+            })
+            .unwrap_or_default();
+        open.sort_by(|a, b| a.0.cmp(&b.0));
+        open
+    }
+
+    /// Moves a strategy's open position on a pair to another strategy, returning the size moved
+    pub async fn reassign_position(&self, from: &str, to: &str, trading_pair: &str) -> Decimal {
+        let mut positions = self.strategy_positions.lock().await;
+        let size = positions
+            .get_mut(from)
+            .and_then(|pairs| pairs.remove(trading_pair))
+            .unwrap_or_default();
+        if !size.is_zero() {
+            *positions
+                .entry(to.to_string())
+                .or_default()
+                .entry(trading_pair.to_string())
+                .or_default() += size;
+        }
+        size
+    }
+
+    /// Cancels the unfilled remainder of a working order
+    pub async fn cancel_remaining(&self, order_id: Uuid) -> Result<Order, ExecutionError> {
+        let mut tracked = self.orders.lock().await.remove(&order_id).ok_or_else(|| {
+            ExecutionError::ValidationError(format!("order {} is not tracked", order_id))
+        })?;
+        tracked.order.status = OrderStatus::Cancelled;
+        Ok(tracked.order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::order::OrderType;
+    use rust_decimal_macros::dec;
+
+    #[derive(Debug, Default)]
+    struct MemoryCloses(parking_lot::Mutex<Vec<PositionClose>>);
+
+    #[async_trait]
+    impl PositionCloseStore for MemoryCloses {
+        async fn record_close(&self, close: &PositionClose) -> Result<(), ExecutionError> {
+            self.0.lock().push(close.clone());
+            Ok(())
+        }
+    }
+
+    fn tracker() -> FillTracker {
+        FillTracker::new(Portfolio::new("test_wallet".to_string(), dec!(100000)).unwrap())
+    }
+
+    fn limit_order(exchange: &str, price: Decimal, size: Decimal) -> Order {
+        Order::new("SOL/USDC".to_string(), exchange.to_string(), OrderType::Limit, price, size).unwrap()
+    }
+
+    fn fill(id: &str, size: Decimal, price: Decimal) -> OrderFill {
+        OrderFill::new(id.to_string(), size, price)
+    }
+
+    #[tokio::test]
+    async fn test_three_part_fill_updates_position_per_fill() {
+        let tracker = tracker();
+        let mut updates = tracker.subscribe();
+        let order = limit_order("drift", dec!(12), dec!(9));
+        let order_id = order.id;
+        tracker.track(order, "grid", TradeSide::Buy).await;
+
+        tracker.apply_fill(order_id, fill("f1", dec!(3), dec!(10))).await.unwrap();
+        let position = tracker.portfolio.get_position("SOL/USDC").await.unwrap();
+        assert_eq!(position.size, dec!(3));
+        assert_eq!(position.entry_price, dec!(10));
+
+        tracker.apply_fill(order_id, fill("f2", dec!(3), dec!(11))).await.unwrap();
+        // A repeated poll reporting the same fill changes nothing
+        assert!(tracker.apply_fill(order_id, fill("f2", dec!(3), dec!(11))).await.unwrap().is_none());
+        let last = tracker.apply_fill(order_id, fill("f3", dec!(3), dec!(12))).await.unwrap().unwrap();
+
+        let statuses: Vec<(OrderStatus, Decimal)> = std::iter::from_fn(|| updates.try_recv().ok())
+            .map(|update| (update.status, update.remaining_size))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (OrderStatus::PartiallyFilled, dec!(6)),
+                (OrderStatus::PartiallyFilled, dec!(3)),
+                (OrderStatus::Executed, dec!(0)),
+            ]
+        );
+        assert_eq!(last.average_price, Some(dec!(11)));
+
+        let position = tracker.portfolio.get_position("SOL/USDC").await.unwrap();
+        assert_eq!(position.size, dec!(9));
+        assert_eq!(position.entry_price, dec!(11));
+        assert!(tracker.get(order_id).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_positions_tracked_per_strategy() {
+        let tracker = tracker();
+        let grid = limit_order("drift", dec!(10), dec!(5));
+        let momentum = limit_order("drift", dec!(10), dec!(2));
+        let (grid_id, momentum_id) = (grid.id, momentum.id);
+        tracker.track(grid, "grid", TradeSide::Buy).await;
+        tracker.track(momentum, "momentum", TradeSide::Sell).await;
+
+        tracker.apply_fill(grid_id, fill("g1", dec!(5), dec!(10))).await.unwrap();
+        tracker.apply_fill(momentum_id, fill("m1", dec!(2), dec!(10))).await.unwrap();
+        assert_eq!(tracker.strategy_positions("grid").await, vec![("SOL/USDC".to_string(), dec!(5))]);
+        assert_eq!(tracker.strategy_positions("momentum").await, vec![("SOL/USDC".to_string(), dec!(-2))]);
+
+        assert_eq!(tracker.reassign_position("grid", "manual", "SOL/USDC").await, dec!(5));
+        assert!(tracker.strategy_positions("grid").await.is_empty());
+        assert_eq!(tracker.strategy_positions("manual").await, vec![("SOL/USDC".to_string(), dec!(5))]);
+        // The portfolio position is unchanged by a reassignment
+        assert_eq!(tracker.portfolio.get_position("SOL/USDC").await.unwrap().size, dec!(3));
+    }
+
+    #[tokio::test]
+    async fn test_amend_after_partial_fill_keeps_position_and_pnl() {
+        let tracker = tracker();
+        let order = limit_order("jupiter", dec!(20), dec!(10));
+        let original_id = order.id;
+        let client_order_id = order.client_order_id;
+        tracker.track(order, "grid", TradeSide::Buy).await;
+
+        // 40% fills before the strategy reprices
+        let update = tracker.apply_fill(original_id, fill("f1", dec!(4), dec!(20))).await.unwrap().unwrap();
+        assert!(update.is_partial());
+
+        let replacement = tracker.amend(original_id, dec!(21), dec!(10)).await.unwrap();
+        assert_eq!(replacement.client_order_id, client_order_id);
+        assert_eq!(replacement.replaces, Some(original_id));
+        assert_eq!(replacement.remaining_size(), dec!(6));
+        assert!(tracker.get(original_id).await.is_none());
+
+        tracker.apply_fill(replacement.id, fill("f2", dec!(6), dec!(21))).await.unwrap();
+        let position = tracker.portfolio.get_position("SOL/USDC").await.unwrap();
+        assert_eq!(position.size, dec!(10));
+        assert_eq!(position.entry_price, dec!(20.6));
+
+        // Exiting at 22 realizes P&L against the blended entry
+        let exit = limit_order("jupiter", dec!(22), dec!(10));
+        let exit_id = exit.id;
+        tracker.track(exit, "grid", TradeSide::Sell).await;
+        let closed = tracker.apply_fill(exit_id, fill("f3", dec!(10), dec!(22))).await.unwrap().unwrap();
+        assert_eq!(closed.realized_pnl, Some(dec!(14)));
+
+        assert!(tracker.portfolio.get_position("SOL/USDC").await.is_none());
+        assert_eq!(tracker.portfolio.get_realized_pnl().await, dec!(14));
+    }
+
+    #[tokio::test]
+    async fn test_reducing_fill_persists_close() {
+        let tracker = tracker();
+        let closes = Arc::new(MemoryCloses::default());
+        tracker.set_close_store(closes.clone());
+
+        let buy = limit_order("jupiter", dec!(10), dec!(4));
+        let sell = limit_order("jupiter", dec!(12), dec!(1));
+        let (buy_id, sell_id) = (buy.id, sell.id);
+        tracker.track(buy, "grid", TradeSide::Buy).await;
+        tracker.track(sell, "grid", TradeSide::Sell).await;
+
+        tracker.apply_fill(buy_id, fill("b1", dec!(4), dec!(10))).await.unwrap();
+        assert!(closes.0.lock().is_empty());
+
+        let update = tracker.apply_fill(sell_id, fill("s1", dec!(1), dec!(12))).await.unwrap().unwrap();
+        assert_eq!(update.realized_pnl, Some(dec!(2)));
+        let recorded = closes.0.lock().clone();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].closed_size, dec!(1));
+        assert_eq!(recorded[0].realized_pnl, dec!(2));
+        assert_eq!(recorded[0].remaining_size, dec!(3));
+    }
+}
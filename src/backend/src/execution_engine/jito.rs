@@ -14,7 +14,7 @@ use solana_sdk::{transaction::Transaction, hash::Hash};
 use tokio::time::sleep;
 use tracing::{debug, error, info, instrument, warn};
 
-use crate::execution_engine::error::ExecutionError;
+use crate::execution_engine::error::{map_signer_error, ExecutionError};
 use crate::utils::solana::{SolanaClient, submit_mev_bundle};
 
 // Constants for MEV optimization
@@ -82,6 +82,19 @@ pub async fn submit_bundle(
         ExecutionError::ValidationError("Jito endpoint not configured".to_string())
     })?;
 
+    // Sign once through the configured signer, ahead of the submission retries
+    let signer = solana_client.signer().cloned().ok_or_else(|| ExecutionError::SigningFailed {
+        message: "no transaction signer configured".to_string(),
+        retryable: false,
+    })?;
+    let mut bundle = bundle;
+    for transaction in &mut bundle.transactions {
+        signer
+            .sign(transaction)
+            .await
+            .map_err(|e| map_signer_error(signer.as_ref(), e))?;
+    }
+
     // Configure submission parameters
     let config = BundleConfig {
         max_timeout_slots: 1,
@@ -0,0 +1,451 @@
+//! Tick-to-trade latency watchdog. Every order carries the stamp of the tick it was
+//! derived from; at dispatch the monotonic time since the tick was received is measured
+//! and cross-checked against the collector's wall-clock observation time. Rolling
+//! percentiles are kept per strategy and pair, p95 breaches of the budget raise alerts,
+//! and a strategy caught acting on a stale tick is paused.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - metrics = "0.20"
+//! - parking_lot = "0.12"
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, histogram};
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+
+use crate::models::market::TickStamp;
+use crate::supervision::{PauseTrigger, StrategySupervisor};
+
+// Latency watchdog constants
+const METRICS_PREFIX: &str = "trading_bot.execution.latency";
+const DEFAULT_P95_BUDGET: Duration = Duration::from_millis(500);
+const DEFAULT_STALE_TICK_AGE: Duration = Duration::from_secs(2);
+const DEFAULT_WINDOW: usize = 512;
+const DEFAULT_MIN_SAMPLES: usize = 20;
+const DEFAULT_ALERT_COOLDOWN: Duration = Duration::from_secs(60);
+const ALERT_CHANNEL_CAPACITY: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct LatencyConfig {
+    /// Tick-to-submission p95 above which an alert is raised
+    pub p95_budget: Duration,
+    /// Orders acting on ticks older than this are refused and their strategy paused
+    pub stale_tick_age: Duration,
+    /// Samples kept per strategy and pair
+    pub window: usize,
+    /// Samples needed before the p95 is compared against the budget
+    pub min_samples: usize,
+    pub alert_cooldown: Duration,
+}
+
+impl Default for LatencyConfig {
+    fn default() -> Self {
+        Self {
+            p95_budget: DEFAULT_P95_BUDGET,
+            stale_tick_age: DEFAULT_STALE_TICK_AGE,
+            window: DEFAULT_WINDOW,
+            min_samples: DEFAULT_MIN_SAMPLES,
+            alert_cooldown: DEFAULT_ALERT_COOLDOWN,
+        }
+    }
+}
+
+/// Latency of one order measured at dispatch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TickLatency {
+    /// Monotonic time from tick receipt to submission
+    pub latency: Duration,
+    /// Age of the tick by the collector's observation time, never less than `latency`
+    pub tick_age: Duration,
+    pub stale: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LatencyAlertKind {
+    BudgetExceeded { p95_ms: u64, budget_ms: u64 },
+    StaleTick { age_ms: u64, threshold_ms: u64 },
+}
+
+/// Latency breach raised for a strategy and pair
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencyAlert {
+    pub strategy_id: String,
+    pub trading_pair: String,
+    #[serde(flatten)]
+    pub kind: LatencyAlertKind,
+    pub at: DateTime<Utc>,
+}
+
+/// Rolling tick-to-trade percentiles for a strategy and pair
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencyPercentiles {
+    pub strategy_id: String,
+    pub trading_pair: String,
+    pub samples: usize,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct LatencyWindow {
+    samples: VecDeque<Duration>,
+    last_alert: Option<Instant>,
+}
+
+impl LatencyWindow {
+    fn push(&mut self, latency: Duration, capacity: usize) {
+        if self.samples.len() == capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency);
+    }
+
+    fn sorted(&self) -> Vec<Duration> {
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        sorted
+    }
+}
+
+/// Measures tick-to-trade latency per strategy and pair and acts on breaches
+pub struct LatencyWatchdog {
+    config: LatencyConfig,
+    supervisor: Arc<StrategySupervisor>,
+    windows: Mutex<HashMap<(String, String), LatencyWindow>>,
+    alerts: broadcast::Sender<LatencyAlert>,
+}
+
+impl std::fmt::Debug for LatencyWatchdog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LatencyWatchdog")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl LatencyWatchdog {
+    pub fn new(config: LatencyConfig, supervisor: Arc<StrategySupervisor>) -> Self {
+        let (alerts, _) = broadcast::channel(ALERT_CHANNEL_CAPACITY);
+        Self {
+            config,
+            supervisor,
+            windows: Mutex::new(HashMap::new()),
+            alerts,
+        }
+    }
+
+    /// Receives latency alerts as they are raised
+    pub fn subscribe(&self) -> broadcast::Receiver<LatencyAlert> {
+        self.alerts.subscribe()
+    }
+
+    /// Latency of an order submitted at `submitted_at` for a tick, without recording it
+    pub fn measure(&self, tick: &TickStamp, submitted_at: Instant, now: DateTime<Utc>) -> TickLatency {
+        let latency = submitted_at.saturating_duration_since(tick.received_at);
+        let wall_age = (now - tick.observed_at).to_std().unwrap_or(Duration::ZERO);
+
+        // The collector stamped the tick before we received it, so its wall-clock age can
+        // only trail the monotonic latency when the two clocks disagree
+        if wall_age < latency {
+            counter!(format!("{}.clock_skew", METRICS_PREFIX), 1);
+            debug!(
+                latency_ms = latency.as_millis() as u64,
+                wall_age_ms = wall_age.as_millis() as u64,
+                "Collector timestamp is ahead of the execution clock"
+            );
+        } else {
+            histogram!(
+                format!("{}.delivery_ms", METRICS_PREFIX),
+                (wall_age - latency).as_secs_f64() * 1000.0
+            );
+        }
+
+        let tick_age = latency.max(wall_age);
+        TickLatency {
+            latency,
+            tick_age,
+            stale: tick_age > self.config.stale_tick_age,
+        }
+    }
+
+    /// Records the latency of an order at dispatch, alerting on p95 breaches and pausing
+    /// the strategy when it acted on a stale tick
+    pub async fn observe(
+        &self,
+        strategy_id: &str,
+        trading_pair: &str,
+        tick: TickStamp,
+        submitted_at: Instant,
+        now: DateTime<Utc>,
+    ) -> TickLatency {
+        let measured = self.measure(&tick, submitted_at, now);
+        histogram!(
+            format!("{}.tick_to_trade_ms", METRICS_PREFIX),
+            measured.latency.as_secs_f64() * 1000.0,
+            "strategy" => strategy_id.to_string(),
+            "pair" => trading_pair.to_string()
+        );
+
+        let budget_breach = {
+            let mut windows = self.windows.lock();
+            let window = windows
+                .entry((strategy_id.to_string(), trading_pair.to_string()))
+                .or_default();
+            window.push(measured.latency, self.config.window.max(1));
+
+            let p95 = percentile(&window.sorted(), 95);
+            let cooled_down = window
+                .last_alert
+                .map_or(true, |at| submitted_at.saturating_duration_since(at) >= self.config.alert_cooldown);
+            if window.samples.len() >= self.config.min_samples && p95 > self.config.p95_budget && cooled_down {
+                window.last_alert = Some(submitted_at);
+                Some(p95)
+            } else {
+                None
+            }
+        };
+
+        if let Some(p95) = budget_breach {
+            self.alert(
+                strategy_id,
+                trading_pair,
+                LatencyAlertKind::BudgetExceeded {
+                    p95_ms: p95.as_millis() as u64,
+                    budget_ms: self.config.p95_budget.as_millis() as u64,
+                },
+                now,
+            );
+        }
+
+        if measured.stale {
+            let age_ms = measured.tick_age.as_millis() as u64;
+            let threshold_ms = self.config.stale_tick_age.as_millis() as u64;
+            self.alert(
+                strategy_id,
+                trading_pair,
+                LatencyAlertKind::StaleTick { age_ms, threshold_ms },
+                now,
+            );
+            self.supervisor
+                .pause(
+                    strategy_id,
+                    PauseTrigger::StaleTicks {
+                        trading_pair: trading_pair.to_string(),
+                        age_ms,
+                        threshold_ms,
+                    },
+                )
+                .await;
+        }
+
+        measured
+    }
+
+    /// Rolling percentiles for one strategy and pair
+    pub fn percentiles(&self, strategy_id: &str, trading_pair: &str) -> Option<LatencyPercentiles> {
+        let windows = self.windows.lock();
+        let window = windows.get(&(strategy_id.to_string(), trading_pair.to_string()))?;
+        Some(summarize(strategy_id, trading_pair, window))
+    }
+
+    /// Rolling percentiles for every strategy and pair seen
+    pub fn snapshot(&self) -> Vec<LatencyPercentiles> {
+        let windows = self.windows.lock();
+        let mut snapshot: Vec<LatencyPercentiles> = windows
+            .iter()
+            .map(|((strategy_id, trading_pair), window)| summarize(strategy_id, trading_pair, window))
+            .collect();
+        snapshot.sort_by(|a, b| (&a.strategy_id, &a.trading_pair).cmp(&(&b.strategy_id, &b.trading_pair)));
+        snapshot
+    }
+
+    fn alert(&self, strategy_id: &str, trading_pair: &str, kind: LatencyAlertKind, at: DateTime<Utc>) {
+        let label = match kind {
+            LatencyAlertKind::BudgetExceeded { .. } => "budget_exceeded",
+            LatencyAlertKind::StaleTick { .. } => "stale_tick",
+        };
+        warn!(strategy_id, trading_pair, ?kind, "Tick-to-trade latency breach");
+        counter!(format!("{}.alerts", METRICS_PREFIX), 1, "kind" => label);
+        let _ = self.alerts.send(LatencyAlert {
+            strategy_id: strategy_id.to_string(),
+            trading_pair: trading_pair.to_string(),
+            kind,
+            at,
+        });
+    }
+}
+
+fn summarize(strategy_id: &str, trading_pair: &str, window: &LatencyWindow) -> LatencyPercentiles {
+    let sorted = window.sorted();
+    LatencyPercentiles {
+        strategy_id: strategy_id.to_string(),
+        trading_pair: trading_pair.to_string(),
+        samples: sorted.len(),
+        p50_ms: percentile(&sorted, 50).as_millis() as u64,
+        p95_ms: percentile(&sorted, 95).as_millis() as u64,
+        p99_ms: percentile(&sorted, 99).as_millis() as u64,
+        max_ms: sorted.last().copied().unwrap_or_default().as_millis() as u64,
+    }
+}
+
+/// Nearest-rank percentile of sorted durations
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = ((percent * sorted.len() + 99) / 100).max(1);
+    sorted[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::MarketData;
+    use crate::models::strategy::{Strategy, StrategyParams, StrategyState, StrategyType};
+    use crate::signals::{Signal, SignalDirection};
+    use crate::supervision::SupervisionConfig;
+    use crate::utils::percent::{Bps, Percent};
+    use rust_decimal_macros::dec;
+    use tokio::sync::RwLock;
+
+    const STRATEGY_ID: &str = "grid-1";
+
+    fn watchdog(config: LatencyConfig) -> (LatencyWatchdog, Arc<StrategySupervisor>) {
+        let mut strategy = Strategy::new(
+            StrategyType::Grid,
+            StrategyParams {
+                position_size_bps: Bps::new(1000),
+                grid_levels: Some(10),
+                stop_loss_pct: Percent::from_percent(dec!(-5)),
+                take_profit_pct: Percent::from_percent(dec!(1)),
+                max_slippage_bps: Bps::new(100),
+                exchanges: vec!["jupiter".to_string()],
+                risk_factor: dec!(0.5),
+                allowed_regimes: Vec::new(),
+                market_making: None,
+            },
+            vec!["SOL/USDC".to_string()],
+        )
+        .unwrap();
+        strategy.state = StrategyState::Active;
+
+        let strategies = Arc::new(RwLock::new(HashMap::from([(STRATEGY_ID.to_string(), strategy)])));
+        let supervisor = Arc::new(StrategySupervisor::new(SupervisionConfig::default(), strategies));
+        supervisor.register(STRATEGY_ID, vec!["SOL/USDC".to_string()]);
+        (LatencyWatchdog::new(config, supervisor.clone()), supervisor)
+    }
+
+    fn tick(received_at: Instant, observed_at: DateTime<Utc>) -> TickStamp {
+        TickStamp {
+            tick_id: uuid::Uuid::new_v4(),
+            observed_at,
+            received_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delayed_tick_pauses_strategy() {
+        let (watchdog, supervisor) = watchdog(LatencyConfig::default());
+        let mut alerts = watchdog.subscribe();
+        let now = Utc::now();
+        let submitted_at = Instant::now();
+        let received_at = submitted_at - Duration::from_millis(2500);
+
+        // Collector observed the tick 2.6s ago and the bot received it 2.5s ago
+        let market_data = MarketData::new("SOL/USDC".to_string(), "jupiter".to_string(), dec!(23.45), dec!(100))
+            .unwrap()
+            .with_timestamp(now - chrono::Duration::milliseconds(2600))
+            .with_received_at(received_at);
+        let signal = Signal::new(
+            STRATEGY_ID,
+            "SOL/USDC",
+            "jupiter",
+            SignalDirection::Long,
+            dec!(23.45),
+            dec!(1),
+            Duration::from_secs(5),
+            now,
+        )
+        .with_tick(market_data.stamp());
+        let params = signal.order_params();
+        let stamp = params.tick.expect("tick survives the signal");
+        assert_eq!(stamp.tick_id, market_data.id());
+
+        let measured = watchdog
+            .observe(STRATEGY_ID, &params.trading_pair, stamp, submitted_at, now)
+            .await;
+        assert_eq!(measured.latency, Duration::from_millis(2500));
+        assert!(measured.tick_age >= Duration::from_millis(2600));
+        assert!(measured.stale);
+
+        assert!(supervisor.is_paused(STRATEGY_ID));
+        let health = supervisor.health(STRATEGY_ID).unwrap();
+        assert!(matches!(
+            health.paused,
+            Some(PauseTrigger::StaleTicks { threshold_ms: 2000, .. })
+        ));
+
+        let alert = alerts.recv().await.unwrap();
+        assert_eq!(alert.strategy_id, STRATEGY_ID);
+        assert!(matches!(alert.kind, LatencyAlertKind::StaleTick { threshold_ms: 2000, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_p95_budget_alert_respects_cooldown() {
+        let config = LatencyConfig {
+            min_samples: 10,
+            ..LatencyConfig::default()
+        };
+        let (watchdog, supervisor) = watchdog(config);
+        let mut alerts = watchdog.subscribe();
+        let now = Utc::now();
+        let start = Instant::now();
+
+        // 18 fast orders then 2 slow but fresh ones put p95 over the 500ms budget
+        for i in 0..20u64 {
+            let received_at = start + Duration::from_millis(i * 10);
+            let latency = if i < 18 { 50 } else { 800 };
+            let submitted_at = received_at + Duration::from_millis(latency);
+            let observed_at = now - chrono::Duration::milliseconds(latency as i64);
+            watchdog
+                .observe(STRATEGY_ID, "SOL/USDC", tick(received_at, observed_at), submitted_at, now)
+                .await;
+        }
+
+        let percentiles = watchdog.percentiles(STRATEGY_ID, "SOL/USDC").unwrap();
+        assert_eq!(percentiles.samples, 20);
+        assert_eq!(percentiles.p50_ms, 50);
+        assert_eq!(percentiles.p95_ms, 800);
+        assert!(!supervisor.is_paused(STRATEGY_ID));
+
+        let alert = alerts.try_recv().unwrap();
+        assert_eq!(
+            alert.kind,
+            LatencyAlertKind::BudgetExceeded { p95_ms: 800, budget_ms: 500 }
+        );
+
+        // Still over budget, but within the cooldown
+        let received_at = start + Duration::from_secs(1);
+        watchdog
+            .observe(
+                STRATEGY_ID,
+                "SOL/USDC",
+                tick(received_at, now - chrono::Duration::milliseconds(900)),
+                received_at + Duration::from_millis(900),
+                now,
+            )
+            .await;
+        assert!(alerts.try_recv().is_err());
+    }
+}
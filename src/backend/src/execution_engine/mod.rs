@@ -6,23 +6,72 @@
 //! - rust_decimal = "1.30"
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use async_trait::async_trait;
+use parking_lot::RwLock as SyncRwLock;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use tokio::sync::RwLock;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, RwLock};
 use tracing::{debug, error, info, instrument, warn};
+use uuid::Uuid;
 
+use crate::execution_engine::adapters::{ExchangeAdapter, OrderRequest, SlippageReport};
+use crate::execution_engine::compute_budget::ComputeUsage;
 use crate::execution_engine::error::{ExecutionError, TradeContext};
 use crate::execution_engine::position::{Position, PositionStatus};
-use crate::execution_engine::trade::TradeExecutor;
-use crate::execution_engine::order_book::LiveOrderBook;
+use crate::execution_engine::position_events::{LivePositions, PositionEventLog, PositionState};
+use crate::execution_engine::multi_leg::{LegFill, LegSubmitter, PreparedLeg};
+use crate::execution_engine::trade::{TradeExecutor, TradeParams, TradeResult, TradeSubmitter};
+use crate::execution_engine::latency::LatencyWatchdog;
+use crate::execution_engine::order_book::{ExecutionPlan, LiveOrderBook, OrderBookSnapshot};
+use crate::execution_engine::passive::{ExecutionStyle, PassiveExecutor};
+use crate::execution_engine::queue::{ExecutionQueue, PriorityClass, QueueConfig};
+use crate::execution_engine::readiness::{ReadinessConfig, ReadinessGate};
+use crate::execution_engine::recovery::{IntentStore, SubmissionIntent};
+use crate::execution_engine::throttle::OrderThrottle;
+use crate::data_collector::market_data::validate_trading_pair;
+use crate::models::market::TickStamp;
+use crate::models::order::{Order, OrderFill, OrderType};
+use crate::models::portfolio::PositionClose;
+use crate::models::trade::{maker_rebate_rate, FeeBreakdown};
+use crate::risk_manager::exposure::TradeSide;
+use crate::utils::circuit_breaker::{BreakerConfig, CircuitBreaker};
+use crate::utils::percent::Bps;
+use crate::utils::solana::FeeEstimator;
+
+pub mod adapters;
+pub mod benchmarks;
+pub mod book_sync;
+pub mod book_view;
+pub mod compute_budget;
+pub mod cost_model;
+pub mod fills;
+pub mod latency;
+pub mod multi_leg;
+pub mod open_orders;
+pub mod passive;
+pub mod position;
+pub mod position_events;
+pub mod preview;
+pub mod queue;
+pub mod readiness;
+pub mod recovery;
+pub mod simulation;
+pub mod stats;
+pub mod swap;
+pub mod telemetry;
+pub mod throttle;
 
 // Global constants from specification
 pub const ENGINE_VERSION: &str = "1.0.0";
 pub const MAX_CONCURRENT_TRADES: usize = 100;
 pub const CIRCUIT_BREAKER_THRESHOLD: f64 = 0.05;
 pub const MEV_OPTIMIZATION_ENABLED: bool = true;
+const TRADE_EVENT_BUFFER: usize = 1024;
 
 /// Performance metrics for execution monitoring
 #[derive(Debug, Default)]
@@ -34,42 +83,43 @@ struct ExecutionMetrics {
     circuit_breaker_triggers: u64,
 }
 
-/// Circuit breaker for risk management
-#[derive(Debug)]
-struct CircuitBreaker {
-    error_count: u32,
-    last_reset: Instant,
-    threshold: f64,
-}
-
-impl CircuitBreaker {
-    fn new(threshold: f64) -> Self {
-        Self {
-            error_count: 0,
-            last_reset: Instant::now(),
-            threshold,
+impl ExecutionMetrics {
+    /// Counts an execution outcome, folding successful run times into the average
+    fn record(&mut self, succeeded: bool, elapsed: Duration) {
+        if !succeeded {
+            self.trades_failed += 1;
+            return;
         }
-    }
-
-    fn record_error(&mut self) -> bool {
-        self.error_count += 1;
-        self.error_count as f64 / 100.0 > self.threshold
-    }
-
-    fn reset(&mut self) {
-        self.error_count = 0;
-        self.last_reset = Instant::now();
+        self.trades_executed += 1;
+        self.average_execution_time = Duration::from_micros(
+            ((self.average_execution_time.as_micros() * (self.trades_executed - 1) as u128
+                + elapsed.as_micros())
+                / self.trades_executed as u128) as u64,
+        );
     }
 }
 
 /// High-performance execution engine coordinator
 #[derive(Debug)]
 pub struct ExecutionEngine {
-    trade_executor: Arc<TradeExecutor>,
+    trade_executor: Arc<dyn TradeSubmitter>,
     order_book: Arc<LiveOrderBook>,
-    active_positions: HashMap<String, Position>,
-    metrics: tokio::sync::RwLock<ExecutionMetrics>,
-    circuit_breaker: CircuitBreaker,
+    execution_queue: Arc<ExecutionQueue>,
+    /// Open positions by trading pair; updates to one pair's position are serialized
+    active_positions: RwLock<HashMap<String, Position>>,
+    position_events: Arc<PositionEventLog>,
+    trades_tx: broadcast::Sender<TradeEvent>,
+    metrics: RwLock<ExecutionMetrics>,
+    circuit_breaker: Arc<CircuitBreaker>,
+    passive: SyncRwLock<Option<Arc<PassiveExecutor>>>,
+    /// Venue adapters encoding slippage limits on-chain, by exchange
+    adapters: SyncRwLock<HashMap<String, Arc<dyn ExchangeAdapter>>>,
+    /// Real transactions are only submitted when the environment allows it
+    live_trading: AtomicBool,
+    /// Holds executions back until market data for every traded pair is fresh
+    readiness: Arc<ReadinessGate>,
+    /// Persists submission intents so fills landing after a crash can be recovered
+    intents: SyncRwLock<Option<Arc<dyn IntentStore>>>,
 }
 
 impl ExecutionEngine {
@@ -78,99 +128,436 @@ impl ExecutionEngine {
         trade_executor: Arc<TradeExecutor>,
         order_book: Arc<LiveOrderBook>,
         cb_config: CircuitBreakerConfig,
+        queue_config: QueueConfig,
+    ) -> Self {
+        Self::from_submitter(trade_executor, order_book, cb_config, queue_config)
+    }
+
+    /// Creates an engine dispatching its executions to any trade submitter
+    pub fn from_submitter(
+        trade_executor: Arc<dyn TradeSubmitter>,
+        order_book: Arc<LiveOrderBook>,
+        cb_config: CircuitBreakerConfig,
+        queue_config: QueueConfig,
     ) -> Self {
         info!("Initializing execution engine v{}", ENGINE_VERSION);
 
+        // Route all executions through the priority queue
+        let execution_queue = Arc::new(ExecutionQueue::new(queue_config));
+        execution_queue.clone().spawn_dispatcher(trade_executor.clone());
+
+        let readiness = Arc::new(ReadinessGate::new(ReadinessConfig::default(), order_book.clone()));
+
         Self {
             trade_executor,
             order_book,
-            active_positions: HashMap::new(),
-            metrics: tokio::sync::RwLock::new(ExecutionMetrics::default()),
-            circuit_breaker: CircuitBreaker::new(cb_config.threshold),
+            execution_queue,
+            active_positions: RwLock::new(HashMap::new()),
+            position_events: Arc::new(PositionEventLog::new()),
+            trades_tx: broadcast::channel(TRADE_EVENT_BUFFER).0,
+            metrics: RwLock::new(ExecutionMetrics::default()),
+            circuit_breaker: execution_breaker(&cb_config),
+            passive: SyncRwLock::new(None),
+            adapters: SyncRwLock::new(HashMap::new()),
+            live_trading: AtomicBool::new(false),
+            readiness,
+            intents: SyncRwLock::new(None),
         }
     }
 
+    /// Replaces the default warm-up gate, keeping the engine's order book as its book source
+    pub fn with_readiness(mut self, config: ReadinessConfig) -> Self {
+        self.readiness = Arc::new(ReadinessGate::new(config, self.order_book.clone()));
+        self
+    }
+
+    /// Warm-up state and per-pair market data readiness
+    pub fn readiness(&self) -> Arc<ReadinessGate> {
+        self.readiness.clone()
+    }
+
+    /// Measures tick-to-trade latency at dispatch, refusing orders that act on stale ticks
+    pub fn with_latency_watchdog(self, watchdog: Arc<LatencyWatchdog>) -> Self {
+        self.execution_queue.set_latency_watchdog(watchdog);
+        self
+    }
+
+    /// Paces the wallet's submissions on each venue within its configured limits
+    pub fn with_order_throttle(self, throttle: Arc<OrderThrottle>, wallet_address: &str) -> Self {
+        self.execution_queue.set_order_throttle(throttle, wallet_address);
+        self
+    }
+
+    /// Permits real transaction submission; see `EnvironmentConfig::live_trading_enabled`
+    pub fn set_live_trading(&self, enabled: bool) {
+        self.live_trading.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Works passive-style orders as post-only maker orders on venues paying rebates
+    pub fn with_passive_executor(self, passive: Arc<PassiveExecutor>) -> Self {
+        self.set_passive_executor(passive);
+        self
+    }
+
+    /// Attaches the passive executor to an engine already shared with the bot
+    pub fn set_passive_executor(&self, passive: Arc<PassiveExecutor>) {
+        *self.passive.write() = Some(passive);
+    }
+
+    /// Builds orders on the adapter's exchange with the slippage limit enforced by the venue
+    pub fn with_exchange_adapter(self, adapter: Arc<dyn ExchangeAdapter>) -> Self {
+        self.set_exchange_adapter(adapter);
+        self
+    }
+
+    /// Registers a venue adapter on an engine already shared with the bot, replacing any
+    /// earlier adapter for the same exchange
+    pub fn set_exchange_adapter(&self, adapter: Arc<dyn ExchangeAdapter>) {
+        self.adapters.write().insert(adapter.exchange().to_string(), adapter);
+    }
+
+    /// Venue adapters registered so far, for executors routing orders of their own
+    pub fn exchange_adapters(&self) -> Vec<Arc<dyn ExchangeAdapter>> {
+        self.adapters.read().values().cloned().collect()
+    }
+
+    /// Prices the trade executor's priority fees from recent prioritization fees
+    pub fn set_fee_estimator(&self, fee_estimator: Arc<FeeEstimator>) {
+        self.trade_executor.set_fee_estimator(fee_estimator);
+    }
+
+    /// Opens a submission intent for each queued order and attaches every attempt's
+    /// signature to it, so fills landing after a crash can be recovered on restart
+    pub fn set_intent_store(&self, intents: Arc<dyn IntentStore>) {
+        self.trade_executor.set_intent_store(intents.clone());
+        *self.intents.write() = Some(intents);
+    }
+
     /// Executes a trading strategy with comprehensive risk management
-    #[instrument(skip(self, params))]
+    #[instrument(
+        skip(self, params),
+        fields(
+            strategy_id = %params.strategy_id,
+            trading_pair = %params.trading_pair,
+            exchange = %params.exchange,
+        )
+    )]
     pub async fn execute_strategy(
         &self,
         params: StrategyParams,
     ) -> Result<ExecutionResult, ExecutionError> {
-        let start_time = Instant::now();
-        let context = TradeContext::new(
+        self.admit(&params.strategy_id, &params.trading_pair).await?;
+
+        let (strategy_id, trading_pair, exchange, side) = (
+            params.strategy_id.clone(),
             params.trading_pair.clone(),
             params.exchange.clone(),
-            params.order_type,
+            params.side,
         );
+        let result = self.execute_admitted(params).await;
+        if let Ok(execution) = &result {
+            if let Some(event) = TradeEvent::from_execution(strategy_id, trading_pair, exchange, side, execution) {
+                // No subscribers is the normal single-process case
+                let _ = self.trades_tx.send(event);
+            }
+        }
+        if record_breaker_outcome(&self.circuit_breaker, &result) {
+            self.metrics.write().await.circuit_breaker_triggers += 1;
+        }
+        result
+    }
+
+    /// Refuses submission while live trading is off, the pair is warming up or the breaker
+    /// is open
+    async fn admit(&self, strategy_id: &str, trading_pair: &str) -> Result<(), ExecutionError> {
+        if !self.live_trading.load(Ordering::SeqCst) {
+            warn!(strategy_id, "Refusing to submit a live transaction");
+            return Err(ExecutionError::LiveTradingDisabled(
+                "profile is not production and ALLOW_LIVE_TRADING is unset".to_string(),
+            ));
+        }
+
+        // Executions held back during warm-up never reach the breaker
+        self.readiness.admit(trading_pair).await?;
 
         // Check circuit breaker status
-        if self.circuit_breaker.error_count > 0 {
+        if !self.circuit_breaker.allow() {
             warn!(
-                error_count = self.circuit_breaker.error_count,
+                consecutive_failures = self.circuit_breaker.status().consecutive_failures,
                 "Circuit breaker active"
             );
             return Err(ExecutionError::ValidationError(
                 "circuit breaker active".to_string(),
             ));
         }
+        Ok(())
+    }
 
-        // Validate strategy parameters
-        self.validate_strategy_params(&params).await?;
+    /// Persists a submission intent when an intent store is attached; it must be durable
+    /// before anything can land on-chain
+    async fn open_intent(
+        &self,
+        strategy_id: &str,
+        order: &OrderRequest,
+        exchange: &str,
+    ) -> Result<Option<Uuid>, ExecutionError> {
+        let intents = self.intents.read().clone();
+        let Some(intents) = intents else {
+            return Ok(None);
+        };
+        let intent = SubmissionIntent::new(
+            strategy_id,
+            &order.trading_pair,
+            exchange,
+            order.side,
+            order.size,
+            order.price,
+        );
+        intents
+            .open(&intent)
+            .await
+            .map_err(|e| ExecutionError::InternalError(e.to_string()))?;
+        Ok(Some(intent.id))
+    }
 
-        // Calculate optimal execution route
-        let execution_plan = self.order_book
-            .get_best_execution(&params.into())
-            .await?;
+    async fn execute_admitted(&self, params: StrategyParams) -> Result<ExecutionResult, ExecutionError> {
+        let start_time = Instant::now();
+        let context = TradeContext::new(
+            params.trading_pair.clone(),
+            params.exchange.clone(),
+            params.order_type,
+        );
 
-        // Apply MEV optimization if enabled
-        let optimized_plan = if MEV_OPTIMIZATION_ENABLED {
-            self.optimize_execution_plan(execution_plan).await?
-        } else {
-            execution_plan
+        // Passive orders rest on the book instead of going through the execution queue
+        if params.execution_style == ExecutionStyle::Passive {
+            let passive = self.passive.read().clone();
+            match &passive {
+                Some(passive) if maker_rebate_rate(&params.exchange).is_some() => {
+                    return self.execute_passive(passive, params, start_time).await;
+                }
+                _ => debug!(
+                    exchange = %params.exchange,
+                    "Passive execution unavailable, crossing the spread"
+                ),
+            }
+        }
+
+        let strategy_id = params.strategy_id.clone();
+        let priority = params.priority;
+        let exchange = params.exchange.clone();
+
+        // Validate parameters and calculate the optimal execution route
+        let optimized_plan = self.plan_execution(&params).await?;
+
+        // Encode the slippage limit in the venue order from a quote taken at build time
+        let request = OrderRequest {
+            trading_pair: params.trading_pair.clone(),
+            side: params.side,
+            size: params.size,
+            price: optimized_plan.estimated_price,
+            max_slippage: params.max_slippage,
         };
+        let adapter = self.adapters.read().get(&exchange).cloned();
+        let guarded = match adapter {
+            Some(adapter) => Some(adapter.prepare(&request).await?),
+            None => None,
+        };
+        let guard = guarded.as_ref().map(|order| order.guard);
+        let mut trade_params: TradeParams = optimized_plan.clone().into();
+        trade_params.guarded = guarded;
+        trade_params.tick = params.tick;
+        trade_params.intent_id = self.open_intent(&strategy_id, &request, &exchange).await?;
 
-        // Execute trades through optimized executor
-        let result = self.trade_executor
-            .execute_trade(optimized_plan.into())
+        // Execute trades through the priority queue
+        let result = self.execution_queue
+            .submit(&strategy_id, priority, trade_params)
             .await;
 
         // Update metrics and handle result
-        {
-            let mut metrics = self.metrics.write().await;
-            match &result {
-                Ok(_) => {
-                    metrics.trades_executed += 1;
-                    metrics.average_execution_time = Duration::from_micros(
-                        ((metrics.average_execution_time.as_micros() * (metrics.trades_executed - 1) as u128
-                            + start_time.elapsed().as_micros())
-                            / metrics.trades_executed as u128) as u64,
-                    );
-                }
-                Err(_) => {
-                    metrics.trades_failed += 1;
-                    if self.circuit_breaker.record_error() {
-                        metrics.circuit_breaker_triggers += 1;
-                    }
+        self.metrics.write().await.record(result.is_ok(), start_time.elapsed());
+
+        result.map(|trade_result| {
+            let mut fees = FeeBreakdown::default();
+            for fill in &trade_result.fills {
+                if let Err(e) = fees.add_taker(&exchange, fill.size, fill.price) {
+                    warn!("Failed to account taker fee: {}", e);
                 }
             }
-        }
+            let slippage = guard.map(|guard| SlippageReport {
+                configured: guard.max_slippage,
+                realized: guard.realized_slippage(
+                    params.side,
+                    average_fill_price(&trade_result.fills).unwrap_or(optimized_plan.estimated_price),
+                ),
+            });
+            ExecutionResult {
+                trade_id: trade_result.transaction_hash,
+                execution_time: start_time.elapsed(),
+                price: optimized_plan.estimated_price,
+                mev_value: trade_result.mev_value,
+                fills: trade_result.fills,
+                fees,
+                slippage,
+                compute: trade_result.compute,
+                size_multiplier: params.size_multiplier,
+            }
+        })
+    }
 
-        result.map(|trade_result| ExecutionResult {
-            trade_id: trade_result.transaction_hash,
+    /// Rests the order post-only, crossing the spread for any remainder at the deadline
+    async fn execute_passive(
+        &self,
+        passive: &PassiveExecutor,
+        params: StrategyParams,
+        start_time: Instant,
+    ) -> Result<ExecutionResult, ExecutionError> {
+        self.validate_strategy_params(&params).await?;
+        let order = Order::new(
+            params.trading_pair.clone(),
+            params.exchange.clone(),
+            OrderType::Limit,
+            params.price,
+            params.size,
+        )
+        .map_err(|e| ExecutionError::ValidationError(e.to_string()))?;
+
+        let result = passive.execute(order, &params.strategy_id, params.side).await;
+
+        self.metrics.write().await.record(result.is_ok(), start_time.elapsed());
+
+        result.map(|execution| ExecutionResult {
+            trade_id: execution.client_order_id.to_string(),
             execution_time: start_time.elapsed(),
-            price: optimized_plan.estimated_price,
-            mev_value: trade_result.mev_value,
+            price: execution.average_price().unwrap_or(params.price),
+            mev_value: 0.0,
+            fills: execution.fills(),
+            fees: execution.fees,
+            slippage: None,
+            compute: None,
+            size_multiplier: params.size_multiplier,
         })
     }
 
+    /// Validates parameters and prices the route an order would take; live execution and
+    /// simulation both go through here so their results cannot drift apart
+    pub async fn plan_execution(
+        &self,
+        params: &StrategyParams,
+    ) -> Result<ExecutionPlan, ExecutionError> {
+        self.validate_strategy_params(params).await?;
+
+        let order = Order::new(
+            params.trading_pair.clone(),
+            params.exchange.clone(),
+            params.order_type.clone(),
+            params.price,
+            params.size,
+        )
+        .map_err(|e| ExecutionError::ValidationError(e.to_string()))?;
+
+        let execution_plan = self.order_book
+            .get_best_execution(&order, params.side)
+            .await
+            .map_err(|e| ExecutionError::OrderBookError(e.to_string()))?;
+
+        // Apply MEV optimization if enabled
+        if MEV_OPTIMIZATION_ENABLED {
+            self.optimize_execution_plan(execution_plan).await
+        } else {
+            Ok(execution_plan)
+        }
+    }
+
+    /// Cancels a strategy's orders still waiting in the execution queue
+    pub fn cancel_queued_orders(&self, strategy_id: &str) -> usize {
+        self.execution_queue.cancel_strategy(strategy_id)
+    }
+
+    /// Live order book shared with routing, for read-only consumers
+    pub fn order_book(&self) -> Arc<LiveOrderBook> {
+        self.order_book.clone()
+    }
+
+    /// Sender feeding live order book snapshots, for services that subscribe per client
+    pub fn order_book_sender(&self) -> tokio::sync::broadcast::Sender<OrderBookSnapshot> {
+        self.order_book.snapshot_sender()
+    }
+
+    /// Routes between near-identical books using realized execution statistics
+    pub fn set_execution_stats(&self, execution_stats: Arc<stats::ExecutionStatsService>) {
+        self.order_book.set_execution_stats(execution_stats);
+    }
+
+    /// Estimates pre-trade impact with calibrated cost models where a venue has one
+    pub fn set_cost_models(&self, cost_models: Arc<cost_model::CostModelCalibrator>) {
+        self.order_book.set_cost_models(cost_models);
+    }
+
+    /// Subscribes to trades as they execute
+    pub fn subscribe_trades(&self) -> broadcast::Receiver<TradeEvent> {
+        self.trades_tx.subscribe()
+    }
+
+    /// Subscribes to live order book snapshots
+    pub fn subscribe_order_books(&self) -> tokio::sync::broadcast::Receiver<OrderBookSnapshot> {
+        self.order_book.subscribe()
+    }
+
+    /// Estimates the priority fee a bundle on the exchange would pay before any MEV value
+    pub fn estimate_priority_fee(&self, exchange: &str) -> u64 {
+        self.trade_executor.priority_fee(exchange, 0.0)
+    }
+
+    /// Lifecycle event log shared by every position the engine opens
+    pub fn position_events(&self) -> Arc<PositionEventLog> {
+        self.position_events.clone()
+    }
+
+    /// Tracks a position for updates through `manage_positions`, replacing any position
+    /// already tracked for its pair
+    pub async fn track_position(&self, position: Position) {
+        track(&mut *self.active_positions.write().await, position);
+    }
+
+    /// Nets a confirmed fill into the tracked position for its pair, so the P&L it realizes
+    /// lands in the position's metrics; the position stops being tracked once flat. A buy
+    /// with no tracked position opens one, recorded in the position event log.
+    #[instrument(skip(self, signed_size, price))]
+    pub async fn apply_position_fill(
+        &self,
+        trading_pair: &str,
+        signed_size: Decimal,
+        price: Decimal,
+        cause_id: Option<Uuid>,
+    ) -> Result<Option<PositionClose>, ExecutionError> {
+        let mut positions = self.active_positions.write().await;
+        let Some(position) = positions.get_mut(trading_pair) else {
+            // Positions are opened long; short exposure is only reached by flipping one
+            if signed_size > Decimal::ZERO {
+                let position = Position::new(trading_pair.to_string(), signed_size, price)?
+                    .with_event_log(self.position_events.clone(), cause_id);
+                track(&mut positions, position);
+            }
+            return Ok(None);
+        };
+
+        let close = position.apply_fill(signed_size, price, cause_id).await?;
+        if position.closed_at.is_some() {
+            positions.remove(trading_pair);
+        }
+        Ok(close)
+    }
+
     /// Updates and manages active trading positions with risk controls
     #[instrument(skip(self, updates))]
     pub async fn manage_positions(
         &self,
         updates: Vec<PositionUpdate>,
     ) -> Result<(), ExecutionError> {
+        // Held for the whole batch so concurrent batches apply one after another
+        let mut positions = self.active_positions.write().await;
         for update in updates {
-            let position = self.active_positions
+            let position = positions
                 .get_mut(&update.trading_pair)
                 .ok_or_else(|| ExecutionError::PositionError(
                     format!("position not found for {}", update.trading_pair)
@@ -192,14 +579,82 @@ impl ExecutionEngine {
         Ok(())
     }
 
+    /// Trade parameters for a prepared group leg, with its submission intent opened
+    async fn leg_trade_params(&self, strategy_id: &str, leg: &PreparedLeg) -> Result<TradeParams, ExecutionError> {
+        let order = &leg.leg.order;
+        let intent_id = self.open_intent(strategy_id, order, &leg.leg.exchange).await?;
+        Ok(TradeParams {
+            id: Uuid::new_v4().to_string(),
+            trading_pair: order.trading_pair.clone(),
+            exchange: leg.leg.exchange.clone(),
+            order_type: OrderType::Market,
+            price: order.price,
+            size: order.size,
+            slippage: order.max_slippage.as_fraction(),
+            guarded: Some(leg.order.clone()),
+            tick: None,
+            intent_id,
+        })
+    }
+
+    /// Publishes a landed leg's trade event and reports what it filled at
+    fn leg_fill(
+        &self,
+        strategy_id: &str,
+        leg: &PreparedLeg,
+        trade_result: TradeResult,
+        start_time: Instant,
+    ) -> Result<LegFill, ExecutionError> {
+        let order = &leg.leg.order;
+        let size = trade_result.filled_size();
+        if size <= Decimal::ZERO {
+            return Err(ExecutionError::LiquidityError(format!(
+                "leg on {} landed without a fill",
+                leg.leg.exchange
+            )));
+        }
+        let price = average_fill_price(&trade_result.fills).unwrap_or(order.price);
+        let execution = ExecutionResult {
+            trade_id: trade_result.transaction_hash,
+            execution_time: start_time.elapsed(),
+            price,
+            mev_value: trade_result.mev_value,
+            fills: trade_result.fills,
+            // Fees are booked with the group's fills by the bot
+            fees: FeeBreakdown::default(),
+            slippage: Some(SlippageReport {
+                configured: leg.order.guard.max_slippage,
+                realized: leg.order.guard.realized_slippage(order.side, price),
+            }),
+            compute: trade_result.compute,
+            size_multiplier: None,
+        };
+        if let Some(event) = TradeEvent::from_execution(
+            strategy_id.to_string(),
+            order.trading_pair.clone(),
+            leg.leg.exchange.clone(),
+            order.side,
+            &execution,
+        ) {
+            let _ = self.trades_tx.send(event);
+        }
+        Ok(LegFill {
+            size,
+            price,
+            signature: Some(execution.trade_id),
+        })
+    }
+
     // Internal helper methods
+    #[instrument(name = "risk_validation", skip(self, params), err, fields(trading_pair = %params.trading_pair))]
     async fn validate_strategy_params(&self, params: &StrategyParams) -> Result<(), ExecutionError> {
         if params.size <= Decimal::ZERO {
             return Err(ExecutionError::ValidationError("invalid size".to_string()));
         }
 
-        // Additional validation logic
-        Ok(())
+        validate_trading_pair(&params.trading_pair)
+            .map(|_| ())
+            .map_err(|e| ExecutionError::ValidationError(e.to_string()))
     }
 
     async fn optimize_execution_plan(
@@ -211,13 +666,134 @@ impl ExecutionEngine {
     }
 }
 
+/// Trade group legs skip the priority queue: a group's legs go out back to back and a
+/// bundle as one submission, each still gated like a single order
+#[async_trait]
+impl LegSubmitter for ExecutionEngine {
+    #[instrument(skip(self, legs), fields(legs = legs.len()))]
+    async fn submit_bundle(&self, strategy_id: &str, legs: &[PreparedLeg]) -> Result<Vec<LegFill>, ExecutionError> {
+        for leg in legs {
+            self.admit(strategy_id, &leg.leg.order.trading_pair).await?;
+        }
+        let start_time = Instant::now();
+        let mut params = Vec::with_capacity(legs.len());
+        for leg in legs {
+            params.push(self.leg_trade_params(strategy_id, leg).await?);
+        }
+
+        let result = self.trade_executor.execute_bundle(params).await;
+        self.metrics.write().await.record(result.is_ok(), start_time.elapsed());
+        if record_breaker_outcome(&self.circuit_breaker, &result) {
+            self.metrics.write().await.circuit_breaker_triggers += 1;
+        }
+        legs.iter()
+            .zip(result?)
+            .map(|(leg, trade_result)| self.leg_fill(strategy_id, leg, trade_result, start_time))
+            .collect()
+    }
+
+    #[instrument(skip(self, leg), fields(exchange = %leg.leg.exchange, trading_pair = %leg.leg.order.trading_pair))]
+    async fn submit(&self, strategy_id: &str, leg: &PreparedLeg) -> Result<LegFill, ExecutionError> {
+        self.admit(strategy_id, &leg.leg.order.trading_pair).await?;
+        let start_time = Instant::now();
+        let params = self.leg_trade_params(strategy_id, leg).await?;
+
+        let result = self.trade_executor.execute_trade(params).await;
+        self.metrics.write().await.record(result.is_ok(), start_time.elapsed());
+        if record_breaker_outcome(&self.circuit_breaker, &result) {
+            self.metrics.write().await.circuit_breaker_triggers += 1;
+        }
+        self.leg_fill(strategy_id, leg, result?, start_time)
+    }
+}
+
+/// Tracks a position under its pair, replacing any position already tracked for it
+fn track(positions: &mut HashMap<String, Position>, position: Position) {
+    positions.insert(position.trading_pair.to_string(), position);
+}
+
+#[async_trait]
+impl LivePositions for ExecutionEngine {
+    async fn position_states(&self) -> Vec<PositionState> {
+        let positions = self.active_positions.read().await;
+        let mut states = Vec::with_capacity(positions.len());
+        for position in positions.values() {
+            states.push(position.state().await);
+        }
+        states
+    }
+}
+
 #[derive(Debug)]
 pub struct StrategyParams {
+    pub strategy_id: String,
+    pub priority: PriorityClass,
     pub trading_pair: String,
     pub exchange: String,
+    pub side: TradeSide,
     pub order_type: OrderType,
     pub size: Decimal,
     pub price: Decimal,
+    /// Passive orders rest post-only for maker rebates where the venue pays them
+    pub execution_style: ExecutionStyle,
+    /// Worst fill tolerated against the quote, encoded in the venue order
+    pub max_slippage: Bps,
+    /// Tick the order was derived from, checked for staleness at dispatch
+    pub tick: Option<TickStamp>,
+    /// Degraded-mode multiplier risk validation already applied to `size`
+    pub size_multiplier: Option<Decimal>,
+}
+
+/// Executed trade as published to in-process subscribers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeEvent {
+    pub trade_id: String,
+    pub strategy_id: String,
+    pub trading_pair: String,
+    pub exchange: String,
+    pub side: TradeSide,
+    /// Size filled during execution
+    pub size: Decimal,
+    pub price: Decimal,
+    pub executed_at: DateTime<Utc>,
+    /// Slippage limit encoded on-chain, for orders built by an exchange adapter
+    #[serde(default)]
+    pub configured_slippage_bps: Option<Bps>,
+    /// Adverse move of the fill from the build-time quote; negative when it improved
+    #[serde(default)]
+    pub realized_slippage_bps: Option<Bps>,
+    /// Degraded-mode multiplier the order was sized with
+    #[serde(default)]
+    pub size_multiplier: Option<Decimal>,
+}
+
+impl TradeEvent {
+    /// Builds the event for an execution, or `None` when nothing filled yet
+    fn from_execution(
+        strategy_id: String,
+        trading_pair: String,
+        exchange: String,
+        side: TradeSide,
+        execution: &ExecutionResult,
+    ) -> Option<Self> {
+        let size: Decimal = execution.fills.iter().map(|fill| fill.size).sum();
+        if size <= Decimal::ZERO {
+            return None;
+        }
+        Some(Self {
+            trade_id: execution.trade_id.clone(),
+            strategy_id,
+            trading_pair,
+            exchange,
+            side,
+            size,
+            price: execution.price,
+            executed_at: Utc::now(),
+            configured_slippage_bps: execution.slippage.map(|report| report.configured),
+            realized_slippage_bps: execution.slippage.map(|report| report.realized),
+            size_multiplier: execution.size_multiplier,
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -226,11 +802,62 @@ pub struct ExecutionResult {
     pub execution_time: Duration,
     pub price: Decimal,
     pub mev_value: f64,
+    /// Fills confirmed during execution; resting orders may fill further afterwards
+    pub fills: Vec<OrderFill>,
+    /// Taker fees and maker rebates across the fills
+    pub fees: FeeBreakdown,
+    /// Configured against realized slippage, for orders built by an exchange adapter
+    pub slippage: Option<SlippageReport>,
+    /// Compute unit limit and realized consumption, for budgeted transactions
+    pub compute: Option<ComputeUsage>,
+    /// Degraded-mode multiplier the order was sized with
+    pub size_multiplier: Option<Decimal>,
 }
 
 #[derive(Debug)]
 pub struct CircuitBreakerConfig {
+    /// Failures per hundred executions tolerated before the breaker opens
     pub threshold: f64,
+    /// Time open before a probe execution is let through; `None` stays open until restart
+    pub cooldown: Option<Duration>,
+}
+
+/// Breaker opening once failures exceed the configured share of a hundred executions
+fn execution_breaker(cb_config: &CircuitBreakerConfig) -> Arc<CircuitBreaker> {
+    let max_failures = (cb_config.threshold * 100.0).floor().max(0.0) as u32 + 1;
+    let mut config = BreakerConfig::consecutive_failures(max_failures);
+    if let Some(cooldown) = cb_config.cooldown {
+        config = config.with_cooldown(cooldown);
+    }
+    CircuitBreaker::new("execution_engine", config).registered()
+}
+
+/// Feeds an execution result to the breaker, returning whether it tripped
+fn record_breaker_outcome<T>(breaker: &CircuitBreaker, result: &Result<T, ExecutionError>) -> bool {
+    match result {
+        // Rejected before reaching a venue, which says nothing about execution health
+        Ok(_) | Err(ExecutionError::ValidationError(_)) | Err(ExecutionError::ComputeBudgetExceeded { .. }) => {
+            breaker.record_success();
+            false
+        }
+        // The venue enforced our own limit against a moving market; the execution path
+        // itself worked, so this neither trips nor resets the breaker
+        Err(ExecutionError::SlippageExceeded(_)) => false,
+        // Market data was still warming up; nothing was attempted
+        Err(ExecutionError::WarmingUp(_)) => false,
+        // Held back by our own venue throttle before signing
+        Err(ExecutionError::Throttled { .. }) => false,
+        Err(_) => breaker.record_failure(),
+    }
+}
+
+/// Size-weighted price across fills, `None` when nothing filled
+fn average_fill_price(fills: &[OrderFill]) -> Option<Decimal> {
+    let size: Decimal = fills.iter().map(|fill| fill.size).sum();
+    if size.is_zero() {
+        return None;
+    }
+    Some(fills.iter().map(|fill| fill.size * fill.price).sum::<Decimal>() / size)
 }
 
 #[derive(Debug)]
@@ -243,7 +870,327 @@ pub struct PositionUpdate {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use crate::execution_engine::order_book::Config as OrderBookConfig;
+    use crate::execution_engine::position_events::{PositionEvent, PositionEventStore};
+    use crate::execution_engine::readiness::{EngineState, PriceActivity, PriceFeed};
+    use crate::execution_engine::trade::TradeResult;
+    use crate::models::market::{OrderBook, OrderBookLevel};
+    use crate::models::strategy::{Strategy, StrategyParams as ModelParams, StrategyState, StrategyType};
+    use crate::utils::percent::Percent;
+    use crate::utils::solana::SolanaClient;
     use rust_decimal_macros::dec;
 
-    // Add comprehensive tests
-}
\ No newline at end of file
+    const PAIR: &str = "SOL/USDC";
+    const CONCURRENT_CALLS: usize = 50;
+
+    /// Submitter standing in for the on-chain executor, tracking how many trades overlap
+    #[derive(Debug, Default)]
+    struct MockSubmitter {
+        failing: AtomicBool,
+        calls: AtomicUsize,
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl TradeSubmitter for MockSubmitter {
+        async fn execute_trade(&self, params: TradeParams) -> Result<TradeResult, ExecutionError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            if self.failing.load(Ordering::SeqCst) {
+                return Err(ExecutionError::NetworkError("bundle rejected".to_string(), 503));
+            }
+            Ok(TradeResult {
+                transaction_hash: params.id.clone(),
+                execution_time: Duration::from_millis(20),
+                mev_value: 0.0,
+                fills: vec![OrderFill::new(params.id, params.size, params.price)],
+                compute: None,
+            })
+        }
+    }
+
+    struct FreshPrices;
+
+    impl PriceFeed for FreshPrices {
+        fn price_activity(&self, _trading_pair: &str, _since: DateTime<Utc>) -> PriceActivity {
+            PriceActivity {
+                ticks: 10,
+                last_tick_at: Some(Utc::now()),
+            }
+        }
+    }
+
+    fn strategies() -> Arc<RwLock<HashMap<String, Strategy>>> {
+        let mut strategy = Strategy::new(
+            StrategyType::Grid,
+            ModelParams {
+                position_size_bps: Bps::new(1000),
+                grid_levels: Some(10),
+                stop_loss_pct: Percent::from_percent(dec!(-5)),
+                take_profit_pct: Percent::from_percent(dec!(1)),
+                max_slippage_bps: Bps::new(100),
+                exchanges: vec!["jupiter".to_string()],
+                risk_factor: dec!(0.5),
+                allowed_regimes: Vec::new(),
+                market_making: None,
+            },
+            vec![PAIR.to_string()],
+        )
+        .unwrap();
+        strategy.state = StrategyState::Active;
+        Arc::new(RwLock::new(HashMap::from([("grid-1".to_string(), strategy)])))
+    }
+
+    /// Ready engine with a live SOL/USDC book, dispatching to the mock submitter
+    async fn ready_engine(submitter: Arc<MockSubmitter>) -> Arc<ExecutionEngine> {
+        let solana_client = Arc::new(
+            SolanaClient::new("http://localhost:8899".to_string(), None, None)
+                .await
+                .unwrap(),
+        );
+        let order_book = Arc::new(LiveOrderBook::new(solana_client, OrderBookConfig::default()));
+        let book = OrderBook::new(
+            PAIR.to_string(),
+            "jupiter".to_string(),
+            vec![OrderBookLevel::new(dec!(99), dec!(1000))],
+            vec![OrderBookLevel::new(dec!(101), dec!(1000))],
+        )
+        .unwrap();
+        order_book.update_book(PAIR.to_string(), book).await.unwrap();
+
+        let engine = ExecutionEngine::from_submitter(
+            submitter,
+            order_book,
+            CircuitBreakerConfig {
+                threshold: CIRCUIT_BREAKER_THRESHOLD,
+                cooldown: None,
+            },
+            QueueConfig {
+                strategy_in_flight_cap: CONCURRENT_CALLS,
+                ..QueueConfig::default()
+            },
+        )
+        .with_readiness(ReadinessConfig {
+            min_ticks: 1,
+            ..ReadinessConfig::default()
+        });
+        engine.set_live_trading(true);
+        let readiness = engine.readiness();
+        readiness.set_price_feed(Arc::new(FreshPrices));
+        readiness.set_strategies(strategies());
+        assert_eq!(readiness.evaluate(Utc::now()).await, EngineState::Ready);
+        Arc::new(engine)
+    }
+
+    fn params(i: usize) -> StrategyParams {
+        StrategyParams {
+            strategy_id: "grid-1".to_string(),
+            priority: PriorityClass::Critical,
+            trading_pair: PAIR.to_string(),
+            exchange: "jupiter".to_string(),
+            side: TradeSide::Buy,
+            order_type: OrderType::Market,
+            size: Decimal::from(i as u64 + 1),
+            price: dec!(101),
+            execution_style: ExecutionStyle::Aggressive,
+            max_slippage: Bps::new(100),
+            tick: None,
+            size_multiplier: None,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+    async fn test_concurrent_executions_lose_no_updates() {
+        let submitter = Arc::new(MockSubmitter::default());
+        let engine = ready_engine(submitter.clone()).await;
+        let position = Position::new(PAIR.to_string(), dec!(1), dec!(100))
+            .unwrap()
+            .with_event_log(engine.position_events(), None);
+        let position_id = position.id;
+        engine.track_position(position).await;
+        let mut trades = engine.subscribe_trades();
+
+        // Every call executes and marks the position while the others are in flight
+        let calls: Vec<_> = (0..CONCURRENT_CALLS)
+            .map(|i| {
+                let engine = engine.clone();
+                tokio::spawn(async move {
+                    let result = engine.execute_strategy(params(i)).await;
+                    engine
+                        .manage_positions(vec![PositionUpdate {
+                            trading_pair: PAIR.to_string(),
+                            size: dec!(1),
+                            price: dec!(100) + Decimal::new(i as i64, 2),
+                        }])
+                        .await
+                        .unwrap();
+                    result
+                })
+            })
+            .collect();
+        for call in futures::future::join_all(calls).await {
+            assert!(call.unwrap().is_ok());
+        }
+
+        assert_eq!(submitter.calls.load(Ordering::SeqCst), CONCURRENT_CALLS);
+        assert!(submitter.max_in_flight.load(Ordering::SeqCst) > 1);
+        {
+            let metrics = engine.metrics.read().await;
+            assert_eq!(metrics.trades_executed, CONCURRENT_CALLS as u64);
+            assert_eq!(metrics.trades_failed, 0);
+            assert!(metrics.average_execution_time >= Duration::from_millis(20));
+            assert_eq!(metrics.circuit_breaker_triggers, 0);
+        }
+        assert_eq!(engine.circuit_breaker.status().consecutive_failures, 0);
+
+        let mut published = Decimal::ZERO;
+        while let Ok(event) = trades.try_recv() {
+            published += event.size;
+        }
+        let expected: u64 = (1..=CONCURRENT_CALLS as u64).sum();
+        assert_eq!(published, Decimal::from(expected));
+
+        // Every update landed as its own event, in an unbroken sequence matching live state
+        let events = engine.position_events().events(position_id).await.unwrap();
+        let marks = events
+            .iter()
+            .filter(|record| matches!(record.event, PositionEvent::PriceMarked { .. }))
+            .count();
+        assert_eq!(marks, CONCURRENT_CALLS);
+        assert!(events.iter().enumerate().all(|(i, record)| record.sequence == i as u64));
+        let live = engine.position_states().await;
+        let replayed = PositionState::replay(&events).unwrap();
+        assert_eq!(live.len(), 1);
+        assert_eq!((live[0].size, live[0].current_price), (replayed.size, replayed.current_price));
+
+        // Concurrent failures are all counted and open the breaker exactly once
+        submitter.failing.store(true, Ordering::SeqCst);
+        let calls: Vec<_> = (0..CONCURRENT_CALLS)
+            .map(|i| {
+                let engine = engine.clone();
+                tokio::spawn(async move { engine.execute_strategy(params(i)).await })
+            })
+            .collect();
+        let mut refused = 0;
+        for call in futures::future::join_all(calls).await {
+            match call.unwrap() {
+                Err(ExecutionError::NetworkError(..)) => {}
+                Err(ExecutionError::ValidationError(_)) => refused += 1,
+                other => panic!("unexpected outcome {:?}", other),
+            }
+        }
+
+        let failed = submitter.calls.load(Ordering::SeqCst) - CONCURRENT_CALLS;
+        assert_eq!(failed + refused, CONCURRENT_CALLS);
+        assert!(failed > 5);
+        let metrics = engine.metrics.read().await;
+        assert_eq!(metrics.trades_executed, CONCURRENT_CALLS as u64);
+        assert_eq!(metrics.trades_failed, failed as u64);
+        assert_eq!(metrics.circuit_breaker_triggers, 1);
+        assert!(engine.circuit_breaker.is_open());
+    }
+
+    #[test]
+    fn test_execution_breaker_opens_past_threshold() {
+        // 5 failures per hundred are tolerated; the sixth opens the breaker
+        let breaker = execution_breaker(&CircuitBreakerConfig {
+            threshold: CIRCUIT_BREAKER_THRESHOLD,
+            cooldown: None,
+        });
+        for _ in 0..5 {
+            assert!(!breaker.record_failure());
+        }
+        assert!(breaker.allow());
+        assert!(breaker.record_failure());
+        assert!(!breaker.allow());
+    }
+
+    #[test]
+    fn test_trade_event_records_slippage() {
+        let execution = ExecutionResult {
+            trade_id: "sig".to_string(),
+            execution_time: Duration::from_millis(120),
+            price: dec!(100.1),
+            mev_value: 0.0,
+            fills: vec![
+                OrderFill::new("sig".to_string(), dec!(1), dec!(100)),
+                OrderFill::new("sig".to_string(), dec!(3), dec!(100.2)),
+            ],
+            fees: FeeBreakdown::default(),
+            slippage: Some(SlippageReport {
+                configured: Bps::new(50),
+                realized: Bps::new(15),
+            }),
+            compute: None,
+            size_multiplier: Some(dec!(0.5)),
+        };
+        assert_eq!(average_fill_price(&execution.fills), Some(dec!(100.15)));
+
+        let event = TradeEvent::from_execution(
+            "grid-1".to_string(),
+            "SOL/USDC".to_string(),
+            "drift".to_string(),
+            TradeSide::Buy,
+            &execution,
+        )
+        .unwrap();
+        assert_eq!(event.configured_slippage_bps, Some(Bps::new(50)));
+        assert_eq!(event.realized_slippage_bps, Some(Bps::new(15)));
+        assert_eq!(event.size_multiplier, Some(dec!(0.5)));
+
+        // Events published before slippage was recorded still decode
+        let mut value = serde_json::to_value(&event).unwrap();
+        value.as_object_mut().unwrap().remove("configured_slippage_bps");
+        value.as_object_mut().unwrap().remove("realized_slippage_bps");
+        let decoded: TradeEvent = serde_json::from_value(value).unwrap();
+        assert_eq!(decoded.configured_slippage_bps, None);
+    }
+
+    #[tokio::test]
+    async fn test_fill_realizes_pnl_into_position_metrics() {
+        let engine = ready_engine(Arc::new(MockSubmitter::default())).await;
+        let position = Position::new(PAIR.to_string(), dec!(2), dec!(100)).unwrap();
+        let tracked = position.clone();
+        engine.track_position(position).await;
+
+        let close = engine.apply_position_fill(PAIR, dec!(-1), dec!(110), None).await.unwrap().unwrap();
+        assert_eq!(close.realized_pnl, dec!(10));
+        assert_eq!(tracked.get_metrics().await.unwrap().realized_pnl, dec!(10));
+
+        engine.apply_position_fill(PAIR, dec!(-1), dec!(120), None).await.unwrap();
+        assert_eq!(tracked.get_metrics().await.unwrap().realized_pnl, dec!(30));
+        assert!(engine.position_states().await.is_empty());
+
+    }
+
+    #[tokio::test]
+    async fn test_opening_fill_tracks_position() {
+        let engine = ready_engine(Arc::new(MockSubmitter::default())).await;
+        let order_id = Uuid::new_v4();
+
+        assert!(engine.apply_position_fill(PAIR, dec!(2), dec!(100), Some(order_id)).await.unwrap().is_none());
+        let states = engine.position_states().await;
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].size, dec!(2));
+        assert_eq!(states[0].entry_price, dec!(100));
+        let history = engine.position_events().events(states[0].position_id).await.unwrap();
+        assert!(matches!(history[0].event, PositionEvent::Opened { .. }));
+        assert_eq!(history[0].cause_id, Some(order_id));
+
+        // A later fill nets into the tracked position instead of replacing it
+        engine.apply_position_fill(PAIR, dec!(1), dec!(110), None).await.unwrap();
+        let states = engine.position_states().await;
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].size, dec!(3));
+
+        // A sell with nothing tracked does not open a position
+        engine.apply_position_fill("BONK/SOL", dec!(-5), dec!(1), None).await.unwrap();
+        assert_eq!(engine.position_states().await.len(), 1);
+    }
+}
@@ -0,0 +1,786 @@
+//! Multi-leg execution: a `TradeGroup` of orders across venues sharing a correlation id and
+//! executed under one atomicity policy. `AllOrNothing` groups go out as a single Jito bundle
+//! holding every venue transaction, so they land whole or not at all; when a leg's venue
+//! builds its own transaction the group cannot be bundled and falls back to sequential
+//! execution with hedging. `BestEffortWithHedge` groups execute leg by leg, and a failed leg
+//! triggers an immediate offsetting order for every leg already filled, on that leg's venue.
+//! Groups are risk-checked on their net exposure, and every partial outcome is recorded,
+//! alerted and kept with its hedges for audit. In the bot the execution engine submits the
+//! legs and strategies emit groups as signals sharing a `SignalGroup`.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - async-trait = "0.1"
+//! - parking_lot = "0.12"
+//! - rust_decimal = "1.30"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::RwLock as SyncRwLock;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::api::WebhookDispatcher;
+use crate::execution_engine::adapters::{ExchangeAdapter, GuardedOrder, OrderRequest};
+use crate::execution_engine::error::ExecutionError;
+use crate::models::webhook::{WebhookEvent, WebhookEventType};
+use crate::risk_manager::exposure::{ExposureBook, ExposureCheck, ExposureLimits, TradeSide};
+use crate::utils::percent::Bps;
+
+// Multi-leg constants
+pub const MAX_BUNDLE_LEGS: usize = 5;
+const DEFAULT_HEDGE_MAX_SLIPPAGE: Bps = Bps::new(100);
+const ALERT_CHANNEL_CAPACITY: usize = 64;
+const METRICS_PREFIX: &str = "trading_bot.multi_leg";
+
+/// How a group behaves when one of its legs cannot execute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AtomicityPolicy {
+    /// Every leg in one Jito bundle where the venues allow it
+    AllOrNothing,
+    /// Legs execute in order; filled legs are offset when a later leg fails
+    BestEffortWithHedge,
+}
+
+/// One order of a group, routed to a named venue adapter
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeLeg {
+    pub exchange: String,
+    pub order: OrderRequest,
+}
+
+/// Orders that must execute together, tied by a correlation id
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeGroup {
+    pub correlation_id: Uuid,
+    pub strategy_id: String,
+    pub policy: AtomicityPolicy,
+    pub legs: Vec<TradeLeg>,
+}
+
+impl TradeGroup {
+    pub fn new(strategy_id: impl Into<String>, policy: AtomicityPolicy, legs: Vec<TradeLeg>) -> Self {
+        Self {
+            correlation_id: Uuid::new_v4(),
+            strategy_id: strategy_id.into(),
+            policy,
+            legs,
+        }
+    }
+}
+
+/// A leg prepared by its venue adapter, ready for submission
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedLeg {
+    pub leg: TradeLeg,
+    pub order: GuardedOrder,
+}
+
+/// What a submitted leg filled at
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LegFill {
+    pub size: Decimal,
+    pub price: Decimal,
+    pub signature: Option<String>,
+}
+
+/// Submits a strategy's prepared legs, alone or as one Jito bundle
+#[async_trait]
+pub trait LegSubmitter: Send + Sync {
+    /// Submits every leg's transaction in one bundle; either all fill or none do
+    async fn submit_bundle(&self, strategy_id: &str, legs: &[PreparedLeg]) -> Result<Vec<LegFill>, ExecutionError>;
+
+    async fn submit(&self, strategy_id: &str, leg: &PreparedLeg) -> Result<LegFill, ExecutionError>;
+}
+
+/// Persists group results for the audit trail
+#[async_trait]
+pub trait TradeGroupStore: Send + Sync {
+    async fn record(&self, result: &TradeGroupResult) -> Result<(), ExecutionError>;
+}
+
+/// How one leg of a group ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LegStatus {
+    Filled,
+    Failed,
+    /// Not submitted because an earlier leg failed
+    Skipped,
+}
+
+/// Outcome of one leg
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LegResult {
+    pub exchange: String,
+    pub trading_pair: String,
+    pub side: TradeSide,
+    pub requested_size: Decimal,
+    pub status: LegStatus,
+    pub fill: Option<LegFill>,
+    pub error: Option<String>,
+}
+
+/// Offsetting order placed for a filled leg after a later leg failed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HedgeAction {
+    /// Index of the leg being offset
+    pub leg: usize,
+    pub exchange: String,
+    pub trading_pair: String,
+    pub side: TradeSide,
+    pub size: Decimal,
+    /// Reference price the hedge was priced from, the offset leg's fill
+    pub price: Decimal,
+    pub reason: String,
+    pub fill: Option<LegFill>,
+    pub error: Option<String>,
+}
+
+/// Where a group ended up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupStatus {
+    /// Every leg filled
+    Completed,
+    /// No leg filled, so nothing needed offsetting
+    Failed,
+    /// Some legs filled and every one of them was offset
+    Hedged,
+    /// Some legs filled and at least one hedge failed, leaving open inventory
+    Unhedged,
+}
+
+/// Group-level result reported to the caller, alerted on partial outcomes and recorded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeGroupResult {
+    pub correlation_id: Uuid,
+    pub strategy_id: String,
+    pub policy: AtomicityPolicy,
+    /// Whether the legs went out as one bundle
+    pub bundled: bool,
+    pub status: GroupStatus,
+    pub legs: Vec<LegResult>,
+    pub hedges: Vec<HedgeAction>,
+    pub completed_at: DateTime<Utc>,
+}
+
+impl TradeGroupResult {
+    /// True when only some legs filled, whether or not they were hedged
+    pub fn is_partial(&self) -> bool {
+        matches!(self.status, GroupStatus::Hedged | GroupStatus::Unhedged)
+    }
+}
+
+/// Multi-leg execution settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiLegConfig {
+    /// Slippage allowed on hedge orders, wider than the legs' so the offset fills
+    pub hedge_max_slippage: Bps,
+    /// Limits the group's net exposure is checked against
+    pub exposure_limits: ExposureLimits,
+}
+
+impl Default for MultiLegConfig {
+    fn default() -> Self {
+        Self {
+            hedge_max_slippage: DEFAULT_HEDGE_MAX_SLIPPAGE,
+            exposure_limits: ExposureLimits::default(),
+        }
+    }
+}
+
+/// Validates, prepares and executes trade groups under their atomicity policy
+pub struct MultiLegExecutor {
+    config: SyncRwLock<MultiLegConfig>,
+    adapters: SyncRwLock<HashMap<String, Arc<dyn ExchangeAdapter>>>,
+    submitter: Arc<dyn LegSubmitter>,
+    store: Option<Arc<dyn TradeGroupStore>>,
+    alerts: broadcast::Sender<TradeGroupResult>,
+    webhooks: SyncRwLock<Option<Arc<WebhookDispatcher>>>,
+}
+
+impl std::fmt::Debug for MultiLegExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiLegExecutor")
+            .field("config", &*self.config.read())
+            .field("adapters", &self.adapters.read().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl MultiLegExecutor {
+    pub fn new(config: MultiLegConfig, submitter: Arc<dyn LegSubmitter>) -> Self {
+        let (alerts, _) = broadcast::channel(ALERT_CHANNEL_CAPACITY);
+        Self {
+            config: SyncRwLock::new(config),
+            adapters: SyncRwLock::new(HashMap::new()),
+            submitter,
+            store: None,
+            alerts,
+            webhooks: SyncRwLock::new(None),
+        }
+    }
+
+    /// Routes legs naming the adapter's exchange through it
+    pub fn with_adapter(self, adapter: Arc<dyn ExchangeAdapter>) -> Self {
+        self.set_adapter(adapter);
+        self
+    }
+
+    /// Registers a venue adapter on an executor already shared with the bot, replacing any
+    /// earlier adapter for the same exchange
+    pub fn set_adapter(&self, adapter: Arc<dyn ExchangeAdapter>) {
+        self.adapters.write().insert(adapter.exchange().to_string(), adapter);
+    }
+
+    /// Records every group result, hedges included, for audit
+    pub fn with_store(mut self, store: Arc<dyn TradeGroupStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Sends partial group outcomes through the webhook dispatcher
+    pub fn set_webhooks(&self, webhooks: Arc<WebhookDispatcher>) {
+        *self.webhooks.write() = Some(webhooks);
+    }
+
+    /// Receives partial group outcomes as they happen
+    pub fn subscribe(&self) -> broadcast::Receiver<TradeGroupResult> {
+        self.alerts.subscribe()
+    }
+
+    pub fn config(&self) -> MultiLegConfig {
+        self.config.read().clone()
+    }
+
+    /// Checks later groups against reloaded exposure limits
+    pub fn set_exposure_limits(&self, exposure_limits: ExposureLimits) {
+        self.config.write().exposure_limits = exposure_limits;
+    }
+
+    /// Checks the group's net exposure, legs in the same underlying offsetting each other.
+    /// Legs are valued at their decision price.
+    pub fn validate(&self, group: &TradeGroup, exposure: &ExposureBook) -> Result<Vec<ExposureCheck>, ExecutionError> {
+        if group.legs.len() < 2 {
+            return Err(ExecutionError::ValidationError(format!(
+                "trade group {} needs at least two legs",
+                group.correlation_id
+            )));
+        }
+        let checks = exposure.evaluate_group(
+            group.legs.iter().map(|leg| {
+                (
+                    leg.order.trading_pair.as_str(),
+                    leg.order.side.signed(leg.order.size),
+                    leg.order.price,
+                )
+            }),
+            &self.config.read().exposure_limits,
+        );
+        if let Some(breach) = checks.iter().find_map(|check| check.breach.clone()) {
+            counter!(format!("{}.risk_rejections", METRICS_PREFIX), 1);
+            return Err(ExecutionError::ValidationError(format!(
+                "trade group {}: {}",
+                group.correlation_id, breach
+            )));
+        }
+        Ok(checks)
+    }
+
+    /// Executes a group after checking its net exposure. Rejections before any leg is
+    /// submitted are errors; once submission starts the outcome is a result, recorded and
+    /// alerted when partial.
+    #[instrument(skip(self, group, exposure), fields(correlation_id = %group.correlation_id, policy = ?group.policy))]
+    pub async fn execute(&self, group: &TradeGroup, exposure: &ExposureBook) -> Result<TradeGroupResult, ExecutionError> {
+        self.validate(group, exposure)?;
+
+        let mut prepared = Vec::with_capacity(group.legs.len());
+        for leg in &group.legs {
+            let order = self.adapter(&leg.exchange)?.prepare(&leg.order).await?;
+            prepared.push(PreparedLeg { leg: leg.clone(), order });
+        }
+
+        let bundled = group.policy == AtomicityPolicy::AllOrNothing && bundleable(&prepared);
+        if group.policy == AtomicityPolicy::AllOrNothing && !bundled {
+            counter!(format!("{}.bundle_fallbacks", METRICS_PREFIX), 1);
+            warn!("Trade group cannot be bundled, executing legs with hedging");
+        }
+
+        let (legs, hedges) = if bundled {
+            (self.execute_bundle(&group.strategy_id, &prepared).await, Vec::new())
+        } else {
+            self.execute_sequential(&group.strategy_id, &prepared).await
+        };
+
+        let result = TradeGroupResult {
+            correlation_id: group.correlation_id,
+            strategy_id: group.strategy_id.clone(),
+            policy: group.policy,
+            bundled,
+            status: group_status(&legs, &hedges),
+            legs,
+            hedges,
+            completed_at: Utc::now(),
+        };
+        self.report(&result).await;
+        Ok(result)
+    }
+
+    fn adapter(&self, exchange: &str) -> Result<Arc<dyn ExchangeAdapter>, ExecutionError> {
+        self.adapters
+            .read()
+            .get(exchange)
+            .cloned()
+            .ok_or_else(|| ExecutionError::ValidationError(format!("no adapter for exchange {}", exchange)))
+    }
+
+    async fn execute_bundle(&self, strategy_id: &str, prepared: &[PreparedLeg]) -> Vec<LegResult> {
+        match self.submitter.submit_bundle(strategy_id, prepared).await {
+            Ok(fills) if fills.len() == prepared.len() => prepared
+                .iter()
+                .zip(fills)
+                .map(|(leg, fill)| leg_result(&leg.leg, LegStatus::Filled, Some(fill), None))
+                .collect(),
+            Ok(fills) => {
+                // A bundle lands whole, so a short fill list means the landing was misreported
+                error!(expected = prepared.len(), got = fills.len(), "Bundle reported wrong number of fills");
+                let message = format!("bundle reported {} fills for {} legs", fills.len(), prepared.len());
+                prepared
+                    .iter()
+                    .map(|leg| leg_result(&leg.leg, LegStatus::Failed, None, Some(message.clone())))
+                    .collect()
+            }
+            Err(e) => prepared
+                .iter()
+                .map(|leg| leg_result(&leg.leg, LegStatus::Failed, None, Some(e.to_string())))
+                .collect(),
+        }
+    }
+
+    async fn execute_sequential(&self, strategy_id: &str, prepared: &[PreparedLeg]) -> (Vec<LegResult>, Vec<HedgeAction>) {
+        let mut legs = Vec::with_capacity(prepared.len());
+        let mut failure = None;
+        for leg in prepared {
+            if failure.is_some() {
+                legs.push(leg_result(&leg.leg, LegStatus::Skipped, None, None));
+                continue;
+            }
+            match self.submitter.submit(strategy_id, leg).await {
+                Ok(fill) => legs.push(leg_result(&leg.leg, LegStatus::Filled, Some(fill), None)),
+                Err(e) => {
+                    failure = Some(format!("leg on {} failed: {}", leg.leg.exchange, e));
+                    legs.push(leg_result(&leg.leg, LegStatus::Failed, None, Some(e.to_string())));
+                }
+            }
+        }
+
+        let Some(reason) = failure else {
+            return (legs, Vec::new());
+        };
+        let mut hedges = Vec::new();
+        for (index, leg) in legs.iter().enumerate() {
+            if let Some(fill) = &leg.fill {
+                hedges.push(self.hedge(strategy_id, index, leg, fill, &reason).await);
+            }
+        }
+        (legs, hedges)
+    }
+
+    /// Offsets a filled leg with an opposite order of the filled size on the same venue
+    async fn hedge(&self, strategy_id: &str, index: usize, leg: &LegResult, fill: &LegFill, reason: &str) -> HedgeAction {
+        let side = match leg.side {
+            TradeSide::Buy => TradeSide::Sell,
+            TradeSide::Sell => TradeSide::Buy,
+        };
+        let order = OrderRequest {
+            trading_pair: leg.trading_pair.clone(),
+            side,
+            size: fill.size,
+            price: fill.price,
+            max_slippage: self.config.read().hedge_max_slippage,
+        };
+        let mut action = HedgeAction {
+            leg: index,
+            exchange: leg.exchange.clone(),
+            trading_pair: leg.trading_pair.clone(),
+            side,
+            size: fill.size,
+            price: fill.price,
+            reason: reason.to_string(),
+            fill: None,
+            error: None,
+        };
+
+        let submitted = match self.adapter(&leg.exchange) {
+            Ok(adapter) => match adapter.prepare(&order).await {
+                Ok(guarded) => {
+                    let prepared = PreparedLeg {
+                        leg: TradeLeg {
+                            exchange: leg.exchange.clone(),
+                            order,
+                        },
+                        order: guarded,
+                    };
+                    self.submitter.submit(strategy_id, &prepared).await
+                }
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        };
+        match submitted {
+            Ok(hedge_fill) => {
+                counter!(format!("{}.hedges", METRICS_PREFIX), 1);
+                info!(exchange = %action.exchange, size = %action.size, "Hedged filled leg");
+                action.fill = Some(hedge_fill);
+            }
+            Err(e) => {
+                counter!(format!("{}.hedge_failures", METRICS_PREFIX), 1);
+                error!(exchange = %action.exchange, size = %action.size, "Hedge order failed: {}", e);
+                action.error = Some(e.to_string());
+            }
+        }
+        action
+    }
+
+    /// Records the result and alerts on partial outcomes; a failed record is logged, never
+    /// dropped silently
+    async fn report(&self, result: &TradeGroupResult) {
+        counter!(format!("{}.groups", METRICS_PREFIX), 1);
+        if let Some(store) = &self.store {
+            if let Err(e) = store.record(result).await {
+                counter!(format!("{}.record_failures", METRICS_PREFIX), 1);
+                error!(correlation_id = %result.correlation_id, "Failed to record trade group: {}", e);
+            }
+        }
+        if !result.is_partial() {
+            return;
+        }
+
+        counter!(format!("{}.partial", METRICS_PREFIX), 1);
+        match result.status {
+            GroupStatus::Unhedged => error!(
+                correlation_id = %result.correlation_id,
+                "Trade group left unhedged inventory"
+            ),
+            _ => warn!(correlation_id = %result.correlation_id, "Trade group partially filled and hedged"),
+        }
+        let _ = self.alerts.send(result.clone());
+
+        let Some(webhooks) = self.webhooks.read().clone() else {
+            return;
+        };
+        match WebhookEvent::new(WebhookEventType::TradeGroupPartial, result) {
+            Ok(event) => webhooks.dispatch(event),
+            Err(e) => warn!("Failed to build webhook event: {}", e),
+        }
+    }
+}
+
+/// Whether prepared legs can share one bundle: each must carry its own instructions, which
+/// venues that build the transaction themselves do not
+pub fn bundleable(prepared: &[PreparedLeg]) -> bool {
+    !prepared.is_empty()
+        && prepared.len() <= MAX_BUNDLE_LEGS
+        && prepared.iter().all(|leg| !leg.order.instructions.is_empty())
+}
+
+fn leg_result(leg: &TradeLeg, status: LegStatus, fill: Option<LegFill>, error: Option<String>) -> LegResult {
+    LegResult {
+        exchange: leg.exchange.clone(),
+        trading_pair: leg.order.trading_pair.clone(),
+        side: leg.order.side,
+        requested_size: leg.order.size,
+        status,
+        fill,
+        error,
+    }
+}
+
+fn group_status(legs: &[LegResult], hedges: &[HedgeAction]) -> GroupStatus {
+    let filled = legs.iter().filter(|leg| leg.status == LegStatus::Filled).count();
+    if filled == legs.len() {
+        GroupStatus::Completed
+    } else if filled == 0 {
+        GroupStatus::Failed
+    } else if hedges.iter().all(|hedge| hedge.fill.is_some()) {
+        GroupStatus::Hedged
+    } else {
+        GroupStatus::Unhedged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_engine::adapters::{DriftDirection, DriftOrderParams, SlippageGuard, VenueOrder};
+    use parking_lot::Mutex;
+    use rust_decimal_macros::dec;
+    use solana_sdk::instruction::Instruction;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[derive(Debug)]
+    struct MockAdapter {
+        exchange: &'static str,
+        /// Whether prepared orders carry instructions and so can be bundled
+        bundleable: bool,
+    }
+
+    #[async_trait]
+    impl ExchangeAdapter for MockAdapter {
+        fn exchange(&self) -> &str {
+            self.exchange
+        }
+
+        async fn prepare(&self, request: &OrderRequest) -> Result<GuardedOrder, ExecutionError> {
+            let instructions = if self.bundleable {
+                vec![Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![])]
+            } else {
+                Vec::new()
+            };
+            Ok(GuardedOrder {
+                guard: SlippageGuard {
+                    max_slippage: request.max_slippage,
+                    quoted_price: request.price,
+                    limit_price: request.price,
+                    min_out_amount: None,
+                },
+                order: VenueOrder::Drift(DriftOrderParams {
+                    market_index: 0,
+                    direction: DriftDirection::Long,
+                    base_asset_amount: 1,
+                    price: 1,
+                    immediate_or_cancel: true,
+                    reduce_only: false,
+                }),
+                compute_budget: None,
+                instructions,
+                account_rent_lamports: 0,
+            })
+        }
+    }
+
+    /// Fills every leg except those on `failing_exchange` or numbered `fail_from` onwards;
+    /// bundles fail when `reject_bundle`
+    #[derive(Default)]
+    struct MockSubmitter {
+        failing_exchange: Option<&'static str>,
+        fail_from: Option<usize>,
+        reject_bundle: bool,
+        bundles: Mutex<usize>,
+        submitted: Mutex<Vec<TradeLeg>>,
+    }
+
+    #[async_trait]
+    impl LegSubmitter for MockSubmitter {
+        async fn submit_bundle(&self, _strategy_id: &str, legs: &[PreparedLeg]) -> Result<Vec<LegFill>, ExecutionError> {
+            *self.bundles.lock() += 1;
+            if self.reject_bundle {
+                return Err(ExecutionError::MevBundleError("bundle dropped".to_string()));
+            }
+            Ok(legs.iter().map(|leg| fill(&leg.leg.order)).collect())
+        }
+
+        async fn submit(&self, _strategy_id: &str, leg: &PreparedLeg) -> Result<LegFill, ExecutionError> {
+            let mut submitted = self.submitted.lock();
+            submitted.push(leg.leg.clone());
+            if self.failing_exchange == Some(leg.leg.exchange.as_str())
+                || self.fail_from.map_or(false, |from| submitted.len() > from)
+            {
+                return Err(ExecutionError::LiquidityError("no depth".to_string()));
+            }
+            Ok(fill(&leg.leg.order))
+        }
+    }
+
+    #[derive(Default)]
+    struct MockStore {
+        recorded: Mutex<Vec<TradeGroupResult>>,
+    }
+
+    #[async_trait]
+    impl TradeGroupStore for MockStore {
+        async fn record(&self, result: &TradeGroupResult) -> Result<(), ExecutionError> {
+            self.recorded.lock().push(result.clone());
+            Ok(())
+        }
+    }
+
+    fn fill(order: &OrderRequest) -> LegFill {
+        LegFill {
+            size: order.size,
+            price: order.price,
+            signature: None,
+        }
+    }
+
+    fn leg(exchange: &str, trading_pair: &str, side: TradeSide, size: Decimal, price: Decimal) -> TradeLeg {
+        TradeLeg {
+            exchange: exchange.to_string(),
+            order: OrderRequest {
+                trading_pair: trading_pair.to_string(),
+                side,
+                size,
+                price,
+                max_slippage: Bps::new(30),
+            },
+        }
+    }
+
+    fn arbitrage(policy: AtomicityPolicy) -> TradeGroup {
+        TradeGroup::new(
+            "arb-1",
+            policy,
+            vec![
+                leg("jupiter", "SOL/USDC", TradeSide::Buy, dec!(15), dec!(20)),
+                leg("drift", "SOL-PERP", TradeSide::Sell, dec!(15), dec!(20.2)),
+            ],
+        )
+    }
+
+    fn executor(submitter: Arc<MockSubmitter>, bundleable: bool) -> MultiLegExecutor {
+        MultiLegExecutor::new(MultiLegConfig::default(), submitter)
+            .with_adapter(Arc::new(MockAdapter {
+                exchange: "jupiter",
+                bundleable,
+            }))
+            .with_adapter(Arc::new(MockAdapter {
+                exchange: "drift",
+                bundleable,
+            }))
+    }
+
+    #[tokio::test]
+    async fn test_bundle_lands_every_leg() {
+        let submitter = Arc::new(MockSubmitter::default());
+        let executor = executor(submitter.clone(), true);
+
+        let result = executor
+            .execute(&arbitrage(AtomicityPolicy::AllOrNothing), &ExposureBook::new(dec!(1000)))
+            .await
+            .unwrap();
+        assert!(result.bundled);
+        assert_eq!(result.status, GroupStatus::Completed);
+        assert_eq!(*submitter.bundles.lock(), 1);
+        assert!(submitter.submitted.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dropped_bundle_leaves_nothing_to_hedge() {
+        let submitter = Arc::new(MockSubmitter {
+            reject_bundle: true,
+            ..Default::default()
+        });
+        let executor = executor(submitter.clone(), true);
+
+        let result = executor
+            .execute(&arbitrage(AtomicityPolicy::AllOrNothing), &ExposureBook::new(dec!(1000)))
+            .await
+            .unwrap();
+        assert_eq!(result.status, GroupStatus::Failed);
+        assert!(result.legs.iter().all(|leg| leg.status == LegStatus::Failed));
+        assert!(result.hedges.is_empty());
+        assert!(submitter.submitted.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_second_leg_hedges_first_venue() {
+        let submitter = Arc::new(MockSubmitter {
+            failing_exchange: Some("drift"),
+            ..Default::default()
+        });
+        let store = Arc::new(MockStore::default());
+        let executor = executor(submitter.clone(), false).with_store(store.clone());
+        let mut alerts = executor.subscribe();
+
+        // Unbundleable venues fall back from all-or-nothing to hedged sequential execution
+        let group = arbitrage(AtomicityPolicy::AllOrNothing);
+        let result = executor.execute(&group, &ExposureBook::new(dec!(1000))).await.unwrap();
+        assert!(!result.bundled);
+        assert_eq!(*submitter.bundles.lock(), 0);
+        assert_eq!(result.status, GroupStatus::Hedged);
+        assert_eq!(result.legs[0].status, LegStatus::Filled);
+        assert_eq!(result.legs[1].status, LegStatus::Failed);
+
+        let hedge = &result.hedges[0];
+        assert_eq!(result.hedges.len(), 1);
+        assert_eq!(hedge.leg, 0);
+        assert_eq!(hedge.exchange, "jupiter");
+        assert_eq!(hedge.side, TradeSide::Sell);
+        assert_eq!(hedge.size, dec!(15));
+        assert!(hedge.fill.is_some());
+        assert!(hedge.reason.contains("drift"));
+
+        let submitted = submitter.submitted.lock().clone();
+        assert_eq!(submitted.len(), 3);
+        assert_eq!(submitted[2].exchange, "jupiter");
+        assert_eq!(submitted[2].order.side, TradeSide::Sell);
+        assert_eq!(submitted[2].order.size, dec!(15));
+        assert_eq!(submitted[2].order.max_slippage, DEFAULT_HEDGE_MAX_SLIPPAGE);
+
+        assert_eq!(alerts.try_recv().unwrap().correlation_id, group.correlation_id);
+        assert_eq!(store.recorded.lock()[0].hedges, result.hedges);
+    }
+
+    #[tokio::test]
+    async fn test_failed_hedge_leaves_group_unhedged() {
+        // The first submission fills; the second leg and the hedge after it both fail
+        let submitter = Arc::new(MockSubmitter {
+            fail_from: Some(1),
+            ..Default::default()
+        });
+        let executor = executor(submitter, false);
+        let mut alerts = executor.subscribe();
+
+        let result = executor
+            .execute(&arbitrage(AtomicityPolicy::BestEffortWithHedge), &ExposureBook::new(dec!(1000)))
+            .await
+            .unwrap();
+        assert_eq!(result.status, GroupStatus::Unhedged);
+        assert!(result.hedges[0].fill.is_none());
+        assert!(result.hedges[0].error.is_some());
+        assert_eq!(alerts.try_recv().unwrap().status, GroupStatus::Unhedged);
+    }
+
+    #[tokio::test]
+    async fn test_failed_first_leg_skips_the_rest() {
+        let submitter = Arc::new(MockSubmitter {
+            failing_exchange: Some("jupiter"),
+            ..Default::default()
+        });
+        let executor = executor(submitter.clone(), false);
+        let mut alerts = executor.subscribe();
+
+        let result = executor
+            .execute(&arbitrage(AtomicityPolicy::BestEffortWithHedge), &ExposureBook::new(dec!(1000)))
+            .await
+            .unwrap();
+        assert_eq!(result.status, GroupStatus::Failed);
+        assert_eq!(result.legs[1].status, LegStatus::Skipped);
+        assert!(result.hedges.is_empty());
+        assert_eq!(submitter.submitted.lock().len(), 1);
+        assert!(alerts.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_group_risk_checked_on_net_exposure() {
+        let executor = executor(Arc::new(MockSubmitter::default()), true);
+        let book = ExposureBook::new(dec!(1000));
+
+        // Either leg alone would be 30% of equity; together they net out
+        let mut group = arbitrage(AtomicityPolicy::AllOrNothing);
+        assert!(executor.validate(&group, &book).is_ok());
+
+        group.legs[1].order.size = dec!(1);
+        let err = executor.validate(&group, &book).unwrap_err();
+        assert!(err.to_string().contains("net SOL exposure"));
+    }
+}
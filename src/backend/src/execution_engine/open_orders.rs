@@ -0,0 +1,468 @@
+//! Registry of open orders keyed by order id, backing single and bulk cancellation. Cancels
+//! race against execution, so an order that fills while its cancel is in flight is reported
+//! as already filled rather than as a failure.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - futures = "0.3"
+//! - async-trait = "0.1"
+//! - parking_lot = "0.12"
+//! - utoipa = "3.5"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use metrics::counter;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{info, instrument, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::order::{Order, OrderError, OrderStatus};
+use crate::risk_manager::exposure::TradeSide;
+use crate::utils::solana::SolanaClient;
+
+// Open order constants
+const MAX_PARALLEL_CANCELS: usize = 8;
+const FILLED_RETENTION_SECS: i64 = 300;
+const METRICS_PREFIX: &str = "trading_bot.open_orders";
+
+/// Cancellation lookup errors
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum CancelError {
+    #[error("order {0} not found")]
+    NotFound(Uuid),
+    #[error("order {0} belongs to another wallet")]
+    NotOwner(Uuid),
+}
+
+/// Submits cancellations to the venue
+#[async_trait]
+pub trait OrderCanceller: Send + Sync {
+    async fn cancel(&self, order: &mut Order) -> Result<(), OrderError>;
+}
+
+#[async_trait]
+impl OrderCanceller for SolanaClient {
+    async fn cancel(&self, order: &mut Order) -> Result<(), OrderError> {
+        order.cancel(self).await
+    }
+}
+
+/// Criteria selecting orders for a bulk cancel; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CancelFilter {
+    pub trading_pair: Option<String>,
+    pub exchange: Option<String>,
+    pub strategy_id: Option<String>,
+    pub side: Option<TradeSide>,
+}
+
+impl CancelFilter {
+    /// True when no criterion is set
+    pub fn is_empty(&self) -> bool {
+        self.trading_pair.is_none() && self.exchange.is_none() && self.strategy_id.is_none() && self.side.is_none()
+    }
+
+    fn matches(&self, open: &OpenOrder) -> bool {
+        self.trading_pair.as_ref().map_or(true, |pair| *pair == open.order.trading_pair)
+            && self.exchange.as_ref().map_or(true, |exchange| *exchange == open.order.exchange)
+            && self.strategy_id.as_ref().map_or(true, |id| *id == open.strategy_id)
+            && self.side.map_or(true, |side| side == open.side)
+    }
+}
+
+/// How a cancel request ended for one order
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CancelStatus {
+    Cancelled,
+    /// The order executed before the cancel took effect
+    AlreadyFilled,
+    Failed { error: String },
+}
+
+/// Cancel result for one order
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct CancelOutcome {
+    pub order_id: Uuid,
+    #[serde(flatten)]
+    pub status: CancelStatus,
+}
+
+/// Pending order as captured in a state snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingOrderSnapshot {
+    pub order: Order,
+    pub wallet_address: String,
+    pub strategy_id: String,
+    pub side: TradeSide,
+}
+
+#[derive(Debug, Clone)]
+struct OpenOrder {
+    order: Order,
+    wallet_address: String,
+    strategy_id: String,
+    side: TradeSide,
+    cancelling: bool,
+}
+
+/// Orders submitted but not yet settled, keyed by order id
+pub struct OpenOrderRegistry {
+    orders: Mutex<HashMap<Uuid, OpenOrder>>,
+    canceller: Arc<dyn OrderCanceller>,
+}
+
+impl std::fmt::Debug for OpenOrderRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpenOrderRegistry")
+            .field("orders", &self.orders.lock().len())
+            .finish()
+    }
+}
+
+impl OpenOrderRegistry {
+    pub fn new(canceller: Arc<dyn OrderCanceller>) -> Self {
+        Self {
+            orders: Mutex::new(HashMap::new()),
+            canceller,
+        }
+    }
+
+    /// Registers a submitted order under the wallet that owns it
+    pub fn register(&self, order: Order, wallet_address: &str, strategy_id: &str, side: TradeSide) {
+        let mut orders = self.orders.lock();
+        Self::prune_filled(&mut orders, Utc::now());
+        orders.insert(
+            order.id,
+            OpenOrder {
+                order,
+                wallet_address: wallet_address.to_string(),
+                strategy_id: strategy_id.to_string(),
+                side,
+                cancelling: false,
+            },
+        );
+    }
+
+    /// Current state of a registered order
+    pub fn get(&self, order_id: Uuid) -> Option<Order> {
+        self.orders.lock().get(&order_id).map(|open| open.order.clone())
+    }
+
+    /// Records that an order executed. The entry is kept for a while so cancels arriving
+    /// just after the fill report it as already filled.
+    pub fn mark_executed(&self, order_id: Uuid) {
+        if let Some(open) = self.orders.lock().get_mut(&order_id) {
+            open.order.status = OrderStatus::Executed;
+            open.order.executed_at = Some(Utc::now());
+        }
+    }
+
+    /// Forgets an order that never reached the venue
+    pub fn remove(&self, order_id: Uuid) -> Option<Order> {
+        self.orders.lock().remove(&order_id).map(|open| open.order)
+    }
+
+    /// Copies orders not yet settled, sorted by creation time
+    pub fn snapshot(&self) -> Vec<PendingOrderSnapshot> {
+        let mut pending: Vec<PendingOrderSnapshot> = self
+            .orders
+            .lock()
+            .values()
+            .filter(|open| open.order.status == OrderStatus::Pending)
+            .map(|open| PendingOrderSnapshot {
+                order: open.order.clone(),
+                wallet_address: open.wallet_address.clone(),
+                strategy_id: open.strategy_id.clone(),
+                side: open.side,
+            })
+            .collect();
+        pending.sort_by(|a, b| a.order.created_at.cmp(&b.order.created_at).then(a.order.id.cmp(&b.order.id)));
+        pending
+    }
+
+    /// Replaces the registry contents with snapshotted pending orders
+    pub fn restore(&self, pending: &[PendingOrderSnapshot]) {
+        *self.orders.lock() = pending
+            .iter()
+            .map(|snapshot| {
+                (
+                    snapshot.order.id,
+                    OpenOrder {
+                        order: snapshot.order.clone(),
+                        wallet_address: snapshot.wallet_address.clone(),
+                        strategy_id: snapshot.strategy_id.clone(),
+                        side: snapshot.side,
+                        cancelling: false,
+                    },
+                )
+            })
+            .collect();
+    }
+
+    /// Pending orders owned by the wallet that match the filter
+    pub fn matching(&self, wallet_address: &str, filter: &CancelFilter) -> Vec<Uuid> {
+        self.orders
+            .lock()
+            .values()
+            .filter(|open| {
+                open.wallet_address == wallet_address
+                    && open.order.status == OrderStatus::Pending
+                    && filter.matches(open)
+            })
+            .map(|open| open.order.id)
+            .collect()
+    }
+
+    /// Cancels one order on behalf of its owner
+    #[instrument(skip(self))]
+    pub async fn cancel(&self, order_id: Uuid, wallet_address: &str) -> Result<CancelOutcome, CancelError> {
+        let mut order = {
+            let mut orders = self.orders.lock();
+            let open = orders.get_mut(&order_id).ok_or(CancelError::NotFound(order_id))?;
+            if open.wallet_address != wallet_address {
+                return Err(CancelError::NotOwner(order_id));
+            }
+            match open.order.status {
+                OrderStatus::Executed => return Ok(Self::outcome(order_id, CancelStatus::AlreadyFilled)),
+                OrderStatus::Pending if !open.cancelling => {}
+                OrderStatus::Pending => {
+                    return Ok(Self::outcome(order_id, CancelStatus::Failed {
+                        error: "cancel already in progress".to_string(),
+                    }))
+                }
+                ref status => {
+                    return Ok(Self::outcome(order_id, CancelStatus::Failed {
+                        error: format!("order is {:?}", status).to_lowercase(),
+                    }))
+                }
+            }
+            open.cancelling = true;
+            open.order.clone()
+        };
+
+        let result = self.canceller.cancel(&mut order).await;
+
+        // Execution may have completed while the cancel was in flight; the fill wins
+        let mut orders = self.orders.lock();
+        let filled = orders
+            .get(&order_id)
+            .map_or(false, |open| open.order.status == OrderStatus::Executed);
+        let status = if filled {
+            warn!(order_id = %order_id, "Order filled before cancel took effect");
+            CancelStatus::AlreadyFilled
+        } else {
+            match result {
+                Ok(()) => {
+                    orders.remove(&order_id);
+                    info!(order_id = %order_id, "Order cancelled");
+                    CancelStatus::Cancelled
+                }
+                Err(e) => {
+                    if let Some(open) = orders.get_mut(&order_id) {
+                        open.cancelling = false;
+                    }
+                    CancelStatus::Failed { error: e.to_string() }
+                }
+            }
+        };
+        Ok(Self::outcome(order_id, status))
+    }
+
+    /// Cancels every matching order concurrently, at most `MAX_PARALLEL_CANCELS` at a time
+    #[instrument(skip(self))]
+    pub async fn cancel_matching(&self, wallet_address: &str, filter: &CancelFilter) -> Vec<CancelOutcome> {
+        let order_ids = self.matching(wallet_address, filter);
+        stream::iter(order_ids)
+            .map(|order_id| async move {
+                match self.cancel(order_id, wallet_address).await {
+                    Ok(outcome) => outcome,
+                    // Gone since matching, which only happens once it settled
+                    Err(e) => Self::outcome(order_id, CancelStatus::Failed { error: e.to_string() }),
+                }
+            })
+            .buffer_unordered(MAX_PARALLEL_CANCELS)
+            .collect()
+            .await
+    }
+
+    fn outcome(order_id: Uuid, status: CancelStatus) -> CancelOutcome {
+        let label = match status {
+            CancelStatus::Cancelled => "cancelled",
+            CancelStatus::AlreadyFilled => "already_filled",
+            CancelStatus::Failed { .. } => "failed",
+        };
+        counter!(format!("{}.cancel", METRICS_PREFIX), 1, "status" => label);
+        CancelOutcome { order_id, status }
+    }
+
+    fn prune_filled(orders: &mut HashMap<Uuid, OpenOrder>, now: DateTime<Utc>) {
+        let cutoff = now - chrono::Duration::seconds(FILLED_RETENTION_SECS);
+        orders.retain(|_, open| {
+            open.cancelling || open.order.executed_at.map_or(true, |executed_at| executed_at > cutoff)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::order::OrderType;
+    use rust_decimal_macros::dec;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::Notify;
+
+    const WALLET: &str = "wallet_a";
+
+    /// Venue that holds each cancel until released and rejects it if told the order filled
+    #[derive(Default)]
+    struct GatedCanceller {
+        entered: Notify,
+        release: Notify,
+        reject: bool,
+    }
+
+    #[async_trait]
+    impl OrderCanceller for GatedCanceller {
+        async fn cancel(&self, order: &mut Order) -> Result<(), OrderError> {
+            self.entered.notify_one();
+            self.release.notified().await;
+            if self.reject {
+                return Err(OrderError::ExecutionError("order already matched".to_string()));
+            }
+            order.status = OrderStatus::Cancelled;
+            Ok(())
+        }
+    }
+
+    /// Venue that accepts every cancel, tracking peak concurrency
+    #[derive(Default)]
+    struct CountingCanceller {
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl OrderCanceller for CountingCanceller {
+        async fn cancel(&self, order: &mut Order) -> Result<(), OrderError> {
+            let current = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(current, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            order.status = OrderStatus::Cancelled;
+            Ok(())
+        }
+    }
+
+    fn order(pair: &str, exchange: &str) -> Order {
+        Order::new(pair.to_string(), exchange.to_string(), OrderType::Limit, dec!(20), dec!(1)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fill_during_cancel_reports_already_filled() {
+        for reject in [true, false] {
+            let canceller = Arc::new(GatedCanceller {
+                reject,
+                ..Default::default()
+            });
+            let registry = Arc::new(OpenOrderRegistry::new(canceller.clone()));
+            let order = order("SOL/USDC", "jupiter");
+            let order_id = order.id;
+            registry.register(order, WALLET, "grid", TradeSide::Buy);
+
+            let cancel = tokio::spawn({
+                let registry = registry.clone();
+                async move { registry.cancel(order_id, WALLET).await }
+            });
+
+            // Execution completes while the venue is still processing the cancel
+            canceller.entered.notified().await;
+            registry.mark_executed(order_id);
+            canceller.release.notify_one();
+
+            let outcome = cancel.await.unwrap().unwrap();
+            assert_eq!(outcome.status, CancelStatus::AlreadyFilled, "reject = {}", reject);
+            assert_eq!(registry.get(order_id).unwrap().status, OrderStatus::Executed);
+
+            // A cancel arriving after the fill gets the same answer
+            let late = registry.cancel(order_id, WALLET).await.unwrap();
+            assert_eq!(late.status, CancelStatus::AlreadyFilled);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancel_checks_ownership() {
+        let registry = OpenOrderRegistry::new(Arc::new(CountingCanceller::default()));
+        let order = order("SOL/USDC", "jupiter");
+        let order_id = order.id;
+        registry.register(order, WALLET, "grid", TradeSide::Buy);
+
+        assert_eq!(
+            registry.cancel(order_id, "wallet_b").await,
+            Err(CancelError::NotOwner(order_id))
+        );
+        let unknown = Uuid::new_v4();
+        assert_eq!(registry.cancel(unknown, WALLET).await, Err(CancelError::NotFound(unknown)));
+
+        let outcome = registry.cancel(order_id, WALLET).await.unwrap();
+        assert_eq!(outcome.status, CancelStatus::Cancelled);
+        assert!(registry.get(order_id).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_bulk_cancel_by_filter() {
+        let canceller = Arc::new(CountingCanceller::default());
+        let registry = OpenOrderRegistry::new(canceller.clone());
+
+        let mut targeted = Vec::new();
+        for _ in 0..20 {
+            let order = order("BONK/USDC", "jupiter");
+            targeted.push(order.id);
+            registry.register(order, WALLET, "momentum", TradeSide::Buy);
+        }
+        // Different side, pair, exchange, strategy and owner
+        registry.register(order("BONK/USDC", "jupiter"), WALLET, "momentum", TradeSide::Sell);
+        registry.register(order("SOL/USDC", "jupiter"), WALLET, "momentum", TradeSide::Buy);
+        registry.register(order("BONK/USDC", "drift"), WALLET, "momentum", TradeSide::Buy);
+        registry.register(order("BONK/USDC", "jupiter"), WALLET, "grid", TradeSide::Buy);
+        registry.register(order("BONK/USDC", "jupiter"), "wallet_b", "momentum", TradeSide::Buy);
+
+        // One matching order already executed is not a bulk cancel target
+        let executed = order("BONK/USDC", "jupiter");
+        let executed_id = executed.id;
+        registry.register(executed, WALLET, "momentum", TradeSide::Buy);
+        registry.mark_executed(executed_id);
+
+        let filter = CancelFilter {
+            trading_pair: Some("BONK/USDC".to_string()),
+            exchange: Some("jupiter".to_string()),
+            strategy_id: Some("momentum".to_string()),
+            side: Some(TradeSide::Buy),
+        };
+        let outcomes = registry.cancel_matching(WALLET, &filter).await;
+
+        let mut cancelled: Vec<Uuid> = outcomes
+            .iter()
+            .filter(|outcome| outcome.status == CancelStatus::Cancelled)
+            .map(|outcome| outcome.order_id)
+            .collect();
+        cancelled.sort();
+        targeted.sort();
+        assert_eq!(cancelled, targeted);
+        assert_eq!(outcomes.len(), targeted.len());
+
+        let peak = canceller.peak.load(Ordering::SeqCst);
+        assert!(peak > 1 && peak <= MAX_PARALLEL_CANCELS, "peak concurrency {}", peak);
+
+        // Everything outside the filter is still open
+        assert_eq!(registry.matching(WALLET, &CancelFilter::default()).len(), 4);
+        assert_eq!(registry.matching("wallet_b", &CancelFilter::default()).len(), 1);
+    }
+}
@@ -6,18 +6,31 @@
 //! - dashmap = "5.5"
 //! - tracing = "0.1"
 //! - metrics = "0.20"
+//! - arc-swap = "1.6"
 
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
+use lazy_static::lazy_static;
 use rust_decimal::Decimal;
+use serde::Serialize;
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tracing::{debug, error, info, instrument, warn};
 
+use crate::execution_engine::book_sync::{
+    book_checksum, BookHealth, BookSequence, BookSnapshotSource, BookSync, BookSyncConfig, ChecksumCheck,
+    SequenceCheck,
+};
+use crate::execution_engine::book_view::{BookView, BookViewHandle, BookViews};
+use crate::execution_engine::cost_model::{CostModelCalibrator, ImpactModel};
+use crate::execution_engine::stats::{ExecutionStatsService, RoutePriors};
 use crate::models::order::{Order, OrderError};
-use crate::models::market::{OrderBook, MarketError};
+use crate::models::market::{OrderBook, OrderBookLevel, MarketError};
+use crate::risk_manager::exposure::TradeSide;
+use crate::utils::logger::LogSampler;
+use crate::utils::metric_handles::LabeledCounter;
 use crate::utils::solana::SolanaClient;
 use crate::utils::time::{current_timestamp, is_valid_market_timestamp};
 
@@ -28,6 +41,27 @@ const MAX_PRICE_LEVELS: usize = 1000;
 const STALE_THRESHOLD_MS: i64 = 5000;
 const CLEANUP_INTERVAL_MS: u64 = 60000;
 const MAX_CONCURRENT_UPDATES: usize = 50;
+const SNAPSHOT_CHANNEL_CAPACITY: usize = 1024;
+/// Execution plans are reused until the next accepted update or this age, whichever is first
+const PLAN_CACHE_TTL: Duration = Duration::from_millis(UPDATE_INTERVAL_MS);
+const BPS_PER_UNIT: Decimal = Decimal::new(10_000, 0);
+const METRICS_PREFIX: &str = "trading_bot.order_book";
+
+// Per-exchange metric handles for the update path, registered once on first use
+lazy_static! {
+    static ref SEQUENCE_GAPS: LabeledCounter =
+        LabeledCounter::new(format!("{}.sequence_gaps", METRICS_PREFIX), "exchange");
+    static ref CHECKSUM_FAILURES: LabeledCounter =
+        LabeledCounter::new(format!("{}.checksum_failures", METRICS_PREFIX), "exchange");
+    static ref RESYNCS: LabeledCounter = LabeledCounter::new(format!("{}.resyncs", METRICS_PREFIX), "exchange");
+}
+
+// Per-update debug events are sampled so enabling debug stays affordable
+static BOOK_UPDATE_LOG: LogSampler = LogSampler::new();
+static ROUTE_LOG: LogSampler = LogSampler::new();
+
+/// Largest depth served to API and WebSocket clients
+pub const MAX_SNAPSHOT_DEPTH: usize = ORDER_BOOK_DEPTH;
 
 /// Order book related error types
 #[derive(Error, Debug)]
@@ -42,6 +76,12 @@ pub enum OrderBookError {
     StaleDataError(String),
     #[error("execution error: {0}")]
     ExecutionError(String),
+    #[error("sequence gap: expected {expected}, received {received}")]
+    SequenceGap { expected: u64, received: u64 },
+    #[error("checksum mismatch: {0}")]
+    ChecksumMismatch(String),
+    #[error("degraded book: {0}")]
+    DegradedBook(String),
 }
 
 /// Memory pool for efficient order book updates
@@ -60,27 +100,82 @@ impl MemoryPool {
     }
 }
 
+/// Point-in-time view of a live order book for external clients
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrderBookSnapshot {
+    pub trading_pair: String,
+    pub exchange: String,
+    pub bids: Vec<OrderBookLevel>,
+    pub asks: Vec<OrderBookLevel>,
+    pub timestamp: DateTime<Utc>,
+    pub is_stale: bool,
+}
+
+impl OrderBookSnapshot {
+    /// Captures up to `depth` levels per side, capped at `MAX_SNAPSHOT_DEPTH`
+    pub fn from_book(book: &OrderBook, depth: usize) -> Self {
+        let (bids, asks) = book.levels(depth.min(MAX_SNAPSHOT_DEPTH));
+        Self {
+            trading_pair: book.trading_pair().to_string(),
+            exchange: book.exchange().to_string(),
+            bids,
+            asks,
+            timestamp: book.timestamp(),
+            is_stale: !is_valid_market_timestamp(book.timestamp()),
+        }
+    }
+
+    /// Drops levels beyond `depth` on each side
+    pub fn truncated(mut self, depth: usize) -> Self {
+        self.bids.truncate(depth);
+        self.asks.truncate(depth);
+        self
+    }
+}
+
 /// High-performance order book manager with concurrent updates
 #[derive(Debug)]
 pub struct LiveOrderBook {
     books: DashMap<String, OrderBook>,
+    plan_cache: DashMap<String, (Instant, ExecutionPlan)>,
+    snapshots_tx: broadcast::Sender<OrderBookSnapshot>,
     solana_client: Arc<SolanaClient>,
     last_updates: RwLock<std::collections::HashMap<String, DateTime<Utc>>>,
     update_latency: metrics::Histogram,
     update_conflicts: metrics::Counter,
     allocation_pool: Arc<MemoryPool>,
+    /// Realized venue outcomes used to break ties between near-identical books
+    execution_stats: parking_lot::RwLock<Option<Arc<ExecutionStatsService>>>,
+    /// Calibrated venue cost models that replace the book-walk impact estimate
+    cost_models: parking_lot::RwLock<Option<Arc<CostModelCalibrator>>>,
+    /// Sequence, checksum and health state per pair
+    sync: DashMap<String, BookSync>,
+    sync_config: BookSyncConfig,
+    /// Venue REST sources used to resync degraded books, by exchange
+    snapshot_sources: DashMap<String, Arc<dyn BookSnapshotSource>>,
+    /// Precomputed top-of-book views served to strategies
+    views: BookViews,
 }
 
 impl LiveOrderBook {
     /// Creates new LiveOrderBook instance with monitoring
     pub fn new(solana_client: Arc<SolanaClient>, config: Config) -> Self {
+        let (snapshots_tx, _) = broadcast::channel(SNAPSHOT_CHANNEL_CAPACITY);
         let instance = Self {
             books: DashMap::with_capacity(config.initial_capacity.unwrap_or(100)),
+            plan_cache: DashMap::new(),
+            snapshots_tx,
             solana_client,
             last_updates: RwLock::new(std::collections::HashMap::new()),
             update_latency: metrics::Histogram::new(),
             update_conflicts: metrics::Counter::new(),
             allocation_pool: Arc::new(MemoryPool::new()),
+            execution_stats: parking_lot::RwLock::new(None),
+            cost_models: parking_lot::RwLock::new(None),
+            sync: DashMap::new(),
+            sync_config: config.sync,
+            snapshot_sources: DashMap::new(),
+            views: BookViews::default(),
         };
 
         // Spawn monitoring task
@@ -144,28 +239,265 @@ impl LiveOrderBook {
             }
         }
 
+        self.book_changed(&trading_pair);
+
         // Record metrics
         let duration = start.elapsed();
         self.update_latency.record(duration.as_millis() as f64);
 
-        debug!(
+        if let Some(sampled) = BOOK_UPDATE_LOG.sample() {
+            debug!(
+                sampled,
+                trading_pair = %trading_pair,
+                duration_ms = %duration.as_millis(),
+                "Order book updated successfully"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Applies a venue depth update after checking its sequence and checksum. A gap or
+    /// escalated checksum failures degrade the book and trigger a snapshot resync; updates
+    /// are not applied to a degraded book until the resync succeeds.
+    #[instrument(skip(self, new_state))]
+    pub async fn apply_sequenced_update(
+        &self,
+        trading_pair: String,
+        new_state: OrderBook,
+        sequence: BookSequence,
+    ) -> Result<(), OrderBookError> {
+        let exchange = new_state.exchange().to_string();
+        let check = self
+            .sync
+            .entry(trading_pair.clone())
+            .or_default()
+            .observe(sequence.sequence);
+        match check {
+            SequenceCheck::InOrder => {}
+            SequenceCheck::Stale => {
+                return Err(OrderBookError::UpdateError(format!(
+                    "stale sequence {}",
+                    sequence.sequence
+                )))
+            }
+            SequenceCheck::Gap { expected, received } => {
+                SEQUENCE_GAPS.increment(&exchange, 1);
+                warn!(
+                    trading_pair = %trading_pair,
+                    expected,
+                    received,
+                    "Order book sequence gap, marking degraded"
+                );
+                self.invalidate_plans(&trading_pair);
+                self.views.mark_degraded(&trading_pair);
+                // The snapshot supersedes the update that revealed the gap
+                return self
+                    .resync(&trading_pair, &exchange)
+                    .await
+                    .map_err(|_| OrderBookError::SequenceGap { expected, received });
+            }
+        }
+
+        if let Some(expected) = sequence.checksum {
+            let actual = book_checksum(&new_state);
+            let outcome = self
+                .sync
+                .entry(trading_pair.clone())
+                .or_default()
+                .record_checksum(actual == expected, &self.sync_config);
+            match outcome {
+                ChecksumCheck::Valid => {}
+                ChecksumCheck::Mismatch { consecutive } | ChecksumCheck::Escalated { consecutive } => {
+                    CHECKSUM_FAILURES.increment(&exchange, 1);
+                    let message = format!(
+                        "expected {:08x}, computed {:08x} ({} consecutive)",
+                        expected, actual, consecutive
+                    );
+                    if matches!(outcome, ChecksumCheck::Escalated { .. }) {
+                        error!(trading_pair = %trading_pair, "Order book checksum failures escalated: {}", message);
+                        self.invalidate_plans(&trading_pair);
+                        self.views.mark_degraded(&trading_pair);
+                        let _ = self.resync(&trading_pair, &exchange).await;
+                    } else {
+                        warn!(trading_pair = %trading_pair, "Order book checksum mismatch: {}", message);
+                    }
+                    return Err(OrderBookError::ChecksumMismatch(message));
+                }
+            }
+        }
+
+        if self.book_health(&trading_pair) == BookHealth::Degraded {
+            self.resync(&trading_pair, &exchange).await?;
+            return Ok(());
+        }
+
+        self.update_book(trading_pair, new_state).await
+    }
+
+    /// Replaces a degraded book with a venue snapshot and re-anchors its sequence
+    pub async fn resync(&self, trading_pair: &str, exchange: &str) -> Result<(), OrderBookError> {
+        let due = self
+            .sync
+            .get(trading_pair)
+            .map_or(false, |sync| sync.resync_due(&self.sync_config));
+        if !due {
+            return Err(OrderBookError::DegradedBook(format!(
+                "{} awaiting resync",
+                trading_pair
+            )));
+        }
+        let source = self
+            .snapshot_sources
+            .get(exchange)
+            .map(|source| source.value().clone())
+            .ok_or_else(|| {
+                OrderBookError::DegradedBook(format!("no snapshot source for {}", exchange))
+            })?;
+        if let Some(mut sync) = self.sync.get_mut(trading_pair) {
+            sync.resync_started();
+        }
+        RESYNCS.increment(exchange, 1);
+
+        let snapshot = source.fetch_snapshot(trading_pair).await.map_err(|e| {
+            warn!(trading_pair = %trading_pair, "Order book resync failed: {}", e);
+            e
+        })?;
+        self.books.insert(trading_pair.to_string(), snapshot.book);
+        self.last_updates
+            .write()
+            .await
+            .insert(trading_pair.to_string(), current_timestamp());
+        if let Some(mut sync) = self.sync.get_mut(trading_pair) {
+            sync.resynced(snapshot.sequence);
+        }
+        self.book_changed(trading_pair);
+
+        info!(
             trading_pair = %trading_pair,
-            duration_ms = %duration.as_millis(),
-            "Order book updated successfully"
+            sequence = snapshot.sequence,
+            "Order book resynced from venue snapshot"
         );
-
         Ok(())
     }
 
-    /// Determines best execution strategy for an order
+    /// Integrity of a pair's book; pairs without sequenced updates are healthy
+    pub fn book_health(&self, trading_pair: &str) -> BookHealth {
+        self.sync
+            .get(trading_pair)
+            .map_or(BookHealth::Healthy, |sync| sync.health())
+    }
+
+    /// Registers the REST snapshot source used to resync an exchange's books
+    pub fn set_snapshot_source(&self, exchange: &str, source: Arc<dyn BookSnapshotSource>) {
+        self.snapshot_sources.insert(exchange.to_string(), source);
+    }
+
+    /// Invalidates cached plans, swaps in the pair's new view and publishes its new
+    /// full-depth snapshot
+    fn book_changed(&self, trading_pair: &str) {
+        self.invalidate_plans(trading_pair);
+
+        // Publish the full-depth snapshot; subscribers apply their own throttling
+        if let Some(book) = self.books.get(trading_pair) {
+            self.views
+                .publish(&book, self.book_health(trading_pair) == BookHealth::Degraded);
+            let _ = self
+                .snapshots_tx
+                .send(OrderBookSnapshot::from_book(&book, MAX_SNAPSHOT_DEPTH));
+        }
+    }
+
+    // Plans priced against the previous state are no longer valid
+    fn invalidate_plans(&self, trading_pair: &str) {
+        let prefix = format!("{}:", trading_pair);
+        self.plan_cache.retain(|key, _| !key.starts_with(&prefix));
+    }
+
+    /// Returns the current book for a pair, optionally restricted to one exchange
+    pub fn snapshot(
+        &self,
+        trading_pair: &str,
+        exchange: Option<&str>,
+        depth: usize,
+    ) -> Option<OrderBookSnapshot> {
+        let book = self.books.get(trading_pair)?;
+        if let Some(exchange) = exchange {
+            if !book.exchange().eq_ignore_ascii_case(exchange) {
+                return None;
+            }
+        }
+        Some(OrderBookSnapshot::from_book(&book, depth))
+    }
+
+    /// Latest precomputed view of a pair's book, without cloning its levels
+    pub fn view(&self, trading_pair: &str) -> Option<Arc<BookView>> {
+        self.views.load(trading_pair)
+    }
+
+    /// Slot holding a pair's latest view, for strategies that read it on every tick;
+    /// `None` until the pair's first update
+    pub fn view_handle(&self, trading_pair: &str) -> Option<BookViewHandle> {
+        self.views.handle(trading_pair)
+    }
+
+    /// Subscribes to snapshots published on every accepted update
+    pub fn subscribe(&self) -> broadcast::Receiver<OrderBookSnapshot> {
+        self.snapshots_tx.subscribe()
+    }
+
+    /// Sender behind `subscribe`, for consumers that subscribe lazily per client
+    pub fn snapshot_sender(&self) -> broadcast::Sender<OrderBookSnapshot> {
+        self.snapshots_tx.clone()
+    }
+
+    /// Feeds realized execution statistics into route selection
+    pub fn set_execution_stats(&self, execution_stats: Arc<ExecutionStatsService>) {
+        *self.execution_stats.write() = Some(execution_stats);
+    }
+
+    /// Estimates impact with a venue's calibrated cost model when it has a valid one
+    pub fn set_cost_models(&self, cost_models: Arc<CostModelCalibrator>) {
+        *self.cost_models.write() = Some(cost_models);
+    }
+
+    /// Visible depth on the side an order on `side` would execute against
+    pub fn visible_depth(&self, trading_pair: &str, side: TradeSide) -> Option<Decimal> {
+        self.books.get(trading_pair).map(|book| visible_depth(&book, side))
+    }
+
+    /// Determines best execution strategy for an order, reusing a plan for an identical
+    /// order priced against the same book state
     #[instrument(skip(self, order))]
     pub async fn get_best_execution(
         &self,
         order: &Order,
+        side: TradeSide,
     ) -> Result<ExecutionPlan, OrderBookError> {
+        // Degraded books are refused unless configured to route with widened impact
+        let book_health = self.book_health(&order.trading_pair);
+        let impact_penalty = match (book_health, self.sync_config.degraded_impact_penalty_bps) {
+            (BookHealth::Healthy, _) => Decimal::ZERO,
+            (BookHealth::Degraded, Some(penalty)) => penalty,
+            (BookHealth::Degraded, None) => {
+                return Err(OrderBookError::DegradedBook(format!(
+                    "order book for {} is degraded pending resync",
+                    order.trading_pair
+                )))
+            }
+        };
+
+        let cache_key = format!("{}:{:?}:{}", order.trading_pair, side, order.size.normalize());
+        if let Some(entry) = self.plan_cache.get(&cache_key) {
+            let (cached_at, plan) = entry.value();
+            if cached_at.elapsed() < PLAN_CACHE_TTL {
+                return Ok(plan.clone());
+            }
+        }
+
         // Check order book freshness
         let book = self.books
-            .get(&order.trading_pair)
+            .get(order.trading_pair.as_str())
             .ok_or_else(|| OrderBookError::MarketError(
                 MarketError::InvalidTradingPair(
                     format!("no order book for {}", order.trading_pair)
@@ -179,16 +511,46 @@ impl LiveOrderBook {
         }
 
         // Calculate optimal route
-        let route = calculate_optimal_route(
+        let priors = self
+            .execution_stats
+            .read()
+            .as_ref()
+            .map(|stats| stats.priors(&order.trading_pair))
+            .unwrap_or_default();
+        let mut route = calculate_optimal_route(
             order,
+            side,
             &[book.clone()],
+            &priors,
         ).await?;
+        let depth = visible_depth(&book, side);
+        drop(book);
+
+        // A calibrated model of the chosen venue replaces the book-walk impact estimate
+        let cost_models = self.cost_models.read().clone();
+        let calibrated = cost_models.and_then(|cost_models| {
+            let exchange = route.steps.first()?.dex.clone();
+            cost_models.estimate(&order.trading_pair, &exchange, order.size, depth, current_timestamp())
+        });
+        let impact_model = match calibrated {
+            Some((impact_bps, model)) => {
+                route.total_price_impact = impact_bps;
+                model
+            }
+            None => ImpactModel::Static,
+        };
+        route.total_price_impact += impact_penalty;
 
-        Ok(ExecutionPlan {
+        let plan = ExecutionPlan {
+            estimated_price: route.average_price(),
             route,
-            estimated_price: book.get_spread()?.unwrap_or_default(),
             timestamp: current_timestamp(),
-        })
+            book_health,
+            impact_model,
+        };
+        self.plan_cache.insert(cache_key, (Instant::now(), plan.clone()));
+
+        Ok(plan)
     }
 
     // Spawns monitoring task
@@ -289,11 +651,84 @@ pub async fn match_orders(
     Ok(matched_orders)
 }
 
-/// Calculates optimal execution route across DEXs
-#[instrument(skip(order, order_books))]
+/// Average fill price and market impact of walking one side of a book
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillEstimate {
+    pub fill_price: Decimal,
+    pub mid_price: Decimal,
+    /// Distance of the average fill price from mid, in basis points
+    pub impact_bps: Decimal,
+}
+
+/// Total volume on the side of the book an order on `side` executes against
+pub fn visible_depth(book: &OrderBook, side: TradeSide) -> Decimal {
+    let (bids, asks) = book.levels(MAX_PRICE_LEVELS);
+    let levels = match side {
+        TradeSide::Buy => asks,
+        TradeSide::Sell => bids,
+    };
+    levels.iter().map(|level| level.volume()).sum()
+}
+
+/// Estimates the average price of filling `size` against the opposite side of the book
+pub fn estimate_fill(
+    book: &OrderBook,
+    side: TradeSide,
+    size: Decimal,
+) -> Result<FillEstimate, OrderBookError> {
+    let (bids, asks) = book.levels(MAX_PRICE_LEVELS);
+    let (best_bid, best_ask) = match (bids.first(), asks.first()) {
+        (Some(bid), Some(ask)) => (bid.price(), ask.price()),
+        _ => {
+            return Err(OrderBookError::ExecutionError(format!(
+                "one-sided order book for {}",
+                book.trading_pair()
+            )))
+        }
+    };
+    let mid_price = (best_bid + best_ask) / Decimal::TWO;
+
+    let levels = match side {
+        TradeSide::Buy => asks,
+        TradeSide::Sell => bids,
+    };
+    let mut remaining = size;
+    let mut cost = Decimal::ZERO;
+    for level in &levels {
+        let filled = remaining.min(level.volume());
+        cost += filled * level.price();
+        remaining -= filled;
+        if remaining.is_zero() {
+            break;
+        }
+    }
+
+    if remaining > Decimal::ZERO {
+        return Err(OrderBookError::ExecutionError(format!(
+            "insufficient depth on {} to fill {} {}",
+            book.exchange(),
+            size,
+            book.trading_pair()
+        )));
+    }
+
+    let fill_price = cost / size;
+    Ok(FillEstimate {
+        fill_price,
+        mid_price,
+        impact_bps: ((fill_price - mid_price).abs() / mid_price * BPS_PER_UNIT).round_dp(2),
+    })
+}
+
+/// Calculates optimal execution route across DEXs, routing the order to the book with the
+/// best estimated fill. Books whose fills are near-identical to the best are ranked by
+/// realized execution cost instead.
+#[instrument(skip(order, order_books, priors))]
 pub async fn calculate_optimal_route(
     order: &Order,
+    side: TradeSide,
     order_books: &[OrderBook],
+    priors: &RoutePriors,
 ) -> Result<ExecutionRoute, OrderBookError> {
     let start = Instant::now();
 
@@ -306,44 +741,210 @@ pub async fn calculate_optimal_route(
         ));
     }
 
-    // Calculate optimal route
+    let mut estimates: Vec<(&OrderBook, FillEstimate)> = Vec::with_capacity(order_books.len());
+    let mut best: Option<usize> = None;
+    let mut last_error = None;
+    for book in order_books {
+        match estimate_fill(book, side, order.size) {
+            Ok(estimate) => {
+                let better = match best {
+                    None => true,
+                    Some(index) => match side {
+                        TradeSide::Buy => estimate.fill_price < estimates[index].1.fill_price,
+                        TradeSide::Sell => estimate.fill_price > estimates[index].1.fill_price,
+                    },
+                };
+                if better {
+                    best = Some(estimates.len());
+                }
+                estimates.push((book, estimate));
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    let best = match (best, last_error) {
+        (Some(best), _) => best,
+        (None, Some(e)) => return Err(e),
+        (None, None) => unreachable!("order books checked non-empty"),
+    };
+
+    // Prefer the venue with the cheapest realized outcomes among near-identical fills
+    let best_price = estimates[best].1.fill_price;
+    let chosen = estimates
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, estimate))| priors.near_identical(estimate.fill_price, best_price))
+        .filter_map(|(index, (book, _))| priors.cost_bps(book.exchange()).map(|cost| (cost, index)))
+        .min()
+        .map(|(_, index)| index)
+        .unwrap_or(best);
+    let (book, estimate) = estimates.swap_remove(chosen);
+
     let route = ExecutionRoute {
-        steps: vec![],  // Implement route calculation
-        total_price_impact: Decimal::ZERO,
+        steps: vec![ExecutionStep {
+            dex: book.exchange().to_string(),
+            amount: order.size,
+            price: estimate.fill_price,
+        }],
+        total_price_impact: estimate.impact_bps,
         estimated_execution_time: Duration::from_millis(500),
     };
 
-    debug!(
-        order_id = %order.id,
-        duration_ms = %start.elapsed().as_millis(),
-        "Route calculation completed"
-    );
+    if let Some(sampled) = ROUTE_LOG.sample() {
+        debug!(
+            sampled,
+            order_id = %order.id,
+            duration_ms = %start.elapsed().as_millis(),
+            "Route calculation completed"
+        );
+    }
 
     Ok(route)
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExecutionPlan {
     pub route: ExecutionRoute,
     pub estimated_price: Decimal,
     pub timestamp: DateTime<Utc>,
+    /// Integrity of the book the plan was priced on
+    pub book_health: BookHealth,
+    /// Model behind `route.total_price_impact`
+    pub impact_model: ImpactModel,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExecutionRoute {
     pub steps: Vec<ExecutionStep>,
+    /// Estimated impact across all steps, in basis points
     pub total_price_impact: Decimal,
     pub estimated_execution_time: Duration,
 }
 
-#[derive(Debug)]
+impl ExecutionRoute {
+    /// Size-weighted average price across all steps
+    pub fn average_price(&self) -> Decimal {
+        let amount: Decimal = self.steps.iter().map(|step| step.amount).sum();
+        if amount.is_zero() {
+            return Decimal::ZERO;
+        }
+        self.steps.iter().map(|step| step.amount * step.price).sum::<Decimal>() / amount
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct ExecutionStep {
     pub dex: String,
     pub amount: Decimal,
     pub price: Decimal,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Config {
     pub initial_capacity: Option<usize>,
-}
\ No newline at end of file
+    pub sync: BookSyncConfig,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_engine::stats::{ExecutionStats, StatsWindow};
+    use crate::utils::percent::Bps;
+    use rust_decimal_macros::dec;
+
+    fn book() -> OrderBook {
+        OrderBook::new(
+            "SOL/USDC".to_string(),
+            "jupiter".to_string(),
+            vec![
+                OrderBookLevel::new(dec!(99), dec!(10)),
+                OrderBookLevel::new(dec!(98), dec!(10)),
+            ],
+            vec![
+                OrderBookLevel::new(dec!(101), dec!(10)),
+                OrderBookLevel::new(dec!(103), dec!(10)),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_estimate_fill_walks_levels() {
+        let estimate = estimate_fill(&book(), TradeSide::Buy, dec!(15)).unwrap();
+        assert_eq!(estimate.mid_price, dec!(100));
+        assert_eq!(estimate.fill_price.round_dp(4), dec!(101.6667));
+        assert_eq!(estimate.impact_bps, dec!(166.67));
+
+        let estimate = estimate_fill(&book(), TradeSide::Sell, dec!(5)).unwrap();
+        assert_eq!(estimate.fill_price, dec!(99));
+        assert_eq!(estimate.impact_bps, dec!(100));
+    }
+
+    fn venue(exchange: &str, ask: Decimal) -> OrderBook {
+        OrderBook::new(
+            "SOL/USDC".to_string(),
+            exchange.to_string(),
+            vec![OrderBookLevel::new(ask - dec!(1), dec!(100))],
+            vec![OrderBookLevel::new(ask, dec!(100))],
+        )
+        .unwrap()
+    }
+
+    fn venue_stats(exchange: &str, avg_slippage_bps: Decimal, fill_rate: Decimal) -> ExecutionStats {
+        ExecutionStats {
+            trading_pair: "SOL/USDC".to_string(),
+            exchange: exchange.to_string(),
+            window: StatsWindow::Week,
+            sample_count: 50,
+            fill_rate,
+            avg_slippage_bps,
+            p95_slippage_bps: avg_slippage_bps * dec!(2),
+            avg_latency_ms: dec!(400),
+            mev_capture_bps: Decimal::ZERO,
+            fee_bps: dec!(3),
+            benchmarked_count: 0,
+            avg_vwap_deviation_bps: None,
+            avg_twap_deviation_bps: None,
+            computed_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_route_tie_break_uses_realized_outcomes() {
+        let order = Order::new(
+            "SOL/USDC".to_string(),
+            "jupiter".to_string(),
+            crate::models::order::OrderType::Limit,
+            dec!(100.01),
+            dec!(10),
+        )
+        .unwrap();
+        let stats = [
+            venue_stats("jupiter", dec!(25), dec!(0.9)),
+            venue_stats("drift", dec!(5), dec!(1)),
+        ];
+        let priors = RoutePriors::new(&stats, 20, Bps::new(2), Bps::new(100));
+
+        // Jupiter quotes 1 bps better, but drift's realized slippage and fill rate win the tie
+        let books = [venue("jupiter", dec!(100.00)), venue("drift", dec!(100.01))];
+        let route = calculate_optimal_route(&order, TradeSide::Buy, &books, &priors).await.unwrap();
+        assert_eq!(route.steps[0].dex, "drift");
+
+        // Without priors the best quote wins
+        let route = calculate_optimal_route(&order, TradeSide::Buy, &books, &RoutePriors::default())
+            .await
+            .unwrap();
+        assert_eq!(route.steps[0].dex, "jupiter");
+
+        // A materially better quote is never overridden
+        let books = [venue("jupiter", dec!(100.00)), venue("drift", dec!(100.50))];
+        let route = calculate_optimal_route(&order, TradeSide::Buy, &books, &priors).await.unwrap();
+        assert_eq!(route.steps[0].dex, "jupiter");
+    }
+
+    #[test]
+    fn test_estimate_fill_insufficient_depth() {
+        let err = estimate_fill(&book(), TradeSide::Buy, dec!(25)).unwrap_err();
+        assert!(err.to_string().contains("insufficient depth"));
+    }
+}
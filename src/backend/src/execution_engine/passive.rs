@@ -0,0 +1,697 @@
+//! Passive maker execution for venues that pay maker rebates. Orders rest post-only at an
+//! offset inside the touch, follow the market with cancel/replace once it moves past a
+//! threshold, and cross the spread for whatever is left when the deadline passes.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - rust_decimal = "1.30"
+//! - async-trait = "0.1"
+//! - parking_lot = "0.12"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use parking_lot::Mutex;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::Instant;
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::execution_engine::error::ExecutionError;
+use crate::execution_engine::fills::{FillTracker, FillUpdate};
+use crate::execution_engine::order_book::OrderBookSnapshot;
+use crate::execution_engine::telemetry::OrderTelemetry;
+use crate::models::order::{Order, OrderError, OrderFill, OrderType};
+use crate::models::trade::FeeBreakdown;
+use crate::risk_manager::exposure::TradeSide;
+use crate::utils::percent::Bps;
+use crate::utils::solana::SolanaClient;
+
+// Passive execution constants
+const METRICS_PREFIX: &str = "trading_bot.execution.passive";
+const PRICE_SCALE: u32 = 6;
+const DEFAULT_OFFSET_BPS: Bps = Bps::new(1);
+const DEFAULT_REPRICE_THRESHOLD_BPS: Bps = Bps::new(5);
+const DEFAULT_DEADLINE: Duration = Duration::from_secs(30);
+
+/// How an order meets the market
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionStyle {
+    /// Crosses the spread immediately
+    #[default]
+    Aggressive,
+    /// Rests post-only for maker rebates, crossing only after the deadline
+    Passive,
+}
+
+/// Pricing and timing of passive orders
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassiveConfig {
+    /// Distance inside the best bid or ask, in basis points; zero joins the touch
+    pub offset_bps: Bps,
+    /// Touch move, in basis points, that triggers a cancel/replace
+    pub reprice_threshold_bps: Bps,
+    /// Time the order may rest before the remainder crosses the spread
+    pub deadline: Duration,
+}
+
+impl Default for PassiveConfig {
+    fn default() -> Self {
+        Self {
+            offset_bps: DEFAULT_OFFSET_BPS,
+            reprice_threshold_bps: DEFAULT_REPRICE_THRESHOLD_BPS,
+            deadline: DEFAULT_DEADLINE,
+        }
+    }
+}
+
+impl PassiveConfig {
+    /// Post-only price for the side, falling back to joining the touch when the offset
+    /// would cross the spread
+    pub fn passive_price(&self, touch: &Touch, side: TradeSide) -> Decimal {
+        let offset = self.offset_bps.as_fraction();
+        let price = match side {
+            TradeSide::Buy => {
+                let improved = touch.best_bid * (Decimal::ONE + offset);
+                if improved >= touch.best_ask { touch.best_bid } else { improved }
+            }
+            TradeSide::Sell => {
+                let improved = touch.best_ask * (Decimal::ONE - offset);
+                if improved <= touch.best_bid { touch.best_ask } else { improved }
+            }
+        };
+        price.round_dp(PRICE_SCALE).normalize()
+    }
+}
+
+/// Best bid and ask of a venue book
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Touch {
+    pub best_bid: Decimal,
+    pub best_ask: Decimal,
+}
+
+impl Touch {
+    pub fn from_snapshot(snapshot: &OrderBookSnapshot) -> Option<Self> {
+        Some(Self {
+            best_bid: snapshot.bids.first()?.price(),
+            best_ask: snapshot.asks.first()?.price(),
+        })
+    }
+
+    /// Price on the side the order rests on
+    pub fn near(&self, side: TradeSide) -> Decimal {
+        match side {
+            TradeSide::Buy => self.best_bid,
+            TradeSide::Sell => self.best_ask,
+        }
+    }
+
+    /// Price the order would pay crossing the spread
+    pub fn far(&self, side: TradeSide) -> Decimal {
+        match side {
+            TradeSide::Buy => self.best_ask,
+            TradeSide::Sell => self.best_bid,
+        }
+    }
+}
+
+/// Volume resting at or ahead of `price` on the order's side of the book
+fn queue_ahead(snapshot: &OrderBookSnapshot, side: TradeSide, price: Decimal) -> Decimal {
+    let levels = match side {
+        TradeSide::Buy => &snapshot.bids,
+        TradeSide::Sell => &snapshot.asks,
+    };
+    levels
+        .iter()
+        .filter(|level| match side {
+            TradeSide::Buy => level.price() >= price,
+            TradeSide::Sell => level.price() <= price,
+        })
+        .map(|level| level.volume())
+        .sum()
+}
+
+fn moved_bps(reference: Decimal, current: Decimal) -> Bps {
+    if reference.is_zero() {
+        return Bps::ZERO;
+    }
+    Bps::from_fraction(((current - reference) / reference).abs())
+}
+
+/// Order placement on a venue that supports post-only orders
+#[async_trait]
+pub trait MakerVenue: Send + Sync {
+    /// Rests a post-only limit order at its price
+    async fn place_post_only(&self, order: &mut Order, strategy_id: &str, side: TradeSide) -> Result<(), OrderError>;
+    /// Moves a resting order to a new price, returning the order now working
+    async fn reprice(&self, order: &Order, price: Decimal) -> Result<Order, OrderError>;
+    async fn cancel(&self, order: &mut Order) -> Result<(), OrderError>;
+    /// Crosses the spread with a market order, returning its fills
+    async fn take(&self, order: &mut Order, side: TradeSide) -> Result<Vec<OrderFill>, OrderError>;
+}
+
+/// Drift adapter for passive orders. Fills reach the executor through the fill tracker,
+/// which also keeps the lineage of replaced orders.
+pub struct DriftMakerAdapter {
+    solana_client: Arc<SolanaClient>,
+    fills: Arc<FillTracker>,
+    telemetry: Arc<OrderTelemetry>,
+}
+
+impl std::fmt::Debug for DriftMakerAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DriftMakerAdapter").finish()
+    }
+}
+
+impl DriftMakerAdapter {
+    pub fn new(solana_client: Arc<SolanaClient>, fills: Arc<FillTracker>, telemetry: Arc<OrderTelemetry>) -> Self {
+        Self {
+            solana_client,
+            fills,
+            telemetry,
+        }
+    }
+}
+
+#[async_trait]
+impl MakerVenue for DriftMakerAdapter {
+    async fn place_post_only(&self, order: &mut Order, strategy_id: &str, side: TradeSide) -> Result<(), OrderError> {
+        order.place_post_only(&self.solana_client).await?;
+        self.fills.track(order.clone(), strategy_id, side).await;
+        Ok(())
+    }
+
+    async fn reprice(&self, order: &Order, price: Decimal) -> Result<Order, OrderError> {
+        let working = self
+            .fills
+            .amend(order.id, price, order.size)
+            .await
+            .map_err(|e| OrderError::ValidationError(e.to_string()))?;
+
+        if working.id == order.id {
+            working.submit_modify(&self.solana_client).await?;
+        } else {
+            // Cancel-and-replace: pull the old order, then rest the replacement
+            let mut cancelled = order.clone();
+            cancelled.cancel(&self.solana_client).await?;
+            let mut replacement = working.clone();
+            replacement.place_post_only(&self.solana_client).await?;
+        }
+        Ok(working)
+    }
+
+    async fn cancel(&self, order: &mut Order) -> Result<(), OrderError> {
+        order.cancel(&self.solana_client).await?;
+        self.fills
+            .cancel_remaining(order.id)
+            .await
+            .map_err(|e| OrderError::ValidationError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn take(&self, order: &mut Order, _side: TradeSide) -> Result<Vec<OrderFill>, OrderError> {
+        // A landed market order fills in full at the crossing price
+        let signature = order.execute(&self.solana_client, &self.telemetry).await?;
+        Ok(vec![OrderFill::new(signature, order.size, order.price)])
+    }
+}
+
+/// Passive order currently resting on a venue
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PassiveOrder {
+    pub order_id: Uuid,
+    pub client_order_id: Uuid,
+    pub strategy_id: String,
+    pub trading_pair: String,
+    pub exchange: String,
+    pub side: TradeSide,
+    pub price: Decimal,
+    pub remaining_size: Decimal,
+    /// Book volume at or ahead of the order's price as of the last book update
+    pub queue_ahead: Option<Decimal>,
+    pub reprices: u32,
+    pub placed_at: DateTime<Utc>,
+}
+
+/// Outcome of a passive execution
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassiveExecution {
+    pub client_order_id: Uuid,
+    pub maker_fills: Vec<OrderFill>,
+    /// Fills from crossing the spread after the deadline
+    pub taker_fills: Vec<OrderFill>,
+    pub reprices: u32,
+    pub crossed: bool,
+    pub fees: FeeBreakdown,
+}
+
+impl PassiveExecution {
+    pub fn fills(&self) -> Vec<OrderFill> {
+        self.maker_fills.iter().chain(&self.taker_fills).cloned().collect()
+    }
+
+    pub fn average_price(&self) -> Option<Decimal> {
+        let fills = self.fills();
+        let size: Decimal = fills.iter().map(|fill| fill.size).sum();
+        if size.is_zero() {
+            return None;
+        }
+        Some(fills.iter().map(|fill| fill.size * fill.price).sum::<Decimal>() / size)
+    }
+}
+
+/// Works orders passively against live books, tracking every order it has resting
+pub struct PassiveExecutor {
+    config: PassiveConfig,
+    venue: Arc<dyn MakerVenue>,
+    books: broadcast::Sender<OrderBookSnapshot>,
+    fills: broadcast::Sender<FillUpdate>,
+    open: Mutex<HashMap<Uuid, PassiveOrder>>,
+}
+
+impl std::fmt::Debug for PassiveExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PassiveExecutor")
+            .field("config", &self.config)
+            .field("open", &self.open.lock().len())
+            .finish()
+    }
+}
+
+impl PassiveExecutor {
+    pub fn new(
+        config: PassiveConfig,
+        venue: Arc<dyn MakerVenue>,
+        books: broadcast::Sender<OrderBookSnapshot>,
+        fills: broadcast::Sender<FillUpdate>,
+    ) -> Self {
+        Self {
+            config,
+            venue,
+            books,
+            fills,
+            open: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Passive orders currently resting, keyed by client order id
+    pub fn open_orders(&self) -> Vec<PassiveOrder> {
+        self.open.lock().values().cloned().collect()
+    }
+
+    /// Rests the order post-only, repricing as the touch moves, and crosses the spread for
+    /// the remainder once the deadline passes
+    #[instrument(skip(self, order), fields(order_id = %order.id))]
+    pub async fn execute(
+        &self,
+        mut order: Order,
+        strategy_id: &str,
+        side: TradeSide,
+    ) -> Result<PassiveExecution, ExecutionError> {
+        // Subscribe before placing so no book update or fill is missed
+        let mut books = self.books.subscribe();
+        let mut fills = self.fills.subscribe();
+        let deadline = Instant::now() + self.config.deadline;
+        let target = order.size;
+
+        let mut touch = self.first_touch(&mut books, &order, deadline).await?;
+        order.order_type = OrderType::Limit;
+        order.price = self.config.passive_price(&touch, side);
+        self.venue
+            .place_post_only(&mut order, strategy_id, side)
+            .await
+            .map_err(|e| ExecutionError::ValidationError(e.to_string()))?;
+        counter!(format!("{}.placed", METRICS_PREFIX), 1);
+
+        let client_order_id = order.client_order_id;
+        self.open.lock().insert(
+            client_order_id,
+            PassiveOrder {
+                order_id: order.id,
+                client_order_id,
+                strategy_id: strategy_id.to_string(),
+                trading_pair: order.trading_pair.to_string(),
+                exchange: order.exchange.clone(),
+                side,
+                price: order.price,
+                remaining_size: target,
+                queue_ahead: None,
+                reprices: 0,
+                placed_at: Utc::now(),
+            },
+        );
+
+        let mut execution = PassiveExecution {
+            client_order_id,
+            maker_fills: Vec::new(),
+            taker_fills: Vec::new(),
+            reprices: 0,
+            crossed: false,
+            fees: FeeBreakdown::default(),
+        };
+        let mut reference = touch.near(side);
+        let result = loop {
+            if execution_remaining(&execution, target).is_zero() {
+                break Ok(());
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep_until(deadline) => break Ok(()),
+                update = fills.recv() => match update {
+                    Ok(update) if update.client_order_id == client_order_id => {
+                        self.record_maker_fill(&mut execution, update.fill, target);
+                    }
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(skipped, "Passive execution lagged behind fill updates");
+                    }
+                    Err(RecvError::Closed) => {
+                        break Err(ExecutionError::InternalError("fill updates closed".to_string()));
+                    }
+                },
+                snapshot = books.recv() => match snapshot {
+                    Ok(snapshot) if is_order_book(&snapshot, &order) => {
+                        let Some(next) = Touch::from_snapshot(&snapshot) else {
+                            continue;
+                        };
+                        touch = next;
+
+                        if moved_bps(reference, touch.near(side)) > self.config.reprice_threshold_bps {
+                            let price = self.config.passive_price(&touch, side);
+                            match self.venue.reprice(&order, price).await {
+                                Ok(working) => {
+                                    debug!(from = %order.price, to = %price, "Repriced passive order");
+                                    order = working;
+                                    reference = touch.near(side);
+                                    execution.reprices += 1;
+                                    counter!(format!("{}.reprices", METRICS_PREFIX), 1);
+                                    if let Some(open) = self.open.lock().get_mut(&client_order_id) {
+                                        open.order_id = order.id;
+                                        open.price = order.price;
+                                        open.reprices = execution.reprices;
+                                    }
+                                }
+                                // A failed reprice leaves the order resting at its old price
+                                Err(e) => warn!("Failed to reprice passive order: {}", e),
+                            }
+                        }
+                        self.update_queue(client_order_id, &snapshot, side, order.price);
+                    }
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(skipped)) => {
+                        debug!(skipped, "Passive execution skipped stale book updates");
+                    }
+                    Err(RecvError::Closed) => {
+                        break Err(ExecutionError::OrderBookError("book updates closed".to_string()));
+                    }
+                },
+            }
+        };
+
+        if let Err(e) = result {
+            self.open.lock().remove(&client_order_id);
+            return Err(e);
+        }
+
+        if !execution_remaining(&execution, target).is_zero() {
+            self.cross_remainder(&mut order, &mut execution, &mut fills, touch, side, target)
+                .await?;
+        }
+        self.open.lock().remove(&client_order_id);
+
+        for fill in &execution.maker_fills {
+            if let Err(e) = execution.fees.add_maker(&order.exchange, fill.size, fill.price) {
+                warn!("Failed to account maker rebate: {}", e);
+            }
+        }
+        for fill in &execution.taker_fills {
+            if let Err(e) = execution.fees.add_taker(&order.exchange, fill.size, fill.price) {
+                warn!("Failed to account taker fee: {}", e);
+            }
+        }
+        if let Some(rebates) = execution.fees.maker_rebates.to_f64() {
+            counter!(format!("{}.rebates_micro_usd", METRICS_PREFIX), (rebates * 1_000_000.0) as u64);
+        }
+
+        info!(
+            client_order_id = %client_order_id,
+            reprices = execution.reprices,
+            crossed = execution.crossed,
+            "Passive execution completed"
+        );
+        Ok(execution)
+    }
+
+    /// Waits for the first usable book for the order's market
+    async fn first_touch(
+        &self,
+        books: &mut broadcast::Receiver<OrderBookSnapshot>,
+        order: &Order,
+        deadline: Instant,
+    ) -> Result<Touch, ExecutionError> {
+        loop {
+            let snapshot = tokio::time::timeout_at(deadline, books.recv())
+                .await
+                .map_err(|_| ExecutionError::OrderBookError(format!("no book for {}", order.trading_pair)))?;
+            match snapshot {
+                Ok(snapshot) if is_order_book(&snapshot, order) => {
+                    if let Some(touch) = Touch::from_snapshot(&snapshot) {
+                        return Ok(touch);
+                    }
+                }
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => {
+                    return Err(ExecutionError::OrderBookError("book updates closed".to_string()));
+                }
+            }
+        }
+    }
+
+    /// Pulls the resting order and crosses the spread for what it didn't fill
+    async fn cross_remainder(
+        &self,
+        order: &mut Order,
+        execution: &mut PassiveExecution,
+        fills: &mut broadcast::Receiver<FillUpdate>,
+        touch: Touch,
+        side: TradeSide,
+        target: Decimal,
+    ) -> Result<(), ExecutionError> {
+        if let Err(e) = self.venue.cancel(order).await {
+            warn!("Failed to cancel passive order before crossing: {}", e);
+        }
+        // Fills that landed while the cancel was in flight shrink the remainder
+        while let Ok(update) = fills.try_recv() {
+            if update.client_order_id == execution.client_order_id {
+                self.record_maker_fill(execution, update.fill, target);
+            }
+        }
+
+        let remaining = execution_remaining(execution, target);
+        if remaining.is_zero() {
+            return Ok(());
+        }
+
+        let mut taker = Order::new(
+            order.trading_pair.to_string(),
+            order.exchange.clone(),
+            OrderType::Market,
+            touch.far(side),
+            remaining,
+        )
+        .map_err(|e| ExecutionError::ValidationError(e.to_string()))?;
+        taker.client_order_id = execution.client_order_id;
+
+        execution.taker_fills = self
+            .venue
+            .take(&mut taker, side)
+            .await
+            .map_err(|e| ExecutionError::LiquidityError(e.to_string()))?;
+        execution.crossed = true;
+        counter!(format!("{}.deadline_crossings", METRICS_PREFIX), 1);
+        Ok(())
+    }
+
+    fn record_maker_fill(&self, execution: &mut PassiveExecution, fill: OrderFill, target: Decimal) {
+        if execution.maker_fills.iter().any(|applied| applied.fill_id == fill.fill_id) {
+            return;
+        }
+        execution.maker_fills.push(fill);
+        if let Some(open) = self.open.lock().get_mut(&execution.client_order_id) {
+            open.remaining_size = execution_remaining(execution, target);
+        }
+    }
+
+    fn update_queue(&self, client_order_id: Uuid, snapshot: &OrderBookSnapshot, side: TradeSide, price: Decimal) {
+        let ahead = queue_ahead(snapshot, side, price);
+        gauge!(format!("{}.queue_ahead", METRICS_PREFIX), ahead.to_f64().unwrap_or(0.0));
+        if let Some(open) = self.open.lock().get_mut(&client_order_id) {
+            open.queue_ahead = Some(ahead);
+        }
+    }
+}
+
+fn is_order_book(snapshot: &OrderBookSnapshot, order: &Order) -> bool {
+    !snapshot.is_stale && snapshot.trading_pair == order.trading_pair && snapshot.exchange == order.exchange
+}
+
+fn execution_remaining(execution: &PassiveExecution, target: Decimal) -> Decimal {
+    let filled: Decimal = execution.maker_fills.iter().map(|fill| fill.size).sum();
+    (target - filled).max(Decimal::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::OrderBookLevel;
+    use crate::models::order::OrderStatus;
+    use rust_decimal_macros::dec;
+
+    const PAIR: &str = "SOL-PERP";
+
+    /// Venue that records every call and fills market orders at the order's price
+    #[derive(Default)]
+    struct MockVenue {
+        calls: Mutex<Vec<String>>,
+        placed: Mutex<Option<Order>>,
+    }
+
+    #[async_trait]
+    impl MakerVenue for MockVenue {
+        async fn place_post_only(&self, order: &mut Order, _strategy_id: &str, _side: TradeSide) -> Result<(), OrderError> {
+            order.post_only = true;
+            self.calls.lock().push(format!("place {}", order.price));
+            *self.placed.lock() = Some(order.clone());
+            Ok(())
+        }
+
+        async fn reprice(&self, order: &Order, price: Decimal) -> Result<Order, OrderError> {
+            self.calls.lock().push(format!("reprice {}", price));
+            let mut working = order.clone();
+            working.price = price;
+            Ok(working)
+        }
+
+        async fn cancel(&self, order: &mut Order) -> Result<(), OrderError> {
+            self.calls.lock().push("cancel".to_string());
+            order.status = OrderStatus::Cancelled;
+            Ok(())
+        }
+
+        async fn take(&self, order: &mut Order, _side: TradeSide) -> Result<Vec<OrderFill>, OrderError> {
+            self.calls.lock().push(format!("take {} @ {}", order.size, order.price));
+            Ok(vec![OrderFill::new("taker".to_string(), order.size, order.price)])
+        }
+    }
+
+    fn book(bid: Decimal, ask: Decimal) -> OrderBookSnapshot {
+        OrderBookSnapshot {
+            trading_pair: PAIR.to_string(),
+            exchange: "drift".to_string(),
+            bids: vec![OrderBookLevel::new(bid, dec!(50)), OrderBookLevel::new(bid - dec!(0.1), dec!(80))],
+            asks: vec![OrderBookLevel::new(ask, dec!(50))],
+            timestamp: Utc::now(),
+            is_stale: false,
+        }
+    }
+
+    #[test]
+    fn test_passive_price_stays_on_own_side() {
+        let config = PassiveConfig::default();
+        let touch = Touch { best_bid: dec!(100), best_ask: dec!(100.5) };
+        assert_eq!(config.passive_price(&touch, TradeSide::Buy), dec!(100.01));
+        assert_eq!(config.passive_price(&touch, TradeSide::Sell), dec!(100.48995));
+
+        // A one-tick spread joins the touch instead of crossing
+        let tight = Touch { best_bid: dec!(100), best_ask: dec!(100.005) };
+        assert_eq!(config.passive_price(&tight, TradeSide::Buy), dec!(100));
+    }
+
+    #[tokio::test]
+    async fn test_reprices_as_market_drifts_then_crosses_at_deadline() {
+        let venue = Arc::new(MockVenue::default());
+        let (books, _) = broadcast::channel(16);
+        let (fills, _) = broadcast::channel(16);
+        let executor = Arc::new(PassiveExecutor::new(
+            PassiveConfig {
+                offset_bps: Bps::new(1),
+                reprice_threshold_bps: Bps::new(5),
+                deadline: Duration::from_millis(300),
+            },
+            venue.clone(),
+            books.clone(),
+            fills.clone(),
+        ));
+        let order = Order::new(PAIR.to_string(), "drift".to_string(), OrderType::Limit, dec!(100), dec!(10)).unwrap();
+
+        let running = {
+            let executor = executor.clone();
+            tokio::spawn(async move { executor.execute(order, "maker", TradeSide::Buy).await })
+        };
+        let step = || tokio::time::sleep(Duration::from_millis(20));
+
+        step().await;
+        books.send(book(dec!(100), dec!(100.5))).unwrap();
+        step().await;
+        let placed = venue.placed.lock().clone().unwrap();
+        assert!(placed.post_only);
+        assert_eq!(executor.open_orders()[0].price, dec!(100.01));
+
+        // Part of the order fills at the maker price
+        fills
+            .send(FillUpdate {
+                order_id: placed.id,
+                client_order_id: placed.client_order_id,
+                strategy_id: "maker".to_string(),
+                trading_pair: PAIR.to_string(),
+                fill: OrderFill::new("maker-1".to_string(), dec!(4), dec!(100.01)),
+                filled_size: dec!(4),
+                remaining_size: dec!(6),
+                average_price: Some(dec!(100.01)),
+                status: OrderStatus::PartiallyFilled,
+                realized_pnl: None,
+            })
+            .unwrap();
+
+        // 3 bps stays within the threshold; 8 bps and then 12 bps each reprice
+        for (bid, ask) in [(dec!(100.03), dec!(100.5)), (dec!(100.08), dec!(100.5)), (dec!(100.2), dec!(100.6))] {
+            books.send(book(bid, ask)).unwrap();
+            step().await;
+        }
+        let open = executor.open_orders();
+        assert_eq!(open[0].remaining_size, dec!(6));
+        // Repriced above the best bid, nothing rests ahead of the order
+        assert_eq!(open[0].queue_ahead, Some(Decimal::ZERO));
+
+        let execution = running.await.unwrap().unwrap();
+        assert_eq!(
+            venue.calls.lock().as_slice(),
+            [
+                "place 100.01",
+                "reprice 100.090008",
+                "reprice 100.21002",
+                "cancel",
+                "take 6 @ 100.6",
+            ]
+        );
+        assert_eq!(execution.reprices, 2);
+        assert!(execution.crossed);
+        assert_eq!(execution.maker_fills.len(), 1);
+        assert_eq!(execution.taker_fills[0].size, dec!(6));
+        assert!(executor.open_orders().is_empty());
+
+        // Rebate on 400.04 at 0.2 bps, fee on 603.6 at 2 bps
+        assert_eq!(execution.fees.maker_rebates, dec!(0.008001));
+        assert_eq!(execution.fees.taker_fees, dec!(0.12072));
+    }
+}
@@ -9,7 +9,8 @@
 //! - metrics = "0.20"
 
 use chrono::{DateTime, Utc};
-use metrics::{counter, gauge, histogram};
+use lazy_static::lazy_static;
+use metrics::{register_counter, register_gauge, register_histogram, Counter, Gauge, Histogram};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::RoundingStrategy;
 use serde::{Deserialize, Serialize};
@@ -18,6 +19,9 @@ use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::execution_engine::error::{ExecutionError, TradeContext};
+use crate::execution_engine::position_events::{Bracket, PositionEvent, PositionEventLog, PositionState};
+use crate::models::pair::TradingPair;
+use crate::models::portfolio::{net_fill, PositionClose};
 use crate::models::trade::Trade;
 
 // Position management constants
@@ -27,6 +31,18 @@ const POSITION_UPDATE_TIMEOUT: std::time::Duration = std::time::Duration::from_m
 const EMERGENCY_CLOSURE_THRESHOLD: Decimal = Decimal::new(20, 2); // 20% drawdown threshold
 const METRICS_PREFIX: &str = "trading_bot.position";
 
+// Metric handles for the per-update paths, registered once on first use
+lazy_static! {
+    static ref CREATED: Counter = register_counter!(format!("{}.created", METRICS_PREFIX));
+    static ref CLOSED: Counter = register_counter!(format!("{}.closed", METRICS_PREFIX));
+    static ref PARTIAL_CLOSES: Counter = register_counter!(format!("{}.partial_closes", METRICS_PREFIX));
+    static ref EMERGENCY_CLOSURES: Counter = register_counter!(format!("{}.emergency_closures", METRICS_PREFIX));
+    static ref SIZE: Gauge = register_gauge!(format!("{}.size", METRICS_PREFIX));
+    static ref UNREALIZED_PNL: Gauge = register_gauge!(format!("{}.unrealized_pnl", METRICS_PREFIX));
+    static ref MAX_DRAWDOWN: Gauge = register_gauge!(format!("{}.max_drawdown", METRICS_PREFIX));
+    static ref UPDATE_DURATION_MS: Histogram = register_histogram!(format!("{}.update_duration_ms", METRICS_PREFIX));
+}
+
 /// Position status tracking
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -83,14 +99,8 @@ impl PositionMetrics {
         self.last_update = Utc::now();
 
         // Record metrics
-        gauge!(
-            format!("{}.unrealized_pnl", METRICS_PREFIX),
-            self.unrealized_pnl.to_f64().unwrap_or(0.0)
-        );
-        gauge!(
-            format!("{}.max_drawdown", METRICS_PREFIX),
-            self.max_drawdown.to_f64().unwrap_or(0.0)
-        );
+        UNREALIZED_PNL.set(self.unrealized_pnl.to_f64().unwrap_or(0.0));
+        MAX_DRAWDOWN.set(self.max_drawdown.to_f64().unwrap_or(0.0));
     }
 }
 
@@ -98,7 +108,7 @@ impl PositionMetrics {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Position {
     pub id: Uuid,
-    pub trading_pair: String,
+    pub trading_pair: TradingPair,
     size: Arc<RwLock<Decimal>>,
     entry_price: Decimal,
     current_price: Arc<RwLock<Decimal>>,
@@ -106,6 +116,10 @@ pub struct Position {
     pub closed_at: Option<DateTime<Utc>>,
     status: Arc<RwLock<PositionStatus>>,
     metrics: Arc<RwLock<PositionMetrics>>,
+    bracket: Arc<RwLock<Option<Bracket>>>,
+    /// Lifecycle event log, when the position's history is recorded
+    #[serde(skip)]
+    events: Option<Arc<PositionEventLog>>,
 }
 
 impl Position {
@@ -116,6 +130,8 @@ impl Position {
         entry_price: Decimal,
     ) -> Result<Self, ExecutionError> {
         // Validate position parameters
+        let trading_pair = TradingPair::parse(&trading_pair)
+            .map_err(|e| ExecutionError::ValidationError(e.to_string()))?;
         validate_position_size(size)?;
         validate_price(entry_price)?;
 
@@ -131,18 +147,40 @@ impl Position {
             closed_at: None,
             status: Arc::new(RwLock::new(PositionStatus::Opening)),
             metrics: Arc::new(RwLock::new(PositionMetrics::new(entry_value))),
+            bracket: Arc::new(RwLock::new(None)),
+            events: None,
         };
 
         // Record creation metrics
-        counter!(format!("{}.created", METRICS_PREFIX), 1);
-        gauge!(
-            format!("{}.size", METRICS_PREFIX),
-            size.to_f64().unwrap_or(0.0)
-        );
+        CREATED.increment(1);
+        SIZE.set(size.to_f64().unwrap_or(0.0));
 
         Ok(position)
     }
 
+    /// Records the position's lifecycle, starting with its open caused by the given order
+    pub fn with_event_log(mut self, events: Arc<PositionEventLog>, cause_id: Option<Uuid>) -> Self {
+        let size = *self.size.try_read().expect("new position is unshared");
+        events.emit(
+            self.id,
+            PositionEvent::Opened {
+                trading_pair: self.trading_pair.to_string(),
+                size,
+                entry_price: self.entry_price,
+            },
+            cause_id,
+            self.opened_at,
+        );
+        self.events = Some(events);
+        self
+    }
+
+    fn record(&self, event: PositionEvent, cause_id: Option<Uuid>, at: DateTime<Utc>) {
+        if let Some(events) = &self.events {
+            events.emit(self.id, event, cause_id, at);
+        }
+    }
+
     /// Updates position details with thread-safe operations and validation
     pub async fn update_position(
         &mut self,
@@ -173,41 +211,151 @@ impl Position {
         let drawdown = calculate_drawdown(metrics.peak_value, new_value);
         if drawdown >= EMERGENCY_CLOSURE_THRESHOLD {
             *status = PositionStatus::EmergencyClosing;
-            counter!(format!("{}.emergency_closures", METRICS_PREFIX), 1);
+            EMERGENCY_CLOSURES.increment(1);
+            self.record(PositionEvent::EmergencyTriggered { drawdown_pct: drawdown }, None, Utc::now());
             return Err(ExecutionError::PositionError(
                 format!("Emergency closure triggered: drawdown {:.2}% exceeds threshold", drawdown)
             ));
         }
 
         // Update position data
+        let now = Utc::now();
+        if *size != new_size {
+            self.record(
+                PositionEvent::SizeChanged {
+                    size: new_size,
+                    entry_price: self.entry_price,
+                    realized_pnl: Decimal::ZERO,
+                },
+                None,
+                now,
+            );
+        }
+        self.record(PositionEvent::PriceMarked { price: new_price }, None, now);
         *size = new_size;
         *price = new_price;
         metrics.update(new_value);
 
         // Record update metrics
-        histogram!(
-            format!("{}.update_duration_ms", METRICS_PREFIX),
-            _start.elapsed().as_millis() as f64
-        );
+        UPDATE_DURATION_MS.record(_start.elapsed().as_millis() as f64);
 
         Ok(())
     }
 
+    /// Nets a signed fill into the position: same-direction fills average the entry,
+    /// opposite fills realize P&L, and oversized fills flip with a fresh cost basis
+    pub async fn apply_fill(
+        &mut self,
+        fill_size: Decimal,
+        fill_price: Decimal,
+        cause_id: Option<Uuid>,
+    ) -> Result<Option<PositionClose>, ExecutionError> {
+        validate_price(fill_price)?;
+
+        let mut size = self.size.write().await;
+        let fill = net_fill(*size, self.entry_price, fill_size, fill_price)
+            .map_err(|e| ExecutionError::PositionError(e.to_string()))?;
+        if !fill.is_flat() {
+            validate_position_size(fill.size.abs())?;
+        }
+
+        let mut price = self.current_price.write().await;
+        let mut metrics = self.metrics.write().await;
+        let mut status = self.status.write().await;
+
+        let previous_entry = self.entry_price;
+        let remaining_value = calculate_position_value(fill.size.abs(), fill_price)?;
+
+        // A changed cost basis restarts value tracking for the remaining position
+        if fill.entry_price != previous_entry && !fill.is_flat() {
+            let entry_value = calculate_position_value(fill.size.abs(), fill.entry_price)?;
+            metrics.entry_value = entry_value;
+            metrics.peak_value = entry_value;
+        }
+
+        *size = fill.size;
+        *price = fill_price;
+        self.entry_price = fill.entry_price;
+        metrics.realized_pnl += fill.realized_pnl;
+        metrics.update(remaining_value);
+
+        let now = Utc::now();
+        self.record(
+            PositionEvent::SizeChanged {
+                size: fill.size,
+                entry_price: fill.entry_price,
+                realized_pnl: fill.realized_pnl,
+            },
+            cause_id,
+            now,
+        );
+        self.record(PositionEvent::PriceMarked { price: fill_price }, cause_id, now);
+
+        if fill.is_flat() {
+            *status = PositionStatus::Closed;
+            self.closed_at = Some(now);
+            self.record(PositionEvent::Closed { realized_pnl: metrics.realized_pnl }, cause_id, now);
+            CLOSED.increment(1);
+        }
+
+        if fill.closed_size.is_zero() {
+            return Ok(None);
+        }
+
+        PARTIAL_CLOSES.increment(1);
+
+        Ok(Some(PositionClose {
+            id: Uuid::new_v4(),
+            trading_pair: self.trading_pair.to_string(),
+            closed_size: fill.closed_size,
+            entry_price: previous_entry,
+            exit_price: fill_price,
+            realized_pnl: fill.realized_pnl,
+            remaining_size: fill.size,
+            closed_at: Utc::now(),
+        }))
+    }
+
     /// Retrieves current position metrics
     pub async fn get_metrics(&self) -> Result<PositionMetrics, ExecutionError> {
         Ok(self.metrics.read().await.clone())
     }
 
+    /// Attaches stop-loss and take-profit prices placed by the given order
+    pub async fn attach_bracket(&self, bracket: Bracket, cause_id: Option<Uuid>) {
+        let mut current = self.bracket.write().await;
+        self.record(PositionEvent::BracketAttached { bracket: bracket.clone() }, cause_id, Utc::now());
+        *current = Some(bracket);
+    }
+
+    /// Current lifecycle state, comparable with the replay of the position's events
+    pub async fn state(&self) -> PositionState {
+        PositionState {
+            position_id: self.id,
+            trading_pair: self.trading_pair.to_string(),
+            size: *self.size.read().await,
+            entry_price: self.entry_price,
+            current_price: *self.current_price.read().await,
+            status: self.status.read().await.clone(),
+            realized_pnl: self.metrics.read().await.realized_pnl,
+            bracket: self.bracket.read().await.clone(),
+            opened_at: self.opened_at,
+            closed_at: self.closed_at,
+        }
+    }
+
     /// Closes the position and finalizes metrics
     pub async fn close(&mut self) -> Result<(), ExecutionError> {
         let mut status = self.status.write().await;
         let mut metrics = self.metrics.write().await;
 
+        let now = Utc::now();
         *status = PositionStatus::Closed;
-        self.closed_at = Some(Utc::now());
+        self.closed_at = Some(now);
         metrics.realized_pnl = metrics.unrealized_pnl;
+        self.record(PositionEvent::Closed { realized_pnl: metrics.realized_pnl }, None, now);
 
-        counter!(format!("{}.closed", METRICS_PREFIX), 1);
+        CLOSED.increment(1);
         
         Ok(())
     }
@@ -304,4 +452,25 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(*position.status.read().await, PositionStatus::EmergencyClosing);
     }
+
+    #[tokio::test]
+    async fn test_fills_accumulate_realized_pnl() {
+        let mut position = Position::new(
+            "SOL/USDC".to_string(),
+            dec!(1),
+            dec!(100),
+        ).unwrap();
+
+        assert!(position.apply_fill(dec!(1), dec!(120), None).await.unwrap().is_none());
+        assert_eq!(position.entry_price, dec!(110));
+
+        let close = position.apply_fill(dec!(-0.5), dec!(130), None).await.unwrap().unwrap();
+        assert_eq!(close.realized_pnl, dec!(10));
+        assert_eq!(*position.size.read().await, dec!(1.5));
+
+        position.apply_fill(dec!(-1.5), dec!(100), None).await.unwrap();
+        let metrics = position.get_metrics().await.unwrap();
+        assert_eq!(metrics.realized_pnl, dec!(-5));
+        assert_eq!(*position.status.read().await, PositionStatus::Closed);
+    }
 }
\ No newline at end of file
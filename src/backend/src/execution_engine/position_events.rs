@@ -0,0 +1,489 @@
+//! Append-only event log for the position lifecycle. Every open, size change, price mark,
+//! bracket attach, emergency trigger and close of a position is appended as a typed event
+//! with its timestamp and causing order or trade, so a position's evolution can be replayed
+//! after the fact. A periodic consistency check replays each live position's events and
+//! flags any position whose live state has drifted from its history.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - serde = "1.0"
+//! - metrics = "0.20"
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::{Mutex, RwLock};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::execution_engine::position::PositionStatus;
+
+// Position event constants
+const METRICS_PREFIX: &str = "trading_bot.position_events";
+const MAX_RETAINED_CLOSED_POSITIONS: usize = 256;
+
+/// Position event log error types
+#[derive(Error, Debug)]
+pub enum PositionEventError {
+    #[error("position event store error: {0}")]
+    Store(String),
+    #[error("no events recorded for position {0}")]
+    NotFound(Uuid),
+    #[error("invalid event history: {0}")]
+    InvalidHistory(String),
+}
+
+/// Stop-loss and take-profit prices attached to a position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bracket {
+    pub stop_loss: Decimal,
+    pub take_profit: Decimal,
+}
+
+/// A change to a position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PositionEvent {
+    Opened {
+        trading_pair: String,
+        size: Decimal,
+        entry_price: Decimal,
+    },
+    /// New size and cost basis, with the P&L realized by the change
+    SizeChanged {
+        size: Decimal,
+        entry_price: Decimal,
+        realized_pnl: Decimal,
+    },
+    PriceMarked {
+        price: Decimal,
+    },
+    BracketAttached {
+        bracket: Bracket,
+    },
+    EmergencyTriggered {
+        drawdown_pct: Decimal,
+    },
+    /// Final realized P&L of the position
+    Closed {
+        realized_pnl: Decimal,
+    },
+}
+
+impl PositionEvent {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            PositionEvent::Opened { .. } => "opened",
+            PositionEvent::SizeChanged { .. } => "size_changed",
+            PositionEvent::PriceMarked { .. } => "price_marked",
+            PositionEvent::BracketAttached { .. } => "bracket_attached",
+            PositionEvent::EmergencyTriggered { .. } => "emergency_triggered",
+            PositionEvent::Closed { .. } => "closed",
+        }
+    }
+}
+
+/// One appended position event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionEventRecord {
+    pub id: Uuid,
+    pub position_id: Uuid,
+    /// Position-local order of the event, starting at zero
+    pub sequence: u64,
+    pub event: PositionEvent,
+    /// Order or trade that caused the event, if any
+    pub cause_id: Option<Uuid>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Position state as reconstructed from its events
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionState {
+    pub position_id: Uuid,
+    pub trading_pair: String,
+    pub size: Decimal,
+    pub entry_price: Decimal,
+    pub current_price: Decimal,
+    pub status: PositionStatus,
+    pub realized_pnl: Decimal,
+    pub bracket: Option<Bracket>,
+    pub opened_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+impl PositionState {
+    /// Folds a position's events, in sequence order, into its state
+    pub fn replay(records: &[PositionEventRecord]) -> Result<Self, PositionEventError> {
+        let (first, rest) = records
+            .split_first()
+            .ok_or_else(|| PositionEventError::InvalidHistory("empty history".to_string()))?;
+        let PositionEvent::Opened { trading_pair, size, entry_price } = &first.event else {
+            return Err(PositionEventError::InvalidHistory(format!(
+                "history of {} starts with {}",
+                first.position_id,
+                first.event.event_type()
+            )));
+        };
+
+        let mut state = Self {
+            position_id: first.position_id,
+            trading_pair: trading_pair.clone(),
+            size: *size,
+            entry_price: *entry_price,
+            current_price: *entry_price,
+            status: PositionStatus::Opening,
+            realized_pnl: Decimal::ZERO,
+            bracket: None,
+            opened_at: first.occurred_at,
+            closed_at: None,
+        };
+        for (expected, record) in (1u64..).zip(rest) {
+            if record.sequence != expected || record.position_id != state.position_id {
+                return Err(PositionEventError::InvalidHistory(format!(
+                    "event {} of {} out of sequence",
+                    record.sequence, record.position_id
+                )));
+            }
+            state.apply(record)?;
+        }
+        Ok(state)
+    }
+
+    fn apply(&mut self, record: &PositionEventRecord) -> Result<(), PositionEventError> {
+        match &record.event {
+            PositionEvent::Opened { .. } => {
+                return Err(PositionEventError::InvalidHistory(format!(
+                    "position {} opened twice",
+                    record.position_id
+                )));
+            }
+            PositionEvent::SizeChanged { size, entry_price, realized_pnl } => {
+                self.size = *size;
+                self.entry_price = *entry_price;
+                self.realized_pnl += *realized_pnl;
+            }
+            PositionEvent::PriceMarked { price } => self.current_price = *price,
+            PositionEvent::BracketAttached { bracket } => self.bracket = Some(bracket.clone()),
+            PositionEvent::EmergencyTriggered { .. } => self.status = PositionStatus::EmergencyClosing,
+            PositionEvent::Closed { realized_pnl } => {
+                self.status = PositionStatus::Closed;
+                self.realized_pnl = *realized_pnl;
+                self.closed_at = Some(record.occurred_at);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Durable append-only storage for position events
+#[async_trait]
+pub trait PositionEventStore: Send + Sync {
+    async fn append(&self, record: &PositionEventRecord) -> Result<(), PositionEventError>;
+
+    /// A position's events in sequence order
+    async fn events(&self, position_id: Uuid) -> Result<Vec<PositionEventRecord>, PositionEventError>;
+}
+
+/// Current state of every position the engine is tracking
+#[async_trait]
+pub trait LivePositions: Send + Sync {
+    async fn position_states(&self) -> Vec<PositionState>;
+}
+
+/// In-process emitter that sequences position events, writes them to the audit log and
+/// forwards them in order to the durable store
+#[derive(Default)]
+pub struct PositionEventLog {
+    events: Mutex<HashMap<Uuid, Vec<PositionEventRecord>>>,
+    closed: Mutex<VecDeque<Uuid>>,
+    writer: RwLock<Option<mpsc::UnboundedSender<PositionEventRecord>>>,
+}
+
+impl std::fmt::Debug for PositionEventLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PositionEventLog")
+            .field("positions", &self.events.lock().len())
+            .field("persisted", &self.writer.read().is_some())
+            .finish()
+    }
+}
+
+impl PositionEventLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Persists every subsequent event through the store, in emission order. Must be called
+    /// from within a Tokio runtime.
+    pub fn set_store(&self, store: Arc<dyn PositionEventStore>) {
+        let (tx, mut rx) = mpsc::unbounded_channel::<PositionEventRecord>();
+        tokio::spawn(async move {
+            while let Some(record) = rx.recv().await {
+                if let Err(e) = store.append(&record).await {
+                    counter!(format!("{}.persist_failures", METRICS_PREFIX), 1);
+                    error!(
+                        position_id = %record.position_id,
+                        sequence = record.sequence,
+                        "Failed to persist position event: {}",
+                        e
+                    );
+                }
+            }
+        });
+        *self.writer.write() = Some(tx);
+    }
+
+    /// Appends an event to a position's history
+    pub fn emit(
+        &self,
+        position_id: Uuid,
+        event: PositionEvent,
+        cause_id: Option<Uuid>,
+        occurred_at: DateTime<Utc>,
+    ) -> PositionEventRecord {
+        let record = {
+            let mut events = self.events.lock();
+            let history = events.entry(position_id).or_default();
+            let record = PositionEventRecord {
+                id: Uuid::new_v4(),
+                position_id,
+                sequence: history.len() as u64,
+                event,
+                cause_id,
+                occurred_at,
+            };
+            history.push(record.clone());
+            record
+        };
+
+        counter!(
+            format!("{}.emitted", METRICS_PREFIX),
+            1,
+            "type" => record.event.event_type()
+        );
+        info!(
+            position_id = %record.position_id,
+            sequence = record.sequence,
+            event_type = record.event.event_type(),
+            cause_id = ?record.cause_id,
+            "Position event"
+        );
+
+        if let Some(writer) = self.writer.read().as_ref() {
+            if writer.send(record.clone()).is_err() {
+                warn!(position_id = %record.position_id, "Position event writer stopped");
+            }
+        }
+        if matches!(record.event, PositionEvent::Closed { .. }) {
+            self.retire(position_id);
+        }
+        record
+    }
+
+    /// Keeps closed positions' histories in memory for post-mortems, dropping the oldest
+    fn retire(&self, position_id: Uuid) {
+        let mut closed = self.closed.lock();
+        closed.push_back(position_id);
+        while closed.len() > MAX_RETAINED_CLOSED_POSITIONS {
+            if let Some(evicted) = closed.pop_front() {
+                self.events.lock().remove(&evicted);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl PositionEventStore for PositionEventLog {
+    async fn append(&self, record: &PositionEventRecord) -> Result<(), PositionEventError> {
+        self.events
+            .lock()
+            .entry(record.position_id)
+            .or_default()
+            .push(record.clone());
+        Ok(())
+    }
+
+    async fn events(&self, position_id: Uuid) -> Result<Vec<PositionEventRecord>, PositionEventError> {
+        Ok(self.events.lock().get(&position_id).cloned().unwrap_or_default())
+    }
+}
+
+/// Live position state that differs from the replay of its events
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PositionDivergence {
+    pub live: PositionState,
+    pub replayed: Option<PositionState>,
+    pub reason: String,
+}
+
+/// Reads and replays position histories
+pub struct PositionHistory {
+    store: Arc<dyn PositionEventStore>,
+}
+
+impl std::fmt::Debug for PositionHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PositionHistory").finish_non_exhaustive()
+    }
+}
+
+impl PositionHistory {
+    pub fn new(store: Arc<dyn PositionEventStore>) -> Self {
+        Self { store }
+    }
+
+    pub async fn events(&self, position_id: Uuid) -> Result<Vec<PositionEventRecord>, PositionEventError> {
+        let events = self.store.events(position_id).await?;
+        if events.is_empty() {
+            return Err(PositionEventError::NotFound(position_id));
+        }
+        Ok(events)
+    }
+
+    /// Rebuilds a position's state from its events
+    pub async fn replay(&self, position_id: Uuid) -> Result<PositionState, PositionEventError> {
+        PositionState::replay(&self.events(position_id).await?)
+    }
+
+    /// Replays every live position and reports those whose state differs from their history
+    pub async fn check(&self, live: Vec<PositionState>) -> Vec<PositionDivergence> {
+        let mut divergences = Vec::new();
+        for state in live {
+            let divergence = match self.replay(state.position_id).await {
+                Ok(replayed) if replayed == state => continue,
+                Ok(replayed) => PositionDivergence {
+                    reason: "live state differs from replayed events".to_string(),
+                    replayed: Some(replayed),
+                    live: state,
+                },
+                Err(e) => PositionDivergence {
+                    reason: e.to_string(),
+                    replayed: None,
+                    live: state,
+                },
+            };
+            counter!(format!("{}.divergences", METRICS_PREFIX), 1);
+            error!(
+                position_id = %divergence.live.position_id,
+                trading_pair = %divergence.live.trading_pair,
+                "Position state inconsistent with its event history: {}",
+                divergence.reason
+            );
+            divergences.push(divergence);
+        }
+        divergences
+    }
+
+    /// Periodically checks every live position against its event history
+    pub fn spawn_consistency_check(
+        self: Arc<Self>,
+        positions: Arc<dyn LivePositions>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                self.check(positions.position_states().await).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_engine::position::Position;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_replays_scripted_history() {
+        let log = PositionEventLog::new();
+        let position_id = Uuid::new_v4();
+        let (open_order, exit_order) = (Uuid::new_v4(), Uuid::new_v4());
+        let opened_at = Utc::now();
+        let closed_at = opened_at + chrono::Duration::minutes(5);
+
+        let script = [
+            PositionEvent::Opened { trading_pair: "SOL/USDC".to_string(), size: dec!(2), entry_price: dec!(100) },
+            PositionEvent::BracketAttached { bracket: Bracket { stop_loss: dec!(95), take_profit: dec!(110) } },
+            PositionEvent::PriceMarked { price: dec!(104) },
+            PositionEvent::SizeChanged { size: dec!(1), entry_price: dec!(100), realized_pnl: dec!(4) },
+            PositionEvent::PriceMarked { price: dec!(80) },
+            PositionEvent::EmergencyTriggered { drawdown_pct: dec!(23.08) },
+        ];
+        for event in script {
+            log.emit(position_id, event, Some(open_order), opened_at);
+        }
+
+        let state = PositionState::replay(&log.events.lock()[&position_id]).unwrap();
+        assert_eq!(state.size, dec!(1));
+        assert_eq!(state.current_price, dec!(80));
+        assert_eq!(state.realized_pnl, dec!(4));
+        assert_eq!(state.status, PositionStatus::EmergencyClosing);
+        assert_eq!(state.bracket.as_ref().unwrap().stop_loss, dec!(95));
+
+        let record = log.emit(position_id, PositionEvent::Closed { realized_pnl: dec!(-16) }, Some(exit_order), closed_at);
+        assert_eq!(record.sequence, 6);
+        let records = log.events.lock()[&position_id].clone();
+        let state = PositionState::replay(&records).unwrap();
+        assert_eq!(state.status, PositionStatus::Closed);
+        assert_eq!(state.realized_pnl, dec!(-16));
+        assert_eq!(state.closed_at, Some(closed_at));
+
+        // Events round-trip through their tagged JSON form
+        let json = serde_json::to_value(&records[3]).unwrap();
+        assert_eq!(json["event"]["type"], "size_changed");
+        assert_eq!(serde_json::from_value::<PositionEventRecord>(json).unwrap(), records[3]);
+
+        // A history must start with its open and stay in sequence
+        assert!(PositionState::replay(&records[1..]).is_err());
+        let mut reordered = records.clone();
+        reordered.swap(2, 3);
+        assert!(PositionState::replay(&reordered).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_consistency_check_catches_corrupted_state() {
+        let log = Arc::new(PositionEventLog::new());
+        let mut position = Position::new("SOL/USDC".to_string(), dec!(2), dec!(100))
+            .unwrap()
+            .with_event_log(log.clone(), Some(Uuid::new_v4()));
+        position
+            .attach_bracket(Bracket { stop_loss: dec!(95), take_profit: dec!(115) }, None)
+            .await;
+        position.update_position(dec!(2), dec!(105)).await.unwrap();
+        position.apply_fill(dec!(-1), dec!(110), Some(Uuid::new_v4())).await.unwrap();
+
+        let history = PositionHistory::new(log.clone());
+        let live = position.state().await;
+        assert_eq!(history.replay(position.id).await.unwrap(), live);
+        assert!(history.check(vec![live.clone()]).await.is_empty());
+
+        // State mutated without an event is caught
+        let mut corrupted = live.clone();
+        corrupted.size = dec!(1.5);
+        let divergences = history.check(vec![corrupted]).await;
+        assert_eq!(divergences.len(), 1);
+        assert_eq!(divergences[0].replayed.as_ref().unwrap().size, dec!(1));
+
+        // So is a position with no history at all
+        let untracked = Position::new("BONK/USDC".to_string(), dec!(1), dec!(1)).unwrap();
+        let divergences = history.check(vec![untracked.state().await]).await;
+        assert!(divergences[0].replayed.is_none());
+
+        // Closing through a fill replays to the same closed state
+        position.apply_fill(dec!(-1), dec!(90), None).await.unwrap();
+        let live = position.state().await;
+        assert_eq!(live.status, PositionStatus::Closed);
+        assert_eq!(history.replay(position.id).await.unwrap(), live);
+    }
+}
@@ -0,0 +1,386 @@
+//! Dry-run preview of a strategy definition. The definition is run once against the latest
+//! live market data for each of its pairs, outside the strategy registry, and every order it
+//! would place is passed through trade simulation for a risk verdict. Nothing is persisted,
+//! submitted or registered.
+//!
+//! Version dependencies:
+//! - rust_decimal = "1.30"
+//! - serde = "1.0"
+//! - async-trait = "0.1"
+
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::FutureExt;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{debug, instrument};
+
+use crate::execution_engine::order_book::LiveOrderBook;
+use crate::execution_engine::passive::ExecutionStyle;
+use crate::execution_engine::simulation::{SimulationRequest, TradeSimulation, TradeSimulator};
+use crate::models::market::MarketData;
+use crate::models::order::OrderType;
+use crate::models::strategy::{Strategy, StrategyError, StrategyParams, StrategyType};
+use crate::risk_manager::exposure::TradeSide;
+use crate::signals::Signal;
+use crate::strategies::grid::anchor_grid;
+use crate::strategies::StrategyContext;
+
+// Preview constants
+pub const MAX_PREVIEW_ORDERS: usize = 200;
+const PREVIEW_STRATEGY_ID: &str = "preview";
+
+/// Preview error types
+#[derive(Error, Debug)]
+pub enum PreviewError {
+    #[error("invalid strategy definition: {0}")]
+    InvalidDefinition(String),
+}
+
+/// Strategy definition submitted for preview
+#[derive(Debug, Clone, Deserialize)]
+pub struct PreviewRequest {
+    pub strategy_type: StrategyType,
+    pub parameters: StrategyParams,
+    pub trading_pairs: Vec<String>,
+    /// Grid anchor to preview against; without one a grid anchors on the live price
+    #[serde(default)]
+    pub anchor_price: Option<Decimal>,
+}
+
+/// Order the strategy would place, with the verdict live risk validation gives it
+#[derive(Debug, Clone, Serialize)]
+pub struct PreviewOrder {
+    pub trading_pair: String,
+    pub exchange: String,
+    pub side: TradeSide,
+    pub order_type: OrderType,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub notional: Decimal,
+    pub risk: TradeSimulation,
+}
+
+/// Pair that could not be previewed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PairPreviewError {
+    pub trading_pair: String,
+    pub error: String,
+}
+
+/// Hypothetical orders for a strategy definition
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyPreview {
+    pub orders: Vec<PreviewOrder>,
+    pub errors: Vec<PairPreviewError>,
+    pub total_buy_notional: Decimal,
+    pub total_sell_notional: Decimal,
+    /// Set when orders beyond the cap were dropped
+    pub truncated: bool,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Latest live market data per pair
+pub trait LiveMarketData: Send + Sync {
+    fn latest(&self, trading_pair: &str) -> Option<MarketData>;
+}
+
+impl LiveMarketData for LiveOrderBook {
+    /// Mid price and top-of-book volume of a fresh book
+    fn latest(&self, trading_pair: &str) -> Option<MarketData> {
+        let snapshot = self.snapshot(trading_pair, None, 1)?;
+        if snapshot.is_stale {
+            return None;
+        }
+        let (bid, ask) = (snapshot.bids.first()?, snapshot.asks.first()?);
+        MarketData::new(
+            snapshot.trading_pair.clone(),
+            snapshot.exchange.clone(),
+            (bid.price() + ask.price()) / Decimal::TWO,
+            bid.volume() + ask.volume(),
+        )
+        .ok()
+        .map(|market_data| market_data.with_timestamp(snapshot.timestamp))
+    }
+}
+
+/// Risk verdict for a hypothetical order
+#[async_trait]
+pub trait OrderCheck: Send + Sync {
+    async fn check(&self, request: &SimulationRequest, wallet_address: &str) -> TradeSimulation;
+}
+
+#[async_trait]
+impl OrderCheck for TradeSimulator {
+    async fn check(&self, request: &SimulationRequest, wallet_address: &str) -> TradeSimulation {
+        self.simulate(request, wallet_address).await
+    }
+}
+
+/// Runs strategy definitions against live data without activating them
+pub struct StrategyPreviewer {
+    market_data: Arc<dyn LiveMarketData>,
+    order_check: Arc<dyn OrderCheck>,
+    max_orders: usize,
+}
+
+impl std::fmt::Debug for StrategyPreviewer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StrategyPreviewer")
+            .field("max_orders", &self.max_orders)
+            .finish()
+    }
+}
+
+impl StrategyPreviewer {
+    pub fn new(market_data: Arc<dyn LiveMarketData>, order_check: Arc<dyn OrderCheck>) -> Self {
+        Self {
+            market_data,
+            order_check,
+            max_orders: MAX_PREVIEW_ORDERS,
+        }
+    }
+
+    pub fn with_max_orders(mut self, max_orders: usize) -> Self {
+        self.max_orders = max_orders;
+        self
+    }
+
+    /// Previews a definition for a wallet; per-pair failures are reported, not returned
+    #[instrument(skip(self, request), fields(strategy_type = ?request.strategy_type))]
+    pub async fn preview(
+        &self,
+        request: &PreviewRequest,
+        wallet_address: &str,
+        now: DateTime<Utc>,
+    ) -> Result<StrategyPreview, PreviewError> {
+        let mut strategy = Strategy::new(
+            request.strategy_type.clone(),
+            request.parameters.clone(),
+            request.trading_pairs.clone(),
+        )
+        .map_err(|e| PreviewError::InvalidDefinition(e.to_string()))?;
+        if let (StrategyType::Grid, Some(anchor)) = (&request.strategy_type, request.anchor_price) {
+            anchor_grid(&mut strategy.risk_metrics, anchor);
+        }
+
+        let mut preview = StrategyPreview {
+            orders: Vec::new(),
+            errors: Vec::new(),
+            total_buy_notional: Decimal::ZERO,
+            total_sell_notional: Decimal::ZERO,
+            truncated: false,
+            generated_at: now,
+        };
+
+        for trading_pair in &request.trading_pairs {
+            let Some(market_data) = self.market_data.latest(trading_pair) else {
+                preview.errors.push(PairPreviewError {
+                    trading_pair: trading_pair.clone(),
+                    error: "no live market data".to_string(),
+                });
+                continue;
+            };
+            let signals = match plan_orders(&strategy, &market_data, now).await {
+                Ok(signals) => signals,
+                Err(e) => {
+                    preview.errors.push(PairPreviewError {
+                        trading_pair: trading_pair.clone(),
+                        error: e,
+                    });
+                    continue;
+                }
+            };
+
+            for signal in signals {
+                if preview.orders.len() >= self.max_orders {
+                    preview.truncated = true;
+                    break;
+                }
+                let order = self.check_order(&signal, &strategy, wallet_address).await;
+                match order.side {
+                    TradeSide::Buy => preview.total_buy_notional += order.notional,
+                    TradeSide::Sell => preview.total_sell_notional += order.notional,
+                }
+                preview.orders.push(order);
+            }
+        }
+
+        debug!(
+            orders = preview.orders.len(),
+            errors = preview.errors.len(),
+            truncated = preview.truncated,
+            "Strategy preview generated"
+        );
+        Ok(preview)
+    }
+
+    async fn check_order(&self, signal: &Signal, strategy: &Strategy, wallet_address: &str) -> PreviewOrder {
+        // Signals are placed as limit orders at their reference price
+        let side = signal.direction.side();
+        let order_type = OrderType::Limit;
+        let request = SimulationRequest {
+            strategy_id: format!("{}:{}", PREVIEW_STRATEGY_ID, strategy.id),
+            trading_pair: signal.pair.clone(),
+            exchange: signal.exchange.clone(),
+            side,
+            order_type: order_type.clone(),
+            size: signal.size,
+            price: signal.price,
+            execution_style: ExecutionStyle::default(),
+            max_slippage: strategy.parameters.max_slippage_bps,
+            tick: None,
+        };
+        let risk = self.order_check.check(&request, wallet_address).await;
+
+        PreviewOrder {
+            trading_pair: request.trading_pair,
+            exchange: request.exchange,
+            side,
+            order_type,
+            price: request.price,
+            size: request.size,
+            notional: request.price * request.size,
+            risk,
+        }
+    }
+}
+
+/// Signals a strategy would emit on one observation, with a panicking strategy reported as
+/// an error rather than unwinding into the caller
+pub async fn plan_orders(
+    strategy: &Strategy,
+    market_data: &MarketData,
+    now: DateTime<Utc>,
+) -> Result<Vec<Signal>, String> {
+    let ctx = StrategyContext::new(&format!("{}:{}", PREVIEW_STRATEGY_ID, strategy.id), now);
+    match AssertUnwindSafe(strategy.preview(&ctx, market_data)).catch_unwind().await {
+        Ok(Ok(signals)) => Ok(signals),
+        Ok(Err(StrategyError::ExecutionError(e))) => Err(e),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err(format!("{} strategy cannot be previewed", strategy.strategy_type)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::strategy::StrategyState;
+    use crate::utils::percent::{Bps, Percent};
+    use rust_decimal_macros::dec;
+    use std::collections::HashMap;
+
+    const PAIR: &str = "SOL/USDC";
+
+    struct FrozenMarketData(HashMap<String, MarketData>);
+
+    impl LiveMarketData for FrozenMarketData {
+        fn latest(&self, trading_pair: &str) -> Option<MarketData> {
+            self.0.get(trading_pair).cloned()
+        }
+    }
+
+    struct AcceptAll;
+
+    #[async_trait]
+    impl OrderCheck for AcceptAll {
+        async fn check(&self, _request: &SimulationRequest, _wallet_address: &str) -> TradeSimulation {
+            TradeSimulation {
+                would_execute: true,
+                ..TradeSimulation::default()
+            }
+        }
+    }
+
+    fn params() -> StrategyParams {
+        StrategyParams {
+            position_size_bps: Bps::new(1000),
+            grid_levels: Some(10),
+            stop_loss_pct: Percent::from_percent(dec!(-5)),
+            take_profit_pct: Percent::from_percent(dec!(1)),
+            max_slippage_bps: Bps::new(100),
+            exchanges: vec!["jupiter".to_string()],
+            risk_factor: dec!(0.5),
+            allowed_regimes: Vec::new(),
+            market_making: None,
+        }
+    }
+
+    fn tick(price: Decimal, at: DateTime<Utc>) -> MarketData {
+        MarketData::new(PAIR.to_string(), "jupiter".to_string(), price, dec!(5))
+            .unwrap()
+            .with_timestamp(at)
+    }
+
+    #[tokio::test]
+    async fn test_preview_matches_activated_strategy_on_frozen_data() {
+        let t0 = Utc::now();
+        let t1 = t0 + chrono::Duration::seconds(1);
+
+        // Activated strategy anchors on the first tick and trades on the second
+        let mut strategy = Strategy::new(StrategyType::Grid, params(), vec![PAIR.to_string()]).unwrap();
+        strategy.state = StrategyState::Active;
+        let ctx = |now| StrategyContext::new("activated", now);
+        assert!(strategy.execute(&ctx(t0), &tick(dec!(100), t0)).await.unwrap().is_empty());
+        let executed = strategy.execute(&ctx(t1), &tick(dec!(97.5), t1)).await.unwrap();
+        assert_eq!(executed.len(), 3);
+
+        let previewer = StrategyPreviewer::new(
+            Arc::new(FrozenMarketData(HashMap::from([(PAIR.to_string(), tick(dec!(97.5), t1))]))),
+            Arc::new(AcceptAll),
+        );
+        let request = PreviewRequest {
+            strategy_type: StrategyType::Grid,
+            parameters: params(),
+            trading_pairs: vec![PAIR.to_string(), "BONK/USDC".to_string()],
+            anchor_price: Some(dec!(100)),
+        };
+        let preview = previewer.preview(&request, "wallet", t1).await.unwrap();
+
+        let previewed: Vec<_> = preview
+            .orders
+            .iter()
+            .map(|order| (order.side, order.price, order.size))
+            .collect();
+        let activated: Vec<_> = executed
+            .iter()
+            .map(|signal| (signal.direction.side(), signal.price, signal.size))
+            .collect();
+        assert_eq!(previewed, activated);
+        assert!(preview.orders.iter().all(|order| order.risk.would_execute));
+        assert_eq!(preview.total_buy_notional, dec!(29.7));
+        assert_eq!(preview.total_sell_notional, Decimal::ZERO);
+        assert_eq!(
+            preview.errors,
+            vec![PairPreviewError {
+                trading_pair: "BONK/USDC".to_string(),
+                error: "no live market data".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_preview_caps_orders() {
+        let now = Utc::now();
+        let previewer = StrategyPreviewer::new(
+            Arc::new(FrozenMarketData(HashMap::from([(PAIR.to_string(), tick(dec!(90), now))]))),
+            Arc::new(AcceptAll),
+        )
+        .with_max_orders(2);
+        let request = PreviewRequest {
+            strategy_type: StrategyType::Grid,
+            parameters: params(),
+            trading_pairs: vec![PAIR.to_string()],
+            anchor_price: Some(dec!(100)),
+        };
+
+        let preview = previewer.preview(&request, "wallet", now).await.unwrap();
+
+        assert_eq!(preview.orders.len(), 2);
+        assert!(preview.truncated);
+    }
+}
@@ -0,0 +1,618 @@
+//! Execution queue in front of the trade executor with priority classes, weighted fair
+//! scheduling across strategies, per-strategy in-flight caps and staleness expiry.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - metrics = "0.20"
+//! - tracing = "0.1"
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use metrics::{counter, gauge, histogram};
+use parking_lot::{Mutex, RwLock as SyncRwLock};
+use tokio::sync::{oneshot, Notify};
+use tracing::{debug, info, instrument, warn, Instrument};
+use uuid::Uuid;
+
+use crate::execution_engine::error::ExecutionError;
+use crate::execution_engine::latency::LatencyWatchdog;
+use crate::execution_engine::throttle::OrderThrottle;
+use crate::execution_engine::trade::{TradeParams, TradeResult, TradeSubmitter};
+
+// Queue configuration defaults
+pub const DEFAULT_STRATEGY_IN_FLIGHT_CAP: usize = 4;
+pub const DEFAULT_CRITICAL_MAX_WAIT_MS: u64 = 5000;
+pub const DEFAULT_HIGH_MAX_WAIT_MS: u64 = 50;
+pub const DEFAULT_NORMAL_MAX_WAIT_MS: u64 = 1000;
+pub const DEFAULT_STRATEGY_WEIGHT: u32 = 1;
+const METRICS_PREFIX: &str = "trading_bot.execution_queue";
+
+/// Execution priority classes, served strictly in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PriorityClass {
+    /// Emergency closures
+    Critical,
+    /// Arbitrage and other latency-sensitive orders
+    High,
+    /// Grid and rebalance orders
+    Normal,
+}
+
+impl PriorityClass {
+    pub const ALL: [PriorityClass; 3] = [
+        PriorityClass::Critical,
+        PriorityClass::High,
+        PriorityClass::Normal,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            PriorityClass::Critical => "critical",
+            PriorityClass::High => "high",
+            PriorityClass::Normal => "normal",
+        }
+    }
+}
+
+/// Execution queue configuration
+#[derive(Debug, Clone)]
+pub struct QueueConfig {
+    pub strategy_in_flight_cap: usize,
+    pub critical_max_wait: Duration,
+    pub high_max_wait: Duration,
+    pub normal_max_wait: Duration,
+    /// Fair-share weights by strategy id; unlisted strategies get the default weight
+    pub strategy_weights: HashMap<String, u32>,
+}
+
+impl QueueConfig {
+    fn max_wait(&self, priority: PriorityClass) -> Duration {
+        match priority {
+            PriorityClass::Critical => self.critical_max_wait,
+            PriorityClass::High => self.high_max_wait,
+            PriorityClass::Normal => self.normal_max_wait,
+        }
+    }
+
+    fn weight(&self, strategy_id: &str) -> u32 {
+        self.strategy_weights
+            .get(strategy_id)
+            .copied()
+            .unwrap_or(DEFAULT_STRATEGY_WEIGHT)
+            .max(1)
+    }
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            strategy_in_flight_cap: DEFAULT_STRATEGY_IN_FLIGHT_CAP,
+            critical_max_wait: Duration::from_millis(DEFAULT_CRITICAL_MAX_WAIT_MS),
+            high_max_wait: Duration::from_millis(DEFAULT_HIGH_MAX_WAIT_MS),
+            normal_max_wait: Duration::from_millis(DEFAULT_NORMAL_MAX_WAIT_MS),
+            strategy_weights: HashMap::new(),
+        }
+    }
+}
+
+/// Final outcome of a queued order as returned to the strategy
+#[derive(Debug)]
+pub enum QueueOutcome {
+    Executed(Result<TradeResult, ExecutionError>),
+    Expired { waited: Duration },
+    Cancelled,
+}
+
+/// Order waiting for execution
+#[derive(Debug)]
+pub struct QueuedOrder {
+    pub id: Uuid,
+    pub strategy_id: String,
+    pub priority: PriorityClass,
+    pub params: TradeParams,
+    pub enqueued_at: Instant,
+    pub deadline: Instant,
+    responder: Option<oneshot::Sender<QueueOutcome>>,
+    /// Submitter's span, so execution on the dispatcher stays in the order's trace
+    span: tracing::Span,
+}
+
+impl QueuedOrder {
+    /// Sends the outcome back to the submitting strategy
+    pub fn respond(mut self, outcome: QueueOutcome) {
+        if let Some(responder) = self.responder.take() {
+            let _ = responder.send(outcome);
+        }
+    }
+}
+
+/// Deficit round robin state for one priority class
+#[derive(Debug, Default)]
+struct ClassQueue {
+    queues: HashMap<String, VecDeque<QueuedOrder>>,
+    rotation: VecDeque<String>,
+    deficits: HashMap<String, u32>,
+    depth: usize,
+}
+
+impl ClassQueue {
+    fn push(&mut self, order: QueuedOrder) {
+        let strategy_id = order.strategy_id.clone();
+        let queue = self.queues.entry(strategy_id.clone()).or_default();
+        if queue.is_empty() && !self.rotation.contains(&strategy_id) {
+            self.rotation.push_back(strategy_id);
+        }
+        queue.push_back(order);
+        self.depth += 1;
+    }
+
+    /// Removes every order whose deadline has passed
+    fn drain_expired(&mut self, now: Instant) -> Vec<QueuedOrder> {
+        let mut expired = Vec::new();
+        for queue in self.queues.values_mut() {
+            let mut kept = VecDeque::with_capacity(queue.len());
+            for order in queue.drain(..) {
+                if order.deadline <= now {
+                    expired.push(order);
+                } else {
+                    kept.push_back(order);
+                }
+            }
+            *queue = kept;
+        }
+        self.depth -= expired.len();
+        expired
+    }
+
+    /// Removes every order queued by a strategy
+    fn remove_strategy(&mut self, strategy_id: &str) -> Vec<QueuedOrder> {
+        let removed: Vec<QueuedOrder> = self
+            .queues
+            .remove(strategy_id)
+            .map(Vec::from)
+            .unwrap_or_default();
+        self.rotation.retain(|id| id != strategy_id);
+        self.deficits.remove(strategy_id);
+        self.depth -= removed.len();
+        removed
+    }
+
+    /// Pops the next order by weighted round robin, skipping strategies at their cap
+    fn pop(&mut self, config: &QueueConfig, in_flight: &HashMap<String, usize>) -> Option<QueuedOrder> {
+        let mut skipped = 0;
+        while skipped < self.rotation.len() {
+            let strategy_id = self.rotation.front()?.clone();
+
+            let has_orders = self
+                .queues
+                .get(&strategy_id)
+                .map_or(false, |queue| !queue.is_empty());
+            if !has_orders {
+                self.rotation.pop_front();
+                self.deficits.remove(&strategy_id);
+                continue;
+            }
+
+            if in_flight.get(&strategy_id).copied().unwrap_or(0) >= config.strategy_in_flight_cap {
+                self.rotation.rotate_left(1);
+                skipped += 1;
+                continue;
+            }
+
+            let deficit = self.deficits.entry(strategy_id.clone()).or_insert(0);
+            if *deficit == 0 {
+                *deficit = config.weight(&strategy_id);
+            }
+            *deficit -= 1;
+            if *deficit == 0 {
+                self.rotation.rotate_left(1);
+            }
+
+            self.depth -= 1;
+            return self.queues.get_mut(&strategy_id)?.pop_front();
+        }
+        None
+    }
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    classes: HashMap<PriorityClass, ClassQueue>,
+    in_flight: HashMap<String, usize>,
+}
+
+/// Priority execution queue with weighted fairness across strategies
+#[derive(Debug)]
+pub struct ExecutionQueue {
+    config: QueueConfig,
+    state: Mutex<QueueState>,
+    notify: Notify,
+    /// Measures tick-to-trade latency at dispatch and refuses orders on stale ticks
+    latency: SyncRwLock<Option<Arc<LatencyWatchdog>>>,
+    /// Paces dispatched orders per venue for the signing wallet
+    throttle: SyncRwLock<Option<(Arc<OrderThrottle>, String)>>,
+}
+
+impl ExecutionQueue {
+    /// Creates new execution queue with configuration
+    pub fn new(config: QueueConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(QueueState::default()),
+            notify: Notify::new(),
+            latency: SyncRwLock::new(None),
+            throttle: SyncRwLock::new(None),
+        }
+    }
+
+    /// Checks each dispatched order's tick against the latency watchdog
+    pub fn set_latency_watchdog(&self, watchdog: Arc<LatencyWatchdog>) {
+        *self.latency.write() = Some(watchdog);
+    }
+
+    /// Holds each dispatched order for a submission slot on its venue before it is signed
+    pub fn set_order_throttle(&self, throttle: Arc<OrderThrottle>, wallet_address: &str) {
+        *self.throttle.write() = Some((throttle, wallet_address.to_string()));
+    }
+
+    /// Enqueues an order and returns a receiver for its outcome
+    #[instrument(skip(self, params), fields(trade_id = %params.id))]
+    pub fn enqueue(
+        &self,
+        strategy_id: &str,
+        priority: PriorityClass,
+        params: TradeParams,
+    ) -> oneshot::Receiver<QueueOutcome> {
+        self.enqueue_at(strategy_id, priority, params, Instant::now())
+    }
+
+    /// Enqueues an order with an explicit enqueue time
+    pub fn enqueue_at(
+        &self,
+        strategy_id: &str,
+        priority: PriorityClass,
+        params: TradeParams,
+        now: Instant,
+    ) -> oneshot::Receiver<QueueOutcome> {
+        let (tx, rx) = oneshot::channel();
+        let order = QueuedOrder {
+            id: Uuid::new_v4(),
+            strategy_id: strategy_id.to_string(),
+            priority,
+            params,
+            enqueued_at: now,
+            deadline: now + self.config.max_wait(priority),
+            responder: Some(tx),
+            span: tracing::Span::current(),
+        };
+
+        {
+            let mut state = self.state.lock();
+            let class = state.classes.entry(priority).or_default();
+            class.push(order);
+            gauge!(
+                format!("{}.depth", METRICS_PREFIX),
+                class.depth as f64,
+                "class" => priority.as_str()
+            );
+        }
+
+        counter!(format!("{}.enqueued", METRICS_PREFIX), 1, "class" => priority.as_str());
+        self.notify.notify_one();
+        rx
+    }
+
+    /// Takes the next dispatchable order, returning expired orders to their strategies
+    pub fn dequeue_at(&self, now: Instant) -> Option<QueuedOrder> {
+        let mut state = self.state.lock();
+        let QueueState { classes, in_flight } = &mut *state;
+
+        // Expire stale orders before scheduling
+        for priority in PriorityClass::ALL {
+            let Some(class) = classes.get_mut(&priority) else {
+                continue;
+            };
+            for order in class.drain_expired(now) {
+                let waited = now.duration_since(order.enqueued_at);
+                counter!(format!("{}.expired", METRICS_PREFIX), 1, "class" => priority.as_str());
+                debug!(
+                    strategy_id = %order.strategy_id,
+                    waited_ms = waited.as_millis() as u64,
+                    "Queued order expired before execution"
+                );
+                order.respond(QueueOutcome::Expired { waited });
+            }
+        }
+
+        for priority in PriorityClass::ALL {
+            let Some(class) = classes.get_mut(&priority) else {
+                continue;
+            };
+            if let Some(order) = class.pop(&self.config, in_flight) {
+                *in_flight.entry(order.strategy_id.clone()).or_insert(0) += 1;
+
+                gauge!(
+                    format!("{}.depth", METRICS_PREFIX),
+                    class.depth as f64,
+                    "class" => priority.as_str()
+                );
+                histogram!(
+                    format!("{}.wait_time_ms", METRICS_PREFIX),
+                    now.duration_since(order.enqueued_at).as_secs_f64() * 1000.0,
+                    "class" => priority.as_str()
+                );
+                return Some(order);
+            }
+        }
+        None
+    }
+
+    /// Releases the in-flight slot held by a dispatched order
+    pub fn complete(&self, strategy_id: &str) {
+        let mut state = self.state.lock();
+        if let Some(count) = state.in_flight.get_mut(strategy_id) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                state.in_flight.remove(strategy_id);
+            }
+        }
+        drop(state);
+        self.notify.notify_one();
+    }
+
+    /// Cancels every order a strategy still has waiting, returning how many were removed;
+    /// orders already dispatched run to completion
+    pub fn cancel_strategy(&self, strategy_id: &str) -> usize {
+        let cancelled: Vec<QueuedOrder> = {
+            let mut state = self.state.lock();
+            state
+                .classes
+                .values_mut()
+                .flat_map(|class| class.remove_strategy(strategy_id))
+                .collect()
+        };
+
+        let count = cancelled.len();
+        for order in cancelled {
+            counter!(format!("{}.cancelled", METRICS_PREFIX), 1, "class" => order.priority.as_str());
+            order.respond(QueueOutcome::Cancelled);
+        }
+        count
+    }
+
+    /// Number of queued orders in a priority class
+    pub fn depth(&self, priority: PriorityClass) -> usize {
+        self.state
+            .lock()
+            .classes
+            .get(&priority)
+            .map_or(0, |class| class.depth)
+    }
+
+    /// Submits an order and waits for its execution or expiry
+    pub async fn submit(
+        &self,
+        strategy_id: &str,
+        priority: PriorityClass,
+        params: TradeParams,
+    ) -> Result<TradeResult, ExecutionError> {
+        match self.enqueue(strategy_id, priority, params).await {
+            Ok(QueueOutcome::Executed(result)) => result,
+            Ok(QueueOutcome::Expired { waited }) => Err(ExecutionError::ExpiredError(format!(
+                "order waited {}ms in {} queue",
+                waited.as_millis(),
+                priority.as_str()
+            ))),
+            Ok(QueueOutcome::Cancelled) => Err(ExecutionError::ValidationError(
+                "order cancelled before execution".to_string(),
+            )),
+            Err(_) => Err(ExecutionError::InternalError(
+                "execution queue dropped order".to_string(),
+            )),
+        }
+    }
+
+    /// Spawns the dispatcher feeding the trade submitter
+    pub fn spawn_dispatcher(self: Arc<Self>, executor: Arc<dyn TradeSubmitter>) -> tokio::task::JoinHandle<()> {
+        info!("Starting execution queue dispatcher");
+        tokio::spawn(async move {
+            loop {
+                let next = self.dequeue_at(Instant::now());
+                let Some(order) = next else {
+                    // Wake on enqueue, completion, or the tightest staleness deadline
+                    let _ = tokio::time::timeout(
+                        self.config.high_max_wait.min(self.config.normal_max_wait),
+                        self.notify.notified(),
+                    )
+                    .await;
+                    continue;
+                };
+
+                let queue = self.clone();
+                let executor = executor.clone();
+                let latency = self.latency.read().clone();
+                let throttle = self.throttle.read().clone();
+                let span = order.span.clone();
+                tokio::spawn(async move {
+                    let strategy_id = order.strategy_id.clone();
+                    if let (Some(latency), Some(tick)) = (latency, order.params.tick) {
+                        let measured = latency
+                            .observe(
+                                &strategy_id,
+                                &order.params.trading_pair,
+                                tick,
+                                Instant::now(),
+                                chrono::Utc::now(),
+                            )
+                            .await;
+                        if measured.stale {
+                            counter!(format!("{}.stale_tick", METRICS_PREFIX), 1, "class" => order.priority.as_str());
+                            order.respond(QueueOutcome::Executed(Err(ExecutionError::ExpiredError(format!(
+                                "order acted on a tick {}ms old",
+                                measured.tick_age.as_millis()
+                            )))));
+                            queue.complete(&strategy_id);
+                            return;
+                        }
+                    }
+
+                    if let Some((throttle, wallet_address)) = throttle {
+                        let slot = throttle
+                            .acquire(&wallet_address, &order.params.exchange, order.priority)
+                            .await;
+                        if let Err(e) = slot {
+                            counter!(format!("{}.throttled", METRICS_PREFIX), 1, "class" => order.priority.as_str());
+                            debug!(strategy_id = %strategy_id, "Order held back by venue throttle: {}", e);
+                            order.respond(QueueOutcome::Executed(Err(e)));
+                            queue.complete(&strategy_id);
+                            return;
+                        }
+                    }
+
+                    let result = executor.execute_trade(order.params.clone()).await;
+                    if let Err(e) = &result {
+                        warn!(strategy_id = %strategy_id, "Queued execution failed: {}", e);
+                    }
+                    order.respond(QueueOutcome::Executed(result));
+                    queue.complete(&strategy_id);
+                }.instrument(span));
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn params(id: &str) -> TradeParams {
+        TradeParams {
+            id: id.to_string(),
+            trading_pair: "SOL/USDC".to_string(),
+            exchange: "jupiter".to_string(),
+            order_type: crate::models::order::OrderType::Market,
+            price: dec!(23.45),
+            size: dec!(1.0),
+            slippage: dec!(0.01),
+            guarded: None,
+            tick: None,
+            intent_id: None,
+        }
+    }
+
+    fn drain(queue: &ExecutionQueue, now: Instant) -> Vec<QueuedOrder> {
+        let mut dispatched = Vec::new();
+        while let Some(order) = queue.dequeue_at(now) {
+            queue.complete(&order.strategy_id);
+            dispatched.push(order);
+        }
+        dispatched
+    }
+
+    #[test]
+    fn test_priority_ordering() {
+        let queue = ExecutionQueue::new(QueueConfig::default());
+        let now = Instant::now();
+
+        queue.enqueue_at("grid", PriorityClass::Normal, params("n1"), now);
+        queue.enqueue_at("arb", PriorityClass::High, params("h1"), now);
+        queue.enqueue_at("risk", PriorityClass::Critical, params("c1"), now);
+
+        let order: Vec<String> = drain(&queue, now).into_iter().map(|o| o.params.id).collect();
+        assert_eq!(order, vec!["c1", "h1", "n1"]);
+    }
+
+    #[test]
+    fn test_weighted_fairness_across_strategies() {
+        let mut config = QueueConfig::default();
+        config.strategy_weights.insert("grid".to_string(), 2);
+        config.strategy_weights.insert("rebalance".to_string(), 1);
+        let queue = ExecutionQueue::new(config);
+        let now = Instant::now();
+
+        // Chatty grid strategy floods first
+        for i in 0..10 {
+            queue.enqueue_at("grid", PriorityClass::Normal, params(&format!("g{}", i)), now);
+        }
+        for i in 0..3 {
+            queue.enqueue_at("rebalance", PriorityClass::Normal, params(&format!("r{}", i)), now);
+        }
+
+        let strategies: Vec<String> = drain(&queue, now)
+            .into_iter()
+            .take(9)
+            .map(|o| o.strategy_id)
+            .collect();
+        let grid_share = strategies.iter().filter(|s| *s == "grid").count();
+        assert_eq!(grid_share, 6);
+        assert_eq!(&strategies[..3], &["grid", "grid", "rebalance"]);
+    }
+
+    #[test]
+    fn test_in_flight_cap() {
+        let mut config = QueueConfig::default();
+        config.strategy_in_flight_cap = 1;
+        let queue = ExecutionQueue::new(config);
+        let now = Instant::now();
+
+        queue.enqueue_at("grid", PriorityClass::Normal, params("g1"), now);
+        queue.enqueue_at("grid", PriorityClass::Normal, params("g2"), now);
+        queue.enqueue_at("arb", PriorityClass::Normal, params("a1"), now);
+
+        let first = queue.dequeue_at(now).unwrap();
+        assert_eq!(first.strategy_id, "grid");
+
+        // grid is at its cap so arb is served next and grid waits
+        assert_eq!(queue.dequeue_at(now).unwrap().strategy_id, "arb");
+        assert!(queue.dequeue_at(now).is_none());
+
+        queue.complete("grid");
+        assert_eq!(queue.dequeue_at(now).unwrap().params.id, "g2");
+    }
+
+    #[tokio::test]
+    async fn test_stale_orders_expire() {
+        let queue = ExecutionQueue::new(QueueConfig::default());
+        let now = Instant::now();
+
+        let arb_rx = queue.enqueue_at("arb", PriorityClass::High, params("h1"), now);
+        let grid_rx = queue.enqueue_at("grid", PriorityClass::Normal, params("n1"), now);
+
+        // Past the high-priority deadline but within the normal one
+        let later = now + Duration::from_millis(DEFAULT_HIGH_MAX_WAIT_MS + 1);
+        let dispatched = queue.dequeue_at(later).unwrap();
+        assert_eq!(dispatched.params.id, "n1");
+        assert_eq!(queue.depth(PriorityClass::High), 0);
+
+        match arb_rx.await.unwrap() {
+            QueueOutcome::Expired { waited } => {
+                assert!(waited > Duration::from_millis(DEFAULT_HIGH_MAX_WAIT_MS))
+            }
+            other => panic!("expected expiry, got {:?}", other),
+        }
+
+        dispatched.respond(QueueOutcome::Expired { waited: Duration::ZERO });
+        assert!(grid_rx.await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_strategy_removes_queued_orders() {
+        let queue = ExecutionQueue::new(QueueConfig::default());
+        let now = Instant::now();
+
+        let grid_rx = queue.enqueue_at("grid", PriorityClass::Normal, params("g1"), now);
+        queue.enqueue_at("grid", PriorityClass::High, params("g2"), now);
+        queue.enqueue_at("arb", PriorityClass::Normal, params("a1"), now);
+
+        assert_eq!(queue.cancel_strategy("grid"), 2);
+        assert_eq!(queue.cancel_strategy("grid"), 0);
+        assert!(matches!(grid_rx.await.unwrap(), QueueOutcome::Cancelled));
+
+        let remaining: Vec<String> = drain(&queue, now).into_iter().map(|o| o.params.id).collect();
+        assert_eq!(remaining, vec!["a1"]);
+    }
+}
@@ -0,0 +1,450 @@
+//! Warm-up gating for the execution engine. The engine starts `WarmingUp` and only turns
+//! `Ready` once every pair traded by an active strategy has a fresh aggregated price and a
+//! non-stale live order book, so the first orders after a restart are not priced off empty
+//! or half-synced books.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - metrics = "0.21"
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use metrics::{gauge, histogram};
+use parking_lot::{Mutex, RwLock as SyncRwLock};
+use serde::Serialize;
+use tokio::sync::{watch, RwLock};
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::data_collector::quality::DataQualityMonitor;
+use crate::execution_engine::error::ExecutionError;
+use crate::execution_engine::order_book::LiveOrderBook;
+use crate::models::strategy::{Strategy, StrategyState};
+use crate::utils::time::to_chrono;
+
+// Readiness constants
+const METRICS_PREFIX: &str = "trading_bot.execution.readiness";
+const DEFAULT_MAX_DATA_AGE: Duration = Duration::from_secs(10);
+const DEFAULT_MIN_TICKS: usize = 3;
+const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Whether the engine accepts executions yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EngineState {
+    WarmingUp,
+    Ready,
+}
+
+/// What happens to executions submitted while warming up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarmUpPolicy {
+    /// Fail immediately with `ExecutionError::WarmingUp`
+    Reject,
+    /// Hold the execution until the engine is ready, rejecting it after `max_wait`
+    Queue { max_wait: Duration },
+}
+
+#[derive(Debug, Clone)]
+pub struct ReadinessConfig {
+    /// Oldest price tick or order book still counted as fresh
+    pub max_data_age: Duration,
+    /// Price ticks each pair needs within `max_data_age`
+    pub min_ticks: usize,
+    pub policy: WarmUpPolicy,
+    pub check_interval: Duration,
+}
+
+impl Default for ReadinessConfig {
+    fn default() -> Self {
+        Self {
+            max_data_age: DEFAULT_MAX_DATA_AGE,
+            min_ticks: DEFAULT_MIN_TICKS,
+            policy: WarmUpPolicy::Reject,
+            check_interval: DEFAULT_CHECK_INTERVAL,
+        }
+    }
+}
+
+/// Price ticks seen for a pair across promoted sources
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PriceActivity {
+    pub ticks: usize,
+    pub last_tick_at: Option<DateTime<Utc>>,
+}
+
+/// Aggregated prices the gate requires before trading a pair
+pub trait PriceFeed: Send + Sync {
+    fn price_activity(&self, trading_pair: &str, since: DateTime<Utc>) -> PriceActivity;
+}
+
+/// Live order books the gate requires before trading a pair
+pub trait BookFeed: Send + Sync {
+    /// Timestamp of the pair's latest book, if one exists and is not stale
+    fn fresh_book_at(&self, trading_pair: &str) -> Option<DateTime<Utc>>;
+}
+
+impl PriceFeed for DataQualityMonitor {
+    fn price_activity(&self, trading_pair: &str, since: DateTime<Utc>) -> PriceActivity {
+        DataQualityMonitor::price_activity(self, trading_pair, since)
+    }
+}
+
+impl BookFeed for LiveOrderBook {
+    fn fresh_book_at(&self, trading_pair: &str) -> Option<DateTime<Utc>> {
+        self.view(trading_pair)
+            .filter(|view| !view.is_stale())
+            .map(|view| view.timestamp)
+    }
+}
+
+/// Market data readiness of one traded pair
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct PairReadiness {
+    pub trading_pair: String,
+    pub ready: bool,
+    pub price_ticks: usize,
+    pub last_price_at: Option<DateTime<Utc>>,
+    pub last_book_at: Option<DateTime<Utc>>,
+}
+
+/// Engine state with the readiness of every traded pair, as served on the health endpoint
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReadinessReport {
+    pub state: EngineState,
+    /// Time from engine start to readiness
+    pub warm_up_ms: Option<u64>,
+    pub pairs: Vec<PairReadiness>,
+}
+
+/// Holds executions back until market data for every traded pair is fresh; once ready the
+/// engine stays ready, later data gaps are the data quality monitor's concern
+pub struct ReadinessGate {
+    config: ReadinessConfig,
+    started_at: Instant,
+    state: watch::Sender<EngineState>,
+    books: Arc<dyn BookFeed>,
+    prices: SyncRwLock<Option<Arc<dyn PriceFeed>>>,
+    strategies: SyncRwLock<Option<Arc<RwLock<HashMap<String, Strategy>>>>>,
+    pairs: Mutex<Vec<PairReadiness>>,
+    warm_up: Mutex<Option<Duration>>,
+}
+
+impl std::fmt::Debug for ReadinessGate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadinessGate")
+            .field("config", &self.config)
+            .field("state", &self.state())
+            .finish()
+    }
+}
+
+impl ReadinessGate {
+    pub fn new(config: ReadinessConfig, books: Arc<dyn BookFeed>) -> Self {
+        gauge!(format!("{}.ready", METRICS_PREFIX), 0.0);
+        Self {
+            config,
+            started_at: Instant::now(),
+            state: watch::channel(EngineState::WarmingUp).0,
+            books,
+            prices: SyncRwLock::new(None),
+            strategies: SyncRwLock::new(None),
+            pairs: Mutex::new(Vec::new()),
+            warm_up: Mutex::new(None),
+        }
+    }
+
+    /// Source of the aggregated prices each pair needs; without one no pair is ready
+    pub fn set_price_feed(&self, prices: Arc<dyn PriceFeed>) {
+        *self.prices.write() = Some(prices);
+    }
+
+    /// Strategies whose active pairs must be ready before trading starts
+    pub fn set_strategies(&self, strategies: Arc<RwLock<HashMap<String, Strategy>>>) {
+        *self.strategies.write() = Some(strategies);
+    }
+
+    pub fn state(&self) -> EngineState {
+        *self.state.borrow()
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.state() == EngineState::Ready
+    }
+
+    /// Notified on the transition to `Ready`
+    pub fn subscribe(&self) -> watch::Receiver<EngineState> {
+        self.state.subscribe()
+    }
+
+    pub fn report(&self) -> ReadinessReport {
+        ReadinessReport {
+            state: self.state(),
+            warm_up_ms: self.warm_up.lock().map(|elapsed| elapsed.as_millis() as u64),
+            pairs: self.pairs.lock().clone(),
+        }
+    }
+
+    /// Admits an execution once the engine is ready, queueing or rejecting it per the
+    /// configured policy while warming up
+    pub async fn admit(&self, trading_pair: &str) -> Result<(), ExecutionError> {
+        if self.is_ready() {
+            return Ok(());
+        }
+        if let WarmUpPolicy::Queue { max_wait } = self.config.policy {
+            let mut state = self.subscribe();
+            let ready = tokio::time::timeout(
+                max_wait,
+                state.wait_for(|state| *state == EngineState::Ready),
+            )
+            .await;
+            if matches!(ready, Ok(Ok(_))) {
+                return Ok(());
+            }
+        }
+        Err(ExecutionError::WarmingUp(format!(
+            "market data for {} not ready yet",
+            trading_pair
+        )))
+    }
+
+    /// Rechecks every active pair, turning `Ready` once all have fresh prices and books
+    pub async fn evaluate(&self, now: DateTime<Utc>) -> EngineState {
+        let pairs = self.required_pairs().await;
+        let since = now - to_chrono(self.config.max_data_age);
+        let prices = self.prices.read().clone();
+
+        let readiness: Vec<PairReadiness> = pairs
+            .into_iter()
+            .map(|trading_pair| {
+                let activity = prices
+                    .as_ref()
+                    .map(|prices| prices.price_activity(&trading_pair, since))
+                    .unwrap_or_default();
+                let last_book_at = self.books.fresh_book_at(&trading_pair);
+                let price_ready = activity.ticks >= self.config.min_ticks
+                    && activity.last_tick_at.map_or(false, |at| at >= since);
+                let book_ready = last_book_at.map_or(false, |at| at >= since);
+                PairReadiness {
+                    trading_pair,
+                    ready: price_ready && book_ready,
+                    price_ticks: activity.ticks,
+                    last_price_at: activity.last_tick_at,
+                    last_book_at,
+                }
+            })
+            .collect();
+
+        // Nothing traded yet means nothing has been shown to be fresh
+        let all_ready = !readiness.is_empty() && readiness.iter().all(|pair| pair.ready);
+        *self.pairs.lock() = readiness;
+
+        if all_ready && !self.is_ready() {
+            let elapsed = self.started_at.elapsed();
+            *self.warm_up.lock() = Some(elapsed);
+            histogram!(format!("{}.warm_up_ms", METRICS_PREFIX), elapsed.as_millis() as f64);
+            gauge!(format!("{}.ready", METRICS_PREFIX), 1.0);
+            info!(warm_up_ms = elapsed.as_millis() as u64, "Execution engine ready");
+            self.state.send_replace(EngineState::Ready);
+        }
+        self.state()
+    }
+
+    /// Evaluates readiness on the check interval until the engine is ready
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.check_interval);
+            loop {
+                interval.tick().await;
+                if self.evaluate(Utc::now()).await == EngineState::Ready {
+                    break;
+                }
+            }
+        })
+    }
+
+    async fn required_pairs(&self) -> BTreeSet<String> {
+        let strategies = self.strategies.read().clone();
+        let Some(strategies) = strategies else {
+            return BTreeSet::new();
+        };
+        let strategies = strategies.read().await;
+        strategies
+            .values()
+            .filter(|strategy| strategy.state == StrategyState::Active)
+            .flat_map(|strategy| strategy.trading_pairs.iter().cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_collector::quality::QualityConfig;
+    use crate::models::strategy::{StrategyParams, StrategyType};
+    use crate::execution_engine::{
+        execution_breaker, record_breaker_outcome, CircuitBreakerConfig, CIRCUIT_BREAKER_THRESHOLD,
+    };
+    use crate::utils::percent::{Bps, Percent};
+    use dashmap::DashMap;
+    use rust_decimal_macros::dec;
+
+    const PAIR: &str = "SOL/USDC";
+
+    /// Books published by a mock collector
+    #[derive(Default)]
+    struct MockBooks(DashMap<String, DateTime<Utc>>);
+
+    impl BookFeed for MockBooks {
+        fn fresh_book_at(&self, trading_pair: &str) -> Option<DateTime<Utc>> {
+            self.0.get(trading_pair).map(|at| *at)
+        }
+    }
+
+    fn strategies() -> Arc<RwLock<HashMap<String, Strategy>>> {
+        let mut strategy = Strategy::new(
+            StrategyType::Grid,
+            StrategyParams {
+                position_size_bps: Bps::new(1000),
+                grid_levels: Some(10),
+                stop_loss_pct: Percent::from_percent(dec!(-5)),
+                take_profit_pct: Percent::from_percent(dec!(1)),
+                max_slippage_bps: Bps::new(100),
+                exchanges: vec!["jupiter".to_string()],
+                risk_factor: dec!(0.5),
+                allowed_regimes: Vec::new(),
+                market_making: None,
+            },
+            vec![PAIR.to_string()],
+        )
+        .unwrap();
+        strategy.state = StrategyState::Active;
+        Arc::new(RwLock::new(HashMap::from([("grid-1".to_string(), strategy)])))
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_with_delayed_collectors() {
+        let strategies = strategies();
+        let books = Arc::new(MockBooks::default());
+        let prices = Arc::new(DataQualityMonitor::new(QualityConfig::default(), strategies.clone()));
+        let gate = Arc::new(ReadinessGate::new(
+            ReadinessConfig {
+                min_ticks: 3,
+                check_interval: Duration::from_millis(10),
+                ..ReadinessConfig::default()
+            },
+            books.clone(),
+        ));
+        gate.set_price_feed(prices.clone());
+        gate.set_strategies(strategies);
+        let breaker = execution_breaker(&CircuitBreakerConfig {
+            threshold: CIRCUIT_BREAKER_THRESHOLD,
+            cooldown: None,
+        });
+
+        // Collectors come up late: prices after 50ms, the first book after 200ms
+        let price_collector = {
+            let prices = prices.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                for _ in 0..3 {
+                    prices.record_price("jupiter", PAIR, dec!(100), Utc::now());
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            })
+        };
+        let book_collector = {
+            let books = books.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                books.0.insert(PAIR.to_string(), Utc::now());
+            })
+        };
+        let mut state = gate.subscribe();
+        gate.clone().spawn();
+
+        // Strategies firing during warm-up are rejected, well past the breaker threshold,
+        // without tripping it
+        assert_eq!(gate.state(), EngineState::WarmingUp);
+        for _ in 0..20 {
+            let rejected = gate.admit(PAIR).await;
+            assert!(matches!(rejected, Err(ExecutionError::WarmingUp(_))));
+            assert!(!record_breaker_outcome(&breaker, &rejected));
+        }
+        assert!(breaker.allow());
+        assert_eq!(breaker.status().consecutive_failures, 0);
+
+        price_collector.await.unwrap();
+        assert_eq!(gate.evaluate(Utc::now()).await, EngineState::WarmingUp);
+        let report = gate.report();
+        assert_eq!(report.pairs[0].price_ticks, 3);
+        assert!(!report.pairs[0].ready);
+
+        book_collector.await.unwrap();
+        tokio::time::timeout(Duration::from_secs(1), state.wait_for(|s| *s == EngineState::Ready))
+            .await
+            .expect("engine never became ready")
+            .unwrap();
+
+        let report = gate.report();
+        assert_eq!(report.state, EngineState::Ready);
+        assert!(report.warm_up_ms.unwrap() >= 200);
+        assert!(report.pairs.iter().all(|pair| pair.ready));
+        assert!(gate.admit(PAIR).await.is_ok());
+        assert!(!breaker.is_open());
+    }
+
+    #[tokio::test]
+    async fn test_queued_execution_waits_for_ready() {
+        let books = Arc::new(MockBooks::default());
+        let prices = Arc::new(DataQualityMonitor::new(QualityConfig::default(), strategies()));
+        let gate = Arc::new(ReadinessGate::new(
+            ReadinessConfig {
+                min_ticks: 1,
+                policy: WarmUpPolicy::Queue { max_wait: Duration::from_secs(1) },
+                ..ReadinessConfig::default()
+            },
+            books.clone(),
+        ));
+        gate.set_price_feed(prices.clone());
+        gate.set_strategies(strategies());
+
+        let queued = {
+            let gate = gate.clone();
+            tokio::spawn(async move { gate.admit(PAIR).await })
+        };
+        assert_eq!(gate.evaluate(Utc::now()).await, EngineState::WarmingUp);
+
+        prices.record_price("jupiter", PAIR, dec!(100), Utc::now());
+        books.0.insert(PAIR.to_string(), Utc::now());
+        assert_eq!(gate.evaluate(Utc::now()).await, EngineState::Ready);
+        assert!(queued.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_stale_data_and_no_strategies_keep_warming_up() {
+        let books = Arc::new(MockBooks::default());
+        let gate = ReadinessGate::new(ReadinessConfig::default(), books.clone());
+        let prices = Arc::new(DataQualityMonitor::new(QualityConfig::default(), strategies()));
+        gate.set_price_feed(prices.clone());
+
+        // No active strategy yet
+        assert_eq!(gate.evaluate(Utc::now()).await, EngineState::WarmingUp);
+        assert!(gate.report().pairs.is_empty());
+
+        // Data older than the configured age does not count
+        gate.set_strategies(strategies());
+        let old = Utc::now() - chrono::Duration::seconds(30);
+        for _ in 0..5 {
+            prices.record_price("jupiter", PAIR, dec!(100), old);
+        }
+        books.0.insert(PAIR.to_string(), old);
+        assert_eq!(gate.evaluate(Utc::now()).await, EngineState::WarmingUp);
+        let pair = &gate.report().pairs[0];
+        assert_eq!(pair.price_ticks, 0);
+        assert!(!pair.ready);
+    }
+}
@@ -0,0 +1,586 @@
+//! Crash recovery for in-flight orders. A `SubmissionIntent` is persisted before an order is
+//! submitted and gains the signature of every signed attempt before its bundle leaves, so a
+//! process that dies between submission and recording the result leaves a pending intent
+//! behind. On startup `OrphanRecovery` scans recent wallet transactions for the signatures
+//! of pending intents, parses the fill from the wallet's token balance changes, applies it
+//! to positions and closes the intent as recovered. Intents past the expiry cutoff with no
+//! landed transaction are closed as failed with an audit entry.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - async-trait = "0.1"
+//! - rust_decimal = "1.30"
+//! - solana-sdk = "1.17"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+use tracing::{info, instrument, warn};
+use uuid::Uuid;
+
+use crate::execution_engine::adapters::PairMints;
+use crate::execution_engine::fills::FillTracker;
+use crate::models::order::{Order, OrderFill, OrderType};
+use crate::models::strategy::StrategyAuditEntry;
+use crate::risk_manager::exposure::TradeSide;
+use crate::utils::solana::{SolanaClient, WalletTransaction};
+
+// Recovery constants
+const METRICS_PREFIX: &str = "trading_bot.execution.recovery";
+const DEFAULT_EXPIRE_AFTER: Duration = Duration::from_secs(15 * 60);
+const DEFAULT_HISTORY_LIMIT: usize = 500;
+const PRICE_SCALE: u32 = 8;
+pub const AUDIT_SUBMISSION_FAILED: &str = "submission_failed";
+
+/// Recovery error types
+#[derive(Error, Debug)]
+pub enum RecoveryError {
+    #[error("intent store error: {0}")]
+    Store(String),
+    #[error("wallet history error: {0}")]
+    History(String),
+    #[error("recovered fill rejected: {0}")]
+    Apply(String),
+}
+
+/// Where a submission intent stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntentStatus {
+    /// Submitted or about to be; the result has not been recorded
+    Pending,
+    /// The result was recorded by the normal execution path
+    Resolved,
+    /// The fill was found on-chain and applied after a restart
+    Recovered,
+    /// No transaction landed before the intent expired
+    Failed,
+}
+
+impl IntentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Resolved => "resolved",
+            Self::Recovered => "recovered",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for IntentStatus {
+    type Err = RecoveryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "resolved" => Ok(Self::Resolved),
+            "recovered" => Ok(Self::Recovered),
+            "failed" => Ok(Self::Failed),
+            other => Err(RecoveryError::Store(format!("unknown intent status: {}", other))),
+        }
+    }
+}
+
+/// Order about to be submitted, persisted so its fill can be found after a crash
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubmissionIntent {
+    /// Order id
+    pub id: Uuid,
+    pub strategy_id: String,
+    pub trading_pair: String,
+    pub exchange: String,
+    pub side: TradeSide,
+    pub size: Decimal,
+    pub price: Decimal,
+    /// Signatures of every signed attempt; any of them may have landed
+    pub signatures: Vec<String>,
+    pub bundle_id: Option<String>,
+    pub status: IntentStatus,
+    pub created_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+impl SubmissionIntent {
+    pub fn new(
+        strategy_id: &str,
+        trading_pair: &str,
+        exchange: &str,
+        side: TradeSide,
+        size: Decimal,
+        price: Decimal,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            strategy_id: strategy_id.to_string(),
+            trading_pair: trading_pair.to_string(),
+            exchange: exchange.to_string(),
+            side,
+            size,
+            price,
+            signatures: Vec::new(),
+            bundle_id: None,
+            status: IntentStatus::Pending,
+            created_at: Utc::now(),
+            closed_at: None,
+        }
+    }
+}
+
+/// How a pending intent was closed
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntentOutcome {
+    Recovered { signature: String, size: Decimal, price: Decimal },
+    Failed { reason: String },
+}
+
+impl IntentOutcome {
+    pub fn status(&self) -> IntentStatus {
+        match self {
+            Self::Recovered { .. } => IntentStatus::Recovered,
+            Self::Failed { .. } => IntentStatus::Failed,
+        }
+    }
+}
+
+/// Persists submission intents; every write must be durable before it returns
+#[async_trait]
+pub trait IntentStore: std::fmt::Debug + Send + Sync {
+    async fn open(&self, intent: &SubmissionIntent) -> Result<(), RecoveryError>;
+
+    async fn attach_signature(&self, id: Uuid, signature: &str) -> Result<(), RecoveryError>;
+
+    async fn attach_bundle(&self, id: Uuid, bundle_id: &str) -> Result<(), RecoveryError>;
+
+    /// Marks the pending intent holding `signature` resolved, returning whether one did
+    async fn resolve_signature(&self, signature: &str, at: DateTime<Utc>) -> Result<bool, RecoveryError>;
+
+    async fn close(&self, id: Uuid, outcome: &IntentOutcome, at: DateTime<Utc>) -> Result<(), RecoveryError>;
+
+    async fn pending(&self) -> Result<Vec<SubmissionIntent>, RecoveryError>;
+}
+
+/// Recent transactions of the trading wallet
+#[async_trait]
+pub trait WalletHistory: Send + Sync {
+    /// Successful transactions, newest first
+    async fn recent_transactions(&self, limit: usize) -> Result<Vec<WalletTransaction>, RecoveryError>;
+}
+
+/// Wallet history read from the chain through the Solana RPC client
+pub struct SolanaWalletHistory {
+    client: Arc<SolanaClient>,
+    owner: Pubkey,
+}
+
+impl std::fmt::Debug for SolanaWalletHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SolanaWalletHistory").field("owner", &self.owner).finish()
+    }
+}
+
+impl SolanaWalletHistory {
+    pub fn new(client: Arc<SolanaClient>, owner: Pubkey) -> Self {
+        Self { client, owner }
+    }
+}
+
+#[async_trait]
+impl WalletHistory for SolanaWalletHistory {
+    async fn recent_transactions(&self, limit: usize) -> Result<Vec<WalletTransaction>, RecoveryError> {
+        self.client
+            .get_wallet_transactions(&self.owner, limit)
+            .await
+            .map_err(|e| RecoveryError::History(e.to_string()))
+    }
+}
+
+/// Expiry and scan depth of orphan recovery
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecoveryConfig {
+    /// Age after which a pending intent with no landed transaction is closed as failed
+    pub expire_after: Duration,
+    /// Recent wallet transactions scanned for intent signatures
+    pub history_limit: usize,
+}
+
+impl Default for RecoveryConfig {
+    fn default() -> Self {
+        Self {
+            expire_after: DEFAULT_EXPIRE_AFTER,
+            history_limit: DEFAULT_HISTORY_LIMIT,
+        }
+    }
+}
+
+/// Fill found on-chain for an intent whose result was never recorded
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecoveredFill {
+    pub intent_id: Uuid,
+    pub strategy_id: String,
+    pub trading_pair: String,
+    pub side: TradeSide,
+    pub signature: String,
+    pub size: Decimal,
+    pub price: Decimal,
+}
+
+/// Outcome of one recovery pass
+#[derive(Debug, Clone, Default)]
+pub struct RecoveryReport {
+    pub recovered: Vec<RecoveredFill>,
+    pub failed: Vec<Uuid>,
+    /// Intents left pending because they may still land
+    pub pending: usize,
+    /// Audit entries for intents closed as failed, to record against their strategies
+    pub audit: Vec<StrategyAuditEntry>,
+}
+
+/// Matches pending intents against the wallet's landed transactions after a restart
+pub struct OrphanRecovery {
+    store: Arc<dyn IntentStore>,
+    history: Arc<dyn WalletHistory>,
+    config: RecoveryConfig,
+    /// Mints by pair, for parsing fills from token balance changes
+    mints: HashMap<String, PairMints>,
+}
+
+impl std::fmt::Debug for OrphanRecovery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OrphanRecovery")
+            .field("config", &self.config)
+            .field("pairs", &self.mints.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl OrphanRecovery {
+    pub fn new(store: Arc<dyn IntentStore>, history: Arc<dyn WalletHistory>, config: RecoveryConfig) -> Self {
+        Self {
+            store,
+            history,
+            config,
+            mints: HashMap::new(),
+        }
+    }
+
+    /// Registers a pair's mints so its fills are parsed from balance changes rather than
+    /// taken at the intended size and price
+    pub fn with_pair(mut self, trading_pair: impl Into<String>, mints: PairMints) -> Self {
+        self.mints.insert(trading_pair.into(), mints);
+        self
+    }
+
+    pub fn store(&self) -> Arc<dyn IntentStore> {
+        self.store.clone()
+    }
+
+    /// Applies the fills of pending intents that landed and closes those that expired
+    #[instrument(skip(self, fills))]
+    pub async fn recover(&self, fills: &FillTracker, now: DateTime<Utc>) -> Result<RecoveryReport, RecoveryError> {
+        let mut report = RecoveryReport::default();
+        let pending = self.store.pending().await?;
+        if pending.is_empty() {
+            return Ok(report);
+        }
+
+        let transactions: HashMap<String, WalletTransaction> = self
+            .history
+            .recent_transactions(self.config.history_limit)
+            .await?
+            .into_iter()
+            .map(|transaction| (transaction.signature.clone(), transaction))
+            .collect();
+        let expire_after = chrono::Duration::from_std(self.config.expire_after).unwrap_or_else(|_| chrono::Duration::zero());
+
+        for intent in pending {
+            let landed = intent
+                .signatures
+                .iter()
+                .find_map(|signature| transactions.get(signature));
+            if let Some(transaction) = landed {
+                let recovered = self.apply(fills, &intent, transaction).await?;
+                self.store
+                    .close(
+                        intent.id,
+                        &IntentOutcome::Recovered {
+                            signature: recovered.signature.clone(),
+                            size: recovered.size,
+                            price: recovered.price,
+                        },
+                        now,
+                    )
+                    .await?;
+                warn!(
+                    intent_id = %intent.id,
+                    signature = %recovered.signature,
+                    trading_pair = %intent.trading_pair,
+                    "Recovered fill of an order whose result was never recorded"
+                );
+                report.recovered.push(recovered);
+            } else if now - intent.created_at > expire_after {
+                let reason = format!(
+                    "no landed transaction for {} {} {} after {}s (signatures: {})",
+                    intent.side.signed(intent.size),
+                    intent.trading_pair,
+                    intent.exchange,
+                    expire_after.num_seconds(),
+                    intent.signatures.len()
+                );
+                self.store
+                    .close(intent.id, &IntentOutcome::Failed { reason: reason.clone() }, now)
+                    .await?;
+                report.audit.push(StrategyAuditEntry::new(
+                    &intent.strategy_id,
+                    AUDIT_SUBMISSION_FAILED,
+                    format!("order {}: {}", intent.id, reason),
+                ));
+                report.failed.push(intent.id);
+            } else {
+                report.pending += 1;
+            }
+        }
+
+        counter!(format!("{}.recovered", METRICS_PREFIX), report.recovered.len() as u64);
+        counter!(format!("{}.failed", METRICS_PREFIX), report.failed.len() as u64);
+        info!(
+            recovered = report.recovered.len(),
+            failed = report.failed.len(),
+            pending = report.pending,
+            "Orphaned order recovery complete"
+        );
+        Ok(report)
+    }
+
+    /// Nets the landed fill into positions under the intent's order id
+    async fn apply(
+        &self,
+        fills: &FillTracker,
+        intent: &SubmissionIntent,
+        transaction: &WalletTransaction,
+    ) -> Result<RecoveredFill, RecoveryError> {
+        let (size, price) = self
+            .parse_fill(intent, transaction)
+            .unwrap_or((intent.size, intent.price));
+
+        let mut order = Order::new(
+            intent.trading_pair.clone(),
+            intent.exchange.clone(),
+            OrderType::Market,
+            price,
+            size,
+        )
+        .map_err(|e| RecoveryError::Apply(e.to_string()))?;
+        order.id = intent.id;
+        fills.track(order, &intent.strategy_id, intent.side).await;
+        fills
+            .apply_fill(intent.id, OrderFill::new(transaction.signature.clone(), size, price))
+            .await
+            .map_err(|e| RecoveryError::Apply(e.to_string()))?;
+
+        Ok(RecoveredFill {
+            intent_id: intent.id,
+            strategy_id: intent.strategy_id.clone(),
+            trading_pair: intent.trading_pair.clone(),
+            side: intent.side,
+            signature: transaction.signature.clone(),
+            size,
+            price,
+        })
+    }
+
+    /// Size and price from the wallet's base and quote balance changes, when the pair's
+    /// mints are known and the base moved in the intended direction
+    fn parse_fill(&self, intent: &SubmissionIntent, transaction: &WalletTransaction) -> Option<(Decimal, Decimal)> {
+        let mints = self.mints.get(&intent.trading_pair)?;
+        let base = *transaction.token_deltas.get(&mints.base_mint.to_string())?;
+        let quote = *transaction.token_deltas.get(&mints.quote_mint.to_string())?;
+        if base.is_zero() || intent.side.signed(base.abs()) != base {
+            return None;
+        }
+        Some((base.abs(), (quote.abs() / base.abs()).round_dp(PRICE_SCALE)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::portfolio::Portfolio;
+    use parking_lot::Mutex;
+    use rust_decimal_macros::dec;
+
+    const PAIR: &str = "SOL/USDC";
+
+    #[derive(Debug, Default)]
+    struct MemoryStore {
+        intents: Mutex<HashMap<Uuid, (SubmissionIntent, Option<IntentOutcome>)>>,
+    }
+
+    impl MemoryStore {
+        fn get(&self, id: Uuid) -> (SubmissionIntent, Option<IntentOutcome>) {
+            self.intents.lock().get(&id).cloned().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl IntentStore for MemoryStore {
+        async fn open(&self, intent: &SubmissionIntent) -> Result<(), RecoveryError> {
+            self.intents.lock().insert(intent.id, (intent.clone(), None));
+            Ok(())
+        }
+
+        async fn attach_signature(&self, id: Uuid, signature: &str) -> Result<(), RecoveryError> {
+            let mut intents = self.intents.lock();
+            let (intent, _) = intents.get_mut(&id).ok_or_else(|| RecoveryError::Store("unknown intent".to_string()))?;
+            intent.signatures.push(signature.to_string());
+            Ok(())
+        }
+
+        async fn attach_bundle(&self, id: Uuid, bundle_id: &str) -> Result<(), RecoveryError> {
+            let mut intents = self.intents.lock();
+            let (intent, _) = intents.get_mut(&id).ok_or_else(|| RecoveryError::Store("unknown intent".to_string()))?;
+            intent.bundle_id = Some(bundle_id.to_string());
+            Ok(())
+        }
+
+        async fn resolve_signature(&self, signature: &str, at: DateTime<Utc>) -> Result<bool, RecoveryError> {
+            let mut intents = self.intents.lock();
+            let resolved = intents.values_mut().find(|(intent, _)| {
+                intent.status == IntentStatus::Pending && intent.signatures.iter().any(|s| s == signature)
+            });
+            Ok(resolved
+                .map(|(intent, _)| {
+                    intent.status = IntentStatus::Resolved;
+                    intent.closed_at = Some(at);
+                })
+                .is_some())
+        }
+
+        async fn close(&self, id: Uuid, outcome: &IntentOutcome, at: DateTime<Utc>) -> Result<(), RecoveryError> {
+            let mut intents = self.intents.lock();
+            let entry = intents.get_mut(&id).ok_or_else(|| RecoveryError::Store("unknown intent".to_string()))?;
+            entry.0.status = outcome.status();
+            entry.0.closed_at = Some(at);
+            entry.1 = Some(outcome.clone());
+            Ok(())
+        }
+
+        async fn pending(&self) -> Result<Vec<SubmissionIntent>, RecoveryError> {
+            Ok(self
+                .intents
+                .lock()
+                .values()
+                .filter(|(intent, _)| intent.status == IntentStatus::Pending)
+                .map(|(intent, _)| intent.clone())
+                .collect())
+        }
+    }
+
+    struct MockHistory(Vec<WalletTransaction>);
+
+    #[async_trait]
+    impl WalletHistory for MockHistory {
+        async fn recent_transactions(&self, limit: usize) -> Result<Vec<WalletTransaction>, RecoveryError> {
+            Ok(self.0.iter().take(limit).cloned().collect())
+        }
+    }
+
+    fn sol_usdc() -> PairMints {
+        PairMints {
+            base_mint: Pubkey::new_unique(),
+            base_decimals: 9,
+            quote_mint: Pubkey::new_unique(),
+            quote_decimals: 6,
+        }
+    }
+
+    async fn submitted(store: &MemoryStore, side: TradeSide, signature: &str) -> SubmissionIntent {
+        let intent = SubmissionIntent::new("grid-1", PAIR, "jupiter", side, dec!(10), dec!(100));
+        store.open(&intent).await.unwrap();
+        store.attach_signature(intent.id, signature).await.unwrap();
+        store.attach_bundle(intent.id, "bundle-1").await.unwrap();
+        intent
+    }
+
+    #[tokio::test]
+    async fn test_orphaned_fill_recovered_into_position() {
+        let store = Arc::new(MemoryStore::default());
+        let mints = sol_usdc();
+        let portfolio = Portfolio::new("test_wallet".to_string(), dec!(100000)).unwrap();
+
+        // The bundle lands but the process dies before the result is recorded
+        let intent = submitted(&store, TradeSide::Buy, "sig-landed").await;
+        assert!(portfolio.get_position(PAIR).await.is_none());
+
+        let history = MockHistory(vec![
+            WalletTransaction {
+                signature: "sig-unrelated".to_string(),
+                ..WalletTransaction::default()
+            },
+            WalletTransaction {
+                signature: "sig-landed".to_string(),
+                block_time: None,
+                token_deltas: HashMap::from([
+                    (mints.base_mint.to_string(), dec!(9.5)),
+                    (mints.quote_mint.to_string(), dec!(-959.5)),
+                ]),
+            },
+        ]);
+        let recovery = OrphanRecovery::new(store.clone(), Arc::new(history), RecoveryConfig::default())
+            .with_pair(PAIR, mints);
+
+        // On restart the fill is parsed from the wallet's balance changes and applied
+        let fills = FillTracker::new(portfolio.clone());
+        let report = recovery.recover(&fills, Utc::now()).await.unwrap();
+        assert_eq!(report.recovered.len(), 1);
+        assert_eq!(report.recovered[0].size, dec!(9.5));
+        assert_eq!(report.recovered[0].price, dec!(101));
+
+        let position = portfolio.get_position(PAIR).await.unwrap();
+        assert_eq!(position.size, dec!(9.5));
+        assert_eq!(position.entry_price, dec!(101));
+        assert_eq!(fills.strategy_positions("grid-1").await, vec![(PAIR.to_string(), dec!(9.5))]);
+
+        let (closed, outcome) = store.get(intent.id);
+        assert_eq!(closed.status, IntentStatus::Recovered);
+        assert!(matches!(outcome, Some(IntentOutcome::Recovered { signature, .. }) if signature == "sig-landed"));
+
+        // A second pass finds nothing left to recover
+        let report = recovery.recover(&fills, Utc::now()).await.unwrap();
+        assert!(report.recovered.is_empty());
+        assert_eq!(portfolio.get_position(PAIR).await.unwrap().size, dec!(9.5));
+    }
+
+    #[tokio::test]
+    async fn test_unmatched_intents_expire_as_failed() {
+        let store = Arc::new(MemoryStore::default());
+        let stale = submitted(&store, TradeSide::Sell, "sig-dropped").await;
+        let fresh = submitted(&store, TradeSide::Sell, "sig-in-flight").await;
+        let recorded = submitted(&store, TradeSide::Buy, "sig-recorded").await;
+        assert!(store.resolve_signature("sig-recorded", Utc::now()).await.unwrap());
+
+        let recovery = OrphanRecovery::new(store.clone(), Arc::new(MockHistory(Vec::new())), RecoveryConfig::default());
+        let fills = FillTracker::new(Portfolio::new("test_wallet".to_string(), dec!(100000)).unwrap());
+        let now = stale.created_at + chrono::Duration::minutes(20);
+        store.intents.lock().get_mut(&fresh.id).unwrap().0.created_at = now - chrono::Duration::minutes(1);
+
+        let report = recovery.recover(&fills, now).await.unwrap();
+        assert_eq!(report.failed, vec![stale.id]);
+        assert_eq!(report.pending, 1);
+        assert_eq!(report.audit.len(), 1);
+        assert_eq!(report.audit[0].strategy_id, "grid-1");
+        assert_eq!(report.audit[0].action, AUDIT_SUBMISSION_FAILED);
+
+        assert_eq!(store.get(stale.id).0.status, IntentStatus::Failed);
+        assert_eq!(store.get(fresh.id).0.status, IntentStatus::Pending);
+        assert_eq!(store.get(recorded.id).0.status, IntentStatus::Resolved);
+    }
+}
@@ -0,0 +1,256 @@
+//! What-if trade simulation that runs an order through pair validation, routing, impact
+//! estimation, risk checks and fee estimation without executing it or touching the portfolio.
+//!
+//! Version dependencies:
+//! - rust_decimal = "1.30"
+//! - serde = "1.0"
+//! - tokio = "1.28"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::instrument;
+
+use crate::execution_engine::cost_model::ImpactModel;
+use crate::execution_engine::order_book::ExecutionPlan;
+use crate::execution_engine::queue::PriorityClass;
+use crate::execution_engine::passive::ExecutionStyle;
+use crate::execution_engine::adapters::DEFAULT_MAX_SLIPPAGE;
+use crate::execution_engine::{ExecutionEngine, StrategyParams};
+use crate::models::order::OrderType;
+use crate::models::trade::calculate_fee;
+use crate::risk_manager::exposure::TradeSide;
+use crate::risk_manager::validation::{TradeRequest, ValidationResult};
+use crate::risk_manager::{RiskError, RiskManager};
+use crate::utils::percent::Bps;
+
+// Simulation constants
+const BPS_PER_PERCENT: Decimal = Decimal::ONE_HUNDRED;
+
+/// Order submitted for simulation
+#[derive(Debug, Clone, Deserialize)]
+pub struct SimulationRequest {
+    pub strategy_id: String,
+    pub trading_pair: String,
+    pub exchange: String,
+    pub side: TradeSide,
+    pub order_type: OrderType,
+    pub size: Decimal,
+    pub price: Decimal,
+    #[serde(default)]
+    pub execution_style: ExecutionStyle,
+    #[serde(default = "default_max_slippage")]
+    pub max_slippage: Bps,
+}
+
+fn default_max_slippage() -> Bps {
+    DEFAULT_MAX_SLIPPAGE
+}
+
+impl SimulationRequest {
+    /// Builds the parameters live execution would receive for this order
+    pub fn strategy_params(&self) -> StrategyParams {
+        StrategyParams {
+            strategy_id: self.strategy_id.clone(),
+            priority: PriorityClass::Normal,
+            trading_pair: self.trading_pair.clone(),
+            exchange: self.exchange.clone(),
+            side: self.side,
+            order_type: self.order_type.clone(),
+            size: self.size,
+            price: self.price,
+            execution_style: self.execution_style,
+            max_slippage: self.max_slippage,
+            tick: None,
+            size_multiplier: None,
+        }
+    }
+}
+
+/// One leg of the route the order would take
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimulatedRouteStep {
+    pub dex: String,
+    pub amount: Decimal,
+    pub price: Decimal,
+}
+
+/// Outcome of a simulated trade
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TradeSimulation {
+    pub would_execute: bool,
+    pub rejection_reasons: Vec<String>,
+    pub estimated_fill_price: Option<Decimal>,
+    pub impact_bps: Option<Decimal>,
+    /// Calibrated cost model or book walk behind `impact_bps`
+    pub impact_model: Option<ImpactModel>,
+    /// DEX fees across all route steps, in the quote currency
+    pub estimated_fees: Option<Decimal>,
+    pub priority_fee_lamports: Option<u64>,
+    pub route: Vec<SimulatedRouteStep>,
+    /// Degraded-mode multiplier risk validation would apply to the size
+    pub size_multiplier: Option<Decimal>,
+    /// Size risk validation would clear for execution
+    pub approved_size: Option<Decimal>,
+}
+
+impl TradeSimulation {
+    fn reject(&mut self, reason: String) {
+        self.rejection_reasons.push(reason);
+    }
+
+    fn apply_plan(&mut self, plan: &ExecutionPlan) {
+        self.estimated_fill_price = Some(plan.estimated_price);
+        self.impact_bps = Some(plan.route.total_price_impact);
+        self.impact_model = Some(plan.impact_model.clone());
+        self.route = plan
+            .route
+            .steps
+            .iter()
+            .map(|step| SimulatedRouteStep {
+                dex: step.dex.clone(),
+                amount: step.amount,
+                price: step.price,
+            })
+            .collect();
+    }
+
+    fn apply_risk(&mut self, validation: Result<ValidationResult, RiskError>) {
+        match validation {
+            Ok(result) if result.is_valid => {
+                self.size_multiplier = Some(result.size_multiplier);
+                self.approved_size = result.approved_size;
+            }
+            Ok(result) => self.reject(
+                result
+                    .failure_reason
+                    .unwrap_or_else(|| "risk validation failed".to_string()),
+            ),
+            Err(e) => self.reject(e.to_string()),
+        }
+    }
+
+    fn finish(mut self) -> Self {
+        self.would_execute = self.rejection_reasons.is_empty();
+        self
+    }
+}
+
+/// Runs orders through the live execution and risk paths up to, but not including, submission
+#[derive(Debug)]
+pub struct TradeSimulator {
+    engine: Arc<ExecutionEngine>,
+    risk_manager: Arc<RwLock<RiskManager>>,
+}
+
+impl TradeSimulator {
+    pub fn new(engine: Arc<ExecutionEngine>, risk_manager: Arc<RwLock<RiskManager>>) -> Self {
+        Self {
+            engine,
+            risk_manager,
+        }
+    }
+
+    /// Simulates an order for a wallet; rejections are reported rather than returned as errors
+    #[instrument(skip(self, request))]
+    pub async fn simulate(&self, request: &SimulationRequest, wallet_address: &str) -> TradeSimulation {
+        let mut simulation = TradeSimulation::default();
+
+        // Pair validation, routing and impact come from the same plan live execution uses
+        let plan = match self.engine.plan_execution(&request.strategy_params()).await {
+            Ok(plan) => plan,
+            Err(e) => {
+                simulation.reject(e.to_string());
+                return simulation.finish();
+            }
+        };
+        simulation.apply_plan(&plan);
+
+        // Risk is checked at the estimated fill price rather than the requested price
+        let trade_request = TradeRequest {
+            strategy_id: request.strategy_id.clone(),
+            wallet_address: wallet_address.to_string(),
+            trading_pair: request.trading_pair.clone(),
+            exchange: request.exchange.clone(),
+            side: request.side,
+            order_type: request.order_type.clone(),
+            size: request.size,
+            price: plan.estimated_price,
+            market_prices: HashMap::from([(request.trading_pair.clone(), plan.estimated_price)]),
+            cross_dex_prices: HashMap::new(),
+            market_impact: HashMap::from([(
+                request.trading_pair.clone(),
+                plan.route.total_price_impact / BPS_PER_PERCENT,
+            )]),
+        };
+        let validation = self
+            .risk_manager
+            .read()
+            .await
+            .simulate_operation(trade_request)
+            .await;
+        simulation.apply_risk(validation);
+
+        // Fees are charged per route step at that step's DEX rate
+        let fees = plan
+            .route
+            .steps
+            .iter()
+            .map(|step| calculate_fee(&step.dex, step.amount, step.price))
+            .sum::<Result<Decimal, _>>();
+        match fees {
+            Ok(fees) => simulation.estimated_fees = Some(fees),
+            Err(e) => simulation.reject(e.to_string()),
+        }
+        simulation.priority_fee_lamports = Some(self.engine.estimate_priority_fee(&request.exchange));
+
+        simulation.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_engine::order_book::{ExecutionRoute, ExecutionStep};
+    use crate::risk_manager::validation::{ValidationSeverity, ValidationType};
+    use rust_decimal_macros::dec;
+    use std::time::Duration;
+
+    #[test]
+    fn test_simulation_collects_rejections() {
+        let plan = ExecutionPlan {
+            route: ExecutionRoute {
+                steps: vec![ExecutionStep {
+                    dex: "jupiter".to_string(),
+                    amount: dec!(10),
+                    price: dec!(101),
+                }],
+                total_price_impact: dec!(100),
+                estimated_execution_time: Duration::from_millis(500),
+            },
+            estimated_price: dec!(101),
+            timestamp: chrono::Utc::now(),
+            book_health: crate::execution_engine::book_sync::BookHealth::Healthy,
+            impact_model: ImpactModel::Static,
+        };
+
+        let mut simulation = TradeSimulation::default();
+        simulation.apply_plan(&plan);
+        let mut rejected = ValidationResult::new(true, ValidationSeverity::Info, ValidationType::Trade);
+        rejected.set_failure("trade value $1010 exceeds maximum $1000".to_string(), ValidationSeverity::Critical);
+        simulation.apply_risk(Ok(rejected));
+        let simulation = simulation.finish();
+
+        assert!(!simulation.would_execute);
+        assert_eq!(simulation.estimated_fill_price, Some(dec!(101)));
+        assert_eq!(simulation.impact_bps, Some(dec!(100)));
+        assert_eq!(simulation.impact_model, Some(ImpactModel::Static));
+        assert_eq!(simulation.route[0].dex, "jupiter");
+        assert_eq!(simulation.rejection_reasons, vec!["trade value $1010 exceeds maximum $1000"]);
+
+        assert!(TradeSimulation::default().finish().would_execute);
+    }
+}
@@ -0,0 +1,630 @@
+//! Realized execution statistics per (trading pair, exchange). Every execution attempt is
+//! recorded; a periodic materialization aggregates them over 1d/7d/30d windows into a
+//! summary table served by the analytics API and used as a routing prior when books are
+//! near-identical. Filled attempts are benchmarked against market VWAP/TWAP after the
+//! fact and the average deviations are reported alongside the other statistics.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+//! - utoipa = "3.5"
+
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::{counter, histogram};
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::execution_engine::benchmarks::{BenchmarkService, ExecutionBenchmark};
+use crate::execution_engine::compute_budget::ComputeUsage;
+use crate::persistence::PersistenceQueue;
+use crate::risk_manager::exposure::TradeSide;
+use crate::utils::percent::Bps;
+
+// Execution statistics constants
+const METRICS_PREFIX: &str = "trading_bot.execution_stats";
+const BPS_PER_UNIT: Decimal = Decimal::new(10_000, 0);
+const P95_PERCENT: u64 = 95;
+const DEFAULT_MATERIALIZE_INTERVAL: Duration = Duration::from_secs(300);
+const DEFAULT_MIN_SAMPLES: u64 = 20;
+const DEFAULT_TIE_BPS: Bps = Bps::new(2);
+const DEFAULT_UNFILLED_PENALTY_BPS: Bps = Bps::new(100);
+
+/// Execution statistics errors
+#[derive(Error, Debug)]
+pub enum StatsError {
+    #[error("unknown stats window: {0}")]
+    UnknownWindow(String),
+    #[error("store error: {0}")]
+    Store(String),
+}
+
+/// Lookback a statistic is aggregated over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+pub enum StatsWindow {
+    #[serde(rename = "1d")]
+    Day,
+    #[serde(rename = "7d")]
+    Week,
+    #[serde(rename = "30d")]
+    Month,
+}
+
+impl StatsWindow {
+    pub const ALL: [StatsWindow; 3] = [StatsWindow::Day, StatsWindow::Week, StatsWindow::Month];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Day => "1d",
+            Self::Week => "7d",
+            Self::Month => "30d",
+        }
+    }
+
+    pub fn duration(&self) -> chrono::Duration {
+        match self {
+            Self::Day => chrono::Duration::days(1),
+            Self::Week => chrono::Duration::days(7),
+            Self::Month => chrono::Duration::days(30),
+        }
+    }
+}
+
+impl FromStr for StatsWindow {
+    type Err = StatsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1d" => Ok(Self::Day),
+            "7d" => Ok(Self::Week),
+            "30d" => Ok(Self::Month),
+            other => Err(StatsError::UnknownWindow(other.to_string())),
+        }
+    }
+}
+
+/// Outcome of one execution attempt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionRecord {
+    pub id: Uuid,
+    pub trading_pair: String,
+    pub exchange: String,
+    pub side: TradeSide,
+    pub requested_size: Decimal,
+    /// Zero when the attempt failed
+    pub filled_size: Decimal,
+    pub expected_price: Decimal,
+    pub executed_price: Option<Decimal>,
+    pub fee: Decimal,
+    pub latency_ms: u64,
+    pub mev_value: Decimal,
+    pub executed_at: DateTime<Utc>,
+    /// Market VWAP/TWAP benchmark, once computed post-trade
+    #[serde(default)]
+    pub benchmark: Option<ExecutionBenchmark>,
+    /// Visible depth on the side the order executed against, when it was planned
+    #[serde(default)]
+    pub book_depth: Option<Decimal>,
+    /// Per-candle volatility of the pair when the order was planned
+    #[serde(default)]
+    pub volatility_bps: Option<Decimal>,
+    /// Compute unit limit and units consumed, for budgeted transactions
+    #[serde(default)]
+    pub compute: Option<ComputeUsage>,
+    /// Economic event whose guard window the execution fell in
+    #[serde(default)]
+    pub event_id: Option<Uuid>,
+}
+
+impl ExecutionRecord {
+    pub fn filled(&self) -> bool {
+        self.filled_size > Decimal::ZERO && self.executed_price.is_some()
+    }
+
+    /// Adverse distance of the executed price from the expected price, in basis points
+    pub fn slippage_bps(&self) -> Option<Decimal> {
+        let executed = self.executed_price?;
+        if self.expected_price.is_zero() {
+            return None;
+        }
+        let adverse = match self.side {
+            TradeSide::Buy => executed - self.expected_price,
+            TradeSide::Sell => self.expected_price - executed,
+        };
+        Some(adverse / self.expected_price * BPS_PER_UNIT)
+    }
+
+    fn notional(&self) -> Decimal {
+        self.executed_price.map(|price| price * self.filled_size).unwrap_or_default()
+    }
+}
+
+/// Aggregated execution quality of one venue for one pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionStats {
+    pub trading_pair: String,
+    pub exchange: String,
+    pub window: StatsWindow,
+    pub sample_count: u64,
+    pub fill_rate: Decimal,
+    pub avg_slippage_bps: Decimal,
+    pub p95_slippage_bps: Decimal,
+    /// Average over filled attempts
+    pub avg_latency_ms: Decimal,
+    /// MEV value captured relative to filled notional, in basis points
+    pub mev_capture_bps: Decimal,
+    pub fee_bps: Decimal,
+    /// Filled attempts with a benchmark of sufficient coverage
+    pub benchmarked_count: u64,
+    pub avg_vwap_deviation_bps: Option<Decimal>,
+    pub avg_twap_deviation_bps: Option<Decimal>,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl ExecutionStats {
+    /// Expected all-in cost of routing to this venue, penalising unfilled attempts
+    pub fn realized_cost_bps(&self, unfilled_penalty_bps: Bps) -> Decimal {
+        self.avg_slippage_bps + self.fee_bps - self.mev_capture_bps
+            + (Decimal::ONE - self.fill_rate) * unfilled_penalty_bps.as_bps()
+    }
+}
+
+/// Aggregates records executed within `window` of `now` per (pair, exchange)
+pub fn aggregate(records: &[ExecutionRecord], window: StatsWindow, now: DateTime<Utc>) -> Vec<ExecutionStats> {
+    let cutoff = now - window.duration();
+    let mut groups: BTreeMap<(&str, &str), Vec<&ExecutionRecord>> = BTreeMap::new();
+    for record in records.iter().filter(|record| record.executed_at >= cutoff && record.executed_at <= now) {
+        groups
+            .entry((record.trading_pair.as_str(), record.exchange.as_str()))
+            .or_default()
+            .push(record);
+    }
+
+    groups
+        .into_iter()
+        .map(|((trading_pair, exchange), records)| {
+            let filled: Vec<&ExecutionRecord> = records.iter().copied().filter(|record| record.filled()).collect();
+            let mut slippage: Vec<Decimal> = filled.iter().filter_map(|record| record.slippage_bps()).collect();
+            slippage.sort();
+            let notional: Decimal = filled.iter().map(|record| record.notional()).sum();
+            let benchmarks: Vec<&ExecutionBenchmark> = filled
+                .iter()
+                .filter_map(|record| record.benchmark.as_ref())
+                .filter(|benchmark| !benchmark.low_coverage)
+                .collect();
+            let vwap_deviations: Vec<Decimal> =
+                benchmarks.iter().filter_map(|benchmark| benchmark.vwap_deviation_bps).collect();
+            let twap_deviations: Vec<Decimal> =
+                benchmarks.iter().filter_map(|benchmark| benchmark.twap_deviation_bps).collect();
+
+            ExecutionStats {
+                trading_pair: trading_pair.to_string(),
+                exchange: exchange.to_string(),
+                window,
+                sample_count: records.len() as u64,
+                fill_rate: ratio(filled.len(), records.len()).round_dp(4),
+                avg_slippage_bps: mean(&slippage).round_dp(2),
+                p95_slippage_bps: percentile(&slippage, P95_PERCENT).round_dp(2),
+                avg_latency_ms: mean(
+                    &filled.iter().map(|record| Decimal::from(record.latency_ms)).collect::<Vec<_>>(),
+                )
+                .round_dp(2),
+                mev_capture_bps: bps_of(filled.iter().map(|record| record.mev_value).sum(), notional),
+                fee_bps: bps_of(filled.iter().map(|record| record.fee).sum(), notional),
+                benchmarked_count: benchmarks.len() as u64,
+                avg_vwap_deviation_bps: (!vwap_deviations.is_empty()).then(|| mean(&vwap_deviations).round_dp(2)),
+                avg_twap_deviation_bps: (!twap_deviations.is_empty()).then(|| mean(&twap_deviations).round_dp(2)),
+                computed_at: now,
+            }
+        })
+        .collect()
+}
+
+fn ratio(part: usize, whole: usize) -> Decimal {
+    if whole == 0 {
+        return Decimal::ZERO;
+    }
+    Decimal::from(part as u64) / Decimal::from(whole as u64)
+}
+
+fn mean(values: &[Decimal]) -> Decimal {
+    if values.is_empty() {
+        return Decimal::ZERO;
+    }
+    values.iter().sum::<Decimal>() / Decimal::from(values.len() as u64)
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[Decimal], percent: u64) -> Decimal {
+    if sorted.is_empty() {
+        return Decimal::ZERO;
+    }
+    let rank = ((percent as usize * sorted.len() + 99) / 100).max(1);
+    sorted[rank - 1]
+}
+
+fn bps_of(amount: Decimal, notional: Decimal) -> Decimal {
+    if notional.is_zero() {
+        return Decimal::ZERO;
+    }
+    (amount / notional * BPS_PER_UNIT).round_dp(2)
+}
+
+/// Realized venue costs consulted when routing between near-identical books
+#[derive(Debug, Clone, Default)]
+pub struct RoutePriors {
+    costs: HashMap<String, Decimal>,
+    tie_bps: Bps,
+}
+
+impl RoutePriors {
+    /// Keeps venues with at least `min_samples` attempts; fill prices within `tie_bps` of
+    /// each other are treated as identical
+    pub fn new(stats: &[ExecutionStats], min_samples: u64, tie_bps: Bps, unfilled_penalty_bps: Bps) -> Self {
+        Self {
+            costs: stats
+                .iter()
+                .filter(|stats| stats.sample_count >= min_samples)
+                .map(|stats| (stats.exchange.clone(), stats.realized_cost_bps(unfilled_penalty_bps)))
+                .collect(),
+            tie_bps,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.costs.is_empty()
+    }
+
+    pub fn cost_bps(&self, exchange: &str) -> Option<Decimal> {
+        self.costs.get(exchange).copied()
+    }
+
+    /// Whether two estimated fill prices are close enough for realized outcomes to decide
+    pub fn near_identical(&self, price: Decimal, best: Decimal) -> bool {
+        !best.is_zero() && Bps::from_fraction((price - best).abs() / best) <= self.tie_bps
+    }
+}
+
+/// Persistence for execution records and their materialized statistics
+#[async_trait]
+pub trait ExecutionStatsStore: Send + Sync {
+    async fn record(&self, record: &ExecutionRecord) -> Result<(), StatsError>;
+    async fn records_since(&self, cutoff: DateTime<Utc>) -> Result<Vec<ExecutionRecord>, StatsError>;
+    /// Replaces the whole summary table
+    async fn replace_stats(&self, stats: &[ExecutionStats]) -> Result<(), StatsError>;
+    async fn load_stats(&self, trading_pair: &str, window: StatsWindow) -> Result<Vec<ExecutionStats>, StatsError>;
+    /// Stores a post-trade benchmark on the execution record
+    async fn record_benchmark(&self, execution_id: Uuid, benchmark: &ExecutionBenchmark) -> Result<(), StatsError>;
+}
+
+/// Materialization schedule and routing prior thresholds
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionStatsConfig {
+    pub materialize_interval: Duration,
+    /// Window whose statistics feed routing
+    pub prior_window: StatsWindow,
+    /// Venues with fewer attempts than this carry no prior
+    pub min_samples: u64,
+    pub tie_bps: Bps,
+    pub unfilled_penalty_bps: Bps,
+}
+
+impl Default for ExecutionStatsConfig {
+    fn default() -> Self {
+        Self {
+            materialize_interval: DEFAULT_MATERIALIZE_INTERVAL,
+            prior_window: StatsWindow::Week,
+            min_samples: DEFAULT_MIN_SAMPLES,
+            tie_bps: DEFAULT_TIE_BPS,
+            unfilled_penalty_bps: DEFAULT_UNFILLED_PENALTY_BPS,
+        }
+    }
+}
+
+/// Records execution outcomes and periodically materializes per-venue statistics
+pub struct ExecutionStatsService {
+    config: ExecutionStatsConfig,
+    store: Arc<dyn ExecutionStatsStore>,
+    /// Latest materialized prior-window statistics, by trading pair
+    priors: RwLock<HashMap<String, Vec<ExecutionStats>>>,
+    benchmarks: Option<Arc<BenchmarkService>>,
+    persistence: Option<Arc<PersistenceQueue>>,
+}
+
+impl std::fmt::Debug for ExecutionStatsService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExecutionStatsService")
+            .field("config", &self.config)
+            .field("benchmarks", &self.benchmarks)
+            .finish()
+    }
+}
+
+impl ExecutionStatsService {
+    pub fn new(config: ExecutionStatsConfig, store: Arc<dyn ExecutionStatsStore>) -> Self {
+        Self {
+            config,
+            store,
+            priors: RwLock::new(HashMap::new()),
+            benchmarks: None,
+            persistence: None,
+        }
+    }
+
+    /// Benchmarks filled executions against market VWAP/TWAP after they are recorded
+    pub fn with_benchmarks(mut self, benchmarks: Arc<BenchmarkService>) -> Self {
+        self.benchmarks = Some(benchmarks);
+        self
+    }
+
+    /// Defers execution records to the write-behind queue instead of awaiting the store
+    pub fn with_persistence(mut self, persistence: Arc<PersistenceQueue>) -> Self {
+        self.persistence = Some(persistence);
+        self
+    }
+
+    /// Persists an execution outcome without failing the trading path
+    pub async fn record(&self, record: ExecutionRecord) {
+        if let Some(persistence) = &self.persistence {
+            let (store, benchmarks) = (self.store.clone(), self.benchmarks.clone());
+            persistence.enqueue("execution_record", move || {
+                let (store, benchmarks, record) = (store.clone(), benchmarks.clone(), record.clone());
+                async move {
+                    store.record(&record).await.map_err(|e| e.to_string())?;
+                    if let Some(benchmarks) = benchmarks {
+                        benchmarks.schedule(record);
+                    }
+                    Ok(())
+                }
+            });
+            return;
+        }
+        if let Err(e) = self.store.record(&record).await {
+            warn!(exchange = %record.exchange, "Failed to record execution: {}", e);
+            return;
+        }
+        if let Some(benchmarks) = &self.benchmarks {
+            benchmarks.clone().schedule(record);
+        }
+    }
+
+    /// Aggregates every window from the raw records and replaces the summary table
+    pub async fn materialize(&self, now: DateTime<Utc>) -> Result<usize, StatsError> {
+        let start = std::time::Instant::now();
+        let records = self.store.records_since(now - StatsWindow::Month.duration()).await?;
+
+        let stats: Vec<ExecutionStats> = StatsWindow::ALL
+            .iter()
+            .flat_map(|window| aggregate(&records, *window, now))
+            .collect();
+        self.store.replace_stats(&stats).await?;
+
+        let mut priors: HashMap<String, Vec<ExecutionStats>> = HashMap::new();
+        for stats in stats.iter().filter(|stats| stats.window == self.config.prior_window) {
+            priors.entry(stats.trading_pair.clone()).or_default().push(stats.clone());
+        }
+        *self.priors.write() = priors;
+
+        counter!(format!("{}.materialized", METRICS_PREFIX), 1);
+        histogram!(format!("{}.materialize_ms", METRICS_PREFIX), start.elapsed().as_millis() as f64);
+        Ok(stats.len())
+    }
+
+    /// Materialized statistics for a pair, one row per venue
+    pub async fn stats(&self, trading_pair: &str, window: StatsWindow) -> Result<Vec<ExecutionStats>, StatsError> {
+        self.store.load_stats(trading_pair, window).await
+    }
+
+    /// Routing priors for a pair from the last materialization
+    pub fn priors(&self, trading_pair: &str) -> RoutePriors {
+        let priors = self.priors.read();
+        let stats = priors.get(trading_pair).map(Vec::as_slice).unwrap_or_default();
+        RoutePriors::new(stats, self.config.min_samples, self.config.tie_bps, self.config.unfilled_penalty_bps)
+    }
+
+    /// Re-materializes statistics on the configured interval
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.materialize_interval);
+            loop {
+                interval.tick().await;
+                match self.materialize(Utc::now()).await {
+                    Ok(rows) => info!(rows, "Execution statistics materialized"),
+                    Err(e) => warn!("Failed to materialize execution statistics: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use rust_decimal_macros::dec;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        records: Mutex<Vec<ExecutionRecord>>,
+        stats: Mutex<Vec<ExecutionStats>>,
+    }
+
+    #[async_trait]
+    impl ExecutionStatsStore for MemoryStore {
+        async fn record(&self, record: &ExecutionRecord) -> Result<(), StatsError> {
+            self.records.lock().push(record.clone());
+            Ok(())
+        }
+
+        async fn records_since(&self, cutoff: DateTime<Utc>) -> Result<Vec<ExecutionRecord>, StatsError> {
+            Ok(self.records.lock().iter().filter(|record| record.executed_at >= cutoff).cloned().collect())
+        }
+
+        async fn replace_stats(&self, stats: &[ExecutionStats]) -> Result<(), StatsError> {
+            *self.stats.lock() = stats.to_vec();
+            Ok(())
+        }
+
+        async fn load_stats(&self, trading_pair: &str, window: StatsWindow) -> Result<Vec<ExecutionStats>, StatsError> {
+            Ok(self
+                .stats
+                .lock()
+                .iter()
+                .filter(|stats| stats.trading_pair == trading_pair && stats.window == window)
+                .cloned()
+                .collect())
+        }
+
+        async fn record_benchmark(&self, execution_id: Uuid, benchmark: &ExecutionBenchmark) -> Result<(), StatsError> {
+            if let Some(record) = self.records.lock().iter_mut().find(|record| record.id == execution_id) {
+                record.benchmark = Some(benchmark.clone());
+            }
+            Ok(())
+        }
+    }
+
+    fn record(exchange: &str, executed_price: Option<Decimal>, latency_ms: u64, age_hours: i64, now: DateTime<Utc>) -> ExecutionRecord {
+        let filled_size = if executed_price.is_some() { dec!(10) } else { Decimal::ZERO };
+        ExecutionRecord {
+            id: Uuid::new_v4(),
+            trading_pair: "SOL/USDC".to_string(),
+            exchange: exchange.to_string(),
+            side: TradeSide::Buy,
+            requested_size: dec!(10),
+            filled_size,
+            expected_price: dec!(100),
+            executed_price,
+            fee: executed_price.map(|_| dec!(0.3)).unwrap_or_default(),
+            latency_ms,
+            mev_value: executed_price.map(|_| dec!(0.1)).unwrap_or_default(),
+            executed_at: now - chrono::Duration::hours(age_hours),
+            benchmark: None,
+            book_depth: None,
+            volatility_bps: None,
+            compute: None,
+            event_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_materialized_stats_per_window() {
+        let now = Utc::now();
+        let store = Arc::new(MemoryStore::default());
+        let service = ExecutionStatsService::new(ExecutionStatsConfig::default(), store.clone());
+
+        // Within a day: three fills at 0, 10 and 20 bps slippage plus one failure
+        for record in [
+            record("jupiter", Some(dec!(100)), 200, 1, now),
+            record("jupiter", Some(dec!(100.1)), 300, 2, now),
+            record("jupiter", Some(dec!(100.2)), 400, 3, now),
+            record("jupiter", None, 900, 4, now),
+            // Only in the weekly and monthly windows
+            record("jupiter", Some(dec!(101)), 500, 48, now),
+            record("drift", Some(dec!(99.9)), 100, 5, now),
+            // Outside every window
+            record("drift", Some(dec!(110)), 100, 24 * 40, now),
+        ] {
+            service.record(record).await;
+        }
+
+        assert_eq!(service.materialize(now).await.unwrap(), 6);
+
+        let daily = service.stats("SOL/USDC", StatsWindow::Day).await.unwrap();
+        let jupiter = daily.iter().find(|stats| stats.exchange == "jupiter").unwrap();
+        assert_eq!(jupiter.sample_count, 4);
+        assert_eq!(jupiter.fill_rate, dec!(0.75));
+        assert_eq!(jupiter.avg_slippage_bps, dec!(10));
+        assert_eq!(jupiter.p95_slippage_bps, dec!(20));
+        assert_eq!(jupiter.avg_latency_ms, dec!(300));
+        assert_eq!(jupiter.fee_bps, dec!(3));
+        assert_eq!(jupiter.mev_capture_bps, dec!(1));
+
+        let drift = daily.iter().find(|stats| stats.exchange == "drift").unwrap();
+        assert_eq!(drift.sample_count, 1);
+        assert_eq!(drift.avg_slippage_bps, dec!(-10));
+
+        let weekly = service.stats("SOL/USDC", StatsWindow::Week).await.unwrap();
+        let jupiter = weekly.iter().find(|stats| stats.exchange == "jupiter").unwrap();
+        assert_eq!(jupiter.sample_count, 5);
+        assert_eq!(jupiter.fill_rate, dec!(0.8));
+        assert_eq!(jupiter.avg_slippage_bps, dec!(32.5));
+        assert_eq!(jupiter.p95_slippage_bps, dec!(100));
+
+        let monthly = service.stats("SOL/USDC", StatsWindow::Month).await.unwrap();
+        assert_eq!(monthly.iter().find(|stats| stats.exchange == "drift").unwrap().sample_count, 1);
+    }
+
+    #[test]
+    fn test_benchmark_deviation_skips_low_coverage() {
+        let now = Utc::now();
+        let benchmark = |vwap_deviation_bps: Decimal, low_coverage: bool| ExecutionBenchmark {
+            window_start: now - chrono::Duration::minutes(1),
+            window_end: now,
+            vwap: Some(dec!(100)),
+            twap: Some(dec!(100)),
+            vwap_deviation_bps: Some(vwap_deviation_bps),
+            twap_deviation_bps: Some(vwap_deviation_bps / dec!(2)),
+            tick_count: 12,
+            coverage: if low_coverage { dec!(0.25) } else { dec!(1) },
+            low_coverage,
+            computed_at: now,
+        };
+        let mut records = vec![
+            record("jupiter", Some(dec!(100)), 200, 1, now),
+            record("jupiter", Some(dec!(100)), 200, 1, now),
+            record("jupiter", Some(dec!(100)), 200, 1, now),
+            record("jupiter", Some(dec!(100)), 200, 1, now),
+        ];
+        records[0].benchmark = Some(benchmark(dec!(4), false));
+        records[1].benchmark = Some(benchmark(dec!(8), false));
+        // Collector outage during the window; excluded from the averages
+        records[2].benchmark = Some(benchmark(dec!(500), true));
+
+        let stats = aggregate(&records, StatsWindow::Day, now);
+        assert_eq!(stats[0].benchmarked_count, 2);
+        assert_eq!(stats[0].avg_vwap_deviation_bps, Some(dec!(6)));
+        assert_eq!(stats[0].avg_twap_deviation_bps, Some(dec!(3)));
+
+        let stats = aggregate(&records[3..], StatsWindow::Day, now);
+        assert_eq!(stats[0].benchmarked_count, 0);
+        assert_eq!(stats[0].avg_vwap_deviation_bps, None);
+    }
+
+    #[tokio::test]
+    async fn test_priors_require_min_samples() {
+        let now = Utc::now();
+        let config = ExecutionStatsConfig {
+            min_samples: 2,
+            ..ExecutionStatsConfig::default()
+        };
+        let service = ExecutionStatsService::new(config, Arc::new(MemoryStore::default()));
+        service.record(record("jupiter", Some(dec!(100)), 200, 1, now)).await;
+        service.record(record("jupiter", Some(dec!(100)), 200, 2, now)).await;
+        service.record(record("drift", Some(dec!(100)), 200, 1, now)).await;
+        service.materialize(now).await.unwrap();
+
+        let priors = service.priors("SOL/USDC");
+        // Zero slippage, 3 bps fees, 1 bps MEV capture, always filled
+        assert_eq!(priors.cost_bps("jupiter"), Some(dec!(2)));
+        assert_eq!(priors.cost_bps("drift"), None);
+        assert!(service.priors("BONK/USDC").is_empty());
+    }
+
+    #[test]
+    fn test_window_parsing() {
+        assert_eq!("7d".parse::<StatsWindow>().unwrap(), StatsWindow::Week);
+        assert!(matches!("2w".parse::<StatsWindow>(), Err(StatsError::UnknownWindow(_))));
+    }
+}
@@ -0,0 +1,187 @@
+//! Swap transaction construction for exchange adapters. Swaps on pairs quoted in native SOL
+//! are bracketed by wrap and unwrap instructions for the payer's wSOL account.
+//!
+//! Version dependencies:
+//! - solana-sdk = "1.16"
+//! - spl-token = "4.0"
+//! - spl-associated-token-account = "2.0"
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use solana_sdk::{
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    system_instruction,
+    transaction::Transaction,
+};
+use spl_associated_token_account::{
+    get_associated_token_address,
+    instruction::create_associated_token_account_idempotent,
+};
+use spl_token::{instruction as token_instruction, native_mint};
+use tracing::debug;
+
+use crate::execution_engine::error::ExecutionError;
+use crate::models::market::QuoteAsset;
+use crate::risk_manager::exposure::TradeSide;
+
+/// Converts a SOL amount to lamports, rejecting amounts that do not fit
+pub fn sol_to_lamports(amount: Decimal) -> Result<u64, ExecutionError> {
+    if amount <= Decimal::ZERO {
+        return Err(ExecutionError::ValidationError("SOL amount must be positive".to_string()));
+    }
+    (amount * Decimal::from(LAMPORTS_PER_SOL))
+        .trunc()
+        .to_u64()
+        .ok_or_else(|| ExecutionError::ValidationError(format!("SOL amount {} out of range", amount)))
+}
+
+/// Payer's wSOL token account
+pub fn wsol_account(owner: &Pubkey) -> Pubkey {
+    get_associated_token_address(owner, &native_mint::id())
+}
+
+/// Creates the payer's wSOL account if it does not exist yet
+fn ensure_wsol_account(owner: &Pubkey) -> Instruction {
+    create_associated_token_account_idempotent(owner, owner, &native_mint::id(), &spl_token::id())
+}
+
+/// Instructions moving native SOL into the payer's wSOL account ahead of a swap
+pub fn wrap_sol_instructions(owner: &Pubkey, lamports: u64) -> Result<Vec<Instruction>, ExecutionError> {
+    let account = wsol_account(owner);
+    let sync = token_instruction::sync_native(&spl_token::id(), &account)
+        .map_err(|e| ExecutionError::ValidationError(format!("failed to build sync instruction: {}", e)))?;
+
+    Ok(vec![
+        ensure_wsol_account(owner),
+        system_instruction::transfer(owner, &account, lamports),
+        sync,
+    ])
+}
+
+/// Instruction closing the payer's wSOL account, returning wrapped SOL as native SOL
+pub fn unwrap_sol_instruction(owner: &Pubkey) -> Result<Instruction, ExecutionError> {
+    token_instruction::close_account(&spl_token::id(), &wsol_account(owner), owner, owner, &[])
+        .map_err(|e| ExecutionError::ValidationError(format!("failed to build unwrap instruction: {}", e)))
+}
+
+/// Builds the transaction for a swap. Buys on SOL-quoted pairs wrap the quote amount first,
+/// sells receive into a wSOL account, and both unwrap whatever wSOL remains afterwards.
+/// wSOL-quoted pairs settle in wSOL, so they only need the account to exist.
+pub fn build_swap_transaction(
+    payer: &Pubkey,
+    trading_pair: &str,
+    side: TradeSide,
+    quote_amount: Decimal,
+    swap_instructions: Vec<Instruction>,
+) -> Result<Transaction, ExecutionError> {
+    if swap_instructions.is_empty() {
+        return Err(ExecutionError::ValidationError("swap has no instructions".to_string()));
+    }
+
+    let quote = QuoteAsset::of_pair(trading_pair);
+    let mut instructions = Vec::with_capacity(swap_instructions.len() + 4);
+
+    match (quote, side) {
+        (QuoteAsset::Sol, TradeSide::Buy) => {
+            instructions.extend(wrap_sol_instructions(payer, sol_to_lamports(quote_amount)?)?);
+        }
+        (QuoteAsset::Sol, TradeSide::Sell) | (QuoteAsset::Wsol, _) => {
+            instructions.push(ensure_wsol_account(payer));
+        }
+        (QuoteAsset::Usdc, _) => {}
+    }
+
+    instructions.extend(swap_instructions);
+
+    if quote == QuoteAsset::Sol {
+        instructions.push(unwrap_sol_instruction(payer)?);
+    }
+
+    debug!(
+        trading_pair,
+        quote = %quote,
+        instructions = instructions.len(),
+        "Built swap transaction"
+    );
+
+    Ok(Transaction::new_with_payer(&instructions, Some(payer)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn swap_instruction(payer: &Pubkey) -> Instruction {
+        // Stand-in for the DEX swap instruction
+        system_instruction::transfer(payer, &Pubkey::new_unique(), 1)
+    }
+
+    fn program_ids(transaction: &Transaction) -> Vec<Pubkey> {
+        transaction
+            .message
+            .instructions
+            .iter()
+            .map(|ix| transaction.message.account_keys[ix.program_id_index as usize])
+            .collect()
+    }
+
+    #[test]
+    fn test_sol_quoted_buy_wraps_and_unwraps() {
+        let payer = Pubkey::new_unique();
+        let transaction = build_swap_transaction(
+            &payer,
+            "BONK/SOL",
+            TradeSide::Buy,
+            dec!(1.5),
+            vec![swap_instruction(&payer)],
+        )
+        .unwrap();
+
+        let programs = program_ids(&transaction);
+        assert_eq!(
+            programs,
+            vec![
+                spl_associated_token_account::id(),
+                solana_sdk::system_program::id(),
+                spl_token::id(),
+                solana_sdk::system_program::id(),
+                spl_token::id(),
+            ]
+        );
+
+        // The wrap transfer funds the wSOL account with the quote amount
+        let wrap = &transaction.message.instructions[1];
+        let lamports = u64::from_le_bytes(wrap.data[4..12].try_into().unwrap());
+        assert_eq!(lamports, 1_500_000_000);
+        assert_eq!(
+            transaction.message.account_keys[wrap.accounts[1] as usize],
+            wsol_account(&payer)
+        );
+    }
+
+    #[test]
+    fn test_wrapping_only_for_sol_quotes() {
+        let payer = Pubkey::new_unique();
+
+        let sell = build_swap_transaction(&payer, "BONK/SOL", TradeSide::Sell, dec!(1), vec![swap_instruction(&payer)])
+            .unwrap();
+        let programs = program_ids(&sell);
+        assert_eq!(programs.first(), Some(&spl_associated_token_account::id()));
+        assert_eq!(programs.last(), Some(&spl_token::id()));
+
+        let usdc = build_swap_transaction(&payer, "SOL/USDC", TradeSide::Buy, dec!(100), vec![swap_instruction(&payer)])
+            .unwrap();
+        assert_eq!(usdc.message.instructions.len(), 1);
+
+        // wSOL-quoted pairs keep the proceeds wrapped
+        let wsol = build_swap_transaction(&payer, "BONK/WSOL", TradeSide::Buy, dec!(1), vec![swap_instruction(&payer)])
+            .unwrap();
+        assert_eq!(
+            program_ids(&wsol),
+            vec![spl_associated_token_account::id(), solana_sdk::system_program::id()]
+        );
+    }
+}
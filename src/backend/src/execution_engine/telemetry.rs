@@ -0,0 +1,123 @@
+//! Runtime-only order execution telemetry kept by the executor, keyed by order id.
+//! Durable facts are copied onto the order once it reaches a terminal state.
+//!
+//! Version dependencies:
+//! - metrics = "0.20"
+//! - parking_lot = "0.12"
+//! - uuid = "1.4"
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use crate::models::order::Order;
+
+// Telemetry constants
+const METRICS_PREFIX: &str = "trading_bot.order";
+
+/// In-flight timing and retry state for one order execution
+#[derive(Debug, Clone)]
+pub struct OrderExecutionTelemetry {
+    started_at: Instant,
+    retry_count: u8,
+}
+
+impl OrderExecutionTelemetry {
+    fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            retry_count: 0,
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    pub fn retry_count(&self) -> u8 {
+        self.retry_count
+    }
+}
+
+/// Telemetry for every order currently executing
+#[derive(Debug, Default)]
+pub struct OrderTelemetry {
+    executions: Mutex<HashMap<Uuid, OrderExecutionTelemetry>>,
+}
+
+impl OrderTelemetry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts timing an execution, restarting any earlier attempt for the same order
+    pub fn begin(&self, order_id: Uuid) {
+        self.executions.lock().insert(order_id, OrderExecutionTelemetry::new());
+    }
+
+    pub fn record_retry(&self, order_id: Uuid) {
+        if let Some(telemetry) = self.executions.lock().get_mut(&order_id) {
+            telemetry.retry_count = telemetry.retry_count.saturating_add(1);
+        }
+    }
+
+    pub fn get(&self, order_id: Uuid) -> Option<OrderExecutionTelemetry> {
+        self.executions.lock().get(&order_id).cloned()
+    }
+
+    /// Copies durable facts onto a terminal order and drops its runtime telemetry
+    pub fn finish(&self, order: &mut Order) {
+        let Some(telemetry) = self.executions.lock().remove(&order.id) else {
+            return;
+        };
+
+        let duration = telemetry.elapsed();
+        order.retry_count = telemetry.retry_count;
+        order.execution_duration_ms = Some(duration.as_millis() as u64);
+
+        metrics::histogram!(
+            format!("{}.execution_duration_ms", METRICS_PREFIX),
+            duration.as_millis() as f64
+        );
+        metrics::counter!(
+            format!("{}.retry_count", METRICS_PREFIX),
+            telemetry.retry_count as u64
+        );
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.executions.lock().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::order::OrderType;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_finish_populates_durable_fields() {
+        let telemetry = OrderTelemetry::new();
+        let mut order = Order::new(
+            "SOL/USDC".to_string(),
+            "jupiter".to_string(),
+            OrderType::Limit,
+            dec!(23.45),
+            dec!(1.5),
+        )
+        .unwrap();
+
+        telemetry.begin(order.id);
+        telemetry.record_retry(order.id);
+        telemetry.record_retry(order.id);
+        assert_eq!(telemetry.get(order.id).unwrap().retry_count(), 2);
+
+        telemetry.finish(&mut order);
+        assert_eq!(order.retry_count, 2);
+        assert!(order.execution_duration_ms.is_some());
+        assert_eq!(telemetry.in_flight(), 0);
+    }
+}
@@ -0,0 +1,462 @@
+//! Outbound order throttles pacing submissions per signing wallet and venue ahead of
+//! signing, so bursts stay inside each DEX's transaction limits. Each (wallet, venue) pair
+//! has a token bucket; orders that can wait queue for the next token with a deadline, and
+//! critical orders are served before normal ones. Latency-sensitive orders are never held
+//! back: without a free token they are rejected at once so the strategy can re-price.
+//! Limits come from the venue section of the environment config and follow reloads.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - metrics = "0.20"
+//! - parking_lot = "0.12"
+//! - tracing = "0.1"
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use metrics::{counter, gauge, histogram};
+use parking_lot::{Mutex, RwLock as SyncRwLock};
+use tokio::sync::{oneshot, watch};
+use tracing::{debug, error, info};
+
+use crate::config::environment::{EnvironmentConfig, VenueThrottle};
+use crate::execution_engine::error::ExecutionError;
+use crate::execution_engine::queue::PriorityClass;
+
+// Throttle configuration defaults
+pub const DEFAULT_THROTTLE_CRITICAL_MAX_WAIT_MS: u64 = 5000;
+pub const DEFAULT_THROTTLE_NORMAL_MAX_WAIT_MS: u64 = 1000;
+/// Shortest sleep between bucket checks while an order waits
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(1);
+/// Absorbs float error when refills add up to exactly one token
+const TOKEN_EPSILON: f64 = 1e-9;
+const METRICS_PREFIX: &str = "trading_bot.order_throttle";
+
+/// Venue limits and how long waiting orders may be held
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThrottleConfig {
+    /// Submission limits by exchange; unlisted venues are not throttled
+    pub venues: HashMap<String, VenueThrottle>,
+    pub critical_max_wait: Duration,
+    pub normal_max_wait: Duration,
+}
+
+impl ThrottleConfig {
+    pub fn from_environment(config: &EnvironmentConfig) -> Self {
+        Self {
+            venues: config.venue_throttles.clone(),
+            ..Self::default()
+        }
+    }
+
+    /// How long an order may wait for a token; latency-sensitive orders may not wait at all
+    fn max_wait(&self, priority: PriorityClass) -> Option<Duration> {
+        match priority {
+            PriorityClass::Critical => Some(self.critical_max_wait),
+            PriorityClass::High => None,
+            PriorityClass::Normal => Some(self.normal_max_wait),
+        }
+    }
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self {
+            venues: HashMap::new(),
+            critical_max_wait: Duration::from_millis(DEFAULT_THROTTLE_CRITICAL_MAX_WAIT_MS),
+            normal_max_wait: Duration::from_millis(DEFAULT_THROTTLE_NORMAL_MAX_WAIT_MS),
+        }
+    }
+}
+
+/// Outcome of an order that waited for a submission slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleOutcome {
+    Granted { waited: Duration },
+    Expired { waited: Duration },
+}
+
+/// Immediate answer to a request for a submission slot
+#[derive(Debug)]
+pub enum ThrottleAdmission {
+    Granted,
+    Rejected(ExecutionError),
+    Queued(oneshot::Receiver<ThrottleOutcome>),
+}
+
+#[derive(Debug)]
+struct Waiter {
+    enqueued_at: Instant,
+    deadline: Instant,
+    responder: oneshot::Sender<ThrottleOutcome>,
+}
+
+/// Token bucket for one wallet on one venue with the orders waiting on it
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+    waiters: HashMap<PriorityClass, VecDeque<Waiter>>,
+}
+
+impl Bucket {
+    fn new(limit: &VenueThrottle, now: Instant) -> Self {
+        Self {
+            tokens: limit.burst.max(1) as f64,
+            refilled_at: now,
+            waiters: HashMap::new(),
+        }
+    }
+
+    fn refill(&mut self, limit: &VenueThrottle, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit.max_per_second as f64).min(limit.burst.max(1) as f64);
+        self.refilled_at = self.refilled_at.max(now);
+    }
+
+    fn take(&mut self) -> bool {
+        if self.tokens + TOKEN_EPSILON >= 1.0 {
+            self.tokens = (self.tokens - 1.0).max(0.0);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Time until the next token accrues
+    fn next_token_in(&self, limit: &VenueThrottle) -> Duration {
+        if self.tokens + TOKEN_EPSILON >= 1.0 || limit.max_per_second == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64((1.0 - self.tokens) / limit.max_per_second as f64)
+    }
+
+    fn waiting(&self) -> usize {
+        self.waiters.values().map(VecDeque::len).sum()
+    }
+
+    /// Refills, expires waiters past their deadline and hands tokens out by priority
+    fn release(&mut self, venue: &str, limit: &VenueThrottle, now: Instant) {
+        self.refill(limit, now);
+
+        for priority in PriorityClass::ALL {
+            let Some(queue) = self.waiters.get_mut(&priority) else {
+                continue;
+            };
+            let mut kept = VecDeque::with_capacity(queue.len());
+            for waiter in queue.drain(..) {
+                if waiter.deadline <= now {
+                    let waited = now.duration_since(waiter.enqueued_at);
+                    counter!(format!("{}.expired", METRICS_PREFIX), 1, "venue" => venue.to_string());
+                    debug!(venue, waited_ms = waited.as_millis() as u64, "Throttled order expired");
+                    let _ = waiter.responder.send(ThrottleOutcome::Expired { waited });
+                } else {
+                    kept.push_back(waiter);
+                }
+            }
+            *queue = kept;
+        }
+
+        for priority in PriorityClass::ALL {
+            let Some(queue) = self.waiters.get_mut(&priority) else {
+                continue;
+            };
+            while !queue.is_empty() && self.tokens + TOKEN_EPSILON >= 1.0 {
+                let Some(waiter) = queue.pop_front() else {
+                    break;
+                };
+                self.tokens = (self.tokens - 1.0).max(0.0);
+                let waited = now.duration_since(waiter.enqueued_at);
+                if waiter.responder.send(ThrottleOutcome::Granted { waited }).is_err() {
+                    // The submitter gave up; keep its slot for the next order
+                    self.tokens += 1.0;
+                    continue;
+                }
+                histogram!(
+                    format!("{}.delay_ms", METRICS_PREFIX),
+                    waited.as_secs_f64() * 1000.0,
+                    "venue" => venue.to_string()
+                );
+            }
+        }
+    }
+
+    /// Grants every waiter, for venues whose throttle was removed
+    fn release_all(&mut self, now: Instant) {
+        for (_, queue) in self.waiters.drain() {
+            for waiter in queue {
+                let waited = now.duration_since(waiter.enqueued_at);
+                let _ = waiter.responder.send(ThrottleOutcome::Granted { waited });
+            }
+        }
+    }
+}
+
+/// Per-wallet, per-venue submission throttle
+#[derive(Debug)]
+pub struct OrderThrottle {
+    config: SyncRwLock<ThrottleConfig>,
+    /// Buckets by (wallet, venue)
+    buckets: Mutex<HashMap<(String, String), Bucket>>,
+}
+
+impl OrderThrottle {
+    /// Creates new order throttle with configuration
+    pub fn new(config: ThrottleConfig) -> Self {
+        Self {
+            config: SyncRwLock::new(config),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Requests a submission slot at an explicit time
+    pub fn request_at(&self, wallet: &str, venue: &str, priority: PriorityClass, now: Instant) -> ThrottleAdmission {
+        let config = self.config.read();
+        let Some(limit) = config.venues.get(venue).copied() else {
+            return ThrottleAdmission::Granted;
+        };
+
+        let mut buckets = self.buckets.lock();
+        let bucket = buckets
+            .entry((wallet.to_string(), venue.to_string()))
+            .or_insert_with(|| Bucket::new(&limit, now));
+        bucket.release(venue, &limit, now);
+
+        // Anything still waiting means no token is free, so a grant here never overtakes
+        if bucket.take() {
+            histogram!(format!("{}.delay_ms", METRICS_PREFIX), 0.0, "venue" => venue.to_string());
+            return ThrottleAdmission::Granted;
+        }
+
+        let Some(max_wait) = config.max_wait(priority) else {
+            counter!(format!("{}.rejected", METRICS_PREFIX), 1, "venue" => venue.to_string());
+            return ThrottleAdmission::Rejected(ExecutionError::Throttled {
+                exchange: venue.to_string(),
+                retry_after_ms: bucket.next_token_in(&limit).as_millis() as u64,
+            });
+        };
+
+        let (tx, rx) = oneshot::channel();
+        bucket.waiters.entry(priority).or_default().push_back(Waiter {
+            enqueued_at: now,
+            deadline: now + max_wait,
+            responder: tx,
+        });
+        gauge!(
+            format!("{}.waiting", METRICS_PREFIX),
+            bucket.waiting() as f64,
+            "venue" => venue.to_string()
+        );
+        ThrottleAdmission::Queued(rx)
+    }
+
+    /// Hands out tokens accrued by `now` and expires waiters past their deadline
+    pub fn release_at(&self, now: Instant) {
+        let config = self.config.read();
+        let mut buckets = self.buckets.lock();
+        for ((_, venue), bucket) in buckets.iter_mut() {
+            match config.venues.get(venue) {
+                Some(limit) => bucket.release(venue, limit, now),
+                None => bucket.release_all(now),
+            }
+        }
+    }
+
+    /// Time until the bucket for a wallet and venue can grant again
+    fn next_release_in(&self, wallet: &str, venue: &str) -> Duration {
+        let config = self.config.read();
+        let Some(limit) = config.venues.get(venue) else {
+            return MIN_POLL_INTERVAL;
+        };
+        self.buckets
+            .lock()
+            .get(&(wallet.to_string(), venue.to_string()))
+            .map_or(MIN_POLL_INTERVAL, |bucket| bucket.next_token_in(limit))
+            .max(MIN_POLL_INTERVAL)
+    }
+
+    /// Waits for a submission slot, returning how long the order was held back
+    pub async fn acquire(&self, wallet: &str, venue: &str, priority: PriorityClass) -> Result<Duration, ExecutionError> {
+        let mut rx = match self.request_at(wallet, venue, priority, Instant::now()) {
+            ThrottleAdmission::Granted => return Ok(Duration::ZERO),
+            ThrottleAdmission::Rejected(e) => return Err(e),
+            ThrottleAdmission::Queued(rx) => rx,
+        };
+
+        loop {
+            match tokio::time::timeout(self.next_release_in(wallet, venue), &mut rx).await {
+                Ok(Ok(ThrottleOutcome::Granted { waited })) => return Ok(waited),
+                Ok(Ok(ThrottleOutcome::Expired { waited })) => {
+                    return Err(ExecutionError::ExpiredError(format!(
+                        "order waited {}ms for a {} submission slot",
+                        waited.as_millis(),
+                        venue
+                    )))
+                }
+                Ok(Err(_)) => {
+                    return Err(ExecutionError::InternalError("order throttle dropped order".to_string()))
+                }
+                Err(_) => self.release_at(Instant::now()),
+            }
+        }
+    }
+
+    /// Applies new venue limits; orders waiting on a venue that is no longer throttled are released
+    pub fn apply_config(&self, config: ThrottleConfig) {
+        info!(venues = config.venues.len(), "Applied order throttle limits");
+        *self.config.write() = config;
+        self.release_at(Instant::now());
+        let config = self.config.read();
+        self.buckets
+            .lock()
+            .retain(|(_, venue), _| config.venues.contains_key(venue));
+    }
+
+    /// Number of orders waiting for a slot on a venue across wallets
+    pub fn waiting(&self, venue: &str) -> usize {
+        self.buckets
+            .lock()
+            .iter()
+            .filter(|((_, bucket_venue), _)| bucket_venue == venue)
+            .map(|(_, bucket)| bucket.waiting())
+            .sum()
+    }
+
+    /// Applies environment config updates from the config reload path
+    pub fn spawn_reload_listener(
+        self: Arc<Self>,
+        mut updates: watch::Receiver<Option<EnvironmentConfig>>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            while updates.changed().await.is_ok() {
+                let config = updates.borrow().clone();
+                if let Some(config) = config {
+                    let mut throttle = self.config.read().clone();
+                    throttle.venues = config.venue_throttles.clone();
+                    self.apply_config(throttle);
+                }
+            }
+            error!("Environment config update channel closed");
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WALLET: &str = "wallet_a";
+
+    fn throttle(max_per_second: u32, burst: u32) -> OrderThrottle {
+        let mut config = ThrottleConfig::default();
+        config
+            .venues
+            .insert("jupiter".to_string(), VenueThrottle { max_per_second, burst });
+        OrderThrottle::new(config)
+    }
+
+    fn queued(admission: ThrottleAdmission) -> oneshot::Receiver<ThrottleOutcome> {
+        match admission {
+            ThrottleAdmission::Queued(rx) => rx,
+            other => panic!("expected order to queue, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_burst_is_paced_with_critical_first() {
+        let throttle = throttle(5, 1);
+        let start = Instant::now();
+
+        // Twenty orders in one burst: 17 normal, 2 critical and 1 latency-sensitive
+        assert!(matches!(
+            throttle.request_at(WALLET, "jupiter", PriorityClass::Normal, start),
+            ThrottleAdmission::Granted
+        ));
+        let mut normal: Vec<_> = (0..16)
+            .map(|_| queued(throttle.request_at(WALLET, "jupiter", PriorityClass::Normal, start)))
+            .collect();
+        let mut critical: Vec<_> = (0..2)
+            .map(|_| queued(throttle.request_at(WALLET, "jupiter", PriorityClass::Critical, start)))
+            .collect();
+        match throttle.request_at(WALLET, "jupiter", PriorityClass::High, start) {
+            ThrottleAdmission::Rejected(ExecutionError::Throttled { exchange, retry_after_ms }) => {
+                assert_eq!(exchange, "jupiter");
+                assert_eq!(retry_after_ms, 200);
+            }
+            other => panic!("expected rejection, got {:?}", other),
+        }
+        assert_eq!(throttle.waiting("jupiter"), 18);
+
+        // One token every 200ms; nothing is released early
+        throttle.release_at(start + Duration::from_millis(199));
+        assert_eq!(throttle.waiting("jupiter"), 18);
+
+        // Critical orders jump the queue, then normal orders follow in arrival order
+        for step in 1..=4u64 {
+            throttle.release_at(start + Duration::from_millis(200 * step));
+            assert_eq!(throttle.waiting("jupiter"), 18 - step as usize);
+        }
+        for rx in critical.iter_mut() {
+            assert!(matches!(rx.try_recv(), Ok(ThrottleOutcome::Granted { .. })));
+        }
+        assert_eq!(
+            normal[0].try_recv().unwrap(),
+            ThrottleOutcome::Granted { waited: Duration::from_millis(600) }
+        );
+        assert_eq!(
+            normal[1].try_recv().unwrap(),
+            ThrottleOutcome::Granted { waited: Duration::from_millis(800) }
+        );
+
+        // Everything still waiting at the one second deadline expires
+        throttle.release_at(start + Duration::from_millis(DEFAULT_THROTTLE_NORMAL_MAX_WAIT_MS));
+        assert_eq!(throttle.waiting("jupiter"), 0);
+        for rx in normal.iter_mut().skip(2) {
+            assert!(matches!(rx.try_recv(), Ok(ThrottleOutcome::Expired { .. })));
+        }
+    }
+
+    #[test]
+    fn test_wallets_and_venues_are_throttled_independently() {
+        let throttle = throttle(5, 1);
+        let now = Instant::now();
+
+        assert!(matches!(
+            throttle.request_at(WALLET, "jupiter", PriorityClass::High, now),
+            ThrottleAdmission::Granted
+        ));
+        assert!(matches!(
+            throttle.request_at(WALLET, "jupiter", PriorityClass::High, now),
+            ThrottleAdmission::Rejected(_)
+        ));
+        assert!(matches!(
+            throttle.request_at("wallet_b", "jupiter", PriorityClass::High, now),
+            ThrottleAdmission::Granted
+        ));
+        // Venues without a configured limit are not throttled
+        for _ in 0..10 {
+            assert!(matches!(
+                throttle.request_at(WALLET, "drift", PriorityClass::High, now),
+                ThrottleAdmission::Granted
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reload_releases_unthrottled_venue() {
+        let throttle = throttle(5, 1);
+        let now = Instant::now();
+
+        throttle.request_at(WALLET, "jupiter", PriorityClass::Normal, now);
+        let mut rx = queued(throttle.request_at(WALLET, "jupiter", PriorityClass::Normal, now));
+
+        throttle.apply_config(ThrottleConfig::default());
+        assert!(matches!(rx.try_recv(), Ok(ThrottleOutcome::Granted { .. })));
+        assert_eq!(throttle.waiting("jupiter"), 0);
+        assert_eq!(
+            throttle.acquire(WALLET, "jupiter", PriorityClass::High).await.unwrap(),
+            Duration::ZERO
+        );
+    }
+}
@@ -5,19 +5,30 @@
 //! - tokio = "1.28"
 //! - rust_decimal = "1.30"
 //! - solana-sdk = "1.17"
+//! - parking_lot = "0.12"
+//! - async-trait = "0.1"
 
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use parking_lot::RwLock as SyncRwLock;
 use tokio::sync::RwLock;
 use rust_decimal::Decimal;
 use solana_sdk::transaction::Transaction;
 use tracing::{debug, error, info, instrument, warn};
+use uuid::Uuid;
 
+use crate::models::order::OrderFill;
 use crate::models::trade::Trade;
-use crate::models::market::OrderBook;
+use crate::models::market::{OrderBook, TickStamp};
 use crate::execution_engine::jito::{JitoClient, create_mev_bundle, submit_bundle};
+use crate::execution_engine::adapters::GuardedOrder;
+use crate::execution_engine::compute_budget::{ComputeBudgeter, ComputeUsage};
 use crate::execution_engine::error::{ExecutionError, TradeContext};
+use crate::execution_engine::recovery::IntentStore;
 use crate::utils::metrics::MetricsCollector;
+use crate::utils::solana::{FeeEstimator, FeeUrgency};
 
 // Constants for execution control
 const MAX_EXECUTION_ATTEMPTS: u8 = 3;
@@ -25,6 +36,8 @@ const EXECUTION_TIMEOUT_MS: u64 = 500;
 const MIN_MEV_PROFIT_THRESHOLD: f64 = 0.001;
 const CIRCUIT_BREAKER_ERROR_THRESHOLD: u32 = 10;
 const MAX_CONCURRENT_EXECUTIONS: usize = 50;
+const HIGH_URGENCY_MEV_THRESHOLD: f64 = 0.01;
+const MAX_MEV_FEE_MULTIPLIER: u64 = 5;
 
 /// High-performance trade executor with MEV optimization
 #[derive(Debug)]
@@ -32,8 +45,11 @@ pub struct TradeExecutor {
     market_data: Arc<RwLock<OrderBook>>,
     jito_client: Arc<JitoClient>,
     metrics: Arc<MetricsCollector>,
-    error_count: Arc<RwLock<u32>>,
-    active_executions: Arc<RwLock<usize>>,
+    error_count: AtomicU32,
+    active_executions: AtomicUsize,
+    fee_estimator: SyncRwLock<Option<Arc<FeeEstimator>>>,
+    compute_budget: Option<Arc<ComputeBudgeter>>,
+    intents: SyncRwLock<Option<Arc<dyn IntentStore>>>,
 }
 
 impl TradeExecutor {
@@ -47,13 +63,42 @@ impl TradeExecutor {
             market_data,
             jito_client,
             metrics,
-            error_count: Arc::new(RwLock::new(0)),
-            active_executions: Arc::new(RwLock::new(0)),
+            error_count: AtomicU32::new(0),
+            active_executions: AtomicUsize::new(0),
+            fee_estimator: SyncRwLock::new(None),
+            compute_budget: None,
+            intents: SyncRwLock::new(None),
         }
     }
 
+    /// Prices priority fees from recent block data instead of MEV value alone
+    pub fn with_fee_estimator(self, fee_estimator: Arc<FeeEstimator>) -> Self {
+        self.set_fee_estimator(fee_estimator);
+        self
+    }
+
+    /// Attaches a fee estimator to an executor already shared with the execution queue
+    pub fn set_fee_estimator(&self, fee_estimator: Arc<FeeEstimator>) {
+        *self.fee_estimator.write() = Some(fee_estimator);
+    }
+
+    /// Records compute units consumed by landed transactions against their simulated budgets
+    pub fn with_compute_budget(mut self, budgeter: Arc<ComputeBudgeter>) -> Self {
+        self.compute_budget = Some(budgeter);
+        self
+    }
+
+    /// Records each attempt's signature on its submission intent before the bundle leaves,
+    /// so a fill that lands after a crash can be recovered on restart
+    pub fn set_intent_store(&self, intents: Arc<dyn IntentStore>) {
+        *self.intents.write() = Some(intents);
+    }
+
     /// Executes trade with MEV optimization and comprehensive monitoring
-    #[instrument(skip(self, params), fields(trade_id = %params.id))]
+    #[instrument(
+        skip(self, params),
+        fields(trade_id = %params.id, trading_pair = %params.trading_pair, exchange = %params.exchange)
+    )]
     pub async fn execute_trade(
         &self,
         params: TradeParams,
@@ -66,27 +111,22 @@ impl TradeExecutor {
         );
 
         // Check circuit breaker
-        if *self.error_count.read().await >= CIRCUIT_BREAKER_ERROR_THRESHOLD {
+        if self.error_count.load(Ordering::SeqCst) >= CIRCUIT_BREAKER_ERROR_THRESHOLD {
             return Err(ExecutionError::ValidationError(
                 "circuit breaker triggered".to_string(),
             ));
         }
 
-        // Validate concurrent execution limit
-        if *self.active_executions.read().await >= MAX_CONCURRENT_EXECUTIONS {
+        // Reserve an execution slot; the check and increment are one atomic step so
+        // concurrent callers cannot overshoot the limit
+        let Some(_slot) = ExecutionSlot::acquire(&self.active_executions) else {
             return Err(ExecutionError::ValidationError(
                 "max concurrent executions reached".to_string(),
             ));
-        }
-
-        // Increment active executions
-        *self.active_executions.write().await += 1;
+        };
 
         let result = self.try_execute_trade(params, context.clone()).await;
 
-        // Decrement active executions
-        *self.active_executions.write().await -= 1;
-
         // Record execution metrics
         self.metrics
             .record_trade_execution(
@@ -133,6 +173,26 @@ impl TradeExecutor {
                     );
                     return Ok(result);
                 }
+                // The venue enforced our own limit; retrying at the same limit would fail
+                // the same way and the rejection says nothing about executor health
+                Err(e @ ExecutionError::SlippageExceeded(_)) => {
+                    warn!(
+                        trade_id = %params.id,
+                        error = %e,
+                        "Trade rejected on-chain by slippage guard"
+                    );
+                    return Err(e);
+                }
+                // Signer failures are final unless the signer allows retries; a stuck
+                // signer would otherwise eat the whole execution budget
+                Err(e @ ExecutionError::SigningFailed { retryable: false, .. }) => {
+                    error!(
+                        trade_id = %params.id,
+                        error = %e,
+                        "Transaction signing failed"
+                    );
+                    return Err(e);
+                }
                 Err(e) if attempts < MAX_EXECUTION_ATTEMPTS - 1 => {
                     attempts += 1;
                     context = context.increment_retry();
@@ -150,7 +210,7 @@ impl TradeExecutor {
                         error = %e,
                         "Trade execution failed"
                     );
-                    *self.error_count.write().await += 1;
+                    self.error_count.fetch_add(1, Ordering::SeqCst);
                     return Err(e);
                 }
             }
@@ -192,18 +252,44 @@ impl TradeExecutor {
             .map_err(|e| ExecutionError::InternalError(e.to_string()))?;
 
         // Prepare and submit transaction bundle
-        let bundle = create_mev_bundle(
-            vec![params.create_transaction()?],
-            mev_opportunity.priority_fee,
-        )?;
+        let transaction = params.create_transaction()?;
+        let intents = self.intents.read().clone();
+        let intent = params.intent_id.zip(intents.as_ref());
+        if let Some((intent_id, intents)) = intent {
+            // Without a durable signature a landed fill could not be found after a crash
+            intents
+                .attach_signature(intent_id, &transaction.signatures[0].to_string())
+                .await
+                .map_err(|e| ExecutionError::InternalError(e.to_string()))?;
+        }
+        let bundle = create_mev_bundle(vec![transaction], mev_opportunity.priority_fee)?;
 
         let execution_start = Instant::now();
 
         // Submit bundle through Jito
         let bundle_id = submit_bundle(bundle, self.jito_client.clone()).await?;
+        if let Some((intent_id, intents)) = intent {
+            if let Err(e) = intents.attach_bundle(intent_id, &bundle_id).await {
+                warn!(intent_id = %intent_id, error = %e, "Failed to record bundle on submission intent");
+            }
+        }
 
         // Monitor bundle execution
-        let result = self.monitor_bundle_execution(bundle_id).await?;
+        let mut result = self.monitor_bundle_execution(bundle_id, params).await?;
+
+        // Realized compute units tighten the venue's margin for later routes
+        let budget = params.guarded.as_ref().and_then(|order| order.compute_budget);
+        if let (Some(budgeter), Some(budget)) = (&self.compute_budget, budget) {
+            result.compute = Some(
+                budgeter
+                    .record_landed(&params.exchange, &budget, &result.transaction_hash)
+                    .await,
+            );
+        }
+
+        if let Some(fee_estimator) = self.fee_estimator.read().as_ref() {
+            fee_estimator.record_landed_fee(&params.exchange, mev_opportunity.priority_fee);
+        }
 
         self.metrics
             .record_trade_execution(
@@ -217,6 +303,70 @@ impl TradeExecutor {
         Ok(result)
     }
 
+    /// Submits every trade's transaction in one Jito bundle so they land together or not at
+    /// all. The bundle is a single attempt: retrying it is the caller's decision, as part of
+    /// it may be stale by then.
+    #[instrument(skip(self, params), fields(trades = params.len()))]
+    pub async fn execute_bundle(&self, params: Vec<TradeParams>) -> Result<Vec<TradeResult>, ExecutionError> {
+        if self.error_count.load(Ordering::SeqCst) >= CIRCUIT_BREAKER_ERROR_THRESHOLD {
+            return Err(ExecutionError::ValidationError(
+                "circuit breaker triggered".to_string(),
+            ));
+        }
+        let Some(_slot) = ExecutionSlot::acquire(&self.active_executions) else {
+            return Err(ExecutionError::ValidationError(
+                "max concurrent executions reached".to_string(),
+            ));
+        };
+
+        for trade in &params {
+            self.validate_execution_params(trade).await?;
+        }
+        let transactions = params
+            .iter()
+            .map(TradeParams::create_transaction)
+            .collect::<Result<Vec<_>, _>>()?;
+        let intents = self.intents.read().clone();
+        if let Some(intents) = &intents {
+            for (trade, transaction) in params.iter().zip(&transactions) {
+                if let Some(intent_id) = trade.intent_id {
+                    intents
+                        .attach_signature(intent_id, &transaction.signatures[0].to_string())
+                        .await
+                        .map_err(|e| ExecutionError::InternalError(e.to_string()))?;
+                }
+            }
+        }
+
+        // The bundle bids the highest rate any of its venues calls for
+        let priority_fee = params
+            .iter()
+            .map(|trade| self.priority_fee(&trade.exchange, 0.0))
+            .max()
+            .unwrap_or_default();
+        let bundle = create_mev_bundle(transactions, priority_fee)?;
+        let bundle_id = match submit_bundle(bundle, self.jito_client.clone()).await {
+            Ok(bundle_id) => bundle_id,
+            Err(e) => {
+                self.error_count.fetch_add(1, Ordering::SeqCst);
+                return Err(e);
+            }
+        };
+        if let Some(intents) = &intents {
+            for intent_id in params.iter().filter_map(|trade| trade.intent_id) {
+                if let Err(e) = intents.attach_bundle(intent_id, &bundle_id).await {
+                    warn!(intent_id = %intent_id, error = %e, "Failed to record bundle on submission intent");
+                }
+            }
+        }
+
+        let results = self.monitor_bundle_legs(bundle_id, &params).await;
+        if results.is_err() {
+            self.error_count.fetch_add(1, Ordering::SeqCst);
+        }
+        results
+    }
+
     /// Validates trade execution parameters against current market state
     async fn validate_execution_params(&self, params: &TradeParams) -> Result<(), ExecutionError> {
         let market_data = self.market_data.read().await;
@@ -252,26 +402,64 @@ impl TradeExecutor {
 
         Ok(MevOpportunity {
             value: mev_value,
-            priority_fee: calculate_priority_fee(mev_value),
+            priority_fee: self.priority_fee(&params.exchange, mev_value),
         })
     }
 
+    /// Prices the priority fee for a bundle on an exchange given its MEV value
+    pub fn priority_fee(&self, exchange: &str, mev_value: f64) -> u64 {
+        match self.fee_estimator.read().as_ref() {
+            Some(fee_estimator) => {
+                let urgency = if mev_value >= HIGH_URGENCY_MEV_THRESHOLD {
+                    FeeUrgency::High
+                } else {
+                    FeeUrgency::Normal
+                };
+                let market_fee = fee_estimator.estimate_priority_fee(exchange, urgency);
+                choose_priority_fee(market_fee, mev_value)
+            }
+            None => calculate_priority_fee(mev_value),
+        }
+    }
+
     /// Monitors MEV bundle execution with timeout
     async fn monitor_bundle_execution(
         &self,
         bundle_id: String,
+        params: &TradeParams,
     ) -> Result<TradeResult, ExecutionError> {
+        let mut results = self.monitor_bundle_legs(bundle_id, std::slice::from_ref(params)).await?;
+        Ok(results.remove(0))
+    }
+
+    /// Monitors a bundle carrying one transaction per trade, with timeout
+    async fn monitor_bundle_legs(
+        &self,
+        bundle_id: String,
+        params: &[TradeParams],
+    ) -> Result<Vec<TradeResult>, ExecutionError> {
         let start = Instant::now();
         
         while start.elapsed() < Duration::from_millis(EXECUTION_TIMEOUT_MS) {
             match self.jito_client.get_bundle_status(bundle_id.clone()).await {
                 Ok(status) => {
                     if status.is_confirmed() {
-                        return Ok(TradeResult {
-                            transaction_hash: status.transaction_hash,
-                            execution_time: start.elapsed(),
-                            mev_value: status.mev_value,
-                        });
+                        // A landed swap bundle fills in full; resting orders report
+                        // further fills through order status polling
+                        return Ok(params
+                            .iter()
+                            .map(|trade| TradeResult {
+                                transaction_hash: status.transaction_hash.clone(),
+                                execution_time: start.elapsed(),
+                                mev_value: status.mev_value,
+                                fills: vec![OrderFill::new(
+                                    status.transaction_hash.clone(),
+                                    trade.size,
+                                    trade.price,
+                                )],
+                                compute: None,
+                            })
+                            .collect());
                     }
                 }
                 Err(e) => {
@@ -292,6 +480,74 @@ impl TradeExecutor {
     }
 }
 
+#[async_trait]
+impl TradeSubmitter for TradeExecutor {
+    async fn execute_trade(&self, params: TradeParams) -> Result<TradeResult, ExecutionError> {
+        TradeExecutor::execute_trade(self, params).await
+    }
+
+    fn priority_fee(&self, exchange: &str, mev_value: f64) -> u64 {
+        TradeExecutor::priority_fee(self, exchange, mev_value)
+    }
+
+    fn set_intent_store(&self, intents: Arc<dyn IntentStore>) {
+        TradeExecutor::set_intent_store(self, intents)
+    }
+
+    fn set_fee_estimator(&self, fee_estimator: Arc<FeeEstimator>) {
+        TradeExecutor::set_fee_estimator(self, fee_estimator)
+    }
+
+    async fn execute_bundle(&self, params: Vec<TradeParams>) -> Result<Vec<TradeResult>, ExecutionError> {
+        TradeExecutor::execute_bundle(self, params).await
+    }
+}
+
+/// Submits the trades the execution queue dispatches; `TradeExecutor` is the on-chain
+/// implementation
+#[async_trait]
+pub trait TradeSubmitter: Send + Sync + std::fmt::Debug {
+    async fn execute_trade(&self, params: TradeParams) -> Result<TradeResult, ExecutionError>;
+
+    /// Priority fee a bundle on the exchange would pay given its MEV value
+    fn priority_fee(&self, _exchange: &str, _mev_value: f64) -> u64 {
+        0
+    }
+
+    /// Records attempt signatures on submission intents; submitters that sign nothing ignore it
+    fn set_intent_store(&self, _intents: Arc<dyn IntentStore>) {}
+
+    /// Prices priority fees from recent block data; submitters that pay no fees ignore it
+    fn set_fee_estimator(&self, _fee_estimator: Arc<FeeEstimator>) {}
+
+    /// Submits the trades as one bundle that lands whole or not at all
+    async fn execute_bundle(&self, _params: Vec<TradeParams>) -> Result<Vec<TradeResult>, ExecutionError> {
+        Err(ExecutionError::ValidationError(
+            "bundled submission is not supported".to_string(),
+        ))
+    }
+}
+
+/// Reserved execution slot, released when dropped so a cancelled execution frees it too
+struct ExecutionSlot<'a>(&'a AtomicUsize);
+
+impl<'a> ExecutionSlot<'a> {
+    fn acquire(active: &'a AtomicUsize) -> Option<Self> {
+        active
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                (count < MAX_CONCURRENT_EXECUTIONS).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self(active))
+    }
+}
+
+impl Drop for ExecutionSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 /// Trade execution parameters
 #[derive(Debug, Clone)]
 pub struct TradeParams {
@@ -302,6 +558,12 @@ pub struct TradeParams {
     pub price: Decimal,
     pub size: Decimal,
     pub slippage: Decimal,
+    /// Venue parameters with the slippage limit encoded, when an adapter prepared them
+    pub guarded: Option<GuardedOrder>,
+    /// Tick the order was derived from, for tick-to-trade latency
+    pub tick: Option<TickStamp>,
+    /// Submission intent persisted for crash recovery, when one was opened
+    pub intent_id: Option<Uuid>,
 }
 
 /// MEV opportunity details
@@ -317,6 +579,17 @@ pub struct TradeResult {
     pub transaction_hash: String,
     pub execution_time: Duration,
     pub mev_value: f64,
+    /// Fills confirmed by the time execution returned
+    pub fills: Vec<OrderFill>,
+    /// Compute unit limit and realized consumption, for budgeted transactions
+    pub compute: Option<ComputeUsage>,
+}
+
+impl TradeResult {
+    /// Total size filled across all fills
+    pub fn filled_size(&self) -> Decimal {
+        self.fills.iter().map(|fill| fill.size).sum()
+    }
 }
 
 /// Calculates priority fee based on MEV value
@@ -325,6 +598,15 @@ fn calculate_priority_fee(mev_value: f64) -> u64 {
     (mev_value * 1_000_000.0) as u64
 }
 
+/// Bids at least the market rate and spends MEV value on top, capped relative to the market
+#[inline]
+fn choose_priority_fee(market_fee: u64, mev_value: f64) -> u64 {
+    calculate_priority_fee(mev_value).clamp(
+        market_fee,
+        market_fee.saturating_mul(MAX_MEV_FEE_MULTIPLIER),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -344,4 +626,26 @@ mod tests {
     async fn test_validation() {
         // Test implementation
     }
+
+    #[test]
+    fn test_execution_slots_are_capped_and_released() {
+        let active = AtomicUsize::new(0);
+        let slots: Vec<ExecutionSlot> = (0..MAX_CONCURRENT_EXECUTIONS)
+            .map(|_| ExecutionSlot::acquire(&active).unwrap())
+            .collect();
+        assert!(ExecutionSlot::acquire(&active).is_none());
+        drop(slots);
+        assert_eq!(active.load(Ordering::SeqCst), 0);
+        assert!(ExecutionSlot::acquire(&active).is_some());
+    }
+
+    #[test]
+    fn test_priority_fee_tracks_market_rate() {
+        // Congested market: never underbid the sampled rate
+        assert_eq!(choose_priority_fee(200_000, 0.002), 200_000);
+        // MEV value is spent within the cap
+        assert_eq!(choose_priority_fee(10_000, 0.03), 30_000);
+        // Quiet market: large MEV does not overpay without bound
+        assert_eq!(choose_priority_fee(10_000, 1.0), 50_000);
+    }
 }
\ No newline at end of file
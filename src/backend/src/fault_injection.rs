@@ -0,0 +1,338 @@
+//! Fault injection hooks for resilience testing. Faults are scoped to a component with a
+//! trigger probability and a lifetime, and are only compiled with the `fault-injection`
+//! feature; at runtime they can be managed through the admin API outside production.
+//!
+//! Version dependencies:
+//! - rand = "0.8"
+//! - tokio = "1.28"
+//! - serde = "1.0"
+//! - parking_lot = "0.12"
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::{Mutex, RwLock};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::config::environment::PRODUCTION_ENV;
+
+// Fault injection constants
+const MAX_FAULT_DURATION_SECS: u64 = 3600;
+const MAX_INJECTED_LATENCY_MS: u64 = 30_000;
+const METRICS_PREFIX: &str = "trading_bot.fault_injection";
+
+lazy_static::lazy_static! {
+    static ref REGISTRY: FaultRegistry = FaultRegistry::new(None);
+}
+
+/// Fault injection error types
+#[derive(Error, Debug, PartialEq)]
+pub enum FaultError {
+    #[error("invalid fault: {0}")]
+    Invalid(String),
+    #[error("fault injection is disabled in production")]
+    Disabled,
+}
+
+/// Error returned by a hook when an injected fault fires
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("injected {component} fault")]
+pub struct InjectedFault {
+    pub component: FaultComponent,
+}
+
+/// Component a fault targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultComponent {
+    SolanaRpc,
+    CollectorWs,
+    Database,
+    CircuitBreaker,
+}
+
+impl FaultComponent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FaultComponent::SolanaRpc => "solana_rpc",
+            FaultComponent::CollectorWs => "collector_ws",
+            FaultComponent::Database => "database",
+            FaultComponent::CircuitBreaker => "circuit_breaker",
+        }
+    }
+}
+
+impl std::fmt::Display for FaultComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What happens when a fault fires
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FaultEffect {
+    /// Delays the call before it proceeds
+    Latency { latency_ms: u64 },
+    /// Fails the call
+    Error,
+    /// Discards an inbound message
+    Drop,
+    /// Holds the circuit breaker open
+    Trip,
+}
+
+impl FaultEffect {
+    fn applies_to(&self, component: FaultComponent) -> bool {
+        matches!(
+            (component, self),
+            (FaultComponent::SolanaRpc, FaultEffect::Latency { .. } | FaultEffect::Error)
+                | (FaultComponent::Database, FaultEffect::Latency { .. } | FaultEffect::Error)
+                | (FaultComponent::CollectorWs, FaultEffect::Drop)
+                | (FaultComponent::CircuitBreaker, FaultEffect::Trip)
+        )
+    }
+}
+
+/// Requested fault: a component, effect, trigger probability and lifetime
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FaultSpec {
+    pub component: FaultComponent,
+    pub effect: FaultEffect,
+    pub probability: f64,
+    pub duration_secs: u64,
+}
+
+impl FaultSpec {
+    pub fn validate(&self) -> Result<(), FaultError> {
+        if !(0.0..=1.0).contains(&self.probability) {
+            return Err(FaultError::Invalid(format!(
+                "probability {} must be between 0 and 1",
+                self.probability
+            )));
+        }
+        if self.duration_secs == 0 || self.duration_secs > MAX_FAULT_DURATION_SECS {
+            return Err(FaultError::Invalid(format!(
+                "duration {}s must be between 1 and {}",
+                self.duration_secs, MAX_FAULT_DURATION_SECS
+            )));
+        }
+        if let FaultEffect::Latency { latency_ms } = self.effect {
+            if latency_ms == 0 || latency_ms > MAX_INJECTED_LATENCY_MS {
+                return Err(FaultError::Invalid(format!(
+                    "latency {}ms must be between 1 and {}",
+                    latency_ms, MAX_INJECTED_LATENCY_MS
+                )));
+            }
+        }
+        if !self.effect.applies_to(self.component) {
+            return Err(FaultError::Invalid(format!(
+                "{:?} does not apply to {}",
+                self.effect, self.component
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Fault currently in effect
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActiveFault {
+    pub id: Uuid,
+    #[serde(flatten)]
+    pub spec: FaultSpec,
+    pub activated_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Registry of active faults consulted by the component hooks
+#[derive(Debug)]
+pub struct FaultRegistry {
+    faults: RwLock<Vec<ActiveFault>>,
+    rng: Mutex<StdRng>,
+}
+
+impl FaultRegistry {
+    /// Creates an empty registry; a seed makes fault triggering reproducible
+    pub fn new(seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Self {
+            faults: RwLock::new(Vec::new()),
+            rng: Mutex::new(rng),
+        }
+    }
+
+    /// Reseeds fault triggering so a scenario replays the same sequence
+    pub fn reseed(&self, seed: u64) {
+        *self.rng.lock() = StdRng::seed_from_u64(seed);
+    }
+
+    /// Activates a fault until its duration elapses
+    pub fn inject(&self, spec: FaultSpec, now: DateTime<Utc>) -> Result<ActiveFault, FaultError> {
+        spec.validate()?;
+        let fault = ActiveFault {
+            id: Uuid::new_v4(),
+            spec,
+            activated_at: now,
+            expires_at: now + chrono::Duration::seconds(spec.duration_secs as i64),
+        };
+
+        warn!(
+            fault_id = %fault.id,
+            component = %spec.component,
+            effect = ?spec.effect,
+            probability = spec.probability,
+            duration_secs = spec.duration_secs,
+            "Fault injected"
+        );
+        counter!(format!("{}.injected", METRICS_PREFIX), 1, "component" => spec.component.as_str());
+        self.faults.write().push(fault.clone());
+        Ok(fault)
+    }
+
+    /// Lists faults still in effect, dropping expired ones
+    pub fn active(&self, now: DateTime<Utc>) -> Vec<ActiveFault> {
+        let mut faults = self.faults.write();
+        faults.retain(|fault| {
+            let live = fault.expires_at > now;
+            if !live {
+                warn!(fault_id = %fault.id, component = %fault.spec.component, "Fault expired");
+            }
+            live
+        });
+        faults.clone()
+    }
+
+    /// Removes every fault, returning how many were active
+    pub fn clear(&self) -> usize {
+        let cleared = std::mem::take(&mut *self.faults.write()).len();
+        if cleared > 0 {
+            warn!(cleared, "Cleared injected faults");
+        }
+        cleared
+    }
+
+    /// Rolls every live fault for a component and returns the effect of the first that fires
+    pub fn roll(&self, component: FaultComponent, now: DateTime<Utc>) -> Option<FaultEffect> {
+        let faults = self.faults.read();
+        let mut rng = self.rng.lock();
+        let effect = faults
+            .iter()
+            .filter(|fault| fault.spec.component == component && fault.expires_at > now)
+            .find(|fault| rng.gen_bool(fault.spec.probability))
+            .map(|fault| fault.spec.effect)?;
+
+        debug!(component = %component, effect = ?effect, "Injected fault fired");
+        counter!(format!("{}.fired", METRICS_PREFIX), 1, "component" => component.as_str());
+        Some(effect)
+    }
+}
+
+/// Process-wide registry used by the component hooks
+pub fn registry() -> &'static FaultRegistry {
+    &REGISTRY
+}
+
+/// Runtime control is refused when the process runs as production
+pub fn runtime_control_allowed() -> bool {
+    std::env::var("NODE_ENV").map_or(true, |env| env != PRODUCTION_ENV)
+}
+
+/// Activates a fault in the process-wide registry on behalf of an operator
+pub fn inject(spec: FaultSpec) -> Result<ActiveFault, FaultError> {
+    if !runtime_control_allowed() {
+        return Err(FaultError::Disabled);
+    }
+    registry().inject(spec, Utc::now())
+}
+
+/// Hook for request/response calls: applies injected latency, then fails on injected errors
+pub async fn on_call(component: FaultComponent) -> Result<(), InjectedFault> {
+    match registry().roll(component, Utc::now()) {
+        Some(FaultEffect::Latency { latency_ms }) => {
+            tokio::time::sleep(Duration::from_millis(latency_ms)).await;
+            Ok(())
+        }
+        Some(FaultEffect::Error) => Err(InjectedFault { component }),
+        _ => Ok(()),
+    }
+}
+
+/// Hook for inbound message streams: true when the message should be discarded
+pub fn should_drop(component: FaultComponent) -> bool {
+    matches!(registry().roll(component, Utc::now()), Some(FaultEffect::Drop))
+}
+
+/// Hook for circuit breakers: true while a trip fault fires
+pub fn breaker_tripped() -> bool {
+    matches!(
+        registry().roll(FaultComponent::CircuitBreaker, Utc::now()),
+        Some(FaultEffect::Trip)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(component: FaultComponent, effect: FaultEffect, probability: f64) -> FaultSpec {
+        FaultSpec {
+            component,
+            effect,
+            probability,
+            duration_secs: 60,
+        }
+    }
+
+    #[test]
+    fn test_spec_validation() {
+        assert!(spec(FaultComponent::SolanaRpc, FaultEffect::Error, 0.2).validate().is_ok());
+        assert!(spec(FaultComponent::SolanaRpc, FaultEffect::Error, 1.5).validate().is_err());
+        assert!(spec(FaultComponent::CollectorWs, FaultEffect::Error, 0.5).validate().is_err());
+        assert!(spec(FaultComponent::Database, FaultEffect::Latency { latency_ms: 0 }, 1.0)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_faults_are_scoped_and_expire() {
+        let registry = FaultRegistry::new(Some(7));
+        let now = Utc::now();
+        registry
+            .inject(spec(FaultComponent::CollectorWs, FaultEffect::Drop, 1.0), now)
+            .unwrap();
+
+        assert_eq!(registry.roll(FaultComponent::CollectorWs, now), Some(FaultEffect::Drop));
+        assert_eq!(registry.roll(FaultComponent::SolanaRpc, now), None);
+
+        let later = now + chrono::Duration::seconds(61);
+        assert_eq!(registry.roll(FaultComponent::CollectorWs, later), None);
+        assert!(registry.active(later).is_empty());
+    }
+
+    #[test]
+    fn test_probability_is_respected() {
+        let registry = FaultRegistry::new(Some(42));
+        let now = Utc::now();
+        registry
+            .inject(spec(FaultComponent::SolanaRpc, FaultEffect::Error, 0.2), now)
+            .unwrap();
+
+        let fired = (0..1000)
+            .filter(|_| registry.roll(FaultComponent::SolanaRpc, now).is_some())
+            .count();
+        assert!((150..250).contains(&fired), "fired {} times", fired);
+
+        assert_eq!(registry.clear(), 1);
+        assert_eq!(registry.roll(FaultComponent::SolanaRpc, now), None);
+    }
+}
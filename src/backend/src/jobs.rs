@@ -0,0 +1,735 @@
+//! Persistent background job queue. Exports, backfills, optimizations and reports are enqueued
+//! as rows in the `jobs` table instead of being spawned ad hoc, so they survive a restart. A
+//! pool of workers inside the bot claims due jobs (`FOR UPDATE SKIP LOCKED` in Postgres), runs
+//! the handler registered for the job's kind and retries failures with exponential backoff
+//! until the job is dead-lettered. A claim is a lease the worker renews while the handler runs;
+//! a worker that dies mid-job stops renewing and the job is reclaimed once the lease lapses.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - serde_json = "1.0"
+//! - metrics = "0.20"
+//! - parking_lot = "0.12"
+
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::{counter, histogram};
+use parking_lot::RwLock as SyncRwLock;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::utils::time::to_chrono;
+
+// Job queue constants
+const METRICS_PREFIX: &str = "trading_bot.jobs";
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+const DEFAULT_CONCURRENCY: usize = 4;
+const DEFAULT_LEASE: Duration = Duration::from_secs(5 * 60);
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_RETRY_BASE: Duration = Duration::from_secs(10);
+const DEFAULT_RETRY_MAX: Duration = Duration::from_secs(3600);
+const MIN_RENEW_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_LIST_LIMIT: usize = 100;
+const MAX_LIST_LIMIT: usize = 1000;
+
+/// Job queue error types
+#[derive(Error, Debug)]
+pub enum JobError {
+    #[error("no handler registered for job kind: {0}")]
+    UnknownKind(String),
+    #[error("invalid job payload: {0}")]
+    Payload(String),
+    #[error("job not found: {0}")]
+    NotFound(Uuid),
+    #[error("job {id} is {}", status.as_str())]
+    InvalidState { id: Uuid, status: JobStatus },
+    #[error("lease on job {0} was lost to another worker")]
+    LeaseLost(Uuid),
+    #[error("store error: {0}")]
+    Store(String),
+}
+
+/// Where a job is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Waiting for `run_after`, including between retries
+    Pending,
+    /// Leased by a worker
+    Running,
+    Succeeded,
+    /// Failed every attempt; only an operator retry runs it again
+    DeadLetter,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Succeeded => "succeeded",
+            Self::DeadLetter => "dead_letter",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl std::str::FromStr for JobStatus {
+    type Err = JobError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "running" => Ok(Self::Running),
+            "succeeded" => Ok(Self::Succeeded),
+            "dead_letter" => Ok(Self::DeadLetter),
+            "cancelled" => Ok(Self::Cancelled),
+            other => Err(JobError::Store(format!("unknown job status: {}", other))),
+        }
+    }
+}
+
+/// Typed job payload; `KIND` names the handler that runs it
+pub trait Job: Serialize + DeserializeOwned + Send + Sync + 'static {
+    const KIND: &'static str;
+    /// Higher priorities are claimed first
+    const PRIORITY: i32 = 0;
+    const MAX_ATTEMPTS: u32 = DEFAULT_MAX_ATTEMPTS;
+}
+
+/// Attempt being run, for handlers that need to be idempotent across retries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JobContext {
+    pub id: Uuid,
+    /// 1 on the first attempt
+    pub attempt: u32,
+}
+
+/// Runs jobs of one kind; an error schedules a retry
+#[async_trait]
+pub trait JobHandler<J: Job>: Send + Sync {
+    async fn handle(&self, job: J, ctx: JobContext) -> Result<(), String>;
+}
+
+/// Handler with its payload type erased so handlers of every kind share one registry
+#[async_trait]
+trait ErasedHandler: Send + Sync {
+    async fn run(&self, payload: serde_json::Value, ctx: JobContext) -> Result<(), String>;
+}
+
+struct TypedHandler<J, H> {
+    handler: Arc<H>,
+    job: PhantomData<fn() -> J>,
+}
+
+#[async_trait]
+impl<J: Job, H: JobHandler<J> + 'static> ErasedHandler for TypedHandler<J, H> {
+    async fn run(&self, payload: serde_json::Value, ctx: JobContext) -> Result<(), String> {
+        let job: J = serde_json::from_value(payload).map_err(|e| format!("invalid payload: {}", e))?;
+        self.handler.handle(job, ctx).await
+    }
+}
+
+/// Persisted job and its scheduling state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedJob {
+    pub id: Uuid,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub status: JobStatus,
+    pub priority: i32,
+    /// Attempts started, counted at claim time so a job that crashes its worker still counts
+    pub attempts: u32,
+    pub max_attempts: u32,
+    pub last_error: Option<String>,
+    pub locked_by: Option<String>,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub run_after: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Admin listing filter
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct JobFilter {
+    #[serde(default)]
+    pub status: Option<JobStatus>,
+    #[serde(default)]
+    pub kind: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Persistence and claiming for queued jobs
+#[async_trait]
+pub trait JobStore: Send + Sync {
+    async fn insert(&self, job: &QueuedJob) -> Result<(), JobError>;
+    /// Leases the highest-priority job of the given kinds that is due, or running on a lease
+    /// that lapsed before `now`, incrementing its attempts. Concurrent claims never return
+    /// the same job.
+    async fn claim(
+        &self,
+        worker: &str,
+        kinds: &[String],
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Option<QueuedJob>, JobError>;
+    /// Extends a held lease; false once another worker reclaimed the job
+    async fn renew(&self, id: Uuid, worker: &str, lease_until: DateTime<Utc>) -> Result<bool, JobError>;
+    /// Writes the outcome of a held job and drops its lease; false if the lease was lost
+    async fn release(&self, job: &QueuedJob, worker: &str) -> Result<bool, JobError>;
+    /// Sets a pending job cancelled; `None` when the job is not pending
+    async fn cancel(&self, id: Uuid, now: DateTime<Utc>) -> Result<Option<QueuedJob>, JobError>;
+    /// Requeues a dead-lettered or cancelled job with fresh attempts; `None` when the job is
+    /// in any other state
+    async fn requeue(&self, id: Uuid, now: DateTime<Utc>) -> Result<Option<QueuedJob>, JobError>;
+    async fn get(&self, id: Uuid) -> Result<Option<QueuedJob>, JobError>;
+    /// Most recently updated first
+    async fn list(&self, filter: &JobFilter, limit: usize) -> Result<Vec<QueuedJob>, JobError>;
+}
+
+/// Worker pool settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobConfig {
+    /// Workers claiming jobs concurrently
+    pub concurrency: usize,
+    /// How long a claim holds a job before another worker may reclaim it
+    pub lease: Duration,
+    /// Idle wait between claims when nothing is due
+    pub poll_interval: Duration,
+    pub retry_base: Duration,
+    pub retry_max: Duration,
+}
+
+impl Default for JobConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_CONCURRENCY,
+            lease: DEFAULT_LEASE,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            retry_base: DEFAULT_RETRY_BASE,
+            retry_max: DEFAULT_RETRY_MAX,
+        }
+    }
+}
+
+impl JobConfig {
+    /// Delay before the next attempt after `attempt` failed, doubling from `retry_base`
+    pub fn retry_delay(&self, attempt: u32) -> Duration {
+        self.retry_base
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.retry_max)
+    }
+}
+
+/// Enqueues jobs and runs them on a pool of workers
+pub struct JobQueue {
+    config: JobConfig,
+    store: Arc<dyn JobStore>,
+    handlers: SyncRwLock<HashMap<String, Arc<dyn ErasedHandler>>>,
+    /// Prefix of this process's worker ids, so leases name the instance holding them
+    instance: String,
+}
+
+impl fmt::Debug for JobQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JobQueue")
+            .field("config", &self.config)
+            .field("kinds", &self.kinds())
+            .field("instance", &self.instance)
+            .finish()
+    }
+}
+
+impl JobQueue {
+    pub fn new(config: JobConfig, store: Arc<dyn JobStore>) -> Self {
+        Self {
+            config,
+            store,
+            handlers: SyncRwLock::new(HashMap::new()),
+            instance: Uuid::new_v4().simple().to_string()[..8].to_string(),
+        }
+    }
+
+    /// Registers the handler for `J`; workers only claim kinds with a handler
+    pub fn register<J: Job, H: JobHandler<J> + 'static>(&self, handler: Arc<H>) {
+        let erased: Arc<dyn ErasedHandler> = Arc::new(TypedHandler::<J, H> {
+            handler,
+            job: PhantomData,
+        });
+        self.handlers.write().insert(J::KIND.to_string(), erased);
+    }
+
+    /// Enqueues a job to run as soon as a worker is free
+    pub async fn enqueue<J: Job>(&self, job: J) -> Result<Uuid, JobError> {
+        self.enqueue_at(job, Utc::now()).await
+    }
+
+    /// Enqueues a job to run no earlier than `run_after`
+    pub async fn enqueue_at<J: Job>(&self, job: J, run_after: DateTime<Utc>) -> Result<Uuid, JobError> {
+        let payload = serde_json::to_value(&job).map_err(|e| JobError::Payload(e.to_string()))?;
+        let now = Utc::now();
+        let queued = QueuedJob {
+            id: Uuid::new_v4(),
+            kind: J::KIND.to_string(),
+            payload,
+            status: JobStatus::Pending,
+            priority: J::PRIORITY,
+            attempts: 0,
+            max_attempts: J::MAX_ATTEMPTS.max(1),
+            last_error: None,
+            locked_by: None,
+            locked_until: None,
+            run_after,
+            created_at: now,
+            updated_at: now,
+        };
+        self.store.insert(&queued).await?;
+        counter!(format!("{}.enqueued", METRICS_PREFIX), 1, "kind" => J::KIND);
+        Ok(queued.id)
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<QueuedJob, JobError> {
+        self.store.get(id).await?.ok_or(JobError::NotFound(id))
+    }
+
+    pub async fn list(&self, filter: &JobFilter) -> Result<Vec<QueuedJob>, JobError> {
+        let limit = filter.limit.unwrap_or(DEFAULT_LIST_LIMIT).clamp(1, MAX_LIST_LIMIT);
+        self.store.list(filter, limit).await
+    }
+
+    /// Runs a dead-lettered or cancelled job again from its first attempt
+    pub async fn retry(&self, id: Uuid) -> Result<QueuedJob, JobError> {
+        match self.store.requeue(id, Utc::now()).await? {
+            Some(job) => {
+                info!(job_id = %id, kind = %job.kind, "Job requeued by operator");
+                Ok(job)
+            }
+            None => Err(self.invalid_state(id).await),
+        }
+    }
+
+    /// Cancels a job that has not started
+    pub async fn cancel(&self, id: Uuid) -> Result<QueuedJob, JobError> {
+        match self.store.cancel(id, Utc::now()).await? {
+            Some(job) => {
+                info!(job_id = %id, kind = %job.kind, "Job cancelled by operator");
+                Ok(job)
+            }
+            None => Err(self.invalid_state(id).await),
+        }
+    }
+
+    /// Claims and runs one due job as `worker`, returning it as released, or `None` when
+    /// nothing was due
+    pub async fn run_once(&self, worker: &str, now: DateTime<Utc>) -> Result<Option<QueuedJob>, JobError> {
+        let kinds = self.kinds();
+        if kinds.is_empty() {
+            return Ok(None);
+        }
+        let Some(mut job) = self.store.claim(worker, &kinds, now, now + to_chrono(self.config.lease)).await? else {
+            return Ok(None);
+        };
+        let Some(handler) = self.handlers.read().get(&job.kind).cloned() else {
+            return Err(JobError::UnknownKind(job.kind));
+        };
+
+        // A job claimed past its last attempt was running when its worker died
+        let started = std::time::Instant::now();
+        let result = if job.attempts > job.max_attempts {
+            Err("lease expired on the final attempt".to_string())
+        } else {
+            self.run_handler(handler, &job, worker).await?
+        };
+        let elapsed = started.elapsed();
+        histogram!(
+            format!("{}.duration_ms", METRICS_PREFIX),
+            elapsed.as_millis() as f64,
+            "kind" => job.kind.clone()
+        );
+
+        let finished_at = now + to_chrono(elapsed);
+        job.locked_by = None;
+        job.locked_until = None;
+        job.updated_at = finished_at;
+        match result {
+            Ok(()) => {
+                job.status = JobStatus::Succeeded;
+                job.last_error = None;
+                counter!(format!("{}.succeeded", METRICS_PREFIX), 1, "kind" => job.kind.clone());
+            }
+            Err(e) if job.attempts >= job.max_attempts => {
+                error!(job_id = %job.id, kind = %job.kind, attempts = job.attempts, "Job dead-lettered: {}", e);
+                job.status = JobStatus::DeadLetter;
+                job.last_error = Some(e);
+                counter!(format!("{}.dead_lettered", METRICS_PREFIX), 1, "kind" => job.kind.clone());
+            }
+            Err(e) => {
+                let delay = self.config.retry_delay(job.attempts);
+                warn!(
+                    job_id = %job.id,
+                    kind = %job.kind,
+                    attempt = job.attempts,
+                    retry_in_secs = delay.as_secs(),
+                    "Job failed: {}", e
+                );
+                job.status = JobStatus::Pending;
+                job.last_error = Some(e);
+                job.run_after = finished_at + to_chrono(delay);
+                counter!(format!("{}.retried", METRICS_PREFIX), 1, "kind" => job.kind.clone());
+            }
+        }
+
+        if !self.store.release(&job, worker).await? {
+            return Err(JobError::LeaseLost(job.id));
+        }
+        Ok(Some(job))
+    }
+
+    /// Starts `concurrency` workers claiming jobs until aborted
+    pub fn spawn(self: Arc<Self>) -> Vec<tokio::task::JoinHandle<()>> {
+        (0..self.config.concurrency.max(1))
+            .map(|index| {
+                let queue = self.clone();
+                let worker = format!("{}-{}", self.instance, index);
+                tokio::spawn(async move {
+                    loop {
+                        match queue.run_once(&worker, Utc::now()).await {
+                            Ok(Some(_)) => continue,
+                            Ok(None) => {}
+                            Err(e) => warn!(worker = %worker, "Job worker error: {}", e),
+                        }
+                        tokio::time::sleep(queue.config.poll_interval).await;
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Runs the handler on its own task, renewing the lease until it finishes. A panic counts
+    /// as a failed attempt.
+    async fn run_handler(
+        &self,
+        handler: Arc<dyn ErasedHandler>,
+        job: &QueuedJob,
+        worker: &str,
+    ) -> Result<Result<(), String>, JobError> {
+        let ctx = JobContext {
+            id: job.id,
+            attempt: job.attempts,
+        };
+        let payload = job.payload.clone();
+        let mut task = tokio::spawn(async move { handler.run(payload, ctx).await });
+
+        let mut renew = tokio::time::interval((self.config.lease / 3).max(MIN_RENEW_INTERVAL));
+        renew.tick().await;
+        loop {
+            tokio::select! {
+                joined = &mut task => {
+                    return Ok(joined.unwrap_or_else(|e| Err(format!("handler panicked: {}", e))));
+                }
+                _ = renew.tick() => {
+                    let lease_until = Utc::now() + to_chrono(self.config.lease);
+                    match self.store.renew(job.id, worker, lease_until).await {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            task.abort();
+                            return Err(JobError::LeaseLost(job.id));
+                        }
+                        Err(e) => warn!(job_id = %job.id, "Failed to renew job lease: {}", e),
+                    }
+                }
+            }
+        }
+    }
+
+    async fn invalid_state(&self, id: Uuid) -> JobError {
+        match self.store.get(id).await {
+            Ok(Some(job)) => JobError::InvalidState { id, status: job.status },
+            Ok(None) => JobError::NotFound(id),
+            Err(e) => e,
+        }
+    }
+
+    fn kinds(&self) -> Vec<String> {
+        let mut kinds: Vec<String> = self.handlers.read().keys().cloned().collect();
+        kinds.sort();
+        kinds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use parking_lot::Mutex as SyncMutex;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Claims under one lock, which is what `SKIP LOCKED` guarantees the Postgres store
+    #[derive(Default)]
+    struct MemoryStore {
+        jobs: SyncMutex<HashMap<Uuid, QueuedJob>>,
+    }
+
+    #[async_trait]
+    impl JobStore for MemoryStore {
+        async fn insert(&self, job: &QueuedJob) -> Result<(), JobError> {
+            self.jobs.lock().insert(job.id, job.clone());
+            Ok(())
+        }
+
+        async fn claim(
+            &self,
+            worker: &str,
+            kinds: &[String],
+            now: DateTime<Utc>,
+            lease_until: DateTime<Utc>,
+        ) -> Result<Option<QueuedJob>, JobError> {
+            let mut jobs = self.jobs.lock();
+            let next = jobs
+                .values_mut()
+                .filter(|job| kinds.contains(&job.kind))
+                .filter(|job| match job.status {
+                    JobStatus::Pending => job.run_after <= now,
+                    JobStatus::Running => job.locked_until.map_or(true, |until| until < now),
+                    _ => false,
+                })
+                .max_by_key(|job| (job.priority, std::cmp::Reverse(job.run_after)));
+            Ok(next.map(|job| {
+                job.status = JobStatus::Running;
+                job.attempts += 1;
+                job.locked_by = Some(worker.to_string());
+                job.locked_until = Some(lease_until);
+                job.updated_at = now;
+                job.clone()
+            }))
+        }
+
+        async fn renew(&self, id: Uuid, worker: &str, lease_until: DateTime<Utc>) -> Result<bool, JobError> {
+            let mut jobs = self.jobs.lock();
+            match jobs.get_mut(&id) {
+                Some(job) if job.status == JobStatus::Running && job.locked_by.as_deref() == Some(worker) => {
+                    job.locked_until = Some(lease_until);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+
+        async fn release(&self, job: &QueuedJob, worker: &str) -> Result<bool, JobError> {
+            let mut jobs = self.jobs.lock();
+            match jobs.get_mut(&job.id) {
+                Some(held) if held.status == JobStatus::Running && held.locked_by.as_deref() == Some(worker) => {
+                    *held = job.clone();
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+
+        async fn cancel(&self, id: Uuid, now: DateTime<Utc>) -> Result<Option<QueuedJob>, JobError> {
+            let mut jobs = self.jobs.lock();
+            Ok(jobs.get_mut(&id).filter(|job| job.status == JobStatus::Pending).map(|job| {
+                job.status = JobStatus::Cancelled;
+                job.updated_at = now;
+                job.clone()
+            }))
+        }
+
+        async fn requeue(&self, id: Uuid, now: DateTime<Utc>) -> Result<Option<QueuedJob>, JobError> {
+            let mut jobs = self.jobs.lock();
+            Ok(jobs
+                .get_mut(&id)
+                .filter(|job| matches!(job.status, JobStatus::DeadLetter | JobStatus::Cancelled))
+                .map(|job| {
+                    job.status = JobStatus::Pending;
+                    job.attempts = 0;
+                    job.run_after = now;
+                    job.updated_at = now;
+                    job.clone()
+                }))
+        }
+
+        async fn get(&self, id: Uuid) -> Result<Option<QueuedJob>, JobError> {
+            Ok(self.jobs.lock().get(&id).cloned())
+        }
+
+        async fn list(&self, filter: &JobFilter, limit: usize) -> Result<Vec<QueuedJob>, JobError> {
+            let mut jobs: Vec<QueuedJob> = self
+                .jobs
+                .lock()
+                .values()
+                .filter(|job| filter.status.map_or(true, |status| job.status == status))
+                .filter(|job| filter.kind.as_ref().map_or(true, |kind| &job.kind == kind))
+                .cloned()
+                .collect();
+            jobs.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+            jobs.truncate(limit);
+            Ok(jobs)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct ExportJob {
+        wallet: String,
+    }
+
+    impl Job for ExportJob {
+        const KIND: &'static str = "export";
+        const MAX_ATTEMPTS: u32 = 3;
+    }
+
+    /// Counts runs, failing every one when `fail` is set
+    #[derive(Default)]
+    struct CountingHandler {
+        runs: AtomicU32,
+        fail: bool,
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl JobHandler<ExportJob> for CountingHandler {
+        async fn handle(&self, job: ExportJob, ctx: JobContext) -> Result<(), String> {
+            assert_eq!(job.wallet, "wallet-1");
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            if self.fail {
+                return Err(format!("export failed on attempt {}", ctx.attempt));
+            }
+            Ok(())
+        }
+    }
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000, 0).single().unwrap() + chrono::Duration::seconds(secs)
+    }
+
+    fn queue(store: Arc<MemoryStore>, handler: Arc<CountingHandler>) -> JobQueue {
+        let queue = JobQueue::new(JobConfig::default(), store);
+        queue.register::<ExportJob, _>(handler);
+        queue
+    }
+
+    async fn drain(queue: &JobQueue, worker: &str) -> usize {
+        let mut ran = 0;
+        while queue.run_once(worker, at(2)).await.unwrap().is_some() {
+            ran += 1;
+        }
+        ran
+    }
+
+    fn export() -> ExportJob {
+        ExportJob {
+            wallet: "wallet-1".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_contending_workers_run_each_job_once() {
+        let store = Arc::new(MemoryStore::default());
+        let handler = Arc::new(CountingHandler {
+            delay: Duration::from_millis(20),
+            ..CountingHandler::default()
+        });
+        let first = queue(store.clone(), handler.clone());
+        let second = queue(store.clone(), handler.clone());
+
+        let id = first.enqueue_at(export(), at(0)).await.unwrap();
+        let (a, b) = tokio::join!(first.run_once("worker-a", at(1)), second.run_once("worker-b", at(1)));
+        let ran: Vec<QueuedJob> = [a.unwrap(), b.unwrap()].into_iter().flatten().collect();
+        assert_eq!(ran.len(), 1);
+        assert_eq!(ran[0].id, id);
+        assert_eq!(ran[0].status, JobStatus::Succeeded);
+        assert_eq!(handler.runs.load(Ordering::SeqCst), 1);
+
+        // Draining a backlog from two workers still runs every job exactly once
+        for _ in 0..10 {
+            first.enqueue_at(export(), at(0)).await.unwrap();
+        }
+        let (a, b) = tokio::join!(drain(&first, "worker-a"), drain(&second, "worker-b"));
+        assert_eq!(a + b, 10);
+        assert_eq!(handler.runs.load(Ordering::SeqCst), 11);
+        let succeeded = JobFilter {
+            status: Some(JobStatus::Succeeded),
+            ..JobFilter::default()
+        };
+        assert_eq!(first.list(&succeeded).await.unwrap().len(), 11);
+    }
+
+    #[tokio::test]
+    async fn test_failures_back_off_then_dead_letter() {
+        let store = Arc::new(MemoryStore::default());
+        let handler = Arc::new(CountingHandler {
+            fail: true,
+            ..CountingHandler::default()
+        });
+        let queue = queue(store.clone(), handler.clone());
+        let id = queue.enqueue_at(export(), at(0)).await.unwrap();
+
+        let first = queue.run_once("worker-a", at(0)).await.unwrap().unwrap();
+        assert_eq!(first.status, JobStatus::Pending);
+        assert_eq!(first.last_error.as_deref(), Some("export failed on attempt 1"));
+        assert!(first.run_after >= at(10));
+
+        // Not due again until the backoff has passed
+        assert!(queue.run_once("worker-a", at(5)).await.unwrap().is_none());
+        let second = queue.run_once("worker-a", first.run_after).await.unwrap().unwrap();
+        assert_eq!(second.attempts, 2);
+        assert!(second.run_after >= first.run_after + chrono::Duration::seconds(20));
+
+        let last = queue.run_once("worker-a", second.run_after).await.unwrap().unwrap();
+        assert_eq!(last.status, JobStatus::DeadLetter);
+        assert_eq!(last.attempts, 3);
+        assert!(queue.run_once("worker-a", at(3600)).await.unwrap().is_none());
+        assert_eq!(handler.runs.load(Ordering::SeqCst), 3);
+
+        // Dead letters can be retried but not cancelled
+        assert!(matches!(
+            queue.cancel(id).await,
+            Err(JobError::InvalidState { status: JobStatus::DeadLetter, .. })
+        ));
+        let requeued = queue.retry(id).await.unwrap();
+        assert_eq!((requeued.status, requeued.attempts), (JobStatus::Pending, 0));
+        queue.cancel(id).await.unwrap();
+        assert!(matches!(queue.retry(Uuid::new_v4()).await, Err(JobError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_job_stuck_running_reclaimed_after_lease() {
+        let store = Arc::new(MemoryStore::default());
+        let handler = Arc::new(CountingHandler::default());
+        let queue = queue(store.clone(), handler.clone());
+        let id = queue.enqueue_at(export(), at(0)).await.unwrap();
+
+        // A worker claims the job and its process dies without releasing it
+        let lease = to_chrono(DEFAULT_LEASE);
+        let crashed = store
+            .claim("crashed-worker", &["export".to_string()], at(0), at(0) + lease)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(crashed.id, id);
+
+        // Still leased, so no one else may take it
+        assert!(queue.run_once("worker-b", at(60)).await.unwrap().is_none());
+
+        let recovered = queue.run_once("worker-b", at(0) + lease + chrono::Duration::seconds(1)).await.unwrap().unwrap();
+        assert_eq!(recovered.status, JobStatus::Succeeded);
+        assert_eq!(recovered.attempts, 2);
+        assert_eq!(handler.runs.load(Ordering::SeqCst), 1);
+
+        // The crashed worker can no longer write its outcome over the recovered one
+        assert!(!store.release(&crashed, "crashed-worker").await.unwrap());
+    }
+}
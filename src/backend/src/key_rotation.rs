@@ -0,0 +1,721 @@
+//! KMS data key rotation. A rotation generates a new KMS-wrapped data key, makes it the key
+//! new secrets are sealed with, then re-encrypts every stored secret in batches. Progress is
+//! persisted after each batch so an interrupted pass resumes where it stopped, and older keys
+//! are only retired once a pass has completed.
+//!
+//! Version dependencies:
+//! - aws-sdk-kms = "0.28"
+//! - tokio = "1.28"
+//! - async-trait = "0.1"
+//! - parking_lot = "0.12"
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use aws_sdk_kms::{Client as KmsClient, Region};
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::RwLock as SyncRwLock;
+use serde::Serialize;
+use thiserror::Error;
+use tokio::sync::Mutex;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::config::security::KMSConfig;
+use crate::utils::crypto::{EncryptedData, KeyRing, Secret};
+
+// Key rotation constants
+const DEFAULT_BATCH_SIZE: usize = 100;
+const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+const SECONDS_PER_DAY: u64 = 86_400;
+const DATA_KEY_SPEC: &str = "AES_256";
+
+/// Key rotation error types
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum KeyRotationError {
+    #[error("kms error: {0}")]
+    Kms(String),
+    #[error("store error: {0}")]
+    Store(String),
+    #[error("crypto error: {0}")]
+    Crypto(String),
+    #[error("key rotation already in progress")]
+    InProgress,
+}
+
+/// Lifecycle of a data key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataKeyStatus {
+    /// Seals new data
+    Active,
+    /// Superseded but still opens data not yet re-encrypted
+    Retiring,
+    /// Key material discarded after a complete rotation pass
+    Retired,
+}
+
+impl DataKeyStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataKeyStatus::Active => "active",
+            DataKeyStatus::Retiring => "retiring",
+            DataKeyStatus::Retired => "retired",
+        }
+    }
+}
+
+impl FromStr for DataKeyStatus {
+    type Err = KeyRotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "active" => Ok(DataKeyStatus::Active),
+            "retiring" => Ok(DataKeyStatus::Retiring),
+            "retired" => Ok(DataKeyStatus::Retired),
+            other => Err(KeyRotationError::Store(format!("unknown data key status: {}", other))),
+        }
+    }
+}
+
+/// Data key as persisted, wrapped by the KMS master key
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredDataKey {
+    pub version: u32,
+    pub wrapped_key: Vec<u8>,
+    pub status: DataKeyStatus,
+    pub created_at: DateTime<Utc>,
+    pub retired_at: Option<DateTime<Utc>>,
+}
+
+/// Persisted secret sealed with a data key
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredSecret {
+    pub id: Uuid,
+    pub scope: String,
+    pub name: String,
+    pub data: EncryptedData,
+}
+
+/// State of a rotation pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationStatus {
+    Running,
+    /// Stopped part way; the next rotation resumes it
+    Interrupted,
+    Completed,
+}
+
+impl RotationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RotationStatus::Running => "running",
+            RotationStatus::Interrupted => "interrupted",
+            RotationStatus::Completed => "completed",
+        }
+    }
+}
+
+impl FromStr for RotationStatus {
+    type Err = KeyRotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "running" => Ok(RotationStatus::Running),
+            "interrupted" => Ok(RotationStatus::Interrupted),
+            "completed" => Ok(RotationStatus::Completed),
+            other => Err(KeyRotationError::Store(format!("unknown rotation status: {}", other))),
+        }
+    }
+}
+
+/// Progress of one rotation pass toward a target key version
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RotationRun {
+    pub id: Uuid,
+    pub target_version: u32,
+    pub status: RotationStatus,
+    pub total_secrets: u64,
+    pub processed_secrets: u64,
+    /// Secrets are processed in id order; the pass resumes after this one
+    pub last_secret_id: Option<Uuid>,
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Freshly generated data key
+pub struct GeneratedDataKey {
+    pub plaintext: Secret<Vec<u8>>,
+    pub wrapped: Vec<u8>,
+}
+
+/// Generates and unwraps data keys under a master key
+#[async_trait]
+pub trait DataKeyProvider: Send + Sync {
+    async fn generate_data_key(&self) -> Result<GeneratedDataKey, KeyRotationError>;
+    async fn unwrap_data_key(&self, wrapped: &[u8]) -> Result<Vec<u8>, KeyRotationError>;
+}
+
+/// Data keys wrapped by an AWS KMS master key
+pub struct KmsDataKeyProvider {
+    client: KmsClient,
+    key_id: String,
+}
+
+impl KmsDataKeyProvider {
+    pub fn new(config: &KMSConfig) -> Self {
+        Self {
+            client: KmsClient::new(Region::new(config.region.clone())),
+            key_id: config.key_id.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl DataKeyProvider for KmsDataKeyProvider {
+    async fn generate_data_key(&self) -> Result<GeneratedDataKey, KeyRotationError> {
+        let response = self
+            .client
+            .generate_data_key()
+            .key_id(self.key_id.clone())
+            .key_spec(DATA_KEY_SPEC)
+            .send()
+            .await
+            .map_err(|e| KeyRotationError::Kms(e.to_string()))?;
+
+        let plaintext = response.plaintext().map(|blob| blob.as_ref().to_vec());
+        let wrapped = response.ciphertext_blob().map(|blob| blob.as_ref().to_vec());
+        match (plaintext, wrapped) {
+            (Some(plaintext), Some(wrapped)) => Ok(GeneratedDataKey {
+                plaintext: Secret::new(plaintext),
+                wrapped,
+            }),
+            _ => Err(KeyRotationError::Kms("data key response missing key material".to_string())),
+        }
+    }
+
+    async fn unwrap_data_key(&self, wrapped: &[u8]) -> Result<Vec<u8>, KeyRotationError> {
+        let response = self
+            .client
+            .decrypt()
+            .key_id(self.key_id.clone())
+            .ciphertext_blob(wrapped.to_vec())
+            .send()
+            .await
+            .map_err(|e| KeyRotationError::Kms(e.to_string()))?;
+
+        response
+            .plaintext()
+            .map(|blob| blob.as_ref().to_vec())
+            .ok_or_else(|| KeyRotationError::Kms("decrypt response missing plaintext".to_string()))
+    }
+}
+
+/// Persistence for data keys, sealed secrets and rotation progress
+#[async_trait]
+pub trait KeyRotationStore: Send + Sync {
+    async fn data_keys(&self) -> Result<Vec<StoredDataKey>, KeyRotationError>;
+    /// Adds an active data key, demoting the previous active key to retiring
+    async fn insert_active_key(&self, key: &StoredDataKey) -> Result<(), KeyRotationError>;
+    async fn retire_key(&self, version: u32, at: DateTime<Utc>) -> Result<(), KeyRotationError>;
+    async fn count_secrets(&self) -> Result<u64, KeyRotationError>;
+    /// Secrets ordered by id, starting after the given id
+    async fn secrets_after(&self, after: Option<Uuid>, limit: usize) -> Result<Vec<StoredSecret>, KeyRotationError>;
+    async fn update_secret(&self, id: Uuid, data: &EncryptedData) -> Result<(), KeyRotationError>;
+    async fn save_run(&self, run: &RotationRun) -> Result<(), KeyRotationError>;
+    /// Most recent run that has not completed
+    async fn unfinished_run(&self) -> Result<Option<RotationRun>, KeyRotationError>;
+}
+
+/// Rotation schedule and batching
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyRotationConfig {
+    pub auto_rotation: bool,
+    pub rotation_period: Duration,
+    pub batch_size: usize,
+    pub check_interval: Duration,
+}
+
+impl KeyRotationConfig {
+    /// Schedule from the KMS config, whose rotation period is in days
+    pub fn from_kms(config: &KMSConfig) -> Self {
+        Self {
+            auto_rotation: config.auto_rotation,
+            rotation_period: Duration::from_secs(config.rotation_period.max(1) as u64 * SECONDS_PER_DAY),
+            batch_size: DEFAULT_BATCH_SIZE,
+            check_interval: DEFAULT_CHECK_INTERVAL,
+        }
+    }
+}
+
+/// Rotates data keys and re-encrypts stored secrets
+pub struct KeyRotationService {
+    config: KeyRotationConfig,
+    provider: Arc<dyn DataKeyProvider>,
+    store: Arc<dyn KeyRotationStore>,
+    keys: Arc<KeyRing>,
+    running: Mutex<()>,
+    progress: SyncRwLock<Option<RotationRun>>,
+}
+
+impl std::fmt::Debug for KeyRotationService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyRotationService")
+            .field("config", &self.config)
+            .field("keys", &self.keys)
+            .finish()
+    }
+}
+
+impl KeyRotationService {
+    /// Creates the service over a key ring, normally `crypto::data_keys()`
+    pub fn new(
+        config: KeyRotationConfig,
+        provider: Arc<dyn DataKeyProvider>,
+        store: Arc<dyn KeyRotationStore>,
+        keys: Arc<KeyRing>,
+    ) -> Self {
+        Self {
+            config,
+            provider,
+            store,
+            keys,
+            running: Mutex::new(()),
+            progress: SyncRwLock::new(None),
+        }
+    }
+
+    /// Latest rotation progress seen by this process
+    pub fn progress(&self) -> Option<RotationRun> {
+        self.progress.read().clone()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.try_lock().is_err()
+    }
+
+    /// Unwraps every data key that has not been retired into the key ring
+    #[instrument(skip(self))]
+    pub async fn load_keys(&self) -> Result<(), KeyRotationError> {
+        for key in self.store.data_keys().await? {
+            if key.status == DataKeyStatus::Retired || self.keys.versions().contains(&key.version) {
+                continue;
+            }
+            let plaintext = self.provider.unwrap_data_key(&key.wrapped_key).await?;
+            self.keys.insert(key.version, plaintext).map_err(KeyRotationError::Crypto)?;
+            if key.status == DataKeyStatus::Active {
+                self.keys.set_active(key.version).map_err(KeyRotationError::Crypto)?;
+            }
+        }
+        info!(versions = ?self.keys.versions(), active = ?self.keys.active_version(), "Loaded data keys");
+        Ok(())
+    }
+
+    /// True when the active key has outlived the rotation period or a pass was interrupted
+    pub async fn rotation_due(&self, now: DateTime<Utc>) -> Result<bool, KeyRotationError> {
+        if self.store.unfinished_run().await?.is_some() {
+            return Ok(true);
+        }
+        let period = chrono::Duration::from_std(self.config.rotation_period).unwrap_or(chrono::Duration::MAX);
+        let active = self
+            .store
+            .data_keys()
+            .await?
+            .into_iter()
+            .find(|key| key.status == DataKeyStatus::Active);
+        Ok(active.map_or(true, |key| key.created_at + period <= now))
+    }
+
+    /// Runs a rotation pass, resuming an interrupted one before starting a new key
+    #[instrument(skip(self))]
+    pub async fn rotate(&self) -> Result<RotationRun, KeyRotationError> {
+        let _running = self.running.try_lock().map_err(|_| KeyRotationError::InProgress)?;
+
+        let mut run = match self.store.unfinished_run().await? {
+            Some(run) => {
+                info!(run_id = %run.id, target_version = run.target_version, "Resuming key rotation");
+                self.load_keys().await?;
+                run
+            }
+            None => self.start_run().await?,
+        };
+        run.status = RotationStatus::Running;
+        run.error = None;
+        self.record(&run).await?;
+
+        if let Err(e) = self.re_encrypt(&mut run).await {
+            error!(run_id = %run.id, error = %e, "Key rotation interrupted");
+            counter!("trading_bot.key_rotation.interrupted", 1);
+            run.status = RotationStatus::Interrupted;
+            run.error = Some(e.to_string());
+            run.updated_at = Utc::now();
+            if let Err(save_error) = self.record(&run).await {
+                warn!(error = %save_error, "Failed to record interrupted key rotation");
+            }
+            return Err(e);
+        }
+
+        // Every secret now opens with the target key, so older keys can go
+        for key in self.store.data_keys().await? {
+            if key.version != run.target_version && key.status != DataKeyStatus::Retired {
+                self.store.retire_key(key.version, Utc::now()).await?;
+                self.keys.retire(key.version).map_err(KeyRotationError::Crypto)?;
+                info!(version = key.version, "Retired data key");
+            }
+        }
+
+        let now = Utc::now();
+        run.status = RotationStatus::Completed;
+        run.updated_at = now;
+        run.completed_at = Some(now);
+        self.record(&run).await?;
+        counter!("trading_bot.key_rotation.completed", 1);
+        info!(
+            run_id = %run.id,
+            target_version = run.target_version,
+            secrets = run.processed_secrets,
+            "Key rotation completed"
+        );
+        Ok(run)
+    }
+
+    /// Checks the schedule periodically and rotates when due
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            if let Err(e) = self.load_keys().await {
+                error!(error = %e, "Failed to load data keys");
+            }
+            if !self.config.auto_rotation {
+                return;
+            }
+            let mut interval = tokio::time::interval(self.config.check_interval);
+            loop {
+                interval.tick().await;
+                match self.rotation_due(Utc::now()).await {
+                    Ok(true) => {
+                        if let Err(e) = self.rotate().await {
+                            warn!(error = %e, "Scheduled key rotation failed");
+                        }
+                    }
+                    Ok(false) => {}
+                    Err(e) => warn!(error = %e, "Failed to check key rotation schedule"),
+                }
+            }
+        })
+    }
+
+    /// Generates the next data key and makes it active for new data
+    async fn start_run(&self) -> Result<RotationRun, KeyRotationError> {
+        let existing = self.store.data_keys().await?;
+        let version = existing.iter().map(|key| key.version).max().unwrap_or(0) + 1;
+        let generated = self.provider.generate_data_key().await?;
+
+        self.keys
+            .insert(version, generated.plaintext.expose().clone())
+            .map_err(KeyRotationError::Crypto)?;
+        let now = Utc::now();
+        self.store
+            .insert_active_key(&StoredDataKey {
+                version,
+                wrapped_key: generated.wrapped,
+                status: DataKeyStatus::Active,
+                created_at: now,
+                retired_at: None,
+            })
+            .await?;
+        self.keys.set_active(version).map_err(KeyRotationError::Crypto)?;
+        info!(version, "Generated data key");
+
+        Ok(RotationRun {
+            id: Uuid::new_v4(),
+            target_version: version,
+            status: RotationStatus::Running,
+            total_secrets: self.store.count_secrets().await?,
+            processed_secrets: 0,
+            last_secret_id: None,
+            error: None,
+            started_at: now,
+            updated_at: now,
+            completed_at: None,
+        })
+    }
+
+    /// Re-encrypts secrets batch by batch, recording progress after each batch
+    async fn re_encrypt(&self, run: &mut RotationRun) -> Result<(), KeyRotationError> {
+        loop {
+            let batch = self
+                .store
+                .secrets_after(run.last_secret_id, self.config.batch_size)
+                .await?;
+            if batch.is_empty() {
+                return Ok(());
+            }
+
+            for secret in batch {
+                if secret.data.key_version != run.target_version {
+                    let plaintext = self.keys.decrypt(&secret.data).map_err(KeyRotationError::Crypto)?;
+                    let resealed = self
+                        .keys
+                        .encrypt_with_version(run.target_version, &plaintext)
+                        .map_err(KeyRotationError::Crypto)?;
+                    self.store.update_secret(secret.id, &resealed).await?;
+                    counter!("trading_bot.key_rotation.secrets_rotated", 1);
+                }
+                run.processed_secrets += 1;
+                run.last_secret_id = Some(secret.id);
+            }
+
+            run.total_secrets = run.total_secrets.max(run.processed_secrets);
+            run.updated_at = Utc::now();
+            self.record(run).await?;
+        }
+    }
+
+    async fn record(&self, run: &RotationRun) -> Result<(), KeyRotationError> {
+        *self.progress.write() = Some(run.clone());
+        self.store.save_run(run).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex as SyncMutex;
+    use std::collections::BTreeMap;
+
+    /// Wraps data keys by XOR with a fixed master key
+    struct MockProvider {
+        next_key: SyncMutex<u8>,
+    }
+
+    const MASTER: u8 = 0x5a;
+
+    impl MockProvider {
+        fn new() -> Self {
+            Self { next_key: SyncMutex::new(1) }
+        }
+
+        fn wrap(key: &[u8]) -> Vec<u8> {
+            key.iter().map(|byte| byte ^ MASTER).collect()
+        }
+    }
+
+    #[async_trait]
+    impl DataKeyProvider for MockProvider {
+        async fn generate_data_key(&self) -> Result<GeneratedDataKey, KeyRotationError> {
+            let mut next = self.next_key.lock();
+            let key = vec![*next; 32];
+            *next += 1;
+            Ok(GeneratedDataKey {
+                wrapped: Self::wrap(&key),
+                plaintext: Secret::new(key),
+            })
+        }
+
+        async fn unwrap_data_key(&self, wrapped: &[u8]) -> Result<Vec<u8>, KeyRotationError> {
+            Ok(Self::wrap(wrapped))
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryStore {
+        keys: SyncMutex<BTreeMap<u32, StoredDataKey>>,
+        secrets: SyncMutex<BTreeMap<Uuid, StoredSecret>>,
+        runs: SyncMutex<Vec<RotationRun>>,
+        /// Fails the update of the nth secret, once
+        fail_update_at: SyncMutex<Option<usize>>,
+        updates: SyncMutex<usize>,
+    }
+
+    #[async_trait]
+    impl KeyRotationStore for MemoryStore {
+        async fn data_keys(&self) -> Result<Vec<StoredDataKey>, KeyRotationError> {
+            Ok(self.keys.lock().values().cloned().collect())
+        }
+
+        async fn insert_active_key(&self, key: &StoredDataKey) -> Result<(), KeyRotationError> {
+            let mut keys = self.keys.lock();
+            for existing in keys.values_mut() {
+                if existing.status == DataKeyStatus::Active {
+                    existing.status = DataKeyStatus::Retiring;
+                }
+            }
+            keys.insert(key.version, key.clone());
+            Ok(())
+        }
+
+        async fn retire_key(&self, version: u32, at: DateTime<Utc>) -> Result<(), KeyRotationError> {
+            let mut keys = self.keys.lock();
+            let key = keys.get_mut(&version).unwrap();
+            key.status = DataKeyStatus::Retired;
+            key.retired_at = Some(at);
+            Ok(())
+        }
+
+        async fn count_secrets(&self) -> Result<u64, KeyRotationError> {
+            Ok(self.secrets.lock().len() as u64)
+        }
+
+        async fn secrets_after(&self, after: Option<Uuid>, limit: usize) -> Result<Vec<StoredSecret>, KeyRotationError> {
+            Ok(self
+                .secrets
+                .lock()
+                .values()
+                .filter(|secret| after.map_or(true, |after| secret.id > after))
+                .take(limit)
+                .cloned()
+                .collect())
+        }
+
+        async fn update_secret(&self, id: Uuid, data: &EncryptedData) -> Result<(), KeyRotationError> {
+            let mut updates = self.updates.lock();
+            *updates += 1;
+            let mut fail_at = self.fail_update_at.lock();
+            if *fail_at == Some(*updates) {
+                *fail_at = None;
+                return Err(KeyRotationError::Store("connection reset".to_string()));
+            }
+            self.secrets.lock().get_mut(&id).unwrap().data = data.clone();
+            Ok(())
+        }
+
+        async fn save_run(&self, run: &RotationRun) -> Result<(), KeyRotationError> {
+            let mut runs = self.runs.lock();
+            runs.retain(|existing| existing.id != run.id);
+            runs.push(run.clone());
+            Ok(())
+        }
+
+        async fn unfinished_run(&self) -> Result<Option<RotationRun>, KeyRotationError> {
+            Ok(self
+                .runs
+                .lock()
+                .iter()
+                .rev()
+                .find(|run| run.status != RotationStatus::Completed)
+                .cloned())
+        }
+    }
+
+    const PLAINTEXTS: [&str; 5] = [
+        "wallet-secret-1",
+        "wallet-secret-2",
+        "webhook-secret-a",
+        "webhook-secret-b",
+        "jito-auth-token",
+    ];
+
+    fn config() -> KeyRotationConfig {
+        KeyRotationConfig {
+            auto_rotation: true,
+            rotation_period: Duration::from_secs(90 * SECONDS_PER_DAY),
+            batch_size: 2,
+            check_interval: DEFAULT_CHECK_INTERVAL,
+        }
+    }
+
+    /// Service with a first data key and a handful of secrets sealed with it
+    async fn seeded() -> (KeyRotationService, Arc<MemoryStore>, Arc<KeyRing>) {
+        let store = Arc::new(MemoryStore::default());
+        let keys = Arc::new(KeyRing::default());
+        let service = KeyRotationService::new(config(), Arc::new(MockProvider::new()), store.clone(), keys.clone());
+
+        let first = service.rotate().await.unwrap();
+        assert_eq!(first.target_version, 1);
+        for (i, plaintext) in PLAINTEXTS.iter().enumerate() {
+            let id = Uuid::new_v4();
+            store.secrets.lock().insert(
+                id,
+                StoredSecret {
+                    id,
+                    scope: if i < 2 { "wallet" } else { "webhook" }.to_string(),
+                    name: format!("secret-{}", i),
+                    data: keys.encrypt(plaintext).unwrap(),
+                },
+            );
+        }
+        (service, store, keys)
+    }
+
+    fn decrypt_all(store: &MemoryStore, keys: &KeyRing) -> Vec<(u32, String)> {
+        let mut opened: Vec<(u32, String)> = store
+            .secrets
+            .lock()
+            .values()
+            .map(|secret| (secret.data.key_version, keys.decrypt(&secret.data).unwrap()))
+            .collect();
+        opened.sort_by(|a, b| a.1.cmp(&b.1));
+        opened
+    }
+
+    fn sorted_plaintexts() -> Vec<String> {
+        let mut plaintexts: Vec<String> = PLAINTEXTS.iter().map(|p| p.to_string()).collect();
+        plaintexts.sort();
+        plaintexts
+    }
+
+    #[tokio::test]
+    async fn test_rotation_re_encrypts_secrets_under_new_key() {
+        let (service, store, keys) = seeded().await;
+
+        let run = service.rotate().await.unwrap();
+        assert_eq!(run.target_version, 2);
+        assert_eq!(run.status, RotationStatus::Completed);
+        assert_eq!(run.processed_secrets, PLAINTEXTS.len() as u64);
+        assert_eq!(service.progress(), Some(run));
+
+        let opened = decrypt_all(&store, &keys);
+        assert!(opened.iter().all(|(version, _)| *version == 2));
+        assert_eq!(opened.into_iter().map(|(_, p)| p).collect::<Vec<_>>(), sorted_plaintexts());
+
+        // The old key is retired in the store and its material dropped
+        assert_eq!(store.keys.lock()[&1].status, DataKeyStatus::Retired);
+        assert_eq!(store.keys.lock()[&2].status, DataKeyStatus::Active);
+        assert_eq!(keys.versions(), vec![2]);
+
+        // A restarted process loads only the live key from its wrapped form
+        let restarted_keys = Arc::new(KeyRing::default());
+        let restarted = KeyRotationService::new(config(), Arc::new(MockProvider::new()), store.clone(), restarted_keys.clone());
+        restarted.load_keys().await.unwrap();
+        assert_eq!(restarted_keys.versions(), vec![2]);
+        assert_eq!(decrypt_all(&store, &restarted_keys).len(), PLAINTEXTS.len());
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_rotation_resumes_and_keeps_old_key() {
+        let (service, store, keys) = seeded().await;
+        *store.fail_update_at.lock() = Some(3);
+
+        let error = service.rotate().await.unwrap_err();
+        assert_eq!(error, KeyRotationError::Store("connection reset".to_string()));
+        let interrupted = store.runs.lock().last().cloned().unwrap();
+        assert_eq!(interrupted.status, RotationStatus::Interrupted);
+        assert_eq!(interrupted.processed_secrets, 2);
+
+        // Mid-rotation, secrets under both versions still open and nothing was retired
+        let versions: Vec<u32> = decrypt_all(&store, &keys).into_iter().map(|(v, _)| v).collect();
+        assert!(versions.contains(&1) && versions.contains(&2));
+        assert_eq!(store.keys.lock()[&1].status, DataKeyStatus::Retiring);
+        assert_eq!(keys.versions(), vec![1, 2]);
+        assert!(service.rotation_due(Utc::now()).await.unwrap());
+
+        // The next rotation resumes toward the same key instead of generating another
+        let resumed = service.rotate().await.unwrap();
+        assert_eq!(resumed.id, interrupted.id);
+        assert_eq!(resumed.target_version, 2);
+        assert_eq!(resumed.processed_secrets, PLAINTEXTS.len() as u64);
+        assert_eq!(store.keys.lock().len(), 2);
+
+        let opened = decrypt_all(&store, &keys);
+        assert!(opened.iter().all(|(version, _)| *version == 2));
+        assert_eq!(opened.into_iter().map(|(_, p)| p).collect::<Vec<_>>(), sorted_plaintexts());
+        assert_eq!(keys.versions(), vec![2]);
+        assert!(!service.rotation_due(Utc::now()).await.unwrap());
+    }
+}
@@ -3,12 +3,119 @@
 //! 
 //! Version: 1.0.0
 
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
-use tokio::sync::RwLock;
-use tracing::{error, info, instrument, warn};
+use tokio::sync::{watch, RwLock};
+use tracing::{debug, error, info, instrument, warn};
 use metrics::{counter, gauge, histogram};
+use rust_decimal::Decimal;
 use thiserror::Error;
+use uuid::Uuid;
+
+pub mod admin_cli;
+pub mod admission;
+pub mod attribution;
+pub mod events;
+pub mod optimizer;
+pub mod supervision;
+pub mod jobs;
+pub mod key_rotation;
+pub mod maintenance;
+pub mod microstructure;
+pub mod order_batching;
+pub mod signals;
+pub mod signal_conflicts;
+pub mod state_snapshot;
+pub mod performance;
+pub mod persistence;
+pub mod quarantine;
+pub mod regime;
+pub mod reports;
+pub mod retention;
+pub mod strategy_archive;
+pub mod strategy_versions;
+pub mod strategy_driver;
+pub mod strategies;
+pub mod sweep;
+pub mod system_info;
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection;
+
+use crate::admission::{AdmissionConfig, AdmissionController};
+use crate::attribution::{AttributionConfig, AttributionEngine};
+use crate::api::bridge::{BridgeEvent, EventBridge};
+use crate::api::websocket::WebSocketServer;
+use crate::api::{OrderGateway, WebhookDispatcher};
+use crate::data_collector::gaps::GapMonitor;
+use crate::data_collector::BookUpdate;
+use crate::data_collector::lifecycle::CollectorManager;
+use crate::data_collector::ohlcv::CandleAggregator;
+use crate::data_collector::quality::DataQualityMonitor;
+use crate::data_collector::sanity::{MarketDataValidator, SanityConfig, SuspectSampleStore};
+use crate::db::repositories::{
+    AttributionRepository, DataQualityRepository, MicrostructureRepository, PerformanceRepository,
+    RegimeRepository, StrategyAuditRepository, StrategyVersionRepository,
+};
+use crate::execution_engine::adapters::{ExchangeAdapter, DEFAULT_MAX_SLIPPAGE};
+use crate::execution_engine::book_sync::BookSnapshotSource;
+use crate::execution_engine::fills::{FillTracker, FillUpdate, PositionCloseStore};
+use crate::execution_engine::latency::LatencyWatchdog;
+use crate::execution_engine::multi_leg::{
+    GroupStatus, LegFill, MultiLegConfig, MultiLegExecutor, TradeGroup, TradeGroupResult, TradeGroupStore,
+};
+use crate::execution_engine::open_orders::OpenOrderRegistry;
+use crate::execution_engine::position_events::{PositionEventStore, PositionHistory};
+use crate::execution_engine::order_book::{LiveOrderBook, OrderBookSnapshot};
+use crate::execution_engine::passive::{DriftMakerAdapter, ExecutionStyle, PassiveConfig, PassiveExecutor};
+use crate::execution_engine::preview::LiveMarketData;
+use crate::execution_engine::queue::PriorityClass;
+use crate::execution_engine::recovery::OrphanRecovery;
+use crate::execution_engine::cost_model::CostModelCalibrator;
+use crate::execution_engine::stats::{ExecutionRecord, ExecutionStatsService};
+use crate::execution_engine::telemetry::OrderTelemetry;
+use crate::execution_engine::throttle::OrderThrottle;
+use crate::execution_engine::{ExecutionResult, StrategyParams};
+use crate::events::{EventCalendar, EventStore};
+use crate::maintenance::{MaintenanceScheduler, MaintenanceStore, MaintenanceTarget, MaintenanceWindow};
+use crate::microstructure::{MicrostructureConfig, MicrostructureTracker};
+use crate::performance::{PerformanceConfig, PerformanceService};
+use crate::persistence::PersistenceQueue;
+use crate::quarantine::{
+    QuarantineConfig, QuarantineError, QuarantineList, QuarantineMode, QuarantineRequest, QuarantineStore,
+    QuarantineTarget, AUDIT_SIGNAL_QUARANTINED,
+};
+use crate::regime::{MarketRegime, RegimeConfig, RegimeDetector};
+use crate::strategy_archive::{ArchiveTarget, StrategyArchive};
+use crate::strategy_versions::{StrategyVersionService, VersionConfig, SYSTEM_AUTHOR};
+use crate::risk_manager::daily_loss::DailyLossLimits;
+use crate::risk_manager::exposure::TradeSide;
+use crate::risk_manager::margin::{MarginError, PerpPositionActions};
+use crate::risk_manager::perp_reconciliation::{DriftReconciler, PerpPositionBook};
+use crate::risk_manager::{RiskError, RiskManager};
+use crate::order_batching::{OrderBatch, OrderBatcher};
+use crate::signal_conflicts::{ConflictArbiter, SignalConflict};
+use crate::signals::{
+    trade_groups, AuditConsumer, ExecutionConsumer, NotificationConsumer, PublishOutcome, Signal, SignalBus,
+};
+use crate::state_snapshot::{BotStateSnapshot, SnapshotError, StateComponents, StateSource};
+use crate::strategies::StrategyContext;
+use crate::strategy_driver::{DriverConfig, StrategyDriver, StrategyRunner};
+use crate::supervision::{OrderOutcome, PauseTrigger, StrategySupervisor};
+use crate::system_info::{ActivityCounts, ActivitySource};
+use crate::models::order::{OrderFill, OrderType};
+use crate::models::pair::{PairRegistry, TradingPair};
+use crate::models::portfolio::Position;
+use crate::models::trade::FeeBreakdown;
+use crate::models::strategy::{StrategyAuditEntry, StrategySnapshot, StrategyState};
+use crate::models::transfer::Transfer;
+use crate::models::webhook::{OrderEvent, WebhookEvent, WebhookEventType};
+use crate::utils::circuit_breaker::{BreakerConfig, CircuitBreaker};
+use crate::utils::health::HealthMonitor;
+use crate::utils::metric_handles::{self, AGGREGATION_FLUSH_INTERVAL};
+use crate::utils::solana::{FeeEstimator, SolanaClient, TokenBalanceChange};
 
 // Re-export core components
 pub use crate::models::{
@@ -25,10 +132,23 @@ pub use crate::api::{init_api, ApiRouter};
 
 // Global constants from specification
 pub const VERSION: &str = "1.0.0";
-pub const MAX_CONCURRENT_TRADES: usize = 100;
+pub const MAX_CONCURRENT_TRADES: usize = crate::config::environment::DEFAULT_MAX_CONCURRENT_TRADES;
 pub const CIRCUIT_BREAKER_THRESHOLD: f64 = 0.15;
-pub const MAX_ERROR_RATE: f64 = 0.05;
+pub const CIRCUIT_BREAKER_WINDOW: Duration = Duration::from_secs(300);
+pub const CIRCUIT_BREAKER_MIN_CALLS: u32 = 20;
+pub const CIRCUIT_BREAKER_COOLDOWN: Duration = Duration::from_secs(60);
 pub const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+pub const RESERVATION_SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+pub const RESERVATION_TIMEOUT: Duration = Duration::from_secs(300);
+pub const POSITION_CONSISTENCY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+pub const DAILY_LOSS_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const DAILY_LOSS_STRATEGY_ID: &str = "risk:daily_loss";
+const MAINTENANCE_STRATEGY_ID: &str = "maintenance";
+const QUARANTINE_STRATEGY_ID: &str = "risk:quarantine";
+const MARGIN_STRATEGY_ID: &str = "risk:margin";
+const PERP_RECONCILIATION_ACTOR: &str = "risk:perp_reconciliation";
+const BPS_PER_UNIT: Decimal = Decimal::new(10_000, 0);
+const USDC_DECIMALS: u32 = 6;
 
 /// Core trading bot error types
 #[derive(Error, Debug)]
@@ -44,6 +164,9 @@ pub enum Error {
     
     #[error("system error: {0}")]
     System(String),
+
+    #[error("risk error: {0}")]
+    Risk(#[from] RiskError),
 }
 
 /// Main trading bot coordinator
@@ -52,10 +175,43 @@ pub struct TradingBot {
     execution_engine: Arc<ExecutionEngine>,
     api_router: Arc<ApiRouter>,
     portfolio: Arc<RwLock<Portfolio>>,
-    active_strategies: HashMap<String, Strategy>,
+    active_strategies: Arc<RwLock<HashMap<String, Strategy>>>,
+    supervisor: Arc<StrategySupervisor>,
+    data_quality: Arc<DataQualityMonitor>,
+    fills: Arc<FillTracker>,
+    performance: Arc<PerformanceService>,
+    versions: Arc<StrategyVersionService>,
+    attribution: Arc<AttributionEngine>,
+    regimes: Arc<RegimeDetector>,
+    microstructure: Arc<MicrostructureTracker>,
     metrics: Arc<MetricsCollector>,
     circuit_breaker: Arc<CircuitBreaker>,
     health_monitor: Arc<HealthMonitor>,
+    admission: Arc<AdmissionController>,
+    throttle: Arc<OrderThrottle>,
+    webhooks: Option<Arc<WebhookDispatcher>>,
+    open_orders: Option<Arc<OpenOrderRegistry>>,
+    signal_bus: Arc<SignalBus>,
+    signal_audit: Arc<AuditConsumer>,
+    risk_manager: Option<Arc<RwLock<RiskManager>>>,
+    multi_leg: Option<Arc<MultiLegExecutor>>,
+    execution_stats: Option<Arc<ExecutionStatsService>>,
+    collectors: Option<Arc<CollectorManager>>,
+    data_gaps: Option<Arc<GapMonitor>>,
+    position_history: Arc<PositionHistory>,
+    event_bridge: Option<Arc<EventBridge>>,
+    maintenance: Arc<MaintenanceScheduler>,
+    quarantine: Arc<QuarantineList>,
+    sanity: Arc<MarketDataValidator>,
+    events: Arc<EventCalendar>,
+    persistence: Option<Arc<PersistenceQueue>>,
+    pairs: Option<Arc<PairRegistry>>,
+    candles: Option<Arc<CandleAggregator>>,
+    orphan_recovery: Option<Arc<OrphanRecovery>>,
+    websocket_addr: Option<SocketAddr>,
+    websocket: OnceLock<Arc<WebSocketServer>>,
+    halted: AtomicBool,
+    shutdown: watch::Sender<bool>,
 }
 
 impl TradingBot {
@@ -70,10 +226,7 @@ impl TradingBot {
             .map_err(|e| Error::Initialization(format!("Failed to initialize metrics: {}", e)))?);
 
         // Initialize circuit breaker
-        let circuit_breaker = Arc::new(CircuitBreaker::new(
-            CIRCUIT_BREAKER_THRESHOLD,
-            MAX_ERROR_RATE,
-        ));
+        let circuit_breaker = trading_breaker();
 
         // Initialize health monitoring
         let health_monitor = Arc::new(HealthMonitor::new(
@@ -81,18 +234,128 @@ impl TradingBot {
             metrics.clone(),
         ));
 
+        // Initialize trade admission control
+        let admission = Arc::new(AdmissionController::new(config.admission));
+
+        // Initialize per-strategy supervision over the shared strategy map
+        let active_strategies = Arc::new(RwLock::new(HashMap::new()));
+        let supervisor = Arc::new(StrategySupervisor::new(
+            config.supervision,
+            active_strategies.clone(),
+        ));
+
+        // Score collector sources and demote degraded feeds from the aggregate mid
+        let data_quality = Arc::new(DataQualityMonitor::new(
+            config.data_quality,
+            active_strategies.clone(),
+        ));
+
+        // Fills update the shared portfolio as they land rather than once per order
+        let portfolio = Portfolio::new(config.wallet_address.clone(), config.initial_balance)?;
+        let fills = Arc::new(FillTracker::new(portfolio.clone()));
+
+        // Realized P&L per strategy feeds equity curves and the performance leaderboard
+        let performance = Arc::new(PerformanceService::new(
+            PerformanceConfig::default(),
+            active_strategies.clone(),
+        ));
+
+        // Parameter changes become versions and fills are attributed to the version that traded
+        let versions = Arc::new(StrategyVersionService::new(
+            VersionConfig::default(),
+            active_strategies.clone(),
+            performance.clone(),
+            supervisor.clone(),
+        ));
+        performance.set_versions(versions.clone());
+
+        // Fills and fees are tagged by wallet, strategy version, venue and pair for reporting
+        let attribution = Arc::new(AttributionEngine::new(AttributionConfig::default()));
+        attribution.set_versions(versions.clone());
+
+        // Strategies publish signals for execution, notification and audit consumers
+        let signal_bus = Arc::new(SignalBus::new(config.signals));
+
+        // Each pair's market regime is published on the bus and gates strategies that exclude it
+        let regimes = Arc::new(RegimeDetector::new(RegimeConfig::default()));
+        regimes.set_bus(signal_bus.clone());
+
+        // Order book imbalance, depth and spread features are shared by every strategy
+        let microstructure = Arc::new(MicrostructureTracker::new(MicrostructureConfig::default()));
+
+        // Every position lifecycle change is logged and replayable
+        let position_history = Arc::new(PositionHistory::new(execution_engine.position_events()));
+
+        // Planned maintenance winds trading down ahead of each window; an open breaker
+        // would refuse the reduction orders, so it is reported as a conflict
+        let maintenance = Arc::new(MaintenanceScheduler::new(config.maintenance));
+        maintenance.watch_breaker(circuit_breaker.clone());
+
+        // Quarantined pairs stop trading while every other pair continues
+        let quarantine = Arc::new(QuarantineList::new(QuarantineConfig::default()));
+
+        // Implausible collector samples are rejected or quarantined before any strategy sees them
+        let sanity = Arc::new(MarketDataValidator::new(SanityConfig::default(), Arc::new(PairRegistry::default())));
+
+        // Limits tighten for a window around high-impact economic events
+        let events = Arc::new(EventCalendar::new(config.events));
+
+        // Executions wait until every traded pair has a fresh aggregated price and live book
+        let execution_engine = execution_engine.with_readiness(config.readiness);
+        let readiness = execution_engine.readiness();
+        readiness.set_price_feed(data_quality.clone());
+        readiness.set_strategies(active_strategies.clone());
+
+        // Orders acting on ticks older than the staleness threshold pause their strategy
+        let latency = Arc::new(LatencyWatchdog::new(config.latency, supervisor.clone()));
+        let execution_engine = execution_engine.with_latency_watchdog(latency);
+
+        // Submissions are paced per venue for the bot's wallet ahead of signing
+        let throttle = Arc::new(OrderThrottle::new(config.throttle));
+        let execution_engine = execution_engine.with_order_throttle(throttle.clone(), &config.wallet_address);
+
         // Create thread-safe components
         let bot = Self {
             execution_engine: Arc::new(execution_engine),
             api_router: Arc::new(api_router),
-            portfolio: Arc::new(RwLock::new(Portfolio::new(
-                config.wallet_address.clone(),
-                config.initial_balance,
-            )?)),
-            active_strategies: HashMap::new(),
+            portfolio: Arc::new(RwLock::new(portfolio)),
+            active_strategies,
+            supervisor,
+            data_quality,
+            fills,
+            performance,
+            versions,
+            attribution,
+            regimes,
+            microstructure,
             metrics,
             circuit_breaker,
             health_monitor,
+            admission,
+            throttle,
+            webhooks: None,
+            open_orders: None,
+            signal_bus,
+            signal_audit: Arc::new(AuditConsumer::default()),
+            risk_manager: None,
+            multi_leg: None,
+            execution_stats: None,
+            collectors: None,
+            data_gaps: None,
+            position_history,
+            event_bridge: None,
+            maintenance,
+            quarantine,
+            sanity,
+            events,
+            persistence: None,
+            pairs: None,
+            candles: None,
+            orphan_recovery: None,
+            websocket_addr: None,
+            websocket: OnceLock::new(),
+            halted: AtomicBool::new(false),
+            shutdown: watch::channel(false).0,
         };
 
         // Record initialization metrics
@@ -114,6 +377,103 @@ impl TradingBot {
         self.metrics.initialize().await
             .map_err(|e| Error::Initialization(format!("Failed to initialize metrics: {}", e)))?;
 
+        // Emit hot-path counters aggregated since the last flush
+        metric_handles::spawn_flusher(AGGREGATION_FLUSH_INTERVAL);
+
+        // Keep admission capacity in sync with config reloads
+        self.admission
+            .clone()
+            .spawn_reload_listener(crate::config::subscribe_environment_updates());
+        self.throttle
+            .clone()
+            .spawn_reload_listener(crate::config::subscribe_environment_updates());
+
+        // Pause strategies whose data, drawdown or rejection rate trip supervision
+        self.supervisor
+            .clone()
+            .spawn_market_data_listener(self.execution_engine.subscribe_order_books());
+        self.supervisor.clone().spawn();
+
+        // Score market data sources from the same order book stream
+        self.data_quality
+            .clone()
+            .spawn_market_data_listener(self.execution_engine.subscribe_order_books());
+        self.data_quality.clone().spawn();
+
+        // Derive microstructure features from every accepted order book update
+        self.microstructure
+            .clone()
+            .spawn(self.execution_engine.order_book());
+
+        // Leave warm-up once market data for every traded pair is fresh
+        self.execution_engine.readiness().spawn();
+
+        // Materialize equity curves from fills and recompute the leaderboard
+        self.performance
+            .clone()
+            .spawn_fill_listener(self.fills.subscribe());
+        self.performance.clone().spawn();
+
+        // Conclude A/B tests whose evaluation period has ended
+        self.versions.clone().spawn();
+
+        // Release funds held by orders that never settled
+        self.portfolio.read().await.spawn_reservation_sweeper(
+            RESERVATION_SWEEP_INTERVAL,
+            chrono::Duration::from_std(RESERVATION_TIMEOUT).unwrap_or_else(|_| chrono::Duration::minutes(5)),
+        );
+
+        // Restart collectors that stay connected but stop producing data
+        if let Some(collectors) = &self.collectors {
+            collectors.clone().spawn();
+        }
+
+        // Flag positions whose live state drifts from their event history
+        self.position_history.clone().spawn_consistency_check(
+            self.execution_engine.clone(),
+            POSITION_CONSISTENCY_CHECK_INTERVAL,
+        );
+
+        // Detect holes in stored market data and backfill them from venue history
+        if let Some(data_gaps) = &self.data_gaps {
+            data_gaps.clone().spawn();
+        }
+
+        // Enforce the daily loss limit from realized fills and marked open positions, refuse
+        // orders on quarantined pairs in risk validation, tighten limits around economic events
+        // and size orders by system quality
+        if let Some(risk_manager) = &self.risk_manager {
+            let mut manager = risk_manager.write().await;
+            manager.set_quarantine(self.quarantine.clone());
+            manager.set_event_calendar(self.events.clone());
+            manager.set_health_monitor(self.health_monitor.clone());
+            drop(manager);
+            spawn_daily_loss_monitor(
+                risk_manager.clone(),
+                self.portfolio.read().await.clone(),
+                self.execution_engine.clone(),
+                self.fills.subscribe(),
+            );
+        }
+
+        // Share executed trades with API replicas in multi-instance deployments
+        if let Some(event_bridge) = &self.event_bridge {
+            event_bridge
+                .clone()
+                .spawn_publisher(self.execution_engine.subscribe_trades(), BridgeEvent::Trade);
+        }
+
+        // Apply fills that landed after the last process died before new orders go out
+        if let Some(recovery) = &self.orphan_recovery {
+            let report = recovery
+                .recover(&self.fills, chrono::Utc::now())
+                .await
+                .map_err(|e| Error::Initialization(format!("Failed to recover orphaned orders: {}", e)))?;
+            for entry in report.audit {
+                self.record_audit(entry).await;
+            }
+        }
+
         // Start execution engine
         self.execution_engine.start().await
             .map_err(|e| Error::System(format!("Failed to start execution engine: {}", e)))?;
@@ -122,17 +482,1120 @@ impl TradingBot {
         self.api_router.start().await
             .map_err(|e| Error::System(format!("Failed to start API server: {}", e)))?;
 
+        // Stream order books and executed trades to WebSocket clients until shutdown
+        if let Some(addr) = self.websocket_addr {
+            self.start_websocket(addr).await?;
+        }
+
         info!("Trading bot started successfully");
         Ok(())
     }
 
+    /// Binds the WebSocket server, registers it with the health monitor, forwards the
+    /// engine's order book and trade streams and any candles to it and reaps unresponsive
+    /// clients. The server closes its clients when `stop` raises the shutdown signal.
+    async fn start_websocket(&self, addr: SocketAddr) -> Result<(), Error> {
+        let server = Arc::new(
+            WebSocketServer::new(Arc::new(metrics::Metrics::new()))
+                .with_health_monitor(self.health_monitor()),
+        );
+        let (_, bound) = server
+            .clone()
+            .start(addr, self.shutdown_signal())
+            .await
+            .map_err(|e| Error::System(format!("Failed to start WebSocket server: {}", e)))?;
+
+        server
+            .clone()
+            .spawn_order_book_forwarder(self.execution_engine.subscribe_order_books());
+        server
+            .clone()
+            .spawn_trade_forwarder(self.execution_engine.subscribe_trades());
+        if let Some(candles) = &self.candles {
+            server.clone().spawn_candle_forwarder(candles.subscribe());
+        }
+        // Relay events published by other instances to this instance's clients
+        if let Some(event_bridge) = &self.event_bridge {
+            event_bridge.clone().spawn_subscriber(server.clone());
+        }
+        // Drop clients that stop answering pings and expire replay history
+        server.clone().spawn_reaper();
+
+        let _ = self.websocket.set(server);
+        info!(%bound, "WebSocket streaming enabled");
+        Ok(())
+    }
+
+    /// Executes a strategy once admitted, holding the slot until the trade reaches a terminal state
+    #[instrument(
+        skip(self, params),
+        err,
+        fields(
+            strategy_id = %params.strategy_id,
+            trading_pair = %params.trading_pair,
+            exchange = %params.exchange,
+            order_id = tracing::field::Empty,
+        )
+    )]
+    pub async fn execute_strategy(&self, params: StrategyParams) -> Result<ExecutionResult, Error> {
+        if self.is_halted() {
+            return Err(Error::System("trading halted pending operator review".to_string()));
+        }
+        if self.supervisor.is_paused(&params.strategy_id) {
+            return Err(Error::System(format!("strategy {} is paused", params.strategy_id)));
+        }
+        if self.maintenance.blocks_new_positions() && !self.reduces_position(&params).await {
+            return Err(Error::System(
+                "maintenance window pending: only orders reducing a position are accepted".to_string(),
+            ));
+        }
+        if let Some(persistence) = &self.persistence {
+            if persistence.pauses_trading() && !self.reduces_position(&params).await {
+                return Err(Error::System(format!(
+                    "trading paused: {} writes awaiting persistence; only orders reducing a position are accepted",
+                    persistence.backlog()
+                )));
+            }
+        }
+        if self.quarantine.mode(&params.trading_pair).is_some() {
+            let reduces = self.reduces_position(&params).await;
+            self.quarantine
+                .check(&params.trading_pair, reduces)
+                .map_err(|e| Error::Risk(RiskError::PairQuarantined(e.to_string())))?;
+        }
+        if let Some(window) = self.events.pauses(&params.strategy_id, chrono::Utc::now()) {
+            return Err(Error::Risk(RiskError::EventRisk(format!(
+                "strategy {} is paused until {} for {}",
+                params.strategy_id, window.ends_at, window.event.name
+            ))));
+        }
+        // Rejected before any funds are held; warm-up says nothing about execution health
+        self.execution_engine
+            .readiness()
+            .admit(&params.trading_pair)
+            .await
+            .map_err(Error::from)?;
+        let strategy_id = params.strategy_id.clone();
+        self.supervisor.record_signal(&strategy_id, chrono::Utc::now());
+        let side = params.side;
+        let (trading_pair, exchange, expected_price, requested_size) =
+            (params.trading_pair.clone(), params.exchange.clone(), params.price, params.size);
+        let started_at = std::time::Instant::now();
+        let order = Order::new(
+            params.trading_pair.clone(),
+            params.exchange.clone(),
+            params.order_type.clone(),
+            params.price,
+            params.size,
+        )
+        .map_err(|e| Error::Execution(ExecutionError::ValidationError(e.to_string())))?;
+        let order_id = order.id;
+        tracing::Span::current().record("order_id", tracing::field::display(order_id));
+
+        // Hold quote funds for buys so concurrent orders cannot spend the same balance
+        let portfolio = self.portfolio.read().await.clone();
+        if side == TradeSide::Buy {
+            let fee = crate::models::trade::calculate_fee(&exchange, requested_size, expected_price)
+                .unwrap_or_default();
+            portfolio
+                .reserve(expected_price * requested_size + fee, order_id)
+                .await
+                .map_err(|e| Error::Execution(ExecutionError::ValidationError(e.to_string())))?;
+        }
+        if !self.circuit_breaker.allow() {
+            portfolio.release(order_id).await;
+            return Err(Error::System("circuit breaker open".to_string()));
+        }
+        if let Some(open_orders) = &self.open_orders {
+            let wallet_address = portfolio.wallet_address().to_string();
+            open_orders.register(order.clone(), &wallet_address, &strategy_id, side);
+        }
+
+        // Conditions the order is planned in, for calibrating execution cost models
+        let book_depth = self.execution_engine.order_book().visible_depth(&trading_pair, side);
+        let volatility_bps = self
+            .regimes
+            .volatility(&trading_pair)
+            .and_then(Decimal::from_f64_retain)
+            .map(|volatility| (volatility * BPS_PER_UNIT).round_dp(4));
+
+        let mut order_event = OrderEvent {
+            strategy_id: params.strategy_id.clone(),
+            trading_pair: params.trading_pair.clone(),
+            exchange: params.exchange.clone(),
+            size: params.size,
+            price: params.price,
+            trade_id: None,
+            executed_price: None,
+            error: None,
+        };
+
+        let result = self.admission
+            .run(async {
+                self.notify(WebhookEventType::OrderAccepted, &order_event);
+                self.execution_engine
+                    .execute_strategy(params)
+                    .await
+                    .map_err(Error::from)
+            })
+            .await;
+
+        let outcome = match &result {
+            Ok(_) => OrderOutcome::Filled,
+            Err(Error::Execution(ExecutionError::ValidationError(_)))
+            | Err(Error::Execution(ExecutionError::ComputeBudgetExceeded { .. }))
+            | Err(Error::Execution(ExecutionError::SlippageExceeded(_)))
+            | Err(Error::Execution(ExecutionError::Throttled { .. })) => OrderOutcome::Rejected,
+            Err(_) => OrderOutcome::Failed,
+        };
+        // Rejections are the risk checks working, not a failing execution path
+        match (&result, outcome) {
+            // The venue enforced our own slippage limit; that neither trips nor resets the breaker
+            (Err(Error::Execution(ExecutionError::SlippageExceeded(_))), _) => {}
+            // Our own venue throttle held the order back before it was signed
+            (Err(Error::Execution(ExecutionError::Throttled { .. })), _) => {}
+            (_, OrderOutcome::Failed) => {
+                self.circuit_breaker.record_failure();
+            }
+            _ => self.circuit_breaker.record_success(),
+        }
+        self.supervisor.record_order(&strategy_id, outcome);
+
+        if side == TradeSide::Buy {
+            match &result {
+                Ok(execution) if !execution.fills.is_empty() => {
+                    let cost = execution
+                        .fills
+                        .iter()
+                        .map(|fill| fill.size * fill.price)
+                        .sum::<Decimal>()
+                        + execution.fees.net();
+                    if let Err(e) = portfolio.settle(order_id, cost.max(Decimal::ZERO)).await {
+                        error!(order_id = %order_id, "Failed to settle reservation: {}", e);
+                    }
+                }
+                // Resting orders keep their hold until they fill or the sweeper releases it
+                Ok(_) => {}
+                Err(_) => {
+                    portfolio.release(order_id).await;
+                }
+            }
+        }
+
+        if let Some(open_orders) = &self.open_orders {
+            match &result {
+                Ok(_) => open_orders.mark_executed(order_id),
+                Err(_) => {
+                    open_orders.remove(order_id);
+                }
+            }
+        }
+
+        if let Some(execution_stats) = &self.execution_stats {
+            let filled_size: Decimal = result
+                .as_ref()
+                .map(|execution| execution.fills.iter().map(|fill| fill.size).sum())
+                .unwrap_or_default();
+            let executed_price = result.as_ref().ok().map(|execution| execution.price);
+            let executed_at = chrono::Utc::now();
+            let fee = executed_price
+                .filter(|_| filled_size > Decimal::ZERO)
+                .and_then(|price| crate::models::trade::calculate_fee(&exchange, filled_size, price).ok())
+                .unwrap_or_default();
+            execution_stats
+                .record(ExecutionRecord {
+                    id: Uuid::new_v4(),
+                    trading_pair,
+                    exchange,
+                    side,
+                    requested_size,
+                    filled_size,
+                    expected_price,
+                    executed_price,
+                    fee,
+                    latency_ms: started_at.elapsed().as_millis() as u64,
+                    mev_value: result
+                        .as_ref()
+                        .ok()
+                        .and_then(|execution| Decimal::from_f64_retain(execution.mev_value))
+                        .unwrap_or_default(),
+                    executed_at,
+                    benchmark: None,
+                    book_depth,
+                    volatility_bps,
+                    compute: result.as_ref().ok().and_then(|execution| execution.compute),
+                    event_id: self.events.active_window(executed_at).map(|window| window.event.id),
+                })
+                .await;
+        }
+
+        if let Ok(execution) = &result {
+            self.record_fills(order, &strategy_id, side, execution).await;
+        }
+
+        match &result {
+            Ok(execution) => {
+                order_event.trade_id = Some(execution.trade_id.clone());
+                order_event.executed_price = Some(execution.price);
+                self.notify(WebhookEventType::OrderExecuted, &order_event);
+            }
+            Err(e) => {
+                order_event.error = Some(e.to_string());
+                self.notify(WebhookEventType::OrderFailed, &order_event);
+            }
+        }
+        result
+    }
+
+    /// Read-only view of the bot handed to a strategy for one observation
+    async fn strategy_context(&self, strategy_id: &str, now: chrono::DateTime<chrono::Utc>) -> StrategyContext {
+        let portfolio = self.portfolio.read().await.clone();
+        let mut ctx = StrategyContext::new(strategy_id, now)
+            .with_portfolio(portfolio.snapshot().await)
+            .with_regimes(self.regimes.clone())
+            .with_microstructure(self.microstructure.clone());
+        if let Some(pairs) = &self.pairs {
+            ctx = ctx.with_pairs(pairs.clone());
+        }
+        if let Some(candles) = &self.candles {
+            ctx = ctx.with_candles(candles.clone());
+        }
+        ctx
+    }
+
+    /// Whether an order only reduces the open position on its pair
+    async fn reduces_position(&self, params: &StrategyParams) -> bool {
+        let portfolio = self.portfolio.read().await.clone();
+        let Some(position) = portfolio.get_position(&params.trading_pair).await else {
+            return false;
+        };
+        match params.side {
+            TradeSide::Sell => position.size > Decimal::ZERO && params.size <= position.size,
+            TradeSide::Buy => position.size < Decimal::ZERO && params.size <= -position.size,
+        }
+    }
+
+    /// Runs a strategy against fresh market data and publishes its signals, or executes
+    /// them directly when the bus is bypassed. Returns how many signals went out.
+    #[instrument(skip(self, market_data), err)]
+    pub async fn run_strategy(&self, strategy_id: &str, market_data: &MarketData) -> Result<usize, Error> {
+        let now = chrono::Utc::now();
+        let config = self.signal_bus.config();
+        let ctx = self.strategy_context(strategy_id, now).await.with_signal_ttl(config.ttl);
+        let tick = market_data.stamp();
+        let mut signals: Vec<Signal> = {
+            let mut strategies = self.active_strategies.write().await;
+            let strategy = strategies
+                .get_mut(strategy_id)
+                .ok_or_else(|| Error::System(format!("strategy not found: {}", strategy_id)))?;
+            strategy
+                .execute(&ctx, market_data)
+                .await
+                .map_err(|e| Error::System(e.to_string()))?
+        }
+        .into_iter()
+        .map(|signal| signal.with_tick(tick))
+        .collect();
+
+        // Signals for quarantined pairs never reach the bus; each drop is audited
+        let mut quarantined = Vec::new();
+        signals.retain(|signal| match self.quarantine.mode(&signal.pair) {
+            Some(mode) => {
+                quarantined.push((signal.id, signal.pair.clone(), mode));
+                false
+            }
+            None => true,
+        });
+        for (signal_id, pair, mode) in quarantined {
+            counter!("trading_bot.quarantine.signals_dropped").increment(1);
+            self.supervisor
+                .record_audit(StrategyAuditEntry::new(
+                    strategy_id,
+                    AUDIT_SIGNAL_QUARANTINED,
+                    format!("signal {} dropped: {} is quarantined ({})", signal_id, pair, mode),
+                ))
+                .await;
+        }
+
+        // Legs of a trade group must execute together, so grouped signals skip the bus and
+        // go out as one group each
+        let (grouped, signals): (Vec<Signal>, Vec<Signal>) =
+            signals.into_iter().partition(|signal| signal.group.is_some());
+        let mut grouped_sent = 0;
+        for group in trade_groups(grouped) {
+            let legs = group.legs.len();
+            match self.execute_trade_group(group).await {
+                Ok(_) => grouped_sent += legs,
+                Err(e) => warn!(strategy_id, "Trade group failed: {}", e),
+            }
+        }
+
+        if config.direct_execution {
+            for signal in &signals {
+                if let Err(e) = self.execute_strategy(signal.order_params()).await {
+                    warn!(strategy_id, "Direct strategy order failed: {}", e);
+                }
+            }
+            return Ok(grouped_sent + signals.len());
+        }
+
+        Ok(grouped_sent
+            + signals
+                .into_iter()
+                .filter(|signal| matches!(self.signal_bus.publish(signal.clone(), now), PublishOutcome::Published { .. }))
+                .count())
+    }
+
+    /// Executes legs that must trade together under the group's atomicity policy. Every leg
+    /// passes the gates of a single order before any is submitted, and the group is checked
+    /// on its net exposure against the risk manager's books and current limits.
+    #[instrument(skip(self, group), fields(correlation_id = %group.correlation_id), err)]
+    pub async fn execute_trade_group(&self, group: TradeGroup) -> Result<TradeGroupResult, Error> {
+        let Some(executor) = &self.multi_leg else {
+            return Err(Error::System("trade groups are not enabled".to_string()));
+        };
+        let Some(risk_manager) = &self.risk_manager else {
+            return Err(Error::System("trade groups need a risk manager to check net exposure".to_string()));
+        };
+        if self.is_halted() {
+            return Err(Error::System("trading halted pending operator review".to_string()));
+        }
+        if self.supervisor.is_paused(&group.strategy_id) {
+            return Err(Error::System(format!("strategy {} is paused", group.strategy_id)));
+        }
+        // Groups open positions across venues, so none go out while new positions are held back
+        if self.maintenance.blocks_new_positions() {
+            return Err(Error::System("maintenance window pending: trade groups are not accepted".to_string()));
+        }
+        if let Some(persistence) = &self.persistence {
+            if persistence.pauses_trading() {
+                return Err(Error::System(format!(
+                    "trading paused: {} writes awaiting persistence",
+                    persistence.backlog()
+                )));
+            }
+        }
+        if let Some(window) = self.events.pauses(&group.strategy_id, chrono::Utc::now()) {
+            return Err(Error::Risk(RiskError::EventRisk(format!(
+                "strategy {} is paused until {} for {}",
+                group.strategy_id, window.ends_at, window.event.name
+            ))));
+        }
+        for leg in &group.legs {
+            if self.quarantine.mode(&leg.order.trading_pair).is_some() {
+                self.quarantine
+                    .check(&leg.order.trading_pair, false)
+                    .map_err(|e| Error::Risk(RiskError::PairQuarantined(e.to_string())))?;
+            }
+            self.execution_engine
+                .readiness()
+                .admit(&leg.order.trading_pair)
+                .await
+                .map_err(Error::from)?;
+        }
+        if !self.circuit_breaker.allow() {
+            return Err(Error::System("circuit breaker open".to_string()));
+        }
+        self.supervisor.record_signal(&group.strategy_id, chrono::Utc::now());
+
+        let exposure = {
+            let risk_manager = risk_manager.read().await;
+            executor.set_exposure_limits(risk_manager.exposure_limits().await);
+            risk_manager.exposure_book(&self.group_prices(&group).await).await?
+        };
+        let result = executor.execute(&group, &exposure).await.map_err(Error::from);
+
+        // Rejections before any leg was submitted are the checks working
+        let outcome = match &result {
+            Ok(result) if result.status == GroupStatus::Completed => OrderOutcome::Filled,
+            Ok(_) => OrderOutcome::Failed,
+            Err(_) => OrderOutcome::Rejected,
+        };
+        match outcome {
+            OrderOutcome::Failed => {
+                self.circuit_breaker.record_failure();
+            }
+            _ => self.circuit_breaker.record_success(),
+        }
+        self.supervisor.record_order(&group.strategy_id, outcome);
+
+        if let Ok(result) = &result {
+            self.record_group_fills(result).await;
+        }
+        result
+    }
+
+    /// Fresh prices for the portfolio's pairs, with each leg's pair at its decision price
+    /// when the book has none
+    async fn group_prices(&self, group: &TradeGroup) -> HashMap<String, Decimal> {
+        let portfolio = self.portfolio.read().await.clone();
+        let mut prices: HashMap<String, Decimal> = current_market_data(&portfolio, &self.execution_engine)
+            .await
+            .into_iter()
+            .map(|(pair, data)| (pair, data.price()))
+            .collect();
+        for leg in &group.legs {
+            prices.entry(leg.order.trading_pair.clone()).or_insert(leg.order.price);
+        }
+        prices
+    }
+
+    /// Books a group's filled legs and hedges into the portfolio like single orders
+    async fn record_group_fills(&self, result: &TradeGroupResult) {
+        let legs = result.legs.iter().filter_map(|leg| {
+            leg.fill
+                .as_ref()
+                .map(|fill| (&leg.exchange, &leg.trading_pair, leg.side, fill))
+        });
+        let hedges = result.hedges.iter().filter_map(|hedge| {
+            hedge
+                .fill
+                .as_ref()
+                .map(|fill| (&hedge.exchange, &hedge.trading_pair, hedge.side, fill))
+        });
+        for (exchange, trading_pair, side, fill) in legs.chain(hedges) {
+            let order = match Order::new(
+                trading_pair.clone(),
+                exchange.clone(),
+                OrderType::Market,
+                fill.price,
+                fill.size,
+            ) {
+                Ok(order) => order,
+                Err(e) => {
+                    error!(correlation_id = %result.correlation_id, "Failed to book trade group fill: {}", e);
+                    continue;
+                }
+            };
+            self.record_fills(order, &result.strategy_id, side, &leg_execution(exchange, fill))
+                .await;
+        }
+    }
+
+    /// Runs the sanity checks over collected market data, passing on only accepted samples
+    /// so quarantined and rejected ones never reach aggregation or strategies
+    pub fn screen_market_data(
+        &self,
+        market_data: tokio::sync::mpsc::Receiver<MarketData>,
+    ) -> tokio::sync::mpsc::Receiver<MarketData> {
+        self.sanity.clone().spawn_screen(market_data).0
+    }
+
+    /// Applies sequenced venue depth updates to the live order book. A sequence gap degrades
+    /// the pair's book until it is resynced from the exchange's snapshot source.
+    pub fn spawn_book_listener(
+        &self,
+        mut updates: tokio::sync::mpsc::Receiver<BookUpdate>,
+    ) -> tokio::task::JoinHandle<()> {
+        let order_book = self.execution_engine.order_book();
+        tokio::spawn(async move {
+            while let Some(update) = updates.recv().await {
+                let trading_pair = update.book.trading_pair().to_string();
+                if let Err(e) = order_book
+                    .apply_sequenced_update(trading_pair.clone(), update.book, update.sequence)
+                    .await
+                {
+                    debug!(trading_pair = %trading_pair, "Order book update not applied: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Registers the REST source degraded books of `exchange` are resynced from
+    pub fn set_book_snapshot_source(&self, exchange: &str, source: Arc<dyn BookSnapshotSource>) {
+        self.execution_engine.order_book().set_snapshot_source(exchange, source);
+    }
+
+    /// Drives every strategy concurrently per pair from the collectors' market data
+    pub fn spawn_strategy_driver(
+        self: Arc<Self>,
+        config: DriverConfig,
+        market_data: tokio::sync::mpsc::Receiver<MarketData>,
+    ) -> Arc<StrategyDriver> {
+        let supervisor = self.supervisor.clone();
+        let regimes = self.regimes.clone();
+        let quarantine = self.quarantine.clone();
+        let driver = Arc::new(
+            StrategyDriver::new(config, self, supervisor)
+                .with_regimes(regimes)
+                .with_quarantine(quarantine),
+        );
+        driver.clone().spawn_market_data_listener(market_data);
+        driver
+    }
+
+    /// Drives scheduled maintenance windows against this bot
+    pub fn spawn_maintenance(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let maintenance = self.maintenance.clone();
+        maintenance.spawn(self)
+    }
+
+    /// Reconciles perp positions against the Drift user account on the reconciler's interval
+    pub fn spawn_perp_reconciler(self: Arc<Self>, reconciler: Arc<DriftReconciler>) -> tokio::task::JoinHandle<()> {
+        if let Some(webhooks) = &self.webhooks {
+            reconciler.set_webhooks(webhooks.clone());
+        }
+        reconciler.spawn(self)
+    }
+
+    /// Registers the execution, notification and audit consumers on the signal bus
+    pub fn spawn_signal_consumers(self: Arc<Self>) -> Vec<tokio::task::JoinHandle<()>> {
+        let arbiter = Arc::new(ConflictArbiter::new(
+            self.signal_bus.config().conflicts.clone(),
+            self.signal_audit.clone(),
+        ));
+        let mut execution = ExecutionConsumer::new(self.clone()).with_arbiter(arbiter);
+        if let Some(batching) = &self.signal_bus.config().batching {
+            execution = execution.with_batcher(Arc::new(OrderBatcher::new(batching.clone(), self.signal_audit.clone())));
+        }
+        let execution = Arc::new(execution);
+        let mut handles = vec![
+            self.signal_bus.register(execution.clone()),
+            self.signal_bus.register(self.signal_audit.clone()),
+        ];
+        handles.extend(execution.spawn_release());
+        if let Some(webhooks) = &self.webhooks {
+            handles.push(self.signal_bus.register(Arc::new(NotificationConsumer::new(
+                webhooks.clone(),
+                self.signal_bus.config().alert_strength,
+            ))));
+        }
+        handles
+    }
+
+    /// Signal bus shared with the WebSocket `signals` channel
+    pub fn signal_bus(&self) -> Arc<SignalBus> {
+        self.signal_bus.clone()
+    }
+
+    /// Recent signals recorded by the audit consumer
+    pub fn signal_audit_log(&self) -> Vec<Signal> {
+        self.signal_audit.recent()
+    }
+
+    /// Recent conflicts between opposing strategy signals
+    pub fn signal_conflicts(&self) -> Vec<SignalConflict> {
+        self.signal_audit.conflicts()
+    }
+
+    /// Recent batches of same-side strategy signals merged into single orders
+    pub fn order_batches(&self) -> Vec<OrderBatch> {
+        self.signal_audit.batches()
+    }
+
+    /// Tracks an executed order, nets its confirmed fills into the portfolio and attributes
+    /// each applied fill, with its share of the order's fees, to the strategy, venue and pair
+    async fn record_fills(&self, order: Order, strategy_id: &str, side: TradeSide, execution: &ExecutionResult) {
+        let order_id = order.id;
+        let wallet = self.portfolio.read().await.wallet_address().to_string();
+        let tags = self
+            .attribution
+            .tags(&wallet, strategy_id, &order.exchange, order.trading_pair.as_str());
+        self.fills.track(order, strategy_id, side).await;
+
+        let filled_size: Decimal = execution.fills.iter().map(|fill| fill.size).sum();
+        for fill in &execution.fills {
+            match self.fills.apply_fill(order_id, fill.clone()).await {
+                Ok(Some(update)) => {
+                    // Realized P&L also lands in the engine's tracked position metrics
+                    if let Err(e) = self
+                        .execution_engine
+                        .apply_position_fill(&update.trading_pair, side.signed(fill.size), fill.price, Some(order_id))
+                        .await
+                    {
+                        warn!(order_id = %order_id, "Failed to apply fill to tracked position: {}", e);
+                    }
+                    let fee = if filled_size > Decimal::ZERO {
+                        execution.fees.net() * fill.size / filled_size
+                    } else {
+                        Decimal::ZERO
+                    };
+                    self.attribution
+                        .record_fill(tags.clone(), side, fill, update.realized_pnl, fee)
+                        .await;
+                }
+                Ok(None) => {}
+                Err(e) => error!(order_id = %order_id, "Failed to apply fill: {}", e),
+            }
+        }
+
+        if let Some(recovery) = &self.orphan_recovery {
+            if let Err(e) = recovery.store().resolve_signature(&execution.trade_id, chrono::Utc::now()).await {
+                warn!(order_id = %order_id, "Failed to resolve submission intent: {}", e);
+            }
+        }
+    }
+
+    /// Fill tracker receiving polled fills for resting orders and publishing partial state
+    pub fn fill_tracker(&self) -> Arc<FillTracker> {
+        self.fills.clone()
+    }
+
+    /// Attaches an outbound webhook dispatcher for order lifecycle, position and strategy
+    /// pause events
+    pub fn with_webhooks(mut self, webhooks: Arc<WebhookDispatcher>) -> Self {
+        if let Some(multi_leg) = &self.multi_leg {
+            multi_leg.set_webhooks(webhooks.clone());
+        }
+        self.fills.set_webhooks(webhooks.clone());
+        self.supervisor.set_webhooks(webhooks.clone());
+        self.data_quality.set_webhooks(webhooks.clone());
+        self.attribution.set_webhooks(webhooks.clone());
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    /// Registers submitted orders so they can be cancelled by id or by filter
+    pub fn with_open_orders(mut self, open_orders: Arc<OpenOrderRegistry>) -> Self {
+        self.open_orders = Some(open_orders);
+        self
+    }
+
+    /// Attaches the risk manager whose velocity windows are captured in state snapshots
+    pub fn with_risk_manager(mut self, risk_manager: Arc<RwLock<RiskManager>>) -> Self {
+        self.maintenance.set_risk_manager(risk_manager.clone());
+        self.risk_manager = Some(risk_manager);
+        self
+    }
+
+    /// Persists scheduled maintenance windows so they survive a restart
+    pub fn with_maintenance_store(self, store: Arc<dyn MaintenanceStore>) -> Self {
+        self.maintenance.set_store(store);
+        self
+    }
+
+    /// Persists pair quarantines so they survive a restart
+    pub fn with_quarantine_store(self, store: Arc<dyn QuarantineStore>) -> Self {
+        self.quarantine.set_store(store);
+        self
+    }
+
+    /// Keeps market data samples quarantined by the sanity checks for review
+    pub fn with_suspect_store(self, store: Arc<dyn SuspectSampleStore>) -> Self {
+        self.sanity.set_store(store);
+        self
+    }
+
+    /// Persists the economic event calendar so it survives a restart
+    pub fn with_event_store(self, store: Arc<dyn EventStore>) -> Self {
+        self.events.set_store(store);
+        self
+    }
+
+    /// Moves ledger and audit writes off the trading path onto the write-behind queue, which
+    /// may also pause new positions while its backlog is past the configured bound
+    pub fn with_persistence(mut self, persistence: Arc<PersistenceQueue>) -> Self {
+        self.supervisor.set_persistence(persistence.clone());
+        self.attribution.set_persistence(persistence.clone());
+        self.persistence = Some(persistence);
+        self
+    }
+
+    /// Exposes the pair registry to strategies through their context and takes market data
+    /// price bounds from it
+    pub fn with_pair_registry(mut self, pairs: Arc<PairRegistry>) -> Self {
+        self.sanity.set_registry(pairs.clone());
+        self.pairs = Some(pairs);
+        self
+    }
+
+    /// Serves order book and trade streams over WebSocket on `addr` once the bot starts
+    pub fn with_websocket(mut self, addr: SocketAddr) -> Self {
+        self.websocket_addr = Some(addr);
+        self
+    }
+
+    /// Exposes recent candles to strategies through their context
+    pub fn with_candles(mut self, candles: Arc<CandleAggregator>) -> Self {
+        self.candles = Some(candles);
+        self
+    }
+
+    /// Persists a submission intent for every order and, when the bot starts, recovers fills
+    /// of orders whose results were never recorded
+    pub fn with_orphan_recovery(mut self, recovery: Arc<OrphanRecovery>) -> Self {
+        self.execution_engine.set_intent_store(recovery.store());
+        self.orphan_recovery = Some(recovery);
+        self
+    }
+
+    /// Allows the execution engine to submit real transactions
+    pub fn with_live_trading(self, enabled: bool) -> Self {
+        self.execution_engine.set_live_trading(enabled);
+        self
+    }
+
+    /// Bids priority fees from the estimator's per-exchange samples instead of MEV value alone
+    pub fn with_fee_estimator(self, fee_estimator: Arc<FeeEstimator>) -> Self {
+        self.execution_engine.set_fee_estimator(fee_estimator);
+        self
+    }
+
+    /// Builds orders on the adapter's exchange with the venue enforcing the slippage limit
+    pub fn with_exchange_adapter(self, adapter: Arc<dyn ExchangeAdapter>) -> Self {
+        if let Some(multi_leg) = &self.multi_leg {
+            multi_leg.set_adapter(adapter.clone());
+        }
+        self.execution_engine.set_exchange_adapter(adapter);
+        self
+    }
+
+    /// Executes strategies' grouped signals as trade groups submitted through the execution
+    /// engine and its venue adapters, recording every result with its hedges
+    pub fn with_trade_groups(mut self, config: MultiLegConfig, store: Arc<dyn TradeGroupStore>) -> Self {
+        let executor = MultiLegExecutor::new(config, self.execution_engine.clone()).with_store(store);
+        for adapter in self.execution_engine.exchange_adapters() {
+            executor.set_adapter(adapter);
+        }
+        if let Some(webhooks) = &self.webhooks {
+            executor.set_webhooks(webhooks.clone());
+        }
+        self.multi_leg = Some(Arc::new(executor));
+        self
+    }
+
+    /// Works passive-style orders post-only on Drift, tracking their fills and following
+    /// the engine's live books
+    pub fn with_passive_executor(self, config: PassiveConfig, solana_client: Arc<SolanaClient>) -> Self {
+        let venue = DriftMakerAdapter::new(solana_client, self.fills.clone(), Arc::new(OrderTelemetry::new()));
+        let passive = PassiveExecutor::new(
+            config,
+            Arc::new(venue),
+            self.execution_engine.order_book_sender(),
+            self.fills.update_sender(),
+        );
+        self.execution_engine.set_passive_executor(Arc::new(passive));
+        self
+    }
+
+    /// Records execution outcomes and routes between near-identical books on realized statistics
+    pub fn with_execution_stats(mut self, execution_stats: Arc<ExecutionStatsService>) -> Self {
+        self.execution_engine.set_execution_stats(execution_stats.clone());
+        self.execution_stats = Some(execution_stats);
+        self
+    }
+
+    /// Prices pre-trade impact with calibrated cost models, conditioned on the regime
+    /// detector's volatility
+    pub fn with_cost_models(self, cost_models: Arc<CostModelCalibrator>) -> Self {
+        cost_models.set_regimes(self.regimes.clone());
+        self.execution_engine.set_cost_models(cost_models);
+        self
+    }
+
+    /// Attaches the collector tasks so each can be restarted without restarting the bot
+    pub fn with_collectors(mut self, collectors: Arc<CollectorManager>) -> Self {
+        self.collectors = Some(collectors);
+        self
+    }
+
+    /// Persists position lifecycle events
+    pub fn with_position_event_store(self, store: Arc<dyn PositionEventStore>) -> Self {
+        self.execution_engine.position_events().set_store(store);
+        self
+    }
+
+    /// Persists the partial and full closes realized by fills
+    pub fn with_position_close_store(self, store: Arc<dyn PositionCloseStore>) -> Self {
+        self.fills.set_close_store(store);
+        self
+    }
+
+    /// Attaches market data gap detection and backfill
+    pub fn with_data_gaps(mut self, data_gaps: Arc<GapMonitor>) -> Self {
+        self.data_gaps = Some(data_gaps);
+        self
+    }
+
+    /// Publishes executed trades to other instances through Redis
+    pub fn with_event_bridge(mut self, event_bridge: Arc<EventBridge>) -> Self {
+        self.event_bridge = Some(event_bridge);
+        self
+    }
+
+    /// Persists strategy pause and resume audit entries
+    pub fn with_strategy_audit(self, repository: Arc<StrategyAuditRepository>) -> Self {
+        self.supervisor.set_repository(repository);
+        self
+    }
+
+    /// Persists hourly data quality scores
+    pub fn with_data_quality_repository(self, repository: Arc<DataQualityRepository>) -> Self {
+        self.data_quality.set_repository(repository);
+        self
+    }
+
+    /// Persists strategy trades and materialized equity curves
+    pub fn with_performance_repository(self, repository: Arc<PerformanceRepository>) -> Self {
+        self.performance.set_store(repository);
+        self
+    }
+
+    /// Persists the attribution ledger, wallet balances and daily rollups
+    pub fn with_attribution_repository(self, repository: Arc<AttributionRepository>) -> Self {
+        self.attribution.set_store(repository);
+        self
+    }
+
+    /// Persists market regime changes and restores them on startup
+    pub fn with_regime_repository(self, repository: Arc<RegimeRepository>) -> Self {
+        self.regimes.set_store(repository);
+        self
+    }
+
+    /// Persists per-minute microstructure aggregates for research
+    pub fn with_microstructure_repository(self, repository: Arc<MicrostructureRepository>) -> Self {
+        self.microstructure.set_store(repository);
+        self
+    }
+
+    /// Persists strategy parameter versions
+    pub fn with_version_repository(self, repository: Arc<StrategyVersionRepository>) -> Self {
+        self.versions.set_store(repository);
+        self
+    }
+
+    /// Adds a strategy to the active set, starts supervising it and records its parameters
+    /// as the first version
+    pub async fn register_strategy(&self, strategy_id: String, strategy: Strategy) {
+        self.supervisor
+            .register(&strategy_id, strategy.trading_pairs.clone());
+        self.active_strategies.write().await.insert(strategy_id.clone(), strategy);
+        if let Err(e) = self.versions.record_initial(&strategy_id, SYSTEM_AUTHOR).await {
+            warn!(strategy_id = %strategy_id, "Failed to record initial strategy version: {}", e);
+        }
+    }
+
+    /// Cancels a strategy's orders still waiting for execution
+    pub fn cancel_orders(&self, strategy_id: &str) -> usize {
+        self.execution_engine.cancel_queued_orders(strategy_id)
+    }
+
+    /// Stops attributing new P&L to soft-deleted strategies
+    pub fn set_strategy_archive(&self, archive: Arc<StrategyArchive>) {
+        self.attribution.set_deletions(archive);
+    }
+
+    /// Portfolio handle sharing state with the bot's live book
+    pub async fn portfolio(&self) -> Portfolio {
+        self.portfolio.read().await.clone()
+    }
+
+    /// Reconciles the observed USDC wallet balance, recording deposits and withdrawals in the
+    /// portfolio and risk manager and spreading them over strategy capital
+    pub async fn reconcile_wallet_balance(
+        &self,
+        observed_balance: Decimal,
+        on_chain_changes: &[TokenBalanceChange],
+    ) -> Result<Option<Transfer>, Error> {
+        // Fills settle into the quote balance as they happen, so no trade movement is
+        // outstanding between observations
+        let trade_delta = Decimal::ZERO;
+        let transfer = self
+            .portfolio()
+            .await
+            .reconcile_balance(observed_balance, trade_delta, on_chain_changes)
+            .await
+            .map_err(|e| Error::System(e.to_string()))?;
+
+        if let Some(risk_manager) = &self.risk_manager {
+            risk_manager
+                .read()
+                .await
+                .reconcile_balance(observed_balance, trade_delta, on_chain_changes)
+                .await?;
+        }
+
+        if let Some(transfer) = &transfer {
+            self.allocate_capital_flow(transfer.signed_amount()).await;
+        }
+        Ok(transfer)
+    }
+
+    /// Splits a capital flow across live strategies in proportion to their allocated capital,
+    /// evenly when nothing is allocated yet
+    async fn allocate_capital_flow(&self, amount: Decimal) {
+        let strategies = self.active_strategies.read().await;
+        let mut allocations = Vec::new();
+        for (strategy_id, strategy) in strategies.iter() {
+            if strategy.state != StrategyState::Terminated {
+                let allocated = strategy.allocated_capital().await.unwrap_or(Decimal::ZERO);
+                allocations.push((strategy_id, strategy, allocated));
+            }
+        }
+
+        let total: Decimal = allocations.iter().map(|(_, _, allocated)| *allocated).sum();
+        if total.is_zero() && amount < Decimal::ZERO {
+            warn!("Withdrawal of {} has no allocated strategy capital to draw from", -amount);
+            return;
+        }
+
+        let count = Decimal::from(allocations.len());
+        let mut remaining = amount;
+        for (index, (strategy_id, strategy, allocated)) in allocations.iter().enumerate() {
+            let share = if index + 1 == allocations.len() {
+                remaining
+            } else if total.is_zero() {
+                (amount / count).round_dp(USDC_DECIMALS)
+            } else {
+                (amount * *allocated / total).round_dp(USDC_DECIMALS)
+            };
+            remaining -= share;
+            if share.is_zero() {
+                continue;
+            }
+            if let Err(e) = strategy.record_capital_flow(share).await {
+                warn!(strategy_id = %strategy_id, "Failed to record capital flow of {}: {}", share, e);
+            }
+        }
+    }
+
+    /// Live order books the engine prices and guards orders against
+    pub fn order_book(&self) -> Arc<LiveOrderBook> {
+        self.execution_engine.order_book()
+    }
+
+    /// Sender feeding live order book snapshots to streaming clients
+    pub fn order_book_sender(&self) -> tokio::sync::broadcast::Sender<OrderBookSnapshot> {
+        self.execution_engine.order_book_sender()
+    }
+
+    /// Open order registry backing the cancellation API, when configured
+    pub fn open_orders(&self) -> Option<Arc<OpenOrderRegistry>> {
+        self.open_orders.clone()
+    }
+
+    /// Resolves to `true` once `stop` begins; servers use it for graceful shutdown
+    pub fn shutdown_signal(&self) -> watch::Receiver<bool> {
+        self.shutdown.subscribe()
+    }
+
+    /// Component health monitor servers register with once bound
+    pub fn health_monitor(&self) -> Arc<HealthMonitor> {
+        self.health_monitor.clone()
+    }
+
+    /// WebSocket server, once started
+    pub fn websocket(&self) -> Option<Arc<WebSocketServer>> {
+        self.websocket.get().cloned()
+    }
+
+    /// Whether trading is halted after a restore, awaiting operator review
+    pub fn is_halted(&self) -> bool {
+        self.halted.load(Ordering::SeqCst)
+    }
+
+    /// Live structures captured by and restored from state snapshots
+    fn state_components(&self) -> StateComponents {
+        StateComponents {
+            portfolio: self.portfolio.clone(),
+            strategies: self.active_strategies.clone(),
+            open_orders: self.open_orders.clone(),
+            risk_manager: self.risk_manager.clone(),
+        }
+    }
+
+    /// Collector lifecycle backing the collector restart API, when configured
+    pub fn collectors(&self) -> Option<Arc<CollectorManager>> {
+        self.collectors.clone()
+    }
+
+    /// In-process position event history backing the consistency check
+    pub fn position_history(&self) -> Arc<PositionHistory> {
+        self.position_history.clone()
+    }
+
+    /// Market data gap monitor backing the data gaps API, when configured
+    pub fn data_gaps(&self) -> Option<Arc<GapMonitor>> {
+        self.data_gaps.clone()
+    }
+
+    /// Supervisor backing the strategy resume API
+    pub fn supervisor(&self) -> Arc<StrategySupervisor> {
+        self.supervisor.clone()
+    }
+
+    /// Maintenance scheduler backing the maintenance admin API
+    pub fn maintenance(&self) -> Arc<MaintenanceScheduler> {
+        self.maintenance.clone()
+    }
+
+    /// Pair quarantines backing the risk quarantine endpoints
+    pub fn quarantine(&self) -> Arc<QuarantineList> {
+        self.quarantine.clone()
+    }
+
+    /// Economic event calendar backing the events API
+    pub fn events(&self) -> Arc<EventCalendar> {
+        self.events.clone()
+    }
+
+    /// Data quality monitor backing the monitoring API
+    pub fn data_quality(&self) -> Arc<DataQualityMonitor> {
+        self.data_quality.clone()
+    }
+
+    /// Strategy leaderboard and equity curves
+    pub fn performance(&self) -> Arc<PerformanceService> {
+        self.performance.clone()
+    }
+
+    /// P&L attribution backing the reports API
+    pub fn attribution(&self) -> Arc<AttributionEngine> {
+        self.attribution.clone()
+    }
+
+    /// Daily loss limits of the attached risk manager, or the defaults it would start with
+    pub async fn daily_loss_limits(&self) -> DailyLossLimits {
+        match &self.risk_manager {
+            Some(risk_manager) => risk_manager.read().await.daily_loss_limits().await,
+            None => DailyLossLimits::default(),
+        }
+    }
+
+    /// Webhook dispatcher, when outbound webhooks are configured
+    pub fn webhooks(&self) -> Option<Arc<WebhookDispatcher>> {
+        self.webhooks.clone()
+    }
+
+    /// Per-pair market regime detector; feed it candle events with `spawn`
+    pub fn regimes(&self) -> Arc<RegimeDetector> {
+        self.regimes.clone()
+    }
+
+    /// Order book microstructure features per pair
+    pub fn microstructure(&self) -> Arc<MicrostructureTracker> {
+        self.microstructure.clone()
+    }
+
+    /// Strategy parameter versions and A/B tests
+    pub fn versions(&self) -> Arc<StrategyVersionService> {
+        self.versions.clone()
+    }
+
+    /// Queues a webhook event without blocking the trading path
+    fn notify(&self, event_type: WebhookEventType, payload: &OrderEvent) {
+        let Some(webhooks) = &self.webhooks else {
+            return;
+        };
+        match WebhookEvent::new(event_type, payload) {
+            Ok(event) => {
+                webhooks.dispatch(event);
+            }
+            Err(e) => warn!("Failed to build webhook event: {}", e),
+        }
+    }
+
     /// Gracefully stops the trading bot and all components
     #[instrument(err)]
     pub async fn stop(&self) -> Result<(), Error> {
         info!("Initiating graceful shutdown");
 
+        // Signal servers to close client connections
+        self.shutdown.send_replace(true);
+
         // Stop accepting new trades
-        self.circuit_breaker.open();
+        self.circuit_breaker.trip("shutting down");
 
         // Close all active positions
         let mut portfolio = self.portfolio.write().await;
@@ -146,6 +1609,14 @@ impl TradingBot {
         self.execution_engine.stop().await
             .map_err(|e| Error::System(format!("Failed to stop execution engine: {}", e)))?;
 
+        // Stop market data collection
+        if let Some(collectors) = &self.collectors {
+            collectors.stop_all().await;
+        }
+
+        // Emit counts still pending in aggregated counters
+        metric_handles::flush_aggregated();
+
         // Stop API server
         self.api_router.stop().await
             .map_err(|e| Error::System(format!("Failed to stop API server: {}", e)))?;
@@ -161,6 +1632,458 @@ impl TradingBot {
     }
 }
 
+#[tonic::async_trait]
+impl OrderGateway for TradingBot {
+    async fn submit_order(&self, params: StrategyParams) -> Result<ExecutionResult, Error> {
+        self.execute_strategy(params).await
+    }
+
+    fn cancel_orders(&self, strategy_id: &str) -> usize {
+        TradingBot::cancel_orders(self, strategy_id)
+    }
+}
+
+#[async_trait::async_trait]
+impl StrategyRunner for TradingBot {
+    async fn strategies_for(&self, trading_pair: &str) -> Vec<String> {
+        self.active_strategies
+            .read()
+            .await
+            .iter()
+            .filter(|(_, strategy)| strategy.trading_pairs.iter().any(|pair| pair == trading_pair))
+            .map(|(strategy_id, _)| strategy_id.clone())
+            .collect()
+    }
+
+    async fn run(&self, strategy_id: &str, market_data: &MarketData) -> Result<usize, Error> {
+        self.run_strategy(strategy_id, market_data).await
+    }
+
+    async fn allowed_regimes(&self, strategy_id: &str) -> Vec<MarketRegime> {
+        self.active_strategies
+            .read()
+            .await
+            .get(strategy_id)
+            .map(|strategy| strategy.parameters.allowed_regimes.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait::async_trait]
+impl MaintenanceTarget for TradingBot {
+    async fn open_positions(&self) -> Vec<(String, Decimal)> {
+        self.portfolio()
+            .await
+            .get_positions()
+            .await
+            .into_iter()
+            .filter(|position| !position.size.is_zero())
+            .map(|position| (position.trading_pair.into_string(), position.size))
+            .collect()
+    }
+
+    async fn reduce_position(&self, trading_pair: &str, side: TradeSide, size: Decimal) -> Result<(), String> {
+        let data = self
+            .execution_engine
+            .order_book()
+            .latest(trading_pair)
+            .ok_or_else(|| format!("no fresh price for {}", trading_pair))?;
+        let params = closing_order(MAINTENANCE_STRATEGY_ID, PriorityClass::High, trading_pair, side, size, &data);
+        self.execute_strategy(params).await.map(|_| ()).map_err(|e| e.to_string())
+    }
+
+    async fn pause_strategies(&self, window: &MaintenanceWindow) -> Vec<String> {
+        let strategy_ids: Vec<String> = self.active_strategies.read().await.keys().cloned().collect();
+        let mut paused = Vec::new();
+        for strategy_id in strategy_ids {
+            if self
+                .supervisor
+                .pause(&strategy_id, PauseTrigger::Maintenance { window_id: window.id })
+                .await
+            {
+                paused.push(strategy_id);
+            }
+        }
+        paused
+    }
+
+    async fn resume_strategies(&self, strategy_ids: &[String]) {
+        for strategy_id in strategy_ids {
+            if let Err(e) = self.supervisor.resume_after_maintenance(strategy_id).await {
+                warn!(strategy_id = %strategy_id, "Strategy not resumed after maintenance: {}", e);
+            }
+        }
+    }
+
+    async fn suspend_collectors(&self) {
+        if let Some(collectors) = &self.collectors {
+            collectors.suspend_all().await;
+        }
+    }
+
+    async fn resume_collectors(&self) {
+        if let Some(collectors) = &self.collectors {
+            collectors.resume_suspended().await;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ArchiveTarget for TradingBot {
+    async fn strategies(&self) -> Vec<StrategySnapshot> {
+        self.active_strategies
+            .read()
+            .await
+            .iter()
+            .map(|(strategy_id, strategy)| strategy.snapshot(strategy_id))
+            .collect()
+    }
+
+    async fn set_lifecycle(
+        &self,
+        strategy_id: &str,
+        state: StrategyState,
+        deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> bool {
+        let mut strategies = self.active_strategies.write().await;
+        let Some(strategy) = strategies.get_mut(strategy_id) else {
+            return false;
+        };
+        // Terminated strategies are no longer supervised; a restore starts supervising again
+        if state == StrategyState::Terminated {
+            self.supervisor.unregister(strategy_id);
+        } else if strategy.state == StrategyState::Terminated {
+            self.supervisor.register(strategy_id, strategy.trading_pairs.clone());
+        }
+        strategy.state = state;
+        strategy.deleted_at = deleted_at;
+        strategy.updated_at = chrono::Utc::now();
+        true
+    }
+
+    fn cancel_orders(&self, strategy_id: &str) -> usize {
+        TradingBot::cancel_orders(self, strategy_id)
+    }
+
+    async fn strategy_positions(&self, strategy_id: &str) -> Vec<(String, Decimal)> {
+        self.fills.strategy_positions(strategy_id).await
+    }
+
+    async fn flatten_position(&self, strategy_id: &str, trading_pair: &str, size: Decimal) -> Result<(), String> {
+        let data = self
+            .execution_engine
+            .order_book()
+            .latest(trading_pair)
+            .ok_or_else(|| format!("no fresh price for {}", trading_pair))?;
+        let side = if size > Decimal::ZERO { TradeSide::Sell } else { TradeSide::Buy };
+        let params = closing_order(strategy_id, PriorityClass::High, trading_pair, side, size.abs(), &data);
+        self.execute_strategy(params).await.map(|_| ()).map_err(|e| e.to_string())
+    }
+
+    async fn transfer_position(&self, from: &str, to: &str, trading_pair: &str) -> Decimal {
+        self.fills.reassign_position(from, to, trading_pair).await
+    }
+
+    async fn record_audit(&self, entry: StrategyAuditEntry) {
+        self.supervisor.record_audit(entry).await;
+    }
+}
+
+#[async_trait::async_trait]
+impl QuarantineTarget for TradingBot {
+    async fn flatten_pair(&self, trading_pair: &str) -> Result<Decimal, String> {
+        let portfolio = self.portfolio().await;
+        let size = portfolio
+            .get_position(trading_pair)
+            .await
+            .map(|position| position.size)
+            .unwrap_or_default();
+        if size.is_zero() {
+            return Ok(Decimal::ZERO);
+        }
+        let data = self
+            .execution_engine
+            .order_book()
+            .latest(trading_pair)
+            .ok_or_else(|| format!("no fresh price for {}", trading_pair))?;
+        emergency_close(&self.execution_engine, QUARANTINE_STRATEGY_ID, trading_pair, size, &data)
+            .await
+            .map_err(|e| e.to_string())?;
+        counter!("trading_bot.quarantine.positions_flattened").increment(1);
+        Ok(size)
+    }
+}
+
+#[async_trait::async_trait]
+impl PerpPositionBook for TradingBot {
+    async fn perp_positions(&self) -> Vec<Position> {
+        self.portfolio()
+            .await
+            .get_positions()
+            .await
+            .into_iter()
+            .filter(|position| position.trading_pair.is_perp())
+            .collect()
+    }
+
+    async fn correct_position(&self, market: &str, size: Decimal, entry_price: Decimal) -> Result<(), String> {
+        let trading_pair = TradingPair::parse(market).map_err(|e| e.to_string())?;
+        self.portfolio()
+            .await
+            .correct_position(trading_pair, size, entry_price)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn halt_market(&self, market: &str, reason: &str) -> Result<(), String> {
+        // Reductions stay open so an operator can de-risk while the mismatch is investigated
+        let request = QuarantineRequest {
+            trading_pair: market.to_string(),
+            mode: QuarantineMode::NoNewEntries,
+            reason: Some(reason.to_string()),
+        };
+        match self.quarantine.quarantine(request, PERP_RECONCILIATION_ACTOR, chrono::Utc::now()).await {
+            Ok(_) | Err(QuarantineError::AlreadyQuarantined(_)) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl PerpPositionActions for TradingBot {
+    async fn reduce_position(&self, market: &str, signed_size: Decimal) -> Result<(), MarginError> {
+        let data = self
+            .execution_engine
+            .order_book()
+            .latest(market)
+            .ok_or_else(|| MarginError::MissingMark(market.to_string()))?;
+        // The order's signed size is the negative of the position slice it closes
+        emergency_close(&self.execution_engine, MARGIN_STRATEGY_ID, market, -signed_size, &data)
+            .await
+            .map(|_| ())
+            .map_err(|e| MarginError::Action(e.to_string()))
+    }
+
+    async fn close_position(&self, market: &str) -> Result<(), MarginError> {
+        let size = self
+            .portfolio()
+            .await
+            .get_position(market)
+            .await
+            .map(|position| position.size)
+            .unwrap_or_default();
+        if size.is_zero() {
+            return Ok(());
+        }
+        self.reduce_position(market, -size).await
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivitySource for TradingBot {
+    async fn activity(&self) -> ActivityCounts {
+        ActivityCounts {
+            active_strategies: self.active_strategies.read().await.len(),
+            open_positions: MaintenanceTarget::open_positions(self).await.len(),
+            ..Default::default()
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl StateSource for TradingBot {
+    async fn capture(&self) -> BotStateSnapshot {
+        let mut snapshot = self.state_components().capture().await;
+        snapshot.circuit_breaker_open = self.circuit_breaker.is_open();
+        snapshot
+    }
+
+    async fn restore(&self, snapshot: &BotStateSnapshot) -> Result<(), SnapshotError> {
+        self.halted.store(true, Ordering::SeqCst);
+        self.state_components().restore(snapshot).await?;
+        // Restored strategies are supervised again; terminated ones stay unsupervised
+        for strategy in &snapshot.strategies {
+            if strategy.state != StrategyState::Terminated {
+                self.register_strategy(strategy.strategy_id.clone(), Strategy::from_snapshot(strategy))
+                    .await;
+            }
+        }
+        if snapshot.circuit_breaker_open {
+            self.circuit_breaker.trip("restored from snapshot");
+        }
+        warn!(captured_at = %snapshot.captured_at, "State restored; trading halted until resumed");
+        Ok(())
+    }
+
+    async fn resume(&self) {
+        self.halted.store(false, Ordering::SeqCst);
+        info!("Trading resumed after operator review");
+    }
+}
+
+/// Breaker halting execution when the failure rate of submitted orders climbs
+fn trading_breaker() -> Arc<CircuitBreaker> {
+    CircuitBreaker::new(
+        "trading_bot",
+        BreakerConfig::error_rate(CIRCUIT_BREAKER_WINDOW, CIRCUIT_BREAKER_THRESHOLD, CIRCUIT_BREAKER_MIN_CALLS)
+            .with_cooldown(CIRCUIT_BREAKER_COOLDOWN),
+    )
+    .registered()
+}
+
+/// Feeds realized and marked P&L into the daily loss limit, flattening open positions the
+/// first time the hard limit is reached when configured to
+fn spawn_daily_loss_monitor(
+    risk_manager: Arc<RwLock<RiskManager>>,
+    portfolio: Portfolio,
+    execution_engine: Arc<ExecutionEngine>,
+    mut fills: tokio::sync::broadcast::Receiver<FillUpdate>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(DAILY_LOSS_CHECK_INTERVAL);
+        loop {
+            let status = tokio::select! {
+                fill = fills.recv() => match fill {
+                    Ok(update) => match update.realized_pnl {
+                        Some(pnl) => risk_manager.read().await.record_realized_pnl(chrono::Utc::now(), pnl).await,
+                        None => continue,
+                    },
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Daily loss monitor missed {} fills", skipped);
+                        continue;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                },
+                _ = interval.tick() => {
+                    // Marks wait for fresh prices on every open position rather than
+                    // understating the day's loss
+                    let market_data = current_market_data(&portfolio, &execution_engine).await;
+                    let prices = market_data
+                        .iter()
+                        .map(|(pair, data)| (pair.clone(), data.price()))
+                        .collect();
+                    let (unrealized, valuation) = match (
+                        portfolio.unrealized_pnl(&prices).await,
+                        portfolio.valuation(&prices).await,
+                    ) {
+                        (Ok(unrealized), Ok(valuation)) => (unrealized, valuation),
+                        (Err(e), _) | (_, Err(e)) => {
+                            debug!("Skipping daily loss mark: {}", e);
+                            continue;
+                        }
+                    };
+                    risk_manager
+                        .read()
+                        .await
+                        .mark_daily_pnl(chrono::Utc::now(), unrealized, valuation.total_value)
+                        .await
+                }
+            };
+
+            if status.flatten {
+                let market_data = current_market_data(&portfolio, &execution_engine).await;
+                flatten_positions(&portfolio, &execution_engine, &market_data).await;
+            }
+        }
+    })
+}
+
+/// Fresh market data for every pair the portfolio holds, plus SOL/USDC for conversions
+async fn current_market_data(
+    portfolio: &Portfolio,
+    execution_engine: &ExecutionEngine,
+) -> HashMap<String, MarketData> {
+    let order_book = execution_engine.order_book();
+    portfolio
+        .get_positions()
+        .await
+        .into_iter()
+        .map(|position| position.trading_pair.into_string())
+        .chain(std::iter::once(crate::models::market::SOL_USDC_PAIR.to_string()))
+        .filter_map(|pair| order_book.latest(&pair).map(|data| (pair, data)))
+        .collect()
+}
+
+/// Execution result for a landed trade group leg or hedge, charged the venue's taker fee
+fn leg_execution(exchange: &str, fill: &LegFill) -> ExecutionResult {
+    let trade_id = fill.signature.clone().unwrap_or_else(|| Uuid::new_v4().to_string());
+    let mut fees = FeeBreakdown::default();
+    if let Err(e) = fees.add_taker(exchange, fill.size, fill.price) {
+        warn!("Failed to account taker fee: {}", e);
+    }
+    ExecutionResult {
+        trade_id: trade_id.clone(),
+        execution_time: Duration::ZERO,
+        price: fill.price,
+        mev_value: 0.0,
+        fills: vec![OrderFill::new(trade_id, fill.size, fill.price)],
+        fees,
+        slippage: None,
+        compute: None,
+        size_multiplier: None,
+    }
+}
+
+/// Closes every open position with a critical-priority market order on the venue quoting it
+async fn flatten_positions(
+    portfolio: &Portfolio,
+    execution_engine: &ExecutionEngine,
+    market_data: &HashMap<String, MarketData>,
+) {
+    for position in portfolio.get_positions().await {
+        let Some(data) = market_data.get(position.trading_pair.as_str()) else {
+            error!(trading_pair = %position.trading_pair, "No fresh price to flatten position");
+            continue;
+        };
+        match emergency_close(execution_engine, DAILY_LOSS_STRATEGY_ID, &position.trading_pair, position.size, data).await {
+            Ok(_) => {
+                counter!("trading_bot.daily_loss.positions_flattened").increment(1);
+                info!(trading_pair = %position.trading_pair, "Flattened position after hard daily loss");
+            }
+            Err(e) => error!(trading_pair = %position.trading_pair, "Failed to flatten position: {}", e),
+        }
+    }
+}
+
+/// Closes a position of the given signed size with a critical-priority market order,
+/// bypassing the bot's order gates so kill switches can always act
+async fn emergency_close(
+    execution_engine: &ExecutionEngine,
+    strategy_id: &str,
+    trading_pair: &str,
+    size: Decimal,
+    data: &MarketData,
+) -> Result<ExecutionResult, ExecutionError> {
+    let side = if size > Decimal::ZERO { TradeSide::Sell } else { TradeSide::Buy };
+    let params = closing_order(strategy_id, PriorityClass::Critical, trading_pair, side, size.abs(), data);
+    execution_engine.execute_strategy(params).await
+}
+
+/// Market order reducing a position on the venue quoting it
+fn closing_order(
+    strategy_id: &str,
+    priority: PriorityClass,
+    trading_pair: &str,
+    side: TradeSide,
+    size: Decimal,
+    data: &MarketData,
+) -> StrategyParams {
+    StrategyParams {
+        strategy_id: strategy_id.to_string(),
+        priority,
+        trading_pair: trading_pair.to_string(),
+        exchange: data.exchange().to_string(),
+        side,
+        order_type: crate::models::order::OrderType::Market,
+        size,
+        price: data.price(),
+        execution_style: ExecutionStyle::Aggressive,
+        max_slippage: DEFAULT_MAX_SLIPPAGE,
+        tick: None,
+        size_multiplier: None,
+    }
+}
+
 /// Initializes the complete trading bot system
 #[instrument(skip(config), err)]
 pub fn init_trading_bot(config: Config) -> Result<TradingBot, Error> {
@@ -196,6 +2119,19 @@ mod tests {
             solana_client: Arc::new(SolanaClient::new_test()),
             execution_config: ExecutionConfig::default(),
             api_config: ApiConfig::default(),
+            admission: AdmissionConfig {
+                max_concurrent_trades: MAX_CONCURRENT_TRADES,
+                wait_timeout: Duration::from_millis(
+                    crate::config::environment::DEFAULT_ADMISSION_TIMEOUT_MS,
+                ),
+            },
+            supervision: crate::supervision::SupervisionConfig::default(),
+            maintenance: crate::maintenance::MaintenanceConfig::default(),
+            events: crate::events::EventGuardConfig::default(),
+            readiness: crate::execution_engine::readiness::ReadinessConfig::default(),
+            latency: crate::execution_engine::latency::LatencyConfig::default(),
+            throttle: crate::execution_engine::throttle::ThrottleConfig::default(),
+            signals: crate::signals::SignalBusConfig::default(),
         };
 
         let bot = init_trading_bot(config).expect("Failed to initialize trading bot");
@@ -204,6 +2140,69 @@ mod tests {
         assert!(bot.stop().await.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_started_bot_drives_registered_strategy() {
+        use crate::models::strategy::StrategyType;
+        use crate::utils::percent::{Bps, Percent};
+
+        let bot = Arc::new(init_trading_bot(Config::default()).expect("Failed to initialize trading bot"));
+        assert!(bot.start().await.is_ok());
+
+        let params = crate::models::strategy::StrategyParams {
+            position_size_bps: Bps::new(1000),
+            grid_levels: Some(10),
+            stop_loss_pct: Percent::from_percent(dec!(-5)),
+            take_profit_pct: Percent::from_percent(dec!(1)),
+            max_slippage_bps: Bps::new(100),
+            exchanges: vec!["jupiter".to_string()],
+            risk_factor: dec!(0.5),
+            allowed_regimes: Vec::new(),
+            market_making: None,
+        };
+        let mut strategy = Strategy::new(StrategyType::Grid, params, vec!["SOL/USDC".to_string()]).unwrap();
+        strategy.state = StrategyState::Active;
+        bot.register_strategy("grid".to_string(), strategy).await;
+
+        let (market_data_tx, market_data_rx) = tokio::sync::mpsc::channel(8);
+        let driver = bot.clone().spawn_strategy_driver(DriverConfig::default(), market_data_rx);
+        let tick = MarketData::new("SOL/USDC".to_string(), "jupiter".to_string(), dec!(100), dec!(5)).unwrap();
+        market_data_tx.send(tick).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // The update reached a lane for the registered strategy on its pair
+        assert_eq!(driver.lane_count(), 1);
+        assert!(bot.stop().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_restored_strategies_are_supervised() {
+        use crate::models::strategy::{StrategyParams, StrategyType};
+        use crate::utils::percent::{Bps, Percent};
+
+        let params = StrategyParams {
+            position_size_bps: Bps::new(1000),
+            grid_levels: Some(10),
+            stop_loss_pct: Percent::from_percent(dec!(-5)),
+            take_profit_pct: Percent::from_percent(dec!(1)),
+            max_slippage_bps: Bps::new(100),
+            exchanges: vec!["jupiter".to_string()],
+            risk_factor: dec!(0.5),
+            allowed_regimes: Vec::new(),
+            market_making: None,
+        };
+        let source = init_trading_bot(Config::default()).expect("Failed to initialize trading bot");
+        let strategy = Strategy::new(StrategyType::Grid, params, vec!["SOL/USDC".to_string()]).unwrap();
+        source.register_strategy("grid".to_string(), strategy).await;
+        let snapshot = source.capture().await;
+
+        let restored = init_trading_bot(Config::default()).expect("Failed to initialize trading bot");
+        assert!(restored.supervisor().health("grid").is_none());
+        restored.restore(&snapshot).await.unwrap();
+
+        assert!(restored.supervisor().health("grid").is_some());
+        assert!(restored.is_halted());
+    }
+
     #[tokio::test]
     async fn test_circuit_breaker() {
         let config = Config::default();
@@ -211,7 +2210,7 @@ mod tests {
         
         // Simulate errors to trigger circuit breaker
         for _ in 0..100 {
-            bot.circuit_breaker.record_error();
+            bot.circuit_breaker.record_failure();
         }
         
         assert!(bot.circuit_breaker.is_open());
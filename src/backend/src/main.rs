@@ -2,26 +2,114 @@
 //! all system components with comprehensive monitoring and error handling.
 //! Version: 1.0.0
 
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+
 use anyhow::Result;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
 use tokio::signal;
+use tokio::sync::{broadcast, mpsc};
 use tracing::{error, info, warn, instrument};
-use tracing_subscriber::{fmt, EnvFilter};
+use uuid::Uuid;
 
+use crate::api::bridge::{BridgeConfig, EventBridge};
+use crate::api::{GrpcServer, JwtKeyStore, WebhookConfig, WebhookDispatcher};
 use crate::lib::{TradingBot, init_trading_bot};
-use crate::config::init_config;
+use crate::data_collector::lifecycle::{CollectorManager, DexCollectorFactory, LifecycleConfig};
+use crate::data_collector::ohlcv::{CandleAggregator, PriceTick};
+use crate::data_collector::replay::{self, ReplaySource, ReplaySpeed};
+use crate::data_collector::{create_replay_collector, BookUpdate, Collector, CollectorConfig, DexType};
+use crate::db::repositories::{
+    AttributionRepository, CandleRepository, CostModelRepository, DailyReportRepository, EconomicEventRepository, ExecutionStatsRepository, JobRepository, KeyRotationRepository, MaintenanceRepository,
+    MarketDataRepository, MicrostructureRepository, PerformanceRepository, PerpReconciliationRepository,
+    PositionCloseRepository, QuarantineRepository,
+    RegimeRepository,
+    RetentionOverrideRepository, SnapshotRepository, StrategyAuditRepository, StrategyVersionRepository, SubmissionIntentRepository,
+    TradeGroupRepository, TransferRepository, WebhookRepository,
+};
+use crate::execution_engine::adapters::{DriftAdapter, JupiterAdapter, JupiterHttpApi, PairMints};
+use crate::execution_engine::benchmarks::{BenchmarkConfig, BenchmarkService};
+use crate::execution_engine::book_sync::RestSnapshotSource;
+use crate::execution_engine::cost_model::{CostModelCalibrator, CostModelConfig};
+use crate::execution_engine::multi_leg::MultiLegConfig;
+use crate::execution_engine::passive::PassiveConfig;
+use crate::execution_engine::recovery::{OrphanRecovery, RecoveryConfig, SolanaWalletHistory};
+use crate::execution_engine::stats::{ExecutionStatsConfig, ExecutionStatsService};
+use crate::risk_manager::margin::{DriftRestAccountSource, MarginConfig, PerpMarginMonitor};
+use crate::risk_manager::{init_risk_manager, spawn_reload_listener, RiskConfig, RiskManager};
+use crate::risk_manager::perp_reconciliation::{DriftReconciler, ReconcileConfig};
+use crate::jobs::{JobConfig, JobQueue};
+use crate::key_rotation::{KeyRotationConfig, KeyRotationService, KmsDataKeyProvider};
+use crate::models::market::MarketData;
+use crate::models::pair::{PairRegistry, TradingPair};
+use crate::persistence::{PersistenceConfig, PersistenceQueue};
+use crate::reports::{DailyReporter, DailySummaryChannel, ReportConfig, TelegramChannel};
+use crate::retention::{RetentionConfig, RetentionManager};
+use crate::state_snapshot::{S3SnapshotUploader, SnapshotConfig, SnapshotService};
+use crate::strategy_archive::{ArchiveConfig, StrategyArchive};
+use crate::strategy_driver::DriverConfig;
+use crate::sweep::{ProfitSweeper, SweepConfig};
+use crate::system_info::{ActivityCounts, SystemInfo};
+use crate::config::logging::LogConfig;
+use crate::utils::crypto::{data_keys, decrypt_sensitive_data, encrypt_sensitive_data};
+use crate::utils::logger::init_logging;
+use crate::config::{
+    check_config, init_config, subscribe_environment_updates, subscribe_security_updates, CONFIG_EXIT_CODE,
+};
 use crate::utils::metrics::MetricsCollector;
+use crate::utils::signer::build_signer;
+use crate::utils::solana::{FeeEstimator, FeeEstimatorConfig, SolanaClient};
 
 // Global constants from specification
 const RUNTIME_THREADS: usize = 16;
 const SHUTDOWN_TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_secs(30);
+const CHECK_CONFIG_FLAG: &str = "--check-config";
+const RESTORE_FROM_FLAG: &str = "--restore-from";
+const AUTO_MIGRATE_FLAG: &str = "--auto-migrate";
+const MARKET_DATA_BUFFER: usize = 10_000;
+const PRICE_TICK_BUFFER: usize = 10_000;
+const DEFAULT_REDIS_HOST: &str = "localhost";
+const DEFAULT_REDIS_PORT: &str = "6379";
+/// `encrypted_secrets` scope of the API's JWT signing keys, keyed by key id
+const API_SECRET_SCOPE: &str = "api";
+
+// Fee cluster constants
+const JUPITER_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+const DRIFT_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("dRiftyHA39MWEi3m9aunc5MzRF1JYuBsbn6VPcn33UH");
+
+// Venue adapter constants
+const JUPITER_QUOTE_URL: &str = "https://quote-api.jup.ag/v6";
+/// Mint and decimals of the spot tokens Jupiter swaps are routed between
+const SPOT_TOKENS: [(&str, Pubkey, u32); 4] = [
+    ("SOL", solana_sdk::pubkey!("So11111111111111111111111111111111111111112"), 9),
+    ("USDC", solana_sdk::pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"), 6),
+    ("ORCA", solana_sdk::pubkey!("orcaEKTdK7LKz57vaAYr9QeNsVEPfiu6QeMU1kXZE"), 6),
+    ("RAY", solana_sdk::pubkey!("4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R"), 6),
+];
+/// Drift perp market indexes by trading pair
+const DRIFT_PERP_MARKETS: [(&str, u16); 3] = [("SOL-PERP", 0), ("BTC-PERP", 1), ("ETH-PERP", 2)];
+
+// Balance reconciliation constants
+const BALANCE_RECONCILE_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(60);
+/// Recent wallet transactions scanned for the token movement behind a balance change
+const BALANCE_RECONCILE_SIGNATURE_LIMIT: usize = 25;
 
 /// Entry point for the trading bot application with comprehensive initialization and error handling
 #[tokio::main(worker_threads = 16)]
 #[tracing::instrument(err)]
 async fn main() -> Result<()> {
-    // Initialize logging system with JSON formatting and correlation IDs
-    setup_logging().await?;
-    info!("Starting Solana trading bot...");
+    crate::system_info::mark_process_start();
+
+    // Validate configuration and probe dependencies without starting the bot
+    if std::env::args().any(|arg| arg == CHECK_CONFIG_FLAG) {
+        let report = check_config().await;
+        println!("{}", report.render());
+        std::process::exit(if report.is_ok() { 0 } else { CONFIG_EXIT_CODE });
+    }
+
+    let restore_from = restore_from_arg()?;
 
     // Initialize metrics collection
     let metrics = MetricsCollector::new()
@@ -29,13 +117,229 @@ async fn main() -> Result<()> {
     metrics.record_startup().await?;
 
     // Load and validate configuration
-    let config = init_config()
+    let config = match init_config().await {
+        Ok(config) => config,
+        Err(report) => {
+            eprintln!("{}", report);
+            std::process::exit(CONFIG_EXIT_CODE);
+        }
+    };
+
+    // Initialize logging with the configured format and per-module directives
+    setup_logging(&config.logging)?;
+    info!("Starting Solana trading bot...");
+    config.environment.log_startup_banner();
+
+    // Identify the build once in the logs and as static labels on the exported metrics
+    let system_info = SystemInfo::new(&config.environment, ActivityCounts::default(), chrono::Utc::now());
+    system_info.log_startup();
+    system_info
+        .export(&metrics)
+        .map_err(|e| anyhow::anyhow!("Failed to record build info: {}", e))?;
+
+    let pool = crate::db::create_pool(config.database.clone())
+        .await
+        .map_err(|e| anyhow::anyhow!("Database initialization failed: {}", e))?;
+
+    // Refuse to run against an outdated schema unless asked to migrate it
+    let auto_migrate = std::env::args().any(|arg| arg == AUTO_MIGRATE_FLAG);
+    crate::db::ensure_schema_current(&pool, auto_migrate)
+        .await
+        .map_err(|e| anyhow::anyhow!("Database schema check failed: {}", e))?;
+
+    // Data keys are loaded before anything sealed is read; rotation then runs on the KMS
+    // schedule and re-encrypts the webhook and API secrets kept in `encrypted_secrets`
+    let key_rotation = init_key_rotation(&config, pool.clone()).await?;
+    key_rotation.clone().spawn();
+    seal_api_secrets(&config, &KeyRotationRepository::new(pool.clone())).await?;
+
+    // Realized execution outcomes feed the analytics API and break routing ties; fills are
+    // benchmarked against market VWAP/TWAP from the stored ticks once they have settled
+    let execution_store = Arc::new(ExecutionStatsRepository::new(pool.clone()));
+    let tick_source = Arc::new(MarketDataRepository::new(
+        pool.clone(),
+        MetricsCollector::new().map_err(|e| anyhow::anyhow!("Failed to initialize metrics: {}", e))?,
+    ));
+    let benchmarks = Arc::new(BenchmarkService::new(
+        BenchmarkConfig::default(),
+        tick_source.clone(),
+        execution_store.clone(),
+    ));
+    // Trade records, ledger and audit entries are written behind the trading path so a
+    // database outage cannot stall execution
+    let persistence_config =
+        PersistenceConfig::from_env().map_err(|e| anyhow::anyhow!("Invalid persistence configuration: {}", e))?;
+    let persistence = Arc::new(PersistenceQueue::new(persistence_config));
+    persistence.clone().spawn();
+    let execution_stats = Arc::new(
+        ExecutionStatsService::new(ExecutionStatsConfig::default(), execution_store.clone())
+            .with_benchmarks(benchmarks)
+            .with_persistence(persistence.clone()),
+    );
+
+    // Pre-trade impact uses per-venue cost models regressed weekly from the same executions
+    let cost_models = Arc::new(CostModelCalibrator::new(CostModelConfig::default(), execution_store));
+    cost_models.set_store(Arc::new(CostModelRepository::new(pool.clone())));
+
+    // Market data is pruned daily by the global policy unless an admin override for the
+    // pair, or the pair on one exchange, says otherwise
+    let retention = Arc::new(RetentionManager::new(RetentionConfig::from_policy(
+        &config.database.retention_policy,
+    )));
+    retention.set_store(Arc::new(RetentionOverrideRepository::new(pool.clone())));
+    let overrides = retention
+        .load()
         .await
-        .map_err(|e| anyhow::anyhow!("Configuration initialization failed: {}", e))?;
+        .map_err(|e| anyhow::anyhow!("Failed to restore retention overrides: {}", e))?;
+    info!(overrides, "Retention overrides loaded");
+
+    // Background jobs outlive the process that enqueued them; features register their
+    // handlers on the queue before the workers start
+    let jobs = Arc::new(JobQueue::new(JobConfig::default(), Arc::new(JobRepository::new(pool.clone()))));
+
+    // Orders are persisted before submission so fills landing after a crash are recovered
+    // from the wallet's history before trading resumes
+    let orphan_recovery = init_orphan_recovery(&config, pool.clone()).await?;
+
+    // Risk windows live in Redis so a restart cannot reset them
+    let redis = init_redis()?;
+    let risk_manager = init_risk(&config, redis.clone())?;
+
+    // Perp positions are reconciled against the trading wallet's Drift account; runs are
+    // kept for audit
+    let perp_reconciler = init_perp_reconciler(&config, pool.clone()).await?;
+
+    // Registered webhook endpoints are restored with their secrets decrypted for signing
+    let webhooks = init_webhooks(&config, pool.clone()).await?;
+
+    // Collectors send validated market data to the strategy driver for the registered
+    // pairs; the manager restarts them individually when they stop producing
+    let pairs = Arc::new(PairRegistry::default());
+    let (market_data_tx, market_data_rx) = mpsc::channel(MARKET_DATA_BUFFER);
+    let (book_updates_tx, book_updates_rx) = mpsc::channel(MARKET_DATA_BUFFER);
+    let collectors = init_collectors(&config, market_data_tx.clone(), book_updates_tx).await?;
+
+    // `--record <path>` captures everything the strategies see; `--replay <path>` feeds a
+    // capture back in place of the live venues
+    let market_data_rx = match replay::record_path_from_args(std::env::args()) {
+        Some(path) => {
+            let (market_data_rx, _) = replay::spawn_recording_tap(market_data_rx, &path)
+                .map_err(|e| anyhow::anyhow!("Failed to open capture file {}: {}", path.display(), e))?;
+            info!(path = %path.display(), "Recording collected market data");
+            market_data_rx
+        }
+        None => market_data_rx,
+    };
+    let replay_from = replay::replay_from_args(std::env::args());
+
+    // Candles are aggregated from screened prices, exposed to strategies, flushed to the
+    // database and streamed to WebSocket clients
+    let candles = Arc::new(CandleAggregator::default());
+    let candle_store = Arc::new(CandleRepository::new(pool.clone()));
+
+    // Priority fees are bid from recent prioritization fees sampled per exchange
+    let fee_estimator = init_fee_estimator(&config)?;
+    fee_estimator.clone().spawn_refresh();
+
+    // Swaps carry their minimum output from a build-time Jupiter quote
+    let jupiter_adapter = init_jupiter_adapter(&config, &pairs).await?;
+
+    // Passive-style orders rest post-only on Drift, signed by the trading wallet
+    let maker_client = init_maker_client(&config).await?;
 
     // Initialize trading bot with all components
     let bot = init_trading_bot(config.clone())
-        .map_err(|e| anyhow::anyhow!("Trading bot initialization failed: {}", e))?;
+        .map_err(|e| anyhow::anyhow!("Trading bot initialization failed: {}", e))?
+        .with_live_trading(config.environment.live_trading_enabled())
+        .with_fee_estimator(fee_estimator.clone())
+        .with_passive_executor(PassiveConfig::default(), maker_client)
+        .with_exchange_adapter(jupiter_adapter)
+        .with_execution_stats(execution_stats.clone())
+        .with_persistence(persistence.clone())
+        .with_cost_models(cost_models.clone())
+        .with_performance_repository(Arc::new(PerformanceRepository::new(pool.clone())))
+        .with_version_repository(Arc::new(StrategyVersionRepository::new(pool.clone())))
+        .with_strategy_audit(Arc::new(StrategyAuditRepository::new(pool.clone())))
+        .with_attribution_repository(Arc::new(AttributionRepository::new(pool.clone())))
+        .with_regime_repository(Arc::new(RegimeRepository::new(pool.clone())))
+        .with_microstructure_repository(Arc::new(MicrostructureRepository::new(pool.clone())))
+        .with_maintenance_store(Arc::new(MaintenanceRepository::new(pool.clone())))
+        .with_quarantine_store(Arc::new(QuarantineRepository::new(pool.clone())))
+        .with_suspect_store(tick_source.clone())
+        .with_event_store(Arc::new(EconomicEventRepository::new(pool.clone())))
+        .with_position_close_store(Arc::new(PositionCloseRepository::new(pool.clone())))
+        .with_webhooks(webhooks.clone())
+        .with_collectors(collectors.clone())
+        .with_risk_manager(risk_manager.clone())
+        .with_event_bridge(Arc::new(EventBridge::new(BridgeConfig::default(), redis.clone())))
+        .with_pair_registry(pairs.clone())
+        .with_candles(candles.clone())
+        .with_orphan_recovery(orphan_recovery);
+
+    // Drift orders are limited to the slippage allowed from the engine's live mid
+    let drift_adapter = drift_adapter(&bot, &pairs);
+    let bot = bot.with_exchange_adapter(Arc::new(drift_adapter));
+
+    // Arbitrage legs across venues execute as trade groups, recorded with their hedges
+    let bot = bot.with_trade_groups(MultiLegConfig::default(), Arc::new(TradeGroupRepository::new(pool.clone())));
+
+    // Stream order books and trades over WebSocket when a port is configured
+    let bot = match config.environment.ws_port {
+        Some(port) => bot.with_websocket(SocketAddr::from(([0, 0, 0, 0], port))),
+        None => bot,
+    };
+
+    let bot = Arc::new(bot);
+
+    // Perp margin is checked pre-trade and liquidation escalations act through the bot
+    let perp_margin = init_perp_margin(&config, bot.clone(), &pairs).await?;
+    perp_margin.set_webhooks(webhooks.clone());
+    risk_manager.write().await.set_perp_margin(perp_margin.clone());
+
+    // Sweeps go to the cold wallet pinned in the environment; off when none is configured
+    let sweep_config =
+        SweepConfig::from_env().map_err(|e| anyhow::anyhow!("Invalid profit sweep configuration: {}", e))?;
+    let sweep_store = TransferRepository::new(pool.clone());
+
+    // Deposits and withdrawals found by reconciling the wallet are kept with the swept transfers
+    let transfer_store = TransferRepository::new(pool.clone());
+
+    // End-of-day reports are assembled from the analytics tables and stored alongside them
+    let report_store = Arc::new(DailyReportRepository::new(pool.clone()));
+
+    // Rehydrate from a stored snapshot before trading starts; the bot stays halted for review
+    let snapshots = init_snapshots(bot.clone(), &config, pool);
+    if let Some(snapshot_id) = restore_from {
+        snapshots
+            .restore(snapshot_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to restore snapshot {}: {}", snapshot_id, e))?;
+        warn!(%snapshot_id, "Restored from snapshot; trading halted until resumed via the admin API");
+    }
+
+    // Deleted strategies come back with the snapshot; downstream jobs must keep skipping them
+    let archive = Arc::new(StrategyArchive::new(ArchiveConfig::default(), bot.clone()));
+    let deleted = archive.load().await;
+    bot.set_strategy_archive(archive);
+    info!(deleted, "Strategy archive loaded");
+
+    // Pairs quarantined before the restart stay blocked; flatten-and-block closes positions
+    // through the bot's emergency closure path
+    let quarantine = bot.quarantine();
+    quarantine.set_target(bot.clone());
+    let quarantined = quarantine
+        .load()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to restore pair quarantines: {}", e))?;
+    info!(quarantined, "Pair quarantines loaded");
+
+    // Event windows still open or ahead tighten risk limits from the first order
+    let events = bot
+        .events()
+        .load(chrono::Utc::now())
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to load economic events: {}", e))?;
+    info!(events, "Economic event calendar loaded");
 
     // Start trading bot components
     bot.start()
@@ -44,47 +348,537 @@ async fn main() -> Result<()> {
 
     info!("Trading bot started successfully");
 
+    // Venue depth is applied in sequence; a gap resyncs the book from the venue's L2 snapshot
+    bot.set_book_snapshot_source(
+        DexType::Drift.as_str(),
+        Arc::new(RestSnapshotSource::new(
+            DexType::Drift.as_str(),
+            config.environment.endpoints.drift_rest_url.clone(),
+        )),
+    );
+    bot.spawn_book_listener(book_updates_rx);
+
+    // Run registered strategies on every collected update that passes the sanity checks
+    let market_data_rx = bot.screen_market_data(market_data_rx);
+    let (price_ticks_tx, price_ticks_rx) = broadcast::channel(PRICE_TICK_BUFFER);
+    candles.clone().spawn_ingest_task(price_ticks_rx);
+    candles.clone().spawn_flush_task(candle_store);
+    let market_data_rx = spawn_price_ticks(market_data_rx, price_ticks_tx);
+    bot.clone().spawn_strategy_driver(DriverConfig::default(), market_data_rx);
+    match replay_from {
+        Some((path, speed)) => spawn_replay(path, speed, market_data_tx),
+        None => start_collectors(&collectors, &pairs).await,
+    }
+
+    webhooks.start();
+    spawn_webhook_notifications(&webhooks);
+    bot.clone().spawn_perp_reconciler(perp_reconciler);
+    perp_margin.spawn();
+    snapshots.spawn();
+    execution_stats.spawn();
+    bot.attribution().spawn(jobs.clone());
+    spawn_daily_reports(&bot, jobs.clone(), report_store).await?;
+    cost_models.clone().spawn(jobs.clone());
+    retention.spawn(tick_source);
+    jobs.clone().spawn();
+
+    if let Some(sweep_config) = sweep_config {
+        spawn_profit_sweep(bot.clone(), &config, sweep_config, sweep_store).await?;
+    }
+    spawn_balance_reconciliation(bot.clone(), &config, transfer_store).await?;
+
+    // Wind trading down ahead of scheduled maintenance windows, including ones restored
+    // from before the restart
+    bot.clone().spawn_maintenance();
+
+    // Serve gRPC alongside REST/WebSocket when a port is configured
+    bot.clone().spawn_signal_consumers();
+    if let Some(port) = config.environment.grpc_port {
+        spawn_grpc_server(bot.clone(), &config, port).await;
+    }
+
     // Handle shutdown signals
     handle_shutdown(bot).await?;
 
     info!("Trading bot shutdown completed");
+    crate::utils::telemetry::shutdown();
+    Ok(())
+}
+
+/// Parses the snapshot id following `--restore-from`, if given
+fn restore_from_arg() -> Result<Option<Uuid>> {
+    let mut args = std::env::args().skip_while(|arg| arg != RESTORE_FROM_FLAG);
+    if args.next().is_none() {
+        return Ok(None);
+    }
+    let id = args
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("{} requires a snapshot id", RESTORE_FROM_FLAG))?;
+    Uuid::parse_str(&id)
+        .map(Some)
+        .map_err(|e| anyhow::anyhow!("Invalid snapshot id {}: {}", id, e))
+}
+
+/// Builds the state snapshot service over the database, uploading to the backup bucket when enabled
+fn init_snapshots(bot: Arc<TradingBot>, config: &crate::config::AppConfig, pool: sqlx::PgPool) -> Arc<SnapshotService> {
+    let snapshot_config = SnapshotConfig::from_backup(&config.database.backup_config);
+    let bucket = snapshot_config.s3_bucket.clone();
+
+    let mut service = SnapshotService::new(snapshot_config, bot, Arc::new(SnapshotRepository::new(pool)));
+    if let Some(bucket) = bucket {
+        service = service.with_uploader(Arc::new(S3SnapshotUploader::new(bucket)));
+    }
+    Arc::new(service)
+}
+
+/// Installs the global subscriber; safe to call more than once
+fn setup_logging(config: &LogConfig) -> Result<()> {
+    init_logging(config).map_err(|e| anyhow::anyhow!("Failed to initialize logging: {}", e))?;
+    info!(
+        json = config.json_format,
+        trace_export = config.tracing.enabled,
+        "Logging system initialized successfully"
+    );
+    Ok(())
+}
+
+/// Starts the gRPC server in the background, sharing the bot's order path and JWT keys
+async fn spawn_grpc_server(bot: Arc<TradingBot>, config: &crate::config::AppConfig, port: u16) {
+    let key_store = Arc::new(JwtKeyStore::new(&config.security.jwt));
+    key_store.clone().spawn_reload_listener(subscribe_security_updates());
+
+    let server = GrpcServer::new(
+        key_store,
+        bot.clone(),
+        bot.portfolio().await,
+        bot.order_book_sender(),
+    );
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    tokio::spawn(async move {
+        if let Err(e) = server.serve(addr).await {
+            error!("gRPC server stopped: {}", e);
+        }
+    });
+}
+
+/// Builds the collector manager; every collector it creates sends market data to
+/// `market_data_tx` and sequenced depth updates to `book_updates_tx`
+async fn init_collectors(
+    config: &crate::config::AppConfig,
+    market_data_tx: mpsc::Sender<MarketData>,
+    book_updates_tx: mpsc::Sender<BookUpdate>,
+) -> Result<Arc<CollectorManager>> {
+    let client = SolanaClient::new(config.environment.endpoints.solana_rpc_url.clone(), None, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create collector RPC client: {}", e))?;
+    let mut collector_config = CollectorConfig::for_environment(&config.environment);
+    collector_config.market_data_tx = Some(market_data_tx);
+    collector_config.book_updates_tx = Some(book_updates_tx);
+
+    let factory = DexCollectorFactory::new(Arc::new(client), collector_config);
+    Ok(Arc::new(CollectorManager::new(LifecycleConfig::default(), Arc::new(factory))))
+}
+
+/// Starts one collector per venue: Drift follows the perp markets, the spot venues the rest.
+/// A venue that fails to start is logged; the others still run.
+async fn start_collectors(collectors: &CollectorManager, pairs: &PairRegistry) {
+    let (perps, spot): (Vec<TradingPair>, Vec<TradingPair>) =
+        pairs.pairs().into_iter().partition(|pair| pair.is_perp());
+    for dex in DexType::ALL {
+        let trading_pairs = if dex == DexType::Drift { perps.clone() } else { spot.clone() };
+        if let Err(e) = collectors.start(dex.clone(), trading_pairs).await {
+            error!(%dex, "Failed to start collector: {}", e);
+        }
+    }
+}
+
+/// Publishes every screened sample to the candle price stream on its way to the strategies
+fn spawn_price_ticks(
+    mut market_data: mpsc::Receiver<MarketData>,
+    price_ticks: broadcast::Sender<PriceTick>,
+) -> mpsc::Receiver<MarketData> {
+    let (tx, rx) = mpsc::channel(MARKET_DATA_BUFFER);
+    tokio::spawn(async move {
+        while let Some(update) = market_data.recv().await {
+            // No receivers only means nothing is aggregating right now
+            let _ = price_ticks.send(PriceTick::from(&update));
+            if tx.send(update).await.is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// Replays a capture file through the collector channel in place of the live venues
+fn spawn_replay(path: std::path::PathBuf, speed: ReplaySpeed, market_data_tx: mpsc::Sender<MarketData>) {
+    info!(path = %path.display(), ?speed, "Replaying recorded market data");
+    let collector = create_replay_collector(ReplaySource::File(path.clone()), speed, market_data_tx);
+    tokio::spawn(async move {
+        match collector.start_collection().await {
+            Ok(()) => info!(path = %path.display(), "Replay finished"),
+            Err(e) => error!(path = %path.display(), "Replay failed: {}", e),
+        }
+    });
+}
+
+/// Connects to the Redis instance shared by replicas, from `REDIS_HOST`, `REDIS_PORT` and
+/// `REDIS_PASSWORD`
+fn init_redis() -> Result<Arc<redis::Client>> {
+    let host = std::env::var("REDIS_HOST").unwrap_or_else(|_| DEFAULT_REDIS_HOST.to_string());
+    let port = std::env::var("REDIS_PORT").unwrap_or_else(|_| DEFAULT_REDIS_PORT.to_string());
+    let url = match std::env::var("REDIS_PASSWORD") {
+        Ok(password) => format!("redis://:{}@{}:{}", password, host, port),
+        Err(_) => format!("redis://{}:{}", host, port),
+    };
+    redis::Client::open(url)
+        .map(Arc::new)
+        .map_err(|e| anyhow::anyhow!("Invalid Redis configuration: {}", e))
+}
+
+/// Builds the risk manager; order rate and turnover windows and the day's loss window
+/// persist to Redis
+fn init_risk(
+    config: &crate::config::AppConfig,
+    redis: Arc<redis::Client>,
+) -> Result<Arc<tokio::sync::RwLock<RiskManager>>> {
+    let manager = init_risk_manager(RiskConfig::from_environment(&config.environment))
+        .map_err(|e| anyhow::anyhow!("Risk manager initialization failed: {}", e))?
+        .with_velocity_persistence(redis.clone())
+        .with_daily_loss_persistence(redis);
+    let manager = Arc::new(tokio::sync::RwLock::new(manager));
+
+    // Reloaded limits apply without resetting the tracked windows or the day's losses
+    spawn_reload_listener(manager.clone(), subscribe_environment_updates());
+    Ok(manager)
+}
+
+/// Builds the priority fee estimator with one account cluster per exchange, keyed by the
+/// exchange name trades are submitted under
+fn init_fee_estimator(config: &crate::config::AppConfig) -> Result<Arc<FeeEstimator>> {
+    let rpc_client = Arc::new(RpcClient::new(config.environment.endpoints.solana_rpc_url.clone()));
+    let estimator = FeeEstimator::new(rpc_client, FeeEstimatorConfig::default());
+
+    let pump_fun_program = Pubkey::from_str(&config.environment.endpoints.pump_fun_program_id)
+        .map_err(|e| anyhow::anyhow!("Invalid Pump Fun program id: {}", e))?;
+    estimator.register_cluster(DexType::Jupiter.as_str(), vec![JUPITER_PROGRAM_ID]);
+    estimator.register_cluster(DexType::Drift.as_str(), vec![DRIFT_PROGRAM_ID]);
+    estimator.register_cluster(DexType::PumpFun.as_str(), vec![pump_fun_program]);
+
+    Ok(Arc::new(estimator))
+}
+
+/// Builds the Jupiter adapter for the trading wallet over every registered spot pair with
+/// known mints
+async fn init_jupiter_adapter(config: &crate::config::AppConfig, pairs: &PairRegistry) -> Result<Arc<JupiterAdapter>> {
+    let signer = build_signer(&config.security.signer)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to build swap signer: {}", e))?;
+    let token = |symbol: &str| {
+        SPOT_TOKENS
+            .iter()
+            .find(|(known, _, _)| *known == symbol)
+            .map(|(_, mint, decimals)| (*mint, *decimals))
+    };
+
+    let mut adapter = JupiterAdapter::new(Arc::new(JupiterHttpApi::new(JUPITER_QUOTE_URL)), signer.pubkey());
+    for pair in pairs.pairs().into_iter().filter(|pair| !pair.is_perp()) {
+        match (token(pair.base()), token(pair.quote())) {
+            (Some((base_mint, base_decimals)), Some((quote_mint, quote_decimals))) => {
+                adapter = adapter.with_pair(
+                    pair.as_str(),
+                    PairMints { base_mint, base_decimals, quote_mint, quote_decimals },
+                );
+            }
+            _ => warn!(trading_pair = %pair, "No mints known for pair; Jupiter orders for it are rejected"),
+        }
+    }
+    Ok(Arc::new(adapter))
+}
+
+/// Builds the Drift adapter over the bot's live books for every registered perp market
+fn drift_adapter(bot: &TradingBot, pairs: &PairRegistry) -> DriftAdapter {
+    let mut adapter = DriftAdapter::new(bot.order_book());
+    for pair in pairs.pairs().into_iter().filter(|pair| pair.is_perp()) {
+        match DRIFT_PERP_MARKETS.iter().find(|(market, _)| *market == pair.as_str()) {
+            Some((_, market_index)) => adapter = adapter.with_market(pair.as_str(), *market_index),
+            None => warn!(trading_pair = %pair, "No Drift market index known for pair; orders for it are rejected"),
+        }
+    }
+    adapter
+}
+
+/// Builds the signing RPC client passive orders are placed, repriced and cancelled through
+async fn init_maker_client(config: &crate::config::AppConfig) -> Result<Arc<SolanaClient>> {
+    let signer = build_signer(&config.security.signer)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to build maker signer: {}", e))?;
+    let client = SolanaClient::new(config.environment.endpoints.solana_rpc_url.clone(), None, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create maker RPC client: {}", e))?
+        .with_signer(signer);
+    Ok(Arc::new(client))
+}
+
+/// Builds perp reconciliation against the Drift user account of the trading wallet
+async fn init_perp_reconciler(
+    config: &crate::config::AppConfig,
+    pool: sqlx::PgPool,
+) -> Result<Arc<DriftReconciler>> {
+    let signer = build_signer(&config.security.signer)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to build reconciliation signer: {}", e))?;
+    let source = DriftRestAccountSource::new(
+        config.environment.endpoints.drift_rest_url.clone(),
+        signer.pubkey().to_string(),
+    );
+
+    let reconciler = DriftReconciler::new(ReconcileConfig::default(), Arc::new(source));
+    reconciler.set_store(Arc::new(PerpReconciliationRepository::new(pool)));
+    Ok(Arc::new(reconciler))
+}
+
+/// Builds the perp margin monitor over the trading wallet's Drift account, tracking every
+/// registered perp market
+async fn init_perp_margin(
+    config: &crate::config::AppConfig,
+    bot: Arc<TradingBot>,
+    pairs: &PairRegistry,
+) -> Result<Arc<PerpMarginMonitor>> {
+    let signer = build_signer(&config.security.signer)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to build margin signer: {}", e))?;
+    let source = DriftRestAccountSource::new(
+        config.environment.endpoints.drift_rest_url.clone(),
+        signer.pubkey().to_string(),
+    );
+
+    let markets = pairs.pairs().into_iter().filter(|pair| pair.is_perp()).map(|pair| pair.to_string());
+    let margin_config = MarginConfig::default().with_markets(markets);
+    Ok(Arc::new(PerpMarginMonitor::new(margin_config, Arc::new(source), bot)))
+}
+
+/// Builds orphaned order recovery over the trading wallet's on-chain history
+async fn init_orphan_recovery(
+    config: &crate::config::AppConfig,
+    pool: sqlx::PgPool,
+) -> Result<Arc<OrphanRecovery>> {
+    let signer = build_signer(&config.security.signer)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to build recovery signer: {}", e))?;
+    let client = SolanaClient::new(config.environment.endpoints.solana_rpc_url.clone(), None, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create recovery RPC client: {}", e))?;
+    let history = SolanaWalletHistory::new(Arc::new(client), signer.pubkey());
+
+    Ok(Arc::new(OrphanRecovery::new(
+        Arc::new(SubmissionIntentRepository::new(pool)),
+        Arc::new(history),
+        RecoveryConfig::default(),
+    )))
+}
+
+/// Loads the webhook registry from the database
+async fn init_webhooks(config: &crate::config::AppConfig, pool: sqlx::PgPool) -> Result<Arc<WebhookDispatcher>> {
+    let repository = WebhookRepository::new(pool, config.security.kms.key_id.clone());
+    let webhooks = Arc::new(WebhookDispatcher::new(WebhookConfig::default(), Some(Arc::new(repository))));
+    let loaded = webhooks
+        .load()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to restore webhooks: {}", e))?;
+    info!(loaded, "Webhooks loaded");
+    Ok(webhooks)
+}
+
+/// Builds data key rotation over the KMS master key and loads the stored data keys
+async fn init_key_rotation(config: &crate::config::AppConfig, pool: sqlx::PgPool) -> Result<Arc<KeyRotationService>> {
+    let service = KeyRotationService::new(
+        KeyRotationConfig::from_kms(&config.security.kms),
+        Arc::new(KmsDataKeyProvider::new(&config.security.kms)),
+        Arc::new(KeyRotationRepository::new(pool)),
+        data_keys(),
+    );
+    service
+        .load_keys()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to load data keys: {}", e))?;
+    Ok(Arc::new(service))
+}
+
+/// Seals the configured API signing keys into `encrypted_secrets` by key id, warning when
+/// a key differs from the one sealed by the previous start
+async fn seal_api_secrets(config: &crate::config::AppConfig, secrets: &KeyRotationRepository) -> Result<()> {
+    let kms_key_id = config.security.kms.key_id.clone();
+    for key in config.security.jwt.active_keys() {
+        if let Some(stored) = secrets
+            .load_secret(API_SECRET_SCOPE, &key.kid)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load API secret {}: {}", key.kid, e))?
+        {
+            match decrypt_sensitive_data(stored, kms_key_id.clone()).await {
+                Ok(previous) if previous != *key.secret.expose() => {
+                    warn!(kid = %key.kid, "API signing key changed; tokens signed with the previous key no longer validate")
+                }
+                Ok(_) => continue,
+                Err(e) => warn!(kid = %key.kid, "Failed to open stored API secret: {}", e),
+            }
+        }
+
+        let sealed = encrypt_sensitive_data(key.secret.expose().clone(), kms_key_id.clone())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to seal API secret {}: {}", key.kid, e))?;
+        secrets
+            .save_secret(API_SECRET_SCOPE, &key.kid, &sealed)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to store API secret {}: {}", key.kid, e))?;
+    }
+    Ok(())
+}
+
+/// Raises an operator alert whenever an endpoint is disabled for persistent failures
+fn spawn_webhook_notifications(webhooks: &WebhookDispatcher) -> tokio::task::JoinHandle<()> {
+    let mut notifications = webhooks.subscribe_notifications();
+    tokio::spawn(async move {
+        loop {
+            match notifications.recv().await {
+                Ok(disabled) => error!(
+                    webhook_id = %disabled.webhook_id,
+                    url = %disabled.url,
+                    consecutive_failures = disabled.consecutive_failures,
+                    "Webhook disabled: {}",
+                    disabled.reason
+                ),
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Missed {} webhook disable notifications", skipped);
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+/// Generates the previous day's performance report each day and pushes its summary through
+/// the webhook dispatcher and, when configured, Telegram
+async fn spawn_daily_reports(
+    bot: &Arc<TradingBot>,
+    jobs: Arc<JobQueue>,
+    repository: Arc<DailyReportRepository>,
+) -> Result<()> {
+    // Report days start when the daily loss budget resets
+    let report_config = ReportConfig::from_env(&bot.daily_loss_limits().await)
+        .map_err(|e| anyhow::anyhow!("Invalid daily report configuration: {}", e))?;
+    let generate_at = report_config.generate_at;
+    let reporter = Arc::new(DailyReporter::new(report_config));
+    reporter.set_source(repository.clone());
+    reporter.set_store(repository);
+    if let Some(webhooks) = bot.webhooks() {
+        let wallet_address = bot.portfolio().await.wallet_address().to_string();
+        reporter.add_channel(Arc::new(DailySummaryChannel::new(webhooks.clone(), wallet_address)));
+        reporter.add_channel(webhooks);
+    }
+    if let Some(telegram) = TelegramChannel::from_env() {
+        reporter.add_channel(Arc::new(telegram));
+    }
+
+    info!(%generate_at, "Daily reports scheduled");
+    reporter.spawn(jobs);
+    Ok(())
+}
+
+/// Starts the profit sweep, restoring the day's swept total so a restart keeps the cap
+async fn spawn_profit_sweep(
+    bot: Arc<TradingBot>,
+    config: &crate::config::AppConfig,
+    sweep_config: SweepConfig,
+    store: TransferRepository,
+) -> Result<()> {
+    let signer = build_signer(&config.security.signer)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to build sweep signer: {}", e))?;
+    let transport = SolanaClient::new(config.environment.endpoints.solana_rpc_url.clone(), None, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create sweep RPC client: {}", e))?
+        .with_signer(signer);
+
+    let dry_run = sweep_config.dry_run;
+    let destination = sweep_config.destination.address().to_string();
+    let sweeper = Arc::new(ProfitSweeper::new(sweep_config, bot.portfolio().await, Arc::new(transport)));
+    sweeper.set_store(Arc::new(store));
+    sweeper.set_attribution(bot.attribution());
+    if let Some(webhooks) = bot.webhooks() {
+        sweeper.set_webhooks(webhooks);
+    }
+    let swept_today = sweeper
+        .load(chrono::Utc::now())
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to restore swept total: {}", e))?;
+
+    info!(%destination, dry_run, %swept_today, "Profit sweep started");
+    sweeper.spawn();
     Ok(())
 }
 
-/// Configures comprehensive application logging and monitoring system
-#[instrument(err)]
-async fn setup_logging() -> Result<()> {
-    let log_level = std::env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string());
-
-    // Configure JSON log formatter for production
-    let formatter = fmt::format()
-        .with_level(true)
-        .with_target(true)
-        .with_thread_ids(true)
-        .with_file(true)
-        .with_line_number(true)
-        .json();
-
-    // Set up logging subscriber with filtering
-    let subscriber = fmt::Subscriber::builder()
-        .with_env_filter(EnvFilter::from_default_env()
-            .add_directive(log_level.parse()?)
-            .add_directive("tokio=warn".parse()?)
-            .add_directive("runtime=warn".parse()?))
-        .with_writer(std::io::stdout)
-        .with_formatter(formatter)
-        .with_ansi(false)
-        .with_max_level(tracing::Level::TRACE)
-        .try_init()
-        .map_err(|e| anyhow::anyhow!("Failed to initialize logging: {}", e))?;
-
-    info!("Logging system initialized successfully");
+/// Periodically reconciles the wallet's USDC balance so deposits and withdrawals are kept
+/// out of P&L and persisted as transfers
+async fn spawn_balance_reconciliation(
+    bot: Arc<TradingBot>,
+    config: &crate::config::AppConfig,
+    store: TransferRepository,
+) -> Result<()> {
+    let signer = build_signer(&config.security.signer)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to build reconciliation signer: {}", e))?;
+    let wallet = signer.pubkey();
+    let client = SolanaClient::new(config.environment.endpoints.solana_rpc_url.clone(), None, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create reconciliation RPC client: {}", e))?;
+    let (_, usdc_mint, _) = SPOT_TOKENS
+        .iter()
+        .find(|(symbol, _, _)| *symbol == "USDC")
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("USDC mint is not configured"))?;
+
+    info!(%wallet, "Balance reconciliation started");
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(BALANCE_RECONCILE_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let observed = match client.get_token_balance(&wallet, &usdc_mint).await {
+                Ok(observed) => observed,
+                Err(e) => {
+                    warn!("Failed to read wallet USDC balance: {}", e);
+                    continue;
+                }
+            };
+            let changes = match client
+                .get_token_balance_changes(&wallet, &usdc_mint, BALANCE_RECONCILE_SIGNATURE_LIMIT)
+                .await
+            {
+                Ok(changes) => changes,
+                Err(e) => {
+                    warn!("Failed to scan wallet USDC transactions: {}", e);
+                    continue;
+                }
+            };
+
+            match bot.reconcile_wallet_balance(observed, &changes).await {
+                Ok(Some(transfer)) => {
+                    if let Err(e) = store.record_transfer(&transfer).await {
+                        error!(transfer_id = %transfer.id, "Failed to persist detected transfer: {}", e);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Balance reconciliation failed: {}", e),
+            }
+        }
+    });
     Ok(())
 }
 
 /// Manages graceful shutdown of all system components
 #[instrument(skip(bot), err)]
-async fn handle_shutdown(bot: TradingBot) -> Result<()> {
+async fn handle_shutdown(bot: Arc<TradingBot>) -> Result<()> {
     // Wait for shutdown signal
     let ctrl_c = signal::ctrl_c();
     let terminate = signal::unix::signal(signal::unix::SignalKind::terminate())?;
@@ -123,9 +917,11 @@ async fn handle_shutdown(bot: TradingBot) -> Result<()> {
 mod tests {
     use super::*;
 
-    #[tokio::test]
-    async fn test_logging_setup() {
-        assert!(setup_logging().await.is_ok());
+    #[test]
+    fn test_logging_setup() {
+        let config = LogConfig::new(&crate::config::environment::EnvironmentConfig::new());
+        assert!(setup_logging(&config).is_ok());
+        assert!(setup_logging(&config).is_ok());
     }
 
     #[tokio::test]
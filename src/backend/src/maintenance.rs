@@ -0,0 +1,908 @@
+//! Scheduled maintenance windows for deploys and database work. Ahead of a window the
+//! scheduler stops new positions from opening and pauses strategies; in flatten mode it also
+//! works every open position down to zero in TWAP slices that finish at the window start.
+//! Collectors are suspended for the window itself and everything resumes once it ends.
+//! Windows are persisted, so a restart before or during one picks it back up.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - metrics = "0.20"
+//! - parking_lot = "0.12"
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use parking_lot::RwLock as SyncRwLock;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::risk_manager::daily_loss::DailyLossLevel;
+use crate::risk_manager::exposure::TradeSide;
+use crate::risk_manager::RiskManager;
+use crate::utils::circuit_breaker::{BreakerState, CircuitBreaker};
+use crate::utils::time::to_chrono;
+
+// Maintenance constants
+const METRICS_PREFIX: &str = "trading_bot.maintenance";
+const DEFAULT_LEAD_TIME: Duration = Duration::from_secs(15 * 60);
+const DEFAULT_SLICE_INTERVAL: Duration = Duration::from_secs(60);
+const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const MAX_WINDOW_DURATION: Duration = Duration::from_secs(24 * 3600);
+const SIZE_DP: u32 = 9;
+
+/// Maintenance error types
+#[derive(Error, Debug)]
+pub enum MaintenanceError {
+    #[error("invalid maintenance window: {0}")]
+    InvalidWindow(String),
+    #[error("overlaps maintenance window {0}")]
+    Overlap(Uuid),
+    #[error("maintenance window not found: {0}")]
+    NotFound(Uuid),
+    #[error("store error: {0}")]
+    Store(String),
+}
+
+/// What happens to open positions ahead of a window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenanceMode {
+    /// Positions are left open; only new positions and strategies stop
+    PauseOnly,
+    /// Positions are reduced to zero by the window start
+    Flatten,
+}
+
+impl MaintenanceMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PauseOnly => "pause_only",
+            Self::Flatten => "flatten",
+        }
+    }
+}
+
+impl std::str::FromStr for MaintenanceMode {
+    type Err = MaintenanceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pause_only" => Ok(Self::PauseOnly),
+            "flatten" => Ok(Self::Flatten),
+            other => Err(MaintenanceError::InvalidWindow(format!("unknown mode: {}", other))),
+        }
+    }
+}
+
+/// Where the clock is relative to a window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenancePhase {
+    Scheduled,
+    /// Inside the lead time: no new positions, strategies paused, positions reducing
+    WindingDown,
+    InProgress,
+    Completed,
+}
+
+/// Admin request scheduling a window
+#[derive(Debug, Clone, Deserialize)]
+pub struct MaintenanceRequest {
+    pub start: DateTime<Utc>,
+    pub duration_secs: u64,
+    pub mode: MaintenanceMode,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Planned maintenance window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    pub id: Uuid,
+    pub start: DateTime<Utc>,
+    pub duration_secs: u64,
+    pub mode: MaintenanceMode,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl MaintenanceWindow {
+    pub fn end(&self) -> DateTime<Utc> {
+        self.start + chrono::Duration::seconds(self.duration_secs as i64)
+    }
+
+    /// When new positions stop and, in flatten mode, reduction begins
+    pub fn wind_down_start(&self, lead_time: Duration) -> DateTime<Utc> {
+        self.start - to_chrono(lead_time)
+    }
+
+    pub fn phase(&self, now: DateTime<Utc>, lead_time: Duration) -> MaintenancePhase {
+        if now >= self.end() {
+            MaintenancePhase::Completed
+        } else if now >= self.start {
+            MaintenancePhase::InProgress
+        } else if now >= self.wind_down_start(lead_time) {
+            MaintenancePhase::WindingDown
+        } else {
+            MaintenancePhase::Scheduled
+        }
+    }
+
+    fn overlaps(&self, other: &MaintenanceWindow) -> bool {
+        self.start < other.end() && other.start < self.end()
+    }
+}
+
+/// Size a position should be down to by a point in time
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReductionSlice {
+    pub at: DateTime<Utc>,
+    /// Absolute size left after this slice
+    pub remaining: Decimal,
+}
+
+/// Splits reducing `size` to zero between `from` and `until` into evenly sized slices every
+/// `interval`, the last one landing on `until`
+pub fn twap_slices(
+    size: Decimal,
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+    interval: Duration,
+) -> Vec<ReductionSlice> {
+    let span = (until - from).num_milliseconds().max(0);
+    let interval_ms = interval.as_millis().max(1) as i64;
+    let count = ((span + interval_ms - 1) / interval_ms).max(1);
+    (1..=count)
+        .map(|i| ReductionSlice {
+            at: from + chrono::Duration::milliseconds(span * i / count),
+            remaining: (size * Decimal::from(count - i) / Decimal::from(count)).round_dp(SIZE_DP),
+        })
+        .collect()
+}
+
+/// TWAP schedule taking one position to zero by the window start
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReductionPlan {
+    pub trading_pair: String,
+    /// Side of the reducing orders
+    pub side: TradeSide,
+    pub slices: Vec<ReductionSlice>,
+    /// Slices already worked
+    pub completed: usize,
+}
+
+impl ReductionPlan {
+    fn new(trading_pair: String, size: Decimal, from: DateTime<Utc>, until: DateTime<Utc>, interval: Duration) -> Self {
+        Self {
+            trading_pair,
+            side: if size > Decimal::ZERO { TradeSide::Sell } else { TradeSide::Buy },
+            slices: twap_slices(size.abs(), from, until, interval),
+            completed: 0,
+        }
+    }
+
+    /// Remaining size targeted by the latest slice due at `now`, if a new one is due
+    fn due_target(&self, now: DateTime<Utc>) -> Option<(usize, Decimal)> {
+        let due = self.slices.iter().take_while(|slice| slice.at <= now).count();
+        (due > self.completed).then(|| (due, self.slices[due - 1].remaining))
+    }
+
+    fn is_complete(&self) -> bool {
+        self.completed >= self.slices.len()
+    }
+}
+
+/// State that could stop a window from winding down or resuming as planned
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "conflict", rename_all = "snake_case")]
+pub enum MaintenanceConflict {
+    /// The daily loss limit already blocks new risk and stays in force after the window
+    DailyLossLimit { level: DailyLossLevel },
+    /// Reduction orders are refused while the breaker is open
+    CircuitBreakerOpen { breaker: String, reason: Option<String> },
+}
+
+impl fmt::Display for MaintenanceConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DailyLossLimit { level } => write!(
+                f,
+                "{} daily loss limit breached; new risk stays blocked after the window ends",
+                level.as_str()
+            ),
+            Self::CircuitBreakerOpen { breaker, reason } => write!(
+                f,
+                "circuit breaker {} is open ({}); reduction orders will be refused",
+                breaker,
+                reason.as_deref().unwrap_or("no reason recorded")
+            ),
+        }
+    }
+}
+
+/// Newly scheduled window with the conflicts present when it was scheduled
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduledWindow {
+    pub window: MaintenanceWindow,
+    pub conflicts: Vec<MaintenanceConflict>,
+}
+
+/// Window with its phase at the time of the request
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowStatus {
+    #[serde(flatten)]
+    pub window: MaintenanceWindow,
+    pub phase: MaintenancePhase,
+}
+
+/// Scheduler state served by the admin API
+#[derive(Debug, Clone, Serialize)]
+pub struct MaintenanceStatus {
+    pub windows: Vec<WindowStatus>,
+    pub blocking_new_positions: bool,
+    pub active_window: Option<Uuid>,
+    pub paused_strategies: Vec<String>,
+    pub reductions: Vec<ReductionPlan>,
+    pub conflicts: Vec<MaintenanceConflict>,
+}
+
+/// Timing of the wind-down
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaintenanceConfig {
+    /// How long before a window new positions stop and reduction begins
+    pub lead_time: Duration,
+    /// Spacing of TWAP reduction slices
+    pub slice_interval: Duration,
+    pub check_interval: Duration,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            lead_time: DEFAULT_LEAD_TIME,
+            slice_interval: DEFAULT_SLICE_INTERVAL,
+            check_interval: DEFAULT_CHECK_INTERVAL,
+        }
+    }
+}
+
+/// Persistence for scheduled windows
+#[async_trait]
+pub trait MaintenanceStore: Send + Sync {
+    async fn save(&self, window: &MaintenanceWindow) -> Result<(), MaintenanceError>;
+    async fn remove(&self, id: Uuid) -> Result<(), MaintenanceError>;
+    /// Windows not yet ended
+    async fn pending(&self, now: DateTime<Utc>) -> Result<Vec<MaintenanceWindow>, MaintenanceError>;
+}
+
+/// The bot as seen by the scheduler
+#[async_trait]
+pub trait MaintenanceTarget: Send + Sync {
+    /// Open positions as signed sizes by pair; positive is long
+    async fn open_positions(&self) -> Vec<(String, Decimal)>;
+    async fn reduce_position(&self, trading_pair: &str, side: TradeSide, size: Decimal) -> Result<(), String>;
+    /// Pauses every running strategy, returning the ones it paused
+    async fn pause_strategies(&self, window: &MaintenanceWindow) -> Vec<String>;
+    async fn resume_strategies(&self, strategy_ids: &[String]);
+    async fn suspend_collectors(&self);
+    async fn resume_collectors(&self);
+}
+
+/// Wind-down state of the window currently being applied
+#[derive(Debug, Clone)]
+struct ActiveWindow {
+    window: MaintenanceWindow,
+    paused_strategies: Vec<String>,
+    reductions: Vec<ReductionPlan>,
+    collectors_suspended: bool,
+}
+
+#[derive(Debug, Default)]
+struct Windows {
+    loaded: bool,
+    scheduled: Vec<MaintenanceWindow>,
+}
+
+/// Schedules maintenance windows and drives the bot through each one
+pub struct MaintenanceScheduler {
+    config: MaintenanceConfig,
+    windows: Mutex<Windows>,
+    active: Mutex<Option<ActiveWindow>>,
+    blocking: AtomicBool,
+    store: SyncRwLock<Option<Arc<dyn MaintenanceStore>>>,
+    risk_manager: SyncRwLock<Option<Arc<RwLock<RiskManager>>>>,
+    breakers: SyncRwLock<Vec<Arc<CircuitBreaker>>>,
+}
+
+impl fmt::Debug for MaintenanceScheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MaintenanceScheduler")
+            .field("config", &self.config)
+            .field("blocking", &self.blocking.load(Ordering::SeqCst))
+            .finish()
+    }
+}
+
+impl MaintenanceScheduler {
+    pub fn new(config: MaintenanceConfig) -> Self {
+        Self {
+            config,
+            windows: Mutex::new(Windows::default()),
+            active: Mutex::new(None),
+            blocking: AtomicBool::new(false),
+            store: SyncRwLock::new(None),
+            risk_manager: SyncRwLock::new(None),
+            breakers: SyncRwLock::new(Vec::new()),
+        }
+    }
+
+    /// Persists windows so they survive a restart
+    pub fn set_store(&self, store: Arc<dyn MaintenanceStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    /// Reports the daily loss limit as a conflict while it blocks new risk
+    pub fn set_risk_manager(&self, risk_manager: Arc<RwLock<RiskManager>>) {
+        *self.risk_manager.write() = Some(risk_manager);
+    }
+
+    /// Reports a breaker as a conflict while it is open
+    pub fn watch_breaker(&self, breaker: Arc<CircuitBreaker>) {
+        self.breakers.write().push(breaker);
+    }
+
+    /// Whether orders opening or adding to positions are refused
+    pub fn blocks_new_positions(&self) -> bool {
+        self.blocking.load(Ordering::SeqCst)
+    }
+
+    /// Schedules a window; the request is refused if it starts in the past or overlaps
+    /// another window
+    pub async fn schedule(
+        &self,
+        request: MaintenanceRequest,
+        now: DateTime<Utc>,
+    ) -> Result<ScheduledWindow, MaintenanceError> {
+        if request.start <= now {
+            return Err(MaintenanceError::InvalidWindow("start must be in the future".to_string()));
+        }
+        if request.duration_secs == 0 || request.duration_secs > MAX_WINDOW_DURATION.as_secs() {
+            return Err(MaintenanceError::InvalidWindow(format!(
+                "duration must be between 1 and {} seconds",
+                MAX_WINDOW_DURATION.as_secs()
+            )));
+        }
+        let window = MaintenanceWindow {
+            id: Uuid::new_v4(),
+            start: request.start,
+            duration_secs: request.duration_secs,
+            mode: request.mode,
+            reason: request.reason,
+            created_at: now,
+        };
+
+        {
+            let mut windows = self.loaded_windows(now).await;
+            if let Some(existing) = windows.scheduled.iter().find(|existing| existing.overlaps(&window)) {
+                return Err(MaintenanceError::Overlap(existing.id));
+            }
+            let store = self.store.read().clone();
+            if let Some(store) = store {
+                store.save(&window).await?;
+            }
+            windows.scheduled.push(window.clone());
+            windows.scheduled.sort_by_key(|window| window.start);
+        }
+
+        let conflicts = self.conflicts(now).await;
+        for conflict in &conflicts {
+            warn!(window_id = %window.id, "Maintenance window scheduled with conflict: {}", conflict);
+        }
+        info!(
+            window_id = %window.id,
+            start = %window.start,
+            duration_secs = window.duration_secs,
+            mode = window.mode.as_str(),
+            "Maintenance window scheduled"
+        );
+        counter!(format!("{}.scheduled", METRICS_PREFIX), 1);
+        Ok(ScheduledWindow { window, conflicts })
+    }
+
+    /// Cancels a window; one already winding down resumes on the next tick
+    pub async fn cancel(&self, id: Uuid, now: DateTime<Utc>) -> Result<MaintenanceWindow, MaintenanceError> {
+        let mut windows = self.loaded_windows(now).await;
+        let index = windows
+            .scheduled
+            .iter()
+            .position(|window| window.id == id)
+            .ok_or(MaintenanceError::NotFound(id))?;
+        let store = self.store.read().clone();
+        if let Some(store) = store {
+            store.remove(id).await?;
+        }
+        info!(window_id = %id, "Maintenance window cancelled");
+        Ok(windows.scheduled.remove(index))
+    }
+
+    pub async fn status(&self, now: DateTime<Utc>) -> MaintenanceStatus {
+        let windows = self
+            .loaded_windows(now)
+            .await
+            .scheduled
+            .iter()
+            .map(|window| WindowStatus {
+                window: window.clone(),
+                phase: window.phase(now, self.config.lead_time),
+            })
+            .collect();
+        let active = self.active.lock().await.clone();
+        MaintenanceStatus {
+            windows,
+            blocking_new_positions: self.blocks_new_positions(),
+            active_window: active.as_ref().map(|active| active.window.id),
+            paused_strategies: active.as_ref().map(|active| active.paused_strategies.clone()).unwrap_or_default(),
+            reductions: active.map(|active| active.reductions).unwrap_or_default(),
+            conflicts: self.conflicts(now).await,
+        }
+    }
+
+    /// Daily loss and circuit breaker state that interferes with maintenance
+    pub async fn conflicts(&self, now: DateTime<Utc>) -> Vec<MaintenanceConflict> {
+        let mut conflicts = Vec::new();
+        let risk_manager = self.risk_manager.read().clone();
+        if let Some(risk_manager) = risk_manager {
+            let level = risk_manager.read().await.daily_loss_status(now).await.level;
+            if level != DailyLossLevel::Within {
+                conflicts.push(MaintenanceConflict::DailyLossLimit { level });
+            }
+        }
+        for breaker in self.breakers.read().iter() {
+            let status = breaker.status();
+            if status.state == BreakerState::Open {
+                conflicts.push(MaintenanceConflict::CircuitBreakerOpen {
+                    breaker: status.name,
+                    reason: status.reason,
+                });
+            }
+        }
+        conflicts
+    }
+
+    /// Advances the current window: enters wind-down, works due reduction slices, suspends
+    /// collectors at the window start and resumes everything once the window ends
+    pub async fn tick(&self, target: &dyn MaintenanceTarget, now: DateTime<Utc>) {
+        let next = {
+            let mut windows = self.loaded_windows(now).await;
+            let expired: Vec<Uuid> = windows
+                .scheduled
+                .iter()
+                .filter(|window| window.end() <= now)
+                .map(|window| window.id)
+                .collect();
+            windows.scheduled.retain(|window| window.end() > now);
+            let store = self.store.read().clone();
+            if let Some(store) = store {
+                for id in expired {
+                    if let Err(e) = store.remove(id).await {
+                        warn!(window_id = %id, "Failed to remove ended maintenance window: {}", e);
+                    }
+                }
+            }
+            windows.scheduled.first().cloned()
+        };
+
+        let mut active = self.active.lock().await;
+
+        // The applied window ended or was cancelled
+        if let Some(current) = active.as_ref() {
+            if next.as_ref().map_or(true, |next| next.id != current.window.id) {
+                let finished = active.take().expect("active window checked above");
+                self.finish(target, finished).await;
+            }
+        }
+
+        let Some(window) = next else {
+            return;
+        };
+        let phase = window.phase(now, self.config.lead_time);
+        if phase == MaintenancePhase::Scheduled {
+            return;
+        }
+
+        if active.is_none() {
+            *active = Some(self.begin(target, &window, now).await);
+        }
+        let current = active.as_mut().expect("active window set above");
+
+        if window.mode == MaintenanceMode::Flatten {
+            self.reduce(target, current, now).await;
+        }
+
+        if phase == MaintenancePhase::InProgress && !current.collectors_suspended {
+            if window.mode == MaintenanceMode::Flatten {
+                let residual = target.open_positions().await;
+                if !residual.is_empty() {
+                    error!(
+                        window_id = %window.id,
+                        positions = residual.len(),
+                        "Positions still open at maintenance window start"
+                    );
+                    counter!(format!("{}.residual_positions", METRICS_PREFIX), residual.len() as u64);
+                }
+            }
+            target.suspend_collectors().await;
+            current.collectors_suspended = true;
+            info!(window_id = %window.id, "Maintenance window started");
+        }
+    }
+
+    /// Checks maintenance windows on the configured interval
+    pub fn spawn(self: Arc<Self>, target: Arc<dyn MaintenanceTarget>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.check_interval);
+            loop {
+                interval.tick().await;
+                self.tick(target.as_ref(), Utc::now()).await;
+            }
+        })
+    }
+
+    async fn begin(&self, target: &dyn MaintenanceTarget, window: &MaintenanceWindow, now: DateTime<Utc>) -> ActiveWindow {
+        self.blocking.store(true, Ordering::SeqCst);
+        gauge!(format!("{}.blocking_new_positions", METRICS_PREFIX), 1.0);
+
+        let paused_strategies = target.pause_strategies(window).await;
+        let reductions = match window.mode {
+            MaintenanceMode::Flatten => target
+                .open_positions()
+                .await
+                .into_iter()
+                .filter(|(_, size)| !size.is_zero())
+                .map(|(trading_pair, size)| {
+                    ReductionPlan::new(trading_pair, size, now, window.start.max(now), self.config.slice_interval)
+                })
+                .collect(),
+            MaintenanceMode::PauseOnly => Vec::new(),
+        };
+
+        for conflict in self.conflicts(now).await {
+            warn!(window_id = %window.id, "Maintenance wind-down conflict: {}", conflict);
+            counter!(format!("{}.conflicts", METRICS_PREFIX), 1);
+        }
+        info!(
+            window_id = %window.id,
+            mode = window.mode.as_str(),
+            paused = paused_strategies.len(),
+            reductions = reductions.len(),
+            "Maintenance wind-down started"
+        );
+
+        ActiveWindow {
+            window: window.clone(),
+            paused_strategies,
+            reductions,
+            collectors_suspended: false,
+        }
+    }
+
+    /// Submits the part of each position due by `now`; slices that fail are retried next tick
+    async fn reduce(&self, target: &dyn MaintenanceTarget, active: &mut ActiveWindow, now: DateTime<Utc>) {
+        let positions = target.open_positions().await;
+        for plan in active.reductions.iter_mut().filter(|plan| !plan.is_complete()) {
+            let Some((due, remaining)) = plan.due_target(now) else {
+                continue;
+            };
+            let current = positions
+                .iter()
+                .find(|(pair, _)| *pair == plan.trading_pair)
+                .map_or(Decimal::ZERO, |(_, size)| *size);
+            // Only work against the side the plan was made for
+            let held = match plan.side {
+                TradeSide::Sell => current.max(Decimal::ZERO),
+                TradeSide::Buy => (-current).max(Decimal::ZERO),
+            };
+            let size = held - remaining;
+            if size > Decimal::ZERO {
+                if let Err(e) = target.reduce_position(&plan.trading_pair, plan.side, size).await {
+                    warn!(trading_pair = %plan.trading_pair, "Maintenance reduction slice failed: {}", e);
+                    counter!(format!("{}.reduction_failures", METRICS_PREFIX), 1);
+                    continue;
+                }
+                counter!(format!("{}.reduction_slices", METRICS_PREFIX), 1);
+            }
+            plan.completed = due;
+        }
+    }
+
+    async fn finish(&self, target: &dyn MaintenanceTarget, active: ActiveWindow) {
+        if active.collectors_suspended {
+            target.resume_collectors().await;
+        }
+        target.resume_strategies(&active.paused_strategies).await;
+        self.blocking.store(false, Ordering::SeqCst);
+        gauge!(format!("{}.blocking_new_positions", METRICS_PREFIX), 0.0);
+        info!(
+            window_id = %active.window.id,
+            resumed = active.paused_strategies.len(),
+            "Maintenance window ended, trading resumed"
+        );
+        counter!(format!("{}.completed", METRICS_PREFIX), 1);
+    }
+
+    /// Scheduled windows, restored from the store on first use
+    async fn loaded_windows(&self, now: DateTime<Utc>) -> tokio::sync::MutexGuard<'_, Windows> {
+        let mut windows = self.windows.lock().await;
+        if !windows.loaded {
+            windows.loaded = true;
+            let store = self.store.read().clone();
+            if let Some(store) = store {
+                match store.pending(now).await {
+                    Ok(pending) => {
+                        if !pending.is_empty() {
+                            info!(windows = pending.len(), "Restored scheduled maintenance windows");
+                        }
+                        windows.scheduled = pending;
+                        windows.scheduled.sort_by_key(|window| window.start);
+                    }
+                    Err(e) => error!("Failed to restore maintenance windows: {}", e),
+                }
+            }
+        }
+        windows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::circuit_breaker::BreakerConfig;
+    use chrono::TimeZone;
+    use parking_lot::Mutex as SyncMutex;
+    use rust_decimal_macros::dec;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        windows: SyncMutex<HashMap<Uuid, MaintenanceWindow>>,
+    }
+
+    #[async_trait]
+    impl MaintenanceStore for MemoryStore {
+        async fn save(&self, window: &MaintenanceWindow) -> Result<(), MaintenanceError> {
+            self.windows.lock().insert(window.id, window.clone());
+            Ok(())
+        }
+
+        async fn remove(&self, id: Uuid) -> Result<(), MaintenanceError> {
+            self.windows.lock().remove(&id);
+            Ok(())
+        }
+
+        async fn pending(&self, now: DateTime<Utc>) -> Result<Vec<MaintenanceWindow>, MaintenanceError> {
+            Ok(self.windows.lock().values().filter(|window| window.end() > now).cloned().collect())
+        }
+    }
+
+    /// Bot stand-in whose reductions fill immediately
+    #[derive(Default)]
+    struct TestBot {
+        positions: SyncMutex<HashMap<String, Decimal>>,
+        orders: SyncMutex<Vec<(String, TradeSide, Decimal)>>,
+        strategies: SyncMutex<HashMap<String, bool>>,
+        collectors_running: SyncMutex<bool>,
+    }
+
+    #[async_trait]
+    impl MaintenanceTarget for TestBot {
+        async fn open_positions(&self) -> Vec<(String, Decimal)> {
+            let mut positions: Vec<_> = self
+                .positions
+                .lock()
+                .iter()
+                .filter(|(_, size)| !size.is_zero())
+                .map(|(pair, size)| (pair.clone(), *size))
+                .collect();
+            positions.sort();
+            positions
+        }
+
+        async fn reduce_position(&self, trading_pair: &str, side: TradeSide, size: Decimal) -> Result<(), String> {
+            let signed = if side == TradeSide::Sell { -size } else { size };
+            *self.positions.lock().get_mut(trading_pair).ok_or("no position")? += signed;
+            self.orders.lock().push((trading_pair.to_string(), side, size));
+            Ok(())
+        }
+
+        async fn pause_strategies(&self, _window: &MaintenanceWindow) -> Vec<String> {
+            let mut paused = Vec::new();
+            for (id, running) in self.strategies.lock().iter_mut() {
+                if *running {
+                    *running = false;
+                    paused.push(id.clone());
+                }
+            }
+            paused
+        }
+
+        async fn resume_strategies(&self, strategy_ids: &[String]) {
+            let mut strategies = self.strategies.lock();
+            for id in strategy_ids {
+                strategies.insert(id.clone(), true);
+            }
+        }
+
+        async fn suspend_collectors(&self) {
+            *self.collectors_running.lock() = false;
+        }
+
+        async fn resume_collectors(&self) {
+            *self.collectors_running.lock() = true;
+        }
+    }
+
+    fn at(minute: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000, 0).single().unwrap() + chrono::Duration::minutes(minute)
+    }
+
+    fn scheduler() -> MaintenanceScheduler {
+        MaintenanceScheduler::new(MaintenanceConfig {
+            lead_time: Duration::from_secs(10 * 60),
+            slice_interval: Duration::from_secs(2 * 60),
+            check_interval: Duration::from_secs(1),
+        })
+    }
+
+    fn request(start: DateTime<Utc>, mode: MaintenanceMode) -> MaintenanceRequest {
+        MaintenanceRequest {
+            start,
+            duration_secs: 30 * 60,
+            mode,
+            reason: Some("database upgrade".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_twap_slices_finish_at_window_start() {
+        let slices = twap_slices(dec!(10), at(0), at(10), Duration::from_secs(120));
+        let remaining: Vec<Decimal> = slices.iter().map(|slice| slice.remaining).collect();
+        assert_eq!(remaining, vec![dec!(8), dec!(6), dec!(4), dec!(2), dec!(0)]);
+        assert_eq!(slices.last().unwrap().at, at(10));
+        assert_eq!(slices[0].at, at(2));
+
+        // A late start still ends flat in a single slice
+        let late = twap_slices(dec!(3), at(10), at(10), Duration::from_secs(120));
+        assert_eq!(late, vec![ReductionSlice { at: at(10), remaining: dec!(0) }]);
+    }
+
+    #[tokio::test]
+    async fn test_flatten_window_reduces_on_schedule_and_resumes() {
+        let scheduler = scheduler();
+        let bot = TestBot::default();
+        bot.positions.lock().insert("SOL/USDC".to_string(), dec!(10));
+        bot.positions.lock().insert("BONK/USDC".to_string(), dec!(-5));
+        bot.strategies.lock().insert("grid".to_string(), true);
+        bot.strategies.lock().insert("arb".to_string(), false);
+        *bot.collectors_running.lock() = true;
+
+        let scheduled = scheduler.schedule(request(at(20), MaintenanceMode::Flatten), at(0)).await.unwrap();
+        assert!(scheduled.conflicts.is_empty());
+
+        // Outside the lead time nothing changes
+        scheduler.tick(&bot, at(5)).await;
+        assert!(!scheduler.blocks_new_positions());
+        assert!(bot.strategies.lock()["grid"]);
+
+        // T-10m: new positions stop and strategies pause
+        scheduler.tick(&bot, at(10)).await;
+        assert!(scheduler.blocks_new_positions());
+        assert!(!bot.strategies.lock()["grid"]);
+        assert!(bot.orders.lock().is_empty());
+
+        let mut remaining = Vec::new();
+        for minute in [12, 14, 15, 16, 18] {
+            scheduler.tick(&bot, at(minute)).await;
+            remaining.push(bot.positions.lock()["SOL/USDC"]);
+        }
+        assert_eq!(remaining, vec![dec!(8), dec!(6), dec!(6), dec!(4), dec!(2)]);
+        assert_eq!(bot.positions.lock()["BONK/USDC"], dec!(-1));
+        assert!(*bot.collectors_running.lock());
+
+        // Window start: flat, collectors suspended
+        scheduler.tick(&bot, at(20)).await;
+        assert!(bot.open_positions().await.is_empty());
+        assert!(!*bot.collectors_running.lock());
+        let orders = bot.orders.lock().clone();
+        assert!(orders.iter().filter(|(pair, ..)| pair == "SOL/USDC").all(|(_, side, size)| {
+            *side == TradeSide::Sell && *size == dec!(2)
+        }));
+        assert!(orders.iter().filter(|(pair, ..)| pair == "BONK/USDC").all(|(_, side, size)| {
+            *side == TradeSide::Buy && *size == dec!(1)
+        }));
+
+        scheduler.tick(&bot, at(35)).await;
+        assert!(scheduler.blocks_new_positions());
+
+        // Window end: everything paused by the window resumes, nothing else does
+        scheduler.tick(&bot, at(50)).await;
+        assert!(!scheduler.blocks_new_positions());
+        assert!(*bot.collectors_running.lock());
+        assert!(bot.strategies.lock()["grid"]);
+        assert!(!bot.strategies.lock()["arb"]);
+        let status = scheduler.status(at(50)).await;
+        assert!(status.windows.is_empty());
+        assert!(status.active_window.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pause_only_window_leaves_positions() {
+        let scheduler = scheduler();
+        let bot = TestBot::default();
+        bot.positions.lock().insert("SOL/USDC".to_string(), dec!(10));
+
+        scheduler.schedule(request(at(20), MaintenanceMode::PauseOnly), at(0)).await.unwrap();
+        for minute in [10, 15, 20, 25] {
+            scheduler.tick(&bot, at(minute)).await;
+        }
+        assert!(scheduler.blocks_new_positions());
+        assert!(bot.orders.lock().is_empty());
+        assert_eq!(bot.positions.lock()["SOL/USDC"], dec!(10));
+    }
+
+    #[tokio::test]
+    async fn test_windows_survive_restart_and_cancel_resumes() {
+        let store = Arc::new(MemoryStore::default());
+        let first = scheduler();
+        first.set_store(store.clone());
+        let scheduled = first.schedule(request(at(20), MaintenanceMode::Flatten), at(0)).await.unwrap();
+
+        // Overlapping and past windows are refused
+        assert!(matches!(
+            first.schedule(request(at(40), MaintenanceMode::PauseOnly), at(0)).await,
+            Err(MaintenanceError::Overlap(id)) if id == scheduled.window.id
+        ));
+        assert!(first.schedule(request(at(-1), MaintenanceMode::PauseOnly), at(0)).await.is_err());
+
+        // A new process restores the window and picks up the wind-down
+        let restarted = scheduler();
+        restarted.set_store(store.clone());
+        let bot = TestBot::default();
+        bot.positions.lock().insert("SOL/USDC".to_string(), dec!(4));
+        restarted.tick(&bot, at(16)).await;
+        assert!(restarted.blocks_new_positions());
+        assert_eq!(restarted.status(at(16)).await.reductions[0].slices.len(), 2);
+
+        restarted.cancel(scheduled.window.id, at(17)).await.unwrap();
+        restarted.tick(&bot, at(17)).await;
+        assert!(!restarted.blocks_new_positions());
+        assert!(store.windows.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_open_breaker_reported_as_conflict() {
+        let scheduler = scheduler();
+        let breaker = Arc::new(CircuitBreaker::new("maintenance_test", BreakerConfig::manual()));
+        scheduler.watch_breaker(breaker.clone());
+        breaker.trip("venue outage");
+
+        let scheduled = scheduler.schedule(request(at(20), MaintenanceMode::Flatten), at(0)).await.unwrap();
+        assert_eq!(
+            scheduled.conflicts,
+            vec![MaintenanceConflict::CircuitBreakerOpen {
+                breaker: "maintenance_test".to_string(),
+                reason: Some("venue outage".to_string()),
+            }]
+        );
+    }
+}
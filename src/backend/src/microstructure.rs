@@ -0,0 +1,800 @@
+//! Order book microstructure features for strategies. Every accepted order book update is
+//! turned into a `MicrostructureSnapshot` from the pair's precomputed `BookView`: top-of-book
+//! imbalance and microprice, resting depth within 10, 25 and 50 bps of the mid, the quoted and
+//! effective spread, and the rolling quote update rate. The latest snapshot per pair is exposed to
+//! strategies through their context, snapshots are broadcast at a throttled cadence, and
+//! per-minute aggregates are persisted for research.
+//!
+//! Degenerate books never produce misleading numbers: one-sided and crossed books leave the
+//! price-derived features null, and every degenerate condition is flagged on the snapshot.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::{Mutex, RwLock as SyncRwLock};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::data_collector::ohlcv::CandleInterval;
+use crate::execution_engine::book_view::BookView;
+use crate::execution_engine::order_book::LiveOrderBook;
+use crate::models::market::OrderBookLevel;
+
+// Microstructure constants
+/// Distances from the mid, in basis points, that resting depth is measured within
+pub const DEPTH_BANDS_BPS: [u32; 3] = [10, 25, 50];
+const METRICS_PREFIX: &str = "trading_bot.microstructure";
+const SNAPSHOT_CHANNEL_CAPACITY: usize = 1024;
+const DEFAULT_PUBLISH_INTERVAL: Duration = Duration::from_millis(500);
+const DEFAULT_RATE_WINDOW: Duration = Duration::from_secs(10);
+const DEFAULT_STALE_AFTER: Duration = Duration::from_secs(5);
+const DEFAULT_EFFECTIVE_NOTIONAL: Decimal = Decimal::from_parts(1_000, 0, 0, false, 0);
+/// Aggregates kept for retry while the store is unavailable
+const MAX_PENDING_AGGREGATES: usize = 10_000;
+const BPS_PER_UNIT: Decimal = Decimal::from_parts(10_000, 0, 0, false, 0);
+
+/// Microstructure error types
+#[derive(Error, Debug)]
+pub enum MicrostructureError {
+    #[error("microstructure store error: {0}")]
+    Store(String),
+}
+
+/// Condition that makes a book's features unreliable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookFlag {
+    /// A side has no levels; price-derived features are null
+    OneSided,
+    /// Best bid at or above best ask; price-derived features are null
+    Crossed,
+    /// The book update is older than the staleness threshold
+    Stale,
+    /// The book awaits a resync after a sequence gap or checksum failures
+    Degraded,
+}
+
+/// Volume resting within `bps` of the mid on each side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepthBand {
+    pub bps: u32,
+    pub bid: Decimal,
+    pub ask: Decimal,
+}
+
+/// Microstructure features of one pair's book at one update
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MicrostructureSnapshot {
+    pub trading_pair: String,
+    pub exchange: String,
+    /// (bid - ask) / (bid + ask) of the best levels' volume, from -1 (all ask) to 1 (all bid)
+    pub imbalance: Option<Decimal>,
+    /// Mid weighted toward the side with less resting volume at the touch
+    #[serde(default)]
+    pub microprice: Option<Decimal>,
+    /// Depth per `DEPTH_BANDS_BPS` entry, over the levels in the book view
+    pub depth: Option<Vec<DepthBand>>,
+    pub spread_bps: Option<Decimal>,
+    /// Round-trip cost of buying and selling the configured notional, in bps of the mid to
+    /// four places; null when the view lacks the depth to fill it on either side
+    pub effective_spread_bps: Option<Decimal>,
+    /// Book updates per second over the rate window
+    pub quote_rate: f64,
+    pub flags: Vec<BookFlag>,
+    /// Timestamp of the source book update
+    pub book_timestamp: DateTime<Utc>,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl MicrostructureSnapshot {
+    /// Computes the features of a book view from scratch, in O(levels)
+    pub fn compute(view: &BookView, quote_rate: f64, config: &MicrostructureConfig, now: DateTime<Utc>) -> Self {
+        let mut flags = Vec::new();
+        let top = match (view.bids.first(), view.asks.first()) {
+            (Some(bid), Some(ask)) if bid.price() >= ask.price() => {
+                flags.push(BookFlag::Crossed);
+                None
+            }
+            (Some(bid), Some(ask)) => Some((bid, ask)),
+            _ => {
+                flags.push(BookFlag::OneSided);
+                None
+            }
+        };
+        let stale_after = chrono::Duration::from_std(config.stale_after).unwrap_or_else(|_| chrono::Duration::zero());
+        if now - view.timestamp > stale_after {
+            flags.push(BookFlag::Stale);
+        }
+        if view.degraded {
+            flags.push(BookFlag::Degraded);
+        }
+
+        let (mut imbalance, mut depth, mut spread_bps, mut effective_spread_bps) = (None, None, None, None);
+        let microprice = top.and(view.microprice);
+        if let Some((bid, ask)) = top {
+            let mid = (bid.price() + ask.price()) / Decimal::TWO;
+            let top_volume = bid.volume() + ask.volume();
+            if top_volume > Decimal::ZERO {
+                imbalance = Some((bid.volume() - ask.volume()) / top_volume);
+            }
+            spread_bps = Some((ask.price() - bid.price()) / mid * BPS_PER_UNIT);
+            depth = Some(depth_bands(&view.bids, &view.asks, mid));
+            effective_spread_bps = match (
+                fill_price(&view.asks, config.effective_notional),
+                fill_price(&view.bids, config.effective_notional),
+            ) {
+                (Some(buy), Some(sell)) => Some(((buy - sell) / mid * BPS_PER_UNIT).round_dp(4)),
+                _ => None,
+            };
+        }
+
+        Self {
+            trading_pair: view.trading_pair.clone(),
+            exchange: view.exchange.clone(),
+            imbalance,
+            microprice,
+            depth,
+            spread_bps,
+            effective_spread_bps,
+            quote_rate,
+            flags,
+            book_timestamp: view.timestamp,
+            computed_at: now,
+        }
+    }
+
+    /// Whether no degenerate condition was flagged
+    pub fn is_clean(&self) -> bool {
+        self.flags.is_empty()
+    }
+
+    pub fn depth_within(&self, bps: u32) -> Option<&DepthBand> {
+        self.depth.as_ref()?.iter().find(|band| band.bps == bps)
+    }
+}
+
+// Levels are sorted best first, so each side is walked once with the bands filled in order
+fn depth_bands(bids: &[OrderBookLevel], asks: &[OrderBookLevel], mid: Decimal) -> Vec<DepthBand> {
+    let side = |levels: &[OrderBookLevel], inside: &dyn Fn(Decimal, Decimal) -> bool| {
+        let mut totals = [Decimal::ZERO; DEPTH_BANDS_BPS.len()];
+        let (mut band, mut cumulative) = (0, Decimal::ZERO);
+        for level in levels {
+            while band < DEPTH_BANDS_BPS.len() && !inside(level.price(), band_offset(mid, DEPTH_BANDS_BPS[band])) {
+                totals[band] = cumulative;
+                band += 1;
+            }
+            if band == DEPTH_BANDS_BPS.len() {
+                break;
+            }
+            cumulative += level.volume();
+        }
+        for total in totals.iter_mut().skip(band) {
+            *total = cumulative;
+        }
+        totals
+    };
+    let bid = side(bids, &|price, offset| price >= mid - offset);
+    let ask = side(asks, &|price, offset| price <= mid + offset);
+    DEPTH_BANDS_BPS
+        .iter()
+        .enumerate()
+        .map(|(i, bps)| DepthBand {
+            bps: *bps,
+            bid: bid[i],
+            ask: ask[i],
+        })
+        .collect()
+}
+
+fn band_offset(mid: Decimal, bps: u32) -> Decimal {
+    mid * Decimal::from(bps) / BPS_PER_UNIT
+}
+
+// Average price of filling `notional` against levels, None when they cannot fill it
+fn fill_price(levels: &[OrderBookLevel], notional: Decimal) -> Option<Decimal> {
+    let (mut remaining, mut base) = (notional, Decimal::ZERO);
+    for level in levels {
+        if level.price() <= Decimal::ZERO {
+            return None;
+        }
+        let level_notional = level.price() * level.volume();
+        if level_notional >= remaining {
+            base += remaining / level.price();
+            return Some(notional / base);
+        }
+        remaining -= level_notional;
+        base += level.volume();
+    }
+    None
+}
+
+/// Mean microstructure features of one pair over one aggregation interval
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MicrostructureAggregate {
+    pub trading_pair: String,
+    pub exchange: String,
+    pub bucket_start: DateTime<Utc>,
+    pub updates: u32,
+    /// Updates with at least one degenerate condition flagged
+    pub flagged: u32,
+    /// Means are over the updates where the feature was not null
+    pub mean_imbalance: Option<Decimal>,
+    pub mean_depth: Option<Vec<DepthBand>>,
+    pub mean_spread_bps: Option<Decimal>,
+    pub mean_effective_spread_bps: Option<Decimal>,
+    pub mean_quote_rate: f64,
+}
+
+/// Persistence for microstructure aggregates
+#[async_trait]
+pub trait MicrostructureStore: Send + Sync {
+    async fn record_aggregates(&self, aggregates: &[MicrostructureAggregate]) -> Result<(), MicrostructureError>;
+}
+
+/// Publishing cadence, rolling windows and the reference size of the effective spread
+#[derive(Debug, Clone, PartialEq)]
+pub struct MicrostructureConfig {
+    /// Minimum time between broadcast snapshots of one pair; the latest is always kept
+    pub publish_interval: Duration,
+    /// Window the quote update rate is measured over
+    pub rate_window: Duration,
+    /// Book age beyond which a snapshot is flagged stale
+    pub stale_after: Duration,
+    /// Quote notional the effective spread is measured for
+    pub effective_notional: Decimal,
+    /// Interval aggregates are bucketed and persisted by
+    pub aggregate_interval: CandleInterval,
+}
+
+impl Default for MicrostructureConfig {
+    fn default() -> Self {
+        Self {
+            publish_interval: DEFAULT_PUBLISH_INTERVAL,
+            rate_window: DEFAULT_RATE_WINDOW,
+            stale_after: DEFAULT_STALE_AFTER,
+            effective_notional: DEFAULT_EFFECTIVE_NOTIONAL,
+            aggregate_interval: CandleInterval::OneMinute,
+        }
+    }
+}
+
+/// Running sum of the non-null values of a feature
+#[derive(Debug, Clone, Copy, Default)]
+struct RunningMean {
+    sum: Decimal,
+    count: u32,
+}
+
+impl RunningMean {
+    fn add(&mut self, value: Option<Decimal>) {
+        if let Some(value) = value {
+            self.sum += value;
+            self.count += 1;
+        }
+    }
+
+    fn mean(&self) -> Option<Decimal> {
+        (self.count > 0).then(|| self.sum / Decimal::from(self.count))
+    }
+}
+
+/// Aggregate of the current interval, built incrementally as updates arrive
+#[derive(Debug, Clone)]
+struct AggregateBuilder {
+    trading_pair: String,
+    exchange: String,
+    bucket_start: DateTime<Utc>,
+    updates: u32,
+    flagged: u32,
+    imbalance: RunningMean,
+    depth: [(RunningMean, RunningMean); DEPTH_BANDS_BPS.len()],
+    spread_bps: RunningMean,
+    effective_spread_bps: RunningMean,
+    quote_rate: f64,
+}
+
+impl AggregateBuilder {
+    fn new(snapshot: &MicrostructureSnapshot, bucket_start: DateTime<Utc>) -> Self {
+        Self {
+            trading_pair: snapshot.trading_pair.clone(),
+            exchange: snapshot.exchange.clone(),
+            bucket_start,
+            updates: 0,
+            flagged: 0,
+            imbalance: RunningMean::default(),
+            depth: Default::default(),
+            spread_bps: RunningMean::default(),
+            effective_spread_bps: RunningMean::default(),
+            quote_rate: 0.0,
+        }
+    }
+
+    fn add(&mut self, snapshot: &MicrostructureSnapshot) {
+        self.updates += 1;
+        if !snapshot.is_clean() {
+            self.flagged += 1;
+        }
+        self.imbalance.add(snapshot.imbalance);
+        if let Some(depth) = &snapshot.depth {
+            for (sums, band) in self.depth.iter_mut().zip(depth) {
+                sums.0.add(Some(band.bid));
+                sums.1.add(Some(band.ask));
+            }
+        }
+        self.spread_bps.add(snapshot.spread_bps);
+        self.effective_spread_bps.add(snapshot.effective_spread_bps);
+        self.quote_rate += snapshot.quote_rate;
+    }
+
+    fn finish(&self) -> MicrostructureAggregate {
+        let mean_depth = DEPTH_BANDS_BPS
+            .iter()
+            .zip(&self.depth)
+            .map(|(bps, (bid, ask))| {
+                Some(DepthBand {
+                    bps: *bps,
+                    bid: bid.mean()?,
+                    ask: ask.mean()?,
+                })
+            })
+            .collect();
+        MicrostructureAggregate {
+            trading_pair: self.trading_pair.clone(),
+            exchange: self.exchange.clone(),
+            bucket_start: self.bucket_start,
+            updates: self.updates,
+            flagged: self.flagged,
+            mean_imbalance: self.imbalance.mean(),
+            mean_depth,
+            mean_spread_bps: self.spread_bps.mean(),
+            mean_effective_spread_bps: self.effective_spread_bps.mean(),
+            mean_quote_rate: if self.updates > 0 { self.quote_rate / self.updates as f64 } else { 0.0 },
+        }
+    }
+}
+
+/// Rolling state of one pair
+#[derive(Debug, Default)]
+struct PairState {
+    /// Update times inside the rate window, oldest first
+    updates: VecDeque<DateTime<Utc>>,
+    latest: Option<MicrostructureSnapshot>,
+    last_published: Option<DateTime<Utc>>,
+    bucket: Option<AggregateBuilder>,
+}
+
+/// Computes microstructure features per pair from order book updates
+pub struct MicrostructureTracker {
+    config: MicrostructureConfig,
+    pairs: Mutex<HashMap<String, PairState>>,
+    /// Closed aggregates awaiting persistence
+    completed: Mutex<Vec<MicrostructureAggregate>>,
+    snapshots_tx: broadcast::Sender<MicrostructureSnapshot>,
+    store: SyncRwLock<Option<Arc<dyn MicrostructureStore>>>,
+}
+
+impl fmt::Debug for MicrostructureTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MicrostructureTracker")
+            .field("config", &self.config)
+            .field("pairs", &self.pairs.lock().len())
+            .finish()
+    }
+}
+
+impl MicrostructureTracker {
+    pub fn new(config: MicrostructureConfig) -> Self {
+        let (snapshots_tx, _) = broadcast::channel(SNAPSHOT_CHANNEL_CAPACITY);
+        Self {
+            config,
+            pairs: Mutex::new(HashMap::new()),
+            completed: Mutex::new(Vec::new()),
+            snapshots_tx,
+            store: SyncRwLock::new(None),
+        }
+    }
+
+    /// Persists per-interval aggregates for research
+    pub fn set_store(&self, store: Arc<dyn MicrostructureStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    pub fn config(&self) -> &MicrostructureConfig {
+        &self.config
+    }
+
+    /// Subscribes to snapshots, at most one per pair every `publish_interval`
+    pub fn subscribe(&self) -> broadcast::Receiver<MicrostructureSnapshot> {
+        self.snapshots_tx.subscribe()
+    }
+
+    /// Snapshot of a pair's most recent book update
+    pub fn latest(&self, trading_pair: &str) -> Option<MicrostructureSnapshot> {
+        self.pairs.lock().get(trading_pair).and_then(|state| state.latest.clone())
+    }
+
+    /// Computes the features of a book update, folds them into the pair's rolling state and
+    /// broadcasts them unless the pair was published within `publish_interval`
+    pub fn observe(&self, view: &BookView, now: DateTime<Utc>) -> MicrostructureSnapshot {
+        let rate_window = chrono::Duration::from_std(self.config.rate_window).unwrap_or_else(|_| chrono::Duration::zero());
+        let publish_interval =
+            chrono::Duration::from_std(self.config.publish_interval).unwrap_or_else(|_| chrono::Duration::zero());
+
+        let (snapshot, publish) = {
+            let mut pairs = self.pairs.lock();
+            let state = pairs.entry(view.trading_pair.clone()).or_default();
+            state.updates.push_back(now);
+            while state.updates.front().map_or(false, |at| *at <= now - rate_window) {
+                state.updates.pop_front();
+            }
+            let snapshot = MicrostructureSnapshot::compute(view, quote_rate(state.updates.len(), rate_window), &self.config, now);
+
+            let bucket_start = self.config.aggregate_interval.bucket_start(now);
+            if let Some(bucket) = take_closed(&mut state.bucket, bucket_start) {
+                self.complete(bucket.finish());
+            }
+            state
+                .bucket
+                .get_or_insert_with(|| AggregateBuilder::new(&snapshot, bucket_start))
+                .add(&snapshot);
+
+            let publish = state.last_published.map_or(true, |at| now - at >= publish_interval);
+            if publish {
+                state.last_published = Some(now);
+            }
+            state.latest = Some(snapshot.clone());
+            (snapshot, publish)
+        };
+
+        if !snapshot.is_clean() {
+            counter!(format!("{}.flagged", METRICS_PREFIX), 1);
+        }
+        if publish {
+            let _ = self.snapshots_tx.send(snapshot.clone());
+        }
+        snapshot
+    }
+
+    /// Closes aggregates of intervals that ended before `now` and persists every closed
+    /// aggregate, returning how many were written; they are kept for retry on failure
+    pub async fn flush(&self, now: DateTime<Utc>) -> Result<usize, MicrostructureError> {
+        let bucket_start = self.config.aggregate_interval.bucket_start(now);
+        {
+            let mut pairs = self.pairs.lock();
+            for state in pairs.values_mut() {
+                if let Some(bucket) = take_closed(&mut state.bucket, bucket_start) {
+                    self.complete(bucket.finish());
+                }
+            }
+        }
+
+        let store = self.store.read().clone();
+        let Some(store) = store else {
+            self.completed.lock().clear();
+            return Ok(0);
+        };
+        let aggregates = std::mem::take(&mut *self.completed.lock());
+        if aggregates.is_empty() {
+            return Ok(0);
+        }
+        if let Err(e) = store.record_aggregates(&aggregates).await {
+            let mut completed = self.completed.lock();
+            let newer = std::mem::replace(&mut *completed, aggregates);
+            completed.extend(newer);
+            let excess = completed.len().saturating_sub(MAX_PENDING_AGGREGATES);
+            if excess > 0 {
+                completed.drain(..excess);
+                counter!(format!("{}.aggregates_dropped", METRICS_PREFIX), excess as u64);
+            }
+            return Err(e);
+        }
+        counter!(format!("{}.aggregates_recorded", METRICS_PREFIX), aggregates.len() as u64);
+        Ok(aggregates.len())
+    }
+
+    fn complete(&self, aggregate: MicrostructureAggregate) {
+        self.completed.lock().push(aggregate);
+    }
+
+    /// Computes features from every accepted update of the live order book and persists
+    /// aggregates once per aggregation interval
+    pub fn spawn(self: Arc<Self>, order_book: Arc<LiveOrderBook>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut books = order_book.subscribe();
+            let mut flush = tokio::time::interval(Duration::from_secs(self.config.aggregate_interval.as_secs() as u64));
+            loop {
+                tokio::select! {
+                    received = books.recv() => match received {
+                        Ok(snapshot) => {
+                            if let Some(view) = order_book.view(&snapshot.trading_pair) {
+                                self.observe(&view, Utc::now());
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            counter!(format!("{}.lagged", METRICS_PREFIX), skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = flush.tick() => {
+                        if let Err(e) = self.flush(Utc::now()).await {
+                            warn!("Failed to persist microstructure aggregates: {}", e);
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+fn quote_rate(updates: usize, window: chrono::Duration) -> f64 {
+    let secs = window.num_milliseconds() as f64 / 1000.0;
+    if secs > 0.0 {
+        updates as f64 / secs
+    } else {
+        0.0
+    }
+}
+
+// Takes the builder when it belongs to an earlier interval than `bucket_start`
+fn take_closed(bucket: &mut Option<AggregateBuilder>, bucket_start: DateTime<Utc>) -> Option<AggregateBuilder> {
+    if bucket.as_ref()?.bucket_start < bucket_start {
+        bucket.take()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_engine::book_view::BOOK_VIEW_DEPTH;
+    use crate::models::market::OrderBook;
+    use chrono::TimeZone;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use rust_decimal_macros::dec;
+
+    const PAIR: &str = "SOL/USDC";
+
+    #[derive(Default)]
+    struct MemoryStore {
+        aggregates: Mutex<Vec<MicrostructureAggregate>>,
+    }
+
+    #[async_trait]
+    impl MicrostructureStore for MemoryStore {
+        async fn record_aggregates(&self, aggregates: &[MicrostructureAggregate]) -> Result<(), MicrostructureError> {
+            self.aggregates.lock().extend_from_slice(aggregates);
+            Ok(())
+        }
+    }
+
+    fn start() -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_040, 0).unwrap()
+    }
+
+    fn view(bids: Vec<(Decimal, Decimal)>, asks: Vec<(Decimal, Decimal)>, at: DateTime<Utc>) -> BookView {
+        let levels = |side: Vec<(Decimal, Decimal)>| -> Vec<OrderBookLevel> {
+            side.into_iter()
+                .map(|(price, volume)| OrderBookLevel::new(price, volume))
+                .collect()
+        };
+        let book = OrderBook::new(PAIR.to_string(), "jupiter".to_string(), levels(bids), levels(asks)).unwrap();
+        BookView {
+            timestamp: at,
+            ..BookView::from_book(&book, BOOK_VIEW_DEPTH, false)
+        }
+    }
+
+    fn reference_view(at: DateTime<Utc>) -> BookView {
+        view(
+            vec![(dec!(99.95), dec!(10)), (dec!(99.90), dec!(20)), (dec!(99.60), dec!(50))],
+            vec![(dec!(100.05), dec!(30)), (dec!(100.20), dec!(40)), (dec!(100.60), dec!(100))],
+            at,
+        )
+    }
+
+    fn random_view(rng: &mut StdRng, at: DateTime<Utc>) -> BookView {
+        let mid: i64 = rng.gen_range(9_900..10_100);
+        let side = |rng: &mut StdRng, sign: i64| -> Vec<(Decimal, Decimal)> {
+            (1..=rng.gen_range(0..30))
+                .map(|i| (Decimal::new(mid + sign * i, 2), Decimal::new(rng.gen_range(1..10_000), 2)))
+                .collect()
+        };
+        let bids = side(rng, -1);
+        let asks = side(rng, 1);
+        BookView {
+            degraded: rng.gen_bool(0.05),
+            ..view(bids, asks, at)
+        }
+    }
+
+    #[test]
+    fn test_features_of_constructed_book() {
+        let now = start();
+        let config = MicrostructureConfig {
+            effective_notional: dec!(500),
+            ..MicrostructureConfig::default()
+        };
+        let snapshot = MicrostructureSnapshot::compute(&reference_view(now), 2.5, &config, now);
+
+        assert!(snapshot.is_clean());
+        assert_eq!(snapshot.imbalance, Some(dec!(-0.5)));
+        // Heavier ask volume pulls the microprice toward the bid
+        assert_eq!(snapshot.microprice, Some(dec!(99.975)));
+        assert_eq!(snapshot.spread_bps, Some(dec!(10)));
+        // 500 USDC fills inside the best level on both sides
+        assert_eq!(snapshot.effective_spread_bps, Some(dec!(10)));
+        assert_eq!(snapshot.quote_rate, 2.5);
+        assert_eq!(
+            snapshot.depth,
+            Some(vec![
+                DepthBand { bps: 10, bid: dec!(30), ask: dec!(30) },
+                DepthBand { bps: 25, bid: dec!(30), ask: dec!(70) },
+                DepthBand { bps: 50, bid: dec!(80), ask: dec!(70) },
+            ])
+        );
+        assert_eq!(snapshot.depth_within(25).unwrap().ask, dec!(70));
+
+        // Walking past the best bid costs more than the quoted spread
+        let deeper = MicrostructureConfig {
+            effective_notional: dec!(2000),
+            ..config.clone()
+        };
+        let snapshot = MicrostructureSnapshot::compute(&reference_view(now), 0.0, &deeper, now);
+        assert!(snapshot.effective_spread_bps.unwrap() > dec!(10));
+
+        let too_large = MicrostructureConfig {
+            effective_notional: dec!(1_000_000),
+            ..config
+        };
+        let snapshot = MicrostructureSnapshot::compute(&reference_view(now), 0.0, &too_large, now);
+        assert_eq!(snapshot.effective_spread_bps, None);
+        assert!(snapshot.depth.is_some());
+    }
+
+    #[test]
+    fn test_degenerate_books_are_flagged() {
+        let now = start();
+        let config = MicrostructureConfig::default();
+        let null_features = |snapshot: &MicrostructureSnapshot| {
+            snapshot.imbalance.is_none()
+                && snapshot.microprice.is_none()
+                && snapshot.depth.is_none()
+                && snapshot.spread_bps.is_none()
+                && snapshot.effective_spread_bps.is_none()
+        };
+
+        let one_sided = view(vec![(dec!(99.95), dec!(10))], Vec::new(), now);
+        let snapshot = MicrostructureSnapshot::compute(&one_sided, 0.0, &config, now);
+        assert_eq!(snapshot.flags, vec![BookFlag::OneSided]);
+        assert!(null_features(&snapshot));
+
+        let crossed = BookView {
+            bids: vec![OrderBookLevel::new(dec!(100.10), dec!(5))],
+            asks: vec![OrderBookLevel::new(dec!(100.00), dec!(5))],
+            ..reference_view(now)
+        };
+        let snapshot = MicrostructureSnapshot::compute(&crossed, 0.0, &config, now);
+        assert_eq!(snapshot.flags, vec![BookFlag::Crossed]);
+        assert!(null_features(&snapshot));
+
+        // Stale and degraded books keep their features but are flagged
+        let stale = BookView {
+            degraded: true,
+            ..reference_view(now - chrono::Duration::seconds(30))
+        };
+        let snapshot = MicrostructureSnapshot::compute(&stale, 0.0, &config, now);
+        assert_eq!(snapshot.flags, vec![BookFlag::Stale, BookFlag::Degraded]);
+        assert!(!snapshot.is_clean());
+        assert_eq!(snapshot.imbalance, Some(dec!(-0.5)));
+    }
+
+    #[test]
+    fn test_quote_rate_and_throttled_publishing() {
+        let tracker = MicrostructureTracker::new(MicrostructureConfig {
+            publish_interval: Duration::from_millis(500),
+            rate_window: Duration::from_secs(1),
+            ..MicrostructureConfig::default()
+        });
+        let mut published = tracker.subscribe();
+
+        let mut last = None;
+        for i in 0..15 {
+            let now = start() + chrono::Duration::milliseconds(100 * i);
+            last = Some(tracker.observe(&reference_view(now), now));
+        }
+
+        // Ten updates fall inside the trailing second
+        assert_eq!(last.unwrap().quote_rate, 10.0);
+        assert_eq!(tracker.latest(PAIR).unwrap().quote_rate, 10.0);
+        let mut times = Vec::new();
+        while let Ok(snapshot) = published.try_recv() {
+            times.push((snapshot.computed_at - start()).num_milliseconds());
+        }
+        assert_eq!(times, vec![0, 500, 1000]);
+        assert!(tracker.latest("BTC/USDC").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_incremental_state_matches_recomputation() {
+        let mut rng = StdRng::seed_from_u64(1919);
+        let config = MicrostructureConfig {
+            rate_window: Duration::from_secs(5),
+            ..MicrostructureConfig::default()
+        };
+        let tracker = MicrostructureTracker::new(config.clone());
+        let store = Arc::new(MemoryStore::default());
+        tracker.set_store(store.clone());
+
+        let window = chrono::Duration::seconds(5);
+        let (mut now, mut times, mut snapshots) = (start(), Vec::new(), Vec::new());
+        for _ in 0..600 {
+            now = now + chrono::Duration::milliseconds(rng.gen_range(1..800));
+            let view = random_view(&mut rng, now);
+            times.push(now);
+            let snapshot = tracker.observe(&view, now);
+
+            let in_window = times.iter().filter(|at| **at > now - window).count();
+            let expected = MicrostructureSnapshot::compute(&view, quote_rate(in_window, window), &config, now);
+            assert_eq!(snapshot, expected);
+            snapshots.push(expected);
+        }
+
+        assert!(tracker.flush(now + chrono::Duration::minutes(1)).await.unwrap() > 1);
+        let recorded = store.aggregates.lock().clone();
+        let mut buckets: Vec<DateTime<Utc>> = snapshots
+            .iter()
+            .map(|snapshot| config.aggregate_interval.bucket_start(snapshot.computed_at))
+            .collect();
+        buckets.dedup();
+        assert_eq!(recorded.len(), buckets.len());
+
+        for (aggregate, bucket_start) in recorded.iter().zip(buckets) {
+            let members: Vec<&MicrostructureSnapshot> = snapshots
+                .iter()
+                .filter(|snapshot| config.aggregate_interval.bucket_start(snapshot.computed_at) == bucket_start)
+                .collect();
+            let mean = |values: Vec<Decimal>| {
+                (!values.is_empty()).then(|| values.iter().copied().sum::<Decimal>() / Decimal::from(values.len()))
+            };
+            assert_eq!(aggregate.bucket_start, bucket_start);
+            assert_eq!(aggregate.updates as usize, members.len());
+            assert_eq!(aggregate.flagged as usize, members.iter().filter(|s| !s.is_clean()).count());
+            assert_eq!(aggregate.mean_imbalance, mean(members.iter().filter_map(|s| s.imbalance).collect()));
+            assert_eq!(aggregate.mean_spread_bps, mean(members.iter().filter_map(|s| s.spread_bps).collect()));
+            assert_eq!(
+                aggregate.mean_effective_spread_bps,
+                mean(members.iter().filter_map(|s| s.effective_spread_bps).collect())
+            );
+            let depths: Vec<&Vec<DepthBand>> = members.iter().filter_map(|s| s.depth.as_ref()).collect();
+            let expected_depth = (!depths.is_empty()).then(|| {
+                (0..DEPTH_BANDS_BPS.len())
+                    .map(|i| DepthBand {
+                        bps: DEPTH_BANDS_BPS[i],
+                        bid: mean(depths.iter().map(|depth| depth[i].bid).collect()).unwrap(),
+                        ask: mean(depths.iter().map(|depth| depth[i].ask).collect()).unwrap(),
+                    })
+                    .collect::<Vec<_>>()
+            });
+            assert_eq!(aggregate.mean_depth, expected_depth);
+        }
+
+        // Everything closed was written once
+        assert_eq!(tracker.flush(now + chrono::Duration::minutes(2)).await.unwrap(), 0);
+    }
+}
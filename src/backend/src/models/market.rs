@@ -15,10 +15,13 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::utils::time::{current_timestamp, is_valid_market_timestamp};
+use crate::models::pair::{PairError, TradingPair};
+use crate::utils::time::{current_timestamp, is_valid_market_timestamp_at};
 
 // Exchange-specific precision requirements
 const MIN_PRICE_PRECISION: u32 = 8;
@@ -42,12 +45,77 @@ pub enum MarketError {
     InvalidExchange(String),
     #[error("invalid trading pair: {0}")]
     InvalidTradingPair(String),
+    #[error(transparent)]
+    Pair(#[from] PairError),
     #[error("order book error: {0}")]
     OrderBookError(String),
     #[error("stale data: {0}")]
     StaleData(String),
 }
 
+/// Pair whose price converts SOL-denominated values into the reporting currency
+pub const SOL_USDC_PAIR: &str = "SOL/USDC";
+
+/// Asset a trading pair is quoted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum QuoteAsset {
+    Usdc,
+    Sol,
+    Wsol,
+}
+
+impl QuoteAsset {
+    /// Currency portfolio values and USDC-denominated risk limits are reported in
+    pub const REPORTING: QuoteAsset = QuoteAsset::Usdc;
+
+    /// Quote asset of a BASE/QUOTE pair; pairs without a SOL quote settle in USDC
+    pub fn of_pair(trading_pair: &str) -> Self {
+        match trading_pair.rsplit_once('/').map(|(_, quote)| quote.to_ascii_uppercase()) {
+            Some(quote) if quote == "SOL" => Self::Sol,
+            Some(quote) if quote == "WSOL" => Self::Wsol,
+            _ => Self::Usdc,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Usdc => "USDC",
+            Self::Sol => "SOL",
+            Self::Wsol => "WSOL",
+        }
+    }
+
+    /// True for native SOL and wrapped SOL
+    pub fn is_sol(&self) -> bool {
+        matches!(self, Self::Sol | Self::Wsol)
+    }
+
+    /// Rate converting one unit of this asset into the reporting currency
+    pub fn reporting_rate(&self, market_prices: &HashMap<String, Decimal>) -> Result<Decimal, MarketError> {
+        if !self.is_sol() {
+            return Ok(Decimal::ONE);
+        }
+        market_prices
+            .get(SOL_USDC_PAIR)
+            .copied()
+            .filter(|rate| *rate > Decimal::ZERO)
+            .ok_or_else(|| {
+                MarketError::InvalidPrice(format!(
+                    "no {} price to convert {} values",
+                    SOL_USDC_PAIR,
+                    self.as_str()
+                ))
+            })
+    }
+}
+
+impl std::fmt::Display for QuoteAsset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Thread-safe cache for validation results
 #[derive(Debug, Clone)]
 struct ValidationCache {
@@ -56,18 +124,34 @@ struct ValidationCache {
     last_update: DateTime<Utc>,
 }
 
+/// Compact, copyable provenance of a tick carried through signals into execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TickStamp {
+    pub tick_id: Uuid,
+    pub observed_at: DateTime<Utc>,
+    pub received_at: Instant,
+}
+
+/// Batch of price updates fanned out to in-process listeners without copying the updates
+pub type MarketDataBatch = Arc<[MarketData]>;
+
 /// High-performance market data point representation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MarketData {
     id: Uuid,
-    trading_pair: String,
+    trading_pair: TradingPair,
     exchange: String,
     price: Decimal,
     volume: Decimal,
     timestamp: DateTime<Utc>,
+    #[serde(skip, default = "Instant::now")]
+    received_at: Instant,
     #[serde(skip)]
     validation_cache: RwLock<ValidationCache>,
+    /// Collected for a quarantined pair, for monitoring only
+    #[serde(default)]
+    quarantined: bool,
 }
 
 impl MarketData {
@@ -81,12 +165,7 @@ impl MarketData {
         // Validate inputs
         validate_price(price, &exchange)?;
         validate_volume(volume, &exchange)?;
-        
-        if trading_pair.split('/').count() != 2 {
-            return Err(MarketError::InvalidTradingPair(
-                "trading pair must be in format BASE/QUOTE".to_string(),
-            ));
-        }
+        let trading_pair = TradingPair::parse(&trading_pair)?;
 
         Ok(Self {
             id: Uuid::new_v4(),
@@ -95,18 +174,88 @@ impl MarketData {
             price,
             volume,
             timestamp: current_timestamp(),
+            received_at: Instant::now(),
             validation_cache: RwLock::new(ValidationCache {
                 price_cache: HashMap::with_capacity(ORDER_BOOK_CACHE_SIZE),
                 volume_cache: HashMap::with_capacity(ORDER_BOOK_CACHE_SIZE),
                 last_update: current_timestamp(),
             }),
+            quarantined: false,
         })
     }
 
+    /// Overrides the observation time, preserving the original timestamp of recorded data
+    pub fn with_timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Overrides the monotonic receive time, used to replay delayed ticks
+    pub fn with_received_at(mut self, received_at: Instant) -> Self {
+        self.received_at = received_at;
+        self
+    }
+
+    /// Marks data collected for a quarantined pair
+    pub fn with_quarantined(mut self, quarantined: bool) -> Self {
+        self.quarantined = quarantined;
+        self
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn trading_pair(&self) -> &str {
+        self.trading_pair.as_str()
+    }
+
+    pub fn pair(&self) -> &TradingPair {
+        &self.trading_pair
+    }
+
+    pub fn exchange(&self) -> &str {
+        &self.exchange
+    }
+
+    pub fn price(&self) -> Decimal {
+        self.price
+    }
+
+    pub fn volume(&self) -> Decimal {
+        self.volume
+    }
+
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    pub fn received_at(&self) -> Instant {
+        self.received_at
+    }
+
+    pub fn is_quarantined(&self) -> bool {
+        self.quarantined
+    }
+
+    /// Tick provenance for downstream latency accounting
+    pub fn stamp(&self) -> TickStamp {
+        TickStamp {
+            tick_id: self.id,
+            observed_at: self.timestamp,
+            received_at: self.received_at,
+        }
+    }
+
     /// Validates market data freshness and correctness
     pub fn is_valid(&self) -> Result<bool, MarketError> {
+        self.is_valid_at(current_timestamp())
+    }
+
+    /// Validates market data freshness relative to a reference time
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> Result<bool, MarketError> {
         // Check timestamp freshness
-        if !is_valid_market_timestamp(self.timestamp) {
+        if !is_valid_market_timestamp_at(self.timestamp, now) {
             return Err(MarketError::StaleData(format!(
                 "market data from {} is stale",
                 self.timestamp
@@ -125,7 +274,7 @@ impl MarketData {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderBook {
-    trading_pair: String,
+    trading_pair: TradingPair,
     exchange: String,
     #[serde(skip)]
     bids: RwLock<BTreeMap<Decimal, Decimal>>,
@@ -135,12 +284,26 @@ pub struct OrderBook {
     depth: AtomicUsize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OrderBookLevel {
     price: Decimal,
     volume: Decimal,
 }
 
+impl OrderBookLevel {
+    pub fn new(price: Decimal, volume: Decimal) -> Self {
+        Self { price, volume }
+    }
+
+    pub fn price(&self) -> Decimal {
+        self.price
+    }
+
+    pub fn volume(&self) -> Decimal {
+        self.volume
+    }
+}
+
 impl OrderBook {
     /// Creates a new order book with depth management
     pub fn new(
@@ -149,6 +312,7 @@ impl OrderBook {
         bids: Vec<OrderBookLevel>,
         asks: Vec<OrderBookLevel>,
     ) -> Result<Self, MarketError> {
+        let trading_pair = TradingPair::parse(&trading_pair)?;
         let mut bid_map = BTreeMap::new();
         let mut ask_map = BTreeMap::new();
 
@@ -193,6 +357,42 @@ impl OrderBook {
             _ => Ok(None),
         }
     }
+
+    pub fn trading_pair(&self) -> &str {
+        self.trading_pair.as_str()
+    }
+
+    pub fn pair(&self) -> &TradingPair {
+        &self.trading_pair
+    }
+
+    pub fn exchange(&self) -> &str {
+        &self.exchange
+    }
+
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    /// Returns up to `depth` levels per side, best bids first and best asks first
+    pub fn levels(&self, depth: usize) -> (Vec<OrderBookLevel>, Vec<OrderBookLevel>) {
+        let bids = self
+            .bids
+            .read()
+            .iter()
+            .rev()
+            .take(depth)
+            .map(|(price, volume)| OrderBookLevel::new(*price, *volume))
+            .collect();
+        let asks = self
+            .asks
+            .read()
+            .iter()
+            .take(depth)
+            .map(|(price, volume)| OrderBookLevel::new(*price, *volume))
+            .collect();
+        (bids, asks)
+    }
 }
 
 /// Validates price against exchange requirements
@@ -252,6 +452,21 @@ mod tests {
         assert!(market_data.is_ok());
     }
 
+    #[test]
+    fn test_market_data_normalizes_pair() {
+        let market_data = MarketData::new(
+            "sol-usdc".to_string(),
+            "jupiter".to_string(),
+            dec!(23.45678900),
+            dec!(100.000000),
+        )
+        .unwrap();
+        assert_eq!(market_data.trading_pair(), "SOL/USDC");
+
+        let result = MarketData::new("SOLUSDC".to_string(), "jupiter".to_string(), dec!(23.45678900), dec!(100.000000));
+        assert!(matches!(result, Err(MarketError::Pair(_))));
+    }
+
     #[test]
     fn test_invalid_price_precision() {
         let result = validate_price(dec!(23.4), "jupiter");
@@ -277,4 +492,39 @@ mod tests {
         let spread = order_book.get_spread().unwrap();
         assert!(spread.is_some());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_order_book_levels_best_first() {
+        let order_book = OrderBook::new(
+            "SOL/USDC".to_string(),
+            "jupiter".to_string(),
+            vec![
+                OrderBookLevel::new(dec!(23.40000000), dec!(50.000000)),
+                OrderBookLevel::new(dec!(23.45000000), dec!(100.000000)),
+            ],
+            vec![
+                OrderBookLevel::new(dec!(23.60000000), dec!(75.000000)),
+                OrderBookLevel::new(dec!(23.55000000), dec!(25.000000)),
+            ],
+        )
+        .unwrap();
+
+        let (bids, asks) = order_book.levels(1);
+        assert_eq!(bids, vec![OrderBookLevel::new(dec!(23.45000000), dec!(100.000000))]);
+        assert_eq!(asks, vec![OrderBookLevel::new(dec!(23.55000000), dec!(25.000000))]);
+    }
+
+    #[test]
+    fn test_quote_asset_conversion() {
+        assert_eq!(QuoteAsset::of_pair("BONK/SOL"), QuoteAsset::Sol);
+        assert_eq!(QuoteAsset::of_pair("BONK/wSOL"), QuoteAsset::Wsol);
+        assert_eq!(QuoteAsset::of_pair("SOL/USDC"), QuoteAsset::Usdc);
+
+        let mut prices = HashMap::new();
+        assert_eq!(QuoteAsset::Usdc.reporting_rate(&prices).unwrap(), Decimal::ONE);
+        assert!(QuoteAsset::Sol.reporting_rate(&prices).is_err());
+
+        prices.insert(SOL_USDC_PAIR.to_string(), dec!(150));
+        assert_eq!(QuoteAsset::Wsol.reporting_rate(&prices).unwrap(), dec!(150));
+    }
+}
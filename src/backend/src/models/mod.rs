@@ -14,11 +14,23 @@
 pub mod market;
 pub use market::{
     MarketData,
+    MarketDataBatch,
     OrderBook,
+    QuoteAsset,
+    TickStamp,
     validate_price,
     validate_volume,
 };
 
+// Re-export canonical trading pair identifiers
+pub mod pair;
+pub use pair::{
+    PairError,
+    PairRegistry,
+    PriceBounds,
+    TradingPair,
+};
+
 // Re-export order management models
 pub mod order;
 pub use order::{
@@ -52,6 +64,22 @@ pub use trade::{
     calculate_slippage,
 };
 
+// Re-export transfer tracking models
+pub mod transfer;
+pub use transfer::{
+    Transfer,
+    TransferDirection,
+    FlowAdjustedTracker,
+};
+
+// Re-export outbound webhook models
+pub mod webhook;
+pub use webhook::{
+    Webhook,
+    WebhookEvent,
+    WebhookEventType,
+};
+
 // Common error types for model operations
 use thiserror::Error;
 
@@ -17,7 +17,9 @@ use thiserror::Error;
 use tracing::{debug, error, info, instrument, warn};
 use uuid::Uuid;
 
+use crate::execution_engine::telemetry::OrderTelemetry;
 use crate::models::market::MarketData;
+use crate::models::pair::TradingPair;
 use crate::utils::solana::SolanaClient;
 
 // Constants for order management
@@ -26,6 +28,11 @@ const MAX_RETRIES: u8 = 3;
 const METRICS_PREFIX: &str = "trading_bot.order";
 const VALIDATION_CACHE_TTL_SECONDS: u64 = 60;
 
+/// Venues whose order programs can modify a resting order in place
+const NATIVE_AMEND_EXCHANGES: &[&str] = &["drift"];
+/// Venues accepting post-only orders, which are rejected rather than allowed to cross
+const POST_ONLY_EXCHANGES: &[&str] = &["drift"];
+
 /// Order-related error types
 #[derive(Error, Debug)]
 pub enum OrderError {
@@ -56,56 +63,53 @@ pub enum OrderStatus {
     Pending,
     Validating,
     Executing,
+    PartiallyFilled,
     Executed,
     Failed,
     Cancelled,
 }
 
-/// Performance metrics tracking for orders
-#[derive(Debug, Clone)]
-pub struct OrderMetrics {
-    start_time: Instant,
-    validation_duration: Option<Duration>,
-    execution_duration: Option<Duration>,
-    retry_count: u8,
+/// Incremental fill reported by bundle status or order status polling
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderFill {
+    /// Venue identifier for the fill, used to drop duplicates from repeated polls
+    pub fill_id: String,
+    pub size: Decimal,
+    pub price: Decimal,
+    pub timestamp: DateTime<Utc>,
 }
 
-impl OrderMetrics {
-    fn new() -> Self {
+impl OrderFill {
+    pub fn new(fill_id: String, size: Decimal, price: Decimal) -> Self {
         Self {
-            start_time: Instant::now(),
-            validation_duration: None,
-            execution_duration: None,
-            retry_count: 0,
+            fill_id,
+            size,
+            price,
+            timestamp: Utc::now(),
         }
     }
+}
 
-    fn record_validation(&mut self, duration: Duration) {
-        self.validation_duration = Some(duration);
-        metrics::histogram!(
-            format!("{}.validation_duration_ms", METRICS_PREFIX),
-            duration.as_millis() as f64
-        );
-    }
-
-    fn record_execution(&mut self, duration: Duration) {
-        self.execution_duration = Some(duration);
-        metrics::histogram!(
-            format!("{}.execution_duration_ms", METRICS_PREFIX),
-            duration.as_millis() as f64
-        );
-        metrics::counter!(
-            format!("{}.retry_count", METRICS_PREFIX),
-            self.retry_count as i64
-        );
-    }
+/// Result of amending an order
+#[derive(Debug, Clone, PartialEq)]
+pub enum Amendment {
+    /// The venue modified the resting order in place
+    InPlace,
+    /// The order was cancelled and this replacement carries the unfilled remainder
+    Replaced(Order),
 }
 
 /// Core order model with full lifecycle management
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Order {
     pub id: Uuid,
-    pub trading_pair: String,
+    /// Identifier shared by an order and every replacement created by amending it
+    #[serde(default = "Uuid::new_v4")]
+    pub client_order_id: Uuid,
+    /// Order this one replaced, when created by cancel-and-replace
+    #[serde(default)]
+    pub replaces: Option<Uuid>,
+    pub trading_pair: TradingPair,
     pub exchange: String,
     pub order_type: OrderType,
     pub price: Decimal,
@@ -113,8 +117,19 @@ pub struct Order {
     pub status: OrderStatus,
     pub created_at: DateTime<Utc>,
     pub executed_at: Option<DateTime<Utc>>,
-    #[serde(skip)]
-    metrics: OrderMetrics,
+    #[serde(default)]
+    pub retry_count: u8,
+    #[serde(default)]
+    pub validation_duration_ms: Option<u64>,
+    #[serde(default)]
+    pub execution_duration_ms: Option<u64>,
+    #[serde(default)]
+    pub filled_size: Decimal,
+    #[serde(default)]
+    pub fills: Vec<OrderFill>,
+    /// Rests on the book as a maker order; the venue rejects it instead of crossing
+    #[serde(default)]
+    pub post_only: bool,
 }
 
 impl Order {
@@ -130,13 +145,21 @@ impl Order {
         let validation_start = Instant::now();
         
         // Validate order parameters
+        let trading_pair = TradingPair::parse(&trading_pair)
+            .map_err(|e| OrderError::ValidationError(e.to_string()))?;
         validate_order_size(size, &trading_pair)?;
-        
-        let mut metrics = OrderMetrics::new();
-        metrics.record_validation(validation_start.elapsed());
 
+        let validation_duration = validation_start.elapsed();
+        metrics::histogram!(
+            format!("{}.validation_duration_ms", METRICS_PREFIX),
+            validation_duration.as_millis() as f64
+        );
+
+        let id = Uuid::new_v4();
         let order = Self {
-            id: Uuid::new_v4(),
+            id,
+            client_order_id: id,
+            replaces: None,
             trading_pair,
             exchange,
             order_type,
@@ -145,7 +168,12 @@ impl Order {
             status: OrderStatus::Pending,
             created_at: Utc::now(),
             executed_at: None,
-            metrics,
+            retry_count: 0,
+            validation_duration_ms: Some(validation_duration.as_millis() as u64),
+            execution_duration_ms: None,
+            filled_size: Decimal::ZERO,
+            fills: Vec::new(),
+            post_only: false,
         };
 
         info!(
@@ -160,8 +188,12 @@ impl Order {
     }
 
     /// Executes the order with performance tracking and MEV optimization
-    #[instrument(skip(self, solana_client))]
-    pub async fn execute(&mut self, solana_client: &SolanaClient) -> Result<String, OrderError> {
+    #[instrument(skip(self, solana_client, telemetry))]
+    pub async fn execute(
+        &mut self,
+        solana_client: &SolanaClient,
+        telemetry: &OrderTelemetry,
+    ) -> Result<String, OrderError> {
         let execution_start = Instant::now();
         self.status = OrderStatus::Executing;
 
@@ -172,13 +204,14 @@ impl Order {
             ));
         }
 
+        telemetry.begin(self.id);
         let mut retry_count = 0;
         loop {
             match self.try_execute(solana_client).await {
                 Ok(signature) => {
                     self.status = OrderStatus::Executed;
                     self.executed_at = Some(Utc::now());
-                    self.metrics.execution_duration = Some(execution_start.elapsed());
+                    telemetry.finish(self);
                     
                     info!(
                         order_id = %self.id,
@@ -192,7 +225,7 @@ impl Order {
                 }
                 Err(e) if retry_count < MAX_RETRIES => {
                     retry_count += 1;
-                    self.metrics.retry_count += 1;
+                    telemetry.record_retry(self.id);
                     
                     warn!(
                         order_id = %self.id,
@@ -205,6 +238,7 @@ impl Order {
                 }
                 Err(e) => {
                     self.status = OrderStatus::Failed;
+                    telemetry.finish(self);
                     error!(
                         order_id = %self.id,
                         error = %e,
@@ -218,12 +252,52 @@ impl Order {
         }
     }
 
-    /// Cancels a pending order with monitoring
+    /// Rests a post-only limit order on the venue without waiting for it to fill
+    #[instrument(skip(self, solana_client), fields(order_id = %self.id))]
+    pub async fn place_post_only(&mut self, solana_client: &SolanaClient) -> Result<String, OrderError> {
+        if !POST_ONLY_EXCHANGES.contains(&self.exchange.as_str()) {
+            return Err(OrderError::ValidationError(format!(
+                "post-only orders are not supported on {}",
+                self.exchange
+            )));
+        }
+        if self.order_type != OrderType::Limit || self.status != OrderStatus::Pending {
+            return Err(OrderError::ValidationError(
+                "only PENDING limit orders can be placed post-only".to_string(),
+            ));
+        }
+
+        self.post_only = true;
+        let signature = self.try_execute(solana_client).await?;
+
+        metrics::counter!(format!("{}.placed_post_only", METRICS_PREFIX), 1);
+        Ok(signature)
+    }
+
+    /// Sends an in-place amendment made by `amend` to a venue with native modify support
+    #[instrument(skip(self, solana_client), fields(order_id = %self.id))]
+    pub async fn submit_modify(&self, solana_client: &SolanaClient) -> Result<String, OrderError> {
+        if !NATIVE_AMEND_EXCHANGES.contains(&self.exchange.as_str()) {
+            return Err(OrderError::ValidationError(format!(
+                "{} orders cannot be modified in place",
+                self.exchange
+            )));
+        }
+
+        let (signature, _) = solana_client
+            .sign_and_send_transaction(self.create_modify_transaction()?, None)
+            .await
+            .map_err(|e| OrderError::ExecutionError(e.to_string()))?;
+
+        Ok(signature.to_string())
+    }
+
+    /// Cancels a working order with monitoring
     #[instrument(skip(self, solana_client))]
     pub async fn cancel(&mut self, solana_client: &SolanaClient) -> Result<(), OrderError> {
-        if self.status != OrderStatus::Pending {
+        if !matches!(self.status, OrderStatus::Pending | OrderStatus::PartiallyFilled) {
             return Err(OrderError::ValidationError(
-                "only PENDING orders can be cancelled".to_string(),
+                "only PENDING or PARTIALLY_FILLED orders can be cancelled".to_string(),
             ));
         }
 
@@ -248,6 +322,107 @@ impl Order {
         Ok(())
     }
 
+    /// Size still open on the venue
+    pub fn remaining_size(&self) -> Decimal {
+        (self.size - self.filled_size).max(Decimal::ZERO)
+    }
+
+    /// Average price across all fills so far
+    pub fn average_fill_price(&self) -> Option<Decimal> {
+        if self.filled_size.is_zero() {
+            return None;
+        }
+        let notional: Decimal = self.fills.iter().map(|fill| fill.size * fill.price).sum();
+        Some(notional / self.filled_size)
+    }
+
+    /// Records an incremental fill, returning false when the fill was already applied
+    pub fn apply_fill(&mut self, fill: &OrderFill) -> Result<bool, OrderError> {
+        if self.fills.iter().any(|applied| applied.fill_id == fill.fill_id) {
+            return Ok(false);
+        }
+        if matches!(self.status, OrderStatus::Executed | OrderStatus::Failed | OrderStatus::Cancelled) {
+            return Err(OrderError::ValidationError(format!(
+                "cannot fill order in {:?} status",
+                self.status
+            )));
+        }
+        if fill.size <= Decimal::ZERO || fill.price <= Decimal::ZERO {
+            return Err(OrderError::ValidationError(
+                "fill size and price must be positive".to_string(),
+            ));
+        }
+        if fill.size > self.remaining_size() {
+            return Err(OrderError::ValidationError(format!(
+                "fill of {} exceeds remaining size {}",
+                fill.size,
+                self.remaining_size()
+            )));
+        }
+
+        self.filled_size += fill.size;
+        self.fills.push(fill.clone());
+        if self.remaining_size().is_zero() {
+            self.status = OrderStatus::Executed;
+            self.executed_at = Some(fill.timestamp);
+        } else {
+            self.status = OrderStatus::PartiallyFilled;
+        }
+
+        metrics::counter!(format!("{}.fills", METRICS_PREFIX), 1);
+        Ok(true)
+    }
+
+    /// Changes the price and total size of a working order. Venues without native amend get
+    /// a cancel-and-replace: this order is cancelled and a replacement for the unfilled
+    /// remainder is returned under the same client order id. Nothing changes on error.
+    #[instrument(skip(self, new_price, new_size), fields(order_id = %self.id))]
+    pub fn amend(&mut self, new_price: Decimal, new_size: Decimal) -> Result<Amendment, OrderError> {
+        if !matches!(self.status, OrderStatus::Pending | OrderStatus::PartiallyFilled) {
+            return Err(OrderError::ValidationError(format!(
+                "cannot amend order in {:?} status",
+                self.status
+            )));
+        }
+        if new_price <= Decimal::ZERO {
+            return Err(OrderError::ValidationError("price must be positive".to_string()));
+        }
+        if new_size <= self.filled_size {
+            return Err(OrderError::ValidationError(format!(
+                "amended size {} must exceed filled size {}",
+                new_size, self.filled_size
+            )));
+        }
+
+        if NATIVE_AMEND_EXCHANGES.contains(&self.exchange.as_str()) {
+            self.price = new_price;
+            self.size = new_size;
+            metrics::counter!(format!("{}.amended", METRICS_PREFIX), 1);
+            return Ok(Amendment::InPlace);
+        }
+
+        let mut replacement = Order::new(
+            self.trading_pair.to_string(),
+            self.exchange.clone(),
+            self.order_type.clone(),
+            new_price,
+            new_size - self.filled_size,
+        )?;
+        replacement.client_order_id = self.client_order_id;
+        replacement.replaces = Some(self.id);
+        replacement.post_only = self.post_only;
+        self.status = OrderStatus::Cancelled;
+
+        info!(
+            order_id = %self.id,
+            replacement_id = %replacement.id,
+            client_order_id = %self.client_order_id,
+            "Order cancelled and replaced"
+        );
+        metrics::counter!(format!("{}.replaced", METRICS_PREFIX), 1);
+        Ok(Amendment::Replaced(replacement))
+    }
+
     async fn try_execute(&self, solana_client: &SolanaClient) -> Result<String, OrderError> {
         // Create transaction based on order type and exchange
         let transaction = match self.exchange.as_str() {
@@ -286,4 +461,81 @@ fn validate_order_size(size: Decimal, trading_pair: &str) -> Result<(), OrderErr
     // For example, checking against market-specific minimum/maximum sizes
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_order_serde_round_trip() {
+        let mut order = Order::new(
+            "SOL/USDC".to_string(),
+            "jupiter".to_string(),
+            OrderType::Limit,
+            dec!(23.45),
+            dec!(1.5),
+        )
+        .unwrap();
+        order.status = OrderStatus::Executed;
+        order.executed_at = Some(Utc::now());
+        order.retry_count = 2;
+        order.execution_duration_ms = Some(180);
+
+        let json = serde_json::to_string(&order).unwrap();
+        let restored: Order = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, order);
+        assert_eq!(restored.retry_count, 2);
+    }
+
+    #[test]
+    fn test_order_pair_is_canonical() {
+        let order = Order::new("sol_usdc".to_string(), "jupiter".to_string(), OrderType::Limit, dec!(20), dec!(1))
+            .unwrap();
+        assert_eq!(order.trading_pair, "SOL/USDC");
+        assert!(serde_json::to_string(&order).unwrap().contains("\"trading_pair\":\"SOL/USDC\""));
+
+        let result = Order::new("SOLUSDC".to_string(), "jupiter".to_string(), OrderType::Limit, dec!(20), dec!(1));
+        assert!(matches!(result, Err(OrderError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_amend_preserves_client_order_id_lineage() {
+        let mut order = Order::new(
+            "SOL/USDC".to_string(),
+            "jupiter".to_string(),
+            OrderType::Limit,
+            dec!(20),
+            dec!(10),
+        )
+        .unwrap();
+        order.apply_fill(&OrderFill::new("f1".to_string(), dec!(4), dec!(20))).unwrap();
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+        assert_eq!(order.remaining_size(), dec!(6));
+
+        // Amending below the filled size leaves the order untouched
+        assert!(order.amend(dec!(21), dec!(3)).is_err());
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+
+        let Amendment::Replaced(replacement) = order.amend(dec!(21), dec!(10)).unwrap() else {
+            panic!("jupiter orders are replaced");
+        };
+        assert_eq!(order.status, OrderStatus::Cancelled);
+        assert_eq!(replacement.client_order_id, order.client_order_id);
+        assert_eq!(replacement.replaces, Some(order.id));
+        assert_eq!(replacement.size, dec!(6));
+        assert_eq!(replacement.price, dec!(21));
+
+        let mut drift = Order::new(
+            "SOL-PERP".to_string(),
+            "drift".to_string(),
+            OrderType::Limit,
+            dec!(20),
+            dec!(10),
+        )
+        .unwrap();
+        assert_eq!(drift.amend(dec!(19), dec!(12)).unwrap(), Amendment::InPlace);
+        assert_eq!(drift.size, dec!(12));
+    }
+}
@@ -0,0 +1,381 @@
+//! Canonical trading pair identifiers. Pairs arrive from collectors, the API and config in
+//! several spellings ("SOL/USDC", "sol-usdc", "SOL-PERP"); they are parsed once at the
+//! boundary into a `TradingPair` so collector, order book and portfolio keys compare equal.
+//!
+//! Version dependencies:
+//! - rust_decimal = "1.30"
+//! - serde = "1.0"
+//! - thiserror = "1.0"
+
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+// Trading pair constants
+pub const PERP_SUFFIX: &str = "-PERP";
+/// Drift perps settle in USDC
+pub const PERP_QUOTE_ASSET: &str = "USDC";
+/// Quote asset of bonding-curve token launches keyed by mint
+pub const TOKEN_LAUNCH_QUOTE_ASSET: &str = "SOL";
+pub const ACCEPTED_FORMATS: &str =
+    "BASE/QUOTE (SOL/USDC), BASE-QUOTE or BASE_QUOTE (sol-usdc), BASE-PERP (SOL-PERP) or MINT/SOL";
+const SEPARATORS: [char; 3] = ['/', '-', '_'];
+const PERP_MARKER: &str = "PERP";
+const MAX_SYMBOL_LEN: usize = 16;
+const MIN_MINT_LEN: usize = 32;
+const MAX_MINT_LEN: usize = 44;
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const DEFAULT_PAIRS: [&str; 4] = ["SOL/USDC", "ORCA/USDC", "RAY/USDC", "SOL-PERP"];
+
+/// Trading pair parse and registry errors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PairError {
+    #[error("invalid trading pair {0:?}, expected {}", ACCEPTED_FORMATS)]
+    Malformed(String),
+    #[error("invalid symbol {symbol:?} in trading pair {pair:?}, expected {}", ACCEPTED_FORMATS)]
+    InvalidSymbol { pair: String, symbol: String },
+    #[error("unsupported trading pair: {0}")]
+    Unregistered(String),
+}
+
+/// Normalized trading pair: upper-case `BASE/QUOTE` for spot, `BASE-PERP` for perps. Token
+/// mints keep their base58 spelling, since upper-casing would name a different account.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TradingPair(String);
+
+impl TradingPair {
+    /// Parses any accepted spelling into the canonical form
+    pub fn parse(raw: &str) -> Result<Self, PairError> {
+        let trimmed = raw.trim();
+        let malformed = || PairError::Malformed(raw.to_string());
+
+        let legs: Vec<&str> = trimmed.split(SEPARATORS).collect();
+        let [base, quote] = legs.as_slice() else {
+            return Err(malformed());
+        };
+        if base.is_empty() || quote.is_empty() {
+            return Err(malformed());
+        }
+
+        if quote.eq_ignore_ascii_case(PERP_MARKER) {
+            let base = symbol(raw, base)?;
+            if is_mint(&base) {
+                return Err(malformed());
+            }
+            return Ok(Self(format!("{}{}", base, PERP_SUFFIX)));
+        }
+
+        let (base, quote) = (symbol(raw, base)?, symbol(raw, quote)?);
+        if base == quote {
+            return Err(malformed());
+        }
+        Ok(Self(format!("{}/{}", base, quote)))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn base(&self) -> &str {
+        match self.0.strip_suffix(PERP_SUFFIX) {
+            Some(base) => base,
+            None => self.0.split_once('/').map_or(self.0.as_str(), |(base, _)| base),
+        }
+    }
+
+    /// Quote or settlement asset
+    pub fn quote(&self) -> &str {
+        if self.is_perp() {
+            return PERP_QUOTE_ASSET;
+        }
+        self.0.split_once('/').map_or("", |(_, quote)| quote)
+    }
+
+    pub fn is_perp(&self) -> bool {
+        self.0.ends_with(PERP_SUFFIX)
+    }
+
+    /// Bonding-curve launch keyed by token mint rather than a listed symbol
+    pub fn is_token_launch(&self) -> bool {
+        !self.is_perp() && self.quote() == TOKEN_LAUNCH_QUOTE_ASSET && is_mint(self.base())
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+/// Upper-cases a listed symbol; base58 mints are kept verbatim
+fn symbol(pair: &str, raw: &str) -> Result<String, PairError> {
+    if is_mint(raw) {
+        return Ok(raw.to_string());
+    }
+    if raw.len() > MAX_SYMBOL_LEN || !raw.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(PairError::InvalidSymbol {
+            pair: pair.to_string(),
+            symbol: raw.to_string(),
+        });
+    }
+    Ok(raw.to_ascii_uppercase())
+}
+
+fn is_mint(raw: &str) -> bool {
+    (MIN_MINT_LEN..=MAX_MINT_LEN).contains(&raw.len()) && raw.chars().all(|c| BASE58_ALPHABET.contains(c))
+}
+
+impl fmt::Display for TradingPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for TradingPair {
+    type Err = PairError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Self::parse(raw)
+    }
+}
+
+impl TryFrom<String> for TradingPair {
+    type Error = PairError;
+
+    fn try_from(raw: String) -> Result<Self, Self::Error> {
+        Self::parse(&raw)
+    }
+}
+
+impl TryFrom<&str> for TradingPair {
+    type Error = PairError;
+
+    fn try_from(raw: &str) -> Result<Self, Self::Error> {
+        Self::parse(raw)
+    }
+}
+
+impl From<TradingPair> for String {
+    fn from(pair: TradingPair) -> Self {
+        pair.0
+    }
+}
+
+impl Deref for TradingPair {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for TradingPair {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Lets maps keyed by pair be queried with a canonical `&str`
+impl Borrow<str> for TradingPair {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for TradingPair {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for TradingPair {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for TradingPair {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+impl PartialEq<TradingPair> for String {
+    fn eq(&self, other: &TradingPair) -> bool {
+        *self == other.0
+    }
+}
+
+impl PartialEq<TradingPair> for &str {
+    fn eq(&self, other: &TradingPair) -> bool {
+        *self == other.0
+    }
+}
+
+/// Inclusive range a pair's price can plausibly trade in; anything outside is bad data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceBounds {
+    pub min: Decimal,
+    pub max: Decimal,
+}
+
+impl PriceBounds {
+    pub fn new(min: Decimal, max: Decimal) -> Self {
+        Self { min, max }
+    }
+
+    pub fn contains(&self, price: Decimal) -> bool {
+        price >= self.min && price <= self.max
+    }
+}
+
+/// Pairs the bot trades; boundaries reject anything else
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairRegistry {
+    pairs: HashSet<TradingPair>,
+    /// Accepts any MINT/SOL launch pair, which appear faster than they can be listed
+    token_launches: bool,
+    bounds: HashMap<TradingPair, PriceBounds>,
+}
+
+impl PairRegistry {
+    pub fn new(pairs: impl IntoIterator<Item = TradingPair>) -> Self {
+        Self {
+            pairs: pairs.into_iter().collect(),
+            token_launches: false,
+            bounds: HashMap::new(),
+        }
+    }
+
+    pub fn with_token_launches(mut self, allowed: bool) -> Self {
+        self.token_launches = allowed;
+        self
+    }
+
+    pub fn register(&mut self, pair: TradingPair) {
+        self.pairs.insert(pair);
+    }
+
+    /// Registers the pair with the price range market data for it must fall in
+    pub fn with_price_bounds(mut self, pair: TradingPair, bounds: PriceBounds) -> Self {
+        self.pairs.insert(pair.clone());
+        self.bounds.insert(pair, bounds);
+        self
+    }
+
+    /// Plausible price range of a pair, when one is configured
+    pub fn price_bounds(&self, pair: &TradingPair) -> Option<PriceBounds> {
+        self.bounds.get(pair).copied()
+    }
+
+    pub fn contains(&self, pair: &TradingPair) -> bool {
+        self.pairs.contains(pair) || (self.token_launches && pair.is_token_launch())
+    }
+
+    /// Parses a pair and checks it is registered
+    pub fn parse(&self, raw: &str) -> Result<TradingPair, PairError> {
+        let pair = TradingPair::parse(raw)?;
+        if !self.contains(&pair) {
+            return Err(PairError::Unregistered(pair.into_string()));
+        }
+        Ok(pair)
+    }
+
+    /// Registered pairs in canonical order
+    pub fn pairs(&self) -> Vec<TradingPair> {
+        let mut pairs: Vec<TradingPair> = self.pairs.iter().cloned().collect();
+        pairs.sort();
+        pairs
+    }
+}
+
+impl Default for PairRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_PAIRS.iter().filter_map(|pair| TradingPair::parse(pair).ok()))
+            .with_token_launches(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const BONK_MINT: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+
+    #[test]
+    fn test_dex_formats_normalize() {
+        // Jupiter quotes BASE/QUOTE, Drift names perp markets BASE-PERP, the API takes
+        // URL-safe lower-case spellings
+        for (raw, canonical) in [
+            ("SOL/USDC", "SOL/USDC"),
+            ("sol/usdc", "SOL/USDC"),
+            ("sol-usdc", "SOL/USDC"),
+            ("RAY_USDC", "RAY/USDC"),
+            (" orca/usdc ", "ORCA/USDC"),
+            ("SOL-PERP", "SOL-PERP"),
+            ("sol-perp", "SOL-PERP"),
+            ("SOL/PERP", "SOL-PERP"),
+        ] {
+            assert_eq!(TradingPair::parse(raw).unwrap(), canonical, "{}", raw);
+        }
+
+        let perp = TradingPair::parse("sol-perp").unwrap();
+        assert!(perp.is_perp());
+        assert_eq!(perp.base(), "SOL");
+        assert_eq!(perp.quote(), PERP_QUOTE_ASSET);
+    }
+
+    #[test]
+    fn test_pump_fun_mint_keeps_case() {
+        // Pump.fun keys launches by mint, which must not be upper-cased
+        let pair = TradingPair::parse(&format!("{}/sol", BONK_MINT)).unwrap();
+        assert_eq!(pair.as_str(), format!("{}/SOL", BONK_MINT));
+        assert_eq!(pair.base(), BONK_MINT);
+        assert!(pair.is_token_launch());
+        assert!(TradingPair::parse(&format!("{}-PERP", BONK_MINT)).is_err());
+    }
+
+    #[test]
+    fn test_malformed_pairs_rejected() {
+        for raw in ["", "SOL", "SOL/USDC/USDT", "SOL-USDC/USDT", "/USDC", "SOL/", "SOL/SOL", "S*L/USDC"] {
+            let err = TradingPair::parse(raw).unwrap_err();
+            assert!(err.to_string().contains(ACCEPTED_FORMATS), "{}", raw);
+        }
+    }
+
+    #[test]
+    fn test_serde_is_canonical() {
+        let pair: TradingPair = serde_json::from_str("\"sol-usdc\"").unwrap();
+        assert_eq!(serde_json::to_string(&pair).unwrap(), "\"SOL/USDC\"");
+        assert!(serde_json::from_str::<TradingPair>("\"SOLUSDC\"").is_err());
+    }
+
+    #[test]
+    fn test_keys_match_across_spellings() {
+        let mut positions = HashMap::new();
+        positions.insert(TradingPair::parse("SOL/USDC").unwrap(), 1);
+        assert_eq!(positions.get(&TradingPair::parse("sol/usdc").unwrap()), Some(&1));
+        assert_eq!(positions.get("SOL/USDC"), Some(&1));
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry = PairRegistry::default();
+        assert_eq!(registry.parse("sol-usdc").unwrap(), "SOL/USDC");
+        assert_eq!(registry.parse("SOL-PERP").unwrap(), "SOL-PERP");
+        assert!(registry.parse(&format!("{}/SOL", BONK_MINT)).is_ok());
+        assert_eq!(
+            registry.parse("BTC/USDC"),
+            Err(PairError::Unregistered("BTC/USDC".to_string()))
+        );
+
+        let listed_only = PairRegistry::new(registry.pairs());
+        assert!(listed_only.parse(&format!("{}/SOL", BONK_MINT)).is_err());
+    }
+}
@@ -10,7 +10,8 @@
 //! - metrics = "0.20"
 
 use chrono::{DateTime, Utc};
-use metrics::{counter, histogram};
+use lazy_static::lazy_static;
+use metrics::{register_counter, register_histogram, Counter, Histogram};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::RoundingStrategy;
 use serde::{Deserialize, Serialize};
@@ -20,16 +21,54 @@ use thiserror::Error;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+use crate::models::market::{QuoteAsset, SOL_USDC_PAIR};
 use crate::models::trade::{Trade, calculate_trade_value};
 use crate::models::order::{Order, validate_order};
+use crate::models::pair::TradingPair;
+use crate::models::transfer::{
+    classify_balance_change, confirm_transfer, FlowAdjustedReturns, FlowAdjustedTracker, Transfer,
+    TransferDirection,
+};
+use crate::utils::metric_handles::{AggregatedCounter, LabeledCounter};
+use crate::utils::percent::Percent;
+use crate::utils::solana::TokenBalanceChange;
 
 // Constants for portfolio management
 const MIN_PORTFOLIO_VALUE: Decimal = Decimal::new(100, 0); // Minimum 100 USDC
-const MAX_POSITION_SIZE_PERCENT: Decimal = Decimal::new(20, 2); // 20% max position size
+const MAX_POSITION_SIZE_PERCENT: Percent = Percent::from_percent(Decimal::new(20, 0)); // 20% max position size
 const CACHE_EXPIRY_SECONDS: i64 = 300; // 5 minutes cache expiry
 const MAX_CONCURRENT_OPERATIONS: usize = 100;
+const ENTRY_PRICE_SCALE: u32 = 8;
 const METRICS_PREFIX: &str = "trading_bot.portfolio";
 
+// Metric handles registered once on first use; fill counts are aggregated between flushes
+lazy_static! {
+    static ref CREATED: Counter = register_counter!(format!("{}.created", METRICS_PREFIX));
+    static ref INITIAL_BALANCE: Histogram = register_histogram!(format!("{}.initial_balance", METRICS_PREFIX));
+    static ref TOTAL_VALUE: Histogram = register_histogram!(format!("{}.total_value", METRICS_PREFIX));
+    static ref POSITIONS_UPDATED: &'static AggregatedCounter =
+        AggregatedCounter::register(format!("{}.positions_updated", METRICS_PREFIX));
+    static ref POSITION_SIZE: Histogram = register_histogram!(format!("{}.position_size", METRICS_PREFIX));
+    static ref PARTIAL_CLOSES: Counter = register_counter!(format!("{}.partial_closes", METRICS_PREFIX));
+    static ref REALIZED_PNL: Histogram = register_histogram!(format!("{}.realized_pnl", METRICS_PREFIX));
+    static ref USDC_BALANCE: Histogram = balance_histogram(QuoteAsset::Usdc);
+    static ref SOL_BALANCE: Histogram = balance_histogram(QuoteAsset::Sol);
+    static ref WSOL_BALANCE: Histogram = balance_histogram(QuoteAsset::Wsol);
+    static ref RESERVATIONS: Counter = register_counter!(format!("{}.reservations", METRICS_PREFIX));
+    static ref RESERVATIONS_REJECTED: Counter = register_counter!(format!("{}.reservations_rejected", METRICS_PREFIX));
+    static ref RESERVATIONS_RELEASED: Counter = register_counter!(format!("{}.reservations_released", METRICS_PREFIX));
+    static ref RESERVATIONS_SETTLED: Counter = register_counter!(format!("{}.reservations_settled", METRICS_PREFIX));
+    static ref RESERVATIONS_EXPIRED: Counter = register_counter!(format!("{}.reservations_expired", METRICS_PREFIX));
+    static ref UNCONFIRMED_BALANCE_CHANGES: Counter =
+        register_counter!(format!("{}.unconfirmed_balance_changes", METRICS_PREFIX));
+    static ref TRANSFERS: LabeledCounter = LabeledCounter::new(format!("{}.transfers", METRICS_PREFIX), "direction");
+    static ref POSITIONS_CLOSED: Counter = register_counter!(format!("{}.positions_closed", METRICS_PREFIX));
+}
+
+fn balance_histogram(asset: QuoteAsset) -> Histogram {
+    register_histogram!(format!("{}.{}_balance", METRICS_PREFIX, asset.as_str().to_lowercase()))
+}
+
 /// Portfolio-related error types
 #[derive(Error, Debug)]
 pub enum PortfolioError {
@@ -43,25 +82,248 @@ pub enum PortfolioError {
     CalculationError(String),
 }
 
-/// Thread-safe position tracking
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Thread-safe position tracking; positive size is long, negative is short
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Position {
-    pub trading_pair: String,
+    pub trading_pair: TradingPair,
     pub size: Decimal,
     pub entry_price: Decimal,
+    #[serde(default)]
+    pub realized_pnl: Decimal,
     pub last_updated: DateTime<Utc>,
 }
 
+/// Perpetual futures position with margin state derived from the venue's account data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PerpPosition {
+    pub market: String,
+    /// Signed base size; positive is long
+    pub size: Decimal,
+    pub entry_price: Decimal,
+    pub mark_price: Decimal,
+    /// Position notional as a multiple of account collateral
+    pub leverage: Decimal,
+    /// Initial margin the position ties up
+    pub margin: Decimal,
+    pub maintenance_margin: Decimal,
+    /// Mark price at which the account falls to maintenance margin, if reachable
+    pub liquidation_price: Option<Decimal>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PerpPosition {
+    pub fn notional(&self) -> Decimal {
+        self.size.abs() * self.mark_price
+    }
+
+    pub fn unrealized_pnl(&self) -> Decimal {
+        self.size * (self.mark_price - self.entry_price)
+    }
+}
+
+/// Result of netting a fill against an existing position
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetFill {
+    /// Signed position size after the fill
+    pub size: Decimal,
+    /// Volume-weighted entry price of the remaining position
+    pub entry_price: Decimal,
+    /// Portion of the prior position offset by the fill
+    pub closed_size: Decimal,
+    /// Profit or loss realized on the offset portion, in quote currency
+    pub realized_pnl: Decimal,
+}
+
+impl NetFill {
+    pub fn is_flat(&self) -> bool {
+        self.size.is_zero()
+    }
+}
+
+/// Partial or full position close produced by an offsetting fill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionClose {
+    pub id: Uuid,
+    pub trading_pair: String,
+    pub closed_size: Decimal,
+    pub entry_price: Decimal,
+    pub exit_price: Decimal,
+    pub realized_pnl: Decimal,
+    pub remaining_size: Decimal,
+    pub closed_at: DateTime<Utc>,
+}
+
+/// Position changes one fill made
+#[derive(Debug, Clone, Default)]
+pub struct FillOutcome {
+    /// Position the fill opened from flat or flipped to the other side
+    pub opened: Option<Position>,
+    /// Portion of the prior position the fill offset
+    pub close: Option<PositionClose>,
+}
+
+/// Nets a signed fill against a signed position.
+/// Same-direction fills average the entry price; opposite fills realize P&L on the
+/// offset amount, and a fill larger than the position flips it at the fill price.
+pub fn net_fill(
+    current_size: Decimal,
+    current_entry: Decimal,
+    fill_size: Decimal,
+    fill_price: Decimal,
+) -> Result<NetFill, PortfolioError> {
+    if fill_size.is_zero() {
+        return Err(PortfolioError::ValidationError("fill size must be non-zero".to_string()));
+    }
+    if fill_price <= Decimal::ZERO {
+        return Err(PortfolioError::ValidationError("fill price must be positive".to_string()));
+    }
+
+    // Opening or adding in the same direction
+    if current_size.is_zero() || current_size.is_sign_positive() == fill_size.is_sign_positive() {
+        let size = current_size + fill_size;
+        let cost = current_size.abs() * current_entry + fill_size.abs() * fill_price;
+        let entry_price = (cost / size.abs())
+            .round_dp_with_strategy(ENTRY_PRICE_SCALE, RoundingStrategy::MidpointAwayFromZero);
+
+        return Ok(NetFill {
+            size,
+            entry_price,
+            closed_size: Decimal::ZERO,
+            realized_pnl: Decimal::ZERO,
+        });
+    }
+
+    // Offsetting fill realizes P&L on the overlapping amount
+    let closed_size = current_size.abs().min(fill_size.abs());
+    let direction = if current_size.is_sign_positive() { Decimal::ONE } else { Decimal::NEGATIVE_ONE };
+    let realized_pnl = closed_size * (fill_price - current_entry) * direction;
+
+    let size = current_size + fill_size;
+    let entry_price = if size.is_zero() {
+        Decimal::ZERO
+    } else if size.is_sign_positive() == current_size.is_sign_positive() {
+        current_entry
+    } else {
+        // Flipped: the remainder starts a fresh cost basis
+        fill_price
+    };
+
+    Ok(NetFill {
+        size,
+        entry_price,
+        closed_size,
+        realized_pnl,
+    })
+}
+
+/// Portfolio valuation in the reporting currency, with the SOL/USDC rate used to convert
+/// SOL-quoted balances and positions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValuationSnapshot {
+    pub reporting_currency: QuoteAsset,
+    pub total_value: Decimal,
+    /// Balances plus position values held in each quote asset, in that asset's units
+    pub quote_values: HashMap<QuoteAsset, Decimal>,
+    pub sol_usdc_rate: Option<Decimal>,
+    pub valued_at: DateTime<Utc>,
+}
+
+/// Point-in-time copy of portfolio balances and positions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioSnapshot {
+    pub wallet_address: String,
+    pub balances: HashMap<QuoteAsset, Decimal>,
+    /// Sorted by trading pair
+    pub positions: Vec<Position>,
+    pub realized_pnl: Decimal,
+}
+
+impl PortfolioSnapshot {
+    /// Values the snapshot's balances and positions at `market_prices`
+    pub fn valuation(
+        &self,
+        market_prices: &HashMap<String, Decimal>,
+        valued_at: DateTime<Utc>,
+    ) -> Result<ValuationSnapshot, PortfolioError> {
+        value_holdings(self.balances.clone(), &self.positions, market_prices, valued_at)
+    }
+}
+
+/// Values quote balances plus positions at `market_prices` in the reporting currency,
+/// converting SOL-quoted value at the SOL/USDC rate
+pub fn value_holdings<'a>(
+    mut quote_values: HashMap<QuoteAsset, Decimal>,
+    positions: impl IntoIterator<Item = &'a Position>,
+    market_prices: &HashMap<String, Decimal>,
+    valued_at: DateTime<Utc>,
+) -> Result<ValuationSnapshot, PortfolioError> {
+    for position in positions {
+        let trading_pair = &position.trading_pair;
+        let current_price = market_prices
+            .get(trading_pair.as_str())
+            .ok_or_else(|| PortfolioError::CalculationError(
+                format!("no price data for {}", trading_pair)
+            ))?;
+
+        let position_value = calculate_trade_value(position.size, *current_price)
+            .map_err(|e| PortfolioError::CalculationError(e.to_string()))?;
+
+        *quote_values.entry(QuoteAsset::of_pair(trading_pair)).or_insert(Decimal::ZERO) += position_value;
+    }
+
+    // Only SOL exposure needs a conversion rate
+    let holds_sol = quote_values.iter().any(|(asset, value)| asset.is_sol() && !value.is_zero());
+    let sol_usdc_rate = if holds_sol {
+        Some(
+            QuoteAsset::Sol
+                .reporting_rate(market_prices)
+                .map_err(|e| PortfolioError::CalculationError(e.to_string()))?,
+        )
+    } else {
+        market_prices.get(SOL_USDC_PAIR).copied()
+    };
+
+    let total_value: Decimal = quote_values
+        .iter()
+        .map(|(asset, value)| match asset {
+            QuoteAsset::Usdc => *value,
+            _ => *value * sol_usdc_rate.unwrap_or(Decimal::ZERO),
+        })
+        .sum();
+
+    Ok(ValuationSnapshot {
+        reporting_currency: QuoteAsset::REPORTING,
+        total_value,
+        quote_values,
+        sol_usdc_rate,
+        valued_at,
+    })
+}
+
+/// Quote funds held for an order between risk validation and settlement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reservation {
+    pub order_id: Uuid,
+    pub asset: QuoteAsset,
+    pub amount: Decimal,
+    pub reserved_at: DateTime<Utc>,
+}
+
 /// High-performance portfolio management system
 #[derive(Debug, Clone)]
 #[metrics(prefix = "portfolio")]
 pub struct Portfolio {
     id: Uuid,
     wallet_address: String,
-    usdc_balance: Arc<RwLock<Decimal>>,
-    positions: Arc<RwLock<HashMap<String, Position>>>,
+    balances: Arc<RwLock<HashMap<QuoteAsset, Decimal>>>,
+    positions: Arc<RwLock<HashMap<TradingPair, Position>>>,
     last_updated: DateTime<Utc>,
     value_cache: Arc<RwLock<(DateTime<Utc>, Decimal)>>,
+    flow_tracker: Arc<RwLock<FlowAdjustedTracker>>,
+    transfers: Arc<RwLock<Vec<Transfer>>>,
+    realized_pnl: Arc<RwLock<Decimal>>,
+    last_valuation: Arc<RwLock<Option<ValuationSnapshot>>>,
+    reservations: Arc<RwLock<HashMap<Uuid, Reservation>>>,
 }
 
 impl Portfolio {
@@ -77,18 +339,20 @@ impl Portfolio {
         let portfolio = Self {
             id: Uuid::new_v4(),
             wallet_address,
-            usdc_balance: Arc::new(RwLock::new(initial_balance)),
+            balances: Arc::new(RwLock::new(HashMap::from([(QuoteAsset::Usdc, initial_balance)]))),
             positions: Arc::new(RwLock::new(HashMap::with_capacity(MAX_CONCURRENT_OPERATIONS))),
             last_updated: Utc::now(),
             value_cache: Arc::new(RwLock::new((Utc::now(), initial_balance))),
+            flow_tracker: Arc::new(RwLock::new(FlowAdjustedTracker::new(initial_balance))),
+            transfers: Arc::new(RwLock::new(Vec::new())),
+            realized_pnl: Arc::new(RwLock::new(Decimal::ZERO)),
+            last_valuation: Arc::new(RwLock::new(None)),
+            reservations: Arc::new(RwLock::new(HashMap::new())),
         };
 
         // Initialize metrics
-        counter!(format!("{}.created", METRICS_PREFIX), 1);
-        histogram!(
-            format!("{}.initial_balance", METRICS_PREFIX),
-            initial_balance.to_f64().unwrap_or(0.0)
-        );
+        CREATED.increment(1);
+        INITIAL_BALANCE.record(initial_balance.to_f64().unwrap_or(0.0));
 
         Ok(portfolio)
     }
@@ -108,109 +372,492 @@ impl Portfolio {
         }
         drop(cache);
 
-        // Calculate new value
-        let usdc_balance = *self.usdc_balance.read().await;
+        Ok(self.valuation(market_prices).await?.total_value)
+    }
+
+    /// Values balances and positions in the reporting currency, converting SOL-quoted
+    /// holdings at the SOL/USDC price, and records the snapshot
+    #[tracing::instrument(skip(self, market_prices))]
+    pub async fn valuation(
+        &self,
+        market_prices: &HashMap<String, Decimal>,
+    ) -> Result<ValuationSnapshot, PortfolioError> {
+        let balances = self.balances.read().await.clone();
         let positions = self.positions.read().await;
-        
-        let mut total_value = usdc_balance;
+        let snapshot = value_holdings(balances, positions.values(), market_prices, Utc::now())?;
+        drop(positions);
+        let total_value = snapshot.total_value;
 
-        for (trading_pair, position) in positions.iter() {
-            let current_price = market_prices
-                .get(trading_pair)
-                .ok_or_else(|| PortfolioError::CalculationError(
-                    format!("no price data for {}", trading_pair)
-                ))?;
+        // Update cache and flow-adjusted performance
+        *self.value_cache.write().await = (snapshot.valued_at, total_value);
+        self.flow_tracker.write().await.observe(total_value);
+        *self.last_valuation.write().await = Some(snapshot.clone());
 
-            let position_value = calculate_trade_value(position.size, *current_price)
-                .map_err(|e| PortfolioError::CalculationError(e.to_string()))?;
+        // Record metrics
+        TOTAL_VALUE.record(total_value.to_f64().unwrap_or(0.0));
+
+        Ok(snapshot)
+    }
 
-            total_value += position_value;
+    /// Portfolio value for a caller already holding the positions lock: the cached value while
+    /// fresh, otherwise a valuation of the given positions that leaves the cache untouched.
+    /// Without market prices at hand, positions are marked at their entry price, the filled
+    /// pair at the fill price and SOL/USDC at the last recorded rate
+    async fn value_under_lock(
+        &self,
+        positions: &HashMap<TradingPair, Position>,
+        fill_pair: &TradingPair,
+        fill_price: Decimal,
+    ) -> Result<Decimal, PortfolioError> {
+        let cache = *self.value_cache.read().await;
+        if (Utc::now() - cache.0).num_seconds() < CACHE_EXPIRY_SECONDS {
+            return Ok(cache.1);
         }
 
-        // Update cache
-        *self.value_cache.write().await = (Utc::now(), total_value);
+        let mut marks: HashMap<String, Decimal> = positions
+            .values()
+            .map(|position| (position.trading_pair.as_str().to_string(), position.entry_price))
+            .collect();
+        let sol_usdc_rate = self.last_valuation.read().await.as_ref().and_then(|snapshot| snapshot.sol_usdc_rate);
+        if let Some(rate) = sol_usdc_rate {
+            marks.insert(SOL_USDC_PAIR.to_string(), rate);
+        }
+        marks.insert(fill_pair.as_str().to_string(), fill_price);
 
-        // Record metrics
-        histogram!(
-            format!("{}.total_value", METRICS_PREFIX),
-            total_value.to_f64().unwrap_or(0.0)
-        );
+        let balances = self.balances.read().await.clone();
+        Ok(value_holdings(balances, positions.values(), &marks, Utc::now())?.total_value)
+    }
 
-        Ok(total_value)
+    /// Returns the most recent valuation snapshot
+    pub async fn last_valuation(&self) -> Option<ValuationSnapshot> {
+        self.last_valuation.read().await.clone()
     }
 
-    /// Opens a new position or updates existing one
-    #[tracing::instrument(skip(self, size, entry_price))]
+    /// Rate converting a quote asset into the reporting currency, taken from the latest valuation
+    async fn reporting_rate(&self, quote: QuoteAsset) -> Result<Decimal, PortfolioError> {
+        if !quote.is_sol() {
+            return Ok(Decimal::ONE);
+        }
+        self.last_valuation
+            .read()
+            .await
+            .as_ref()
+            .and_then(|snapshot| snapshot.sol_usdc_rate)
+            .ok_or_else(|| PortfolioError::CalculationError(format!(
+                "no {} rate recorded to convert {} values",
+                SOL_USDC_PAIR, quote
+            )))
+    }
+
+    /// Applies a signed fill (positive buys, negative sells) to the position for a pair,
+    /// returning the close record when part of the position was offset
     pub async fn add_position(
         &self,
-        trading_pair: String,
+        trading_pair: TradingPair,
         size: Decimal,
         entry_price: Decimal,
-    ) -> Result<(), PortfolioError> {
-        // Validate position parameters
-        if size <= Decimal::ZERO {
-            return Err(PortfolioError::ValidationError("position size must be positive".to_string()));
-        }
+    ) -> Result<Option<PositionClose>, PortfolioError> {
+        self.apply_fill(trading_pair, size, entry_price)
+            .await
+            .map(|outcome| outcome.close)
+    }
 
-        // Calculate position value
-        let position_value = calculate_trade_value(size, entry_price)
-            .map_err(|e| PortfolioError::CalculationError(e.to_string()))?;
+    /// Applies a signed fill like `add_position`, also returning the position it opened
+    /// from flat or flipped to the other side
+    #[tracing::instrument(skip(self, size, entry_price))]
+    pub async fn apply_fill(
+        &self,
+        trading_pair: TradingPair,
+        size: Decimal,
+        entry_price: Decimal,
+    ) -> Result<FillOutcome, PortfolioError> {
+        // Held from read to write so concurrent fills on a pair net one after another
+        let mut positions = self.positions.write().await;
+        let (current_size, current_entry, current_realized) = positions
+            .get(&trading_pair)
+            .map(|p| (p.size, p.entry_price, p.realized_pnl))
+            .unwrap_or((Decimal::ZERO, Decimal::ZERO, Decimal::ZERO));
 
-        // Check portfolio limits
-        let total_value = self.calculate_portfolio_value(&HashMap::new()).await?;
-        let position_percentage = (position_value * Decimal::new(100, 0)) / total_value;
+        let fill = net_fill(current_size, current_entry, size, entry_price)?;
 
-        if position_percentage > MAX_POSITION_SIZE_PERCENT {
-            return Err(PortfolioError::ValidationError(format!(
-                "position size {}% exceeds maximum {}%",
-                position_percentage, MAX_POSITION_SIZE_PERCENT
-            )));
+        // Check portfolio limits in the reporting currency when exposure grows
+        if fill.size.abs() > current_size.abs() {
+            let position_value = calculate_trade_value(fill.size.abs(), entry_price)
+                .map_err(|e| PortfolioError::CalculationError(e.to_string()))?
+                * self.reporting_rate(QuoteAsset::of_pair(&trading_pair)).await?;
+            let total_value = self.value_under_lock(&positions, &trading_pair, entry_price).await?;
+            let position_percentage = Percent::ratio(position_value, total_value);
+
+            if position_percentage > MAX_POSITION_SIZE_PERCENT {
+                return Err(PortfolioError::ValidationError(format!(
+                    "position size {} exceeds maximum {}",
+                    position_percentage, MAX_POSITION_SIZE_PERCENT
+                )));
+            }
         }
 
         // Update position
-        let mut positions = self.positions.write().await;
-        positions.insert(trading_pair.clone(), Position {
-            trading_pair,
-            size,
-            entry_price,
-            last_updated: Utc::now(),
-        });
+        let mut opened = None;
+        if fill.is_flat() {
+            positions.remove(&trading_pair);
+        } else {
+            let position = Position {
+                trading_pair: trading_pair.clone(),
+                size: fill.size,
+                entry_price: fill.entry_price,
+                realized_pnl: current_realized + fill.realized_pnl,
+                last_updated: Utc::now(),
+            };
+            if current_size.is_zero() || current_size.is_sign_positive() != fill.size.is_sign_positive() {
+                opened = Some(position.clone());
+            }
+            positions.insert(trading_pair.clone(), position);
+        }
+        drop(positions);
 
         // Invalidate value cache
         self.value_cache.write().await.0 = Utc::now() - chrono::Duration::seconds(CACHE_EXPIRY_SECONDS + 1);
 
         // Record metrics
-        counter!(format!("{}.positions_updated", METRICS_PREFIX), 1);
-        histogram!(
-            format!("{}.position_size", METRICS_PREFIX),
-            position_value.to_f64().unwrap_or(0.0)
-        );
+        POSITIONS_UPDATED.increment(1);
+        POSITION_SIZE.record((fill.size.abs() * fill.entry_price).to_f64().unwrap_or(0.0));
 
-        Ok(())
+        if fill.closed_size.is_zero() {
+            return Ok(FillOutcome { opened, close: None });
+        }
+
+        *self.realized_pnl.write().await += fill.realized_pnl;
+        PARTIAL_CLOSES.increment(1);
+        REALIZED_PNL.record(fill.realized_pnl.to_f64().unwrap_or(0.0));
+
+        Ok(FillOutcome {
+            opened,
+            close: Some(PositionClose {
+                id: Uuid::new_v4(),
+                trading_pair: trading_pair.into_string(),
+                closed_size: fill.closed_size,
+                entry_price: current_entry,
+                exit_price: entry_price,
+                realized_pnl: fill.realized_pnl,
+                remaining_size: fill.size,
+                closed_at: Utc::now(),
+            }),
+        })
+    }
+
+    /// Returns the position held for a trading pair
+    pub async fn get_position(&self, trading_pair: &str) -> Option<Position> {
+        self.positions.read().await.get(trading_pair).cloned()
+    }
+
+    /// Returns every open position
+    pub async fn get_positions(&self) -> Vec<Position> {
+        self.positions.read().await.values().cloned().collect()
+    }
+
+    /// Unrealized P&L of open positions at the given prices, in the reporting currency
+    pub async fn unrealized_pnl(
+        &self,
+        market_prices: &HashMap<String, Decimal>,
+    ) -> Result<Decimal, PortfolioError> {
+        let positions = self.positions.read().await;
+        let mut unrealized = Decimal::ZERO;
+        for (trading_pair, position) in positions.iter() {
+            let current_price = market_prices
+                .get(trading_pair.as_str())
+                .ok_or_else(|| PortfolioError::CalculationError(
+                    format!("no price data for {}", trading_pair)
+                ))?;
+            let rate = QuoteAsset::of_pair(trading_pair)
+                .reporting_rate(market_prices)
+                .map_err(|e| PortfolioError::CalculationError(e.to_string()))?;
+            unrealized += position.size * (*current_price - position.entry_price) * rate;
+        }
+        Ok(unrealized)
+    }
+
+    /// Returns total P&L realized by offsetting fills
+    pub async fn get_realized_pnl(&self) -> Decimal {
+        *self.realized_pnl.read().await
     }
 
     /// Updates portfolio USDC balance with thread safety
     #[tracing::instrument(skip(self, new_balance))]
     pub async fn update_balance(&self, new_balance: Decimal) -> Result<(), PortfolioError> {
+        self.update_quote_balance(QuoteAsset::Usdc, new_balance).await
+    }
+
+    /// Updates the balance held in a quote asset
+    #[tracing::instrument(skip(self, new_balance))]
+    pub async fn update_quote_balance(
+        &self,
+        asset: QuoteAsset,
+        new_balance: Decimal,
+    ) -> Result<(), PortfolioError> {
         if new_balance < Decimal::ZERO {
             return Err(PortfolioError::ValidationError("balance cannot be negative".to_string()));
         }
 
-        let mut balance = self.usdc_balance.write().await;
-        *balance = new_balance;
+        self.balances.write().await.insert(asset, new_balance);
 
         // Invalidate value cache
         self.value_cache.write().await.0 = Utc::now() - chrono::Duration::seconds(CACHE_EXPIRY_SECONDS + 1);
 
         // Record metrics
-        histogram!(
-            format!("{}.usdc_balance", METRICS_PREFIX),
-            new_balance.to_f64().unwrap_or(0.0)
+        let balance = match asset {
+            QuoteAsset::Usdc => &*USDC_BALANCE,
+            QuoteAsset::Sol => &*SOL_BALANCE,
+            QuoteAsset::Wsol => &*WSOL_BALANCE,
+        };
+        balance.record(new_balance.to_f64().unwrap_or(0.0));
+
+        Ok(())
+    }
+
+    /// Returns the balance held in a quote asset
+    pub async fn quote_balance(&self, asset: QuoteAsset) -> Decimal {
+        self.balances.read().await.get(&asset).copied().unwrap_or(Decimal::ZERO)
+    }
+
+    /// Balance in a quote asset not held by outstanding reservations
+    pub async fn available_balance(&self, asset: QuoteAsset) -> Decimal {
+        let balance = self.quote_balance(asset).await;
+        balance - reserved_total(&*self.reservations.read().await, asset)
+    }
+
+    /// Outstanding reservations, oldest first
+    pub async fn reservations(&self) -> Vec<Reservation> {
+        let mut reservations: Vec<Reservation> = self.reservations.read().await.values().cloned().collect();
+        reservations.sort_by_key(|reservation| reservation.reserved_at);
+        reservations
+    }
+
+    /// Holds USDC for an order once it passes risk validation, failing when the available
+    /// balance cannot cover it. The check and the hold happen under one lock so concurrent
+    /// orders cannot both spend the same funds.
+    #[tracing::instrument(skip(self))]
+    pub async fn reserve(&self, amount: Decimal, order_id: Uuid) -> Result<(), PortfolioError> {
+        if amount <= Decimal::ZERO {
+            return Err(PortfolioError::ValidationError("reservation must be positive".to_string()));
+        }
+
+        let balances = self.balances.read().await;
+        let mut reservations = self.reservations.write().await;
+        if reservations.contains_key(&order_id) {
+            return Err(PortfolioError::BalanceError(format!("order {} already has a reservation", order_id)));
+        }
+        let balance = balances.get(&QuoteAsset::Usdc).copied().unwrap_or(Decimal::ZERO);
+        let available = balance - reserved_total(&reservations, QuoteAsset::Usdc);
+        if amount > available {
+            RESERVATIONS_REJECTED.increment(1);
+            return Err(PortfolioError::BalanceError(format!(
+                "insufficient available balance: {} requested, {} available",
+                amount, available
+            )));
+        }
+
+        reservations.insert(
+            order_id,
+            Reservation {
+                order_id,
+                asset: QuoteAsset::Usdc,
+                amount,
+                reserved_at: Utc::now(),
+            },
         );
+        RESERVATIONS.increment(1);
+        Ok(())
+    }
 
+    /// Returns an order's held funds to the available balance after a failure or cancel
+    pub async fn release(&self, order_id: Uuid) -> Option<Reservation> {
+        let released = self.reservations.write().await.remove(&order_id);
+        if released.is_some() {
+            RESERVATIONS_RELEASED.increment(1);
+        }
+        released
+    }
+
+    /// Replaces an order's reservation with its actual cost once filled
+    #[tracing::instrument(skip(self))]
+    pub async fn settle(&self, order_id: Uuid, actual_cost: Decimal) -> Result<(), PortfolioError> {
+        let mut balances = self.balances.write().await;
+        let mut reservations = self.reservations.write().await;
+        let reservation = reservations
+            .remove(&order_id)
+            .ok_or_else(|| PortfolioError::BalanceError(format!("no reservation for order {}", order_id)))?;
+
+        let balance = balances.entry(reservation.asset).or_insert(Decimal::ZERO);
+        if actual_cost > *balance {
+            // Keep the hold so the shortfall stays visible until the balance is reconciled
+            reservations.insert(order_id, reservation);
+            return Err(PortfolioError::BalanceError(format!(
+                "settlement of {} exceeds balance {}",
+                actual_cost, balance
+            )));
+        }
+        *balance -= actual_cost;
+        drop(reservations);
+        drop(balances);
+
+        self.value_cache.write().await.0 = Utc::now() - chrono::Duration::seconds(CACHE_EXPIRY_SECONDS + 1);
+        RESERVATIONS_SETTLED.increment(1);
         Ok(())
     }
 
+    /// Releases reservations held longer than the timeout, for orders that never settled
+    pub async fn release_expired(&self, timeout: chrono::Duration, now: DateTime<Utc>) -> Vec<Reservation> {
+        let mut reservations = self.reservations.write().await;
+        let expired: Vec<Uuid> = reservations
+            .values()
+            .filter(|reservation| now - reservation.reserved_at > timeout)
+            .map(|reservation| reservation.order_id)
+            .collect();
+        let released: Vec<Reservation> = expired
+            .iter()
+            .filter_map(|order_id| reservations.remove(order_id))
+            .collect();
+        if !released.is_empty() {
+            RESERVATIONS_EXPIRED.increment(released.len() as u64);
+        }
+        released
+    }
+
+    /// Periodically releases reservations for orders stuck past the timeout
+    pub fn spawn_reservation_sweeper(
+        &self,
+        interval: std::time::Duration,
+        timeout: chrono::Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let portfolio = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                for reservation in portfolio.release_expired(timeout, Utc::now()).await {
+                    tracing::warn!(
+                        order_id = %reservation.order_id,
+                        amount = %reservation.amount,
+                        "Released reservation for order stuck past timeout"
+                    );
+                }
+            }
+        })
+    }
+
+    /// Reconciles an observed on-chain balance, recording changes not explained by trades
+    /// as deposits or withdrawals once confirmed by a matching token transaction
+    #[tracing::instrument(skip(self, on_chain_changes))]
+    pub async fn reconcile_balance(
+        &self,
+        observed_balance: Decimal,
+        trade_delta: Decimal,
+        on_chain_changes: &[TokenBalanceChange],
+    ) -> Result<Option<Transfer>, PortfolioError> {
+        let previous_balance = self.quote_balance(QuoteAsset::Usdc).await;
+
+        let Some((direction, amount)) =
+            classify_balance_change(previous_balance, observed_balance, trade_delta)
+        else {
+            self.update_balance(observed_balance).await?;
+            return Ok(None);
+        };
+
+        // Only treat the change as a flow when a matching token transaction exists
+        let signed_amount = observed_balance - previous_balance - trade_delta;
+        let Some(change) = confirm_transfer(on_chain_changes, signed_amount) else {
+            UNCONFIRMED_BALANCE_CHANGES.increment(1);
+            tracing::warn!(
+                "Unexplained balance change of {} has no matching token transaction",
+                signed_amount
+            );
+            self.update_balance(observed_balance).await?;
+            return Ok(None);
+        };
+
+        let already_recorded = self
+            .transfers
+            .read()
+            .await
+            .iter()
+            .any(|transfer| transfer.signature.as_deref() == Some(change.signature.as_str()));
+        if already_recorded {
+            self.update_balance(observed_balance).await?;
+            return Ok(None);
+        }
+
+        let transfer = Transfer::new(
+            self.wallet_address.clone(),
+            direction,
+            amount,
+            Some(change.signature.clone()),
+        )
+        .map_err(|e| PortfolioError::ValidationError(e.to_string()))?;
+
+        // Close the performance sub-period at the pre-flow valuation
+        let value_before = self.value_cache.read().await.1 + trade_delta;
+        self.flow_tracker
+            .write()
+            .await
+            .record_flow(value_before, transfer.signed_amount());
+        self.value_cache.write().await.1 = value_before + transfer.signed_amount();
+
+        self.update_balance(observed_balance).await?;
+        self.transfers.write().await.push(transfer.clone());
+
+        TRANSFERS.increment(transfer.direction.as_str(), 1);
+
+        Ok(Some(transfer))
+    }
+
+    /// Records a withdrawal the bot submitted itself, debiting USDC and closing the
+    /// performance sub-period at the pre-flow valuation. Reconciliation later finds the
+    /// balance already debited, so the flow is not counted twice.
+    #[tracing::instrument(skip(self, transfer))]
+    pub async fn record_withdrawal(&self, transfer: Transfer) -> Result<(), PortfolioError> {
+        if transfer.direction != TransferDirection::Withdrawal {
+            return Err(PortfolioError::ValidationError("transfer is not a withdrawal".to_string()));
+        }
+        let balance = self.quote_balance(QuoteAsset::Usdc).await;
+        if transfer.amount > balance {
+            return Err(PortfolioError::BalanceError(format!(
+                "withdrawal of {} exceeds balance {}",
+                transfer.amount, balance
+            )));
+        }
+
+        let value_before = self.value_cache.read().await.1;
+        self.flow_tracker
+            .write()
+            .await
+            .record_flow(value_before, transfer.signed_amount());
+        self.value_cache.write().await.1 = value_before + transfer.signed_amount();
+
+        self.update_balance(balance - transfer.amount).await?;
+        TRANSFERS.increment(transfer.direction.as_str(), 1);
+        self.transfers.write().await.push(transfer);
+        Ok(())
+    }
+
+    /// Returns the flow-adjusted high-water mark in portfolio value terms
+    pub async fn get_high_water_mark(&self) -> Decimal {
+        self.flow_tracker.read().await.high_water_mark()
+    }
+
+    /// Returns raw and flow-adjusted returns
+    pub async fn flow_adjusted_returns(&self) -> FlowAdjustedReturns {
+        self.flow_tracker.read().await.returns()
+    }
+
+    /// Returns recorded deposits and withdrawals, oldest first
+    pub async fn get_transfers(&self) -> Vec<Transfer> {
+        self.transfers.read().await.clone()
+    }
+
+    pub fn wallet_address(&self) -> &str {
+        &self.wallet_address
+    }
+
     /// Closes an existing position
     #[tracing::instrument(skip(self))]
     pub async fn close_position(&self, trading_pair: &str) -> Result<(), PortfolioError> {
@@ -226,29 +873,121 @@ impl Portfolio {
         self.value_cache.write().await.0 = Utc::now() - chrono::Duration::seconds(CACHE_EXPIRY_SECONDS + 1);
 
         // Record metrics
-        counter!(format!("{}.positions_closed", METRICS_PREFIX), 1);
+        POSITIONS_CLOSED.increment(1);
+
+        Ok(())
+    }
+
+    /// Overwrites a position with externally reconciled size and entry price, keeping its
+    /// realized P&L; a zero size removes the position
+    #[tracing::instrument(skip(self))]
+    pub async fn correct_position(
+        &self,
+        trading_pair: TradingPair,
+        size: Decimal,
+        entry_price: Decimal,
+    ) -> Result<(), PortfolioError> {
+        if !size.is_zero() && entry_price <= Decimal::ZERO {
+            return Err(PortfolioError::ValidationError("entry price must be positive".to_string()));
+        }
+
+        let mut positions = self.positions.write().await;
+        if size.is_zero() {
+            positions.remove(trading_pair.as_str());
+        } else {
+            let realized_pnl = positions
+                .get(trading_pair.as_str())
+                .map(|position| position.realized_pnl)
+                .unwrap_or_default();
+            positions.insert(
+                trading_pair.clone(),
+                Position {
+                    trading_pair,
+                    size,
+                    entry_price,
+                    realized_pnl,
+                    last_updated: Utc::now(),
+                },
+            );
+        }
+
+        // Invalidate value cache
+        self.value_cache.write().await.0 = Utc::now() - chrono::Duration::seconds(CACHE_EXPIRY_SECONDS + 1);
+
+        POSITIONS_UPDATED.increment(1);
+
+        Ok(())
+    }
+
+    /// Copies balances and positions, holding each lock only long enough to clone it
+    pub async fn snapshot(&self) -> PortfolioSnapshot {
+        let balances = self.balances.read().await.clone();
+        let mut positions: Vec<Position> = self.positions.read().await.values().cloned().collect();
+        positions.sort_by(|a, b| a.trading_pair.cmp(&b.trading_pair));
+
+        PortfolioSnapshot {
+            wallet_address: self.wallet_address.clone(),
+            balances,
+            positions,
+            realized_pnl: *self.realized_pnl.read().await,
+        }
+    }
 
+    /// Replaces balances and positions with a snapshot of the same wallet
+    pub async fn restore(&self, snapshot: &PortfolioSnapshot) -> Result<(), PortfolioError> {
+        if snapshot.wallet_address != self.wallet_address {
+            return Err(PortfolioError::ValidationError(format!(
+                "snapshot belongs to wallet {}",
+                snapshot.wallet_address
+            )));
+        }
+
+        *self.balances.write().await = snapshot.balances.clone();
+        *self.positions.write().await = snapshot
+            .positions
+            .iter()
+            .map(|position| (position.trading_pair.clone(), position.clone()))
+            .collect();
+        *self.realized_pnl.write().await = snapshot.realized_pnl;
+        self.value_cache.write().await.0 = Utc::now() - chrono::Duration::seconds(CACHE_EXPIRY_SECONDS + 1);
         Ok(())
     }
 
     /// Returns current portfolio metrics
     pub async fn get_metrics(&self) -> Result<PortfolioMetrics, PortfolioError> {
         let positions = self.positions.read().await;
-        let usdc_balance = *self.usdc_balance.read().await;
+        let quote_balances = self.balances.read().await.clone();
+        let usdc_balance = quote_balances.get(&QuoteAsset::Usdc).copied().unwrap_or(Decimal::ZERO);
+        let reserved = reserved_total(&*self.reservations.read().await, QuoteAsset::Usdc);
 
         Ok(PortfolioMetrics {
             total_positions: positions.len(),
             usdc_balance,
+            available_usdc_balance: usdc_balance - reserved,
+            quote_balances,
             last_updated: self.last_updated,
         })
     }
 }
 
+fn reserved_total(reservations: &HashMap<Uuid, Reservation>, asset: QuoteAsset) -> Decimal {
+    reservations
+        .values()
+        .filter(|reservation| reservation.asset == asset)
+        .map(|reservation| reservation.amount)
+        .sum()
+}
+
 /// Portfolio performance metrics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PortfolioMetrics {
     pub total_positions: usize,
     pub usdc_balance: Decimal,
+    /// USDC balance less funds reserved for in-flight orders
+    #[serde(default)]
+    pub available_usdc_balance: Decimal,
+    #[serde(default)]
+    pub quote_balances: HashMap<QuoteAsset, Decimal>,
     pub last_updated: DateTime<Utc>,
 }
 
@@ -274,13 +1013,46 @@ mod tests {
         ).unwrap();
 
         let result = portfolio.add_position(
-            "SOL/USDC".to_string(),
+            "SOL/USDC".parse().unwrap(),
             dec!(1.5),
             dec!(100.00),
         ).await;
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_position_size_limit_in_percent() {
+        let portfolio = Portfolio::new("wallet123".to_string(), dec!(1000.00)).unwrap();
+
+        // 30% of the portfolio breaches the 20% cap, 15% does not
+        let result = portfolio.add_position("SOL/USDC".parse().unwrap(), dec!(3), dec!(100)).await;
+        assert!(matches!(result, Err(PortfolioError::ValidationError(_))));
+        assert!(portfolio.add_position("SOL/USDC".parse().unwrap(), dec!(1.5), dec!(100)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unrealized_pnl_in_reporting_currency() {
+        let portfolio = Portfolio::new("wallet123".to_string(), dec!(1000.00)).unwrap();
+        for (trading_pair, size, entry_price) in [("SOL/USDC", dec!(2), dec!(100)), ("BONK/SOL", dec!(-1000), dec!(0.001))] {
+            let trading_pair = TradingPair::parse(trading_pair).unwrap();
+            portfolio.positions.write().await.insert(trading_pair.clone(), Position {
+                trading_pair,
+                size,
+                entry_price,
+                realized_pnl: Decimal::ZERO,
+                last_updated: Utc::now(),
+            });
+        }
+
+        let prices = HashMap::from([
+            ("SOL/USDC".to_string(), dec!(95)),
+            ("BONK/SOL".to_string(), dec!(0.0012)),
+        ]);
+        // Long SOL loses 10 USDC; short BONK loses 0.2 SOL at 95
+        assert_eq!(portfolio.unrealized_pnl(&prices).await.unwrap(), dec!(-29));
+        assert!(portfolio.unrealized_pnl(&HashMap::new()).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_portfolio_value_calculation() {
         let portfolio = Portfolio::new(
@@ -294,4 +1066,257 @@ mod tests {
         let value = portfolio.calculate_portfolio_value(&prices).await;
         assert!(value.is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_net_fill_adds_with_weighted_entry() {
+        let first = net_fill(Decimal::ZERO, Decimal::ZERO, dec!(1), dec!(100)).unwrap();
+        let second = net_fill(first.size, first.entry_price, dec!(1), dec!(120)).unwrap();
+
+        assert_eq!(second.size, dec!(2));
+        assert_eq!(second.entry_price, dec!(110));
+        assert_eq!(second.realized_pnl, Decimal::ZERO);
+
+        let third = net_fill(second.size, second.entry_price, dec!(2), dec!(101)).unwrap();
+        assert_eq!(third.size, dec!(4));
+        assert_eq!(third.entry_price, dec!(105.5));
+    }
+
+    #[test]
+    fn test_net_fill_partial_reduce() {
+        let fill = net_fill(dec!(2), dec!(110), dec!(-0.5), dec!(130)).unwrap();
+
+        assert_eq!(fill.size, dec!(1.5));
+        assert_eq!(fill.entry_price, dec!(110));
+        assert_eq!(fill.closed_size, dec!(0.5));
+        assert_eq!(fill.realized_pnl, dec!(10));
+    }
+
+    #[test]
+    fn test_net_fill_full_close() {
+        let fill = net_fill(dec!(1.5), dec!(110), dec!(-1.5), dec!(100)).unwrap();
+
+        assert!(fill.is_flat());
+        assert_eq!(fill.closed_size, dec!(1.5));
+        assert_eq!(fill.realized_pnl, dec!(-15));
+    }
+
+    #[test]
+    fn test_net_fill_flip_direction() {
+        let fill = net_fill(dec!(1), dec!(100), dec!(-3), dec!(90)).unwrap();
+
+        assert_eq!(fill.size, dec!(-2));
+        assert_eq!(fill.entry_price, dec!(90));
+        assert_eq!(fill.closed_size, dec!(1));
+        assert_eq!(fill.realized_pnl, dec!(-10));
+
+        // Short profits when covered lower
+        let cover = net_fill(fill.size, fill.entry_price, dec!(2), dec!(85)).unwrap();
+        assert!(cover.is_flat());
+        assert_eq!(cover.realized_pnl, dec!(10));
+    }
+
+    #[tokio::test]
+    async fn test_add_position_keeps_cost_basis() {
+        let portfolio = Portfolio::new(
+            "wallet123".to_string(),
+            dec!(1000000.00),
+        ).unwrap();
+
+        portfolio.add_position("SOL/USDC".parse().unwrap(), dec!(2), dec!(110)).await.unwrap();
+        let close = portfolio
+            .add_position("SOL/USDC".parse().unwrap(), dec!(-0.5), dec!(130))
+            .await
+            .unwrap()
+            .expect("reduce should record a partial close");
+
+        assert_eq!(close.closed_size, dec!(0.5));
+        assert_eq!(close.realized_pnl, dec!(10));
+        assert_eq!(close.remaining_size, dec!(1.5));
+
+        let position = portfolio.get_position("SOL/USDC").await.unwrap();
+        assert_eq!(position.size, dec!(1.5));
+        assert_eq!(position.entry_price, dec!(110));
+        assert_eq!(position.realized_pnl, dec!(10));
+        assert_eq!(portfolio.get_realized_pnl().await, dec!(10));
+    }
+
+    #[tokio::test]
+    async fn test_apply_fill_reports_opened_positions() {
+        let portfolio = Portfolio::new(
+            "wallet123".to_string(),
+            dec!(1000000.00),
+        ).unwrap();
+        let pair: TradingPair = "SOL/USDC".parse().unwrap();
+
+        let outcome = portfolio.apply_fill(pair.clone(), dec!(2), dec!(100)).await.unwrap();
+        assert_eq!(outcome.opened.unwrap().size, dec!(2));
+        assert!(outcome.close.is_none());
+
+        // Adding to the position opens nothing new
+        let outcome = portfolio.apply_fill(pair.clone(), dec!(1), dec!(100)).await.unwrap();
+        assert!(outcome.opened.is_none());
+
+        // Flipping short closes the long and opens the short
+        let outcome = portfolio.apply_fill(pair, dec!(-4), dec!(110)).await.unwrap();
+        assert_eq!(outcome.opened.unwrap().size, dec!(-1));
+        assert_eq!(outcome.close.unwrap().closed_size, dec!(3));
+    }
+
+    #[tokio::test]
+    async fn test_adding_to_position_after_cache_invalidated() {
+        let portfolio = Portfolio::new(
+            "wallet123".to_string(),
+            dec!(1000000.00),
+        ).unwrap();
+        let pair: TradingPair = "SOL/USDC".parse().unwrap();
+
+        // Each fill expires the value cache, so the second is sized without market prices
+        portfolio.apply_fill(pair.clone(), dec!(1), dec!(100)).await.unwrap();
+        portfolio.apply_fill(pair.clone(), dec!(1), dec!(120)).await.unwrap();
+
+        let position = portfolio.get_position(pair.as_str()).await.unwrap();
+        assert_eq!(position.size, dec!(2));
+        assert_eq!(position.entry_price, dec!(110));
+    }
+
+    #[tokio::test]
+    async fn test_sol_quoted_position_valued_in_usdc() {
+        let portfolio = Portfolio::new(
+            "wallet123".to_string(),
+            dec!(100000.00),
+        ).unwrap();
+        portfolio.update_quote_balance(QuoteAsset::Sol, dec!(10)).await.unwrap();
+
+        let mut prices = HashMap::from([(SOL_USDC_PAIR.to_string(), dec!(100))]);
+        // Sizing a SOL-quoted position needs a recorded conversion rate
+        assert!(portfolio
+            .add_position("BONK/SOL".parse().unwrap(), dec!(50000), dec!(0.00002))
+            .await
+            .is_err());
+        portfolio.valuation(&prices).await.unwrap();
+        portfolio.add_position("BONK/SOL".parse().unwrap(), dec!(50000), dec!(0.00002)).await.unwrap();
+
+        prices.insert("BONK/SOL".to_string(), dec!(0.00002));
+        let snapshot = portfolio.valuation(&prices).await.unwrap();
+        assert_eq!(snapshot.quote_values[&QuoteAsset::Sol], dec!(11));
+        assert_eq!(snapshot.sol_usdc_rate, Some(dec!(100)));
+        assert_eq!(snapshot.total_value, dec!(101100));
+
+        // The same SOL holdings are worth more once SOL/USDC rallies
+        prices.insert(SOL_USDC_PAIR.to_string(), dec!(150));
+        let snapshot = portfolio.valuation(&prices).await.unwrap();
+        assert_eq!(snapshot.sol_usdc_rate, Some(dec!(150)));
+        assert_eq!(snapshot.total_value, dec!(101650));
+        assert_eq!(portfolio.last_valuation().await.unwrap().total_value, dec!(101650));
+
+        prices.remove(SOL_USDC_PAIR);
+        assert!(portfolio.valuation(&prices).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_confirmed_deposit_is_flow_adjusted() {
+        let portfolio = Portfolio::new(
+            "wallet123".to_string(),
+            dec!(10000.00),
+        ).unwrap();
+
+        let changes = vec![TokenBalanceChange {
+            signature: "deposit_sig".to_string(),
+            delta: dec!(5000.00),
+            block_time: None,
+        }];
+
+        let transfer = portfolio
+            .reconcile_balance(dec!(15000.00), Decimal::ZERO, &changes)
+            .await
+            .unwrap()
+            .expect("deposit should be recorded");
+        assert_eq!(transfer.direction, crate::models::transfer::TransferDirection::Deposit);
+        assert_eq!(transfer.amount, dec!(5000.00));
+
+        // Re-reconciling the same balance must not record the deposit twice
+        let repeat = portfolio
+            .reconcile_balance(dec!(15000.00), Decimal::ZERO, &changes)
+            .await
+            .unwrap();
+        assert!(repeat.is_none());
+        assert_eq!(portfolio.get_transfers().await.len(), 1);
+
+        let returns = portfolio.flow_adjusted_returns().await;
+        assert_eq!(returns.time_weighted_return_pct, Decimal::ZERO);
+        assert_eq!(returns.drawdown_pct, Decimal::ZERO);
+        assert_eq!(returns.raw_return_pct, dec!(50));
+        assert_eq!(portfolio.get_high_water_mark().await, dec!(15000.00));
+    }
+
+    #[tokio::test]
+    async fn test_unconfirmed_change_not_recorded_as_transfer() {
+        let portfolio = Portfolio::new(
+            "wallet123".to_string(),
+            dec!(10000.00),
+        ).unwrap();
+
+        let transfer = portfolio
+            .reconcile_balance(dec!(12000.00), Decimal::ZERO, &[])
+            .await
+            .unwrap();
+        assert!(transfer.is_none());
+        assert!(portfolio.get_transfers().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_reservations_never_overspend() {
+        let portfolio = Portfolio::new("wallet123".to_string(), dec!(1000.00)).unwrap();
+
+        // Ten simultaneous 150 USDC orders against 1000 USDC; only six are affordable
+        let attempts = (0..10).map(|_| {
+            let portfolio = portfolio.clone();
+            tokio::spawn(async move {
+                let order_id = Uuid::new_v4();
+                portfolio.reserve(dec!(150), order_id).await.map(|_| order_id)
+            })
+        });
+        let results: Vec<_> = futures::future::join_all(attempts)
+            .await
+            .into_iter()
+            .map(|joined| joined.unwrap())
+            .collect();
+        let reserved: Vec<Uuid> = results.iter().filter_map(|result| result.as_ref().ok().copied()).collect();
+        assert_eq!(reserved.len(), 6);
+        assert!(results
+            .iter()
+            .filter_map(|result| result.as_ref().err())
+            .all(|e| matches!(e, PortfolioError::BalanceError(_))));
+        assert_eq!(portfolio.available_balance(QuoteAsset::Usdc).await, dec!(100));
+        assert_eq!(portfolio.quote_balance(QuoteAsset::Usdc).await, dec!(1000));
+
+        // Fills settle at their actual cost; a failed order hands its funds back
+        for order_id in &reserved[..5] {
+            portfolio.settle(*order_id, dec!(149.50)).await.unwrap();
+        }
+        assert!(portfolio.release(reserved[5]).await.is_some());
+        assert_eq!(portfolio.quote_balance(QuoteAsset::Usdc).await, dec!(252.50));
+        assert_eq!(portfolio.available_balance(QuoteAsset::Usdc).await, dec!(252.50));
+        assert!(portfolio.reservations().await.is_empty());
+        assert!(portfolio.settle(reserved[5], dec!(150)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stuck_reservations_are_swept() {
+        let portfolio = Portfolio::new("wallet123".to_string(), dec!(1000.00)).unwrap();
+        let stuck = Uuid::new_v4();
+        portfolio.reserve(dec!(600), stuck).await.unwrap();
+        assert!(portfolio.reserve(dec!(600), Uuid::new_v4()).await.is_err());
+
+        let now = Utc::now();
+        assert!(portfolio.release_expired(chrono::Duration::minutes(5), now).await.is_empty());
+        let released = portfolio
+            .release_expired(chrono::Duration::minutes(5), now + chrono::Duration::minutes(6))
+            .await;
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].order_id, stuck);
+
+        assert_eq!(portfolio.get_metrics().await.unwrap().available_usdc_balance, dec!(1000));
+        portfolio.reserve(dec!(600), Uuid::new_v4()).await.unwrap();
+    }
+}
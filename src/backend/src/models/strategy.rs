@@ -7,27 +7,37 @@
 //! - uuid = "1.4"
 //! - serde = "1.0"
 //! - tokio = "1.28"
+//! - utoipa = "3.5"
 
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::RoundingStrategy;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 use thiserror::Error;
 use tokio::sync::RwLock;
+use utoipa::openapi::{ObjectBuilder, RefOr, Schema, SchemaType};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::models::market::MarketData;
 use crate::models::trade::Trade;
+use crate::models::transfer::FlowAdjustedTracker;
+use crate::regime::MarketRegime;
+use crate::signals::Signal;
+use crate::strategies::{self, MarketMakingParams, StrategyContext, TradingStrategy};
+use crate::utils::percent::{Bps, Percent};
 
 // Strategy configuration constants
-const MIN_GRID_LEVELS: u32 = 5;
+pub(crate) const MIN_GRID_LEVELS: u32 = 5;
 const MAX_GRID_LEVELS: u32 = 100;
-const MIN_POSITION_SIZE_BPS: u32 = 100; // 1%
-const MAX_POSITION_SIZE_BPS: u32 = 5000; // 50%
+const MIN_POSITION_SIZE: Bps = Bps::new(100); // 1%
+const MAX_POSITION_SIZE: Bps = Bps::new(5000); // 50%
 const PERFORMANCE_HISTORY_DAYS: i64 = 30;
 const MIN_TRADE_INTERVAL_MS: u64 = 100;
-const RISK_FREE_RATE_BPS: u32 = 200; // 2%
+const RISK_FREE_RATE: Bps = Bps::new(200); // 2%
 
 /// Strategy-related error types
 #[derive(Error, Debug)]
@@ -40,17 +50,78 @@ pub enum StrategyError {
     PerformanceError(String),
 }
 
-/// Supported strategy types
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+/// Strategy types; serialized by name, so any name other than a built-in one refers to a
+/// strategy registered in `strategies::registry()`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum StrategyType {
     Grid,
     Arbitrage,
     MLBased,
+    MarketMaking,
+    Custom(String),
+}
+
+impl StrategyType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            StrategyType::Grid => "GRID",
+            StrategyType::Arbitrage => "ARBITRAGE",
+            StrategyType::MLBased => "M_L_BASED",
+            StrategyType::MarketMaking => "MARKET_MAKING",
+            StrategyType::Custom(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for StrategyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for StrategyType {
+    type Err = StrategyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "GRID" => Ok(StrategyType::Grid),
+            "ARBITRAGE" => Ok(StrategyType::Arbitrage),
+            "M_L_BASED" => Ok(StrategyType::MLBased),
+            "MARKET_MAKING" => Ok(StrategyType::MarketMaking),
+            "" => Err(StrategyError::ValidationError("strategy type must not be empty".to_string())),
+            name => Ok(StrategyType::Custom(name.to_string())),
+        }
+    }
+}
+
+impl Serialize for StrategyType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for StrategyType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl<'s> ToSchema<'s> for StrategyType {
+    fn schema() -> (&'s str, RefOr<Schema>) {
+        (
+            "StrategyType",
+            ObjectBuilder::new()
+                .schema_type(SchemaType::String)
+                .description(Some("GRID, ARBITRAGE, M_L_BASED, MARKET_MAKING or the name of a registered strategy"))
+                .example(Some(serde_json::json!("GRID")))
+                .into(),
+        )
+    }
 }
 
 /// Strategy lifecycle states
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum StrategyState {
     Inactive,
@@ -60,19 +131,32 @@ pub enum StrategyState {
 }
 
 /// Strategy configuration parameters
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct StrategyParams {
-    pub position_size_bps: u32,
+    pub position_size_bps: Bps,
     pub grid_levels: Option<u32>,
-    pub stop_loss_pct: Decimal,
-    pub take_profit_pct: Decimal,
-    pub max_slippage_bps: u32,
+    pub stop_loss_pct: Percent,
+    pub take_profit_pct: Percent,
+    pub max_slippage_bps: Bps,
     pub exchanges: Vec<String>,
     pub risk_factor: Decimal,
+    /// Market regimes the strategy trades in; empty allows every regime
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_regimes: Vec<MarketRegime>,
+    /// Quoting parameters, required by market making strategies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market_making: Option<MarketMakingParams>,
+}
+
+impl StrategyParams {
+    /// Checks the parameters against the same constraints applied at strategy creation
+    pub fn validate(&self) -> Result<(), StrategyError> {
+        validate_strategy_params(self, None).map(|_| ())
+    }
 }
 
 /// Performance metrics for strategy evaluation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct PerformanceMetrics {
     pub total_trades: u32,
     pub win_rate: Decimal,
@@ -81,6 +165,51 @@ pub struct PerformanceMetrics {
     pub max_drawdown: Decimal,
     pub avg_trade_duration: i64,
     pub roi: Decimal,
+    /// Strategy-specific metrics reported by the implementation, e.g. maker fill rate
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extensions: HashMap<String, Decimal>,
+}
+
+/// Audit record of an automatic pause or manual resume of a strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyAuditEntry {
+    pub id: Uuid,
+    pub strategy_id: String,
+    pub action: String,
+    pub detail: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StrategyAuditEntry {
+    pub fn new(strategy_id: &str, action: &str, detail: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            strategy_id: strategy_id.to_string(),
+            action: action.to_string(),
+            detail,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Strategy state and parameters as captured in a state snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StrategySnapshot {
+    /// Key the strategy is registered under
+    pub strategy_id: String,
+    pub id: Uuid,
+    pub strategy_type: StrategyType,
+    pub parameters: StrategyParams,
+    pub state: StrategyState,
+    pub trading_pairs: Vec<String>,
+    pub performance_score: Decimal,
+    pub metrics: PerformanceMetrics,
+    pub risk_metrics: HashMap<String, Decimal>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Set once the strategy is soft-deleted; its history is retained
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 /// Core strategy model with comprehensive lifecycle management
@@ -95,9 +224,28 @@ pub struct Strategy {
     pub metrics: PerformanceMetrics,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Soft deletion time; deleted strategies stay terminated and keep their history
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
     #[serde(skip)]
     trade_history: RwLock<Vec<Trade>>,
+    #[serde(skip)]
+    equity: RwLock<Option<FlowAdjustedTracker>>,
+    /// Strategy state, including the implementation's persisted state
     pub risk_metrics: HashMap<String, Decimal>,
+    /// Implementation resolved from the registry on first execution
+    #[serde(skip)]
+    implementation: Implementation,
+}
+
+/// Resolved implementation; a clone resolves its own from the persisted state
+#[derive(Debug, Default)]
+struct Implementation(Option<Box<dyn TradingStrategy>>);
+
+impl Clone for Implementation {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
 }
 
 impl Strategy {
@@ -107,8 +255,9 @@ impl Strategy {
         parameters: StrategyParams,
         trading_pairs: Vec<String>,
     ) -> Result<Self, StrategyError> {
-        // Validate strategy parameters
+        // Validate strategy parameters, including the implementation's own checks
         validate_strategy_params(&parameters, None)?;
+        strategies::registry().create(&strategy_type, &parameters, &HashMap::new())?;
 
         Ok(Self {
             id: Uuid::new_v4(),
@@ -125,16 +274,95 @@ impl Strategy {
                 max_drawdown: Decimal::ZERO,
                 avg_trade_duration: 0,
                 roi: Decimal::ZERO,
+                extensions: HashMap::new(),
             },
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            deleted_at: None,
             trade_history: RwLock::new(Vec::new()),
+            equity: RwLock::new(None),
             risk_metrics: HashMap::new(),
+            implementation: Implementation::default(),
         })
     }
 
+    /// Copies the strategy's state, parameters and grid position
+    pub fn snapshot(&self, strategy_id: &str) -> StrategySnapshot {
+        StrategySnapshot {
+            strategy_id: strategy_id.to_string(),
+            id: self.id,
+            strategy_type: self.strategy_type.clone(),
+            parameters: self.parameters.clone(),
+            state: self.state.clone(),
+            trading_pairs: self.trading_pairs.clone(),
+            performance_score: self.performance_score,
+            metrics: self.metrics.clone(),
+            risk_metrics: self.risk_metrics.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            deleted_at: self.deleted_at,
+        }
+    }
+
+    /// Rebuilds a strategy from a snapshot; trade history restarts empty
+    pub fn from_snapshot(snapshot: &StrategySnapshot) -> Self {
+        Self {
+            id: snapshot.id,
+            strategy_type: snapshot.strategy_type.clone(),
+            parameters: snapshot.parameters.clone(),
+            state: snapshot.state.clone(),
+            trading_pairs: snapshot.trading_pairs.clone(),
+            performance_score: snapshot.performance_score,
+            metrics: snapshot.metrics.clone(),
+            created_at: snapshot.created_at,
+            updated_at: snapshot.updated_at,
+            deleted_at: snapshot.deleted_at,
+            trade_history: RwLock::new(Vec::new()),
+            equity: RwLock::new(None),
+            risk_metrics: snapshot.risk_metrics.clone(),
+            implementation: Implementation::default(),
+        }
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// Records capital allocated to (positive) or withdrawn from (negative) the strategy
+    pub async fn record_capital_flow(&self, amount: Decimal) -> Result<(), StrategyError> {
+        let mut equity = self.equity.write().await;
+        match equity.as_mut() {
+            Some(tracker) => {
+                let value_before = tracker.last_value();
+                if value_before + amount < Decimal::ZERO {
+                    return Err(StrategyError::ValidationError(
+                        "withdrawal exceeds allocated capital".to_string(),
+                    ));
+                }
+                tracker.record_flow(value_before, amount);
+            }
+            None if amount > Decimal::ZERO => *equity = Some(FlowAdjustedTracker::new(amount)),
+            None => {
+                return Err(StrategyError::ValidationError(
+                    "initial capital allocation must be positive".to_string(),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Capital currently allocated to the strategy, if an allocation was ever recorded
+    pub async fn allocated_capital(&self) -> Option<Decimal> {
+        self.equity.read().await.as_ref().map(FlowAdjustedTracker::last_value)
+    }
+
     /// Updates strategy performance metrics with new trade data
     pub async fn update_performance(&mut self, new_trades: Vec<Trade>) -> Result<PerformanceMetrics, StrategyError> {
+        let new_pnl: Decimal = new_trades
+            .iter()
+            .map(|trade| trade.get_value().unwrap_or(Decimal::ZERO))
+            .sum();
+
         let mut trade_history = self.trade_history.write().await;
         trade_history.extend(new_trades);
 
@@ -143,11 +371,19 @@ impl Strategy {
         trade_history.retain(|trade| trade.executed_at > cutoff);
 
         // Calculate performance metrics
-        let metrics = calculate_risk_adjusted_returns(
+        let mut metrics = calculate_risk_adjusted_returns(
             &trade_history,
-            Decimal::new(RISK_FREE_RATE_BPS as i64, 4),
+            RISK_FREE_RATE.as_fraction(),
         )?;
 
+        // Use flow-adjusted returns when capital allocation is tracked
+        if let Some(tracker) = self.equity.write().await.as_mut() {
+            tracker.observe(tracker.last_value() + new_pnl);
+            metrics.roi = tracker.time_weighted_return_pct();
+            metrics.max_drawdown = tracker.max_drawdown_pct();
+        }
+        metrics.extensions = self.metrics.extensions.clone();
+
         self.metrics = metrics.clone();
         self.updated_at = Utc::now();
         self.performance_score = calculate_performance_score(&metrics)?;
@@ -155,29 +391,61 @@ impl Strategy {
         Ok(metrics)
     }
 
-    /// Executes strategy logic based on current market conditions
-    pub async fn execute(&mut self, market_data: &MarketData) -> Result<Vec<Trade>, StrategyError> {
+    /// Signals for current market conditions, judged against the context's time so
+    /// recorded data can be replayed
+    pub async fn execute(&mut self, ctx: &StrategyContext, market_data: &MarketData) -> Result<Vec<Signal>, StrategyError> {
         if self.state != StrategyState::Active {
             return Err(StrategyError::ExecutionError(
                 "strategy must be active to execute trades".to_string(),
             ));
         }
-
-        // Validate market data freshness
-        if !market_data.is_valid()? {
-            return Err(StrategyError::ExecutionError("stale market data".to_string()));
+        validate_freshness(market_data, ctx)?;
+
+        if self.implementation.0.is_none() {
+            self.implementation.0 = Some(strategies::registry().create(
+                &self.strategy_type,
+                &self.parameters,
+                &self.risk_metrics,
+            )?);
         }
-
-        let trades = match self.strategy_type {
-            StrategyType::Grid => execute_grid_strategy(self, market_data)?,
-            StrategyType::Arbitrage => execute_arbitrage_strategy(self, market_data)?,
-            StrategyType::MLBased => execute_ml_strategy(self, market_data)?,
+        let Some(implementation) = self.implementation.0.as_mut() else {
+            return Ok(Vec::new());
         };
+        let signals = implementation.on_market_data(ctx, market_data).await;
+        self.risk_metrics.extend(implementation.state());
+        self.metrics.extensions.extend(implementation.metrics());
+        Ok(signals)
+    }
 
-        Ok(trades)
+    /// Signals the strategy would emit on `market_data` without requiring it to be active
+    /// and without advancing its state
+    pub async fn preview(&self, ctx: &StrategyContext, market_data: &MarketData) -> Result<Vec<Signal>, StrategyError> {
+        validate_freshness(market_data, ctx)?;
+        let mut implementation =
+            strategies::registry().create(&self.strategy_type, &self.parameters, &self.risk_metrics)?;
+        Ok(implementation.on_market_data(ctx, market_data).await)
+    }
+
+    /// Replaces the parameters, rebuilding the implementation from the current state
+    pub fn set_parameters(&mut self, parameters: StrategyParams) -> Result<(), StrategyError> {
+        validate_strategy_params(&parameters, None)?;
+        let implementation = strategies::registry().create(&self.strategy_type, &parameters, &self.risk_metrics)?;
+        self.parameters = parameters;
+        self.implementation.0 = Some(implementation);
+        Ok(())
     }
 }
 
+fn validate_freshness(market_data: &MarketData, ctx: &StrategyContext) -> Result<(), StrategyError> {
+    if !market_data
+        .is_valid_at(ctx.now())
+        .map_err(|e| StrategyError::ExecutionError(e.to_string()))?
+    {
+        return Err(StrategyError::ExecutionError("stale market data".to_string()));
+    }
+    Ok(())
+}
+
 /// Validates strategy parameters against defined constraints
 #[tracing::instrument(skip(params, market_data))]
 fn validate_strategy_params(
@@ -185,10 +453,10 @@ fn validate_strategy_params(
     market_data: Option<&MarketData>,
 ) -> Result<ValidationReport, StrategyError> {
     // Validate position size
-    if params.position_size_bps < MIN_POSITION_SIZE_BPS || params.position_size_bps > MAX_POSITION_SIZE_BPS {
+    if params.position_size_bps < MIN_POSITION_SIZE || params.position_size_bps > MAX_POSITION_SIZE {
         return Err(StrategyError::ValidationError(format!(
-            "position size must be between {} and {} bps",
-            MIN_POSITION_SIZE_BPS, MAX_POSITION_SIZE_BPS
+            "position size must be between {} and {}",
+            MIN_POSITION_SIZE, MAX_POSITION_SIZE
         )));
     }
 
@@ -203,7 +471,7 @@ fn validate_strategy_params(
     }
 
     // Validate stop loss and take profit
-    if params.stop_loss_pct >= Decimal::ZERO || params.take_profit_pct <= Decimal::ZERO {
+    if params.stop_loss_pct >= Percent::ZERO || params.take_profit_pct <= Percent::ZERO {
         return Err(StrategyError::ValidationError(
             "invalid stop loss or take profit levels".to_string(),
         ));
@@ -229,6 +497,7 @@ fn calculate_risk_adjusted_returns(
             max_drawdown: Decimal::ZERO,
             avg_trade_duration: 0,
             roi: Decimal::ZERO,
+            extensions: HashMap::new(),
         });
     }
 
@@ -254,6 +523,7 @@ fn calculate_risk_adjusted_returns(
         max_drawdown: calculate_max_drawdown(&returns)?,
         avg_trade_duration: calculate_avg_duration(trades),
         roi,
+        extensions: HashMap::new(),
     })
 }
 
@@ -273,22 +543,6 @@ struct ValidationReport {
     warnings: Vec<String>,
 }
 
-// Strategy-specific execution functions
-fn execute_grid_strategy(strategy: &Strategy, market_data: &MarketData) -> Result<Vec<Trade>, StrategyError> {
-    // Grid strategy implementation
-    todo!("Implement grid strategy execution")
-}
-
-fn execute_arbitrage_strategy(strategy: &Strategy, market_data: &MarketData) -> Result<Vec<Trade>, StrategyError> {
-    // Arbitrage strategy implementation
-    todo!("Implement arbitrage strategy execution")
-}
-
-fn execute_ml_strategy(strategy: &Strategy, market_data: &MarketData) -> Result<Vec<Trade>, StrategyError> {
-    // ML-based strategy implementation
-    todo!("Implement ML strategy execution")
-}
-
 // Helper functions for performance calculations
 fn calculate_volatility(returns: &[Decimal]) -> Result<Decimal, StrategyError> {
     // Volatility calculation implementation
@@ -32,6 +32,11 @@ const DEX_FEE_RATES: &[(&str, Decimal)] = &[
     ("drift", Decimal::new(2, 4)),      // 0.0002
 ];
 
+// Rebates paid to resting maker orders (in decimal form)
+const DEX_MAKER_REBATE_RATES: &[(&str, Decimal)] = &[
+    ("drift", Decimal::new(2, 5)),      // 0.00002
+];
+
 /// Trade-related error types
 #[derive(Error, Debug)]
 pub enum TradeError {
@@ -180,7 +185,7 @@ fn calculate_slippage(expected_price: Decimal, executed_price: Decimal) -> Resul
 
 /// Calculates the trade fee based on exchange and trade details
 #[inline]
-fn calculate_fee(exchange: &str, size: Decimal, price: Decimal) -> Result<Decimal, TradeError> {
+pub fn calculate_fee(exchange: &str, size: Decimal, price: Decimal) -> Result<Decimal, TradeError> {
     let fee_rate = DEX_FEE_RATES
         .iter()
         .find(|(dex, _)| *dex == exchange.to_lowercase())
@@ -196,6 +201,46 @@ fn calculate_fee(exchange: &str, size: Decimal, price: Decimal) -> Result<Decima
         .map(|fee| fee.round_dp_with_strategy(6, RoundingStrategy::MidpointAwayFromZero))
 }
 
+/// Maker rebate rate on exchanges that pay one
+#[inline]
+pub fn maker_rebate_rate(exchange: &str) -> Option<Decimal> {
+    DEX_MAKER_REBATE_RATES
+        .iter()
+        .find(|(dex, _)| *dex == exchange.to_lowercase())
+        .map(|(_, rate)| *rate)
+}
+
+/// Fees paid crossing the spread and rebates earned resting, in the quote currency
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeBreakdown {
+    pub taker_fees: Decimal,
+    pub maker_rebates: Decimal,
+}
+
+impl FeeBreakdown {
+    /// Fees net of rebates; negative when rebates exceed fees
+    pub fn net(&self) -> Decimal {
+        self.taker_fees - self.maker_rebates
+    }
+
+    /// Adds the fee for a fill that crossed the spread
+    pub fn add_taker(&mut self, exchange: &str, size: Decimal, price: Decimal) -> Result<(), TradeError> {
+        self.taker_fees += calculate_fee(exchange, size, price)?;
+        Ok(())
+    }
+
+    /// Adds the rebate for a fill that rested on the book
+    pub fn add_maker(&mut self, exchange: &str, size: Decimal, price: Decimal) -> Result<(), TradeError> {
+        let rate = maker_rebate_rate(exchange)
+            .ok_or_else(|| TradeError::FeeError(format!("no maker rebate on {}", exchange)))?;
+        let rebate = calculate_trade_value(size, price)?
+            .checked_mul(rate)
+            .ok_or_else(|| TradeError::CalculationError("rebate calculation overflow".to_string()))?;
+        self.maker_rebates += rebate.round_dp_with_strategy(6, RoundingStrategy::MidpointAwayFromZero);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -236,4 +281,17 @@ mod tests {
         assert!(value.is_ok());
         assert_eq!(value.unwrap(), dec!(250.000000));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_fee_breakdown_nets_maker_rebates() {
+        let mut fees = FeeBreakdown::default();
+        fees.add_maker("drift", dec!(10), dec!(100)).unwrap();
+        fees.add_taker("drift", dec!(5), dec!(100)).unwrap();
+        assert_eq!(fees.maker_rebates, dec!(0.02));
+        assert_eq!(fees.taker_fees, dec!(0.1));
+        assert_eq!(fees.net(), dec!(0.08));
+
+        // Venues without rebates can't book maker fills
+        assert!(fees.add_maker("jupiter", dec!(1), dec!(100)).is_err());
+    }
+}
@@ -0,0 +1,290 @@
+//! Deposit and withdrawal tracking with flow-adjusted performance so that external
+//! capital movements are not mistaken for trading profit or loss.
+//!
+//! Version dependencies:
+//! - rust_decimal = "1.30"
+//! - chrono = "0.4"
+//! - uuid = "1.4"
+//! - serde = "1.0"
+//! - utoipa = "3.5"
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::utils::solana::TokenBalanceChange;
+
+// Transfer detection constants
+const TRANSFER_TOLERANCE: Decimal = Decimal::new(1, 2); // 0.01 USDC dust tolerance
+const PERCENT: Decimal = Decimal::ONE_HUNDRED;
+
+/// Transfer-related error types
+#[derive(Error, Debug)]
+pub enum TransferError {
+    #[error("validation error: {0}")]
+    ValidationError(String),
+    #[error("unknown transfer direction: {0}")]
+    UnknownDirection(String),
+}
+
+/// Direction of an external capital movement
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransferDirection {
+    Deposit,
+    Withdrawal,
+}
+
+impl TransferDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Deposit => "DEPOSIT",
+            Self::Withdrawal => "WITHDRAWAL",
+        }
+    }
+}
+
+impl std::str::FromStr for TransferDirection {
+    type Err = TransferError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "DEPOSIT" => Ok(Self::Deposit),
+            "WITHDRAWAL" => Ok(Self::Withdrawal),
+            other => Err(TransferError::UnknownDirection(other.to_string())),
+        }
+    }
+}
+
+/// Deposit or withdrawal detected on the trading wallet
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Transfer {
+    pub id: Uuid,
+    pub wallet_address: String,
+    pub direction: TransferDirection,
+    pub amount: Decimal,
+    pub signature: Option<String>,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl Transfer {
+    /// Creates a transfer with a positive amount
+    pub fn new(
+        wallet_address: String,
+        direction: TransferDirection,
+        amount: Decimal,
+        signature: Option<String>,
+    ) -> Result<Self, TransferError> {
+        if amount <= Decimal::ZERO {
+            return Err(TransferError::ValidationError(
+                "transfer amount must be positive".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            wallet_address,
+            direction,
+            amount,
+            signature,
+            detected_at: Utc::now(),
+        })
+    }
+
+    /// Returns the amount signed by direction: positive for deposits, negative for withdrawals
+    pub fn signed_amount(&self) -> Decimal {
+        match self.direction {
+            TransferDirection::Deposit => self.amount,
+            TransferDirection::Withdrawal => -self.amount,
+        }
+    }
+}
+
+/// Classifies the part of a balance change not explained by recorded trades
+pub fn classify_balance_change(
+    previous_balance: Decimal,
+    observed_balance: Decimal,
+    trade_delta: Decimal,
+) -> Option<(TransferDirection, Decimal)> {
+    let unexplained = observed_balance - previous_balance - trade_delta;
+    if unexplained.abs() <= TRANSFER_TOLERANCE {
+        return None;
+    }
+
+    let direction = if unexplained > Decimal::ZERO {
+        TransferDirection::Deposit
+    } else {
+        TransferDirection::Withdrawal
+    };
+    Some((direction, unexplained.abs()))
+}
+
+/// Finds the on-chain token balance change matching an unexplained balance delta
+pub fn confirm_transfer<'a>(
+    changes: &'a [TokenBalanceChange],
+    signed_amount: Decimal,
+) -> Option<&'a TokenBalanceChange> {
+    changes
+        .iter()
+        .find(|change| (change.delta - signed_amount).abs() <= TRANSFER_TOLERANCE)
+}
+
+/// Raw and flow-adjusted return figures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowAdjustedReturns {
+    pub raw_return_pct: Decimal,
+    pub time_weighted_return_pct: Decimal,
+    pub drawdown_pct: Decimal,
+    pub max_drawdown_pct: Decimal,
+    pub net_flows: Decimal,
+}
+
+/// Time-weighted return tracker that closes a sub-period at every external flow
+#[derive(Debug, Clone)]
+pub struct FlowAdjustedTracker {
+    initial_value: Decimal,
+    last_value: Decimal,
+    net_flows: Decimal,
+    twr_index: Decimal,
+    peak_index: Decimal,
+    max_drawdown_pct: Decimal,
+}
+
+impl FlowAdjustedTracker {
+    pub fn new(initial_value: Decimal) -> Self {
+        Self {
+            initial_value,
+            last_value: initial_value,
+            net_flows: Decimal::ZERO,
+            twr_index: Decimal::ONE,
+            peak_index: Decimal::ONE,
+            max_drawdown_pct: Decimal::ZERO,
+        }
+    }
+
+    /// Records a valuation, chaining the sub-period return into the index
+    pub fn observe(&mut self, value: Decimal) {
+        if self.last_value > Decimal::ZERO {
+            self.twr_index = self.twr_index * value / self.last_value;
+        }
+        self.last_value = value;
+
+        if self.twr_index > self.peak_index {
+            self.peak_index = self.twr_index;
+        }
+        self.max_drawdown_pct = self.max_drawdown_pct.max(self.drawdown_pct());
+    }
+
+    /// Records an external flow; `value_before` is the valuation just before the flow settled
+    pub fn record_flow(&mut self, value_before: Decimal, signed_amount: Decimal) {
+        self.observe(value_before);
+        self.last_value = value_before + signed_amount;
+        self.net_flows += signed_amount;
+    }
+
+    pub fn last_value(&self) -> Decimal {
+        self.last_value
+    }
+
+    /// Return on contributed capital, including the effect of flows
+    pub fn raw_return_pct(&self) -> Decimal {
+        if self.initial_value.is_zero() {
+            return Decimal::ZERO;
+        }
+        (self.last_value - self.initial_value) * PERCENT / self.initial_value
+    }
+
+    /// Time-weighted return, independent of flow size and timing
+    pub fn time_weighted_return_pct(&self) -> Decimal {
+        (self.twr_index - Decimal::ONE) * PERCENT
+    }
+
+    /// Current drawdown from the flow-adjusted peak
+    pub fn drawdown_pct(&self) -> Decimal {
+        if self.peak_index.is_zero() {
+            return Decimal::ZERO;
+        }
+        (self.peak_index - self.twr_index) * PERCENT / self.peak_index
+    }
+
+    pub fn max_drawdown_pct(&self) -> Decimal {
+        self.max_drawdown_pct
+    }
+
+    /// Portfolio value that would restore the flow-adjusted peak
+    pub fn high_water_mark(&self) -> Decimal {
+        if self.twr_index.is_zero() {
+            return self.last_value;
+        }
+        self.last_value * self.peak_index / self.twr_index
+    }
+
+    pub fn returns(&self) -> FlowAdjustedReturns {
+        FlowAdjustedReturns {
+            raw_return_pct: self.raw_return_pct(),
+            time_weighted_return_pct: self.time_weighted_return_pct(),
+            drawdown_pct: self.drawdown_pct(),
+            max_drawdown_pct: self.max_drawdown_pct,
+            net_flows: self.net_flows,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_mid_period_deposit_does_not_affect_returns() {
+        let mut tracker = FlowAdjustedTracker::new(dec!(10000));
+
+        // +10%, then a 5k deposit, then -5%
+        tracker.observe(dec!(11000));
+        tracker.record_flow(dec!(11000), dec!(5000));
+        tracker.observe(dec!(15200));
+
+        assert_eq!(tracker.time_weighted_return_pct(), dec!(4.5));
+        assert_eq!(tracker.drawdown_pct(), dec!(5));
+        assert_eq!(tracker.raw_return_pct(), dec!(52));
+        assert_eq!(tracker.returns().net_flows, dec!(5000));
+    }
+
+    #[test]
+    fn test_deposit_alone_is_not_a_return() {
+        let mut tracker = FlowAdjustedTracker::new(dec!(10000));
+        tracker.record_flow(dec!(10000), dec!(5000));
+        tracker.observe(dec!(15000));
+
+        assert_eq!(tracker.time_weighted_return_pct(), Decimal::ZERO);
+        assert_eq!(tracker.drawdown_pct(), Decimal::ZERO);
+        assert_eq!(tracker.high_water_mark(), dec!(15000));
+    }
+
+    #[test]
+    fn test_withdrawal_does_not_register_as_drawdown() {
+        let mut tracker = FlowAdjustedTracker::new(dec!(10000));
+        tracker.record_flow(dec!(10000), dec!(-4000));
+        tracker.observe(dec!(6000));
+
+        assert_eq!(tracker.drawdown_pct(), Decimal::ZERO);
+        assert_eq!(tracker.max_drawdown_pct(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_classify_balance_change() {
+        // Trades explain +200, the remaining +5000 is a deposit
+        assert_eq!(
+            classify_balance_change(dec!(1000), dec!(6200), dec!(200)),
+            Some((TransferDirection::Deposit, dec!(5000)))
+        );
+        assert_eq!(
+            classify_balance_change(dec!(1000), dec!(700), dec!(0)),
+            Some((TransferDirection::Withdrawal, dec!(300)))
+        );
+        assert_eq!(classify_balance_change(dec!(1000), dec!(1200), dec!(200)), None);
+    }
+}
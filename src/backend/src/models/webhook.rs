@@ -0,0 +1,369 @@
+//! Outbound webhook endpoints and the order, position and summary events delivered to them.
+//!
+//! Version dependencies:
+//! - rust_decimal = "1.30"
+//! - chrono = "0.4"
+//! - uuid = "1.4"
+//! - serde = "1.0"
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use url::Url;
+use uuid::Uuid;
+
+use crate::models::portfolio::{Position, PositionClose};
+use crate::utils::crypto::REDACTED;
+
+// Webhook validation constants
+const MIN_SECRET_LENGTH: usize = 16;
+const MAX_EVENT_TYPES: usize = 16;
+
+/// Webhook-related error types
+#[derive(Error, Debug)]
+pub enum WebhookError {
+    #[error("validation error: {0}")]
+    ValidationError(String),
+    #[error("unknown event type: {0}")]
+    UnknownEventType(String),
+    #[error("webhook not found: {0}")]
+    NotFound(Uuid),
+    #[error("serialization error: {0}")]
+    SerializationError(String),
+    #[error("delivery error: {0}")]
+    DeliveryError(String),
+    #[error("storage error: {0}")]
+    StorageError(String),
+}
+
+/// Lifecycle events that can be delivered to a webhook
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum WebhookEventType {
+    #[serde(rename = "order.accepted")]
+    OrderAccepted,
+    #[serde(rename = "order.executed")]
+    OrderExecuted,
+    #[serde(rename = "order.failed")]
+    OrderFailed,
+    #[serde(rename = "position.opened")]
+    PositionOpened,
+    #[serde(rename = "position.closed")]
+    PositionClosed,
+    #[serde(rename = "summary.daily")]
+    DailySummary,
+    #[serde(rename = "strategy.paused")]
+    StrategyPaused,
+    #[serde(rename = "strategy.resumed")]
+    StrategyResumed,
+    #[serde(rename = "data_source.demoted")]
+    DataSourceDemoted,
+    #[serde(rename = "data_source.promoted")]
+    DataSourcePromoted,
+    #[serde(rename = "signal.strong")]
+    SignalStrong,
+    #[serde(rename = "position.liquidation_risk")]
+    PositionLiquidationRisk,
+    #[serde(rename = "reconciliation.unexplained")]
+    ReconciliationUnexplained,
+    #[serde(rename = "reconciliation.position_mismatch")]
+    PositionMismatch,
+    #[serde(rename = "transfer.swept")]
+    ProfitSwept,
+    #[serde(rename = "trade_group.partial")]
+    TradeGroupPartial,
+    #[serde(rename = "report.daily")]
+    DailyReport,
+}
+
+impl WebhookEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::OrderAccepted => "order.accepted",
+            Self::OrderExecuted => "order.executed",
+            Self::OrderFailed => "order.failed",
+            Self::PositionOpened => "position.opened",
+            Self::PositionClosed => "position.closed",
+            Self::DailySummary => "summary.daily",
+            Self::StrategyPaused => "strategy.paused",
+            Self::StrategyResumed => "strategy.resumed",
+            Self::DataSourceDemoted => "data_source.demoted",
+            Self::DataSourcePromoted => "data_source.promoted",
+            Self::SignalStrong => "signal.strong",
+            Self::PositionLiquidationRisk => "position.liquidation_risk",
+            Self::ReconciliationUnexplained => "reconciliation.unexplained",
+            Self::PositionMismatch => "reconciliation.position_mismatch",
+            Self::ProfitSwept => "transfer.swept",
+            Self::TradeGroupPartial => "trade_group.partial",
+            Self::DailyReport => "report.daily",
+        }
+    }
+}
+
+impl std::str::FromStr for WebhookEventType {
+    type Err = WebhookError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "order.accepted" => Ok(Self::OrderAccepted),
+            "order.executed" => Ok(Self::OrderExecuted),
+            "order.failed" => Ok(Self::OrderFailed),
+            "position.opened" => Ok(Self::PositionOpened),
+            "position.closed" => Ok(Self::PositionClosed),
+            "summary.daily" => Ok(Self::DailySummary),
+            "strategy.paused" => Ok(Self::StrategyPaused),
+            "strategy.resumed" => Ok(Self::StrategyResumed),
+            "data_source.demoted" => Ok(Self::DataSourceDemoted),
+            "data_source.promoted" => Ok(Self::DataSourcePromoted),
+            "signal.strong" => Ok(Self::SignalStrong),
+            "position.liquidation_risk" => Ok(Self::PositionLiquidationRisk),
+            "reconciliation.unexplained" => Ok(Self::ReconciliationUnexplained),
+            "reconciliation.position_mismatch" => Ok(Self::PositionMismatch),
+            "transfer.swept" => Ok(Self::ProfitSwept),
+            "trade_group.partial" => Ok(Self::TradeGroupPartial),
+            "report.daily" => Ok(Self::DailyReport),
+            other => Err(WebhookError::UnknownEventType(other.to_string())),
+        }
+    }
+}
+
+/// Registered webhook endpoint; the secret is never serialized into API responses or logs
+#[derive(Clone, Serialize)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub url: String,
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub event_types: Vec<WebhookEventType>,
+    pub active: bool,
+    pub consecutive_failures: u32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl std::fmt::Debug for Webhook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Webhook")
+            .field("id", &self.id)
+            .field("url", &self.url)
+            .field("secret", &REDACTED)
+            .field("event_types", &self.event_types)
+            .field("active", &self.active)
+            .field("consecutive_failures", &self.consecutive_failures)
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .finish()
+    }
+}
+
+impl Webhook {
+    /// Creates an active webhook after validating its URL, secret and subscriptions
+    pub fn new(
+        url: String,
+        secret: String,
+        event_types: Vec<WebhookEventType>,
+    ) -> Result<Self, WebhookError> {
+        validate_url(&url)?;
+        if secret.len() < MIN_SECRET_LENGTH {
+            return Err(WebhookError::ValidationError(format!(
+                "secret must be at least {} characters",
+                MIN_SECRET_LENGTH
+            )));
+        }
+        validate_event_types(&event_types)?;
+
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            url,
+            secret,
+            event_types,
+            active: true,
+            consecutive_failures: 0,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn subscribes_to(&self, event_type: WebhookEventType) -> bool {
+        self.active && self.event_types.contains(&event_type)
+    }
+}
+
+/// Validates a webhook URL
+pub fn validate_url(url: &str) -> Result<(), WebhookError> {
+    let parsed = Url::parse(url)
+        .map_err(|e| WebhookError::ValidationError(format!("invalid url: {}", e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(WebhookError::ValidationError(
+            "url must use http or https".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validates a webhook's event subscriptions
+pub fn validate_event_types(event_types: &[WebhookEventType]) -> Result<(), WebhookError> {
+    if event_types.is_empty() || event_types.len() > MAX_EVENT_TYPES {
+        return Err(WebhookError::ValidationError(format!(
+            "between 1 and {} event types are required",
+            MAX_EVENT_TYPES
+        )));
+    }
+    Ok(())
+}
+
+/// Event envelope posted to webhooks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    pub id: Uuid,
+    pub event_type: WebhookEventType,
+    pub created_at: DateTime<Utc>,
+    pub data: serde_json::Value,
+}
+
+impl WebhookEvent {
+    /// Wraps a payload using the API's serialization conventions
+    pub fn new<T: Serialize>(event_type: WebhookEventType, payload: &T) -> Result<Self, WebhookError> {
+        let data = serde_json::to_value(payload)
+            .map_err(|e| WebhookError::SerializationError(e.to_string()))?;
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            event_type,
+            created_at: Utc::now(),
+            data,
+        })
+    }
+
+    pub fn position_opened(position: &Position) -> Result<Self, WebhookError> {
+        Self::new(WebhookEventType::PositionOpened, position)
+    }
+
+    pub fn position_closed(close: &PositionClose) -> Result<Self, WebhookError> {
+        Self::new(WebhookEventType::PositionClosed, close)
+    }
+
+    pub fn daily_summary(summary: &DailySummary) -> Result<Self, WebhookError> {
+        Self::new(WebhookEventType::DailySummary, summary)
+    }
+}
+
+/// Order lifecycle payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderEvent {
+    pub strategy_id: String,
+    pub trading_pair: String,
+    pub exchange: String,
+    pub size: Decimal,
+    pub price: Decimal,
+    pub trade_id: Option<String>,
+    pub executed_price: Option<Decimal>,
+    pub error: Option<String>,
+}
+
+/// Strategy pause and resume payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyStatusEvent {
+    pub strategy_id: String,
+    pub reason: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Market data source demotion and re-promotion payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataSourceStatusEvent {
+    pub exchange: String,
+    pub trading_pair: String,
+    pub score: f64,
+    /// Strategies trading the pair on this venue
+    pub affected_strategies: Vec<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// End-of-day trading summary payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailySummary {
+    pub date: NaiveDate,
+    pub wallet_address: String,
+    pub portfolio_value: Decimal,
+    /// Net realized P&L over the report day
+    pub realized_pnl: Decimal,
+    /// Fills over the report day, opening and closing
+    pub trades_executed: u64,
+}
+
+/// Audit record of an automatic or manual webhook status change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookAuditEntry {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub action: String,
+    pub detail: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookAuditEntry {
+    pub fn new(webhook_id: Uuid, action: &str, detail: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            webhook_id,
+            action: action.to_string(),
+            detail,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_webhook_validation() {
+        let events = vec![WebhookEventType::OrderExecuted];
+        assert!(Webhook::new("https://example.com/hook".to_string(), "s".repeat(32), events.clone()).is_ok());
+        assert!(Webhook::new("ftp://example.com".to_string(), "s".repeat(32), events.clone()).is_err());
+        assert!(Webhook::new("https://example.com/hook".to_string(), "short".to_string(), events).is_err());
+        assert!(Webhook::new("https://example.com/hook".to_string(), "s".repeat(32), vec![]).is_err());
+    }
+
+    #[test]
+    fn test_debug_redacts_secret() {
+        let secret = "s".repeat(32);
+        let webhook = Webhook::new(
+            "https://example.com/hook".to_string(),
+            secret.clone(),
+            vec![WebhookEventType::OrderExecuted],
+        )
+        .unwrap();
+
+        let debug = format!("{:?}", webhook);
+        assert!(!debug.contains(&secret));
+        assert!(debug.contains(REDACTED));
+    }
+
+    #[test]
+    fn test_event_payload_uses_string_decimals() {
+        let event = WebhookEvent::new(
+            WebhookEventType::OrderExecuted,
+            &OrderEvent {
+                strategy_id: "grid-1".to_string(),
+                trading_pair: "SOL/USDC".to_string(),
+                exchange: "jupiter".to_string(),
+                size: dec!(1.5),
+                price: dec!(23.45),
+                trade_id: Some("sig".to_string()),
+                executed_price: Some(dec!(23.46)),
+                error: None,
+            },
+        )
+        .unwrap();
+
+        let body = serde_json::to_value(&event).unwrap();
+        assert_eq!(body["event_type"], "order.executed");
+        assert_eq!(body["data"]["price"], "23.45");
+        assert_eq!(body["data"]["executed_price"], "23.46");
+    }
+}
@@ -0,0 +1,147 @@
+//! Deterministic strategy backtests over recorded market data, producing the metrics
+//! the optimizer ranks parameter combinations by.
+//!
+//! Version dependencies:
+//! - rust_decimal = "1.30"
+//! - serde = "1.0"
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::models::market::MarketData;
+use crate::models::portfolio::net_fill;
+use crate::models::strategy::{Strategy, StrategyParams, StrategyState, StrategyType};
+use crate::optimizer::OptimizerError;
+use crate::signals::SignalDirection;
+use crate::strategies::StrategyContext;
+
+// Backtest constants
+const BACKTEST_STRATEGY_ID: &str = "backtest";
+
+/// Metrics produced by a single backtest run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BacktestMetrics {
+    pub total_trades: u32,
+    pub realized_pnl: Decimal,
+    pub roi_pct: Decimal,
+    pub sharpe_ratio: f64,
+    pub max_drawdown_pct: Decimal,
+}
+
+/// Replays ticks through a fresh strategy, marking the book to market after every tick
+pub async fn run_backtest(
+    strategy_type: StrategyType,
+    params: StrategyParams,
+    trading_pair: &str,
+    ticks: &[MarketData],
+    initial_capital: Decimal,
+) -> Result<BacktestMetrics, OptimizerError> {
+    if initial_capital <= Decimal::ZERO {
+        return Err(OptimizerError::ValidationError(
+            "initial capital must be positive".to_string(),
+        ));
+    }
+
+    let mut strategy = Strategy::new(strategy_type, params, vec![trading_pair.to_string()])
+        .map_err(|e| OptimizerError::ValidationError(e.to_string()))?;
+    strategy.state = StrategyState::Active;
+
+    let mut size = Decimal::ZERO;
+    let mut entry_price = Decimal::ZERO;
+    let mut realized_pnl = Decimal::ZERO;
+    let mut total_trades = 0u32;
+    let mut equity_curve = Vec::with_capacity(ticks.len() + 1);
+    equity_curve.push(initial_capital);
+
+    for tick in ticks {
+        let ctx = StrategyContext::new(BACKTEST_STRATEGY_ID, tick.timestamp());
+        let signals = strategy
+            .execute(&ctx, tick)
+            .await
+            .map_err(|e| OptimizerError::BacktestError(e.to_string()))?;
+
+        for signal in signals {
+            // Signals fill at the observed price; shorts reduce the position
+            let fill_size = match signal.direction {
+                SignalDirection::Long => signal.size,
+                SignalDirection::Short => -signal.size,
+            };
+            let fill = net_fill(size, entry_price, fill_size, tick.price())
+                .map_err(|e| OptimizerError::BacktestError(e.to_string()))?;
+            size = fill.size;
+            entry_price = fill.entry_price;
+            realized_pnl += fill.realized_pnl;
+            total_trades += 1;
+        }
+
+        let unrealized = size * (tick.price() - entry_price);
+        equity_curve.push(initial_capital + realized_pnl + unrealized);
+    }
+
+    let final_equity = *equity_curve.last().unwrap_or(&initial_capital);
+    Ok(BacktestMetrics {
+        total_trades,
+        realized_pnl,
+        roi_pct: (final_equity - initial_capital) / initial_capital * Decimal::ONE_HUNDRED,
+        sharpe_ratio: sharpe_ratio(&equity_curve),
+        max_drawdown_pct: max_drawdown_pct(&equity_curve),
+    })
+}
+
+/// Mean over sample standard deviation of per-tick equity returns, unannualized
+pub fn sharpe_ratio(equity_curve: &[Decimal]) -> f64 {
+    let returns: Vec<f64> = equity_curve
+        .windows(2)
+        .filter(|pair| !pair[0].is_zero())
+        .filter_map(|pair| ((pair[1] - pair[0]) / pair[0]).to_f64())
+        .collect();
+    if returns.len() < 2 {
+        return 0.0;
+    }
+
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+    let std_dev = variance.sqrt();
+    if std_dev == 0.0 {
+        return 0.0;
+    }
+    mean / std_dev
+}
+
+/// Largest peak-to-trough decline of the equity curve as a positive percentage
+pub fn max_drawdown_pct(equity_curve: &[Decimal]) -> Decimal {
+    let mut peak = Decimal::ZERO;
+    let mut max_drawdown = Decimal::ZERO;
+    for equity in equity_curve {
+        peak = peak.max(*equity);
+        if peak > Decimal::ZERO {
+            max_drawdown = max_drawdown.max((peak - equity) / peak * Decimal::ONE_HUNDRED);
+        }
+    }
+    max_drawdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_max_drawdown() {
+        let curve = [dec!(100), dec!(120), dec!(90), dec!(110), dec!(130)];
+        assert_eq!(max_drawdown_pct(&curve), dec!(25));
+        assert_eq!(max_drawdown_pct(&[dec!(100), dec!(101)]), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_sharpe_ratio() {
+        assert_eq!(sharpe_ratio(&[dec!(100), dec!(101), dec!(102.01)]), 0.0);
+        assert_eq!(sharpe_ratio(&[dec!(100)]), 0.0);
+
+        let rising = sharpe_ratio(&[dec!(100), dec!(102), dec!(101), dec!(104)]);
+        let falling = sharpe_ratio(&[dec!(100), dec!(98), dec!(99), dec!(96)]);
+        assert!(rising > 0.0);
+        assert!(falling < 0.0);
+    }
+}
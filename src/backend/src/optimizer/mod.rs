@@ -0,0 +1,629 @@
+//! Strategy parameter optimization: grid search over backtests executed by a bounded
+//! worker pool, with progress reporting, cancellation and deterministic rankings.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - rust_decimal = "1.30"
+//! - serde = "1.0"
+//! - parking_lot = "0.12"
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, histogram};
+use parking_lot::RwLock;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::Semaphore;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::data_collector::replay::ReplaySource;
+use crate::db::repositories::OptimizationRunRepository;
+use crate::models::market::MarketData;
+use crate::models::strategy::{StrategyParams, StrategyType};
+use crate::utils::percent::{Bps, Percent};
+
+pub mod backtest;
+
+use self::backtest::{run_backtest, BacktestMetrics};
+
+// Optimizer constants
+pub const DEFAULT_MAX_WORKERS: usize = 4;
+pub const DEFAULT_MAX_COMBINATIONS: usize = 10_000;
+pub const DEFAULT_CAPTURE_PATH: &str = "data/backtest.ndjson";
+const DEFAULT_INITIAL_CAPITAL: i64 = 10_000;
+const METRICS_PREFIX: &str = "trading_bot.optimizer";
+
+/// Optimizer error types
+#[derive(Error, Debug)]
+pub enum OptimizerError {
+    #[error("validation error: {0}")]
+    ValidationError(String),
+    #[error("too many combinations: {0} exceeds the limit of {1}")]
+    TooManyCombinations(usize, usize),
+    #[error("job not found: {0}")]
+    NotFound(Uuid),
+    #[error("data error: {0}")]
+    DataError(String),
+    #[error("backtest error: {0}")]
+    BacktestError(String),
+}
+
+/// Inclusive range of values for one strategy parameter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterRange {
+    pub start: Decimal,
+    pub end: Decimal,
+    pub step: Decimal,
+}
+
+impl ParameterRange {
+    fn validate(&self, name: &str) -> Result<(), OptimizerError> {
+        if self.step <= Decimal::ZERO || self.end < self.start {
+            return Err(OptimizerError::ValidationError(format!(
+                "{} needs a positive step and end >= start",
+                name
+            )));
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        ((self.end - self.start) / self.step)
+            .floor()
+            .to_usize()
+            .map_or(usize::MAX, |steps| steps.saturating_add(1))
+    }
+
+    fn value(&self, index: usize) -> Decimal {
+        self.start + self.step * Decimal::from(index)
+    }
+}
+
+/// Metric used to rank backtest runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Objective {
+    Sharpe,
+    Roi,
+}
+
+impl Objective {
+    fn score(&self, metrics: &BacktestMetrics) -> f64 {
+        match self {
+            Self::Sharpe => metrics.sharpe_ratio,
+            Self::Roi => metrics.roi_pct.to_f64().unwrap_or(f64::MIN),
+        }
+    }
+}
+
+/// Optimization job submission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizationRequest {
+    pub strategy_type: StrategyType,
+    pub base_parameters: StrategyParams,
+    pub parameter_space: BTreeMap<String, ParameterRange>,
+    pub objective: Objective,
+    pub max_drawdown_pct: Option<Decimal>,
+    pub trading_pair: String,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub seed: u64,
+}
+
+/// Optimization job lifecycle states
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+/// Job progress snapshot
+#[derive(Debug, Clone, Serialize)]
+pub struct JobProgress {
+    pub id: Uuid,
+    pub status: JobStatus,
+    pub completed: usize,
+    pub total: usize,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of one parameter combination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizationRun {
+    pub combination: usize,
+    pub parameters: BTreeMap<String, Decimal>,
+    pub metrics: Option<BacktestMetrics>,
+    pub error: Option<String>,
+    pub feasible: bool,
+    pub rank: Option<usize>,
+}
+
+/// Optimizer configuration
+#[derive(Debug, Clone)]
+pub struct OptimizerConfig {
+    pub max_workers: usize,
+    pub max_combinations: usize,
+    pub initial_capital: Decimal,
+}
+
+impl Default for OptimizerConfig {
+    fn default() -> Self {
+        Self {
+            max_workers: DEFAULT_MAX_WORKERS,
+            max_combinations: DEFAULT_MAX_COMBINATIONS,
+            initial_capital: Decimal::from(DEFAULT_INITIAL_CAPITAL),
+        }
+    }
+}
+
+/// Running or finished optimization job
+#[derive(Debug)]
+struct OptimizationJob {
+    id: Uuid,
+    request: OptimizationRequest,
+    total: usize,
+    completed: AtomicUsize,
+    cancelled: AtomicBool,
+    state: RwLock<JobState>,
+}
+
+#[derive(Debug)]
+struct JobState {
+    status: JobStatus,
+    error: Option<String>,
+    runs: Vec<OptimizationRun>,
+    created_at: DateTime<Utc>,
+    finished_at: Option<DateTime<Utc>>,
+}
+
+impl OptimizationJob {
+    fn progress(&self) -> JobProgress {
+        let state = self.state.read();
+        JobProgress {
+            id: self.id,
+            status: state.status,
+            completed: self.completed.load(Ordering::SeqCst),
+            total: self.total,
+            error: state.error.clone(),
+            created_at: state.created_at,
+            finished_at: state.finished_at,
+        }
+    }
+
+    fn finish(&self, status: JobStatus, error: Option<String>) {
+        let mut state = self.state.write();
+        state.status = status;
+        state.error = error;
+        state.finished_at = Some(Utc::now());
+    }
+}
+
+/// Runs grid-search optimization jobs over recorded market data
+#[derive(Debug)]
+pub struct OptimizerService {
+    config: OptimizerConfig,
+    source: ReplaySource,
+    repository: Option<Arc<OptimizationRunRepository>>,
+    jobs: RwLock<HashMap<Uuid, Arc<OptimizationJob>>>,
+}
+
+impl OptimizerService {
+    pub fn new(
+        config: OptimizerConfig,
+        source: ReplaySource,
+        repository: Option<Arc<OptimizationRunRepository>>,
+    ) -> Self {
+        Self {
+            config,
+            source,
+            repository,
+            jobs: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Validates a job and starts it in the background
+    #[instrument(skip(self, request))]
+    pub fn submit(self: &Arc<Self>, request: OptimizationRequest) -> Result<JobProgress, OptimizerError> {
+        let total = validate_request(&request, self.config.max_combinations)?;
+
+        let job = Arc::new(OptimizationJob {
+            id: Uuid::new_v4(),
+            request,
+            total,
+            completed: AtomicUsize::new(0),
+            cancelled: AtomicBool::new(false),
+            state: RwLock::new(JobState {
+                status: JobStatus::Running,
+                error: None,
+                runs: Vec::new(),
+                created_at: Utc::now(),
+                finished_at: None,
+            }),
+        });
+        self.jobs.write().insert(job.id, job.clone());
+        counter!(format!("{}.jobs_submitted", METRICS_PREFIX), 1);
+        info!(job_id = %job.id, combinations = total, "Optimization job submitted");
+
+        let service = self.clone();
+        let progress = job.progress();
+        tokio::spawn(async move { service.run_job(job).await });
+        Ok(progress)
+    }
+
+    pub fn progress(&self, id: Uuid) -> Result<JobProgress, OptimizerError> {
+        self.job(id).map(|job| job.progress())
+    }
+
+    /// Returns runs ordered by rank, followed by infeasible and failed runs
+    pub fn results(&self, id: Uuid) -> Result<(JobProgress, Vec<OptimizationRun>), OptimizerError> {
+        let job = self.job(id)?;
+        let runs = job.state.read().runs.clone();
+        Ok((job.progress(), runs))
+    }
+
+    /// Stops scheduling new combinations; runs already executing finish
+    pub fn cancel(&self, id: Uuid) -> Result<JobProgress, OptimizerError> {
+        let job = self.job(id)?;
+        job.cancelled.store(true, Ordering::SeqCst);
+        info!(job_id = %id, "Optimization job cancellation requested");
+        Ok(job.progress())
+    }
+
+    fn job(&self, id: Uuid) -> Result<Arc<OptimizationJob>, OptimizerError> {
+        self.jobs
+            .read()
+            .get(&id)
+            .cloned()
+            .ok_or(OptimizerError::NotFound(id))
+    }
+
+    async fn run_job(self: Arc<Self>, job: Arc<OptimizationJob>) {
+        let start = std::time::Instant::now();
+        match self.execute_job(&job).await {
+            Ok(runs) => {
+                let status = if job.cancelled.load(Ordering::SeqCst) {
+                    JobStatus::Cancelled
+                } else {
+                    JobStatus::Completed
+                };
+
+                if let Some(repository) = &self.repository {
+                    if let Err(e) = repository.record_runs(job.id, &job.request, &runs).await {
+                        error!(job_id = %job.id, error = %e, "Failed to persist optimization runs");
+                    }
+                }
+
+                job.state.write().runs = runs;
+                job.finish(status, None);
+                info!(job_id = %job.id, ?status, "Optimization job finished");
+            }
+            Err(e) => {
+                warn!(job_id = %job.id, error = %e, "Optimization job failed");
+                job.finish(JobStatus::Failed, Some(e.to_string()));
+            }
+        }
+        histogram!(
+            format!("{}.job_duration_ms", METRICS_PREFIX),
+            start.elapsed().as_millis() as f64
+        );
+    }
+
+    async fn execute_job(&self, job: &Arc<OptimizationJob>) -> Result<Vec<OptimizationRun>, OptimizerError> {
+        let ticks = Arc::new(self.load_ticks(&job.request).await?);
+        let combinations = expand_space(&job.request.parameter_space);
+        let semaphore = Arc::new(Semaphore::new(self.config.max_workers.max(1)));
+        let mut handles = Vec::with_capacity(combinations.len());
+
+        for index in evaluation_order(combinations.len(), job.request.seed) {
+            let permit = semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .map_err(|e| OptimizerError::BacktestError(e.to_string()))?;
+            if job.cancelled.load(Ordering::SeqCst) {
+                break;
+            }
+
+            let job = job.clone();
+            let ticks = ticks.clone();
+            let parameters = combinations[index].clone();
+            let initial_capital = self.config.initial_capital;
+            handles.push(tokio::spawn(async move {
+                let result = match apply_parameters(&job.request.base_parameters, &parameters) {
+                    Ok(params) => {
+                        run_backtest(
+                            job.request.strategy_type.clone(),
+                            params,
+                            &job.request.trading_pair,
+                            &ticks,
+                            initial_capital,
+                        )
+                        .await
+                    }
+                    Err(e) => Err(e),
+                };
+                job.completed.fetch_add(1, Ordering::SeqCst);
+                counter!(format!("{}.backtests", METRICS_PREFIX), 1);
+                drop(permit);
+                (index, parameters, result)
+            }));
+        }
+
+        let mut runs = Vec::with_capacity(handles.len());
+        for handle in handles {
+            let (combination, parameters, result) = handle
+                .await
+                .map_err(|e| OptimizerError::BacktestError(e.to_string()))?;
+            let (metrics, error) = match result {
+                Ok(metrics) => (Some(metrics), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            runs.push(OptimizationRun {
+                combination,
+                parameters,
+                metrics,
+                error,
+                feasible: false,
+                rank: None,
+            });
+        }
+
+        rank_runs(&mut runs, job.request.objective, job.request.max_drawdown_pct, job.request.seed);
+        Ok(runs)
+    }
+
+    /// Loads the requested pair and time range from the configured source
+    async fn load_ticks(&self, request: &OptimizationRequest) -> Result<Vec<MarketData>, OptimizerError> {
+        let ticks = self
+            .source
+            .load_ticks()
+            .await
+            .map_err(|e| OptimizerError::DataError(e.to_string()))?;
+
+        let data = ticks
+            .into_iter()
+            .filter(|tick| tick.trading_pair == request.trading_pair)
+            .filter(|tick| request.from.map_or(true, |from| tick.timestamp >= from))
+            .filter(|tick| request.to.map_or(true, |to| tick.timestamp < to))
+            .map(|tick| tick.into_market_data())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| OptimizerError::DataError(e.to_string()))?;
+
+        if data.is_empty() {
+            return Err(OptimizerError::DataError(format!(
+                "no market data for {} in the requested range",
+                request.trading_pair
+            )));
+        }
+        Ok(data)
+    }
+}
+
+/// Validates a request and returns its combination count
+fn validate_request(request: &OptimizationRequest, max_combinations: usize) -> Result<usize, OptimizerError> {
+    if request.parameter_space.is_empty() {
+        return Err(OptimizerError::ValidationError(
+            "parameter space must not be empty".to_string(),
+        ));
+    }
+
+    let mut total: usize = 1;
+    for (name, range) in &request.parameter_space {
+        range.validate(name)?;
+        apply_parameter(&mut request.base_parameters.clone(), name, range.start)?;
+        total = total.saturating_mul(range.len());
+    }
+
+    if total > max_combinations {
+        return Err(OptimizerError::TooManyCombinations(total, max_combinations));
+    }
+    Ok(total)
+}
+
+/// Expands the space into every combination, iterating parameter names in order
+fn expand_space(space: &BTreeMap<String, ParameterRange>) -> Vec<BTreeMap<String, Decimal>> {
+    let mut combinations = vec![BTreeMap::new()];
+    for (name, range) in space {
+        combinations = combinations
+            .into_iter()
+            .flat_map(|combination| {
+                (0..range.len()).map(move |i| {
+                    let mut next = combination.clone();
+                    next.insert(name.clone(), range.value(i));
+                    next
+                })
+            })
+            .collect();
+    }
+    combinations
+}
+
+fn apply_parameters(
+    base: &StrategyParams,
+    parameters: &BTreeMap<String, Decimal>,
+) -> Result<StrategyParams, OptimizerError> {
+    let mut params = base.clone();
+    for (name, value) in parameters {
+        apply_parameter(&mut params, name, *value)?;
+    }
+    Ok(params)
+}
+
+/// Sets a named strategy parameter
+fn apply_parameter(params: &mut StrategyParams, name: &str, value: Decimal) -> Result<(), OptimizerError> {
+    let as_u32 = |value: Decimal| {
+        value.trunc().to_u32().ok_or_else(|| {
+            OptimizerError::ValidationError(format!("{} must be a non-negative integer", name))
+        })
+    };
+
+    match name {
+        "grid_levels" => params.grid_levels = Some(as_u32(value)?),
+        "position_size_bps" => params.position_size_bps = Bps::new(as_u32(value)?),
+        "max_slippage_bps" => params.max_slippage_bps = Bps::new(as_u32(value)?),
+        "stop_loss_pct" => params.stop_loss_pct = Percent::from_percent(value),
+        "take_profit_pct" => params.take_profit_pct = Percent::from_percent(value),
+        "risk_factor" => params.risk_factor = value,
+        other => {
+            return Err(OptimizerError::ValidationError(format!(
+                "unknown parameter: {}",
+                other
+            )))
+        }
+    }
+    Ok(())
+}
+
+/// SplitMix64 mix of the seed and combination index
+fn seeded_key(seed: u64, index: usize) -> u64 {
+    let mut z = seed ^ (index as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Seeded permutation so a cancelled job has sampled across the whole space
+fn evaluation_order(count: usize, seed: u64) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..count).collect();
+    order.sort_by_key(|index| (seeded_key(seed, *index), *index));
+    order
+}
+
+/// Ranks feasible runs by objective, breaking ties with the seeded key
+fn rank_runs(runs: &mut [OptimizationRun], objective: Objective, max_drawdown_pct: Option<Decimal>, seed: u64) {
+    for run in runs.iter_mut() {
+        run.feasible = run.metrics.as_ref().map_or(false, |metrics| {
+            max_drawdown_pct.map_or(true, |limit| metrics.max_drawdown_pct <= limit)
+        });
+    }
+
+    runs.sort_by(|a, b| {
+        let score = |run: &OptimizationRun| {
+            run.metrics
+                .as_ref()
+                .filter(|_| run.feasible)
+                .map(|metrics| objective.score(metrics))
+        };
+        let tier = |run: &OptimizationRun| match (run.feasible, run.metrics.is_some()) {
+            (true, _) => 0,
+            (false, true) => 1,
+            (false, false) => 2,
+        };
+
+        tier(a)
+            .cmp(&tier(b))
+            .then_with(|| {
+                score(b)
+                    .unwrap_or(f64::MIN)
+                    .total_cmp(&score(a).unwrap_or(f64::MIN))
+            })
+            .then_with(|| seeded_key(seed, a.combination).cmp(&seeded_key(seed, b.combination)))
+            .then_with(|| a.combination.cmp(&b.combination))
+    });
+
+    let mut rank = 0;
+    for run in runs.iter_mut().filter(|run| run.feasible) {
+        rank += 1;
+        run.rank = Some(rank);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn range(start: Decimal, end: Decimal, step: Decimal) -> ParameterRange {
+        ParameterRange { start, end, step }
+    }
+
+    fn request(space: BTreeMap<String, ParameterRange>) -> OptimizationRequest {
+        OptimizationRequest {
+            strategy_type: StrategyType::Grid,
+            base_parameters: StrategyParams {
+                position_size_bps: Bps::new(1000),
+                grid_levels: Some(10),
+                stop_loss_pct: Percent::from_percent(dec!(-5)),
+                take_profit_pct: Percent::from_percent(dec!(1)),
+                max_slippage_bps: Bps::new(100),
+                exchanges: vec!["jupiter".to_string()],
+                risk_factor: dec!(0.5),
+                allowed_regimes: Vec::new(),
+                market_making: None,
+            },
+            parameter_space: space,
+            objective: Objective::Roi,
+            max_drawdown_pct: None,
+            trading_pair: "SOL/USDC".to_string(),
+            from: None,
+            to: None,
+            seed: 7,
+        }
+    }
+
+    #[test]
+    fn test_space_expansion_and_cap() {
+        let mut space = BTreeMap::new();
+        space.insert("grid_levels".to_string(), range(dec!(5), dec!(50), dec!(5)));
+        space.insert("stop_loss_pct".to_string(), range(dec!(-5), dec!(-1), dec!(1)));
+
+        assert_eq!(validate_request(&request(space.clone()), 1_000).unwrap(), 50);
+        assert_eq!(expand_space(&space).len(), 50);
+        assert!(matches!(
+            validate_request(&request(space.clone()), 49),
+            Err(OptimizerError::TooManyCombinations(50, 49))
+        ));
+
+        space.insert("leverage".to_string(), range(dec!(1), dec!(2), dec!(1)));
+        assert!(validate_request(&request(space), 1_000).is_err());
+    }
+
+    #[test]
+    fn test_evaluation_order_is_seeded_permutation() {
+        let first = evaluation_order(20, 42);
+        assert_eq!(first, evaluation_order(20, 42));
+        assert_ne!(first, evaluation_order(20, 43));
+
+        let mut sorted = first.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_ranking_respects_drawdown_constraint() {
+        let run = |combination, roi_pct, max_drawdown_pct| OptimizationRun {
+            combination,
+            parameters: BTreeMap::new(),
+            metrics: Some(BacktestMetrics {
+                total_trades: 1,
+                realized_pnl: Decimal::ZERO,
+                roi_pct,
+                sharpe_ratio: 0.0,
+                max_drawdown_pct,
+            }),
+            error: None,
+            feasible: false,
+            rank: None,
+        };
+        let mut runs = vec![
+            run(0, dec!(5), dec!(2)),
+            run(1, dec!(20), dec!(30)),
+            run(2, dec!(8), dec!(4)),
+        ];
+
+        rank_runs(&mut runs, Objective::Roi, Some(dec!(10)), 1);
+        let order: Vec<(usize, Option<usize>)> = runs.iter().map(|r| (r.combination, r.rank)).collect();
+        assert_eq!(order, vec![(2, Some(1)), (0, Some(2)), (1, None)]);
+    }
+}
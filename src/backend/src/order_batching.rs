@@ -0,0 +1,355 @@
+//! Batching of small same-side strategy orders. Grid and market-making strategies can emit
+//! many small orders for the same pair and side within a single tick, and each one pays its
+//! own transaction fee. Signals from the same strategy, pair, venue and side arriving within
+//! the batching window are held and merged into one order with the aggregate size at the
+//! size-weighted price, up to a maximum merged notional. The merged signal keeps the ids of
+//! the signals it replaced so fills can still be attributed to them, and every batch is
+//! recorded in the signal audit log.
+//!
+//! Version dependencies:
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::Mutex;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::execution_engine::passive::ExecutionStyle;
+use crate::models::order::OrderType;
+use crate::signals::{AuditConsumer, Signal, SignalDirection};
+use crate::utils::time::to_chrono;
+
+// Order batching constants
+const METRICS_PREFIX: &str = "trading_bot.order_batching";
+const DEFAULT_WINDOW: Duration = Duration::from_millis(500);
+const DEFAULT_RELEASE_INTERVAL: Duration = Duration::from_millis(50);
+const DEFAULT_MAX_NOTIONAL: Decimal = Decimal::new(10_000, 0);
+/// Base signature fee plus priority fee on a typical swap's compute budget
+const DEFAULT_FEE_PER_ORDER_LAMPORTS: u64 = 7_000;
+/// Decimal places merged prices are rounded to
+const PRICE_SCALE: u32 = 9;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchingConfig {
+    /// Same-side signals within this window of the first are merged into one order
+    pub window: Duration,
+    /// Largest notional a merged order may reach; larger signals pass through unmerged
+    pub max_notional: Decimal,
+    /// Fee one order costs, for metering what merging saves
+    pub fee_per_order_lamports: u64,
+    /// How often batches whose window has closed are released
+    pub release_interval: Duration,
+}
+
+impl Default for BatchingConfig {
+    fn default() -> Self {
+        Self {
+            window: DEFAULT_WINDOW,
+            max_notional: DEFAULT_MAX_NOTIONAL,
+            fee_per_order_lamports: DEFAULT_FEE_PER_ORDER_LAMPORTS,
+            release_interval: DEFAULT_RELEASE_INTERVAL,
+        }
+    }
+}
+
+/// A signal merged into a batch, at the size and price it asked for
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BatchedSignal {
+    pub signal_id: Uuid,
+    pub size: Decimal,
+    pub price: Decimal,
+}
+
+/// Signals merged into a single order
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrderBatch {
+    /// Id of the merged signal that was executed
+    pub signal_id: Uuid,
+    pub strategy_id: String,
+    pub pair: String,
+    pub exchange: String,
+    pub direction: SignalDirection,
+    pub size: Decimal,
+    pub price: Decimal,
+    /// Merged signals in arrival order
+    pub children: Vec<BatchedSignal>,
+    pub fees_saved_lamports: u64,
+    pub merged_at: DateTime<Utc>,
+}
+
+/// Strategy, pair, venue and direction whose signals merge together
+type BatchKey = (String, String, String, SignalDirection);
+
+/// Signals held until the window opened by the first of them closes
+#[derive(Debug)]
+struct PendingBatch {
+    closes_at: DateTime<Utc>,
+    notional: Decimal,
+    signals: Vec<Signal>,
+}
+
+/// Holds same-side signals for the batching window and merges them into single orders
+#[derive(Debug)]
+pub struct OrderBatcher {
+    config: BatchingConfig,
+    pending: Mutex<HashMap<BatchKey, PendingBatch>>,
+    audit: Arc<AuditConsumer>,
+}
+
+impl OrderBatcher {
+    pub fn new(config: BatchingConfig, audit: Arc<AuditConsumer>) -> Self {
+        Self {
+            config,
+            pending: Mutex::new(HashMap::new()),
+            audit,
+        }
+    }
+
+    pub fn config(&self) -> &BatchingConfig {
+        &self.config
+    }
+
+    /// Accepts a signal, returning the signals to execute now. Bracketed, market, passive
+    /// and over-cap signals pass through; a signal that would take its batch over the cap
+    /// releases the batch early and opens a new one.
+    pub fn offer(&self, signal: Signal, now: DateTime<Utc>) -> Vec<Signal> {
+        let notional = signal.price * signal.size;
+        if signal.bracket.is_some()
+            || signal.order_type != OrderType::Limit
+            || signal.execution_style != ExecutionStyle::Aggressive
+            || notional > self.config.max_notional
+        {
+            counter!(format!("{}.passed_through", METRICS_PREFIX), 1);
+            return vec![signal];
+        }
+
+        let key = (
+            signal.strategy_id.clone(),
+            signal.pair.clone(),
+            signal.exchange.clone(),
+            signal.direction,
+        );
+        let full = {
+            let mut pending = self.pending.lock();
+            let full = match pending.get(&key) {
+                Some(batch) if batch.notional + notional > self.config.max_notional => pending.remove(&key),
+                _ => None,
+            };
+            let batch = pending.entry(key).or_insert_with(|| PendingBatch {
+                closes_at: now + to_chrono(self.config.window),
+                notional: Decimal::ZERO,
+                signals: Vec::new(),
+            });
+            batch.notional += notional;
+            batch.signals.push(signal);
+            full
+        };
+        full.and_then(|batch| self.merge(batch.signals, now)).into_iter().collect()
+    }
+
+    /// Merges every batch whose window has closed, returning the orders to execute
+    pub fn release_due(&self, now: DateTime<Utc>) -> Vec<Signal> {
+        let due: Vec<PendingBatch> = {
+            let mut pending = self.pending.lock();
+            let keys: Vec<BatchKey> = pending
+                .iter()
+                .filter(|(_, batch)| batch.closes_at <= now)
+                .map(|(key, _)| key.clone())
+                .collect();
+            keys.into_iter().filter_map(|key| pending.remove(&key)).collect()
+        };
+
+        due.into_iter().filter_map(|batch| self.merge(batch.signals, now)).collect()
+    }
+
+    /// Number of signals currently held
+    pub fn pending_len(&self) -> usize {
+        self.pending.lock().values().map(|batch| batch.signals.len()).sum()
+    }
+
+    fn merge(&self, signals: Vec<Signal>, now: DateTime<Utc>) -> Option<Signal> {
+        // Held signals may lapse while waiting out the window
+        let (mut signals, expired): (Vec<Signal>, Vec<Signal>) =
+            signals.into_iter().partition(|signal| !signal.is_expired(now));
+        if !expired.is_empty() {
+            counter!(format!("{}.expired", METRICS_PREFIX), expired.len() as u64);
+        }
+        if signals.len() <= 1 {
+            return signals.pop();
+        }
+
+        let size: Decimal = signals.iter().map(|signal| signal.size).sum();
+        if size.is_zero() {
+            return None;
+        }
+        let weighted: Decimal = signals.iter().map(|signal| signal.price * signal.size).sum();
+        let price = (weighted / size).round_dp(PRICE_SCALE);
+
+        let first = &signals[0];
+        let mut merged = Signal::new(
+            &first.strategy_id,
+            &first.pair,
+            &first.exchange,
+            first.direction,
+            price,
+            size,
+            first.ttl,
+            first.created_at,
+        );
+        merged.strength = signals.iter().map(|signal| signal.strength).max().unwrap_or(Decimal::ONE);
+        merged.expires_at = signals.iter().map(|signal| signal.expires_at).min().unwrap_or(merged.expires_at);
+        merged.tick = signals.iter().find_map(|signal| signal.tick);
+        merged.merged_from = signals.iter().map(|signal| signal.id).collect();
+
+        let saved = (signals.len() - 1) as u64;
+        let fees_saved_lamports = saved * self.config.fee_per_order_lamports;
+        counter!(format!("{}.merged_orders", METRICS_PREFIX), 1, "strategy_id" => merged.strategy_id.clone());
+        counter!(format!("{}.orders_saved", METRICS_PREFIX), saved, "strategy_id" => merged.strategy_id.clone());
+        counter!(format!("{}.fees_saved_lamports", METRICS_PREFIX), fees_saved_lamports);
+        debug!(
+            strategy_id = %merged.strategy_id,
+            pair = %merged.pair,
+            signals = signals.len(),
+            size = %size,
+            price = %price,
+            "Merged strategy signals into one order"
+        );
+
+        self.audit.record_batch(OrderBatch {
+            signal_id: merged.id,
+            strategy_id: merged.strategy_id.clone(),
+            pair: merged.pair.clone(),
+            exchange: merged.exchange.clone(),
+            direction: merged.direction,
+            size,
+            price,
+            children: signals
+                .iter()
+                .map(|signal| BatchedSignal {
+                    signal_id: signal.id,
+                    size: signal.size,
+                    price: signal.price,
+                })
+                .collect(),
+            fees_saved_lamports,
+            merged_at: now,
+        });
+        Some(merged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_engine::position_events::Bracket;
+    use crate::risk_manager::exposure::TradeSide;
+    use rust_decimal_macros::dec;
+
+    const TTL: Duration = Duration::from_secs(5);
+
+    fn batcher(max_notional: Decimal) -> (OrderBatcher, Arc<AuditConsumer>) {
+        let audit = Arc::new(AuditConsumer::default());
+        let config = BatchingConfig {
+            max_notional,
+            ..BatchingConfig::default()
+        };
+        (OrderBatcher::new(config, audit.clone()), audit)
+    }
+
+    fn grid_order(direction: SignalDirection, price: Decimal, now: DateTime<Utc>) -> Signal {
+        Signal::new("grid-1", "SOL/USDC", "jupiter", direction, price, dec!(0.5), TTL, now)
+    }
+
+    #[test]
+    fn test_grid_orders_in_one_window_merge_into_one() {
+        let (batcher, audit) = batcher(dec!(10000));
+        let now = Utc::now();
+
+        // Ten buy levels 10 cents apart from 23.00 to 23.90
+        let levels: Vec<Signal> = (0..10)
+            .map(|i| grid_order(SignalDirection::Long, dec!(23) + Decimal::new(i, 1), now))
+            .collect();
+        for level in &levels {
+            assert!(batcher.offer(level.clone(), now).is_empty());
+        }
+        assert_eq!(batcher.pending_len(), 10);
+        assert!(batcher.release_due(now + chrono::Duration::milliseconds(400)).is_empty());
+
+        let released = batcher.release_due(now + chrono::Duration::milliseconds(500));
+        assert_eq!(released.len(), 1);
+        let params = released[0].order_params();
+        assert_eq!(params.side, TradeSide::Buy);
+        assert_eq!(params.size, dec!(5));
+        // Equal sizes weight every level evenly
+        assert_eq!(params.price, dec!(23.45));
+        assert_eq!(released[0].merged_from, levels.iter().map(|level| level.id).collect::<Vec<_>>());
+        assert_eq!(batcher.pending_len(), 0);
+
+        let batches = audit.batches();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].signal_id, released[0].id);
+        assert_eq!(batches[0].children.len(), 10);
+        assert_eq!(batches[0].fees_saved_lamports, 9 * DEFAULT_FEE_PER_ORDER_LAMPORTS);
+    }
+
+    #[test]
+    fn test_max_notional_splits_batches() {
+        // Each order is 0.5 SOL at 20 USDC, so two fit under a 25 USDC cap
+        let (batcher, audit) = batcher(dec!(25));
+        let now = Utc::now();
+
+        assert!(batcher.offer(grid_order(SignalDirection::Long, dec!(20), now), now).is_empty());
+        assert!(batcher.offer(grid_order(SignalDirection::Long, dec!(20), now), now).is_empty());
+        let full = batcher.offer(grid_order(SignalDirection::Long, dec!(20), now), now);
+        assert_eq!(full.len(), 1);
+        assert_eq!(full[0].size, dec!(1));
+        assert_eq!(full[0].merged_from.len(), 2);
+
+        // The third order opened a batch of its own and goes out alone, unmerged
+        let released = batcher.release_due(now + chrono::Duration::seconds(1));
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].size, dec!(0.5));
+        assert!(released[0].merged_from.is_empty());
+        assert_eq!(audit.batches().len(), 1);
+    }
+
+    #[test]
+    fn test_unmergeable_signals_pass_through() {
+        let (batcher, _) = batcher(dec!(25));
+        let now = Utc::now();
+
+        let large = Signal::new("grid-1", "SOL/USDC", "jupiter", SignalDirection::Long, dec!(20), dec!(2), TTL, now);
+        assert_eq!(batcher.offer(large.clone(), now), vec![large]);
+        let bracketed = grid_order(SignalDirection::Long, dec!(20), now)
+            .with_bracket(Bracket { stop_loss: dec!(19), take_profit: dec!(22) });
+        assert_eq!(batcher.offer(bracketed.clone(), now), vec![bracketed]);
+        assert_eq!(batcher.pending_len(), 0);
+    }
+
+    #[test]
+    fn test_opposite_sides_batch_separately() {
+        let (batcher, _) = batcher(dec!(10000));
+        let now = Utc::now();
+
+        for direction in [SignalDirection::Long, SignalDirection::Short] {
+            batcher.offer(grid_order(direction, dec!(23), now), now);
+            batcher.offer(grid_order(direction, dec!(24), now), now);
+        }
+        let released = batcher.release_due(now + chrono::Duration::seconds(1));
+        assert_eq!(released.len(), 2);
+        for order in released {
+            assert_eq!(order.size, dec!(1));
+            assert_eq!(order.price, dec!(23.5));
+            assert_eq!(order.merged_from.len(), 2);
+        }
+    }
+}
@@ -0,0 +1,779 @@
+//! Strategy performance leaderboard and equity curves. Realized P&L from every fill that
+//! reduces a position is persisted per strategy and folded incrementally into 1m/5m/1h
+//! cumulative P&L buckets; the leaderboard ranks strategies by any metric column and
+//! recomputed entries are pushed to subscribers. Trades carry the parameter version that
+//! produced them so versions can be compared on their own results.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+//! - utoipa = "3.5"
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::RwLock as SyncRwLock;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::data_collector::ohlcv::CandleInterval;
+use crate::execution_engine::fills::FillUpdate;
+use crate::models::strategy::{Strategy, StrategyState, StrategyType};
+use crate::strategy_versions::is_variant;
+
+// Performance tracking constants
+const METRICS_PREFIX: &str = "trading_bot.performance";
+const UPDATE_CHANNEL_CAPACITY: usize = 256;
+const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+const DEFAULT_MIN_SHARPE_SAMPLE: u32 = 30;
+pub const MAX_EQUITY_POINTS: usize = 1000;
+pub const MAX_TRADES_PER_REQUEST: usize = 500;
+
+/// Equity curve granularities materialized as trades land
+pub const EQUITY_GRANULARITIES: [CandleInterval; 3] = [
+    CandleInterval::OneMinute,
+    CandleInterval::FiveMinutes,
+    CandleInterval::OneHour,
+];
+
+/// Performance tracking errors
+#[derive(Error, Debug)]
+pub enum PerformanceError {
+    #[error("unknown strategy: {0}")]
+    UnknownStrategy(String),
+    #[error("unsupported equity granularity: {0}")]
+    UnsupportedGranularity(String),
+    #[error("unknown leaderboard column: {0}")]
+    UnknownColumn(String),
+    #[error("unknown sort order: {0}")]
+    UnknownOrder(String),
+    #[error("store error: {0}")]
+    Store(String),
+}
+
+/// Realized P&L of one position-reducing fill, attributed to the strategy that placed it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StrategyTrade {
+    pub id: Uuid,
+    pub strategy_id: String,
+    pub trading_pair: String,
+    pub realized_pnl: Decimal,
+    pub closed_at: DateTime<Utc>,
+    /// Parameter version active when the trade closed; null for trades predating versioning
+    #[serde(default)]
+    pub version: Option<u32>,
+}
+
+impl StrategyTrade {
+    /// Trade recorded from a fill, if the fill realized P&L
+    pub fn from_fill(update: &FillUpdate) -> Option<Self> {
+        update.realized_pnl.map(|realized_pnl| Self {
+            id: Uuid::new_v4(),
+            strategy_id: update.strategy_id.clone(),
+            trading_pair: update.trading_pair.clone(),
+            realized_pnl,
+            closed_at: update.fill.timestamp,
+            version: None,
+        })
+    }
+}
+
+/// One bucket of a strategy's cumulative P&L series
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct EquityPoint {
+    pub strategy_id: String,
+    pub granularity: CandleInterval,
+    pub bucket_start: DateTime<Utc>,
+    /// P&L realized within the bucket
+    pub pnl: Decimal,
+    /// P&L realized since the strategy's first trade, as of the bucket's last trade
+    pub cumulative_pnl: Decimal,
+    pub trade_count: u64,
+}
+
+/// Running total and open bucket per granularity for one strategy
+#[derive(Debug, Clone, Default)]
+struct EquityCurve {
+    cumulative_pnl: Decimal,
+    latest: HashMap<CandleInterval, EquityPoint>,
+}
+
+impl EquityCurve {
+    /// Rebuilds the running state from the latest persisted bucket of each granularity
+    fn from_latest(points: Vec<EquityPoint>) -> Self {
+        let cumulative_pnl = points
+            .iter()
+            .max_by_key(|point| point.bucket_start)
+            .map(|point| point.cumulative_pnl)
+            .unwrap_or_default();
+        Self {
+            cumulative_pnl,
+            latest: points.into_iter().map(|point| (point.granularity, point)).collect(),
+        }
+    }
+
+    /// Folds a trade into every granularity and returns the buckets it changed. Trades
+    /// arriving after a later bucket has opened are folded into that bucket so the
+    /// persisted series never needs rewriting.
+    fn apply(&mut self, trade: &StrategyTrade) -> Vec<EquityPoint> {
+        self.cumulative_pnl += trade.realized_pnl;
+
+        let mut changed = Vec::with_capacity(EQUITY_GRANULARITIES.len());
+        for granularity in EQUITY_GRANULARITIES {
+            let bucket_start = granularity.bucket_start(trade.closed_at);
+            let empty = EquityPoint {
+                strategy_id: trade.strategy_id.clone(),
+                granularity,
+                bucket_start,
+                pnl: Decimal::ZERO,
+                cumulative_pnl: Decimal::ZERO,
+                trade_count: 0,
+            };
+            let point = self.latest.entry(granularity).or_insert_with(|| empty.clone());
+            if bucket_start > point.bucket_start {
+                *point = empty;
+            }
+            point.pnl += trade.realized_pnl;
+            point.trade_count += 1;
+            point.cumulative_pnl = self.cumulative_pnl;
+            changed.push(point.clone());
+        }
+        changed
+    }
+}
+
+/// A strategy's current metrics as ranked on the leaderboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LeaderboardEntry {
+    pub strategy_id: String,
+    pub strategy_type: StrategyType,
+    pub state: StrategyState,
+    /// Null until the strategy has enough trades for the ratio to mean anything
+    pub sharpe_ratio: Option<Decimal>,
+    /// Trades the Sharpe ratio is computed over
+    pub sample_size: u32,
+    pub win_rate: Decimal,
+    pub roi: Decimal,
+    pub max_drawdown: Decimal,
+    pub total_trades: u32,
+    /// Capital allocated to the strategy, when allocations are tracked
+    pub allocation_used: Option<Decimal>,
+    pub performance_score: Decimal,
+    pub realized_pnl: Decimal,
+    pub updated_at: DateTime<Utc>,
+    /// Present on deleted strategies, which are only listed on request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+/// Column the leaderboard can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardColumn {
+    SharpeRatio,
+    WinRate,
+    Roi,
+    MaxDrawdown,
+    TotalTrades,
+    AllocationUsed,
+    PerformanceScore,
+    RealizedPnl,
+}
+
+impl LeaderboardColumn {
+    fn value(&self, entry: &LeaderboardEntry) -> Option<Decimal> {
+        match self {
+            Self::SharpeRatio => entry.sharpe_ratio,
+            Self::WinRate => Some(entry.win_rate),
+            Self::Roi => Some(entry.roi),
+            Self::MaxDrawdown => Some(entry.max_drawdown),
+            Self::TotalTrades => Some(Decimal::from(entry.total_trades)),
+            Self::AllocationUsed => entry.allocation_used,
+            Self::PerformanceScore => Some(entry.performance_score),
+            Self::RealizedPnl => Some(entry.realized_pnl),
+        }
+    }
+}
+
+impl FromStr for LeaderboardColumn {
+    type Err = PerformanceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sharpe_ratio" => Ok(Self::SharpeRatio),
+            "win_rate" => Ok(Self::WinRate),
+            "roi" => Ok(Self::Roi),
+            "max_drawdown" => Ok(Self::MaxDrawdown),
+            "total_trades" => Ok(Self::TotalTrades),
+            "allocation_used" => Ok(Self::AllocationUsed),
+            "performance_score" => Ok(Self::PerformanceScore),
+            "realized_pnl" => Ok(Self::RealizedPnl),
+            other => Err(PerformanceError::UnknownColumn(other.to_string())),
+        }
+    }
+}
+
+/// Leaderboard sort direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl FromStr for SortOrder {
+    type Err = PerformanceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "asc" => Ok(Self::Asc),
+            "desc" => Ok(Self::Desc),
+            other => Err(PerformanceError::UnknownOrder(other.to_string())),
+        }
+    }
+}
+
+/// Sorts entries by a column; strategies without a value sort last in either direction
+pub fn sort_leaderboard(entries: &mut [LeaderboardEntry], column: LeaderboardColumn, order: SortOrder) {
+    entries.sort_by(|a, b| {
+        let ordering = match (column.value(a), column.value(b)) {
+            (Some(x), Some(y)) if order == SortOrder::Asc => x.cmp(&y),
+            (Some(x), Some(y)) => y.cmp(&x),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        ordering.then_with(|| a.strategy_id.cmp(&b.strategy_id))
+    });
+}
+
+/// Maps the strategy id a fill was attributed to onto the strategy and parameter version it
+/// belongs to; A/B challengers trade under their own id but report under their strategy's
+pub trait VersionTagger: Send + Sync {
+    fn tag(&self, strategy_id: &str) -> (String, Option<u32>);
+}
+
+/// Persistence for per-strategy trades and their materialized equity buckets
+#[async_trait]
+pub trait PerformanceStore: Send + Sync {
+    async fn record_trade(&self, trade: &StrategyTrade) -> Result<(), PerformanceError>;
+    /// Most recent trades of a strategy, newest first
+    async fn trades(&self, strategy_id: &str, limit: usize) -> Result<Vec<StrategyTrade>, PerformanceError>;
+    /// Trades of one parameter version closed at or after `since`, oldest first
+    async fn version_trades(
+        &self,
+        strategy_id: &str,
+        version: u32,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<StrategyTrade>, PerformanceError>;
+    /// Inserts or replaces buckets keyed by strategy, granularity and bucket start
+    async fn upsert_equity(&self, points: &[EquityPoint]) -> Result<(), PerformanceError>;
+    /// Most recent bucket of each granularity for a strategy
+    async fn latest_equity(&self, strategy_id: &str) -> Result<Vec<EquityPoint>, PerformanceError>;
+    /// Most recent buckets in ascending time order
+    async fn equity(
+        &self,
+        strategy_id: &str,
+        granularity: CandleInterval,
+        limit: usize,
+    ) -> Result<Vec<EquityPoint>, PerformanceError>;
+}
+
+/// Leaderboard refresh schedule and Sharpe sample threshold
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerformanceConfig {
+    pub refresh_interval: Duration,
+    /// Strategies with fewer trades than this report a null Sharpe ratio
+    pub min_sharpe_sample: u32,
+}
+
+impl Default for PerformanceConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval: DEFAULT_REFRESH_INTERVAL,
+            min_sharpe_sample: DEFAULT_MIN_SHARPE_SAMPLE,
+        }
+    }
+}
+
+/// Persists strategy trades, materializes equity curves and publishes leaderboard changes
+pub struct PerformanceService {
+    config: PerformanceConfig,
+    strategies: Arc<RwLock<HashMap<String, Strategy>>>,
+    store: SyncRwLock<Option<Arc<dyn PerformanceStore>>>,
+    versions: SyncRwLock<Option<Arc<dyn VersionTagger>>>,
+    curves: Mutex<HashMap<String, EquityCurve>>,
+    published: Mutex<HashMap<String, LeaderboardEntry>>,
+    updates: broadcast::Sender<LeaderboardEntry>,
+}
+
+impl std::fmt::Debug for PerformanceService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PerformanceService")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl PerformanceService {
+    pub fn new(config: PerformanceConfig, strategies: Arc<RwLock<HashMap<String, Strategy>>>) -> Self {
+        let (updates, _) = broadcast::channel(UPDATE_CHANNEL_CAPACITY);
+        Self {
+            config,
+            strategies,
+            store: SyncRwLock::new(None),
+            versions: SyncRwLock::new(None),
+            curves: Mutex::new(HashMap::new()),
+            published: Mutex::new(HashMap::new()),
+            updates,
+        }
+    }
+
+    /// Persists trades and equity buckets
+    pub fn set_store(&self, store: Arc<dyn PerformanceStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    /// Tags recorded fills with the parameter version that produced them
+    pub fn set_versions(&self, versions: Arc<dyn VersionTagger>) {
+        *self.versions.write() = Some(versions);
+    }
+
+    /// Receives leaderboard entries whose metrics changed on recompute
+    pub fn subscribe(&self) -> broadcast::Receiver<LeaderboardEntry> {
+        self.updates.subscribe()
+    }
+
+    fn store(&self) -> Result<Arc<dyn PerformanceStore>, PerformanceError> {
+        self.store
+            .read()
+            .clone()
+            .ok_or_else(|| PerformanceError::Store("performance store unavailable".to_string()))
+    }
+
+    /// Records the realized P&L of a fill, if any, without failing the fill path
+    pub async fn record_fill(&self, update: &FillUpdate) {
+        let Some(mut trade) = StrategyTrade::from_fill(update) else {
+            return;
+        };
+        if let Some(versions) = self.versions.read().clone() {
+            (trade.strategy_id, trade.version) = versions.tag(&trade.strategy_id);
+        }
+        // Deleted strategies keep their history but take no new trades
+        if self
+            .strategies
+            .read()
+            .await
+            .get(&trade.strategy_id)
+            .map_or(false, Strategy::is_deleted)
+        {
+            counter!(format!("{}.deleted_strategy_fills", METRICS_PREFIX), 1);
+            return;
+        }
+        if let Err(e) = self.record_trade(&trade).await {
+            warn!(strategy_id = %trade.strategy_id, "Failed to record strategy trade: {}", e);
+        }
+    }
+
+    /// Persists a trade and folds it into the strategy's equity buckets
+    pub async fn record_trade(&self, trade: &StrategyTrade) -> Result<Vec<EquityPoint>, PerformanceError> {
+        let store = self.store()?;
+        store.record_trade(trade).await?;
+
+        let mut curves = self.curves.lock().await;
+        let curve = Self::load_curve(&mut curves, store.as_ref(), &trade.strategy_id).await?;
+        let points = curve.apply(trade);
+        store.upsert_equity(&points).await?;
+
+        counter!(format!("{}.trades_recorded", METRICS_PREFIX), 1);
+        Ok(points)
+    }
+
+    async fn load_curve<'a>(
+        curves: &'a mut HashMap<String, EquityCurve>,
+        store: &dyn PerformanceStore,
+        strategy_id: &str,
+    ) -> Result<&'a mut EquityCurve, PerformanceError> {
+        if !curves.contains_key(strategy_id) {
+            let latest = store.latest_equity(strategy_id).await?;
+            curves.insert(strategy_id.to_string(), EquityCurve::from_latest(latest));
+        }
+        Ok(curves.get_mut(strategy_id).expect("curve just loaded"))
+    }
+
+    /// Cumulative P&L series for a strategy at a materialized granularity
+    pub async fn equity(
+        &self,
+        strategy_id: &str,
+        granularity: CandleInterval,
+    ) -> Result<Vec<EquityPoint>, PerformanceError> {
+        if !EQUITY_GRANULARITIES.contains(&granularity) {
+            return Err(PerformanceError::UnsupportedGranularity(granularity.as_str().to_string()));
+        }
+        if !self.strategies.read().await.contains_key(strategy_id) {
+            return Err(PerformanceError::UnknownStrategy(strategy_id.to_string()));
+        }
+        self.store()?.equity(strategy_id, granularity, MAX_EQUITY_POINTS).await
+    }
+
+    /// A strategy's most recent trades, newest first
+    pub async fn trades(&self, strategy_id: &str, limit: usize) -> Result<Vec<StrategyTrade>, PerformanceError> {
+        if !self.strategies.read().await.contains_key(strategy_id) {
+            return Err(PerformanceError::UnknownStrategy(strategy_id.to_string()));
+        }
+        self.store()?.trades(strategy_id, limit.min(MAX_TRADES_PER_REQUEST)).await
+    }
+
+    /// Trades one parameter version closed since a point in time, oldest first
+    pub async fn version_trades(
+        &self,
+        strategy_id: &str,
+        version: u32,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<StrategyTrade>, PerformanceError> {
+        self.store()?.version_trades(strategy_id, version, since).await
+    }
+
+    /// Current metrics for every strategy, sorted by the given column; deleted strategies
+    /// are listed only when asked for
+    pub async fn leaderboard(
+        &self,
+        column: LeaderboardColumn,
+        order: SortOrder,
+        include_deleted: bool,
+    ) -> Result<Vec<LeaderboardEntry>, PerformanceError> {
+        let mut entries = self.entries(include_deleted).await?;
+        sort_leaderboard(&mut entries, column, order);
+        Ok(entries)
+    }
+
+    async fn entries(&self, include_deleted: bool) -> Result<Vec<LeaderboardEntry>, PerformanceError> {
+        let store = self.store.read().clone();
+        let mut curves = self.curves.lock().await;
+        let strategies = self.strategies.read().await;
+
+        let mut entries = Vec::with_capacity(strategies.len());
+        for (strategy_id, strategy) in strategies.iter() {
+            // A/B challengers report under the strategy they are trialled for
+            if is_variant(strategy_id) || (strategy.is_deleted() && !include_deleted) {
+                continue;
+            }
+            let realized_pnl = match &store {
+                Some(store) => Self::load_curve(&mut curves, store.as_ref(), strategy_id).await?.cumulative_pnl,
+                None => curves.get(strategy_id).map(|curve| curve.cumulative_pnl).unwrap_or_default(),
+            };
+            entries.push(self.entry(strategy_id, strategy, realized_pnl).await);
+        }
+        Ok(entries)
+    }
+
+    async fn entry(&self, strategy_id: &str, strategy: &Strategy, realized_pnl: Decimal) -> LeaderboardEntry {
+        let metrics = &strategy.metrics;
+        let sample_size = metrics.total_trades;
+        LeaderboardEntry {
+            strategy_id: strategy_id.to_string(),
+            strategy_type: strategy.strategy_type.clone(),
+            state: strategy.state.clone(),
+            sharpe_ratio: (sample_size >= self.config.min_sharpe_sample).then_some(metrics.sharpe_ratio),
+            sample_size,
+            win_rate: metrics.win_rate,
+            roi: metrics.roi,
+            max_drawdown: metrics.max_drawdown,
+            total_trades: metrics.total_trades,
+            allocation_used: strategy.allocated_capital().await,
+            performance_score: strategy.performance_score,
+            realized_pnl,
+            updated_at: strategy.updated_at,
+            deleted_at: strategy.deleted_at,
+        }
+    }
+
+    /// Recomputes every entry and publishes those that changed since the last refresh
+    pub async fn refresh(&self) -> Result<usize, PerformanceError> {
+        let entries = self.entries(false).await?;
+        let mut published = self.published.lock().await;
+
+        let mut changed = 0;
+        for entry in entries {
+            if published.get(&entry.strategy_id) == Some(&entry) {
+                continue;
+            }
+            let _ = self.updates.send(entry.clone());
+            published.insert(entry.strategy_id.clone(), entry);
+            changed += 1;
+        }
+        counter!(format!("{}.entries_published", METRICS_PREFIX), changed as u64);
+        Ok(changed)
+    }
+
+    /// Recomputes the leaderboard on the configured interval
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.refresh_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.refresh().await {
+                    warn!("Failed to refresh strategy performance: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Records realized P&L from every fill as it lands
+    pub fn spawn_fill_listener(
+        self: Arc<Self>,
+        mut fills: broadcast::Receiver<FillUpdate>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match fills.recv().await {
+                    Ok(update) => self.record_fill(&update).await,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Performance listener skipped {} fills", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::strategy::StrategyParams;
+    use crate::utils::percent::{Bps, Percent};
+    use chrono::TimeZone;
+    use parking_lot::Mutex as SyncMutex;
+    use rust_decimal_macros::dec;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        trades: SyncMutex<Vec<StrategyTrade>>,
+        equity: SyncMutex<Vec<EquityPoint>>,
+    }
+
+    #[async_trait]
+    impl PerformanceStore for MemoryStore {
+        async fn record_trade(&self, trade: &StrategyTrade) -> Result<(), PerformanceError> {
+            self.trades.lock().push(trade.clone());
+            Ok(())
+        }
+
+        async fn trades(&self, strategy_id: &str, limit: usize) -> Result<Vec<StrategyTrade>, PerformanceError> {
+            let mut trades: Vec<StrategyTrade> = self
+                .trades
+                .lock()
+                .iter()
+                .filter(|trade| trade.strategy_id == strategy_id)
+                .cloned()
+                .collect();
+            trades.sort_by(|a, b| b.closed_at.cmp(&a.closed_at));
+            trades.truncate(limit);
+            Ok(trades)
+        }
+
+        async fn version_trades(
+            &self,
+            strategy_id: &str,
+            version: u32,
+            since: DateTime<Utc>,
+        ) -> Result<Vec<StrategyTrade>, PerformanceError> {
+            let mut trades: Vec<StrategyTrade> = self
+                .trades
+                .lock()
+                .iter()
+                .filter(|trade| {
+                    trade.strategy_id == strategy_id && trade.version == Some(version) && trade.closed_at >= since
+                })
+                .cloned()
+                .collect();
+            trades.sort_by_key(|trade| trade.closed_at);
+            Ok(trades)
+        }
+
+        async fn upsert_equity(&self, points: &[EquityPoint]) -> Result<(), PerformanceError> {
+            let mut equity = self.equity.lock();
+            for point in points {
+                equity.retain(|existing| {
+                    (&existing.strategy_id, existing.granularity, existing.bucket_start)
+                        != (&point.strategy_id, point.granularity, point.bucket_start)
+                });
+                equity.push(point.clone());
+            }
+            Ok(())
+        }
+
+        async fn latest_equity(&self, strategy_id: &str) -> Result<Vec<EquityPoint>, PerformanceError> {
+            let equity = self.equity.lock();
+            Ok(EQUITY_GRANULARITIES
+                .iter()
+                .filter_map(|granularity| {
+                    equity
+                        .iter()
+                        .filter(|point| point.strategy_id == strategy_id && point.granularity == *granularity)
+                        .max_by_key(|point| point.bucket_start)
+                        .cloned()
+                })
+                .collect())
+        }
+
+        async fn equity(
+            &self,
+            strategy_id: &str,
+            granularity: CandleInterval,
+            limit: usize,
+        ) -> Result<Vec<EquityPoint>, PerformanceError> {
+            let mut points: Vec<EquityPoint> = self
+                .equity
+                .lock()
+                .iter()
+                .filter(|point| point.strategy_id == strategy_id && point.granularity == granularity)
+                .cloned()
+                .collect();
+            points.sort_by_key(|point| point.bucket_start);
+            let skip = points.len().saturating_sub(limit);
+            Ok(points.split_off(skip))
+        }
+    }
+
+    fn strategy() -> Strategy {
+        let mut strategy = Strategy::new(
+            StrategyType::Grid,
+            StrategyParams {
+                position_size_bps: Bps::new(1000),
+                grid_levels: Some(10),
+                stop_loss_pct: Percent::from_percent(dec!(-5)),
+                take_profit_pct: Percent::from_percent(dec!(1)),
+                max_slippage_bps: Bps::new(100),
+                exchanges: vec!["jupiter".to_string()],
+                risk_factor: dec!(0.5),
+                allowed_regimes: Vec::new(),
+                market_making: None,
+            },
+            vec!["SOL/USDC".to_string()],
+        )
+        .unwrap();
+        strategy.state = StrategyState::Active;
+        strategy
+    }
+
+    fn service(strategies: Vec<(&str, Strategy)>, store: Arc<MemoryStore>) -> PerformanceService {
+        let strategies = strategies
+            .into_iter()
+            .map(|(id, strategy)| (id.to_string(), strategy))
+            .collect();
+        let service = PerformanceService::new(PerformanceConfig::default(), Arc::new(RwLock::new(strategies)));
+        service.set_store(store);
+        service
+    }
+
+    fn trade(strategy_id: &str, pnl: Decimal, closed_at: DateTime<Utc>) -> StrategyTrade {
+        StrategyTrade {
+            id: Uuid::new_v4(),
+            strategy_id: strategy_id.to_string(),
+            trading_pair: "SOL/USDC".to_string(),
+            realized_pnl: pnl,
+            closed_at,
+            version: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_equity_materialized_incrementally_and_resumed() {
+        let store = Arc::new(MemoryStore::default());
+        let service = service(vec![("grid", strategy())], store.clone());
+        let start = Utc.with_ymd_and_hms(2024, 3, 1, 10, 0, 0).unwrap();
+
+        service.record_trade(&trade("grid", dec!(10), start)).await.unwrap();
+        service.record_trade(&trade("grid", dec!(-4), start + chrono::Duration::minutes(30))).await.unwrap();
+        service.record_trade(&trade("grid", dec!(7), start + chrono::Duration::minutes(70))).await.unwrap();
+
+        let hourly = service.equity("grid", CandleInterval::OneHour).await.unwrap();
+        let series: Vec<(Decimal, Decimal, u64)> = hourly
+            .iter()
+            .map(|point| (point.pnl, point.cumulative_pnl, point.trade_count))
+            .collect();
+        assert_eq!(series, vec![(dec!(6), dec!(6), 2), (dec!(7), dec!(13), 1)]);
+        assert_eq!(service.equity("grid", CandleInterval::OneMinute).await.unwrap().len(), 3);
+        assert_eq!(store.trades.lock().len(), 3);
+        let trades = service.trades("grid", 2).await.unwrap();
+        assert_eq!(trades.iter().map(|t| t.realized_pnl).collect::<Vec<_>>(), vec![dec!(7), dec!(-4)]);
+
+        // A restarted service continues the running total from the persisted buckets
+        let restarted = service(vec![("grid", strategy())], store.clone());
+        restarted.record_trade(&trade("grid", dec!(1), start + chrono::Duration::minutes(75))).await.unwrap();
+        let hourly = restarted.equity("grid", CandleInterval::OneHour).await.unwrap();
+        assert_eq!(hourly.last().unwrap().cumulative_pnl, dec!(14));
+        assert_eq!(hourly.last().unwrap().trade_count, 2);
+
+        assert!(matches!(
+            restarted.equity("grid", CandleInterval::OneSecond).await,
+            Err(PerformanceError::UnsupportedGranularity(_))
+        ));
+        assert!(matches!(
+            restarted.equity("missing", CandleInterval::OneHour).await,
+            Err(PerformanceError::UnknownStrategy(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_leaderboard_nulls_sharpe_below_sample_and_sorts() {
+        let mut seasoned = strategy();
+        seasoned.metrics.total_trades = 40;
+        seasoned.metrics.sharpe_ratio = dec!(1.2);
+        seasoned.metrics.roi = dec!(3);
+        let mut fresh = strategy();
+        fresh.metrics.total_trades = 5;
+        fresh.metrics.sharpe_ratio = dec!(9.9);
+        fresh.metrics.roi = dec!(8);
+
+        let store = Arc::new(MemoryStore::default());
+        let service = service(vec![("seasoned", seasoned), ("fresh", fresh)], store);
+
+        let by_sharpe = service
+            .leaderboard(LeaderboardColumn::SharpeRatio, SortOrder::Desc, false)
+            .await
+            .unwrap();
+        assert_eq!(by_sharpe[0].strategy_id, "seasoned");
+        assert_eq!(by_sharpe[0].sharpe_ratio, Some(dec!(1.2)));
+        assert_eq!(by_sharpe[1].sharpe_ratio, None);
+        assert_eq!(by_sharpe[1].sample_size, 5);
+
+        let by_roi = service.leaderboard("roi".parse().unwrap(), "asc".parse().unwrap(), false).await.unwrap();
+        let order: Vec<&str> = by_roi.iter().map(|entry| entry.strategy_id.as_str()).collect();
+        assert_eq!(order, vec!["seasoned", "fresh"]);
+        assert!("sharpe".parse::<LeaderboardColumn>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_publishes_only_changed_entries() {
+        let store = Arc::new(MemoryStore::default());
+        let service = service(vec![("grid", strategy()), ("arb", strategy())], store);
+        let mut updates = service.subscribe();
+
+        assert_eq!(service.refresh().await.unwrap(), 2);
+        assert_eq!(service.refresh().await.unwrap(), 0);
+
+        service.record_trade(&trade("grid", dec!(5), Utc::now())).await.unwrap();
+        assert_eq!(service.refresh().await.unwrap(), 1);
+
+        let published: Vec<LeaderboardEntry> = std::iter::from_fn(|| updates.try_recv().ok()).collect();
+        assert_eq!(published.len(), 3);
+        assert_eq!(published[2].strategy_id, "grid");
+        assert_eq!(published[2].realized_pnl, dec!(5));
+    }
+}
@@ -0,0 +1,498 @@
+//! Write-behind persistence keeping the database off the trade-critical path. Execution
+//! records, attribution ledger entries and strategy audit entries are queued in memory and
+//! flushed in order by a background writer that retries with backoff while the database is
+//! unavailable, so an outage delays bookkeeping instead of stalling execution. The queue is
+//! bounded: past capacity the oldest write is dropped and counted. Backlog depth and the age
+//! of the oldest unflushed write are reported on the health endpoint, persistence is flagged
+//! degraded while the writer is failing or lagging, and trading can optionally pause new
+//! positions once the backlog passes a bound.
+//!
+//! Market data (candles, public trades) and state snapshots already persist from their own
+//! background tasks with batching and retries, and reservations live in memory, so the
+//! execution path awaits no database write.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - futures = "0.3"
+//! - parking_lot = "0.12"
+//! - metrics = "0.20"
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::future::BoxFuture;
+use metrics::{counter, gauge, histogram};
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio::sync::Notify;
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+
+// Persistence constants
+const METRICS_PREFIX: &str = "trading_bot.persistence";
+const DEFAULT_CAPACITY: usize = 50_000;
+const DEFAULT_DEGRADED_BACKLOG: usize = 1_000;
+const DEFAULT_DEGRADED_AGE: Duration = Duration::from_secs(30);
+const DEFAULT_WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(250);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+const IDLE_POLL: Duration = Duration::from_secs(1);
+const PAUSE_BACKLOG_VAR: &str = "PERSISTENCE_PAUSE_BACKLOG";
+
+/// A queued write; called again on every retry
+type WriteFn = Arc<dyn Fn() -> BoxFuture<'static, Result<(), String>> + Send + Sync>;
+
+/// Bounds and retry timing of the write-behind queue
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersistenceConfig {
+    /// Most writes held in memory; past it the oldest is dropped
+    pub capacity: usize,
+    /// Backlog at which persistence is reported degraded
+    pub degraded_backlog: usize,
+    /// Age of the oldest unflushed write at which persistence is reported degraded
+    pub degraded_age: Duration,
+    /// Backlog above which new positions are refused; `None` never pauses trading
+    pub pause_backlog: Option<usize>,
+    /// Longest a single write may take before it counts as failed
+    pub write_timeout: Duration,
+    pub retry_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for PersistenceConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_CAPACITY,
+            degraded_backlog: DEFAULT_DEGRADED_BACKLOG,
+            degraded_age: DEFAULT_DEGRADED_AGE,
+            pause_backlog: None,
+            write_timeout: DEFAULT_WRITE_TIMEOUT,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+}
+
+impl PersistenceConfig {
+    /// Defaults, pausing trading past `PERSISTENCE_PAUSE_BACKLOG` queued writes when set
+    pub fn from_env() -> Result<Self, String> {
+        let pause_backlog = std::env::var(PAUSE_BACKLOG_VAR)
+            .ok()
+            .map(|v| {
+                v.trim()
+                    .parse::<usize>()
+                    .map_err(|_| format!("{} must be a whole number of writes", PAUSE_BACKLOG_VAR))
+            })
+            .transpose()?;
+        Ok(Self {
+            pause_backlog,
+            ..Self::default()
+        })
+    }
+}
+
+struct PendingWrite {
+    sequence: u64,
+    kind: &'static str,
+    enqueued_at: Instant,
+    attempts: u32,
+    write: WriteFn,
+}
+
+/// Persistence state reported on the health endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct PersistenceStatus {
+    /// Writes waiting to be flushed
+    pub backlog: usize,
+    /// Age of the oldest unflushed write
+    pub oldest_age_ms: Option<u64>,
+    /// Whether the writer is failing or lagging behind
+    pub degraded: bool,
+    /// Whether the backlog currently pauses new positions
+    pub trading_paused: bool,
+    /// Writes dropped because the queue was full
+    pub dropped: u64,
+}
+
+/// Bounded in-memory queue of non-critical writes, flushed in order by a background writer
+pub struct PersistenceQueue {
+    config: PersistenceConfig,
+    pending: Mutex<VecDeque<PendingWrite>>,
+    wake: Notify,
+    /// Set while the front write keeps failing
+    failing: AtomicBool,
+    degraded: AtomicBool,
+    dropped: AtomicU64,
+    sequence: AtomicU64,
+}
+
+impl std::fmt::Debug for PersistenceQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersistenceQueue")
+            .field("config", &self.config)
+            .field("backlog", &self.backlog())
+            .field("failing", &self.failing.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl PersistenceQueue {
+    pub fn new(config: PersistenceConfig) -> Self {
+        Self {
+            config,
+            pending: Mutex::new(VecDeque::new()),
+            wake: Notify::new(),
+            failing: AtomicBool::new(false),
+            degraded: AtomicBool::new(false),
+            dropped: AtomicU64::new(0),
+            sequence: AtomicU64::new(0),
+        }
+    }
+
+    pub fn config(&self) -> &PersistenceConfig {
+        &self.config
+    }
+
+    /// Queues a write without waiting for it; `write` is called again on each retry
+    pub fn enqueue<F, Fut>(&self, kind: &'static str, write: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let write: WriteFn = Arc::new(move || Box::pin(write()));
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        let dropped = {
+            let mut pending = self.pending.lock();
+            pending.push_back(PendingWrite {
+                sequence,
+                kind,
+                enqueued_at: Instant::now(),
+                attempts: 0,
+                write,
+            });
+            if pending.len() > self.config.capacity {
+                pending.pop_front()
+            } else {
+                None
+            }
+        };
+        counter!(format!("{}.enqueued", METRICS_PREFIX), 1, "kind" => kind);
+        if let Some(dropped) = dropped {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            counter!(format!("{}.dropped", METRICS_PREFIX), 1, "kind" => dropped.kind);
+            error!(
+                kind = dropped.kind,
+                attempts = dropped.attempts,
+                capacity = self.config.capacity,
+                "Persistence queue full; dropped oldest unflushed write"
+            );
+        }
+        self.wake.notify_one();
+    }
+
+    /// Writes waiting to be flushed
+    pub fn backlog(&self) -> usize {
+        self.pending.lock().len()
+    }
+
+    /// Age of the oldest unflushed write
+    pub fn oldest_age(&self) -> Option<Duration> {
+        self.pending.lock().front().map(|write| write.enqueued_at.elapsed())
+    }
+
+    /// Whether the writer is failing, or the backlog is past its depth or age bound
+    pub fn is_degraded(&self) -> bool {
+        self.failing.load(Ordering::Relaxed)
+            || self.backlog() >= self.config.degraded_backlog
+            || self.oldest_age().map_or(false, |age| age >= self.config.degraded_age)
+    }
+
+    /// Whether new positions are refused until the backlog drains
+    pub fn pauses_trading(&self) -> bool {
+        self.config.pause_backlog.map_or(false, |bound| self.backlog() > bound)
+    }
+
+    pub fn status(&self) -> PersistenceStatus {
+        PersistenceStatus {
+            backlog: self.backlog(),
+            oldest_age_ms: self.oldest_age().map(|age| age.as_millis() as u64),
+            degraded: self.is_degraded(),
+            trading_paused: self.pauses_trading(),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Flushes queued writes in order until the queue is empty or a write fails. A write
+    /// stays queued, and counted in the backlog, until it succeeds.
+    pub async fn flush(&self) -> Result<usize, String> {
+        let mut flushed = 0;
+        loop {
+            let front = self
+                .pending
+                .lock()
+                .front()
+                .map(|write| (write.sequence, write.kind, write.write.clone()));
+            let Some((sequence, kind, write)) = front else {
+                self.failing.store(false, Ordering::Relaxed);
+                return Ok(flushed);
+            };
+            let result = match tokio::time::timeout(self.config.write_timeout, write()).await {
+                Ok(result) => result,
+                Err(_) => Err(format!("write timed out after {:?}", self.config.write_timeout)),
+            };
+
+            // The write may have been dropped for capacity while in flight
+            let mut pending = self.pending.lock();
+            let current = pending.front_mut().filter(|write| write.sequence == sequence);
+            match result {
+                Ok(()) => {
+                    if let Some(write) = current {
+                        histogram!(
+                            format!("{}.flush_lag_ms", METRICS_PREFIX),
+                            write.enqueued_at.elapsed().as_millis() as f64
+                        );
+                        pending.pop_front();
+                    }
+                    flushed += 1;
+                    counter!(format!("{}.flushed", METRICS_PREFIX), 1, "kind" => kind);
+                }
+                Err(e) => {
+                    let attempts = current.map_or(0, |write| {
+                        write.attempts += 1;
+                        write.attempts
+                    });
+                    drop(pending);
+                    self.failing.store(true, Ordering::Relaxed);
+                    counter!(format!("{}.write_failures", METRICS_PREFIX), 1, "kind" => kind);
+                    warn!(kind, attempts, "Deferred write failed; will retry: {}", e);
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// Runs the background writer, backing off while writes fail
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut backoff = self.config.retry_backoff;
+            loop {
+                let result = self.flush().await;
+                self.report();
+                match result {
+                    Ok(_) => {
+                        backoff = self.config.retry_backoff;
+                        tokio::select! {
+                            _ = self.wake.notified() => {}
+                            _ = tokio::time::sleep(IDLE_POLL) => {}
+                        }
+                    }
+                    Err(_) => {
+                        tokio::time::sleep(backoff).await;
+                        backoff = (backoff * 2).min(self.config.max_backoff);
+                    }
+                }
+            }
+        })
+    }
+
+    /// Publishes backlog gauges and logs transitions in and out of degraded mode
+    fn report(&self) {
+        let status = self.status();
+        gauge!(format!("{}.backlog", METRICS_PREFIX), status.backlog as f64);
+        gauge!(
+            format!("{}.oldest_age_ms", METRICS_PREFIX),
+            status.oldest_age_ms.unwrap_or(0) as f64
+        );
+        gauge!(format!("{}.degraded", METRICS_PREFIX), if status.degraded { 1.0 } else { 0.0 });
+        let was_degraded = self.degraded.swap(status.degraded, Ordering::Relaxed);
+        if status.degraded && !was_degraded {
+            warn!(
+                backlog = status.backlog,
+                oldest_age_ms = ?status.oldest_age_ms,
+                trading_paused = status.trading_paused,
+                "Persistence degraded; writes are queued in memory"
+            );
+        } else if was_degraded && !status.degraded {
+            info!(dropped = status.dropped, "Persistence caught up");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    use crate::execution_engine::benchmarks::ExecutionBenchmark;
+    use crate::execution_engine::stats::{
+        ExecutionRecord, ExecutionStats, ExecutionStatsConfig, ExecutionStatsService, ExecutionStatsStore, StatsError,
+        StatsWindow,
+    };
+    use crate::risk_manager::exposure::TradeSide;
+
+    /// Execution store standing in for the database; while unavailable, writes hang until
+    /// the statement timeout as they do against an unreachable server
+    #[derive(Default)]
+    struct OutageStore {
+        available: AtomicBool,
+        records: Mutex<Vec<ExecutionRecord>>,
+    }
+
+    #[async_trait]
+    impl ExecutionStatsStore for OutageStore {
+        async fn record(&self, record: &ExecutionRecord) -> Result<(), StatsError> {
+            if !self.available.load(Ordering::SeqCst) {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+            self.records.lock().push(record.clone());
+            Ok(())
+        }
+
+        async fn records_since(&self, _: DateTime<Utc>) -> Result<Vec<ExecutionRecord>, StatsError> {
+            Ok(self.records.lock().clone())
+        }
+
+        async fn replace_stats(&self, _: &[ExecutionStats]) -> Result<(), StatsError> {
+            Ok(())
+        }
+
+        async fn load_stats(&self, _: &str, _: StatsWindow) -> Result<Vec<ExecutionStats>, StatsError> {
+            Ok(Vec::new())
+        }
+
+        async fn record_benchmark(&self, _: Uuid, _: &ExecutionBenchmark) -> Result<(), StatsError> {
+            Ok(())
+        }
+    }
+
+    fn config() -> PersistenceConfig {
+        PersistenceConfig {
+            capacity: 3,
+            degraded_backlog: 2,
+            pause_backlog: Some(1),
+            write_timeout: Duration::from_millis(50),
+            retry_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(20),
+            ..PersistenceConfig::default()
+        }
+    }
+
+    fn execution() -> ExecutionRecord {
+        ExecutionRecord {
+            id: Uuid::new_v4(),
+            trading_pair: "SOL/USDC".to_string(),
+            exchange: "jupiter".to_string(),
+            side: TradeSide::Buy,
+            requested_size: dec!(10),
+            filled_size: dec!(10),
+            expected_price: dec!(23.50),
+            executed_price: Some(dec!(23.52)),
+            fee: dec!(0.07),
+            latency_ms: 120,
+            mev_value: Decimal::ZERO,
+            executed_at: Utc::now(),
+            benchmark: None,
+            book_depth: None,
+            volatility_bps: None,
+            compute: None,
+            event_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trade_completes_during_outage_and_persists_after() {
+        let store = Arc::new(OutageStore::default());
+        let queue = Arc::new(PersistenceQueue::new(config()));
+        let stats = ExecutionStatsService::new(ExecutionStatsConfig::default(), store.clone())
+            .with_persistence(queue.clone());
+        let writer = queue.clone().spawn();
+
+        // The database goes away mid-trade; recording the execution must not wait on it
+        let started = Instant::now();
+        stats.record(execution()).await;
+        stats.record(execution()).await;
+        assert!(started.elapsed() < Duration::from_millis(20), "trade path blocked on persistence");
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert!(store.records.lock().is_empty());
+        let status = queue.status();
+        assert_eq!(status.backlog, 2);
+        assert!(status.degraded);
+        assert!(status.trading_paused);
+        assert!(status.oldest_age_ms.unwrap() >= 100);
+
+        // Once the database returns both records land, in order, and the flags clear
+        store.available.store(true, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(store.records.lock().len(), 2);
+        let status = queue.status();
+        assert_eq!(status.backlog, 0);
+        assert!(!status.degraded);
+        assert!(!status.trading_paused);
+        writer.abort();
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_drops_oldest() {
+        let queue = PersistenceQueue::new(config());
+        let written = Arc::new(Mutex::new(Vec::new()));
+        for i in 0..4 {
+            let written = written.clone();
+            queue.enqueue("test", move || {
+                let written = written.clone();
+                async move {
+                    written.lock().push(i);
+                    Ok(())
+                }
+            });
+        }
+        assert_eq!(queue.status().dropped, 1);
+
+        assert_eq!(queue.flush().await.unwrap(), 3);
+        assert_eq!(*written.lock(), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_write_retried_in_order() {
+        let queue = PersistenceQueue::new(config());
+        let attempts = Arc::new(AtomicU64::new(0));
+        let written = Arc::new(Mutex::new(Vec::new()));
+        {
+            let (attempts, written) = (attempts.clone(), written.clone());
+            queue.enqueue("test", move || {
+                let (attempts, written) = (attempts.clone(), written.clone());
+                async move {
+                    if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                        return Err("connection refused".to_string());
+                    }
+                    written.lock().push("first");
+                    Ok(())
+                }
+            });
+        }
+        {
+            let written = written.clone();
+            queue.enqueue("test", move || {
+                let written = written.clone();
+                async move {
+                    written.lock().push("second");
+                    Ok(())
+                }
+            });
+        }
+
+        assert!(queue.flush().await.is_err());
+        assert!(queue.is_degraded());
+        assert_eq!(queue.backlog(), 2);
+        assert_eq!(queue.flush().await.unwrap(), 2);
+        assert_eq!(*written.lock(), vec!["first", "second"]);
+        assert!(!queue.is_degraded());
+    }
+}
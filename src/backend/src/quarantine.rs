@@ -0,0 +1,532 @@
+//! Per-pair kill switches. Quarantining a pair stops it from taking new entries while every
+//! other pair keeps trading; in flatten-and-block mode its open position is also closed
+//! through the emergency closure path and every further order on it is refused. Strategy
+//! signals for a quarantined pair are dropped with an audit entry, and collector data for it
+//! is either forwarded flagged, for monitoring, or withheld. Quarantines are persisted so
+//! they survive a restart, and only an admin can lift one, giving a reason.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+//! - utoipa = "3.5"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::RwLock as SyncRwLock;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+
+use crate::models::pair::TradingPair;
+use crate::models::MarketData;
+
+// Quarantine constants
+const METRICS_PREFIX: &str = "trading_bot.quarantine";
+pub const AUDIT_SIGNAL_QUARANTINED: &str = "signal_quarantined";
+
+/// Quarantine errors
+#[derive(Error, Debug)]
+pub enum QuarantineError {
+    #[error("invalid trading pair: {0}")]
+    InvalidPair(String),
+    #[error("pair already quarantined: {0}")]
+    AlreadyQuarantined(String),
+    #[error("pair not quarantined: {0}")]
+    NotQuarantined(String),
+    #[error("a reason is required to lift a quarantine")]
+    MissingReason,
+    #[error("pair {trading_pair} is quarantined ({mode})")]
+    Blocked { trading_pair: String, mode: QuarantineMode },
+    #[error("failed to flatten {trading_pair}: {reason}")]
+    Flatten { trading_pair: String, reason: String },
+    #[error("store error: {0}")]
+    Store(String),
+}
+
+/// How far a quarantine reaches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuarantineMode {
+    /// Orders that open or add to a position are refused; reductions still go through
+    NoNewEntries,
+    /// The open position is closed and every further order is refused
+    FlattenAndBlock,
+}
+
+impl QuarantineMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NoNewEntries => "no-new-entries",
+            Self::FlattenAndBlock => "flatten-and-block",
+        }
+    }
+}
+
+impl std::fmt::Display for QuarantineMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for QuarantineMode {
+    type Err = QuarantineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "no-new-entries" => Ok(Self::NoNewEntries),
+            "flatten-and-block" => Ok(Self::FlattenAndBlock),
+            other => Err(QuarantineError::Store(format!("unknown quarantine mode: {}", other))),
+        }
+    }
+}
+
+/// Request quarantining a pair
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct QuarantineRequest {
+    pub trading_pair: String,
+    pub mode: QuarantineMode,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// A pair currently quarantined
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct QuarantineEntry {
+    pub trading_pair: String,
+    pub mode: QuarantineMode,
+    pub reason: Option<String>,
+    pub quarantined_by: String,
+    pub quarantined_at: DateTime<Utc>,
+}
+
+/// Result of quarantining a pair
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct QuarantineOutcome {
+    pub entry: QuarantineEntry,
+    /// Signed size of the position closed in flatten-and-block mode; negative for shorts
+    pub flattened: Option<Decimal>,
+}
+
+/// Admin request lifting a quarantine
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseRequest {
+    pub reason: String,
+}
+
+/// A lifted quarantine, kept as history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuarantineRelease {
+    pub entry: QuarantineEntry,
+    pub released_by: String,
+    pub reason: String,
+    pub released_at: DateTime<Utc>,
+}
+
+/// Persistence for quarantined pairs and lifted quarantines
+#[async_trait]
+pub trait QuarantineStore: Send + Sync {
+    async fn save(&self, entry: &QuarantineEntry) -> Result<(), QuarantineError>;
+    /// Removes the active quarantine and records its release
+    async fn release(&self, release: &QuarantineRelease) -> Result<(), QuarantineError>;
+    async fn active(&self) -> Result<Vec<QuarantineEntry>, QuarantineError>;
+}
+
+/// The bot as seen by a flatten-and-block quarantine
+#[async_trait]
+pub trait QuarantineTarget: Send + Sync {
+    /// Closes the open position on a pair through the emergency closure path, returning
+    /// its signed size; zero when there was nothing to close
+    async fn flatten_pair(&self, trading_pair: &str) -> Result<Decimal, String>;
+}
+
+/// Whether collector data for quarantined pairs keeps flowing
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuarantineConfig {
+    /// Forward quarantined pairs' market data flagged for monitoring instead of withholding it
+    pub keep_collecting: bool,
+}
+
+impl Default for QuarantineConfig {
+    fn default() -> Self {
+        Self { keep_collecting: true }
+    }
+}
+
+/// Quarantined pairs, checked on every order, signal and market data update
+pub struct QuarantineList {
+    config: QuarantineConfig,
+    entries: SyncRwLock<HashMap<String, QuarantineEntry>>,
+    store: SyncRwLock<Option<Arc<dyn QuarantineStore>>>,
+    target: SyncRwLock<Option<Arc<dyn QuarantineTarget>>>,
+}
+
+impl std::fmt::Debug for QuarantineList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuarantineList")
+            .field("config", &self.config)
+            .field("quarantined", &self.entries.read().len())
+            .finish()
+    }
+}
+
+impl QuarantineList {
+    pub fn new(config: QuarantineConfig) -> Self {
+        Self {
+            config,
+            entries: SyncRwLock::new(HashMap::new()),
+            store: SyncRwLock::new(None),
+            target: SyncRwLock::new(None),
+        }
+    }
+
+    /// Persists quarantines so they survive a restart
+    pub fn set_store(&self, store: Arc<dyn QuarantineStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    /// Bot whose positions flatten-and-block quarantines close
+    pub fn set_target(&self, target: Arc<dyn QuarantineTarget>) {
+        *self.target.write() = Some(target);
+    }
+
+    /// Restores active quarantines from the store, returning how many are in force
+    pub async fn load(&self) -> Result<usize, QuarantineError> {
+        let store = self.store.read().clone();
+        let Some(store) = store else {
+            return Ok(0);
+        };
+        let active = store.active().await?;
+        let mut entries = self.entries.write();
+        for entry in active {
+            warn!(trading_pair = %entry.trading_pair, mode = entry.mode.as_str(), "Pair quarantine restored");
+            entries.insert(entry.trading_pair.clone(), entry);
+        }
+        Ok(entries.len())
+    }
+
+    /// Quarantines a pair; in flatten-and-block mode the pair is blocked before its position
+    /// is closed, so it stays blocked even when the closing order fails
+    pub async fn quarantine(
+        &self,
+        request: QuarantineRequest,
+        quarantined_by: &str,
+        now: DateTime<Utc>,
+    ) -> Result<QuarantineOutcome, QuarantineError> {
+        let trading_pair = canonical(&request.trading_pair)?;
+        if self.entries.read().contains_key(&trading_pair) {
+            return Err(QuarantineError::AlreadyQuarantined(trading_pair));
+        }
+        let entry = QuarantineEntry {
+            trading_pair: trading_pair.clone(),
+            mode: request.mode,
+            reason: request.reason,
+            quarantined_by: quarantined_by.to_string(),
+            quarantined_at: now,
+        };
+
+        let store = self.store.read().clone();
+        if let Some(store) = store {
+            store.save(&entry).await?;
+        }
+        self.entries.write().insert(trading_pair.clone(), entry.clone());
+        counter!(format!("{}.quarantined", METRICS_PREFIX), 1, "mode" => entry.mode.as_str());
+        warn!(
+            trading_pair = %trading_pair,
+            mode = entry.mode.as_str(),
+            quarantined_by,
+            reason = entry.reason.as_deref().unwrap_or_default(),
+            "Pair quarantined"
+        );
+
+        let flattened = match entry.mode {
+            QuarantineMode::NoNewEntries => None,
+            QuarantineMode::FlattenAndBlock => Some(self.flatten(&trading_pair).await?),
+        };
+        Ok(QuarantineOutcome { entry, flattened })
+    }
+
+    /// Lifts a quarantine, recording who lifted it and why
+    pub async fn release(
+        &self,
+        trading_pair: &str,
+        released_by: &str,
+        reason: &str,
+        now: DateTime<Utc>,
+    ) -> Result<QuarantineRelease, QuarantineError> {
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(QuarantineError::MissingReason);
+        }
+        let trading_pair = canonical(trading_pair)?;
+        let entry = self
+            .entries
+            .read()
+            .get(&trading_pair)
+            .cloned()
+            .ok_or_else(|| QuarantineError::NotQuarantined(trading_pair.clone()))?;
+        let release = QuarantineRelease {
+            entry,
+            released_by: released_by.to_string(),
+            reason: reason.to_string(),
+            released_at: now,
+        };
+
+        let store = self.store.read().clone();
+        if let Some(store) = store {
+            store.release(&release).await?;
+        }
+        self.entries.write().remove(&trading_pair);
+        counter!(format!("{}.released", METRICS_PREFIX), 1);
+        info!(trading_pair = %trading_pair, released_by, reason, "Pair quarantine lifted");
+        Ok(release)
+    }
+
+    /// Pairs currently quarantined, in pair order
+    pub fn entries(&self) -> Vec<QuarantineEntry> {
+        let mut entries: Vec<_> = self.entries.read().values().cloned().collect();
+        entries.sort_by(|a, b| a.trading_pair.cmp(&b.trading_pair));
+        entries
+    }
+
+    /// Quarantine mode in force for a pair, if any
+    pub fn mode(&self, trading_pair: &str) -> Option<QuarantineMode> {
+        let entries = self.entries.read();
+        if entries.is_empty() {
+            return None;
+        }
+        let trading_pair = canonical(trading_pair).ok()?;
+        entries.get(&trading_pair).map(|entry| entry.mode)
+    }
+
+    /// Refuses orders on a quarantined pair; under no-new-entries, orders that only reduce
+    /// the open position still go through
+    pub fn check(&self, trading_pair: &str, reduces_position: bool) -> Result<(), QuarantineError> {
+        match self.mode(trading_pair) {
+            None => Ok(()),
+            Some(QuarantineMode::NoNewEntries) if reduces_position => Ok(()),
+            Some(mode) => {
+                counter!(format!("{}.orders_blocked", METRICS_PREFIX), 1, "mode" => mode.as_str());
+                Err(QuarantineError::Blocked {
+                    trading_pair: trading_pair.to_string(),
+                    mode,
+                })
+            }
+        }
+    }
+
+    /// Flags collector data for a quarantined pair, or withholds it when collection for
+    /// quarantined pairs is off
+    pub fn screen(&self, market_data: MarketData) -> Option<MarketData> {
+        if self.mode(market_data.trading_pair()).is_none() {
+            return Some(market_data);
+        }
+        if !self.config.keep_collecting {
+            counter!(format!("{}.market_data_withheld", METRICS_PREFIX), 1);
+            return None;
+        }
+        Some(market_data.with_quarantined(true))
+    }
+
+    async fn flatten(&self, trading_pair: &str) -> Result<Decimal, QuarantineError> {
+        let target = self.target.read().clone();
+        let Some(target) = target else {
+            return Err(QuarantineError::Flatten {
+                trading_pair: trading_pair.to_string(),
+                reason: "no execution target attached".to_string(),
+            });
+        };
+        match target.flatten_pair(trading_pair).await {
+            Ok(size) => {
+                info!(trading_pair, size = %size, "Quarantined pair flattened");
+                Ok(size)
+            }
+            Err(reason) => {
+                error!(trading_pair, "Failed to flatten quarantined pair: {}", reason);
+                Err(QuarantineError::Flatten {
+                    trading_pair: trading_pair.to_string(),
+                    reason,
+                })
+            }
+        }
+    }
+}
+
+/// Canonical pair key, so "sol-usdc" and "SOL/USDC" name the same quarantine
+fn canonical(trading_pair: &str) -> Result<String, QuarantineError> {
+    TradingPair::parse(trading_pair)
+        .map(TradingPair::into_string)
+        .map_err(|e| QuarantineError::InvalidPair(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use parking_lot::Mutex;
+    use rust_decimal_macros::dec;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        active: Mutex<HashMap<String, QuarantineEntry>>,
+        releases: Mutex<Vec<QuarantineRelease>>,
+    }
+
+    #[async_trait]
+    impl QuarantineStore for MemoryStore {
+        async fn save(&self, entry: &QuarantineEntry) -> Result<(), QuarantineError> {
+            self.active.lock().insert(entry.trading_pair.clone(), entry.clone());
+            Ok(())
+        }
+
+        async fn release(&self, release: &QuarantineRelease) -> Result<(), QuarantineError> {
+            self.active.lock().remove(&release.entry.trading_pair);
+            self.releases.lock().push(release.clone());
+            Ok(())
+        }
+
+        async fn active(&self) -> Result<Vec<QuarantineEntry>, QuarantineError> {
+            Ok(self.active.lock().values().cloned().collect())
+        }
+    }
+
+    /// Bot stand-in holding signed positions by pair and recording the pairs it closed
+    #[derive(Default)]
+    struct TestBot {
+        positions: Mutex<HashMap<String, Decimal>>,
+        closed: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl QuarantineTarget for TestBot {
+        async fn flatten_pair(&self, trading_pair: &str) -> Result<Decimal, String> {
+            self.closed.lock().push(trading_pair.to_string());
+            Ok(self.positions.lock().remove(trading_pair).unwrap_or_default())
+        }
+    }
+
+    fn at(minute: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap() + chrono::Duration::minutes(minute)
+    }
+
+    fn request(trading_pair: &str, mode: QuarantineMode) -> QuarantineRequest {
+        QuarantineRequest {
+            trading_pair: trading_pair.to_string(),
+            mode,
+            reason: Some("depeg".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_no_new_entries_allows_reductions_only() {
+        let list = QuarantineList::new(QuarantineConfig::default());
+        let bot = Arc::new(TestBot::default());
+        bot.positions.lock().insert("BONK/USDC".to_string(), dec!(1000));
+        list.set_target(bot.clone());
+
+        let outcome = list
+            .quarantine(request("bonk-usdc", QuarantineMode::NoNewEntries), "ops", at(0))
+            .await
+            .unwrap();
+        assert_eq!(outcome.entry.trading_pair, "BONK/USDC");
+        assert_eq!(outcome.flattened, None);
+        assert!(bot.closed.lock().is_empty());
+
+        assert!(matches!(
+            list.check("BONK/USDC", false),
+            Err(QuarantineError::Blocked { mode: QuarantineMode::NoNewEntries, .. })
+        ));
+        assert!(list.check("BONK/USDC", true).is_ok());
+        // Other pairs keep trading
+        assert!(list.check("SOL/USDC", false).is_ok());
+        assert!(matches!(
+            list.quarantine(request("BONK/USDC", QuarantineMode::FlattenAndBlock), "ops", at(1)).await,
+            Err(QuarantineError::AlreadyQuarantined(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_flatten_and_block_closes_only_that_pair() {
+        let list = QuarantineList::new(QuarantineConfig::default());
+        let bot = Arc::new(TestBot::default());
+        bot.positions.lock().insert("BONK/USDC".to_string(), dec!(-250));
+        bot.positions.lock().insert("SOL/USDC".to_string(), dec!(4));
+        list.set_target(bot.clone());
+
+        let outcome = list
+            .quarantine(request("BONK/USDC", QuarantineMode::FlattenAndBlock), "ops", at(0))
+            .await
+            .unwrap();
+        assert_eq!(outcome.flattened, Some(dec!(-250)));
+        assert_eq!(*bot.closed.lock(), vec!["BONK/USDC".to_string()]);
+        assert_eq!(bot.positions.lock().get("SOL/USDC"), Some(&dec!(4)));
+
+        // Every order on the pair is refused, reductions included
+        assert!(list.check("BONK/USDC", true).is_err());
+        assert!(list.check("SOL/USDC", false).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_collector_data_flagged_or_withheld() {
+        let tick = || MarketData::new("BONK/USDC".to_string(), "jupiter".to_string(), dec!(0.00002), dec!(100)).unwrap();
+
+        let monitoring = QuarantineList::new(QuarantineConfig::default());
+        assert!(!monitoring.screen(tick()).unwrap().is_quarantined());
+        monitoring
+            .quarantine(request("BONK/USDC", QuarantineMode::NoNewEntries), "ops", at(0))
+            .await
+            .unwrap();
+        assert!(monitoring.screen(tick()).unwrap().is_quarantined());
+
+        let silent = QuarantineList::new(QuarantineConfig { keep_collecting: false });
+        silent
+            .quarantine(request("BONK/USDC", QuarantineMode::NoNewEntries), "ops", at(0))
+            .await
+            .unwrap();
+        assert!(silent.screen(tick()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_quarantine_survives_restart_until_released() {
+        let store = Arc::new(MemoryStore::default());
+        let first = QuarantineList::new(QuarantineConfig::default());
+        first.set_store(store.clone());
+        first.set_target(Arc::new(TestBot::default()));
+        first
+            .quarantine(request("BONK/USDC", QuarantineMode::FlattenAndBlock), "ops", at(0))
+            .await
+            .unwrap();
+
+        // A new process restores the quarantine without flattening again
+        let restarted = QuarantineList::new(QuarantineConfig::default());
+        restarted.set_store(store.clone());
+        let bot = Arc::new(TestBot::default());
+        restarted.set_target(bot.clone());
+        assert_eq!(restarted.load().await.unwrap(), 1);
+        assert_eq!(restarted.mode("BONK/USDC"), Some(QuarantineMode::FlattenAndBlock));
+        assert!(restarted.check("BONK/USDC", true).is_err());
+        assert!(bot.closed.lock().is_empty());
+
+        // Lifting requires a reason and is kept as history
+        assert!(matches!(
+            restarted.release("BONK/USDC", "admin", "  ", at(30)).await,
+            Err(QuarantineError::MissingReason)
+        ));
+        let release = restarted.release("BONK/USDC", "admin", "peg restored", at(30)).await.unwrap();
+        assert_eq!(release.entry.quarantined_by, "ops");
+        assert!(restarted.check("BONK/USDC", false).is_ok());
+        assert!(store.active.lock().is_empty());
+        assert_eq!(store.releases.lock()[0].reason, "peg restored");
+
+        let again = QuarantineList::new(QuarantineConfig::default());
+        again.set_store(store);
+        assert_eq!(again.load().await.unwrap(), 0);
+    }
+}
@@ -0,0 +1,687 @@
+//! Market regime detection. Closed candles from the OHLCV aggregator feed a rolling window per
+//! pair from which realized volatility, trend strength (least-squares fit of log price) and
+//! the recent volume regime are computed and classified as quiet, trending, volatile or
+//! dislocated. Regime changes are published on the signal bus and persisted, and the strategy
+//! driver skips strategies whose parameters exclude the pair's current regime.
+//!
+//! Classification is hysteretic: the thresholds guarding the current regime are relaxed by an
+//! exit band, and a new regime must be seen on consecutive candles before it is adopted.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+//! - utoipa = "3.5"
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::{Mutex, RwLock as SyncRwLock};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::data_collector::ohlcv::{Candle, CandleEvent, CandleInterval};
+use crate::models::strategy::StrategyAuditEntry;
+use crate::signals::SignalBus;
+
+// Regime detection constants
+const METRICS_PREFIX: &str = "trading_bot.regimes";
+const DEFAULT_WINDOW: usize = 60;
+const DEFAULT_VOLUME_WINDOW: usize = 5;
+const DEFAULT_VOLATILE_VOLATILITY: f64 = 0.004;
+const DEFAULT_DISLOCATED_VOLATILITY: f64 = 0.03;
+const DEFAULT_DISLOCATED_VOLUME_RATIO: f64 = 4.0;
+const DEFAULT_TREND_R_SQUARED: f64 = 0.6;
+const DEFAULT_TREND_MIN_SLOPE: f64 = 0.0005;
+const DEFAULT_EXIT_BAND: f64 = 0.2;
+const DEFAULT_CONFIRMATIONS: usize = 3;
+pub const AUDIT_REGIME_DISABLED: &str = "regime_disabled";
+pub const AUDIT_REGIME_ENABLED: &str = "regime_enabled";
+
+/// Regime detection error types
+#[derive(Error, Debug)]
+pub enum RegimeError {
+    #[error("unknown market regime: {0}")]
+    UnknownRegime(String),
+    #[error("regime store error: {0}")]
+    Store(String),
+}
+
+/// Market conditions a pair is trading in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MarketRegime {
+    /// Low volatility without direction; grids and market making thrive
+    Quiet,
+    /// Persistent drift in one direction
+    Trending,
+    /// Wide swings without persistent direction
+    Volatile,
+    /// Extreme volatility or a volume surge, typically around news or liquidations
+    Dislocated,
+}
+
+impl MarketRegime {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Quiet => "quiet",
+            Self::Trending => "trending",
+            Self::Volatile => "volatile",
+            Self::Dislocated => "dislocated",
+        }
+    }
+}
+
+impl fmt::Display for MarketRegime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MarketRegime {
+    type Err = RegimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "quiet" => Ok(Self::Quiet),
+            "trending" => Ok(Self::Trending),
+            "volatile" => Ok(Self::Volatile),
+            "dislocated" => Ok(Self::Dislocated),
+            other => Err(RegimeError::UnknownRegime(other.to_string())),
+        }
+    }
+}
+
+/// Classification thresholds; volatility and slope are per candle of `interval`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegimeConfig {
+    /// Candle interval the detector classifies
+    pub interval: CandleInterval,
+    /// Candles in the rolling window; no regime is reported until it is full
+    pub window: usize,
+    /// Most recent candles whose volume is compared against the rest of the window
+    pub volume_window: usize,
+    /// Standard deviation of log returns at or above which a pair is volatile
+    pub volatile_volatility: f64,
+    pub dislocated_volatility: f64,
+    /// Recent to baseline volume ratio at or above which a pair is dislocated
+    pub dislocated_volume_ratio: f64,
+    /// Fit of log price against time at or above which a pair is trending
+    pub trend_r_squared: f64,
+    /// Minimum absolute log price slope for a trend
+    pub trend_min_slope: f64,
+    /// Fraction the current regime's thresholds are relaxed by before it is left
+    pub exit_band: f64,
+    /// Consecutive candles a new regime must be classified on before it is adopted
+    pub confirmations: usize,
+}
+
+impl Default for RegimeConfig {
+    fn default() -> Self {
+        Self {
+            interval: CandleInterval::OneMinute,
+            window: DEFAULT_WINDOW,
+            volume_window: DEFAULT_VOLUME_WINDOW,
+            volatile_volatility: DEFAULT_VOLATILE_VOLATILITY,
+            dislocated_volatility: DEFAULT_DISLOCATED_VOLATILITY,
+            dislocated_volume_ratio: DEFAULT_DISLOCATED_VOLUME_RATIO,
+            trend_r_squared: DEFAULT_TREND_R_SQUARED,
+            trend_min_slope: DEFAULT_TREND_MIN_SLOPE,
+            exit_band: DEFAULT_EXIT_BAND,
+            confirmations: DEFAULT_CONFIRMATIONS,
+        }
+    }
+}
+
+/// Statistics a classification was made from
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct RegimeFeatures {
+    /// Standard deviation of log returns per candle
+    pub volatility: f64,
+    /// Coefficient of determination of log price against candle index
+    pub r_squared: f64,
+    /// Log price change per candle of the fitted line
+    pub slope: f64,
+    /// Average volume of the most recent candles over that of the rest of the window
+    pub volume_ratio: f64,
+}
+
+impl RegimeFeatures {
+    /// Computes the features of a window of (close, volume) samples, oldest first
+    fn compute(samples: &VecDeque<(f64, f64)>, volume_window: usize) -> Self {
+        let logs: Vec<f64> = samples.iter().map(|(close, _)| close.ln()).collect();
+        let returns: Vec<f64> = logs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        let volatility = if returns.len() > 1 {
+            sample_variance(&returns).max(0.0).sqrt()
+        } else {
+            0.0
+        };
+
+        // Least-squares fit of log price against candle index
+        let mean_x = (logs.len() as f64 - 1.0) / 2.0;
+        let mean_y = mean(&logs);
+        let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+        for (i, y) in logs.iter().enumerate() {
+            let (dx, dy) = (i as f64 - mean_x, y - mean_y);
+            sxy += dx * dy;
+            sxx += dx * dx;
+            syy += dy * dy;
+        }
+        let slope = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+        let r_squared = if sxx > 0.0 && syy > 0.0 { (sxy * sxy / (sxx * syy)).min(1.0) } else { 0.0 };
+
+        // Recent volume against the candles before it, so a surge does not dilute its baseline
+        let volumes: Vec<f64> = samples.iter().map(|(_, volume)| *volume).collect();
+        let (baseline, recent) = volumes.split_at(volumes.len().saturating_sub(volume_window.max(1)));
+        let baseline = if baseline.is_empty() { mean(recent) } else { mean(baseline) };
+        let volume_ratio = if baseline > 0.0 { mean(recent) / baseline } else { 1.0 };
+
+        Self {
+            volatility,
+            r_squared,
+            slope,
+            volume_ratio,
+        }
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn sample_variance(values: &[f64]) -> f64 {
+    let mean = mean(values);
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64
+}
+
+/// Regime change of a pair, as published on the signal bus and persisted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegimeChange {
+    pub id: Uuid,
+    pub trading_pair: String,
+    /// None for the first classification of a pair
+    pub previous: Option<MarketRegime>,
+    pub regime: MarketRegime,
+    pub features: RegimeFeatures,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Whether a strategy may trade a pair in its current regime
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegimeGate {
+    pub open: bool,
+    pub regime: Option<MarketRegime>,
+    /// Audit entry when this check disabled or re-enabled the strategy on the pair
+    pub audit: Option<StrategyAuditEntry>,
+}
+
+/// Persistence for regime changes
+#[async_trait]
+pub trait RegimeStore: Send + Sync {
+    async fn record_change(&self, change: &RegimeChange) -> Result<(), RegimeError>;
+    /// Most recent change of every pair
+    async fn latest(&self) -> Result<Vec<RegimeChange>, RegimeError>;
+}
+
+/// Rolling classification state of one pair
+#[derive(Debug, Default)]
+struct PairRegime {
+    samples: VecDeque<(f64, f64)>,
+    regime: Option<MarketRegime>,
+    /// Candidate regime and how many consecutive candles it has been classified on
+    pending: Option<(MarketRegime, usize)>,
+}
+
+/// Classifies each pair's market regime and gates strategies on it
+pub struct RegimeDetector {
+    config: RegimeConfig,
+    pairs: Mutex<HashMap<String, PairRegime>>,
+    /// (strategy, pair) combinations currently disabled by their pair's regime
+    gated: Mutex<HashSet<(String, String)>>,
+    store: SyncRwLock<Option<Arc<dyn RegimeStore>>>,
+    bus: SyncRwLock<Option<Arc<SignalBus>>>,
+}
+
+impl fmt::Debug for RegimeDetector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegimeDetector")
+            .field("config", &self.config)
+            .field("pairs", &self.pairs.lock().len())
+            .finish()
+    }
+}
+
+impl RegimeDetector {
+    pub fn new(config: RegimeConfig) -> Self {
+        Self {
+            config,
+            pairs: Mutex::new(HashMap::new()),
+            gated: Mutex::new(HashSet::new()),
+            store: SyncRwLock::new(None),
+            bus: SyncRwLock::new(None),
+        }
+    }
+
+    pub fn set_store(&self, store: Arc<dyn RegimeStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    /// Publishes regime changes on the signal bus
+    pub fn set_bus(&self, bus: Arc<SignalBus>) {
+        *self.bus.write() = Some(bus);
+    }
+
+    pub fn config(&self) -> &RegimeConfig {
+        &self.config
+    }
+
+    /// Current regime of a pair, None until its window first fills
+    pub fn regime(&self, trading_pair: &str) -> Option<MarketRegime> {
+        self.pairs.lock().get(trading_pair).and_then(|pair| pair.regime)
+    }
+
+    /// Current regime of every classified pair
+    pub fn regimes(&self) -> HashMap<String, MarketRegime> {
+        self.pairs
+            .lock()
+            .iter()
+            .filter_map(|(pair, state)| state.regime.map(|regime| (pair.clone(), regime)))
+            .collect()
+    }
+
+    /// Standard deviation of a pair's log returns per candle over its current window, once it
+    /// holds at least three candles
+    pub fn volatility(&self, trading_pair: &str) -> Option<f64> {
+        let pairs = self.pairs.lock();
+        let samples = &pairs.get(trading_pair)?.samples;
+        if samples.len() < 3 {
+            return None;
+        }
+        Some(RegimeFeatures::compute(samples, self.config.volume_window).volatility)
+    }
+
+    /// Classifies features given the pair's current regime; the current regime's thresholds
+    /// are relaxed by the exit band so readings hovering at a boundary keep it
+    pub fn classify(&self, features: &RegimeFeatures, current: Option<MarketRegime>) -> MarketRegime {
+        let config = &self.config;
+        let threshold = |regime: MarketRegime, value: f64| {
+            if current == Some(regime) {
+                value * (1.0 - config.exit_band)
+            } else {
+                value
+            }
+        };
+
+        if features.volatility >= threshold(MarketRegime::Dislocated, config.dislocated_volatility)
+            || features.volume_ratio >= threshold(MarketRegime::Dislocated, config.dislocated_volume_ratio)
+        {
+            MarketRegime::Dislocated
+        } else if features.r_squared >= threshold(MarketRegime::Trending, config.trend_r_squared)
+            && features.slope.abs() >= threshold(MarketRegime::Trending, config.trend_min_slope)
+        {
+            MarketRegime::Trending
+        } else if features.volatility >= threshold(MarketRegime::Volatile, config.volatile_volatility) {
+            MarketRegime::Volatile
+        } else {
+            MarketRegime::Quiet
+        }
+    }
+
+    /// Adds a closed candle to its pair's window and returns the regime change it caused
+    pub async fn observe(&self, candle: &Candle) -> Option<RegimeChange> {
+        if candle.interval != self.config.interval {
+            return None;
+        }
+        let close = candle.close.to_f64().filter(|close| *close > 0.0)?;
+        let volume = candle.volume.to_f64().unwrap_or(0.0).max(0.0);
+
+        let change = {
+            let mut pairs = self.pairs.lock();
+            let state = pairs.entry(candle.trading_pair.clone()).or_default();
+            state.samples.push_back((close, volume));
+            while state.samples.len() > self.config.window.max(2) {
+                state.samples.pop_front();
+            }
+            if state.samples.len() < self.config.window.max(2) {
+                return None;
+            }
+
+            let features = RegimeFeatures::compute(&state.samples, self.config.volume_window);
+            let candidate = self.classify(&features, state.regime);
+            if state.regime == Some(candidate) {
+                state.pending = None;
+                return None;
+            }
+
+            // The first classification is adopted at once; later ones need confirming
+            let seen = match state.pending {
+                Some((pending, seen)) if pending == candidate => seen + 1,
+                _ => 1,
+            };
+            if state.regime.is_some() && seen < self.config.confirmations.max(1) {
+                state.pending = Some((candidate, seen));
+                return None;
+            }
+
+            let previous = state.regime.replace(candidate);
+            state.pending = None;
+            RegimeChange {
+                id: Uuid::new_v4(),
+                trading_pair: candle.trading_pair.clone(),
+                previous,
+                regime: candidate,
+                features,
+                changed_at: candle.close_time(),
+            }
+        };
+
+        self.on_change(&change).await;
+        Some(change)
+    }
+
+    async fn on_change(&self, change: &RegimeChange) {
+        info!(
+            trading_pair = %change.trading_pair,
+            previous = ?change.previous,
+            regime = %change.regime,
+            volatility = change.features.volatility,
+            r_squared = change.features.r_squared,
+            volume_ratio = change.features.volume_ratio,
+            "Market regime changed"
+        );
+        counter!(
+            format!("{}.changes", METRICS_PREFIX),
+            1,
+            "trading_pair" => change.trading_pair.clone(),
+            "regime" => change.regime.as_str()
+        );
+
+        let store = self.store.read().clone();
+        if let Some(store) = store {
+            if let Err(e) = store.record_change(change).await {
+                warn!(trading_pair = %change.trading_pair, "Failed to persist regime change: {}", e);
+            }
+        }
+
+        let bus = self.bus.read().clone();
+        if let Some(bus) = bus {
+            bus.publish_regime(change.clone());
+        }
+    }
+
+    /// Checks whether a strategy allowed to trade in `allowed` regimes may trade a pair now.
+    /// An empty list allows every regime, and pairs without a regime yet are not gated.
+    pub fn gate(&self, strategy_id: &str, trading_pair: &str, allowed: &[MarketRegime]) -> RegimeGate {
+        let regime = self.regime(trading_pair);
+        let open = allowed.is_empty() || regime.map_or(true, |regime| allowed.contains(&regime));
+
+        let key = (strategy_id.to_string(), trading_pair.to_string());
+        let mut gated = self.gated.lock();
+        let audit = match (open, gated.contains(&key)) {
+            (false, false) => {
+                gated.insert(key);
+                let regime = regime.map_or("unknown", |regime| regime.as_str());
+                counter!(format!("{}.strategies_disabled", METRICS_PREFIX), 1, "strategy_id" => strategy_id.to_string());
+                warn!(strategy_id, trading_pair, regime, "Strategy disabled by market regime");
+                Some(StrategyAuditEntry::new(
+                    strategy_id,
+                    AUDIT_REGIME_DISABLED,
+                    format!(
+                        "{} regime on {} is not among allowed regimes [{}]",
+                        regime,
+                        trading_pair,
+                        allowed.iter().map(MarketRegime::as_str).collect::<Vec<_>>().join(", ")
+                    ),
+                ))
+            }
+            (true, true) => {
+                gated.remove(&key);
+                let regime = regime.map_or("unknown", |regime| regime.as_str());
+                info!(strategy_id, trading_pair, regime, "Strategy re-enabled by market regime");
+                Some(StrategyAuditEntry::new(
+                    strategy_id,
+                    AUDIT_REGIME_ENABLED,
+                    format!("{} regime on {} is allowed", regime, trading_pair),
+                ))
+            }
+            _ => None,
+        };
+
+        RegimeGate { open, regime, audit }
+    }
+
+    /// Seeds each pair's regime from the store so hysteresis carries across restarts
+    pub async fn restore(&self) -> Result<usize, RegimeError> {
+        let store = self.store.read().clone();
+        let Some(store) = store else {
+            return Ok(0);
+        };
+        let latest = store.latest().await?;
+        let mut pairs = self.pairs.lock();
+        for change in &latest {
+            pairs.entry(change.trading_pair.clone()).or_default().regime = Some(change.regime);
+        }
+        Ok(latest.len())
+    }
+
+    /// Restores persisted regimes, then classifies every closed candle. Corrections are
+    /// ignored; a late tick rarely moves a window's statistics.
+    pub fn spawn(self: Arc<Self>, mut candles: broadcast::Receiver<CandleEvent>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            match self.restore().await {
+                Ok(restored) => info!(restored, "Restored market regimes"),
+                Err(e) => warn!("Failed to restore market regimes: {}", e),
+            }
+            loop {
+                match candles.recv().await {
+                    Ok(CandleEvent::Closed { candle }) => {
+                        self.observe(&candle).await;
+                    }
+                    Ok(CandleEvent::Correction { .. }) => {}
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        counter!(format!("{}.lagged", METRICS_PREFIX), skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signals::SignalBusConfig;
+    use chrono::{Duration as ChronoDuration, TimeZone};
+    use rust_decimal::Decimal;
+
+    const PAIR: &str = "SOL/USDC";
+    const WINDOW: usize = 20;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        changes: Mutex<Vec<RegimeChange>>,
+    }
+
+    #[async_trait]
+    impl RegimeStore for MemoryStore {
+        async fn record_change(&self, change: &RegimeChange) -> Result<(), RegimeError> {
+            self.changes.lock().push(change.clone());
+            Ok(())
+        }
+
+        async fn latest(&self) -> Result<Vec<RegimeChange>, RegimeError> {
+            let mut latest: HashMap<String, RegimeChange> = HashMap::new();
+            for change in self.changes.lock().iter() {
+                latest.insert(change.trading_pair.clone(), change.clone());
+            }
+            Ok(latest.into_values().collect())
+        }
+    }
+
+    fn new_detector() -> RegimeDetector {
+        RegimeDetector::new(RegimeConfig {
+            window: WINDOW,
+            ..RegimeConfig::default()
+        })
+    }
+
+    fn candle(i: usize, close: f64, volume: f64) -> Candle {
+        let open_time = Utc.timestamp_opt(1_700_000_000, 0).unwrap() + ChronoDuration::minutes(i as i64);
+        let close = Decimal::from_f64_retain(close).unwrap().round_dp(8);
+        Candle::from_parts(
+            PAIR.to_string(),
+            CandleInterval::OneMinute,
+            open_time,
+            close,
+            close,
+            close,
+            close,
+            Decimal::from_f64_retain(volume).unwrap().round_dp(8),
+            1,
+        )
+    }
+
+    /// Flat price with a 1bp zigzag and steady volume
+    fn quiet(i: usize) -> (f64, f64) {
+        (if i % 2 == 0 { 100.0 } else { 100.01 }, 1000.0)
+    }
+
+    /// 0.3% drift per candle with a 0.05% zigzag
+    fn trending(i: usize) -> (f64, f64) {
+        let noise = if i % 2 == 0 { 1.0005 } else { 0.9995 };
+        (100.0 * 1.003f64.powi(i as i32) * noise, 1000.0)
+    }
+
+    /// 1% swings around a flat price
+    fn volatile(i: usize) -> (f64, f64) {
+        (if i % 2 == 0 { 100.0 } else { 101.0 }, 1000.0)
+    }
+
+    async fn feed(detector: &RegimeDetector, path: impl Fn(usize) -> (f64, f64), range: std::ops::Range<usize>) {
+        for i in range {
+            let (close, volume) = path(i);
+            detector.observe(&candle(i, close, volume)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_synthetic_paths_classified() {
+        for (path, expected) in [
+            (quiet as fn(usize) -> (f64, f64), MarketRegime::Quiet),
+            (trending, MarketRegime::Trending),
+            (volatile, MarketRegime::Volatile),
+        ] {
+            let detector = new_detector();
+            feed(&detector, path, 0..WINDOW - 1).await;
+            assert_eq!(detector.regime(PAIR), None);
+            feed(&detector, path, WINDOW - 1..WINDOW).await;
+            assert_eq!(detector.regime(PAIR), Some(expected));
+        }
+
+        // A quiet tape that suddenly trades ten times its volume with a gap down
+        let detector = new_detector();
+        feed(&detector, quiet, 0..WINDOW).await;
+        feed(&detector, |i| (92.0 - (i % 2) as f64 * 0.5, 10_000.0), WINDOW..WINDOW + 5).await;
+        assert_eq!(detector.regime(PAIR), Some(MarketRegime::Dislocated));
+    }
+
+    #[tokio::test]
+    async fn test_change_published_and_persisted_after_confirmation() {
+        let detector = new_detector();
+        let store = Arc::new(MemoryStore::default());
+        let bus = Arc::new(SignalBus::new(SignalBusConfig::default()));
+        detector.set_store(store.clone());
+        detector.set_bus(bus.clone());
+        let mut regimes = bus.subscribe_regimes();
+
+        feed(&detector, quiet, 0..WINDOW).await;
+        let first = regimes.try_recv().unwrap();
+        assert_eq!((first.previous, first.regime), (None, MarketRegime::Quiet));
+
+        // Swings push volatility over the threshold well inside the window, but the regime
+        // only moves once the classification has held for the configured confirmations
+        let mut changed_after = None;
+        for i in WINDOW..WINDOW * 2 {
+            let (close, volume) = volatile(i);
+            if detector.observe(&candle(i, close, volume)).await.is_some() {
+                changed_after = Some(i - WINDOW + 1);
+                break;
+            }
+        }
+        let changed_after = changed_after.expect("regime never changed");
+        assert!(changed_after >= DEFAULT_CONFIRMATIONS);
+
+        let change = regimes.try_recv().unwrap();
+        assert_eq!((change.previous, change.regime), (Some(MarketRegime::Quiet), MarketRegime::Volatile));
+        assert!(change.features.volatility >= DEFAULT_VOLATILE_VOLATILITY);
+        assert_eq!(store.changes.lock().len(), 2);
+
+        // Restored regimes survive a restart
+        let restarted = new_detector();
+        restarted.set_store(store);
+        assert_eq!(restarted.restore().await.unwrap(), 1);
+        assert_eq!(restarted.regime(PAIR), Some(MarketRegime::Volatile));
+    }
+
+    #[test]
+    fn test_hysteresis_band() {
+        let detector = new_detector();
+        // Just under the volatile threshold: quiet when entering, volatile when already there
+        let features = RegimeFeatures {
+            volatility: DEFAULT_VOLATILE_VOLATILITY * 0.9,
+            ..RegimeFeatures::default()
+        };
+        assert_eq!(detector.classify(&features, Some(MarketRegime::Quiet)), MarketRegime::Quiet);
+        assert_eq!(detector.classify(&features, Some(MarketRegime::Volatile)), MarketRegime::Volatile);
+
+        let calm = RegimeFeatures {
+            volatility: DEFAULT_VOLATILE_VOLATILITY * 0.7,
+            ..RegimeFeatures::default()
+        };
+        assert_eq!(detector.classify(&calm, Some(MarketRegime::Volatile)), MarketRegime::Quiet);
+    }
+
+    #[tokio::test]
+    async fn test_gate_audits_transitions() {
+        let detector = new_detector();
+        let grid = [MarketRegime::Quiet, MarketRegime::Volatile];
+
+        // Not gated before the pair has a regime
+        assert!(detector.gate("grid", PAIR, &grid).open);
+
+        feed(&detector, trending, 0..WINDOW).await;
+        let gate = detector.gate("grid", PAIR, &grid);
+        assert!(!gate.open);
+        let audit = gate.audit.unwrap();
+        assert_eq!(audit.action, AUDIT_REGIME_DISABLED);
+        assert_eq!(
+            audit.detail,
+            "trending regime on SOL/USDC is not among allowed regimes [quiet, volatile]"
+        );
+
+        // Audited once per transition, not on every skipped run
+        assert!(detector.gate("grid", PAIR, &grid).audit.is_none());
+        // Strategies without a regime list trade everywhere
+        assert!(detector.gate("arbitrage", PAIR, &[]).open);
+
+        detector.pairs.lock().get_mut(PAIR).unwrap().regime = Some(MarketRegime::Quiet);
+        let gate = detector.gate("grid", PAIR, &grid);
+        assert!(gate.open);
+        assert_eq!(gate.audit.unwrap().action, AUDIT_REGIME_ENABLED);
+    }
+}
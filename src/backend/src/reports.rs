@@ -0,0 +1,1024 @@
+//! End-of-day performance reports. Once a day, at a configured UTC time, a job assembles the
+//! previous day's report from the persisted analytics tables: gross and net P&L from the
+//! attribution ledger broken down by strategy and venue, the best and worst closed trades,
+//! peak risk limit utilization and breaker trips from the risk snapshots, quarantines and
+//! reconciliation issues, and an equity sparkline. The report is stored as JSON for the API
+//! and pushed once, as text or HTML, through each configured notification channel.
+//!
+//! A report's day starts at the daily loss limit's reset time so the P&L it shows is the P&L
+//! the loss budget was measured against.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+//! - utoipa = "3.5"
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+use metrics::counter;
+use parking_lot::RwLock as SyncRwLock;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::api::WebhookDispatcher;
+use crate::attribution::{EntryKind, LedgerEntry, UNATTRIBUTED};
+use crate::jobs::{Job, JobContext, JobHandler, JobQueue};
+use crate::models::webhook::{DailySummary, WebhookEvent, WebhookEventType};
+use crate::performance::StrategyTrade;
+use crate::risk_manager::daily_loss::DailyLossLimits;
+use crate::risk_manager::factors::RiskFactorSnapshot;
+use crate::utils::http;
+
+// Report constants
+const METRICS_PREFIX: &str = "trading_bot.reports";
+const GENERATE_AT_VAR: &str = "REPORT_GENERATE_AT";
+const TELEGRAM_TOKEN_VAR: &str = "TELEGRAM_BOT_TOKEN";
+const TELEGRAM_CHAT_VAR: &str = "TELEGRAM_CHAT_ID";
+const TELEGRAM_API: &str = "https://api.telegram.org";
+const TELEGRAM_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_HIGHLIGHT_TRADES: usize = 3;
+const DEFAULT_SPARKLINE_POINTS: usize = 48;
+const MARGIN_UTILIZATION: &str = "margin_utilization";
+const CIRCUIT_BREAKER: &str = "circuit_breaker";
+
+/// Report errors
+#[derive(Error, Debug)]
+pub enum ReportError {
+    #[error("no daily report for {0}")]
+    NotFound(NaiveDate),
+    #[error("invalid report configuration: {0}")]
+    Config(String),
+    #[error("store error: {0}")]
+    Store(String),
+    #[error("delivery through {channel} failed: {reason}")]
+    Delivery { channel: String, reason: String },
+}
+
+/// Daily report configuration
+#[derive(Debug, Clone)]
+pub struct ReportConfig {
+    /// UTC time of day the last completed day's report is generated
+    pub generate_at: NaiveTime,
+    /// UTC time of day a report's day starts; the daily loss limit's reset time
+    pub day_boundary: NaiveTime,
+    /// Trades listed under best and under worst
+    pub highlight_trades: usize,
+    /// Most equity points kept for the sparkline
+    pub sparkline_points: usize,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self::for_limits(&DailyLossLimits::default())
+    }
+}
+
+impl ReportConfig {
+    /// Defaults with the day starting when the daily loss budget resets
+    pub fn for_limits(limits: &DailyLossLimits) -> Self {
+        Self {
+            generate_at: NaiveTime::from_hms_opt(0, 15, 0).expect("00:15 is a valid time"),
+            day_boundary: limits.reset_time,
+            highlight_trades: DEFAULT_HIGHLIGHT_TRADES,
+            sparkline_points: DEFAULT_SPARKLINE_POINTS,
+        }
+    }
+
+    /// Reads the generation time from `REPORT_GENERATE_AT` ("HH:MM", UTC); the day boundary
+    /// always follows `limits`
+    pub fn from_env(limits: &DailyLossLimits) -> Result<Self, ReportError> {
+        let mut config = Self::for_limits(limits);
+        if let Ok(value) = std::env::var(GENERATE_AT_VAR) {
+            config.generate_at = NaiveTime::parse_from_str(value.trim(), "%H:%M")
+                .map_err(|_| ReportError::Config(format!("{} must be HH:MM in UTC, got {}", GENERATE_AT_VAR, value)))?;
+        }
+        Ok(config)
+    }
+
+    /// Bounds `[start, end)` of the report day labelled `date`, which starts on `date` at the
+    /// day boundary
+    pub fn period(&self, date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+        let start = Utc.from_utc_datetime(&date.and_time(self.day_boundary));
+        (start, start + chrono::Duration::days(1))
+    }
+
+    /// Start of the report day containing `now`
+    pub fn day_start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let today = Utc.from_utc_datetime(&now.date_naive().and_time(self.day_boundary));
+        if today <= now {
+            today
+        } else {
+            today - chrono::Duration::days(1)
+        }
+    }
+
+    /// Most recent report day that has fully ended by `now`
+    pub fn last_completed(&self, now: DateTime<Utc>) -> NaiveDate {
+        (self.day_start(now) - chrono::Duration::days(1)).date_naive()
+    }
+
+    /// First generation time strictly after `now`
+    pub fn next_run(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let today = Utc.from_utc_datetime(&now.date_naive().and_time(self.generate_at));
+        if today > now {
+            today
+        } else {
+            today + chrono::Duration::days(1)
+        }
+    }
+}
+
+/// Gross and net P&L of the day, a strategy or a venue
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, ToSchema)]
+pub struct PnlLine {
+    pub name: String,
+    /// Realized P&L before fees and funding
+    pub gross_pnl: Decimal,
+    /// Fees paid net of rebates, as a negative amount
+    pub fees: Decimal,
+    pub funding: Decimal,
+    pub net_pnl: Decimal,
+    /// Fills, opening and closing
+    pub trade_count: u64,
+}
+
+impl PnlLine {
+    fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    fn apply(&mut self, entry: &LedgerEntry) {
+        match entry.kind {
+            EntryKind::Trade => {
+                self.gross_pnl += entry.amount;
+                self.trade_count += 1;
+            }
+            EntryKind::Fee => self.fees += entry.amount,
+            EntryKind::Funding => self.funding += entry.amount,
+            EntryKind::Transfer => return,
+        }
+        self.net_pnl += entry.amount;
+    }
+}
+
+/// Highest utilization of one risk limit over the day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LimitUtilization {
+    pub name: String,
+    /// Peak value as a fraction of the threshold; above 1 once breached
+    pub peak: Decimal,
+    pub value: Decimal,
+    pub threshold: Decimal,
+    pub at: DateTime<Utc>,
+}
+
+/// What kind of incident a report lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentKind {
+    BreakerTrip,
+    Quarantine,
+    Reconciliation,
+}
+
+impl IncidentKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BreakerTrip => "breaker_trip",
+            Self::Quarantine => "quarantine",
+            Self::Reconciliation => "reconciliation",
+        }
+    }
+}
+
+/// Breaker trip, quarantine or reconciliation issue during the day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Incident {
+    pub kind: IncidentKind,
+    /// Breaker, trading pair or market affected
+    pub subject: String,
+    pub detail: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// One day's performance report, as stored in `reports` and served by the API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DailyReport {
+    pub date: NaiveDate,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub total: PnlLine,
+    /// By net P&L, highest first
+    pub strategies: Vec<PnlLine>,
+    /// By net P&L, highest first
+    pub venues: Vec<PnlLine>,
+    pub best_trades: Vec<StrategyTrade>,
+    pub worst_trades: Vec<StrategyTrade>,
+    /// Highest utilization first
+    pub limit_utilization: Vec<LimitUtilization>,
+    /// Oldest first
+    pub incidents: Vec<Incident>,
+    /// Portfolio value over the day, oldest first
+    pub equity_sparkline: Vec<Decimal>,
+    pub generated_at: DateTime<Utc>,
+    /// Set once the summary has been pushed through the notification channels
+    #[serde(default)]
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// Persisted analytics a report is assembled from
+#[async_trait]
+pub trait ReportSource: Send + Sync {
+    /// Ledger entries that occurred in `[from, to)`
+    async fn ledger(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<LedgerEntry>, ReportError>;
+    /// Trades closed in `[from, to)`
+    async fn closed_trades(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<StrategyTrade>, ReportError>;
+    /// Risk snapshots computed in `[from, to)`, oldest first
+    async fn risk_snapshots(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<RiskFactorSnapshot>, ReportError>;
+    /// Quarantines and reconciliation issues raised in `[from, to)`
+    async fn incidents(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<Incident>, ReportError>;
+}
+
+/// Persists generated reports
+#[async_trait]
+pub trait ReportStore: Send + Sync {
+    /// Inserts or replaces the report for its date, keeping the delivery time
+    async fn save(&self, report: &DailyReport) -> Result<(), ReportError>;
+    async fn get(&self, date: NaiveDate) -> Result<Option<DailyReport>, ReportError>;
+    /// Marks the report delivered; false if it already was, so each report is pushed once
+    async fn mark_delivered(&self, date: NaiveDate, at: DateTime<Utc>) -> Result<bool, ReportError>;
+}
+
+/// Notification channel a report summary is pushed through
+#[async_trait]
+pub trait ReportChannel: Send + Sync {
+    fn name(&self) -> &'static str;
+    async fn send(&self, report: &DailyReport) -> Result<(), ReportError>;
+}
+
+/// Report pushed to webhook subscribers
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyReportEvent<'a> {
+    pub report: &'a DailyReport,
+    pub summary: String,
+}
+
+#[async_trait]
+impl ReportChannel for WebhookDispatcher {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    async fn send(&self, report: &DailyReport) -> Result<(), ReportError> {
+        let delivery_error = |reason: String| ReportError::Delivery {
+            channel: "webhook".to_string(),
+            reason,
+        };
+        let payload = DailyReportEvent {
+            report,
+            summary: render_text(report),
+        };
+        let event = WebhookEvent::new(WebhookEventType::DailyReport, &payload)
+            .map_err(|e| delivery_error(e.to_string()))?;
+        if !self.dispatch(event) {
+            return Err(delivery_error("webhook queue full".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Sends the compact `summary.daily` webhook event for each report
+#[derive(Debug)]
+pub struct DailySummaryChannel {
+    webhooks: Arc<WebhookDispatcher>,
+    wallet_address: String,
+}
+
+impl DailySummaryChannel {
+    pub fn new(webhooks: Arc<WebhookDispatcher>, wallet_address: String) -> Self {
+        Self { webhooks, wallet_address }
+    }
+
+    fn summary(&self, report: &DailyReport) -> DailySummary {
+        DailySummary {
+            date: report.date,
+            wallet_address: self.wallet_address.clone(),
+            portfolio_value: report.equity_sparkline.last().copied().unwrap_or_default(),
+            realized_pnl: report.total.net_pnl,
+            trades_executed: report.total.trade_count,
+        }
+    }
+}
+
+#[async_trait]
+impl ReportChannel for DailySummaryChannel {
+    fn name(&self) -> &'static str {
+        "daily_summary"
+    }
+
+    async fn send(&self, report: &DailyReport) -> Result<(), ReportError> {
+        let delivery_error = |reason: String| ReportError::Delivery {
+            channel: "daily_summary".to_string(),
+            reason,
+        };
+        let event = WebhookEvent::daily_summary(&self.summary(report))
+            .map_err(|e| delivery_error(e.to_string()))?;
+        if !self.webhooks.dispatch(event) {
+            return Err(delivery_error("webhook queue full".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Telegram bot and chat the HTML summary is sent to
+#[derive(Debug, Clone)]
+pub struct TelegramChannel {
+    bot_token: String,
+    chat_id: String,
+}
+
+impl TelegramChannel {
+    pub fn new(bot_token: String, chat_id: String) -> Self {
+        Self { bot_token, chat_id }
+    }
+
+    /// Channel from `TELEGRAM_BOT_TOKEN` and `TELEGRAM_CHAT_ID`; None unless both are set
+    pub fn from_env() -> Option<Self> {
+        let bot_token = std::env::var(TELEGRAM_TOKEN_VAR).ok().filter(|v| !v.is_empty())?;
+        let chat_id = std::env::var(TELEGRAM_CHAT_VAR).ok().filter(|v| !v.is_empty())?;
+        Some(Self::new(bot_token, chat_id))
+    }
+}
+
+#[async_trait]
+impl ReportChannel for TelegramChannel {
+    fn name(&self) -> &'static str {
+        "telegram"
+    }
+
+    async fn send(&self, report: &DailyReport) -> Result<(), ReportError> {
+        let url = format!("{}/bot{}/sendMessage", TELEGRAM_API, self.bot_token);
+        let body = serde_json::json!({
+            "chat_id": self.chat_id,
+            "text": render_html(report),
+            "parse_mode": "HTML",
+            "disable_web_page_preview": true,
+        });
+        http::shared()
+            .post(&url, Some(TELEGRAM_TIMEOUT), |builder| builder.json(&body))
+            .await
+            .map_err(|e| ReportError::Delivery {
+                channel: "telegram".to_string(),
+                reason: e.to_string(),
+            })?;
+        Ok(())
+    }
+}
+
+/// Generates the report of one day and delivers it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyReportJob {
+    pub date: NaiveDate,
+}
+
+impl Job for DailyReportJob {
+    const KIND: &'static str = "report.daily";
+}
+
+/// Assembles, stores and delivers the daily reports
+pub struct DailyReporter {
+    config: ReportConfig,
+    source: SyncRwLock<Option<Arc<dyn ReportSource>>>,
+    store: SyncRwLock<Option<Arc<dyn ReportStore>>>,
+    channels: SyncRwLock<Vec<Arc<dyn ReportChannel>>>,
+}
+
+impl std::fmt::Debug for DailyReporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DailyReporter")
+            .field("config", &self.config)
+            .field("channels", &self.channels.read().len())
+            .finish()
+    }
+}
+
+impl DailyReporter {
+    pub fn new(config: ReportConfig) -> Self {
+        Self {
+            config,
+            source: SyncRwLock::new(None),
+            store: SyncRwLock::new(None),
+            channels: SyncRwLock::new(Vec::new()),
+        }
+    }
+
+    pub fn config(&self) -> &ReportConfig {
+        &self.config
+    }
+
+    pub fn set_source(&self, source: Arc<dyn ReportSource>) {
+        *self.source.write() = Some(source);
+    }
+
+    pub fn set_store(&self, store: Arc<dyn ReportStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    /// Pushes each generated report through `channel` as well
+    pub fn add_channel(&self, channel: Arc<dyn ReportChannel>) {
+        self.channels.write().push(channel);
+    }
+
+    fn store(&self) -> Result<Arc<dyn ReportStore>, ReportError> {
+        self.store
+            .read()
+            .clone()
+            .ok_or_else(|| ReportError::Store("no report store configured".to_string()))
+    }
+
+    /// Stored report for `date`
+    pub async fn report(&self, date: NaiveDate) -> Result<DailyReport, ReportError> {
+        self.store()?.get(date).await?.ok_or(ReportError::NotFound(date))
+    }
+
+    /// Assembles the report of `date` from the analytics tables
+    pub async fn generate(&self, date: NaiveDate, now: DateTime<Utc>) -> Result<DailyReport, ReportError> {
+        let source = self
+            .source
+            .read()
+            .clone()
+            .ok_or_else(|| ReportError::Store("no report source configured".to_string()))?;
+        let (period_start, period_end) = self.config.period(date);
+
+        let ledger = source.ledger(period_start, period_end).await?;
+        let mut trades = source.closed_trades(period_start, period_end).await?;
+        let snapshots = source.risk_snapshots(period_start, period_end).await?;
+        let mut incidents = breaker_trips(&snapshots);
+        incidents.extend(source.incidents(period_start, period_end).await?);
+        incidents.sort_by_key(|incident| incident.occurred_at);
+
+        let (total, strategies, venues) = pnl_breakdown(&ledger);
+        trades.sort_by(|a, b| b.realized_pnl.cmp(&a.realized_pnl).then(a.closed_at.cmp(&b.closed_at)));
+        let highlight = self.config.highlight_trades;
+        let best_trades: Vec<StrategyTrade> = trades
+            .iter()
+            .filter(|trade| trade.realized_pnl > Decimal::ZERO)
+            .take(highlight)
+            .cloned()
+            .collect();
+        let worst_trades: Vec<StrategyTrade> = trades
+            .iter()
+            .rev()
+            .filter(|trade| trade.realized_pnl < Decimal::ZERO)
+            .take(highlight)
+            .cloned()
+            .collect();
+
+        Ok(DailyReport {
+            date,
+            period_start,
+            period_end,
+            total,
+            strategies,
+            venues,
+            best_trades,
+            worst_trades,
+            limit_utilization: limit_peaks(&snapshots),
+            incidents,
+            equity_sparkline: sparkline(&snapshots, period_start, period_end, self.config.sparkline_points),
+            generated_at: now,
+            delivered_at: None,
+        })
+    }
+
+    /// Generates and stores the report of `date`, then delivers it unless an earlier run
+    /// already did; a channel failure is logged and does not fail the run
+    pub async fn run(&self, date: NaiveDate, now: DateTime<Utc>) -> Result<DailyReport, ReportError> {
+        let store = self.store()?;
+        let mut report = self.generate(date, now).await?;
+        store.save(&report).await?;
+        counter!(format!("{}.generated", METRICS_PREFIX), 1);
+
+        if !store.mark_delivered(date, now).await? {
+            return Ok(report);
+        }
+        report.delivered_at = Some(now);
+        let channels = self.channels.read().clone();
+        for channel in channels {
+            match channel.send(&report).await {
+                Ok(()) => counter!(format!("{}.delivered", METRICS_PREFIX), 1, "channel" => channel.name()),
+                Err(e) => {
+                    counter!(format!("{}.delivery_failures", METRICS_PREFIX), 1, "channel" => channel.name());
+                    warn!(%date, channel = channel.name(), "Failed to deliver daily report: {}", e);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Registers the report job and enqueues the last completed day's report at the
+    /// configured time each day
+    pub fn spawn(self: Arc<Self>, jobs: Arc<JobQueue>) -> tokio::task::JoinHandle<()> {
+        jobs.register::<DailyReportJob, _>(self.clone());
+        tokio::spawn(async move {
+            loop {
+                let now = Utc::now();
+                let next = self.config.next_run(now);
+                tokio::time::sleep((next - now).to_std().unwrap_or_default()).await;
+
+                let date = self.config.last_completed(next);
+                if let Err(e) = jobs.enqueue(DailyReportJob { date }).await {
+                    warn!(%date, "Failed to enqueue daily report: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl JobHandler<DailyReportJob> for DailyReporter {
+    async fn handle(&self, job: DailyReportJob, _ctx: JobContext) -> Result<(), String> {
+        let report = self.run(job.date, Utc::now()).await.map_err(|e| e.to_string())?;
+        info!(
+            date = %report.date,
+            net_pnl = %report.total.net_pnl,
+            incidents = report.incidents.len(),
+            "Generated daily report"
+        );
+        Ok(())
+    }
+}
+
+/// Day total and per-strategy and per-venue lines, highest net P&L first
+fn pnl_breakdown(ledger: &[LedgerEntry]) -> (PnlLine, Vec<PnlLine>, Vec<PnlLine>) {
+    let mut total = PnlLine::named("total");
+    let mut strategies: BTreeMap<&str, PnlLine> = BTreeMap::new();
+    let mut venues: BTreeMap<&str, PnlLine> = BTreeMap::new();
+    for entry in ledger.iter().filter(|entry| entry.kind != EntryKind::Transfer) {
+        total.apply(entry);
+        let strategy = entry.tags.strategy_id.as_deref().unwrap_or(UNATTRIBUTED);
+        strategies.entry(strategy).or_insert_with(|| PnlLine::named(strategy)).apply(entry);
+        let venue = entry.tags.venue.as_deref().unwrap_or(UNATTRIBUTED);
+        venues.entry(venue).or_insert_with(|| PnlLine::named(venue)).apply(entry);
+    }
+    let ranked = |lines: BTreeMap<&str, PnlLine>| {
+        let mut lines: Vec<PnlLine> = lines.into_values().collect();
+        lines.sort_by(|a, b| b.net_pnl.cmp(&a.net_pnl).then(a.name.cmp(&b.name)));
+        lines
+    };
+    (total, ranked(strategies), ranked(venues))
+}
+
+/// Highest utilization of each breaker and of perp margin across the snapshots
+fn limit_peaks(snapshots: &[RiskFactorSnapshot]) -> Vec<LimitUtilization> {
+    let mut peaks: BTreeMap<String, LimitUtilization> = BTreeMap::new();
+    let mut observe = |name: &str, value: Decimal, threshold: Decimal, at: DateTime<Utc>| {
+        if threshold <= Decimal::ZERO {
+            return;
+        }
+        let utilization = LimitUtilization {
+            name: name.to_string(),
+            peak: value / threshold,
+            value,
+            threshold,
+            at,
+        };
+        match peaks.get(name) {
+            Some(peak) if peak.peak >= utilization.peak => {}
+            _ => {
+                peaks.insert(name.to_string(), utilization);
+            }
+        }
+    };
+    for snapshot in snapshots {
+        for breaker in &snapshot.breakers {
+            observe(&breaker.name, breaker.current, breaker.threshold, snapshot.computed_at);
+        }
+        if let Some(margin) = snapshot.margin_utilization.value {
+            observe(MARGIN_UTILIZATION, margin, Decimal::ONE, snapshot.computed_at);
+        }
+    }
+    let mut peaks: Vec<LimitUtilization> = peaks.into_values().collect();
+    peaks.sort_by(|a, b| b.peak.cmp(&a.peak).then(a.name.cmp(&b.name)));
+    peaks
+}
+
+/// Each time the circuit breaker went from clear to tripped
+fn breaker_trips(snapshots: &[RiskFactorSnapshot]) -> Vec<Incident> {
+    let mut tripped = false;
+    let mut incidents = Vec::new();
+    for snapshot in snapshots {
+        if snapshot.circuit_breaker_tripped && !tripped {
+            incidents.push(Incident {
+                kind: IncidentKind::BreakerTrip,
+                subject: CIRCUIT_BREAKER.to_string(),
+                detail: format!("drawdown {}", snapshot.drawdown.round_dp(4)),
+                occurred_at: snapshot.computed_at,
+            });
+        }
+        tripped = snapshot.circuit_breaker_tripped;
+    }
+    incidents
+}
+
+/// Last portfolio value in each of up to `points` equal slices of the day
+fn sparkline(
+    snapshots: &[RiskFactorSnapshot],
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+    points: usize,
+) -> Vec<Decimal> {
+    let span = (period_end - period_start).num_seconds().max(1);
+    let points = points.max(1) as i64;
+    let mut buckets: BTreeMap<i64, Decimal> = BTreeMap::new();
+    for snapshot in snapshots {
+        let offset = (snapshot.computed_at - period_start).num_seconds();
+        if !(0..span).contains(&offset) {
+            continue;
+        }
+        buckets.insert(offset * points / span, snapshot.total_value);
+    }
+    buckets.into_values().collect()
+}
+
+/// Plain-text summary for webhook subscribers
+pub fn render_text(report: &DailyReport) -> String {
+    render(report, false)
+}
+
+/// Summary using the HTML subset Telegram accepts
+pub fn render_html(report: &DailyReport) -> String {
+    render(report, true)
+}
+
+fn render(report: &DailyReport, html: bool) -> String {
+    let escape = |text: &str| {
+        if html {
+            text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+        } else {
+            text.to_string()
+        }
+    };
+    let heading = |text: &str| if html { format!("<b>{}</b>", escape(text)) } else { text.to_string() };
+    let pnl = |line: &PnlLine| {
+        format!(
+            "net {} (gross {}, fees {}, funding {}, {} fills)",
+            line.net_pnl.round_dp(2),
+            line.gross_pnl.round_dp(2),
+            line.fees.round_dp(2),
+            line.funding.round_dp(2),
+            line.trade_count
+        )
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{}",
+        heading(&format!(
+            "Daily report {} ({} to {} UTC)",
+            report.date,
+            report.period_start.format("%Y-%m-%d %H:%M"),
+            report.period_end.format("%Y-%m-%d %H:%M")
+        ))
+    );
+    let _ = writeln!(out, "P&L: {}", pnl(&report.total));
+
+    for (title, lines) in [("Strategies", &report.strategies), ("Venues", &report.venues)] {
+        if lines.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out, "\n{}", heading(title));
+        for line in lines {
+            let _ = writeln!(out, "  {}: {}", escape(&line.name), pnl(line));
+        }
+    }
+    for (title, trades) in [("Best trades", &report.best_trades), ("Worst trades", &report.worst_trades)] {
+        if trades.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out, "\n{}", heading(title));
+        for trade in trades {
+            let _ = writeln!(
+                out,
+                "  {} {} {} at {}",
+                escape(&trade.strategy_id),
+                escape(&trade.trading_pair),
+                trade.realized_pnl.round_dp(2),
+                trade.closed_at.format("%H:%M")
+            );
+        }
+    }
+    if !report.limit_utilization.is_empty() {
+        let _ = writeln!(out, "\n{}", heading("Risk limit peaks"));
+        for limit in &report.limit_utilization {
+            let _ = writeln!(
+                out,
+                "  {}: {}% ({} of {}) at {}",
+                escape(&limit.name),
+                (limit.peak * Decimal::ONE_HUNDRED).round_dp(1),
+                limit.value.round_dp(4),
+                limit.threshold.round_dp(4),
+                limit.at.format("%H:%M")
+            );
+        }
+    }
+    let _ = writeln!(out, "\n{}", heading("Incidents"));
+    if report.incidents.is_empty() {
+        let _ = writeln!(out, "  none");
+    }
+    for incident in &report.incidents {
+        let _ = writeln!(
+            out,
+            "  [{}] {}: {} at {}",
+            incident.kind.as_str(),
+            escape(&incident.subject),
+            escape(&incident.detail),
+            incident.occurred_at.format("%H:%M")
+        );
+    }
+    if let (Some(first), Some(last)) = (report.equity_sparkline.first(), report.equity_sparkline.last()) {
+        let _ = writeln!(out, "\nEquity: {} to {}", first.round_dp(2), last.round_dp(2));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribution::AttributionTags;
+    use crate::risk_manager::exposure::TradeSide;
+    use crate::risk_manager::factors::{BreakerDistance, RiskFactor};
+    use parking_lot::Mutex;
+    use rust_decimal_macros::dec;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use uuid::Uuid;
+
+    #[derive(Default)]
+    struct MemorySource {
+        ledger: Vec<LedgerEntry>,
+        trades: Vec<StrategyTrade>,
+        snapshots: Vec<RiskFactorSnapshot>,
+        incidents: Vec<Incident>,
+    }
+
+    #[async_trait]
+    impl ReportSource for MemorySource {
+        async fn ledger(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<LedgerEntry>, ReportError> {
+            Ok(self.ledger.iter().filter(|e| e.occurred_at >= from && e.occurred_at < to).cloned().collect())
+        }
+
+        async fn closed_trades(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<StrategyTrade>, ReportError> {
+            Ok(self.trades.iter().filter(|t| t.closed_at >= from && t.closed_at < to).cloned().collect())
+        }
+
+        async fn risk_snapshots(
+            &self,
+            from: DateTime<Utc>,
+            to: DateTime<Utc>,
+        ) -> Result<Vec<RiskFactorSnapshot>, ReportError> {
+            Ok(self.snapshots.iter().filter(|s| s.computed_at >= from && s.computed_at < to).cloned().collect())
+        }
+
+        async fn incidents(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<Incident>, ReportError> {
+            Ok(self.incidents.iter().filter(|i| i.occurred_at >= from && i.occurred_at < to).cloned().collect())
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryStore {
+        reports: Mutex<BTreeMap<NaiveDate, DailyReport>>,
+    }
+
+    #[async_trait]
+    impl ReportStore for MemoryStore {
+        async fn save(&self, report: &DailyReport) -> Result<(), ReportError> {
+            let mut reports = self.reports.lock();
+            let delivered_at = reports.get(&report.date).and_then(|r| r.delivered_at);
+            let mut report = report.clone();
+            report.delivered_at = delivered_at;
+            reports.insert(report.date, report);
+            Ok(())
+        }
+
+        async fn get(&self, date: NaiveDate) -> Result<Option<DailyReport>, ReportError> {
+            Ok(self.reports.lock().get(&date).cloned())
+        }
+
+        async fn mark_delivered(&self, date: NaiveDate, at: DateTime<Utc>) -> Result<bool, ReportError> {
+            match self.reports.lock().get_mut(&date) {
+                Some(report) if report.delivered_at.is_none() => {
+                    report.delivered_at = Some(at);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct CountingChannel {
+        sent: AtomicUsize,
+        last: Mutex<Option<String>>,
+    }
+
+    #[async_trait]
+    impl ReportChannel for CountingChannel {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        async fn send(&self, report: &DailyReport) -> Result<(), ReportError> {
+            self.sent.fetch_add(1, Ordering::SeqCst);
+            *self.last.lock() = Some(render_html(report));
+            Ok(())
+        }
+    }
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 1, hour, minute, 0).unwrap()
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()
+    }
+
+    fn fill(strategy: &str, venue: &str, pnl: Decimal, fee: Decimal, when: DateTime<Utc>) -> Vec<LedgerEntry> {
+        let tags = AttributionTags::trade("wallet", strategy, venue, "SOL/USDC");
+        vec![
+            LedgerEntry::fill(tags.clone(), TradeSide::Sell, dec!(1), dec!(100), Some(pnl), when),
+            LedgerEntry::fee(tags, fee, when),
+        ]
+    }
+
+    fn trade(strategy: &str, pnl: Decimal, when: DateTime<Utc>) -> StrategyTrade {
+        StrategyTrade {
+            id: Uuid::new_v4(),
+            strategy_id: strategy.to_string(),
+            trading_pair: "SOL/USDC".to_string(),
+            realized_pnl: pnl,
+            closed_at: when,
+            version: None,
+        }
+    }
+
+    fn snapshot(when: DateTime<Utc>, total_value: Decimal, drawdown: Decimal, tripped: bool) -> RiskFactorSnapshot {
+        RiskFactorSnapshot {
+            id: Uuid::new_v4(),
+            computed_at: when,
+            total_value,
+            exposures: BTreeMap::new(),
+            net_exposure: Decimal::ZERO,
+            gross_exposure: Decimal::ZERO,
+            leverage: Decimal::ONE,
+            drawdown,
+            value_at_risk: RiskFactor::missing("not computed"),
+            margin_utilization: RiskFactor::known(dec!(0.25)),
+            breakers: vec![BreakerDistance {
+                name: "drawdown_circuit_breaker".to_string(),
+                current: drawdown,
+                threshold: dec!(10),
+                distance: dec!(10) - drawdown,
+            }],
+            circuit_breaker_tripped: tripped,
+        }
+    }
+
+    fn seeded() -> MemorySource {
+        let mut ledger = Vec::new();
+        ledger.extend(fill("momentum", "jupiter", dec!(40), dec!(1), at(9, 0)));
+        ledger.extend(fill("momentum", "drift", dec!(-15), dec!(0.5), at(11, 0)));
+        ledger.extend(fill("grid", "jupiter", dec!(10), dec!(0.5), at(14, 0)));
+        ledger.push(LedgerEntry::funding(
+            AttributionTags::trade("wallet", "grid", "drift", "SOL-PERP"),
+            dec!(-2),
+            at(16, 0),
+        ));
+        // Outside the day; must not be counted
+        ledger.extend(fill("grid", "jupiter", dec!(1000), dec!(0), at(0, 0) - chrono::Duration::minutes(1)));
+
+        MemorySource {
+            ledger,
+            trades: vec![
+                trade("momentum", dec!(40), at(9, 0)),
+                trade("momentum", dec!(-15), at(11, 0)),
+                trade("grid", dec!(10), at(14, 0)),
+            ],
+            snapshots: vec![
+                snapshot(at(8, 0), dec!(10000), dec!(2), false),
+                snapshot(at(12, 0), dec!(9800), dec!(11), true),
+                snapshot(at(12, 30), dec!(9810), dec!(10.5), true),
+                snapshot(at(18, 0), dec!(10031), dec!(4), false),
+            ],
+            incidents: vec![Incident {
+                kind: IncidentKind::Quarantine,
+                subject: "BONK/USDC".to_string(),
+                detail: "no-new-entries: oracle <stale>".to_string(),
+                occurred_at: at(10, 0),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_report_from_fixtures_is_delivered_once() {
+        let reporter = DailyReporter::new(ReportConfig::default());
+        let store = Arc::new(MemoryStore::default());
+        let channel = Arc::new(CountingChannel::default());
+        reporter.set_source(Arc::new(seeded()));
+        reporter.set_store(store.clone());
+        reporter.add_channel(channel.clone());
+
+        let now = at(0, 15) + chrono::Duration::days(1);
+        let report = reporter.run(date(), now).await.unwrap();
+        // A retried job regenerates the report but does not push it again
+        reporter.run(date(), now + chrono::Duration::minutes(5)).await.unwrap();
+        assert_eq!(channel.sent.load(Ordering::SeqCst), 1);
+
+        assert_eq!(report.total.gross_pnl, dec!(35));
+        assert_eq!(report.total.fees, dec!(-2));
+        assert_eq!(report.total.funding, dec!(-2));
+        assert_eq!(report.total.net_pnl, dec!(31));
+        assert_eq!(report.total.trade_count, 3);
+
+        let strategies: Vec<(&str, Decimal)> =
+            report.strategies.iter().map(|line| (line.name.as_str(), line.net_pnl)).collect();
+        assert_eq!(strategies, vec![("momentum", dec!(23.5)), ("grid", dec!(7.5))]);
+        let venues: Vec<(&str, Decimal)> = report.venues.iter().map(|line| (line.name.as_str(), line.net_pnl)).collect();
+        assert_eq!(venues, vec![("jupiter", dec!(48.5)), ("drift", dec!(-17.5))]);
+
+        assert_eq!(report.best_trades.iter().map(|t| t.realized_pnl).collect::<Vec<_>>(), vec![dec!(40), dec!(10)]);
+        assert_eq!(report.worst_trades.iter().map(|t| t.realized_pnl).collect::<Vec<_>>(), vec![dec!(-15)]);
+
+        let peak = &report.limit_utilization[0];
+        assert_eq!(peak.name, "drawdown_circuit_breaker");
+        assert_eq!(peak.peak, dec!(1.1));
+        assert_eq!(peak.at, at(12, 0));
+
+        let kinds: Vec<IncidentKind> = report.incidents.iter().map(|i| i.kind).collect();
+        assert_eq!(kinds, vec![IncidentKind::Quarantine, IncidentKind::BreakerTrip]);
+        assert_eq!(report.equity_sparkline, vec![dec!(10000), dec!(9800), dec!(9810), dec!(10031)]);
+
+        let stored = reporter.report(date()).await.unwrap();
+        assert_eq!(stored.delivered_at, Some(now));
+        assert_eq!(stored.total, report.total);
+        let html = channel.last.lock().clone().unwrap();
+        assert!(html.contains("<b>Incidents</b>"));
+        assert!(html.contains("oracle &lt;stale&gt;"));
+    }
+
+    #[tokio::test]
+    async fn test_daily_summary_from_report() {
+        let reporter = DailyReporter::new(ReportConfig::default());
+        reporter.set_source(Arc::new(seeded()));
+        let report = reporter.generate(date(), at(0, 15) + chrono::Duration::days(1)).await.unwrap();
+
+        let webhooks = Arc::new(WebhookDispatcher::new(Default::default(), None));
+        let summary = DailySummaryChannel::new(webhooks, "wallet123".to_string()).summary(&report);
+        assert_eq!(summary.date, date());
+        assert_eq!(summary.wallet_address, "wallet123");
+        assert_eq!(summary.portfolio_value, dec!(10031));
+        assert_eq!(summary.realized_pnl, dec!(31));
+        assert_eq!(summary.trades_executed, 3);
+    }
+
+    #[tokio::test]
+    async fn test_missing_report_is_not_found() {
+        let reporter = DailyReporter::new(ReportConfig::default());
+        reporter.set_store(Arc::new(MemoryStore::default()));
+        assert!(matches!(reporter.report(date()).await, Err(ReportError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_day_boundary_follows_daily_loss_reset() {
+        let limits = DailyLossLimits {
+            reset_time: NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+            ..DailyLossLimits::default()
+        };
+        let config = ReportConfig::for_limits(&limits);
+        let (start, end) = config.period(date());
+        assert_eq!(start, at(22, 0));
+        assert_eq!(end - start, chrono::Duration::days(1));
+        for now in [at(21, 59), at(22, 0), at(23, 30), end + chrono::Duration::hours(3)] {
+            assert_eq!(config.day_start(now), limits.day_start(now));
+        }
+        assert_eq!(config.last_completed(end + chrono::Duration::minutes(15)), date());
+        assert_eq!(config.next_run(at(0, 15)), at(0, 15) + chrono::Duration::days(1));
+    }
+}
@@ -0,0 +1,473 @@
+//! Per-pair market data retention. The global default from `RetentionPolicy` applies unless
+//! an admin override matches; an override for a pair on one exchange wins over one for the
+//! pair on every exchange, which wins over the default. Overrides are persisted and the
+//! retention run reads the current set each time, so a change is picked up by the next run.
+//! Lengthening retention only keeps rows that still exist; deleted data stays deleted.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - parking_lot = "0.12"
+//! - utoipa = "3.5"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::RwLock as SyncRwLock;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::config::database::RetentionPolicy;
+use crate::models::pair::TradingPair;
+
+// Retention constants
+pub const MAX_RETENTION_DAYS: u32 = 1825; // 5 years
+const DEFAULT_RUN_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const METRICS_PREFIX: &str = "trading_bot.retention";
+
+/// Retention override errors
+#[derive(Error, Debug)]
+pub enum RetentionError {
+    #[error("invalid trading pair: {0}")]
+    InvalidPair(String),
+    #[error("retention of {days} days must be between 1 and {max}")]
+    InvalidDays { days: u32, max: u32 },
+    #[error("no retention override for {0}")]
+    NotFound(String),
+    #[error("store error: {0}")]
+    Store(String),
+}
+
+/// Which rule decided a pair's retention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionSource {
+    PairExchange,
+    Pair,
+    Default,
+}
+
+/// Admin request setting a pair's retention, optionally on one exchange only
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetentionOverrideRequest {
+    pub trading_pair: String,
+    pub exchange: Option<String>,
+    pub days: u32,
+}
+
+/// Retention set for a pair, on every exchange when `exchange` is unset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionOverride {
+    pub trading_pair: String,
+    pub exchange: Option<String>,
+    pub days: u32,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Retention in force for a pair, as shown on the monitoring endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct EffectiveRetention {
+    pub trading_pair: String,
+    pub exchange: Option<String>,
+    pub days: u32,
+    pub source: RetentionSource,
+}
+
+/// Default and overrides as of one retention run
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetentionSchedule {
+    pub default_days: u32,
+    pub overrides: Vec<RetentionOverride>,
+}
+
+impl RetentionSchedule {
+    /// Most specific retention for market data from a pair on an exchange
+    pub fn days_for(&self, trading_pair: &str, exchange: &str) -> (u32, RetentionSource) {
+        let exchange = exchange.to_lowercase();
+        let mut pair_days = None;
+        for entry in self.overrides.iter().filter(|entry| entry.trading_pair == trading_pair) {
+            match &entry.exchange {
+                Some(venue) if *venue == exchange => return (entry.days, RetentionSource::PairExchange),
+                Some(_) => {}
+                None => pair_days = Some(entry.days),
+            }
+        }
+        pair_days.map_or((self.default_days, RetentionSource::Default), |days| {
+            (days, RetentionSource::Pair)
+        })
+    }
+
+    /// Whether a row stamped `timestamp` is past its retention at `now`
+    pub fn is_expired(&self, trading_pair: &str, exchange: &str, timestamp: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        let (days, _) = self.days_for(trading_pair, exchange);
+        timestamp < now - chrono::Duration::days(days as i64)
+    }
+}
+
+/// Persistence for retention overrides
+#[async_trait]
+pub trait RetentionOverrideStore: Send + Sync {
+    /// Inserts or replaces the override for its pair and exchange
+    async fn save(&self, entry: &RetentionOverride) -> Result<(), RetentionError>;
+    async fn remove(&self, trading_pair: &str, exchange: Option<&str>) -> Result<(), RetentionError>;
+    async fn load(&self) -> Result<Vec<RetentionOverride>, RetentionError>;
+}
+
+/// Deletes market data past the retention a schedule gives it, returning the rows deleted
+#[async_trait]
+pub trait MarketDataPruner: Send + Sync {
+    async fn prune(&self, schedule: &RetentionSchedule, now: DateTime<Utc>) -> Result<u64, RetentionError>;
+}
+
+/// Default retention, the ceiling overrides are held to and how often retention runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetentionConfig {
+    pub default_days: u32,
+    pub max_days: u32,
+    pub run_interval: Duration,
+}
+
+impl RetentionConfig {
+    /// Takes the global default from the database retention policy
+    pub fn from_policy(policy: &RetentionPolicy) -> Self {
+        Self {
+            default_days: policy.market_data_days,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            default_days: 90,
+            max_days: MAX_RETENTION_DAYS,
+            run_interval: DEFAULT_RUN_INTERVAL,
+        }
+    }
+}
+
+/// Retention overrides keyed by pair and exchange, applied by the retention run
+pub struct RetentionManager {
+    config: RetentionConfig,
+    overrides: SyncRwLock<HashMap<(String, Option<String>), RetentionOverride>>,
+    store: SyncRwLock<Option<Arc<dyn RetentionOverrideStore>>>,
+}
+
+impl std::fmt::Debug for RetentionManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetentionManager")
+            .field("config", &self.config)
+            .field("overrides", &self.overrides.read().len())
+            .finish()
+    }
+}
+
+impl RetentionManager {
+    pub fn new(config: RetentionConfig) -> Self {
+        Self {
+            config,
+            overrides: SyncRwLock::new(HashMap::new()),
+            store: SyncRwLock::new(None),
+        }
+    }
+
+    /// Persists overrides so they survive a restart
+    pub fn set_store(&self, store: Arc<dyn RetentionOverrideStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    pub fn config(&self) -> &RetentionConfig {
+        &self.config
+    }
+
+    /// Restores overrides from the store, returning how many are in force
+    pub async fn load(&self) -> Result<usize, RetentionError> {
+        let store = self.store.read().clone();
+        let Some(store) = store else {
+            return Ok(0);
+        };
+        let loaded = store.load().await?;
+        let mut overrides = self.overrides.write();
+        for entry in loaded {
+            overrides.insert((entry.trading_pair.clone(), entry.exchange.clone()), entry);
+        }
+        Ok(overrides.len())
+    }
+
+    /// Sets a pair's retention, replacing any override for the same pair and exchange
+    pub async fn set(
+        &self,
+        request: RetentionOverrideRequest,
+        updated_by: &str,
+        now: DateTime<Utc>,
+    ) -> Result<RetentionOverride, RetentionError> {
+        if request.days == 0 || request.days > self.config.max_days {
+            return Err(RetentionError::InvalidDays {
+                days: request.days,
+                max: self.config.max_days,
+            });
+        }
+        let entry = RetentionOverride {
+            trading_pair: canonical(&request.trading_pair)?,
+            exchange: canonical_exchange(request.exchange.as_deref()),
+            days: request.days,
+            updated_by: updated_by.to_string(),
+            updated_at: now,
+        };
+
+        let store = self.store.read().clone();
+        if let Some(store) = store {
+            store.save(&entry).await?;
+        }
+        self.overrides
+            .write()
+            .insert((entry.trading_pair.clone(), entry.exchange.clone()), entry.clone());
+        counter!(format!("{}.overrides_set", METRICS_PREFIX), 1);
+        info!(
+            trading_pair = %entry.trading_pair,
+            exchange = entry.exchange.as_deref().unwrap_or("*"),
+            days = entry.days,
+            updated_by,
+            "Retention override set"
+        );
+        Ok(entry)
+    }
+
+    /// Removes an override; the pair falls back to the next most specific rule
+    pub async fn remove(&self, trading_pair: &str, exchange: Option<&str>) -> Result<RetentionOverride, RetentionError> {
+        let key = (canonical(trading_pair)?, canonical_exchange(exchange));
+        let entry = self.overrides.read().get(&key).cloned().ok_or_else(|| {
+            RetentionError::NotFound(match &key.1 {
+                Some(exchange) => format!("{} on {}", key.0, exchange),
+                None => key.0.clone(),
+            })
+        })?;
+
+        let store = self.store.read().clone();
+        if let Some(store) = store {
+            store.remove(&key.0, key.1.as_deref()).await?;
+        }
+        self.overrides.write().remove(&key);
+        counter!(format!("{}.overrides_removed", METRICS_PREFIX), 1);
+        info!(trading_pair = %key.0, exchange = key.1.as_deref().unwrap_or("*"), "Retention override removed");
+        Ok(entry)
+    }
+
+    /// Overrides in pair then exchange order, pair-wide ones first
+    pub fn list(&self) -> Vec<RetentionOverride> {
+        let mut entries: Vec<_> = self.overrides.read().values().cloned().collect();
+        entries.sort_by(|a, b| (&a.trading_pair, &a.exchange).cmp(&(&b.trading_pair, &b.exchange)));
+        entries
+    }
+
+    /// Default and overrides as they stand now
+    pub fn schedule(&self) -> RetentionSchedule {
+        RetentionSchedule {
+            default_days: self.config.default_days,
+            overrides: self.list(),
+        }
+    }
+
+    /// Retention in force for each pair, plus a row per exchange-specific override
+    pub fn effective(&self, pairs: &[TradingPair]) -> Vec<EffectiveRetention> {
+        let schedule = self.schedule();
+        let mut rows: Vec<EffectiveRetention> = pairs
+            .iter()
+            .map(|pair| pair.as_str().to_string())
+            .chain(schedule.overrides.iter().map(|entry| entry.trading_pair.clone()))
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .map(|trading_pair| {
+                // No exchange matches the empty name, so only pair-wide rules apply
+                let (days, source) = schedule.days_for(&trading_pair, "");
+                EffectiveRetention {
+                    trading_pair,
+                    exchange: None,
+                    days,
+                    source,
+                }
+            })
+            .collect();
+        rows.extend(schedule.overrides.iter().filter(|entry| entry.exchange.is_some()).map(|entry| {
+            EffectiveRetention {
+                trading_pair: entry.trading_pair.clone(),
+                exchange: entry.exchange.clone(),
+                days: entry.days,
+                source: RetentionSource::PairExchange,
+            }
+        }));
+        rows.sort_by(|a, b| (&a.trading_pair, &a.exchange).cmp(&(&b.trading_pair, &b.exchange)));
+        rows
+    }
+
+    /// Applies the current overrides once, returning the rows deleted
+    pub async fn run_once(&self, pruner: &dyn MarketDataPruner, now: DateTime<Utc>) -> Result<u64, RetentionError> {
+        let schedule = self.schedule();
+        let deleted = pruner.prune(&schedule, now).await?;
+        counter!(format!("{}.rows_deleted", METRICS_PREFIX), deleted);
+        info!(deleted, overrides = schedule.overrides.len(), "Market data retention applied");
+        Ok(deleted)
+    }
+
+    /// Runs retention on the configured interval
+    pub fn spawn(self: Arc<Self>, pruner: Arc<dyn MarketDataPruner>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.run_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_once(pruner.as_ref(), Utc::now()).await {
+                    counter!(format!("{}.failures", METRICS_PREFIX), 1);
+                    warn!("Market data retention failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+/// Canonical pair key, so "sol-usdc" and "SOL/USDC" name the same override
+fn canonical(trading_pair: &str) -> Result<String, RetentionError> {
+    TradingPair::parse(trading_pair)
+        .map(TradingPair::into_string)
+        .map_err(|e| RetentionError::InvalidPair(e.to_string()))
+}
+
+/// Exchanges match case-insensitively; a blank name means every exchange
+fn canonical_exchange(exchange: Option<&str>) -> Option<String> {
+    exchange
+        .map(|exchange| exchange.trim().to_lowercase())
+        .filter(|exchange| !exchange.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use parking_lot::Mutex;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        overrides: Mutex<Vec<RetentionOverride>>,
+    }
+
+    #[async_trait]
+    impl RetentionOverrideStore for MemoryStore {
+        async fn save(&self, entry: &RetentionOverride) -> Result<(), RetentionError> {
+            let mut overrides = self.overrides.lock();
+            overrides.retain(|e| (&e.trading_pair, &e.exchange) != (&entry.trading_pair, &entry.exchange));
+            overrides.push(entry.clone());
+            Ok(())
+        }
+
+        async fn remove(&self, trading_pair: &str, exchange: Option<&str>) -> Result<(), RetentionError> {
+            self.overrides
+                .lock()
+                .retain(|e| (e.trading_pair.as_str(), e.exchange.as_deref()) != (trading_pair, exchange));
+            Ok(())
+        }
+
+        async fn load(&self) -> Result<Vec<RetentionOverride>, RetentionError> {
+            Ok(self.overrides.lock().clone())
+        }
+    }
+
+    fn request(trading_pair: &str, exchange: Option<&str>, days: u32) -> RetentionOverrideRequest {
+        RetentionOverrideRequest {
+            trading_pair: trading_pair.to_string(),
+            exchange: exchange.map(str::to_string),
+            days,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_most_specific_override_wins() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let manager = RetentionManager::new(RetentionConfig::default());
+        manager.set(request("sol-usdc", None, 365), "admin", now).await.unwrap();
+        manager.set(request("SOL/USDC", Some("Drift"), 30), "admin", now).await.unwrap();
+
+        let schedule = manager.schedule();
+        assert_eq!(schedule.days_for("SOL/USDC", "drift"), (30, RetentionSource::PairExchange));
+        assert_eq!(schedule.days_for("SOL/USDC", "jupiter"), (365, RetentionSource::Pair));
+        assert_eq!(schedule.days_for("RAY/USDC", "jupiter"), (90, RetentionSource::Default));
+
+        // Data older than its own pair's retention goes, regardless of the other rules
+        let aged = |days: i64| now - chrono::Duration::days(days);
+        assert!(schedule.is_expired("SOL/USDC", "Drift", aged(31), now));
+        assert!(!schedule.is_expired("SOL/USDC", "jupiter", aged(200), now));
+        assert!(schedule.is_expired("RAY/USDC", "jupiter", aged(91), now));
+
+        // Removing the exchange override falls back to the pair-wide one
+        manager.remove("SOL/USDC", Some("drift")).await.unwrap();
+        assert_eq!(manager.schedule().days_for("SOL/USDC", "drift"), (365, RetentionSource::Pair));
+        assert!(matches!(
+            manager.remove("SOL/USDC", Some("drift")).await,
+            Err(RetentionError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_overrides_validated_against_hard_maximum() {
+        let now = Utc::now();
+        let manager = RetentionManager::new(RetentionConfig::default());
+        assert!(matches!(
+            manager.set(request("SOL/USDC", None, MAX_RETENTION_DAYS + 1), "admin", now).await,
+            Err(RetentionError::InvalidDays { .. })
+        ));
+        assert!(matches!(
+            manager.set(request("SOL/USDC", None, 0), "admin", now).await,
+            Err(RetentionError::InvalidDays { .. })
+        ));
+        assert!(matches!(
+            manager.set(request("not a pair", None, 30), "admin", now).await,
+            Err(RetentionError::InvalidPair(_))
+        ));
+        assert!(manager.list().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_overrides_restored_from_store() {
+        let now = Utc::now();
+        let store = Arc::new(MemoryStore::default());
+        let manager = RetentionManager::new(RetentionConfig::default());
+        manager.set_store(store.clone());
+        manager.set(request("SOL/USDC", None, 365), "admin", now).await.unwrap();
+        manager.set(request("SOL/USDC", Some("drift"), 30), "admin", now).await.unwrap();
+        manager.set(request("SOL/USDC", Some("drift"), 45), "admin", now).await.unwrap();
+
+        let restored = RetentionManager::new(RetentionConfig::default());
+        restored.set_store(store);
+        assert_eq!(restored.load().await.unwrap(), 2);
+        assert_eq!(restored.list(), manager.list());
+    }
+
+    #[tokio::test]
+    async fn test_effective_retention_per_pair() {
+        let now = Utc::now();
+        let manager = RetentionManager::new(RetentionConfig::default());
+        manager.set(request("BONK/SOL", None, 7), "admin", now).await.unwrap();
+        manager.set(request("SOL/USDC", Some("drift"), 365), "admin", now).await.unwrap();
+
+        let pairs = [TradingPair::parse("SOL/USDC").unwrap()];
+        let effective = manager.effective(&pairs);
+        let rows: Vec<_> = effective
+            .iter()
+            .map(|row| (row.trading_pair.as_str(), row.exchange.as_deref(), row.days, row.source))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("BONK/SOL", None, 7, RetentionSource::Pair),
+                ("SOL/USDC", None, 90, RetentionSource::Default),
+                ("SOL/USDC", Some("drift"), 365, RetentionSource::PairExchange),
+            ]
+        );
+    }
+}
@@ -0,0 +1,592 @@
+//! Historical volatility and correlation analytics for portfolio risk. Maintains rolling
+//! log-return series per underlying asset from the price stream or persisted market data,
+//! and reports explicit insufficient-data markers instead of zeros when a series is too
+//! short or flat to estimate from.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - parking_lot = "0.12"
+//! - rust_decimal = "1.30"
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::RwLock;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::db::repositories::{MarketDataRepository, RepositoryError};
+use crate::models::market::{MarketData, MarketDataBatch, QuoteAsset};
+use crate::risk_manager::exposure::{underlying_asset, ExposureBook};
+use crate::utils::percent::Percent;
+
+// Analytics constants
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 3600.0;
+const MIN_RETURN_VARIANCE: f64 = 1e-18;
+const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_secs(3600);
+const DEFAULT_VOLATILITY_LOOKBACK: usize = 720; // 30 days of hourly returns
+const DEFAULT_CORRELATION_LOOKBACK: usize = 720;
+const DEFAULT_MIN_OBSERVATIONS: usize = 24;
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Rolling window configuration for volatility and correlation estimates
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AnalyticsConfig {
+    /// Prices are sampled once per interval, keeping the last price seen in each
+    pub sample_interval: Duration,
+    /// Returns used for volatility
+    pub volatility_lookback: usize,
+    /// Aligned returns used for pairwise correlation
+    pub correlation_lookback: usize,
+    /// Fewest returns an estimate is made from
+    pub min_observations: usize,
+    /// How long computed estimates are served before recomputing
+    pub cache_ttl: Duration,
+}
+
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            sample_interval: DEFAULT_SAMPLE_INTERVAL,
+            volatility_lookback: DEFAULT_VOLATILITY_LOOKBACK,
+            correlation_lookback: DEFAULT_CORRELATION_LOOKBACK,
+            min_observations: DEFAULT_MIN_OBSERVATIONS,
+            cache_ttl: DEFAULT_CACHE_TTL,
+        }
+    }
+}
+
+impl AnalyticsConfig {
+    /// Sampling periods per year, used to annualize volatility
+    fn periods_per_year(&self) -> f64 {
+        SECONDS_PER_YEAR / self.sample_interval.as_secs().max(1) as f64
+    }
+}
+
+/// Why an estimate could not be made
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InsufficientData {
+    TooFewObservations,
+    ConstantPrices,
+}
+
+/// Statistic estimated from a return series, or a marker explaining why it could not be
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Estimate {
+    Available { value: f64, observations: usize },
+    InsufficientData { reason: InsufficientData, observations: usize },
+}
+
+impl Estimate {
+    pub fn value(&self) -> Option<f64> {
+        match self {
+            Estimate::Available { value, .. } => Some(*value),
+            Estimate::InsufficientData { .. } => None,
+        }
+    }
+
+    fn insufficient(reason: InsufficientData, observations: usize) -> Self {
+        Estimate::InsufficientData { reason, observations }
+    }
+}
+
+/// Pairwise return correlations between assets
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CorrelationMatrix {
+    assets: Vec<String>,
+    values: Vec<Vec<Estimate>>,
+}
+
+impl CorrelationMatrix {
+    pub fn assets(&self) -> &[String] {
+        &self.assets
+    }
+
+    /// Correlation between two assets; an asset is always fully correlated with itself
+    pub fn get(&self, a: &str, b: &str) -> Option<Estimate> {
+        if a == b {
+            return Some(Estimate::Available { value: 1.0, observations: 0 });
+        }
+        let i = self.assets.iter().position(|asset| asset == a)?;
+        let j = self.assets.iter().position(|asset| asset == b)?;
+        Some(self.values[i][j])
+    }
+}
+
+/// Volatility and correlation estimates computed together
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalyticsSnapshot {
+    /// Annualized volatility of log returns per asset
+    pub volatility: BTreeMap<String, Estimate>,
+    pub correlations: CorrelationMatrix,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl AnalyticsSnapshot {
+    pub fn volatility(&self, asset: &str) -> Option<Estimate> {
+        self.volatility.get(asset).copied()
+    }
+
+    /// Copy with each listed asset's volatility scaled by its multiplier and, when given,
+    /// every pairwise correlation replaced by `correlation_override`
+    pub fn stressed(
+        &self,
+        volatility_multipliers: &BTreeMap<String, Decimal>,
+        correlation_override: Option<f64>,
+    ) -> Self {
+        let volatility = self
+            .volatility
+            .iter()
+            .map(|(asset, estimate)| {
+                let multiplier = volatility_multipliers.get(asset).and_then(|m| m.to_f64()).unwrap_or(1.0);
+                let estimate = match *estimate {
+                    Estimate::Available { value, observations } => Estimate::Available {
+                        value: value * multiplier,
+                        observations,
+                    },
+                    insufficient => insufficient,
+                };
+                (asset.clone(), estimate)
+            })
+            .collect();
+
+        let correlations = match correlation_override {
+            Some(rho) => {
+                let assets: Vec<String> = self.volatility.keys().cloned().collect();
+                let values = (0..assets.len())
+                    .map(|i| {
+                        (0..assets.len())
+                            .map(|j| Estimate::Available {
+                                value: if i == j { 1.0 } else { rho },
+                                observations: 0,
+                            })
+                            .collect()
+                    })
+                    .collect();
+                CorrelationMatrix { assets, values }
+            }
+            None => self.correlations.clone(),
+        };
+
+        Self {
+            volatility,
+            correlations,
+            computed_at: self.computed_at,
+        }
+    }
+
+    /// Annualized volatility of the book's net exposure as a fraction of equity. Fails with
+    /// the assets lacking a volatility or correlation estimate.
+    pub fn portfolio_volatility(&self, exposure: &ExposureBook) -> Result<Decimal, Vec<String>> {
+        let equity = exposure.equity().to_f64().unwrap_or(0.0);
+        let held: Vec<(&String, f64)> = exposure
+            .assets()
+            .iter()
+            .filter(|(_, e)| !e.net_value.is_zero())
+            .map(|(asset, e)| (asset, e.net_value.to_f64().unwrap_or(0.0)))
+            .collect();
+        if held.is_empty() || equity <= 0.0 {
+            return Ok(Decimal::ZERO);
+        }
+
+        let mut missing = Vec::new();
+        let mut sigma = HashMap::new();
+        for (asset, _) in &held {
+            match self.volatility(asset).and_then(|e| e.value()) {
+                Some(value) => {
+                    sigma.insert(asset.as_str(), value);
+                }
+                None => missing.push(asset.to_string()),
+            }
+        }
+
+        let mut variance = 0.0;
+        for (a, value_a) in &held {
+            for (b, value_b) in &held {
+                let (Some(sigma_a), Some(sigma_b)) = (sigma.get(a.as_str()), sigma.get(b.as_str())) else {
+                    continue;
+                };
+                match self.correlations.get(a, b).and_then(|e| e.value()) {
+                    Some(rho) => variance += (value_a / equity) * (value_b / equity) * rho * sigma_a * sigma_b,
+                    None => {
+                        for asset in [a, b] {
+                            if !missing.contains(*asset) {
+                                missing.push(asset.to_string());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if !missing.is_empty() {
+            missing.sort();
+            return Err(missing);
+        }
+        Ok(Decimal::from_f64_retain(variance.max(0.0).sqrt()).unwrap_or_default())
+    }
+
+    /// Largest exposure to one asset after adding the exposure of assets correlated with
+    /// it, as a percentage of equity. Where a correlation is unknown, same-direction
+    /// exposure counts in full and opposite exposure does not offset.
+    pub fn correlated_concentration_pct(&self, exposure: &ExposureBook) -> Percent {
+        let equity = exposure.equity();
+
+        exposure
+            .assets()
+            .iter()
+            .map(|(asset, own)| {
+                let correlated: Decimal = exposure
+                    .assets()
+                    .iter()
+                    .filter(|(other, _)| *other != asset)
+                    .map(|(other, e)| {
+                        match self.correlations.get(asset, other).and_then(|e| e.value()) {
+                            Some(rho) => e.net_value * Decimal::from_f64_retain(rho).unwrap_or_default(),
+                            None if e.net_value.is_sign_positive() == own.net_value.is_sign_positive() => e.net_value,
+                            None => Decimal::ZERO,
+                        }
+                    })
+                    .sum();
+                Percent::ratio((own.net_value + correlated).abs(), equity)
+            })
+            .max()
+            .unwrap_or(Percent::ZERO)
+    }
+}
+
+/// Rolling price samples per asset with cached volatility and correlation estimates
+#[derive(Debug)]
+pub struct RiskAnalytics {
+    config: AnalyticsConfig,
+    /// Last price per sampling bucket, oldest first
+    samples: RwLock<HashMap<String, VecDeque<(i64, f64)>>>,
+    cached: RwLock<Option<Arc<AnalyticsSnapshot>>>,
+}
+
+impl RiskAnalytics {
+    pub fn new(config: AnalyticsConfig) -> Self {
+        Self {
+            config,
+            samples: RwLock::new(HashMap::new()),
+            cached: RwLock::new(None),
+        }
+    }
+
+    pub fn config(&self) -> &AnalyticsConfig {
+        &self.config
+    }
+
+    /// Records a USDC-valued price; pairs quoted in other assets are ignored
+    pub fn record_price(&self, trading_pair: &str, price: Decimal, at: DateTime<Utc>) {
+        if QuoteAsset::of_pair(trading_pair) != QuoteAsset::Usdc {
+            return;
+        }
+        let Some(price) = price.to_f64().filter(|p| *p > 0.0) else {
+            return;
+        };
+
+        let bucket = at.timestamp().div_euclid(self.config.sample_interval.as_secs().max(1) as i64);
+        let capacity = self.config.volatility_lookback.max(self.config.correlation_lookback) + 1;
+        let mut samples = self.samples.write();
+        let series = samples.entry(underlying_asset(trading_pair)).or_default();
+
+        match series.back_mut() {
+            Some((last, value)) if *last == bucket => *value = price,
+            Some((last, _)) if *last > bucket => {
+                // Late data for a past bucket is slotted in place
+                match series.binary_search_by_key(&bucket, |(b, _)| *b) {
+                    Ok(index) => series[index].1 = price,
+                    Err(index) => series.insert(index, (bucket, price)),
+                }
+            }
+            _ => series.push_back((bucket, price)),
+        }
+        while series.len() > capacity {
+            series.pop_front();
+        }
+    }
+
+    pub fn record_market_data(&self, market_data: &MarketData) {
+        self.record_price(market_data.trading_pair(), market_data.price(), market_data.timestamp());
+    }
+
+    /// Seeds return series from persisted market data
+    pub async fn backfill(
+        &self,
+        repository: &MarketDataRepository,
+        trading_pairs: &[String],
+    ) -> Result<usize, RepositoryError> {
+        let limit = (self.config.volatility_lookback.max(self.config.correlation_lookback) + 1) as i64;
+        let mut loaded = 0;
+        for trading_pair in trading_pairs {
+            for record in repository.get_market_data(trading_pair, limit).await? {
+                self.record_price(&record.trading_pair, record.price, record.timestamp);
+                loaded += 1;
+            }
+        }
+        info!(loaded, pairs = trading_pairs.len(), "Backfilled risk analytics from market data");
+        Ok(loaded)
+    }
+
+    /// Feeds price updates from the aggregator stream
+    pub fn spawn_price_listener(
+        self: Arc<Self>,
+        mut prices: broadcast::Receiver<MarketDataBatch>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match prices.recv().await {
+                    Ok(batch) => batch.iter().for_each(|data| self.record_market_data(data)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(skipped, "Risk analytics lagged behind the price stream");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Cached estimates, recomputed once older than the cache TTL
+    pub fn snapshot(&self, now: DateTime<Utc>) -> Arc<AnalyticsSnapshot> {
+        if let Some(cached) = self.cached.read().as_ref() {
+            let age = now.signed_duration_since(cached.computed_at).to_std().unwrap_or_default();
+            if age < self.config.cache_ttl {
+                return cached.clone();
+            }
+        }
+
+        let snapshot = Arc::new(self.compute(now));
+        *self.cached.write() = Some(snapshot.clone());
+        counter!("trading_bot.risk_manager.analytics_recomputed", 1);
+        snapshot
+    }
+
+    /// Annualized volatility of an asset's log returns
+    pub fn volatility(&self, asset: &str) -> Estimate {
+        let returns = self.returns(asset);
+        let start = returns.len().saturating_sub(self.config.volatility_lookback);
+        let values: Vec<f64> = returns[start..].iter().map(|(_, r)| *r).collect();
+        self.annualized_volatility(&values)
+    }
+
+    /// Correlation of two assets' log returns over the periods both have data for
+    pub fn correlation(&self, a: &str, b: &str) -> Estimate {
+        let returns_b: HashMap<i64, f64> = self.returns(b).into_iter().collect();
+        let aligned: Vec<(f64, f64)> = self
+            .returns(a)
+            .into_iter()
+            .filter_map(|(bucket, r)| returns_b.get(&bucket).map(|rb| (r, *rb)))
+            .collect();
+        let start = aligned.len().saturating_sub(self.config.correlation_lookback);
+        self.pearson(&aligned[start..])
+    }
+
+    fn compute(&self, now: DateTime<Utc>) -> AnalyticsSnapshot {
+        let mut assets: Vec<String> = self.samples.read().keys().cloned().collect();
+        assets.sort();
+
+        let volatility = assets
+            .iter()
+            .map(|asset| (asset.clone(), self.volatility(asset)))
+            .collect();
+
+        let mut values = vec![vec![Estimate::Available { value: 1.0, observations: 0 }; assets.len()]; assets.len()];
+        for i in 0..assets.len() {
+            for j in (i + 1)..assets.len() {
+                let estimate = self.correlation(&assets[i], &assets[j]);
+                values[i][j] = estimate;
+                values[j][i] = estimate;
+            }
+        }
+
+        debug!(assets = assets.len(), "Computed volatility and correlation estimates");
+        AnalyticsSnapshot {
+            volatility,
+            correlations: CorrelationMatrix { assets, values },
+            computed_at: now,
+        }
+    }
+
+    /// Log returns between consecutive samples, keyed by the later sample's bucket
+    fn returns(&self, asset: &str) -> Vec<(i64, f64)> {
+        let samples = self.samples.read();
+        let Some(series) = samples.get(asset) else {
+            return Vec::new();
+        };
+        series
+            .iter()
+            .zip(series.iter().skip(1))
+            .map(|((_, previous), (bucket, price))| (*bucket, (price / previous).ln()))
+            .collect()
+    }
+
+    fn annualized_volatility(&self, returns: &[f64]) -> Estimate {
+        let n = returns.len();
+        if n < self.config.min_observations.max(2) {
+            return Estimate::insufficient(InsufficientData::TooFewObservations, n);
+        }
+        let variance = sample_variance(returns);
+        if variance < MIN_RETURN_VARIANCE {
+            return Estimate::insufficient(InsufficientData::ConstantPrices, n);
+        }
+        Estimate::Available {
+            value: (variance * self.config.periods_per_year()).sqrt(),
+            observations: n,
+        }
+    }
+
+    fn pearson(&self, pairs: &[(f64, f64)]) -> Estimate {
+        let n = pairs.len();
+        if n < self.config.min_observations.max(2) {
+            return Estimate::insufficient(InsufficientData::TooFewObservations, n);
+        }
+        let a: Vec<f64> = pairs.iter().map(|(a, _)| *a).collect();
+        let b: Vec<f64> = pairs.iter().map(|(_, b)| *b).collect();
+        let (var_a, var_b) = (sample_variance(&a), sample_variance(&b));
+        if var_a < MIN_RETURN_VARIANCE || var_b < MIN_RETURN_VARIANCE {
+            return Estimate::insufficient(InsufficientData::ConstantPrices, n);
+        }
+
+        let (mean_a, mean_b) = (mean(&a), mean(&b));
+        let covariance = pairs
+            .iter()
+            .map(|(x, y)| (x - mean_a) * (y - mean_b))
+            .sum::<f64>()
+            / (n - 1) as f64;
+        Estimate::Available {
+            value: (covariance / (var_a.sqrt() * var_b.sqrt())).clamp(-1.0, 1.0),
+            observations: n,
+        }
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn sample_variance(values: &[f64]) -> f64 {
+    let mean = mean(values);
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const DAY: i64 = 86_400;
+    const EPSILON: f64 = 1e-9;
+
+    fn daily_analytics(min_observations: usize) -> RiskAnalytics {
+        RiskAnalytics::new(AnalyticsConfig {
+            sample_interval: Duration::from_secs(DAY as u64),
+            volatility_lookback: 30,
+            correlation_lookback: 30,
+            min_observations,
+            cache_ttl: Duration::from_secs(60),
+        })
+    }
+
+    fn feed(analytics: &RiskAnalytics, pair: &str, prices: &[Decimal]) {
+        let start = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        for (day, price) in prices.iter().enumerate() {
+            // Intraday updates collapse to the day's last price
+            analytics.record_price(pair, price * dec!(0.5), start + chrono::Duration::seconds(day as i64 * DAY));
+            analytics.record_price(pair, *price, start + chrono::Duration::seconds(day as i64 * DAY + 3600));
+        }
+    }
+
+    #[test]
+    fn test_volatility_and_correlation_match_hand_computed_values() {
+        let analytics = daily_analytics(4);
+        // SOL returns: ln 1.1, ln 0.9, ln 1.1, ln 0.94
+        feed(&analytics, "SOL/USDC", &[dec!(100), dec!(110), dec!(99), dec!(108.9), dec!(102.366)]);
+        // ORCA returns: ln 1.04, ln 0.98, ln 1.05, ln 0.98
+        feed(&analytics, "ORCA/USDC", &[dec!(50), dec!(52), dec!(50.96), dec!(53.508), dec!(52.43784)]);
+
+        // SOL mean return 0.00584611, sample variance 0.01098692, daily sd 0.10481850,
+        // annualized by sqrt(365)
+        let sol = analytics.volatility("SOL").value().unwrap();
+        assert!((sol - 2.002554714405824).abs() < EPSILON, "SOL volatility {}", sol);
+        let orca = analytics.volatility("ORCA").value().unwrap();
+        assert!((orca - 0.7121546462071452).abs() < EPSILON, "ORCA volatility {}", orca);
+
+        // Both series rise and fall on the same days
+        let rho = analytics.correlation("SOL", "ORCA").value().unwrap();
+        assert!((rho - 0.9801254872290421).abs() < EPSILON, "correlation {}", rho);
+
+        let snapshot = analytics.snapshot(Utc::now());
+        assert_eq!(snapshot.correlations.get("ORCA", "SOL").unwrap().value(), Some(rho));
+        assert_eq!(snapshot.correlations.get("SOL", "SOL").unwrap().value(), Some(1.0));
+    }
+
+    #[test]
+    fn test_degenerate_series_are_marked_insufficient() {
+        let analytics = daily_analytics(4);
+        // A new pair with three prices has two returns
+        feed(&analytics, "BONK/USDC", &[dec!(0.00002), dec!(0.000021), dec!(0.000019)]);
+        feed(&analytics, "USDT/USDC", &[dec!(1), dec!(1), dec!(1), dec!(1), dec!(1), dec!(1)]);
+        feed(&analytics, "SOL/USDC", &[dec!(100), dec!(110), dec!(99), dec!(108.9), dec!(102.366), dec!(101)]);
+
+        assert_eq!(
+            analytics.volatility("BONK"),
+            Estimate::InsufficientData { reason: InsufficientData::TooFewObservations, observations: 2 }
+        );
+        assert_eq!(
+            analytics.volatility("USDT"),
+            Estimate::InsufficientData { reason: InsufficientData::ConstantPrices, observations: 5 }
+        );
+        assert_eq!(
+            analytics.correlation("SOL", "USDT"),
+            Estimate::InsufficientData { reason: InsufficientData::ConstantPrices, observations: 5 }
+        );
+        assert!(analytics.correlation("SOL", "BONK").value().is_none());
+        assert!(analytics.volatility("JUP").value().is_none());
+
+        // SOL-quoted prices are not USDC valuations and are ignored
+        analytics.record_price("BONK/SOL", dec!(0.0000002), Utc::now());
+        assert_eq!(analytics.returns("BONK").len(), 2);
+    }
+
+    #[test]
+    fn test_portfolio_volatility_and_correlated_concentration() {
+        let analytics = daily_analytics(4);
+        feed(&analytics, "SOL/USDC", &[dec!(100), dec!(110), dec!(99), dec!(108.9), dec!(102.366)]);
+        feed(&analytics, "ORCA/USDC", &[dec!(50), dec!(52), dec!(50.96), dec!(53.508), dec!(52.43784)]);
+        let snapshot = analytics.snapshot(Utc::now());
+
+        let mut book = ExposureBook::new(dec!(1000));
+        book.add_fill("SOL/USDC", dec!(2), dec!(100)); // 20% of equity
+        book.add_fill("ORCA/USDC", dec!(4), dec!(50)); // 20% of equity
+
+        // Weights 0.2 and 0.2: variance = 0.04 sa^2 + 0.04 sb^2 + 2 * 0.04 rho sa sb
+        let (sa, sb, rho) = (2.002554714405824, 0.7121546462071452, 0.9801254872290421);
+        let expected = (0.04 * sa * sa + 0.04 * sb * sb + 0.08 * rho * sa * sb).sqrt();
+        let volatility = snapshot.portfolio_volatility(&book).unwrap().to_f64().unwrap();
+        assert!((volatility - expected).abs() < 1e-9, "portfolio volatility {}", volatility);
+
+        // Naive per-asset weights are 20% each; correlated exposure is 20% + 0.98 * 20%
+        assert_eq!(book.max_net_concentration_pct(), Percent::from_percent(dec!(20)));
+        let concentration = snapshot.correlated_concentration_pct(&book).as_percent().to_f64().unwrap();
+        assert!((concentration - (20.0 + 20.0 * rho)).abs() < 1e-9);
+
+        // An asset without history makes portfolio volatility unknown rather than zero,
+        // and same-direction exposure with unknown correlation counts in full
+        book.add_fill("BONK/USDC", dec!(5000000), dec!(0.00002)); // 10% of equity
+        assert_eq!(snapshot.portfolio_volatility(&book), Err(vec!["BONK".to_string()]));
+        assert_eq!(snapshot.correlated_concentration_pct(&book), Percent::from_percent(dec!(50)));
+
+        // Opposite exposure with unknown correlation does not offset
+        book.add_fill("JUP/USDC", dec!(-100), dec!(1)); // -10% of equity
+        assert_eq!(snapshot.correlated_concentration_pct(&book), Percent::from_percent(dec!(50)));
+    }
+}
@@ -0,0 +1,526 @@
+//! Portfolio-level daily loss limit. P&L is measured from a configurable UTC reset time as
+//! the P&L realized since the reset plus the change in open positions' unrealized P&L. Past
+//! the soft threshold new risk is rejected; past the optional hard threshold open positions
+//! are flattened once. The day's window persists to Redis so a restart mid-day does not
+//! forget losses taken earlier that day.
+//!
+//! Version dependencies:
+//! - rust_decimal = "1.30"
+//! - chrono = "0.4"
+//! - redis = "0.23"
+//! - metrics = "0.20"
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, NaiveTime, TimeZone, Utc};
+use metrics::gauge;
+use redis::AsyncCommands; // v0.23.0
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::config::environment::EnvironmentConfig;
+use crate::utils::percent::Percent;
+
+// Daily loss constants
+const DEFAULT_MAX_LOSS_PCT: Percent = Percent::from_percent(Decimal::from_parts(5, 0, 0, false, 0));
+const DEFAULT_HARD_MAX_LOSS_PCT: Percent = Percent::from_percent(Decimal::from_parts(8, 0, 0, false, 0));
+const REDIS_KEY: &str = "risk:daily_loss";
+const PERSIST_TTL_SECS: usize = 2 * 86_400;
+const METRICS_PREFIX: &str = "trading_bot.risk_manager.daily_loss";
+
+/// Loss thresholds for one trading day; each may be set in USDC, as a share of the day's
+/// starting equity, or both, in which case the tighter applies
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DailyLossLimits {
+    /// Loss past which new risk is rejected
+    pub max_loss: Option<Decimal>,
+    pub max_loss_pct: Option<Percent>,
+    /// Loss past which open positions are flattened, when enabled
+    pub hard_max_loss: Option<Decimal>,
+    pub hard_max_loss_pct: Option<Percent>,
+    pub flatten_on_hard_breach: bool,
+    /// UTC time of day at which the loss budget resets
+    pub reset_time: NaiveTime,
+}
+
+impl Default for DailyLossLimits {
+    fn default() -> Self {
+        Self {
+            max_loss: None,
+            max_loss_pct: Some(DEFAULT_MAX_LOSS_PCT),
+            hard_max_loss: None,
+            hard_max_loss_pct: Some(DEFAULT_HARD_MAX_LOSS_PCT),
+            flatten_on_hard_breach: false,
+            reset_time: NaiveTime::from_hms_opt(0, 0, 0).expect("midnight is a valid time"),
+        }
+    }
+}
+
+impl DailyLossLimits {
+    /// Defaults overlaid with the thresholds and reset time set in the environment
+    pub fn from_environment(config: &EnvironmentConfig) -> Self {
+        let defaults = Self::default();
+        Self {
+            max_loss: config.daily_max_loss.or(defaults.max_loss),
+            max_loss_pct: config.daily_max_loss_pct.or(defaults.max_loss_pct),
+            hard_max_loss: config.daily_hard_max_loss.or(defaults.hard_max_loss),
+            hard_max_loss_pct: config.daily_hard_max_loss_pct.or(defaults.hard_max_loss_pct),
+            flatten_on_hard_breach: config.flatten_on_daily_hard_breach,
+            reset_time: config.daily_reset_time.unwrap_or(defaults.reset_time),
+        }
+    }
+
+    /// Most recent reset at or before `now`
+    pub fn day_start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let today = Utc.from_utc_datetime(&now.date_naive().and_time(self.reset_time));
+        if today <= now {
+            today
+        } else {
+            today - Duration::days(1)
+        }
+    }
+
+    /// Soft loss limit in USDC for a day that started at `start_equity`
+    pub fn soft_limit(&self, start_equity: Decimal) -> Option<Decimal> {
+        tighter_limit(self.max_loss, self.max_loss_pct, start_equity)
+    }
+
+    /// Hard loss limit in USDC for a day that started at `start_equity`
+    pub fn hard_limit(&self, start_equity: Decimal) -> Option<Decimal> {
+        tighter_limit(self.hard_max_loss, self.hard_max_loss_pct, start_equity)
+    }
+}
+
+fn tighter_limit(amount: Option<Decimal>, pct: Option<Percent>, start_equity: Decimal) -> Option<Decimal> {
+    // A percentage of an unknown starting equity limits nothing
+    let pct_amount = pct
+        .filter(|_| start_equity > Decimal::ZERO)
+        .map(|pct| pct.of(start_equity));
+    match (amount, pct_amount) {
+        (Some(amount), Some(pct_amount)) => Some(amount.min(pct_amount)),
+        (amount, pct_amount) => amount.or(pct_amount),
+    }
+}
+
+/// How far into the day's loss budget the portfolio is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DailyLossLevel {
+    Within,
+    /// New risk is rejected
+    SoftBreached,
+    /// Open positions are flattened, when enabled
+    HardBreached,
+}
+
+impl DailyLossLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Within => "within",
+            Self::SoftBreached => "soft",
+            Self::HardBreached => "hard",
+        }
+    }
+}
+
+/// P&L observed since one reset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyLossWindow {
+    pub day_start: DateTime<Utc>,
+    /// Equity at the start of the day, the base of percentage limits
+    pub start_equity: Decimal,
+    /// Latest marked equity, carried into the next day's window
+    pub equity: Decimal,
+    /// P&L realized since the reset
+    pub realized: Decimal,
+    /// Unrealized P&L of open positions at the reset and at the latest mark
+    pub start_unrealized: Decimal,
+    pub unrealized: Decimal,
+    /// Set once positions were flattened so the hard breach acts only once per day
+    pub flattened: bool,
+}
+
+impl DailyLossWindow {
+    fn new(day_start: DateTime<Utc>, equity: Decimal, unrealized: Decimal) -> Self {
+        Self {
+            day_start,
+            start_equity: equity,
+            equity,
+            realized: Decimal::ZERO,
+            start_unrealized: unrealized,
+            unrealized,
+            flattened: false,
+        }
+    }
+
+    /// Realized plus unrealized P&L since the reset
+    pub fn pnl(&self) -> Decimal {
+        self.realized + self.unrealized - self.start_unrealized
+    }
+
+    pub fn loss(&self) -> Decimal {
+        (-self.pnl()).max(Decimal::ZERO)
+    }
+}
+
+/// Position of the day's P&L against the configured limits
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyLossStatus {
+    pub day_start: DateTime<Utc>,
+    pub pnl: Decimal,
+    pub soft_limit: Option<Decimal>,
+    pub hard_limit: Option<Decimal>,
+    /// Loss still allowed before the soft limit; `None` when no limit applies
+    pub remaining_budget: Option<Decimal>,
+    pub level: DailyLossLevel,
+    /// True the first time the hard limit is crossed with flattening enabled
+    pub flatten: bool,
+}
+
+/// Daily loss limit crossed by the day's P&L
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyLossBreach {
+    pub level: DailyLossLevel,
+    pub loss: Decimal,
+    pub limit: Decimal,
+    pub day_start: DateTime<Utc>,
+}
+
+impl std::fmt::Display for DailyLossBreach {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} daily loss limit reached: lost {} since {}, limit {}",
+            self.level.as_str(),
+            self.loss,
+            self.day_start,
+            self.limit
+        )
+    }
+}
+
+/// Daily P&L tracker with optional Redis persistence across restarts
+#[derive(Debug)]
+pub struct DailyLossTracker {
+    limits: DailyLossLimits,
+    window: Option<DailyLossWindow>,
+    loaded: bool,
+    redis_client: Option<Arc<redis::Client>>,
+}
+
+impl DailyLossTracker {
+    pub fn new(limits: DailyLossLimits) -> Self {
+        Self {
+            limits,
+            window: None,
+            loaded: false,
+            redis_client: None,
+        }
+    }
+
+    /// Persists the day's window to Redis so restarts keep the day's losses
+    pub fn set_redis_client(&mut self, redis_client: Arc<redis::Client>) {
+        self.redis_client = Some(redis_client);
+    }
+
+    /// Replaces thresholds without resetting the day; a new reset time applies from the
+    /// next boundary it defines
+    pub fn update_limits(&mut self, limits: DailyLossLimits) {
+        self.limits = limits;
+    }
+
+    pub fn limits(&self) -> &DailyLossLimits {
+        &self.limits
+    }
+
+    /// Adds P&L realized by a reducing or closing fill
+    pub async fn record_realized(&mut self, now: DateTime<Utc>, pnl: Decimal) -> DailyLossStatus {
+        self.window_mut(now).await.realized += pnl;
+        self.settle(now).await
+    }
+
+    /// Marks open positions' unrealized P&L and the portfolio's equity
+    pub async fn mark(&mut self, now: DateTime<Utc>, unrealized: Decimal, equity: Decimal) -> DailyLossStatus {
+        let window = self.window_mut(now).await;
+        // The first equity seen today becomes the base when nothing was carried over
+        if window.start_equity <= Decimal::ZERO {
+            window.start_equity = equity;
+        }
+        window.unrealized = unrealized;
+        window.equity = equity;
+        self.settle(now).await
+    }
+
+    /// Rejects new risk once the day's loss reaches the soft limit
+    pub async fn check(&mut self, now: DateTime<Utc>) -> Result<(), DailyLossBreach> {
+        let limits = self.limits;
+        let window = self.window_mut(now).await;
+        let loss = window.loss();
+        let day_start = window.day_start;
+        for (level, limit) in [
+            (DailyLossLevel::HardBreached, limits.hard_limit(window.start_equity)),
+            (DailyLossLevel::SoftBreached, limits.soft_limit(window.start_equity)),
+        ] {
+            if let Some(limit) = limit.filter(|limit| loss >= *limit) {
+                return Err(DailyLossBreach {
+                    level,
+                    loss,
+                    limit,
+                    day_start,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Current status without recording anything
+    pub async fn status(&mut self, now: DateTime<Utc>) -> DailyLossStatus {
+        let limits = self.limits;
+        status(&limits, self.window_mut(now).await, false)
+    }
+
+    /// Copies the day's window for a state snapshot
+    pub fn snapshot(&self) -> Option<DailyLossWindow> {
+        self.window.clone()
+    }
+
+    /// Replaces the day's window with a snapshotted one
+    pub fn restore(&mut self, window: Option<DailyLossWindow>) {
+        self.window = window;
+        self.loaded = true;
+    }
+
+    /// Evaluates limits after a change, flagging a first hard breach and persisting
+    async fn settle(&mut self, now: DateTime<Utc>) -> DailyLossStatus {
+        let limits = self.limits;
+        let window = self.window_mut(now).await;
+        let status = status(&limits, window, limits.flatten_on_hard_breach && !window.flattened);
+        if status.flatten {
+            window.flattened = true;
+            warn!(pnl = %status.pnl, "Hard daily loss limit reached, flattening positions");
+        }
+
+        let window = window.clone();
+        record_gauges(&status);
+        self.persist(&window).await;
+        status
+    }
+
+    /// Today's window, restored from Redis on first use and rolled at the reset boundary
+    async fn window_mut(&mut self, now: DateTime<Utc>) -> &mut DailyLossWindow {
+        if !self.loaded {
+            self.loaded = true;
+            if self.window.is_none() {
+                self.window = self.load().await;
+            }
+        }
+
+        let day_start = self.limits.day_start(now);
+        let rolled = match self.window.take() {
+            Some(window) if window.day_start >= day_start => window,
+            Some(window) => {
+                info!(pnl = %window.pnl(), day_start = %day_start, "Daily loss window reset");
+                DailyLossWindow::new(day_start, window.equity, window.unrealized)
+            }
+            None => DailyLossWindow::new(day_start, Decimal::ZERO, Decimal::ZERO),
+        };
+        self.window.insert(rolled)
+    }
+
+    async fn load(&self) -> Option<DailyLossWindow> {
+        let client = self.redis_client.as_ref()?;
+        let mut conn = match client.get_async_connection().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!("Daily loss window restore failed: {}", e);
+                return None;
+            }
+        };
+
+        let raw: Option<String> = conn.get(REDIS_KEY).await.ok()?;
+        let window = serde_json::from_str(&raw?).ok()?;
+        debug!("Restored daily loss window from Redis");
+        Some(window)
+    }
+
+    async fn persist(&self, window: &DailyLossWindow) {
+        let Some(client) = &self.redis_client else {
+            return;
+        };
+        let Ok(payload) = serde_json::to_string(window) else {
+            return;
+        };
+
+        let result: redis::RedisResult<()> = async {
+            let mut conn = client.get_async_connection().await?;
+            conn.set_ex(REDIS_KEY, payload, PERSIST_TTL_SECS).await
+        }
+        .await;
+        if let Err(e) = result {
+            warn!("Daily loss window persistence failed: {}", e);
+        }
+    }
+}
+
+fn status(limits: &DailyLossLimits, window: &DailyLossWindow, may_flatten: bool) -> DailyLossStatus {
+    let loss = window.loss();
+    let soft_limit = limits.soft_limit(window.start_equity);
+    let hard_limit = limits.hard_limit(window.start_equity);
+    let level = if hard_limit.map_or(false, |limit| loss >= limit) {
+        DailyLossLevel::HardBreached
+    } else if soft_limit.map_or(false, |limit| loss >= limit) {
+        DailyLossLevel::SoftBreached
+    } else {
+        DailyLossLevel::Within
+    };
+
+    DailyLossStatus {
+        day_start: window.day_start,
+        pnl: window.pnl(),
+        soft_limit,
+        hard_limit,
+        remaining_budget: soft_limit.map(|limit| (limit - loss).max(Decimal::ZERO)),
+        level,
+        flatten: may_flatten && level == DailyLossLevel::HardBreached,
+    }
+}
+
+/// Publishes the day's P&L and remaining budget so alerts can fire before the limit
+fn record_gauges(status: &DailyLossStatus) {
+    gauge!(format!("{}.pnl", METRICS_PREFIX), status.pnl.to_f64().unwrap_or(0.0));
+    if let Some(remaining) = status.remaining_budget {
+        gauge!(
+            format!("{}.remaining_budget", METRICS_PREFIX),
+            remaining.to_f64().unwrap_or(0.0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 14, hour, minute, 0).unwrap()
+    }
+
+    fn tracker(flatten: bool) -> DailyLossTracker {
+        DailyLossTracker::new(DailyLossLimits {
+            max_loss: Some(dec!(600)),
+            max_loss_pct: Some(Percent::from_percent(dec!(5))),
+            hard_max_loss: None,
+            hard_max_loss_pct: Some(Percent::from_percent(dec!(8))),
+            flatten_on_hard_breach: flatten,
+            reset_time: NaiveTime::from_hms_opt(13, 30, 0).unwrap(),
+        })
+    }
+
+    #[test]
+    fn test_day_start_follows_reset_time() {
+        let limits = tracker(false).limits;
+        assert_eq!(limits.day_start(at(14, 0)), at(13, 30));
+        assert_eq!(limits.day_start(at(13, 30)), at(13, 30));
+        assert_eq!(limits.day_start(at(9, 0)), at(13, 30) - Duration::days(1));
+
+        // The tighter of the USDC and percentage limits applies
+        assert_eq!(limits.soft_limit(dec!(10000)), Some(dec!(500)));
+        assert_eq!(limits.soft_limit(dec!(20000)), Some(dec!(600)));
+        assert_eq!(limits.soft_limit(Decimal::ZERO), Some(dec!(600)));
+    }
+
+    #[test]
+    fn test_limits_from_environment_reset_off_midnight() {
+        let mut config = EnvironmentConfig::new();
+        config.daily_max_loss = Some(dec!(750));
+        config.flatten_on_daily_hard_breach = true;
+        config.daily_reset_time = NaiveTime::from_hms_opt(17, 0, 0);
+
+        let limits = DailyLossLimits::from_environment(&config);
+        assert_eq!(limits.day_start(at(18, 0)), at(17, 0));
+        assert_eq!(limits.day_start(at(9, 0)), at(17, 0) - Duration::days(1));
+        assert_eq!(limits.soft_limit(dec!(100000)), Some(dec!(750)));
+        assert_eq!(limits.hard_max_loss_pct, Some(DEFAULT_HARD_MAX_LOSS_PCT));
+        assert!(limits.flatten_on_hard_breach);
+    }
+
+    #[tokio::test]
+    async fn test_losses_cross_soft_then_hard_threshold() {
+        let mut tracker = tracker(true);
+        let status = tracker.mark(at(14, 0), Decimal::ZERO, dec!(10000)).await;
+        assert_eq!(status.remaining_budget, Some(dec!(500)));
+
+        // Realized and unrealized losses add up
+        tracker.record_realized(at(15, 0), dec!(-300)).await;
+        let status = tracker.mark(at(15, 5), dec!(-150), dec!(9550)).await;
+        assert_eq!(status.pnl, dec!(-450));
+        assert_eq!(status.remaining_budget, Some(dec!(50)));
+        assert_eq!(status.level, DailyLossLevel::Within);
+        assert!(tracker.check(at(15, 5)).await.is_ok());
+
+        let status = tracker.mark(at(16, 0), dec!(-200), dec!(9500)).await;
+        assert_eq!(status.level, DailyLossLevel::SoftBreached);
+        assert_eq!(status.remaining_budget, Some(Decimal::ZERO));
+        assert!(!status.flatten);
+        let breach = tracker.check(at(16, 0)).await.unwrap_err();
+        assert_eq!(breach.level, DailyLossLevel::SoftBreached);
+        assert_eq!(breach.loss, dec!(500));
+
+        // Hard limit at 8% of the starting 10,000 flattens once
+        let status = tracker.mark(at(17, 0), dec!(-500), dec!(9200)).await;
+        assert_eq!(status.level, DailyLossLevel::HardBreached);
+        assert!(status.flatten);
+        assert!(!tracker.mark(at(17, 1), dec!(-520), dec!(9180)).await.flatten);
+        assert_eq!(tracker.check(at(17, 1)).await.unwrap_err().level, DailyLossLevel::HardBreached);
+    }
+
+    #[tokio::test]
+    async fn test_hard_breach_without_flattening() {
+        let mut tracker = tracker(false);
+        tracker.mark(at(14, 0), Decimal::ZERO, dec!(10000)).await;
+        let status = tracker.record_realized(at(15, 0), dec!(-900)).await;
+        assert_eq!(status.level, DailyLossLevel::HardBreached);
+        assert!(!status.flatten);
+    }
+
+    #[tokio::test]
+    async fn test_window_resets_at_rollover() {
+        let mut tracker = tracker(true);
+        tracker.mark(at(14, 0), Decimal::ZERO, dec!(10000)).await;
+        tracker.record_realized(at(15, 0), dec!(-600)).await;
+        tracker.mark(at(15, 0), dec!(-100), dec!(9300)).await;
+        assert!(tracker.check(at(13, 29) + Duration::days(1)).await.is_err());
+
+        // The new day starts from the carried equity and the open positions' marks
+        let next_day = at(13, 30) + Duration::days(1);
+        assert!(tracker.check(next_day).await.is_ok());
+        let status = tracker.status(next_day).await;
+        assert_eq!(status.day_start, next_day);
+        assert_eq!(status.pnl, Decimal::ZERO);
+        assert_eq!(status.remaining_budget, Some(dec!(465)));
+
+        // Only the unrealized move after the reset counts against the new day
+        let status = tracker.mark(next_day + Duration::hours(1), dec!(-200), dec!(9200)).await;
+        assert_eq!(status.pnl, dec!(-100));
+        assert!(!status.flatten);
+    }
+
+    #[tokio::test]
+    async fn test_restored_window_keeps_todays_losses() {
+        let mut tracker = tracker(false);
+        tracker.mark(at(14, 0), Decimal::ZERO, dec!(10000)).await;
+        tracker.record_realized(at(15, 0), dec!(-520)).await;
+
+        // A restart mid-day resumes the same window
+        let mut restarted = self::tracker(false);
+        restarted.restore(tracker.snapshot());
+        assert_eq!(restarted.check(at(18, 0)).await.unwrap_err().loss, dec!(520));
+
+        // A window from a previous day is rolled rather than enforced
+        let mut next_day = self::tracker(false);
+        next_day.restore(tracker.snapshot());
+        assert!(next_day.check(at(14, 0) + Duration::days(1)).await.is_ok());
+    }
+}
@@ -0,0 +1,388 @@
+//! Degraded-mode sizing. As the health monitor's system quality score drops, validated
+//! orders are scaled down along a configurable curve rather than trading stopping outright;
+//! where the curve reaches zero, new orders are halted. A strategy is exempted through
+//! configuration or the admin API; admin exemptions are held in memory so they lapse on
+//! restart.
+//!
+//! Version dependencies:
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+//! - metrics = "0.20"
+//! - utoipa = "3.5"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use metrics::gauge;
+use parking_lot::RwLock as SyncRwLock;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use thiserror::Error;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::config::environment::EnvironmentConfig;
+use crate::utils::health::HealthMonitor;
+
+// Degradation sizing constants
+const METRICS_PREFIX: &str = "trading_bot.risk_manager.degradation";
+const MULTIPLIER_DECIMALS: u32 = 4;
+/// Recorded as the author of exemptions set through configuration
+const CONFIG_AUTHOR: &str = "config";
+
+/// Degraded-mode sizing errors
+#[derive(Error, Debug)]
+pub enum DegradationError {
+    #[error("invalid sizing curve: {0}")]
+    InvalidCurve(String),
+    #[error("invalid strategy id: {0}")]
+    InvalidStrategy(String),
+}
+
+/// Size multiplier reached at a system quality score
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizingBreakpoint {
+    pub score: Decimal,
+    pub multiplier: Decimal,
+}
+
+impl SizingBreakpoint {
+    pub const fn new(score: Decimal, multiplier: Decimal) -> Self {
+        Self { score, multiplier }
+    }
+}
+
+/// Size multiplier by system quality score, interpolated linearly between breakpoints;
+/// scores beyond either end take the nearest breakpoint's multiplier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DegradationCurve {
+    breakpoints: Vec<SizingBreakpoint>,
+}
+
+impl Default for DegradationCurve {
+    /// Full size above 0.9, half size at 0.7 and halted at 0.5 and below
+    fn default() -> Self {
+        Self {
+            breakpoints: vec![
+                SizingBreakpoint::new(Decimal::new(9, 1), Decimal::ONE),
+                SizingBreakpoint::new(Decimal::new(7, 1), Decimal::new(5, 1)),
+                SizingBreakpoint::new(Decimal::new(5, 1), Decimal::ZERO),
+            ],
+        }
+    }
+}
+
+impl DegradationCurve {
+    /// Builds a curve from breakpoints in any order; a lower score may never size larger
+    pub fn new(mut breakpoints: Vec<SizingBreakpoint>) -> Result<Self, DegradationError> {
+        if breakpoints.is_empty() {
+            return Err(DegradationError::InvalidCurve("no breakpoints".to_string()));
+        }
+        let unit = Decimal::ZERO..=Decimal::ONE;
+        if let Some(point) = breakpoints
+            .iter()
+            .find(|point| !unit.contains(&point.score) || !unit.contains(&point.multiplier))
+        {
+            return Err(DegradationError::InvalidCurve(format!(
+                "score {} and multiplier {} must both lie between 0 and 1",
+                point.score, point.multiplier
+            )));
+        }
+
+        breakpoints.sort_by(|a, b| b.score.cmp(&a.score));
+        for pair in breakpoints.windows(2) {
+            if pair[0].score == pair[1].score {
+                return Err(DegradationError::InvalidCurve(format!(
+                    "duplicate breakpoint at score {}",
+                    pair[0].score
+                )));
+            }
+            if pair[1].multiplier > pair[0].multiplier {
+                return Err(DegradationError::InvalidCurve(format!(
+                    "multiplier rises from {} to {} as the score drops to {}",
+                    pair[0].multiplier, pair[1].multiplier, pair[1].score
+                )));
+            }
+        }
+        Ok(Self { breakpoints })
+    }
+
+    /// Curve set in the environment, or the default when none is set
+    pub fn from_environment(config: &EnvironmentConfig) -> Self {
+        if config.degradation_breakpoints.is_empty() {
+            return Self::default();
+        }
+        let breakpoints = config
+            .degradation_breakpoints
+            .iter()
+            .map(|(score, multiplier)| SizingBreakpoint::new(*score, *multiplier))
+            .collect();
+        Self::new(breakpoints).unwrap_or_else(|e| {
+            warn!("Ignoring configured degradation curve: {}", e);
+            Self::default()
+        })
+    }
+
+    /// Breakpoints from the highest score down
+    pub fn breakpoints(&self) -> &[SizingBreakpoint] {
+        &self.breakpoints
+    }
+
+    /// Multiplier applied to order size at the given score
+    pub fn multiplier(&self, score: Decimal) -> Decimal {
+        let (Some(first), Some(last)) = (self.breakpoints.first(), self.breakpoints.last()) else {
+            return Decimal::ONE;
+        };
+        if score >= first.score {
+            return first.multiplier;
+        }
+        for pair in self.breakpoints.windows(2) {
+            let (upper, lower) = (pair[0], pair[1]);
+            if score >= lower.score {
+                let position = (score - lower.score) / (upper.score - lower.score);
+                return (lower.multiplier + (upper.multiplier - lower.multiplier) * position)
+                    .round_dp(MULTIPLIER_DECIMALS);
+            }
+        }
+        last.multiplier
+    }
+}
+
+/// Strategy exempted from degraded-mode sizing
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct SizingOptOut {
+    pub strategy_id: String,
+    /// Admin who exempted the strategy
+    pub set_by: String,
+    pub set_at: DateTime<Utc>,
+}
+
+/// Sizing decision for one order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizeAdjustment {
+    /// System quality score the multiplier was read at; `None` before the first score
+    pub score: Option<Decimal>,
+    pub multiplier: Decimal,
+    /// Whether the strategy's opt-out kept it at full size
+    pub opted_out: bool,
+}
+
+impl SizeAdjustment {
+    /// Full size, for orders placed before any quality score or by an opted-out strategy
+    fn full(score: Option<Decimal>, opted_out: bool) -> Self {
+        Self {
+            score,
+            multiplier: Decimal::ONE,
+            opted_out,
+        }
+    }
+
+    /// Order size after the multiplier
+    pub fn apply(&self, size: Decimal) -> Decimal {
+        size * self.multiplier
+    }
+
+    /// Whether the curve has reached zero and no order may be placed
+    pub fn halts(&self) -> bool {
+        self.multiplier.is_zero()
+    }
+}
+
+/// Reads the system quality score and sizes orders along the degradation curve
+#[derive(Debug)]
+pub struct DegradationSizer {
+    curve: SyncRwLock<DegradationCurve>,
+    health: SyncRwLock<Option<Arc<HealthMonitor>>>,
+    opt_outs: SyncRwLock<HashMap<String, SizingOptOut>>,
+}
+
+impl DegradationSizer {
+    pub fn new(curve: DegradationCurve) -> Self {
+        Self {
+            curve: SyncRwLock::new(curve),
+            health: SyncRwLock::new(None),
+            opt_outs: SyncRwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Reads the system quality score from this monitor; orders trade at full size until set
+    pub fn set_health(&self, health: Arc<HealthMonitor>) {
+        *self.health.write() = Some(health);
+    }
+
+    pub fn curve(&self) -> DegradationCurve {
+        self.curve.read().clone()
+    }
+
+    /// Replaces the curve; opt-outs are kept
+    pub fn set_curve(&self, curve: DegradationCurve) {
+        *self.curve.write() = curve;
+    }
+
+    /// Exempts a strategy from degraded-mode sizing or lifts its exemption; only the admin
+    /// API calls this. Returns the exemption now in effect.
+    pub fn set_opt_out(
+        &self,
+        strategy_id: &str,
+        opted_out: bool,
+        set_by: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<SizingOptOut>, DegradationError> {
+        if strategy_id.trim().is_empty() {
+            return Err(DegradationError::InvalidStrategy("strategy id is empty".to_string()));
+        }
+
+        let mut opt_outs = self.opt_outs.write();
+        if !opted_out {
+            if opt_outs.remove(strategy_id).is_some() {
+                info!(strategy_id, set_by, "Strategy returned to degraded-mode sizing");
+            }
+            return Ok(None);
+        }
+        let entry = opt_outs
+            .entry(strategy_id.to_string())
+            .or_insert_with(|| {
+                info!(strategy_id, set_by, "Strategy exempted from degraded-mode sizing");
+                SizingOptOut {
+                    strategy_id: strategy_id.to_string(),
+                    set_by: set_by.to_string(),
+                    set_at: now,
+                }
+            })
+            .clone();
+        Ok(Some(entry))
+    }
+
+    /// Replaces the exemptions set through configuration; admin exemptions are kept
+    pub fn set_config_opt_outs(&self, strategy_ids: &[String], now: DateTime<Utc>) {
+        let mut opt_outs = self.opt_outs.write();
+        opt_outs.retain(|strategy_id, opt_out| opt_out.set_by != CONFIG_AUTHOR || strategy_ids.contains(strategy_id));
+        for strategy_id in strategy_ids {
+            opt_outs.entry(strategy_id.clone()).or_insert_with(|| {
+                info!(strategy_id = %strategy_id, "Strategy exempted from degraded-mode sizing by config");
+                SizingOptOut {
+                    strategy_id: strategy_id.clone(),
+                    set_by: CONFIG_AUTHOR.to_string(),
+                    set_at: now,
+                }
+            });
+        }
+    }
+
+    /// Strategies currently exempted from degraded-mode sizing
+    pub fn opt_outs(&self) -> Vec<SizingOptOut> {
+        let mut opt_outs: Vec<SizingOptOut> = self.opt_outs.read().values().cloned().collect();
+        opt_outs.sort_by(|a, b| a.strategy_id.cmp(&b.strategy_id));
+        opt_outs
+    }
+
+    pub fn is_opted_out(&self, strategy_id: &str) -> bool {
+        self.opt_outs.read().contains_key(strategy_id)
+    }
+
+    /// Multiplier for the strategy's next order at the current quality score, exported as a
+    /// gauge per strategy
+    pub fn adjustment(&self, strategy_id: &str) -> SizeAdjustment {
+        let score = self
+            .health
+            .read()
+            .as_ref()
+            .and_then(|health| health.quality_score())
+            .and_then(Decimal::from_f64)
+            .map(|score| score.round_dp(MULTIPLIER_DECIMALS));
+        let adjustment = match score {
+            _ if self.is_opted_out(strategy_id) => SizeAdjustment::full(score, true),
+            Some(score) => SizeAdjustment {
+                score: Some(score),
+                multiplier: self.curve.read().multiplier(score),
+                opted_out: false,
+            },
+            None => SizeAdjustment::full(None, false),
+        };
+
+        let labels = [("strategy_id", strategy_id.to_string())];
+        gauge!(
+            format!("{}.size_multiplier", METRICS_PREFIX),
+            adjustment.multiplier.to_f64().unwrap_or(0.0),
+            &labels
+        );
+        adjustment
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::health::{ComponentStatus, QualityInputs};
+    use crate::utils::metrics::MetricsCollector;
+    use rust_decimal_macros::dec;
+    use std::time::Duration;
+
+    #[test]
+    fn test_default_curve_sizes() {
+        let curve = DegradationCurve::default();
+        let sized = |score: Decimal| curve.multiplier(score) * dec!(10);
+
+        assert_eq!(sized(dec!(1)), dec!(10));
+        assert_eq!(sized(dec!(0.9)), dec!(10));
+        assert_eq!(sized(dec!(0.8)), dec!(7.5));
+        assert_eq!(sized(dec!(0.7)), dec!(5));
+        assert_eq!(sized(dec!(0.6)), dec!(2.5));
+        assert_eq!(sized(dec!(0.5)), dec!(0));
+        assert_eq!(sized(dec!(0.2)), dec!(0));
+    }
+
+    #[test]
+    fn test_curve_rejects_rising_multiplier() {
+        let curve = DegradationCurve::new(vec![
+            SizingBreakpoint::new(dec!(0.4), dec!(0.25)),
+            SizingBreakpoint::new(dec!(0.8), dec!(1)),
+        ])
+        .unwrap();
+        assert_eq!(curve.breakpoints()[0].score, dec!(0.8));
+        // Beyond the lowest breakpoint the curve stays flat rather than halting
+        assert_eq!(curve.multiplier(dec!(0.1)), dec!(0.25));
+
+        assert!(DegradationCurve::new(vec![
+            SizingBreakpoint::new(dec!(0.9), dec!(0.5)),
+            SizingBreakpoint::new(dec!(0.7), dec!(0.8)),
+        ])
+        .is_err());
+        assert!(DegradationCurve::new(vec![SizingBreakpoint::new(dec!(1.2), dec!(1))]).is_err());
+        assert!(DegradationCurve::new(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_opted_out_strategy_keeps_full_size() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let health = Arc::new(HealthMonitor::new(Duration::from_secs(30), metrics));
+        let sizer = DegradationSizer::new(DegradationCurve::default());
+        assert_eq!(sizer.adjustment("grid-1"), SizeAdjustment::full(None, false));
+
+        sizer.set_health(health.clone());
+        health.record_quality(&QualityInputs {
+            collector_staleness: Duration::from_secs(1),
+            aggregator_confidence: 0.6,
+            rpc: ComponentStatus::Healthy,
+            persistence_backlog: 0,
+            slippage_ratio: 1.0,
+        });
+        assert_eq!(sizer.adjustment("grid-1").apply(dec!(10)), dec!(2.5));
+
+        let now = Utc::now();
+        let opt_out = sizer.set_opt_out("grid-1", true, "admin", now).unwrap().unwrap();
+        assert_eq!(opt_out.set_by, "admin");
+        let adjustment = sizer.adjustment("grid-1");
+        assert!(adjustment.opted_out);
+        assert_eq!(adjustment.score, Some(dec!(0.6)));
+        assert_eq!(adjustment.apply(dec!(10)), dec!(10));
+        assert_eq!(sizer.adjustment("arb-1").apply(dec!(10)), dec!(2.5));
+
+        assert_eq!(sizer.set_opt_out("grid-1", false, "admin", now).unwrap(), None);
+        assert!(sizer.opt_outs().is_empty());
+        assert_eq!(sizer.adjustment("grid-1").apply(dec!(10)), dec!(2.5));
+        assert!(sizer.set_opt_out(" ", true, "admin", now).is_err());
+    }
+}
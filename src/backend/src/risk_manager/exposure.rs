@@ -0,0 +1,367 @@
+//! Per-asset exposure aggregation across strategy and wallet books, netting offsetting
+//! positions in the same underlying while tracking the gross capital they tie up.
+//!
+//! Version dependencies:
+//! - rust_decimal = "1.30"
+//! - serde = "1.0"
+//! - utoipa = "3.5"
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use utoipa::ToSchema;
+
+use crate::models::portfolio::Position;
+use crate::utils::percent::Percent;
+
+// Exposure limit defaults
+const DEFAULT_MAX_NET_CONCENTRATION: Percent = Percent::from_percent(Decimal::new(25, 0)); // 25%
+const DEFAULT_MAX_GROSS_LEVERAGE: Decimal = Decimal::new(3, 0); // 3x
+
+/// Wrapped or venue-specific symbols that trade the same underlying
+const ASSET_ALIASES: &[(&str, &str)] = &[("WSOL", "SOL"), ("WBTC", "BTC"), ("WETH", "ETH")];
+
+/// Maps a trading pair to its underlying asset, e.g. SOL/USDC and SOL-PERP both map to SOL
+pub fn underlying_asset(trading_pair: &str) -> String {
+    let base = trading_pair
+        .split(['/', '-', '_'])
+        .next()
+        .unwrap_or(trading_pair)
+        .trim()
+        .to_uppercase();
+
+    ASSET_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == base)
+        .map(|(_, asset)| asset.to_string())
+        .unwrap_or(base)
+}
+
+/// Direction of a proposed trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+impl TradeSide {
+    /// Applies the side to an unsigned size (positive buys, negative sells)
+    pub fn signed(&self, size: Decimal) -> Decimal {
+        match self {
+            TradeSide::Buy => size.abs(),
+            TradeSide::Sell => -size.abs(),
+        }
+    }
+}
+
+/// Limits applied to aggregated exposure
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExposureLimits {
+    /// Largest net exposure to a single asset as a percentage of equity
+    pub max_net_concentration_pct: Percent,
+    /// Largest sum of gross exposure across assets as a multiple of equity
+    pub max_gross_leverage: Decimal,
+}
+
+impl Default for ExposureLimits {
+    fn default() -> Self {
+        Self {
+            max_net_concentration_pct: DEFAULT_MAX_NET_CONCENTRATION,
+            max_gross_leverage: DEFAULT_MAX_GROSS_LEVERAGE,
+        }
+    }
+}
+
+/// Net and gross exposure to one underlying asset
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AssetExposure {
+    /// Signed size after offsetting longs against shorts
+    pub net_size: Decimal,
+    /// Sum of absolute position sizes
+    pub gross_size: Decimal,
+    /// Signed notional after offsetting longs against shorts
+    pub net_value: Decimal,
+    /// Sum of absolute position notionals
+    pub gross_value: Decimal,
+}
+
+impl AssetExposure {
+    fn add(&mut self, size: Decimal, price: Decimal) {
+        self.net_size += size;
+        self.gross_size += size.abs();
+        self.net_value += size * price;
+        self.gross_value += (size * price).abs();
+    }
+}
+
+/// Outcome of evaluating a fill against the aggregated book
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExposureCheck {
+    pub asset: String,
+    pub net_value_before: Decimal,
+    pub net_value_after: Decimal,
+    pub concentration_pct: Percent,
+    pub gross_leverage: Decimal,
+    /// Description of the first limit breached, if any
+    pub breach: Option<String>,
+}
+
+/// Exposure per underlying asset aggregated across every strategy and wallet book
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExposureBook {
+    assets: BTreeMap<String, AssetExposure>,
+    equity: Decimal,
+}
+
+impl ExposureBook {
+    /// Creates an empty book measured against the given total equity
+    pub fn new(equity: Decimal) -> Self {
+        Self {
+            assets: BTreeMap::new(),
+            equity,
+        }
+    }
+
+    /// Adds positions from one book, pricing each at its market price or entry price when
+    /// no quote is available
+    pub fn add_positions<'a>(
+        &mut self,
+        positions: impl IntoIterator<Item = &'a Position>,
+        market_prices: &HashMap<String, Decimal>,
+    ) {
+        for position in positions {
+            let price = market_prices
+                .get(position.trading_pair.as_str())
+                .copied()
+                .unwrap_or(position.entry_price);
+            self.add_fill(&position.trading_pair, position.size, price);
+        }
+    }
+
+    /// Adds a signed size in a trading pair to its underlying asset
+    pub fn add_fill(&mut self, trading_pair: &str, size: Decimal, price: Decimal) {
+        self.assets
+            .entry(underlying_asset(trading_pair))
+            .or_default()
+            .add(size, price);
+    }
+
+    pub fn get(&self, asset: &str) -> Option<&AssetExposure> {
+        self.assets.get(asset)
+    }
+
+    pub fn assets(&self) -> &BTreeMap<String, AssetExposure> {
+        &self.assets
+    }
+
+    pub fn equity(&self) -> Decimal {
+        self.equity
+    }
+
+    /// Sum of absolute net notional across assets
+    pub fn total_net_value(&self) -> Decimal {
+        self.assets.values().map(|e| e.net_value.abs()).sum()
+    }
+
+    /// Sum of gross notional across assets
+    pub fn total_gross_value(&self) -> Decimal {
+        self.assets.values().map(|e| e.gross_value).sum()
+    }
+
+    /// Largest absolute net exposure to a single asset as a percentage of equity
+    pub fn max_net_concentration_pct(&self) -> Percent {
+        self.assets
+            .values()
+            .map(|e| Percent::ratio(e.net_value.abs(), self.equity))
+            .max()
+            .unwrap_or(Percent::ZERO)
+    }
+
+    /// Gross notional as a multiple of equity
+    pub fn gross_leverage(&self) -> Decimal {
+        if self.equity <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        self.total_gross_value() / self.equity
+    }
+
+    /// Returns the first limit the current book breaches
+    pub fn breach(&self, limits: &ExposureLimits) -> Option<String> {
+        let concentration = self.max_net_concentration_pct();
+        if concentration > limits.max_net_concentration_pct {
+            return Some(format!(
+                "net concentration {} exceeds maximum {}",
+                concentration.round_dp(2),
+                limits.max_net_concentration_pct
+            ));
+        }
+
+        let leverage = self.gross_leverage();
+        if leverage > limits.max_gross_leverage {
+            return Some(format!(
+                "gross leverage {}x exceeds maximum {}x",
+                leverage.round_dp(2),
+                limits.max_gross_leverage
+            ));
+        }
+
+        None
+    }
+
+    /// Evaluates the book as it would stand after a signed fill
+    pub fn evaluate_fill(
+        &self,
+        trading_pair: &str,
+        size: Decimal,
+        price: Decimal,
+        limits: &ExposureLimits,
+    ) -> ExposureCheck {
+        let asset = underlying_asset(trading_pair);
+        let net_value_before = self.get(&asset).map(|e| e.net_value).unwrap_or_default();
+
+        // Gross is measured conservatively, as if the fill opened a position in a new book
+        let mut after = self.clone();
+        after.add_fill(trading_pair, size, price);
+        let net_value_after = after.get(&asset).map(|e| e.net_value).unwrap_or_default();
+
+        // Only the traded asset's concentration matters for the trade decision
+        let concentration_pct = Percent::ratio(net_value_after.abs(), after.equity);
+        let gross_leverage = after.gross_leverage();
+
+        // Trades that shrink net exposure are allowed even when the book is over the limit
+        let reduces_exposure = net_value_after.abs() <= net_value_before.abs();
+        let breach = if concentration_pct > limits.max_net_concentration_pct && !reduces_exposure {
+            Some(format!(
+                "post-trade net {} exposure {} exceeds maximum {}",
+                asset,
+                concentration_pct.round_dp(2),
+                limits.max_net_concentration_pct
+            ))
+        } else if gross_leverage > limits.max_gross_leverage
+            && gross_leverage > self.gross_leverage()
+        {
+            Some(format!(
+                "post-trade gross leverage {}x exceeds maximum {}x",
+                gross_leverage.round_dp(2),
+                limits.max_gross_leverage
+            ))
+        } else {
+            None
+        };
+
+        ExposureCheck {
+            asset,
+            net_value_before,
+            net_value_after,
+            concentration_pct,
+            gross_leverage,
+            breach,
+        }
+    }
+
+    /// Evaluates a group of signed fills that execute together. Legs in the same underlying
+    /// are netted first, so offsetting legs are judged on what the group adds, not each leg.
+    /// Returns one check per underlying the group touches.
+    pub fn evaluate_group<'a>(
+        &self,
+        fills: impl IntoIterator<Item = (&'a str, Decimal, Decimal)>,
+        limits: &ExposureLimits,
+    ) -> Vec<ExposureCheck> {
+        let mut netted: BTreeMap<String, AssetExposure> = BTreeMap::new();
+        for (trading_pair, size, price) in fills {
+            netted.entry(underlying_asset(trading_pair)).or_default().add(size, price);
+        }
+
+        // Each asset is evaluated against the book with the group's earlier assets applied
+        let mut book = self.clone();
+        let mut checks = Vec::with_capacity(netted.len());
+        for (asset, net) in netted {
+            let price = if net.net_size.is_zero() {
+                Decimal::ZERO
+            } else {
+                net.net_value / net.net_size
+            };
+            let check = book.evaluate_fill(&asset, net.net_size, price, limits);
+            book.add_fill(&asset, net.net_size, price);
+            checks.push(check);
+        }
+        checks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_underlying_asset() {
+        assert_eq!(underlying_asset("SOL/USDC"), "SOL");
+        assert_eq!(underlying_asset("SOL-PERP"), "SOL");
+        assert_eq!(underlying_asset("sol_usdt"), "SOL");
+        assert_eq!(underlying_asset("WSOL/USDC"), "SOL");
+        assert_eq!(underlying_asset("ORCA/USDC"), "ORCA");
+        assert_eq!(underlying_asset("BTC"), "BTC");
+    }
+
+    #[test]
+    fn test_offsetting_books_net_out() {
+        let mut book = ExposureBook::new(dec!(1000));
+        book.add_fill("SOL/USDC", dec!(10), dec!(20));
+        book.add_fill("SOL-PERP", dec!(-8), dec!(20));
+
+        let sol = book.get("SOL").unwrap();
+        assert_eq!(sol.net_size, dec!(2));
+        assert_eq!(sol.gross_size, dec!(18));
+        assert_eq!(sol.net_value, dec!(40));
+        assert_eq!(sol.gross_value, dec!(360));
+        assert_eq!(book.max_net_concentration_pct(), Percent::from_percent(dec!(4)));
+        assert_eq!(book.gross_leverage(), dec!(0.36));
+    }
+
+    #[test]
+    fn test_gross_leverage_breach() {
+        let mut book = ExposureBook::new(dec!(100));
+        book.add_fill("SOL/USDC", dec!(10), dec!(20));
+        book.add_fill("SOL-PERP", dec!(-10), dec!(20));
+
+        let limits = ExposureLimits::default();
+        assert_eq!(book.max_net_concentration_pct(), Percent::ZERO);
+        assert!(book.breach(&limits).unwrap().contains("gross leverage"));
+    }
+
+    #[test]
+    fn test_evaluate_fill_uses_post_trade_net() {
+        let mut book = ExposureBook::new(dec!(1000));
+        book.add_fill("SOL/USDC", dec!(-10), dec!(20));
+        let limits = ExposureLimits::default();
+
+        // Buying against an existing short reduces net exposure
+        let check = book.evaluate_fill("SOL-PERP", TradeSide::Buy.signed(dec!(12)), dec!(20), &limits);
+        assert_eq!(check.net_value_after, dec!(40));
+        assert!(check.breach.is_none());
+
+        // Adding to the short pushes net exposure past 25% of equity
+        let check = book.evaluate_fill("SOL/USDC", TradeSide::Sell.signed(dec!(5)), dec!(20), &limits);
+        assert_eq!(check.concentration_pct, Percent::from_percent(dec!(30)));
+        assert!(check.breach.unwrap().contains("net SOL exposure"));
+    }
+
+    #[test]
+    fn test_evaluate_group_nets_offsetting_legs() {
+        let book = ExposureBook::new(dec!(1000));
+        let limits = ExposureLimits::default();
+
+        // Either leg alone would be 30% of equity; bought and sold together they net out
+        let legs = [("SOL/USDC", dec!(15), dec!(20)), ("SOL-PERP", dec!(-15), dec!(20.1))];
+        assert!(book.evaluate_fill(legs[0].0, legs[0].1, legs[0].2, &limits).breach.is_some());
+        let checks = book.evaluate_group(legs, &limits);
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].asset, "SOL");
+        assert!(checks[0].breach.is_none());
+
+        let checks = book.evaluate_group([("SOL/USDC", dec!(15), dec!(20)), ("SOL-PERP", dec!(-1), dec!(20))], &limits);
+        assert!(checks[0].breach.as_ref().unwrap().contains("net SOL exposure"));
+    }
+}
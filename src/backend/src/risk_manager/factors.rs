@@ -0,0 +1,466 @@
+//! Periodic aggregation of portfolio risk factors for the dashboard. Each tick reruns the
+//! risk manager's health check at the latest mid prices and derives net/gross exposure per
+//! asset, drawdown, value at risk from the volatility and correlation estimates, perp margin
+//! utilization and the distance to each circuit breaker threshold. Factors whose inputs are
+//! missing are reported as null with the reason rather than as zero.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::RwLock as SyncRwLock;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, RwLock};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::execution_engine::order_book::OrderBookSnapshot;
+use crate::risk_manager::exposure::{AssetExposure, ExposureLimits};
+use crate::risk_manager::margin::PerpMarginMonitor;
+use crate::risk_manager::portfolio::{PortfolioHealth, CIRCUIT_BREAKER_THRESHOLD, MAX_DRAWDOWN};
+use crate::risk_manager::{RiskError, RiskManager};
+
+// Risk factor constants
+const UPDATE_CHANNEL_CAPACITY: usize = 64;
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_VAR_Z_SCORE: f64 = 1.645; // one-tailed 95%
+const DEFAULT_VAR_HORIZON: Duration = Duration::from_secs(86_400);
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 3600.0;
+
+/// Snapshot cadence and value at risk parameters
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskFactorConfig {
+    pub interval: Duration,
+    /// Standard normal quantile of the VaR confidence level
+    pub var_z_score: f64,
+    pub var_horizon: Duration,
+}
+
+impl Default for RiskFactorConfig {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_INTERVAL,
+            var_z_score: DEFAULT_VAR_Z_SCORE,
+            var_horizon: DEFAULT_VAR_HORIZON,
+        }
+    }
+}
+
+/// Factor value, or the reason it could not be computed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskFactor {
+    pub value: Option<Decimal>,
+    pub reason: Option<String>,
+}
+
+impl RiskFactor {
+    pub fn known(value: Decimal) -> Self {
+        Self { value: Some(value), reason: None }
+    }
+
+    pub fn missing(reason: impl Into<String>) -> Self {
+        Self { value: None, reason: Some(reason.into()) }
+    }
+}
+
+/// Headroom between a monitored value and the threshold that trips it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BreakerDistance {
+    pub name: String,
+    pub current: Decimal,
+    pub threshold: Decimal,
+    /// Threshold minus current value; negative once breached
+    pub distance: Decimal,
+}
+
+impl BreakerDistance {
+    fn new(name: &str, current: Decimal, threshold: Decimal) -> Self {
+        Self {
+            name: name.to_string(),
+            current,
+            threshold,
+            distance: threshold - current,
+        }
+    }
+}
+
+/// Portfolio risk factors at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskFactorSnapshot {
+    pub id: Uuid,
+    pub computed_at: DateTime<Utc>,
+    pub total_value: Decimal,
+    /// Net and gross exposure per underlying asset across all books
+    pub exposures: BTreeMap<String, AssetExposure>,
+    pub net_exposure: Decimal,
+    pub gross_exposure: Decimal,
+    pub leverage: Decimal,
+    pub drawdown: Decimal,
+    /// Loss not exceeded at the configured confidence over the VaR horizon
+    pub value_at_risk: RiskFactor,
+    /// Initial margin requirement as a fraction of perp collateral
+    pub margin_utilization: RiskFactor,
+    pub breakers: Vec<BreakerDistance>,
+    pub circuit_breaker_tripped: bool,
+}
+
+impl RiskFactorSnapshot {
+    /// Derives risk factors from a portfolio health check and the perp margin monitor
+    pub fn from_health(
+        health: &PortfolioHealth,
+        exposure_limits: &ExposureLimits,
+        margin: Option<&PerpMarginMonitor>,
+        config: &RiskFactorConfig,
+        computed_at: DateTime<Utc>,
+    ) -> Self {
+        let breakers = vec![
+            BreakerDistance::new(
+                "drawdown_circuit_breaker",
+                health.drawdown.as_percent(),
+                CIRCUIT_BREAKER_THRESHOLD.as_percent(),
+            ),
+            BreakerDistance::new("max_drawdown", health.drawdown.as_percent(), MAX_DRAWDOWN.as_percent()),
+            BreakerDistance::new(
+                "net_concentration",
+                health.concentration.as_percent(),
+                exposure_limits.max_net_concentration_pct.as_percent(),
+            ),
+            BreakerDistance::new("gross_leverage", health.leverage, exposure_limits.max_gross_leverage),
+        ];
+
+        Self {
+            id: Uuid::new_v4(),
+            computed_at,
+            total_value: health.total_value,
+            exposures: health.exposure.assets().clone(),
+            net_exposure: health.net_exposure,
+            gross_exposure: health.gross_exposure,
+            leverage: health.leverage,
+            drawdown: health.drawdown.as_percent(),
+            value_at_risk: value_at_risk(health, config),
+            margin_utilization: margin_utilization(margin),
+            breakers,
+            circuit_breaker_tripped: health.circuit_breaker_active,
+        }
+    }
+}
+
+/// Parametric VaR scaling annualized portfolio volatility down to the horizon
+fn value_at_risk(health: &PortfolioHealth, config: &RiskFactorConfig) -> RiskFactor {
+    let Some(volatility) = health.volatility else {
+        if health.insufficient_data.is_empty() {
+            return RiskFactor::missing("portfolio volatility unavailable");
+        }
+        return RiskFactor::missing(format!(
+            "insufficient volatility or correlation history for {}",
+            health.insufficient_data.join(", ")
+        ));
+    };
+
+    let horizon = (config.var_horizon.as_secs_f64() / SECONDS_PER_YEAR).sqrt();
+    let scale = Decimal::from_f64(config.var_z_score * horizon).unwrap_or_default();
+    RiskFactor::known(scale * volatility * health.total_value)
+}
+
+fn margin_utilization(margin: Option<&PerpMarginMonitor>) -> RiskFactor {
+    let Some(monitor) = margin else {
+        return RiskFactor::missing("perp margin monitoring not configured");
+    };
+    let Some(account) = monitor.account() else {
+        return RiskFactor::missing("perp margin account not yet loaded");
+    };
+    let collateral = account.total_collateral();
+    if collateral <= Decimal::ZERO {
+        return RiskFactor::missing("no perp collateral");
+    }
+    RiskFactor::known(account.initial_requirement() / collateral)
+}
+
+/// Persistence for risk factor snapshots
+#[async_trait]
+pub trait RiskSnapshotStore: Send + Sync {
+    async fn record(&self, snapshot: &RiskFactorSnapshot) -> Result<(), RiskError>;
+    /// Most recent snapshots, newest first
+    async fn recent(&self, limit: i64) -> Result<Vec<RiskFactorSnapshot>, RiskError>;
+}
+
+/// Computes risk factor snapshots on an interval, persists them and publishes them
+pub struct RiskFactorService {
+    config: RiskFactorConfig,
+    risk_manager: Arc<RwLock<RiskManager>>,
+    prices: SyncRwLock<HashMap<String, Decimal>>,
+    store: Option<Arc<dyn RiskSnapshotStore>>,
+    updates: broadcast::Sender<RiskFactorSnapshot>,
+}
+
+impl std::fmt::Debug for RiskFactorService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RiskFactorService")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RiskFactorService {
+    pub fn new(config: RiskFactorConfig, risk_manager: Arc<RwLock<RiskManager>>) -> Self {
+        let (updates, _) = broadcast::channel(UPDATE_CHANNEL_CAPACITY);
+        Self {
+            config,
+            risk_manager,
+            prices: SyncRwLock::new(HashMap::new()),
+            store: None,
+            updates,
+        }
+    }
+
+    /// Persists every snapshot
+    pub fn with_store(mut self, store: Arc<dyn RiskSnapshotStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Receives each snapshot as it is computed
+    pub fn subscribe(&self) -> broadcast::Receiver<RiskFactorSnapshot> {
+        self.updates.subscribe()
+    }
+
+    /// Latest price used to value a pair
+    pub fn record_price(&self, trading_pair: &str, price: Decimal) {
+        self.prices.write().insert(trading_pair.to_string(), price);
+    }
+
+    /// Latest price recorded per pair
+    pub fn prices(&self) -> HashMap<String, Decimal> {
+        self.prices.read().clone()
+    }
+
+    /// Values positions at the mid of each fresh order book snapshot
+    pub fn record_snapshot(&self, snapshot: &OrderBookSnapshot) {
+        if snapshot.is_stale {
+            return;
+        }
+        let (Some(bid), Some(ask)) = (snapshot.bids.first(), snapshot.asks.first()) else {
+            return;
+        };
+        self.record_price(&snapshot.trading_pair, (bid.price + ask.price) / Decimal::TWO);
+    }
+
+    /// Runs the health check at the latest prices and derives the risk factors from it
+    pub async fn compute(&self, now: DateTime<Utc>) -> Result<RiskFactorSnapshot, RiskError> {
+        let prices = self.prices();
+        let risk_manager = self.risk_manager.read().await;
+        let health = risk_manager.portfolio_health(&prices).await?;
+        let exposure_limits = risk_manager.exposure_limits().await;
+
+        let mut snapshot = RiskFactorSnapshot::from_health(
+            &health,
+            &exposure_limits,
+            risk_manager.perp_margin().as_deref(),
+            &self.config,
+            now,
+        );
+        snapshot.circuit_breaker_tripped |= risk_manager.check_circuit_breaker();
+        Ok(snapshot)
+    }
+
+    /// Computes, persists and publishes one snapshot
+    pub async fn tick(&self, now: DateTime<Utc>) -> Result<RiskFactorSnapshot, RiskError> {
+        let snapshot = self.compute(now).await?;
+        if let Some(store) = &self.store {
+            // Dashboard subscribers still get the snapshot when persistence fails
+            if let Err(e) = store.record(&snapshot).await {
+                warn!("Failed to persist risk snapshot: {}", e);
+                counter!("trading_bot.risk_manager.risk_snapshot_store_failures", 1);
+            }
+        }
+        // No receivers is not an error; the dashboard may not be connected
+        let _ = self.updates.send(snapshot.clone());
+        counter!("trading_bot.risk_manager.risk_snapshots", 1);
+        Ok(snapshot)
+    }
+
+    /// Publishes a snapshot every configured interval
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.tick(Utc::now()).await {
+                    warn!("Failed to compute risk snapshot: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Tracks mid prices from the order book stream
+    pub fn spawn_market_data_listener(
+        self: Arc<Self>,
+        mut snapshots: broadcast::Receiver<OrderBookSnapshot>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match snapshots.recv().await {
+                    Ok(snapshot) => self.record_snapshot(&snapshot),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Risk factor listener skipped {} snapshots", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::portfolio::Portfolio;
+    use crate::risk_manager::analytics::{AnalyticsSnapshot, CorrelationMatrix, Estimate};
+    use crate::risk_manager::exposure::ExposureBook;
+    use crate::risk_manager::portfolio::check_portfolio_health;
+    use crate::risk_manager::RiskConfig;
+    use crate::utils::percent::Percent;
+    use rust_decimal::prelude::ToPrimitive;
+    use rust_decimal_macros::dec;
+
+    fn health(volatility: Option<Decimal>, insufficient_data: Vec<String>) -> PortfolioHealth {
+        let mut exposure = ExposureBook::new(dec!(1000));
+        exposure.add_fill("SOL/USDC", dec!(3), dec!(100)); // 30% of equity
+        exposure.add_fill("ORCA/USDC", dec!(-2), dec!(50)); // -10% of equity
+        PortfolioHealth {
+            total_value: dec!(1000),
+            drawdown: Percent::from_percent(dec!(5)),
+            concentration: exposure.max_net_concentration_pct(),
+            volatility,
+            insufficient_data,
+            leverage: exposure.gross_leverage(),
+            net_exposure: exposure.total_net_value(),
+            gross_exposure: exposure.total_gross_value(),
+            exposure,
+            is_healthy: true,
+            circuit_breaker_active: false,
+            last_checked: Utc::now(),
+        }
+    }
+
+    fn annual_var_config() -> RiskFactorConfig {
+        RiskFactorConfig {
+            var_horizon: Duration::from_secs(SECONDS_PER_YEAR as u64),
+            ..RiskFactorConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_synthetic_portfolio_factors() {
+        let limits = ExposureLimits {
+            max_net_concentration_pct: Percent::from_percent(dec!(40)),
+            max_gross_leverage: dec!(2),
+        };
+        // A one-year horizon leaves annualized volatility unscaled
+        let snapshot =
+            RiskFactorSnapshot::from_health(&health(Some(dec!(0.2)), Vec::new()), &limits, None, &annual_var_config(), Utc::now());
+
+        assert_eq!(snapshot.exposures["SOL"].net_value, dec!(300));
+        assert_eq!(snapshot.exposures["ORCA"].net_value, dec!(-100));
+        assert_eq!(snapshot.net_exposure, dec!(400));
+        assert_eq!(snapshot.gross_exposure, dec!(400));
+        assert_eq!(snapshot.leverage, dec!(0.4));
+
+        // 1.645 * 0.2 * 1000
+        assert_eq!(snapshot.value_at_risk.value.unwrap().round_dp(6), dec!(329));
+        assert!(snapshot.value_at_risk.reason.is_none());
+
+        let distance = |name: &str| snapshot.breakers.iter().find(|b| b.name == name).unwrap().distance;
+        assert_eq!(distance("drawdown_circuit_breaker"), dec!(20));
+        assert_eq!(distance("max_drawdown"), dec!(15));
+        assert_eq!(distance("net_concentration"), dec!(10));
+        assert_eq!(distance("gross_leverage"), dec!(1.6));
+
+        assert!(snapshot.margin_utilization.value.is_none());
+        assert_eq!(
+            snapshot.margin_utilization.reason.as_deref(),
+            Some("perp margin monitoring not configured")
+        );
+    }
+
+    #[test]
+    fn test_var_horizon_scales_with_square_root_of_time() {
+        let config = RiskFactorConfig::default();
+        let snapshot = RiskFactorSnapshot::from_health(
+            &health(Some(dec!(0.2)), Vec::new()),
+            &ExposureLimits::default(),
+            None,
+            &config,
+            Utc::now(),
+        );
+        let expected = 1.645 * 0.2 * 1000.0 / 365f64.sqrt();
+        let var = snapshot.value_at_risk.value.unwrap().to_f64().unwrap();
+        assert!((var - expected).abs() < 1e-6, "value at risk {}", var);
+    }
+
+    #[tokio::test]
+    async fn test_missing_correlations_report_null_var_not_zero() {
+        let portfolio = Portfolio::new("wallet".to_string(), dec!(1000)).unwrap();
+        portfolio.add_position("SOL/USDC".parse().unwrap(), dec!(2), dec!(100)).await.unwrap();
+        portfolio.add_position("ORCA/USDC".parse().unwrap(), dec!(4), dec!(50)).await.unwrap();
+        let prices = HashMap::from([
+            ("SOL/USDC".to_string(), dec!(100)),
+            ("ORCA/USDC".to_string(), dec!(50)),
+        ]);
+
+        // Volatility is known for both assets but the correlation matrix is empty
+        let analytics = AnalyticsSnapshot {
+            volatility: BTreeMap::from([
+                ("SOL".to_string(), Estimate::Available { value: 0.8, observations: 30 }),
+                ("ORCA".to_string(), Estimate::Available { value: 0.6, observations: 30 }),
+            ]),
+            correlations: CorrelationMatrix::default(),
+            computed_at: Utc::now(),
+        };
+        let health = check_portfolio_health(&[portfolio], &prices, &ExposureLimits::default(), Some(&analytics))
+            .await
+            .unwrap();
+        let snapshot = RiskFactorSnapshot::from_health(
+            &health,
+            &ExposureLimits::default(),
+            None,
+            &RiskFactorConfig::default(),
+            Utc::now(),
+        );
+
+        assert_eq!(snapshot.value_at_risk.value, None);
+        assert_eq!(
+            snapshot.value_at_risk.reason.as_deref(),
+            Some("insufficient volatility or correlation history for ORCA, SOL")
+        );
+
+        // Nulls survive serialization to the dashboard rather than becoming zeros
+        let frame = serde_json::to_value(&snapshot).unwrap();
+        assert!(frame["value_at_risk"]["value"].is_null());
+        assert!(frame["margin_utilization"]["value"].is_null());
+        assert_eq!(snapshot.exposures["SOL"].net_value, dec!(200));
+    }
+
+    #[tokio::test]
+    async fn test_tick_publishes_snapshot_at_latest_prices() {
+        let risk_manager = Arc::new(RwLock::new(RiskManager::new(RiskConfig::default()).unwrap()));
+        let service = RiskFactorService::new(RiskFactorConfig::default(), risk_manager);
+        let mut updates = service.subscribe();
+
+        let snapshot = service.tick(Utc::now()).await.unwrap();
+        assert_eq!(updates.try_recv().unwrap(), snapshot);
+        assert!(!snapshot.circuit_breaker_tripped);
+        assert_eq!(snapshot.breakers.len(), 4);
+    }
+}
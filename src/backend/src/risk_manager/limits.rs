@@ -13,20 +13,24 @@ use cached::proc_macro::cached;
 use parking_lot::RwLock;
 use rust_decimal::Decimal;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::time::timeout;
 use tracing::{debug, error, info, instrument, warn};
 
+use crate::models::market::QuoteAsset;
 use crate::models::portfolio::Portfolio;
+use crate::utils::circuit_breaker::{BreakerConfig, CircuitBreaker};
 use crate::utils::metrics::MetricsCollector;
+use crate::utils::percent::Percent;
 
 // Global risk limits with thread-safe access
-static MAX_POSITION_SIZE_PERCENT: Decimal = Decimal::new(20, 2); // 20%
-static MAX_PORTFOLIO_EXPOSURE: Decimal = Decimal::new(80, 2); // 80%
+static MAX_POSITION_SIZE: Percent = Percent::from_percent(Decimal::new(20, 0)); // 20%
+static MAX_PORTFOLIO_EXPOSURE: Percent = Percent::from_percent(Decimal::new(80, 0)); // 80%
 static MIN_TRADE_INTERVAL_MS: u64 = 500;
 static VALIDATION_CACHE_TTL_MS: u64 = 100;
-static CIRCUIT_BREAKER_THRESHOLD: Decimal = Decimal::new(95, 2); // 95%
+static CIRCUIT_BREAKER_THRESHOLD: Percent = Percent::from_percent(Decimal::new(95, 0)); // 95%
 
 /// Risk-related error types
 #[derive(Error, Debug)]
@@ -48,65 +52,35 @@ pub enum RiskError {
 pub struct ValidationResult {
     pub is_valid: bool,
     pub validation_time_ms: u64,
-    pub current_exposure: Decimal,
-    pub new_exposure: Decimal,
-}
-
-/// Circuit breaker for risk management
-#[derive(Debug)]
-struct CircuitBreaker {
-    triggered: AtomicU64,
-    last_reset: RwLock<Instant>,
-    threshold: Decimal,
-}
-
-impl CircuitBreaker {
-    fn new(threshold: Decimal) -> Self {
-        Self {
-            triggered: AtomicU64::new(0),
-            last_reset: RwLock::new(Instant::now()),
-            threshold,
-        }
-    }
-
-    fn check(&self, exposure: Decimal) -> bool {
-        if exposure >= self.threshold {
-            self.triggered.fetch_add(1, Ordering::SeqCst);
-            true
-        } else {
-            false
-        }
-    }
-
-    fn reset(&self) {
-        self.triggered.store(0, Ordering::SeqCst);
-        *self.last_reset.write() = Instant::now();
-    }
+    /// Share of portfolio value held outside USDC before the trade
+    pub current_exposure: Percent,
+    /// Share of portfolio value held outside USDC if the trade fills
+    pub new_exposure: Percent,
 }
 
 /// Thread-safe risk limits manager with enhanced monitoring
 #[derive(Debug, Clone)]
 pub struct RiskLimits {
-    max_position_size: RwLock<Decimal>,
-    max_portfolio_exposure: RwLock<Decimal>,
+    max_position_size: RwLock<Percent>,
+    max_portfolio_exposure: RwLock<Percent>,
     min_trade_interval: AtomicU64,
     metrics: MetricsCollector,
-    breaker: CircuitBreaker,
+    breaker: Arc<CircuitBreaker>,
 }
 
 impl RiskLimits {
     /// Creates a new RiskLimits instance with monitoring setup
     pub fn new(metrics: MetricsCollector) -> Self {
         let instance = Self {
-            max_position_size: RwLock::new(MAX_POSITION_SIZE_PERCENT),
+            max_position_size: RwLock::new(MAX_POSITION_SIZE),
             max_portfolio_exposure: RwLock::new(MAX_PORTFOLIO_EXPOSURE),
             min_trade_interval: AtomicU64::new(MIN_TRADE_INTERVAL_MS),
             metrics,
-            breaker: CircuitBreaker::new(CIRCUIT_BREAKER_THRESHOLD),
+            breaker: CircuitBreaker::new("risk_limits", BreakerConfig::manual()).registered(),
         };
 
-        info!("Risk limits initialized with max position size: {}%, max exposure: {}%",
-            MAX_POSITION_SIZE_PERCENT, MAX_PORTFOLIO_EXPOSURE);
+        info!("Risk limits initialized with max position size: {}, max exposure: {}",
+            MAX_POSITION_SIZE, MAX_PORTFOLIO_EXPOSURE);
 
         instance
     }
@@ -126,44 +100,52 @@ impl RiskLimits {
     ) -> Result<ValidationResult, RiskError> {
         let start = Instant::now();
 
-        // Check circuit breaker
-        let current_exposure = timeout(
+        let portfolio_value = timeout(
             Duration::from_millis(VALIDATION_CACHE_TTL_MS),
             portfolio.calculate_portfolio_value(&Default::default())
         ).await.map_err(|_| RiskError::ValidationTimeout("portfolio value calculation timeout".into()))??;
+        if portfolio_value <= Decimal::ZERO {
+            return Err(RiskError::PositionLimitExceeded("portfolio has no value".into()));
+        }
 
-        if self.breaker.check(current_exposure) {
-            error!("Circuit breaker triggered at {}% exposure", current_exposure);
-            return Err(RiskError::CircuitBreakerTriggered(
-                format!("current exposure: {}%", current_exposure)
-            ));
+        // Exposure is the share of portfolio value held outside USDC
+        let cash = portfolio.quote_balance(QuoteAsset::Usdc).await;
+        let current_exposure = Percent::ratio((portfolio_value - cash).max(Decimal::ZERO), portfolio_value);
+        let position_size = Percent::ratio(trade_size, portfolio_value);
+        let new_exposure = current_exposure + position_size;
+
+        // Check circuit breaker
+        if new_exposure >= CIRCUIT_BREAKER_THRESHOLD {
+            error!("Circuit breaker triggered at {} exposure", new_exposure);
+            let reason = format!("projected exposure: {}", new_exposure);
+            self.breaker.trip(reason.clone());
+            return Err(RiskError::CircuitBreakerTriggered(reason));
+        }
+        if self.breaker.is_open() {
+            self.breaker.reset();
         }
 
         // Validate position size
         let max_position_size = *self.max_position_size.read();
-        let position_percentage = (trade_size * Decimal::new(100, 0)) / current_exposure;
-
-        if position_percentage > max_position_size {
+        if position_size > max_position_size {
             warn!(
-                "Position size {}% exceeds limit of {}%",
-                position_percentage, max_position_size
+                "Position size {} exceeds limit of {}",
+                position_size, max_position_size
             );
             return Err(RiskError::PositionLimitExceeded(
-                format!("size {}% exceeds {}%", position_percentage, max_position_size)
+                format!("size {} exceeds {}", position_size.round_dp(2), max_position_size)
             ));
         }
 
-        // Calculate new exposure
-        let new_exposure = current_exposure + trade_size;
+        // Validate new exposure
         let max_exposure = *self.max_portfolio_exposure.read();
-
         if new_exposure > max_exposure {
             warn!(
-                "New exposure {}% exceeds limit of {}%",
+                "New exposure {} exceeds limit of {}",
                 new_exposure, max_exposure
             );
             return Err(RiskError::ExposureLimitExceeded(
-                format!("exposure {}% exceeds {}%", new_exposure, max_exposure)
+                format!("exposure {} exceeds {}", new_exposure.round_dp(2), max_exposure)
             ));
         }
 
@@ -173,7 +155,7 @@ impl RiskLimits {
         self.metrics.record_validation_latency(validation_time).await?;
 
         debug!(
-            "Position validation completed in {}ms: {}% exposure",
+            "Position validation completed in {}ms: {} exposure",
             validation_time, new_exposure
         );
 
@@ -188,8 +170,8 @@ impl RiskLimits {
     /// Updates risk limits with thread safety
     pub fn update_limits(
         &self,
-        new_position_size: Option<Decimal>,
-        new_portfolio_exposure: Option<Decimal>,
+        new_position_size: Option<Percent>,
+        new_portfolio_exposure: Option<Percent>,
     ) {
         if let Some(size) = new_position_size {
             *self.max_position_size.write() = size;
@@ -199,7 +181,7 @@ impl RiskLimits {
         }
         
         info!(
-            "Risk limits updated - position: {}%, exposure: {}%",
+            "Risk limits updated - position: {}, exposure: {}",
             self.max_position_size.read(),
             self.max_portfolio_exposure.read()
         );
@@ -229,8 +211,8 @@ impl RiskLimits {
         // Record performance metrics
         self.metrics.record_strategy_performance(
             "risk_validation".into(),
-            result.current_exposure.to_f64().unwrap_or(0.0),
-            result.new_exposure.to_f64().unwrap_or(0.0),
+            result.current_exposure.as_percent().to_f64().unwrap_or(0.0),
+            result.new_exposure.as_percent().to_f64().unwrap_or(0.0),
             100.0,
         ).await?;
 
@@ -281,4 +263,24 @@ mod tests {
         
         assert!(matches!(result, Err(RiskError::CircuitBreakerTriggered(_))));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_position_limit_is_percent_of_portfolio() {
+        let metrics = MetricsCollector::new().unwrap();
+        let limits = RiskLimits::new(metrics);
+
+        let portfolio = Portfolio::new(
+            "test_wallet".to_string(),
+            dec!(1000.00),
+        ).unwrap();
+
+        // 15% of the portfolio is within the 20% limit
+        let result = limits.validate_position_limits(&portfolio, dec!(150.00), "SOL/USDC".to_string()).await.unwrap();
+        assert_eq!(result.current_exposure, Percent::ZERO);
+        assert_eq!(result.new_exposure, Percent::from_percent(dec!(15)));
+
+        // 25% exceeds it
+        let result = limits.validate_position_limits(&portfolio, dec!(250.00), "SOL/USDC".to_string()).await;
+        assert!(matches!(result, Err(RiskError::PositionLimitExceeded(_))));
+    }
+}
@@ -0,0 +1,900 @@
+//! Margin management for Drift perpetual positions. Account-level margin state is rebuilt
+//! from Drift user account data, a pre-trade check keeps the post-trade margin ratio a
+//! configurable buffer above maintenance, and a liquidation monitor escalates from
+//! notification to auto-deleverage to emergency close as mark approaches liquidation.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+//! - utoipa = "3.5"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use parking_lot::RwLock as SyncRwLock;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::{broadcast, Mutex};
+use tracing::{error, warn};
+use utoipa::ToSchema;
+
+use crate::api::WebhookDispatcher;
+use crate::models::portfolio::{net_fill, PerpPosition};
+use crate::models::webhook::{WebhookEvent, WebhookEventType};
+use crate::risk_manager::exposure::TradeSide;
+use crate::utils::http::{self, HttpClient};
+use crate::utils::percent::Percent;
+
+// Margin management constants
+const METRICS_PREFIX: &str = "trading_bot.risk_manager.margin";
+const ALERT_CHANNEL_CAPACITY: usize = 256;
+const SIZE_SCALE: u32 = 8;
+const DEFAULT_MAINTENANCE_BUFFER: Decimal = Decimal::new(2, 2); // 2% above maintenance
+const DEFAULT_NOTIFY_DISTANCE_PCT: Percent = Percent::from_percent(Decimal::new(15, 0));
+const DEFAULT_DELEVERAGE_DISTANCE_PCT: Percent = Percent::from_percent(Decimal::new(8, 0));
+const DEFAULT_EMERGENCY_DISTANCE_PCT: Percent = Percent::from_percent(Decimal::new(3, 0));
+const DEFAULT_DELEVERAGE_FRACTION: Decimal = Decimal::new(5, 1); // 50%
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const ACCOUNT_FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_INITIAL_MARGIN_RATIO: Decimal = Decimal::new(1, 1); // 10x
+const DEFAULT_MAINTENANCE_MARGIN_RATIO: Decimal = Decimal::new(5, 2);
+
+/// Margin management errors
+#[derive(Error, Debug)]
+pub enum MarginError {
+    #[error("unknown perp market: {0}")]
+    UnknownMarket(String),
+    #[error("no mark price for {0}")]
+    MissingMark(String),
+    #[error("margin account not loaded")]
+    AccountUnavailable,
+    #[error("invalid account data: {0}")]
+    InvalidAccount(String),
+    #[error("account source error: {0}")]
+    Source(String),
+    #[error("position action failed: {0}")]
+    Action(String),
+}
+
+/// Margin ratios of a perp market as published by Drift
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PerpMarketParams {
+    pub initial_margin_ratio: Decimal,
+    pub maintenance_margin_ratio: Decimal,
+}
+
+impl Default for PerpMarketParams {
+    /// Conservative ratios for markets whose published parameters aren't configured
+    fn default() -> Self {
+        Self {
+            initial_margin_ratio: DEFAULT_INITIAL_MARGIN_RATIO,
+            maintenance_margin_ratio: DEFAULT_MAINTENANCE_MARGIN_RATIO,
+        }
+    }
+}
+
+impl PerpMarketParams {
+    /// Highest leverage the initial margin ratio allows
+    pub fn max_leverage(&self) -> Decimal {
+        if self.initial_margin_ratio.is_zero() {
+            return Decimal::ZERO;
+        }
+        Decimal::ONE / self.initial_margin_ratio
+    }
+}
+
+/// Perp position as reported in a Drift user account
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DriftPerpPositionData {
+    pub market: String,
+    /// Signed base asset amount; positive is long
+    pub base_asset_amount: Decimal,
+    /// Quote amount paid to open, negative for longs as Drift reports it
+    pub quote_entry_amount: Decimal,
+}
+
+/// Collateral and perp positions of a Drift user account
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DriftUserAccount {
+    /// Deposited collateral in USDC, excluding unrealized P&L
+    pub collateral: Decimal,
+    pub perp_positions: Vec<DriftPerpPositionData>,
+}
+
+/// Outcome of a pre-trade margin check
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MarginCheck {
+    pub market: String,
+    /// Collateral over notional after the fill; None when the fill leaves no positions
+    pub margin_ratio: Option<Decimal>,
+    /// Maintenance ratio plus the configured buffer
+    pub required_ratio: Decimal,
+    pub leverage: Decimal,
+    pub breach: Option<String>,
+}
+
+/// Account-level margin state across every perp position
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarginAccount {
+    /// Deposited collateral, excluding unrealized P&L
+    pub collateral: Decimal,
+    pub positions: Vec<PerpPosition>,
+    markets: HashMap<String, PerpMarketParams>,
+}
+
+impl MarginAccount {
+    /// Builds margin state from a Drift user account, marking each position at `marks`
+    pub fn from_drift(
+        account: &DriftUserAccount,
+        markets: &HashMap<String, PerpMarketParams>,
+        marks: &HashMap<String, Decimal>,
+        now: DateTime<Utc>,
+    ) -> Result<Self, MarginError> {
+        let positions = account
+            .perp_positions
+            .iter()
+            .filter(|data| !data.base_asset_amount.is_zero())
+            .map(|data| {
+                let mark = *marks
+                    .get(&data.market)
+                    .ok_or_else(|| MarginError::MissingMark(data.market.clone()))?;
+                let entry_price = (data.quote_entry_amount / data.base_asset_amount).abs();
+                Ok((data.market.clone(), data.base_asset_amount, entry_price, mark))
+            })
+            .collect::<Result<Vec<_>, MarginError>>()?;
+
+        Self::build(account.collateral, positions, markets.clone(), now)
+    }
+
+    /// Derives margin, leverage and liquidation price for (market, size, entry, mark) tuples
+    fn build(
+        collateral: Decimal,
+        positions: Vec<(String, Decimal, Decimal, Decimal)>,
+        markets: HashMap<String, PerpMarketParams>,
+        now: DateTime<Utc>,
+    ) -> Result<Self, MarginError> {
+        let positions = positions
+            .into_iter()
+            .map(|(market, size, entry_price, mark_price)| {
+                if mark_price <= Decimal::ZERO {
+                    return Err(MarginError::InvalidAccount(format!("non-positive mark for {}", market)));
+                }
+                let params = markets
+                    .get(&market)
+                    .ok_or_else(|| MarginError::UnknownMarket(market.clone()))?;
+                let notional = size.abs() * mark_price;
+                Ok(PerpPosition {
+                    market,
+                    size,
+                    entry_price,
+                    mark_price,
+                    leverage: Decimal::ZERO,
+                    margin: notional * params.initial_margin_ratio,
+                    maintenance_margin: notional * params.maintenance_margin_ratio,
+                    liquidation_price: None,
+                    updated_at: now,
+                })
+            })
+            .collect::<Result<Vec<_>, MarginError>>()?;
+
+        let mut account = Self {
+            collateral,
+            positions,
+            markets,
+        };
+        let total_collateral = account.total_collateral();
+        let maintenance = account.maintenance_requirement();
+        for position in &mut account.positions {
+            let params = account.markets[&position.market];
+            position.leverage = if total_collateral > Decimal::ZERO {
+                position.notional() / total_collateral
+            } else {
+                Decimal::MAX
+            };
+            position.liquidation_price =
+                liquidation_price(position, params.maintenance_margin_ratio, total_collateral, maintenance);
+        }
+        Ok(account)
+    }
+
+    /// Collateral including unrealized P&L
+    pub fn total_collateral(&self) -> Decimal {
+        self.collateral + self.positions.iter().map(PerpPosition::unrealized_pnl).sum::<Decimal>()
+    }
+
+    pub fn total_notional(&self) -> Decimal {
+        self.positions.iter().map(PerpPosition::notional).sum()
+    }
+
+    pub fn initial_requirement(&self) -> Decimal {
+        self.positions.iter().map(|position| position.margin).sum()
+    }
+
+    pub fn maintenance_requirement(&self) -> Decimal {
+        self.positions.iter().map(|position| position.maintenance_margin).sum()
+    }
+
+    /// Total collateral over total notional; None without open positions
+    pub fn margin_ratio(&self) -> Option<Decimal> {
+        let notional = self.total_notional();
+        (!notional.is_zero()).then(|| self.total_collateral() / notional)
+    }
+
+    pub fn position(&self, market: &str) -> Option<&PerpPosition> {
+        self.positions.iter().find(|position| position.market == market)
+    }
+
+    pub fn is_perp_market(&self, market: &str) -> bool {
+        self.markets.contains_key(market)
+    }
+
+    /// Account state after a hypothetical fill, keeping the market's current mark
+    pub fn with_fill(
+        &self,
+        market: &str,
+        signed_size: Decimal,
+        price: Decimal,
+        now: DateTime<Utc>,
+    ) -> Result<Self, MarginError> {
+        let (size, entry, mark) = self
+            .position(market)
+            .map(|position| (position.size, position.entry_price, position.mark_price))
+            .unwrap_or((Decimal::ZERO, Decimal::ZERO, price));
+        let fill = net_fill(size, entry, signed_size, price)
+            .map_err(|e| MarginError::InvalidAccount(e.to_string()))?;
+
+        let mut positions: Vec<(String, Decimal, Decimal, Decimal)> = self
+            .positions
+            .iter()
+            .filter(|position| position.market != market)
+            .map(|position| (position.market.clone(), position.size, position.entry_price, position.mark_price))
+            .collect();
+        if !fill.is_flat() {
+            positions.push((market.to_string(), fill.size, fill.entry_price, mark));
+        }
+
+        Self::build(self.collateral + fill.realized_pnl, positions, self.markets.clone(), now)
+    }
+
+    /// Account state with positions re-marked at `marks`; markets without a mark keep theirs
+    pub fn with_marks(&self, marks: &HashMap<String, Decimal>, now: DateTime<Utc>) -> Result<Self, MarginError> {
+        let positions = self
+            .positions
+            .iter()
+            .map(|position| {
+                let mark = marks.get(&position.market).copied().unwrap_or(position.mark_price);
+                (position.market.clone(), position.size, position.entry_price, mark)
+            })
+            .collect();
+        Self::build(self.collateral, positions, self.markets.clone(), now)
+    }
+
+    /// True once collateral including unrealized P&L no longer covers maintenance margin
+    pub fn is_liquidatable(&self) -> bool {
+        !self.positions.is_empty() && self.total_collateral() < self.maintenance_requirement()
+    }
+
+    /// Checks that an order keeps the account within initial margin and the maintenance
+    /// buffer. Orders that only reduce exposure always pass so deleveraging is never blocked.
+    pub fn check_order(
+        &self,
+        market: &str,
+        side: TradeSide,
+        size: Decimal,
+        price: Decimal,
+        maintenance_buffer: Decimal,
+        now: DateTime<Utc>,
+    ) -> Result<MarginCheck, MarginError> {
+        if !self.is_perp_market(market) {
+            return Err(MarginError::UnknownMarket(market.to_string()));
+        }
+        let current = self.position(market).map(|position| position.size).unwrap_or_default();
+        let signed_size = side.signed(size);
+        let after = self.with_fill(market, signed_size, price, now)?;
+
+        let notional = after.total_notional();
+        let collateral = after.total_collateral();
+        let margin_ratio = after.margin_ratio();
+        let maintenance_ratio = if notional.is_zero() {
+            Decimal::ZERO
+        } else {
+            after.maintenance_requirement() / notional
+        };
+        let required_ratio = maintenance_ratio + maintenance_buffer;
+        let leverage = if collateral > Decimal::ZERO { notional / collateral } else { Decimal::MAX };
+
+        let increases_exposure = (current + signed_size).abs() > current.abs();
+        let breach = match margin_ratio {
+            _ if !increases_exposure => None,
+            _ if collateral < after.initial_requirement() => Some(format!(
+                "initial margin {} exceeds collateral {}",
+                after.initial_requirement().round_dp(2),
+                collateral.round_dp(2)
+            )),
+            Some(ratio) if ratio < required_ratio => Some(format!(
+                "post-trade margin ratio {} below maintenance plus buffer {}",
+                ratio.round_dp(4),
+                required_ratio.round_dp(4)
+            )),
+            _ => None,
+        };
+
+        Ok(MarginCheck {
+            market: market.to_string(),
+            margin_ratio,
+            required_ratio,
+            leverage,
+            breach,
+        })
+    }
+
+    /// Largest order that stays within initial margin and the maintenance buffer when filled
+    /// at the current mark, for leverage-aware sizing
+    pub fn max_order_size(
+        &self,
+        market: &str,
+        side: TradeSide,
+        price: Decimal,
+        maintenance_buffer: Decimal,
+    ) -> Result<Decimal, MarginError> {
+        let params = self
+            .markets
+            .get(market)
+            .ok_or_else(|| MarginError::UnknownMarket(market.to_string()))?;
+        if price <= Decimal::ZERO {
+            return Err(MarginError::InvalidAccount("price must be positive".to_string()));
+        }
+
+        // Closing an opposite position is always allowed; only new exposure consumes margin
+        let current = self.position(market).map(|position| position.size).unwrap_or_default();
+        let reducible = if current * side.signed(Decimal::ONE) < Decimal::ZERO {
+            current.abs()
+        } else {
+            Decimal::ZERO
+        };
+
+        let collateral = self.total_collateral();
+        let free_initial = (collateral - self.initial_requirement()) / params.initial_margin_ratio;
+        let free_buffer = (collateral
+            - self.maintenance_requirement()
+            - maintenance_buffer * self.total_notional())
+            / (params.maintenance_margin_ratio + maintenance_buffer);
+        let additional = free_initial.min(free_buffer).max(Decimal::ZERO) / price;
+
+        Ok((reducible + additional).round_dp_with_strategy(SIZE_SCALE, rust_decimal::RoundingStrategy::ToZero))
+    }
+}
+
+/// Mark price at which the account's total collateral falls to its maintenance requirement,
+/// holding every other position's P&L fixed; None when no positive price reaches it
+fn liquidation_price(
+    position: &PerpPosition,
+    maintenance_margin_ratio: Decimal,
+    total_collateral: Decimal,
+    maintenance: Decimal,
+) -> Option<Decimal> {
+    let other_collateral = total_collateral - position.unrealized_pnl();
+    let other_maintenance = maintenance - position.maintenance_margin;
+    let denominator = position.size - position.size.abs() * maintenance_margin_ratio;
+    if denominator.is_zero() {
+        return None;
+    }
+
+    let price = (other_maintenance - other_collateral + position.size * position.entry_price) / denominator;
+    (price > Decimal::ZERO).then_some(price)
+}
+
+/// Distance from mark to liquidation in percent of mark; zero once mark has crossed it
+pub fn liquidation_distance_pct(position: &PerpPosition) -> Option<Percent> {
+    let liquidation = position.liquidation_price?;
+    let distance = if position.size > Decimal::ZERO {
+        position.mark_price - liquidation
+    } else {
+        liquidation - position.mark_price
+    };
+    Some(Percent::ratio(distance.max(Decimal::ZERO), position.mark_price))
+}
+
+/// Escalation reached by a position as mark approaches liquidation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LiquidationLevel {
+    Safe,
+    Notify,
+    AutoDeleverage,
+    EmergencyClose,
+}
+
+/// Perp market parameters, pre-trade buffer and liquidation escalation thresholds
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarginConfig {
+    /// Perp markets keyed by trading pair, e.g. SOL-PERP
+    pub markets: HashMap<String, PerpMarketParams>,
+    /// Post-trade margin ratio must stay this far above the maintenance ratio
+    pub maintenance_buffer: Decimal,
+    /// Mark-to-liquidation distances, in percent of mark, at which each escalation fires
+    pub notify_distance_pct: Percent,
+    pub deleverage_distance_pct: Percent,
+    pub emergency_distance_pct: Percent,
+    /// Share of a position closed on auto-deleverage
+    pub deleverage_fraction: Decimal,
+    pub poll_interval: Duration,
+}
+
+impl Default for MarginConfig {
+    fn default() -> Self {
+        Self {
+            markets: HashMap::new(),
+            maintenance_buffer: DEFAULT_MAINTENANCE_BUFFER,
+            notify_distance_pct: DEFAULT_NOTIFY_DISTANCE_PCT,
+            deleverage_distance_pct: DEFAULT_DELEVERAGE_DISTANCE_PCT,
+            emergency_distance_pct: DEFAULT_EMERGENCY_DISTANCE_PCT,
+            deleverage_fraction: DEFAULT_DELEVERAGE_FRACTION,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+}
+
+impl MarginConfig {
+    /// Tracks the markets with default parameters, keeping any already configured
+    pub fn with_markets<I, S>(mut self, markets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for market in markets {
+            self.markets.entry(market.into()).or_default();
+        }
+        self
+    }
+
+    /// Escalation level for a mark-to-liquidation distance
+    pub fn level(&self, distance_pct: Percent) -> LiquidationLevel {
+        if distance_pct <= self.emergency_distance_pct {
+            LiquidationLevel::EmergencyClose
+        } else if distance_pct <= self.deleverage_distance_pct {
+            LiquidationLevel::AutoDeleverage
+        } else if distance_pct <= self.notify_distance_pct {
+            LiquidationLevel::Notify
+        } else {
+            LiquidationLevel::Safe
+        }
+    }
+}
+
+/// Escalation raised for a perp position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiquidationAlert {
+    pub market: String,
+    pub level: LiquidationLevel,
+    pub size: Decimal,
+    pub mark_price: Decimal,
+    pub liquidation_price: Decimal,
+    pub distance_pct: Percent,
+    pub at: DateTime<Utc>,
+}
+
+/// Source of Drift account data and mark prices
+#[async_trait]
+pub trait PerpAccountSource: Send + Sync {
+    async fn user_account(&self) -> Result<DriftUserAccount, MarginError>;
+    async fn mark_prices(&self) -> Result<HashMap<String, Decimal>, MarginError>;
+}
+
+/// Reads a Drift user account and perp mark prices from the Drift data API
+#[derive(Debug)]
+pub struct DriftRestAccountSource {
+    base_url: String,
+    authority: String,
+    http: Arc<HttpClient>,
+}
+
+impl DriftRestAccountSource {
+    /// Source for the user account owned by the `authority` wallet
+    pub fn new(base_url: impl Into<String>, authority: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            authority: authority.into(),
+            http: http::shared(),
+        }
+    }
+
+    pub fn with_http(mut self, http: Arc<HttpClient>) -> Self {
+        self.http = http;
+        self
+    }
+
+    async fn fetch<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, MarginError> {
+        self.http
+            .get(
+                &format!("{}/{}", self.base_url.trim_end_matches('/'), path),
+                Some(ACCOUNT_FETCH_TIMEOUT),
+                |request| request,
+            )
+            .await
+            .map_err(|e| MarginError::Source(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| MarginError::InvalidAccount(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl PerpAccountSource for DriftRestAccountSource {
+    async fn user_account(&self) -> Result<DriftUserAccount, MarginError> {
+        self.fetch(&format!("user/{}", self.authority)).await
+    }
+
+    async fn mark_prices(&self) -> Result<HashMap<String, Decimal>, MarginError> {
+        self.fetch("markPrices").await
+    }
+}
+
+/// Reduce-only order placement used by the liquidation monitor
+#[async_trait]
+pub trait PerpPositionActions: Send + Sync {
+    /// Submits a reduce-only market order for a signed base size
+    async fn reduce_position(&self, market: &str, signed_size: Decimal) -> Result<(), MarginError>;
+    /// Closes the whole position at market
+    async fn close_position(&self, market: &str) -> Result<(), MarginError>;
+}
+
+/// Tracks perp margin from Drift account data, checks orders against it, and escalates
+/// as positions approach liquidation
+pub struct PerpMarginMonitor {
+    config: MarginConfig,
+    source: Arc<dyn PerpAccountSource>,
+    actions: Arc<dyn PerpPositionActions>,
+    account: SyncRwLock<Option<MarginAccount>>,
+    /// Highest escalation already acted on per market; lowered as positions recover
+    levels: Mutex<HashMap<String, LiquidationLevel>>,
+    alerts: broadcast::Sender<LiquidationAlert>,
+    webhooks: SyncRwLock<Option<Arc<WebhookDispatcher>>>,
+}
+
+impl std::fmt::Debug for PerpMarginMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PerpMarginMonitor")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl PerpMarginMonitor {
+    pub fn new(
+        config: MarginConfig,
+        source: Arc<dyn PerpAccountSource>,
+        actions: Arc<dyn PerpPositionActions>,
+    ) -> Self {
+        let (alerts, _) = broadcast::channel(ALERT_CHANNEL_CAPACITY);
+        Self {
+            config,
+            source,
+            actions,
+            account: SyncRwLock::new(None),
+            levels: Mutex::new(HashMap::new()),
+            alerts,
+            webhooks: SyncRwLock::new(None),
+        }
+    }
+
+    /// Sends liquidation alerts through the webhook dispatcher
+    pub fn set_webhooks(&self, webhooks: Arc<WebhookDispatcher>) {
+        *self.webhooks.write() = Some(webhooks);
+    }
+
+    /// Receives escalations as they are raised
+    pub fn subscribe(&self) -> broadcast::Receiver<LiquidationAlert> {
+        self.alerts.subscribe()
+    }
+
+    pub fn config(&self) -> &MarginConfig {
+        &self.config
+    }
+
+    /// Margin state as of the last refresh
+    pub fn account(&self) -> Option<MarginAccount> {
+        self.account.read().clone()
+    }
+
+    /// Checks an order against the last loaded margin state; None for non-perp markets
+    pub fn check_order(
+        &self,
+        market: &str,
+        side: TradeSide,
+        size: Decimal,
+        price: Decimal,
+        now: DateTime<Utc>,
+    ) -> Result<Option<MarginCheck>, MarginError> {
+        if !self.config.markets.contains_key(market) {
+            return Ok(None);
+        }
+        let account = self.account.read().clone().ok_or(MarginError::AccountUnavailable)?;
+        account
+            .check_order(market, side, size, price, self.config.maintenance_buffer, now)
+            .map(Some)
+    }
+
+    /// Largest order that keeps margin within limits at the current mark
+    pub fn max_order_size(&self, market: &str, side: TradeSide, price: Decimal) -> Result<Decimal, MarginError> {
+        let account = self.account.read().clone().ok_or(MarginError::AccountUnavailable)?;
+        account.max_order_size(market, side, price, self.config.maintenance_buffer)
+    }
+
+    /// Reloads account data and escalates every position that moved closer to liquidation
+    pub async fn refresh(&self, now: DateTime<Utc>) -> Result<Vec<LiquidationAlert>, MarginError> {
+        let mut levels = self.levels.lock().await;
+
+        let user_account = self.source.user_account().await?;
+        let marks = self.source.mark_prices().await?;
+        let account = MarginAccount::from_drift(&user_account, &self.config.markets, &marks, now)?;
+        if let Some(ratio) = account.margin_ratio() {
+            gauge!(format!("{}.margin_ratio", METRICS_PREFIX), ratio.to_f64().unwrap_or(0.0));
+        }
+        *self.account.write() = Some(account.clone());
+
+        let mut alerts = Vec::new();
+        for position in &account.positions {
+            let (Some(liquidation_price), Some(distance_pct)) =
+                (position.liquidation_price, liquidation_distance_pct(position))
+            else {
+                levels.insert(position.market.clone(), LiquidationLevel::Safe);
+                continue;
+            };
+
+            let level = self.config.level(distance_pct);
+            let previous = levels
+                .insert(position.market.clone(), level)
+                .unwrap_or(LiquidationLevel::Safe);
+            // Each level fires once; recovering below it re-arms the ladder
+            if level <= previous {
+                continue;
+            }
+
+            let alert = LiquidationAlert {
+                market: position.market.clone(),
+                level,
+                size: position.size,
+                mark_price: position.mark_price,
+                liquidation_price,
+                distance_pct,
+                at: now,
+            };
+            self.escalate(position, &alert).await;
+            alerts.push(alert);
+        }
+        levels.retain(|market, _| account.position(market).is_some());
+
+        Ok(alerts)
+    }
+
+    async fn escalate(&self, position: &PerpPosition, alert: &LiquidationAlert) {
+        warn!(
+            market = %alert.market,
+            level = ?alert.level,
+            mark = %alert.mark_price,
+            liquidation = %alert.liquidation_price,
+            "Perp position approaching liquidation"
+        );
+        counter!(format!("{}.escalations", METRICS_PREFIX), 1);
+        let _ = self.alerts.send(alert.clone());
+        self.notify(alert);
+
+        let result = match alert.level {
+            LiquidationLevel::Safe | LiquidationLevel::Notify => Ok(()),
+            LiquidationLevel::AutoDeleverage => {
+                let reduce = -position.size * self.config.deleverage_fraction;
+                self.actions.reduce_position(&position.market, reduce).await
+            }
+            LiquidationLevel::EmergencyClose => self.actions.close_position(&position.market).await,
+        };
+        if let Err(e) = result {
+            counter!(format!("{}.action_failures", METRICS_PREFIX), 1);
+            error!(market = %position.market, "Liquidation response failed: {}", e);
+        }
+    }
+
+    fn notify(&self, alert: &LiquidationAlert) {
+        let Some(webhooks) = self.webhooks.read().clone() else {
+            return;
+        };
+        match WebhookEvent::new(WebhookEventType::PositionLiquidationRisk, alert) {
+            Ok(event) => webhooks.dispatch(event),
+            Err(e) => warn!("Failed to build webhook event: {}", e),
+        }
+    }
+
+    /// Refreshes margin state on the configured interval
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.poll_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.refresh(Utc::now()).await {
+                    warn!("Failed to refresh perp margin: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex as SyncMutex;
+    use rust_decimal_macros::dec;
+
+    const MARKET: &str = "SOL-PERP";
+
+    struct MockVenue {
+        account: SyncMutex<DriftUserAccount>,
+        mark: SyncMutex<Decimal>,
+        actions: SyncMutex<Vec<String>>,
+    }
+
+    impl MockVenue {
+        fn new(collateral: Decimal, size: Decimal, entry: Decimal) -> Self {
+            Self {
+                account: SyncMutex::new(account(collateral, size, entry)),
+                mark: SyncMutex::new(entry),
+                actions: SyncMutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl PerpAccountSource for MockVenue {
+        async fn user_account(&self) -> Result<DriftUserAccount, MarginError> {
+            Ok(self.account.lock().clone())
+        }
+
+        async fn mark_prices(&self) -> Result<HashMap<String, Decimal>, MarginError> {
+            Ok(HashMap::from([(MARKET.to_string(), *self.mark.lock())]))
+        }
+    }
+
+    #[async_trait]
+    impl PerpPositionActions for MockVenue {
+        async fn reduce_position(&self, market: &str, signed_size: Decimal) -> Result<(), MarginError> {
+            self.actions.lock().push(format!("reduce {} {}", market, signed_size));
+            Ok(())
+        }
+
+        async fn close_position(&self, market: &str) -> Result<(), MarginError> {
+            self.actions.lock().push(format!("close {}", market));
+            Ok(())
+        }
+    }
+
+    fn account(collateral: Decimal, size: Decimal, entry: Decimal) -> DriftUserAccount {
+        DriftUserAccount {
+            collateral,
+            perp_positions: vec![DriftPerpPositionData {
+                market: MARKET.to_string(),
+                base_asset_amount: size,
+                quote_entry_amount: -size * entry,
+            }],
+        }
+    }
+
+    fn config(initial: Decimal, maintenance: Decimal, buffer: Decimal) -> MarginConfig {
+        MarginConfig {
+            markets: HashMap::from([(
+                MARKET.to_string(),
+                PerpMarketParams {
+                    initial_margin_ratio: initial,
+                    maintenance_margin_ratio: maintenance,
+                },
+            )]),
+            maintenance_buffer: buffer,
+            ..MarginConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_drift_account_populates_margin_and_liquidation_price() {
+        let config = config(dec!(0.1), dec!(0.05), dec!(0.02));
+        let marks = HashMap::from([(MARKET.to_string(), dec!(100))]);
+        let margin = MarginAccount::from_drift(&account(dec!(200), dec!(10), dec!(100)), &config.markets, &marks, Utc::now())
+            .unwrap();
+
+        let position = margin.position(MARKET).unwrap();
+        assert_eq!(position.entry_price, dec!(100));
+        assert_eq!(position.leverage, dec!(5));
+        assert_eq!(position.margin, dec!(100));
+        assert_eq!(position.maintenance_margin, dec!(50));
+        // 200 + 10 * (p - 100) = 10 * p * 0.05
+        assert_eq!(position.liquidation_price.unwrap().round_dp(4), dec!(84.2105));
+        assert_eq!(margin.margin_ratio(), Some(dec!(0.2)));
+    }
+
+    #[test]
+    fn test_with_markets_keeps_configured_params() {
+        let config = config(dec!(0.04), dec!(0.03), dec!(0.02)).with_markets([MARKET, "BTC-PERP"]);
+
+        assert_eq!(config.markets[MARKET].initial_margin_ratio, dec!(0.04));
+        assert_eq!(config.markets["BTC-PERP"], PerpMarketParams::default());
+    }
+
+    #[test]
+    fn test_order_rejected_past_maintenance_buffer() {
+        // Buffer above maintenance (3% + 3%) binds before initial margin (4%)
+        let config = config(dec!(0.04), dec!(0.03), dec!(0.03));
+        let margin = MarginAccount::from_drift(
+            &DriftUserAccount { collateral: dec!(1000), perp_positions: Vec::new() },
+            &config.markets,
+            &HashMap::new(),
+            Utc::now(),
+        )
+        .unwrap();
+        let now = Utc::now();
+
+        // 1000 / 0.06 caps notional at 16,666 at a price of 100
+        let within = margin.check_order(MARKET, TradeSide::Buy, dec!(166), dec!(100), config.maintenance_buffer, now).unwrap();
+        assert!(within.breach.is_none());
+        assert_eq!(within.required_ratio, dec!(0.06));
+
+        let beyond = margin.check_order(MARKET, TradeSide::Buy, dec!(167), dec!(100), config.maintenance_buffer, now).unwrap();
+        assert!(beyond.breach.unwrap().contains("below maintenance plus buffer"));
+
+        let max = margin.max_order_size(MARKET, TradeSide::Buy, dec!(100), config.maintenance_buffer).unwrap();
+        assert!(max > dec!(166) && max < dec!(167));
+
+        // Reducing an over-levered position is never blocked
+        let levered = margin.with_fill(MARKET, dec!(300), dec!(100), now).unwrap();
+        let reduce = levered.check_order(MARKET, TradeSide::Sell, dec!(10), dec!(100), config.maintenance_buffer, now).unwrap();
+        assert!(reduce.breach.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_escalation_ladder_as_mark_approaches_liquidation() {
+        let mut config = config(dec!(0.1), dec!(0.05), dec!(0.02));
+        config.notify_distance_pct = Percent::from_percent(dec!(15));
+        config.deleverage_distance_pct = Percent::from_percent(dec!(8));
+        config.emergency_distance_pct = Percent::from_percent(dec!(3));
+        let venue = Arc::new(MockVenue::new(dec!(200), dec!(10), dec!(100)));
+        let monitor = PerpMarginMonitor::new(config, venue.clone(), venue.clone());
+        let mut alerts = monitor.subscribe();
+        let now = Utc::now();
+
+        // Liquidation sits at 84.21
+        let levels = |alerts: Vec<LiquidationAlert>| alerts.into_iter().map(|alert| alert.level).collect::<Vec<_>>();
+        assert!(monitor.refresh(now).await.unwrap().is_empty());
+
+        *venue.mark.lock() = dec!(98);
+        assert_eq!(levels(monitor.refresh(now).await.unwrap()), vec![LiquidationLevel::Notify]);
+        // Staying at the same level does not repeat the alert
+        assert!(monitor.refresh(now).await.unwrap().is_empty());
+
+        *venue.mark.lock() = dec!(91);
+        assert_eq!(levels(monitor.refresh(now).await.unwrap()), vec![LiquidationLevel::AutoDeleverage]);
+        assert_eq!(venue.actions.lock().as_slice(), ["reduce SOL-PERP -5.0"]);
+
+        // The venue halves the position, realizing the loss; liquidation moves to 72.63
+        *venue.account.lock() = account(dec!(155), dec!(5), dec!(100));
+        assert!(monitor.refresh(now).await.unwrap().is_empty());
+
+        *venue.mark.lock() = dec!(74);
+        assert_eq!(levels(monitor.refresh(now).await.unwrap()), vec![LiquidationLevel::EmergencyClose]);
+        assert_eq!(venue.actions.lock().last().unwrap(), "close SOL-PERP");
+
+        let published: Vec<LiquidationLevel> = std::iter::from_fn(|| alerts.try_recv().ok())
+            .map(|alert| alert.level)
+            .collect();
+        assert_eq!(
+            published,
+            vec![
+                LiquidationLevel::Notify,
+                LiquidationLevel::AutoDeleverage,
+                LiquidationLevel::EmergencyClose,
+            ]
+        );
+    }
+}
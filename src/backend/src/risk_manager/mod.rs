@@ -9,27 +9,54 @@
 //! - metrics = "0.20"
 //! - lru = "0.8"
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 use tracing::{debug, error, info, instrument, warn};
 use metrics::{counter, histogram};
 use lru::LruCache;
+use serde::{Deserialize, Serialize};
 
+pub mod analytics;
+pub mod daily_loss;
+pub mod degradation;
+pub mod exposure;
+pub mod factors;
 pub mod limits;
+pub mod margin;
+pub mod perp_reconciliation;
+pub mod stress;
 pub mod validation;
 pub mod portfolio;
+pub mod velocity;
 
+use analytics::{AnalyticsConfig, RiskAnalytics};
+use daily_loss::{DailyLossLimits, DailyLossStatus, DailyLossTracker, DailyLossWindow};
+use degradation::{DegradationCurve, DegradationSizer, SizeAdjustment};
+use exposure::{ExposureBook, ExposureLimits};
 use limits::RiskLimits;
+use margin::PerpMarginMonitor;
 use validation::{ValidationResult, validate_trade};
-use portfolio::PortfolioRiskManager;
+use portfolio::{PortfolioHealth, PortfolioRiskManager};
+use velocity::{VelocityLimits, VelocityScope, VelocitySnapshot, VelocityTracker};
+
+use crate::config::environment::EnvironmentConfig;
+use crate::events::EventCalendar;
+use crate::models::portfolio::PortfolioSnapshot;
+use crate::models::transfer::Transfer;
+use crate::quarantine::QuarantineList;
+use crate::utils::circuit_breaker::{BreakerConfig, CircuitBreaker};
+use crate::utils::health::HealthMonitor;
+use crate::utils::percent::Percent;
+use crate::utils::solana::TokenBalanceChange;
 
 /// Version of the risk management system
 const RISK_MANAGER_VERSION: &str = "1.0.0";
 /// Time-to-live for validation cache entries
 const VALIDATION_CACHE_TTL: Duration = Duration::from_secs(60);
 /// Threshold for circuit breaker activation
-const CIRCUIT_BREAKER_THRESHOLD: f64 = 0.95;
+const CIRCUIT_BREAKER_THRESHOLD: Percent = Percent::from_percent(rust_decimal::Decimal::new(95, 0));
 
 /// Risk management system error types
 #[derive(thiserror::Error, Debug)]
@@ -44,37 +71,105 @@ pub enum RiskError {
     CircuitBreaker(String),
     #[error("monitoring error: {0}")]
     MonitoringError(String),
+    #[error("velocity limit exceeded: {0}")]
+    VelocityLimit(String),
+    #[error("margin limit exceeded: {0}")]
+    MarginLimit(String),
+    #[error("daily loss limit reached: {0}")]
+    DailyLossLimit(String),
+    #[error("pair quarantined: {0}")]
+    PairQuarantined(String),
+    #[error("event risk limit: {0}")]
+    EventRisk(String),
 }
 
 /// Configuration for the risk management system
 #[derive(Debug, Clone)]
 pub struct RiskConfig {
-    pub max_position_size: rust_decimal::Decimal,
-    pub max_portfolio_exposure: rust_decimal::Decimal,
-    pub circuit_breaker_threshold: f64,
+    /// Largest single trade as a share of portfolio value
+    pub max_position_size: Percent,
+    /// Largest share of portfolio value held outside USDC
+    pub max_portfolio_exposure: Percent,
+    pub circuit_breaker_threshold: Percent,
     pub validation_cache_size: usize,
     pub monitoring_interval: Duration,
+    /// Order-rate and turnover limits applied per strategy and per wallet
+    pub velocity: VelocityLimits,
+    /// Per-strategy velocity limits replacing the defaults
+    pub strategy_velocity_overrides: HashMap<String, VelocityLimits>,
+    /// Net concentration and gross leverage limits across all strategy and wallet books
+    pub exposure_limits: ExposureLimits,
+    /// Lookbacks for historical volatility and correlation estimates
+    pub analytics: AnalyticsConfig,
+    /// Loss allowed per trading day and the UTC time the day resets
+    pub daily_loss: DailyLossLimits,
+    /// Order size multiplier by system quality score
+    pub degradation: DegradationCurve,
+    /// Strategies exempted from degraded-mode sizing by config
+    pub degradation_opt_outs: Vec<String>,
 }
 
 impl Default for RiskConfig {
     fn default() -> Self {
         Self {
-            max_position_size: rust_decimal::Decimal::new(20, 2), // 20%
-            max_portfolio_exposure: rust_decimal::Decimal::new(80, 2), // 80%
+            max_position_size: Percent::from_percent(rust_decimal::Decimal::new(20, 0)), // 20%
+            max_portfolio_exposure: Percent::from_percent(rust_decimal::Decimal::new(80, 0)), // 80%
             circuit_breaker_threshold: CIRCUIT_BREAKER_THRESHOLD,
             validation_cache_size: 1000,
             monitoring_interval: Duration::from_secs(1),
+            velocity: VelocityLimits::default(),
+            strategy_velocity_overrides: HashMap::new(),
+            exposure_limits: ExposureLimits::default(),
+            analytics: AnalyticsConfig::default(),
+            daily_loss: DailyLossLimits::default(),
+            degradation: DegradationCurve::default(),
+            degradation_opt_outs: Vec::new(),
+        }
+    }
+}
+
+impl RiskConfig {
+    /// Defaults overlaid with the risk settings loaded from the environment
+    pub fn from_environment(config: &EnvironmentConfig) -> Self {
+        let defaults = Self::default();
+        Self {
+            velocity: config.velocity_limits.map(VelocityLimits::from).unwrap_or(defaults.velocity),
+            strategy_velocity_overrides: config
+                .strategy_velocity_overrides
+                .iter()
+                .map(|(strategy_id, caps)| (strategy_id.clone(), VelocityLimits::from(*caps)))
+                .collect(),
+            daily_loss: DailyLossLimits::from_environment(config),
+            degradation: DegradationCurve::from_environment(config),
+            degradation_opt_outs: config.degradation_opt_outs.clone(),
+            ..defaults
         }
     }
 }
 
+/// Risk counters captured in a state snapshot
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RiskSnapshot {
+    pub velocity: Vec<VelocitySnapshot>,
+    pub circuit_breaker_tripped: bool,
+    #[serde(default)]
+    pub daily_loss: Option<DailyLossWindow>,
+}
+
 /// Thread-safe risk management coordinator with enhanced monitoring
 #[derive(Debug)]
 pub struct RiskManager {
     limits: Arc<RwLock<RiskLimits>>,
     portfolio_manager: Arc<RwLock<PortfolioRiskManager>>,
     validation_cache: LruCache<String, ValidationResult>,
-    circuit_breaker: std::sync::atomic::AtomicBool,
+    circuit_breaker: Arc<CircuitBreaker>,
+    velocity: RwLock<VelocityTracker>,
+    daily_loss: RwLock<DailyLossTracker>,
+    analytics: Arc<RiskAnalytics>,
+    margin: Option<Arc<PerpMarginMonitor>>,
+    quarantine: Option<Arc<QuarantineList>>,
+    events: Option<Arc<EventCalendar>>,
+    degradation: Arc<DegradationSizer>,
 }
 
 impl RiskManager {
@@ -88,6 +183,7 @@ impl RiskManager {
             )?
         )));
 
+        let analytics = Arc::new(RiskAnalytics::new(config.analytics));
         let portfolio_manager = Arc::new(RwLock::new(
             PortfolioRiskManager::new(
                 portfolio::Portfolio::new(
@@ -98,9 +194,18 @@ impl RiskManager {
                 )?,
                 config.clone(),
             )
+            .with_analytics(analytics.clone())
         ));
 
         let validation_cache = LruCache::new(config.validation_cache_size);
+        let velocity = RwLock::new(VelocityTracker::new(
+            config.velocity,
+            config.strategy_velocity_overrides.clone(),
+        ));
+
+        let daily_loss = RwLock::new(DailyLossTracker::new(config.daily_loss));
+        let degradation = Arc::new(DegradationSizer::new(config.degradation.clone()));
+        degradation.set_config_opt_outs(&config.degradation_opt_outs, chrono::Utc::now());
 
         counter!("trading_bot.risk_manager.initialized", 1);
 
@@ -108,10 +213,156 @@ impl RiskManager {
             limits,
             portfolio_manager,
             validation_cache,
-            circuit_breaker: std::sync::atomic::AtomicBool::new(false),
+            circuit_breaker: CircuitBreaker::new("risk_manager", BreakerConfig::manual()).registered(),
+            velocity,
+            daily_loss,
+            analytics,
+            margin: None,
+            quarantine: None,
+            events: None,
+            degradation,
         })
     }
 
+    /// Volatility and correlation analytics, fed from the price stream
+    pub fn analytics(&self) -> Arc<RiskAnalytics> {
+        self.analytics.clone()
+    }
+
+    /// Persists velocity windows to Redis so restarts don't reset them
+    pub fn with_velocity_persistence(mut self, redis_client: Arc<redis::Client>) -> Self {
+        self.velocity.get_mut().set_redis_client(redis_client);
+        self
+    }
+
+    /// Persists the daily loss window to Redis so a restart mid-day keeps the day's losses
+    pub fn with_daily_loss_persistence(mut self, redis_client: Arc<redis::Client>) -> Self {
+        self.daily_loss.get_mut().set_redis_client(redis_client);
+        self
+    }
+
+    /// Counts P&L realized by a fill against the daily loss limit
+    pub async fn record_realized_pnl(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        pnl: rust_decimal::Decimal,
+    ) -> DailyLossStatus {
+        self.daily_loss.write().await.record_realized(now, pnl).await
+    }
+
+    /// Marks open positions' unrealized P&L and portfolio equity for the daily loss limit;
+    /// the returned status asks for positions to be flattened once the hard limit is hit
+    pub async fn mark_daily_pnl(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        unrealized: rust_decimal::Decimal,
+        equity: rust_decimal::Decimal,
+    ) -> DailyLossStatus {
+        self.daily_loss.write().await.mark(now, unrealized, equity).await
+    }
+
+    /// Daily loss thresholds and the time of day they reset
+    pub async fn daily_loss_limits(&self) -> DailyLossLimits {
+        *self.daily_loss.read().await.limits()
+    }
+
+    /// P&L since the last daily reset against the configured limits
+    pub async fn daily_loss_status(&self, now: chrono::DateTime<chrono::Utc>) -> DailyLossStatus {
+        self.daily_loss.write().await.status(now).await
+    }
+
+    /// Checks perp orders against account margin before they reach the venue
+    pub fn with_perp_margin(mut self, margin: Arc<PerpMarginMonitor>) -> Self {
+        self.set_perp_margin(margin);
+        self
+    }
+
+    /// Attaches the perp margin monitor to a risk manager the bot already holds
+    pub fn set_perp_margin(&mut self, margin: Arc<PerpMarginMonitor>) {
+        self.margin = Some(margin);
+    }
+
+    /// Perp margin monitor checking orders, when configured
+    pub fn perp_margin(&self) -> Option<Arc<PerpMarginMonitor>> {
+        self.margin.clone()
+    }
+
+    /// Refuses orders on quarantined pairs
+    pub fn set_quarantine(&mut self, quarantine: Arc<QuarantineList>) {
+        self.quarantine = Some(quarantine);
+    }
+
+    /// Tightens limits for the window around high-impact economic events
+    pub fn set_event_calendar(&mut self, events: Arc<EventCalendar>) {
+        self.events = Some(events);
+    }
+
+    /// Scales validated orders by the monitor's system quality score
+    pub fn set_health_monitor(&mut self, health: Arc<HealthMonitor>) {
+        self.degradation.set_health(health);
+    }
+
+    /// Degraded-mode sizing curve and per-strategy opt-outs
+    pub fn degradation(&self) -> Arc<DegradationSizer> {
+        self.degradation.clone()
+    }
+
+    /// Health of the primary portfolio and every registered book at the given prices
+    pub async fn portfolio_health(
+        &self,
+        market_prices: &HashMap<String, rust_decimal::Decimal>,
+    ) -> Result<PortfolioHealth, RiskError> {
+        self.portfolio_manager
+            .read()
+            .await
+            .check_portfolio_health(market_prices)
+            .await
+            .map_err(|e| RiskError::PortfolioError(e.to_string()))
+    }
+
+    /// Balances and positions of every book the health check covers
+    pub async fn book_snapshots(&self) -> Vec<PortfolioSnapshot> {
+        self.portfolio_manager.read().await.book_snapshots().await
+    }
+
+    /// Worst flow-adjusted drawdown across the books
+    pub async fn drawdown(&self) -> Percent {
+        self.portfolio_manager.read().await.drawdown().await
+    }
+
+    /// Reconciles an observed wallet balance against the primary portfolio, adjusting the
+    /// high-water mark for deposits and withdrawals
+    pub async fn reconcile_balance(
+        &self,
+        observed_balance: rust_decimal::Decimal,
+        trade_delta: rust_decimal::Decimal,
+        on_chain_changes: &[TokenBalanceChange],
+    ) -> Result<Option<Transfer>, RiskError> {
+        self.portfolio_manager
+            .read()
+            .await
+            .reconcile_balance(observed_balance, trade_delta, on_chain_changes)
+            .await
+    }
+
+    /// Velocity thresholds currently applied to a strategy or wallet
+    pub async fn velocity_limits(&self, scope: VelocityScope, id: &str) -> VelocityLimits {
+        self.velocity.read().await.limits_for(scope, id)
+    }
+
+    /// Net concentration and gross leverage limits currently applied
+    pub async fn exposure_limits(&self) -> ExposureLimits {
+        *self.portfolio_manager.read().await.exposure_limits()
+    }
+
+    /// Net and gross exposure per underlying across the books, marked at `market_prices`
+    pub async fn exposure_book(
+        &self,
+        market_prices: &HashMap<String, rust_decimal::Decimal>,
+    ) -> Result<ExposureBook, RiskError> {
+        self.portfolio_manager.read().await.exposure_book(market_prices).await
+    }
+
     /// Validates a trading operation against all risk controls with caching
     #[instrument(skip(self, trade_request))]
     pub async fn validate_operation(
@@ -120,39 +371,47 @@ impl RiskManager {
     ) -> Result<ValidationResult, RiskError> {
         let start = std::time::Instant::now();
 
-        // Check circuit breaker status
-        if self.circuit_breaker.load(std::sync::atomic::Ordering::Relaxed) {
-            return Err(RiskError::CircuitBreaker(
-                "trading suspended - circuit breaker active".to_string()
-            ));
-        }
+        // Check order rate and turnover before the cache so repeated orders are still counted
+        let now = chrono::Utc::now();
+        self.precheck(&trade_request, now).await?;
+        let notional = trade_request
+            .reporting_notional()
+            .map_err(|e| RiskError::ValidationError(e.to_string()))?;
 
-        // Check validation cache
-        let cache_key = format!("{}{}{}", 
-            trade_request.trading_pair,
-            trade_request.size,
-            trade_request.exchange
-        );
+        // Size is scaled outside the cache since the quality score moves independently of the order
+        let adjustment = self.degradation.adjustment(&trade_request.strategy_id);
+        let sized_notional = notional * adjustment.multiplier;
 
-        if let Some(cached) = self.validation_cache.get(&cache_key) {
+        // Check validation cache
+        let cache_key = validation_cache_key(&trade_request);
+        if let Some(cached) = self.validation_cache.get(&cache_key).cloned() {
             debug!("Using cached validation result for {}", cache_key);
-            return Ok(cached.clone());
+            let sized = apply_size_adjustment(cached, &trade_request, &adjustment);
+            if sized.is_valid {
+                self.velocity
+                    .write()
+                    .await
+                    .record(now, &trade_request.strategy_id, &trade_request.wallet_address, sized_notional)
+                    .await;
+            }
+            return Ok(sized);
         }
 
         // Perform validation
-        let portfolio = self.portfolio_manager.read().await;
-        let validation = validate_trade(
-            &trade_request.into(),
-            &portfolio,
-            &trade_request.market_prices,
-            &trade_request.cross_dex_prices,
-            &trade_request.market_impact,
-        ).await.map_err(|e| RiskError::ValidationError(e.to_string()))?;
+        let validation = self.evaluate(&trade_request).await?;
 
-        // Update cache
+        // Update cache and count the accepted order
         if validation.is_valid {
             self.validation_cache.put(cache_key, validation.clone());
         }
+        let validation = apply_size_adjustment(validation, &trade_request, &adjustment);
+        if validation.is_valid {
+            self.velocity
+                .write()
+                .await
+                .record(now, &trade_request.strategy_id, &trade_request.wallet_address, sized_notional)
+                .await;
+        }
 
         // Record metrics
         histogram!(
@@ -163,6 +422,132 @@ impl RiskManager {
         Ok(validation)
     }
 
+    /// Runs the same checks as `validate_operation` without caching the result or counting
+    /// the order against velocity limits
+    #[instrument(skip(self, trade_request))]
+    pub async fn simulate_operation(
+        &self,
+        trade_request: validation::TradeRequest,
+    ) -> Result<ValidationResult, RiskError> {
+        self.precheck(&trade_request, chrono::Utc::now()).await?;
+        let adjustment = self.degradation.adjustment(&trade_request.strategy_id);
+
+        let validation = match self.validation_cache.peek(&validation_cache_key(&trade_request)) {
+            Some(cached) => cached.clone(),
+            None => self.evaluate(&trade_request).await?,
+        };
+        Ok(apply_size_adjustment(validation, &trade_request, &adjustment))
+    }
+
+    /// Rejects trades while the circuit breaker is open, on a quarantined pair, beyond the
+    /// tightened limits of an economic event window, once the daily loss limit is reached,
+    /// when velocity limits are exhausted, or when a perp order would leave too little margin
+    /// above maintenance
+    async fn precheck(
+        &self,
+        trade_request: &validation::TradeRequest,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), RiskError> {
+        if self.check_circuit_breaker() {
+            return Err(RiskError::CircuitBreaker(
+                "trading suspended - circuit breaker active".to_string()
+            ));
+        }
+
+        if let Some(quarantine) = &self.quarantine {
+            if quarantine.mode(&trade_request.trading_pair).is_some() {
+                let reduces = self
+                    .portfolio_manager
+                    .read()
+                    .await
+                    .reduces_position(&trade_request.trading_pair, trade_request.side, trade_request.size)
+                    .await;
+                if let Err(e) = quarantine.check(&trade_request.trading_pair, reduces) {
+                    counter!("trading_bot.risk_manager.quarantine_rejections", 1);
+                    warn!("Order on quarantined pair refused: {}", e);
+                    return Err(RiskError::PairQuarantined(e.to_string()));
+                }
+            }
+        }
+
+        if let Some(events) = &self.events {
+            let notional = trade_request
+                .reporting_notional()
+                .map_err(|e| RiskError::ValidationError(e.to_string()))?;
+            if let Err(e) = events.check(
+                &trade_request.strategy_id,
+                notional,
+                trade_request.cross_dex_edge(),
+                now,
+            ) {
+                counter!("trading_bot.risk_manager.event_rejections", 1);
+                return Err(RiskError::EventRisk(e.to_string()));
+            }
+        }
+
+        if let Err(breach) = self.daily_loss.write().await.check(now).await {
+            counter!("trading_bot.risk_manager.daily_loss_rejections", 1);
+            warn!("Daily loss limit reached: {}", breach);
+            return Err(RiskError::DailyLossLimit(breach.to_string()));
+        }
+
+        if let Some(margin) = &self.margin {
+            let check = margin
+                .check_order(
+                    &trade_request.trading_pair,
+                    trade_request.side,
+                    trade_request.size,
+                    trade_request.price,
+                    now,
+                )
+                .map_err(|e| RiskError::MarginLimit(e.to_string()))?;
+            if let Some(breach) = check.and_then(|check| check.breach) {
+                counter!("trading_bot.risk_manager.margin_rejections", 1);
+                warn!("Margin limit exceeded: {}", breach);
+                return Err(RiskError::MarginLimit(breach));
+            }
+        }
+
+        let notional = trade_request
+            .reporting_notional()
+            .map_err(|e| RiskError::ValidationError(e.to_string()))?;
+        self.velocity
+            .write()
+            .await
+            .check(now, &trade_request.strategy_id, &trade_request.wallet_address, notional)
+            .await
+            .map_err(|breach| {
+                counter!("trading_bot.risk_manager.velocity_rejections", 1);
+                warn!("Velocity limit exceeded: {}", breach);
+                RiskError::VelocityLimit(breach.to_string())
+            })
+    }
+
+    /// Validates the trade against portfolio and cross-book exposure limits
+    async fn evaluate(
+        &self,
+        trade_request: &validation::TradeRequest,
+    ) -> Result<ValidationResult, RiskError> {
+        let order = trade_request
+            .to_order()
+            .map_err(|e| RiskError::ValidationError(e.to_string()))?;
+        let portfolio = self.portfolio_manager.read().await;
+        let exposure = portfolio
+            .exposure_book(&trade_request.market_prices)
+            .await
+            .map_err(|e| RiskError::PortfolioError(e.to_string()))?;
+        validate_trade(
+            &order,
+            trade_request.side,
+            &portfolio,
+            &exposure,
+            portfolio.exposure_limits(),
+            &trade_request.market_prices,
+            &trade_request.cross_dex_prices,
+            &trade_request.market_impact,
+        ).await.map_err(|e| RiskError::ValidationError(e.to_string()))
+    }
+
     /// Updates risk management configuration with validation
     #[instrument(skip(self, new_config))]
     pub async fn update_risk_config(
@@ -178,18 +563,23 @@ impl RiskManager {
             Some(new_config.max_portfolio_exposure),
         );
 
-        // Update portfolio manager
-        let mut portfolio = self.portfolio_manager.write().await;
-        *portfolio = PortfolioRiskManager::new(
-            portfolio::Portfolio::new(
-                "system".to_string(),
-                rust_decimal::Decimal::ZERO,
-            ).map_err(|e| 
-                RiskError::InitializationError(format!("failed to create portfolio: {}", e))
-            )?,
-            new_config.clone(),
+        // Apply velocity thresholds without resetting the tracked windows
+        self.velocity.write().await.update_limits(
+            new_config.velocity,
+            new_config.strategy_velocity_overrides.clone(),
         );
 
+        // The day's losses carry over; only thresholds and the reset time change
+        self.daily_loss.write().await.update_limits(new_config.daily_loss);
+
+        // Admin opt-outs carry over; the curve and config opt-outs are replaced
+        self.degradation.set_curve(new_config.degradation.clone());
+        self.degradation
+            .set_config_opt_outs(&new_config.degradation_opt_outs, chrono::Utc::now());
+
+        // Positions, books and the value cache survive config reloads; only limits change
+        self.portfolio_manager.write().await.update_limits(new_config.clone());
+
         // Clear validation cache
         self.validation_cache.clear();
 
@@ -197,12 +587,85 @@ impl RiskManager {
         Ok(())
     }
 
+    /// Copies the velocity windows and circuit breaker for a state snapshot
+    pub async fn snapshot(&self) -> RiskSnapshot {
+        RiskSnapshot {
+            velocity: self.velocity.read().await.snapshot(),
+            circuit_breaker_tripped: self.circuit_breaker.is_open(),
+            daily_loss: self.daily_loss.read().await.snapshot(),
+        }
+    }
+
+    /// Restores velocity windows, the daily loss window and circuit breaker state from a snapshot
+    pub async fn restore(&self, snapshot: &RiskSnapshot) {
+        self.velocity.write().await.restore(&snapshot.velocity);
+        self.daily_loss.write().await.restore(snapshot.daily_loss.clone());
+        if snapshot.circuit_breaker_tripped {
+            self.circuit_breaker.trip("restored from snapshot");
+        } else {
+            self.circuit_breaker.reset();
+        }
+    }
+
     /// Checks and manages circuit breaker status
     pub fn check_circuit_breaker(&self) -> bool {
-        self.circuit_breaker.load(std::sync::atomic::Ordering::Relaxed)
+        self.circuit_breaker.is_open() || injected_breaker_trip()
+    }
+}
+
+/// True while an injected circuit breaker fault is firing
+fn injected_breaker_trip() -> bool {
+    #[cfg(feature = "fault-injection")]
+    {
+        crate::fault_injection::breaker_tripped()
+    }
+    #[cfg(not(feature = "fault-injection"))]
+    {
+        false
     }
 }
 
+/// Records the degraded-mode size on a validation result, failing it once the curve halts
+fn apply_size_adjustment(
+    mut validation: ValidationResult,
+    trade_request: &validation::TradeRequest,
+    adjustment: &SizeAdjustment,
+) -> ValidationResult {
+    if !validation.is_valid {
+        return validation;
+    }
+    validation.size_multiplier = adjustment.multiplier;
+    validation.approved_size = Some(adjustment.apply(trade_request.size));
+    if adjustment.halts() {
+        counter!("trading_bot.risk_manager.degradation_halts", 1);
+        warn!(
+            strategy_id = %trade_request.strategy_id,
+            score = ?adjustment.score,
+            "Order halted by degraded-mode sizing"
+        );
+        validation.is_valid = false;
+        validation.severity_level = validation::ValidationSeverity::Critical;
+        validation.failure_reason = Some(format!(
+            "system quality {} is too low to trade",
+            adjustment.score.unwrap_or_default()
+        ));
+    } else if adjustment.multiplier < rust_decimal::Decimal::ONE {
+        counter!("trading_bot.risk_manager.degradation_reductions", 1);
+    }
+    validation
+}
+
+/// Cache key identifying trades that validate identically
+fn validation_cache_key(trade_request: &validation::TradeRequest) -> String {
+    format!(
+        "{}{}{}{:?}",
+        trade_request.trading_pair,
+        trade_request.size,
+        trade_request.exchange,
+        trade_request.side
+    )
+}
+
 /// Initializes the risk management system with configuration
 #[instrument(skip(config))]
 pub fn init_risk_manager(config: RiskConfig) -> Result<RiskManager, RiskError> {
@@ -210,9 +673,29 @@ pub fn init_risk_manager(config: RiskConfig) -> Result<RiskManager, RiskError> {
     RiskManager::new(config)
 }
 
+/// Applies environment config updates from the config reload path
+pub fn spawn_reload_listener(
+    manager: Arc<RwLock<RiskManager>>,
+    mut updates: watch::Receiver<Option<EnvironmentConfig>>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        while updates.changed().await.is_ok() {
+            let config = updates.borrow().clone();
+            if let Some(config) = config {
+                let risk_config = RiskConfig::from_environment(&config);
+                if let Err(e) = manager.write().await.update_risk_config(risk_config).await {
+                    error!("Failed to apply reloaded risk config: {}", e);
+                }
+            }
+        }
+        error!("Environment config update channel closed");
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rust_decimal::Decimal;
     use rust_decimal_macros::dec;
 
     #[tokio::test]
@@ -237,4 +720,319 @@ mod tests {
         // Test cache functionality
         // Implementation details would go here
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_simulated_rejection_matches_validation() {
+        let mut manager = RiskManager::new(RiskConfig::default()).unwrap();
+        let request = validation::TradeRequest {
+            strategy_id: "grid-1".to_string(),
+            wallet_address: "wallet-1".to_string(),
+            trading_pair: "SOL/USDC".to_string(),
+            exchange: "jupiter".to_string(),
+            side: exposure::TradeSide::Buy,
+            order_type: crate::models::order::OrderType::Limit,
+            size: dec!(10000),
+            price: dec!(23.45),
+            market_prices: HashMap::from([("SOL/USDC".to_string(), dec!(23.45))]),
+            cross_dex_prices: HashMap::new(),
+            market_impact: HashMap::new(),
+        };
+
+        let simulated = manager.simulate_operation(request.clone()).await.unwrap();
+        let validated = manager.validate_operation(request).await.unwrap();
+
+        assert!(!simulated.is_valid);
+        assert!(simulated.failure_reason.as_deref().unwrap().contains("exceeds maximum"));
+        assert_eq!(simulated.failure_reason, validated.failure_reason);
+        assert_eq!(simulated.severity_level, validated.severity_level);
+    }
+
+    #[tokio::test]
+    async fn test_daily_loss_limit_rejects_new_risk() {
+        let mut manager = RiskManager::new(RiskConfig::default()).unwrap();
+        let now = chrono::Utc::now();
+        let request = validation::TradeRequest {
+            strategy_id: "grid-1".to_string(),
+            wallet_address: "wallet-1".to_string(),
+            trading_pair: "SOL/USDC".to_string(),
+            exchange: "jupiter".to_string(),
+            side: exposure::TradeSide::Buy,
+            order_type: crate::models::order::OrderType::Limit,
+            size: dec!(1),
+            price: dec!(23.45),
+            market_prices: HashMap::from([("SOL/USDC".to_string(), dec!(23.45))]),
+            cross_dex_prices: HashMap::new(),
+            market_impact: HashMap::new(),
+        };
+
+        // Default soft limit is 5% of the day's starting equity
+        manager.mark_daily_pnl(now, dec!(0), dec!(10000)).await;
+        let status = manager.record_realized_pnl(now, dec!(-500)).await;
+        assert_eq!(status.remaining_budget, Some(dec!(0)));
+
+        let rejected = manager.validate_operation(request.clone()).await.unwrap_err();
+        assert!(matches!(rejected, RiskError::DailyLossLimit(_)));
+        assert!(matches!(
+            manager.simulate_operation(request).await.unwrap_err(),
+            RiskError::DailyLossLimit(_)
+        ));
+        assert_eq!(manager.snapshot().await.daily_loss.unwrap().realized, dec!(-500));
+    }
+
+    #[tokio::test]
+    async fn test_quarantined_pair_rejected_with_distinct_error() {
+        use crate::quarantine::{QuarantineConfig, QuarantineMode, QuarantineRequest};
+
+        let quarantine = Arc::new(QuarantineList::new(QuarantineConfig::default()));
+        quarantine
+            .quarantine(
+                QuarantineRequest {
+                    trading_pair: "BONK/USDC".to_string(),
+                    mode: QuarantineMode::NoNewEntries,
+                    reason: None,
+                },
+                "ops",
+                chrono::Utc::now(),
+            )
+            .await
+            .unwrap();
+        let mut manager = RiskManager::new(RiskConfig::default()).unwrap();
+        manager.set_quarantine(quarantine);
+        let request = |trading_pair: &str| validation::TradeRequest {
+            strategy_id: "grid-1".to_string(),
+            wallet_address: "wallet-1".to_string(),
+            trading_pair: trading_pair.to_string(),
+            exchange: "jupiter".to_string(),
+            side: exposure::TradeSide::Buy,
+            order_type: crate::models::order::OrderType::Limit,
+            size: dec!(1),
+            price: dec!(23.45),
+            market_prices: HashMap::from([(trading_pair.to_string(), dec!(23.45))]),
+            cross_dex_prices: HashMap::new(),
+            market_impact: HashMap::new(),
+        };
+
+        assert!(matches!(
+            manager.simulate_operation(request("BONK/USDC")).await.unwrap_err(),
+            RiskError::PairQuarantined(_)
+        ));
+        assert!(manager.simulate_operation(request("SOL/USDC")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_event_window_tightens_limits_only_inside_window() {
+        use crate::events::{EventGuardConfig, EventRequest, ImpactLevel};
+        use chrono::TimeZone;
+
+        let start = chrono::Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let at = |minute: i64| start + chrono::Duration::minutes(minute);
+        let events = Arc::new(EventCalendar::new(EventGuardConfig {
+            paused_strategies: vec!["momentum-1".to_string()],
+            ..EventGuardConfig::default()
+        }));
+        events
+            .add(
+                EventRequest {
+                    timestamp: at(120),
+                    name: "FOMC".to_string(),
+                    impact: ImpactLevel::High,
+                },
+                "ops",
+                start,
+            )
+            .await
+            .unwrap();
+        let mut manager = RiskManager::new(RiskConfig::default()).unwrap();
+        manager.set_event_calendar(events);
+        let request = |strategy_id: &str, size, cross_dex_price: Option<Decimal>| validation::TradeRequest {
+            strategy_id: strategy_id.to_string(),
+            wallet_address: "wallet-1".to_string(),
+            trading_pair: "SOL/USDC".to_string(),
+            exchange: "jupiter".to_string(),
+            side: exposure::TradeSide::Buy,
+            order_type: crate::models::order::OrderType::Limit,
+            size,
+            price: dec!(20),
+            market_prices: HashMap::from([("SOL/USDC".to_string(), dec!(20))]),
+            cross_dex_prices: cross_dex_price
+                .map(|price| HashMap::from([("orca".to_string(), price)]))
+                .unwrap_or_default(),
+            market_impact: HashMap::new(),
+        };
+        // 4,000 USDC notional against the 2,500 cap; 20 bps of edge against the 50 required
+        let large = request("grid-1", dec!(200), None);
+        let thin_arbitrage = request("arb-1", dec!(10), Some(dec!(20.04)));
+        let paused = request("momentum-1", dec!(10), None);
+
+        // Window runs from 30 minutes before to 60 minutes after the event, end exclusive
+        for (minute, guarded) in [(89, false), (90, true), (179, true), (180, false)] {
+            for trade_request in [&large, &thin_arbitrage, &paused] {
+                let checked = manager.precheck(trade_request, at(minute)).await;
+                assert_eq!(
+                    matches!(checked, Err(RiskError::EventRisk(_))),
+                    guarded,
+                    "{} at minute {}",
+                    trade_request.strategy_id,
+                    minute
+                );
+                if !guarded {
+                    assert!(checked.is_ok());
+                }
+            }
+        }
+        assert!(manager.precheck(&request("grid-1", dec!(100), None), at(120)).await.is_ok());
+        assert!(manager
+            .precheck(&request("arb-1", dec!(10), Some(dec!(20.2))), at(120))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_degraded_quality_scales_validated_size() {
+        use crate::utils::health::{ComponentStatus, QualityInputs};
+
+        let metrics = Arc::new(crate::utils::metrics::MetricsCollector::new().unwrap());
+        let health = Arc::new(HealthMonitor::new(Duration::from_secs(30), metrics));
+        let mut manager = RiskManager::new(RiskConfig::default()).unwrap();
+        manager.set_health_monitor(health.clone());
+        let request = |strategy_id: &str| validation::TradeRequest {
+            strategy_id: strategy_id.to_string(),
+            wallet_address: "wallet-1".to_string(),
+            trading_pair: "SOL/USDC".to_string(),
+            exchange: "jupiter".to_string(),
+            side: exposure::TradeSide::Buy,
+            order_type: crate::models::order::OrderType::Limit,
+            size: dec!(4),
+            price: dec!(23.45),
+            market_prices: HashMap::from([("SOL/USDC".to_string(), dec!(23.45))]),
+            cross_dex_prices: HashMap::new(),
+            market_impact: HashMap::new(),
+        };
+        let set_score = |score: f64| {
+            health.record_quality(&QualityInputs {
+                collector_staleness: Duration::ZERO,
+                aggregator_confidence: score,
+                rpc: ComponentStatus::Healthy,
+                persistence_backlog: 0,
+                slippage_ratio: 1.0,
+            });
+        };
+
+        // Default curve: full size from 0.9, half at 0.7, nothing at 0.5
+        for (score, multiplier, size) in [
+            (0.95, dec!(1), dec!(4)),
+            (0.9, dec!(1), dec!(4)),
+            (0.8, dec!(0.75), dec!(3)),
+            (0.7, dec!(0.5), dec!(2)),
+            (0.6, dec!(0.25), dec!(1)),
+        ] {
+            set_score(score);
+            let validated = manager.validate_operation(request("grid-1")).await.unwrap();
+            assert!(validated.is_valid, "score {}", score);
+            assert_eq!(validated.size_multiplier, multiplier, "score {}", score);
+            assert_eq!(validated.approved_size, Some(size), "score {}", score);
+        }
+
+        set_score(0.5);
+        let halted = manager.validate_operation(request("grid-1")).await.unwrap();
+        assert!(!halted.is_valid);
+        assert_eq!(halted.approved_size, Some(dec!(0)));
+        assert!(halted.failure_reason.unwrap().contains("system quality 0.5"));
+
+        // Only an explicit opt-out keeps a strategy at full size while degraded
+        manager
+            .degradation()
+            .set_opt_out("hedger", true, "admin", chrono::Utc::now())
+            .unwrap();
+        let exempt = manager.simulate_operation(request("hedger")).await.unwrap();
+        assert!(exempt.is_valid);
+        assert_eq!(exempt.size_multiplier, dec!(1));
+        assert_eq!(exempt.approved_size, Some(dec!(4)));
+        assert!(!manager.simulate_operation(request("grid-1")).await.unwrap().is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_config_update_keeps_portfolio() {
+        use crate::models::market::QuoteAsset;
+
+        let mut manager = RiskManager::new(RiskConfig::default()).unwrap();
+        manager.reconcile_balance(dec!(500), Decimal::ZERO, &[]).await.unwrap();
+
+        manager.update_risk_config(RiskConfig::default()).await.unwrap();
+
+        let primary = &manager.book_snapshots().await[0];
+        assert_eq!(primary.balances[&QuoteAsset::Usdc], dec!(500));
+    }
+
+    #[tokio::test]
+    async fn test_reloaded_velocity_override_applies() {
+        use crate::config::environment::VelocityCaps;
+
+        let manager = Arc::new(RwLock::new(RiskManager::new(RiskConfig::default()).unwrap()));
+        let (updates, receiver) = watch::channel(None);
+        let listener = spawn_reload_listener(manager.clone(), receiver);
+
+        let mut config = EnvironmentConfig::new();
+        let caps = VelocityCaps { max_orders_per_minute: 5, max_notional_per_hour: dec!(1000) };
+        config.strategy_velocity_overrides.insert("grid-1".to_string(), caps);
+        updates.send(Some(config)).unwrap();
+
+        let expected = VelocityLimits::from(caps);
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while manager.read().await.velocity_limits(VelocityScope::Strategy, "grid-1").await != expected {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("reloaded override was not applied");
+        let defaults = manager.read().await.velocity_limits(VelocityScope::Strategy, "grid-2").await;
+        assert_eq!(defaults, VelocityLimits::default());
+        listener.abort();
+    }
+
+    #[tokio::test]
+    async fn test_configured_degradation_curve_applied() {
+        use crate::utils::health::{ComponentStatus, QualityInputs};
+
+        let mut config = EnvironmentConfig::new();
+        config.degradation_breakpoints = vec![(dec!(0.8), dec!(1)), (dec!(0.6), dec!(0))];
+        config.degradation_opt_outs = vec!["hedger".to_string()];
+
+        let metrics = Arc::new(crate::utils::metrics::MetricsCollector::new().unwrap());
+        let health = Arc::new(HealthMonitor::new(Duration::from_secs(30), metrics));
+        let mut manager = RiskManager::new(RiskConfig::from_environment(&config)).unwrap();
+        manager.set_health_monitor(health.clone());
+        health.record_quality(&QualityInputs {
+            collector_staleness: Duration::ZERO,
+            aggregator_confidence: 0.65,
+            rpc: ComponentStatus::Healthy,
+            persistence_backlog: 0,
+            slippage_ratio: 1.0,
+        });
+        let request = |strategy_id: &str| validation::TradeRequest {
+            strategy_id: strategy_id.to_string(),
+            wallet_address: "wallet-1".to_string(),
+            trading_pair: "SOL/USDC".to_string(),
+            exchange: "jupiter".to_string(),
+            side: exposure::TradeSide::Buy,
+            order_type: crate::models::order::OrderType::Limit,
+            size: dec!(4),
+            price: dec!(23.45),
+            market_prices: HashMap::from([("SOL/USDC".to_string(), dec!(23.45))]),
+            cross_dex_prices: HashMap::new(),
+            market_impact: HashMap::new(),
+        };
+
+        // A quarter of the way up the configured curve; the default would size at 0.375
+        let validated = manager.simulate_operation(request("grid-1")).await.unwrap();
+        assert_eq!(validated.size_multiplier, dec!(0.25));
+        assert_eq!(validated.approved_size, Some(dec!(1)));
+        assert_eq!(manager.simulate_operation(request("hedger")).await.unwrap().size_multiplier, dec!(1));
+
+        // A reload without the opt-out sizes the strategy along the curve again
+        config.degradation_opt_outs.clear();
+        manager.update_risk_config(RiskConfig::from_environment(&config)).await.unwrap();
+        assert!(!manager.degradation().is_opted_out("hedger"));
+        assert_eq!(manager.simulate_operation(request("hedger")).await.unwrap().size_multiplier, dec!(0.25));
+    }
+}
@@ -0,0 +1,533 @@
+//! Differential reconciliation of perp positions against Drift's on-chain user account.
+//! Token balances cannot show a missed or double-counted perp fill, so each run compares
+//! per-market base size, quote entry amount and unrealized P&L between our position book
+//! and the Drift account. Mark-driven drift within the size tolerance is corrected to the
+//! venue's figures; a size mismatch halts new entries on the market and raises an alert.
+//! Every run is persisted for audit.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use parking_lot::RwLock as SyncRwLock;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::api::WebhookDispatcher;
+use crate::models::portfolio::Position;
+use crate::models::webhook::{WebhookEvent, WebhookEventType};
+use crate::risk_manager::margin::{DriftPerpPositionData, MarginError, PerpAccountSource};
+
+// Perp reconciliation constants
+const METRICS_PREFIX: &str = "trading_bot.risk_manager.perp_reconciliation";
+const DEFAULT_SIZE_TOLERANCE: Decimal = Decimal::new(1, 6);
+const DEFAULT_PNL_TOLERANCE: Decimal = Decimal::new(1, 2); // 0.01 USDC
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Perp reconciliation errors
+#[derive(Error, Debug)]
+pub enum ReconcileError {
+    #[error("unknown reconcile action: {0}")]
+    UnknownAction(String),
+    #[error("account source error: {0}")]
+    Source(#[from] MarginError),
+    #[error("store error: {0}")]
+    Store(String),
+}
+
+/// What a run did about one market
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconcileAction {
+    /// Our position agrees with the venue within tolerance
+    Matched,
+    /// Size agreed but entry or P&L had drifted; our position now carries Drift's figures
+    Corrected,
+    /// Size disagrees beyond tolerance; new entries on the market are refused
+    Halted,
+}
+
+impl ReconcileAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Matched => "matched",
+            Self::Corrected => "corrected",
+            Self::Halted => "halted",
+        }
+    }
+}
+
+impl FromStr for ReconcileAction {
+    type Err = ReconcileError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "matched" => Ok(Self::Matched),
+            "corrected" => Ok(Self::Corrected),
+            "halted" => Ok(Self::Halted),
+            other => Err(ReconcileError::UnknownAction(other.to_string())),
+        }
+    }
+}
+
+/// Comparison of one market between our book and the Drift account
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketReconciliation {
+    pub market: String,
+    /// Signed base sizes; positive is long
+    pub internal_size: Decimal,
+    pub venue_size: Decimal,
+    /// Venue size minus internal size
+    pub size_diff: Decimal,
+    /// Quote amounts paid to open, negative for longs as Drift reports them
+    pub internal_quote_entry: Decimal,
+    pub venue_quote_entry: Decimal,
+    /// None when the source had no mark for the market
+    pub mark_price: Option<Decimal>,
+    pub internal_unrealized_pnl: Option<Decimal>,
+    pub venue_unrealized_pnl: Option<Decimal>,
+    pub action: ReconcileAction,
+}
+
+impl MarketReconciliation {
+    /// Compares our position with the venue's, either of which may be absent
+    fn compare(
+        market: &str,
+        internal: Option<&Position>,
+        venue: Option<&DriftPerpPositionData>,
+        mark_price: Option<Decimal>,
+        config: &ReconcileConfig,
+    ) -> Self {
+        let (internal_size, internal_entry) = internal
+            .map(|position| (position.size, position.entry_price))
+            .unwrap_or_default();
+        let (venue_size, venue_quote_entry) = venue
+            .map(|data| (data.base_asset_amount, data.quote_entry_amount))
+            .unwrap_or_default();
+        let internal_quote_entry = -internal_size * internal_entry;
+        let size_diff = venue_size - internal_size;
+
+        let internal_unrealized_pnl = mark_price.map(|mark| internal_size * mark + internal_quote_entry);
+        let venue_unrealized_pnl = mark_price.map(|mark| venue_size * mark + venue_quote_entry);
+        // Without a mark, entry cost stands in for P&L; both differ by the same amount
+        let pnl_diff = match (internal_unrealized_pnl, venue_unrealized_pnl) {
+            (Some(internal), Some(venue)) => venue - internal,
+            _ => venue_quote_entry - internal_quote_entry,
+        };
+
+        let action = if size_diff.abs() > config.size_tolerance {
+            ReconcileAction::Halted
+        } else if !size_diff.is_zero() || pnl_diff.abs() > config.pnl_tolerance {
+            ReconcileAction::Corrected
+        } else {
+            ReconcileAction::Matched
+        };
+
+        Self {
+            market: market.to_string(),
+            internal_size,
+            venue_size,
+            size_diff,
+            internal_quote_entry,
+            venue_quote_entry,
+            mark_price,
+            internal_unrealized_pnl,
+            venue_unrealized_pnl,
+            action,
+        }
+    }
+
+    /// Entry price implied by the venue's quote entry amount; zero when flat
+    pub fn venue_entry_price(&self) -> Decimal {
+        if self.venue_size.is_zero() {
+            return Decimal::ZERO;
+        }
+        (self.venue_quote_entry / self.venue_size).abs()
+    }
+}
+
+/// One pass over every perp market held on either side
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReconciliationRun {
+    pub id: Uuid,
+    pub reconciled_at: DateTime<Utc>,
+    pub markets: Vec<MarketReconciliation>,
+}
+
+impl ReconciliationRun {
+    pub fn market(&self, market: &str) -> Option<&MarketReconciliation> {
+        self.markets.iter().find(|result| result.market == market)
+    }
+
+    pub fn halted(&self) -> impl Iterator<Item = &MarketReconciliation> {
+        self.markets
+            .iter()
+            .filter(|result| result.action == ReconcileAction::Halted)
+    }
+}
+
+/// Tolerances separating matches, corrections and halts
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconcileConfig {
+    /// Largest base size difference still treated as rounding rather than a missed fill
+    pub size_tolerance: Decimal,
+    /// Unrealized P&L difference, in USDC, above which our entry is corrected to Drift's
+    pub pnl_tolerance: Decimal,
+    pub interval: Duration,
+}
+
+impl Default for ReconcileConfig {
+    fn default() -> Self {
+        Self {
+            size_tolerance: DEFAULT_SIZE_TOLERANCE,
+            pnl_tolerance: DEFAULT_PNL_TOLERANCE,
+            interval: DEFAULT_INTERVAL,
+        }
+    }
+}
+
+/// Persists reconciliation runs for audit
+#[async_trait]
+pub trait ReconciliationStore: Send + Sync {
+    async fn save(&self, run: &ReconciliationRun) -> Result<(), ReconcileError>;
+}
+
+/// Our perp positions and the actions a reconciliation can take on them
+#[async_trait]
+pub trait PerpPositionBook: Send + Sync {
+    /// Open perp positions as we track them internally
+    async fn perp_positions(&self) -> Vec<Position>;
+    /// Overwrites a position with the venue's size and entry; zero size removes it
+    async fn correct_position(&self, market: &str, size: Decimal, entry_price: Decimal) -> Result<(), String>;
+    /// Stops new entries on a market until an operator clears it
+    async fn halt_market(&self, market: &str, reason: &str) -> Result<(), String>;
+}
+
+/// Periodically reconciles our perp positions against the Drift user account
+pub struct DriftReconciler {
+    config: ReconcileConfig,
+    source: Arc<dyn PerpAccountSource>,
+    store: SyncRwLock<Option<Arc<dyn ReconciliationStore>>>,
+    webhooks: SyncRwLock<Option<Arc<WebhookDispatcher>>>,
+    /// Serializes runs so a slow pass never overlaps the next tick
+    running: Mutex<()>,
+}
+
+impl std::fmt::Debug for DriftReconciler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DriftReconciler")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl DriftReconciler {
+    pub fn new(config: ReconcileConfig, source: Arc<dyn PerpAccountSource>) -> Self {
+        Self {
+            config,
+            source,
+            store: SyncRwLock::new(None),
+            webhooks: SyncRwLock::new(None),
+            running: Mutex::new(()),
+        }
+    }
+
+    /// Persists runs through the given store
+    pub fn set_store(&self, store: Arc<dyn ReconciliationStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    /// Sends size mismatch alerts through the webhook dispatcher
+    pub fn set_webhooks(&self, webhooks: Arc<WebhookDispatcher>) {
+        *self.webhooks.write() = Some(webhooks);
+    }
+
+    pub fn config(&self) -> &ReconcileConfig {
+        &self.config
+    }
+
+    /// Compares every perp market held by us or by the Drift account, correcting
+    /// mark-driven drift and halting markets whose size disagrees
+    pub async fn reconcile(
+        &self,
+        book: &dyn PerpPositionBook,
+        now: DateTime<Utc>,
+    ) -> Result<ReconciliationRun, ReconcileError> {
+        let _running = self.running.lock().await;
+
+        let account = self.source.user_account().await?;
+        let marks = self.source.mark_prices().await?;
+        let internal: HashMap<String, Position> = book
+            .perp_positions()
+            .await
+            .into_iter()
+            .map(|position| (position.trading_pair.as_str().to_string(), position))
+            .collect();
+        let venue: HashMap<&str, &DriftPerpPositionData> = account
+            .perp_positions
+            .iter()
+            .map(|data| (data.market.as_str(), data))
+            .collect();
+
+        let markets: BTreeSet<&str> = internal
+            .keys()
+            .map(String::as_str)
+            .chain(venue.keys().copied())
+            .collect();
+        let mut results = Vec::with_capacity(markets.len());
+        for market in markets {
+            let result = MarketReconciliation::compare(
+                market,
+                internal.get(market),
+                venue.get(market).copied(),
+                marks.get(market).copied(),
+                &self.config,
+            );
+            self.apply(book, &result).await;
+            results.push(result);
+        }
+
+        let run = ReconciliationRun {
+            id: Uuid::new_v4(),
+            reconciled_at: now,
+            markets: results,
+        };
+        counter!(format!("{}.runs", METRICS_PREFIX), 1);
+        if run.markets.is_empty() {
+            return Ok(run);
+        }
+
+        let store = self.store.read().clone();
+        if let Some(store) = store {
+            store.save(&run).await?;
+        }
+        Ok(run)
+    }
+
+    async fn apply(&self, book: &dyn PerpPositionBook, result: &MarketReconciliation) {
+        gauge!(
+            format!("{}.size_diff", METRICS_PREFIX),
+            result.size_diff.abs().to_f64().unwrap_or(0.0),
+            "market" => result.market.clone()
+        );
+
+        match result.action {
+            ReconcileAction::Matched => {}
+            ReconcileAction::Corrected => {
+                info!(
+                    market = %result.market,
+                    size_diff = %result.size_diff,
+                    venue_quote_entry = %result.venue_quote_entry,
+                    internal_quote_entry = %result.internal_quote_entry,
+                    "Correcting perp position to Drift account state"
+                );
+                counter!(format!("{}.corrections", METRICS_PREFIX), 1, "market" => result.market.clone());
+                if let Err(e) = book
+                    .correct_position(&result.market, result.venue_size, result.venue_entry_price())
+                    .await
+                {
+                    counter!(format!("{}.action_failures", METRICS_PREFIX), 1);
+                    error!(market = %result.market, "Failed to correct perp position: {}", e);
+                }
+            }
+            ReconcileAction::Halted => {
+                warn!(
+                    market = %result.market,
+                    internal_size = %result.internal_size,
+                    venue_size = %result.venue_size,
+                    "Perp position size disagrees with Drift account"
+                );
+                counter!(format!("{}.size_mismatches", METRICS_PREFIX), 1, "market" => result.market.clone());
+                let reason = format!(
+                    "perp size mismatch: internal {} vs drift {}",
+                    result.internal_size, result.venue_size
+                );
+                if let Err(e) = book.halt_market(&result.market, &reason).await {
+                    counter!(format!("{}.action_failures", METRICS_PREFIX), 1);
+                    error!(market = %result.market, "Failed to halt perp market: {}", e);
+                }
+                self.notify(result);
+            }
+        }
+    }
+
+    fn notify(&self, result: &MarketReconciliation) {
+        let Some(webhooks) = self.webhooks.read().clone() else {
+            return;
+        };
+        match WebhookEvent::new(WebhookEventType::PositionMismatch, result) {
+            Ok(event) => webhooks.dispatch(event),
+            Err(e) => warn!("Failed to build webhook event: {}", e),
+        }
+    }
+
+    /// Reconciles against the book on the configured interval
+    pub fn spawn(self: Arc<Self>, book: Arc<dyn PerpPositionBook>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.reconcile(book.as_ref(), Utc::now()).await {
+                    warn!("Failed to reconcile perp positions: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::pair::TradingPair;
+    use crate::risk_manager::margin::DriftUserAccount;
+    use parking_lot::Mutex as SyncMutex;
+    use rust_decimal_macros::dec;
+
+    const SMALL: &str = "SOL-PERP";
+    const LARGE: &str = "ETH-PERP";
+
+    struct MockDrift {
+        account: DriftUserAccount,
+        marks: HashMap<String, Decimal>,
+    }
+
+    #[async_trait]
+    impl PerpAccountSource for MockDrift {
+        async fn user_account(&self) -> Result<DriftUserAccount, MarginError> {
+            Ok(self.account.clone())
+        }
+
+        async fn mark_prices(&self) -> Result<HashMap<String, Decimal>, MarginError> {
+            Ok(self.marks.clone())
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryBook {
+        positions: SyncMutex<HashMap<String, Position>>,
+        halted: SyncMutex<Vec<String>>,
+    }
+
+    impl MemoryBook {
+        fn open(&self, market: &str, size: Decimal, entry_price: Decimal) {
+            self.positions.lock().insert(
+                market.to_string(),
+                Position {
+                    trading_pair: TradingPair::parse(market).unwrap(),
+                    size,
+                    entry_price,
+                    realized_pnl: Decimal::ZERO,
+                    last_updated: Utc::now(),
+                },
+            );
+        }
+    }
+
+    #[async_trait]
+    impl PerpPositionBook for MemoryBook {
+        async fn perp_positions(&self) -> Vec<Position> {
+            self.positions.lock().values().cloned().collect()
+        }
+
+        async fn correct_position(&self, market: &str, size: Decimal, entry_price: Decimal) -> Result<(), String> {
+            self.open(market, size, entry_price);
+            Ok(())
+        }
+
+        async fn halt_market(&self, market: &str, _reason: &str) -> Result<(), String> {
+            self.halted.lock().push(market.to_string());
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryStore {
+        runs: SyncMutex<Vec<ReconciliationRun>>,
+    }
+
+    #[async_trait]
+    impl ReconciliationStore for MemoryStore {
+        async fn save(&self, run: &ReconciliationRun) -> Result<(), ReconcileError> {
+            self.runs.lock().push(run.clone());
+            Ok(())
+        }
+    }
+
+    fn drift_position(market: &str, size: Decimal, entry: Decimal) -> DriftPerpPositionData {
+        DriftPerpPositionData {
+            market: market.to_string(),
+            base_asset_amount: size,
+            quote_entry_amount: -size * entry,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_small_disagreement_corrected_and_large_one_halts_market() {
+        let book = MemoryBook::default();
+        book.open(SMALL, dec!(10), dec!(100));
+        book.open(LARGE, dec!(2), dec!(3000));
+        // Drift agrees on SOL size but settled the entry slightly higher; ETH shows a
+        // missed fill of half a contract
+        let source = Arc::new(MockDrift {
+            account: DriftUserAccount {
+                collateral: dec!(5000),
+                perp_positions: vec![
+                    drift_position(SMALL, dec!(10), dec!(100.05)),
+                    drift_position(LARGE, dec!(2.5), dec!(3000)),
+                ],
+            },
+            marks: HashMap::from([(SMALL.to_string(), dec!(101)), (LARGE.to_string(), dec!(3010))]),
+        });
+        let store = Arc::new(MemoryStore::default());
+        let reconciler = DriftReconciler::new(ReconcileConfig::default(), source);
+        reconciler.set_store(store.clone());
+
+        let run = reconciler.reconcile(&book, Utc::now()).await.unwrap();
+
+        let small = run.market(SMALL).unwrap();
+        assert_eq!(small.action, ReconcileAction::Corrected);
+        assert_eq!(small.internal_unrealized_pnl, Some(dec!(10)));
+        assert_eq!(small.venue_unrealized_pnl, Some(dec!(9.5)));
+        assert_eq!(book.positions.lock()[SMALL].entry_price, dec!(100.05));
+
+        let large = run.market(LARGE).unwrap();
+        assert_eq!(large.action, ReconcileAction::Halted);
+        assert_eq!(large.size_diff, dec!(0.5));
+        // A halted market keeps our figures for an operator to investigate
+        assert_eq!(book.positions.lock()[LARGE].size, dec!(2));
+        assert_eq!(book.halted.lock().as_slice(), [LARGE]);
+
+        assert_eq!(store.runs.lock().as_slice(), [run]);
+
+        // The corrected market now matches on the next pass
+        let again = reconciler.reconcile(&book, Utc::now()).await.unwrap();
+        assert_eq!(again.market(SMALL).unwrap().action, ReconcileAction::Matched);
+    }
+
+    #[test]
+    fn test_position_missing_on_either_side_is_a_size_mismatch() {
+        let config = ReconcileConfig::default();
+        let venue_only = drift_position(SMALL, dec!(-1), dec!(100));
+        let result = MarketReconciliation::compare(SMALL, None, Some(&venue_only), Some(dec!(100)), &config);
+        assert_eq!(result.action, ReconcileAction::Halted);
+        assert_eq!(result.venue_entry_price(), dec!(100));
+
+        let dust = drift_position(SMALL, dec!(0.0000005), dec!(100));
+        let result = MarketReconciliation::compare(SMALL, None, Some(&dust), None, &config);
+        assert_eq!(result.action, ReconcileAction::Corrected);
+    }
+}
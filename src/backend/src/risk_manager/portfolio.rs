@@ -19,16 +19,23 @@ use thiserror::Error;
 use tokio::time::sleep;
 use tracing::{debug, error, info, instrument, warn};
 
-use crate::models::portfolio::Portfolio;
+use crate::attribution::{AttributionEngine, LedgerEntry};
+use crate::models::portfolio::{Portfolio, PortfolioSnapshot};
+use crate::models::transfer::Transfer;
+use crate::risk_manager::analytics::{AnalyticsSnapshot, RiskAnalytics};
+use crate::risk_manager::exposure::{ExposureBook, ExposureLimits, TradeSide};
 use crate::risk_manager::limits::RiskLimits;
 use crate::risk_manager::validation::{ValidationResult, ValidationSeverity};
+use crate::utils::circuit_breaker::{BreakerConfig, CircuitBreaker};
+use crate::utils::percent::Percent;
+use crate::utils::solana::TokenBalanceChange;
 
 // Risk management constants
-const REBALANCE_THRESHOLD_PERCENT: Decimal = Decimal::new(5, 2); // 5%
-const MAX_DRAWDOWN_PERCENT: Decimal = Decimal::new(20, 2); // 20%
+const REBALANCE_THRESHOLD: Percent = Percent::from_percent(Decimal::new(5, 0)); // 5%
+pub(crate) const MAX_DRAWDOWN: Percent = Percent::from_percent(Decimal::new(20, 0)); // 20%
 const RISK_CHECK_INTERVAL_MS: u64 = 1000;
 const CACHE_EXPIRY_MS: u64 = 500;
-const CIRCUIT_BREAKER_THRESHOLD: Decimal = Decimal::new(25, 2); // 25%
+pub(crate) const CIRCUIT_BREAKER_THRESHOLD: Percent = Percent::from_percent(Decimal::new(25, 0)); // 25%
 
 /// Portfolio risk management error types
 #[derive(Error, Debug)]
@@ -47,10 +54,23 @@ pub enum RiskError {
 #[derive(Debug, Clone)]
 pub struct PortfolioHealth {
     pub total_value: Decimal,
-    pub drawdown: Decimal,
-    pub concentration: Decimal,
-    pub volatility: Decimal,
+    /// Worst flow-adjusted drawdown across the books
+    pub drawdown: Percent,
+    /// Largest exposure to a single asset as a percentage of total value, including the
+    /// exposure of correlated assets when analytics are available
+    pub concentration: Percent,
+    /// Annualized volatility of net exposure as a fraction of total value; `None` when a
+    /// held asset lacks the history to estimate it
+    pub volatility: Option<Decimal>,
+    /// Held assets whose volatility or correlations could not be estimated
+    pub insufficient_data: Vec<String>,
+    /// Gross exposure across all books as a multiple of total value
     pub leverage: Decimal,
+    /// Sum of absolute net exposure per asset after offsetting books
+    pub net_exposure: Decimal,
+    /// Sum of gross exposure per asset before offsetting books
+    pub gross_exposure: Decimal,
+    pub exposure: ExposureBook,
     pub is_healthy: bool,
     pub circuit_breaker_active: bool,
     pub last_checked: chrono::DateTime<chrono::Utc>,
@@ -80,7 +100,12 @@ pub struct PortfolioRiskManager {
     metrics_cache: Arc<parking_lot::RwLock<HashMap<String, (Instant, Decimal)>>>,
     high_water_mark: RwLock<Decimal>,
     target_allocations: HashMap<String, Decimal>,
-    circuit_breaker: RwLock<bool>,
+    circuit_breaker: Arc<CircuitBreaker>,
+    /// Additional strategy and wallet books netted against the primary portfolio
+    books: RwLock<HashMap<String, Portfolio>>,
+    exposure_limits: ExposureLimits,
+    analytics: Option<Arc<RiskAnalytics>>,
+    attribution: Option<Arc<AttributionEngine>>,
 }
 
 impl PortfolioRiskManager {
@@ -92,7 +117,11 @@ impl PortfolioRiskManager {
             metrics_cache: Arc::new(parking_lot::RwLock::new(HashMap::new())),
             high_water_mark: RwLock::new(Decimal::ZERO),
             target_allocations: risk_config.target_allocations,
-            circuit_breaker: RwLock::new(false),
+            circuit_breaker: CircuitBreaker::new("portfolio_drawdown", BreakerConfig::manual()).registered(),
+            books: RwLock::new(HashMap::new()),
+            exposure_limits: risk_config.exposure_limits,
+            analytics: None,
+            attribution: None,
         };
 
         // Initialize metrics
@@ -101,6 +130,85 @@ impl PortfolioRiskManager {
         instance
     }
 
+    /// Uses historical volatility and correlations in health checks
+    pub fn with_analytics(mut self, analytics: Arc<RiskAnalytics>) -> Self {
+        self.analytics = Some(analytics);
+        self
+    }
+
+    /// Records reconciled balances and detected transfers for P&L attribution
+    pub fn with_attribution(mut self, attribution: Arc<AttributionEngine>) -> Self {
+        self.attribution = Some(attribution);
+        self
+    }
+
+    /// Registers a strategy or wallet book whose positions count toward aggregate exposure
+    pub fn register_book(&self, book_id: String, portfolio: Portfolio) {
+        self.books.write().insert(book_id, portfolio);
+    }
+
+    pub fn remove_book(&self, book_id: &str) -> Option<Portfolio> {
+        self.books.write().remove(book_id)
+    }
+
+    /// Primary portfolio followed by every registered book
+    fn all_books(&self) -> Vec<Portfolio> {
+        let mut portfolios = vec![self.portfolio.read().clone()];
+        portfolios.extend(self.books.read().values().cloned());
+        portfolios
+    }
+
+    /// Checks health across the primary portfolio and all registered books
+    pub async fn check_portfolio_health(
+        &self,
+        market_prices: &HashMap<String, Decimal>,
+    ) -> Result<PortfolioHealth, RiskError> {
+        let analytics = self
+            .analytics
+            .as_ref()
+            .map(|analytics| analytics.snapshot(chrono::Utc::now()));
+        check_portfolio_health(
+            &self.all_books(),
+            market_prices,
+            &self.exposure_limits,
+            analytics.as_deref(),
+        )
+        .await
+    }
+
+    /// Balances and positions of the primary portfolio and every registered book
+    pub async fn book_snapshots(&self) -> Vec<PortfolioSnapshot> {
+        let mut snapshots = Vec::new();
+        for portfolio in self.all_books() {
+            snapshots.push(portfolio.snapshot().await);
+        }
+        snapshots
+    }
+
+    /// Worst flow-adjusted drawdown across all books
+    pub async fn drawdown(&self) -> Percent {
+        worst_drawdown(&self.all_books()).await
+    }
+
+    /// Net and gross exposure per underlying asset across all books
+    pub async fn exposure_book(
+        &self,
+        market_prices: &HashMap<String, Decimal>,
+    ) -> Result<ExposureBook, RiskError> {
+        build_exposure_book(&self.all_books(), market_prices).await
+    }
+
+    pub fn exposure_limits(&self) -> &ExposureLimits {
+        &self.exposure_limits
+    }
+
+    /// Applies reloaded limits, keeping the live portfolio, books and high-water mark
+    pub fn update_limits(&mut self, risk_config: RiskConfig) {
+        self.risk_limits = RiskLimits::new(risk_config.metrics.clone());
+        self.target_allocations = risk_config.target_allocations;
+        self.exposure_limits = risk_config.exposure_limits;
+    }
+
     /// Continuously monitors portfolio health with circuit breaker protection
     #[instrument(skip(self))]
     pub async fn monitor_portfolio(&self) -> Result<(), RiskError> {
@@ -111,6 +219,8 @@ impl PortfolioRiskManager {
 
             // Check portfolio health
             let health = self.check_portfolio_health(&HashMap::new()).await?;
+            let portfolio = self.portfolio.read().clone();
+            *self.high_water_mark.write() = portfolio.get_high_water_mark().await;
 
             // Update metrics
             histogram!(
@@ -119,13 +229,13 @@ impl PortfolioRiskManager {
             );
             histogram!(
                 "trading_bot.risk_manager.drawdown",
-                health.drawdown.to_f64().unwrap_or(0.0)
+                health.drawdown.as_percent().to_f64().unwrap_or(0.0)
             );
 
             // Handle circuit breaker
             if health.circuit_breaker_active {
                 error!("Circuit breaker activated - portfolio risk exceeded thresholds");
-                *self.circuit_breaker.write() = true;
+                self.circuit_breaker.trip(format!("drawdown {} exceeded threshold", health.drawdown));
                 counter!("trading_bot.risk_manager.circuit_breaker_trips", 1);
                 return Err(RiskError::CircuitBreaker(
                     "portfolio risk thresholds exceeded".to_string(),
@@ -156,6 +266,55 @@ impl PortfolioRiskManager {
         }
     }
 
+    /// Reconciles an observed wallet balance, separating deposits and withdrawals from P&L
+    #[instrument(skip(self, on_chain_changes))]
+    pub async fn reconcile_balance(
+        &self,
+        observed_balance: Decimal,
+        trade_delta: Decimal,
+        on_chain_changes: &[TokenBalanceChange],
+    ) -> Result<Option<Transfer>, RiskError> {
+        let portfolio = self.portfolio.read().clone();
+        let transfer = portfolio
+            .reconcile_balance(observed_balance, trade_delta, on_chain_changes)
+            .await
+            .map_err(|e| RiskError::PortfolioError(e.to_string()))?;
+
+        if let Some(transfer) = &transfer {
+            info!(
+                "Recorded {} of {} - high-water mark adjusted",
+                transfer.direction.as_str(),
+                transfer.amount
+            );
+            *self.high_water_mark.write() = portfolio.get_high_water_mark().await;
+        }
+
+        // Transfers explain balance changes that are not P&L; the observation anchors the
+        // next reconciliation
+        if let Some(attribution) = &self.attribution {
+            if let Some(transfer) = &transfer {
+                attribution.record(LedgerEntry::transfer(transfer)).await;
+            }
+            attribution
+                .record_balance(portfolio.wallet_address(), observed_balance, chrono::Utc::now())
+                .await;
+        }
+
+        Ok(transfer)
+    }
+
+    /// Whether an order only reduces the primary portfolio's open position on its pair
+    pub async fn reduces_position(&self, trading_pair: &str, side: TradeSide, size: Decimal) -> bool {
+        let portfolio = self.portfolio.read().clone();
+        let Some(position) = portfolio.get_position(trading_pair).await else {
+            return false;
+        };
+        match side {
+            TradeSide::Sell => position.size > Decimal::ZERO && size <= position.size,
+            TradeSide::Buy => position.size < Decimal::ZERO && size <= -position.size,
+        }
+    }
+
     /// Validates trade against risk limits and current portfolio state
     #[instrument(skip(self, trade_request))]
     pub async fn validate_trade_risk(
@@ -165,7 +324,7 @@ impl PortfolioRiskManager {
         let start = Instant::now();
 
         // Check circuit breaker
-        if *self.circuit_breaker.read() {
+        if self.circuit_breaker.is_open() {
             return Err(RiskError::CircuitBreaker(
                 "trading suspended - circuit breaker active".to_string(),
             ));
@@ -200,39 +359,94 @@ impl PortfolioRiskManager {
     }
 }
 
-/// Performs comprehensive portfolio health check
-#[instrument(skip(portfolio, market_prices))]
+/// Aggregates net and gross exposure per underlying asset across books, measured
+/// against their combined value
+pub async fn build_exposure_book(
+    portfolios: &[Portfolio],
+    market_prices: &HashMap<String, Decimal>,
+) -> Result<ExposureBook, RiskError> {
+    let mut equity = Decimal::ZERO;
+    for portfolio in portfolios {
+        equity += portfolio
+            .calculate_portfolio_value(market_prices)
+            .await
+            .map_err(|e| RiskError::PortfolioError(e.to_string()))?;
+    }
+
+    let mut exposure = ExposureBook::new(equity);
+    for portfolio in portfolios {
+        exposure.add_positions(&portfolio.get_positions().await, market_prices);
+    }
+    Ok(exposure)
+}
+
+/// Drawdown on flow-adjusted returns so deposits and withdrawals are ignored, taking the
+/// worst book so one losing strategy isn't hidden by the others
+pub async fn worst_drawdown(portfolios: &[Portfolio]) -> Percent {
+    let mut drawdown = Percent::ZERO;
+    for portfolio in portfolios {
+        drawdown = drawdown.max(Percent::from_percent(portfolio.flow_adjusted_returns().await.drawdown_pct));
+    }
+    drawdown
+}
+
+/// Performs comprehensive health check across strategy and wallet books, applying
+/// concentration limits to net exposure and a separate limit to gross leverage. With
+/// analytics, concentration counts correlated exposure and volatility is estimated from
+/// historical returns.
+#[instrument(skip(portfolios, market_prices, exposure_limits, analytics))]
 pub async fn check_portfolio_health(
-    portfolio: &Portfolio,
+    portfolios: &[Portfolio],
     market_prices: &HashMap<String, Decimal>,
+    exposure_limits: &ExposureLimits,
+    analytics: Option<&AnalyticsSnapshot>,
 ) -> Result<PortfolioHealth, RiskError> {
     let start = Instant::now();
 
-    // Calculate current portfolio value
-    let total_value = portfolio
-        .calculate_portfolio_value(market_prices)
-        .await
-        .map_err(|e| RiskError::PortfolioError(e.to_string()))?;
+    // Calculate combined value and per-asset exposure
+    let exposure = build_exposure_book(portfolios, market_prices).await?;
+    let total_value = exposure.equity();
+
+    let drawdown = worst_drawdown(portfolios).await;
 
-    // Calculate drawdown
-    let high_water_mark = portfolio.get_high_water_mark().await;
-    let drawdown = if total_value < high_water_mark {
-        ((high_water_mark - total_value) * Decimal::new(100, 0)) / high_water_mark
-    } else {
-        Decimal::ZERO
+    // Without analytics nothing is known about volatility, which is reported as such
+    let (volatility, insufficient_data) = match analytics.map(|a| a.portfolio_volatility(&exposure)) {
+        Some(Ok(volatility)) => (Some(volatility), Vec::new()),
+        Some(Err(assets)) => (None, assets),
+        None => (None, exposure.assets().keys().cloned().collect()),
     };
+    if !insufficient_data.is_empty() {
+        debug!("Insufficient history to estimate risk for {:?}", insufficient_data);
+    }
 
-    // Calculate risk metrics
-    let metrics = portfolio.get_metrics().await
-        .map_err(|e| RiskError::PortfolioError(e.to_string()))?;
+    let concentration = analytics
+        .map(|a| a.correlated_concentration_pct(&exposure))
+        .unwrap_or_else(|| exposure.max_net_concentration_pct());
+    let exposure_breach = exposure.breach(exposure_limits).or_else(|| {
+        (concentration > exposure_limits.max_net_concentration_pct).then(|| {
+            format!(
+                "correlated concentration {} exceeds maximum {}",
+                concentration.round_dp(2),
+                exposure_limits.max_net_concentration_pct
+            )
+        })
+    });
+    if let Some(breach) = &exposure_breach {
+        warn!("Exposure limit breached: {}", breach);
+        counter!("trading_bot.risk_manager.exposure_breaches", 1);
+    }
 
     let health = PortfolioHealth {
         total_value,
         drawdown,
-        concentration: calculate_concentration(portfolio, market_prices).await?,
-        volatility: calculate_volatility(portfolio, market_prices).await?,
-        leverage: calculate_leverage(portfolio).await?,
-        is_healthy: drawdown < MAX_DRAWDOWN_PERCENT,
+        concentration,
+        volatility,
+        insufficient_data,
+        leverage: exposure.gross_leverage(),
+        net_exposure: exposure.total_net_value(),
+        gross_exposure: exposure.total_gross_value(),
+        exposure,
+        is_healthy: drawdown < MAX_DRAWDOWN && exposure_breach.is_none(),
         circuit_breaker_active: drawdown > CIRCUIT_BREAKER_THRESHOLD,
         last_checked: chrono::Utc::now(),
     };
@@ -265,15 +479,16 @@ pub async fn calculate_rebalance_requirements(
         let current = portfolio.get_position_allocation(trading_pair).await
             .map_err(|e| RiskError::PortfolioError(e.to_string()))?;
 
-        let difference = (current - *target).abs();
-        if difference > REBALANCE_THRESHOLD_PERCENT {
+        // Allocations are percentages of portfolio value
+        let difference = Percent::from_percent((current - *target).abs());
+        if difference > REBALANCE_THRESHOLD {
             let direction = if current < *target {
                 RebalanceDirection::Increase
             } else {
                 RebalanceDirection::Decrease
             };
 
-            let size = (difference * current_value) / Decimal::new(100, 0);
+            let size = difference.of(current_value);
             
             rebalance_actions.push(RebalanceAction {
                 trading_pair: trading_pair.clone(),
@@ -302,28 +517,7 @@ pub async fn calculate_rebalance_requirements(
 }
 
 // Helper functions
-async fn calculate_concentration(
-    portfolio: &Portfolio,
-    market_prices: &HashMap<String, Decimal>,
-) -> Result<Decimal, RiskError> {
-    // Implementation details
-    Ok(Decimal::new(0, 0))
-}
-
-async fn calculate_volatility(
-    portfolio: &Portfolio,
-    market_prices: &HashMap<String, Decimal>,
-) -> Result<Decimal, RiskError> {
-    // Implementation details
-    Ok(Decimal::new(0, 0))
-}
-
-async fn calculate_leverage(portfolio: &Portfolio) -> Result<Decimal, RiskError> {
-    // Implementation details
-    Ok(Decimal::new(0, 0))
-}
-
-fn calculate_rebalance_priority(difference: Decimal) -> u8 {
+fn calculate_rebalance_priority(difference: Percent) -> u8 {
     // Implementation details
     0
 }
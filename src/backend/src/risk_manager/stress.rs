@@ -0,0 +1,696 @@
+//! Portfolio stress testing. A scenario shocks underlying asset prices, scales volatility,
+//! optionally overrides every correlation and charges an exit cost for unwinding; the
+//! current books are revalued under it with the portfolio valuation, exposure limits and
+//! perp margin logic the live risk checks use, including whether perp positions would be
+//! liquidated. Runs are pure computation and never trade; each run is persisted for audit.
+//!
+//! Stablecoin shocks model a depeg: pairs quoted in the stablecoin are repriced against it
+//! and the reporting-currency total is converted back to dollars at the shocked peg.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - utoipa = "3.5"
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::market::QuoteAsset;
+use crate::models::portfolio::PortfolioSnapshot;
+use crate::risk_manager::analytics::AnalyticsSnapshot;
+use crate::risk_manager::exposure::{underlying_asset, ExposureBook, ExposureLimits};
+use crate::risk_manager::factors::RiskFactorService;
+use crate::risk_manager::margin::{liquidation_distance_pct, LiquidationLevel, MarginAccount, MarginConfig};
+use crate::risk_manager::portfolio::{CIRCUIT_BREAKER_THRESHOLD, MAX_DRAWDOWN};
+use crate::risk_manager::RiskManager;
+use crate::utils::percent::{Bps, Percent};
+
+// Stress testing constants
+const METRICS_PREFIX: &str = "trading_bot.risk_manager.stress";
+/// Assets pegged to the dollar; they are left out of the default shock
+const STABLECOINS: &[&str] = &["USDC", "USDT"];
+const MAX_SCENARIO_NAME_LEN: usize = 64;
+pub const MAX_SCENARIOS_PER_RUN: usize = 10;
+pub const CRYPTO_CRASH: &str = "crypto_crash";
+pub const STABLECOIN_DEPEG: &str = "stablecoin_depeg";
+pub const LIQUIDITY_CRUNCH: &str = "liquidity_crunch";
+
+/// Stress testing errors
+#[derive(Error, Debug)]
+pub enum StressError {
+    #[error("unknown scenario: {0}")]
+    UnknownScenario(String),
+    #[error("invalid scenario: {0}")]
+    InvalidScenario(String),
+    #[error("valuation failed: {0}")]
+    Valuation(String),
+    #[error("stress run store error: {0}")]
+    Store(String),
+}
+
+/// Shocks applied to the current portfolio
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StressScenario {
+    pub name: String,
+    /// Price change per underlying asset, e.g. SOL at -30
+    #[serde(default)]
+    pub price_shocks: BTreeMap<String, Percent>,
+    /// Price change of every non-stablecoin asset without its own shock
+    #[serde(default)]
+    pub default_shock: Percent,
+    /// Multiplier on each asset's volatility estimate
+    #[serde(default)]
+    pub volatility_multipliers: BTreeMap<String, Decimal>,
+    /// Multiplier on the volatility of assets without their own multiplier; 1 when unset
+    #[serde(default)]
+    pub default_volatility_multiplier: Option<Decimal>,
+    /// Replaces every pairwise correlation, e.g. 1 for a market-wide selloff
+    #[serde(default)]
+    pub correlation_override: Option<Decimal>,
+    /// Cost of unwinding each position as a share of its stressed notional, covering
+    /// widened spreads and slippage
+    #[serde(default)]
+    pub exit_cost: Bps,
+}
+
+impl StressScenario {
+    pub fn validate(&self) -> Result<(), StressError> {
+        let invalid = |reason: String| Err(StressError::InvalidScenario(format!("{}: {}", self.name, reason)));
+        if self.name.trim().is_empty() || self.name.len() > MAX_SCENARIO_NAME_LEN {
+            return Err(StressError::InvalidScenario(format!(
+                "name must be 1 to {} characters",
+                MAX_SCENARIO_NAME_LEN
+            )));
+        }
+        let floor = -Percent::ONE_HUNDRED;
+        if let Some((asset, _)) = self.price_shocks.iter().find(|(_, shock)| **shock <= floor) {
+            return invalid(format!("{} shock must be above -100%", asset));
+        }
+        if self.default_shock <= floor {
+            return invalid("default shock must be above -100%".to_string());
+        }
+        let mut multipliers = self.volatility_multipliers.values().chain(self.default_volatility_multiplier.as_ref());
+        if multipliers.any(|multiplier| multiplier.is_sign_negative()) {
+            return invalid("volatility multipliers must not be negative".to_string());
+        }
+        if let Some(rho) = self.correlation_override {
+            if rho < Decimal::NEGATIVE_ONE || rho > Decimal::ONE {
+                return invalid("correlation override must be between -1 and 1".to_string());
+            }
+        }
+        if self.exit_cost < Bps::ZERO || self.exit_cost > Bps::from_fraction(Decimal::ONE) {
+            return invalid("exit cost must be between 0 and 10000 bps".to_string());
+        }
+        Ok(())
+    }
+
+    /// Price change applied to an underlying asset
+    pub fn shock(&self, asset: &str) -> Percent {
+        match self.price_shocks.get(asset) {
+            Some(shock) => *shock,
+            None if STABLECOINS.contains(&asset) => Percent::ZERO,
+            None => self.default_shock,
+        }
+    }
+
+    fn volatility_multiplier(&self, asset: &str) -> Decimal {
+        self.volatility_multipliers
+            .get(asset)
+            .or(self.default_volatility_multiplier.as_ref())
+            .copied()
+            .unwrap_or(Decimal::ONE)
+    }
+
+    /// Price of a pair after shocking its base asset against its quote; perp markets are
+    /// quoted in the reporting currency
+    fn shocked_price(&self, trading_pair: &str, price: Decimal) -> Decimal {
+        let quote = trading_pair
+            .rsplit_once('/')
+            .map(|(_, quote)| underlying_asset(quote))
+            .unwrap_or_else(|| QuoteAsset::REPORTING.as_str().to_string());
+        let base_factor = Decimal::ONE + self.shock(&underlying_asset(trading_pair)).as_fraction();
+        let quote_factor = Decimal::ONE + self.shock(&quote).as_fraction();
+        price * base_factor / quote_factor
+    }
+}
+
+/// Scenarios available by name
+pub fn builtin_scenarios() -> Vec<StressScenario> {
+    vec![
+        // Broad selloff with volatility doubling and correlations converging
+        StressScenario {
+            name: CRYPTO_CRASH.to_string(),
+            price_shocks: BTreeMap::new(),
+            default_shock: Percent::from_percent(Decimal::new(-40, 0)),
+            volatility_multipliers: BTreeMap::new(),
+            default_volatility_multiplier: Some(Decimal::TWO),
+            correlation_override: Some(Decimal::new(9, 1)),
+            exit_cost: Bps::ZERO,
+        },
+        // Dollar stablecoins lose a tenth of their peg while other assets hold their dollar value
+        StressScenario {
+            name: STABLECOIN_DEPEG.to_string(),
+            price_shocks: STABLECOINS
+                .iter()
+                .map(|asset| (asset.to_string(), Percent::from_percent(Decimal::new(-10, 0))))
+                .collect(),
+            default_shock: Percent::ZERO,
+            volatility_multipliers: BTreeMap::new(),
+            default_volatility_multiplier: None,
+            correlation_override: None,
+            exit_cost: Bps::ZERO,
+        },
+        // Moderate drop with spreads widening enough that unwinding costs 3%
+        StressScenario {
+            name: LIQUIDITY_CRUNCH.to_string(),
+            price_shocks: BTreeMap::new(),
+            default_shock: Percent::from_percent(Decimal::new(-15, 0)),
+            volatility_multipliers: BTreeMap::new(),
+            default_volatility_multiplier: Some(Decimal::new(15, 1)),
+            correlation_override: None,
+            exit_cost: Bps::new(300),
+        },
+    ]
+}
+
+/// Built-in scenarios by name plus custom scenarios; every built-in when both are empty
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct StressRequest {
+    #[serde(default)]
+    pub scenarios: Vec<String>,
+    #[serde(default)]
+    pub custom: Vec<StressScenario>,
+}
+
+impl StressRequest {
+    /// Resolves named scenarios and validates custom ones
+    pub fn resolve(self) -> Result<Vec<StressScenario>, StressError> {
+        if self.scenarios.is_empty() && self.custom.is_empty() {
+            return Ok(builtin_scenarios());
+        }
+        if self.scenarios.len() + self.custom.len() > MAX_SCENARIOS_PER_RUN {
+            return Err(StressError::InvalidScenario(format!(
+                "at most {} scenarios per run",
+                MAX_SCENARIOS_PER_RUN
+            )));
+        }
+
+        let builtins = builtin_scenarios();
+        let mut scenarios = self
+            .scenarios
+            .iter()
+            .map(|name| {
+                builtins
+                    .iter()
+                    .find(|scenario| scenario.name == *name)
+                    .cloned()
+                    .ok_or_else(|| StressError::UnknownScenario(name.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        for scenario in &self.custom {
+            scenario.validate()?;
+        }
+        scenarios.extend(self.custom);
+        Ok(scenarios)
+    }
+}
+
+/// Perp position at or near liquidation under a scenario
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MarginCall {
+    pub market: String,
+    pub size: Decimal,
+    /// Mark after the shock
+    pub mark_price: Decimal,
+    pub liquidation_price: Option<Decimal>,
+    pub distance_pct: Option<Percent>,
+    pub level: LiquidationLevel,
+}
+
+/// Risk limit exceeded under a scenario
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LimitBreach {
+    pub limit: String,
+    /// Underlying asset for per-asset limits
+    pub asset: Option<String>,
+    pub value: Decimal,
+    pub threshold: Decimal,
+}
+
+impl LimitBreach {
+    fn new(limit: &str, asset: Option<&str>, value: Decimal, threshold: Decimal) -> Self {
+        Self {
+            limit: limit.to_string(),
+            asset: asset.map(str::to_string),
+            value,
+            threshold,
+        }
+    }
+}
+
+/// Portfolio under one scenario
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StressResult {
+    pub scenario: StressScenario,
+    pub base_value: Decimal,
+    /// Value of every book at shocked prices, in dollars
+    pub stressed_value: Decimal,
+    /// Cost of unwinding every position at the scenario's exit cost
+    pub exit_cost: Decimal,
+    /// Base value less stressed value net of exit costs
+    pub loss: Decimal,
+    pub loss_pct: Percent,
+    /// Drawdown from the flow-adjusted peak once the loss is taken
+    pub drawdown: Percent,
+    /// Annualized volatility of stressed net exposure; None without volatility and
+    /// correlation estimates for every held asset
+    pub volatility: Option<Decimal>,
+    pub gross_leverage: Decimal,
+    /// Perp collateral including unrealized P&L at shocked marks
+    pub perp_collateral: Option<Decimal>,
+    /// True when perp collateral no longer covers maintenance margin
+    pub perp_liquidated: bool,
+    pub margin_calls: Vec<MarginCall>,
+    pub limit_breaches: Vec<LimitBreach>,
+}
+
+/// Scenarios evaluated together against the same portfolio state, kept for audit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StressRun {
+    pub id: Uuid,
+    pub requested_by: String,
+    pub computed_at: DateTime<Utc>,
+    pub base_value: Decimal,
+    pub results: Vec<StressResult>,
+}
+
+impl StressRun {
+    /// Largest loss across the run's scenarios
+    pub fn max_loss(&self) -> Decimal {
+        self.results.iter().map(|result| result.loss).max().unwrap_or_default()
+    }
+}
+
+/// Portfolio state every scenario in a run is applied to
+#[derive(Debug, Clone)]
+pub struct StressBaseline {
+    pub books: Vec<PortfolioSnapshot>,
+    pub prices: HashMap<String, Decimal>,
+    /// Current worst flow-adjusted drawdown across the books
+    pub drawdown: Percent,
+    pub exposure_limits: ExposureLimits,
+    pub analytics: Option<Arc<AnalyticsSnapshot>>,
+    pub margin: Option<(MarginAccount, MarginConfig)>,
+}
+
+impl StressBaseline {
+    /// Total value of every book at `prices`, in the reporting currency
+    fn value(&self, prices: &HashMap<String, Decimal>, now: DateTime<Utc>) -> Result<Decimal, StressError> {
+        let mut total = Decimal::ZERO;
+        for book in &self.books {
+            total += book
+                .valuation(prices, now)
+                .map_err(|e| StressError::Valuation(e.to_string()))?
+                .total_value;
+        }
+        Ok(total)
+    }
+
+    /// Revalues the books, perp account and exposure under a scenario
+    pub fn apply(&self, scenario: &StressScenario, now: DateTime<Utc>) -> Result<StressResult, StressError> {
+        let base_value = self.value(&self.prices, now)?;
+        let prices: HashMap<String, Decimal> = self
+            .prices
+            .iter()
+            .map(|(pair, price)| (pair.clone(), scenario.shocked_price(pair, *price)))
+            .collect();
+
+        // Books are valued in the reporting currency, then converted to dollars at its shocked peg
+        let peg = Decimal::ONE + scenario.shock(QuoteAsset::REPORTING.as_str()).as_fraction();
+        let stressed_reporting_value = self.value(&prices, now)?;
+        let mut exposure = ExposureBook::new(stressed_reporting_value);
+        for book in &self.books {
+            exposure.add_positions(&book.positions, &prices);
+        }
+        let stressed_value = stressed_reporting_value * peg;
+        let exit_cost = scenario.exit_cost.of(exposure.total_gross_value()) * peg;
+
+        let loss = base_value - (stressed_value - exit_cost);
+        let loss_pct = Percent::ratio(loss, base_value);
+        let remaining = Decimal::ONE - self.drawdown.as_fraction();
+        let drawdown = if base_value > Decimal::ZERO {
+            Percent::from_fraction(Decimal::ONE - remaining * (base_value - loss) / base_value)
+        } else {
+            self.drawdown
+        };
+
+        let volatility = self.analytics.as_ref().and_then(|analytics| {
+            let multipliers = analytics
+                .volatility
+                .keys()
+                .map(|asset| (asset.clone(), scenario.volatility_multiplier(asset)))
+                .collect();
+            let correlation_override = scenario.correlation_override.and_then(|rho| rho.to_f64());
+            analytics.stressed(&multipliers, correlation_override).portfolio_volatility(&exposure).ok()
+        });
+
+        let (perp_collateral, perp_liquidated, margin_calls) = match &self.margin {
+            Some((account, config)) => {
+                let marks = account
+                    .positions
+                    .iter()
+                    .map(|position| (position.market.clone(), scenario.shocked_price(&position.market, position.mark_price)))
+                    .collect();
+                let stressed = account
+                    .with_marks(&marks, now)
+                    .map_err(|e| StressError::Valuation(e.to_string()))?;
+                let calls = stressed
+                    .positions
+                    .iter()
+                    .filter_map(|position| {
+                        let distance_pct = liquidation_distance_pct(position);
+                        let level = match distance_pct {
+                            _ if stressed.is_liquidatable() => LiquidationLevel::EmergencyClose,
+                            Some(distance) => config.level(distance),
+                            None => LiquidationLevel::Safe,
+                        };
+                        (level != LiquidationLevel::Safe).then(|| MarginCall {
+                            market: position.market.clone(),
+                            size: position.size,
+                            mark_price: position.mark_price,
+                            liquidation_price: position.liquidation_price,
+                            distance_pct,
+                            level,
+                        })
+                    })
+                    .collect();
+                (Some(stressed.total_collateral()), stressed.is_liquidatable(), calls)
+            }
+            None => (None, false, Vec::new()),
+        };
+
+        let limits = &self.exposure_limits;
+        let mut limit_breaches: Vec<LimitBreach> = exposure
+            .assets()
+            .iter()
+            .filter_map(|(asset, e)| {
+                let concentration = Percent::ratio(e.net_value.abs(), exposure.equity());
+                (concentration > limits.max_net_concentration_pct).then(|| {
+                    LimitBreach::new(
+                        "net_concentration",
+                        Some(asset),
+                        concentration.as_percent(),
+                        limits.max_net_concentration_pct.as_percent(),
+                    )
+                })
+            })
+            .collect();
+        let gross_leverage = exposure.gross_leverage();
+        if gross_leverage > limits.max_gross_leverage {
+            limit_breaches.push(LimitBreach::new("gross_leverage", None, gross_leverage, limits.max_gross_leverage));
+        }
+        for (name, threshold) in [("max_drawdown", MAX_DRAWDOWN), ("drawdown_circuit_breaker", CIRCUIT_BREAKER_THRESHOLD)] {
+            if drawdown > threshold {
+                limit_breaches.push(LimitBreach::new(name, None, drawdown.as_percent(), threshold.as_percent()));
+            }
+        }
+
+        Ok(StressResult {
+            scenario: scenario.clone(),
+            base_value,
+            stressed_value,
+            exit_cost,
+            loss,
+            loss_pct,
+            drawdown,
+            volatility,
+            gross_leverage,
+            perp_collateral,
+            perp_liquidated,
+            margin_calls,
+            limit_breaches,
+        })
+    }
+}
+
+/// Persistence for stress runs
+#[async_trait]
+pub trait StressRunStore: Send + Sync {
+    async fn record(&self, run: &StressRun) -> Result<(), StressError>;
+    /// Most recent runs, newest first
+    async fn recent(&self, limit: i64) -> Result<Vec<StressRun>, StressError>;
+}
+
+/// Runs stress scenarios against the risk manager's books at the latest risk factor prices
+pub struct StressTester {
+    risk_manager: Arc<RwLock<RiskManager>>,
+    prices: Arc<RiskFactorService>,
+    store: Option<Arc<dyn StressRunStore>>,
+}
+
+impl std::fmt::Debug for StressTester {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StressTester").finish_non_exhaustive()
+    }
+}
+
+impl StressTester {
+    pub fn new(risk_manager: Arc<RwLock<RiskManager>>, prices: Arc<RiskFactorService>) -> Self {
+        Self {
+            risk_manager,
+            prices,
+            store: None,
+        }
+    }
+
+    /// Persists every run for audit
+    pub fn with_store(mut self, store: Arc<dyn StressRunStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Captures the books, prices, limits, analytics and perp margin state
+    pub async fn baseline(&self, now: DateTime<Utc>) -> StressBaseline {
+        let risk_manager = self.risk_manager.read().await;
+        let margin = risk_manager
+            .perp_margin()
+            .and_then(|monitor| monitor.account().map(|account| (account, monitor.config().clone())));
+
+        StressBaseline {
+            books: risk_manager.book_snapshots().await,
+            prices: self.prices.prices(),
+            drawdown: risk_manager.drawdown().await,
+            exposure_limits: risk_manager.exposure_limits().await,
+            analytics: Some(risk_manager.analytics().snapshot(now)),
+            margin,
+        }
+    }
+
+    /// Applies every scenario to the same baseline and records the run
+    pub async fn run(
+        &self,
+        scenarios: &[StressScenario],
+        requested_by: &str,
+        now: DateTime<Utc>,
+    ) -> Result<StressRun, StressError> {
+        let baseline = self.baseline(now).await;
+        let results = scenarios
+            .iter()
+            .map(|scenario| baseline.apply(scenario, now))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let run = StressRun {
+            id: Uuid::new_v4(),
+            requested_by: requested_by.to_string(),
+            computed_at: now,
+            base_value: baseline.value(&baseline.prices, now)?,
+            results,
+        };
+        // A run that cannot be audited is not returned
+        if let Some(store) = &self.store {
+            store.record(&run).await?;
+        }
+
+        counter!(format!("{}.runs", METRICS_PREFIX), 1);
+        Ok(run)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::pair::TradingPair;
+    use crate::models::portfolio::Position;
+    use crate::risk_manager::analytics::{CorrelationMatrix, Estimate};
+    use crate::risk_manager::margin::{DriftPerpPositionData, DriftUserAccount, PerpMarketParams};
+    use rust_decimal_macros::dec;
+
+    fn position(pair: &str, size: Decimal, entry_price: Decimal) -> Position {
+        Position {
+            trading_pair: TradingPair::parse(pair).unwrap(),
+            size,
+            entry_price,
+            realized_pnl: Decimal::ZERO,
+            last_updated: Utc::now(),
+        }
+    }
+
+    /// 1000 USDC, 10 SOL at 100 and 100 ORCA at 2: 2200 USDC in total
+    fn baseline() -> StressBaseline {
+        StressBaseline {
+            books: vec![PortfolioSnapshot {
+                wallet_address: "wallet".to_string(),
+                balances: HashMap::from([(QuoteAsset::Usdc, dec!(1000))]),
+                positions: vec![position("ORCA/USDC", dec!(100), dec!(2)), position("SOL/USDC", dec!(10), dec!(90))],
+                realized_pnl: Decimal::ZERO,
+            }],
+            prices: HashMap::from([("SOL/USDC".to_string(), dec!(100)), ("ORCA/USDC".to_string(), dec!(2))]),
+            drawdown: Percent::ZERO,
+            exposure_limits: ExposureLimits::default(),
+            analytics: None,
+            margin: None,
+        }
+    }
+
+    fn builtin(name: &str) -> StressScenario {
+        builtin_scenarios().into_iter().find(|scenario| scenario.name == name).unwrap()
+    }
+
+    /// 1000 USDC of collateral and 20 SOL-PERP long from 100: liquidation at 1000 / 19
+    fn margin() -> (MarginAccount, MarginConfig) {
+        let markets = HashMap::from([(
+            "SOL-PERP".to_string(),
+            PerpMarketParams {
+                initial_margin_ratio: dec!(0.1),
+                maintenance_margin_ratio: dec!(0.05),
+            },
+        )]);
+        let account = DriftUserAccount {
+            collateral: dec!(1000),
+            perp_positions: vec![DriftPerpPositionData {
+                market: "SOL-PERP".to_string(),
+                base_asset_amount: dec!(20),
+                quote_entry_amount: dec!(-2000),
+            }],
+        };
+        let marks = HashMap::from([("SOL-PERP".to_string(), dec!(100))]);
+        let account = MarginAccount::from_drift(&account, &markets, &marks, Utc::now()).unwrap();
+        (account, MarginConfig { markets, ..MarginConfig::default() })
+    }
+
+    #[test]
+    fn test_crypto_crash_revalues_positions_and_flags_margin_call() {
+        let mut baseline = baseline();
+        baseline.margin = Some(margin());
+        let result = baseline.apply(&builtin(CRYPTO_CRASH), Utc::now()).unwrap();
+
+        // SOL 100 -> 60 and ORCA 2 -> 1.2: 1000 + 600 + 120
+        assert_eq!(result.base_value, dec!(2200));
+        assert_eq!(result.stressed_value, dec!(1720));
+        assert_eq!(result.loss, dec!(480));
+        assert_eq!(result.loss_pct.round_dp(4), Percent::from_percent(dec!(21.8182)));
+        assert!(result.limit_breaches.iter().any(|b| b.limit == "max_drawdown"));
+
+        // SOL is 600 / 1720 of stressed equity, over the 25% concentration limit
+        let sol = result.limit_breaches.iter().find(|b| b.asset.as_deref() == Some("SOL")).unwrap();
+        assert_eq!(sol.value.round_dp(4), dec!(34.8837));
+
+        // Perp loses 20 * 40: 200 collateral left, 60 mark is 12.28% above liquidation at 52.63
+        assert_eq!(result.perp_collateral, Some(dec!(200)));
+        assert!(!result.perp_liquidated);
+        let call = &result.margin_calls[0];
+        assert_eq!(call.mark_price, dec!(60));
+        assert_eq!(call.liquidation_price.unwrap().round_dp(4), dec!(52.6316));
+        assert_eq!(call.level, LiquidationLevel::Notify);
+    }
+
+    #[test]
+    fn test_stablecoin_depeg_loses_only_stablecoin_value() {
+        let result = baseline().apply(&builtin(STABLECOIN_DEPEG), Utc::now()).unwrap();
+
+        // Tokens keep their dollar value; the 1000 USDC is worth 900
+        assert_eq!(result.stressed_value.round_dp(4), dec!(2100));
+        assert_eq!(result.loss.round_dp(4), dec!(100));
+        assert_eq!(result.exit_cost, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_liquidity_crunch_charges_exit_cost_on_gross_exposure() {
+        let result = baseline().apply(&builtin(LIQUIDITY_CRUNCH), Utc::now()).unwrap();
+
+        // SOL 85 and ORCA 1.7: 1000 + 850 + 170, with 3% of the 1020 notional to exit
+        assert_eq!(result.stressed_value, dec!(2020));
+        assert_eq!(result.exit_cost, dec!(30.6));
+        assert_eq!(result.loss, dec!(210.6));
+        assert!(result.margin_calls.is_empty());
+        assert_eq!(result.perp_collateral, None);
+    }
+
+    #[test]
+    fn test_custom_scenario_overrides_volatility_and_correlation() {
+        let mut baseline = baseline();
+        baseline.drawdown = Percent::from_percent(dec!(10));
+        baseline.analytics = Some(Arc::new(AnalyticsSnapshot {
+            volatility: BTreeMap::from([
+                ("SOL".to_string(), Estimate::Available { value: 0.8, observations: 30 }),
+                ("ORCA".to_string(), Estimate::Available { value: 0.6, observations: 30 }),
+            ]),
+            correlations: CorrelationMatrix::default(),
+            computed_at: Utc::now(),
+        }));
+        let scenario = StressScenario {
+            name: "sol_halving".to_string(),
+            price_shocks: BTreeMap::from([("SOL".to_string(), Percent::from_percent(dec!(-50)))]),
+            default_shock: Percent::ZERO,
+            volatility_multipliers: BTreeMap::from([("SOL".to_string(), dec!(2))]),
+            default_volatility_multiplier: None,
+            correlation_override: Some(Decimal::ONE),
+            exit_cost: Bps::ZERO,
+        };
+        let result = baseline.apply(&scenario, Utc::now()).unwrap();
+
+        // 1000 + 500 + 200; perfectly correlated volatility is (500 * 1.6 + 200 * 0.6) / 1700
+        assert_eq!(result.stressed_value, dec!(1700));
+        let volatility = result.volatility.unwrap().to_f64().unwrap();
+        assert!((volatility - 920.0 / 1700.0).abs() < 1e-9, "volatility {}", volatility);
+
+        // 10% already down, then 500 / 2200 more: 1 - 0.9 * 1700 / 2200
+        assert_eq!(result.drawdown.round_dp(4), Percent::from_percent(dec!(30.4545)));
+
+        // Without the override the correlation is unknown and volatility is not reported
+        let scenario = StressScenario { correlation_override: None, ..scenario };
+        assert_eq!(baseline.apply(&scenario, Utc::now()).unwrap().volatility, None);
+    }
+
+    #[test]
+    fn test_request_resolution() {
+        assert_eq!(StressRequest::default().resolve().unwrap().len(), 3);
+
+        let request = StressRequest {
+            scenarios: vec![LIQUIDITY_CRUNCH.to_string()],
+            custom: vec![StressScenario { name: "wipeout".to_string(), ..builtin(CRYPTO_CRASH) }],
+        };
+        let names: Vec<String> = request.resolve().unwrap().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec![LIQUIDITY_CRUNCH, "wipeout"]);
+
+        let unknown = StressRequest { scenarios: vec!["meteor".to_string()], custom: Vec::new() };
+        assert!(matches!(unknown.resolve(), Err(StressError::UnknownScenario(_))));
+
+        let mut invalid = builtin(CRYPTO_CRASH);
+        invalid.default_shock = Percent::from_percent(dec!(-100));
+        let request = StressRequest { scenarios: Vec::new(), custom: vec![invalid] };
+        assert!(matches!(request.resolve(), Err(StressError::InvalidScenario(_))));
+    }
+}
@@ -14,16 +14,22 @@ use std::collections::HashMap;
 use thiserror::Error;
 use tracing::{warn, instrument};
 
-use crate::models::order::Order;
+use crate::models::market::QuoteAsset;
+use crate::models::order::{Order, OrderType};
 use crate::models::portfolio::Portfolio;
+use crate::risk_manager::exposure::{ExposureBook, ExposureLimits, TradeSide};
+use crate::utils::percent::{Bps, Percent};
 
 // Risk management constants
 const MAX_TRADE_VALUE_USDC: Decimal = Decimal::new(100_000, 0); // $100,000
 const MIN_TRADE_VALUE_USDC: Decimal = Decimal::new(10, 0); // $10
 const MAX_POSITION_COUNT: usize = 10;
-const MAX_CONCENTRATION_PCT: Decimal = Decimal::new(25, 0); // 25%
-const MAX_DRAWDOWN_PCT: Decimal = Decimal::new(15, 0); // 15%
-const MAX_VOLATILITY_PCT: Decimal = Decimal::new(40, 0); // 40%
+const MAX_CONCENTRATION: Percent = Percent::from_percent(Decimal::new(25, 0)); // 25%
+const MAX_DRAWDOWN: Percent = Percent::from_percent(Decimal::new(15, 0)); // 15%
+const MAX_VOLATILITY: Percent = Percent::from_percent(Decimal::new(40, 0)); // 40%
+const MARKET_IMPACT_WARNING: Percent = Percent::from_percent(Decimal::new(5, 1)); // 0.5%
+const MARKET_IMPACT_ALERT: Percent = Percent::from_percent(Decimal::new(10, 1)); // 1.0%
+const MAX_PRICE_DIFF: Percent = Percent::from_percent(Decimal::new(5, 1)); // 0.5%
 const MAX_LEVERAGE_RATIO: Decimal = Decimal::new(3, 0); // 3x
 
 /// Validation severity levels for risk assessment
@@ -56,6 +62,61 @@ pub enum ValidationError {
     RiskLimit(String),
 }
 
+/// Proposed trade submitted for risk validation
+#[derive(Debug, Clone)]
+pub struct TradeRequest {
+    pub strategy_id: String,
+    pub wallet_address: String,
+    pub trading_pair: String,
+    pub exchange: String,
+    pub side: TradeSide,
+    pub order_type: OrderType,
+    pub size: Decimal,
+    pub price: Decimal,
+    pub market_prices: HashMap<String, Decimal>,
+    pub cross_dex_prices: HashMap<String, Decimal>,
+    /// Estimated market impact per trading pair, in percent
+    pub market_impact: HashMap<String, Decimal>,
+}
+
+impl TradeRequest {
+    /// Builds the order validated on behalf of this request
+    pub fn to_order(&self) -> Result<Order, ValidationError> {
+        Order::new(
+            self.trading_pair.clone(),
+            self.exchange.clone(),
+            self.order_type.clone(),
+            self.price,
+            self.size,
+        )
+        .map_err(|e| ValidationError::TradeValidation(e.to_string()))
+    }
+
+    /// Order notional in the reporting currency, so USDC limits apply to SOL-quoted pairs
+    pub fn reporting_notional(&self) -> Result<Decimal, ValidationError> {
+        let rate = QuoteAsset::of_pair(&self.trading_pair)
+            .reporting_rate(&self.market_prices)
+            .map_err(|e| ValidationError::MarketValidation(e.to_string()))?;
+        Ok(self.size * self.price * rate)
+    }
+
+    /// Best edge over the other venues' prices for the side taken, when the request is an
+    /// arbitrage leg quoted against them
+    pub fn cross_dex_edge(&self) -> Option<Bps> {
+        if self.price.is_zero() {
+            return None;
+        }
+        self.cross_dex_prices
+            .values()
+            .map(|other| match self.side {
+                TradeSide::Buy => *other - self.price,
+                TradeSide::Sell => self.price - *other,
+            })
+            .max()
+            .map(|edge| Bps::from_fraction(edge / self.price))
+    }
+}
+
 /// Individual validation metric with detailed context
 #[derive(Debug, Clone)]
 pub struct ValidationMetric {
@@ -74,6 +135,10 @@ pub struct ValidationResult {
     pub severity_level: ValidationSeverity,
     pub timestamp: DateTime<Utc>,
     pub validation_type: ValidationType,
+    /// Degraded-mode multiplier applied to the requested size
+    pub size_multiplier: Decimal,
+    /// Size cleared for execution once the multiplier is applied
+    pub approved_size: Option<Decimal>,
 }
 
 impl ValidationResult {
@@ -86,6 +151,8 @@ impl ValidationResult {
             severity_level: severity,
             timestamp: Utc::now(),
             validation_type: v_type,
+            size_multiplier: Decimal::ONE,
+            approved_size: None,
         }
     }
 
@@ -102,11 +169,16 @@ impl ValidationResult {
     }
 }
 
-/// Validates a proposed trade against all risk rules and portfolio constraints
-#[instrument(skip(order, portfolio, market_prices, cross_dex_prices, market_impact))]
+/// Validates a proposed trade against all risk rules and portfolio constraints, including
+/// the net exposure across all books once the trade is filled
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip(order, portfolio, exposure, exposure_limits, market_prices, cross_dex_prices, market_impact))]
 pub async fn validate_trade(
     order: &Order,
+    side: TradeSide,
     portfolio: &Portfolio,
+    exposure: &ExposureBook,
+    exposure_limits: &ExposureLimits,
     market_prices: &HashMap<String, Decimal>,
     cross_dex_prices: &HashMap<String, Decimal>,
     market_impact: &HashMap<String, Decimal>,
@@ -123,11 +195,16 @@ pub async fn validate_trade(
     }
 
     // Calculate and validate trade value
-    let price = market_prices.get(&order.trading_pair).ok_or_else(|| {
+    let price = market_prices.get(order.trading_pair.as_str()).ok_or_else(|| {
         ValidationError::MarketValidation(format!("no price data for {}", order.trading_pair))
     })?;
 
-    let trade_value = order.size * *price;
+    // Limits are denominated in USDC, so SOL-quoted prices are converted first
+    let rate = QuoteAsset::of_pair(&order.trading_pair)
+        .reporting_rate(market_prices)
+        .map_err(|e| ValidationError::MarketValidation(e.to_string()))?;
+    let reporting_price = *price * rate;
+    let trade_value = order.size * reporting_price;
     if trade_value < MIN_TRADE_VALUE_USDC {
         result.set_failure(
             format!("trade value ${} below minimum ${}", trade_value, MIN_TRADE_VALUE_USDC),
@@ -157,16 +234,41 @@ pub async fn validate_trade(
         return Ok(result);
     }
 
+    // Evaluate post-trade net exposure in the underlying across all strategies and wallets
+    let check = exposure.evaluate_fill(
+        &order.trading_pair,
+        side.signed(order.size),
+        reporting_price,
+        exposure_limits,
+    );
+    result.add_metric(ValidationMetric {
+        name: format!("net_exposure_{}", check.asset),
+        value: check.concentration_pct.as_percent(),
+        threshold: exposure_limits.max_net_concentration_pct.as_percent(),
+        severity: ValidationSeverity::Info,
+    });
+    result.add_metric(ValidationMetric {
+        name: "gross_leverage".to_string(),
+        value: check.gross_leverage,
+        threshold: exposure_limits.max_gross_leverage,
+        severity: ValidationSeverity::Info,
+    });
+
+    if let Some(breach) = check.breach {
+        result.set_failure(breach, ValidationSeverity::Critical);
+        return Ok(result);
+    }
+
     // Check market impact
-    if let Some(impact) = market_impact.get(&order.trading_pair) {
+    if let Some(impact) = market_impact.get(order.trading_pair.as_str()) {
         result.add_metric(ValidationMetric {
             name: "market_impact".to_string(),
             value: *impact,
-            threshold: Decimal::new(5, 1), // 0.5%
+            threshold: MARKET_IMPACT_WARNING.as_percent(),
             severity: ValidationSeverity::Warning,
         });
 
-        if *impact > Decimal::new(10, 1) { // 1.0%
+        if Percent::from_percent(*impact) > MARKET_IMPACT_ALERT {
             warn!(
                 trading_pair = %order.trading_pair,
                 impact = %impact,
@@ -177,11 +279,11 @@ pub async fn validate_trade(
 
     // Validate cross-DEX price discrepancies
     for (dex, price) in cross_dex_prices {
-        let price_diff = ((*price - *price) * Decimal::new(100, 0)) / *price;
+        let price_diff = Percent::ratio(*price - *price, *price);
         result.add_metric(ValidationMetric {
             name: format!("price_diff_{}", dex),
-            value: price_diff,
-            threshold: Decimal::new(5, 1), // 0.5%
+            value: price_diff.as_percent(),
+            threshold: MAX_PRICE_DIFF.as_percent(),
             severity: ValidationSeverity::Info,
         });
     }
@@ -198,39 +300,33 @@ pub async fn validate_portfolio_metrics(
     let mut result = ValidationResult::new(true, ValidationSeverity::Info, ValidationType::Portfolio);
 
     // Validate concentration
-    let concentration = risk_metrics.get("concentration").ok_or_else(|| {
-        ValidationError::PortfolioValidation("missing concentration metric".to_string())
-    })?;
+    let concentration = percent_metric(risk_metrics, "concentration")?;
 
-    if *concentration > MAX_CONCENTRATION_PCT {
+    if concentration > MAX_CONCENTRATION {
         result.set_failure(
-            format!("concentration {}% exceeds maximum {}%", concentration, MAX_CONCENTRATION_PCT),
+            format!("concentration {} exceeds maximum {}", concentration, MAX_CONCENTRATION),
             ValidationSeverity::Critical,
         );
         return Ok(result);
     }
 
     // Validate drawdown
-    let drawdown = risk_metrics.get("drawdown").ok_or_else(|| {
-        ValidationError::PortfolioValidation("missing drawdown metric".to_string())
-    })?;
+    let drawdown = percent_metric(risk_metrics, "drawdown")?;
 
-    if *drawdown > MAX_DRAWDOWN_PCT {
+    if drawdown > MAX_DRAWDOWN {
         result.set_failure(
-            format!("drawdown {}% exceeds maximum {}%", drawdown, MAX_DRAWDOWN_PCT),
+            format!("drawdown {} exceeds maximum {}", drawdown, MAX_DRAWDOWN),
             ValidationSeverity::Critical,
         );
         return Ok(result);
     }
 
     // Validate volatility
-    let volatility = risk_metrics.get("volatility").ok_or_else(|| {
-        ValidationError::PortfolioValidation("missing volatility metric".to_string())
-    })?;
+    let volatility = percent_metric(risk_metrics, "volatility")?;
 
-    if *volatility > MAX_VOLATILITY_PCT {
+    if volatility > MAX_VOLATILITY {
         result.set_failure(
-            format!("volatility {}% exceeds maximum {}%", volatility, MAX_VOLATILITY_PCT),
+            format!("volatility {} exceeds maximum {}", volatility, MAX_VOLATILITY),
             ValidationSeverity::Warning,
         );
     }
@@ -261,13 +357,21 @@ pub async fn validate_portfolio_metrics(
     Ok(result)
 }
 
+/// Reads a portfolio metric reported on the percentage scale
+fn percent_metric(risk_metrics: &HashMap<String, Decimal>, name: &str) -> Result<Percent, ValidationError> {
+    risk_metrics
+        .get(name)
+        .map(|value| Percent::from_percent(*value))
+        .ok_or_else(|| ValidationError::PortfolioValidation(format!("missing {} metric", name)))
+}
+
 /// Returns the threshold value for a given metric
 #[inline]
 fn get_threshold_for_metric(metric: &str) -> Decimal {
     match metric {
-        "concentration" => MAX_CONCENTRATION_PCT,
-        "drawdown" => MAX_DRAWDOWN_PCT,
-        "volatility" => MAX_VOLATILITY_PCT,
+        "concentration" => MAX_CONCENTRATION.as_percent(),
+        "drawdown" => MAX_DRAWDOWN.as_percent(),
+        "volatility" => MAX_VOLATILITY.as_percent(),
         "leverage" => MAX_LEVERAGE_RATIO,
         _ => Decimal::new(100, 0), // Default 100%
     }
@@ -276,10 +380,11 @@ fn get_threshold_for_metric(metric: &str) -> Decimal {
 /// Determines severity level based on metric and value
 #[inline]
 fn get_severity_for_metric(metric: &str, value: Decimal) -> ValidationSeverity {
+    let percent = Percent::from_percent(value);
     match metric {
-        "concentration" if value > MAX_CONCENTRATION_PCT => ValidationSeverity::Critical,
-        "drawdown" if value > MAX_DRAWDOWN_PCT => ValidationSeverity::Critical,
-        "volatility" if value > MAX_VOLATILITY_PCT => ValidationSeverity::Warning,
+        "concentration" if percent > MAX_CONCENTRATION => ValidationSeverity::Critical,
+        "drawdown" if percent > MAX_DRAWDOWN => ValidationSeverity::Critical,
+        "volatility" if percent > MAX_VOLATILITY => ValidationSeverity::Warning,
         "leverage" if value > MAX_LEVERAGE_RATIO => ValidationSeverity::Critical,
         _ => ValidationSeverity::Info,
     }
@@ -300,6 +405,35 @@ mod tests {
         // Test implementation
     }
 
+    #[test]
+    fn test_reporting_notional_converts_sol_quotes() {
+        let mut request = TradeRequest {
+            strategy_id: "grid".to_string(),
+            wallet_address: "wallet123".to_string(),
+            trading_pair: "BONK/SOL".to_string(),
+            exchange: "pump_fun".to_string(),
+            side: TradeSide::Buy,
+            order_type: OrderType::Market,
+            size: dec!(100000),
+            price: dec!(0.00002),
+            market_prices: HashMap::new(),
+            cross_dex_prices: HashMap::new(),
+            market_impact: HashMap::new(),
+        };
+        assert!(request.reporting_notional().is_err());
+
+        request.market_prices.insert("SOL/USDC".to_string(), dec!(150));
+        assert_eq!(request.reporting_notional().unwrap(), dec!(300));
+    }
+
+    #[test]
+    fn test_percent_thresholds() {
+        // Metrics are reported on the 0-100 scale, so a 16% drawdown breaches the 15% limit
+        assert_eq!(get_severity_for_metric("drawdown", dec!(16)), ValidationSeverity::Critical);
+        assert_eq!(get_severity_for_metric("drawdown", dec!(0.16)), ValidationSeverity::Info);
+        assert_eq!(get_threshold_for_metric("concentration"), dec!(25));
+    }
+
     #[test]
     fn test_validation_result() {
         let mut result = ValidationResult::new(true, ValidationSeverity::Info, ValidationType::Trade);
@@ -0,0 +1,481 @@
+//! Order-rate and notional-turnover velocity limits tracked per strategy and per wallet
+//! in sliding windows, so a runaway loop of individually valid orders is stopped.
+//!
+//! Version dependencies:
+//! - rust_decimal = "1.30"
+//! - chrono = "0.4"
+//! - redis = "0.23"
+//! - metrics = "0.20"
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use metrics::gauge;
+use redis::AsyncCommands; // v0.23.0
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::config::environment::VelocityCaps;
+
+// Velocity window constants
+const ORDER_WINDOW_SECS: i64 = 60;
+const NOTIONAL_WINDOW_SECS: i64 = 3600;
+const DEFAULT_MAX_ORDERS_PER_MINUTE: u32 = 60;
+const DEFAULT_MAX_NOTIONAL_PER_HOUR: Decimal = Decimal::new(250_000, 0);
+const REDIS_KEY_PREFIX: &str = "risk:velocity:";
+const METRICS_PREFIX: &str = "trading_bot.risk_manager.velocity";
+
+/// Velocity thresholds for one strategy or wallet
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VelocityLimits {
+    pub max_orders_per_minute: u32,
+    pub max_notional_per_hour: Decimal,
+}
+
+impl Default for VelocityLimits {
+    fn default() -> Self {
+        Self {
+            max_orders_per_minute: DEFAULT_MAX_ORDERS_PER_MINUTE,
+            max_notional_per_hour: DEFAULT_MAX_NOTIONAL_PER_HOUR,
+        }
+    }
+}
+
+impl From<VelocityCaps> for VelocityLimits {
+    fn from(caps: VelocityCaps) -> Self {
+        Self {
+            max_orders_per_minute: caps.max_orders_per_minute,
+            max_notional_per_hour: caps.max_notional_per_hour,
+        }
+    }
+}
+
+/// Whether a window tracks a strategy or a wallet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VelocityScope {
+    Strategy,
+    Wallet,
+}
+
+impl VelocityScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Strategy => "strategy",
+            Self::Wallet => "wallet",
+        }
+    }
+}
+
+/// Limit that would be exceeded by an order
+#[derive(Debug, Clone, PartialEq)]
+pub struct VelocityBreach {
+    pub scope: VelocityScope,
+    pub id: String,
+    pub limit: String,
+    pub current: Decimal,
+    pub threshold: Decimal,
+}
+
+impl std::fmt::Display for VelocityBreach {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} would exceed {}: {} > {}",
+            self.scope.as_str(),
+            self.id,
+            self.limit,
+            self.current,
+            self.threshold
+        )
+    }
+}
+
+/// Time-ordered amounts within a fixed-length trailing window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SlidingWindow {
+    length_secs: i64,
+    entries: VecDeque<(DateTime<Utc>, Decimal)>,
+    total: Decimal,
+}
+
+impl SlidingWindow {
+    fn new(length_secs: i64) -> Self {
+        Self {
+            length_secs,
+            entries: VecDeque::new(),
+            total: Decimal::ZERO,
+        }
+    }
+
+    /// Drops entries that are at least one window length old
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::seconds(self.length_secs);
+        while let Some((at, amount)) = self.entries.front() {
+            if *at > cutoff {
+                break;
+            }
+            self.total -= *amount;
+            self.entries.pop_front();
+        }
+    }
+
+    fn record(&mut self, now: DateTime<Utc>, amount: Decimal) {
+        self.entries.push_back((now, amount));
+        self.total += amount;
+    }
+
+    fn count(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Order count and notional windows for one strategy or wallet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VelocityWindows {
+    orders: SlidingWindow,
+    notional: SlidingWindow,
+}
+
+impl VelocityWindows {
+    fn new() -> Self {
+        Self {
+            orders: SlidingWindow::new(ORDER_WINDOW_SECS),
+            notional: SlidingWindow::new(NOTIONAL_WINDOW_SECS),
+        }
+    }
+
+    fn prune(&mut self, now: DateTime<Utc>) {
+        self.orders.prune(now);
+        self.notional.prune(now);
+    }
+}
+
+/// Velocity windows of one strategy or wallet as captured in a state snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VelocitySnapshot {
+    pub scope: VelocityScope,
+    pub id: String,
+    pub windows: VelocityWindows,
+}
+
+/// Sliding-window velocity tracker with optional Redis persistence across restarts
+#[derive(Debug)]
+pub struct VelocityTracker {
+    limits: VelocityLimits,
+    strategy_overrides: HashMap<String, VelocityLimits>,
+    windows: HashMap<(VelocityScope, String), VelocityWindows>,
+    redis_client: Option<Arc<redis::Client>>,
+}
+
+impl VelocityTracker {
+    pub fn new(limits: VelocityLimits, strategy_overrides: HashMap<String, VelocityLimits>) -> Self {
+        Self {
+            limits,
+            strategy_overrides,
+            windows: HashMap::new(),
+            redis_client: None,
+        }
+    }
+
+    /// Persists window state to Redis so restarts don't reset the windows
+    pub fn set_redis_client(&mut self, redis_client: Arc<redis::Client>) {
+        self.redis_client = Some(redis_client);
+    }
+
+    /// Replaces thresholds without resetting the tracked windows
+    pub fn update_limits(
+        &mut self,
+        limits: VelocityLimits,
+        strategy_overrides: HashMap<String, VelocityLimits>,
+    ) {
+        self.limits = limits;
+        self.strategy_overrides = strategy_overrides;
+    }
+
+    /// Returns the thresholds that apply to a scope
+    pub fn limits_for(&self, scope: VelocityScope, id: &str) -> VelocityLimits {
+        match scope {
+            VelocityScope::Strategy => self
+                .strategy_overrides
+                .get(id)
+                .copied()
+                .unwrap_or(self.limits),
+            VelocityScope::Wallet => self.limits,
+        }
+    }
+
+    /// Checks an order against strategy and wallet limits without recording it
+    pub async fn check(
+        &mut self,
+        now: DateTime<Utc>,
+        strategy_id: &str,
+        wallet_address: &str,
+        notional: Decimal,
+    ) -> Result<(), VelocityBreach> {
+        for (scope, id) in [
+            (VelocityScope::Strategy, strategy_id),
+            (VelocityScope::Wallet, wallet_address),
+        ] {
+            let limits = self.limits_for(scope, id);
+            let windows = self.windows_mut(scope, id, now).await;
+
+            let orders = Decimal::from(windows.orders.count() + 1);
+            if orders > Decimal::from(limits.max_orders_per_minute) {
+                return Err(VelocityBreach {
+                    scope,
+                    id: id.to_string(),
+                    limit: "orders per minute".to_string(),
+                    current: orders,
+                    threshold: Decimal::from(limits.max_orders_per_minute),
+                });
+            }
+
+            let turnover = windows.notional.total + notional;
+            if turnover > limits.max_notional_per_hour {
+                return Err(VelocityBreach {
+                    scope,
+                    id: id.to_string(),
+                    limit: "notional per hour".to_string(),
+                    current: turnover,
+                    threshold: limits.max_notional_per_hour,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Records an accepted order in the strategy and wallet windows
+    pub async fn record(
+        &mut self,
+        now: DateTime<Utc>,
+        strategy_id: &str,
+        wallet_address: &str,
+        notional: Decimal,
+    ) {
+        for (scope, id) in [
+            (VelocityScope::Strategy, strategy_id),
+            (VelocityScope::Wallet, wallet_address),
+        ] {
+            let limits = self.limits_for(scope, id);
+            let windows = self.windows_mut(scope, id, now).await;
+            windows.orders.record(now, Decimal::ONE);
+            windows.notional.record(now, notional);
+
+            let snapshot = windows.clone();
+            record_gauges(scope, id, &snapshot, &limits);
+            self.persist(scope, id, &snapshot).await;
+        }
+    }
+
+    /// Orders in the current one-minute window
+    pub fn orders_in_window(&mut self, scope: VelocityScope, id: &str, now: DateTime<Utc>) -> usize {
+        self.windows
+            .get_mut(&(scope, id.to_string()))
+            .map(|windows| {
+                windows.prune(now);
+                windows.orders.count()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Notional turnover in the current one-hour window
+    pub fn notional_in_window(&mut self, scope: VelocityScope, id: &str, now: DateTime<Utc>) -> Decimal {
+        self.windows
+            .get_mut(&(scope, id.to_string()))
+            .map(|windows| {
+                windows.prune(now);
+                windows.notional.total
+            })
+            .unwrap_or(Decimal::ZERO)
+    }
+
+    /// Copies every tracked window, sorted by scope and id
+    pub fn snapshot(&self) -> Vec<VelocitySnapshot> {
+        let mut snapshots: Vec<VelocitySnapshot> = self
+            .windows
+            .iter()
+            .map(|((scope, id), windows)| VelocitySnapshot {
+                scope: *scope,
+                id: id.clone(),
+                windows: windows.clone(),
+            })
+            .collect();
+        snapshots.sort_by(|a, b| (a.scope.as_str(), &a.id).cmp(&(b.scope.as_str(), &b.id)));
+        snapshots
+    }
+
+    /// Replaces the tracked windows with snapshotted ones
+    pub fn restore(&mut self, snapshots: &[VelocitySnapshot]) {
+        self.windows = snapshots
+            .iter()
+            .map(|snapshot| ((snapshot.scope, snapshot.id.clone()), snapshot.windows.clone()))
+            .collect();
+    }
+
+    async fn windows_mut(
+        &mut self,
+        scope: VelocityScope,
+        id: &str,
+        now: DateTime<Utc>,
+    ) -> &mut VelocityWindows {
+        let key = (scope, id.to_string());
+        if !self.windows.contains_key(&key) {
+            let restored = self.restore(scope, id).await.unwrap_or_else(VelocityWindows::new);
+            self.windows.insert(key.clone(), restored);
+        }
+
+        let windows = self.windows.get_mut(&key).expect("windows inserted above");
+        windows.prune(now);
+        windows
+    }
+
+    async fn restore(&self, scope: VelocityScope, id: &str) -> Option<VelocityWindows> {
+        let client = self.redis_client.as_ref()?;
+        let mut conn = match client.get_async_connection().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!("Velocity window restore failed: {}", e);
+                return None;
+            }
+        };
+
+        let raw: Option<String> = conn.get(redis_key(scope, id)).await.ok()?;
+        let windows = serde_json::from_str(&raw?).ok()?;
+        debug!(scope = scope.as_str(), id, "Restored velocity windows from Redis");
+        Some(windows)
+    }
+
+    async fn persist(&self, scope: VelocityScope, id: &str, windows: &VelocityWindows) {
+        let Some(client) = &self.redis_client else {
+            return;
+        };
+        let Ok(payload) = serde_json::to_string(windows) else {
+            return;
+        };
+
+        let result: redis::RedisResult<()> = async {
+            let mut conn = client.get_async_connection().await?;
+            conn.set_ex(redis_key(scope, id), payload, NOTIONAL_WINDOW_SECS as usize)
+                .await
+        }
+        .await;
+        if let Err(e) = result {
+            warn!("Velocity window persistence failed: {}", e);
+        }
+    }
+}
+
+fn redis_key(scope: VelocityScope, id: &str) -> String {
+    format!("{}{}:{}", REDIS_KEY_PREFIX, scope.as_str(), id)
+}
+
+/// Publishes window levels and utilization so alerts can fire before limits are hit
+fn record_gauges(scope: VelocityScope, id: &str, windows: &VelocityWindows, limits: &VelocityLimits) {
+    let orders = windows.orders.count() as f64;
+    let notional = windows.notional.total.to_f64().unwrap_or(0.0);
+    let max_notional = limits.max_notional_per_hour.to_f64().unwrap_or(0.0);
+
+    let labels = [("scope", scope.as_str().to_string()), ("id", id.to_string())];
+    gauge!(format!("{}.orders_per_minute", METRICS_PREFIX), orders, &labels);
+    gauge!(format!("{}.notional_per_hour", METRICS_PREFIX), notional, &labels);
+    gauge!(
+        format!("{}.orders_utilization", METRICS_PREFIX),
+        orders / f64::from(limits.max_orders_per_minute.max(1)),
+        &labels
+    );
+    if max_notional > 0.0 {
+        gauge!(
+            format!("{}.notional_utilization", METRICS_PREFIX),
+            notional / max_notional,
+            &labels
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const STRATEGY: &str = "grid-1";
+    const WALLET: &str = "wallet123";
+
+    fn tracker() -> VelocityTracker {
+        VelocityTracker::new(VelocityLimits::default(), HashMap::new())
+    }
+
+    async fn submit(tracker: &mut VelocityTracker, now: DateTime<Utc>, notional: Decimal) -> Result<(), VelocityBreach> {
+        tracker.check(now, STRATEGY, WALLET, notional).await?;
+        tracker.record(now, STRATEGY, WALLET, notional).await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sixty_first_order_in_a_minute_rejected() {
+        let mut tracker = tracker();
+        let start = Utc::now();
+
+        for i in 0..60 {
+            let at = start + Duration::milliseconds(i * 900);
+            assert!(submit(&mut tracker, at, dec!(10)).await.is_ok(), "order {} rejected", i + 1);
+        }
+
+        let breach = submit(&mut tracker, start + Duration::seconds(54), dec!(10))
+            .await
+            .unwrap_err();
+        assert_eq!(breach.scope, VelocityScope::Strategy);
+        assert_eq!(breach.current, dec!(61));
+        assert_eq!(tracker.orders_in_window(VelocityScope::Wallet, WALLET, start + Duration::seconds(54)), 60);
+
+        // The first order leaves the window a minute after it was placed
+        assert!(submit(&mut tracker, start + Duration::seconds(60), dec!(10)).await.is_ok());
+        assert!(submit(&mut tracker, start + Duration::seconds(60), dec!(10)).await.is_err());
+        assert!(submit(&mut tracker, start + Duration::milliseconds(60_900), dec!(10)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_notional_turnover_per_hour() {
+        let mut tracker = tracker();
+        let start = Utc::now();
+
+        assert!(submit(&mut tracker, start, dec!(200000)).await.is_ok());
+        let breach = submit(&mut tracker, start + Duration::minutes(30), dec!(60000))
+            .await
+            .unwrap_err();
+        assert_eq!(breach.limit, "notional per hour");
+        assert_eq!(breach.current, dec!(260000));
+
+        assert!(submit(&mut tracker, start + Duration::minutes(61), dec!(60000)).await.is_ok());
+        assert_eq!(
+            tracker.notional_in_window(VelocityScope::Wallet, WALLET, start + Duration::minutes(61)),
+            dec!(60000)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_strategy_override_and_hot_reload() {
+        let mut tracker = tracker();
+        let start = Utc::now();
+        let strict = VelocityLimits {
+            max_orders_per_minute: 2,
+            max_notional_per_hour: dec!(1000),
+        };
+
+        tracker.update_limits(
+            VelocityLimits::default(),
+            HashMap::from([(STRATEGY.to_string(), strict)]),
+        );
+        assert!(submit(&mut tracker, start, dec!(10)).await.is_ok());
+        assert!(submit(&mut tracker, start, dec!(10)).await.is_ok());
+        assert!(submit(&mut tracker, start, dec!(10)).await.is_err());
+
+        // Reloading keeps the window but applies the new thresholds immediately
+        tracker.update_limits(VelocityLimits::default(), HashMap::new());
+        assert_eq!(tracker.orders_in_window(VelocityScope::Strategy, STRATEGY, start), 2);
+        assert!(submit(&mut tracker, start, dec!(10)).await.is_ok());
+    }
+}
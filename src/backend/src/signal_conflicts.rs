@@ -0,0 +1,495 @@
+//! Arbitration of opposing strategy signals. Two strategies trading the same pair in
+//! opposite directions moments apart pay the spread twice for no net position change, so
+//! opposing signals arriving within the conflict window are resolved by policy before they
+//! reach the order path: netted down to the residual, decided in favour of the
+//! higher-priority strategy, or executed as-is and flagged. Every conflict is recorded with
+//! its signals and resolution in the signal audit log.
+//!
+//! Version dependencies:
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::Mutex;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::signals::{AuditConsumer, Signal, SignalDirection};
+use crate::utils::time::to_chrono;
+
+// Signal conflict constants
+const METRICS_PREFIX: &str = "trading_bot.signal_conflicts";
+const DEFAULT_WINDOW: Duration = Duration::from_secs(1);
+const DEFAULT_RELEASE_INTERVAL: Duration = Duration::from_millis(50);
+/// Decimal places residual sizes are allocated to
+const SIZE_SCALE: u32 = 9;
+
+/// How opposing signals for a pair are resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Offset opposing signals internally and execute only the residual
+    Net,
+    /// Execute only the side of the highest-priority strategy
+    PreferPriority,
+    /// Execute every signal, recording the conflict
+    Flag,
+}
+
+impl ConflictPolicy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ConflictPolicy::Net => "net",
+            ConflictPolicy::PreferPriority => "prefer_priority",
+            ConflictPolicy::Flag => "flag",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictConfig {
+    pub policy: ConflictPolicy,
+    /// Opposing signals for a pair within this window conflict; under `Net` and
+    /// `PreferPriority` every signal is held for the window before it executes
+    pub window: Duration,
+    /// Strategy ranks for `PreferPriority`, higher wins; unlisted strategies rank 0
+    pub strategy_priorities: HashMap<String, u32>,
+    /// How often held signals whose window has closed are released
+    pub release_interval: Duration,
+}
+
+impl Default for ConflictConfig {
+    fn default() -> Self {
+        Self {
+            policy: ConflictPolicy::Flag,
+            window: DEFAULT_WINDOW,
+            strategy_priorities: HashMap::new(),
+            release_interval: DEFAULT_RELEASE_INTERVAL,
+        }
+    }
+}
+
+/// A signal executed after arbitration, at the size it executes with
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExecutedSignal {
+    pub signal_id: Uuid,
+    pub strategy_id: String,
+    pub direction: SignalDirection,
+    pub size: Decimal,
+}
+
+/// Opposing signals for a pair and how they were resolved
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SignalConflict {
+    pub id: Uuid,
+    pub pair: String,
+    pub policy: ConflictPolicy,
+    /// Every signal in the window, both directions, in arrival order
+    pub signals: Vec<Signal>,
+    pub executed: Vec<ExecutedSignal>,
+    /// Signal size that never reached a venue
+    pub offset_size: Decimal,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Signals for a pair held until the window opened by the first of them closes
+#[derive(Debug)]
+struct PendingWindow {
+    closes_at: DateTime<Utc>,
+    signals: Vec<Signal>,
+}
+
+/// Signals executed for a pair with their arrival time, oldest first
+type ExecutedWindow = VecDeque<(DateTime<Utc>, Signal)>;
+
+/// Detects opposing signals per pair and resolves them by the configured policy
+#[derive(Debug)]
+pub struct ConflictArbiter {
+    config: ConflictConfig,
+    /// Held signals per pair, under `Net` and `PreferPriority`
+    pending: Mutex<HashMap<String, PendingWindow>>,
+    /// Signals executed per pair, under `Flag`
+    recent: Mutex<HashMap<String, ExecutedWindow>>,
+    audit: Arc<AuditConsumer>,
+}
+
+impl ConflictArbiter {
+    pub fn new(config: ConflictConfig, audit: Arc<AuditConsumer>) -> Self {
+        Self {
+            config,
+            pending: Mutex::new(HashMap::new()),
+            recent: Mutex::new(HashMap::new()),
+            audit,
+        }
+    }
+
+    pub fn config(&self) -> &ConflictConfig {
+        &self.config
+    }
+
+    /// Accepts a signal, returning the signals to execute now. Under `Flag` that is the
+    /// signal itself; otherwise it is held until its pair's window closes.
+    pub fn offer(&self, signal: Signal, now: DateTime<Utc>) -> Vec<Signal> {
+        let window = to_chrono(self.config.window);
+        if self.config.policy == ConflictPolicy::Flag {
+            let opposing: Vec<Signal> = {
+                let mut recent = self.recent.lock();
+                let executed = recent.entry(signal.pair.clone()).or_default();
+                while executed.front().is_some_and(|(at, _)| now - *at >= window) {
+                    executed.pop_front();
+                }
+                let opposing = executed
+                    .iter()
+                    .filter(|(_, earlier)| earlier.direction != signal.direction)
+                    .map(|(_, earlier)| earlier.clone())
+                    .collect();
+                executed.push_back((now, signal.clone()));
+                opposing
+            };
+            if !opposing.is_empty() {
+                let executed = vec![executed_at(&signal, signal.size)];
+                let mut signals = opposing;
+                signals.push(signal.clone());
+                self.record(&signal.pair, signals, executed, Decimal::ZERO, now);
+            }
+            return vec![signal];
+        }
+
+        self.pending
+            .lock()
+            .entry(signal.pair.clone())
+            .or_insert_with(|| PendingWindow {
+                closes_at: now + window,
+                signals: Vec::new(),
+            })
+            .signals
+            .push(signal);
+        Vec::new()
+    }
+
+    /// Resolves every pair whose window has closed, returning the signals to execute
+    pub fn release_due(&self, now: DateTime<Utc>) -> Vec<Signal> {
+        let due: Vec<(String, PendingWindow)> = {
+            let mut pending = self.pending.lock();
+            let pairs: Vec<String> = pending
+                .iter()
+                .filter(|(_, window)| window.closes_at <= now)
+                .map(|(pair, _)| pair.clone())
+                .collect();
+            pairs
+                .into_iter()
+                .filter_map(|pair| pending.remove(&pair).map(|window| (pair, window)))
+                .collect()
+        };
+
+        due.into_iter()
+            .flat_map(|(pair, window)| self.resolve(&pair, window.signals, now))
+            .collect()
+    }
+
+    /// Number of signals currently held
+    pub fn pending_len(&self) -> usize {
+        self.pending.lock().values().map(|window| window.signals.len()).sum()
+    }
+
+    fn resolve(&self, pair: &str, signals: Vec<Signal>, now: DateTime<Utc>) -> Vec<Signal> {
+        // Held signals may lapse while waiting out the window
+        let (signals, expired): (Vec<Signal>, Vec<Signal>) =
+            signals.into_iter().partition(|signal| !signal.is_expired(now));
+        if !expired.is_empty() {
+            counter!(format!("{}.expired", METRICS_PREFIX), expired.len() as u64);
+        }
+
+        let has = |direction| signals.iter().any(|signal| signal.direction == direction);
+        if !(has(SignalDirection::Long) && has(SignalDirection::Short)) {
+            return signals;
+        }
+
+        let total: Decimal = signals.iter().map(|signal| signal.size).sum();
+        let executed = match self.config.policy {
+            ConflictPolicy::Net => net(&signals),
+            ConflictPolicy::PreferPriority => self.prefer_priority(&signals),
+            ConflictPolicy::Flag => signals.clone(),
+        };
+        let executed_size: Decimal = executed.iter().map(|signal| signal.size).sum();
+        self.record(
+            pair,
+            signals,
+            executed.iter().map(|signal| executed_at(signal, signal.size)).collect(),
+            total - executed_size,
+            now,
+        );
+        executed
+    }
+
+    /// Keeps the side holding the highest-ranked strategy; on a tie, the side that signalled first
+    fn prefer_priority(&self, signals: &[Signal]) -> Vec<Signal> {
+        let rank = |direction: SignalDirection| {
+            signals
+                .iter()
+                .filter(|signal| signal.direction == direction)
+                .map(|signal| self.config.strategy_priorities.get(&signal.strategy_id).copied().unwrap_or(0))
+                .max()
+                .unwrap_or(0)
+        };
+        let (long, short) = (rank(SignalDirection::Long), rank(SignalDirection::Short));
+        let winner = if long != short {
+            if long > short {
+                SignalDirection::Long
+            } else {
+                SignalDirection::Short
+            }
+        } else {
+            signals[0].direction
+        };
+        signals.iter().filter(|signal| signal.direction == winner).cloned().collect()
+    }
+
+    fn record(
+        &self,
+        pair: &str,
+        signals: Vec<Signal>,
+        executed: Vec<ExecutedSignal>,
+        offset_size: Decimal,
+        now: DateTime<Utc>,
+    ) {
+        let conflict = SignalConflict {
+            id: Uuid::new_v4(),
+            pair: pair.to_string(),
+            policy: self.config.policy,
+            signals,
+            executed,
+            offset_size,
+            detected_at: now,
+        };
+        counter!(
+            format!("{}.detected", METRICS_PREFIX),
+            1,
+            "pair" => pair.to_string(),
+            "policy" => self.config.policy.as_str()
+        );
+        if self.config.policy == ConflictPolicy::Flag {
+            warn!(pair, signals = conflict.signals.len(), "Opposing strategy signals both executed");
+        } else {
+            info!(
+                pair,
+                policy = self.config.policy.as_str(),
+                signals = conflict.signals.len(),
+                executed = conflict.executed.len(),
+                offset_size = %offset_size,
+                "Resolved opposing strategy signals"
+            );
+        }
+        self.audit.record_conflict(conflict);
+    }
+}
+
+/// Offsets the smaller side against the larger and splits the residual across the larger
+/// side's signals in proportion to their size. Each residual order keeps its own signal's
+/// bracket; the offset side opens no position, so its brackets are dropped with it.
+fn net(signals: &[Signal]) -> Vec<Signal> {
+    let side_total = |direction: SignalDirection| -> Decimal {
+        signals
+            .iter()
+            .filter(|signal| signal.direction == direction)
+            .map(|signal| signal.size)
+            .sum()
+    };
+    let (long, short) = (side_total(SignalDirection::Long), side_total(SignalDirection::Short));
+    let (dominant, dominant_total, residual) = if long > short {
+        (SignalDirection::Long, long, long - short)
+    } else {
+        (SignalDirection::Short, short, short - long)
+    };
+    if residual.is_zero() {
+        return Vec::new();
+    }
+
+    let dominant: Vec<&Signal> = signals.iter().filter(|signal| signal.direction == dominant).collect();
+    let mut allocated = Decimal::ZERO;
+    let mut residual_orders = Vec::with_capacity(dominant.len());
+    for (i, signal) in dominant.iter().enumerate() {
+        // The last signal takes whatever rounding left over so the residual is exact
+        let size = if i + 1 == dominant.len() {
+            residual - allocated
+        } else {
+            (signal.size * residual / dominant_total).round_dp(SIZE_SCALE)
+        };
+        allocated += size;
+        if size > Decimal::ZERO {
+            let mut order = (*signal).clone();
+            order.size = size;
+            residual_orders.push(order);
+        }
+    }
+    residual_orders
+}
+
+fn executed_at(signal: &Signal, size: Decimal) -> ExecutedSignal {
+    ExecutedSignal {
+        signal_id: signal.id,
+        strategy_id: signal.strategy_id.clone(),
+        direction: signal.direction,
+        size,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_engine::position_events::Bracket;
+    use crate::risk_manager::exposure::TradeSide;
+    use rust_decimal_macros::dec;
+
+    const PAIR: &str = "SOL/USDC";
+    const TTL: Duration = Duration::from_secs(5);
+
+    fn arbiter(policy: ConflictPolicy) -> (ConflictArbiter, Arc<AuditConsumer>) {
+        let audit = Arc::new(AuditConsumer::default());
+        let config = ConflictConfig {
+            policy,
+            strategy_priorities: HashMap::from([("ml-1".to_string(), 10), ("grid-1".to_string(), 5)]),
+            ..ConflictConfig::default()
+        };
+        (ConflictArbiter::new(config, audit.clone()), audit)
+    }
+
+    fn signal(strategy_id: &str, direction: SignalDirection, size: Decimal, now: DateTime<Utc>) -> Signal {
+        Signal::new(strategy_id, PAIR, "jupiter", direction, dec!(23.50), size, TTL, now)
+    }
+
+    /// Side, size and strategy of each order the released signals would place
+    fn orders(signals: &[Signal]) -> Vec<(TradeSide, Decimal, String)> {
+        signals
+            .iter()
+            .map(|signal| {
+                let params = signal.order_params();
+                (params.side, params.size, params.strategy_id)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_net_executes_only_the_residual() {
+        let (arbiter, audit) = arbiter(ConflictPolicy::Net);
+        let now = Utc::now();
+        let bracket = Bracket { stop_loss: dec!(22), take_profit: dec!(26) };
+
+        // Grid buys 2 SOL while ML sells 1.5 SOL within the same second
+        let grid = signal("grid-1", SignalDirection::Long, dec!(2), now).with_bracket(bracket.clone());
+        let ml = signal("ml-1", SignalDirection::Short, dec!(1.5), now)
+            .with_bracket(Bracket { stop_loss: dec!(25), take_profit: dec!(21) });
+        assert!(arbiter.offer(grid.clone(), now).is_empty());
+        assert!(arbiter.offer(ml.clone(), now + chrono::Duration::milliseconds(300)).is_empty());
+        assert_eq!(arbiter.pending_len(), 2);
+
+        // Nothing is released before the window closes
+        assert!(arbiter.release_due(now + chrono::Duration::milliseconds(900)).is_empty());
+        let released = arbiter.release_due(now + chrono::Duration::seconds(1));
+        assert_eq!(orders(&released), vec![(TradeSide::Buy, dec!(0.5), "grid-1".to_string())]);
+        // The residual long keeps the grid's bracket; the offset short's bracket is dropped
+        assert_eq!(released[0].bracket, Some(bracket));
+        assert_eq!(arbiter.pending_len(), 0);
+
+        let conflicts = audit.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].signals.iter().map(|s| s.id).collect::<Vec<_>>(), vec![grid.id, ml.id]);
+        assert_eq!(conflicts[0].executed.len(), 1);
+        assert_eq!(conflicts[0].executed[0].size, dec!(0.5));
+        assert_eq!(conflicts[0].offset_size, dec!(3));
+    }
+
+    #[test]
+    fn test_net_splits_residual_across_the_larger_side() {
+        let (arbiter, audit) = arbiter(ConflictPolicy::Net);
+        let now = Utc::now();
+
+        // 3 long against 1 short leaves 2 long, split 1:2 between the two longs
+        arbiter.offer(signal("grid-1", SignalDirection::Long, dec!(1), now), now);
+        arbiter.offer(signal("ml-1", SignalDirection::Short, dec!(1), now), now);
+        arbiter.offer(signal("momentum-1", SignalDirection::Long, dec!(2), now), now);
+        let released = arbiter.release_due(now + chrono::Duration::seconds(1));
+        let mut placed = orders(&released);
+        placed.sort_by(|a, b| a.2.cmp(&b.2));
+        assert_eq!(
+            placed,
+            vec![
+                (TradeSide::Buy, dec!(0.666666667), "grid-1".to_string()),
+                (TradeSide::Buy, dec!(1.333333333), "momentum-1".to_string()),
+            ]
+        );
+        assert_eq!(released.iter().map(|s| s.size).sum::<Decimal>(), dec!(2));
+
+        // Equal and opposite signals cancel out entirely
+        arbiter.offer(signal("grid-1", SignalDirection::Long, dec!(1.5), now), now);
+        arbiter.offer(signal("ml-1", SignalDirection::Short, dec!(1.5), now), now);
+        assert!(arbiter.release_due(now + chrono::Duration::seconds(1)).is_empty());
+        assert_eq!(audit.conflicts().len(), 2);
+        assert!(audit.conflicts()[1].executed.is_empty());
+    }
+
+    #[test]
+    fn test_prefer_priority_executes_the_higher_ranked_side() {
+        let (arbiter, audit) = arbiter(ConflictPolicy::PreferPriority);
+        let now = Utc::now();
+
+        // ML outranks grid even though grid signalled first
+        arbiter.offer(signal("grid-1", SignalDirection::Long, dec!(2), now), now);
+        arbiter.offer(signal("ml-1", SignalDirection::Short, dec!(1.5), now), now);
+        let released = arbiter.release_due(now + chrono::Duration::seconds(1));
+        assert_eq!(orders(&released), vec![(TradeSide::Sell, dec!(1.5), "ml-1".to_string())]);
+        assert_eq!(audit.conflicts()[0].offset_size, dec!(2));
+
+        // Equal ranks fall back to whichever side signalled first
+        arbiter.offer(signal("unranked-1", SignalDirection::Short, dec!(1), now), now);
+        arbiter.offer(signal("unranked-2", SignalDirection::Long, dec!(3), now), now);
+        let released = arbiter.release_due(now + chrono::Duration::seconds(1));
+        assert_eq!(orders(&released), vec![(TradeSide::Sell, dec!(1), "unranked-1".to_string())]);
+    }
+
+    #[test]
+    fn test_flag_executes_both_and_records_the_conflict() {
+        let (arbiter, audit) = arbiter(ConflictPolicy::Flag);
+        let now = Utc::now();
+
+        let grid = signal("grid-1", SignalDirection::Long, dec!(2), now);
+        let ml = signal("ml-1", SignalDirection::Short, dec!(1.5), now);
+        assert_eq!(orders(&arbiter.offer(grid.clone(), now)), vec![(TradeSide::Buy, dec!(2), "grid-1".to_string())]);
+        assert!(audit.conflicts().is_empty());
+
+        let released = arbiter.offer(ml.clone(), now + chrono::Duration::milliseconds(400));
+        assert_eq!(orders(&released), vec![(TradeSide::Sell, dec!(1.5), "ml-1".to_string())]);
+        let conflicts = audit.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].signals.iter().map(|s| s.id).collect::<Vec<_>>(), vec![grid.id, ml.id]);
+        assert_eq!(conflicts[0].offset_size, Decimal::ZERO);
+
+        // Outside the window the opposite signal is no conflict
+        arbiter.offer(signal("grid-1", SignalDirection::Long, dec!(2), now), now + chrono::Duration::seconds(3));
+        assert_eq!(audit.conflicts().len(), 1);
+    }
+
+    #[test]
+    fn test_same_direction_and_other_pairs_do_not_conflict() {
+        let (arbiter, audit) = arbiter(ConflictPolicy::Net);
+        let now = Utc::now();
+
+        arbiter.offer(signal("grid-1", SignalDirection::Long, dec!(1), now), now);
+        arbiter.offer(signal("ml-1", SignalDirection::Long, dec!(2), now), now);
+        let mut other = signal("ml-1", SignalDirection::Short, dec!(2), now);
+        other.pair = "BONK/SOL".to_string();
+        arbiter.offer(other, now);
+
+        let released = arbiter.release_due(now + chrono::Duration::seconds(1));
+        assert_eq!(released.len(), 3);
+        assert!(audit.conflicts().is_empty());
+    }
+}
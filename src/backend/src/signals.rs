@@ -0,0 +1,698 @@
+//! Strategy signal bus. Strategies publish signals onto a broadcast channel instead of
+//! executing trades directly, so execution, notifications, auditing and the dashboard can
+//! each react to the same signal. Expired signals are never delivered and duplicates within
+//! the suppression window are coalesced into the first. Execution can hold signals to
+//! resolve conflicts and to merge small same-side orders before they reach the gateway.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - async-trait = "0.1"
+//! - parking_lot = "0.12"
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::Mutex;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::api::{OrderGateway, WebhookDispatcher};
+use crate::execution_engine::adapters::{OrderRequest, DEFAULT_MAX_SLIPPAGE};
+use crate::execution_engine::multi_leg::{AtomicityPolicy, TradeGroup, TradeLeg};
+use crate::execution_engine::queue::PriorityClass;
+use crate::execution_engine::passive::ExecutionStyle;
+use crate::execution_engine::position_events::Bracket;
+use crate::execution_engine::StrategyParams;
+use crate::models::market::TickStamp;
+use crate::models::order::OrderType;
+use crate::models::webhook::{WebhookEvent, WebhookEventType};
+use crate::order_batching::{BatchingConfig, OrderBatch, OrderBatcher};
+use crate::regime::RegimeChange;
+use crate::risk_manager::exposure::TradeSide;
+use crate::signal_conflicts::{ConflictArbiter, ConflictConfig, SignalConflict};
+
+// Signal bus constants
+const METRICS_PREFIX: &str = "trading_bot.signals";
+const DEFAULT_CAPACITY: usize = 1024;
+const DEFAULT_TTL: Duration = Duration::from_secs(5);
+const DEFAULT_SUPPRESSION_WINDOW: Duration = Duration::from_secs(2);
+const DEFAULT_ALERT_STRENGTH: Decimal = Decimal::new(8, 1);
+const AUDIT_LOG_CAPACITY: usize = 1000;
+const REGIME_CHANNEL_CAPACITY: usize = 256;
+
+/// Direction a signal asks to trade in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalDirection {
+    Long,
+    Short,
+}
+
+impl SignalDirection {
+    pub fn side(&self) -> TradeSide {
+        match self {
+            SignalDirection::Long => TradeSide::Buy,
+            SignalDirection::Short => TradeSide::Sell,
+        }
+    }
+}
+
+/// Ties signals that must execute together, such as the legs of an arbitrage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SignalGroup {
+    pub correlation_id: Uuid,
+    pub policy: AtomicityPolicy,
+}
+
+impl SignalGroup {
+    pub fn new(policy: AtomicityPolicy) -> Self {
+        Self {
+            correlation_id: Uuid::new_v4(),
+            policy,
+        }
+    }
+}
+
+/// Trading intent published by a strategy
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Signal {
+    pub id: Uuid,
+    pub strategy_id: String,
+    pub pair: String,
+    pub direction: SignalDirection,
+    /// Conviction from 0 to 1
+    pub strength: Decimal,
+    #[serde(skip)]
+    pub ttl: Duration,
+    /// Venue, reference price and size the strategy would trade at
+    pub exchange: String,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Exits for the position the signal opens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bracket: Option<Bracket>,
+    /// Tick the signal was derived from, for tick-to-trade latency accounting
+    #[serde(skip)]
+    pub tick: Option<TickStamp>,
+    /// Signals this one was merged from by the order batcher, for attribution
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub merged_from: Vec<Uuid>,
+    /// Order type the signal executes with; limit by default
+    pub order_type: OrderType,
+    /// Whether the order rests post-only or crosses the spread; crosses by default
+    pub execution_style: ExecutionStyle,
+    /// Group the signal executes with as one leg; grouped signals bypass the bus
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<SignalGroup>,
+}
+
+impl Signal {
+    pub fn new(
+        strategy_id: &str,
+        pair: &str,
+        exchange: &str,
+        direction: SignalDirection,
+        price: Decimal,
+        size: Decimal,
+        ttl: Duration,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let expires_at = now + chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::MAX);
+        Self {
+            id: Uuid::new_v4(),
+            strategy_id: strategy_id.to_string(),
+            pair: pair.to_string(),
+            direction,
+            strength: Decimal::ONE,
+            ttl,
+            exchange: exchange.to_string(),
+            price,
+            size,
+            created_at: now,
+            expires_at,
+            bracket: None,
+            tick: None,
+            merged_from: Vec::new(),
+            order_type: OrderType::Limit,
+            execution_style: ExecutionStyle::Aggressive,
+            group: None,
+        }
+    }
+
+    pub fn with_strength(mut self, strength: Decimal) -> Self {
+        self.strength = strength.max(Decimal::ZERO).min(Decimal::ONE);
+        self
+    }
+
+    pub fn with_bracket(mut self, bracket: Bracket) -> Self {
+        self.bracket = Some(bracket);
+        self
+    }
+
+    pub fn with_tick(mut self, tick: TickStamp) -> Self {
+        self.tick = Some(tick);
+        self
+    }
+
+    pub fn with_order_type(mut self, order_type: OrderType) -> Self {
+        self.order_type = order_type;
+        self
+    }
+
+    pub fn with_execution_style(mut self, execution_style: ExecutionStyle) -> Self {
+        self.execution_style = execution_style;
+        self
+    }
+
+    pub fn with_group(mut self, group: SignalGroup) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Order at the signal's reference price, with the signal's order type and style
+    pub fn order_params(&self) -> StrategyParams {
+        StrategyParams {
+            strategy_id: self.strategy_id.clone(),
+            priority: PriorityClass::Normal,
+            trading_pair: self.pair.clone(),
+            exchange: self.exchange.clone(),
+            side: self.direction.side(),
+            order_type: self.order_type.clone(),
+            size: self.size,
+            price: self.price,
+            execution_style: self.execution_style,
+            max_slippage: DEFAULT_MAX_SLIPPAGE,
+            tick: self.tick,
+            size_multiplier: None,
+        }
+    }
+
+    fn key(&self) -> SignalKey {
+        (self.strategy_id.clone(), self.pair.clone(), self.direction, self.price)
+    }
+}
+
+/// Strategy, pair, direction and price; signals sharing all four are identical
+type SignalKey = (String, String, SignalDirection, Decimal);
+
+/// Collects grouped signals into trade groups, one per correlation id in order of first
+/// appearance, each leg at the signal's venue, reference price and size. Ungrouped signals
+/// are ignored.
+pub fn trade_groups(signals: Vec<Signal>) -> Vec<TradeGroup> {
+    let mut groups: Vec<TradeGroup> = Vec::new();
+    for signal in signals {
+        let Some(group) = signal.group else {
+            continue;
+        };
+        let leg = TradeLeg {
+            exchange: signal.exchange,
+            order: OrderRequest {
+                trading_pair: signal.pair,
+                side: signal.direction.side(),
+                size: signal.size,
+                price: signal.price,
+                max_slippage: DEFAULT_MAX_SLIPPAGE,
+            },
+        };
+        match groups.iter_mut().find(|trade_group| trade_group.correlation_id == group.correlation_id) {
+            Some(trade_group) => trade_group.legs.push(leg),
+            None => groups.push(TradeGroup {
+                correlation_id: group.correlation_id,
+                strategy_id: signal.strategy_id,
+                policy: group.policy,
+                legs: vec![leg],
+            }),
+        }
+    }
+    groups
+}
+
+/// Signal bus settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalBusConfig {
+    /// Executes strategy trades directly, bypassing the bus, while consumers migrate
+    pub direct_execution: bool,
+    pub capacity: usize,
+    pub ttl: Duration,
+    /// Identical signals from a strategy within this window are coalesced
+    pub suppression_window: Duration,
+    /// Strength at or above which a signal raises a notification
+    pub alert_strength: Decimal,
+    /// Resolution of opposing signals for the same pair
+    pub conflicts: ConflictConfig,
+    /// Merging of small same-side orders; signals execute one order each when unset
+    pub batching: Option<BatchingConfig>,
+}
+
+impl Default for SignalBusConfig {
+    fn default() -> Self {
+        Self {
+            direct_execution: false,
+            capacity: DEFAULT_CAPACITY,
+            ttl: DEFAULT_TTL,
+            suppression_window: DEFAULT_SUPPRESSION_WINDOW,
+            alert_strength: DEFAULT_ALERT_STRENGTH,
+            conflicts: ConflictConfig::default(),
+            batching: None,
+        }
+    }
+}
+
+/// Result of publishing a signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublishOutcome {
+    Published { receivers: usize },
+    /// Coalesced into an identical signal published within the suppression window
+    Suppressed,
+    Expired,
+}
+
+/// Reacts to signals delivered by the bus
+#[async_trait]
+pub trait SignalConsumer: Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+    async fn handle(&self, signal: &Signal);
+}
+
+/// Broadcast channel fanning strategy signals out to every registered consumer
+#[derive(Debug)]
+pub struct SignalBus {
+    config: SignalBusConfig,
+    sender: broadcast::Sender<Signal>,
+    /// Last published time per strategy, pair and direction
+    recent: Mutex<HashMap<SignalKey, DateTime<Utc>>>,
+    /// Market regime changes, for consumers that adapt to conditions rather than trade them
+    regimes: broadcast::Sender<RegimeChange>,
+}
+
+impl SignalBus {
+    pub fn new(config: SignalBusConfig) -> Self {
+        let (sender, _) = broadcast::channel(config.capacity.max(1));
+        let (regimes, _) = broadcast::channel(REGIME_CHANNEL_CAPACITY);
+        Self {
+            config,
+            sender,
+            recent: Mutex::new(HashMap::new()),
+            regimes,
+        }
+    }
+
+    pub fn config(&self) -> &SignalBusConfig {
+        &self.config
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Signal> {
+        self.sender.subscribe()
+    }
+
+    /// Publishes a signal unless it has expired or duplicates one inside the suppression window
+    pub fn publish(&self, signal: Signal, now: DateTime<Utc>) -> PublishOutcome {
+        if signal.is_expired(now) {
+            counter!(format!("{}.expired", METRICS_PREFIX), 1);
+            return PublishOutcome::Expired;
+        }
+
+        let window = chrono::Duration::from_std(self.config.suppression_window).unwrap_or(chrono::Duration::MAX);
+        {
+            let mut recent = self.recent.lock();
+            recent.retain(|_, published_at| now - *published_at < window);
+            if recent.contains_key(&signal.key()) {
+                debug!(strategy_id = %signal.strategy_id, pair = %signal.pair, "Suppressed duplicate signal");
+                counter!(format!("{}.suppressed", METRICS_PREFIX), 1);
+                return PublishOutcome::Suppressed;
+            }
+            recent.insert(signal.key(), now);
+        }
+
+        // No receivers is not an error; the signal simply has no audience yet
+        let receivers = self.sender.send(signal).unwrap_or(0);
+        counter!(format!("{}.published", METRICS_PREFIX), 1);
+        PublishOutcome::Published { receivers }
+    }
+
+    /// Publishes a market regime change; regime changes never expire or coalesce
+    pub fn publish_regime(&self, change: RegimeChange) -> usize {
+        counter!(format!("{}.regime_changes", METRICS_PREFIX), 1);
+        self.regimes.send(change).unwrap_or(0)
+    }
+
+    pub fn subscribe_regimes(&self) -> broadcast::Receiver<RegimeChange> {
+        self.regimes.subscribe()
+    }
+
+    /// Delivers every unexpired signal published from now on to the consumer
+    pub fn register(&self, consumer: Arc<dyn SignalConsumer>) -> tokio::task::JoinHandle<()> {
+        let mut signals = self.subscribe();
+        info!(consumer = consumer.name(), "Registered signal consumer");
+        tokio::spawn(async move {
+            loop {
+                match signals.recv().await {
+                    Ok(signal) => {
+                        // A slow consumer may reach a signal only after it has lapsed
+                        if signal.is_expired(Utc::now()) {
+                            counter!(format!("{}.{}.expired", METRICS_PREFIX, consumer.name()), 1);
+                            continue;
+                        }
+                        consumer.handle(&signal).await;
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(consumer = consumer.name(), skipped, "Signal consumer lagged");
+                        counter!(format!("{}.{}.lagged", METRICS_PREFIX, consumer.name()), skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+/// Converts signals into orders through the risk-checked order path
+pub struct ExecutionConsumer {
+    gateway: Arc<dyn OrderGateway>,
+    /// Resolves opposing signals before they reach the gateway
+    arbiter: Option<Arc<ConflictArbiter>>,
+    /// Merges small same-side orders after arbitration
+    batcher: Option<Arc<OrderBatcher>>,
+}
+
+impl ExecutionConsumer {
+    pub fn new(gateway: Arc<dyn OrderGateway>) -> Self {
+        Self {
+            gateway,
+            arbiter: None,
+            batcher: None,
+        }
+    }
+
+    pub fn with_arbiter(mut self, arbiter: Arc<ConflictArbiter>) -> Self {
+        self.arbiter = Some(arbiter);
+        self
+    }
+
+    pub fn with_batcher(mut self, batcher: Arc<OrderBatcher>) -> Self {
+        self.batcher = Some(batcher);
+        self
+    }
+
+    /// Submits signals the arbiter or batcher held once their window closes
+    pub fn spawn_release(self: Arc<Self>) -> Option<tokio::task::JoinHandle<()>> {
+        let period = [
+            self.arbiter.as_ref().map(|arbiter| arbiter.config().release_interval),
+            self.batcher.as_ref().map(|batcher| batcher.config().release_interval),
+        ]
+        .into_iter()
+        .flatten()
+        .min()?;
+        Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                let now = Utc::now();
+                if let Some(arbiter) = &self.arbiter {
+                    self.dispatch(arbiter.release_due(now), now).await;
+                }
+                if let Some(batcher) = &self.batcher {
+                    for order in batcher.release_due(now) {
+                        self.submit(&order).await;
+                    }
+                }
+            }
+        }))
+    }
+
+    /// Submits arbitrated signals, through the batcher when one is set
+    async fn dispatch(&self, signals: Vec<Signal>, now: DateTime<Utc>) {
+        let orders: Vec<Signal> = match &self.batcher {
+            Some(batcher) => signals.into_iter().flat_map(|signal| batcher.offer(signal, now)).collect(),
+            None => signals,
+        };
+        for order in orders {
+            self.submit(&order).await;
+        }
+    }
+
+    async fn submit(&self, signal: &Signal) {
+        if let Err(e) = self.gateway.submit_order(signal.order_params()).await {
+            warn!(signal_id = %signal.id, strategy_id = %signal.strategy_id, "Signal order failed: {}", e);
+        }
+    }
+}
+
+#[async_trait]
+impl SignalConsumer for ExecutionConsumer {
+    fn name(&self) -> &'static str {
+        "execution"
+    }
+
+    async fn handle(&self, signal: &Signal) {
+        let now = Utc::now();
+        let signals = match &self.arbiter {
+            Some(arbiter) => arbiter.offer(signal.clone(), now),
+            None => vec![signal.clone()],
+        };
+        self.dispatch(signals, now).await;
+    }
+}
+
+/// Raises a webhook notification for high-strength signals
+pub struct NotificationConsumer {
+    webhooks: Arc<WebhookDispatcher>,
+    alert_strength: Decimal,
+}
+
+impl NotificationConsumer {
+    pub fn new(webhooks: Arc<WebhookDispatcher>, alert_strength: Decimal) -> Self {
+        Self { webhooks, alert_strength }
+    }
+}
+
+#[async_trait]
+impl SignalConsumer for NotificationConsumer {
+    fn name(&self) -> &'static str {
+        "notification"
+    }
+
+    async fn handle(&self, signal: &Signal) {
+        if signal.strength < self.alert_strength {
+            return;
+        }
+        match WebhookEvent::new(WebhookEventType::SignalStrong, signal) {
+            Ok(event) => {
+                self.webhooks.dispatch(event);
+            }
+            Err(e) => warn!("Failed to build webhook event: {}", e),
+        }
+    }
+}
+
+/// Keeps a bounded log of recent signals, the conflicts between them and merged order batches
+#[derive(Debug, Default)]
+pub struct AuditConsumer {
+    entries: Mutex<VecDeque<Signal>>,
+    conflicts: Mutex<VecDeque<SignalConflict>>,
+    batches: Mutex<VecDeque<OrderBatch>>,
+}
+
+impl AuditConsumer {
+    /// Most recent signals, oldest first
+    pub fn recent(&self) -> Vec<Signal> {
+        self.entries.lock().iter().cloned().collect()
+    }
+
+    pub fn record_conflict(&self, conflict: SignalConflict) {
+        let mut conflicts = self.conflicts.lock();
+        if conflicts.len() == AUDIT_LOG_CAPACITY {
+            conflicts.pop_front();
+        }
+        conflicts.push_back(conflict);
+    }
+
+    /// Most recent signal conflicts, oldest first
+    pub fn conflicts(&self) -> Vec<SignalConflict> {
+        self.conflicts.lock().iter().cloned().collect()
+    }
+
+    pub fn record_batch(&self, batch: OrderBatch) {
+        let mut batches = self.batches.lock();
+        if batches.len() == AUDIT_LOG_CAPACITY {
+            batches.pop_front();
+        }
+        batches.push_back(batch);
+    }
+
+    /// Most recent merged order batches, oldest first
+    pub fn batches(&self) -> Vec<OrderBatch> {
+        self.batches.lock().iter().cloned().collect()
+    }
+}
+
+#[async_trait]
+impl SignalConsumer for AuditConsumer {
+    fn name(&self) -> &'static str {
+        "audit"
+    }
+
+    async fn handle(&self, signal: &Signal) {
+        info!(
+            signal_id = %signal.id,
+            strategy_id = %signal.strategy_id,
+            pair = %signal.pair,
+            direction = ?signal.direction,
+            strength = %signal.strength,
+            "Strategy signal"
+        );
+        let mut entries = self.entries.lock();
+        if entries.len() == AUDIT_LOG_CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(signal.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use tokio::sync::mpsc;
+
+    /// Forwards handled signal ids, optionally stalling on each
+    struct RecordingConsumer {
+        handled: mpsc::UnboundedSender<Uuid>,
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl SignalConsumer for RecordingConsumer {
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+
+        async fn handle(&self, signal: &Signal) {
+            tokio::time::sleep(self.delay).await;
+            let _ = self.handled.send(signal.id);
+        }
+    }
+
+    fn recording(delay: Duration) -> (Arc<RecordingConsumer>, mpsc::UnboundedReceiver<Uuid>) {
+        let (handled, received) = mpsc::unbounded_channel();
+        (Arc::new(RecordingConsumer { handled, delay }), received)
+    }
+
+    fn signal(direction: SignalDirection, ttl: Duration, now: DateTime<Utc>) -> Signal {
+        Signal::new("grid-1", "SOL/USDC", "jupiter", direction, dec!(23.50), dec!(1.5), ttl, now)
+    }
+
+    async fn next(received: &mut mpsc::UnboundedReceiver<Uuid>) -> Option<Uuid> {
+        tokio::time::timeout(Duration::from_millis(500), received.recv()).await.ok().flatten()
+    }
+
+    #[tokio::test]
+    async fn test_signal_reaches_every_consumer() {
+        let bus = SignalBus::new(SignalBusConfig::default());
+        let mut receivers = Vec::new();
+        for _ in 0..3 {
+            let (consumer, received) = recording(Duration::ZERO);
+            bus.register(consumer);
+            receivers.push(received);
+        }
+        let audit = Arc::new(AuditConsumer::default());
+        bus.register(audit.clone());
+
+        let published = signal(SignalDirection::Long, DEFAULT_TTL, Utc::now());
+        let id = published.id;
+        assert_eq!(bus.publish(published, Utc::now()), PublishOutcome::Published { receivers: 4 });
+
+        for received in receivers.iter_mut() {
+            assert_eq!(next(received).await, Some(id));
+        }
+        for _ in 0..50 {
+            if !audit.recent().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(audit.recent().iter().map(|s| s.id).collect::<Vec<_>>(), vec![id]);
+    }
+
+    #[tokio::test]
+    async fn test_expired_signals_are_not_delivered() {
+        let bus = SignalBus::new(SignalBusConfig::default());
+        let now = Utc::now();
+
+        // Already past its TTL when published
+        let stale = signal(SignalDirection::Long, Duration::from_millis(100), now - chrono::Duration::seconds(1));
+        assert_eq!(bus.publish(stale, now), PublishOutcome::Expired);
+
+        // Lapses while the consumer is still busy with the previous signal
+        let (consumer, mut received) = recording(Duration::from_millis(150));
+        bus.register(consumer);
+        let first = signal(SignalDirection::Long, DEFAULT_TTL, Utc::now());
+        let short_lived = signal(SignalDirection::Short, Duration::from_millis(50), Utc::now());
+        let first_id = first.id;
+        assert!(matches!(bus.publish(first, Utc::now()), PublishOutcome::Published { .. }));
+        assert!(matches!(bus.publish(short_lived, Utc::now()), PublishOutcome::Published { .. }));
+
+        assert_eq!(next(&mut received).await, Some(first_id));
+        assert_eq!(next(&mut received).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_duplicates_within_window_are_suppressed() {
+        let bus = SignalBus::new(SignalBusConfig {
+            suppression_window: Duration::from_secs(2),
+            ..SignalBusConfig::default()
+        });
+        let _receiver = bus.subscribe();
+        let now = Utc::now();
+
+        let published = |direction, at| bus.publish(signal(direction, DEFAULT_TTL, at), at);
+        assert_eq!(published(SignalDirection::Long, now), PublishOutcome::Published { receivers: 1 });
+        assert_eq!(
+            published(SignalDirection::Long, now + chrono::Duration::seconds(1)),
+            PublishOutcome::Suppressed
+        );
+        // The opposite direction is a different signal
+        assert_eq!(published(SignalDirection::Short, now), PublishOutcome::Published { receivers: 1 });
+        // The window runs from the first publish, not the suppressed duplicate
+        assert_eq!(
+            published(SignalDirection::Long, now + chrono::Duration::seconds(2)),
+            PublishOutcome::Published { receivers: 1 }
+        );
+    }
+
+    #[test]
+    fn test_grouped_signals_become_trade_groups() {
+        let now = Utc::now();
+        let group = SignalGroup::new(AtomicityPolicy::AllOrNothing);
+        let buy = signal(SignalDirection::Long, DEFAULT_TTL, now).with_group(group);
+        let mut sell = signal(SignalDirection::Short, DEFAULT_TTL, now).with_group(group);
+        sell.exchange = "drift".to_string();
+
+        let groups = trade_groups(vec![buy, signal(SignalDirection::Long, DEFAULT_TTL, now), sell]);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].correlation_id, group.correlation_id);
+        assert_eq!(groups[0].strategy_id, "grid-1");
+        assert_eq!(groups[0].policy, AtomicityPolicy::AllOrNothing);
+        let legs: Vec<_> = groups[0]
+            .legs
+            .iter()
+            .map(|leg| (leg.exchange.as_str(), leg.order.side))
+            .collect();
+        assert_eq!(legs, vec![("jupiter", TradeSide::Buy), ("drift", TradeSide::Sell)]);
+    }
+}
@@ -0,0 +1,611 @@
+//! Disaster recovery snapshots of the bot's runtime state. A snapshot copies open positions,
+//! pending orders, strategy states and parameters, risk counters and balances into a
+//! versioned, checksummed artifact stored in the database and optionally uploaded to the
+//! backup bucket. Restoring rehydrates the bot halted, for operator review before resuming.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - serde_json = "1.0"
+//! - sha2 = "0.10"
+//! - aws-sdk-s3 = "0.28"
+//! - async-trait = "0.1"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use aws_sdk_s3::{Client as S3Client, Region};
+use chrono::{DateTime, Utc};
+use metrics::{counter, histogram};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::config::database::BackupConfig;
+use crate::execution_engine::open_orders::{OpenOrderRegistry, PendingOrderSnapshot};
+use crate::models::portfolio::{Portfolio, PortfolioSnapshot};
+use crate::models::strategy::{Strategy, StrategySnapshot};
+use crate::risk_manager::{RiskManager, RiskSnapshot};
+
+// Snapshot constants
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+const DEFAULT_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(900);
+const S3_KEY_PREFIX: &str = "state-snapshots";
+const S3_REGION: &str = "ap-southeast-1";
+const METRICS_PREFIX: &str = "trading_bot.state_snapshot";
+
+/// Snapshot error types
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SnapshotError {
+    #[error("serialization error: {0}")]
+    Serialization(String),
+    #[error("checksum mismatch for snapshot {0}")]
+    ChecksumMismatch(Uuid),
+    #[error("unsupported snapshot format version: {0}")]
+    UnsupportedVersion(u32),
+    #[error("snapshot not found: {0}")]
+    NotFound(Uuid),
+    #[error("store error: {0}")]
+    Store(String),
+    #[error("upload error: {0}")]
+    Upload(String),
+    #[error("restore error: {0}")]
+    Restore(String),
+}
+
+/// Everything needed to bring the bot back to where it was
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BotStateSnapshot {
+    pub captured_at: DateTime<Utc>,
+    pub portfolio: PortfolioSnapshot,
+    pub pending_orders: Vec<PendingOrderSnapshot>,
+    /// Sorted by strategy id
+    pub strategies: Vec<StrategySnapshot>,
+    pub risk: RiskSnapshot,
+    pub circuit_breaker_open: bool,
+}
+
+/// What caused a snapshot to be taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotTrigger {
+    Manual,
+    Scheduled,
+}
+
+impl SnapshotTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SnapshotTrigger::Manual => "manual",
+            SnapshotTrigger::Scheduled => "scheduled",
+        }
+    }
+}
+
+impl std::str::FromStr for SnapshotTrigger {
+    type Err = SnapshotError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "manual" => Ok(SnapshotTrigger::Manual),
+            "scheduled" => Ok(SnapshotTrigger::Scheduled),
+            other => Err(SnapshotError::Store(format!("unknown snapshot trigger: {}", other))),
+        }
+    }
+}
+
+/// Serialized snapshot with its format version and SHA-256 checksum
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotArtifact {
+    pub id: Uuid,
+    pub format_version: u32,
+    /// Hex SHA-256 of the payload
+    pub checksum: String,
+    pub payload: Vec<u8>,
+    pub trigger: SnapshotTrigger,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Snapshot metadata listed by the admin API
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SnapshotSummary {
+    pub id: Uuid,
+    pub format_version: u32,
+    pub checksum: String,
+    pub size_bytes: usize,
+    pub trigger: SnapshotTrigger,
+    pub created_at: DateTime<Utc>,
+    /// Object key in the backup bucket, once uploaded
+    pub s3_key: Option<String>,
+}
+
+impl SnapshotArtifact {
+    /// Serializes and checksums a snapshot
+    pub fn seal(state: &BotStateSnapshot, trigger: SnapshotTrigger) -> Result<Self, SnapshotError> {
+        let payload = serde_json::to_vec(state).map_err(|e| SnapshotError::Serialization(e.to_string()))?;
+        Ok(Self {
+            id: Uuid::new_v4(),
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            checksum: checksum(&payload),
+            payload,
+            trigger,
+            created_at: state.captured_at,
+        })
+    }
+
+    /// Verifies the version and checksum, then deserializes the snapshot
+    pub fn open(&self) -> Result<BotStateSnapshot, SnapshotError> {
+        if self.format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(self.format_version));
+        }
+        if checksum(&self.payload) != self.checksum {
+            return Err(SnapshotError::ChecksumMismatch(self.id));
+        }
+        serde_json::from_slice(&self.payload).map_err(|e| SnapshotError::Serialization(e.to_string()))
+    }
+
+    pub fn summary(&self, s3_key: Option<String>) -> SnapshotSummary {
+        SnapshotSummary {
+            id: self.id,
+            format_version: self.format_version,
+            checksum: self.checksum.clone(),
+            size_bytes: self.payload.len(),
+            trigger: self.trigger,
+            created_at: self.created_at,
+            s3_key,
+        }
+    }
+
+    /// Object key in the backup bucket
+    pub fn s3_key(&self) -> String {
+        format!("{}/{}/{}.json", S3_KEY_PREFIX, self.created_at.format("%Y/%m/%d"), self.id)
+    }
+}
+
+fn checksum(payload: &[u8]) -> String {
+    hex::encode(Sha256::digest(payload))
+}
+
+/// Live structures a snapshot is copied from and restored into
+#[derive(Clone)]
+pub struct StateComponents {
+    pub portfolio: Arc<RwLock<Portfolio>>,
+    pub strategies: Arc<RwLock<HashMap<String, Strategy>>>,
+    pub open_orders: Option<Arc<OpenOrderRegistry>>,
+    pub risk_manager: Option<Arc<RwLock<RiskManager>>>,
+}
+
+impl StateComponents {
+    /// Copies each structure under a short read lock so trading is never held up for the
+    /// duration of serialization or storage
+    pub async fn capture(&self) -> BotStateSnapshot {
+        let strategies = {
+            let strategies = self.strategies.read().await;
+            let mut snapshots: Vec<StrategySnapshot> = strategies
+                .iter()
+                .map(|(strategy_id, strategy)| strategy.snapshot(strategy_id))
+                .collect();
+            snapshots.sort_by(|a, b| a.strategy_id.cmp(&b.strategy_id));
+            snapshots
+        };
+        let portfolio = self.portfolio.read().await.snapshot().await;
+        let pending_orders = self
+            .open_orders
+            .as_ref()
+            .map(|open_orders| open_orders.snapshot())
+            .unwrap_or_default();
+        let risk = match &self.risk_manager {
+            Some(risk_manager) => risk_manager.read().await.snapshot().await,
+            None => RiskSnapshot::default(),
+        };
+
+        BotStateSnapshot {
+            captured_at: Utc::now(),
+            portfolio,
+            pending_orders,
+            strategies,
+            risk,
+            circuit_breaker_open: false,
+        }
+    }
+
+    /// Replaces every structure's contents with the snapshot's
+    pub async fn restore(&self, snapshot: &BotStateSnapshot) -> Result<(), SnapshotError> {
+        self.portfolio
+            .read()
+            .await
+            .restore(&snapshot.portfolio)
+            .await
+            .map_err(|e| SnapshotError::Restore(e.to_string()))?;
+
+        *self.strategies.write().await = snapshot
+            .strategies
+            .iter()
+            .map(|strategy| (strategy.strategy_id.clone(), Strategy::from_snapshot(strategy)))
+            .collect();
+
+        if let Some(open_orders) = &self.open_orders {
+            open_orders.restore(&snapshot.pending_orders);
+        } else if !snapshot.pending_orders.is_empty() {
+            warn!(orders = snapshot.pending_orders.len(), "No order registry to restore pending orders into");
+        }
+        if let Some(risk_manager) = &self.risk_manager {
+            risk_manager.read().await.restore(&snapshot.risk).await;
+        }
+        Ok(())
+    }
+}
+
+/// Bot whose state can be snapshotted and restored
+#[async_trait]
+pub trait StateSource: Send + Sync {
+    async fn capture(&self) -> BotStateSnapshot;
+    /// Rehydrates state and leaves trading halted
+    async fn restore(&self, snapshot: &BotStateSnapshot) -> Result<(), SnapshotError>;
+    /// Lifts the halt after operator review
+    async fn resume(&self);
+}
+
+/// Persistence for snapshot artifacts
+#[async_trait]
+pub trait SnapshotStore: Send + Sync {
+    async fn save(&self, artifact: &SnapshotArtifact) -> Result<(), SnapshotError>;
+    async fn load(&self, id: Uuid) -> Result<Option<SnapshotArtifact>, SnapshotError>;
+    /// Newest first
+    async fn list(&self, limit: i64) -> Result<Vec<SnapshotSummary>, SnapshotError>;
+    async fn mark_uploaded(&self, id: Uuid, s3_key: &str) -> Result<(), SnapshotError>;
+    async fn delete_before(&self, cutoff: DateTime<Utc>) -> Result<u64, SnapshotError>;
+}
+
+/// Off-site copy of snapshot artifacts
+#[async_trait]
+pub trait SnapshotUploader: Send + Sync {
+    async fn upload(&self, key: &str, body: Vec<u8>) -> Result<(), SnapshotError>;
+}
+
+/// Uploads artifacts to the backup bucket
+pub struct S3SnapshotUploader {
+    client: S3Client,
+    bucket: String,
+}
+
+impl S3SnapshotUploader {
+    pub fn new(bucket: String) -> Self {
+        Self {
+            client: S3Client::new(Region::new(S3_REGION)),
+            bucket,
+        }
+    }
+}
+
+#[async_trait]
+impl SnapshotUploader for S3SnapshotUploader {
+    async fn upload(&self, key: &str, body: Vec<u8>) -> Result<(), SnapshotError> {
+        self.client
+            .put_object()
+            .bucket(self.bucket.clone())
+            .key(key)
+            .body(body.into())
+            .send()
+            .await
+            .map_err(|e| SnapshotError::Upload(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Snapshot schedule and retention
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotConfig {
+    pub interval: Duration,
+    pub retention_days: u32,
+    /// Bucket artifacts are uploaded to, when backups are enabled
+    pub s3_bucket: Option<String>,
+}
+
+impl SnapshotConfig {
+    pub fn from_backup(backup: &BackupConfig) -> Self {
+        Self {
+            interval: DEFAULT_SNAPSHOT_INTERVAL,
+            retention_days: backup.retention_days,
+            s3_bucket: backup.enabled.then(|| backup.s3_bucket.clone()),
+        }
+    }
+}
+
+/// Takes, lists and restores state snapshots
+pub struct SnapshotService {
+    config: SnapshotConfig,
+    source: Arc<dyn StateSource>,
+    store: Arc<dyn SnapshotStore>,
+    uploader: Option<Arc<dyn SnapshotUploader>>,
+}
+
+impl std::fmt::Debug for SnapshotService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapshotService")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl SnapshotService {
+    pub fn new(config: SnapshotConfig, source: Arc<dyn StateSource>, store: Arc<dyn SnapshotStore>) -> Self {
+        Self {
+            config,
+            source,
+            store,
+            uploader: None,
+        }
+    }
+
+    pub fn with_uploader(mut self, uploader: Arc<dyn SnapshotUploader>) -> Self {
+        self.uploader = Some(uploader);
+        self
+    }
+
+    /// Captures, stores and uploads a snapshot. A failed upload leaves the stored copy.
+    #[instrument(skip(self))]
+    pub async fn take(&self, trigger: SnapshotTrigger) -> Result<SnapshotSummary, SnapshotError> {
+        let start = std::time::Instant::now();
+        let state = self.source.capture().await;
+        let artifact = SnapshotArtifact::seal(&state, trigger)?;
+        self.store.save(&artifact).await?;
+
+        let mut s3_key = None;
+        if let Some(uploader) = &self.uploader {
+            let key = artifact.s3_key();
+            let body = serde_json::to_vec(&artifact).map_err(|e| SnapshotError::Serialization(e.to_string()))?;
+            match uploader.upload(&key, body).await {
+                Ok(()) => {
+                    self.store.mark_uploaded(artifact.id, &key).await?;
+                    s3_key = Some(key);
+                }
+                Err(e) => {
+                    warn!(snapshot_id = %artifact.id, error = %e, "Snapshot upload failed");
+                    counter!(format!("{}.upload_failures", METRICS_PREFIX), 1);
+                }
+            }
+        }
+
+        counter!(format!("{}.taken", METRICS_PREFIX), 1, "trigger" => trigger.as_str());
+        histogram!(format!("{}.size_bytes", METRICS_PREFIX), artifact.payload.len() as f64);
+        histogram!(format!("{}.duration_ms", METRICS_PREFIX), start.elapsed().as_millis() as f64);
+        info!(snapshot_id = %artifact.id, bytes = artifact.payload.len(), "State snapshot taken");
+        Ok(artifact.summary(s3_key))
+    }
+
+    pub async fn list(&self, limit: i64) -> Result<Vec<SnapshotSummary>, SnapshotError> {
+        self.store.list(limit).await
+    }
+
+    /// Restores a stored snapshot into the bot, leaving trading halted
+    #[instrument(skip(self))]
+    pub async fn restore(&self, id: Uuid) -> Result<BotStateSnapshot, SnapshotError> {
+        let artifact = self.store.load(id).await?.ok_or(SnapshotError::NotFound(id))?;
+        let state = artifact.open()?;
+        self.source.restore(&state).await?;
+        counter!(format!("{}.restored", METRICS_PREFIX), 1);
+        info!(snapshot_id = %id, captured_at = %state.captured_at, "State restored; trading halted for review");
+        Ok(state)
+    }
+
+    /// Lifts the post-restore halt
+    pub async fn resume(&self) {
+        self.source.resume().await;
+    }
+
+    /// Takes scheduled snapshots and prunes those past retention
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.interval);
+            // The first tick fires immediately; skip it so startup isn't snapshotted
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.take(SnapshotTrigger::Scheduled).await {
+                    error!(error = %e, "Scheduled state snapshot failed");
+                }
+                let cutoff = Utc::now() - chrono::Duration::days(self.config.retention_days as i64);
+                if let Err(e) = self.store.delete_before(cutoff).await {
+                    warn!(error = %e, "Failed to prune old state snapshots");
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_engine::open_orders::OrderCanceller;
+    use crate::models::market::QuoteAsset;
+    use crate::models::order::{Order, OrderError, OrderType};
+    use crate::models::strategy::{StrategyParams, StrategyState, StrategyType};
+    use crate::risk_manager::exposure::TradeSide;
+    use crate::risk_manager::velocity::{VelocityLimits, VelocityTracker};
+    use crate::risk_manager::RiskConfig;
+    use crate::utils::percent::{Bps, Percent};
+    use parking_lot::Mutex;
+    use rust_decimal_macros::dec;
+
+    const WALLET: &str = "wallet-1";
+
+    struct NoopCanceller;
+
+    #[async_trait]
+    impl OrderCanceller for NoopCanceller {
+        async fn cancel(&self, _order: &mut Order) -> Result<(), OrderError> {
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryStore {
+        artifacts: Mutex<HashMap<Uuid, SnapshotArtifact>>,
+    }
+
+    #[async_trait]
+    impl SnapshotStore for MemoryStore {
+        async fn save(&self, artifact: &SnapshotArtifact) -> Result<(), SnapshotError> {
+            self.artifacts.lock().insert(artifact.id, artifact.clone());
+            Ok(())
+        }
+
+        async fn load(&self, id: Uuid) -> Result<Option<SnapshotArtifact>, SnapshotError> {
+            Ok(self.artifacts.lock().get(&id).cloned())
+        }
+
+        async fn list(&self, _limit: i64) -> Result<Vec<SnapshotSummary>, SnapshotError> {
+            Ok(self.artifacts.lock().values().map(|artifact| artifact.summary(None)).collect())
+        }
+
+        async fn mark_uploaded(&self, _id: Uuid, _s3_key: &str) -> Result<(), SnapshotError> {
+            Ok(())
+        }
+
+        async fn delete_before(&self, _cutoff: DateTime<Utc>) -> Result<u64, SnapshotError> {
+            Ok(0)
+        }
+    }
+
+    /// Bot stand-in over real components, with a halt flag
+    struct TestBot {
+        components: RwLock<StateComponents>,
+        halted: Mutex<bool>,
+    }
+
+    #[async_trait]
+    impl StateSource for TestBot {
+        async fn capture(&self) -> BotStateSnapshot {
+            self.components.read().await.capture().await
+        }
+
+        async fn restore(&self, snapshot: &BotStateSnapshot) -> Result<(), SnapshotError> {
+            self.components.read().await.restore(snapshot).await?;
+            *self.halted.lock() = true;
+            Ok(())
+        }
+
+        async fn resume(&self) {
+            *self.halted.lock() = false;
+        }
+    }
+
+    fn empty_components() -> StateComponents {
+        StateComponents {
+            portfolio: Arc::new(RwLock::new(Portfolio::new(WALLET.to_string(), dec!(1000)).unwrap())),
+            strategies: Arc::new(RwLock::new(HashMap::new())),
+            open_orders: Some(Arc::new(OpenOrderRegistry::new(Arc::new(NoopCanceller)))),
+            risk_manager: Some(Arc::new(RwLock::new(RiskManager::new(RiskConfig::default()).unwrap()))),
+        }
+    }
+
+    /// Components with a position, a pending order, a paused strategy and velocity history
+    async fn populated_components() -> StateComponents {
+        let components = empty_components();
+        let portfolio = components.portfolio.read().await.clone();
+        portfolio.update_balance(dec!(750)).await.unwrap();
+        portfolio.update_quote_balance(QuoteAsset::Sol, dec!(4.5)).await.unwrap();
+        portfolio.add_position("SOL/USDC".parse().unwrap(), dec!(2), dec!(23.5)).await.unwrap();
+        portfolio.add_position("SOL/USDC".parse().unwrap(), dec!(-1), dec!(25.0)).await.unwrap();
+
+        let mut strategy = Strategy::new(
+            StrategyType::Grid,
+            StrategyParams {
+                position_size_bps: Bps::new(500),
+                grid_levels: Some(10),
+                stop_loss_pct: Percent::from_percent(dec!(-5)),
+                take_profit_pct: Percent::from_percent(dec!(2)),
+                max_slippage_bps: Bps::new(50),
+                exchanges: vec!["jupiter".to_string()],
+                risk_factor: dec!(1),
+                allowed_regimes: Vec::new(),
+                market_making: None,
+            },
+            vec!["SOL/USDC".to_string()],
+        )
+        .unwrap();
+        strategy.state = StrategyState::Paused;
+        strategy.risk_metrics.insert("grid_anchor".to_string(), dec!(23.0));
+        components.strategies.write().await.insert("grid-1".to_string(), strategy);
+
+        let order = Order::new(
+            "SOL/USDC".to_string(),
+            "jupiter".to_string(),
+            OrderType::Limit,
+            dec!(23.10),
+            dec!(2),
+        )
+        .unwrap();
+        components
+            .open_orders
+            .as_ref()
+            .unwrap()
+            .register(order, WALLET, "grid-1", TradeSide::Buy);
+
+        let mut velocity = VelocityTracker::new(VelocityLimits::default(), HashMap::new());
+        velocity.record(Utc::now(), "grid-1", WALLET, dec!(46.20)).await;
+        let risk = RiskSnapshot {
+            velocity: velocity.snapshot(),
+            circuit_breaker_tripped: true,
+            daily_loss: None,
+        };
+        components.risk_manager.as_ref().unwrap().read().await.restore(&risk).await;
+
+        components
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip_restores_identical_state() {
+        let bot = Arc::new(TestBot {
+            components: RwLock::new(populated_components().await),
+            halted: Mutex::new(false),
+        });
+        let store = Arc::new(MemoryStore::default());
+        let service = SnapshotService::new(SnapshotConfig::from_backup(&BackupConfig {
+            enabled: false,
+            retention_days: 30,
+            schedule: "0 0 * * *".to_string(),
+            s3_bucket: "trading-bot-backups".to_string(),
+        }), bot.clone(), store.clone());
+
+        let before = bot.capture().await;
+        assert_eq!(before.pending_orders.len(), 1);
+        assert_eq!(before.risk.velocity.len(), 2);
+        assert_eq!(before.portfolio.positions[0].size, dec!(1));
+        let summary = service.take(SnapshotTrigger::Manual).await.unwrap();
+        assert_eq!(summary.format_version, SNAPSHOT_FORMAT_VERSION);
+
+        // Wipe in-memory state, then restore into the fresh components
+        *bot.components.write().await = empty_components();
+        assert_ne!(bot.capture().await.portfolio, before.portfolio);
+
+        let restored = service.restore(summary.id).await.unwrap();
+        assert_eq!(restored, before);
+        assert!(*bot.halted.lock());
+
+        let mut after = bot.capture().await;
+        after.captured_at = before.captured_at;
+        assert_eq!(after, before);
+
+        service.resume().await;
+        assert!(!*bot.halted.lock());
+    }
+
+    #[tokio::test]
+    async fn test_tampered_artifact_is_rejected() {
+        let state = empty_components().capture().await;
+        let mut artifact = SnapshotArtifact::seal(&state, SnapshotTrigger::Scheduled).unwrap();
+        assert_eq!(artifact.open().unwrap(), state);
+
+        artifact.payload[0] ^= 0xff;
+        assert_eq!(artifact.open(), Err(SnapshotError::ChecksumMismatch(artifact.id)));
+
+        artifact.format_version = SNAPSHOT_FORMAT_VERSION + 1;
+        assert_eq!(artifact.open(), Err(SnapshotError::UnsupportedVersion(SNAPSHOT_FORMAT_VERSION + 1)));
+    }
+}
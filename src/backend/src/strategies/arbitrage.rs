@@ -0,0 +1,198 @@
+//! Cross-venue arbitrage strategy. Keeps the latest price each configured venue quotes for
+//! a pair and, when the richest venue trades at least `take_profit_pct` above the cheapest,
+//! buys on the cheap venue and sells on the rich one. Both legs share a `SignalGroup`, so
+//! they execute together as one trade group: in a single bundle where the venues allow it,
+//! otherwise leg by leg with filled legs hedged when a later one fails.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+
+use super::{StrategyContext, TradingStrategy};
+use crate::execution_engine::multi_leg::AtomicityPolicy;
+use crate::models::market::MarketData;
+use crate::models::strategy::{StrategyError, StrategyParams, StrategyType};
+use crate::signals::{Signal, SignalDirection, SignalGroup};
+use crate::utils::percent::Percent;
+
+// Arbitrage constants
+const MIN_VENUES: usize = 2;
+
+/// Latest price a venue quoted for a pair
+#[derive(Debug, Clone, Copy)]
+struct VenueQuote {
+    price: Decimal,
+    at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct ArbitrageStrategy {
+    params: StrategyParams,
+    /// Latest quote per venue, by trading pair
+    quotes: HashMap<String, HashMap<String, VenueQuote>>,
+}
+
+impl ArbitrageStrategy {
+    pub fn new(params: StrategyParams) -> Self {
+        Self {
+            params,
+            quotes: HashMap::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl TradingStrategy for ArbitrageStrategy {
+    fn kind(&self) -> StrategyType {
+        StrategyType::Arbitrage
+    }
+
+    fn validate_params(&self) -> Result<(), StrategyError> {
+        if self.params.exchanges.len() < MIN_VENUES {
+            return Err(StrategyError::ValidationError(format!(
+                "arbitrage needs at least {} exchanges",
+                MIN_VENUES
+            )));
+        }
+        if self.params.take_profit_pct <= Percent::ZERO {
+            return Err(StrategyError::ValidationError(
+                "arbitrage edge (take profit) must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn on_market_data(&mut self, ctx: &StrategyContext, data: &MarketData) -> Vec<Signal> {
+        if !self.params.exchanges.iter().any(|exchange| exchange == data.exchange()) {
+            return Vec::new();
+        }
+        let quotes = self.quotes.entry(data.trading_pair().to_string()).or_default();
+        quotes.insert(
+            data.exchange().to_string(),
+            VenueQuote {
+                price: data.price(),
+                at: data.timestamp(),
+            },
+        );
+
+        // Quotes older than a signal's lifetime no longer say where the venue trades
+        let ttl = chrono::Duration::from_std(ctx.signal_ttl()).unwrap_or(chrono::Duration::MAX);
+        quotes.retain(|_, quote| ctx.now() - quote.at <= ttl);
+
+        let Some((cheap, buy)) = quotes.iter().min_by_key(|(_, quote)| quote.price) else {
+            return Vec::new();
+        };
+        let Some((rich, sell)) = quotes.iter().max_by_key(|(_, quote)| quote.price) else {
+            return Vec::new();
+        };
+        if sell.price - buy.price < self.params.take_profit_pct.of(buy.price) {
+            return Vec::new();
+        }
+
+        let group = SignalGroup::new(AtomicityPolicy::AllOrNothing);
+        let size = self.params.position_size_bps.as_fraction();
+        let legs: Vec<Signal> = [(cheap, SignalDirection::Long, buy.price), (rich, SignalDirection::Short, sell.price)]
+            .into_iter()
+            .map(|(exchange, direction, price)| {
+                Signal::new(
+                    ctx.strategy_id(),
+                    data.trading_pair(),
+                    exchange,
+                    direction,
+                    price,
+                    size,
+                    ctx.signal_ttl(),
+                    ctx.now(),
+                )
+                .with_group(group)
+            })
+            .collect();
+
+        // The spread is taken; trade it again only on fresh quotes from both venues
+        quotes.clear();
+        legs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::percent::Bps;
+    use rust_decimal_macros::dec;
+
+    const PAIR: &str = "SOL/USDC";
+
+    fn params() -> StrategyParams {
+        StrategyParams {
+            position_size_bps: Bps::new(1000),
+            grid_levels: None,
+            stop_loss_pct: Percent::from_percent(dec!(-5)),
+            take_profit_pct: Percent::from_percent(dec!(0.5)),
+            max_slippage_bps: Bps::new(30),
+            exchanges: vec!["jupiter".to_string(), "drift".to_string()],
+            risk_factor: dec!(0.5),
+            allowed_regimes: Vec::new(),
+            market_making: None,
+        }
+    }
+
+    async fn observe(strategy: &mut ArbitrageStrategy, exchange: &str, price: Decimal, at: DateTime<Utc>) -> Vec<Signal> {
+        let ctx = StrategyContext::new("arb-1", at);
+        let data = MarketData::new(PAIR.to_string(), exchange.to_string(), price, dec!(5))
+            .unwrap()
+            .with_timestamp(at);
+        strategy.on_market_data(&ctx, &data).await
+    }
+
+    #[tokio::test]
+    async fn test_spread_past_edge_emits_grouped_legs() {
+        let mut strategy = ArbitrageStrategy::new(params());
+        let now = Utc::now();
+
+        assert!(observe(&mut strategy, "jupiter", dec!(100), now).await.is_empty());
+        // 0.4% apart is inside the 0.5% edge
+        assert!(observe(&mut strategy, "drift", dec!(100.4), now).await.is_empty());
+
+        let signals = observe(&mut strategy, "drift", dec!(100.6), now).await;
+        let legs: Vec<_> = signals
+            .iter()
+            .map(|signal| (signal.exchange.as_str(), signal.direction, signal.price))
+            .collect();
+        assert_eq!(
+            legs,
+            vec![
+                ("jupiter", SignalDirection::Long, dec!(100)),
+                ("drift", SignalDirection::Short, dec!(100.6)),
+            ]
+        );
+        assert!(signals[0].group.is_some());
+        assert_eq!(signals[0].group, signals[1].group);
+
+        // Quotes are spent once traded
+        assert!(observe(&mut strategy, "drift", dec!(100.6), now).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stale_quotes_and_other_venues_are_ignored() {
+        let mut strategy = ArbitrageStrategy::new(params());
+        let now = Utc::now();
+
+        assert!(observe(&mut strategy, "jupiter", dec!(100), now - chrono::Duration::seconds(30)).await.is_empty());
+        assert!(observe(&mut strategy, "drift", dec!(101), now).await.is_empty());
+        assert!(observe(&mut strategy, "raydium", dec!(99), now).await.is_empty());
+    }
+
+    #[test]
+    fn test_needs_two_venues_and_an_edge() {
+        let mut single = params();
+        single.exchanges.truncate(1);
+        assert!(ArbitrageStrategy::new(single).validate_params().is_err());
+
+        let mut no_edge = params();
+        no_edge.take_profit_pct = Percent::ZERO;
+        assert!(ArbitrageStrategy::new(no_edge).validate_params().is_err());
+        assert!(ArbitrageStrategy::new(params()).validate_params().is_ok());
+    }
+}
@@ -0,0 +1,116 @@
+//! Symmetric grid around the first observed price, spaced by `take_profit_pct`. Crossing a
+//! level downward buys at that level; crossing upward sells (take profit).
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use tracing::warn;
+
+use super::{StrategyContext, TradingStrategy};
+use crate::models::market::MarketData;
+use crate::models::strategy::{StrategyError, StrategyParams, StrategyType, MIN_GRID_LEVELS};
+use crate::signals::{Signal, SignalDirection};
+use crate::utils::percent::Percent;
+
+// Grid state keys, persisted in the strategy's risk metrics
+const GRID_ANCHOR_KEY: &str = "grid_anchor";
+const GRID_LEVEL_KEY: &str = "grid_level";
+
+/// Seeds grid state so the grid is anchored at `anchor` rather than the next observation
+pub fn anchor_grid(state: &mut HashMap<String, Decimal>, anchor: Decimal) {
+    state.insert(GRID_ANCHOR_KEY.to_string(), anchor);
+    state.insert(GRID_LEVEL_KEY.to_string(), Decimal::ZERO);
+}
+
+#[derive(Debug)]
+pub struct GridStrategy {
+    params: StrategyParams,
+    anchor: Option<Decimal>,
+    level: Decimal,
+}
+
+impl GridStrategy {
+    pub fn new(params: StrategyParams, state: &HashMap<String, Decimal>) -> Self {
+        Self {
+            params,
+            anchor: state.get(GRID_ANCHOR_KEY).copied(),
+            level: state.get(GRID_LEVEL_KEY).copied().unwrap_or(Decimal::ZERO),
+        }
+    }
+}
+
+#[async_trait]
+impl TradingStrategy for GridStrategy {
+    fn kind(&self) -> StrategyType {
+        StrategyType::Grid
+    }
+
+    fn validate_params(&self) -> Result<(), StrategyError> {
+        if self.params.take_profit_pct <= Percent::ZERO {
+            return Err(StrategyError::ValidationError(
+                "grid spacing (take profit) must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn on_market_data(&mut self, ctx: &StrategyContext, data: &MarketData) -> Vec<Signal> {
+        let price = data.price();
+
+        // Anchor the grid on the first observation
+        let Some(anchor) = self.anchor else {
+            self.anchor = Some(price);
+            self.level = Decimal::ZERO;
+            return Vec::new();
+        };
+
+        let half_levels = Decimal::from(self.params.grid_levels.unwrap_or(MIN_GRID_LEVELS) / 2);
+        let step = self.params.take_profit_pct.of(anchor);
+        if step <= Decimal::ZERO {
+            warn!(strategy_id = ctx.strategy_id(), %anchor, "Grid step must be positive; skipping observation");
+            return Vec::new();
+        }
+
+        let level = ((price - anchor) / step).floor().max(-half_levels).min(half_levels);
+        let size = self.params.position_size_bps.as_fraction();
+
+        // Walk every level crossed since the last observation
+        let mut crossings = Vec::new();
+        let mut current = self.level;
+        while current > level {
+            crossings.push((current, SignalDirection::Long));
+            current -= Decimal::ONE;
+        }
+        while current < level {
+            current += Decimal::ONE;
+            crossings.push((current, SignalDirection::Short));
+        }
+        self.level = level;
+
+        crossings
+            .into_iter()
+            .map(|(crossed, direction)| {
+                Signal::new(
+                    ctx.strategy_id(),
+                    data.trading_pair(),
+                    data.exchange(),
+                    direction,
+                    anchor + crossed * step,
+                    size,
+                    ctx.signal_ttl(),
+                    ctx.now(),
+                )
+            })
+            .collect()
+    }
+
+    fn state(&self) -> HashMap<String, Decimal> {
+        let mut state = HashMap::new();
+        if let Some(anchor) = self.anchor {
+            anchor_grid(&mut state, anchor);
+            state.insert(GRID_LEVEL_KEY.to_string(), self.level);
+        }
+        state
+    }
+}
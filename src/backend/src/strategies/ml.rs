@@ -0,0 +1,35 @@
+//! ML-based strategy.
+
+use async_trait::async_trait;
+
+use super::{StrategyContext, TradingStrategy};
+use crate::models::market::MarketData;
+use crate::models::strategy::{StrategyError, StrategyParams, StrategyType};
+use crate::signals::Signal;
+
+#[derive(Debug)]
+pub struct MlStrategy {
+    params: StrategyParams,
+}
+
+impl MlStrategy {
+    pub fn new(params: StrategyParams) -> Self {
+        Self { params }
+    }
+}
+
+#[async_trait]
+impl TradingStrategy for MlStrategy {
+    fn kind(&self) -> StrategyType {
+        StrategyType::MLBased
+    }
+
+    fn validate_params(&self) -> Result<(), StrategyError> {
+        Ok(())
+    }
+
+    async fn on_market_data(&mut self, ctx: &StrategyContext, data: &MarketData) -> Vec<Signal> {
+        // ML-based strategy implementation
+        todo!("Implement ML strategy execution")
+    }
+}
@@ -0,0 +1,418 @@
+//! Pluggable strategy implementations. Each strategy type implements `TradingStrategy` and
+//! is registered under its type name with a factory; `Strategy` looks its implementation up
+//! in the registry, so adding a strategy means implementing the trait and registering a
+//! factory in `StrategyRegistry::default` (built-ins) or through `registry().register`.
+//! Implementations see the portfolio, registered pairs, candles, regimes and order book
+//! microstructure through a read-only `StrategyContext` and express intent as signals.
+//!
+//! Version dependencies:
+//! - async-trait = "0.1"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+//! - lazy_static = "1.4"
+
+pub mod arbitrage;
+pub mod grid;
+pub mod market_making;
+pub mod ml;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+
+use crate::data_collector::ohlcv::{Candle, CandleAggregator, CandleInterval};
+use crate::microstructure::{MicrostructureSnapshot, MicrostructureTracker};
+use crate::models::market::MarketData;
+use crate::models::pair::PairRegistry;
+use crate::models::portfolio::PortfolioSnapshot;
+use crate::models::strategy::{StrategyError, StrategyParams, StrategyType};
+use crate::regime::{MarketRegime, RegimeDetector};
+use crate::signals::Signal;
+
+pub use self::arbitrage::ArbitrageStrategy;
+pub use self::grid::GridStrategy;
+pub use self::market_making::{MarketMakingParams, MarketMakingStrategy};
+pub use self::ml::MlStrategy;
+
+// Strategy registry constants
+const DEFAULT_SIGNAL_TTL: Duration = Duration::from_secs(5);
+
+lazy_static::lazy_static! {
+    static ref REGISTRY: StrategyRegistry = StrategyRegistry::default();
+}
+
+/// Registry every `Strategy` resolves its implementation from
+pub fn registry() -> &'static StrategyRegistry {
+    &REGISTRY
+}
+
+/// Trading logic of one strategy type. State carried between observations lives in the
+/// implementation and is exposed through `state` so it survives snapshots and restarts.
+#[async_trait]
+pub trait TradingStrategy: Send + Sync + fmt::Debug {
+    fn kind(&self) -> StrategyType;
+
+    /// Checks type-specific parameters; common limits are checked by `Strategy::new`
+    fn validate_params(&self) -> Result<(), StrategyError>;
+
+    /// Signals for one market data observation
+    async fn on_market_data(&mut self, ctx: &StrategyContext, data: &MarketData) -> Vec<Signal>;
+
+    /// State to persist with the strategy, handed back to the factory on restore
+    fn state(&self) -> HashMap<String, Decimal> {
+        HashMap::new()
+    }
+
+    /// Strategy-specific performance metrics, merged into `PerformanceMetrics::extensions`
+    fn metrics(&self) -> HashMap<String, Decimal> {
+        HashMap::new()
+    }
+}
+
+/// Builds an implementation from its parameters and previously persisted state
+pub type StrategyFactory =
+    Arc<dyn Fn(&StrategyParams, &HashMap<String, Decimal>) -> Box<dyn TradingStrategy> + Send + Sync>;
+
+/// Factories by strategy type name
+pub struct StrategyRegistry {
+    factories: RwLock<HashMap<String, StrategyFactory>>,
+}
+
+impl fmt::Debug for StrategyRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StrategyRegistry")
+            .field("kinds", &self.kinds())
+            .finish()
+    }
+}
+
+impl Default for StrategyRegistry {
+    /// Registry holding the built-in strategies
+    fn default() -> Self {
+        let registry = Self::empty();
+        registry.register(StrategyType::Grid, |params, state| {
+            Box::new(GridStrategy::new(params.clone(), state))
+        });
+        registry.register(StrategyType::Arbitrage, |params, _| {
+            Box::new(ArbitrageStrategy::new(params.clone()))
+        });
+        registry.register(StrategyType::MLBased, |params, _| Box::new(MlStrategy::new(params.clone())));
+        registry.register(StrategyType::MarketMaking, |params, state| {
+            Box::new(MarketMakingStrategy::new(params.clone(), state))
+        });
+        registry
+    }
+}
+
+impl StrategyRegistry {
+    pub fn empty() -> Self {
+        Self {
+            factories: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Registers a factory for a strategy type, replacing any previous one
+    pub fn register<F>(&self, kind: StrategyType, factory: F)
+    where
+        F: Fn(&StrategyParams, &HashMap<String, Decimal>) -> Box<dyn TradingStrategy> + Send + Sync + 'static,
+    {
+        self.factories
+            .write()
+            .insert(kind.as_str().to_string(), Arc::new(factory));
+    }
+
+    pub fn contains(&self, kind: &StrategyType) -> bool {
+        self.factories.read().contains_key(kind.as_str())
+    }
+
+    /// Registered type names, sorted
+    pub fn kinds(&self) -> Vec<String> {
+        let mut kinds: Vec<String> = self.factories.read().keys().cloned().collect();
+        kinds.sort();
+        kinds
+    }
+
+    /// Builds and validates an implementation of a registered type
+    pub fn create(
+        &self,
+        kind: &StrategyType,
+        params: &StrategyParams,
+        state: &HashMap<String, Decimal>,
+    ) -> Result<Box<dyn TradingStrategy>, StrategyError> {
+        let factory = self
+            .factories
+            .read()
+            .get(kind.as_str())
+            .cloned()
+            .ok_or_else(|| StrategyError::ValidationError(format!("unknown strategy type: {}", kind)))?;
+        let strategy = factory(params, state);
+        strategy.validate_params()?;
+        Ok(strategy)
+    }
+}
+
+/// Read-only view of bot state handed to a strategy for one observation
+#[derive(Clone)]
+pub struct StrategyContext {
+    strategy_id: String,
+    now: DateTime<Utc>,
+    signal_ttl: Duration,
+    portfolio: Option<PortfolioSnapshot>,
+    pairs: Option<Arc<PairRegistry>>,
+    candles: Option<Arc<CandleAggregator>>,
+    regimes: Option<Arc<RegimeDetector>>,
+    microstructure: Option<Arc<MicrostructureTracker>>,
+}
+
+impl fmt::Debug for StrategyContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StrategyContext")
+            .field("strategy_id", &self.strategy_id)
+            .field("now", &self.now)
+            .field("portfolio", &self.portfolio.is_some())
+            .finish()
+    }
+}
+
+impl StrategyContext {
+    pub fn new(strategy_id: &str, now: DateTime<Utc>) -> Self {
+        Self {
+            strategy_id: strategy_id.to_string(),
+            now,
+            signal_ttl: DEFAULT_SIGNAL_TTL,
+            portfolio: None,
+            pairs: None,
+            candles: None,
+            regimes: None,
+            microstructure: None,
+        }
+    }
+
+    pub fn with_signal_ttl(mut self, ttl: Duration) -> Self {
+        self.signal_ttl = ttl;
+        self
+    }
+
+    pub fn with_portfolio(mut self, portfolio: PortfolioSnapshot) -> Self {
+        self.portfolio = Some(portfolio);
+        self
+    }
+
+    pub fn with_pairs(mut self, pairs: Arc<PairRegistry>) -> Self {
+        self.pairs = Some(pairs);
+        self
+    }
+
+    pub fn with_candles(mut self, candles: Arc<CandleAggregator>) -> Self {
+        self.candles = Some(candles);
+        self
+    }
+
+    pub fn with_regimes(mut self, regimes: Arc<RegimeDetector>) -> Self {
+        self.regimes = Some(regimes);
+        self
+    }
+
+    pub fn with_microstructure(mut self, microstructure: Arc<MicrostructureTracker>) -> Self {
+        self.microstructure = Some(microstructure);
+        self
+    }
+
+    pub fn strategy_id(&self) -> &str {
+        &self.strategy_id
+    }
+
+    /// Time the observation is judged at; replays pass the recorded time
+    pub fn now(&self) -> DateTime<Utc> {
+        self.now
+    }
+
+    pub fn signal_ttl(&self) -> Duration {
+        self.signal_ttl
+    }
+
+    /// Balances and positions when the observation arrived, when running against a wallet
+    pub fn portfolio(&self) -> Option<&PortfolioSnapshot> {
+        self.portfolio.as_ref()
+    }
+
+    pub fn pairs(&self) -> Option<&PairRegistry> {
+        self.pairs.as_deref()
+    }
+
+    /// Closed candles followed by the open one, oldest first
+    pub fn recent_candles(&self, trading_pair: &str, interval: CandleInterval) -> Vec<Candle> {
+        self.candles
+            .as_ref()
+            .map(|candles| candles.recent_candles(trading_pair, interval))
+            .unwrap_or_default()
+    }
+
+    pub fn regime(&self, trading_pair: &str) -> Option<MarketRegime> {
+        self.regimes.as_ref().and_then(|regimes| regimes.regime(trading_pair))
+    }
+
+    pub fn volatility(&self, trading_pair: &str) -> Option<f64> {
+        self.regimes.as_ref().and_then(|regimes| regimes.volatility(trading_pair))
+    }
+
+    /// Microstructure features of the pair's latest book update; check `flags` before use
+    pub fn microstructure(&self, trading_pair: &str) -> Option<MicrostructureSnapshot> {
+        self.microstructure.as_ref().and_then(|microstructure| microstructure.latest(trading_pair))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::strategy::{Strategy, StrategyState};
+    use crate::signals::SignalDirection;
+    use crate::utils::percent::{Bps, Percent};
+    use rust_decimal_macros::dec;
+
+    const PAIR: &str = "SOL/USDC";
+    const MOMENTUM: &str = "MOMENTUM";
+
+    /// Buys after `streak` consecutive rising ticks
+    #[derive(Debug)]
+    struct Momentum {
+        params: StrategyParams,
+        last_price: Option<Decimal>,
+        rises: Decimal,
+    }
+
+    #[async_trait]
+    impl TradingStrategy for Momentum {
+        fn kind(&self) -> StrategyType {
+            StrategyType::Custom(MOMENTUM.to_string())
+        }
+
+        fn validate_params(&self) -> Result<(), StrategyError> {
+            if self.params.exchanges.is_empty() {
+                return Err(StrategyError::ValidationError("momentum needs an exchange".to_string()));
+            }
+            Ok(())
+        }
+
+        async fn on_market_data(&mut self, ctx: &StrategyContext, data: &MarketData) -> Vec<Signal> {
+            let price = data.price();
+            let rising = self.last_price.map_or(false, |last| price > last);
+            self.last_price = Some(price);
+            self.rises = if rising { self.rises + Decimal::ONE } else { Decimal::ZERO };
+            if self.rises < dec!(2) || ctx.regime(PAIR).is_some() {
+                return Vec::new();
+            }
+            vec![Signal::new(
+                ctx.strategy_id(),
+                data.trading_pair(),
+                &self.params.exchanges[0],
+                SignalDirection::Long,
+                price,
+                self.params.position_size_bps.as_fraction(),
+                ctx.signal_ttl(),
+                ctx.now(),
+            )]
+        }
+
+        fn state(&self) -> HashMap<String, Decimal> {
+            HashMap::from([("rises".to_string(), self.rises)])
+        }
+    }
+
+    fn params() -> StrategyParams {
+        StrategyParams {
+            position_size_bps: Bps::new(1000),
+            grid_levels: None,
+            stop_loss_pct: Percent::from_percent(dec!(-5)),
+            take_profit_pct: Percent::from_percent(dec!(1)),
+            max_slippage_bps: Bps::new(100),
+            exchanges: vec!["jupiter".to_string()],
+            risk_factor: dec!(0.5),
+            allowed_regimes: Vec::new(),
+            market_making: None,
+        }
+    }
+
+    fn tick(price: Decimal, at: DateTime<Utc>) -> MarketData {
+        MarketData::new(PAIR.to_string(), "jupiter".to_string(), price, dec!(5))
+            .unwrap()
+            .with_timestamp(at)
+    }
+
+    fn register_momentum() {
+        registry().register(StrategyType::Custom(MOMENTUM.to_string()), |params, state| {
+            Box::new(Momentum {
+                params: params.clone(),
+                last_price: None,
+                rises: state.get("rises").copied().unwrap_or_default(),
+            })
+        });
+    }
+
+    #[tokio::test]
+    async fn test_registered_strategy_driven_end_to_end() {
+        register_momentum();
+        let kind: StrategyType = serde_json::from_str(&format!("\"{}\"", MOMENTUM)).unwrap();
+        assert_eq!(kind, StrategyType::Custom(MOMENTUM.to_string()));
+        assert!(registry().kinds().contains(&MOMENTUM.to_string()));
+
+        let mut strategy = Strategy::new(kind, params(), vec![PAIR.to_string()]).unwrap();
+        strategy.state = StrategyState::Active;
+
+        let t0 = Utc::now();
+        let mut signals = Vec::new();
+        for (i, price) in [dec!(100), dec!(101), dec!(102), dec!(101)].into_iter().enumerate() {
+            let at = t0 + chrono::Duration::seconds(i as i64);
+            let ctx = StrategyContext::new("momentum-1", at);
+            signals.push(strategy.execute(&ctx, &tick(price, at)).await.unwrap());
+        }
+
+        assert!(signals[0].is_empty() && signals[1].is_empty() && signals[3].is_empty());
+        assert_eq!(signals[2].len(), 1);
+        let signal = &signals[2][0];
+        assert_eq!(signal.strategy_id, "momentum-1");
+        assert_eq!(signal.direction, SignalDirection::Long);
+        assert_eq!(signal.price, dec!(102));
+        assert_eq!(signal.size, dec!(0.1));
+
+        // Implementation state is persisted with the strategy and survives a snapshot
+        let snapshot = strategy.snapshot("momentum-1");
+        assert_eq!(serde_json::to_value(&snapshot.strategy_type).unwrap(), MOMENTUM);
+        assert_eq!(snapshot.risk_metrics.get("rises"), Some(&Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_unknown_and_invalid_strategies_rejected() {
+        register_momentum();
+        assert!(matches!(
+            Strategy::new(StrategyType::Custom("UNREGISTERED".to_string()), params(), vec![PAIR.to_string()]),
+            Err(StrategyError::ValidationError(_))
+        ));
+        let no_exchange = StrategyParams {
+            exchanges: Vec::new(),
+            ..params()
+        };
+        assert!(Strategy::new(StrategyType::Custom(MOMENTUM.to_string()), no_exchange, vec![PAIR.to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_builtin_types_keep_their_names() {
+        for (kind, name) in [
+            (StrategyType::Grid, "GRID"),
+            (StrategyType::Arbitrage, "ARBITRAGE"),
+            (StrategyType::MLBased, "M_L_BASED"),
+            (StrategyType::MarketMaking, "MARKET_MAKING"),
+        ] {
+            assert_eq!(serde_json::to_value(&kind).unwrap(), name);
+            assert_eq!(serde_json::from_value::<StrategyType>(name.into()).unwrap(), kind);
+            assert!(StrategyRegistry::default().contains(&kind));
+        }
+    }
+}
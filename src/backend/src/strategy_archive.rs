@@ -0,0 +1,548 @@
+//! Soft deletion and archival of strategies. Deleting a strategy terminates it, cancels its
+//! working orders and either flattens its open positions or transfers them to the designated
+//! manual strategy, then stamps it deleted. Deleted strategies keep their ID, trades, equity
+//! and attribution history: they are hidden from default listings, their ID cannot be
+//! reused, downstream jobs record no new data for them, and they can be restored within the
+//! retention window.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - parking_lot = "0.12"
+//! - utoipa = "3.5"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::RwLock as SyncRwLock;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::models::strategy::{StrategyAuditEntry, StrategySnapshot, StrategyState};
+
+// Strategy archive constants
+const METRICS_PREFIX: &str = "trading_bot.strategy_archive";
+const DEFAULT_RETENTION_DAYS: i64 = 30;
+const DEFAULT_MANUAL_STRATEGY_ID: &str = "manual";
+const AUDIT_DELETED: &str = "deleted";
+const AUDIT_RESTORED: &str = "restored";
+
+/// Strategy archive errors
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    #[error("strategy not found: {0}")]
+    NotFound(String),
+    #[error("strategy already deleted: {0}")]
+    AlreadyDeleted(String),
+    #[error("strategy is not deleted: {0}")]
+    NotDeleted(String),
+    #[error("strategy {strategy_id} was deleted at {deleted_at}, outside the restore window")]
+    RetentionExpired {
+        strategy_id: String,
+        deleted_at: DateTime<Utc>,
+    },
+    #[error("invalid position policy: {0}")]
+    InvalidPolicy(String),
+    #[error("failed to flatten {trading_pair}: {reason}")]
+    Flatten { trading_pair: String, reason: String },
+}
+
+/// What happens to a deleted strategy's open positions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionPolicy {
+    /// Close every open position with a market order
+    #[default]
+    Flatten,
+    /// Hand open positions to the designated manual strategy
+    Transfer,
+}
+
+/// An open position closed or transferred by a deletion
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct PositionDisposition {
+    pub trading_pair: String,
+    /// Net size the strategy held; negative for shorts
+    pub size: Decimal,
+}
+
+/// Result of deleting a strategy
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct DeletionOutcome {
+    pub strategy: StrategySnapshot,
+    pub policy: PositionPolicy,
+    pub cancelled_orders: usize,
+    pub positions: Vec<PositionDisposition>,
+    /// Strategy that took over the positions under the transfer policy
+    pub transferred_to: Option<String>,
+    /// Restores are refused after this time
+    pub restorable_until: DateTime<Utc>,
+}
+
+/// Answers whether a strategy has been soft-deleted, for jobs that stop recording new data
+pub trait DeletionRegistry: Send + Sync {
+    fn is_deleted(&self, strategy_id: &str) -> bool;
+}
+
+/// Strategy lifecycle and position operations a deletion or restore needs from the bot
+#[async_trait]
+pub trait ArchiveTarget: Send + Sync {
+    /// Every registered strategy, deleted ones included
+    async fn strategies(&self) -> Vec<StrategySnapshot>;
+    /// Moves a strategy to `state` and sets or clears its deletion time; false when unknown
+    async fn set_lifecycle(&self, strategy_id: &str, state: StrategyState, deleted_at: Option<DateTime<Utc>>) -> bool;
+    /// Cancels the strategy's working orders, returning how many were cancelled
+    fn cancel_orders(&self, strategy_id: &str) -> usize;
+    /// Net open size per pair held by the strategy, shorts negative
+    async fn strategy_positions(&self, strategy_id: &str) -> Vec<(String, Decimal)>;
+    /// Closes a position of the given signed size with a market order
+    async fn flatten_position(&self, strategy_id: &str, trading_pair: &str, size: Decimal) -> Result<(), String>;
+    /// Reassigns a strategy's position on a pair to another strategy
+    async fn transfer_position(&self, from: &str, to: &str, trading_pair: &str) -> Decimal;
+    async fn record_audit(&self, entry: StrategyAuditEntry);
+}
+
+/// Restore window and the strategy transferred positions are handed to
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveConfig {
+    pub retention: chrono::Duration,
+    pub manual_strategy_id: String,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            retention: chrono::Duration::days(DEFAULT_RETENTION_DAYS),
+            manual_strategy_id: DEFAULT_MANUAL_STRATEGY_ID.to_string(),
+        }
+    }
+}
+
+/// Deletes and restores strategies, tracking which are deleted for downstream jobs
+pub struct StrategyArchive {
+    config: ArchiveConfig,
+    target: Arc<dyn ArchiveTarget>,
+    deleted: SyncRwLock<HashMap<String, DateTime<Utc>>>,
+}
+
+impl std::fmt::Debug for StrategyArchive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StrategyArchive")
+            .field("config", &self.config)
+            .field("deleted", &self.deleted.read().len())
+            .finish()
+    }
+}
+
+impl StrategyArchive {
+    pub fn new(config: ArchiveConfig, target: Arc<dyn ArchiveTarget>) -> Self {
+        Self {
+            config,
+            target,
+            deleted: SyncRwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Rebuilds the deleted set from the registered strategies, e.g. after a snapshot restore
+    pub async fn load(&self) -> usize {
+        let deleted: HashMap<String, DateTime<Utc>> = self
+            .target
+            .strategies()
+            .await
+            .into_iter()
+            .filter_map(|strategy| strategy.deleted_at.map(|at| (strategy.strategy_id, at)))
+            .collect();
+        let count = deleted.len();
+        *self.deleted.write() = deleted;
+        count
+    }
+
+    async fn strategy(&self, strategy_id: &str) -> Result<StrategySnapshot, ArchiveError> {
+        self.target
+            .strategies()
+            .await
+            .into_iter()
+            .find(|strategy| strategy.strategy_id == strategy_id)
+            .ok_or_else(|| ArchiveError::NotFound(strategy_id.to_string()))
+    }
+
+    /// Terminates a strategy, disposes of its open positions per `policy` and marks it
+    /// deleted. A failed flatten leaves the strategy terminated but not deleted, so the
+    /// deletion can be retried.
+    pub async fn delete(
+        &self,
+        strategy_id: &str,
+        policy: PositionPolicy,
+        now: DateTime<Utc>,
+    ) -> Result<DeletionOutcome, ArchiveError> {
+        let strategy = self.strategy(strategy_id).await?;
+        if strategy.deleted_at.is_some() {
+            return Err(ArchiveError::AlreadyDeleted(strategy_id.to_string()));
+        }
+        let manual = &self.config.manual_strategy_id;
+        if policy == PositionPolicy::Transfer && strategy_id == manual {
+            return Err(ArchiveError::InvalidPolicy(format!(
+                "{} is the manual strategy positions are transferred to",
+                manual
+            )));
+        }
+
+        self.target
+            .set_lifecycle(strategy_id, StrategyState::Terminated, None)
+            .await;
+        let cancelled_orders = self.target.cancel_orders(strategy_id);
+
+        let mut positions = Vec::new();
+        for (trading_pair, size) in self.target.strategy_positions(strategy_id).await {
+            match policy {
+                PositionPolicy::Flatten => {
+                    if let Err(reason) = self.target.flatten_position(strategy_id, &trading_pair, size).await {
+                        counter!(format!("{}.flatten_failures", METRICS_PREFIX), 1);
+                        warn!(strategy_id, trading_pair = %trading_pair, "Deletion halted: {}", reason);
+                        return Err(ArchiveError::Flatten { trading_pair, reason });
+                    }
+                }
+                PositionPolicy::Transfer => {
+                    self.target.transfer_position(strategy_id, manual, &trading_pair).await;
+                }
+            }
+            positions.push(PositionDisposition { trading_pair, size });
+        }
+
+        self.target
+            .set_lifecycle(strategy_id, StrategyState::Terminated, Some(now))
+            .await;
+        self.deleted.write().insert(strategy_id.to_string(), now);
+
+        let transferred_to = (policy == PositionPolicy::Transfer).then(|| manual.clone());
+        let detail = match &transferred_to {
+            Some(to) => format!("deleted; {} open positions transferred to {}", positions.len(), to),
+            None => format!("deleted; {} open positions flattened", positions.len()),
+        };
+        info!(strategy_id, cancelled_orders, "Strategy {}", detail);
+        counter!(format!("{}.deleted", METRICS_PREFIX), 1);
+        self.target
+            .record_audit(StrategyAuditEntry::new(strategy_id, AUDIT_DELETED, detail))
+            .await;
+
+        Ok(DeletionOutcome {
+            strategy: self.strategy(strategy_id).await?,
+            policy,
+            cancelled_orders,
+            positions,
+            transferred_to,
+            restorable_until: now + self.config.retention,
+        })
+    }
+
+    /// Restores a deleted strategy within the retention window; it comes back inactive
+    pub async fn restore(&self, strategy_id: &str, now: DateTime<Utc>) -> Result<StrategySnapshot, ArchiveError> {
+        let strategy = self.strategy(strategy_id).await?;
+        let deleted_at = strategy
+            .deleted_at
+            .ok_or_else(|| ArchiveError::NotDeleted(strategy_id.to_string()))?;
+        if now - deleted_at > self.config.retention {
+            return Err(ArchiveError::RetentionExpired {
+                strategy_id: strategy_id.to_string(),
+                deleted_at,
+            });
+        }
+
+        self.target
+            .set_lifecycle(strategy_id, StrategyState::Inactive, None)
+            .await;
+        self.deleted.write().remove(strategy_id);
+
+        info!(strategy_id, %deleted_at, "Strategy restored");
+        counter!(format!("{}.restored", METRICS_PREFIX), 1);
+        self.target
+            .record_audit(StrategyAuditEntry::new(
+                strategy_id,
+                AUDIT_RESTORED,
+                format!("restored after deletion at {}", deleted_at),
+            ))
+            .await;
+        self.strategy(strategy_id).await
+    }
+}
+
+impl DeletionRegistry for StrategyArchive {
+    fn is_deleted(&self, strategy_id: &str) -> bool {
+        self.deleted.read().contains_key(strategy_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::strategy::{Strategy, StrategyParams, StrategyType};
+    use crate::supervision::{StrategySupervisor, SupervisionConfig, SupervisionError};
+    use crate::utils::percent::{Bps, Percent};
+    use chrono::TimeZone;
+    use parking_lot::Mutex;
+    use rust_decimal_macros::dec;
+    use tokio::sync::RwLock;
+
+    /// Strategies in the shared map with per-strategy positions and recorded side effects
+    struct TestBot {
+        strategies: Arc<RwLock<HashMap<String, Strategy>>>,
+        positions: Mutex<HashMap<String, Vec<(String, Decimal)>>>,
+        flattened: Mutex<Vec<(String, String, Decimal)>>,
+        failing_pair: Option<String>,
+        audit: Mutex<Vec<StrategyAuditEntry>>,
+    }
+
+    impl TestBot {
+        fn new(strategies: Arc<RwLock<HashMap<String, Strategy>>>) -> Self {
+            Self {
+                strategies,
+                positions: Mutex::new(HashMap::new()),
+                flattened: Mutex::new(Vec::new()),
+                failing_pair: None,
+                audit: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ArchiveTarget for TestBot {
+        async fn strategies(&self) -> Vec<StrategySnapshot> {
+            self.strategies
+                .read()
+                .await
+                .iter()
+                .map(|(strategy_id, strategy)| strategy.snapshot(strategy_id))
+                .collect()
+        }
+
+        async fn set_lifecycle(
+            &self,
+            strategy_id: &str,
+            state: StrategyState,
+            deleted_at: Option<DateTime<Utc>>,
+        ) -> bool {
+            match self.strategies.write().await.get_mut(strategy_id) {
+                Some(strategy) => {
+                    strategy.state = state;
+                    strategy.deleted_at = deleted_at;
+                    true
+                }
+                None => false,
+            }
+        }
+
+        fn cancel_orders(&self, _: &str) -> usize {
+            1
+        }
+
+        async fn strategy_positions(&self, strategy_id: &str) -> Vec<(String, Decimal)> {
+            self.positions.lock().get(strategy_id).cloned().unwrap_or_default()
+        }
+
+        async fn flatten_position(&self, strategy_id: &str, trading_pair: &str, size: Decimal) -> Result<(), String> {
+            if self.failing_pair.as_deref() == Some(trading_pair) {
+                return Err("no fresh price".to_string());
+            }
+            self.flattened
+                .lock()
+                .push((strategy_id.to_string(), trading_pair.to_string(), size));
+            let mut positions = self.positions.lock();
+            if let Some(open) = positions.get_mut(strategy_id) {
+                open.retain(|(pair, _)| pair != trading_pair);
+            }
+            Ok(())
+        }
+
+        async fn transfer_position(&self, from: &str, to: &str, trading_pair: &str) -> Decimal {
+            let mut positions = self.positions.lock();
+            let moved: Vec<(String, Decimal)> = positions
+                .get_mut(from)
+                .map(|open| {
+                    let moved = open.iter().filter(|(pair, _)| pair == trading_pair).cloned().collect();
+                    open.retain(|(pair, _)| pair != trading_pair);
+                    moved
+                })
+                .unwrap_or_default();
+            let size = moved.iter().map(|(_, size)| *size).sum();
+            positions.entry(to.to_string()).or_default().extend(moved);
+            size
+        }
+
+        async fn record_audit(&self, entry: StrategyAuditEntry) {
+            self.audit.lock().push(entry);
+        }
+    }
+
+    fn strategy() -> Strategy {
+        let mut strategy = Strategy::new(
+            StrategyType::Grid,
+            StrategyParams {
+                position_size_bps: Bps::new(1000),
+                grid_levels: Some(10),
+                stop_loss_pct: Percent::from_percent(dec!(-5)),
+                take_profit_pct: Percent::from_percent(dec!(1)),
+                max_slippage_bps: Bps::new(100),
+                exchanges: vec!["jupiter".to_string()],
+                risk_factor: dec!(0.5),
+                allowed_regimes: Vec::new(),
+                market_making: None,
+            },
+            vec!["SOL/USDC".to_string(), "ORCA/USDC".to_string()],
+        )
+        .unwrap();
+        strategy.state = StrategyState::Active;
+        strategy
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap()
+    }
+
+    fn setup() -> (Arc<RwLock<HashMap<String, Strategy>>>, Arc<TestBot>) {
+        let strategies = Arc::new(RwLock::new(HashMap::from([("grid".to_string(), strategy())])));
+        let bot = Arc::new(TestBot::new(strategies.clone()));
+        bot.positions.lock().insert(
+            "grid".to_string(),
+            vec![("ORCA/USDC".to_string(), dec!(-40)), ("SOL/USDC".to_string(), dec!(12))],
+        );
+        (strategies, bot)
+    }
+
+    #[tokio::test]
+    async fn test_delete_with_flatten() {
+        let (strategies, bot) = setup();
+        let archive = StrategyArchive::new(ArchiveConfig::default(), bot.clone());
+
+        let outcome = archive.delete("grid", PositionPolicy::Flatten, now()).await.unwrap();
+        assert_eq!(outcome.strategy.state, StrategyState::Terminated);
+        assert_eq!(outcome.strategy.deleted_at, Some(now()));
+        assert_eq!(outcome.cancelled_orders, 1);
+        assert_eq!(outcome.positions.len(), 2);
+        assert_eq!(outcome.transferred_to, None);
+        assert_eq!(outcome.restorable_until, now() + chrono::Duration::days(30));
+        assert_eq!(
+            *bot.flattened.lock(),
+            vec![
+                ("grid".to_string(), "ORCA/USDC".to_string(), dec!(-40)),
+                ("grid".to_string(), "SOL/USDC".to_string(), dec!(12)),
+            ]
+        );
+        assert!(archive.is_deleted("grid"));
+        assert_eq!(bot.audit.lock()[0].action, AUDIT_DELETED);
+        // The strategy and its history stay registered under its ID
+        assert!(strategies.read().await["grid"].is_deleted());
+
+        assert!(matches!(
+            archive.delete("grid", PositionPolicy::Flatten, now()).await,
+            Err(ArchiveError::AlreadyDeleted(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failed_flatten_leaves_strategy_undeleted() {
+        let strategies = Arc::new(RwLock::new(HashMap::from([("grid".to_string(), strategy())])));
+        let mut bot = TestBot::new(strategies.clone());
+        bot.failing_pair = Some("SOL/USDC".to_string());
+        bot.positions
+            .lock()
+            .insert("grid".to_string(), vec![("SOL/USDC".to_string(), dec!(12))]);
+        let archive = StrategyArchive::new(ArchiveConfig::default(), Arc::new(bot));
+
+        assert!(matches!(
+            archive.delete("grid", PositionPolicy::Flatten, now()).await,
+            Err(ArchiveError::Flatten { .. })
+        ));
+        let strategies = strategies.read().await;
+        let grid = &strategies["grid"];
+        assert_eq!(grid.state, StrategyState::Terminated);
+        assert!(!grid.is_deleted());
+        assert!(!archive.is_deleted("grid"));
+    }
+
+    #[tokio::test]
+    async fn test_delete_with_transfer() {
+        let (_, bot) = setup();
+        let archive = StrategyArchive::new(ArchiveConfig::default(), bot.clone());
+
+        let outcome = archive.delete("grid", PositionPolicy::Transfer, now()).await.unwrap();
+        assert_eq!(outcome.transferred_to.as_deref(), Some(DEFAULT_MANUAL_STRATEGY_ID));
+        assert!(bot.flattened.lock().is_empty());
+        assert!(bot.strategy_positions("grid").await.is_empty());
+        assert_eq!(
+            bot.strategy_positions(DEFAULT_MANUAL_STRATEGY_ID).await,
+            vec![("ORCA/USDC".to_string(), dec!(-40)), ("SOL/USDC".to_string(), dec!(12))]
+        );
+
+        let manual_strategies = Arc::new(RwLock::new(HashMap::from([(
+            DEFAULT_MANUAL_STRATEGY_ID.to_string(),
+            strategy(),
+        )])));
+        let archive = StrategyArchive::new(ArchiveConfig::default(), Arc::new(TestBot::new(manual_strategies)));
+        assert!(matches!(
+            archive
+                .delete(DEFAULT_MANUAL_STRATEGY_ID, PositionPolicy::Transfer, now())
+                .await,
+            Err(ArchiveError::InvalidPolicy(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_restore_within_retention_window() {
+        let (strategies, bot) = setup();
+        let archive = StrategyArchive::new(ArchiveConfig::default(), bot.clone());
+        archive.delete("grid", PositionPolicy::Flatten, now()).await.unwrap();
+
+        let restored = archive
+            .restore("grid", now() + chrono::Duration::days(29))
+            .await
+            .unwrap();
+        assert_eq!(restored.state, StrategyState::Inactive);
+        assert_eq!(restored.deleted_at, None);
+        assert!(!archive.is_deleted("grid"));
+        assert_eq!(bot.audit.lock().last().unwrap().action, AUDIT_RESTORED);
+        assert!(matches!(
+            archive.restore("grid", now()).await,
+            Err(ArchiveError::NotDeleted(_))
+        ));
+
+        archive.delete("grid", PositionPolicy::Flatten, now()).await.unwrap();
+        assert!(matches!(
+            archive.restore("grid", now() + chrono::Duration::days(31)).await,
+            Err(ArchiveError::RetentionExpired { .. })
+        ));
+        assert!(strategies.read().await["grid"].is_deleted());
+
+        // A restarted archive picks the deleted strategies back up from the bot
+        let reloaded = StrategyArchive::new(ArchiveConfig::default(), bot);
+        assert_eq!(reloaded.load().await, 1);
+        assert!(reloaded.is_deleted("grid"));
+    }
+
+    #[tokio::test]
+    async fn test_deleted_strategy_id_not_reusable() {
+        let (strategies, bot) = setup();
+        let archive = StrategyArchive::new(ArchiveConfig::default(), bot);
+        let supervisor = StrategySupervisor::new(SupervisionConfig::default(), strategies.clone());
+        archive.delete("grid", PositionPolicy::Flatten, now()).await.unwrap();
+
+        assert!(matches!(
+            supervisor.add_strategy("grid", strategy()).await,
+            Err(SupervisionError::Deleted(_))
+        ));
+        assert!(supervisor.add_strategy("grid-2", strategy()).await.is_ok());
+
+        // Once restored, the ID is simply taken by the live strategy
+        archive.restore("grid", now()).await.unwrap();
+        assert!(matches!(
+            supervisor.add_strategy("grid", strategy()).await,
+            Err(SupervisionError::AlreadyRegistered(_))
+        ));
+    }
+}
@@ -0,0 +1,408 @@
+//! Concurrent strategy driver. Every (strategy, pair) combination runs in its own lane fed
+//! by that pair's market data, so a slow pair or strategy no longer delays the others. A
+//! global semaphore bounds concurrent strategy runs, each lane runs one at a time to keep
+//! per-pair ordering, and a panicking strategy is auto-paused instead of taking the driver
+//! down. With a regime detector attached, runs are skipped while the pair's market regime is
+//! outside the strategy's allowed regimes. With a quarantine list attached, collector data
+//! for quarantined pairs is flagged before dispatch, or withheld when configured to.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - futures = "0.3"
+//! - metrics = "0.20"
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use futures::FutureExt;
+use metrics::{counter, histogram};
+use parking_lot::Mutex;
+use tokio::sync::{mpsc, Semaphore};
+use tracing::{debug, error, warn};
+
+use crate::models::MarketData;
+use crate::quarantine::QuarantineList;
+use crate::regime::{MarketRegime, RegimeDetector};
+use crate::supervision::{PauseTrigger, StrategySupervisor};
+use crate::Error;
+
+// Driver constants
+const METRICS_PREFIX: &str = "trading_bot.strategy_driver";
+const DEFAULT_MAX_CONCURRENT_EXECUTIONS: usize = 16;
+const DEFAULT_LANE_CAPACITY: usize = 64;
+
+/// Concurrency bounds for the strategy driver
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriverConfig {
+    /// Strategy runs in flight across every lane
+    pub max_concurrent_executions: usize,
+    /// Market data buffered per lane before updates are dropped
+    pub lane_capacity: usize,
+}
+
+impl Default for DriverConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_executions: DEFAULT_MAX_CONCURRENT_EXECUTIONS,
+            lane_capacity: DEFAULT_LANE_CAPACITY,
+        }
+    }
+}
+
+/// Runs strategies against market data on behalf of the driver
+#[async_trait]
+pub trait StrategyRunner: Send + Sync {
+    /// Strategies currently trading a pair
+    async fn strategies_for(&self, trading_pair: &str) -> Vec<String>;
+
+    /// Runs one strategy against fresh market data, returning the signals it produced
+    async fn run(&self, strategy_id: &str, market_data: &MarketData) -> Result<usize, Error>;
+
+    /// Market regimes a strategy may trade in; empty allows every regime
+    async fn allowed_regimes(&self, _strategy_id: &str) -> Vec<MarketRegime> {
+        Vec::new()
+    }
+}
+
+/// Drives strategies concurrently across pairs
+pub struct StrategyDriver {
+    config: DriverConfig,
+    runner: Arc<dyn StrategyRunner>,
+    supervisor: Arc<StrategySupervisor>,
+    permits: Arc<Semaphore>,
+    lanes: Mutex<HashMap<(String, String), mpsc::Sender<Arc<MarketData>>>>,
+    regimes: Option<Arc<RegimeDetector>>,
+    quarantine: Option<Arc<QuarantineList>>,
+}
+
+impl std::fmt::Debug for StrategyDriver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StrategyDriver")
+            .field("config", &self.config)
+            .field("lanes", &self.lanes.lock().len())
+            .finish()
+    }
+}
+
+impl StrategyDriver {
+    pub fn new(
+        config: DriverConfig,
+        runner: Arc<dyn StrategyRunner>,
+        supervisor: Arc<StrategySupervisor>,
+    ) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(config.max_concurrent_executions.max(1))),
+            config,
+            runner,
+            supervisor,
+            lanes: Mutex::new(HashMap::new()),
+            regimes: None,
+            quarantine: None,
+        }
+    }
+
+    /// Gates strategy runs on each pair's market regime
+    pub fn with_regimes(mut self, regimes: Arc<RegimeDetector>) -> Self {
+        self.regimes = Some(regimes);
+        self
+    }
+
+    /// Flags or withholds collector data for quarantined pairs
+    pub fn with_quarantine(mut self, quarantine: Arc<QuarantineList>) -> Self {
+        self.quarantine = Some(quarantine);
+        self
+    }
+
+    /// Routes market data to the lane of every strategy trading its pair, starting lanes
+    /// for newly registered strategies and closing those of removed ones. Returns how many
+    /// lanes accepted the update. Lanes share one copy of the update.
+    pub async fn dispatch(&self, market_data: MarketData) -> usize {
+        let market_data = Arc::new(market_data);
+        let trading_pair = market_data.trading_pair().to_string();
+        let strategy_ids = self.runner.strategies_for(&trading_pair).await;
+
+        let senders: Vec<(String, mpsc::Sender<Arc<MarketData>>)> = {
+            let mut lanes = self.lanes.lock();
+            lanes.retain(|(strategy_id, pair), _| {
+                pair != &trading_pair || strategy_ids.contains(strategy_id)
+            });
+            strategy_ids
+                .into_iter()
+                .map(|strategy_id| {
+                    let sender = lanes
+                        .entry((strategy_id.clone(), trading_pair.clone()))
+                        .or_insert_with(|| self.spawn_lane(&strategy_id, &trading_pair))
+                        .clone();
+                    (strategy_id, sender)
+                })
+                .collect()
+        };
+
+        let mut accepted = 0;
+        for (strategy_id, sender) in senders {
+            match sender.try_send(market_data.clone()) {
+                Ok(()) => accepted += 1,
+                Err(_) => {
+                    counter!(format!("{}.dropped", METRICS_PREFIX), 1, "strategy_id" => strategy_id.clone());
+                    warn!(strategy_id = %strategy_id, trading_pair = %trading_pair, "Strategy lane full, dropping market data");
+                }
+            }
+        }
+        accepted
+    }
+
+    /// Number of running (strategy, pair) lanes
+    pub fn lane_count(&self) -> usize {
+        self.lanes.lock().len()
+    }
+
+    /// Dispatches every market data update from the collectors
+    pub fn spawn_market_data_listener(
+        self: Arc<Self>,
+        mut market_data: mpsc::Receiver<MarketData>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            while let Some(update) = market_data.recv().await {
+                let update = match &self.quarantine {
+                    Some(quarantine) => match quarantine.screen(update) {
+                        Some(update) => update,
+                        None => continue,
+                    },
+                    None => update,
+                };
+                self.dispatch(update).await;
+            }
+        })
+    }
+
+    /// Runs one strategy on one pair in arrival order until the lane is closed
+    fn spawn_lane(&self, strategy_id: &str, trading_pair: &str) -> mpsc::Sender<Arc<MarketData>> {
+        let (tx, mut rx) = mpsc::channel::<Arc<MarketData>>(self.config.lane_capacity.max(1));
+        let strategy_id = strategy_id.to_string();
+        let trading_pair = trading_pair.to_string();
+        let runner = self.runner.clone();
+        let supervisor = self.supervisor.clone();
+        let permits = self.permits.clone();
+        let regimes = self.regimes.clone();
+
+        tokio::spawn(async move {
+            while let Some(market_data) = rx.recv().await {
+                if supervisor.is_paused(&strategy_id) {
+                    continue;
+                }
+                if let Some(regimes) = &regimes {
+                    let allowed = runner.allowed_regimes(&strategy_id).await;
+                    let gate = regimes.gate(&strategy_id, &trading_pair, &allowed);
+                    if let Some(entry) = gate.audit {
+                        supervisor.record_audit(entry).await;
+                    }
+                    if !gate.open {
+                        counter!(format!("{}.regime_gated", METRICS_PREFIX), 1, "strategy_id" => strategy_id.clone());
+                        continue;
+                    }
+                }
+                let Ok(_permit) = permits.acquire().await else {
+                    break;
+                };
+
+                let started = Instant::now();
+                let result = AssertUnwindSafe(runner.run(&strategy_id, &market_data))
+                    .catch_unwind()
+                    .await;
+                histogram!(
+                    format!("{}.run_ms", METRICS_PREFIX),
+                    started.elapsed().as_millis() as f64,
+                    "strategy_id" => strategy_id.clone()
+                );
+
+                match result {
+                    Ok(Ok(signals)) => debug!(strategy_id = %strategy_id, trading_pair = %trading_pair, signals, "Strategy run completed"),
+                    Ok(Err(e)) => warn!(strategy_id = %strategy_id, trading_pair = %trading_pair, "Strategy run failed: {}", e),
+                    Err(panic) => {
+                        counter!(format!("{}.panics", METRICS_PREFIX), 1, "strategy_id" => strategy_id.clone());
+                        let message = panic_message(panic.as_ref());
+                        error!(strategy_id = %strategy_id, trading_pair = %trading_pair, "Strategy panicked: {}", message);
+                        supervisor
+                            .pause(
+                                &strategy_id,
+                                PauseTrigger::Panicked { trading_pair: trading_pair.clone(), message },
+                            )
+                            .await;
+                    }
+                }
+            }
+        });
+        tx
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_collector::ohlcv::{Candle, CandleInterval};
+    use crate::regime::{RegimeConfig, AUDIT_REGIME_DISABLED};
+    use crate::supervision::SupervisionConfig;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::time::Duration;
+    use tokio::sync::RwLock;
+
+    const PAIRS: [&str; 4] = ["SOL/USDC", "BONK/USDC", "JUP/USDC", "RAY/USDC"];
+
+    /// Runs "slow" for 300ms, panics "faulty" and completes everything else immediately,
+    /// recording each fast run's latency from market data creation
+    #[derive(Default)]
+    struct MockRunner {
+        latencies: Mutex<Vec<(String, i64)>>,
+    }
+
+    #[async_trait]
+    impl StrategyRunner for MockRunner {
+        async fn strategies_for(&self, _trading_pair: &str) -> Vec<String> {
+            vec!["slow".to_string(), "fast".to_string(), "faulty".to_string()]
+        }
+
+        async fn run(&self, strategy_id: &str, market_data: &MarketData) -> Result<usize, Error> {
+            match strategy_id {
+                "slow" => tokio::time::sleep(Duration::from_millis(300)).await,
+                "faulty" => panic!("index out of bounds"),
+                _ => {}
+            }
+            let latency = (Utc::now() - market_data.timestamp()).num_milliseconds();
+            self.latencies.lock().push((strategy_id.to_string(), latency));
+            Ok(0)
+        }
+    }
+
+    fn tick(trading_pair: &str) -> MarketData {
+        MarketData::new(trading_pair.to_string(), "jupiter".to_string(), dec!(10), dec!(1)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_slow_strategy_does_not_delay_others() {
+        let supervisor = Arc::new(StrategySupervisor::new(
+            SupervisionConfig::default(),
+            Arc::new(RwLock::new(HashMap::new())),
+        ));
+        for strategy_id in ["slow", "fast", "faulty"] {
+            supervisor.register(strategy_id, PAIRS.iter().map(|pair| pair.to_string()).collect());
+        }
+        let runner = Arc::new(MockRunner::default());
+        let driver = Arc::new(StrategyDriver::new(
+            DriverConfig { max_concurrent_executions: 8, lane_capacity: 64 },
+            runner.clone(),
+            supervisor.clone(),
+        ));
+
+        for _ in 0..10 {
+            for pair in PAIRS {
+                driver.dispatch(tick(pair)).await;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(driver.lane_count(), PAIRS.len() * 3);
+
+        // Every fast run landed promptly while slow lanes were still working through their backlog
+        let latencies = runner.latencies.lock().clone();
+        let fast: Vec<i64> = latencies
+            .iter()
+            .filter(|(strategy_id, _)| strategy_id == "fast")
+            .map(|(_, latency)| *latency)
+            .collect();
+        assert_eq!(fast.len(), 10 * PAIRS.len());
+        assert!(fast.iter().all(|latency| *latency < 100), "fast latencies {:?}", fast);
+        let slow_runs = latencies.iter().filter(|(strategy_id, _)| strategy_id == "slow").count();
+        assert!(slow_runs < 10 * PAIRS.len());
+
+        // The panic paused only the faulty strategy
+        assert!(supervisor.is_paused("faulty"));
+        assert!(!supervisor.is_paused("fast"));
+        assert!(!supervisor.is_paused("slow"));
+        assert!(matches!(
+            supervisor.health("faulty").unwrap().paused,
+            Some(PauseTrigger::Panicked { .. })
+        ));
+    }
+
+    /// "grid" trades only quiet markets, "momentum" only trending ones
+    #[derive(Default)]
+    struct RegimeRunner {
+        runs: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl StrategyRunner for RegimeRunner {
+        async fn strategies_for(&self, _trading_pair: &str) -> Vec<String> {
+            vec!["grid".to_string(), "momentum".to_string()]
+        }
+
+        async fn run(&self, strategy_id: &str, _market_data: &MarketData) -> Result<usize, Error> {
+            self.runs.lock().push(strategy_id.to_string());
+            Ok(0)
+        }
+
+        async fn allowed_regimes(&self, strategy_id: &str) -> Vec<MarketRegime> {
+            match strategy_id {
+                "grid" => vec![MarketRegime::Quiet],
+                _ => vec![MarketRegime::Trending],
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_regime_gates_strategies() {
+        let supervisor = Arc::new(StrategySupervisor::new(
+            SupervisionConfig::default(),
+            Arc::new(RwLock::new(HashMap::new())),
+        ));
+        let regimes = Arc::new(RegimeDetector::new(RegimeConfig {
+            window: 10,
+            ..RegimeConfig::default()
+        }));
+        let start = Utc::now();
+        for i in 0..10 {
+            let close = Decimal::from_f64_retain(100.0 * 1.01f64.powi(i)).unwrap().round_dp(6);
+            let candle = Candle::from_parts(
+                "SOL/USDC".to_string(),
+                CandleInterval::OneMinute,
+                start + chrono::Duration::minutes(i as i64),
+                close,
+                close,
+                close,
+                close,
+                dec!(1000),
+                1,
+            );
+            regimes.observe(&candle).await;
+        }
+        assert_eq!(regimes.regime("SOL/USDC"), Some(MarketRegime::Trending));
+
+        let runner = Arc::new(RegimeRunner::default());
+        let driver = StrategyDriver::new(DriverConfig::default(), runner.clone(), supervisor.clone())
+            .with_regimes(regimes);
+        for _ in 0..3 {
+            driver.dispatch(tick("SOL/USDC")).await;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(*runner.runs.lock(), vec!["momentum".to_string(); 3]);
+        let audit = supervisor.audit_log("grid");
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].action, AUDIT_REGIME_DISABLED);
+        assert!(supervisor.audit_log("momentum").is_empty());
+    }
+}
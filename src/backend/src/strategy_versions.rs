@@ -0,0 +1,973 @@
+//! Strategy parameter versions and A/B comparison. Every parameter change is recorded as an
+//! immutable, numbered version and fills are tagged with the version that produced them so
+//! each version's results can be read in isolation. An A/B test runs a challenger version
+//! next to the active one on a split of the strategy's position size and, once its
+//! evaluation period ends, promotes whichever arm scored better on the chosen objective.
+//! Rolling back re-activates any earlier version in one call.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - sha2 = "0.10"
+//! - hex = "0.4"
+//! - parking_lot = "0.12"
+//! - utoipa = "3.5"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use parking_lot::RwLock as SyncRwLock;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::strategy::{Strategy, StrategyParams};
+use crate::performance::{PerformanceService, StrategyTrade, VersionTagger};
+use crate::supervision::StrategySupervisor;
+use crate::utils::percent::Bps;
+
+// Strategy versioning constants
+const METRICS_PREFIX: &str = "trading_bot.strategy_versions";
+const DEFAULT_EVALUATION_INTERVAL: Duration = Duration::from_secs(60);
+const DEFAULT_MIN_TRADES: u32 = 20;
+/// Author recorded for versions the bot creates itself, such as a strategy's starting parameters
+pub const SYSTEM_AUTHOR: &str = "system";
+/// Separates a strategy id from the version an A/B challenger trades under
+const VARIANT_SEPARATOR: &str = "@v";
+
+/// Strategy versioning errors
+#[derive(Error, Debug)]
+pub enum VersionError {
+    #[error("unknown strategy: {0}")]
+    UnknownStrategy(String),
+    #[error("unknown version {version} of strategy {strategy_id}")]
+    UnknownVersion { strategy_id: String, version: u32 },
+    #[error("invalid parameters: {0}")]
+    InvalidParameters(String),
+    #[error("invalid A/B test: {0}")]
+    InvalidAbTest(String),
+    #[error("strategy {0} already has an A/B test running")]
+    AbTestRunning(String),
+    #[error("store error: {0}")]
+    Store(String),
+}
+
+/// Id an A/B challenger trades under while its test runs
+pub fn variant_id(strategy_id: &str, version: u32) -> String {
+    format!("{}{}{}", strategy_id, VARIANT_SEPARATOR, version)
+}
+
+/// Splits an A/B challenger id into the strategy it is trialled for and its version
+pub fn parse_variant(strategy_id: &str) -> Option<(&str, u32)> {
+    let (base, version) = strategy_id.rsplit_once(VARIANT_SEPARATOR)?;
+    Some((base, version.parse().ok()?))
+}
+
+/// Whether a strategy id names an A/B challenger rather than a strategy of its own
+pub fn is_variant(strategy_id: &str) -> bool {
+    parse_variant(strategy_id).is_some()
+}
+
+/// Hex SHA-256 of the parameters' JSON encoding
+pub fn param_hash(parameters: &StrategyParams) -> String {
+    let encoded = serde_json::to_vec(parameters).expect("strategy parameters serialize");
+    hex::encode(Sha256::digest(&encoded))
+}
+
+/// One immutable parameter set a strategy has run with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StrategyVersion {
+    pub strategy_id: String,
+    /// Starts at 1 and increases with every parameter change
+    pub version: u32,
+    pub param_hash: String,
+    pub parameters: StrategyParams,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    /// Most recent time the version became the live one; null if it never has
+    pub activated_at: Option<DateTime<Utc>>,
+}
+
+/// Results of the trades one version closed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct VersionMetrics {
+    pub version: u32,
+    pub total_trades: u32,
+    pub realized_pnl: Decimal,
+    /// Fraction of trades that closed with positive P&L
+    pub win_rate: Decimal,
+    /// Mean over sample standard deviation of per-trade P&L; null below two trades or
+    /// without variance
+    pub sharpe_ratio: Option<f64>,
+    /// Largest peak-to-trough fall of cumulative P&L, starting from zero
+    pub max_drawdown: Decimal,
+}
+
+impl VersionMetrics {
+    /// Metrics over a version's trades in close order
+    pub fn from_trades(version: u32, trades: &[StrategyTrade]) -> Self {
+        let total_trades = trades.len() as u32;
+        let wins = trades.iter().filter(|trade| trade.realized_pnl > Decimal::ZERO).count() as u32;
+        let win_rate = if total_trades == 0 {
+            Decimal::ZERO
+        } else {
+            Decimal::from(wins) / Decimal::from(total_trades)
+        };
+
+        let mut cumulative = Decimal::ZERO;
+        let mut peak = Decimal::ZERO;
+        let mut max_drawdown = Decimal::ZERO;
+        for trade in trades {
+            cumulative += trade.realized_pnl;
+            peak = peak.max(cumulative);
+            max_drawdown = max_drawdown.max(peak - cumulative);
+        }
+
+        Self {
+            version,
+            total_trades,
+            realized_pnl: cumulative,
+            win_rate,
+            sharpe_ratio: per_trade_sharpe(trades),
+            max_drawdown,
+        }
+    }
+}
+
+fn per_trade_sharpe(trades: &[StrategyTrade]) -> Option<f64> {
+    let pnl: Vec<f64> = trades.iter().filter_map(|trade| trade.realized_pnl.to_f64()).collect();
+    if pnl.len() < 2 {
+        return None;
+    }
+    let mean = pnl.iter().sum::<f64>() / pnl.len() as f64;
+    let variance = pnl.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / (pnl.len() - 1) as f64;
+    let std_dev = variance.sqrt();
+    (std_dev > 0.0).then(|| mean / std_dev)
+}
+
+/// Metric an A/B test promotes on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AbObjective {
+    /// Realized P&L per unit of position size share
+    RealizedPnl,
+    WinRate,
+    SharpeRatio,
+    /// Smallest drawdown per unit of position size share
+    MaxDrawdown,
+}
+
+impl AbObjective {
+    /// Score of an arm trading `share` of the position size; higher is better
+    fn score(&self, metrics: &VersionMetrics, share: Decimal) -> Option<f64> {
+        match self {
+            Self::RealizedPnl => (metrics.realized_pnl / share).to_f64(),
+            Self::WinRate => metrics.win_rate.to_f64(),
+            Self::SharpeRatio => metrics.sharpe_ratio,
+            Self::MaxDrawdown => (-metrics.max_drawdown / share).to_f64(),
+        }
+    }
+}
+
+fn default_min_trades() -> u32 {
+    DEFAULT_MIN_TRADES
+}
+
+/// How a challenger version is trialled against the active one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AbTestConfig {
+    /// Share of the strategy's position size the challenger trades with, strictly between 0 and 1
+    pub challenger_share: Decimal,
+    pub evaluation_period_secs: u64,
+    pub objective: AbObjective,
+    /// Trades each arm needs before the challenger can be promoted
+    #[serde(default = "default_min_trades")]
+    pub min_trades: u32,
+}
+
+impl AbTestConfig {
+    fn validate(&self) -> Result<(), VersionError> {
+        if self.challenger_share <= Decimal::ZERO || self.challenger_share >= Decimal::ONE {
+            return Err(VersionError::InvalidAbTest(
+                "challenger share must be between 0 and 1".to_string(),
+            ));
+        }
+        if self.evaluation_period_secs == 0 {
+            return Err(VersionError::InvalidAbTest(
+                "evaluation period must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Lifecycle of an A/B test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AbTestStatus {
+    Running,
+    /// The challenger won and became the active version
+    Promoted,
+    /// The champion won or the challenger lacked trades
+    Retained,
+    /// Ended early by a rollback
+    Cancelled,
+}
+
+impl AbTestStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Promoted => "promoted",
+            Self::Retained => "retained",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// A challenger version trialled against the active one on split position size
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AbTest {
+    pub strategy_id: String,
+    /// Version active when the test started
+    pub champion: u32,
+    pub challenger: u32,
+    pub config: AbTestConfig,
+    pub status: AbTestStatus,
+    pub started_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub concluded_at: Option<DateTime<Utc>>,
+    /// Each arm's results over the evaluation period, once concluded
+    pub champion_metrics: Option<VersionMetrics>,
+    pub challenger_metrics: Option<VersionMetrics>,
+}
+
+/// Version that wins an A/B test: the challenger only when both arms closed enough trades
+/// and it scores strictly higher on the objective, otherwise the champion
+pub fn ab_winner(config: &AbTestConfig, champion: &VersionMetrics, challenger: &VersionMetrics) -> u32 {
+    if champion.total_trades < config.min_trades || challenger.total_trades < config.min_trades {
+        return champion.version;
+    }
+    let champion_score = config.objective.score(champion, Decimal::ONE - config.challenger_share);
+    let challenger_score = config.objective.score(challenger, config.challenger_share);
+    match (champion_score, challenger_score) {
+        (Some(champion_score), Some(challenger_score)) if challenger_score > champion_score => challenger.version,
+        _ => champion.version,
+    }
+}
+
+/// A strategy's versions with the results each produced
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct VersionHistory {
+    pub strategy_id: String,
+    pub active_version: Option<u32>,
+    /// Every version in ascending order
+    pub versions: Vec<StrategyVersion>,
+    /// Metrics of each version, in the same order
+    pub metrics: Vec<VersionMetrics>,
+    /// Most recent A/B test, running or concluded
+    pub ab_test: Option<AbTest>,
+}
+
+/// Persistence for strategy versions
+#[async_trait]
+pub trait VersionStore: Send + Sync {
+    async fn insert_version(&self, version: &StrategyVersion) -> Result<(), VersionError>;
+    async fn set_activated(&self, strategy_id: &str, version: u32, at: DateTime<Utc>) -> Result<(), VersionError>;
+    /// Every version of a strategy in ascending order
+    async fn versions(&self, strategy_id: &str) -> Result<Vec<StrategyVersion>, VersionError>;
+}
+
+/// A/B evaluation schedule
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionConfig {
+    pub evaluation_interval: Duration,
+}
+
+impl Default for VersionConfig {
+    fn default() -> Self {
+        Self {
+            evaluation_interval: DEFAULT_EVALUATION_INTERVAL,
+        }
+    }
+}
+
+fn active_of(history: &[StrategyVersion]) -> Option<&StrategyVersion> {
+    history
+        .iter()
+        .filter(|version| version.activated_at.is_some())
+        .max_by_key(|version| version.activated_at)
+}
+
+fn scaled(size: Bps, share: Decimal) -> Bps {
+    Bps::from_bps(size.as_bps() * share)
+}
+
+/// Records parameter versions, applies them to live strategies and runs A/B tests
+pub struct StrategyVersionService {
+    config: VersionConfig,
+    strategies: Arc<RwLock<HashMap<String, Strategy>>>,
+    performance: Arc<PerformanceService>,
+    supervisor: Arc<StrategySupervisor>,
+    store: SyncRwLock<Option<Arc<dyn VersionStore>>>,
+    versions: Mutex<HashMap<String, Vec<StrategyVersion>>>,
+    /// Active version per strategy, read synchronously when fills are tagged
+    active: SyncRwLock<HashMap<String, u32>>,
+    ab_tests: Mutex<HashMap<String, AbTest>>,
+}
+
+impl std::fmt::Debug for StrategyVersionService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StrategyVersionService")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl StrategyVersionService {
+    pub fn new(
+        config: VersionConfig,
+        strategies: Arc<RwLock<HashMap<String, Strategy>>>,
+        performance: Arc<PerformanceService>,
+        supervisor: Arc<StrategySupervisor>,
+    ) -> Self {
+        Self {
+            config,
+            strategies,
+            performance,
+            supervisor,
+            store: SyncRwLock::new(None),
+            versions: Mutex::new(HashMap::new()),
+            active: SyncRwLock::new(HashMap::new()),
+            ab_tests: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Persists versions; without a store they live only as long as the process
+    pub fn set_store(&self, store: Arc<dyn VersionStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    async fn load<'a>(
+        &self,
+        versions: &'a mut HashMap<String, Vec<StrategyVersion>>,
+        strategy_id: &str,
+    ) -> Result<&'a mut Vec<StrategyVersion>, VersionError> {
+        if !versions.contains_key(strategy_id) {
+            let store = self.store.read().clone();
+            let loaded = match store {
+                Some(store) => store.versions(strategy_id).await?,
+                None => Vec::new(),
+            };
+            if let Some(active) = active_of(&loaded) {
+                self.active.write().insert(strategy_id.to_string(), active.version);
+            }
+            versions.insert(strategy_id.to_string(), loaded);
+        }
+        Ok(versions.get_mut(strategy_id).expect("versions just loaded"))
+    }
+
+    async fn append(
+        &self,
+        strategy_id: &str,
+        history: &mut Vec<StrategyVersion>,
+        parameters: StrategyParams,
+        created_by: &str,
+        now: DateTime<Utc>,
+    ) -> Result<StrategyVersion, VersionError> {
+        let version = StrategyVersion {
+            strategy_id: strategy_id.to_string(),
+            version: history.last().map_or(1, |latest| latest.version + 1),
+            param_hash: param_hash(&parameters),
+            parameters,
+            created_by: created_by.to_string(),
+            created_at: now,
+            activated_at: None,
+        };
+        let store = self.store.read().clone();
+        if let Some(store) = store {
+            store.insert_version(&version).await?;
+        }
+        history.push(version.clone());
+        counter!(format!("{}.created", METRICS_PREFIX), 1);
+        Ok(version)
+    }
+
+    async fn activate(
+        &self,
+        strategy_id: &str,
+        history: &mut [StrategyVersion],
+        version: u32,
+        now: DateTime<Utc>,
+    ) -> Result<StrategyVersion, VersionError> {
+        let store = self.store.read().clone();
+        if let Some(store) = store {
+            store.set_activated(strategy_id, version, now).await?;
+        }
+        let entry = history
+            .iter_mut()
+            .find(|entry| entry.version == version)
+            .ok_or_else(|| VersionError::UnknownVersion {
+                strategy_id: strategy_id.to_string(),
+                version,
+            })?;
+        entry.activated_at = Some(now);
+        self.active.write().insert(strategy_id.to_string(), version);
+        Ok(entry.clone())
+    }
+
+    async fn apply(&self, strategy_id: &str, parameters: &StrategyParams) -> Result<(), VersionError> {
+        let mut strategies = self.strategies.write().await;
+        let strategy = strategies
+            .get_mut(strategy_id)
+            .ok_or_else(|| VersionError::UnknownStrategy(strategy_id.to_string()))?;
+        strategy
+            .set_parameters(parameters.clone())
+            .map_err(|e| VersionError::InvalidParameters(e.to_string()))?;
+        strategy.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Records the strategy's live parameters as version 1 if it has no versions yet
+    async fn ensure_baseline(
+        &self,
+        strategy_id: &str,
+        history: &mut Vec<StrategyVersion>,
+        created_by: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), VersionError> {
+        if !history.is_empty() {
+            return Ok(());
+        }
+        let parameters = self
+            .strategies
+            .read()
+            .await
+            .get(strategy_id)
+            .map(|strategy| strategy.parameters.clone())
+            .ok_or_else(|| VersionError::UnknownStrategy(strategy_id.to_string()))?;
+        let baseline = self.append(strategy_id, history, parameters, created_by, now).await?;
+        self.activate(strategy_id, history, baseline.version, now).await?;
+        Ok(())
+    }
+
+    /// Records a newly registered strategy's parameters as its first version
+    pub async fn record_initial(&self, strategy_id: &str, created_by: &str) -> Result<StrategyVersion, VersionError> {
+        let now = Utc::now();
+        let mut versions = self.versions.lock().await;
+        let history = self.load(&mut versions, strategy_id).await?;
+        self.ensure_baseline(strategy_id, history, created_by, now).await?;
+        Ok(active_of(history).or(history.last()).cloned().expect("baseline recorded"))
+    }
+
+    /// Records new parameters as the next version and makes it live; unchanged parameters
+    /// return the active version without creating one
+    pub async fn update_parameters(
+        &self,
+        strategy_id: &str,
+        parameters: StrategyParams,
+        created_by: &str,
+    ) -> Result<StrategyVersion, VersionError> {
+        parameters
+            .validate()
+            .map_err(|e| VersionError::InvalidParameters(e.to_string()))?;
+        let now = Utc::now();
+        let mut versions = self.versions.lock().await;
+        if self.running_test(strategy_id).await.is_some() {
+            return Err(VersionError::AbTestRunning(strategy_id.to_string()));
+        }
+        let history = self.load(&mut versions, strategy_id).await?;
+        self.ensure_baseline(strategy_id, history, SYSTEM_AUTHOR, now).await?;
+
+        let hash = param_hash(&parameters);
+        if let Some(active) = active_of(history).filter(|active| active.param_hash == hash) {
+            return Ok(active.clone());
+        }
+        let version = self.append(strategy_id, history, parameters, created_by, now).await?;
+        self.apply(strategy_id, &version.parameters).await?;
+        let version = self.activate(strategy_id, history, version.version, now).await?;
+
+        info!(strategy_id, version = version.version, created_by, "Strategy parameters updated");
+        Ok(version)
+    }
+
+    /// Makes an earlier version live again, ending any running A/B test
+    pub async fn rollback(&self, strategy_id: &str, version: u32) -> Result<StrategyVersion, VersionError> {
+        let now = Utc::now();
+        let mut versions = self.versions.lock().await;
+        let history = self.load(&mut versions, strategy_id).await?;
+        let target = history
+            .iter()
+            .find(|entry| entry.version == version)
+            .cloned()
+            .ok_or_else(|| VersionError::UnknownVersion {
+                strategy_id: strategy_id.to_string(),
+                version,
+            })?;
+
+        if let Some(test) = self.ab_tests.lock().await.get_mut(strategy_id) {
+            if test.status == AbTestStatus::Running {
+                self.end_test(test, AbTestStatus::Cancelled, now).await;
+            }
+        }
+        self.apply(strategy_id, &target.parameters).await?;
+        let activated = self.activate(strategy_id, history, version, now).await?;
+
+        counter!(format!("{}.rollbacks", METRICS_PREFIX), 1);
+        info!(strategy_id, version, "Strategy rolled back");
+        Ok(activated)
+    }
+
+    /// Versions of a strategy with per-version metrics and its latest A/B test
+    pub async fn history(&self, strategy_id: &str) -> Result<VersionHistory, VersionError> {
+        if !self.strategies.read().await.contains_key(strategy_id) {
+            return Err(VersionError::UnknownStrategy(strategy_id.to_string()));
+        }
+        let history = {
+            let mut versions = self.versions.lock().await;
+            self.load(&mut versions, strategy_id).await?.clone()
+        };
+
+        let mut metrics = Vec::with_capacity(history.len());
+        for version in &history {
+            metrics.push(self.metrics(strategy_id, version.version, DateTime::<Utc>::MIN_UTC).await?);
+        }
+        Ok(VersionHistory {
+            strategy_id: strategy_id.to_string(),
+            active_version: active_of(&history).map(|active| active.version),
+            versions: history,
+            metrics,
+            ab_test: self.ab_tests.lock().await.get(strategy_id).cloned(),
+        })
+    }
+
+    async fn metrics(
+        &self,
+        strategy_id: &str,
+        version: u32,
+        since: DateTime<Utc>,
+    ) -> Result<VersionMetrics, VersionError> {
+        let trades = self
+            .performance
+            .version_trades(strategy_id, version, since)
+            .await
+            .map_err(|e| VersionError::Store(e.to_string()))?;
+        Ok(VersionMetrics::from_trades(version, &trades))
+    }
+
+    async fn running_test(&self, strategy_id: &str) -> Option<AbTest> {
+        self.ab_tests
+            .lock()
+            .await
+            .get(strategy_id)
+            .filter(|test| test.status == AbTestStatus::Running)
+            .cloned()
+    }
+
+    /// Records the challenger parameters as a new version and trades them alongside the
+    /// active version, splitting the strategy's position size between the two
+    pub async fn start_ab_test(
+        &self,
+        strategy_id: &str,
+        parameters: StrategyParams,
+        created_by: &str,
+        config: AbTestConfig,
+    ) -> Result<AbTest, VersionError> {
+        config.validate()?;
+        parameters
+            .validate()
+            .map_err(|e| VersionError::InvalidParameters(e.to_string()))?;
+        let now = Utc::now();
+        let mut versions = self.versions.lock().await;
+        let mut tests = self.ab_tests.lock().await;
+        if tests.get(strategy_id).is_some_and(|test| test.status == AbTestStatus::Running) {
+            return Err(VersionError::AbTestRunning(strategy_id.to_string()));
+        }
+        let history = self.load(&mut versions, strategy_id).await?;
+        self.ensure_baseline(strategy_id, history, SYSTEM_AUTHOR, now).await?;
+        let champion = active_of(history).expect("baseline recorded").version;
+        let challenger = self.append(strategy_id, history, parameters, created_by, now).await?;
+
+        let variant = variant_id(strategy_id, challenger.version);
+        let trading_pairs = {
+            let mut strategies = self.strategies.write().await;
+            let strategy = strategies
+                .get_mut(strategy_id)
+                .ok_or_else(|| VersionError::UnknownStrategy(strategy_id.to_string()))?;
+            let mut snapshot = strategy.snapshot(&variant);
+            snapshot.id = Uuid::new_v4();
+            snapshot.parameters = challenger.parameters.clone();
+            snapshot.parameters.position_size_bps =
+                scaled(challenger.parameters.position_size_bps, config.challenger_share);
+            strategy.parameters.position_size_bps =
+                scaled(strategy.parameters.position_size_bps, Decimal::ONE - config.challenger_share);
+            strategies.insert(variant.clone(), Strategy::from_snapshot(&snapshot));
+            snapshot.trading_pairs
+        };
+        self.supervisor.register(&variant, trading_pairs);
+
+        let test = AbTest {
+            strategy_id: strategy_id.to_string(),
+            champion,
+            challenger: challenger.version,
+            ends_at: now + chrono::Duration::seconds(config.evaluation_period_secs as i64),
+            config,
+            status: AbTestStatus::Running,
+            started_at: now,
+            concluded_at: None,
+            champion_metrics: None,
+            challenger_metrics: None,
+        };
+        tests.insert(strategy_id.to_string(), test.clone());
+
+        counter!(format!("{}.ab_tests_started", METRICS_PREFIX), 1);
+        info!(strategy_id, champion, challenger = test.challenger, "A/B test started");
+        Ok(test)
+    }
+
+    /// Stops trading the challenger and marks the test concluded
+    async fn end_test(&self, test: &mut AbTest, status: AbTestStatus, now: DateTime<Utc>) {
+        let variant = variant_id(&test.strategy_id, test.challenger);
+        self.strategies.write().await.remove(&variant);
+        self.supervisor.unregister(&variant);
+        test.status = status;
+        test.concluded_at = Some(now);
+        counter!(format!("{}.ab_tests_concluded", METRICS_PREFIX), 1, "status" => status.as_str());
+    }
+
+    /// Concludes every A/B test whose evaluation period has ended, making the winning
+    /// version live at the strategy's full position size
+    pub async fn evaluate(&self, now: DateTime<Utc>) -> Result<Vec<AbTest>, VersionError> {
+        let mut versions = self.versions.lock().await;
+        let mut tests = self.ab_tests.lock().await;
+        let due: Vec<String> = tests
+            .values()
+            .filter(|test| test.status == AbTestStatus::Running && test.ends_at <= now)
+            .map(|test| test.strategy_id.clone())
+            .collect();
+
+        let mut concluded = Vec::with_capacity(due.len());
+        for strategy_id in due {
+            let test = tests.get_mut(&strategy_id).expect("due test present");
+            let champion = self.metrics(&strategy_id, test.champion, test.started_at).await?;
+            let challenger = self.metrics(&strategy_id, test.challenger, test.started_at).await?;
+            let winner = ab_winner(&test.config, &champion, &challenger);
+            test.champion_metrics = Some(champion);
+            test.challenger_metrics = Some(challenger);
+
+            let status = if winner == test.challenger {
+                AbTestStatus::Promoted
+            } else {
+                AbTestStatus::Retained
+            };
+            self.end_test(test, status, now).await;
+
+            let history = self.load(&mut versions, &strategy_id).await?;
+            let parameters = history
+                .iter()
+                .find(|entry| entry.version == winner)
+                .map(|entry| entry.parameters.clone())
+                .ok_or_else(|| VersionError::UnknownVersion {
+                    strategy_id: strategy_id.clone(),
+                    version: winner,
+                })?;
+            self.apply(&strategy_id, &parameters).await?;
+            if status == AbTestStatus::Promoted {
+                self.activate(&strategy_id, history, winner, now).await?;
+            }
+
+            info!(strategy_id = %strategy_id, winner, status = status.as_str(), "A/B test concluded");
+            concluded.push(test.clone());
+        }
+        Ok(concluded)
+    }
+
+    /// Loads every strategy's versions. A/B tests do not survive a restart, so challengers
+    /// restored from a snapshot are dropped and their strategy returns to its active version.
+    pub async fn load_all(&self) -> Result<(), VersionError> {
+        let strategy_ids: Vec<String> = self.strategies.read().await.keys().cloned().collect();
+        let mut versions = self.versions.lock().await;
+        for strategy_id in strategy_ids {
+            if is_variant(&strategy_id) {
+                self.strategies.write().await.remove(&strategy_id);
+                self.supervisor.unregister(&strategy_id);
+                continue;
+            }
+            let history = self.load(&mut versions, &strategy_id).await?;
+            if let Some(active) = active_of(history) {
+                self.apply(&strategy_id, &active.parameters).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Concludes due A/B tests on the configured interval
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            if let Err(e) = self.load_all().await {
+                warn!("Failed to load strategy versions: {}", e);
+            }
+            let mut interval = tokio::time::interval(self.config.evaluation_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.evaluate(Utc::now()).await {
+                    warn!("Failed to evaluate A/B tests: {}", e);
+                }
+            }
+        })
+    }
+}
+
+impl VersionTagger for StrategyVersionService {
+    fn tag(&self, strategy_id: &str) -> (String, Option<u32>) {
+        if let Some((base, version)) = parse_variant(strategy_id) {
+            return (base.to_string(), Some(version));
+        }
+        (strategy_id.to_string(), self.active.read().get(strategy_id).copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_collector::ohlcv::CandleInterval;
+    use crate::models::strategy::StrategyType;
+    use crate::performance::{EquityPoint, PerformanceConfig, PerformanceError, PerformanceStore};
+    use crate::supervision::SupervisionConfig;
+    use crate::utils::percent::Percent;
+    use parking_lot::Mutex as SyncMutex;
+    use rust_decimal_macros::dec;
+
+    #[derive(Default)]
+    struct MemoryTrades {
+        trades: SyncMutex<Vec<StrategyTrade>>,
+    }
+
+    #[async_trait]
+    impl PerformanceStore for MemoryTrades {
+        async fn record_trade(&self, trade: &StrategyTrade) -> Result<(), PerformanceError> {
+            self.trades.lock().push(trade.clone());
+            Ok(())
+        }
+
+        async fn trades(&self, strategy_id: &str, limit: usize) -> Result<Vec<StrategyTrade>, PerformanceError> {
+            let trades = self.trades.lock();
+            Ok(trades.iter().rev().filter(|t| t.strategy_id == strategy_id).take(limit).cloned().collect())
+        }
+
+        async fn version_trades(
+            &self,
+            strategy_id: &str,
+            version: u32,
+            since: DateTime<Utc>,
+        ) -> Result<Vec<StrategyTrade>, PerformanceError> {
+            let trades = self.trades.lock();
+            Ok(trades
+                .iter()
+                .filter(|t| t.strategy_id == strategy_id && t.version == Some(version) && t.closed_at >= since)
+                .cloned()
+                .collect())
+        }
+
+        async fn upsert_equity(&self, _points: &[EquityPoint]) -> Result<(), PerformanceError> {
+            Ok(())
+        }
+
+        async fn latest_equity(&self, _strategy_id: &str) -> Result<Vec<EquityPoint>, PerformanceError> {
+            Ok(Vec::new())
+        }
+
+        async fn equity(
+            &self,
+            _strategy_id: &str,
+            _granularity: CandleInterval,
+            _limit: usize,
+        ) -> Result<Vec<EquityPoint>, PerformanceError> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn params(position_size_bps: u32) -> StrategyParams {
+        StrategyParams {
+            position_size_bps: Bps::new(position_size_bps),
+            grid_levels: Some(10),
+            stop_loss_pct: Percent::from_percent(dec!(-5)),
+            take_profit_pct: Percent::from_percent(dec!(1)),
+            max_slippage_bps: Bps::new(100),
+            exchanges: vec!["jupiter".to_string()],
+            risk_factor: dec!(0.5),
+            allowed_regimes: Vec::new(),
+            market_making: None,
+        }
+    }
+
+    fn service() -> (Arc<StrategyVersionService>, Arc<PerformanceService>) {
+        let strategy = Strategy::new(StrategyType::Grid, params(1000), vec!["SOL/USDC".to_string()]).unwrap();
+        let strategies = Arc::new(RwLock::new(HashMap::from([("grid".to_string(), strategy)])));
+        let performance = Arc::new(PerformanceService::new(PerformanceConfig::default(), strategies.clone()));
+        performance.set_store(Arc::new(MemoryTrades::default()));
+        let supervisor = Arc::new(StrategySupervisor::new(SupervisionConfig::default(), strategies.clone()));
+        let versions = Arc::new(StrategyVersionService::new(
+            VersionConfig::default(),
+            strategies,
+            performance.clone(),
+            supervisor,
+        ));
+        performance.set_versions(versions.clone());
+        (versions, performance)
+    }
+
+    /// Records a trade the way a fill attributed to `strategy_id` would be tagged
+    async fn close(versions: &StrategyVersionService, performance: &PerformanceService, strategy_id: &str, pnl: Decimal) {
+        let (strategy_id, version) = versions.tag(strategy_id);
+        let trade = StrategyTrade {
+            id: Uuid::new_v4(),
+            strategy_id,
+            trading_pair: "SOL/USDC".to_string(),
+            realized_pnl: pnl,
+            closed_at: Utc::now(),
+            version,
+        };
+        performance.record_trade(&trade).await.unwrap();
+    }
+
+    async fn live_params(versions: &StrategyVersionService, strategy_id: &str) -> Option<StrategyParams> {
+        versions.strategies.read().await.get(strategy_id).map(|s| s.parameters.clone())
+    }
+
+    #[tokio::test]
+    async fn test_update_creates_version_and_rollback_restores() {
+        let (versions, _) = service();
+        let initial = versions.record_initial("grid", "alice").await.unwrap();
+        assert_eq!((initial.version, initial.created_by.as_str()), (1, "alice"));
+
+        let updated = versions.update_parameters("grid", params(1500), "bob").await.unwrap();
+        assert_eq!(updated.version, 2);
+        assert_eq!(updated.created_by, "bob");
+        assert_ne!(updated.param_hash, initial.param_hash);
+        assert!(updated.activated_at.is_some());
+        assert_eq!(live_params(&versions, "grid").await, Some(params(1500)));
+
+        // Resubmitting the live parameters does not create a version
+        let unchanged = versions.update_parameters("grid", params(1500), "bob").await.unwrap();
+        assert_eq!(unchanged.version, 2);
+        assert!(matches!(
+            versions.update_parameters("grid", params(1), "bob").await,
+            Err(VersionError::InvalidParameters(_))
+        ));
+
+        let rolled_back = versions.rollback("grid", 1).await.unwrap();
+        assert_eq!(rolled_back.param_hash, initial.param_hash);
+        assert_eq!(live_params(&versions, "grid").await, Some(params(1000)));
+        let history = versions.history("grid").await.unwrap();
+        assert_eq!(history.versions.len(), 2);
+        assert_eq!(history.active_version, Some(1));
+        assert!(matches!(versions.rollback("grid", 7).await, Err(VersionError::UnknownVersion { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_trades_tagged_and_metrics_isolated_per_version() {
+        let (versions, performance) = service();
+        versions.record_initial("grid", SYSTEM_AUTHOR).await.unwrap();
+        close(&versions, &performance, "grid", dec!(10)).await;
+        close(&versions, &performance, "grid", dec!(-4)).await;
+
+        versions.update_parameters("grid", params(1500), "bob").await.unwrap();
+        close(&versions, &performance, "grid", dec!(3)).await;
+
+        let history = versions.history("grid").await.unwrap();
+        let summary: Vec<(u32, u32, Decimal)> = history
+            .metrics
+            .iter()
+            .map(|m| (m.version, m.total_trades, m.realized_pnl))
+            .collect();
+        assert_eq!(summary, vec![(1, 2, dec!(6)), (2, 1, dec!(3))]);
+        assert_eq!(history.metrics[0].win_rate, dec!(0.5));
+        assert_eq!(history.metrics[0].max_drawdown, dec!(4));
+        assert_eq!(history.metrics[1].sharpe_ratio, None);
+
+        let trades = performance.trades("grid", 10).await.unwrap();
+        assert_eq!(trades.iter().map(|t| t.version).collect::<Vec<_>>(), vec![Some(2), Some(1), Some(1)]);
+    }
+
+    #[test]
+    fn test_ab_winner_on_synthetic_results() {
+        let metrics = |version, total_trades, realized_pnl, win_rate| VersionMetrics {
+            version,
+            total_trades,
+            realized_pnl,
+            win_rate,
+            sharpe_ratio: None,
+            max_drawdown: Decimal::ZERO,
+        };
+        let config = AbTestConfig {
+            challenger_share: dec!(0.2),
+            evaluation_period_secs: 3600,
+            objective: AbObjective::RealizedPnl,
+            min_trades: 10,
+        };
+
+        // 30 on a fifth of the size beats 100 on four fifths once scaled
+        assert_eq!(ab_winner(&config, &metrics(1, 40, dec!(100), dec!(0.5)), &metrics(2, 12, dec!(30), dec!(0.4))), 2);
+        assert_eq!(ab_winner(&config, &metrics(1, 40, dec!(200), dec!(0.5)), &metrics(2, 12, dec!(30), dec!(0.4))), 1);
+        // Too few challenger trades keeps the champion regardless of score
+        assert_eq!(ab_winner(&config, &metrics(1, 40, dec!(100), dec!(0.5)), &metrics(2, 9, dec!(90), dec!(0.9))), 1);
+
+        let by_win_rate = AbTestConfig { objective: AbObjective::WinRate, ..config };
+        assert_eq!(ab_winner(&by_win_rate, &metrics(1, 40, dec!(100), dec!(0.5)), &metrics(2, 12, dec!(30), dec!(0.5))), 1);
+        assert_eq!(ab_winner(&by_win_rate, &metrics(1, 40, dec!(100), dec!(0.5)), &metrics(2, 12, dec!(1), dec!(0.6))), 2);
+    }
+
+    #[tokio::test]
+    async fn test_ab_test_splits_capital_and_promotes_challenger() {
+        let (versions, performance) = service();
+        versions.record_initial("grid", SYSTEM_AUTHOR).await.unwrap();
+        let config = AbTestConfig {
+            challenger_share: dec!(0.25),
+            evaluation_period_secs: 600,
+            objective: AbObjective::RealizedPnl,
+            min_trades: 2,
+        };
+        let test = versions.start_ab_test("grid", params(2000), "bob", config.clone()).await.unwrap();
+        assert_eq!((test.champion, test.challenger), (1, 2));
+        assert!(matches!(
+            versions.start_ab_test("grid", params(2000), "bob", config).await,
+            Err(VersionError::AbTestRunning(_))
+        ));
+        assert!(matches!(
+            versions.update_parameters("grid", params(1200), "bob").await,
+            Err(VersionError::AbTestRunning(_))
+        ));
+
+        let champion = live_params(&versions, "grid").await.unwrap();
+        let challenger = live_params(&versions, "grid@v2").await.unwrap();
+        assert_eq!(champion.position_size_bps, Bps::new(750));
+        assert_eq!(challenger.position_size_bps, Bps::new(500));
+
+        close(&versions, &performance, "grid", dec!(6)).await;
+        close(&versions, &performance, "grid", dec!(3)).await;
+        close(&versions, &performance, "grid@v2", dec!(4)).await;
+        close(&versions, &performance, "grid@v2", dec!(2)).await;
+
+        assert!(versions.evaluate(test.started_at).await.unwrap().is_empty());
+        let concluded = versions.evaluate(test.ends_at).await.unwrap();
+        assert_eq!(concluded[0].status, AbTestStatus::Promoted);
+        assert_eq!(concluded[0].challenger_metrics.as_ref().unwrap().realized_pnl, dec!(6));
+
+        assert_eq!(live_params(&versions, "grid").await, Some(params(2000)));
+        assert_eq!(live_params(&versions, "grid@v2").await, None);
+        assert_eq!(versions.tag("grid"), ("grid".to_string(), Some(2)));
+        assert_eq!(versions.history("grid").await.unwrap().active_version, Some(2));
+    }
+}
@@ -0,0 +1,815 @@
+//! Per-strategy supervision that pauses a strategy when its market data goes stale, its
+//! rolling drawdown breaches a per-strategy threshold or the risk manager keeps rejecting
+//! its orders. Paused strategies stay paused until resumed explicitly through the API.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - metrics = "0.20"
+//! - parking_lot = "0.12"
+//! - utoipa = "3.5"
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use parking_lot::{Mutex, RwLock as SyncRwLock};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use thiserror::Error;
+use tokio::sync::{broadcast, RwLock};
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+
+use crate::api::WebhookDispatcher;
+use crate::db::repositories::StrategyAuditRepository;
+use crate::execution_engine::order_book::OrderBookSnapshot;
+use crate::models::strategy::{Strategy, StrategyAuditEntry, StrategySnapshot, StrategyState};
+use crate::models::webhook::{StrategyStatusEvent, WebhookEvent, WebhookEventType};
+use crate::optimizer::backtest::max_drawdown_pct;
+use crate::persistence::PersistenceQueue;
+use crate::utils::percent::Percent;
+
+// Supervision constants
+pub const AUDIT_AUTO_PAUSED: &str = "auto_paused";
+pub const AUDIT_RESUMED: &str = "resumed";
+const METRICS_PREFIX: &str = "trading_bot.supervision";
+const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_MAX_STALE_INTERVALS: u32 = 3;
+const DEFAULT_DRAWDOWN_WINDOW: Duration = Duration::from_secs(3600);
+const DEFAULT_MAX_DRAWDOWN_PCT: Percent = Percent::from_percent(Decimal::new(10, 0)); // tighter than the 15% portfolio limit
+const DEFAULT_MAX_REJECTION_RATE_PCT: Percent = Percent::from_percent(Decimal::new(50, 0));
+const DEFAULT_REJECTION_WINDOW: usize = 50;
+const DEFAULT_MIN_ORDERS_FOR_REJECTION_RATE: usize = 10;
+
+/// Supervision error types
+#[derive(Error, Debug)]
+pub enum SupervisionError {
+    #[error("strategy not found: {0}")]
+    NotFound(String),
+    #[error("strategy is not paused: {0}")]
+    NotPaused(String),
+    #[error("strategy is already paused: {0}")]
+    AlreadyPaused(String),
+    #[error("strategy already registered: {0}")]
+    AlreadyRegistered(String),
+    #[error("strategy ID belongs to a deleted strategy: {0}")]
+    Deleted(String),
+}
+
+/// Thresholds that trigger an automatic pause
+#[derive(Debug, Clone, PartialEq)]
+pub struct SupervisionConfig {
+    pub check_interval: Duration,
+    /// Check intervals a strategy may go without fresh data for any of its pairs
+    pub max_stale_intervals: u32,
+    pub drawdown_window: Duration,
+    pub max_drawdown_pct: Percent,
+    /// Per-strategy drawdown thresholds replacing the default
+    pub drawdown_overrides: HashMap<String, Percent>,
+    pub max_rejection_rate_pct: Percent,
+    /// Number of most recent orders the rejection rate is measured over
+    pub rejection_window: usize,
+    pub min_orders_for_rejection_rate: usize,
+}
+
+impl Default for SupervisionConfig {
+    fn default() -> Self {
+        Self {
+            check_interval: DEFAULT_CHECK_INTERVAL,
+            max_stale_intervals: DEFAULT_MAX_STALE_INTERVALS,
+            drawdown_window: DEFAULT_DRAWDOWN_WINDOW,
+            max_drawdown_pct: DEFAULT_MAX_DRAWDOWN_PCT,
+            drawdown_overrides: HashMap::new(),
+            max_rejection_rate_pct: DEFAULT_MAX_REJECTION_RATE_PCT,
+            rejection_window: DEFAULT_REJECTION_WINDOW,
+            min_orders_for_rejection_rate: DEFAULT_MIN_ORDERS_FOR_REJECTION_RATE,
+        }
+    }
+}
+
+impl SupervisionConfig {
+    pub fn max_drawdown_for(&self, strategy_id: &str) -> Percent {
+        self.drawdown_overrides
+            .get(strategy_id)
+            .copied()
+            .unwrap_or(self.max_drawdown_pct)
+    }
+}
+
+/// Outcome of an order submitted by a strategy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderOutcome {
+    Filled,
+    /// Refused by risk or validation checks before execution
+    Rejected,
+    Failed,
+}
+
+/// Condition that paused a strategy
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(tag = "trigger", rename_all = "snake_case")]
+pub enum PauseTrigger {
+    StaleMarketData {
+        trading_pair: String,
+        intervals: u32,
+    },
+    Drawdown {
+        drawdown_pct: Percent,
+        max_drawdown_pct: Percent,
+    },
+    RejectionRate {
+        rejection_rate_pct: Percent,
+        max_rejection_rate_pct: Percent,
+    },
+    /// The strategy panicked while running against a pair
+    Panicked {
+        trading_pair: String,
+        message: String,
+    },
+    /// Paused ahead of a maintenance window; resumed automatically when it ends
+    Maintenance {
+        window_id: uuid::Uuid,
+    },
+    /// An order reached execution acting on a tick older than the staleness threshold
+    StaleTicks {
+        trading_pair: String,
+        age_ms: u64,
+        threshold_ms: u64,
+    },
+    /// Paused by an operator through the admin API
+    Manual {
+        paused_by: String,
+    },
+}
+
+impl fmt::Display for PauseTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StaleMarketData { trading_pair, intervals } => write!(
+                f,
+                "no fresh market data for {} in {} check intervals",
+                trading_pair, intervals
+            ),
+            Self::Drawdown { drawdown_pct, max_drawdown_pct } => write!(
+                f,
+                "rolling drawdown {}% exceeds strategy maximum {}%",
+                drawdown_pct.as_percent().round_dp(2).normalize(),
+                max_drawdown_pct.as_percent()
+            ),
+            Self::RejectionRate { rejection_rate_pct, max_rejection_rate_pct } => write!(
+                f,
+                "risk rejection rate {}% exceeds maximum {}%",
+                rejection_rate_pct.as_percent().round_dp(2).normalize(),
+                max_rejection_rate_pct.as_percent()
+            ),
+            Self::Panicked { trading_pair, message } => {
+                write!(f, "panicked while running on {}: {}", trading_pair, message)
+            }
+            Self::Maintenance { window_id } => write!(f, "maintenance window {}", window_id),
+            Self::StaleTicks { trading_pair, age_ms, threshold_ms } => write!(
+                f,
+                "acted on a {}ms old tick for {}, threshold {}ms",
+                age_ms, trading_pair, threshold_ms
+            ),
+            Self::Manual { paused_by } => write!(f, "paused by {}", paused_by),
+        }
+    }
+}
+
+/// Supervision state tracked for one strategy
+#[derive(Debug, Clone)]
+struct StrategyHealth {
+    trading_pairs: Vec<String>,
+    /// Baseline for staleness; reset on resume so a strategy isn't re-paused immediately
+    watched_since: DateTime<Utc>,
+    last_signal_at: Option<DateTime<Utc>>,
+    orders: u64,
+    fills: u64,
+    recent_outcomes: VecDeque<OrderOutcome>,
+    equity: VecDeque<(DateTime<Utc>, Decimal)>,
+    paused: Option<PauseTrigger>,
+}
+
+impl StrategyHealth {
+    fn new(trading_pairs: Vec<String>, now: DateTime<Utc>) -> Self {
+        Self {
+            trading_pairs,
+            watched_since: now,
+            last_signal_at: None,
+            orders: 0,
+            fills: 0,
+            recent_outcomes: VecDeque::new(),
+            equity: VecDeque::new(),
+            paused: None,
+        }
+    }
+
+    fn fill_ratio(&self) -> Decimal {
+        if self.orders == 0 {
+            return Decimal::ZERO;
+        }
+        Decimal::from(self.fills) / Decimal::from(self.orders)
+    }
+
+    fn rejection_rate_pct(&self) -> Percent {
+        if self.recent_outcomes.is_empty() {
+            return Percent::ZERO;
+        }
+        let rejected = self
+            .recent_outcomes
+            .iter()
+            .filter(|outcome| **outcome == OrderOutcome::Rejected)
+            .count();
+        Percent::ratio(Decimal::from(rejected), Decimal::from(self.recent_outcomes.len()))
+    }
+
+    fn rolling_drawdown_pct(&self) -> Percent {
+        let curve: Vec<Decimal> = self.equity.iter().map(|(_, equity)| *equity).collect();
+        Percent::from_percent(max_drawdown_pct(&curve))
+    }
+
+    fn trim_equity(&mut self, now: DateTime<Utc>, window: Duration) {
+        let cutoff = now - chrono::Duration::from_std(window).unwrap_or_else(|_| chrono::Duration::zero());
+        while self.equity.front().map_or(false, |(at, _)| *at < cutoff) {
+            self.equity.pop_front();
+        }
+    }
+}
+
+/// Point-in-time supervision view of a strategy
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct StrategyHealthSnapshot {
+    pub strategy_id: String,
+    pub last_signal_at: Option<DateTime<Utc>>,
+    pub fill_ratio: Decimal,
+    pub rejection_rate_pct: Percent,
+    pub rolling_drawdown_pct: Percent,
+    pub paused: Option<PauseTrigger>,
+}
+
+/// Watches every registered strategy and pauses those that trip a supervision trigger
+#[derive(Debug)]
+pub struct StrategySupervisor {
+    config: SupervisionConfig,
+    strategies: Arc<RwLock<HashMap<String, Strategy>>>,
+    health: Mutex<HashMap<String, StrategyHealth>>,
+    market_data: Mutex<HashMap<String, DateTime<Utc>>>,
+    audit_log: Mutex<Vec<StrategyAuditEntry>>,
+    repository: SyncRwLock<Option<Arc<StrategyAuditRepository>>>,
+    webhooks: SyncRwLock<Option<Arc<WebhookDispatcher>>>,
+    persistence: SyncRwLock<Option<Arc<PersistenceQueue>>>,
+}
+
+impl StrategySupervisor {
+    pub fn new(config: SupervisionConfig, strategies: Arc<RwLock<HashMap<String, Strategy>>>) -> Self {
+        Self {
+            config,
+            strategies,
+            health: Mutex::new(HashMap::new()),
+            market_data: Mutex::new(HashMap::new()),
+            audit_log: Mutex::new(Vec::new()),
+            repository: SyncRwLock::new(None),
+            webhooks: SyncRwLock::new(None),
+            persistence: SyncRwLock::new(None),
+        }
+    }
+
+    /// Persists pause and resume audit entries
+    pub fn set_repository(&self, repository: Arc<StrategyAuditRepository>) {
+        *self.repository.write() = Some(repository);
+    }
+
+    /// Sends pause and resume notifications through the webhook dispatcher
+    pub fn set_webhooks(&self, webhooks: Arc<WebhookDispatcher>) {
+        *self.webhooks.write() = Some(webhooks);
+    }
+
+    /// Defers audit entries to the write-behind queue instead of awaiting the repository
+    pub fn set_persistence(&self, persistence: Arc<PersistenceQueue>) {
+        *self.persistence.write() = Some(persistence);
+    }
+
+    /// Starts supervising a strategy trading the given pairs
+    pub fn register(&self, strategy_id: &str, trading_pairs: Vec<String>) {
+        self.health
+            .lock()
+            .insert(strategy_id.to_string(), StrategyHealth::new(trading_pairs, Utc::now()));
+    }
+
+    /// Adds a new strategy to the shared strategy map and starts supervising it
+    pub async fn add_strategy(
+        &self,
+        strategy_id: &str,
+        strategy: Strategy,
+    ) -> Result<StrategySnapshot, SupervisionError> {
+        let mut strategies = self.strategies.write().await;
+        match strategies.get(strategy_id) {
+            // Deleted strategies keep their ID so their history stays unambiguous
+            Some(existing) if existing.is_deleted() => {
+                return Err(SupervisionError::Deleted(strategy_id.to_string()))
+            }
+            Some(_) => return Err(SupervisionError::AlreadyRegistered(strategy_id.to_string())),
+            None => {}
+        }
+        let snapshot = strategy.snapshot(strategy_id);
+        self.register(strategy_id, strategy.trading_pairs.clone());
+        strategies.insert(strategy_id.to_string(), strategy);
+        Ok(snapshot)
+    }
+
+    pub fn unregister(&self, strategy_id: &str) {
+        self.health.lock().remove(strategy_id);
+    }
+
+    pub fn record_market_data(&self, trading_pair: &str, at: DateTime<Utc>) {
+        let mut market_data = self.market_data.lock();
+        let last = market_data.entry(trading_pair.to_string()).or_insert(at);
+        *last = (*last).max(at);
+    }
+
+    pub fn record_signal(&self, strategy_id: &str, at: DateTime<Utc>) {
+        if let Some(health) = self.health.lock().get_mut(strategy_id) {
+            health.last_signal_at = Some(at);
+        }
+    }
+
+    pub fn record_order(&self, strategy_id: &str, outcome: OrderOutcome) {
+        if let Some(health) = self.health.lock().get_mut(strategy_id) {
+            health.orders += 1;
+            if outcome == OrderOutcome::Filled {
+                health.fills += 1;
+            }
+            health.recent_outcomes.push_back(outcome);
+            while health.recent_outcomes.len() > self.config.rejection_window {
+                health.recent_outcomes.pop_front();
+            }
+        }
+    }
+
+    /// Records a strategy equity mark for the rolling drawdown window
+    pub fn record_equity(&self, strategy_id: &str, at: DateTime<Utc>, equity: Decimal) {
+        if let Some(health) = self.health.lock().get_mut(strategy_id) {
+            health.equity.push_back((at, equity));
+            health.trim_equity(at, self.config.drawdown_window);
+        }
+    }
+
+    pub fn is_paused(&self, strategy_id: &str) -> bool {
+        self.health
+            .lock()
+            .get(strategy_id)
+            .map_or(false, |health| health.paused.is_some())
+    }
+
+    pub fn health(&self, strategy_id: &str) -> Option<StrategyHealthSnapshot> {
+        self.health.lock().get(strategy_id).map(|health| StrategyHealthSnapshot {
+            strategy_id: strategy_id.to_string(),
+            last_signal_at: health.last_signal_at,
+            fill_ratio: health.fill_ratio(),
+            rejection_rate_pct: health.rejection_rate_pct(),
+            rolling_drawdown_pct: health.rolling_drawdown_pct(),
+            paused: health.paused.clone(),
+        })
+    }
+
+    pub fn audit_log(&self, strategy_id: &str) -> Vec<StrategyAuditEntry> {
+        self.audit_log
+            .lock()
+            .iter()
+            .filter(|entry| entry.strategy_id == strategy_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Evaluates every running strategy and pauses those that trip a trigger
+    pub async fn supervise(&self, now: DateTime<Utc>) -> Vec<(String, PauseTrigger)> {
+        let paused = {
+            let market_data = self.market_data.lock();
+            let mut health = self.health.lock();
+            let mut paused = Vec::new();
+            for (strategy_id, strategy) in health.iter_mut() {
+                if strategy.paused.is_some() {
+                    continue;
+                }
+                strategy.trim_equity(now, self.config.drawdown_window);
+                if let Some(trigger) = self.check(strategy_id, strategy, &market_data, now) {
+                    strategy.paused = Some(trigger.clone());
+                    paused.push((strategy_id.clone(), trigger));
+                }
+            }
+            paused
+        };
+
+        for (strategy_id, trigger) in &paused {
+            self.on_paused(strategy_id, trigger).await;
+        }
+        self.record_paused_gauge();
+
+        paused
+    }
+
+    /// Pauses a running strategy outside the periodic checks; returns false when it is
+    /// unknown or already paused
+    pub async fn pause(&self, strategy_id: &str, trigger: PauseTrigger) -> bool {
+        {
+            let mut health = self.health.lock();
+            match health.get_mut(strategy_id) {
+                Some(strategy) if strategy.paused.is_none() => strategy.paused = Some(trigger.clone()),
+                _ => return false,
+            }
+        }
+        self.on_paused(strategy_id, &trigger).await;
+        self.record_paused_gauge();
+        true
+    }
+
+    async fn on_paused(&self, strategy_id: &str, trigger: &PauseTrigger) {
+        self.set_state(strategy_id, StrategyState::Paused).await;
+
+        let reason = trigger.to_string();
+        match trigger {
+            PauseTrigger::Maintenance { .. } => info!(strategy_id = %strategy_id, "Strategy paused for {}", reason),
+            PauseTrigger::Manual { .. } => info!(strategy_id = %strategy_id, "Strategy {}", reason),
+            _ => error!(strategy_id = %strategy_id, "Strategy auto-paused: {}", reason),
+        }
+        counter!(format!("{}.auto_paused", METRICS_PREFIX), 1);
+
+        self.audit(StrategyAuditEntry::new(strategy_id, AUDIT_AUTO_PAUSED, reason.clone()))
+            .await;
+        self.notify(WebhookEventType::StrategyPaused, strategy_id, reason);
+    }
+
+    fn record_paused_gauge(&self) {
+        let paused_count = self.health.lock().values().filter(|h| h.paused.is_some()).count();
+        gauge!(format!("{}.paused_strategies", METRICS_PREFIX), paused_count as f64);
+    }
+
+    /// Pauses a strategy on an operator's request; it stays paused until resumed
+    pub async fn pause_manually(
+        &self,
+        strategy_id: &str,
+        paused_by: &str,
+    ) -> Result<StrategyHealthSnapshot, SupervisionError> {
+        let trigger = PauseTrigger::Manual { paused_by: paused_by.to_string() };
+        if !self.pause(strategy_id, trigger).await {
+            return Err(match self.health(strategy_id) {
+                Some(_) => SupervisionError::AlreadyPaused(strategy_id.to_string()),
+                None => SupervisionError::NotFound(strategy_id.to_string()),
+            });
+        }
+        self.health(strategy_id)
+            .ok_or_else(|| SupervisionError::NotFound(strategy_id.to_string()))
+    }
+
+    /// Resumes a paused strategy, restarting its staleness, drawdown and rejection windows
+    pub async fn resume(&self, strategy_id: &str) -> Result<StrategyHealthSnapshot, SupervisionError> {
+        self.resume_with(strategy_id, "api", |_| true).await
+    }
+
+    /// Resumes a strategy paused for a maintenance window; strategies paused by a
+    /// supervision trigger in the meantime stay paused
+    pub async fn resume_after_maintenance(
+        &self,
+        strategy_id: &str,
+    ) -> Result<StrategyHealthSnapshot, SupervisionError> {
+        self.resume_with(strategy_id, "maintenance", |trigger| {
+            matches!(trigger, PauseTrigger::Maintenance { .. })
+        })
+        .await
+    }
+
+    async fn resume_with(
+        &self,
+        strategy_id: &str,
+        via: &str,
+        resumable: impl Fn(&PauseTrigger) -> bool,
+    ) -> Result<StrategyHealthSnapshot, SupervisionError> {
+        let trigger = {
+            let mut health = self.health.lock();
+            let strategy = health
+                .get_mut(strategy_id)
+                .ok_or_else(|| SupervisionError::NotFound(strategy_id.to_string()))?;
+            let trigger = match strategy.paused.take() {
+                Some(trigger) if resumable(&trigger) => trigger,
+                other => {
+                    strategy.paused = other;
+                    return Err(SupervisionError::NotPaused(strategy_id.to_string()));
+                }
+            };
+            strategy.watched_since = Utc::now();
+            strategy.recent_outcomes.clear();
+            strategy.equity.clear();
+            trigger
+        };
+
+        self.set_state(strategy_id, StrategyState::Active).await;
+
+        let detail = format!("resumed via {} after: {}", via, trigger);
+        info!(strategy_id = %strategy_id, "Strategy {}", detail);
+        counter!(format!("{}.resumed", METRICS_PREFIX), 1);
+
+        self.audit(StrategyAuditEntry::new(strategy_id, AUDIT_RESUMED, detail.clone()))
+            .await;
+        self.notify(WebhookEventType::StrategyResumed, strategy_id, detail);
+
+        self.health(strategy_id)
+            .ok_or_else(|| SupervisionError::NotFound(strategy_id.to_string()))
+    }
+
+    /// Runs supervision on the configured interval
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.check_interval);
+            loop {
+                interval.tick().await;
+                self.supervise(Utc::now()).await;
+            }
+        })
+    }
+
+    /// Treats every fresh order book snapshot as market data for its pair
+    pub fn spawn_market_data_listener(
+        self: Arc<Self>,
+        mut snapshots: broadcast::Receiver<OrderBookSnapshot>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match snapshots.recv().await {
+                    Ok(snapshot) if !snapshot.is_stale => {
+                        self.record_market_data(&snapshot.trading_pair, snapshot.timestamp);
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Supervision market data listener skipped {} snapshots", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    fn check(
+        &self,
+        strategy_id: &str,
+        health: &StrategyHealth,
+        market_data: &HashMap<String, DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Option<PauseTrigger> {
+        let interval_ms = self.config.check_interval.as_millis().max(1) as i64;
+        for trading_pair in &health.trading_pairs {
+            let last_fresh = market_data
+                .get(trading_pair)
+                .map_or(health.watched_since, |at| (*at).max(health.watched_since));
+            let intervals = ((now - last_fresh).num_milliseconds() / interval_ms).max(0) as u32;
+            if intervals >= self.config.max_stale_intervals {
+                return Some(PauseTrigger::StaleMarketData {
+                    trading_pair: trading_pair.clone(),
+                    intervals,
+                });
+            }
+        }
+
+        let max_drawdown_pct = self.config.max_drawdown_for(strategy_id);
+        let drawdown_pct = health.rolling_drawdown_pct();
+        if drawdown_pct > max_drawdown_pct {
+            return Some(PauseTrigger::Drawdown {
+                drawdown_pct,
+                max_drawdown_pct,
+            });
+        }
+
+        if health.recent_outcomes.len() >= self.config.min_orders_for_rejection_rate {
+            let rejection_rate_pct = health.rejection_rate_pct();
+            if rejection_rate_pct > self.config.max_rejection_rate_pct {
+                return Some(PauseTrigger::RejectionRate {
+                    rejection_rate_pct,
+                    max_rejection_rate_pct: self.config.max_rejection_rate_pct,
+                });
+            }
+        }
+
+        None
+    }
+
+    async fn set_state(&self, strategy_id: &str, state: StrategyState) {
+        if let Some(strategy) = self.strategies.write().await.get_mut(strategy_id) {
+            strategy.state = state;
+            strategy.updated_at = Utc::now();
+        }
+    }
+
+    /// Records an audit entry raised outside the supervisor, such as a regime gate
+    pub async fn record_audit(&self, entry: StrategyAuditEntry) {
+        self.audit(entry).await;
+    }
+
+    async fn audit(&self, entry: StrategyAuditEntry) {
+        let repository = self.repository.read().clone();
+        let persistence = self.persistence.read().clone();
+        match (repository, persistence) {
+            (Some(repository), Some(persistence)) => {
+                let queued = entry.clone();
+                persistence.enqueue("strategy_audit", move || {
+                    let (repository, entry) = (repository.clone(), queued.clone());
+                    async move { repository.record_audit(&entry).await.map_err(|e| e.to_string()) }
+                });
+            }
+            (Some(repository), None) => {
+                if let Err(e) = repository.record_audit(&entry).await {
+                    warn!("Failed to persist strategy audit entry: {}", e);
+                }
+            }
+            (None, _) => {}
+        }
+        self.audit_log.lock().push(entry);
+    }
+
+    fn notify(&self, event_type: WebhookEventType, strategy_id: &str, reason: String) {
+        let Some(webhooks) = self.webhooks.read().clone() else {
+            return;
+        };
+        let payload = StrategyStatusEvent {
+            strategy_id: strategy_id.to_string(),
+            reason,
+            changed_at: Utc::now(),
+        };
+        match WebhookEvent::new(event_type, &payload) {
+            Ok(event) => webhooks.dispatch(event),
+            Err(e) => warn!("Failed to build webhook event: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::strategy::{StrategyParams, StrategyType};
+    use crate::utils::percent::Bps;
+    use rust_decimal_macros::dec;
+
+    const STRATEGY_ID: &str = "grid-1";
+
+    fn supervisor(config: SupervisionConfig) -> StrategySupervisor {
+        let mut strategy = Strategy::new(
+            StrategyType::Grid,
+            StrategyParams {
+                position_size_bps: Bps::new(1000),
+                grid_levels: Some(10),
+                stop_loss_pct: Percent::from_percent(dec!(-5)),
+                take_profit_pct: Percent::from_percent(dec!(1)),
+                max_slippage_bps: Bps::new(100),
+                exchanges: vec!["jupiter".to_string()],
+                risk_factor: dec!(0.5),
+                allowed_regimes: Vec::new(),
+                market_making: None,
+            },
+            vec!["SOL/USDC".to_string()],
+        )
+        .unwrap();
+        strategy.state = StrategyState::Active;
+
+        let strategies = Arc::new(RwLock::new(HashMap::from([(STRATEGY_ID.to_string(), strategy)])));
+        let supervisor = StrategySupervisor::new(config, strategies);
+        supervisor.register(STRATEGY_ID, vec!["SOL/USDC".to_string()]);
+        supervisor
+    }
+
+    async fn state(supervisor: &StrategySupervisor) -> StrategyState {
+        supervisor.strategies.read().await[STRATEGY_ID].state.clone()
+    }
+
+    #[tokio::test]
+    async fn test_stale_market_data_pauses() {
+        let supervisor = supervisor(SupervisionConfig::default());
+        let now = Utc::now();
+        supervisor.record_market_data("SOL/USDC", now);
+        assert!(supervisor.supervise(now + chrono::Duration::seconds(10)).await.is_empty());
+
+        let paused = supervisor.supervise(now + chrono::Duration::seconds(16)).await;
+        assert_eq!(paused.len(), 1);
+        assert!(matches!(paused[0].1, PauseTrigger::StaleMarketData { intervals: 3, .. }));
+        assert_eq!(state(&supervisor).await, StrategyState::Paused);
+
+        let audit = supervisor.audit_log(STRATEGY_ID);
+        assert_eq!(audit[0].action, AUDIT_AUTO_PAUSED);
+        assert_eq!(audit[0].detail, "no fresh market data for SOL/USDC in 3 check intervals");
+    }
+
+    #[tokio::test]
+    async fn test_drawdown_uses_per_strategy_threshold() {
+        let mut config = SupervisionConfig::default();
+        config.drawdown_overrides.insert(STRATEGY_ID.to_string(), Percent::from_percent(dec!(5)));
+        let supervisor = supervisor(config);
+        let now = Utc::now();
+        supervisor.record_market_data("SOL/USDC", now);
+        supervisor.record_equity(STRATEGY_ID, now, dec!(1000));
+        supervisor.record_equity(STRATEGY_ID, now, dec!(940));
+
+        let paused = supervisor.supervise(now).await;
+        assert_eq!(paused.len(), 1);
+        assert_eq!(
+            paused[0].1,
+            PauseTrigger::Drawdown {
+                drawdown_pct: Percent::from_percent(dec!(6)),
+                max_drawdown_pct: Percent::from_percent(dec!(5)),
+            }
+        );
+        assert_eq!(
+            supervisor.audit_log(STRATEGY_ID)[0].detail,
+            "rolling drawdown 6% exceeds strategy maximum 5%"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejection_rate_pauses_and_requires_resume() {
+        let supervisor = supervisor(SupervisionConfig::default());
+        let now = Utc::now();
+        supervisor.record_market_data("SOL/USDC", now);
+        for outcome in [OrderOutcome::Filled; 4].into_iter().chain([OrderOutcome::Rejected; 6]) {
+            supervisor.record_order(STRATEGY_ID, outcome);
+        }
+
+        let paused = supervisor.supervise(now).await;
+        assert_eq!(paused[0].1.to_string(), "risk rejection rate 60% exceeds maximum 50%");
+        assert!(supervisor.is_paused(STRATEGY_ID));
+
+        // Stays paused until resumed explicitly
+        supervisor.supervise(now).await;
+        assert!(supervisor.is_paused(STRATEGY_ID));
+
+        let health = supervisor.resume(STRATEGY_ID).await.unwrap();
+        assert!(health.paused.is_none());
+        assert_eq!(health.fill_ratio, dec!(0.4));
+        assert_eq!(state(&supervisor).await, StrategyState::Active);
+        assert_eq!(supervisor.audit_log(STRATEGY_ID)[1].action, AUDIT_RESUMED);
+        assert!(matches!(
+            supervisor.resume(STRATEGY_ID).await,
+            Err(SupervisionError::NotPaused(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_resume_leaves_other_pauses() {
+        let supervisor = supervisor(SupervisionConfig::default());
+        let window_id = uuid::Uuid::new_v4();
+        assert!(supervisor.pause(STRATEGY_ID, PauseTrigger::Maintenance { window_id }).await);
+        assert_eq!(state(&supervisor).await, StrategyState::Paused);
+
+        let health = supervisor.resume_after_maintenance(STRATEGY_ID).await.unwrap();
+        assert!(health.paused.is_none());
+        assert!(supervisor.audit_log(STRATEGY_ID)[1].detail.starts_with("resumed via maintenance"));
+
+        // A supervision pause is only lifted by an operator
+        let trigger = PauseTrigger::Drawdown {
+            drawdown_pct: Percent::from_percent(dec!(12)),
+            max_drawdown_pct: Percent::from_percent(dec!(10)),
+        };
+        assert!(supervisor.pause(STRATEGY_ID, trigger.clone()).await);
+        assert!(matches!(
+            supervisor.resume_after_maintenance(STRATEGY_ID).await,
+            Err(SupervisionError::NotPaused(_))
+        ));
+        assert_eq!(supervisor.health(STRATEGY_ID).unwrap().paused, Some(trigger));
+    }
+
+    #[tokio::test]
+    async fn test_manual_pause_until_resumed() {
+        let supervisor = supervisor(SupervisionConfig::default());
+
+        let health = supervisor.pause_manually(STRATEGY_ID, "ops").await.unwrap();
+        assert_eq!(health.paused, Some(PauseTrigger::Manual { paused_by: "ops".to_string() }));
+        assert_eq!(state(&supervisor).await, StrategyState::Paused);
+        assert_eq!(supervisor.audit_log(STRATEGY_ID)[0].detail, "paused by ops");
+        assert!(matches!(
+            supervisor.pause_manually(STRATEGY_ID, "ops").await,
+            Err(SupervisionError::AlreadyPaused(_))
+        ));
+        assert!(matches!(
+            supervisor.pause_manually("unknown", "ops").await,
+            Err(SupervisionError::NotFound(_))
+        ));
+
+        supervisor.resume(STRATEGY_ID).await.unwrap();
+        assert_eq!(state(&supervisor).await, StrategyState::Active);
+    }
+
+    #[tokio::test]
+    async fn test_add_strategy_registers_once() {
+        let supervisor = supervisor(SupervisionConfig::default());
+        let strategy = Strategy::from_snapshot(&supervisor.strategies.read().await[STRATEGY_ID].snapshot("grid-2"));
+
+        let snapshot = supervisor.add_strategy("grid-2", strategy.clone()).await.unwrap();
+        assert_eq!(snapshot.strategy_id, "grid-2");
+        assert!(supervisor.health("grid-2").is_some());
+        assert!(supervisor.strategies.read().await.contains_key("grid-2"));
+        assert!(matches!(
+            supervisor.add_strategy(STRATEGY_ID, strategy).await,
+            Err(SupervisionError::AlreadyRegistered(_))
+        ));
+    }
+}
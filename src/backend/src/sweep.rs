@@ -0,0 +1,645 @@
+//! Profit sweep from the hot trading wallet to a cold wallet. A periodic check takes the
+//! wallet's free USDC (balance less open reservations and a working-capital floor) and, once
+//! it exceeds the configured ceiling, transfers the excess to a cold wallet pinned in config
+//! together with its checksum; the destination cannot be changed through the API. Sweeps
+//! are recorded as withdrawals so flow-adjusted returns ignore them, announced by webhook,
+//! and capped per UTC day. In dry-run mode the sweep is computed and announced only.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - chrono = "0.4"
+//! - rust_decimal = "1.30"
+//! - sha2 = "0.10"
+//! - spl-token = "4.0"
+//! - spl-associated-token-account = "2.0"
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use metrics::{counter, gauge};
+use parking_lot::{Mutex, RwLock as SyncRwLock};
+use rust_decimal::prelude::{RoundingStrategy, ToPrimitive};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+use thiserror::Error;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::api::WebhookDispatcher;
+use crate::attribution::{AttributionEngine, LedgerEntry};
+use crate::models::market::QuoteAsset;
+use crate::models::portfolio::Portfolio;
+use crate::models::transfer::{Transfer, TransferDirection};
+use crate::models::webhook::{WebhookEvent, WebhookEventType};
+use crate::utils::solana::SolanaClient;
+
+// Sweep constants
+const METRICS_PREFIX: &str = "trading_bot.sweep";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const USDC_DECIMALS: u8 = 6;
+const CHECKSUM_LENGTH: usize = 8;
+const DEFAULT_SWEEP_INTERVAL_SECS: u64 = 3600;
+const COLD_WALLET_VAR: &str = "SWEEP_COLD_WALLET";
+const COLD_WALLET_CHECKSUM_VAR: &str = "SWEEP_COLD_WALLET_CHECKSUM";
+const CEILING_VAR: &str = "SWEEP_CEILING_USDC";
+const WORKING_CAPITAL_VAR: &str = "SWEEP_WORKING_CAPITAL_USDC";
+const DAILY_CAP_VAR: &str = "SWEEP_DAILY_CAP_USDC";
+const DRY_RUN_VAR: &str = "SWEEP_DRY_RUN";
+const INTERVAL_VAR: &str = "SWEEP_INTERVAL_SECS";
+
+/// Profit sweep errors
+#[derive(Error, Debug)]
+pub enum SweepError {
+    #[error("configuration error: {0}")]
+    Config(String),
+    #[error("invalid cold wallet address: {0}")]
+    InvalidDestination(String),
+    #[error("cold wallet checksum mismatch for {0}")]
+    ChecksumMismatch(String),
+    #[error("transfer error: {0}")]
+    Transfer(String),
+    #[error("portfolio error: {0}")]
+    Portfolio(String),
+    #[error("store error: {0}")]
+    Store(String),
+}
+
+/// Checksum pinning a cold wallet address in config: the first eight hex digits of the
+/// address's SHA-256
+pub fn address_checksum(address: &str) -> String {
+    let digest = hex::encode(Sha256::digest(address.as_bytes()));
+    digest[..CHECKSUM_LENGTH].to_string()
+}
+
+/// Cold wallet the sweep sends to, fixed at startup once its checksum matches
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedDestination {
+    address: String,
+    pubkey: Pubkey,
+}
+
+impl PinnedDestination {
+    /// Accepts the address only with the checksum it was pinned with, so a mistyped or
+    /// swapped address is refused rather than funded
+    pub fn pin(address: &str, checksum: &str) -> Result<Self, SweepError> {
+        let address = address.trim();
+        let pubkey =
+            Pubkey::from_str(address).map_err(|e| SweepError::InvalidDestination(format!("{}: {}", address, e)))?;
+        if !address_checksum(address).eq_ignore_ascii_case(checksum.trim()) {
+            return Err(SweepError::ChecksumMismatch(address.to_string()));
+        }
+        Ok(Self {
+            address: address.to_string(),
+            pubkey,
+        })
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn pubkey(&self) -> &Pubkey {
+        &self.pubkey
+    }
+}
+
+/// Ceiling, floor, safety valves and destination of the profit sweep
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepConfig {
+    pub destination: PinnedDestination,
+    /// Most free USDC the hot wallet may hold after a sweep
+    pub ceiling: Decimal,
+    /// USDC kept back for trading before anything counts as free
+    pub working_capital_floor: Decimal,
+    /// Most USDC swept per UTC day
+    pub daily_cap: Decimal,
+    /// Compute and announce sweeps without transferring
+    pub dry_run: bool,
+    pub interval: Duration,
+}
+
+impl SweepConfig {
+    /// Reads the sweep settings from the environment; the sweep is off without a cold
+    /// wallet and runs dry unless `SWEEP_DRY_RUN=false`
+    pub fn from_env() -> Result<Option<Self>, SweepError> {
+        let Ok(address) = std::env::var(COLD_WALLET_VAR) else {
+            return Ok(None);
+        };
+        let checksum = std::env::var(COLD_WALLET_CHECKSUM_VAR)
+            .map_err(|_| SweepError::Config(format!("{} requires {}", COLD_WALLET_VAR, COLD_WALLET_CHECKSUM_VAR)))?;
+
+        let config = Self {
+            destination: PinnedDestination::pin(&address, &checksum)?,
+            ceiling: required_amount(CEILING_VAR)?,
+            working_capital_floor: optional_amount(WORKING_CAPITAL_VAR)?.unwrap_or(Decimal::ZERO),
+            daily_cap: required_amount(DAILY_CAP_VAR)?,
+            dry_run: std::env::var(DRY_RUN_VAR).map_or(true, |v| v != "false"),
+            interval: Duration::from_secs(
+                std::env::var(INTERVAL_VAR)
+                    .ok()
+                    .map(|v| v.parse().map_err(|_| SweepError::Config(format!("{} must be whole seconds", INTERVAL_VAR))))
+                    .transpose()?
+                    .unwrap_or(DEFAULT_SWEEP_INTERVAL_SECS),
+            ),
+        };
+        config.validate()?;
+        Ok(Some(config))
+    }
+
+    fn validate(&self) -> Result<(), SweepError> {
+        if self.ceiling < Decimal::ZERO || self.working_capital_floor < Decimal::ZERO {
+            return Err(SweepError::Config("ceiling and working capital must not be negative".to_string()));
+        }
+        if self.daily_cap <= Decimal::ZERO {
+            return Err(SweepError::Config("daily cap must be positive".to_string()));
+        }
+        if self.interval.is_zero() {
+            return Err(SweepError::Config("sweep interval must be positive".to_string()));
+        }
+        Ok(())
+    }
+}
+
+fn optional_amount(var: &str) -> Result<Option<Decimal>, SweepError> {
+    std::env::var(var)
+        .ok()
+        .map(|v| Decimal::from_str(v.trim()).map_err(|_| SweepError::Config(format!("{} must be a USDC amount", var))))
+        .transpose()
+}
+
+fn required_amount(var: &str) -> Result<Decimal, SweepError> {
+    optional_amount(var)?.ok_or_else(|| SweepError::Config(format!("{} is required", var)))
+}
+
+/// USDC to sweep: free balance above the ceiling, limited to what is left of the day's cap
+/// and truncated to USDC precision
+pub fn sweep_amount(free_balance: Decimal, ceiling: Decimal, swept_today: Decimal, daily_cap: Decimal) -> Decimal {
+    let excess = free_balance - ceiling;
+    let remaining_cap = daily_cap - swept_today;
+    if excess <= Decimal::ZERO || remaining_cap <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+    excess
+        .min(remaining_cap)
+        .round_dp_with_strategy(u32::from(USDC_DECIMALS), RoundingStrategy::ToZero)
+}
+
+/// A sweep carried out, or computed in dry-run mode
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SweepOutcome {
+    pub id: Uuid,
+    pub wallet_address: String,
+    pub destination: String,
+    pub free_balance: Decimal,
+    pub ceiling: Decimal,
+    pub amount: Decimal,
+    /// Whether the daily cap held the sweep below the excess
+    pub capped: bool,
+    pub dry_run: bool,
+    pub signature: Option<String>,
+    pub swept_at: DateTime<Utc>,
+}
+
+/// Persistence for sweeps in the transfers table
+#[async_trait]
+pub trait SweepStore: Send + Sync {
+    async fn record_sweep(&self, transfer: &Transfer, destination: &str) -> Result<(), SweepError>;
+    /// USDC swept from a wallet since a point in time
+    async fn swept_since(&self, wallet_address: &str, since: DateTime<Utc>) -> Result<Decimal, SweepError>;
+}
+
+/// Submits USDC transfers out of the hot wallet
+#[async_trait]
+pub trait SweepTransport: Send + Sync {
+    /// Sends USDC to the destination's token account, returning the transaction signature
+    async fn transfer_usdc(&self, destination: &Pubkey, amount: Decimal) -> Result<String, SweepError>;
+}
+
+/// Builds a USDC transfer from the owner's token account to the destination's, creating the
+/// destination account when it does not exist yet
+pub fn build_sweep_transaction(owner: &Pubkey, destination: &Pubkey, amount: Decimal) -> Result<Transaction, SweepError> {
+    let mint = Pubkey::from_str(USDC_MINT).map_err(|e| SweepError::Transfer(e.to_string()))?;
+    let base_units = (amount * Decimal::from(10u64.pow(u32::from(USDC_DECIMALS))))
+        .trunc()
+        .to_u64()
+        .filter(|units| *units > 0)
+        .ok_or_else(|| SweepError::Transfer(format!("sweep amount {} out of range", amount)))?;
+
+    let transfer = spl_token::instruction::transfer_checked(
+        &spl_token::id(),
+        &get_associated_token_address(owner, &mint),
+        &mint,
+        &get_associated_token_address(destination, &mint),
+        owner,
+        &[],
+        base_units,
+        USDC_DECIMALS,
+    )
+    .map_err(|e| SweepError::Transfer(format!("failed to build transfer instruction: {}", e)))?;
+
+    Ok(Transaction::new_with_payer(
+        &[
+            create_associated_token_account_idempotent(owner, destination, &mint, &spl_token::id()),
+            transfer,
+        ],
+        Some(owner),
+    ))
+}
+
+#[async_trait]
+impl SweepTransport for SolanaClient {
+    async fn transfer_usdc(&self, destination: &Pubkey, amount: Decimal) -> Result<String, SweepError> {
+        let owner = self
+            .signer()
+            .ok_or_else(|| SweepError::Transfer("no transaction signer configured".to_string()))?
+            .pubkey();
+        let transaction = build_sweep_transaction(&owner, destination, amount)?;
+        let (signature, _) = self
+            .sign_and_send_transaction(transaction, None)
+            .await
+            .map_err(|e| SweepError::Transfer(e.to_string()))?;
+        Ok(signature.to_string())
+    }
+}
+
+/// Periodically sweeps excess USDC from the hot wallet to the pinned cold wallet
+pub struct ProfitSweeper {
+    config: SweepConfig,
+    portfolio: Portfolio,
+    transport: Arc<dyn SweepTransport>,
+    store: SyncRwLock<Option<Arc<dyn SweepStore>>>,
+    attribution: SyncRwLock<Option<Arc<AttributionEngine>>>,
+    webhooks: SyncRwLock<Option<Arc<WebhookDispatcher>>>,
+    /// USDC swept on the current UTC day
+    swept: Mutex<(NaiveDate, Decimal)>,
+}
+
+impl std::fmt::Debug for ProfitSweeper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProfitSweeper")
+            .field("config", &self.config)
+            .field("swept", &*self.swept.lock())
+            .finish()
+    }
+}
+
+impl ProfitSweeper {
+    pub fn new(config: SweepConfig, portfolio: Portfolio, transport: Arc<dyn SweepTransport>) -> Self {
+        Self {
+            config,
+            portfolio,
+            transport,
+            store: SyncRwLock::new(None),
+            attribution: SyncRwLock::new(None),
+            webhooks: SyncRwLock::new(None),
+            swept: Mutex::new((NaiveDate::MIN, Decimal::ZERO)),
+        }
+    }
+
+    /// Records sweeps in the transfers table and restores the day's total from it
+    pub fn set_store(&self, store: Arc<dyn SweepStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    /// Ledger recording sweeps as wallet transfers
+    pub fn set_attribution(&self, attribution: Arc<AttributionEngine>) {
+        *self.attribution.write() = Some(attribution);
+    }
+
+    pub fn set_webhooks(&self, webhooks: Arc<WebhookDispatcher>) {
+        *self.webhooks.write() = Some(webhooks);
+    }
+
+    pub fn config(&self) -> &SweepConfig {
+        &self.config
+    }
+
+    /// Restores the amount already swept today, so a restart cannot reset the daily cap
+    pub async fn load(&self, now: DateTime<Utc>) -> Result<Decimal, SweepError> {
+        let store = self.store.read().clone();
+        let Some(store) = store else {
+            return Ok(Decimal::ZERO);
+        };
+        let day = now.date_naive();
+        let start_of_day = DateTime::<Utc>::from_naive_utc_and_offset(day.and_hms_opt(0, 0, 0).unwrap_or_default(), Utc);
+        let swept = store.swept_since(self.portfolio.wallet_address(), start_of_day).await?;
+        *self.swept.lock() = (day, swept);
+        Ok(swept)
+    }
+
+    /// USDC swept on the day of `now`
+    pub fn swept_on(&self, now: DateTime<Utc>) -> Decimal {
+        let swept = self.swept.lock();
+        if swept.0 == now.date_naive() {
+            swept.1
+        } else {
+            Decimal::ZERO
+        }
+    }
+
+    /// Balance less open reservations and the working-capital floor
+    pub async fn free_balance(&self) -> Decimal {
+        self.portfolio.available_balance(QuoteAsset::Usdc).await - self.config.working_capital_floor
+    }
+
+    /// Checks the free balance against the ceiling and sweeps the excess, returning the
+    /// sweep when there was one to make
+    pub async fn run_once(&self, now: DateTime<Utc>) -> Result<Option<SweepOutcome>, SweepError> {
+        let free_balance = self.free_balance().await;
+        gauge!(format!("{}.free_balance", METRICS_PREFIX), free_balance.to_f64().unwrap_or(0.0));
+        let excess = free_balance - self.config.ceiling;
+        if excess <= Decimal::ZERO {
+            return Ok(None);
+        }
+
+        let amount = sweep_amount(free_balance, self.config.ceiling, self.swept_on(now), self.config.daily_cap);
+        if amount.is_zero() {
+            counter!(format!("{}.cap_reached", METRICS_PREFIX), 1);
+            warn!(excess = %excess, daily_cap = %self.config.daily_cap, "Daily sweep cap reached; excess stays in the hot wallet");
+            return Ok(None);
+        }
+
+        let mut outcome = SweepOutcome {
+            id: Uuid::new_v4(),
+            wallet_address: self.portfolio.wallet_address().to_string(),
+            destination: self.config.destination.address().to_string(),
+            free_balance,
+            ceiling: self.config.ceiling,
+            amount,
+            capped: amount < excess,
+            dry_run: self.config.dry_run,
+            signature: None,
+            swept_at: now,
+        };
+
+        if self.config.dry_run {
+            counter!(format!("{}.dry_runs", METRICS_PREFIX), 1);
+            info!(amount = %amount, destination = %outcome.destination, "Dry run: would sweep excess USDC to cold wallet");
+            self.notify(&outcome);
+            return Ok(Some(outcome));
+        }
+
+        let signature = self
+            .transport
+            .transfer_usdc(self.config.destination.pubkey(), amount)
+            .await
+            .map_err(|e| {
+                counter!(format!("{}.failed", METRICS_PREFIX), 1);
+                error!(amount = %amount, "Profit sweep transfer failed: {}", e);
+                e
+            })?;
+        outcome.signature = Some(signature.clone());
+        self.add_swept(now, amount);
+
+        // Funds have moved; bookkeeping failures are reported without failing the sweep
+        let mut transfer = Transfer::new(
+            outcome.wallet_address.clone(),
+            TransferDirection::Withdrawal,
+            amount,
+            Some(signature),
+        )
+        .map_err(|e| SweepError::Portfolio(e.to_string()))?;
+        transfer.detected_at = now;
+        if let Err(e) = self.portfolio.record_withdrawal(transfer.clone()).await {
+            error!(signature = ?transfer.signature, "Failed to record sweep against the portfolio: {}", e);
+        }
+        let attribution = self.attribution.read().clone();
+        if let Some(attribution) = attribution {
+            attribution.record(LedgerEntry::transfer(&transfer)).await;
+        }
+        let store = self.store.read().clone();
+        if let Some(store) = store {
+            if let Err(e) = store.record_sweep(&transfer, &outcome.destination).await {
+                error!(signature = ?transfer.signature, "Failed to persist sweep: {}", e);
+            }
+        }
+
+        counter!(format!("{}.swept", METRICS_PREFIX), 1);
+        info!(
+            amount = %amount,
+            destination = %outcome.destination,
+            signature = ?outcome.signature,
+            capped = outcome.capped,
+            "Swept excess USDC to cold wallet"
+        );
+        self.notify(&outcome);
+        Ok(Some(outcome))
+    }
+
+    /// Runs the sweep check every configured interval
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_once(Utc::now()).await {
+                    warn!("Profit sweep failed: {}", e);
+                }
+            }
+        })
+    }
+
+    fn add_swept(&self, now: DateTime<Utc>, amount: Decimal) {
+        let mut swept = self.swept.lock();
+        let day = now.date_naive();
+        if swept.0 != day {
+            *swept = (day, Decimal::ZERO);
+        }
+        swept.1 += amount;
+    }
+
+    fn notify(&self, outcome: &SweepOutcome) {
+        let Some(webhooks) = self.webhooks.read().clone() else {
+            return;
+        };
+        match WebhookEvent::new(WebhookEventType::ProfitSwept, outcome) {
+            Ok(event) => {
+                webhooks.dispatch(event);
+            }
+            Err(e) => warn!("Failed to build webhook event: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+
+    const COLD_WALLET: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+    /// Transport recording each transfer and answering with a numbered signature
+    #[derive(Default)]
+    struct MockTransport {
+        transfers: Mutex<Vec<(Pubkey, Decimal)>>,
+    }
+
+    #[async_trait]
+    impl SweepTransport for MockTransport {
+        async fn transfer_usdc(&self, destination: &Pubkey, amount: Decimal) -> Result<String, SweepError> {
+            let mut transfers = self.transfers.lock();
+            transfers.push((*destination, amount));
+            Ok(format!("sweep_sig_{}", transfers.len()))
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryStore {
+        sweeps: Mutex<Vec<(Transfer, String)>>,
+    }
+
+    #[async_trait]
+    impl SweepStore for MemoryStore {
+        async fn record_sweep(&self, transfer: &Transfer, destination: &str) -> Result<(), SweepError> {
+            self.sweeps.lock().push((transfer.clone(), destination.to_string()));
+            Ok(())
+        }
+
+        async fn swept_since(&self, wallet_address: &str, since: DateTime<Utc>) -> Result<Decimal, SweepError> {
+            Ok(self
+                .sweeps
+                .lock()
+                .iter()
+                .filter(|(transfer, _)| transfer.wallet_address == wallet_address && transfer.detected_at >= since)
+                .map(|(transfer, _)| transfer.amount)
+                .sum())
+        }
+    }
+
+    fn config(dry_run: bool) -> SweepConfig {
+        SweepConfig {
+            destination: PinnedDestination::pin(COLD_WALLET, &address_checksum(COLD_WALLET)).unwrap(),
+            ceiling: dec!(10000),
+            working_capital_floor: dec!(2000),
+            daily_cap: dec!(1500),
+            dry_run,
+            interval: Duration::from_secs(60),
+        }
+    }
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 1, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_sweep_amount_above_and_below_ceiling() {
+        // 500 over the ceiling with the whole cap left
+        assert_eq!(sweep_amount(dec!(10500), dec!(10000), dec!(0), dec!(1500)), dec!(500));
+        // At or below the ceiling nothing moves
+        assert_eq!(sweep_amount(dec!(10000), dec!(10000), dec!(0), dec!(1500)), Decimal::ZERO);
+        assert_eq!(sweep_amount(dec!(9000), dec!(10000), dec!(0), dec!(1500)), Decimal::ZERO);
+        // The cap limits the sweep, and an exhausted cap stops it
+        assert_eq!(sweep_amount(dec!(14000), dec!(10000), dec!(1000), dec!(1500)), dec!(500));
+        assert_eq!(sweep_amount(dec!(14000), dec!(10000), dec!(1500), dec!(1500)), Decimal::ZERO);
+        // Sub-unit USDC dust is left behind
+        assert_eq!(sweep_amount(dec!(10000.12345678), dec!(10000), dec!(0), dec!(1500)), dec!(0.123456));
+    }
+
+    #[test]
+    fn test_destination_pinned_by_checksum() {
+        let checksum = address_checksum(COLD_WALLET);
+        assert_eq!(checksum.len(), CHECKSUM_LENGTH);
+        assert!(PinnedDestination::pin(COLD_WALLET, &checksum.to_uppercase()).is_ok());
+        assert!(matches!(
+            PinnedDestination::pin(COLD_WALLET, "00000000"),
+            Err(SweepError::ChecksumMismatch(_))
+        ));
+        assert!(matches!(
+            PinnedDestination::pin("not-a-wallet", &address_checksum("not-a-wallet")),
+            Err(SweepError::InvalidDestination(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_excess_swept_up_to_daily_cap() {
+        // 15000 balance, 1000 reserved and a 2000 floor leave 12000 free: 2000 over the ceiling
+        let portfolio = Portfolio::new("hot_wallet".to_string(), dec!(15000)).unwrap();
+        portfolio.reserve(dec!(1000), Uuid::new_v4()).await.unwrap();
+        let transport = Arc::new(MockTransport::default());
+        let store = Arc::new(MemoryStore::default());
+        let sweeper = ProfitSweeper::new(config(false), portfolio.clone(), transport.clone());
+        sweeper.set_store(store.clone());
+        assert_eq!(sweeper.free_balance().await, dec!(12000));
+
+        let outcome = sweeper.run_once(at(9)).await.unwrap().expect("excess should be swept");
+        assert_eq!(outcome.amount, dec!(1500));
+        assert!(outcome.capped);
+        assert_eq!(outcome.signature.as_deref(), Some("sweep_sig_1"));
+        assert_eq!(transport.transfers.lock()[0], (Pubkey::from_str(COLD_WALLET).unwrap(), dec!(1500)));
+
+        // Recorded as a withdrawal, so the sweep is a flow rather than a loss
+        assert_eq!(portfolio.quote_balance(QuoteAsset::Usdc).await, dec!(13500));
+        let transfers = portfolio.get_transfers().await;
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].direction, TransferDirection::Withdrawal);
+        let returns = portfolio.flow_adjusted_returns().await;
+        assert_eq!(returns.time_weighted_return_pct, Decimal::ZERO);
+        assert_eq!(returns.drawdown_pct, Decimal::ZERO);
+        assert_eq!(returns.net_flows, dec!(-1500));
+        assert_eq!(store.sweeps.lock()[0].1, COLD_WALLET);
+
+        // 500 is still over the ceiling but the day's cap is spent, including after a restart
+        assert!(sweeper.run_once(at(10)).await.unwrap().is_none());
+        let restarted = ProfitSweeper::new(config(false), portfolio.clone(), transport.clone());
+        restarted.set_store(store.clone());
+        assert_eq!(restarted.load(at(11)).await.unwrap(), dec!(1500));
+        assert!(restarted.run_once(at(11)).await.unwrap().is_none());
+        assert_eq!(transport.transfers.lock().len(), 1);
+
+        // The cap resets the next day
+        let next_day = at(9) + chrono::Duration::days(1);
+        let outcome = restarted.run_once(next_day).await.unwrap().unwrap();
+        assert_eq!(outcome.amount, dec!(500));
+        assert!(!outcome.capped);
+    }
+
+    #[tokio::test]
+    async fn test_below_ceiling_not_swept() {
+        let portfolio = Portfolio::new("hot_wallet".to_string(), dec!(11500)).unwrap();
+        let transport = Arc::new(MockTransport::default());
+        let sweeper = ProfitSweeper::new(config(false), portfolio.clone(), transport.clone());
+
+        assert_eq!(sweeper.free_balance().await, dec!(9500));
+        assert!(sweeper.run_once(at(9)).await.unwrap().is_none());
+        assert!(transport.transfers.lock().is_empty());
+        assert_eq!(portfolio.quote_balance(QuoteAsset::Usdc).await, dec!(11500));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_does_not_transfer() {
+        let portfolio = Portfolio::new("hot_wallet".to_string(), dec!(12800)).unwrap();
+        let transport = Arc::new(MockTransport::default());
+        let sweeper = ProfitSweeper::new(config(true), portfolio.clone(), transport.clone());
+
+        let outcome = sweeper.run_once(at(9)).await.unwrap().unwrap();
+        assert!(outcome.dry_run);
+        assert_eq!(outcome.amount, dec!(800));
+        assert!(outcome.signature.is_none());
+        assert!(transport.transfers.lock().is_empty());
+        assert_eq!(portfolio.quote_balance(QuoteAsset::Usdc).await, dec!(12800));
+        assert_eq!(sweeper.swept_on(at(9)), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_sweep_transaction_targets_destination_token_account() {
+        let owner = Pubkey::new_unique();
+        let destination = Pubkey::from_str(COLD_WALLET).unwrap();
+        let transaction = build_sweep_transaction(&owner, &destination, dec!(1500.25)).unwrap();
+
+        let mint = Pubkey::from_str(USDC_MINT).unwrap();
+        let keys = &transaction.message.account_keys;
+        assert_eq!(keys[0], owner);
+        assert!(keys.contains(&get_associated_token_address(&destination, &mint)));
+        assert!(build_sweep_transaction(&owner, &destination, dec!(0.0000001)).is_err());
+    }
+}
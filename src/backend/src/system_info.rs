@@ -0,0 +1,232 @@
+//! Build and runtime identity of the running instance: crate version, git commit and build
+//! time baked in by the build script, process start and uptime, the environment profile and
+//! feature flags, live activity counts reported by the bot and the WebSocket server, and the
+//! high-impact economic events coming up. Logged once at startup, exported as labels on the
+//! build info gauge and served on `/api/v1/system/info`.
+//!
+//! Version dependencies:
+//! - chrono = "0.4"
+//! - lazy_static = "1.4"
+//! - async-trait = "0.1"
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::config::environment::EnvironmentConfig;
+use crate::events::EconomicEvent;
+use crate::execution_engine::MEV_OPTIMIZATION_ENABLED;
+use crate::utils::metrics::{MetricsCollector, MetricsError};
+
+// System info constants
+pub const GIT_COMMIT: &str = env!("FIREBOT_GIT_COMMIT");
+const BUILD_TIMESTAMP_SECS: &str = env!("FIREBOT_BUILD_TIMESTAMP");
+
+lazy_static! {
+    static ref PROCESS_STARTED_AT: DateTime<Utc> = Utc::now();
+}
+
+/// Pins the process start time; called first thing in main so uptime covers initialization
+pub fn mark_process_start() -> DateTime<Utc> {
+    *PROCESS_STARTED_AT
+}
+
+/// Version, commit and build time of this binary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct BuildInfo {
+    pub version: String,
+    pub git_commit: String,
+    pub build_timestamp: DateTime<Utc>,
+}
+
+impl BuildInfo {
+    pub fn current() -> Self {
+        let build_timestamp = BUILD_TIMESTAMP_SECS
+            .parse::<i64>()
+            .ok()
+            .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+            .unwrap_or_else(|| DateTime::<Utc>::from(std::time::UNIX_EPOCH));
+        Self {
+            version: crate::VERSION.to_string(),
+            git_commit: GIT_COMMIT.to_string(),
+            build_timestamp,
+        }
+    }
+}
+
+/// Trading features switched on for this instance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub struct FeatureFlags {
+    pub paper_trading: bool,
+    pub mev_optimization: bool,
+    pub grpc: bool,
+}
+
+impl FeatureFlags {
+    pub fn from_environment(environment: &EnvironmentConfig) -> Self {
+        Self {
+            paper_trading: !environment.live_trading_enabled(),
+            mev_optimization: MEV_OPTIMIZATION_ENABLED,
+            grpc: environment.grpc_port.is_some(),
+        }
+    }
+}
+
+/// Live workload of the instance; each source fills in the counts it owns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ActivityCounts {
+    pub active_strategies: usize,
+    pub open_positions: usize,
+    pub websocket_connections: usize,
+}
+
+impl ActivityCounts {
+    /// Sums counts reported by separate sources
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            active_strategies: self.active_strategies + other.active_strategies,
+            open_positions: self.open_positions + other.open_positions,
+            websocket_connections: self.websocket_connections + other.websocket_connections,
+        }
+    }
+}
+
+/// Component reporting its share of the instance's live activity
+#[async_trait]
+pub trait ActivitySource: Send + Sync {
+    async fn activity(&self) -> ActivityCounts;
+}
+
+/// Response of the system info endpoint
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SystemInfo {
+    pub build: BuildInfo,
+    pub started_at: DateTime<Utc>,
+    pub uptime_secs: i64,
+    pub environment: String,
+    pub features: FeatureFlags,
+    pub module_versions: BTreeMap<String, String>,
+    pub activity: ActivityCounts,
+    /// High-impact economic events due within the next 24 hours
+    pub upcoming_events: Vec<EconomicEvent>,
+}
+
+impl SystemInfo {
+    pub fn new(environment: &EnvironmentConfig, activity: ActivityCounts, now: DateTime<Utc>) -> Self {
+        let started_at = mark_process_start();
+        Self {
+            build: BuildInfo::current(),
+            started_at,
+            uptime_secs: (now - started_at).num_seconds().max(0),
+            environment: environment.profile().as_str().to_string(),
+            features: FeatureFlags::from_environment(environment),
+            module_versions: crate::utils::module_versions()
+                .iter()
+                .map(|(module, version)| (module.to_string(), version.to_string()))
+                .collect(),
+            activity,
+            upcoming_events: Vec::new(),
+        }
+    }
+
+    /// Lists events from the calendar that tighten risk limits soon
+    pub fn with_upcoming_events(mut self, upcoming_events: Vec<EconomicEvent>) -> Self {
+        self.upcoming_events = upcoming_events;
+        self
+    }
+
+    /// Collects activity from every source into a current snapshot
+    pub async fn collect(
+        environment: &EnvironmentConfig,
+        sources: &[std::sync::Arc<dyn ActivitySource>],
+        now: DateTime<Utc>,
+    ) -> Self {
+        let mut activity = ActivityCounts::default();
+        for source in sources {
+            activity = activity.merge(source.activity().await);
+        }
+        Self::new(environment, activity, now)
+    }
+
+    /// Logs the build identity and feature flags once at startup
+    pub fn log_startup(&self) {
+        info!(
+            version = %self.build.version,
+            git_commit = %self.build.git_commit,
+            build_timestamp = %self.build.build_timestamp,
+            environment = %self.environment,
+            paper_trading = self.features.paper_trading,
+            mev_optimization = self.features.mev_optimization,
+            grpc = self.features.grpc,
+            "Build info"
+        );
+    }
+
+    /// Exports the build identity as labels on the build info gauge
+    pub fn export(&self, metrics: &MetricsCollector) -> Result<(), MetricsError> {
+        metrics.record_build_info(
+            &self.build.version,
+            &self.build.git_commit,
+            &self.build.build_timestamp.to_rfc3339(),
+            &self.environment,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    struct FixedActivity(ActivityCounts);
+
+    #[async_trait]
+    impl ActivitySource for FixedActivity {
+        async fn activity(&self) -> ActivityCounts {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_build_info_baked_in() {
+        let build = BuildInfo::current();
+        assert_eq!(build.version, crate::VERSION);
+        assert!(!build.git_commit.is_empty());
+        assert!(build.build_timestamp > DateTime::<Utc>::from(std::time::UNIX_EPOCH));
+    }
+
+    #[tokio::test]
+    async fn test_activity_summed_across_sources() {
+        let environment = EnvironmentConfig::new();
+        let sources: Vec<Arc<dyn ActivitySource>> = vec![
+            Arc::new(FixedActivity(ActivityCounts {
+                active_strategies: 3,
+                open_positions: 2,
+                ..Default::default()
+            })),
+            Arc::new(FixedActivity(ActivityCounts {
+                websocket_connections: 5,
+                ..Default::default()
+            })),
+        ];
+
+        let started_at = mark_process_start();
+        let info = SystemInfo::collect(&environment, &sources, started_at + chrono::Duration::seconds(90)).await;
+        assert_eq!(info.uptime_secs, 90);
+        assert_eq!(
+            info.activity,
+            ActivityCounts {
+                active_strategies: 3,
+                open_positions: 2,
+                websocket_connections: 5,
+            }
+        );
+        assert_eq!(info.module_versions.len(), 5);
+        assert_eq!(info.features.mev_optimization, MEV_OPTIMIZATION_ENABLED);
+    }
+}
@@ -0,0 +1,516 @@
+//! Shared circuit breaker for guarding calls into failing dependencies. A breaker opens
+//! under one of three policies (consecutive failures, error rate over a sliding window, or
+//! a manual trip), optionally moves to half-open after a cooldown to let a limited number
+//! of probe calls through, and closes again on a successful probe. Breakers registered with
+//! the global registry are listed by the health endpoint.
+//!
+//! Version dependencies:
+//! - parking_lot = "0.12"
+//! - chrono = "0.4"
+//! - metrics = "0.20"
+//! - lazy_static = "1.4"
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+// Circuit breaker constants
+const METRICS_PREFIX: &str = "trading_bot.circuit_breaker";
+const DEFAULT_HALF_OPEN_PROBES: u32 = 1;
+
+lazy_static::lazy_static! {
+    static ref REGISTRY: CircuitBreakerRegistry = CircuitBreakerRegistry::default();
+}
+
+/// Breakers listed by the health endpoint
+pub fn registry() -> &'static CircuitBreakerRegistry {
+    &REGISTRY
+}
+
+/// Whether calls pass through a breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    Closed,
+    Open,
+    /// Cooldown elapsed; a limited number of probe calls decide whether to close
+    HalfOpen,
+}
+
+impl BreakerState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BreakerState::Closed => "closed",
+            BreakerState::Open => "open",
+            BreakerState::HalfOpen => "half_open",
+        }
+    }
+
+    fn gauge_value(&self) -> f64 {
+        match self {
+            BreakerState::Closed => 0.0,
+            BreakerState::HalfOpen => 0.5,
+            BreakerState::Open => 1.0,
+        }
+    }
+}
+
+/// Condition that opens a breaker
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BreakerPolicy {
+    /// Opens on the `threshold`th failure without an intervening success
+    ConsecutiveFailures { threshold: u32 },
+    /// Opens once the failure fraction of calls within `window` exceeds `max_rate`, after
+    /// at least `min_calls` calls in the window
+    ErrorRate { window: Duration, max_rate: f64, min_calls: u32 },
+    /// Opens only when tripped
+    Manual,
+}
+
+/// Opening policy and recovery behavior
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BreakerConfig {
+    pub policy: BreakerPolicy,
+    /// Time open before probing; `None` stays open until reset
+    pub cooldown: Option<Duration>,
+    /// Calls let through while half-open
+    pub half_open_probes: u32,
+}
+
+impl BreakerConfig {
+    pub fn consecutive_failures(threshold: u32) -> Self {
+        Self::new(BreakerPolicy::ConsecutiveFailures { threshold: threshold.max(1) })
+    }
+
+    pub fn error_rate(window: Duration, max_rate: f64, min_calls: u32) -> Self {
+        Self::new(BreakerPolicy::ErrorRate { window, max_rate, min_calls: min_calls.max(1) })
+    }
+
+    pub fn manual() -> Self {
+        Self::new(BreakerPolicy::Manual)
+    }
+
+    fn new(policy: BreakerPolicy) -> Self {
+        Self {
+            policy,
+            cooldown: None,
+            half_open_probes: DEFAULT_HALF_OPEN_PROBES,
+        }
+    }
+
+    /// Probes the dependency once `cooldown` has passed since opening
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = Some(cooldown);
+        self
+    }
+
+    pub fn with_half_open_probes(mut self, probes: u32) -> Self {
+        self.half_open_probes = probes.max(1);
+        self
+    }
+}
+
+/// Point-in-time view of a breaker for health reporting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BreakerStatus {
+    pub name: String,
+    pub state: BreakerState,
+    pub consecutive_failures: u32,
+    pub opened_at: Option<DateTime<Utc>>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug)]
+struct BreakerInner {
+    state: BreakerState,
+    consecutive_failures: u32,
+    /// (time, failed) per call within the error rate window
+    outcomes: VecDeque<(Instant, bool)>,
+    opened_at: Option<(Instant, DateTime<Utc>)>,
+    /// Manual trips ignore the cooldown and stay open until reset
+    tripped: bool,
+    reason: Option<String>,
+    probes_remaining: u32,
+}
+
+/// Named circuit breaker; cheap to share behind an `Arc`
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    name: String,
+    config: BreakerConfig,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(name: impl Into<String>, config: BreakerConfig) -> Self {
+        let breaker = Self {
+            name: name.into(),
+            config,
+            inner: Mutex::new(BreakerInner {
+                state: BreakerState::Closed,
+                consecutive_failures: 0,
+                outcomes: VecDeque::new(),
+                opened_at: None,
+                tripped: false,
+                reason: None,
+                probes_remaining: 0,
+            }),
+        };
+        breaker.record_state(BreakerState::Closed);
+        breaker
+    }
+
+    /// Wraps the breaker in an `Arc` and lists it in the global registry
+    pub fn registered(self) -> Arc<Self> {
+        let breaker = Arc::new(self);
+        registry().register(&breaker);
+        breaker
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn config(&self) -> &BreakerConfig {
+        &self.config
+    }
+
+    /// Current state, moving to half-open if the cooldown has elapsed
+    pub fn state(&self) -> BreakerState {
+        self.state_at(Instant::now())
+    }
+
+    /// True while calls are refused; half-open breakers are not open
+    pub fn is_open(&self) -> bool {
+        self.state() == BreakerState::Open
+    }
+
+    /// Whether a call may proceed. While half-open this claims one of the probe slots, so
+    /// the caller must report the outcome.
+    pub fn allow(&self) -> bool {
+        self.allow_at(Instant::now())
+    }
+
+    pub fn record_success(&self) {
+        self.record_at(Instant::now(), false);
+    }
+
+    /// Records a failed call; returns true if this failure opened the breaker
+    pub fn record_failure(&self) -> bool {
+        self.record_at(Instant::now(), true)
+    }
+
+    /// Opens the breaker until reset, regardless of policy or cooldown
+    pub fn trip(&self, reason: impl Into<String>) {
+        let mut inner = self.inner.lock();
+        inner.tripped = true;
+        self.open(&mut inner, Instant::now(), reason.into());
+    }
+
+    /// Closes the breaker and clears its failure history
+    pub fn reset(&self) {
+        let mut inner = self.inner.lock();
+        inner.tripped = false;
+        self.close(&mut inner);
+    }
+
+    pub fn status(&self) -> BreakerStatus {
+        let mut inner = self.inner.lock();
+        self.refresh(&mut inner, Instant::now());
+        BreakerStatus {
+            name: self.name.clone(),
+            state: inner.state,
+            consecutive_failures: inner.consecutive_failures,
+            opened_at: inner.opened_at.map(|(_, at)| at),
+            reason: inner.reason.clone(),
+        }
+    }
+
+    fn state_at(&self, now: Instant) -> BreakerState {
+        let mut inner = self.inner.lock();
+        self.refresh(&mut inner, now);
+        inner.state
+    }
+
+    fn allow_at(&self, now: Instant) -> bool {
+        let mut inner = self.inner.lock();
+        self.refresh(&mut inner, now);
+        match inner.state {
+            BreakerState::Closed => true,
+            BreakerState::Open => {
+                counter!(format!("{}.rejected", METRICS_PREFIX), 1, "breaker" => self.name.clone());
+                false
+            }
+            BreakerState::HalfOpen if inner.probes_remaining > 0 => {
+                inner.probes_remaining -= 1;
+                true
+            }
+            BreakerState::HalfOpen => false,
+        }
+    }
+
+    fn record_at(&self, now: Instant, failed: bool) -> bool {
+        let mut inner = self.inner.lock();
+        self.refresh(&mut inner, now);
+
+        if let BreakerPolicy::ErrorRate { window, .. } = self.config.policy {
+            inner.outcomes.push_back((now, failed));
+            while let Some((at, _)) = inner.outcomes.front() {
+                if now.duration_since(*at) <= window {
+                    break;
+                }
+                inner.outcomes.pop_front();
+            }
+        }
+
+        match (inner.state, failed) {
+            // A failed probe reopens for another cooldown
+            (BreakerState::HalfOpen, true) => {
+                self.open(&mut inner, now, "half-open probe failed".to_string());
+                true
+            }
+            (BreakerState::HalfOpen, false) => {
+                self.close(&mut inner);
+                false
+            }
+            // Late outcomes of calls admitted before opening don't change state
+            (BreakerState::Open, _) => false,
+            (BreakerState::Closed, false) => {
+                inner.consecutive_failures = 0;
+                false
+            }
+            (BreakerState::Closed, true) => {
+                inner.consecutive_failures += 1;
+                match self.breach(&inner) {
+                    Some(reason) => {
+                        self.open(&mut inner, now, reason);
+                        true
+                    }
+                    None => false,
+                }
+            }
+        }
+    }
+
+    /// Reason the policy opens the breaker, if its threshold is crossed
+    fn breach(&self, inner: &BreakerInner) -> Option<String> {
+        match self.config.policy {
+            BreakerPolicy::ConsecutiveFailures { threshold } => (inner.consecutive_failures >= threshold)
+                .then(|| format!("{} consecutive failures", inner.consecutive_failures)),
+            BreakerPolicy::ErrorRate { max_rate, min_calls, .. } => {
+                let calls = inner.outcomes.len();
+                let failures = inner.outcomes.iter().filter(|(_, failed)| *failed).count();
+                let rate = failures as f64 / calls.max(1) as f64;
+                (calls >= min_calls as usize && rate > max_rate)
+                    .then(|| format!("error rate {:.2} over {} calls exceeds {:.2}", rate, calls, max_rate))
+            }
+            BreakerPolicy::Manual => None,
+        }
+    }
+
+    /// Moves an open breaker to half-open once its cooldown has elapsed
+    fn refresh(&self, inner: &mut BreakerInner, now: Instant) {
+        if inner.state != BreakerState::Open || inner.tripped {
+            return;
+        }
+        let (Some(cooldown), Some((opened_at, _))) = (self.config.cooldown, inner.opened_at) else {
+            return;
+        };
+        if now.duration_since(opened_at) >= cooldown {
+            inner.state = BreakerState::HalfOpen;
+            inner.probes_remaining = self.config.half_open_probes;
+            self.record_state(BreakerState::HalfOpen);
+        }
+    }
+
+    fn open(&self, inner: &mut BreakerInner, now: Instant, reason: String) {
+        warn!(breaker = %self.name, %reason, "Circuit breaker opened");
+        inner.state = BreakerState::Open;
+        inner.opened_at = Some((now, Utc::now()));
+        inner.reason = Some(reason);
+        inner.probes_remaining = 0;
+        self.record_state(BreakerState::Open);
+    }
+
+    fn close(&self, inner: &mut BreakerInner) {
+        if inner.state != BreakerState::Closed {
+            info!(breaker = %self.name, "Circuit breaker closed");
+        }
+        inner.state = BreakerState::Closed;
+        inner.consecutive_failures = 0;
+        inner.outcomes.clear();
+        inner.opened_at = None;
+        inner.reason = None;
+        inner.probes_remaining = 0;
+        self.record_state(BreakerState::Closed);
+    }
+
+    fn record_state(&self, state: BreakerState) {
+        counter!(
+            format!("{}.transitions", METRICS_PREFIX), 1,
+            "breaker" => self.name.clone(),
+            "state" => state.as_str()
+        );
+        gauge!(format!("{}.state", METRICS_PREFIX), state.gauge_value(), "breaker" => self.name.clone());
+    }
+}
+
+/// Breakers reported by the health endpoint; dropped breakers fall out of the listing
+#[derive(Debug, Default)]
+pub struct CircuitBreakerRegistry {
+    breakers: RwLock<Vec<Weak<CircuitBreaker>>>,
+}
+
+impl CircuitBreakerRegistry {
+    pub fn register(&self, breaker: &Arc<CircuitBreaker>) {
+        let mut breakers = self.breakers.write();
+        breakers.retain(|weak| weak.strong_count() > 0);
+        breakers.push(Arc::downgrade(breaker));
+    }
+
+    /// Status of every live breaker, sorted by name
+    pub fn statuses(&self) -> Vec<BreakerStatus> {
+        let mut statuses: Vec<BreakerStatus> = self
+            .breakers
+            .read()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|breaker| breaker.status())
+            .collect();
+        statuses.sort_by(|a, b| a.name.cmp(&b.name));
+        statuses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COOLDOWN: Duration = Duration::from_secs(30);
+
+    #[test]
+    fn test_consecutive_failures_open_and_success_resets_count() {
+        let breaker = CircuitBreaker::new("test", BreakerConfig::consecutive_failures(3));
+        assert!(!breaker.record_failure());
+        assert!(!breaker.record_failure());
+        breaker.record_success();
+        assert!(!breaker.record_failure());
+        assert!(!breaker.record_failure());
+        assert!(breaker.allow());
+
+        assert!(breaker.record_failure());
+        assert!(breaker.is_open());
+        assert!(!breaker.allow());
+        assert_eq!(breaker.status().reason.as_deref(), Some("3 consecutive failures"));
+    }
+
+    #[test]
+    fn test_error_rate_over_window() {
+        let breaker = CircuitBreaker::new("test", BreakerConfig::error_rate(Duration::from_secs(10), 0.5, 4));
+        let start = Instant::now();
+
+        // Below the minimum call count nothing opens
+        assert!(!breaker.record_at(start, true));
+        assert!(!breaker.record_at(start, true));
+        assert!(!breaker.record_at(start, false));
+
+        // Failures that age out of the window no longer count
+        let later = start + Duration::from_secs(11);
+        assert!(!breaker.record_at(later, false));
+        assert!(!breaker.record_at(later, false));
+        assert!(!breaker.record_at(later, true));
+        assert_eq!(breaker.state_at(later), BreakerState::Closed);
+
+        // Three failures in five calls exceeds half
+        assert!(!breaker.record_at(later, true));
+        assert!(breaker.record_at(later, true));
+        assert_eq!(breaker.state_at(later), BreakerState::Open);
+    }
+
+    #[test]
+    fn test_half_open_probe_success_closes() {
+        let breaker = CircuitBreaker::new("test", BreakerConfig::consecutive_failures(1).with_cooldown(COOLDOWN));
+        let start = Instant::now();
+        assert!(breaker.record_at(start, true));
+        assert!(!breaker.allow_at(start + COOLDOWN / 2));
+
+        // One probe is let through after the cooldown; further calls wait on its outcome
+        let probe_at = start + COOLDOWN;
+        assert_eq!(breaker.state_at(probe_at), BreakerState::HalfOpen);
+        assert!(breaker.allow_at(probe_at));
+        assert!(!breaker.allow_at(probe_at));
+        assert!(!breaker.is_open());
+
+        breaker.record_at(probe_at, false);
+        assert_eq!(breaker.state_at(probe_at), BreakerState::Closed);
+        assert!(breaker.allow_at(probe_at));
+        assert_eq!(breaker.status().reason, None);
+    }
+
+    #[test]
+    fn test_half_open_probe_failure_reopens_for_another_cooldown() {
+        let config = BreakerConfig::consecutive_failures(2)
+            .with_cooldown(COOLDOWN)
+            .with_half_open_probes(2);
+        let breaker = CircuitBreaker::new("test", config);
+        let start = Instant::now();
+        breaker.record_at(start, true);
+        assert!(breaker.record_at(start, true));
+
+        let probe_at = start + COOLDOWN;
+        assert!(breaker.allow_at(probe_at));
+        assert!(breaker.allow_at(probe_at));
+        assert!(!breaker.allow_at(probe_at));
+
+        // A single failed probe reopens, whatever the consecutive failure threshold
+        assert!(breaker.record_at(probe_at, true));
+        assert_eq!(breaker.state_at(probe_at + COOLDOWN / 2), BreakerState::Open);
+        assert_eq!(breaker.status().reason.as_deref(), Some("half-open probe failed"));
+        assert_eq!(breaker.state_at(probe_at + COOLDOWN), BreakerState::HalfOpen);
+    }
+
+    #[test]
+    fn test_manual_trip_ignores_cooldown_until_reset() {
+        let breaker = CircuitBreaker::new("test", BreakerConfig::manual().with_cooldown(COOLDOWN));
+        for _ in 0..10 {
+            assert!(!breaker.record_failure());
+        }
+        assert!(breaker.allow());
+
+        breaker.trip("operator halt");
+        assert_eq!(breaker.state_at(Instant::now() + COOLDOWN * 2), BreakerState::Open);
+        assert_eq!(breaker.status().reason.as_deref(), Some("operator halt"));
+
+        breaker.reset();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        assert!(breaker.status().opened_at.is_none());
+    }
+
+    #[test]
+    fn test_registry_lists_live_breakers() {
+        let registry = CircuitBreakerRegistry::default();
+        let alive = Arc::new(CircuitBreaker::new("b-alive", BreakerConfig::manual()));
+        let first = Arc::new(CircuitBreaker::new("a-first", BreakerConfig::manual()));
+        registry.register(&alive);
+        registry.register(&first);
+        {
+            let dropped = Arc::new(CircuitBreaker::new("dropped", BreakerConfig::manual()));
+            registry.register(&dropped);
+        }
+        alive.trip("halted");
+
+        let statuses = registry.statuses();
+        let names: Vec<&str> = statuses.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["a-first", "b-alive"]);
+        assert_eq!(statuses[1].state, BreakerState::Open);
+
+        // Serialized state uses the health endpoint's snake_case names
+        assert_eq!(serde_json::to_value(BreakerState::HalfOpen).unwrap(), "half_open");
+    }
+}
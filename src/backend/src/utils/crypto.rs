@@ -10,11 +10,16 @@ use aws_sdk_kms::{Client as KmsClient, Region}; // v0.28.0
 use base58::{FromBase58, ToBase58}; // v0.2.0
 use ed25519_dalek::{PublicKey, Signature, Verifier}; // v1.0.1
 use rand::{rngs::OsRng, RngCore}; // v0.8.5
+use serde::{Deserialize, Serialize, Serializer}; // v1.0.164
 use tracing::{error, info, instrument, warn}; // v0.1.37
 
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
 use std::time::Duration;
 use std::convert::TryFrom;
 use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, RwLock};
 
 // Security constants
 const NONCE_LENGTH: usize = 32;
@@ -24,15 +29,26 @@ const MAX_KMS_RETRIES: u32 = 3;
 const KMS_TIMEOUT_MS: u64 = 1000;
 const MAX_ENCRYPTION_SIZE: usize = 1048576; // 1MB
 const WALLET_ADDRESS_LENGTH: usize = 32;
+/// Placeholder printed in place of secret values
+pub const REDACTED: &str = "[REDACTED]";
+/// Prefix marking an env value as `kms:<base58 nonce>:<base58 ciphertext>`
+pub const KMS_VALUE_PREFIX: &str = "kms:";
+/// Key version of data encrypted before versioned data keys existed
+pub const LEGACY_KEY_VERSION: u32 = 0;
 
 // Rate limiting for signature verification
 static FAILED_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
 
-/// Represents encrypted data with its GCM nonce
+lazy_static::lazy_static! {
+    static ref DATA_KEYS: Arc<KeyRing> = Arc::new(KeyRing::default());
+}
+
+/// Represents encrypted data with its GCM nonce and the data key version it was sealed with
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct EncryptedData {
     pub ciphertext: Vec<u8>,
     pub nonce: Vec<u8>,
+    pub key_version: u32,
 }
 
 impl EncryptedData {
@@ -44,7 +60,243 @@ impl EncryptedData {
         if ciphertext.is_empty() {
             return Err("Ciphertext cannot be empty".to_string());
         }
-        Ok(Self { ciphertext, nonce })
+        Ok(Self { ciphertext, nonce, key_version: LEGACY_KEY_VERSION })
+    }
+
+    /// Tags the data with the version of the data key that sealed it
+    pub fn with_key_version(mut self, key_version: u32) -> Self {
+        self.key_version = key_version;
+        self
+    }
+}
+
+impl EncryptedData {
+    /// Encodes as `<base58 nonce>:<base58 ciphertext>` for storage in env values, prefixed
+    /// with `v<version>:` when sealed with a versioned data key
+    pub fn encode(&self) -> String {
+        let encoded = format!("{}:{}", self.nonce.to_base58(), self.ciphertext.to_base58());
+        if self.key_version == LEGACY_KEY_VERSION {
+            encoded
+        } else {
+            format!("v{}:{}", self.key_version, encoded)
+        }
+    }
+
+    /// Parses the form produced by `encode`
+    pub fn decode(encoded: &str) -> Result<Self, String> {
+        let (key_version, encoded) = match encoded.strip_prefix('v').and_then(|rest| rest.split_once(':')) {
+            Some((version, rest)) => (
+                version.parse().map_err(|_| "Invalid key version".to_string())?,
+                rest,
+            ),
+            None => (LEGACY_KEY_VERSION, encoded),
+        };
+        let (nonce, ciphertext) = encoded
+            .split_once(':')
+            .ok_or_else(|| "Encrypted value must be nonce:ciphertext".to_string())?;
+        let nonce = nonce
+            .from_base58()
+            .map_err(|_| "Invalid nonce encoding".to_string())?;
+        let ciphertext = ciphertext
+            .from_base58()
+            .map_err(|_| "Invalid ciphertext encoding".to_string())?;
+        Self::new(ciphertext, nonce).map(|data| data.with_key_version(key_version))
+    }
+}
+
+/// Plaintext data keys by version. New data is sealed with the active version; older
+/// versions stay available for decryption until a rotation retires them.
+#[derive(Default)]
+pub struct KeyRing {
+    keys: RwLock<BTreeMap<u32, Secret<Vec<u8>>>>,
+    active: RwLock<Option<u32>>,
+}
+
+impl fmt::Debug for KeyRing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyRing")
+            .field("versions", &self.versions())
+            .field("active", &self.active_version())
+            .finish()
+    }
+}
+
+impl KeyRing {
+    /// Adds a data key; the key must be 256 bits
+    pub fn insert(&self, version: u32, key: Vec<u8>) -> Result<(), String> {
+        if version == LEGACY_KEY_VERSION {
+            return Err("Key version 0 is reserved for legacy data".to_string());
+        }
+        if key.len() != AES_KEY_LENGTH {
+            return Err(format!("Invalid data key length: {}", key.len()));
+        }
+        self.keys.write().unwrap().insert(version, Secret::new(key));
+        Ok(())
+    }
+
+    /// Makes a loaded version the one new data is sealed with
+    pub fn set_active(&self, version: u32) -> Result<(), String> {
+        if !self.keys.read().unwrap().contains_key(&version) {
+            return Err(format!("Data key version {} is not loaded", version));
+        }
+        *self.active.write().unwrap() = Some(version);
+        Ok(())
+    }
+
+    pub fn active_version(&self) -> Option<u32> {
+        *self.active.read().unwrap()
+    }
+
+    pub fn versions(&self) -> Vec<u32> {
+        self.keys.read().unwrap().keys().copied().collect()
+    }
+
+    /// Drops a version's key material; the active version cannot be retired
+    pub fn retire(&self, version: u32) -> Result<(), String> {
+        if self.active_version() == Some(version) {
+            return Err(format!("Data key version {} is active", version));
+        }
+        self.keys.write().unwrap().remove(&version);
+        Ok(())
+    }
+
+    /// Seals data with the active data key
+    pub fn encrypt(&self, data: &str) -> Result<EncryptedData, String> {
+        let version = self.active_version().ok_or_else(|| "No active data key".to_string())?;
+        self.encrypt_with_version(version, data)
+    }
+
+    /// Seals data with a specific loaded data key version
+    pub fn encrypt_with_version(&self, version: u32, data: &str) -> Result<EncryptedData, String> {
+        let keys = self.keys.read().unwrap();
+        let key = keys
+            .get(&version)
+            .ok_or_else(|| format!("Data key version {} is not loaded", version))?;
+        seal(key.expose(), data).map(|data| data.with_key_version(version))
+    }
+
+    /// Opens data with the key version recorded alongside it
+    pub fn decrypt(&self, encrypted_data: &EncryptedData) -> Result<String, String> {
+        let keys = self.keys.read().unwrap();
+        let key = keys.get(&encrypted_data.key_version).ok_or_else(|| {
+            format!("Data key version {} is not loaded", encrypted_data.key_version)
+        })?;
+        open(key.expose(), encrypted_data)
+    }
+}
+
+/// Process-wide data keys consulted by `encrypt_sensitive_data` and `decrypt_sensitive_data`
+pub fn data_keys() -> Arc<KeyRing> {
+    DATA_KEYS.clone()
+}
+
+fn seal(key: &[u8], data: &str) -> Result<EncryptedData, String> {
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|e| {
+        error!("Failed to initialize cipher: {}", e);
+        "Cipher initialization failed".to_string()
+    })?;
+    let mut nonce = vec![0u8; GCM_NONCE_LENGTH];
+    OsRng.fill_bytes(&mut nonce);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), data.as_bytes())
+        .map_err(|e| {
+            error!("Encryption failed: {}", e);
+            "Encryption operation failed".to_string()
+        })?;
+    EncryptedData::new(ciphertext, nonce)
+}
+
+fn open(key: &[u8], encrypted_data: &EncryptedData) -> Result<String, String> {
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|e| {
+        error!("Failed to initialize cipher for decryption: {}", e);
+        "Cipher initialization failed".to_string()
+    })?;
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&encrypted_data.nonce), encrypted_data.ciphertext.as_ref())
+        .map_err(|e| {
+            error!("Decryption failed: {}", e);
+            "Decryption operation failed".to_string()
+        })?;
+    String::from_utf8(plaintext).map_err(|e| {
+        error!("Invalid UTF-8 in decrypted data: {}", e);
+        "Invalid UTF-8 in decrypted data".to_string()
+    })
+}
+
+/// Sensitive value whose Debug, Display and Serialize output is always redacted
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the plaintext; callers must not log it
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+}
+
+impl Secret<String> {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl From<String> for Secret<String> {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for Secret<String> {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T> fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T> Serialize for Secret<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
+/// Resolves a raw env value into a secret, decrypting `kms:`-prefixed values with KMS
+pub async fn resolve_secret(raw: String, kms_key_id: &str) -> Result<Secret<String>, String> {
+    let kms_key_id = kms_key_id.to_string();
+    resolve_secret_with(raw, |encrypted| decrypt_sensitive_data(encrypted, kms_key_id)).await
+}
+
+/// Resolves a raw env value into a secret using the given decryptor for `kms:` values
+pub async fn resolve_secret_with<F, Fut>(raw: String, decrypt: F) -> Result<Secret<String>, String>
+where
+    F: FnOnce(EncryptedData) -> Fut,
+    Fut: Future<Output = Result<String, String>>,
+{
+    match raw.strip_prefix(KMS_VALUE_PREFIX) {
+        Some(encoded) => {
+            let encrypted = EncryptedData::decode(encoded)?;
+            decrypt(encrypted).await.map(Secret::new)
+        }
+        None => Ok(Secret::new(raw)),
     }
 }
 
@@ -59,6 +311,12 @@ pub async fn encrypt_sensitive_data(
         return Err("Data size exceeds maximum allowed size".to_string());
     }
 
+    // Seal with the active versioned data key once rotation has installed one
+    let data_keys = data_keys();
+    if data_keys.active_version().is_some() {
+        return data_keys.encrypt(&data);
+    }
+
     let kms_client = KmsClient::new(Region::ApSoutheast1);
     let mut retries = 0;
 
@@ -122,6 +380,12 @@ pub async fn decrypt_sensitive_data(
     encrypted_data: EncryptedData,
     kms_key_id: String,
 ) -> Result<String, String> {
+    // Versioned data picks its key from the ring, so old and new versions both open
+    // during a rotation
+    if encrypted_data.key_version != LEGACY_KEY_VERSION {
+        return data_keys().decrypt(&encrypted_data);
+    }
+
     let kms_client = KmsClient::new(Region::ApSoutheast1);
     let mut retries = 0;
 
@@ -298,6 +562,70 @@ mod tests {
         assert!(nonce1.from_base58().unwrap().len() == NONCE_LENGTH);
     }
 
+    #[test]
+    fn test_secret_redacted() {
+        let secret = Secret::from("hunter2-database-password");
+        assert_eq!(format!("{:?}", secret), REDACTED);
+        assert_eq!(secret.to_string(), REDACTED);
+        assert_eq!(serde_json::to_string(&secret).unwrap(), "\"[REDACTED]\"");
+        assert_eq!(secret.expose(), "hunter2-database-password");
+    }
+
+    #[tokio::test]
+    async fn test_kms_prefixed_secret_decrypted() {
+        let encrypted = EncryptedData::new(vec![7u8; 32], vec![1u8; GCM_NONCE_LENGTH]).unwrap();
+        let raw = format!("{}{}", KMS_VALUE_PREFIX, encrypted.encode());
+
+        let secret = resolve_secret_with(raw, move |received| async move {
+            assert_eq!(received, encrypted);
+            Ok("decrypted-jwt-secret".to_string())
+        })
+        .await
+        .unwrap();
+        assert_eq!(secret.expose(), "decrypted-jwt-secret");
+
+        // Plain values bypass KMS entirely
+        let plain = resolve_secret_with("plain-value".to_string(), |_| async {
+            Err::<String, String>("decryptor should not run".to_string())
+        })
+        .await
+        .unwrap();
+        assert_eq!(plain.expose(), "plain-value");
+
+        assert!(resolve_secret_with("kms:not-encoded".to_string(), |_| async {
+            Ok::<String, String>(String::new())
+        })
+        .await
+        .is_err());
+    }
+
+    #[test]
+    fn test_key_ring_decrypts_each_version() {
+        let ring = KeyRing::default();
+        ring.insert(1, vec![1u8; AES_KEY_LENGTH]).unwrap();
+        ring.set_active(1).unwrap();
+        let old = ring.encrypt("webhook-secret").unwrap();
+        assert_eq!(old.key_version, 1);
+
+        ring.insert(2, vec![2u8; AES_KEY_LENGTH]).unwrap();
+        ring.set_active(2).unwrap();
+        let new = ring.encrypt("webhook-secret").unwrap();
+        assert_eq!(new.key_version, 2);
+
+        // Both versions open during the transition window
+        assert_eq!(ring.decrypt(&old).unwrap(), "webhook-secret");
+        assert_eq!(ring.decrypt(&new).unwrap(), "webhook-secret");
+
+        // The version survives encoding
+        assert_eq!(EncryptedData::decode(&new.encode()).unwrap(), new);
+        assert!(new.encode().starts_with("v2:"));
+
+        assert!(ring.retire(2).is_err());
+        ring.retire(1).unwrap();
+        assert!(ring.decrypt(&old).is_err());
+        assert!(ring.insert(3, vec![0u8; 16]).is_err());
+    }
+
     #[test]
     fn test_encrypted_data_validation() {
         let valid_nonce = vec![0u8; GCM_NONCE_LENGTH];
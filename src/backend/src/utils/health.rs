@@ -0,0 +1,363 @@
+//! Component health monitor. Long-running services register a probe once they are serving;
+//! the monitor polls every probe on an interval, logs status transitions and exports each
+//! component's status through the system health gauges. When a quality source is set, each
+//! poll also folds collector staleness, aggregator confidence, RPC health, persistence backlog
+//! and recent slippage into a single system quality score between 0 and 1.
+//!
+//! Version dependencies:
+//! - tokio = "1.28"
+//! - parking_lot = "0.12"
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::utils::metrics::MetricsCollector;
+
+// Health monitor constants
+const STATUS_METRIC: &str = "status";
+const QUALITY_COMPONENT: &str = "system";
+const QUALITY_METRIC: &str = "quality_score";
+const DEFAULT_FRESH_STALENESS: Duration = Duration::from_secs(2);
+const DEFAULT_MAX_STALENESS: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_PERSISTENCE_BACKLOG: usize = 10_000;
+const DEFAULT_MAX_SLIPPAGE_RATIO: f64 = 3.0;
+
+/// Health of one registered component
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "reason", rename_all = "snake_case")]
+pub enum ComponentStatus {
+    Healthy,
+    Degraded(String),
+    Down(String),
+}
+
+impl ComponentStatus {
+    /// Gauge value exported per component: 1 healthy, 0.5 degraded, 0 down
+    fn gauge_value(&self) -> f64 {
+        match self {
+            Self::Healthy => 1.0,
+            Self::Degraded(_) => 0.5,
+            Self::Down(_) => 0.0,
+        }
+    }
+}
+
+/// Reports a component's current health; called from the monitor's polling task
+pub trait HealthProbe: Send + Sync {
+    fn status(&self) -> ComponentStatus;
+}
+
+/// Readings behind the system quality score
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityInputs {
+    /// Age of the stalest collector's latest update
+    pub collector_staleness: Duration,
+    /// Price aggregator confidence between 0 and 1
+    pub aggregator_confidence: f64,
+    pub rpc: ComponentStatus,
+    /// Writes waiting in the persistence queue
+    pub persistence_backlog: usize,
+    /// Recent realized slippage over the slippage expected at sizing
+    pub slippage_ratio: f64,
+}
+
+/// Supplies the quality score inputs; read on every poll
+pub trait QualitySource: Send + Sync {
+    fn inputs(&self) -> QualityInputs;
+}
+
+/// Points at which each quality input starts and finishes pulling the score down
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityThresholds {
+    /// Collector staleness tolerated without any penalty
+    pub fresh_staleness: Duration,
+    /// Collector staleness scoring zero
+    pub max_staleness: Duration,
+    /// Persistence backlog scoring zero
+    pub max_persistence_backlog: usize,
+    /// Realized over expected slippage scoring zero; at or below 1 there is no penalty
+    pub max_slippage_ratio: f64,
+}
+
+impl Default for QualityThresholds {
+    fn default() -> Self {
+        Self {
+            fresh_staleness: DEFAULT_FRESH_STALENESS,
+            max_staleness: DEFAULT_MAX_STALENESS,
+            max_persistence_backlog: DEFAULT_MAX_PERSISTENCE_BACKLOG,
+            max_slippage_ratio: DEFAULT_MAX_SLIPPAGE_RATIO,
+        }
+    }
+}
+
+impl QualityInputs {
+    /// Score between 0 and 1 set by the worst input, so one failing dependency is not
+    /// averaged away by healthy ones
+    pub fn score(&self, thresholds: &QualityThresholds) -> f64 {
+        let staleness = falloff(
+            self.collector_staleness.as_secs_f64(),
+            thresholds.fresh_staleness.as_secs_f64(),
+            thresholds.max_staleness.as_secs_f64(),
+        );
+        let backlog = falloff(
+            self.persistence_backlog as f64,
+            0.0,
+            thresholds.max_persistence_backlog as f64,
+        );
+        let slippage = falloff(self.slippage_ratio, 1.0, thresholds.max_slippage_ratio);
+        let confidence = if self.aggregator_confidence.is_finite() {
+            self.aggregator_confidence.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        [staleness, backlog, slippage, confidence, self.rpc.gauge_value()]
+            .into_iter()
+            .fold(1.0, f64::min)
+    }
+}
+
+/// 1 up to `start`, falling linearly to 0 at `end`
+fn falloff(value: f64, start: f64, end: f64) -> f64 {
+    if value.is_nan() {
+        return 0.0;
+    }
+    if value <= start {
+        return 1.0;
+    }
+    if end <= start {
+        return 0.0;
+    }
+    (1.0 - (value - start) / (end - start)).clamp(0.0, 1.0)
+}
+
+/// Polls registered component probes and records their status
+pub struct HealthMonitor {
+    interval: Duration,
+    metrics: Arc<MetricsCollector>,
+    probes: RwLock<HashMap<String, Arc<dyn HealthProbe>>>,
+    statuses: RwLock<HashMap<String, ComponentStatus>>,
+    quality_source: RwLock<Option<Arc<dyn QualitySource>>>,
+    quality_thresholds: QualityThresholds,
+    quality: RwLock<Option<f64>>,
+    task: Mutex<Option<tokio::task::JoinHandle<()>>>,
+}
+
+impl fmt::Debug for HealthMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HealthMonitor")
+            .field("interval", &self.interval)
+            .field("components", &self.probes.read().keys().collect::<Vec<_>>())
+            .field("quality", &*self.quality.read())
+            .finish()
+    }
+}
+
+impl HealthMonitor {
+    pub fn new(interval: Duration, metrics: Arc<MetricsCollector>) -> Self {
+        Self {
+            interval,
+            metrics,
+            probes: RwLock::new(HashMap::new()),
+            statuses: RwLock::new(HashMap::new()),
+            quality_source: RwLock::new(None),
+            quality_thresholds: QualityThresholds::default(),
+            quality: RwLock::new(None),
+            task: Mutex::new(None),
+        }
+    }
+
+    /// Replaces the default points at which quality inputs pull the score down
+    pub fn with_quality_thresholds(mut self, thresholds: QualityThresholds) -> Self {
+        self.quality_thresholds = thresholds;
+        self
+    }
+
+    /// Computes the system quality score from this source on every poll
+    pub fn set_quality_source(&self, source: Arc<dyn QualitySource>) {
+        let inputs = source.inputs();
+        *self.quality_source.write() = Some(source);
+        self.record_quality(&inputs);
+    }
+
+    /// Registers or replaces a component's probe and records its status immediately
+    pub fn register(&self, component: &str, probe: Arc<dyn HealthProbe>) {
+        let status = probe.status();
+        self.probes.write().insert(component.to_string(), probe);
+        self.record(component, status);
+        info!(component, "Registered component with health monitor");
+    }
+
+    /// Stops polling a component that has shut down
+    pub fn deregister(&self, component: &str) {
+        self.probes.write().remove(component);
+        self.statuses.write().remove(component);
+    }
+
+    /// Polls every probe now and returns the resulting statuses
+    pub fn check(&self) -> HashMap<String, ComponentStatus> {
+        let probes: Vec<(String, Arc<dyn HealthProbe>)> = self
+            .probes
+            .read()
+            .iter()
+            .map(|(component, probe)| (component.clone(), probe.clone()))
+            .collect();
+        for (component, probe) in probes {
+            self.record(&component, probe.status());
+        }
+        let source = self.quality_source.read().clone();
+        if let Some(source) = source {
+            self.record_quality(&source.inputs());
+        }
+        self.statuses()
+    }
+
+    /// Scores the inputs, exports the score and keeps it as the current system quality
+    pub fn record_quality(&self, inputs: &QualityInputs) -> f64 {
+        let score = inputs.score(&self.quality_thresholds);
+        if let Err(e) = self
+            .metrics
+            .update_system_health(QUALITY_COMPONENT, QUALITY_METRIC, score)
+        {
+            warn!("Failed to record system quality: {}", e);
+        }
+        let previous = self.quality.write().replace(score);
+        if previous.map_or(true, |previous| previous >= 1.0) && score < 1.0 {
+            warn!(score, inputs = ?inputs, "System quality degraded");
+        } else if previous.map_or(false, |previous| previous < 1.0) && score >= 1.0 {
+            info!("System quality restored");
+        }
+        score
+    }
+
+    /// System quality as of the last poll; `None` until a quality source is set
+    pub fn quality_score(&self) -> Option<f64> {
+        *self.quality.read()
+    }
+
+    /// Statuses as of the last poll
+    pub fn statuses(&self) -> HashMap<String, ComponentStatus> {
+        self.statuses.read().clone()
+    }
+
+    fn record(&self, component: &str, status: ComponentStatus) {
+        if let Err(e) = self
+            .metrics
+            .update_system_health(component, STATUS_METRIC, status.gauge_value())
+        {
+            warn!(component, "Failed to record component health: {}", e);
+        }
+
+        let previous = self.statuses.write().insert(component.to_string(), status.clone());
+        if previous.as_ref() == Some(&status) {
+            return;
+        }
+        match &status {
+            ComponentStatus::Healthy => info!(component, "Component healthy"),
+            ComponentStatus::Degraded(reason) | ComponentStatus::Down(reason) => {
+                warn!(component, status = ?status, "Component unhealthy: {}", reason)
+            }
+        }
+    }
+
+    /// Starts polling on the configured interval; a second call is a no-op
+    pub async fn start(self: &Arc<Self>) {
+        let mut task = self.task.lock();
+        if task.is_some() {
+            return;
+        }
+        let monitor = self.clone();
+        *task = Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(monitor.interval);
+            loop {
+                interval.tick().await;
+                monitor.check();
+            }
+        }));
+    }
+
+    pub async fn stop(&self) {
+        if let Some(task) = self.task.lock().take() {
+            task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    struct FlagProbe(AtomicBool);
+
+    impl HealthProbe for FlagProbe {
+        fn status(&self) -> ComponentStatus {
+            if self.0.load(Ordering::SeqCst) {
+                ComponentStatus::Healthy
+            } else {
+                ComponentStatus::Down("flag cleared".to_string())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registered_probe_polled() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let monitor = HealthMonitor::new(Duration::from_secs(30), metrics);
+        let probe = Arc::new(FlagProbe(AtomicBool::new(true)));
+        monitor.register("websocket", probe.clone());
+        assert_eq!(monitor.statuses()["websocket"], ComponentStatus::Healthy);
+
+        probe.0.store(false, Ordering::SeqCst);
+        assert_eq!(
+            monitor.check()["websocket"],
+            ComponentStatus::Down("flag cleared".to_string())
+        );
+
+        monitor.deregister("websocket");
+        assert!(monitor.check().is_empty());
+    }
+
+    #[test]
+    fn test_quality_score_follows_worst_input() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let monitor = HealthMonitor::new(Duration::from_secs(30), metrics);
+        assert_eq!(monitor.quality_score(), None);
+
+        let healthy = QualityInputs {
+            collector_staleness: Duration::from_secs(1),
+            aggregator_confidence: 1.0,
+            rpc: ComponentStatus::Healthy,
+            persistence_backlog: 0,
+            slippage_ratio: 0.8,
+        };
+        assert_eq!(monitor.record_quality(&healthy), 1.0);
+
+        // Staleness halfway between the 2s and 30s thresholds outweighs a small backlog
+        let stale = QualityInputs {
+            collector_staleness: Duration::from_secs(16),
+            persistence_backlog: 1_000,
+            ..healthy.clone()
+        };
+        assert_eq!(monitor.record_quality(&stale), 0.5);
+
+        let slipping = QualityInputs {
+            slippage_ratio: 2.0,
+            aggregator_confidence: 0.7,
+            ..healthy.clone()
+        };
+        assert_eq!(monitor.record_quality(&slipping), 0.5);
+
+        let rpc_down = QualityInputs {
+            rpc: ComponentStatus::Down("timeouts".to_string()),
+            ..healthy
+        };
+        assert_eq!(monitor.record_quality(&rpc_down), 0.0);
+        assert_eq!(monitor.quality_score(), Some(0.0));
+    }
+}
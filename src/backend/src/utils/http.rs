@@ -0,0 +1,570 @@
+//! Shared client for outbound REST calls. Each host gets its own pooled reqwest client
+//! with connect and read timeouts, a cap on requests in flight, jittered retries for
+//! idempotent GETs and a circuit breaker from the shared breaker registry, so a hung venue
+//! endpoint fails fast instead of stalling the task that called it.
+//!
+//! Version dependencies:
+//! - reqwest = "0.11"
+//! - tokio = "1.28"
+//! - rand = "0.8"
+//! - lazy_static = "1.4"
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use metrics::{counter, histogram};
+use parking_lot::RwLock;
+use rand::Rng;
+use reqwest::{Method, RequestBuilder, Response, Url};
+use thiserror::Error;
+use tokio::sync::Semaphore;
+use tracing::{debug, warn};
+
+use crate::utils::circuit_breaker::{BreakerConfig, CircuitBreaker};
+
+// HTTP client constants
+const METRICS_PREFIX: &str = "trading_bot.http";
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_MAX_RETRIES: u32 = 2;
+const DEFAULT_BASE_BACKOFF: Duration = Duration::from_millis(100);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(2);
+const DEFAULT_JITTER: f64 = 0.5;
+const DEFAULT_MAX_IN_FLIGHT: usize = 16;
+const DEFAULT_BREAKER_FAILURES: u32 = 5;
+const DEFAULT_BREAKER_COOLDOWN: Duration = Duration::from_secs(30);
+const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+const DEFAULT_POOL_MAX_IDLE_PER_HOST: usize = 8;
+
+lazy_static::lazy_static! {
+    static ref SHARED: Arc<HttpClient> = Arc::new(HttpClient::new("shared", HttpConfig::from_env()));
+}
+
+/// Client shared by venue REST calls, configured from the environment
+pub fn shared() -> Arc<HttpClient> {
+    SHARED.clone()
+}
+
+#[derive(Debug, Error)]
+pub enum HttpError {
+    #[error("invalid url {0}")]
+    InvalidUrl(String),
+    #[error("circuit open for {host}")]
+    CircuitOpen { host: String },
+    #[error("too many requests in flight to {host}")]
+    Saturated { host: String },
+    #[error("request to {host} timed out after {timeout_ms}ms")]
+    Timeout { host: String, timeout_ms: u64 },
+    #[error("{host} responded {status}")]
+    Status { host: String, status: u16 },
+    #[error("request to {host} failed: {message}")]
+    Request { host: String, message: String },
+    #[error("http client configuration error: {0}")]
+    Config(String),
+}
+
+impl HttpError {
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            HttpError::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Failures that say the host is unhealthy; they count against its breaker and GETs retry them
+    pub fn is_transient(&self) -> bool {
+        match self {
+            HttpError::Timeout { .. } | HttpError::Request { .. } => true,
+            HttpError::Status { status, .. } => *status >= 500 || *status == 429,
+            _ => false,
+        }
+    }
+
+    fn outcome(&self) -> &'static str {
+        match self {
+            HttpError::Timeout { .. } => "timeout",
+            HttpError::Status { .. } => "status",
+            HttpError::CircuitOpen { .. } => "circuit_open",
+            HttpError::Saturated { .. } => "saturated",
+            _ => "error",
+        }
+    }
+}
+
+/// Backoff between attempts of an idempotent request
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Attempts after the first
+    pub max_retries: u32,
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Fraction of each backoff randomly shaved off so callers don't retry in lockstep
+    pub jitter: f64,
+}
+
+impl RetryPolicy {
+    pub fn none() -> Self {
+        Self { max_retries: 0, ..Self::default() }
+    }
+
+    fn backoff(&self, retry: u32) -> Duration {
+        let backoff = self
+            .base_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return backoff;
+        }
+        backoff.mul_f64(1.0 - rand::thread_rng().gen_range(0.0..=jitter))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_RETRIES,
+            base_backoff: DEFAULT_BASE_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            jitter: DEFAULT_JITTER,
+        }
+    }
+}
+
+/// Timeouts, retries, concurrency and breaker settings for one host
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HostConfig {
+    pub connect_timeout: Duration,
+    /// Whole-request timeout unless the call sets its own
+    pub read_timeout: Duration,
+    pub retry: RetryPolicy,
+    pub max_in_flight: usize,
+    pub breaker: BreakerConfig,
+}
+
+impl Default for HostConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            read_timeout: DEFAULT_READ_TIMEOUT,
+            retry: RetryPolicy::default(),
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
+            breaker: BreakerConfig::consecutive_failures(DEFAULT_BREAKER_FAILURES)
+                .with_cooldown(DEFAULT_BREAKER_COOLDOWN),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpConfig {
+    /// Settings for hosts without an entry in `hosts`
+    pub default_host: HostConfig,
+    /// Per-host overrides keyed by host name
+    pub hosts: HashMap<String, HostConfig>,
+    /// Proxy every request is routed through
+    pub proxy: Option<String>,
+    pub pool_idle_timeout: Duration,
+    pub pool_max_idle_per_host: usize,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            default_host: HostConfig::default(),
+            hosts: HashMap::new(),
+            proxy: None,
+            pool_idle_timeout: DEFAULT_POOL_IDLE_TIMEOUT,
+            pool_max_idle_per_host: DEFAULT_POOL_MAX_IDLE_PER_HOST,
+        }
+    }
+}
+
+impl HttpConfig {
+    /// Defaults overridden by `HTTP_*` environment variables
+    pub fn from_env() -> Self {
+        let millis = |name: &str| env_number(name).map(Duration::from_millis);
+
+        let mut config = Self::default();
+        let host = &mut config.default_host;
+        host.connect_timeout = millis("HTTP_CONNECT_TIMEOUT_MS").unwrap_or(host.connect_timeout);
+        host.read_timeout = millis("HTTP_READ_TIMEOUT_MS").unwrap_or(host.read_timeout);
+        host.retry.max_retries = env_number("HTTP_MAX_RETRIES").unwrap_or(host.retry.max_retries);
+        host.max_in_flight = env_number("HTTP_MAX_IN_FLIGHT_PER_HOST").unwrap_or(host.max_in_flight);
+        config.proxy = std::env::var("HTTP_PROXY_URL").ok().filter(|url| !url.is_empty());
+        config
+    }
+
+    pub fn host(&self, host: &str) -> &HostConfig {
+        self.hosts.get(host).unwrap_or(&self.default_host)
+    }
+}
+
+fn env_number<T: std::str::FromStr>(name: &str) -> Option<T> {
+    std::env::var(name).ok().and_then(|value| value.parse().ok())
+}
+
+/// Client certificate presented to, and the only CA trusted for, every host
+#[derive(Clone)]
+struct ClientIdentity {
+    identity: reqwest::Identity,
+    ca: reqwest::Certificate,
+}
+
+/// Pooled client, concurrency cap and breaker for one host
+#[derive(Debug)]
+struct HostState {
+    config: HostConfig,
+    client: reqwest::Client,
+    in_flight: Semaphore,
+    breaker: Arc<CircuitBreaker>,
+}
+
+/// Outbound HTTP client with per-host timeouts, retries, concurrency limits and breakers
+pub struct HttpClient {
+    /// Distinguishes this client's breakers and metrics from other clients'
+    name: &'static str,
+    config: HttpConfig,
+    identity: Option<ClientIdentity>,
+    hosts: RwLock<HashMap<String, Arc<HostState>>>,
+}
+
+impl std::fmt::Debug for HttpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpClient")
+            .field("name", &self.name)
+            .field("config", &self.config)
+            .field("mtls", &self.identity.is_some())
+            .finish()
+    }
+}
+
+impl HttpClient {
+    pub fn new(name: &'static str, config: HttpConfig) -> Self {
+        Self {
+            name,
+            config,
+            identity: None,
+            hosts: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Client for a single host's settings, such as a webhook endpoint or a signer
+    pub fn for_host(name: &'static str, host: HostConfig) -> Self {
+        Self::new(name, HttpConfig { default_host: host, ..HttpConfig::default() })
+    }
+
+    /// Makes every request HTTPS-only with mutual TLS, trusting only `ca`
+    pub fn with_client_identity(mut self, identity: reqwest::Identity, ca: reqwest::Certificate) -> Self {
+        self.identity = Some(ClientIdentity { identity, ca });
+        self
+    }
+
+    /// Breaker guarding the url's host
+    pub fn breaker(&self, url: &str) -> Result<Arc<CircuitBreaker>, HttpError> {
+        Ok(self.host(url)?.1.breaker.clone())
+    }
+
+    /// Sends an idempotent GET, retrying transient failures with jittered backoff
+    pub async fn get<F>(&self, url: &str, timeout: Option<Duration>, build: F) -> Result<Response, HttpError>
+    where
+        F: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        self.execute(Method::GET, url, timeout, true, build).await
+    }
+
+    /// Sends a POST once; non-idempotent requests are never retried here
+    pub async fn post<F>(&self, url: &str, timeout: Option<Duration>, build: F) -> Result<Response, HttpError>
+    where
+        F: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        self.execute(Method::POST, url, timeout, false, build).await
+    }
+
+    async fn execute<F>(
+        &self,
+        method: Method,
+        url: &str,
+        timeout: Option<Duration>,
+        idempotent: bool,
+        build: F,
+    ) -> Result<Response, HttpError>
+    where
+        F: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        let (host, state) = self.host(url)?;
+        let timeout = timeout.unwrap_or(state.config.read_timeout);
+        let max_retries = if idempotent { state.config.retry.max_retries } else { 0 };
+
+        let mut retry = 0;
+        loop {
+            let result = self.attempt(&host, &state, method.clone(), url, timeout, &build).await;
+            self.record(&host, &result);
+            let err = match result {
+                Ok(response) => return Ok(response),
+                Err(e) => e,
+            };
+            // A breaker opened by this failure ends the retries too
+            if !err.is_transient() || retry >= max_retries || state.breaker.is_open() {
+                return Err(err);
+            }
+
+            retry += 1;
+            counter!(format!("{}.retries", METRICS_PREFIX), 1, "client" => self.name, "host" => host.clone());
+            debug!(%host, retry, "Retrying request: {}", err);
+            tokio::time::sleep(state.config.retry.backoff(retry - 1)).await;
+        }
+    }
+
+    async fn attempt<F>(
+        &self,
+        host: &str,
+        state: &HostState,
+        method: Method,
+        url: &str,
+        timeout: Duration,
+        build: &F,
+    ) -> Result<Response, HttpError>
+    where
+        F: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        let timed_out = || HttpError::Timeout {
+            host: host.to_string(),
+            timeout_ms: timeout.as_millis() as u64,
+        };
+
+        // Waiting for a slot counts against the request's own timeout
+        let started = Instant::now();
+        let _permit = tokio::time::timeout(timeout, state.in_flight.acquire())
+            .await
+            .map_err(|_| HttpError::Saturated { host: host.to_string() })?
+            .map_err(|_| HttpError::Saturated { host: host.to_string() })?;
+        let remaining = timeout.saturating_sub(started.elapsed());
+        if remaining.is_zero() {
+            return Err(timed_out());
+        }
+
+        if !state.breaker.allow() {
+            return Err(HttpError::CircuitOpen { host: host.to_string() });
+        }
+
+        let request = build(state.client.request(method, url)).timeout(remaining);
+        let result = match request.send().await {
+            Ok(response) if response.status().is_success() => Ok(response),
+            Ok(response) => Err(HttpError::Status {
+                host: host.to_string(),
+                status: response.status().as_u16(),
+            }),
+            Err(e) if e.is_timeout() => Err(timed_out()),
+            Err(e) => Err(HttpError::Request {
+                host: host.to_string(),
+                message: e.to_string(),
+            }),
+        };
+        histogram!(
+            format!("{}.latency_ms", METRICS_PREFIX),
+            started.elapsed().as_secs_f64() * 1000.0,
+            "client" => self.name,
+            "host" => host.to_string()
+        );
+
+        // Client errors mean the host is up and answering
+        match &result {
+            Err(e) if e.is_transient() => {
+                if state.breaker.record_failure() {
+                    warn!(client = self.name, %host, "Outbound requests to host suspended: {}", e);
+                }
+            }
+            _ => state.breaker.record_success(),
+        }
+        result
+    }
+
+    fn record(&self, host: &str, result: &Result<Response, HttpError>) {
+        let outcome = match result {
+            Ok(_) => "ok",
+            Err(e) => e.outcome(),
+        };
+        counter!(
+            format!("{}.requests", METRICS_PREFIX), 1,
+            "client" => self.name,
+            "host" => host.to_string(),
+            "outcome" => outcome
+        );
+    }
+
+    /// Host key and state for a url, creating the host's client on first use
+    fn host(&self, url: &str) -> Result<(String, Arc<HostState>), HttpError> {
+        let parsed = Url::parse(url).map_err(|_| HttpError::InvalidUrl(url.to_string()))?;
+        let name = parsed
+            .host_str()
+            .ok_or_else(|| HttpError::InvalidUrl(url.to_string()))?;
+        let key = match parsed.port() {
+            Some(port) => format!("{}:{}", name, port),
+            None => name.to_string(),
+        };
+
+        if let Some(state) = self.hosts.read().get(&key) {
+            return Ok((key, state.clone()));
+        }
+        let mut hosts = self.hosts.write();
+        if let Some(state) = hosts.get(&key) {
+            return Ok((key, state.clone()));
+        }
+        let config = *self.config.host(name);
+        let state = Arc::new(HostState {
+            config,
+            client: self.build_client(&config)?,
+            in_flight: Semaphore::new(config.max_in_flight.max(1)),
+            breaker: CircuitBreaker::new(format!("http.{}.{}", self.name, key), config.breaker).registered(),
+        });
+        hosts.insert(key.clone(), state.clone());
+        Ok((key, state))
+    }
+
+    fn build_client(&self, config: &HostConfig) -> Result<reqwest::Client, HttpError> {
+        let mut builder = reqwest::Client::builder()
+            .use_rustls_tls()
+            .connect_timeout(config.connect_timeout)
+            .timeout(config.read_timeout)
+            .pool_idle_timeout(self.config.pool_idle_timeout)
+            .pool_max_idle_per_host(self.config.pool_max_idle_per_host);
+        if let Some(proxy) = &self.config.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy).map_err(|e| HttpError::Config(e.to_string()))?);
+        }
+        if let Some(ClientIdentity { identity, ca }) = self.identity.clone() {
+            builder = builder
+                .https_only(true)
+                .tls_built_in_root_certs(false)
+                .add_root_certificate(ca)
+                .identity(identity);
+        }
+        builder.build().map_err(|e| HttpError::Config(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::circuit_breaker::BreakerState;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const COOLDOWN: Duration = Duration::from_millis(300);
+
+    fn client() -> HttpClient {
+        HttpClient::for_host(
+            "test",
+            HostConfig {
+                read_timeout: Duration::from_millis(100),
+                retry: RetryPolicy {
+                    max_retries: 4,
+                    base_backoff: Duration::from_millis(10),
+                    max_backoff: Duration::from_millis(20),
+                    jitter: 0.5,
+                },
+                breaker: BreakerConfig::consecutive_failures(3).with_cooldown(COOLDOWN),
+                ..HostConfig::default()
+            },
+        )
+    }
+
+    async fn received(server: &MockServer) -> usize {
+        server.received_requests().await.unwrap().len()
+    }
+
+    #[tokio::test]
+    async fn test_stalled_host_times_out_opens_breaker_and_recovers() {
+        let server = MockServer::start().await;
+        // Stalls well past the read timeout for the first three requests, then recovers
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(2)))
+            .up_to_n_times(3)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+
+        let client = client();
+        let url = format!("{}/quote", server.uri());
+        let started = Instant::now();
+        let err = client.get(&url, None, |request| request).await.unwrap_err();
+        assert!(matches!(err, HttpError::Timeout { timeout_ms: 100, .. }));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        // Four retries were allowed, but the third timeout opened the breaker and ended them
+        assert_eq!(received(&server).await, 3);
+        let breaker = client.breaker(&url).unwrap();
+        assert_eq!(breaker.state(), BreakerState::Open);
+
+        // While open nothing reaches the host
+        let err = client.get(&url, None, |request| request).await.unwrap_err();
+        assert!(matches!(err, HttpError::CircuitOpen { .. }));
+        assert_eq!(received(&server).await, 3);
+
+        // After the cooldown a probe reaches the recovered host and closes the breaker
+        tokio::time::sleep(COOLDOWN).await;
+        let response = client.get(&url, None, |request| request).await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "ok");
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        assert_eq!(received(&server).await, 4);
+    }
+
+    #[tokio::test]
+    async fn test_only_transient_get_failures_are_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let client = client();
+        let url = server.uri();
+
+        // Two 503s are retried through to the success
+        assert!(client.get(&url, None, |request| request).await.is_ok());
+        assert_eq!(received(&server).await, 3);
+
+        // A client error is returned at once and leaves the breaker closed
+        let err = client.get(&url, None, |request| request).await.unwrap_err();
+        assert_eq!(err.status(), Some(404));
+        assert_eq!(received(&server).await, 4);
+
+        // POSTs are sent once
+        let err = client.post(&url, None, |request| request.body("{}")).await.unwrap_err();
+        assert_eq!(err.status(), Some(503));
+        assert_eq!(received(&server).await, 5);
+        assert_eq!(client.breaker(&url).unwrap().state(), BreakerState::Closed);
+    }
+
+    #[test]
+    fn test_backoff_is_capped_and_jittered() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+            jitter: 0.5,
+        };
+        for retry in 0..5 {
+            let uncapped = Duration::from_millis(100 * 2u64.pow(retry)).min(Duration::from_millis(500));
+            let backoff = policy.backoff(retry);
+            assert!(backoff <= uncapped && backoff >= uncapped / 2, "retry {} backoff {:?}", retry, backoff);
+        }
+        assert_eq!(RetryPolicy { jitter: 0.0, ..policy }.backoff(1), Duration::from_millis(200));
+    }
+}
@@ -1,23 +1,36 @@
-use std::sync::Once;
-use tracing::{info, error, Level, Metadata, Subscriber};
-use tracing_appender::rolling::{RollingFileAppender, Rotation};
-use tracing_elastic::ElasticSubscriber;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use tracing::{info, error, Level};
+use tracing_subscriber::filter::Directive;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
 use serde_json::{json, Value};
 use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
 
-use crate::config::environment::EnvironmentConfig;
+use crate::config::logging::LogConfig;
 
 // Package versions:
 // tracing = "0.1.37"
-// tracing-appender = "0.2.2"
+// tracing-subscriber = "0.3.17"
 // serde_json = "1.0.96"
-// tracing-elastic = "0.1.0"
 
-static INIT: Once = Once::new();
-static DEFAULT_LOG_LEVEL: &str = "INFO";
-static LOG_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
-const MAX_LOG_FILE_SIZE: u64 = 100 * 1024 * 1024; // 100MB
-static LOG_ROTATION_DURATION: &str = "1d";
+static INIT_LOCK: Mutex<()> = Mutex::new(());
+static LOG_HANDLE: OnceLock<LogHandle> = OnceLock::new();
+static SAMPLE_EVERY: AtomicU32 = AtomicU32::new(1);
+
+/// Logging setup and runtime filter errors
+#[derive(Error, Debug)]
+pub enum LoggerError {
+    #[error("invalid log directive '{0}': {1}")]
+    InvalidDirective(String, String),
+    #[error("logging not initialized")]
+    NotInitialized,
+    #[error("failed to reload log filter: {0}")]
+    Reload(String),
+    #[error("failed to install trace exporter: {0}")]
+    TraceExport(String),
+}
 
 #[derive(Debug, Clone)]
 pub struct LogFormatter {
@@ -78,65 +91,150 @@ impl LogFormatter {
     }
 }
 
-pub fn init_logger(config: &EnvironmentConfig) -> Result<(), Box<dyn std::error::Error>> {
-    INIT.call_once(|| {
-        let log_level = match config.log_level.as_deref() {
-            Some("DEBUG") => Level::DEBUG,
-            Some("INFO") => Level::INFO,
-            Some("WARN") => Level::WARN,
-            Some("ERROR") => Level::ERROR,
-            _ => Level::INFO,
-        };
+/// Installs the global subscriber on first call: pretty or JSON output per `LogConfig`, with
+/// a reloadable filter built from the base level and per-module directives, plus OTLP span
+/// export when tracing is enabled. Later calls return the existing handle.
+pub fn init_logging(config: &LogConfig) -> Result<&'static LogHandle, LoggerError> {
+    let _guard = INIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(handle) = LOG_HANDLE.get() {
+        return Ok(handle);
+    }
 
-        let file_appender = RollingFileAppender::new(
-            Rotation::new(LOG_ROTATION_DURATION.parse().unwrap(), MAX_LOG_FILE_SIZE),
-            "logs",
-            "trading-bot.log",
-        );
+    let mut directives = vec![config.log_level.to_lowercase()];
+    directives.extend(config.directives.iter().cloned());
+    let (filter, handle) = reloadable_filter(directives)?;
+    SAMPLE_EVERY.store(config.sampling_rate.max(1), Ordering::Relaxed);
 
-        let formatter = LogFormatter::new(
-            !config.is_production(),
-            true,
-            true,
-            config.is_production(),
-            Some(config.node_env.clone()),
-        );
-
-        let subscriber = tracing_subscriber::fmt()
+    let output = if config.json_format {
+        fmt::layer()
+            .json()
+            .with_current_span(true)
             .with_file(true)
             .with_line_number(true)
             .with_thread_ids(true)
-            .with_target(true)
-            .with_max_level(log_level)
-            .with_writer(file_appender)
-            .with_ansi(false)
-            .event_format(move |event| {
-                formatter.format(&tracing::log::Record::new(
-                    &event.metadata(),
-                    &format_args!("{:?}", event),
-                ))
-            });
-
-        if config.is_production() {
-            // Initialize ELK Stack integration for production
-            let elastic_subscriber = ElasticSubscriber::new(
-                "http://elasticsearch:9200",
-                "trading-bot-logs",
-            );
-            
-            let multi_subscriber = tracing_subscriber::registry()
-                .with(subscriber)
-                .with(elastic_subscriber);
-
-            tracing::subscriber::set_global_default(multi_subscriber)
-                .expect("Failed to set global subscriber");
-        } else {
-            tracing::subscriber::set_global_default(subscriber)
-                .expect("Failed to set global subscriber");
+            .boxed()
+    } else {
+        fmt::layer().pretty().with_target(true).boxed()
+    };
+
+    let telemetry = if config.tracing.enabled {
+        Some(crate::utils::telemetry::otel_layer(&config.tracing)?)
+    } else {
+        None
+    };
+
+    // A subscriber installed elsewhere (e.g. by a test harness) keeps precedence
+    let _ = tracing_subscriber::registry()
+        .with(filter)
+        .with(output)
+        .with(telemetry)
+        .try_init();
+
+    Ok(LOG_HANDLE.get_or_init(|| handle))
+}
+
+/// Handle over the running subscriber's filter, once logging is initialized
+pub fn log_handle() -> Option<&'static LogHandle> {
+    LOG_HANDLE.get()
+}
+
+fn reloadable_filter(
+    directives: Vec<String>,
+) -> Result<(reload::Layer<EnvFilter, Registry>, LogHandle), LoggerError> {
+    let filter = build_filter(&directives)?;
+    let (layer, reload) = reload::Layer::new(filter);
+    Ok((
+        layer,
+        LogHandle {
+            reload,
+            directives: Mutex::new(directives),
+        },
+    ))
+}
+
+fn build_filter(directives: &[String]) -> Result<EnvFilter, LoggerError> {
+    let joined = directives.join(",");
+    EnvFilter::try_new(&joined).map_err(|e| LoggerError::InvalidDirective(joined, e.to_string()))
+}
+
+/// Target a directive applies to; bare levels apply to every target
+fn directive_target(directive: &str) -> &str {
+    match directive.rsplit_once('=') {
+        Some((target, _)) => target,
+        None => "",
+    }
+}
+
+/// Runtime control over the active log filter
+pub struct LogHandle {
+    reload: reload::Handle<EnvFilter, Registry>,
+    directives: Mutex<Vec<String>>,
+}
+
+impl std::fmt::Debug for LogHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LogHandle")
+            .field("directives", &*self.directives.lock().unwrap_or_else(|e| e.into_inner()))
+            .finish()
+    }
+}
+
+impl LogHandle {
+    /// Applies comma-separated directives such as `firebot::data_collector=debug`, replacing
+    /// any existing directive for the same target. Returns the resulting filter.
+    pub fn set_directives(&self, input: &str) -> Result<String, LoggerError> {
+        let mut directives = self.directives.lock().unwrap_or_else(|e| e.into_inner());
+        let mut updated = directives.clone();
+        for directive in input.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            directive
+                .parse::<Directive>()
+                .map_err(|e| LoggerError::InvalidDirective(directive.to_string(), e.to_string()))?;
+            let target = directive_target(directive);
+            updated.retain(|existing| directive_target(existing) != target);
+            updated.push(directive.to_string());
         }
-    });
 
-    Ok(())
+        self.reload
+            .reload(build_filter(&updated)?)
+            .map_err(|e| LoggerError::Reload(e.to_string()))?;
+        *directives = updated;
+        let filter = directives.join(",");
+        info!(filter = %filter, "Log filter updated");
+        Ok(filter)
+    }
+
+    /// Directives currently in effect
+    pub fn filter(&self) -> String {
+        self.directives.lock().unwrap_or_else(|e| e.into_inner()).join(",")
+    }
+}
+
+/// Lets 1 in N high-frequency debug events through, N being the configured sampling rate
+#[derive(Debug, Default)]
+pub struct LogSampler {
+    seen: AtomicU64,
+}
+
+impl LogSampler {
+    pub const fn new() -> Self {
+        Self {
+            seen: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns how many events the emitted one stands for, or None if it should be skipped
+    pub fn sample(&self) -> Option<u64> {
+        self.sample_every(SAMPLE_EVERY.load(Ordering::Relaxed) as u64)
+    }
+
+    fn sample_every(&self, every: u64) -> Option<u64> {
+        let every = every.max(1);
+        let seen = self.seen.fetch_add(1, Ordering::Relaxed);
+        if seen % every != 0 {
+            return None;
+        }
+        Some(if seen == 0 { 1 } else { every })
+    }
 }
 
 pub fn log_trade_execution(trade_info: Value, correlation_id: String) {
@@ -202,8 +300,48 @@ mod tests {
     }
 
     #[test]
-    fn test_init_logger() {
-        let config = EnvironmentConfig::new();
-        assert!(init_logger(&config).is_ok());
+    fn test_init_logging_is_idempotent() {
+        let config = LogConfig::new(&EnvironmentConfig::new());
+        let first = init_logging(&config).unwrap();
+        let second = init_logging(&config).unwrap();
+        assert!(std::ptr::eq(first, second));
+        assert!(std::ptr::eq(log_handle().unwrap(), first));
+    }
+
+    #[test]
+    fn test_reload_changes_effective_level() {
+        let (filter, handle) = reloadable_filter(vec!["info".to_string()]).unwrap();
+        let subscriber = tracing_subscriber::registry().with(filter);
+
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(!tracing::enabled!(target: "firebot::data_collector", Level::DEBUG));
+
+            let filter = handle.set_directives("firebot::data_collector=debug").unwrap();
+            assert_eq!(filter, "info,firebot::data_collector=debug");
+            assert!(tracing::enabled!(target: "firebot::data_collector", Level::DEBUG));
+            assert!(!tracing::enabled!(target: "firebot::execution_engine", Level::DEBUG));
+
+            // A later directive for the same target replaces the earlier one
+            handle.set_directives("firebot::data_collector=warn").unwrap();
+            assert!(!tracing::enabled!(target: "firebot::data_collector", Level::INFO));
+            assert_eq!(handle.filter(), "info,firebot::data_collector=warn");
+        });
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_directive_keeps_filter() {
+        let (_filter, handle) = reloadable_filter(vec!["info".to_string()]).unwrap();
+        assert!(matches!(
+            handle.set_directives("firebot::data_collector=loud"),
+            Err(LoggerError::InvalidDirective(..))
+        ));
+        assert_eq!(handle.filter(), "info");
+    }
+
+    #[test]
+    fn test_sampler_lets_one_in_n_through() {
+        let sampler = LogSampler::new();
+        let emitted: Vec<u64> = (0..25).filter_map(|_| sampler.sample_every(10)).collect();
+        assert_eq!(emitted, vec![1, 10, 10]);
+    }
+}
@@ -0,0 +1,143 @@
+//! Pre-registered metric handles for hot paths. A handle is registered once, so recording
+//! through it neither formats the metric name nor looks its key up again, and ultra-hot
+//! counters accumulate in-process and reach the recorder once per flush interval. Names
+//! are passed through unchanged, so exported series match the per-call macros they replace.
+//!
+//! Handles bind to the recorder installed when they are registered; modules register them
+//! lazily on first use, after the exporter has been installed at startup.
+//!
+//! Version dependencies:
+//! - metrics = "0.21"
+//! - lazy_static = "1.4"
+//! - parking_lot = "0.12"
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use metrics::{register_counter, Counter};
+use parking_lot::{Mutex, RwLock};
+
+/// How often aggregated counters are emitted
+pub const AGGREGATION_FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+
+lazy_static! {
+    static ref AGGREGATED: Mutex<Vec<&'static AggregatedCounter>> = Mutex::new(Vec::new());
+}
+
+/// Counter accumulated locally and emitted by the flusher rather than per event
+pub struct AggregatedCounter {
+    name: String,
+    counter: Counter,
+    pending: AtomicU64,
+}
+
+impl std::fmt::Debug for AggregatedCounter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AggregatedCounter")
+            .field("name", &self.name)
+            .field("pending", &self.pending.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl AggregatedCounter {
+    /// Registers a counter with the flusher; it lives for the rest of the process
+    pub fn register(name: String) -> &'static Self {
+        let counter: &'static Self = Box::leak(Box::new(Self {
+            counter: register_counter!(name.clone()),
+            name,
+            pending: AtomicU64::new(0),
+        }));
+        AGGREGATED.lock().push(counter);
+        counter
+    }
+
+    pub fn increment(&self, value: u64) {
+        self.pending.fetch_add(value, Ordering::Relaxed);
+    }
+
+    /// Emits the count accumulated since the last flush, returning it
+    pub fn flush(&self) -> u64 {
+        let pending = self.pending.swap(0, Ordering::Relaxed);
+        if pending > 0 {
+            self.counter.increment(pending);
+        }
+        pending
+    }
+}
+
+/// Emits every aggregated counter's pending count
+pub fn flush_aggregated() {
+    for counter in AGGREGATED.lock().iter() {
+        counter.flush();
+    }
+}
+
+/// Flushes aggregated counters on a fixed interval
+pub fn spawn_flusher(interval: Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            flush_aggregated();
+        }
+    })
+}
+
+/// Counter handles for one metric, registered once per value of a single label
+#[derive(Debug)]
+pub struct LabeledCounter {
+    name: String,
+    label: &'static str,
+    handles: RwLock<HashMap<String, Counter>>,
+}
+
+impl LabeledCounter {
+    pub fn new(name: String, label: &'static str) -> Self {
+        Self {
+            name,
+            label,
+            handles: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn increment(&self, label_value: &str, value: u64) {
+        if let Some(counter) = self.handles.read().get(label_value) {
+            counter.increment(value);
+            return;
+        }
+        let counter = register_counter!(self.name.clone(), self.label => label_value.to_string());
+        counter.increment(value);
+        self.handles.write().insert(label_value.to_string(), counter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregated_counter_emits_once_per_flush() {
+        let counter = AggregatedCounter::register("trading_bot.test.aggregated".to_string());
+        for _ in 0..50_000 {
+            counter.increment(1);
+        }
+        assert_eq!(counter.flush(), 50_000);
+        assert_eq!(counter.flush(), 0);
+
+        counter.increment(3);
+        flush_aggregated();
+        assert_eq!(counter.flush(), 0);
+    }
+
+    #[test]
+    fn test_labeled_counter_registers_each_value_once() {
+        let counter = LabeledCounter::new("trading_bot.test.labeled".to_string(), "exchange");
+        for exchange in ["jupiter", "drift", "jupiter"] {
+            counter.increment(exchange, 1);
+        }
+        assert_eq!(counter.handles.read().len(), 2);
+    }
+}
@@ -53,6 +53,12 @@ lazy_static! {
         &["component", "metric"]
     ).unwrap();
     
+    // Build identity exported as labels on a constant gauge
+    static ref BUILD_INFO_GAUGE: GaugeVec = register_gauge_vec!(
+        opts!("build_info", "Version, commit, build time and environment of the running binary"),
+        &["version", "git_commit", "build_timestamp", "environment"]
+    ).unwrap();
+    
     // Trade success/failure counters
     static ref TRADE_RESULT_COUNTER: IntCounterVec = register_int_counter_vec!(
         opts!("trade_results", "Trade execution success and failure counts"),
@@ -147,6 +153,21 @@ impl MetricsCollector {
         Ok(())
     }
 
+    /// Sets the build info gauge carrying the binary's identity as static labels
+    pub fn record_build_info(
+        &self,
+        version: &str,
+        git_commit: &str,
+        build_timestamp: &str,
+        environment: &str,
+    ) -> Result<(), MetricsError> {
+        BUILD_INFO_GAUGE
+            .with_label_values(&[version, git_commit, build_timestamp, environment])
+            .set(1.0);
+            
+        Ok(())
+    }
+
     /// Retrieves current metrics for reporting
     pub fn get_metrics(&self) -> Result<String, MetricsError> {
         let mut buffer = Vec::new();
@@ -10,6 +10,10 @@
 //! Version: 1.0.0
 //! Security Notice: Contains critical security and trading functions - handle with care
 
+// Re-export the shared circuit breaker and its health registry
+pub mod circuit_breaker;
+pub use circuit_breaker::{BreakerConfig, BreakerPolicy, BreakerState, BreakerStatus, CircuitBreaker};
+
 // Re-export cryptographic utilities with version compatibility tracking
 pub mod crypto;
 pub use crypto::{
@@ -23,10 +27,14 @@ pub use crypto::{
 // Re-export logging utilities with structured logging support
 pub mod logger;
 pub use logger::{
-    init_logger,
+    init_logging,
+    log_handle,
     log_trade_execution,
     log_error,
     LogFormatter,
+    LogHandle,
+    LogSampler,
+    LoggerError,
 };
 
 // Re-export metrics collection utilities with Prometheus integration
@@ -38,6 +46,17 @@ pub use metrics::{
     expose_metrics,
 };
 
+// Component health probes polled into the system health gauges
+pub mod health;
+pub use health::{ComponentStatus, HealthMonitor, HealthProbe, QualityInputs, QualitySource, QualityThresholds};
+
+// Pre-registered metric handles and aggregated counters for hot paths
+pub mod metric_handles;
+
+// Percentage and basis point newtypes for risk and strategy thresholds
+pub mod percent;
+pub use percent::{Bps, Percent};
+
 // Re-export Solana blockchain utilities with MEV optimization support
 pub mod solana;
 pub use solana::{
@@ -49,6 +68,21 @@ pub use solana::{
     HealthStatus,
 };
 
+// Associated token account creation, caching and rent reclamation for the trading wallet
+pub mod token_accounts;
+pub use token_accounts::{TokenAccountError, TokenAccountManager, TokenAccountSetup};
+
+// OpenTelemetry span export and W3C trace context propagation
+pub mod telemetry;
+
+// Transaction signing backends: in-memory keypair, AWS KMS and remote mTLS signer
+pub mod signer;
+pub use signer::{build_signer, Signer, SignerError};
+
+// Shared outbound HTTP client with per-host timeouts, retries and circuit breakers
+pub mod http;
+pub use http::{HttpClient, HttpConfig, HttpError};
+
 // Re-export time management utilities with high-precision timestamp support
 pub mod time;
 pub use time::{
@@ -57,6 +91,7 @@ pub use time::{
     is_valid_market_timestamp,
     calculate_duration_ms,
     format_timestamp,
+    to_chrono,
     TimeError,
 };
 
@@ -80,10 +115,21 @@ pub fn validate_module_versions() -> bool {
     versions.iter().all(|&v| v == "1.0.0")
 }
 
+/// Utility module versions by module name, reported on the system info endpoint
+pub fn module_versions() -> [(&'static str, &'static str); 5] {
+    [
+        ("crypto", CRYPTO_MODULE_VERSION),
+        ("logger", LOGGER_MODULE_VERSION),
+        ("metrics", METRICS_MODULE_VERSION),
+        ("solana", SOLANA_MODULE_VERSION),
+        ("time", TIME_MODULE_VERSION),
+    ]
+}
+
 /// Initializes all utility modules with proper configuration
 pub async fn init_utils(config: &crate::config::environment::EnvironmentConfig) -> Result<(), Box<dyn std::error::Error>> {
     // Initialize logging system
-    logger::init_logger(config)?;
+    logger::init_logging(&crate::config::logging::LogConfig::new(config))?;
     
     // Initialize metrics collection
     metrics::init_metrics()?;
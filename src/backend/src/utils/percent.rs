@@ -0,0 +1,291 @@
+//! Percentage and basis point newtypes. A `Percent` holds a value on the 0-100 scale and a
+//! `Bps` one on the 0-10,000 scale; each converts to the other and to a plain fraction
+//! explicitly, so a limit written as a fraction can no longer be compared against a ratio
+//! computed in percent, and one type is never accepted where the other is expected:
+//!
+//! ```compile_fail
+//! use solana_trading_bot::utils::percent::{Bps, Percent};
+//!
+//! fn max_position(limit: Percent) -> Percent { limit }
+//! max_position(Bps::new(2000));
+//! ```
+//!
+//! Both serialize as the bare decimal on their own scale.
+//!
+//! Version dependencies:
+//! - rust_decimal = "1.30"
+//! - serde = "1.0"
+//! - utoipa = "3.5"
+
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// Scale constants
+const PERCENT_PER_UNIT: Decimal = Decimal::ONE_HUNDRED;
+const BPS_PER_UNIT: Decimal = Decimal::from_parts(10_000, 0, 0, false, 0);
+const BPS_PER_PERCENT: Decimal = Decimal::ONE_HUNDRED;
+
+/// Value on the 0-100 percentage scale
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct Percent(Decimal);
+
+impl Percent {
+    pub const ZERO: Percent = Percent(Decimal::ZERO);
+    pub const ONE_HUNDRED: Percent = Percent(Decimal::ONE_HUNDRED);
+
+    /// Wraps a value already on the percentage scale, e.g. `20` for 20%
+    pub const fn from_percent(value: Decimal) -> Self {
+        Self(value)
+    }
+
+    /// Converts a fraction, e.g. `0.2` for 20%
+    pub fn from_fraction(fraction: Decimal) -> Self {
+        Self(fraction * PERCENT_PER_UNIT)
+    }
+
+    /// Share of `whole` that `part` represents; zero when `whole` is not positive
+    pub fn ratio(part: Decimal, whole: Decimal) -> Self {
+        if whole <= Decimal::ZERO {
+            return Self::ZERO;
+        }
+        Self(part * PERCENT_PER_UNIT / whole)
+    }
+
+    pub fn as_percent(&self) -> Decimal {
+        self.0
+    }
+
+    pub fn as_fraction(&self) -> Decimal {
+        self.0 / PERCENT_PER_UNIT
+    }
+
+    pub fn to_bps(&self) -> Bps {
+        Bps(self.0 * BPS_PER_PERCENT)
+    }
+
+    /// This percentage of an amount
+    pub fn of(&self, amount: Decimal) -> Decimal {
+        amount * self.0 / PERCENT_PER_UNIT
+    }
+
+    pub fn abs(&self) -> Self {
+        Self(self.0.abs())
+    }
+
+    pub fn round_dp(&self, dp: u32) -> Self {
+        Self(self.0.round_dp(dp))
+    }
+}
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
+impl From<Bps> for Percent {
+    fn from(bps: Bps) -> Self {
+        bps.to_percent()
+    }
+}
+
+impl Add for Percent {
+    type Output = Percent;
+
+    fn add(self, other: Percent) -> Percent {
+        Percent(self.0 + other.0)
+    }
+}
+
+impl Sub for Percent {
+    type Output = Percent;
+
+    fn sub(self, other: Percent) -> Percent {
+        Percent(self.0 - other.0)
+    }
+}
+
+impl Neg for Percent {
+    type Output = Percent;
+
+    fn neg(self) -> Percent {
+        Percent(-self.0)
+    }
+}
+
+impl Mul<Decimal> for Percent {
+    type Output = Decimal;
+
+    fn mul(self, amount: Decimal) -> Decimal {
+        self.of(amount)
+    }
+}
+
+impl Mul<Percent> for Decimal {
+    type Output = Decimal;
+
+    fn mul(self, percent: Percent) -> Decimal {
+        percent.of(self)
+    }
+}
+
+/// Value on the 0-10,000 basis point scale
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct Bps(Decimal);
+
+impl Bps {
+    pub const ZERO: Bps = Bps(Decimal::ZERO);
+
+    /// Whole basis points, e.g. `50` for 0.5%
+    pub const fn new(bps: u32) -> Self {
+        Self(Decimal::from_parts(bps, 0, 0, false, 0))
+    }
+
+    /// Wraps a value already on the basis point scale
+    pub const fn from_bps(value: Decimal) -> Self {
+        Self(value)
+    }
+
+    /// Converts a fraction, e.g. `0.005` for 50 bps
+    pub fn from_fraction(fraction: Decimal) -> Self {
+        Self(fraction * BPS_PER_UNIT)
+    }
+
+    pub fn as_bps(&self) -> Decimal {
+        self.0
+    }
+
+    pub fn as_fraction(&self) -> Decimal {
+        self.0 / BPS_PER_UNIT
+    }
+
+    pub fn to_percent(&self) -> Percent {
+        Percent(self.0 / BPS_PER_PERCENT)
+    }
+
+    /// These basis points of an amount
+    pub fn of(&self, amount: Decimal) -> Decimal {
+        amount * self.0 / BPS_PER_UNIT
+    }
+}
+
+impl fmt::Display for Bps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}bps", self.0)
+    }
+}
+
+impl From<Percent> for Bps {
+    fn from(percent: Percent) -> Self {
+        percent.to_bps()
+    }
+}
+
+impl Add for Bps {
+    type Output = Bps;
+
+    fn add(self, other: Bps) -> Bps {
+        Bps(self.0 + other.0)
+    }
+}
+
+impl Sub for Bps {
+    type Output = Bps;
+
+    fn sub(self, other: Bps) -> Bps {
+        Bps(self.0 - other.0)
+    }
+}
+
+impl Mul<Decimal> for Bps {
+    type Output = Decimal;
+
+    fn mul(self, amount: Decimal) -> Decimal {
+        self.of(amount)
+    }
+}
+
+impl Mul<Bps> for Decimal {
+    type Output = Decimal;
+
+    fn mul(self, bps: Bps) -> Decimal {
+        bps.of(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_percent_scales() {
+        let twenty = Percent::from_percent(dec!(20));
+        assert_eq!(twenty, Percent::from_fraction(dec!(0.2)));
+        assert_eq!(twenty.as_fraction(), dec!(0.2));
+        assert_eq!(twenty.as_percent(), dec!(20));
+        assert_eq!(twenty.to_bps(), Bps::new(2000));
+        assert_eq!(twenty.of(dec!(1000)), dec!(200));
+        assert_eq!(twenty * dec!(50), dec!(10));
+        assert_eq!(dec!(50) * twenty, dec!(10));
+        assert_eq!(twenty.to_string(), "20%");
+
+        // The two readings of Decimal::new(20, 2) are now distinct values
+        assert_ne!(Percent::from_percent(dec!(0.20)), Percent::from_fraction(dec!(0.20)));
+    }
+
+    #[test]
+    fn test_percent_ratio() {
+        assert_eq!(Percent::ratio(dec!(100), dec!(1000)), Percent::from_percent(dec!(10)));
+        assert_eq!(Percent::ratio(dec!(-30), dec!(100)).abs(), Percent::from_percent(dec!(30)));
+        assert_eq!(Percent::ratio(dec!(100), Decimal::ZERO), Percent::ZERO);
+        assert_eq!(Percent::ratio(dec!(100), dec!(-5)), Percent::ZERO);
+        assert!(Percent::ratio(dec!(21), dec!(100)) > Percent::from_percent(dec!(20)));
+    }
+
+    #[test]
+    fn test_bps_scales() {
+        let fifty = Bps::new(50);
+        assert_eq!(fifty, Bps::from_bps(dec!(50)));
+        assert_eq!(fifty, Bps::from_fraction(dec!(0.005)));
+        assert_eq!(fifty.as_fraction(), dec!(0.005));
+        assert_eq!(fifty.to_percent(), Percent::from_percent(dec!(0.5)));
+        assert_eq!(Percent::from(fifty), Percent::from_percent(dec!(0.5)));
+        assert_eq!(Bps::from(Percent::from_percent(dec!(0.5))), fifty);
+        assert_eq!(fifty.of(dec!(10000)), dec!(50));
+        assert_eq!(dec!(10000) * fifty, dec!(50));
+        assert_eq!(fifty.to_string(), "50bps");
+
+        // Dividing bps by 100 yields percent, not a fraction
+        assert_eq!(Bps::new(1000).as_fraction(), dec!(0.1));
+        assert_eq!(Bps::new(1000).to_percent().as_percent(), dec!(10));
+    }
+
+    #[test]
+    fn test_round_trips_and_arithmetic() {
+        for bps in [0, 1, 50, 100, 2500, 10_000] {
+            assert_eq!(Bps::new(bps).to_percent().to_bps(), Bps::new(bps));
+            assert_eq!(Bps::from_fraction(Bps::new(bps).as_fraction()), Bps::new(bps));
+        }
+        let p = Percent::from_percent(dec!(12.5));
+        assert_eq!(Percent::from_fraction(p.as_fraction()), p);
+        assert_eq!(p + p - Percent::from_percent(dec!(5)), Percent::from_percent(dec!(20)));
+        assert_eq!(-p, Percent::from_percent(dec!(-12.5)));
+        assert_eq!(Bps::new(30) + Bps::new(20) - Bps::new(10), Bps::new(40));
+    }
+
+    #[test]
+    fn test_serializes_on_own_scale() {
+        let percent: Percent = serde_json::from_str("\"20\"").unwrap();
+        assert_eq!(percent, Percent::from_percent(dec!(20)));
+        let bps: Bps = serde_json::from_str("1000").unwrap();
+        assert_eq!(bps, Bps::new(1000));
+        assert_eq!(serde_json::to_value(Percent::from_percent(dec!(5))).unwrap(), serde_json::to_value(dec!(5)).unwrap());
+    }
+}
@@ -0,0 +1,482 @@
+//! Transaction signing behind a single `Signer` trait so the trading key need not live in
+//! process memory. Backends: an in-memory keypair, an AWS KMS Ed25519 key, and a remote
+//! signer reached over HTTPS with mutual TLS. Every signature is verified against the
+//! signer's public key before it is placed in the transaction.
+//!
+//! Version dependencies:
+//! - solana-sdk = "1.16"
+//! - aws-sdk-kms = "0.28"
+//! - reqwest = "0.11"
+//! - metrics = "0.21"
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use aws_sdk_kms::primitives::Blob;
+use aws_sdk_kms::types::{MessageType, SigningAlgorithmSpec};
+use aws_sdk_kms::{Client as KmsClient, Region};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use metrics::{counter, histogram};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::signer::Signer as _;
+use solana_sdk::transaction::Transaction;
+use thiserror::Error;
+use tracing::{instrument, warn};
+
+use crate::config::security::{RemoteSignerConfig, SignerConfig};
+use crate::utils::http::{HostConfig, HttpClient, HttpError, RetryPolicy};
+
+// Signer constants
+const METRICS_PREFIX: &str = "trading_bot.signer";
+/// Signing slower than this is logged; it comes straight out of the 500ms execution budget
+const SLOW_SIGNING_MS: u128 = 100;
+const REMOTE_SIGN_PATH: &str = "/v1/sign";
+const KMS_ED25519_ALGORITHM: &str = "ED25519_SHA_512";
+/// Raw Ed25519 public keys are the trailing bytes of KMS's DER SubjectPublicKeyInfo
+const ED25519_PUBKEY_LEN: usize = 32;
+
+#[derive(Debug, Error)]
+pub enum SignerError {
+    #[error("{0} is not a required signer of the transaction")]
+    NotASigner(Pubkey),
+
+    #[error("{backend} signer returned a signature that does not verify against {pubkey}")]
+    SignatureMismatch { backend: &'static str, pubkey: Pubkey },
+
+    #[error("{backend} signer timed out after {timeout_ms}ms")]
+    Timeout { backend: &'static str, timeout_ms: u64 },
+
+    #[error("remote signer failed: {0}")]
+    Remote(String),
+
+    #[error("KMS signing failed: {0}")]
+    Kms(String),
+
+    #[error("invalid signer configuration: {0}")]
+    Config(String),
+}
+
+/// Signs transactions for the trading wallet
+#[async_trait]
+pub trait Signer: Send + Sync + fmt::Debug {
+    fn pubkey(&self) -> Pubkey;
+
+    /// Backend name used in metrics and errors
+    fn backend(&self) -> &'static str;
+
+    /// Whether a failed signing attempt may be retried; off unless the backend opts in
+    fn retryable(&self) -> bool {
+        false
+    }
+
+    /// Ed25519 signature over serialized message bytes
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError>;
+
+    /// Signs the transaction's message, verifies the signature and places it in the
+    /// transaction's slot for this signer
+    async fn sign(&self, tx: &mut Transaction) -> Result<Signature, SignerError> {
+        let pubkey = self.pubkey();
+        let required = tx.message.header.num_required_signatures as usize;
+        let index = tx
+            .message
+            .account_keys
+            .iter()
+            .take(required)
+            .position(|key| *key == pubkey)
+            .ok_or(SignerError::NotASigner(pubkey))?;
+
+        let message = tx.message_data();
+        let start = Instant::now();
+        let result = self.sign_message(&message).await;
+        let elapsed = start.elapsed();
+        histogram!(
+            format!("{}.latency_ms", METRICS_PREFIX),
+            elapsed.as_secs_f64() * 1000.0,
+            "backend" => self.backend()
+        );
+        if elapsed.as_millis() > SLOW_SIGNING_MS {
+            warn!(backend = self.backend(), elapsed_ms = elapsed.as_millis() as u64, "Slow transaction signing");
+        }
+
+        let signature = match result {
+            Ok(signature) => signature,
+            Err(e) => {
+                counter!(format!("{}.failures", METRICS_PREFIX), 1, "backend" => self.backend());
+                return Err(e);
+            }
+        };
+        if !signature.verify(pubkey.as_ref(), &message) {
+            counter!(format!("{}.mismatches", METRICS_PREFIX), 1, "backend" => self.backend());
+            return Err(SignerError::SignatureMismatch {
+                backend: self.backend(),
+                pubkey,
+            });
+        }
+
+        if tx.signatures.len() < required {
+            tx.signatures.resize(required, Signature::default());
+        }
+        tx.signatures[index] = signature;
+        Ok(signature)
+    }
+}
+
+/// Keypair held in process memory
+pub struct KeypairSigner {
+    keypair: Keypair,
+}
+
+impl fmt::Debug for KeypairSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeypairSigner")
+            .field("pubkey", &self.keypair.pubkey())
+            .finish()
+    }
+}
+
+impl KeypairSigner {
+    pub fn new(keypair: Keypair) -> Self {
+        Self { keypair }
+    }
+
+    /// Loads a base58-encoded 64-byte secret key
+    pub fn from_base58(secret_key: &str) -> Result<Self, SignerError> {
+        let bytes = solana_sdk::bs58::decode(secret_key)
+            .into_vec()
+            .map_err(|_| SignerError::Config("wallet secret key is not valid base58".to_string()))?;
+        let keypair = Keypair::from_bytes(&bytes)
+            .map_err(|_| SignerError::Config("wallet secret key is not a valid keypair".to_string()))?;
+        Ok(Self::new(keypair))
+    }
+}
+
+#[async_trait]
+impl Signer for KeypairSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.keypair.pubkey()
+    }
+
+    fn backend(&self) -> &'static str {
+        "keypair"
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        Ok(self.keypair.sign_message(message))
+    }
+}
+
+/// Ed25519 key held in AWS KMS; the private key never leaves KMS
+#[derive(Debug)]
+pub struct KmsSigner {
+    client: KmsClient,
+    key_id: String,
+    pubkey: Pubkey,
+}
+
+impl KmsSigner {
+    /// Resolves the key's public key up front so every signature can be verified
+    pub async fn connect(key_id: &str, region: &str) -> Result<Self, SignerError> {
+        let client = KmsClient::new(Region::new(region.to_string()));
+        let response = client
+            .get_public_key()
+            .key_id(key_id)
+            .send()
+            .await
+            .map_err(|e| SignerError::Kms(e.to_string()))?;
+        let der = response
+            .public_key()
+            .ok_or_else(|| SignerError::Kms(format!("no public key returned for {}", key_id)))?
+            .as_ref();
+        if der.len() < ED25519_PUBKEY_LEN {
+            return Err(SignerError::Kms(format!("{} is not an Ed25519 key", key_id)));
+        }
+        let pubkey = Pubkey::try_from(&der[der.len() - ED25519_PUBKEY_LEN..])
+            .map_err(|_| SignerError::Kms(format!("{} is not an Ed25519 key", key_id)))?;
+
+        Ok(Self {
+            client,
+            key_id: key_id.to_string(),
+            pubkey,
+        })
+    }
+}
+
+#[async_trait]
+impl Signer for KmsSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    fn backend(&self) -> &'static str {
+        "kms"
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        let response = self
+            .client
+            .sign()
+            .key_id(&self.key_id)
+            .message(Blob::new(message))
+            .message_type(MessageType::Raw)
+            .signing_algorithm(SigningAlgorithmSpec::from(KMS_ED25519_ALGORITHM))
+            .send()
+            .await
+            .map_err(|e| SignerError::Kms(e.to_string()))?;
+        let signature = response
+            .signature()
+            .ok_or_else(|| SignerError::Kms("no signature returned".to_string()))?;
+        Signature::try_from(signature.as_ref())
+            .map_err(|_| SignerError::Kms("signature is not 64 bytes".to_string()))
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RemoteSignRequest {
+    pubkey: String,
+    /// Base64 serialized transaction message
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteSignResponse {
+    /// Base58 signature
+    signature: String,
+}
+
+/// Signing service reached over HTTPS with mutual TLS; the message goes out and only the
+/// signature comes back
+#[derive(Debug)]
+pub struct RemoteSigner {
+    http: HttpClient,
+    url: String,
+    pubkey: Pubkey,
+    timeout: Duration,
+    retryable: bool,
+}
+
+impl RemoteSigner {
+    /// Builds an HTTPS-only client presenting the configured client certificate and trusting
+    /// only the signer's CA
+    pub fn connect(config: &RemoteSignerConfig) -> Result<Self, SignerError> {
+        let read = |path: &str| {
+            std::fs::read(path).map_err(|e| SignerError::Config(format!("failed to read {}: {}", path, e)))
+        };
+        let mut identity_pem = read(&config.client_cert_path)?;
+        identity_pem.extend(read(&config.client_key_path)?);
+        let identity = reqwest::Identity::from_pem(&identity_pem)
+            .map_err(|e| SignerError::Config(format!("invalid client certificate: {}", e)))?;
+        let ca = reqwest::Certificate::from_pem(&read(&config.ca_cert_path)?)
+            .map_err(|e| SignerError::Config(format!("invalid signer CA certificate: {}", e)))?;
+
+        let pubkey = Pubkey::from_str(&config.pubkey)
+            .map_err(|_| SignerError::Config(format!("invalid signer pubkey {}", config.pubkey)))?;
+        let http = Self::http_client(config.timeout).with_client_identity(identity, ca);
+        Ok(Self::new(http, &config.url, pubkey, config.timeout).with_retries(config.retry_on_failure))
+    }
+
+    pub fn new(http: HttpClient, url: &str, pubkey: Pubkey, timeout: Duration) -> Self {
+        Self {
+            http,
+            url: url.trim_end_matches('/').to_string(),
+            pubkey,
+            timeout,
+            retryable: false,
+        }
+    }
+
+    /// Client sending each signing request once within the signing timeout; retrying a
+    /// signature is left to the caller
+    pub fn http_client(timeout: Duration) -> HttpClient {
+        HttpClient::for_host(
+            "signer",
+            HostConfig {
+                read_timeout: timeout,
+                retry: RetryPolicy::none(),
+                ..HostConfig::default()
+            },
+        )
+    }
+
+    /// Lets callers retry after a signer failure
+    pub fn with_retries(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    async fn request_signature(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        let request = RemoteSignRequest {
+            pubkey: self.pubkey.to_string(),
+            message: BASE64.encode(message),
+        };
+        let response = self
+            .http
+            .post(&format!("{}{}", self.url, REMOTE_SIGN_PATH), None, |builder| builder.json(&request))
+            .await
+            .map_err(|e| match e {
+                HttpError::Timeout { timeout_ms, .. } => SignerError::Timeout { backend: self.backend(), timeout_ms },
+                e => SignerError::Remote(e.to_string()),
+            })?;
+        let body: RemoteSignResponse = response
+            .json()
+            .await
+            .map_err(|e| SignerError::Remote(format!("invalid response: {}", e)))?;
+        Signature::from_str(&body.signature)
+            .map_err(|_| SignerError::Remote("response signature is not valid base58".to_string()))
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    fn backend(&self) -> &'static str {
+        "remote"
+    }
+
+    fn retryable(&self) -> bool {
+        self.retryable
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        tokio::time::timeout(self.timeout, self.request_signature(message))
+            .await
+            .map_err(|_| SignerError::Timeout {
+                backend: self.backend(),
+                timeout_ms: self.timeout.as_millis() as u64,
+            })?
+    }
+}
+
+/// Builds the signing backend selected in the security config
+#[instrument(skip(config))]
+pub async fn build_signer(config: &SignerConfig) -> Result<Arc<dyn Signer>, SignerError> {
+    Ok(match config {
+        SignerConfig::Keypair { secret_key } => Arc::new(KeypairSigner::from_base58(secret_key.expose())?),
+        SignerConfig::Kms { key_id, region } => Arc::new(KmsSigner::connect(key_id, region).await?),
+        SignerConfig::Remote(remote) => Arc::new(RemoteSigner::connect(remote)?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::system_instruction;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+    fn transfer(payer: &Pubkey) -> Transaction {
+        let mut tx = Transaction::new_with_payer(
+            &[system_instruction::transfer(payer, &Pubkey::new_unique(), 1_000)],
+            Some(payer),
+        );
+        tx.message.recent_blockhash = Hash::new_unique();
+        tx
+    }
+
+    /// Signs whatever message it is sent with the given keypair, as a real signer would
+    struct SignWith(Keypair);
+
+    impl Respond for SignWith {
+        fn respond(&self, request: &Request) -> ResponseTemplate {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            let message = BASE64.decode(body["message"].as_str().unwrap()).unwrap();
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "signature": self.0.sign_message(&message).to_string(),
+            }))
+        }
+    }
+
+    fn remote_signer(server: &MockServer, pubkey: Pubkey, timeout: Duration) -> RemoteSigner {
+        RemoteSigner::new(RemoteSigner::http_client(timeout), &server.uri(), pubkey, timeout)
+    }
+
+    #[tokio::test]
+    async fn test_keypair_signer_signs_in_place() {
+        let keypair = Keypair::new();
+        let pubkey = keypair.pubkey();
+        let signer = KeypairSigner::new(keypair);
+
+        let mut tx = transfer(&pubkey);
+        let signature = signer.sign(&mut tx).await.unwrap();
+        assert_eq!(tx.signatures[0], signature);
+        assert!(tx.verify().is_ok());
+
+        // A transaction the wallet is not a signer of is refused
+        let mut other = transfer(&Pubkey::new_unique());
+        assert!(matches!(signer.sign(&mut other).await, Err(SignerError::NotASigner(_))));
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_success() {
+        let keypair = Keypair::new();
+        let pubkey = keypair.pubkey();
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(REMOTE_SIGN_PATH))
+            .respond_with(SignWith(keypair))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let signer = remote_signer(&server, pubkey, Duration::from_secs(1));
+        let mut tx = transfer(&pubkey);
+        let signature = signer.sign(&mut tx).await.unwrap();
+        assert_eq!(tx.signatures[0], signature);
+        assert!(tx.verify().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_timeout_is_not_retryable() {
+        let pubkey = Keypair::new().pubkey();
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(REMOTE_SIGN_PATH))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+            .mount(&server)
+            .await;
+
+        let signer = remote_signer(&server, pubkey, Duration::from_millis(50));
+        let mut tx = transfer(&pubkey);
+        let err = signer.sign(&mut tx).await.unwrap_err();
+        assert!(matches!(err, SignerError::Timeout { backend: "remote", timeout_ms: 50 }));
+        assert!(!signer.retryable());
+        assert_eq!(tx.signatures[0], Signature::default());
+    }
+
+    #[tokio::test]
+    async fn test_remote_signature_mismatch_rejected() {
+        // The signer answers with a valid signature from the wrong key
+        let pubkey = Keypair::new().pubkey();
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(REMOTE_SIGN_PATH))
+            .respond_with(SignWith(Keypair::new()))
+            .mount(&server)
+            .await;
+
+        let signer = remote_signer(&server, pubkey, Duration::from_secs(1));
+        let mut tx = transfer(&pubkey);
+        let err = signer.sign(&mut tx).await.unwrap_err();
+        assert!(matches!(err, SignerError::SignatureMismatch { backend: "remote", .. }));
+        assert_eq!(tx.signatures[0], Signature::default());
+
+        // Server errors surface as remote failures
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+        let signer = remote_signer(&server, pubkey, Duration::from_secs(1));
+        let err = signer.sign(&mut transfer(&pubkey)).await.unwrap_err();
+        assert!(matches!(err, SignerError::Remote(_)));
+    }
+}
@@ -1,28 +1,45 @@
 use jito_bundle_client::{BundleClient, BundleConfig, BundleError, BundleSubmissionResult};
+use rust_decimal::Decimal;
 use solana_client::{
     client_error::ClientError,
     nonblocking::rpc_client::RpcClient,
-    rpc_config::RpcSendTransactionConfig,
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig, RpcTransactionConfig},
     rpc_request::RpcRequest,
+    rpc_response::RpcSimulateTransactionResult,
 };
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     hash::Hash,
-    signature::{Keypair, Signature},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Signature,
     transaction::Transaction,
 };
-use solana_transaction_status::UiTransactionStatusMeta;
-use std::{sync::Arc, time::Duration};
+use solana_transaction_status::{
+    UiTransactionEncoding, UiTransactionStatusMeta, UiTransactionTokenBalance,
+};
+use metrics::{counter, gauge, histogram};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::time::sleep;
 use tracing::{debug, error, info, instrument, warn};
 
+use crate::utils::signer::{Signer, SignerError};
+
 // Package versions in use:
 // solana-client = "1.17"
 // solana-sdk = "1.17"
 // solana-transaction-status = "1.17"
+// spl-token = "4.0"
 // tokio = "1.28"
 // jito-bundle-client = "0.1"
 // tracing = "0.1"
+// metrics = "0.21"
 
 /// Default commitment level for RPC requests
 const DEFAULT_COMMITMENT_LEVEL: CommitmentConfig = CommitmentConfig::confirmed();
@@ -34,6 +51,16 @@ const MAX_RETRIES: u8 = 3;
 const MIN_PRIORITY_FEE: u64 = 10_000;
 /// Health check interval in seconds
 const HEALTH_CHECK_INTERVAL_SECONDS: u64 = 60;
+/// Interval between prioritization fee samples in seconds
+const FEE_REFRESH_INTERVAL_SECONDS: u64 = 10;
+/// Age after which sampled fee estimates are ignored in seconds
+const FEE_STALENESS_SECONDS: u64 = 60;
+/// Fee percentiles used for each urgency level
+const LOW_URGENCY_PERCENTILE: f64 = 25.0;
+const NORMAL_URGENCY_PERCENTILE: f64 = 50.0;
+const HIGH_URGENCY_PERCENTILE: f64 = 90.0;
+/// Metrics prefix for priority fee estimation
+const FEE_METRICS_PREFIX: &str = "trading_bot.priority_fee";
 
 /// Error types for Solana client operations
 #[derive(Debug, thiserror::Error)]
@@ -44,6 +71,34 @@ pub enum SolanaError {
     BundleError(#[from] BundleError),
     #[error("Health check failed: {0}")]
     HealthCheckError(String),
+    #[error("Parse error: {0}")]
+    ParseError(String),
+    #[error("Signing error: {0}")]
+    SigningError(#[from] SignerError),
+}
+
+/// Net token balance change of a wallet within a single transaction
+#[derive(Debug, Clone)]
+pub struct TokenBalanceChange {
+    pub signature: String,
+    pub delta: Decimal,
+    pub block_time: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Net token balance changes of a wallet in one successful transaction, by mint
+#[derive(Debug, Clone, Default)]
+pub struct WalletTransaction {
+    pub signature: String,
+    pub block_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub token_deltas: HashMap<String, Decimal>,
+}
+
+/// Log messages emitted by one successful transaction
+#[derive(Debug, Clone)]
+pub struct TransactionLogs {
+    pub signature: String,
+    pub block_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub logs: Vec<String>,
 }
 
 /// Health status of Solana client connections
@@ -73,6 +128,8 @@ pub struct SolanaClient {
     commitment: CommitmentConfig,
     metrics: Arc<parking_lot::RwLock<ClientMetrics>>,
     health_checker: Arc<tokio::sync::RwLock<HealthStatus>>,
+    /// Signs every transaction this client submits
+    signer: Option<Arc<dyn Signer>>,
 }
 
 impl SolanaClient {
@@ -97,6 +154,7 @@ impl SolanaClient {
             commitment: DEFAULT_COMMITMENT_LEVEL,
             metrics: Arc::new(parking_lot::RwLock::new(ClientMetrics::default())),
             health_checker: Arc::new(tokio::sync::RwLock::new(health_status)),
+            signer: None,
         };
 
         // Start health monitoring task
@@ -105,10 +163,41 @@ impl SolanaClient {
         Ok(client)
     }
 
+    /// Signs submitted transactions with the backend selected in `SecurityConfig::signer`
+    pub fn with_signer(mut self, signer: Arc<dyn Signer>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    pub fn signer(&self) -> Option<&Arc<dyn Signer>> {
+        self.signer.as_ref()
+    }
+
+    fn configured_signer(&self) -> Result<&Arc<dyn Signer>, SolanaError> {
+        self.signer
+            .as_ref()
+            .ok_or_else(|| SignerError::Config("no transaction signer configured".to_string()).into())
+    }
+
+    /// Signs a transaction against its current blockhash, e.g. ahead of bundling
+    pub async fn sign_transaction(&self, transaction: &mut Transaction) -> Result<Signature, SolanaError> {
+        Ok(self.configured_signer()?.sign(transaction).await?)
+    }
+
+    /// Signs with the configured signer and submits with retries
+    pub async fn sign_and_send_transaction(
+        &self,
+        transaction: Transaction,
+        priority_fee: Option<u64>,
+    ) -> Result<(Signature, u64), SolanaError> {
+        sign_and_send_transaction(transaction, self.rpc_client.clone(), self.configured_signer()?.as_ref(), priority_fee).await
+    }
+
     /// Retrieves and caches the latest blockhash with monitoring
     #[instrument(skip(self))]
     pub async fn get_latest_blockhash(&self) -> Result<Hash, SolanaError> {
         let start = std::time::Instant::now();
+        rpc_fault().await.map_err(SolanaError::ClientError)?;
         let blockhash = self.rpc_client
             .get_latest_blockhash()
             .await
@@ -122,6 +211,70 @@ impl SolanaClient {
         Ok(blockhash)
     }
 
+    /// Simulates an unsigned transaction against the latest blockhash, reporting its compute
+    /// units and logs
+    #[instrument(skip(self, transaction))]
+    pub async fn simulate_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<RpcSimulateTransactionResult, SolanaError> {
+        rpc_fault().await.map_err(SolanaError::ClientError)?;
+        let response = self.rpc_client
+            .simulate_transaction_with_config(
+                transaction,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    commitment: Some(self.commitment),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(response.value)
+    }
+
+    /// Compute units a landed transaction consumed, from its status meta
+    #[instrument(skip(self))]
+    pub async fn get_compute_units_consumed(&self, signature: &str) -> Result<Option<u64>, SolanaError> {
+        let signature = Signature::from_str(signature)
+            .map_err(|e| SolanaError::ParseError(e.to_string()))?;
+        let transaction = self.rpc_client
+            .get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Json),
+                    commitment: Some(self.commitment),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await?;
+        Ok(transaction.transaction.meta.as_ref().and_then(compute_units_consumed))
+    }
+
+    /// Token amount held in an SPL token account, or `None` when the account does not exist
+    #[instrument(skip(self))]
+    pub async fn get_token_account_amount(&self, address: &Pubkey) -> Result<Option<u64>, SolanaError> {
+        rpc_fault().await.map_err(SolanaError::ClientError)?;
+        let account = self.rpc_client
+            .get_account_with_commitment(address, self.commitment)
+            .await?
+            .value;
+        account
+            .map(|account| {
+                spl_token::state::Account::unpack(&account.data)
+                    .map(|token_account| token_account.amount)
+                    .map_err(|e| SolanaError::ParseError(format!("{} is not a token account: {}", address, e)))
+            })
+            .transpose()
+    }
+
+    /// Lamports an account holding `data_len` bytes needs to be rent exempt
+    #[instrument(skip(self))]
+    pub async fn get_rent_exempt_minimum(&self, data_len: usize) -> Result<u64, SolanaError> {
+        rpc_fault().await.map_err(SolanaError::ClientError)?;
+        Ok(self.rpc_client.get_minimum_balance_for_rent_exemption(data_len).await?)
+    }
+
     /// Performs continuous health monitoring of RPC and Jito connections
     #[instrument(skip(self))]
     pub async fn monitor_health(&self) -> Result<HealthStatus, SolanaError> {
@@ -162,6 +315,198 @@ impl SolanaClient {
         Ok(status)
     }
 
+    /// UI amount of a mint held in the owner's associated token account
+    #[instrument(skip(self))]
+    pub async fn get_token_balance(&self, owner: &Pubkey, mint: &Pubkey) -> Result<Decimal, SolanaError> {
+        let account = spl_associated_token_account::get_associated_token_address(owner, mint);
+        let balance = self.rpc_client.get_token_account_balance(&account).await?;
+        Decimal::from_str(&balance.ui_amount_string).map_err(|e| SolanaError::ParseError(e.to_string()))
+    }
+
+    /// Scans recent transactions of a wallet for balance changes of the given token mint
+    #[instrument(skip(self))]
+    pub async fn get_token_balance_changes(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        limit: usize,
+    ) -> Result<Vec<TokenBalanceChange>, SolanaError> {
+        let statuses = self.rpc_client
+            .get_signatures_for_address_with_config(
+                owner,
+                GetConfirmedSignaturesForAddress2Config {
+                    limit: Some(limit),
+                    commitment: Some(self.commitment),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let owner = owner.to_string();
+        let mint = mint.to_string();
+        let mut changes = Vec::new();
+
+        for status in statuses.into_iter().filter(|status| status.err.is_none()) {
+            let signature = Signature::from_str(&status.signature)
+                .map_err(|e| SolanaError::ParseError(e.to_string()))?;
+
+            let transaction = self.rpc_client
+                .get_transaction_with_config(
+                    &signature,
+                    RpcTransactionConfig {
+                        encoding: Some(UiTransactionEncoding::Json),
+                        commitment: Some(self.commitment),
+                        max_supported_transaction_version: Some(0),
+                    },
+                )
+                .await?;
+
+            let Some(meta) = transaction.transaction.meta else {
+                continue;
+            };
+
+            let pre: Option<Vec<UiTransactionTokenBalance>> = meta.pre_token_balances.into();
+            let post: Option<Vec<UiTransactionTokenBalance>> = meta.post_token_balances.into();
+            let delta = token_balance_for(&post.unwrap_or_default(), &owner, &mint)?
+                - token_balance_for(&pre.unwrap_or_default(), &owner, &mint)?;
+
+            if !delta.is_zero() {
+                changes.push(TokenBalanceChange {
+                    signature: status.signature,
+                    delta,
+                    block_time: status
+                        .block_time
+                        .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0)),
+                });
+            }
+        }
+
+        debug!("Found {} token balance changes for {}", changes.len(), owner);
+        Ok(changes)
+    }
+
+    /// Successful recent transactions of a wallet, newest first, with the wallet's net
+    /// balance change of every token each one touched
+    #[instrument(skip(self))]
+    pub async fn get_wallet_transactions(
+        &self,
+        owner: &Pubkey,
+        limit: usize,
+    ) -> Result<Vec<WalletTransaction>, SolanaError> {
+        let statuses = self.rpc_client
+            .get_signatures_for_address_with_config(
+                owner,
+                GetConfirmedSignaturesForAddress2Config {
+                    limit: Some(limit),
+                    commitment: Some(self.commitment),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let owner = owner.to_string();
+        let mut transactions = Vec::new();
+        for status in statuses.into_iter().filter(|status| status.err.is_none()) {
+            let signature = Signature::from_str(&status.signature)
+                .map_err(|e| SolanaError::ParseError(e.to_string()))?;
+
+            let transaction = self.rpc_client
+                .get_transaction_with_config(
+                    &signature,
+                    RpcTransactionConfig {
+                        encoding: Some(UiTransactionEncoding::Json),
+                        commitment: Some(self.commitment),
+                        max_supported_transaction_version: Some(0),
+                    },
+                )
+                .await?;
+
+            let mut token_deltas = HashMap::new();
+            if let Some(meta) = transaction.transaction.meta {
+                let pre: Vec<UiTransactionTokenBalance> =
+                    Option::<Vec<UiTransactionTokenBalance>>::from(meta.pre_token_balances).unwrap_or_default();
+                let post: Vec<UiTransactionTokenBalance> =
+                    Option::<Vec<UiTransactionTokenBalance>>::from(meta.post_token_balances).unwrap_or_default();
+                let mut mints: Vec<&str> = pre.iter().chain(&post).map(|balance| balance.mint.as_str()).collect();
+                mints.sort_unstable();
+                mints.dedup();
+                for mint in mints {
+                    let delta = token_balance_for(&post, &owner, mint)? - token_balance_for(&pre, &owner, mint)?;
+                    if !delta.is_zero() {
+                        token_deltas.insert(mint.to_string(), delta);
+                    }
+                }
+            }
+            transactions.push(WalletTransaction {
+                signature: status.signature,
+                block_time: status
+                    .block_time
+                    .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0)),
+                token_deltas,
+            });
+        }
+
+        debug!("Fetched {} wallet transactions for {}", transactions.len(), owner);
+        Ok(transactions)
+    }
+
+    /// Logs of successful transactions touching an address, newest first, stopping before `until`
+    #[instrument(skip(self))]
+    pub async fn get_recent_transaction_logs(
+        &self,
+        address: &Pubkey,
+        until: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<TransactionLogs>, SolanaError> {
+        let until = until
+            .map(Signature::from_str)
+            .transpose()
+            .map_err(|e| SolanaError::ParseError(e.to_string()))?;
+        let statuses = self.rpc_client
+            .get_signatures_for_address_with_config(
+                address,
+                GetConfirmedSignaturesForAddress2Config {
+                    until,
+                    limit: Some(limit),
+                    commitment: Some(self.commitment),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let mut transactions = Vec::new();
+        for status in statuses.into_iter().filter(|status| status.err.is_none()) {
+            let signature = Signature::from_str(&status.signature)
+                .map_err(|e| SolanaError::ParseError(e.to_string()))?;
+
+            let transaction = self.rpc_client
+                .get_transaction_with_config(
+                    &signature,
+                    RpcTransactionConfig {
+                        encoding: Some(UiTransactionEncoding::Json),
+                        commitment: Some(self.commitment),
+                        max_supported_transaction_version: Some(0),
+                    },
+                )
+                .await?;
+
+            let logs: Option<Vec<String>> = transaction
+                .transaction
+                .meta
+                .and_then(|meta| meta.log_messages.into());
+            transactions.push(TransactionLogs {
+                signature: status.signature,
+                block_time: status
+                    .block_time
+                    .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0)),
+                logs: logs.unwrap_or_default(),
+            });
+        }
+
+        debug!("Fetched logs of {} transactions for {}", transactions.len(), address);
+        Ok(transactions)
+    }
+
     // Spawns a background task for continuous health monitoring
     fn spawn_health_monitor(&self) {
         let client = self.clone();
@@ -176,6 +521,29 @@ impl SolanaClient {
     }
 }
 
+/// Compute units recorded in a transaction's status meta, when the node reports them
+pub fn compute_units_consumed(meta: &UiTransactionStatusMeta) -> Option<u64> {
+    meta.compute_units_consumed.clone().into()
+}
+
+/// Sums the UI token amount held by an owner for a mint
+fn token_balance_for(
+    balances: &[UiTransactionTokenBalance],
+    owner: &str,
+    mint: &str,
+) -> Result<Decimal, SolanaError> {
+    balances
+        .iter()
+        .filter(|balance| {
+            balance.mint == mint && Option::<String>::from(balance.owner.clone()).as_deref() == Some(owner)
+        })
+        .try_fold(Decimal::ZERO, |total, balance| {
+            Decimal::from_str(&balance.ui_token_amount.ui_amount_string)
+                .map(|amount| total + amount)
+                .map_err(|e| SolanaError::ParseError(e.to_string()))
+        })
+}
+
 /// Creates a new Solana RPC client with optimized settings
 #[instrument]
 pub fn create_rpc_client(
@@ -199,14 +567,15 @@ pub fn create_rpc_client(
     Ok(Arc::new(client))
 }
 
-/// Signs and sends a transaction with optimized fee calculation
-#[instrument(skip(transaction, client, signer))]
+/// Signs and sends a transaction with optimized fee calculation; signing happens once, ahead
+/// of the submission retries, and a signer failure is returned without retrying
+#[instrument(skip(transaction, client, signer), fields(signer = signer.backend()))]
 pub async fn sign_and_send_transaction(
     mut transaction: Transaction,
     client: Arc<RpcClient>,
-    signer: &Keypair,
+    signer: &dyn Signer,
     priority_fee: Option<u64>,
-) -> Result<(Signature, u64), ClientError> {
+) -> Result<(Signature, u64), SolanaError> {
     let priority_fee = priority_fee.unwrap_or(MIN_PRIORITY_FEE);
     
     // Add priority fee to transaction
@@ -214,21 +583,24 @@ pub async fn sign_and_send_transaction(
         .get_latest_blockhash()
         .await?;
     
-    transaction.sign(&[signer], transaction.message.recent_blockhash);
+    signer.sign(&mut transaction).await?;
 
     let mut retries = 0;
     loop {
-        let result = client
-            .send_transaction_with_config(
-                &transaction,
-                RpcSendTransactionConfig {
-                    skip_preflight: true,
-                    preflight_commitment: Some(CommitmentConfig::processed()),
-                    encoding: None,
-                    max_retries: Some(MAX_RETRIES),
-                },
-            )
-            .await;
+        let result = match rpc_fault().await {
+            Ok(()) => client
+                .send_transaction_with_config(
+                    &transaction,
+                    RpcSendTransactionConfig {
+                        skip_preflight: true,
+                        preflight_commitment: Some(CommitmentConfig::processed()),
+                        encoding: None,
+                        max_retries: Some(MAX_RETRIES),
+                    },
+                )
+                .await,
+            Err(e) => Err(e),
+        };
 
         match result {
             Ok(signature) => {
@@ -248,11 +620,23 @@ pub async fn sign_and_send_transaction(
                 retries += 1;
                 sleep(Duration::from_millis(500 * 2u64.pow(retries as u32))).await;
             }
-            Err(e) => return Err(e),
+            Err(e) => return Err(e.into()),
         }
     }
 }
 
+/// Applies injected RPC latency or errors ahead of a call; a no-op without fault injection
+async fn rpc_fault() -> Result<(), ClientError> {
+    #[cfg(feature = "fault-injection")]
+    {
+        use crate::fault_injection::{self, FaultComponent};
+        fault_injection::on_call(FaultComponent::SolanaRpc)
+            .await
+            .map_err(|e| ClientError::from(solana_client::client_error::ClientErrorKind::Custom(e.to_string())))?;
+    }
+    Ok(())
+}
+
 /// Submits and monitors a bundle of transactions for MEV optimization
 #[instrument(skip(transactions, client))]
 pub async fn submit_mev_bundle(
@@ -277,6 +661,209 @@ pub async fn submit_mev_bundle(
     Ok(result)
 }
 
+/// How aggressively a transaction should bid for block inclusion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeUrgency {
+    Low,
+    Normal,
+    High,
+}
+
+impl FeeUrgency {
+    fn percentile(&self) -> f64 {
+        match self {
+            Self::Low => LOW_URGENCY_PERCENTILE,
+            Self::Normal => NORMAL_URGENCY_PERCENTILE,
+            Self::High => HIGH_URGENCY_PERCENTILE,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::High => "high",
+        }
+    }
+}
+
+/// Priority fee estimator configuration
+#[derive(Debug, Clone)]
+pub struct FeeEstimatorConfig {
+    pub refresh_interval: Duration,
+    pub max_age: Duration,
+    pub floor: u64,
+}
+
+impl Default for FeeEstimatorConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval: Duration::from_secs(FEE_REFRESH_INTERVAL_SECONDS),
+            max_age: Duration::from_secs(FEE_STALENESS_SECONDS),
+            floor: MIN_PRIORITY_FEE,
+        }
+    }
+}
+
+/// Percentile estimates computed from one sample of recent prioritization fees
+#[derive(Debug, Clone)]
+pub struct FeeEstimate {
+    pub low: u64,
+    pub normal: u64,
+    pub high: u64,
+    pub sample_count: usize,
+    pub sampled_at: Instant,
+}
+
+impl FeeEstimate {
+    /// Builds percentile estimates from raw per-slot fee samples
+    pub fn from_samples(samples: &[u64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        Some(Self {
+            low: fee_percentile(&sorted, FeeUrgency::Low.percentile()),
+            normal: fee_percentile(&sorted, FeeUrgency::Normal.percentile()),
+            high: fee_percentile(&sorted, FeeUrgency::High.percentile()),
+            sample_count: sorted.len(),
+            sampled_at: Instant::now(),
+        })
+    }
+
+    pub fn for_urgency(&self, urgency: FeeUrgency) -> u64 {
+        match urgency {
+            FeeUrgency::Low => self.low,
+            FeeUrgency::Normal => self.normal,
+            FeeUrgency::High => self.high,
+        }
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice
+fn fee_percentile(sorted: &[u64], percentile: f64) -> u64 {
+    let rank = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Accounts whose write locks a group of transactions contend for
+#[derive(Debug)]
+struct FeeCluster {
+    accounts: Vec<Pubkey>,
+    estimate: Option<FeeEstimate>,
+}
+
+/// Estimates compute unit prices from recent prioritization fees per account cluster
+#[derive(Debug)]
+pub struct FeeEstimator {
+    rpc_client: Arc<RpcClient>,
+    config: FeeEstimatorConfig,
+    clusters: parking_lot::RwLock<HashMap<String, FeeCluster>>,
+}
+
+impl FeeEstimator {
+    pub fn new(rpc_client: Arc<RpcClient>, config: FeeEstimatorConfig) -> Self {
+        Self {
+            rpc_client,
+            config,
+            clusters: parking_lot::RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Registers the program and pool accounts sampled for a cluster
+    pub fn register_cluster(&self, name: &str, accounts: Vec<Pubkey>) {
+        self.clusters.write().insert(
+            name.to_string(),
+            FeeCluster {
+                accounts,
+                estimate: None,
+            },
+        );
+    }
+
+    /// Samples `getRecentPrioritizationFees` for every registered cluster
+    #[instrument(skip(self))]
+    pub async fn refresh(&self) {
+        let clusters: Vec<(String, Vec<Pubkey>)> = self
+            .clusters
+            .read()
+            .iter()
+            .map(|(name, cluster)| (name.clone(), cluster.accounts.clone()))
+            .collect();
+
+        for (name, accounts) in clusters {
+            match self.rpc_client.get_recent_prioritization_fees(&accounts).await {
+                Ok(fees) => {
+                    let samples: Vec<u64> = fees.iter().map(|fee| fee.prioritization_fee).collect();
+                    self.update_cluster(&name, &samples);
+                }
+                Err(e) => {
+                    counter!(format!("{}.refresh_errors", FEE_METRICS_PREFIX), 1);
+                    warn!(cluster = %name, error = %e, "Failed to sample prioritization fees");
+                }
+            }
+        }
+    }
+
+    /// Replaces a cluster's estimate with one computed from new samples
+    fn update_cluster(&self, name: &str, samples: &[u64]) {
+        let Some(estimate) = FeeEstimate::from_samples(samples) else {
+            debug!(cluster = %name, "No prioritization fee samples returned");
+            return;
+        };
+
+        if let Some(cluster) = self.clusters.write().get_mut(name) {
+            gauge!(format!("{}.{}.p50", FEE_METRICS_PREFIX, name), estimate.normal as f64);
+            gauge!(format!("{}.{}.p90", FEE_METRICS_PREFIX, name), estimate.high as f64);
+            cluster.estimate = Some(estimate);
+        }
+    }
+
+    /// Returns the compute unit price to bid, falling back to the floor when data is missing or stale
+    pub fn estimate_priority_fee(&self, cluster: &str, urgency: FeeUrgency) -> u64 {
+        let clusters = self.clusters.read();
+        let estimate = clusters
+            .get(cluster)
+            .and_then(|cluster| cluster.estimate.as_ref())
+            .filter(|estimate| estimate.sampled_at.elapsed() <= self.config.max_age);
+
+        let fee = match estimate {
+            Some(estimate) => estimate.for_urgency(urgency).max(self.config.floor),
+            None => {
+                counter!(format!("{}.fallback", FEE_METRICS_PREFIX), 1);
+                debug!(cluster, "Using priority fee floor; no fresh estimate");
+                self.config.floor
+            }
+        };
+
+        histogram!(
+            format!("{}.chosen.{}", FEE_METRICS_PREFIX, urgency.as_str()),
+            fee as f64
+        );
+        fee
+    }
+
+    /// Records the fee paid by a transaction that landed on chain
+    pub fn record_landed_fee(&self, cluster: &str, fee: u64) {
+        histogram!(format!("{}.landed", FEE_METRICS_PREFIX), fee as f64);
+        debug!(cluster, fee, "Priority fee landed");
+    }
+
+    /// Refreshes every cluster on the configured interval
+    pub fn spawn_refresh(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.refresh_interval);
+            loop {
+                interval.tick().await;
+                self.refresh().await;
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -303,4 +890,52 @@ mod tests {
         let health = client.monitor_health().await;
         assert!(health.is_ok());
     }
-}
\ No newline at end of file
+
+    fn estimator(max_age: Duration) -> FeeEstimator {
+        FeeEstimator::new(
+            Arc::new(RpcClient::new("http://localhost:8899".to_string())),
+            FeeEstimatorConfig {
+                max_age,
+                ..FeeEstimatorConfig::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_fee_percentiles() {
+        let samples: Vec<u64> = (1..=100).map(|fee| fee * 1_000).collect();
+        let estimate = FeeEstimate::from_samples(&samples).unwrap();
+        assert_eq!(estimate.low, 25_000);
+        assert_eq!(estimate.normal, 50_000);
+        assert_eq!(estimate.high, 90_000);
+
+        let single = FeeEstimate::from_samples(&[42]).unwrap();
+        assert_eq!(single.for_urgency(FeeUrgency::High), 42);
+        assert!(FeeEstimate::from_samples(&[]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_estimate_uses_cluster_samples_above_floor() {
+        let estimator = estimator(Duration::from_secs(60));
+        estimator.register_cluster("jupiter", vec![Pubkey::new_unique()]);
+        estimator.update_cluster("jupiter", &[0, 5_000, 20_000, 40_000, 200_000]);
+
+        assert_eq!(estimator.estimate_priority_fee("jupiter", FeeUrgency::Low), MIN_PRIORITY_FEE);
+        assert_eq!(estimator.estimate_priority_fee("jupiter", FeeUrgency::Normal), 20_000);
+        assert_eq!(estimator.estimate_priority_fee("jupiter", FeeUrgency::High), 200_000);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_falls_back_to_floor() {
+        let estimator = estimator(Duration::ZERO);
+        estimator.register_cluster("drift", vec![Pubkey::new_unique()]);
+        assert_eq!(estimator.estimate_priority_fee("drift", FeeUrgency::High), MIN_PRIORITY_FEE);
+
+        // Stale samples are ignored
+        estimator.update_cluster("drift", &[500_000]);
+        sleep(Duration::from_millis(5)).await;
+        assert_eq!(estimator.estimate_priority_fee("drift", FeeUrgency::High), MIN_PRIORITY_FEE);
+
+        assert_eq!(estimator.estimate_priority_fee("unknown", FeeUrgency::Normal), MIN_PRIORITY_FEE);
+    }
+}
@@ -0,0 +1,106 @@
+//! OpenTelemetry trace export for `tracing` spans. Spans are exported over OTLP with
+//! ratio-based sampling, and W3C `traceparent` context from incoming requests is extracted
+//! so one order's API request, risk validation, execution and DB writes form a single trace.
+//!
+//! Version dependencies:
+//! - opentelemetry = "0.19"
+//! - opentelemetry-otlp = "0.12"
+//! - tracing-opentelemetry = "0.19"
+
+use axum::http::HeaderMap;
+use opentelemetry::propagation::Extractor;
+use opentelemetry::sdk::propagation::TraceContextPropagator;
+use opentelemetry::sdk::trace::{self, Sampler};
+use opentelemetry::sdk::Resource;
+use opentelemetry::{global, Context, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use tracing::Subscriber;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{EnvFilter, Layer};
+
+use crate::config::logging::TracingConfig;
+use crate::utils::logger::LoggerError;
+
+/// Span layer exporting to the configured OTLP collector, filtered by the tracing directives
+pub fn otel_layer<S>(config: &TracingConfig) -> Result<Box<dyn Layer<S> + Send + Sync>, LoggerError>
+where
+    S: Subscriber + for<'span> LookupSpan<'span> + Send + Sync,
+{
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let sampler = Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(config.sample_ratio)));
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(config.otlp_endpoint.clone()),
+        )
+        .with_trace_config(
+            trace::config()
+                .with_sampler(sampler)
+                .with_resource(Resource::new(vec![KeyValue::new(
+                    "service.name",
+                    config.service_name.clone(),
+                )])),
+        )
+        .install_batch(opentelemetry::runtime::Tokio)
+        .map_err(|e| LoggerError::TraceExport(e.to_string()))?;
+
+    let joined = config.directives.join(",");
+    let filter = EnvFilter::try_new(&joined)
+        .map_err(|e| LoggerError::InvalidDirective(joined, e.to_string()))?;
+
+    Ok(tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(filter)
+        .boxed())
+}
+
+/// Flushes spans still buffered by the batch exporter
+pub fn shutdown() {
+    global::shutdown_tracer_provider();
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
+/// Remote trace context carried by W3C `traceparent`/`tracestate` request headers
+pub fn extract_context(headers: &HeaderMap) -> Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TraceContextExt;
+
+    #[test]
+    fn test_extracts_w3c_traceparent() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".parse().unwrap(),
+        );
+
+        let context = extract_context(&headers);
+        let span_context = context.span().span_context().clone();
+        assert!(span_context.is_remote());
+        assert!(span_context.is_sampled());
+        assert_eq!(span_context.trace_id().to_string(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(span_context.span_id().to_string(), "00f067aa0ba902b7");
+
+        // Without the header there is no remote parent and sampling starts a new trace
+        assert!(!extract_context(&HeaderMap::new()).span().span_context().is_valid());
+    }
+}
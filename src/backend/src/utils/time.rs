@@ -56,7 +56,13 @@ pub fn to_trading_timezone(
 /// Validates market data timestamp freshness with configurable threshold
 #[inline]
 pub fn is_valid_market_timestamp(timestamp: DateTime<Utc>) -> bool {
-    let now = current_timestamp();
+    is_valid_market_timestamp_at(timestamp, current_timestamp())
+}
+
+/// Validates market data timestamp freshness relative to a reference time,
+/// used when replaying recorded data on its original clock
+#[inline]
+pub fn is_valid_market_timestamp_at(timestamp: DateTime<Utc>, now: DateTime<Utc>) -> bool {
     let age = calculate_duration_ms(timestamp, now)
         .unwrap_or(MAX_MARKET_DATA_AGE_MS + 1);
     age <= MAX_MARKET_DATA_AGE_MS
@@ -80,6 +86,13 @@ pub fn calculate_duration_ms(
         .map_err(|e| TimeError::DurationError(e.to_string()))
 }
 
+/// Converts a std duration into a chrono duration, saturating to zero when it
+/// does not fit
+#[inline]
+pub fn to_chrono(duration: std::time::Duration) -> Duration {
+    Duration::from_std(duration).unwrap_or_else(|_| Duration::zero())
+}
+
 /// Formats a timestamp for logging and display with consistent ISO 8601 format
 pub fn format_timestamp(timestamp: DateTime<Utc>) -> Result<String, TimeError> {
     to_trading_timezone(timestamp)
@@ -131,6 +144,12 @@ mod tests {
         assert!(calculate_duration_ms(end, start).is_err());
     }
 
+    #[test]
+    fn test_to_chrono_saturates() {
+        assert_eq!(to_chrono(std::time::Duration::from_millis(1500)), Duration::milliseconds(1500));
+        assert_eq!(to_chrono(std::time::Duration::MAX), Duration::zero());
+    }
+
     #[test]
     fn test_timestamp_formatting() {
         let ts = current_timestamp();
@@ -0,0 +1,427 @@
+//! Associated token account management for the trading wallet. Before an order on a pair
+//! whose tokens the wallet has never held, the missing associated token accounts are found
+//! and created with the idempotent create instruction, ideally inside the order's own
+//! transaction, and the rent they lock up is reported with the order's fees. Accounts seen
+//! on-chain are cached so later orders skip the lookup; missing ones are looked up again
+//! until they exist, since the transaction creating them may not land.
+//!
+//! Empty accounts of a quarantined or abandoned pair can be closed to reclaim their rent,
+//! keeping any mint another registered pair still trades.
+//!
+//! Version dependencies:
+//! - solana-sdk = "1.16"
+//! - spl-token = "4.0"
+//! - spl-associated-token-account = "2.0"
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use metrics::counter;
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+use thiserror::Error;
+use tracing::{debug, info};
+
+use crate::utils::signer::SignerError;
+use crate::utils::solana::{SolanaClient, SolanaError};
+
+// Token account constants
+const METRICS_PREFIX: &str = "trading_bot.token_accounts";
+
+/// Token account management errors
+#[derive(Error, Debug)]
+pub enum TokenAccountError {
+    #[error("no mints registered for {0}")]
+    UnknownPair(String),
+    #[error("token account rpc error: {0}")]
+    Rpc(String),
+    #[error("failed to build token account instruction: {0}")]
+    Instruction(String),
+}
+
+impl From<SolanaError> for TokenAccountError {
+    fn from(error: SolanaError) -> Self {
+        Self::Rpc(error.to_string())
+    }
+}
+
+/// Chain access token account management needs
+#[async_trait]
+pub trait TokenAccountRpc: Send + Sync {
+    /// Token amount in an account, or `None` when it does not exist
+    async fn token_amount(&self, account: &Pubkey) -> Result<Option<u64>, SolanaError>;
+    /// Lamports an account of `data_len` bytes needs to be rent exempt
+    async fn rent_exempt_minimum(&self, data_len: usize) -> Result<u64, SolanaError>;
+    /// Signs and sends the instructions as one transaction paid by the wallet
+    async fn send(&self, instructions: Vec<Instruction>) -> Result<String, SolanaError>;
+}
+
+#[async_trait]
+impl TokenAccountRpc for SolanaClient {
+    async fn token_amount(&self, account: &Pubkey) -> Result<Option<u64>, SolanaError> {
+        self.get_token_account_amount(account).await
+    }
+
+    async fn rent_exempt_minimum(&self, data_len: usize) -> Result<u64, SolanaError> {
+        self.get_rent_exempt_minimum(data_len).await
+    }
+
+    async fn send(&self, instructions: Vec<Instruction>) -> Result<String, SolanaError> {
+        let payer = self
+            .signer()
+            .ok_or_else(|| SolanaError::from(SignerError::Config("no transaction signer configured".to_string())))?
+            .pubkey();
+        let transaction = Transaction::new_with_payer(&instructions, Some(&payer));
+        let (signature, _) = self.sign_and_send_transaction(transaction, None).await?;
+        Ok(signature.to_string())
+    }
+}
+
+/// Accounts an order needs created, with the instructions creating them
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenAccountSetup {
+    /// Idempotent create instructions, one per missing account
+    pub instructions: Vec<Instruction>,
+    /// Associated token accounts being created
+    pub accounts: Vec<Pubkey>,
+    /// Lamports the new accounts lock up for rent exemption
+    pub rent_lamports: u64,
+}
+
+impl TokenAccountSetup {
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+}
+
+/// A token account closed to reclaim its rent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClosedAccount {
+    pub mint: String,
+    pub account: String,
+}
+
+/// A token account left open, and why
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RetainedAccount {
+    pub mint: String,
+    pub reason: String,
+}
+
+/// Outcome of closing a pair's token accounts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AccountClosure {
+    pub trading_pair: String,
+    pub closed: Vec<ClosedAccount>,
+    pub retained: Vec<RetainedAccount>,
+    pub reclaimed_lamports: u64,
+    /// Transaction closing the accounts, when any were closed
+    pub signature: Option<String>,
+}
+
+/// Creates, caches and closes the wallet's associated token accounts
+pub struct TokenAccountManager {
+    owner: Pubkey,
+    rpc: Arc<dyn TokenAccountRpc>,
+    /// Base and quote mints per trading pair
+    pairs: RwLock<HashMap<String, [Pubkey; 2]>>,
+    /// Associated token accounts seen on-chain
+    known: RwLock<HashSet<Pubkey>>,
+    rent_exempt_minimum: Mutex<Option<u64>>,
+}
+
+impl std::fmt::Debug for TokenAccountManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenAccountManager")
+            .field("owner", &self.owner)
+            .field("pairs", &self.pairs.read().len())
+            .field("known", &self.known.read().len())
+            .finish()
+    }
+}
+
+impl TokenAccountManager {
+    pub fn new(owner: Pubkey, rpc: Arc<dyn TokenAccountRpc>) -> Self {
+        Self {
+            owner,
+            rpc,
+            pairs: RwLock::new(HashMap::new()),
+            known: RwLock::new(HashSet::new()),
+            rent_exempt_minimum: Mutex::new(None),
+        }
+    }
+
+    pub fn owner(&self) -> &Pubkey {
+        &self.owner
+    }
+
+    /// Records the mints a pair trades, so closing one pair keeps mints another still uses
+    pub fn register_pair(&self, trading_pair: impl Into<String>, base_mint: Pubkey, quote_mint: Pubkey) {
+        self.pairs.write().insert(trading_pair.into(), [base_mint, quote_mint]);
+    }
+
+    /// Owner's associated token account for a mint
+    pub fn account(&self, mint: &Pubkey) -> Pubkey {
+        get_associated_token_address(&self.owner, mint)
+    }
+
+    /// Whether the mint's account is cached as existing
+    pub fn is_known(&self, mint: &Pubkey) -> bool {
+        self.known.read().contains(&self.account(mint))
+    }
+
+    /// Instructions creating whichever of the mints' accounts do not exist yet
+    pub async fn setup(&self, mints: &[Pubkey]) -> Result<TokenAccountSetup, TokenAccountError> {
+        let mut setup = TokenAccountSetup::default();
+        for mint in mints {
+            let account = self.account(mint);
+            if self.known.read().contains(&account) || setup.accounts.contains(&account) {
+                continue;
+            }
+            if self.rpc.token_amount(&account).await?.is_some() {
+                self.known.write().insert(account);
+                continue;
+            }
+
+            setup.instructions.push(create_associated_token_account_idempotent(
+                &self.owner,
+                &self.owner,
+                mint,
+                &spl_token::id(),
+            ));
+            setup.accounts.push(account);
+            setup.rent_lamports += self.rent_exempt_minimum().await?;
+        }
+
+        if !setup.is_empty() {
+            counter!(format!("{}.missing", METRICS_PREFIX), setup.accounts.len() as u64);
+            debug!(accounts = setup.accounts.len(), rent = setup.rent_lamports, "Creating token accounts");
+        }
+        Ok(setup)
+    }
+
+    /// Sends the setup as its own transaction, for orders whose transaction the venue builds
+    pub async fn create(&self, setup: &TokenAccountSetup) -> Result<Option<String>, TokenAccountError> {
+        if setup.is_empty() {
+            return Ok(None);
+        }
+        let signature = self.rpc.send(setup.instructions.clone()).await?;
+        self.known.write().extend(setup.accounts.iter().copied());
+        Ok(Some(signature))
+    }
+
+    /// Closes the pair's empty accounts to reclaim their rent. Mints another registered pair
+    /// trades and accounts still holding tokens are kept.
+    pub async fn close_pair(&self, trading_pair: &str) -> Result<AccountClosure, TokenAccountError> {
+        let (mints, shared) = {
+            let pairs = self.pairs.read();
+            let mints = *pairs
+                .get(trading_pair)
+                .ok_or_else(|| TokenAccountError::UnknownPair(trading_pair.to_string()))?;
+            let shared: HashSet<Pubkey> = pairs
+                .iter()
+                .filter(|(pair, _)| pair.as_str() != trading_pair)
+                .flat_map(|(_, mints)| mints.iter().copied())
+                .collect();
+            (mints, shared)
+        };
+
+        let mut closure = AccountClosure {
+            trading_pair: trading_pair.to_string(),
+            closed: Vec::new(),
+            retained: Vec::new(),
+            reclaimed_lamports: 0,
+            signature: None,
+        };
+        let mut instructions = Vec::new();
+        for mint in mints {
+            let retain = |reason: &str| RetainedAccount {
+                mint: mint.to_string(),
+                reason: reason.to_string(),
+            };
+            if shared.contains(&mint) {
+                closure.retained.push(retain("traded by another pair"));
+                continue;
+            }
+            let account = self.account(&mint);
+            match self.rpc.token_amount(&account).await? {
+                None => closure.retained.push(retain("no account")),
+                Some(amount) if amount > 0 => closure.retained.push(retain("holds a balance")),
+                Some(_) => {
+                    let close = spl_token::instruction::close_account(
+                        &spl_token::id(),
+                        &account,
+                        &self.owner,
+                        &self.owner,
+                        &[],
+                    )
+                    .map_err(|e| TokenAccountError::Instruction(e.to_string()))?;
+                    instructions.push(close);
+                    closure.closed.push(ClosedAccount {
+                        mint: mint.to_string(),
+                        account: account.to_string(),
+                    });
+                    closure.reclaimed_lamports += self.rent_exempt_minimum().await?;
+                }
+            }
+        }
+
+        if !instructions.is_empty() {
+            closure.signature = Some(self.rpc.send(instructions).await?);
+            let mut known = self.known.write();
+            for closed in &closure.closed {
+                if let Ok(account) = closed.account.parse::<Pubkey>() {
+                    known.remove(&account);
+                }
+            }
+            counter!(format!("{}.closed", METRICS_PREFIX), closure.closed.len() as u64);
+            info!(
+                trading_pair,
+                closed = closure.closed.len(),
+                reclaimed_lamports = closure.reclaimed_lamports,
+                "Closed empty token accounts"
+            );
+        }
+        Ok(closure)
+    }
+
+    /// Rent-exempt minimum of a token account, fetched once
+    async fn rent_exempt_minimum(&self) -> Result<u64, TokenAccountError> {
+        if let Some(lamports) = *self.rent_exempt_minimum.lock() {
+            return Ok(lamports);
+        }
+        let lamports = self.rpc.rent_exempt_minimum(spl_token::state::Account::LEN).await?;
+        *self.rent_exempt_minimum.lock() = Some(lamports);
+        Ok(lamports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RENT: u64 = 2_039_280;
+
+    /// Chain with a fixed set of token accounts, counting lookups and recording sends
+    #[derive(Default)]
+    struct MockRpc {
+        balances: Mutex<HashMap<Pubkey, u64>>,
+        lookups: Mutex<usize>,
+        sent: Mutex<Vec<Vec<Instruction>>>,
+    }
+
+    impl MockRpc {
+        fn with_account(self, owner: &Pubkey, mint: &Pubkey, amount: u64) -> Self {
+            self.balances.lock().insert(get_associated_token_address(owner, mint), amount);
+            self
+        }
+    }
+
+    #[async_trait]
+    impl TokenAccountRpc for MockRpc {
+        async fn token_amount(&self, account: &Pubkey) -> Result<Option<u64>, SolanaError> {
+            *self.lookups.lock() += 1;
+            Ok(self.balances.lock().get(account).copied())
+        }
+
+        async fn rent_exempt_minimum(&self, data_len: usize) -> Result<u64, SolanaError> {
+            assert_eq!(data_len, spl_token::state::Account::LEN);
+            Ok(RENT)
+        }
+
+        async fn send(&self, instructions: Vec<Instruction>) -> Result<String, SolanaError> {
+            let mut sent = self.sent.lock();
+            sent.push(instructions);
+            Ok(format!("sig_{}", sent.len()))
+        }
+    }
+
+    fn is_create(instruction: &Instruction) -> bool {
+        instruction.program_id == spl_associated_token_account::id()
+    }
+
+    #[tokio::test]
+    async fn test_create_included_only_for_missing_accounts() {
+        let owner = Pubkey::new_unique();
+        let (held, new) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let rpc = Arc::new(MockRpc::default().with_account(&owner, &held, 5));
+        let manager = TokenAccountManager::new(owner, rpc.clone());
+
+        let setup = manager.setup(&[held, new]).await.unwrap();
+        assert_eq!(setup.instructions.len(), 1);
+        assert!(is_create(&setup.instructions[0]));
+        assert_eq!(setup.accounts, vec![manager.account(&new)]);
+        assert_eq!(setup.rent_lamports, RENT);
+        assert_eq!(*rpc.lookups.lock(), 2);
+
+        // The existing account is cached; the missing one is checked until it exists
+        assert!(manager.is_known(&held));
+        assert!(!manager.is_known(&new));
+        rpc.balances.lock().insert(manager.account(&new), 0);
+        let setup = manager.setup(&[held, new]).await.unwrap();
+        assert!(setup.is_empty());
+        assert_eq!(*rpc.lookups.lock(), 3);
+
+        // Both cached now, so no more lookups
+        assert!(manager.setup(&[held, new]).await.unwrap().is_empty());
+        assert_eq!(*rpc.lookups.lock(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_created_accounts_cached() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let rpc = Arc::new(MockRpc::default());
+        let manager = TokenAccountManager::new(owner, rpc.clone());
+
+        let setup = manager.setup(&[mint, mint]).await.unwrap();
+        assert_eq!(setup.instructions.len(), 1);
+        assert_eq!(manager.create(&setup).await.unwrap().as_deref(), Some("sig_1"));
+        assert!(manager.setup(&[mint]).await.unwrap().is_empty());
+        assert_eq!(*rpc.lookups.lock(), 1);
+        assert_eq!(manager.create(&TokenAccountSetup::default()).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_close_pair_keeps_shared_and_funded_accounts() {
+        let owner = Pubkey::new_unique();
+        let (bonk, wif, usdc) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let rpc = Arc::new(
+            MockRpc::default()
+                .with_account(&owner, &bonk, 0)
+                .with_account(&owner, &wif, 7)
+                .with_account(&owner, &usdc, 0),
+        );
+        let manager = TokenAccountManager::new(owner, rpc.clone());
+        manager.register_pair("BONK/USDC", bonk, usdc);
+        manager.register_pair("WIF/USDC", wif, usdc);
+        manager.setup(&[bonk]).await.unwrap();
+        assert!(manager.is_known(&bonk));
+
+        let closure = manager.close_pair("BONK/USDC").await.unwrap();
+        assert_eq!(closure.closed.len(), 1);
+        assert_eq!(closure.closed[0].mint, bonk.to_string());
+        assert_eq!(closure.retained[0].reason, "traded by another pair");
+        assert_eq!(closure.reclaimed_lamports, RENT);
+        assert_eq!(closure.signature.as_deref(), Some("sig_1"));
+        assert_eq!(rpc.sent.lock()[0][0].program_id, spl_token::id());
+        assert!(!manager.is_known(&bonk));
+
+        // Nothing to close on a funded account
+        let closure = manager.close_pair("WIF/USDC").await.unwrap();
+        assert!(closure.closed.is_empty());
+        assert_eq!(closure.retained.len(), 2);
+        assert_eq!(closure.signature, None);
+        assert_eq!(rpc.sent.lock().len(), 1);
+
+        assert!(matches!(manager.close_pair("SOL/USDC").await, Err(TokenAccountError::UnknownPair(_))));
+    }
+}
@@ -12,6 +12,7 @@ use crate::api::auth::{
     AuthRequest,
     RefreshRequest,
 };
+use crate::api::jwks::JwtKeyStore;
 use crate::config::security::{SecurityConfig, JWTConfig, RateLimitConfig};
 use crate::utils::crypto::verify_wallet_signature;
 
@@ -23,6 +24,7 @@ const TEST_NONCE: &str = "9XDtxJv2DKKyfAHJGvZ9MFt5bKyXbfQPqHrhPF9zNNfw";
 /// Enhanced test context with security testing capabilities
 pub struct TestContext {
     pub security_config: Arc<SecurityConfig>,
+    pub key_store: Arc<JwtKeyStore>,
     pub redis_client: Arc<redis::Client>,
     pub rate_limiter: Arc<Mutex<HashMap<String, (u32, Instant)>>>,
     pub audit_logs: Arc<Mutex<Vec<String>>>,
@@ -32,10 +34,12 @@ impl AsyncTestContext for TestContext {
     async fn setup() -> Self {
         // Initialize test JWT config
         let jwt_config = JWTConfig {
-            secret_key: "test_secret_key_for_jwt_token_generation_and_validation".to_string(),
+            secret_key: "test_secret_key_for_jwt_token_generation_and_validation".into(),
             token_expiry: 3600,
             refresh_expiry: 86400,
             algorithm: jsonwebtoken::Algorithm::HS256,
+            keys: vec![],
+            jwks: None,
         };
 
         // Initialize test rate limit config
@@ -54,6 +58,8 @@ impl AsyncTestContext for TestContext {
             Default::default(), // Access control config
         ).unwrap());
 
+        let key_store = Arc::new(JwtKeyStore::new(&security_config.jwt));
+
         // Initialize test Redis client
         let redis_client = Arc::new(redis::Client::open("redis://127.0.0.1:6379").unwrap());
 
@@ -63,6 +69,7 @@ impl AsyncTestContext for TestContext {
 
         Self {
             security_config,
+            key_store,
             redis_client,
             rate_limiter,
             audit_logs,
@@ -104,6 +111,7 @@ async fn test_wallet_authentication_success() {
         serde_json::to_value(auth_request).unwrap().into(),
         ctx.redis_client.clone(),
         ctx.security_config.clone(),
+        ctx.key_store.clone(),
     ).await;
 
     assert!(result.is_ok());
@@ -141,6 +149,7 @@ async fn test_rate_limiting() {
             serde_json::to_value(auth_request).unwrap().into(),
             ctx.redis_client.clone(),
             ctx.security_config.clone(),
+            ctx.key_store.clone(),
         ).await;
 
         if i < 5 {
@@ -172,6 +181,7 @@ async fn test_token_refresh() {
         serde_json::to_value(auth_request).unwrap().into(),
         ctx.redis_client.clone(),
         ctx.security_config.clone(),
+        ctx.key_store.clone(),
     ).await.unwrap();
 
     // Test token refresh
@@ -183,6 +193,7 @@ async fn test_token_refresh() {
         serde_json::to_value(refresh_request).unwrap().into(),
         ctx.redis_client.clone(),
         ctx.security_config.clone(),
+        ctx.key_store.clone(),
     ).await;
 
     assert!(refresh_result.is_ok());
@@ -209,6 +220,7 @@ async fn test_invalid_nonce() {
         serde_json::to_value(auth_request).unwrap().into(),
         ctx.redis_client.clone(),
         ctx.security_config.clone(),
+        ctx.key_store.clone(),
     ).await;
 
     assert!(result.is_err());
@@ -237,6 +249,7 @@ async fn test_security_audit_logging() {
         serde_json::to_value(auth_request).unwrap().into(),
         ctx.redis_client.clone(),
         ctx.security_config.clone(),
+        ctx.key_store.clone(),
     ).await;
 
     // Verify audit logs
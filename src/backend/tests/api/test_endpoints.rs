@@ -10,6 +10,7 @@ use crate::api::endpoints::{
     get_market_data, create_order, get_portfolio, update_strategy,
     MarketDataRequest, OrderRequest, OrderType, TimeInForce,
 };
+use crate::risk_manager::exposure::TradeSide;
 
 // Test constants
 const TEST_WALLET_ADDRESS: &str = "11111111111111111111111111111111";
@@ -117,11 +118,13 @@ async fn test_get_market_data_rate_limit(ctx: &TestContext) -> Result<(), Box<dy
 async fn test_create_order_success(ctx: &TestContext) -> Result<(), Box<dyn std::error::Error>> {
     let request = OrderRequest {
         trading_pair: TEST_TRADING_PAIR.to_string(),
+        side: TradeSide::Buy,
         amount: 1.0,
         price: 100.0,
         order_type: OrderType::Limit,
         time_in_force: TimeInForce::GoodTilCancelled,
         slippage_tolerance: Some(1.0),
+        authorization: None,
     };
 
     let timer = metrics::histogram!("test.order_creation.latency");
@@ -152,11 +155,13 @@ async fn test_create_order_success(ctx: &TestContext) -> Result<(), Box<dyn std:
 async fn test_create_order_invalid_signature(ctx: &TestContext) -> Result<(), Box<dyn std::error::Error>> {
     let request = OrderRequest {
         trading_pair: TEST_TRADING_PAIR.to_string(),
+        side: TradeSide::Buy,
         amount: 1.0,
         price: 100.0,
         order_type: OrderType::Market,
         time_in_force: TimeInForce::ImmediateOrCancel,
         slippage_tolerance: Some(1.0),
+        authorization: None,
     };
 
     let response = ctx.server
@@ -190,6 +195,26 @@ async fn test_get_portfolio_success(ctx: &TestContext) -> Result<(), Box<dyn std
     Ok(())
 }
 
+// Order Book Tests
+#[tokio::test]
+#[test_context(TestContext)]
+#[instrument]
+async fn test_get_order_book_unknown_pair(ctx: &TestContext) -> Result<(), Box<dyn std::error::Error>> {
+    let response = ctx.server
+        .get("/api/v1/markets/NOPE-USDC/orderbook?depth=500")
+        .add_header("Authorization", &format!("Bearer {}", ctx.test_jwt))
+        .send()
+        .await?;
+
+    assert_eq!(response.status_code(), 404);
+
+    let data: Value = response.json().await?;
+    assert_eq!(data["status"], 404);
+    assert!(data["error"].as_str().unwrap().contains("NOPE/USDC"));
+
+    Ok(())
+}
+
 // Strategy Tests
 #[tokio::test]
 #[test_context(TestContext)]
@@ -233,11 +258,13 @@ async fn test_full_trading_cycle(ctx: &TestContext) -> Result<(), Box<dyn std::e
     // 2. Create buy order
     let buy_order = OrderRequest {
         trading_pair: TEST_TRADING_PAIR.to_string(),
+        side: TradeSide::Buy,
         amount: 1.0,
         price: 100.0,
         order_type: OrderType::Limit,
         time_in_force: TimeInForce::GoodTilCancelled,
         slippage_tolerance: Some(1.0),
+        authorization: None,
     };
 
     let buy_response = ctx.server
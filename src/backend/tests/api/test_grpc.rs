@@ -0,0 +1,312 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use rust_decimal_macros::dec; // v1.30
+use tokio::net::TcpListener; // v1.28
+use tokio::sync::broadcast;
+use tokio::time::{timeout, Duration};
+use tokio_stream::StreamExt; // v0.1
+use tonic::transport::Channel; // v0.9
+use tonic::{Code, Request};
+
+use crate::api::auth::Claims;
+use crate::api::grpc::proto::market_data_event::Event;
+use crate::api::grpc::proto::market_data_stream_client::MarketDataStreamClient;
+use crate::api::grpc::proto::order_service_client::OrderServiceClient;
+use crate::api::grpc::proto::portfolio_service_client::PortfolioServiceClient;
+use crate::api::grpc::proto::{
+    CancelOrdersRequest, MarketDataRequest, OrderKind, PortfolioRequest, Side, SubmitOrderRequest,
+};
+use crate::api::grpc::{GrpcServer, OrderGateway};
+use crate::api::jwks::JwtKeyStore;
+use crate::config::security::JWTConfig;
+use crate::execution_engine::order_book::OrderBookSnapshot;
+use crate::execution_engine::{ExecutionResult, StrategyParams};
+use crate::models::market::{MarketData, MarketDataBatch, OrderBookLevel};
+use crate::models::portfolio::Portfolio;
+use crate::risk_manager::exposure::TradeSide;
+use crate::{Error, ExecutionError};
+
+// Test constants
+const TEST_WALLET_ADDRESS: &str = "DxPv2QMA5cWR5Xj7BHt45Xx3vXvHGkTGVZen2Y3pVH9L";
+const TEST_TRADING_PAIR: &str = "SOL/USDC";
+const STREAM_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Order gateway recording submitted orders and rejecting oversized ones
+#[derive(Default)]
+struct FakeGateway {
+    submitted: Mutex<Vec<StrategyParams>>,
+}
+
+#[tonic::async_trait]
+impl OrderGateway for FakeGateway {
+    async fn submit_order(&self, params: StrategyParams) -> Result<ExecutionResult, Error> {
+        if params.size > dec!(100) {
+            return Err(Error::Execution(ExecutionError::ValidationError(
+                "trade value exceeds maximum".to_string(),
+            )));
+        }
+        let price = params.price;
+        self.submitted.lock().push(params);
+        Ok(ExecutionResult {
+            trade_id: "tx-1".to_string(),
+            execution_time: Duration::from_millis(12),
+            price,
+            mev_value: 0.0,
+            fills: vec![],
+            slippage: None,
+        })
+    }
+
+    fn cancel_orders(&self, strategy_id: &str) -> usize {
+        self.submitted
+            .lock()
+            .iter()
+            .filter(|params| params.strategy_id == strategy_id)
+            .count()
+    }
+}
+
+struct TestServer {
+    addr: SocketAddr,
+    token: String,
+    gateway: Arc<FakeGateway>,
+    portfolio: Portfolio,
+    prices: broadcast::Sender<MarketDataBatch>,
+    order_books: broadcast::Sender<OrderBookSnapshot>,
+}
+
+impl TestServer {
+    async fn start() -> Self {
+        let key_store = Arc::new(JwtKeyStore::new(&JWTConfig {
+            secret_key: "test_secret_key_for_jwt_token_generation_and_validation".into(),
+            token_expiry: 3600,
+            refresh_expiry: 86400,
+            algorithm: jsonwebtoken::Algorithm::HS256,
+            keys: vec![],
+            jwks: None,
+        }));
+        let now = chrono::Utc::now().timestamp();
+        let token = key_store
+            .sign(&Claims {
+                sub: TEST_WALLET_ADDRESS.to_string(),
+                exp: now + 3600,
+                iat: now,
+                device_id: None,
+            })
+            .unwrap();
+
+        let gateway = Arc::new(FakeGateway::default());
+        let portfolio = Portfolio::new(TEST_WALLET_ADDRESS.to_string(), dec!(1000)).unwrap();
+        let (order_books, _) = broadcast::channel(16);
+        let (prices, _) = broadcast::channel(16);
+
+        let server = GrpcServer::new(key_store, gateway.clone(), portfolio.clone(), order_books.clone())
+            .with_price_feed(prices.clone());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(server.serve_with_listener(listener));
+
+        Self {
+            addr,
+            token,
+            gateway,
+            portfolio,
+            prices,
+            order_books,
+        }
+    }
+
+    async fn channel(&self) -> Channel {
+        Channel::from_shared(format!("http://{}", self.addr))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap()
+    }
+
+    fn authorized<T>(&self, message: T) -> Request<T> {
+        let mut request = Request::new(message);
+        request
+            .metadata_mut()
+            .insert("authorization", format!("Bearer {}", self.token).parse().unwrap());
+        request
+    }
+}
+
+fn submit_request(size: &str) -> SubmitOrderRequest {
+    SubmitOrderRequest {
+        strategy_id: "grid".to_string(),
+        trading_pair: TEST_TRADING_PAIR.to_string(),
+        exchange: "jupiter".to_string(),
+        side: Side::Buy as i32,
+        order_type: OrderKind::Market as i32,
+        size: size.to_string(),
+        price: "23.45".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_market_data_stream_filters_by_pair() {
+    let server = TestServer::start().await;
+    let mut client = MarketDataStreamClient::new(server.channel().await);
+
+    let mut stream = client
+        .subscribe(server.authorized(MarketDataRequest {
+            trading_pairs: vec![TEST_TRADING_PAIR.to_string()],
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    // Give the server task time to subscribe before publishing
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    server
+        .prices
+        .send(vec![
+            MarketData::new("ORCA/USDC".to_string(), "jupiter".to_string(), dec!(1.2), dec!(50)).unwrap(),
+            MarketData::new(TEST_TRADING_PAIR.to_string(), "jupiter".to_string(), dec!(23.45), dec!(10)).unwrap(),
+        ]
+        .into())
+        .unwrap();
+    server
+        .order_books
+        .send(OrderBookSnapshot {
+            trading_pair: TEST_TRADING_PAIR.to_string(),
+            exchange: "jupiter".to_string(),
+            bids: vec![OrderBookLevel::new(dec!(23.40), dec!(5))],
+            asks: vec![OrderBookLevel::new(dec!(23.50), dec!(4))],
+            timestamp: chrono::Utc::now(),
+            is_stale: false,
+        })
+        .unwrap();
+
+    let first = timeout(STREAM_TIMEOUT, stream.next()).await.unwrap().unwrap().unwrap();
+    match first.event {
+        Some(Event::Price(price)) => {
+            assert_eq!(price.trading_pair, TEST_TRADING_PAIR);
+            assert_eq!(price.price, "23.45");
+        }
+        other => panic!("expected price update, got {:?}", other),
+    }
+
+    let second = timeout(STREAM_TIMEOUT, stream.next()).await.unwrap().unwrap().unwrap();
+    match second.event {
+        Some(Event::OrderBook(book)) => {
+            assert_eq!(book.bids[0].price, "23.40");
+            assert_eq!(book.asks[0].volume, "4");
+        }
+        other => panic!("expected order book update, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_order_service_submits_and_cancels() {
+    let server = TestServer::start().await;
+    let mut client = OrderServiceClient::new(server.channel().await);
+
+    let response = client
+        .submit_order(server.authorized(submit_request("1.5")))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(response.trade_id, "tx-1");
+    assert_eq!(response.executed_price, "23.45");
+    {
+        let submitted = server.gateway.submitted.lock();
+        assert_eq!(submitted[0].size, dec!(1.5));
+        assert_eq!(submitted[0].side, TradeSide::Buy);
+    }
+
+    // Rejections from the shared order path surface as failed preconditions
+    let rejected = client
+        .submit_order(server.authorized(submit_request("250")))
+        .await
+        .unwrap_err();
+    assert_eq!(rejected.code(), Code::FailedPrecondition);
+
+    let malformed = client
+        .submit_order(server.authorized(submit_request("one")))
+        .await
+        .unwrap_err();
+    assert_eq!(malformed.code(), Code::InvalidArgument);
+
+    let cancelled = client
+        .cancel_orders(server.authorized(CancelOrdersRequest {
+            strategy_id: "grid".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(cancelled.cancelled, 1);
+}
+
+#[tokio::test]
+async fn test_portfolio_service_snapshot_and_stream() {
+    let server = TestServer::start().await;
+    server
+        .portfolio
+        .add_position(TEST_TRADING_PAIR.to_string(), dec!(2), dec!(20))
+        .await
+        .unwrap();
+    let mut client = PortfolioServiceClient::new(server.channel().await);
+
+    let snapshot = client
+        .get_snapshot(server.authorized(PortfolioRequest {}))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(snapshot.wallet_address, TEST_WALLET_ADDRESS);
+    assert_eq!(snapshot.positions.len(), 1);
+    assert_eq!(snapshot.positions[0].size, "2");
+
+    let mut stream = client
+        .stream_positions(server.authorized(PortfolioRequest {}))
+        .await
+        .unwrap()
+        .into_inner();
+
+    // The stream opens with current positions, then reports changes
+    let initial = timeout(STREAM_TIMEOUT, stream.next()).await.unwrap().unwrap().unwrap();
+    assert_eq!(initial.position.unwrap().size, "2");
+
+    server
+        .portfolio
+        .add_position(TEST_TRADING_PAIR.to_string(), dec!(1), dec!(23))
+        .await
+        .unwrap();
+    let update = timeout(STREAM_TIMEOUT, stream.next()).await.unwrap().unwrap().unwrap();
+    assert!(!update.closed);
+    assert_eq!(update.position.unwrap().size, "3");
+}
+
+#[tokio::test]
+async fn test_requests_without_valid_token_are_rejected() {
+    let server = TestServer::start().await;
+    let channel = server.channel().await;
+
+    let status = OrderServiceClient::new(channel.clone())
+        .submit_order(Request::new(submit_request("1")))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    let mut forged = Request::new(PortfolioRequest {});
+    forged
+        .metadata_mut()
+        .insert("authorization", "Bearer not-a-jwt".parse().unwrap());
+    let status = PortfolioServiceClient::new(channel.clone())
+        .get_snapshot(forged)
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    let status = MarketDataStreamClient::new(channel)
+        .subscribe(Request::new(MarketDataRequest::default()))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    assert!(server.gateway.submitted.lock().is_empty());
+}
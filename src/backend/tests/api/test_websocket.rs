@@ -8,9 +8,10 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 use tokio_tungstenite::{
     connect_async,
+    tungstenite::client::IntoClientRequest,
     tungstenite::protocol::Message,
     WebSocketStream,
 };
@@ -43,8 +44,9 @@ struct Subscription {
 impl TestClient {
     /// Creates new test client with enhanced capabilities
     async fn new(server_addr: SocketAddr, auth_token: String) -> Result<Self, Box<dyn std::error::Error>> {
-        let url = format!("ws://{}/ws", server_addr);
-        let (ws_stream, _) = connect_async(url).await?;
+        let mut request = format!("ws://{}/ws", server_addr).into_client_request()?;
+        request.headers_mut().insert("authorization", auth_token.parse()?);
+        let (ws_stream, _) = connect_async(request).await?;
 
         Ok(Self {
             ws_stream,
@@ -98,26 +100,24 @@ impl TestClient {
     }
 }
 
-/// Sets up test server with mock metrics
-async fn setup_test_server() -> Result<(Arc<WebSocketServer>, SocketAddr), Box<dyn std::error::Error>> {
+/// Sets up test server with mock metrics on an ephemeral port; dropping the returned
+/// sender shuts the server down
+async fn setup_test_server(
+) -> Result<(Arc<WebSocketServer>, SocketAddr, watch::Sender<bool>), Box<dyn std::error::Error>> {
     let metrics = Arc::new(metrics::Metrics::new());
     let server = Arc::new(WebSocketServer::new(metrics.clone()));
-    
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
     // Bind to random port
     let addr: SocketAddr = "127.0.0.1:0".parse()?;
-    let server_clone = server.clone();
-    
-    tokio::spawn(async move {
-        server_clone.start(addr).await.expect("Failed to start server");
-    });
+    let (_handle, bound) = server.clone().start(addr, shutdown_rx).await?;
 
-    tokio::time::sleep(Duration::from_millis(100)).await;
-    Ok((server, addr))
+    Ok((server, bound, shutdown_tx))
 }
 
 #[tokio::test]
 async fn test_websocket_connection() -> Result<(), Box<dyn std::error::Error>> {
-    let (server, addr) = setup_test_server().await?;
+    let (server, addr, _shutdown) = setup_test_server().await?;
     let client = TestClient::new(addr, TEST_AUTH_TOKEN.to_string()).await?;
     
     assert!(!client.subscriptions.is_empty(), "Client should connect successfully");
@@ -126,7 +126,7 @@ async fn test_websocket_connection() -> Result<(), Box<dyn std::error::Error>> {
 
 #[tokio::test]
 async fn test_market_data_streaming() -> Result<(), Box<dyn std::error::Error>> {
-    let (server, addr) = setup_test_server().await?;
+    let (server, addr, _shutdown) = setup_test_server().await?;
     let mut client = TestClient::new(addr, TEST_AUTH_TOKEN.to_string()).await?;
 
     // Subscribe to test market
@@ -170,7 +170,7 @@ async fn test_market_data_streaming() -> Result<(), Box<dyn std::error::Error>>
 
 #[tokio::test]
 async fn test_performance_metrics() -> Result<(), Box<dyn std::error::Error>> {
-    let (server, addr) = setup_test_server().await?;
+    let (server, addr, _shutdown) = setup_test_server().await?;
     let mut client = TestClient::new(addr, TEST_AUTH_TOKEN.to_string()).await?;
 
     // Subscribe and generate load
@@ -194,7 +194,7 @@ async fn test_performance_metrics() -> Result<(), Box<dyn std::error::Error>> {
 
 #[tokio::test]
 async fn test_connection_management() -> Result<(), Box<dyn std::error::Error>> {
-    let (server, addr) = setup_test_server().await?;
+    let (server, addr, _shutdown) = setup_test_server().await?;
     
     // Test connection limits
     let mut clients = vec![];
@@ -221,7 +221,7 @@ async fn test_connection_management() -> Result<(), Box<dyn std::error::Error>>
 
 #[tokio::test]
 async fn test_error_handling() -> Result<(), Box<dyn std::error::Error>> {
-    let (server, addr) = setup_test_server().await?;
+    let (server, addr, _shutdown) = setup_test_server().await?;
     let mut client = TestClient::new(addr, TEST_AUTH_TOKEN.to_string()).await?;
 
     // Test invalid subscription
@@ -245,4 +245,28 @@ async fn test_error_handling() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_graceful_shutdown_sends_close_frame() -> Result<(), Box<dyn std::error::Error>> {
+    let (_server, addr, shutdown) = setup_test_server().await?;
+    let mut client = TestClient::new(addr, TEST_AUTH_TOKEN.to_string()).await?;
+
+    shutdown.send(true)?;
+
+    let frame = tokio::time::timeout(Duration::from_millis(TEST_TIMEOUT_MS), async {
+        loop {
+            match client.ws_stream.next().await {
+                Some(Ok(Message::Ping(_))) => continue,
+                other => return other,
+            }
+        }
+    })
+    .await?;
+
+    match frame {
+        Some(Ok(Message::Close(Some(close)))) => assert_eq!(u16::from(close.code), 1001),
+        other => panic!("expected close frame, got {:?}", other),
+    }
+    Ok(())
+}
@@ -15,11 +15,15 @@ use rust_decimal_macros::dec;
 use tokio::sync::RwLock;
 use tokio::time::sleep;
 
+use crate::execution_engine::book_sync::{
+    BookHealth, BookSequence, BookSnapshot, BookSnapshotSource, BookSyncConfig,
+};
 use crate::execution_engine::order_book::{
-    LiveOrderBook, OrderBookError, ExecutionPlan, ExecutionRoute,
+    Config, LiveOrderBook, OrderBookError, ExecutionPlan, ExecutionRoute,
 };
 use crate::models::order::{Order, OrderType, OrderStatus};
 use crate::models::market::{OrderBook, OrderBookLevel, MarketData};
+use crate::risk_manager::exposure::TradeSide;
 use crate::utils::solana::SolanaClient;
 use crate::utils::time::current_timestamp;
 
@@ -144,7 +148,7 @@ mod tests {
         // Test order matching latency
         for order in &env.test_orders {
             let start = Instant::now();
-            let result = env.order_book.get_best_execution(order).await;
+            let result = env.order_book.get_best_execution(order, TradeSide::Buy).await;
             let duration = start.elapsed();
 
             assert!(result.is_ok());
@@ -202,7 +206,7 @@ mod tests {
 
         // Test execution routing for different order types
         for order in &env.test_orders {
-            let execution_plan = env.order_book.get_best_execution(order).await.unwrap();
+            let execution_plan = env.order_book.get_best_execution(order, TradeSide::Buy).await.unwrap();
             
             assert!(execution_plan.route.steps.len() > 0);
             assert!(execution_plan.route.estimated_execution_time <= Duration::from_millis(500));
@@ -239,11 +243,83 @@ mod tests {
                 OrderType::Market,
                 dec!(23.50),
                 dec!(100.00),
-            ).unwrap()
+            ).unwrap(),
+            TradeSide::Buy,
         ).await;
 
         assert!(matches!(result, Err(OrderBookError::MarketError(_))));
     }
+
+    /// Venue snapshot endpoint that is down until `available` is set
+    #[derive(Debug)]
+    struct MockSnapshotSource {
+        available: std::sync::atomic::AtomicBool,
+        sequence: u64,
+    }
+
+    #[async_trait::async_trait]
+    impl BookSnapshotSource for MockSnapshotSource {
+        async fn fetch_snapshot(&self, _trading_pair: &str) -> Result<BookSnapshot, OrderBookError> {
+            if !self.available.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(OrderBookError::UpdateError("snapshot endpoint unavailable".to_string()));
+            }
+            Ok(BookSnapshot { book: generate_test_order_book(), sequence: self.sequence })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dropped_sequence_degrade_resync_recover() {
+        let env = setup_test_environment().await;
+        let config = Config {
+            sync: BookSyncConfig { resync_backoff: Duration::ZERO, ..BookSyncConfig::default() },
+            ..Config::default()
+        };
+        let order_book = LiveOrderBook::new(env.solana_client.clone(), config);
+        let source = Arc::new(MockSnapshotSource {
+            available: std::sync::atomic::AtomicBool::new(false),
+            sequence: 10,
+        });
+        order_book.set_snapshot_source("jupiter", source.clone());
+        let update = |sequence| BookSequence { sequence, checksum: None };
+        let order = &env.test_orders[0];
+
+        order_book
+            .apply_sequenced_update(TEST_TRADING_PAIR.to_string(), generate_test_order_book(), update(1))
+            .await
+            .unwrap();
+        assert_eq!(order_book.book_health(TEST_TRADING_PAIR), BookHealth::Healthy);
+        sleep(Duration::from_millis(110)).await;
+
+        // Sequence 2 is dropped; with the snapshot endpoint down the book stays degraded
+        let result = order_book
+            .apply_sequenced_update(TEST_TRADING_PAIR.to_string(), generate_test_order_book(), update(3))
+            .await;
+        assert!(matches!(result, Err(OrderBookError::SequenceGap { expected: 2, received: 3 })));
+        assert_eq!(order_book.book_health(TEST_TRADING_PAIR), BookHealth::Degraded);
+        let result = order_book.get_best_execution(order, TradeSide::Buy).await;
+        assert!(matches!(result, Err(OrderBookError::DegradedBook(_))));
+
+        // The next update retries the resync, which re-anchors the stream on the snapshot
+        source.available.store(true, std::sync::atomic::Ordering::SeqCst);
+        let result = order_book
+            .apply_sequenced_update(TEST_TRADING_PAIR.to_string(), generate_test_order_book(), update(4))
+            .await;
+        assert!(result.is_ok());
+        assert_eq!(order_book.book_health(TEST_TRADING_PAIR), BookHealth::Healthy);
+        let plan = order_book.get_best_execution(order, TradeSide::Buy).await.unwrap();
+        assert_eq!(plan.book_health, BookHealth::Healthy);
+
+        // Updates already covered by the snapshot are dropped, the following one applies
+        let result = order_book
+            .apply_sequenced_update(TEST_TRADING_PAIR.to_string(), generate_test_order_book(), update(10))
+            .await;
+        assert!(matches!(result, Err(OrderBookError::UpdateError(_))));
+        sleep(Duration::from_millis(110)).await;
+        order_book
+            .apply_sequenced_update(TEST_TRADING_PAIR.to_string(), generate_test_order_book(), update(11))
+            .await
+            .unwrap();
+    }
 }
 
 #[cfg(test)]
@@ -266,7 +342,7 @@ mod benchmarks {
                     ).await.unwrap();
 
                     for order in &env.test_orders {
-                        env.order_book.get_best_execution(order).await.unwrap();
+                        env.order_book.get_best_execution(order, TradeSide::Buy).await.unwrap();
                     }
                 })
             })
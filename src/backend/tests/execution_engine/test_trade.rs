@@ -72,6 +72,7 @@ async fn test_execute_trade_success() -> Result<(), ExecutionError> {
         price: dec!(23.50),
         size: dec!(1.5),
         slippage: dec!(0.001),
+        guarded: None,
     };
 
     // Record start time for latency validation
@@ -106,6 +107,7 @@ async fn test_mev_optimization() -> Result<(), ExecutionError> {
         price: dec!(23.50),
         size: dec!(100.0), // Large trade to trigger MEV optimization
         slippage: dec!(0.001),
+        guarded: None,
     };
 
     // Execute trade with MEV optimization
@@ -137,6 +139,7 @@ async fn test_multi_dex_execution() -> Result<(), ExecutionError> {
             price: dec!(23.50),
             size: dec!(1.0),
             slippage: dec!(0.001),
+            guarded: None,
         },
         TradeParams {
             id: "pump_fun_trade".to_string(),
@@ -146,6 +149,7 @@ async fn test_multi_dex_execution() -> Result<(), ExecutionError> {
             price: dec!(23.51),
             size: dec!(1.0),
             slippage: dec!(0.001),
+            guarded: None,
         },
         TradeParams {
             id: "drift_trade".to_string(),
@@ -155,6 +159,7 @@ async fn test_multi_dex_execution() -> Result<(), ExecutionError> {
             price: dec!(23.49),
             size: dec!(1.0),
             slippage: dec!(0.001),
+            guarded: None,
         },
     ];
 
@@ -192,6 +197,7 @@ async fn test_trade_validation() -> Result<(), ExecutionError> {
             price: dec!(0),
             size: dec!(1.0),
             slippage: dec!(0.001),
+            guarded: None,
         },
         // Invalid size
         TradeParams {
@@ -202,6 +208,7 @@ async fn test_trade_validation() -> Result<(), ExecutionError> {
             price: dec!(23.50),
             size: dec!(0),
             slippage: dec!(0.001),
+            guarded: None,
         },
         // Excessive slippage
         TradeParams {
@@ -212,6 +219,7 @@ async fn test_trade_validation() -> Result<(), ExecutionError> {
             price: dec!(23.50),
             size: dec!(1.0),
             slippage: dec!(0.1),
+            guarded: None,
         },
     ];
 
@@ -237,6 +245,7 @@ async fn test_error_handling() -> Result<(), ExecutionError> {
         price: dec!(23.50),
         size: dec!(1.0),
         slippage: dec!(0.001),
+        guarded: None,
     };
 
     // Simulate network failure
@@ -0,0 +1,57 @@
+use serde_json::json;
+use wiremock::matchers::{body_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::admin_cli::{parse_args, run, CliError, Invocation, EXIT_FAILURE, TOKEN_ENV};
+
+// Test constants
+const ADMIN_TOKEN: &str = "test-admin-token";
+
+fn invocation(server: &MockServer, args: &[&str]) -> Invocation {
+    std::env::set_var(TOKEN_ENV, ADMIN_TOKEN);
+    let uri = server.uri();
+    let args = args.iter().chain(&["--url", uri.as_str()]).map(|arg| arg.to_string());
+    parse_args(args).unwrap()
+}
+
+#[tokio::test]
+async fn test_quarantine_remove_sends_authenticated_release() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/admin/risk/quarantine/BONK-USDC"))
+        .and(header("authorization", format!("Bearer {}", ADMIN_TOKEN).as_str()))
+        .and(body_json(json!({ "reason": "feed repaired" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "trading_pair": "BONK/USDC",
+            "released_by": "ops",
+            "reason": "feed repaired",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let invocation = invocation(&server, &["quarantine", "remove", "bonk-usdc", "--reason", "feed repaired"]);
+    let output = run(&invocation).await.unwrap();
+
+    assert!(output.contains("released_by   ops"));
+    assert!(output.contains("reason        feed repaired"));
+}
+
+#[tokio::test]
+async fn test_api_error_maps_to_failure_exit_code() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/strategies/grid-9/resume"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "error": "strategy not found: grid-9",
+            "status": 404,
+        })))
+        .mount(&server)
+        .await;
+
+    let error = run(&invocation(&server, &["strategies", "resume", "grid-9"])).await.unwrap_err();
+
+    assert!(matches!(error, CliError::Api { status: 404, .. }));
+    assert_eq!(error.to_string(), "API returned 404: strategy not found: grid-9");
+    assert_eq!(error.exit_code(), EXIT_FAILURE);
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use rust_decimal_macros::dec;
+use tokio::sync::broadcast;
+use tokio::time::{timeout, Duration, Instant};
+
+use crate::{
+    api::{
+        bridge::{BridgeConfig, BridgeEvent, EventBridge},
+        websocket::WebSocketServer,
+    },
+    execution_engine::TradeEvent,
+    risk_manager::exposure::TradeSide,
+    utils::percent::Bps,
+};
+
+// Test constants
+const DEFAULT_REDIS_URL: &str = "redis://127.0.0.1:6379";
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn redis_client() -> Arc<redis::Client> {
+    let url = std::env::var("REDIS_URL").unwrap_or_else(|_| DEFAULT_REDIS_URL.to_string());
+    Arc::new(redis::Client::open(url).unwrap())
+}
+
+/// Both instances share a Redis server but get a per-test channel prefix
+fn bridge(instance_id: &str, channel_prefix: &str) -> Arc<EventBridge> {
+    Arc::new(EventBridge::new(
+        BridgeConfig {
+            instance_id: instance_id.to_string(),
+            channel_prefix: channel_prefix.to_string(),
+            ..BridgeConfig::default()
+        },
+        redis_client(),
+    ))
+}
+
+fn trade() -> TradeEvent {
+    TradeEvent {
+        trade_id: "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW".to_string(),
+        strategy_id: "grid-1".to_string(),
+        trading_pair: "SOL/USDC".to_string(),
+        exchange: "jupiter".to_string(),
+        side: TradeSide::Buy,
+        size: dec!(2.5),
+        price: dec!(23.45),
+        executed_at: Utc::now(),
+        configured_slippage_bps: Some(Bps::new(50)),
+        realized_slippage_bps: Some(Bps::new(12)),
+        size_multiplier: None,
+    }
+}
+
+#[tokio::test]
+async fn test_trade_on_instance_a_reaches_ws_client_on_instance_b() {
+    let channel_prefix = format!("firebot:test:{}", uuid::Uuid::new_v4());
+
+    // Instance A trades and publishes its executions
+    let (trades_tx, _) = broadcast::channel(16);
+    let bridge_a = bridge("instance-a", &channel_prefix);
+    let publisher = bridge_a.clone().spawn_publisher(trades_tx.subscribe(), BridgeEvent::Trade);
+
+    // Instance B serves WebSocket clients from the bridge
+    let server_b = Arc::new(WebSocketServer::new(Arc::new(metrics::Metrics::new())));
+    let (_client_id, mut client_rx) = server_b.subscribe_local("trades");
+    let bridge_b = bridge("instance-b", &channel_prefix);
+    let subscriber = bridge_b.clone().spawn_subscriber(server_b.clone());
+
+    // Keep executing until B's subscription is live; pub/sub drops messages sent before it
+    let sent = trade();
+    let deadline = Instant::now() + DELIVERY_TIMEOUT;
+    let frame = loop {
+        assert!(Instant::now() < deadline, "trade never reached instance B");
+        trades_tx.send(sent.clone()).unwrap();
+        if let Ok(Some(frame)) = timeout(Duration::from_millis(200), client_rx.recv()).await {
+            break frame;
+        }
+    };
+
+    let frame: serde_json::Value = serde_json::from_str(frame.to_str().unwrap()).unwrap();
+    assert_eq!(frame["channel"], "trades");
+    assert_eq!(frame["data"]["trade_id"], sent.trade_id);
+    assert_eq!(frame["data"]["size"], "2.5");
+
+    publisher.abort();
+    subscriber.abort();
+}
+
+#[tokio::test]
+async fn test_instance_does_not_redeliver_its_own_events() {
+    let channel_prefix = format!("firebot:test:{}", uuid::Uuid::new_v4());
+
+    // A standby that both publishes and subscribes must not echo its own trades
+    let server = Arc::new(WebSocketServer::new(Arc::new(metrics::Metrics::new())));
+    let (_client_id, mut client_rx) = server.subscribe_local("trades");
+    let bridge = bridge("standby", &channel_prefix);
+    let subscriber = bridge.clone().spawn_subscriber(server.clone());
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    for _ in 0..5 {
+        bridge.publish(BridgeEvent::Trade(trade())).await.unwrap();
+    }
+    assert!(timeout(Duration::from_millis(500), client_rx.recv()).await.is_err());
+
+    subscriber.abort();
+}
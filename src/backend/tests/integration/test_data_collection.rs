@@ -194,6 +194,7 @@ async fn setup_test_environment() -> Result<TestContext, Box<dyn std::error::Err
         connection_pool_size: CONCURRENT_COLLECTORS,
         max_reconnect_attempts: MAX_RECONNECT_ATTEMPTS,
         validation_timeout: Duration::from_millis(MAX_LATENCY_MS),
+        ..CollectorConfig::default()
     };
 
     // Create market data channel
@@ -0,0 +1,193 @@
+#![cfg(feature = "fault-injection")]
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use tokio::sync::Mutex;
+use tokio::time::{Duration, Instant};
+
+use crate::{
+    execution_engine::fills::FillTracker,
+    fault_injection::{self, FaultComponent, FaultEffect, FaultSpec},
+    models::{
+        order::{Order, OrderFill, OrderType},
+        portfolio::Portfolio,
+    },
+    risk_manager::{exposure::TradeSide, validation::TradeRequest, RiskConfig, RiskError, RiskManager},
+};
+
+// Test constants
+const RPC_ERROR_PROBABILITY: f64 = 0.2;
+const SEED: u64 = 1855;
+const STRATEGIES: usize = 4;
+const ORDERS_PER_STRATEGY: usize = 50;
+const MAX_SUBMIT_ATTEMPTS: usize = 20;
+
+// Faults live in a process-wide registry, so scenarios must not overlap
+static SERIAL: Mutex<()> = Mutex::const_new(());
+
+fn rpc_error_fault() -> FaultSpec {
+    FaultSpec {
+        component: FaultComponent::SolanaRpc,
+        effect: FaultEffect::Error,
+        probability: RPC_ERROR_PROBABILITY,
+        duration_secs: 60,
+    }
+}
+
+/// Submits through the RPC fault hook and books a full fill on success
+async fn submit(
+    tracker: &FillTracker,
+    pair: &str,
+    side: TradeSide,
+    size: Decimal,
+    price: Decimal,
+    fill_id: String,
+) -> bool {
+    let order = Order::new(pair.to_string(), "jupiter".to_string(), OrderType::Market, price, size).unwrap();
+    let order_id = order.id;
+    tracker.track(order, "chaos", side).await;
+
+    if fault_injection::on_call(FaultComponent::SolanaRpc).await.is_err() {
+        tracker.cancel_remaining(order_id).await.unwrap();
+        return false;
+    }
+    tracker
+        .apply_fill(order_id, OrderFill::new(fill_id, size, price))
+        .await
+        .unwrap();
+    true
+}
+
+#[tokio::test]
+async fn test_trading_under_rpc_faults_keeps_portfolio_consistent() {
+    let _serial = SERIAL.lock().await;
+    fault_injection::registry().clear();
+    fault_injection::registry().reseed(SEED);
+    fault_injection::inject(rpc_error_fault()).unwrap();
+
+    let portfolio = Portfolio::new("chaos_wallet".to_string(), dec!(1000000)).unwrap();
+    let tracker = Arc::new(FillTracker::new(portfolio.clone()));
+
+    // Each strategy buys its own pair at a fixed price so expectations are exact
+    let handles: Vec<_> = (0..STRATEGIES)
+        .map(|strategy| {
+            let tracker = tracker.clone();
+            tokio::spawn(async move {
+                let pair = format!("PAIR{}/USDC", strategy);
+                let price = Decimal::from(10 + strategy as i64);
+                let mut filled = Decimal::ZERO;
+                let mut failed = 0;
+                for i in 0..ORDERS_PER_STRATEGY {
+                    let fill_id = format!("{}-buy-{}", pair, i);
+                    if submit(&tracker, &pair, TradeSide::Buy, dec!(1), price, fill_id).await {
+                        filled += dec!(1);
+                    } else {
+                        failed += 1;
+                    }
+                }
+                (pair, price, filled, failed)
+            })
+        })
+        .collect();
+
+    let mut expected = HashMap::new();
+    let mut total_failed = 0;
+    for handle in handles {
+        // A panic anywhere in the order path surfaces as a join error
+        let (pair, price, filled, failed) = handle.await.expect("scenario task panicked");
+        total_failed += failed;
+        expected.insert(pair, (price, filled));
+    }
+
+    let total_orders = STRATEGIES * ORDERS_PER_STRATEGY;
+    assert!(total_failed > 0, "no RPC faults fired");
+    assert!(
+        total_failed < total_orders / 2,
+        "{} of {} orders failed",
+        total_failed,
+        total_orders
+    );
+
+    // Positions reflect exactly the orders that reached the venue
+    for (pair, (price, filled)) in &expected {
+        let position = portfolio.get_position(pair).await.unwrap();
+        assert_eq!(position.size, *filled, "{}", pair);
+        assert_eq!(position.entry_price, *price, "{}", pair);
+    }
+
+    // Exit everything a point higher, retrying through the faults
+    let mut expected_pnl = Decimal::ZERO;
+    for (pair, (price, filled)) in &expected {
+        let exit_price = *price + dec!(1);
+        let mut attempts = 0;
+        while !submit(&tracker, pair, TradeSide::Sell, *filled, exit_price, format!("{}-exit-{}", pair, attempts)).await {
+            attempts += 1;
+            assert!(attempts < MAX_SUBMIT_ATTEMPTS, "exit for {} never succeeded", pair);
+        }
+        expected_pnl += *filled;
+        assert!(portfolio.get_position(pair).await.is_none());
+    }
+    assert_eq!(portfolio.get_realized_pnl().await, expected_pnl);
+
+    assert_eq!(fault_injection::registry().clear(), 1);
+}
+
+#[tokio::test]
+async fn test_injected_breaker_trip_and_latency() {
+    let _serial = SERIAL.lock().await;
+    fault_injection::registry().clear();
+
+    let manager = RiskManager::new(RiskConfig::default()).unwrap();
+    assert!(!manager.check_circuit_breaker());
+
+    fault_injection::inject(FaultSpec {
+        component: FaultComponent::CircuitBreaker,
+        effect: FaultEffect::Trip,
+        probability: 1.0,
+        duration_secs: 60,
+    })
+    .unwrap();
+    assert!(manager.check_circuit_breaker());
+
+    // The tripped breaker rejects trades before any other check runs
+    let result = manager
+        .simulate_operation(TradeRequest {
+            strategy_id: "chaos".to_string(),
+            wallet_address: "chaos_wallet".to_string(),
+            trading_pair: "SOL/USDC".to_string(),
+            exchange: "jupiter".to_string(),
+            side: TradeSide::Buy,
+            order_type: OrderType::Market,
+            size: dec!(1),
+            price: dec!(23.45),
+            market_prices: HashMap::new(),
+            cross_dex_prices: HashMap::new(),
+            market_impact: HashMap::new(),
+        })
+        .await;
+    assert!(matches!(result, Err(RiskError::CircuitBreaker(_))));
+
+    // Latency faults delay calls without failing them
+    fault_injection::inject(FaultSpec {
+        component: FaultComponent::Database,
+        effect: FaultEffect::Latency { latency_ms: 50 },
+        probability: 1.0,
+        duration_secs: 60,
+    })
+    .unwrap();
+    let start = Instant::now();
+    assert!(fault_injection::on_call(FaultComponent::Database).await.is_ok());
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    let active = fault_injection::registry().active(Utc::now());
+    assert_eq!(active.len(), 2);
+
+    // Clearing restores normal behaviour
+    assert_eq!(fault_injection::registry().clear(), 2);
+    assert!(!manager.check_circuit_breaker());
+    assert!(fault_injection::registry().active(Utc::now()).is_empty());
+}
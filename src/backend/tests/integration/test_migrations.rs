@@ -0,0 +1,426 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use rust_decimal_macros::dec;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    data_collector::{
+        ingest::{IngestConfig, IngestFormat, MarketDataIngestor},
+        ohlcv::{Candle, CandleInterval},
+    },
+    db::{
+        ensure_schema_current,
+        models::{MarketDataRecord, PortfolioRecord, PositionRecord, StrategyRecord},
+        pending_migrations,
+        repositories::{
+            CandleRepository, ExecutionStatsRepository, KeyRotationRepository, MarketDataRepository,
+            PerformanceRepository, PositionCloseRepository, RetentionOverrideRepository, RiskSnapshotRepository,
+            SnapshotRepository, StrategyAuditRepository, TransferRepository, WebhookRepository,
+        },
+        MIGRATOR,
+    },
+    execution_engine::{
+        benchmarks::ExecutionBenchmark,
+        stats::{ExecutionRecord, ExecutionStatsStore},
+    },
+    models::{
+        portfolio::PositionClose,
+        strategy::StrategyAuditEntry,
+        transfer::{Transfer, TransferDirection},
+        webhook::{Webhook, WebhookAuditEntry, WebhookEventType},
+    },
+    performance::{PerformanceStore, StrategyTrade},
+    retention::{RetentionConfig, RetentionManager, RetentionOverrideRequest},
+    risk_manager::{
+        exposure::TradeSide,
+        factors::{RiskFactor, RiskFactorSnapshot, RiskSnapshotStore},
+    },
+    state_snapshot::{SnapshotArtifact, SnapshotStore, SnapshotTrigger},
+    utils::{
+        crypto::{data_keys, EncryptedData},
+        metrics::MetricsCollector,
+    },
+};
+
+// Test constants
+const WALLET: &str = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+const KMS_KEY_ID: &str = "test-key";
+
+/// Starts from an empty database and brings it up purely through migrations
+async fn migrated(pool: &PgPool) {
+    let pending = pending_migrations(pool).await.unwrap();
+    assert_eq!(pending.len(), MIGRATOR.iter().count());
+
+    // Without --auto-migrate startup refuses to run against the empty schema
+    assert!(ensure_schema_current(pool, false).await.is_err());
+    ensure_schema_current(pool, true).await.unwrap();
+    assert!(pending_migrations(pool).await.unwrap().is_empty());
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_empty_database_boots_through_migrations(pool: PgPool) {
+    migrated(&pool).await;
+
+    // Already current: the check passes without migrating
+    ensure_schema_current(&pool, false).await.unwrap();
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_core_tables_match_records(pool: PgPool) {
+    migrated(&pool).await;
+
+    let portfolio = sqlx::query_as::<_, PortfolioRecord>(
+        "INSERT INTO portfolios (wallet_address, balance) VALUES ($1, $2)
+         RETURNING id, wallet_address, balance, risk_params, created_at, updated_at",
+    )
+    .bind(WALLET)
+    .bind(dec!(1000))
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(portfolio.balance, dec!(1000));
+
+    let position = sqlx::query_as::<_, PositionRecord>(
+        "INSERT INTO positions (portfolio_id, trading_pair, size, entry_price, current_price, unrealized_pnl, pnl, status)
+         VALUES ($1, 'SOL/USDC', 2, 20, 21, 2, 2, 'OPEN')
+         RETURNING id, portfolio_id, trading_pair, size, entry_price, current_price, pnl, status,
+                   created_at, updated_at, closed_at",
+    )
+    .bind(portfolio.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(position.pnl, dec!(2));
+
+    let strategy = sqlx::query_as::<_, StrategyRecord>(
+        "INSERT INTO strategies (name, strategy_type, parameters, trading_pairs)
+         VALUES ('grid-sol', 'grid', '{\"risk_level\": 1, \"max_position_size\": 5}', ARRAY['SOL/USDC'])
+         RETURNING id, name, parameters, performance_score, win_rate, created_at, updated_at",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(strategy.win_rate, 0.0);
+
+    // Strategy names are unique and positions go with their portfolio
+    let duplicate = sqlx::query(
+        "INSERT INTO strategies (name, strategy_type, parameters, trading_pairs)
+         VALUES ('grid-sol', 'grid', '{\"risk_level\": 1, \"max_position_size\": 5}', ARRAY['SOL/USDC'])",
+    )
+    .execute(&pool)
+    .await;
+    assert!(duplicate.is_err());
+
+    sqlx::query("DELETE FROM portfolios WHERE id = $1")
+        .bind(portfolio.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM positions")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 0);
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_repositories_against_migrated_schema(pool: PgPool) {
+    migrated(&pool).await;
+    let now = Utc::now();
+
+    // market_data
+    let market_data = MarketDataRepository::new(pool.clone(), MetricsCollector::new().unwrap());
+    let record = MarketDataRecord::new("SOL/USDC".into(), "jupiter".into(), dec!(23.45), dec!(1000), now).unwrap();
+    market_data.save_market_data(vec![record]).await.unwrap();
+    assert_eq!(market_data.get_market_data("SOL/USDC", 10).await.unwrap().len(), 1);
+
+    // candles
+    let candles = CandleRepository::new(pool.clone());
+    let open_time = now - Duration::minutes(5);
+    let candle = Candle::from_parts(
+        "SOL/USDC".into(),
+        CandleInterval::OneMinute,
+        open_time,
+        dec!(23),
+        dec!(24),
+        dec!(22),
+        dec!(23.5),
+        dec!(100),
+        4,
+    );
+    assert_eq!(candles.upsert_candles(&[candle]).await.unwrap(), 1);
+    let stored = candles
+        .get_candles("SOL/USDC", CandleInterval::OneMinute, open_time, now)
+        .await
+        .unwrap();
+    assert_eq!(stored.len(), 1);
+
+    // transfers
+    let transfers = TransferRepository::new(pool.clone());
+    let transfer = Transfer::new(WALLET.into(), TransferDirection::Deposit, dec!(50), None).unwrap();
+    assert!(transfers.record_transfer(&transfer).await.unwrap());
+    assert_eq!(transfers.get_transfers(WALLET, 10).await.unwrap().len(), 1);
+
+    // position_closes
+    PositionCloseRepository::new(pool.clone())
+        .record_close(&PositionClose {
+            id: Uuid::new_v4(),
+            trading_pair: "SOL/USDC".into(),
+            closed_size: dec!(1),
+            entry_price: dec!(20),
+            exit_price: dec!(22),
+            realized_pnl: dec!(2),
+            remaining_size: dec!(0),
+            closed_at: now,
+        })
+        .await
+        .unwrap();
+
+    // webhooks and webhook_audit_log; secrets are sealed with a local data key
+    let keys = data_keys();
+    keys.insert(1, vec![7; 32]).unwrap();
+    keys.set_active(1).unwrap();
+    let webhooks = WebhookRepository::new(pool.clone(), KMS_KEY_ID.into());
+    let webhook = Webhook::new(
+        "https://hooks.example.com/bot".into(),
+        "0123456789abcdef0123456789abcdef".into(),
+        vec![WebhookEventType::PositionClosed],
+    )
+    .unwrap();
+    webhooks.save_webhook(&webhook).await.unwrap();
+    webhooks
+        .record_audit(&WebhookAuditEntry::new(webhook.id, "created", String::new()))
+        .await
+        .unwrap();
+    let loaded = webhooks.get_webhooks().await.unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].secret, webhook.secret);
+
+    // strategy_audit_log
+    StrategyAuditRepository::new(pool.clone())
+        .record_audit(&StrategyAuditEntry::new("grid-sol", "paused", "drawdown".into()))
+        .await
+        .unwrap();
+
+    // encrypted_secrets
+    let key_rotation = KeyRotationRepository::new(pool.clone());
+    let sealed = EncryptedData {
+        ciphertext: vec![1, 2, 3],
+        nonce: vec![0; 12],
+        key_version: 1,
+    };
+    key_rotation.save_secret("wallet", "signer", &sealed).await.unwrap();
+    assert_eq!(key_rotation.load_secret("wallet", "signer").await.unwrap(), Some(sealed));
+
+    // trade_executions
+    let execution_stats = ExecutionStatsRepository::new(pool.clone());
+    let execution_id = Uuid::new_v4();
+    execution_stats
+        .record(&ExecutionRecord {
+            id: execution_id,
+            trading_pair: "SOL/USDC".into(),
+            exchange: "jupiter".into(),
+            side: TradeSide::Buy,
+            requested_size: dec!(1),
+            filled_size: dec!(1),
+            expected_price: dec!(23),
+            executed_price: Some(dec!(23.01)),
+            fee: dec!(0.01),
+            latency_ms: 120,
+            mev_value: dec!(0),
+            executed_at: now,
+            benchmark: None,
+        })
+        .await
+        .unwrap();
+    let benchmark = ExecutionBenchmark {
+        window_start: now - Duration::minutes(1),
+        window_end: now,
+        vwap: Some(dec!(23)),
+        twap: Some(dec!(22.99)),
+        vwap_deviation_bps: Some(dec!(4.35)),
+        twap_deviation_bps: Some(dec!(8.7)),
+        tick_count: 12,
+        coverage: dec!(1),
+        low_coverage: false,
+        computed_at: now,
+    };
+    execution_stats.record_benchmark(execution_id, &benchmark).await.unwrap();
+    let records = execution_stats.records_since(now - Duration::hours(1)).await.unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].benchmark.as_ref().map(|b| b.vwap_deviation_bps), Some(Some(dec!(4.35))));
+
+    // state_snapshots
+    let snapshots = SnapshotRepository::new(pool.clone());
+    let artifact = SnapshotArtifact {
+        id: Uuid::new_v4(),
+        format_version: 1,
+        checksum: "00".repeat(32),
+        payload: vec![0x1f, 0x8b],
+        trigger: SnapshotTrigger::Manual,
+        created_at: now,
+    };
+    snapshots.save(&artifact).await.unwrap();
+    assert!(snapshots.load(artifact.id).await.unwrap().is_some());
+
+    // strategy_trades and strategy_equity
+    let performance = PerformanceRepository::new(pool.clone());
+    performance
+        .record_trade(&StrategyTrade {
+            id: Uuid::new_v4(),
+            strategy_id: "grid-sol".into(),
+            trading_pair: "SOL/USDC".into(),
+            realized_pnl: dec!(2),
+            closed_at: now,
+        })
+        .await
+        .unwrap();
+    assert!(performance.latest_equity("grid-sol").await.unwrap().is_empty());
+
+    // risk_snapshots
+    let risk_snapshots = RiskSnapshotRepository::new(pool.clone());
+    let snapshot = RiskFactorSnapshot {
+        id: Uuid::new_v4(),
+        computed_at: now,
+        total_value: dec!(1000),
+        exposures: Default::default(),
+        net_exposure: dec!(0),
+        gross_exposure: dec!(0),
+        leverage: dec!(0),
+        drawdown: dec!(0),
+        value_at_risk: RiskFactor::missing("insufficient volatility or correlation history for SOL"),
+        margin_utilization: RiskFactor::known(dec!(0.25)),
+        breakers: Vec::new(),
+        circuit_breaker_tripped: false,
+    };
+    risk_snapshots.record(&snapshot).await.unwrap();
+    assert_eq!(risk_snapshots.recent(10).await.unwrap(), vec![snapshot]);
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_retention_overrides_prune_by_most_specific_rule(pool: PgPool) {
+    migrated(&pool).await;
+    let now = Utc::now();
+
+    let market_data = MarketDataRepository::new(pool.clone(), MetricsCollector::new().unwrap());
+    let rows = [
+        ("SOL/USDC", "jupiter", 200),
+        ("SOL/USDC", "jupiter", 400),
+        ("SOL/USDC", "drift", 45),
+        ("SOL/USDC", "drift", 20),
+        ("RAY/USDC", "jupiter", 100),
+        ("RAY/USDC", "jupiter", 80),
+        ("JUNK/USDC", "jupiter", 10),
+        ("JUNK/USDC", "jupiter", 5),
+    ];
+    let records = rows
+        .iter()
+        .map(|(pair, exchange, age)| {
+            MarketDataRecord::new(
+                pair.to_string(),
+                exchange.to_string(),
+                dec!(1),
+                dec!(1),
+                now - Duration::days(*age),
+            )
+            .unwrap()
+        })
+        .collect();
+    market_data.save_market_data(records).await.unwrap();
+
+    let manager = RetentionManager::new(RetentionConfig::default());
+    manager.set_store(Arc::new(RetentionOverrideRepository::new(pool.clone())));
+    for (pair, exchange, days) in [("SOL/USDC", None, 365), ("SOL/USDC", Some("Drift"), 30), ("JUNK/USDC", None, 7)] {
+        let request = RetentionOverrideRequest {
+            trading_pair: pair.to_string(),
+            exchange: exchange.map(str::to_string),
+            days,
+        };
+        manager.set(request, "ops", now).await.unwrap();
+    }
+
+    // Overrides come back from the store; RAY/USDC has none and keeps the 90 day default
+    let restored = RetentionManager::new(RetentionConfig::default());
+    restored.set_store(Arc::new(RetentionOverrideRepository::new(pool.clone())));
+    assert_eq!(restored.load().await.unwrap(), 3);
+    assert_eq!(restored.run_once(&market_data, now).await.unwrap(), 4);
+
+    let mut surviving: Vec<(String, String, i64)> = sqlx::query_as(
+        "SELECT trading_pair, exchange, EXTRACT(DAY FROM $1 - timestamp)::BIGINT FROM market_data",
+    )
+    .bind(now)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    surviving.sort();
+    assert_eq!(
+        surviving,
+        vec![
+            ("JUNK/USDC".to_string(), "jupiter".to_string(), 5),
+            ("RAY/USDC".to_string(), "jupiter".to_string(), 80),
+            ("SOL/USDC".to_string(), "drift".to_string(), 20),
+            ("SOL/USDC".to_string(), "jupiter".to_string(), 200),
+        ]
+    );
+
+    // Lengthening retention keeps what is left; the pruned row does not come back
+    let request = RetentionOverrideRequest {
+        trading_pair: "RAY/USDC".into(),
+        exchange: None,
+        days: 365,
+    };
+    restored.set(request, "ops", now).await.unwrap();
+    assert_eq!(restored.run_once(&market_data, now).await.unwrap(), 0);
+    let ray: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM market_data WHERE trading_pair = 'RAY/USDC'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(ray, 1);
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_vendor_ingestion_skips_rows_already_stored(pool: PgPool) {
+    migrated(&pool).await;
+    let now = Utc::now();
+
+    // A live row at the same instant as a vendor row is kept apart from it
+    let market_data = Arc::new(MarketDataRepository::new(pool.clone(), MetricsCollector::new().unwrap()));
+    let live = MarketDataRecord::new("SOL/USDC".into(), "jupiter".into(), dec!(23.5), dec!(2), now).unwrap();
+    market_data.save_market_data(vec![live]).await.unwrap();
+
+    let mut lines = vec!["trading_pair,exchange,price,volume,timestamp".to_string()];
+    for i in 0..5 {
+        lines.push(format!("SOL/USDC,jupiter,23.{},1,{}", i, (now - Duration::minutes(i)).to_rfc3339()));
+    }
+    lines.push("SOL/USDC,jupiter,0,1,2023-01-01T00:00:00Z".to_string());
+    let body = || futures::stream::iter(vec![Ok::<_, std::io::Error>(lines.join("\n").into_bytes())]);
+
+    let ingestor = MarketDataIngestor::new(IngestConfig::default(), market_data);
+    let first = ingestor.ingest("kaiko", IngestFormat::Csv, body()).await.unwrap();
+    assert_eq!((first.accepted, first.rejected, first.duplicates), (5, 1, 0));
+    assert_eq!(first.rejections[0].line, 7);
+
+    let second = ingestor.ingest("kaiko", IngestFormat::Csv, body()).await.unwrap();
+    assert_eq!((second.accepted, second.rejected, second.duplicates), (0, 1, 5));
+
+    // Another vendor's copy of the same rows is stored under its own source
+    let other = ingestor.ingest("amberdata", IngestFormat::Csv, body()).await.unwrap();
+    assert_eq!(other.accepted, 5);
+
+    let mut sources: Vec<(String, i64)> =
+        sqlx::query_as("SELECT source, COUNT(*) FROM market_data GROUP BY source")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    sources.sort();
+    assert_eq!(
+        sources,
+        vec![
+            ("live".to_string(), 1),
+            ("vendor:amberdata".to_string(), 5),
+            ("vendor:kaiko".to_string(), 5),
+        ]
+    );
+}
@@ -0,0 +1,316 @@
+//! Integration tests for multi-leg execution through the execution engine: legs are gated
+//! and submitted by the engine, and a leg failing after another filled is rolled back by an
+//! offsetting order on the filled leg's venue.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::RwLock;
+
+use crate::{
+    execution_engine::{
+        adapters::{
+            DriftDirection, DriftOrderParams, ExchangeAdapter, GuardedOrder, OrderRequest, SlippageGuard, VenueOrder,
+        },
+        error::ExecutionError,
+        multi_leg::{
+            AtomicityPolicy, GroupStatus, LegStatus, MultiLegConfig, MultiLegExecutor, TradeGroup, TradeGroupResult,
+            TradeGroupStore, TradeLeg,
+        },
+        order_book::{Config as OrderBookConfig, LiveOrderBook},
+        queue::QueueConfig,
+        readiness::{EngineState, PriceActivity, PriceFeed, ReadinessConfig},
+        trade::{TradeParams, TradeResult, TradeSubmitter},
+        CircuitBreakerConfig, ExecutionEngine, CIRCUIT_BREAKER_THRESHOLD,
+    },
+    models::{
+        market::{OrderBook, OrderBookLevel},
+        order::OrderFill,
+        strategy::{Strategy, StrategyParams, StrategyState, StrategyType},
+    },
+    risk_manager::exposure::{ExposureBook, TradeSide},
+    utils::{
+        percent::{Bps, Percent},
+        solana::SolanaClient,
+    },
+};
+
+// Test constants
+const PAIR: &str = "SOL/USDC";
+const STRATEGY_ID: &str = "arb-1";
+
+/// Fills every trade except those on `failing_exchange`; bundles are rejected whole
+#[derive(Debug)]
+struct VenueSubmitter {
+    failing_exchange: &'static str,
+    bundles: Mutex<usize>,
+}
+
+#[async_trait]
+impl TradeSubmitter for VenueSubmitter {
+    async fn execute_trade(&self, params: TradeParams) -> Result<TradeResult, ExecutionError> {
+        if params.exchange == self.failing_exchange {
+            return Err(ExecutionError::LiquidityError("no depth".to_string()));
+        }
+        Ok(TradeResult {
+            transaction_hash: params.id.clone(),
+            execution_time: Duration::from_millis(20),
+            mev_value: 0.0,
+            fills: vec![OrderFill::new(params.id, params.size, params.price)],
+            compute: None,
+        })
+    }
+
+    async fn execute_bundle(&self, _params: Vec<TradeParams>) -> Result<Vec<TradeResult>, ExecutionError> {
+        *self.bundles.lock() += 1;
+        Err(ExecutionError::MevBundleError("bundle dropped".to_string()))
+    }
+}
+
+/// Prepares orders for one venue, with instructions only when the venue can be bundled
+#[derive(Debug)]
+struct VenueAdapter {
+    exchange: &'static str,
+    bundleable: bool,
+}
+
+#[async_trait]
+impl ExchangeAdapter for VenueAdapter {
+    fn exchange(&self) -> &str {
+        self.exchange
+    }
+
+    async fn prepare(&self, request: &OrderRequest) -> Result<GuardedOrder, ExecutionError> {
+        let instructions = if self.bundleable {
+            vec![Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![])]
+        } else {
+            Vec::new()
+        };
+        Ok(GuardedOrder {
+            guard: SlippageGuard {
+                max_slippage: request.max_slippage,
+                quoted_price: request.price,
+                limit_price: request.price,
+                min_out_amount: None,
+            },
+            order: VenueOrder::Drift(DriftOrderParams {
+                market_index: 0,
+                direction: DriftDirection::Long,
+                base_asset_amount: 1,
+                price: 1,
+                immediate_or_cancel: true,
+                reduce_only: false,
+            }),
+            compute_budget: None,
+            instructions,
+            account_rent_lamports: 0,
+        })
+    }
+}
+
+#[derive(Default)]
+struct RecordingStore {
+    recorded: Mutex<Vec<TradeGroupResult>>,
+}
+
+#[async_trait]
+impl TradeGroupStore for RecordingStore {
+    async fn record(&self, result: &TradeGroupResult) -> Result<(), ExecutionError> {
+        self.recorded.lock().push(result.clone());
+        Ok(())
+    }
+}
+
+struct FreshPrices;
+
+impl PriceFeed for FreshPrices {
+    fn price_activity(&self, _trading_pair: &str, _since: DateTime<Utc>) -> PriceActivity {
+        PriceActivity {
+            ticks: 10,
+            last_tick_at: Some(Utc::now()),
+        }
+    }
+}
+
+fn arbitrage_strategy() -> Strategy {
+    let mut strategy = Strategy::new(
+        StrategyType::Arbitrage,
+        StrategyParams {
+            position_size_bps: Bps::new(1000),
+            grid_levels: None,
+            stop_loss_pct: Percent::from_percent(dec!(-5)),
+            take_profit_pct: Percent::from_percent(dec!(0.5)),
+            max_slippage_bps: Bps::new(30),
+            exchanges: vec!["jupiter".to_string(), "drift".to_string()],
+            risk_factor: dec!(0.5),
+            allowed_regimes: Vec::new(),
+            market_making: None,
+        },
+        vec![PAIR.to_string()],
+    )
+    .unwrap();
+    strategy.state = StrategyState::Active;
+    strategy
+}
+
+/// Ready live engine with a SOL/USDC book, submitting through `submitter`
+async fn ready_engine(submitter: Arc<VenueSubmitter>) -> Arc<ExecutionEngine> {
+    let solana_client = Arc::new(
+        SolanaClient::new("http://localhost:8899".to_string(), None, None)
+            .await
+            .unwrap(),
+    );
+    let order_book = Arc::new(LiveOrderBook::new(solana_client, OrderBookConfig::default()));
+    let book = OrderBook::new(
+        PAIR.to_string(),
+        "jupiter".to_string(),
+        vec![OrderBookLevel::new(dec!(99), dec!(1000))],
+        vec![OrderBookLevel::new(dec!(101), dec!(1000))],
+    )
+    .unwrap();
+    order_book.update_book(PAIR.to_string(), book).await.unwrap();
+
+    let engine = ExecutionEngine::from_submitter(
+        submitter,
+        order_book,
+        CircuitBreakerConfig {
+            threshold: CIRCUIT_BREAKER_THRESHOLD,
+            cooldown: None,
+        },
+        QueueConfig::default(),
+    )
+    .with_readiness(ReadinessConfig {
+        min_ticks: 1,
+        ..ReadinessConfig::default()
+    });
+    engine.set_live_trading(true);
+    let readiness = engine.readiness();
+    readiness.set_price_feed(Arc::new(FreshPrices));
+    readiness.set_strategies(Arc::new(RwLock::new(HashMap::from([(
+        STRATEGY_ID.to_string(),
+        arbitrage_strategy(),
+    )]))));
+    assert_eq!(readiness.evaluate(Utc::now()).await, EngineState::Ready);
+    Arc::new(engine)
+}
+
+/// Executor submitting through the engine, with both venues' adapters
+fn executor(engine: Arc<ExecutionEngine>, bundleable: bool, store: Arc<RecordingStore>) -> MultiLegExecutor {
+    MultiLegExecutor::new(MultiLegConfig::default(), engine)
+        .with_adapter(Arc::new(VenueAdapter {
+            exchange: "jupiter",
+            bundleable,
+        }))
+        .with_adapter(Arc::new(VenueAdapter {
+            exchange: "drift",
+            bundleable,
+        }))
+        .with_store(store)
+}
+
+fn leg(exchange: &str, side: TradeSide, price: Decimal) -> TradeLeg {
+    TradeLeg {
+        exchange: exchange.to_string(),
+        order: OrderRequest {
+            trading_pair: PAIR.to_string(),
+            side,
+            size: dec!(1.5),
+            price,
+            max_slippage: Bps::new(30),
+        },
+    }
+}
+
+fn arbitrage(policy: AtomicityPolicy) -> TradeGroup {
+    TradeGroup::new(
+        STRATEGY_ID,
+        policy,
+        vec![
+            leg("jupiter", TradeSide::Buy, dec!(100)),
+            leg("drift", TradeSide::Sell, dec!(100.6)),
+        ],
+    )
+}
+
+#[tokio::test]
+async fn test_partial_fill_rolled_back_on_filled_venue() {
+    let submitter = Arc::new(VenueSubmitter {
+        failing_exchange: "drift",
+        bundles: Mutex::new(0),
+    });
+    let engine = ready_engine(submitter).await;
+    let mut trades = engine.subscribe_trades();
+    let store = Arc::new(RecordingStore::default());
+    let executor = executor(engine, false, store.clone());
+    let mut alerts = executor.subscribe();
+
+    let group = arbitrage(AtomicityPolicy::BestEffortWithHedge);
+    let result = executor.execute(&group, &ExposureBook::new(dec!(1000))).await.unwrap();
+    assert_eq!(result.status, GroupStatus::Hedged);
+    assert_eq!(result.legs[0].status, LegStatus::Filled);
+    assert_eq!(result.legs[1].status, LegStatus::Failed);
+    assert_eq!(result.hedges.len(), 1);
+    assert_eq!(result.hedges[0].exchange, "jupiter");
+    assert_eq!(result.hedges[0].side, TradeSide::Sell);
+    assert!(result.hedges[0].fill.is_some());
+
+    // The engine published the filled leg and its offset, leaving the venue flat
+    let buy = trades.try_recv().unwrap();
+    let sell = trades.try_recv().unwrap();
+    assert_eq!((buy.exchange.as_str(), buy.side, buy.size), ("jupiter", TradeSide::Buy, dec!(1.5)));
+    assert_eq!((sell.exchange.as_str(), sell.side, sell.size), ("jupiter", TradeSide::Sell, dec!(1.5)));
+    assert_eq!(buy.strategy_id, STRATEGY_ID);
+    assert!(trades.try_recv().is_err());
+
+    assert_eq!(alerts.try_recv().unwrap().correlation_id, group.correlation_id);
+    assert_eq!(store.recorded.lock()[0].hedges, result.hedges);
+}
+
+#[tokio::test]
+async fn test_dropped_bundle_fills_no_leg() {
+    let submitter = Arc::new(VenueSubmitter {
+        failing_exchange: "drift",
+        bundles: Mutex::new(0),
+    });
+    let engine = ready_engine(submitter.clone()).await;
+    let mut trades = engine.subscribe_trades();
+    let store = Arc::new(RecordingStore::default());
+    let executor = executor(engine, true, store.clone());
+
+    let result = executor
+        .execute(&arbitrage(AtomicityPolicy::AllOrNothing), &ExposureBook::new(dec!(1000)))
+        .await
+        .unwrap();
+    assert!(result.bundled);
+    assert_eq!(result.status, GroupStatus::Failed);
+    assert!(result.hedges.is_empty());
+    assert_eq!(*submitter.bundles.lock(), 1);
+    assert!(trades.try_recv().is_err());
+    assert_eq!(store.recorded.lock().len(), 1);
+}
+
+#[tokio::test]
+async fn test_legs_refused_while_live_trading_disabled() {
+    let submitter = Arc::new(VenueSubmitter {
+        failing_exchange: "none",
+        bundles: Mutex::new(0),
+    });
+    let engine = ready_engine(submitter).await;
+    engine.set_live_trading(false);
+    let executor = executor(engine, false, Arc::new(RecordingStore::default()));
+
+    let result = executor
+        .execute(&arbitrage(AtomicityPolicy::BestEffortWithHedge), &ExposureBook::new(dec!(1000)))
+        .await
+        .unwrap();
+    assert_eq!(result.status, GroupStatus::Failed);
+    assert_eq!(result.legs[1].status, LegStatus::Skipped);
+}
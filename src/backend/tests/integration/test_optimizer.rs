@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::{
+    data_collector::replay::ReplaySource,
+    models::strategy::{StrategyParams, StrategyType},
+    optimizer::{
+        backtest::BacktestMetrics, JobProgress, JobStatus, Objective, OptimizationRequest,
+        OptimizerConfig, OptimizerService, ParameterRange,
+    },
+    utils::percent::{Bps, Percent},
+};
+
+// Test constants
+const FIXTURE: &str = "tests/fixtures/replay_sol_usdc.ndjson";
+const JOB_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn optimizer(max_workers: usize) -> Arc<OptimizerService> {
+    Arc::new(OptimizerService::new(
+        OptimizerConfig {
+            max_workers,
+            ..OptimizerConfig::default()
+        },
+        ReplaySource::File(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(FIXTURE)),
+        None,
+    ))
+}
+
+fn range(start: Decimal, end: Decimal, step: Decimal) -> ParameterRange {
+    ParameterRange { start, end, step }
+}
+
+fn grid_request(parameter_space: BTreeMap<String, ParameterRange>) -> OptimizationRequest {
+    OptimizationRequest {
+        strategy_type: StrategyType::Grid,
+        base_parameters: StrategyParams {
+            position_size_bps: Bps::new(1000),
+            grid_levels: Some(10),
+            stop_loss_pct: Percent::from_percent(dec!(-5)),
+            take_profit_pct: Percent::from_percent(dec!(1)),
+            max_slippage_bps: Bps::new(100),
+            exchanges: vec!["jupiter".to_string()],
+            risk_factor: dec!(0.5),
+            allowed_regimes: Vec::new(),
+            market_making: None,
+        },
+        parameter_space,
+        objective: Objective::Roi,
+        max_drawdown_pct: None,
+        trading_pair: "SOL/USDC".to_string(),
+        from: None,
+        to: None,
+        seed: 42,
+    }
+}
+
+fn tiny_space() -> BTreeMap<String, ParameterRange> {
+    let mut space = BTreeMap::new();
+    space.insert("grid_levels".to_string(), range(dec!(10), dec!(20), dec!(10)));
+    space.insert("take_profit_pct".to_string(), range(dec!(1), dec!(2), dec!(1)));
+    space
+}
+
+async fn wait_for_finish(optimizer: &OptimizerService, progress: &JobProgress) -> JobProgress {
+    tokio::time::timeout(JOB_TIMEOUT, async {
+        loop {
+            let progress = optimizer.progress(progress.id).unwrap();
+            if progress.status != JobStatus::Running {
+                return progress;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("optimization job did not finish")
+}
+
+async fn ranked_runs(seed: u64) -> Vec<(Option<usize>, BTreeMap<String, Decimal>, Option<BacktestMetrics>)> {
+    let optimizer = optimizer(2);
+    let mut request = grid_request(tiny_space());
+    request.seed = seed;
+
+    let submitted = optimizer.submit(request).unwrap();
+    assert_eq!(submitted.total, 4);
+
+    let finished = wait_for_finish(&optimizer, &submitted).await;
+    assert_eq!(finished.status, JobStatus::Completed);
+    assert_eq!(finished.completed, 4);
+
+    let (_, runs) = optimizer.results(submitted.id).unwrap();
+    runs.into_iter().map(|run| (run.rank, run.parameters, run.metrics)).collect()
+}
+
+#[tokio::test]
+async fn test_grid_search_ranks_runs_deterministically() {
+    let first = ranked_runs(42).await;
+    assert_eq!(first.len(), 4);
+    assert_eq!(
+        first.iter().map(|(rank, _, _)| *rank).collect::<Vec<_>>(),
+        vec![Some(1), Some(2), Some(3), Some(4)]
+    );
+
+    // Runs are ordered by descending ROI
+    let rois: Vec<Decimal> = first
+        .iter()
+        .map(|(_, _, metrics)| metrics.as_ref().unwrap().roi_pct)
+        .collect();
+    assert!(rois.windows(2).all(|pair| pair[0] >= pair[1]));
+
+    // The 1% grid round-trips the fixture's dip and recovery at a profit
+    let (_, best_params, best_metrics) = &first[0];
+    assert_eq!(best_params["take_profit_pct"], dec!(1));
+    assert!(best_metrics.as_ref().unwrap().realized_pnl > Decimal::ZERO);
+
+    assert_eq!(first, ranked_runs(42).await);
+}
+
+#[tokio::test]
+async fn test_cancelled_job_stops_scheduling() {
+    let optimizer = optimizer(1);
+    let mut space = BTreeMap::new();
+    space.insert("grid_levels".to_string(), range(dec!(5), dec!(100), dec!(1)));
+    space.insert("take_profit_pct".to_string(), range(dec!(1), dec!(3), dec!(1)));
+
+    let submitted = optimizer.submit(grid_request(space)).unwrap();
+    assert_eq!(submitted.total, 288);
+    optimizer.cancel(submitted.id).unwrap();
+
+    let finished = wait_for_finish(&optimizer, &submitted).await;
+    assert_eq!(finished.status, JobStatus::Cancelled);
+    assert!(finished.completed < finished.total);
+}
+
+#[tokio::test]
+async fn test_rejects_oversized_parameter_space() {
+    let optimizer = optimizer(1);
+    let mut space = tiny_space();
+    space.insert("position_size_bps".to_string(), range(dec!(100), dec!(5000), dec!(1)));
+    space.insert("stop_loss_pct".to_string(), range(dec!(-50), dec!(-1), dec!(0.01)));
+
+    assert!(optimizer.submit(grid_request(space)).is_err());
+}
@@ -114,6 +114,7 @@ async fn test_multi_dex_execution() {
                 price: order.price,
                 size: order.size,
                 slippage: dec!(0.01),
+                guarded: None,
             })
             .await;
 
@@ -151,6 +152,7 @@ async fn test_mev_optimization() {
         price: order.price,
         size: dec!(1000.0), // Large size to trigger MEV
         slippage: dec!(0.01),
+        guarded: None,
     };
 
     // Set up MEV bundle expectations
@@ -208,6 +210,7 @@ async fn test_error_handling() {
             price: order.price,
             size: order.size,
             slippage: dec!(0.01),
+            guarded: None,
         })
         .await;
 
@@ -9,11 +9,12 @@ use crate::risk_manager::portfolio::{
     PortfolioHealth, PortfolioRiskManager, RebalanceAction, RebalanceDirection, RiskError,
 };
 use crate::utils::metrics::MetricsCollector;
+use crate::utils::percent::Percent;
 
 // Test constants
 const INITIAL_BALANCE: Decimal = dec!(100000.00); // 100k USDC
-const REBALANCE_THRESHOLD: Decimal = dec!(0.05); // 5%
-const CIRCUIT_BREAKER_THRESHOLD: Decimal = dec!(0.25); // 25%
+const REBALANCE_THRESHOLD: Percent = Percent::from_percent(dec!(5)); // 5%
+const CIRCUIT_BREAKER_THRESHOLD: Percent = Percent::from_percent(dec!(25)); // 25%
 const PERFORMANCE_THRESHOLD_MS: u64 = 500; // 500ms latency requirement
 
 /// Helper function to create a test portfolio with predefined positions
@@ -73,7 +74,7 @@ async fn test_portfolio_health_check() {
     // Verify health metrics
     assert!(health.is_healthy);
     assert!(!health.circuit_breaker_active);
-    assert!(health.drawdown < dec!(0.20)); // 20% max drawdown
+    assert!(health.drawdown < Percent::from_percent(dec!(20))); // 20% max drawdown
     assert!(health.total_value > INITIAL_BALANCE);
 }
 
@@ -205,8 +206,10 @@ async fn test_risk_metrics_validation() {
     let health = risk_manager.check_portfolio_health(&market_data).await.unwrap();
 
     // Verify risk metrics
-    assert!(health.concentration < dec!(0.40)); // Max 40% concentration
-    assert!(health.volatility < dec!(0.30)); // Max 30% volatility
+    assert!(health.concentration < Percent::from_percent(dec!(40))); // Max 40% concentration
+    // No price history is attached, so volatility is unknown rather than zero
+    assert!(health.volatility.is_none());
+    assert_eq!(health.insufficient_data, vec!["ORCA".to_string(), "SOL".to_string()]);
     assert!(health.leverage < dec!(3.00)); // Max 3x leverage
 }
 
@@ -10,6 +10,8 @@ use tokio::time::{Duration, Instant};
 use crate::models::order::{Order, OrderType};
 use crate::models::portfolio::{Portfolio, PortfolioError};
 use crate::models::market::{MarketData, OrderBook, OrderBookLevel};
+use crate::risk_manager::exposure::{ExposureBook, ExposureLimits, TradeSide};
+use crate::risk_manager::portfolio::build_exposure_book;
 use crate::risk_manager::validation::{
     validate_trade, validate_portfolio_metrics, ValidationResult, ValidationSeverity, ValidationType,
 };
@@ -81,6 +83,10 @@ impl TestContext {
         }
         Ok(())
     }
+
+    async fn exposure(&self) -> Result<ExposureBook, Box<dyn Error>> {
+        Ok(build_exposure_book(&[self.portfolio.clone()], &self.market_prices).await?)
+    }
 }
 
 #[tokio::test]
@@ -103,10 +109,14 @@ async fn test_validate_trade_size_limits() -> Result<(), Box<dyn Error>> {
         dec!(5000.00), // Very large order
     )?;
 
+    let exposure = ctx.exposure().await?;
     let start = Instant::now();
     let result = validate_trade(
         &large_order,
+        TradeSide::Buy,
         &ctx.portfolio,
+        &exposure,
+        &ExposureLimits::default(),
         &ctx.market_prices,
         &HashMap::new(), // Cross-DEX prices
         &HashMap::new(), // Market impact
@@ -131,7 +141,10 @@ async fn test_validate_trade_size_limits() -> Result<(), Box<dyn Error>> {
 
     let result = validate_trade(
         &valid_order,
+        TradeSide::Buy,
         &ctx.portfolio,
+        &exposure,
+        &ExposureLimits::default(),
         &ctx.market_prices,
         &HashMap::new(),
         &HashMap::new(),
@@ -142,6 +155,62 @@ async fn test_validate_trade_size_limits() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[tokio::test]
+#[tracing_test::traced_test]
+#[timeout(1000)]
+async fn test_validate_trade_nets_exposure_across_books() -> Result<(), Box<dyn Error>> {
+    let ctx = TestContext::new().await?;
+
+    // Grid book long 100 SOL, ML book short 80 SOL through the perp
+    let mut exposure = ExposureBook::new(dec!(20000.00));
+    exposure.add_fill("SOL/USDC", dec!(100.00), dec!(23.45));
+    exposure.add_fill("SOL-PERP", dec!(-80.00), dec!(23.45));
+
+    let sol = exposure.get("SOL").unwrap();
+    assert_eq!(sol.net_size, dec!(20.00));
+    assert_eq!(sol.gross_size, dec!(180.00));
+
+    // Selling reduces the net long even though it adds gross exposure
+    let order = Order::new(
+        "SOL/USDC".to_string(),
+        "jupiter".to_string(),
+        OrderType::Market,
+        dec!(23.45),
+        dec!(30.00),
+    )?;
+    let result = validate_trade(
+        &order,
+        TradeSide::Sell,
+        &ctx.portfolio,
+        &exposure,
+        &ExposureLimits::default(),
+        &ctx.market_prices,
+        &HashMap::new(),
+        &HashMap::new(),
+    ).await?;
+    assert!(result.is_valid);
+
+    // A tight gross limit rejects the offsetting but capital-hungry book
+    let limits = ExposureLimits {
+        max_gross_leverage: dec!(0.2),
+        ..ExposureLimits::default()
+    };
+    let result = validate_trade(
+        &order,
+        TradeSide::Sell,
+        &ctx.portfolio,
+        &exposure,
+        &limits,
+        &ctx.market_prices,
+        &HashMap::new(),
+        &HashMap::new(),
+    ).await?;
+    assert!(!result.is_valid);
+    assert!(result.failure_reason.unwrap().contains("gross leverage"));
+
+    Ok(())
+}
+
 #[tokio::test]
 #[tracing_test::traced_test]
 #[timeout(1000)]